@@ -0,0 +1,244 @@
+//! N-best alternatives: other readings of a delivered transcript, so history
+//! can offer "tap to pick" corrections.
+//!
+//! Opt-in (`alternatives`), Whisper only. whisper-rs doesn't hand back the
+//! beam or best-of candidates whisper.cpp discards, so after a transcript is
+//! delivered the same audio is decoded again at rising temperatures, off the
+//! paste path. Each hypothesis goes through the same transformations as the
+//! delivered text and is word-diffed against it; the changed runs become
+//! [`AlternativeSpan`]s, sent as `transcription-alternatives`.
+
+use crate::text_metrics::{word_diff, DiffOp};
+use crate::transcriber::whisper::WhisperSampling;
+use crate::transcriber::WhisperDecoding;
+use serde::Serialize;
+use tauri::Emitter;
+
+pub const TRANSCRIPTION_ALTERNATIVES: &str = "transcription-alternatives";
+
+/// Most hypotheses decoded per transcript.
+pub const MAX_ALTERNATIVES: u32 = 4;
+/// Changes longer than this, on either side, are rewrites rather than a
+/// different reading of a word or two, and aren't offered.
+const MAX_SPAN_WORDS: usize = 6;
+const TEMPERATURE_STEP: f32 = 0.2;
+
+/// Words of the delivered text that another hypothesis read differently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlternativeSpan {
+    /// Index of the first word, counting whitespace-separated words.
+    pub start: usize,
+    /// One past the last word.
+    pub end: usize,
+    /// Other readings of those words, in hypothesis order. Empty text means
+    /// a hypothesis without them.
+    pub options: Vec<String>,
+}
+
+/// Payload of `transcription-alternatives`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionAlternatives {
+    pub recording_id: u64,
+    /// The delivered text the spans index into.
+    pub text: String,
+    pub spans: Vec<AlternativeSpan>,
+}
+
+/// Decoding for each extra hypothesis: greedy sampling at rising
+/// temperatures, so each pass can read unsure words differently.
+pub fn sampling_passes(base: WhisperDecoding, count: u32) -> Vec<WhisperDecoding> {
+    (1..=count.min(MAX_ALTERNATIVES))
+        .map(|pass| WhisperDecoding {
+            sampling: WhisperSampling::Greedy,
+            temperature: (base.temperature + TEMPERATURE_STEP * pass as f32).min(1.0),
+            ..base
+        })
+        .collect()
+}
+
+/// The changed runs between `best` and one hypothesis, as
+/// `(start, end, replacement)`. A pure insertion is attached to the word
+/// before it (or after it, at the start) so there is a word to tap.
+fn changes(best: &[&str], hypothesis: &str) -> Vec<(usize, usize, String)> {
+    let diff = word_diff(&best.join(" "), hypothesis);
+    // (first changed word, words deleted, words inserted)
+    let mut runs: Vec<(usize, usize, Vec<&str>)> = Vec::new();
+    let mut index = 0;
+    let mut in_run = false;
+    for span in &diff {
+        let words: Vec<&str> = span.text.split(' ').collect();
+        if span.op == DiffOp::Equal {
+            in_run = false;
+            index += words.len();
+            continue;
+        }
+        if !in_run {
+            runs.push((index, 0, Vec::new()));
+            in_run = true;
+        }
+        if let Some((_, deleted, inserted)) = runs.last_mut() {
+            if span.op == DiffOp::Delete {
+                *deleted += words.len();
+                index += words.len();
+            } else {
+                inserted.extend(words);
+            }
+        }
+    }
+    runs.into_iter()
+        .map(|(start, deleted, inserted)| {
+            let inserted = inserted.join(" ");
+            if deleted > 0 {
+                (start, start + deleted, inserted)
+            } else if start > 0 {
+                (
+                    start - 1,
+                    start,
+                    format!("{} {}", best[start - 1], inserted),
+                )
+            } else if let Some(first) = best.first() {
+                (0, 1, format!("{} {}", inserted, first))
+            } else {
+                (0, 0, inserted)
+            }
+        })
+        .collect()
+}
+
+/// Spans of `best` that the hypotheses read differently. Spans never
+/// overlap: a change overlapping an earlier hypothesis's differently sized
+/// change is dropped.
+pub fn spans(best: &str, hypotheses: &[String]) -> Vec<AlternativeSpan> {
+    let words: Vec<&str> = best.split_whitespace().collect();
+    let mut spans: Vec<AlternativeSpan> = Vec::new();
+    for hypothesis in hypotheses {
+        for (start, end, option) in changes(&words, hypothesis) {
+            if end == start || end - start > MAX_SPAN_WORDS {
+                continue;
+            }
+            if option.split_whitespace().count() > MAX_SPAN_WORDS {
+                continue;
+            }
+            match spans
+                .iter_mut()
+                .find(|span| span.start < end && start < span.end)
+            {
+                Some(span) if span.start == start && span.end == end => {
+                    if !span.options.contains(&option) {
+                        span.options.push(option);
+                    }
+                }
+                Some(_) => {}
+                None => spans.push(AlternativeSpan {
+                    start,
+                    end,
+                    options: vec![option],
+                }),
+            }
+        }
+    }
+    spans.sort_by_key(|span| span.start);
+    spans
+}
+
+pub fn emit(app: &tauri::AppHandle, payload: &TranscriptionAlternatives) {
+    let _ = app.emit(TRANSCRIPTION_ALTERNATIVES, payload);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hypotheses(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|text| text.to_string()).collect()
+    }
+
+    #[test]
+    fn differing_words_become_spans_with_each_reading_once() {
+        let spans = spans(
+            "meet me at the key",
+            &hypotheses(&[
+                "meet me at the quay",
+                "meat me at the quay",
+                "meet me at the key",
+            ]),
+        );
+        assert_eq!(
+            spans,
+            vec![
+                AlternativeSpan {
+                    start: 0,
+                    end: 1,
+                    options: vec!["meat".to_string()],
+                },
+                AlternativeSpan {
+                    start: 4,
+                    end: 5,
+                    options: vec!["quay".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn insertions_attach_to_a_neighbouring_word_and_deletions_offer_nothing() {
+        let spans = spans(
+            "send it now",
+            &hypotheses(&["send it right now", "just send it now", "send now"]),
+        );
+        assert_eq!(
+            spans,
+            vec![
+                AlternativeSpan {
+                    start: 0,
+                    end: 1,
+                    options: vec!["just send".to_string()],
+                },
+                AlternativeSpan {
+                    start: 1,
+                    end: 2,
+                    options: vec!["it right".to_string(), String::new()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rewrites_and_overlapping_changes_are_not_offered() {
+        let spans = spans(
+            "one two three four five six seven eight",
+            &hypotheses(&[
+                "a b c d e f g h",
+                "one too three four five six seven eight",
+                "one to tree four five six seven eight",
+            ]),
+        );
+        assert_eq!(
+            spans,
+            vec![AlternativeSpan {
+                start: 1,
+                end: 2,
+                options: vec!["too".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn passes_sample_greedily_at_rising_temperatures() {
+        let base = WhisperDecoding {
+            sampling: WhisperSampling::BeamSearch,
+            ..WhisperDecoding::default()
+        };
+        let passes = sampling_passes(base, 9);
+        assert_eq!(passes.len(), MAX_ALTERNATIVES as usize);
+        assert!(passes
+            .iter()
+            .all(|pass| pass.sampling == WhisperSampling::Greedy));
+        assert!(passes[0].temperature > 0.0);
+        assert!(passes
+            .windows(2)
+            .all(|pair| pair[0].temperature < pair[1].temperature));
+    }
+}
@@ -0,0 +1,676 @@
+//! Ambient mode: continuous background transcription into a searchable log.
+//!
+//! While on, a listening capture (`audio::start_listening`) feeds 100 ms
+//! frames to a `Segmenter`, which keeps the last few quiet frames in a ring
+//! buffer. When speech starts the ring becomes the head of a new segment, so
+//! the first word isn't clipped; the segment closes after `END_SILENCE_MS` of
+//! quiet or at `MAX_SEGMENT_SECS`. Closed segments are VAD-filtered and
+//! transcribed on their own thread with the dictation model, then appended to
+//! `ambient-log.jsonl` in the app data directory, which is trimmed to its
+//! newest entries past `MAX_LOG_BYTES`. In privacy mode entries are only shown,
+//! never written. Nothing is ever pasted or copied.
+//!
+//! Dictation always wins. Audio heard while a dictation records is dropped
+//! (that speech already reaches history), and segments wait while the shared
+//! backend serves a dictation, file transcription, benchmark, or transform.
+//! At most `MAX_PENDING_SEGMENTS` wait; the oldest is dropped past that.
+
+use crate::audio::{self, ListeningCapture};
+use crate::model_runtime::PreparationReason;
+use crate::state::{DictationStatus, WHISPER_SAMPLE_RATE};
+use crate::{vad, MutexExt};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const LOG_FILE: &str = "ambient-log.jsonl";
+
+/// 100 ms at 16 kHz.
+pub(crate) const FRAME_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize / 10;
+pub(crate) const FRAME_MS: u64 = 100;
+/// Quiet frames kept ahead of speech.
+const PRE_ROLL_FRAMES: usize = 3;
+/// Frames below this RMS (about -40 dBFS) count as quiet.
+pub(crate) const SPEECH_RMS_FLOOR: f32 = 0.01;
+/// Quiet that closes a segment.
+const END_SILENCE_MS: u64 = 800;
+/// Segments with less speech than this are dropped as coughs and clicks.
+const MIN_SPEECH_MS: u64 = 400;
+/// Longer speech is split so entries appear while someone keeps talking, and
+/// so decoding one never keeps a dictation waiting on the backend for long.
+const MAX_SEGMENT_SECS: usize = 10;
+const MAX_SEGMENT_SAMPLES: usize = MAX_SEGMENT_SECS * WHISPER_SAMPLE_RATE as usize;
+const MAX_PENDING_SEGMENTS: usize = 8;
+/// How often a waiting segment checks whether the backend is free.
+const BUSY_RETRY: Duration = Duration::from_millis(500);
+/// Default and ceiling for `get_ambient_log`.
+const DEFAULT_LOG_LIMIT: usize = 200;
+const MAX_LOG_LIMIT: usize = 2_000;
+/// Past this the log is rewritten with its newest entries, down to half.
+const MAX_LOG_BYTES: u64 = 4 * 1024 * 1024;
+
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// One transcribed stretch of speech.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmbientEntry {
+    pub started_at_ms: i64,
+    pub duration_ms: u64,
+    pub text: String,
+}
+
+/// A closed stretch of audio waiting for transcription.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Segment {
+    pub started_at_ms: i64,
+    pub samples: Vec<f32>,
+}
+
+#[derive(Debug)]
+struct OpenSegment {
+    started_at_ms: i64,
+    samples: Vec<f32>,
+    speech_ms: u64,
+    quiet_ms: u64,
+}
+
+impl OpenSegment {
+    fn close(self) -> Option<Segment> {
+        (self.speech_ms >= MIN_SPEECH_MS).then_some(Segment {
+            started_at_ms: self.started_at_ms,
+            samples: self.samples,
+        })
+    }
+}
+
+/// Splits a continuous stream of frames into speech segments.
+#[derive(Debug, Default)]
+pub(crate) struct Segmenter {
+    ring: VecDeque<Vec<f32>>,
+    open: Option<OpenSegment>,
+}
+
+impl Segmenter {
+    /// Add one frame that ended at `now_ms`; returns a segment when one closes.
+    pub fn push(&mut self, frame: Vec<f32>, rms: f32, now_ms: i64) -> Option<Segment> {
+        let speaking = rms >= SPEECH_RMS_FLOOR;
+        let Some(open) = self.open.as_mut() else {
+            if !speaking {
+                self.ring.push_back(frame);
+                if self.ring.len() > PRE_ROLL_FRAMES {
+                    self.ring.pop_front();
+                }
+                return None;
+            }
+            let frames = self.ring.len() as i64 + 1;
+            let mut samples: Vec<f32> = self.ring.drain(..).flatten().collect();
+            samples.extend(frame);
+            self.open = Some(OpenSegment {
+                started_at_ms: now_ms - frames * FRAME_MS as i64,
+                samples,
+                speech_ms: FRAME_MS,
+                quiet_ms: 0,
+            });
+            return None;
+        };
+
+        open.samples.extend(frame);
+        if speaking {
+            open.speech_ms += FRAME_MS;
+            open.quiet_ms = 0;
+        } else {
+            open.quiet_ms += FRAME_MS;
+        }
+        if open.quiet_ms >= END_SILENCE_MS || open.samples.len() >= MAX_SEGMENT_SAMPLES {
+            return self.open.take().and_then(OpenSegment::close);
+        }
+        None
+    }
+
+    /// Close whatever is open, for when the capture ends.
+    pub fn finish(&mut self) -> Option<Segment> {
+        self.ring.clear();
+        self.open.take().and_then(OpenSegment::close)
+    }
+
+    /// Drop buffered audio without producing a segment.
+    pub fn discard(&mut self) {
+        self.ring.clear();
+        self.open = None;
+    }
+}
+
+/// Queue `item`, dropping the oldest waiting one past `max_pending`. Returns
+/// the one dropped.
+fn push_pending<T>(pending: &mut VecDeque<T>, item: T, max_pending: usize) -> Option<T> {
+    let dropped = if pending.len() >= max_pending {
+        pending.pop_front()
+    } else {
+        None
+    };
+    pending.push_back(item);
+    dropped
+}
+
+fn read_entries(path: &Path) -> Vec<AmbientEntry> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    // A line torn by a crash mid-append is skipped rather than failing the log.
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn append_in(path: &Path, entry: &AmbientEntry) -> Result<(), String> {
+    let mut line = serde_json::to_string(entry)
+        .map_err(|e| format!("Failed to encode ambient entry: {}", e))?;
+    line.push('\n');
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let _guard = WRITE_LOCK.lock_or_recover();
+    let len = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| {
+            file.write_all(line.as_bytes())?;
+            file.metadata()
+        })
+        .map_err(|e| format!("Failed to write ambient log: {}", e))?
+        .len();
+    if len > MAX_LOG_BYTES {
+        trim_in(path, MAX_LOG_BYTES / 2)
+            .map_err(|e| format!("Failed to trim ambient log: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Rewrite the log at `path` with the newest whole lines that fit in
+/// `keep_bytes`. The caller holds `WRITE_LOCK`.
+fn trim_in(path: &Path, keep_bytes: u64) -> std::io::Result<()> {
+    let contents = std::fs::read(path)?;
+    let start = contents.len().saturating_sub(keep_bytes as usize);
+    let start = match start {
+        0 => 0,
+        start => contents[start - 1..]
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .map_or(contents.len(), |offset| start + offset),
+    };
+    let temp = path.with_extension("jsonl.tmp");
+    std::fs::write(&temp, &contents[start..])?;
+    std::fs::rename(&temp, path)?;
+    tracing::info!(target: "system", kept_bytes = contents.len() - start, "ambient log trimmed");
+    Ok(())
+}
+
+/// Newest first, at most `limit`. Every word of `query` must appear in the
+/// entry's text, ignoring case.
+fn search(entries: Vec<AmbientEntry>, query: &str, limit: usize) -> Vec<AmbientEntry> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| term.to_lowercase())
+        .collect();
+    entries
+        .into_iter()
+        .rev()
+        .filter(|entry| {
+            let text = entry.text.to_lowercase();
+            terms.iter().all(|term| text.contains(term.as_str()))
+        })
+        .take(limit)
+        .collect()
+}
+
+/// The running capture and the thread that segments its frames.
+struct AmbientWorker {
+    capture: ListeningCapture,
+    segmenter: JoinHandle<()>,
+}
+
+static AMBIENT_WORKER: Mutex<Option<AmbientWorker>> = Mutex::new(None);
+
+/// Set the log location. Call once from setup.
+pub fn init(app_data_dir: &Path) {
+    let _ = LOG_PATH.set(app_data_dir.join(LOG_FILE));
+}
+
+fn dictation_recording(app_handle: &tauri::AppHandle) -> bool {
+    let state = app_handle.state::<crate::State>();
+    let status = state.app_state.dictation.lock_or_recover().status;
+    status == DictationStatus::Recording
+}
+
+/// Whether anything that owns the shared transcription backend is running.
+pub(crate) fn backend_busy(app_handle: &tauri::AppHandle) -> bool {
+    let state = app_handle.state::<crate::State>();
+    let status = state.app_state.dictation.lock_or_recover().status;
+    matches!(
+        status,
+        DictationStatus::Recording | DictationStatus::Processing
+    ) || state
+        .app_state
+        .file_transcribing
+        .load(std::sync::atomic::Ordering::SeqCst)
+        || state.benchmark.is_running()
+        || state.app_state.transform_status().blocks_recording()
+        || state.transform_runtime.is_transform_busy()
+}
+
+fn run_segmenter(
+    app_handle: &tauri::AppHandle,
+    frames: Receiver<Vec<f32>>,
+    segments: Sender<Segment>,
+) {
+    let mut segmenter = Segmenter::default();
+    for frame in frames {
+        if dictation_recording(app_handle) {
+            segmenter.discard();
+            continue;
+        }
+        let rms = audio::compute_rms(&frame);
+        if let Some(segment) = segmenter.push(frame, rms, Local::now().timestamp_millis()) {
+            let _ = segments.send(segment);
+        }
+    }
+    if let Some(segment) = segmenter.finish() {
+        let _ = segments.send(segment);
+    }
+}
+
+/// Hand queued audio to `transcribe` as the backend frees up, keeping at most
+/// `max_pending` waiting; the oldest past that goes to `dropped` instead.
+/// Outlives the capture long enough to finish what was already captured.
+/// `what` names the queued items in the log.
+pub(crate) fn run_transcriber<T>(
+    app_handle: &tauri::AppHandle,
+    segments: Receiver<T>,
+    max_pending: usize,
+    what: &str,
+    mut transcribe: impl FnMut(&tauri::AppHandle, T),
+    mut dropped: impl FnMut(&tauri::AppHandle, T),
+) {
+    let mut pending: VecDeque<T> = VecDeque::new();
+    let mut closed = false;
+    loop {
+        if closed && pending.is_empty() {
+            break;
+        }
+        let incoming = if closed {
+            thread::sleep(BUSY_RETRY);
+            None
+        } else if pending.is_empty() {
+            match segments.recv() {
+                Ok(segment) => Some(segment),
+                Err(_) => {
+                    closed = true;
+                    None
+                }
+            }
+        } else {
+            match segments.recv_timeout(BUSY_RETRY) {
+                Ok(segment) => Some(segment),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => {
+                    closed = true;
+                    None
+                }
+            }
+        };
+        if let Some(segment) = incoming {
+            if let Some(oldest) = push_pending(&mut pending, segment, max_pending) {
+                tracing::warn!(target: "pipeline", "backend busy too long, dropped the oldest {}", what);
+                dropped(app_handle, oldest);
+            }
+        }
+        while !pending.is_empty() && !backend_busy(app_handle) {
+            if let Some(segment) = pending.pop_front() {
+                transcribe(app_handle, segment);
+            }
+        }
+    }
+}
+
+fn transcribe_segment(app_handle: &tauri::AppHandle, segment: Segment) {
+    let duration_ms = segment.samples.len() as u64 * 1_000 / WHISPER_SAMPLE_RATE as u64;
+    let Some(text) =
+        transcribe_background(app_handle, &segment.samples, PreparationReason::Ambient)
+    else {
+        return;
+    };
+
+    let entry = AmbientEntry {
+        started_at_ms: segment.started_at_ms,
+        duration_ms,
+        text,
+    };
+    let saved = !crate::telemetry::privacy_mode();
+    if let Some(path) = LOG_PATH.get().filter(|_| saved) {
+        if let Err(e) = append_in(path, &entry) {
+            tracing::warn!(target: "pipeline", "ambient entry not saved: {}", e);
+            return;
+        }
+    }
+    tracing::info!(target: "pipeline", duration_ms, saved, "ambient entry added");
+    let _ = app_handle.emit("ambient-entry", &entry);
+}
+
+/// Transcribe audio nobody is waiting on with the dictation settings: VAD,
+/// the dictation model, the hallucination filter, and redaction. None when
+/// there was no speech or nothing worth keeping.
+pub(crate) fn transcribe_background(
+    app_handle: &tauri::AppHandle,
+    samples: &[f32],
+    reason: PreparationReason,
+) -> Option<String> {
+    let state = app_handle.state::<crate::State>();
+    let (
+        model_name,
+        language,
+        vad_sensitivity,
+        custom_vocabulary,
+        smart_punctuation,
+        whisper_decoding,
+    ) = {
+        let dictation = state.app_state.dictation.lock_or_recover();
+        (
+            dictation.model_name.clone(),
+            dictation.language.clone(),
+            dictation.vad_sensitivity,
+            dictation.custom_vocabulary.clone(),
+            dictation.smart_punctuation,
+            dictation.whisper_decoding,
+        )
+    };
+    let rms = audio::compute_rms(samples);
+
+    let vad_threshold = 1.0 - (vad_sensitivity as f32 / 100.0);
+    let speech = match vad::vad_model_path().filter(|path| path.exists()) {
+        Some(path) => match vad::filter_speech(&path.to_string_lossy(), samples, vad_threshold) {
+            Ok(vad::VadResult::NoSpeech) => return None,
+            Ok(vad::VadResult::Speech(trimmed)) => trimmed,
+            Err(e) => {
+                tracing::warn!(target: "pipeline", reason = reason.as_str(), "VAD failed ({}), using the whole segment", e);
+                samples.to_vec()
+            }
+        }
+        None => samples.to_vec(),
+    };
+    let speech_ratio = crate::hallucination::speech_ratio(speech.len(), samples.len());
+
+    let prompt = custom_vocabulary.replace('\0', "");
+    let prompt = (!prompt.trim().is_empty()).then_some(prompt.as_str());
+    let result = state.app_state.model_runtime.with_ready_backend(
+        Some(app_handle),
+        &model_name,
+        reason,
+        |backend| {
+            backend.set_whisper_decoding(&whisper_decoding);
+            backend.transcribe(&speech, &language, prompt, smart_punctuation)
+        },
+    );
+    let text = match result {
+        Ok((text, _)) => text.trim().to_string(),
+        Err(e) => {
+            tracing::warn!(target: "pipeline", reason = reason.as_str(), "background transcription failed: {}", e);
+            return None;
+        }
+    };
+    // Background noise is what these captures hear most, so the hallucination
+    // filter always applies here.
+    if text.is_empty() || crate::hallucination::is_hallucination(&text, speech_ratio, rms) {
+        return None;
+    }
+    tracing::debug!(target: "pipeline", reason = reason.as_str(), model = model_name.as_str(), "background transcription done");
+    Some(match state.app_state.redactor.lock_or_recover().clone() {
+        Some(redactor) => redactor.redact(&text),
+        None => text,
+    })
+}
+
+/// Start ambient mode on `device_name` (None = system default), replacing a
+/// capture that is already running.
+#[tauri::command]
+pub async fn start_ambient_mode(
+    app_handle: tauri::AppHandle,
+    device_name: Option<String>,
+) -> Result<(), String> {
+    stop();
+    let (frame_tx, frame_rx) = channel::<Vec<f32>>();
+    let capture = audio::start_listening(device_name, FRAME_SAMPLES, frame_tx)?;
+
+    let (segment_tx, segment_rx) = channel::<Segment>();
+    let transcriber_handle = app_handle.clone();
+    thread::spawn(move || {
+        run_transcriber(
+            &transcriber_handle,
+            segment_rx,
+            MAX_PENDING_SEGMENTS,
+            "ambient segment",
+            transcribe_segment,
+            |_, _| {},
+        )
+    });
+    let segmenter = thread::spawn(move || run_segmenter(&app_handle, frame_rx, segment_tx));
+
+    *AMBIENT_WORKER.lock_or_recover() = Some(AmbientWorker { capture, segmenter });
+    tracing::info!(target: "audio", "ambient mode started");
+    Ok(())
+}
+
+pub(crate) fn stop() {
+    let Some(worker) = AMBIENT_WORKER.lock_or_recover().take() else {
+        return;
+    };
+    // Closing the capture ends the segmenter, which flushes its open segment
+    // and closes the transcriber's channel.
+    worker.capture.stop();
+    let _ = worker.segmenter.join();
+    tracing::info!(target: "audio", "ambient mode stopped");
+}
+
+#[tauri::command]
+pub async fn stop_ambient_mode() {
+    stop();
+}
+
+#[tauri::command]
+pub fn is_ambient_mode_active() -> bool {
+    AMBIENT_WORKER
+        .lock_or_recover()
+        .as_ref()
+        .is_some_and(|worker| !worker.segmenter.is_finished())
+}
+
+/// Log entries, newest first. `query` keeps entries containing every word of
+/// it; `limit` defaults to 200 and is capped at 2000.
+#[tauri::command]
+pub fn get_ambient_log(query: Option<String>, limit: Option<usize>) -> Vec<AmbientEntry> {
+    let entries = LOG_PATH
+        .get()
+        .map(|path| read_entries(path))
+        .unwrap_or_default();
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT).min(MAX_LOG_LIMIT);
+    search(entries, query.as_deref().unwrap_or(""), limit)
+}
+
+#[tauri::command]
+pub fn clear_ambient_log() -> Result<(), String> {
+    let Some(path) = LOG_PATH.get() else {
+        return Ok(());
+    };
+    let _guard = WRITE_LOCK.lock_or_recover();
+    match std::fs::remove_file(path) {
+        Ok(()) => {
+            tracing::info!(target: "system", "ambient log cleared");
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to clear ambient log: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Vec<f32> {
+        vec![0.0; FRAME_SAMPLES]
+    }
+
+    fn entry(started_at_ms: i64, text: &str) -> AmbientEntry {
+        AmbientEntry {
+            started_at_ms,
+            duration_ms: 1_000,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn segment_starts_with_pre_roll_and_closes_after_silence() {
+        let mut segmenter = Segmenter::default();
+        for _ in 0..5 {
+            assert!(segmenter.push(frame(), 0.0, 0).is_none());
+        }
+        for _ in 0..5 {
+            assert!(segmenter.push(frame(), 0.1, 10_000).is_none());
+        }
+        let quiet = (END_SILENCE_MS / FRAME_MS) as usize;
+        for _ in 1..quiet {
+            assert!(segmenter.push(frame(), 0.0, 11_000).is_none());
+        }
+        let segment = segmenter.push(frame(), 0.0, 11_000).unwrap();
+        assert_eq!(
+            segment.samples.len(),
+            (PRE_ROLL_FRAMES + 5 + quiet) * FRAME_SAMPLES
+        );
+        assert_eq!(
+            segment.started_at_ms,
+            10_000 - (PRE_ROLL_FRAMES as i64 + 1) * FRAME_MS as i64
+        );
+    }
+
+    #[test]
+    fn short_noises_never_become_segments() {
+        let mut segmenter = Segmenter::default();
+        segmenter.push(frame(), 0.1, 0);
+        for _ in 0..(END_SILENCE_MS / FRAME_MS) {
+            assert!(segmenter.push(frame(), 0.0, 0).is_none());
+        }
+    }
+
+    #[test]
+    fn long_speech_is_split_at_the_cap() {
+        let mut segmenter = Segmenter::default();
+        let frames = MAX_SEGMENT_SAMPLES / FRAME_SAMPLES;
+        for _ in 1..frames {
+            assert!(segmenter.push(frame(), 0.1, 0).is_none());
+        }
+        let segment = segmenter.push(frame(), 0.1, 0).unwrap();
+        assert_eq!(segment.samples.len(), MAX_SEGMENT_SAMPLES);
+    }
+
+    #[test]
+    fn finish_flushes_speech_and_discard_drops_it() {
+        let mut segmenter = Segmenter::default();
+        for _ in 0..5 {
+            segmenter.push(frame(), 0.1, 0);
+        }
+        assert!(segmenter.finish().is_some());
+
+        for _ in 0..5 {
+            segmenter.push(frame(), 0.1, 0);
+        }
+        segmenter.discard();
+        assert!(segmenter.finish().is_none());
+    }
+
+    #[test]
+    fn pending_queue_drops_the_oldest_past_the_cap() {
+        let mut pending = VecDeque::new();
+        for started_at_ms in 0..=MAX_PENDING_SEGMENTS as i64 {
+            let dropped = push_pending(
+                &mut pending,
+                Segment {
+                    started_at_ms,
+                    samples: Vec::new(),
+                },
+                MAX_PENDING_SEGMENTS,
+            );
+            assert_eq!(
+                dropped.map(|segment| segment.started_at_ms),
+                (started_at_ms == MAX_PENDING_SEGMENTS as i64).then_some(0)
+            );
+        }
+        assert_eq!(pending.len(), MAX_PENDING_SEGMENTS);
+        assert_eq!(pending.front().unwrap().started_at_ms, 1);
+    }
+
+    #[test]
+    fn search_returns_newest_matches_first() {
+        let entries = vec![
+            entry(1, "Budget review on Friday"),
+            entry(2, "lunch plans"),
+            entry(3, "Move the budget review to Monday"),
+        ];
+        let found = search(entries.clone(), "BUDGET review", 10);
+        assert_eq!(
+            found.iter().map(|e| e.started_at_ms).collect::<Vec<_>>(),
+            vec![3, 1]
+        );
+        assert_eq!(search(entries, "", 2).len(), 2);
+    }
+
+    #[test]
+    fn log_round_trips_and_skips_torn_lines() {
+        let dir = std::env::temp_dir().join(format!("murmur-ambient-{}", std::process::id()));
+        let path = dir.join(LOG_FILE);
+        let _ = std::fs::remove_dir_all(&dir);
+        append_in(&path, &entry(1, "first")).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"startedAtMs\":\n")
+            .unwrap();
+        append_in(&path, &entry(2, "second")).unwrap();
+        assert_eq!(
+            read_entries(&path),
+            vec![entry(1, "first"), entry(2, "second")]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn trimming_keeps_the_newest_whole_lines() {
+        let dir = std::env::temp_dir().join(format!("murmur-ambient-trim-{}", std::process::id()));
+        let path = dir.join(LOG_FILE);
+        let _ = std::fs::remove_dir_all(&dir);
+        for started_at_ms in 0..10 {
+            append_in(&path, &entry(started_at_ms, "some words")).unwrap();
+        }
+        let line_len = std::fs::metadata(&path).unwrap().len() / 10;
+        trim_in(&path, line_len * 3 + line_len / 2).unwrap();
+        assert_eq!(
+            read_entries(&path)
+                .iter()
+                .map(|e| e.started_at_ms)
+                .collect::<Vec<_>>(),
+            vec![7, 8, 9]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
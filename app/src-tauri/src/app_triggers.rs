@@ -0,0 +1,376 @@
+//! App triggers: react when a chosen app becomes frontmost.
+//!
+//! Each `appTriggers` entry names an app by bundle identifier and what its
+//! activation does: keep the overlay showing over it (even fullscreen, where
+//! `overlayFullscreen` would hide it), and/or offer ambient transcription
+//! from the overlay. An `NSWorkspace` activation observer matches the new
+//! frontmost app. Nothing records on its own: the offer waits for a click,
+//! goes away when another app comes forward, and is made at most once per
+//! `PROMPT_COOLDOWN` per app.
+//!
+//! Like `frontmost`, this module never logs app names or bundle identifiers.
+
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// An ignored offer isn't repeated for the same app within this long, so
+/// switching back and forth doesn't nag.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const PROMPT_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+const MAX_TRIGGERS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppTrigger {
+    pub bundle_id: String,
+    #[serde(default)]
+    pub name: String,
+    /// Keep the overlay showing while this app is frontmost.
+    #[serde(default)]
+    pub show_overlay: bool,
+    /// Offer ambient transcription from the overlay when this app comes
+    /// forward.
+    #[serde(default)]
+    pub prompt_ambient: bool,
+}
+
+/// Payload of `ambient-prompt`: the app that asked for the offer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmbientPrompt {
+    pub bundle_id: String,
+    pub name: String,
+}
+
+/// What an activation changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Activation {
+    overlay_changed: bool,
+    prompt_changed: bool,
+}
+
+struct Triggers {
+    triggers: Vec<AppTrigger>,
+    /// Bundle identifier of the frontmost app, as last reported.
+    frontmost: Option<String>,
+    prompt: Option<AmbientPrompt>,
+    /// When each app was last offered ambient transcription.
+    prompted: Vec<(String, Instant)>,
+}
+
+impl Triggers {
+    const fn new() -> Self {
+        Self {
+            triggers: Vec::new(),
+            frontmost: None,
+            prompt: None,
+            prompted: Vec::new(),
+        }
+    }
+
+    fn trigger_for(&self, bundle_id: &str) -> Option<&AppTrigger> {
+        self.triggers
+            .iter()
+            .find(|trigger| trigger.bundle_id.eq_ignore_ascii_case(bundle_id))
+    }
+
+    fn overlay_pinned(&self) -> bool {
+        self.frontmost
+            .as_deref()
+            .and_then(|bundle_id| self.trigger_for(bundle_id))
+            .is_some_and(|trigger| trigger.show_overlay)
+    }
+
+    /// Replace the configured triggers. The frontmost app is re-matched, but
+    /// no new offer is made until the next activation.
+    fn configure(&mut self, triggers: Vec<AppTrigger>) -> Activation {
+        let pinned = self.overlay_pinned();
+        self.triggers = normalized(triggers);
+        let prompt = self.prompt.clone().filter(|prompt| {
+            self.trigger_for(&prompt.bundle_id)
+                .is_some_and(|trigger| trigger.prompt_ambient)
+        });
+        let activation = Activation {
+            overlay_changed: self.overlay_pinned() != pinned,
+            prompt_changed: self.prompt != prompt,
+        };
+        self.prompt = prompt;
+        activation
+    }
+
+    /// `bundle_id` came forward. An offer for any other app is withdrawn; one
+    /// for this app is made unless ambient transcription is already running
+    /// or it was offered within `PROMPT_COOLDOWN`.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    fn activate(
+        &mut self,
+        bundle_id: Option<&str>,
+        transcribing: bool,
+        now: Instant,
+    ) -> Activation {
+        let pinned = self.overlay_pinned();
+        self.frontmost = bundle_id.map(str::to_string);
+        let prompt = bundle_id
+            .and_then(|bundle_id| self.trigger_for(bundle_id))
+            .filter(|trigger| trigger.prompt_ambient && !transcribing)
+            .filter(|trigger| {
+                !self.prompted.iter().any(|(id, at)| {
+                    id.eq_ignore_ascii_case(&trigger.bundle_id)
+                        && now.duration_since(*at) < PROMPT_COOLDOWN
+                })
+            })
+            .map(|trigger| AmbientPrompt {
+                bundle_id: trigger.bundle_id.clone(),
+                name: trigger.name.clone(),
+            });
+        if let Some(prompt) = &prompt {
+            self.prompted
+                .retain(|(id, _)| !id.eq_ignore_ascii_case(&prompt.bundle_id));
+            self.prompted.push((prompt.bundle_id.clone(), now));
+        }
+        let activation = Activation {
+            overlay_changed: self.overlay_pinned() != pinned,
+            prompt_changed: self.prompt != prompt,
+        };
+        self.prompt = prompt;
+        activation
+    }
+}
+
+/// Trim identifiers and names, drop blank identifiers and repeats, and cap
+/// the list.
+fn normalized(triggers: Vec<AppTrigger>) -> Vec<AppTrigger> {
+    let mut kept: Vec<AppTrigger> = Vec::new();
+    for trigger in triggers {
+        let bundle_id = trigger.bundle_id.trim().to_string();
+        if bundle_id.is_empty()
+            || kept
+                .iter()
+                .any(|other| other.bundle_id.eq_ignore_ascii_case(&bundle_id))
+        {
+            continue;
+        }
+        let name = trigger.name.trim();
+        kept.push(AppTrigger {
+            name: if name.is_empty() {
+                bundle_id.clone()
+            } else {
+                name.to_string()
+            },
+            bundle_id,
+            ..trigger
+        });
+    }
+    kept.truncate(MAX_TRIGGERS);
+    kept
+}
+
+static TRIGGERS: Mutex<Triggers> = Mutex::new(Triggers::new());
+
+/// Whether the frontmost app asked to keep the overlay showing. Read by
+/// overlay placement, which then treats a fullscreen app like any other.
+pub(crate) fn overlay_pinned() -> bool {
+    TRIGGERS.lock_or_recover().overlay_pinned()
+}
+
+fn apply(app: &tauri::AppHandle, activation: Activation, prompt: Option<AmbientPrompt>) {
+    if activation.overlay_changed {
+        #[cfg(target_os = "macos")]
+        {
+            let handle = app.clone();
+            let _ = app.run_on_main_thread(move || {
+                crate::commands::overlay::refresh_overlay_placement(&handle, false)
+            });
+        }
+    }
+    if activation.prompt_changed {
+        if prompt.is_some() {
+            tracing::info!(target: "system", "app trigger: offering ambient transcription");
+        }
+        let _ = app.emit("ambient-prompt", prompt);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn on_app_activated(app: &tauri::AppHandle) {
+    let bundle_id = objc2_app_kit::NSWorkspace::sharedWorkspace()
+        .frontmostApplication()
+        .and_then(|application| application.bundleIdentifier())
+        .map(|value| value.to_string());
+    let transcribing = crate::ambient::is_ambient_mode_active() || crate::meeting::is_active();
+    let (activation, prompt) = {
+        let mut triggers = TRIGGERS.lock_or_recover();
+        let activation = triggers.activate(bundle_id.as_deref(), transcribing, Instant::now());
+        (activation, triggers.prompt.clone())
+    };
+    apply(app, activation, prompt);
+}
+
+/// Watch app activations. Call once from setup.
+#[cfg(target_os = "macos")]
+pub(crate) fn start(app: &tauri::AppHandle) {
+    use objc2_foundation::{NSNotification, NSNotificationName, NSOperationQueue};
+
+    let handle = app.clone();
+    let name = NSNotificationName::from_str("NSWorkspaceDidActivateApplicationNotification");
+    let block = block2::RcBlock::new(move |_notification: std::ptr::NonNull<NSNotification>| {
+        on_app_activated(&handle);
+    });
+    let center = objc2_app_kit::NSWorkspace::sharedWorkspace().notificationCenter();
+    unsafe {
+        let observer = center.addObserverForName_object_queue_usingBlock(
+            Some(&name),
+            None,
+            Some(&NSOperationQueue::mainQueue()),
+            &block,
+        );
+        // App-lifetime observer — intentionally leak to avoid premature deallocation
+        std::mem::forget(observer);
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn start(_app: &tauri::AppHandle) {}
+
+/// Withdraw the ambient offer. Returns whether one was showing.
+fn take_prompt(app: &tauri::AppHandle) -> bool {
+    let had_prompt = TRIGGERS.lock_or_recover().prompt.take().is_some();
+    if had_prompt {
+        let _ = app.emit("ambient-prompt", None::<AmbientPrompt>);
+    }
+    had_prompt
+}
+
+/// The overlay's Start on the ambient offer: withdraw it and ask the main
+/// window, which owns the `ambientMode` setting, to turn ambient mode on.
+pub(crate) fn accept_prompt(app: &tauri::AppHandle) {
+    if take_prompt(app) {
+        tracing::info!(target: "system", "app trigger: ambient transcription accepted");
+        let _ = app.emit("ambient-start-requested", ());
+    }
+}
+
+/// The overlay's dismiss on the ambient offer.
+pub(crate) fn dismiss_prompt(app: &tauri::AppHandle) {
+    take_prompt(app);
+}
+
+/// Replace the app triggers. Sent by the main window on startup and on change.
+#[tauri::command]
+pub fn set_app_triggers(app: tauri::AppHandle, triggers: Vec<AppTrigger>) {
+    let (activation, prompt) = {
+        let mut state = TRIGGERS.lock_or_recover();
+        let activation = state.configure(triggers);
+        tracing::info!(target: "system", count = state.triggers.len(), "set_app_triggers");
+        (activation, state.prompt.clone())
+    };
+    apply(&app, activation, prompt);
+}
+
+/// The ambient offer showing now, for an overlay that mounts after it was made.
+#[tauri::command]
+pub fn get_ambient_prompt() -> Option<AmbientPrompt> {
+    TRIGGERS.lock_or_recover().prompt.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(bundle_id: &str, show_overlay: bool, prompt_ambient: bool) -> AppTrigger {
+        AppTrigger {
+            bundle_id: bundle_id.to_string(),
+            name: String::new(),
+            show_overlay,
+            prompt_ambient,
+        }
+    }
+
+    fn configured(triggers: Vec<AppTrigger>) -> Triggers {
+        let mut state = Triggers::new();
+        state.configure(triggers);
+        state
+    }
+
+    #[test]
+    fn activation_pins_the_overlay_only_for_its_app() {
+        let mut state = configured(vec![trigger("us.zoom.xos", true, false)]);
+        let now = Instant::now();
+        let activation = state.activate(Some("US.ZOOM.XOS"), false, now);
+        assert!(activation.overlay_changed);
+        assert!(state.overlay_pinned());
+        assert!(!activation.prompt_changed);
+
+        assert!(
+            state
+                .activate(Some("com.apple.Safari"), false, now)
+                .overlay_changed
+        );
+        assert!(!state.overlay_pinned());
+        assert!(!state.activate(None, false, now).overlay_changed);
+    }
+
+    #[test]
+    fn ambient_offer_is_withdrawn_on_switch_and_not_repeated_within_cooldown() {
+        let mut state = configured(vec![trigger("md.obsidian", false, true)]);
+        let start = Instant::now();
+        assert!(
+            state
+                .activate(Some("md.obsidian"), false, start)
+                .prompt_changed
+        );
+        assert_eq!(state.prompt.as_ref().unwrap().name, "md.obsidian");
+
+        assert!(
+            state
+                .activate(Some("com.apple.Safari"), false, start)
+                .prompt_changed
+        );
+        assert!(state.prompt.is_none());
+
+        let soon = start + Duration::from_secs(60);
+        assert!(
+            !state
+                .activate(Some("md.obsidian"), false, soon)
+                .prompt_changed
+        );
+        assert!(state.prompt.is_none());
+
+        let later = start + PROMPT_COOLDOWN;
+        assert!(
+            state
+                .activate(Some("md.obsidian"), false, later)
+                .prompt_changed
+        );
+        assert!(state.prompt.is_some());
+    }
+
+    #[test]
+    fn no_offer_while_already_transcribing() {
+        let mut state = configured(vec![trigger("md.obsidian", false, true)]);
+        state.activate(Some("md.obsidian"), true, Instant::now());
+        assert!(state.prompt.is_none());
+    }
+
+    #[test]
+    fn configure_normalizes_and_withdraws_stale_offers() {
+        let mut state = configured(vec![
+            trigger(" md.obsidian ", false, true),
+            trigger("MD.OBSIDIAN", true, false),
+            trigger("  ", true, true),
+        ]);
+        assert_eq!(state.triggers.len(), 1);
+        assert_eq!(state.triggers[0].bundle_id, "md.obsidian");
+        assert_eq!(state.triggers[0].name, "md.obsidian");
+
+        state.activate(Some("md.obsidian"), false, Instant::now());
+        assert!(state.prompt.is_some());
+        let activation = state.configure(vec![trigger("md.obsidian", true, false)]);
+        assert!(activation.prompt_changed);
+        assert!(activation.overlay_changed);
+        assert!(state.prompt.is_none());
+    }
+}
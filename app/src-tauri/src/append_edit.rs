@@ -0,0 +1,219 @@
+//! Spoken edits to the append-mode buffer.
+//!
+//! While takes are buffered, a take that consists only of an edit command
+//! changes the buffer instead of joining it: "scratch that" drops the last
+//! take, "new paragraph" starts a new paragraph, and "replace X with Y"
+//! rewrites X in the buffered takes. Commands are matched on the raw
+//! transcript, before voice commands turn "scratch that" or "new paragraph"
+//! into their in-take effects.
+
+/// Buffer entry that stands for a paragraph break rather than spoken text.
+const PARAGRAPH_BREAK: &str = "\n\n";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BufferCommand {
+    ScratchThat,
+    NewParagraph,
+    Replace { from: String, to: String },
+}
+
+/// Parse a whole take as a buffer command. Case, surrounding whitespace, and
+/// trailing punctuation are ignored; anything else in the take means it is
+/// dictation, not a command.
+pub(crate) fn parse_buffer_command(take: &str) -> Option<BufferCommand> {
+    let take = trim_punctuation(take);
+    let words: Vec<&str> = take.split_whitespace().collect();
+    let lower: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+
+    match lower
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["scratch", "that"] => return Some(BufferCommand::ScratchThat),
+        ["new", "paragraph"] => return Some(BufferCommand::NewParagraph),
+        _ => {}
+    }
+
+    // "replace X with Y": split on the first standalone "with" so both sides
+    // keep their own words, and Y keeps the case it was spoken with.
+    if lower.first().map(String::as_str) != Some("replace") {
+        return None;
+    }
+    let with = lower.iter().skip(2).position(|word| word == "with")? + 2;
+    let from = trim_punctuation(&words[1..with].join(" ")).to_string();
+    let to = trim_punctuation(&words[with + 1..].join(" ")).to_string();
+    if from.is_empty() || to.is_empty() {
+        return None;
+    }
+    Some(BufferCommand::Replace { from, to })
+}
+
+/// Apply `command` to the buffered takes. Returns whether the buffer changed.
+pub(crate) fn apply_buffer_command(takes: &mut Vec<String>, command: &BufferCommand) -> bool {
+    match command {
+        BufferCommand::ScratchThat => takes.pop().is_some(),
+        BufferCommand::NewParagraph => {
+            // A break only means something after text, and two in a row are one.
+            if takes.last().is_none_or(|last| last == PARAGRAPH_BREAK) {
+                return false;
+            }
+            takes.push(PARAGRAPH_BREAK.to_string());
+            true
+        }
+        BufferCommand::Replace { from, to } => {
+            let mut changed = false;
+            for take in takes.iter_mut().filter(|take| *take != PARAGRAPH_BREAK) {
+                let replaced = crate::voice_commands::replace_phrase(take, from, to);
+                if replaced != *take {
+                    *take = replaced;
+                    changed = true;
+                }
+            }
+            changed
+        }
+    }
+}
+
+/// Spoken takes in the buffer, not counting paragraph breaks.
+pub(crate) fn take_count(takes: &[String]) -> usize {
+    takes.iter().filter(|take| *take != PARAGRAPH_BREAK).count()
+}
+
+/// Takes joined the way they are committed: one space between each, and a
+/// blank line wherever "new paragraph" was spoken.
+pub(crate) fn join_takes(takes: &[String]) -> String {
+    let mut out = String::new();
+    for take in takes {
+        if take == PARAGRAPH_BREAK {
+            if !out.is_empty() {
+                out.push_str(PARAGRAPH_BREAK);
+            }
+            continue;
+        }
+        let take = take.trim();
+        if take.is_empty() {
+            continue;
+        }
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push(' ');
+        }
+        out.push_str(take);
+    }
+    out.trim_end().to_string()
+}
+
+fn trim_punctuation(text: &str) -> &str {
+    text.trim_matches(|c: char| c.is_whitespace() || matches!(c, '.' | ',' | '!' | '?' | ';' | ':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn takes(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_commands_regardless_of_case_and_punctuation() {
+        assert_eq!(
+            parse_buffer_command(" Scratch that. "),
+            Some(BufferCommand::ScratchThat)
+        );
+        assert_eq!(
+            parse_buffer_command("New paragraph!"),
+            Some(BufferCommand::NewParagraph)
+        );
+        assert_eq!(
+            parse_buffer_command("Replace Bob with Robert."),
+            Some(BufferCommand::Replace {
+                from: "Bob".to_string(),
+                to: "Robert".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn replace_splits_on_the_first_with_and_keeps_multi_word_sides() {
+        assert_eq!(
+            parse_buffer_command("replace the old plan, with the plan with dates"),
+            Some(BufferCommand::Replace {
+                from: "the old plan".to_string(),
+                to: "the plan with dates".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn ordinary_dictation_is_not_a_command() {
+        assert_eq!(
+            parse_buffer_command("Scratch that idea, it won't work."),
+            None
+        );
+        assert_eq!(parse_buffer_command("Start a new paragraph here."), None);
+        assert_eq!(parse_buffer_command("Replace the filter."), None);
+        assert_eq!(parse_buffer_command("Replace with nothing."), None);
+        assert_eq!(parse_buffer_command("Replace it with"), None);
+        assert_eq!(parse_buffer_command(""), None);
+    }
+
+    #[test]
+    fn scratch_that_drops_the_last_take() {
+        let mut buffer = takes(&["One.", "Two."]);
+        assert!(apply_buffer_command(
+            &mut buffer,
+            &BufferCommand::ScratchThat
+        ));
+        assert_eq!(buffer, takes(&["One."]));
+        buffer.clear();
+        assert!(!apply_buffer_command(
+            &mut buffer,
+            &BufferCommand::ScratchThat
+        ));
+    }
+
+    #[test]
+    fn new_paragraph_breaks_the_committed_text() {
+        let mut buffer = Vec::new();
+        assert!(!apply_buffer_command(
+            &mut buffer,
+            &BufferCommand::NewParagraph
+        ));
+        buffer.push("Intro.".to_string());
+        assert!(apply_buffer_command(
+            &mut buffer,
+            &BufferCommand::NewParagraph
+        ));
+        assert!(!apply_buffer_command(
+            &mut buffer,
+            &BufferCommand::NewParagraph
+        ));
+        buffer.push("Body one.".to_string());
+        buffer.push("Body two.".to_string());
+        assert_eq!(join_takes(&buffer), "Intro.\n\nBody one. Body two.");
+    }
+
+    #[test]
+    fn replace_rewrites_words_in_every_take() {
+        let mut buffer = takes(&["Ask Bob.", "\n\n", "Bobby and bob agree."]);
+        let command = BufferCommand::Replace {
+            from: "bob".to_string(),
+            to: "Robert".to_string(),
+        };
+        assert!(apply_buffer_command(&mut buffer, &command));
+        assert_eq!(
+            buffer,
+            takes(&["Ask Robert.", "\n\n", "Bobby and Robert agree."])
+        );
+        assert!(!apply_buffer_command(&mut buffer, &command));
+    }
+
+    #[test]
+    fn trailing_paragraph_break_is_dropped_on_join() {
+        let buffer = takes(&["Intro.", "\n\n"]);
+        assert_eq!(join_takes(&buffer), "Intro.");
+        assert_eq!(take_count(&buffer), 1);
+    }
+}
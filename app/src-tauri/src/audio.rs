@@ -1,4 +1,6 @@
+use crate::input_quality::{self, DegradedInput, LowQualityInputAction};
 use crate::state::WHISPER_SAMPLE_RATE;
+use crate::MutexExt;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Sample, SampleFormat};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -28,8 +30,8 @@ pub fn compute_peak(samples: &[f32]) -> f32 {
 const AUDIO_LEVEL_THROTTLE_MS: u64 = 16;
 
 /// Build an input stream that converts interleaved multi-channel samples to mono f32,
-/// computes RMS for each buffer chunk and emits an "audio-level" event if an AppHandle
-/// is provided, throttled to ~60 fps to avoid IPC spam.
+/// computes RMS for each buffer chunk and emits it as an `overlay-state` level if an
+/// AppHandle is provided, throttled to ~60 fps to avoid IPC spam.
 macro_rules! build_mono_input_stream {
     ($device:expr, $config:expr, $shared:expr, $channels:expr, $err_fn:expr, $sample_type:ty, $app_handle:expr, $active:expr) => {{
         let samples_ref = Arc::clone(&$shared);
@@ -64,7 +66,10 @@ macro_rules! build_mono_input_stream {
                         if now.saturating_sub(last) >= AUDIO_LEVEL_THROTTLE_MS {
                             last_emit_ms.store(now, Ordering::Relaxed);
                             let rms = compute_rms(&mono);
-                            let _ = handle.emit("audio-level", rms);
+                            crate::events::emit_overlay_state(
+                                handle,
+                                &crate::events::OverlayState::Level { rms },
+                            );
                         }
                     }
 
@@ -99,12 +104,52 @@ struct RecordingState {
     /// the callback stops accumulating samples even if the stream hasn't been
     /// fully torn down yet (prevents unbounded buffer growth).
     active: Arc<AtomicBool>,
-    /// Device sample rate for the current/last recording.
-    sample_rate: u32,
+    /// The device the current/last recording is capturing from. Shared with
+    /// the audio thread, which updates it when it fails over to another one.
+    device: Arc<Mutex<ActiveDevice>>,
     /// Wall-clock instant when recording started.
     started_at: Option<std::time::Instant>,
-    /// Name of the audio input device used for the current/last recording.
-    device_name: Option<String>,
+    /// Audio from just before the recording started (`pre_roll.rs`), at
+    /// 16 kHz. Prepended when the recording stops.
+    pre_roll: Vec<f32>,
+}
+
+struct ActiveDevice {
+    /// Name of the audio input device.
+    name: Option<String>,
+    /// Sample rate of everything in the recording buffer. After a failover
+    /// the audio already captured is resampled to the new device's rate.
+    sample_rate: u32,
+}
+
+impl ActiveDevice {
+    fn unknown() -> Self {
+        Self {
+            name: None,
+            sample_rate: WHISPER_SAMPLE_RATE,
+        }
+    }
+}
+
+/// Input devices to try, in order, before falling back to any available one.
+/// Set from the `microphonePreferences` setting.
+static DEVICE_PREFERENCES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Replace the ordered input device preference list.
+pub fn set_device_preferences(devices: Vec<String>) {
+    *DEVICE_PREFERENCES.lock_or_recover() = devices;
+}
+
+/// The order a recording tries devices in: the requested one, then the
+/// preference list, without repeats.
+fn device_ranking(device_name: Option<String>) -> Vec<String> {
+    let mut ranking: Vec<String> = device_name.into_iter().collect();
+    for name in DEVICE_PREFERENCES.lock_or_recover().iter() {
+        if !ranking.contains(name) {
+            ranking.push(name.clone());
+        }
+    }
+    ranking
 }
 
 fn get_state() -> &'static Mutex<RecordingState> {
@@ -114,9 +159,9 @@ fn get_state() -> &'static Mutex<RecordingState> {
             thread_handle: None,
             shared: None,
             active: Arc::new(AtomicBool::new(false)),
-            sample_rate: WHISPER_SAMPLE_RATE,
+            device: Arc::new(Mutex::new(ActiveDevice::unknown())),
             started_at: None,
-            device_name: None,
+            pre_roll: Vec::new(),
         })
     })
 }
@@ -154,12 +199,16 @@ pub fn start_recording(
     // Create a brand-new buffer for this recording — no stale data possible
     let new_buffer = Arc::new(Mutex::new(Vec::<f32>::new()));
     state_guard.shared = Some(Arc::clone(&new_buffer));
+    state_guard.pre_roll.clear();
     let active = Arc::new(AtomicBool::new(true));
     state_guard.active = Arc::clone(&active);
+    let device = Arc::new(Mutex::new(ActiveDevice::unknown()));
+    state_guard.device = Arc::clone(&device);
     tracing::info!(target: "audio", "start_recording: created fresh sample buffer");
+    let ranking = device_ranking(device_name);
 
     let (cmd_tx, cmd_rx) = channel::<AudioCommand>();
-    let (ready_tx, ready_rx) = channel::<Result<(u32, String), String>>();
+    let (ready_tx, ready_rx) = channel::<Result<(), String>>();
 
     // Spawn audio thread
     let handle = thread::spawn(move || {
@@ -167,9 +216,10 @@ pub fn start_recording(
             cmd_rx,
             new_buffer,
             active,
+            device,
             ready_tx.clone(),
             app_handle,
-            device_name,
+            ranking,
         ) {
             tracing::error!(target: "audio", "Audio capture error: {}", e);
             let _ = ready_tx.send(Err(e));
@@ -181,10 +231,11 @@ pub fn start_recording(
 
     // Wait for thread to signal ready (with timeout)
     let init_result = match ready_rx.recv_timeout(std::time::Duration::from_secs(5)) {
-        Ok(Ok((device_sample_rate, actual_device_name))) => {
-            state_guard.sample_rate = device_sample_rate;
-            state_guard.device_name = Some(actual_device_name);
+        Ok(Ok(())) => {
             state_guard.started_at = Some(std::time::Instant::now());
+            // Taken once the stream runs, so the ring also covers the time
+            // the device took to open and the two meet without a gap.
+            state_guard.pre_roll = crate::pre_roll::take();
             Ok(())
         }
         Ok(Err(e)) => Err(e),
@@ -202,70 +253,172 @@ pub fn start_recording(
     init_result
 }
 
-fn run_audio_capture(
-    cmd_rx: Receiver<AudioCommand>,
-    shared: Arc<Mutex<Vec<f32>>>,
-    active: Arc<AtomicBool>,
-    ready_tx: Sender<Result<(u32, String), String>>,
-    app_handle: Option<tauri::AppHandle>,
-    device_name: Option<String>,
-) -> Result<(), String> {
+/// Open the named input device, falling back to the system default when it
+/// is missing or devices can't be enumerated.
+fn input_device(device_name: Option<&str>) -> Result<cpal::Device, String> {
     let host = cpal::default_host();
-
-    let device = if let Some(ref name) = device_name {
-        match host.input_devices() {
-            Ok(mut devices) => match devices.find(|d| d.name().ok().as_deref() == Some(name)) {
-                Some(d) => d,
-                None => {
-                    tracing::warn!(target: "audio", "Requested device '{}' not found, falling back to default", name);
-                    host.default_input_device().ok_or_else(|| {
-                        "No input device available. Please grant microphone permission.".to_string()
-                    })?
-                }
-            },
-            Err(e) => {
-                tracing::warn!(target: "audio", "Failed to enumerate devices: {}, falling back to default", e);
-                host.default_input_device().ok_or_else(|| {
-                    "No input device available. Please grant microphone permission.".to_string()
-                })?
-            }
-        }
-    } else {
+    let default_device = || {
         host.default_input_device().ok_or_else(|| {
             "No input device available. Please grant microphone permission.".to_string()
-        })?
+        })
     };
 
-    let actual_name = device.name().unwrap_or_else(|_| "unknown".to_string());
+    let Some(name) = device_name else {
+        return default_device();
+    };
+    match host.input_devices() {
+        Ok(mut devices) => match devices.find(|d| d.name().ok().as_deref() == Some(name)) {
+            Some(d) => Ok(d),
+            None => {
+                tracing::warn!(target: "audio", "Requested device '{}' not found, falling back to default", name);
+                default_device()
+            }
+        },
+        Err(e) => {
+            tracing::warn!(target: "audio", "Failed to enumerate devices: {}, falling back to default", e);
+            default_device()
+        }
+    }
+}
 
-    let config = device
+/// A device picked by `choose_device`: its index among the available ones,
+/// and its place in the ranking (`None` when no ranked device was usable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DeviceChoice {
+    index: usize,
+    rank: Option<usize>,
+}
+
+/// Pick from `available` (device names, in enumeration order) the first
+/// ranked device that isn't `excluded`, then the system default, then any
+/// other input.
+fn choose_device(
+    ranking: &[String],
+    available: &[String],
+    excluded: &[String],
+    default: Option<&str>,
+) -> Option<DeviceChoice> {
+    let usable = |name: &str| !excluded.iter().any(|e| e == name);
+    for (rank, wanted) in ranking.iter().enumerate() {
+        if let Some(index) = available.iter().position(|n| n == wanted && usable(n)) {
+            return Some(DeviceChoice {
+                index,
+                rank: Some(rank),
+            });
+        }
+    }
+    let index = default
+        .filter(|name| usable(name))
+        .and_then(|name| available.iter().position(|n| n == name))
+        .or_else(|| available.iter().position(|n| usable(n)))?;
+    Some(DeviceChoice { index, rank: None })
+}
+
+/// An input device picked for a recording, with its default config.
+struct SelectedInput {
+    device: cpal::Device,
+    config: cpal::SupportedStreamConfig,
+    name: String,
+    rank: Option<usize>,
+    /// Set when the first pick was a low-quality Bluetooth capture.
+    degraded: Option<DegradedInput>,
+}
+
+fn select_input(ranking: &[String], excluded: &[String]) -> Result<SelectedInput, String> {
+    let host = cpal::default_host();
+    let default = host.default_input_device();
+    let default_name = default.as_ref().and_then(|d| d.name().ok());
+    let mut devices: Vec<cpal::Device> = match host.input_devices() {
+        Ok(devices) => devices.collect(),
+        Err(e) => {
+            tracing::warn!(target: "audio", "Failed to enumerate devices: {}, falling back to default", e);
+            Vec::new()
+        }
+    };
+    let names: Vec<String> = devices
+        .iter()
+        .map(|d| d.name().unwrap_or_default())
+        .collect();
+    let Some(choice) = choose_device(ranking, &names, excluded, default_name.as_deref()) else {
+        // Enumeration failed: the default is all there is.
+        return match default {
+            Some(device) if default_name.as_ref().is_none_or(|n| !excluded.contains(n)) => {
+                let config = device
+                    .default_input_config()
+                    .map_err(|e| format!("Failed to get input config: {}", e))?;
+                Ok(SelectedInput {
+                    name: device.name().unwrap_or_else(|_| "unknown".to_string()),
+                    device,
+                    config,
+                    rank: None,
+                    degraded: None,
+                })
+            }
+            _ => Err("No input device available. Please grant microphone permission.".to_string()),
+        };
+    };
+    if !ranking.is_empty() && choice.rank != Some(0) {
+        tracing::warn!(target: "audio", "First-choice input device unavailable, using rank {:?}", choice.rank);
+    }
+    let mut index = choice.index;
+    let mut rank = choice.rank;
+    let mut config = devices[index]
         .default_input_config()
         .map_err(|e| format!("Failed to get input config: {}", e))?;
 
-    let device_sample_rate = config.sample_rate().0;
+    // A Bluetooth headset on the hands-free profile: warn, or move to the
+    // built-in microphone when the user asked for that.
+    let mut degraded = input_quality::check(&names[index], config.sample_rate().0);
+    if let Some(warning) = degraded.as_mut() {
+        let built_in = (input_quality::action() == LowQualityInputAction::UseBuiltIn)
+            .then(|| input_quality::built_in_index(&names))
+            .flatten()
+            .filter(|&i| !excluded.contains(&names[i]));
+        if let Some(built_in) = built_in {
+            if let Ok(built_in_config) = devices[built_in].default_input_config() {
+                tracing::warn!(target: "audio", "Bluetooth input at {} Hz, switching to the built-in microphone", warning.sample_rate);
+                warning.switched_to = Some(names[built_in].clone());
+                rank = ranking.iter().position(|n| *n == names[built_in]);
+                index = built_in;
+                config = built_in_config;
+            }
+        }
+    }
+
+    let device = devices.swap_remove(index);
+    Ok(SelectedInput {
+        name: device.name().unwrap_or_else(|_| "unknown".to_string()),
+        device,
+        config,
+        rank,
+        degraded,
+    })
+}
+
+/// Build and start a stream on `input` that appends to `shared`. `lost` is
+/// set when the device disappears.
+fn start_capture_stream(
+    input: SelectedInput,
+    shared: &Arc<Mutex<Vec<f32>>>,
+    active: &Arc<AtomicBool>,
+    app_handle: Option<tauri::AppHandle>,
+    lost: &Arc<AtomicBool>,
+) -> Result<cpal::Stream, String> {
+    let SelectedInput { device, config, .. } = input;
     let sample_format = config.sample_format();
     let channels = config.channels() as usize;
 
-    let telemetry_device = if cfg!(debug_assertions) {
-        actual_name.clone()
-    } else {
-        "<redacted>".to_string()
+    let lost_flag = Arc::clone(lost);
+    let err_fn = move |err: cpal::StreamError| {
+        if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+            lost_flag.store(true, Ordering::SeqCst);
+        }
+        tracing::error!(target: "audio", "Audio stream error: {}", err);
     };
-    tracing::info!(target: "audio", "run_audio_capture: device='{}', sample_rate={}, channels={}, format={:?}",
-        telemetry_device, device_sample_rate, channels, sample_format);
-
-    let err_fn = |err| tracing::error!(target: "audio", "Audio stream error: {}", err);
 
     let stream = match sample_format {
         SampleFormat::F32 => build_mono_input_stream!(
-            device,
-            config,
-            shared,
-            channels,
-            err_fn,
-            f32,
-            app_handle.clone(),
-            active
+            device, config, shared, channels, err_fn, f32, app_handle, active
         ),
         SampleFormat::I16 => build_mono_input_stream!(
             device, config, shared, channels, err_fn, i16, app_handle, active
@@ -276,21 +429,179 @@ fn run_audio_capture(
     stream
         .play()
         .map_err(|e| format!("Failed to start stream: {}", e))?;
+    Ok(stream)
+}
+
+/// Payload of `recording-device`.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordingDeviceEvent<'a> {
+    /// `None` when the device was lost and nothing else could be opened.
+    device_name: Option<&'a str>,
+    /// Position in the ranking; `None` when no ranked device was available.
+    rank: Option<usize>,
+    /// The recording switched to this device after losing the previous one.
+    failover: bool,
+}
+
+fn emit_recording_device(
+    app_handle: Option<&tauri::AppHandle>,
+    device_name: Option<&str>,
+    rank: Option<usize>,
+    failover: bool,
+) {
+    if let Some(handle) = app_handle {
+        let _ = handle.emit(
+            "recording-device",
+            RecordingDeviceEvent {
+                device_name,
+                rank,
+                failover,
+            },
+        );
+    }
+}
+
+fn emit_degraded_input(app_handle: Option<&tauri::AppHandle>, degraded: Option<DegradedInput>) {
+    if let (Some(handle), Some(warning)) = (app_handle, degraded) {
+        let _ = handle.emit("degraded-input-warning", warning);
+    }
+}
+
+fn log_input(input: &SelectedInput) {
+    let telemetry_device = if cfg!(debug_assertions) {
+        input.name.clone()
+    } else {
+        "<redacted>".to_string()
+    };
+    tracing::info!(target: "audio", "run_audio_capture: device='{}', rank={:?}, sample_rate={}, channels={}, format={:?}",
+        telemetry_device, input.rank, input.config.sample_rate().0, input.config.channels(), input.config.sample_format());
+}
+
+fn run_audio_capture(
+    cmd_rx: Receiver<AudioCommand>,
+    shared: Arc<Mutex<Vec<f32>>>,
+    active: Arc<AtomicBool>,
+    active_device: Arc<Mutex<ActiveDevice>>,
+    ready_tx: Sender<Result<(), String>>,
+    app_handle: Option<tauri::AppHandle>,
+    ranking: Vec<String>,
+) -> Result<(), String> {
+    let mut input = select_input(&ranking, &[])?;
+    log_input(&input);
+    let mut current_name = input.name.clone();
+    let rank = input.rank;
+    let degraded = input.degraded.take();
+    let mut sample_rate = input.config.sample_rate().0;
+    *active_device.lock_or_recover() = ActiveDevice {
+        name: Some(current_name.clone()),
+        sample_rate,
+    };
+
+    let mut lost = Arc::new(AtomicBool::new(false));
+    let mut stream = Some(start_capture_stream(
+        input,
+        &shared,
+        &active,
+        app_handle.clone(),
+        &lost,
+    )?);
 
-    // Signal ready with the device sample rate and name
-    let _ = ready_tx.send(Ok((device_sample_rate, actual_name.clone())));
+    // Signal ready; the device is in `active_device`
+    let _ = ready_tx.send(Ok(()));
+    emit_recording_device(
+        app_handle.as_ref(),
+        Some(current_name.as_str()),
+        rank,
+        false,
+    );
+    emit_degraded_input(app_handle.as_ref(), degraded);
 
-    // Wait for stop command
+    // Wait for stop command, journaling new samples so a crash mid-recording
+    // doesn't lose them. `stop_recording` removes the journal.
+    let mut journal = crate::recording_journal::JournalWriter::create(sample_rate);
+    let mut last_flush = std::time::Instant::now();
+    let mut excluded: Vec<String> = Vec::new();
     loop {
         match cmd_rx.recv_timeout(std::time::Duration::from_millis(100)) {
             Ok(AudioCommand::Stop) => break,
-            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
             Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
         }
+        if lost.load(Ordering::SeqCst) {
+            // The device went away mid-recording: move to the next one in the
+            // ranking and keep appending to the same buffer.
+            if let Some(old) = stream.take() {
+                let _ = old.pause();
+            }
+            tracing::warn!(target: "audio", "Input device lost mid-recording, failing over");
+            excluded.push(current_name.clone());
+            let mut next = match select_input(&ranking, &excluded) {
+                Ok(next) => next,
+                Err(e) => {
+                    // Nothing left to record from; keep what was captured.
+                    tracing::error!(target: "audio", "No input device to fail over to: {}", e);
+                    lost.store(false, Ordering::SeqCst);
+                    emit_recording_device(app_handle.as_ref(), None, None, true);
+                    continue;
+                }
+            };
+            log_input(&next);
+            let next_rate = next.config.sample_rate().0;
+            let next_rank = next.rank;
+            let next_degraded = next.degraded.take();
+            current_name = next.name.clone();
+            // Stitch: bring what was captured to the new device's rate so the
+            // buffer stays one continuous signal.
+            if next_rate != sample_rate {
+                let mut samples = shared.lock_or_recover();
+                *samples = resample(&samples, sample_rate, next_rate);
+                sample_rate = next_rate;
+                journal = crate::recording_journal::JournalWriter::create(sample_rate);
+            }
+            *active_device.lock_or_recover() = ActiveDevice {
+                name: Some(current_name.clone()),
+                sample_rate,
+            };
+            lost = Arc::new(AtomicBool::new(false));
+            match start_capture_stream(next, &shared, &active, app_handle.clone(), &lost) {
+                Ok(next_stream) => {
+                    stream = Some(next_stream);
+                    emit_recording_device(
+                        app_handle.as_ref(),
+                        Some(current_name.as_str()),
+                        next_rank,
+                        true,
+                    );
+                    emit_degraded_input(app_handle.as_ref(), next_degraded);
+                }
+                Err(e) => {
+                    tracing::error!(target: "audio", "Failover device failed to start: {}", e);
+                    // Try the one after it on the next tick.
+                    lost.store(true, Ordering::SeqCst);
+                }
+            }
+        }
+        if last_flush.elapsed() < crate::recording_journal::FLUSH_INTERVAL {
+            continue;
+        }
+        last_flush = std::time::Instant::now();
+        if let Some(writer) = journal.as_mut() {
+            let pending = match shared.lock() {
+                Ok(samples) => samples.get(writer.written()..).unwrap_or_default().to_vec(),
+                Err(_) => continue,
+            };
+            if let Err(e) = writer.append(&pending) {
+                tracing::warn!(target: "audio", "recording journal stopped: {}", e);
+                journal = None;
+            }
+        }
     }
 
     // Explicitly pause before dropping to ensure CoreAudio stops calling us
-    let _ = stream.pause();
+    if let Some(stream) = stream {
+        let _ = stream.pause();
+    }
 
     Ok(())
 }
@@ -317,10 +628,18 @@ pub fn stop_recording() -> Result<Vec<f32>, String> {
         let _ = handle.join();
     }
 
+    // The samples are about to be handed over in memory; the crash journal
+    // has done its job.
+    crate::recording_journal::remove();
+
     // Take this recording's buffer — leaves None for next recording
     let buffer = state_guard.shared.take();
     let started_at = state_guard.started_at.take();
-    let sample_rate = state_guard.sample_rate;
+    let pre_roll = std::mem::take(&mut state_guard.pre_roll);
+    // What the pre-roll heard during this recording must not lead the next.
+    crate::pre_roll::clear();
+    // Read after the join: a failover can't change it any more.
+    let sample_rate = state_guard.device.lock_or_recover().sample_rate;
 
     let samples = if let Some(buf) = buffer {
         let mut guard = buf.lock().unwrap_or_else(|poisoned| {
@@ -348,14 +667,197 @@ pub fn stop_recording() -> Result<Vec<f32>, String> {
     };
 
     // Resample to Whisper's required sample rate if needed
-    if sample_rate != WHISPER_SAMPLE_RATE && !samples.is_empty() {
-        Ok(resample(&samples, sample_rate, WHISPER_SAMPLE_RATE))
+    let samples = if sample_rate != WHISPER_SAMPLE_RATE && !samples.is_empty() {
+        crate::pipeline_trace::phase(crate::pipeline_trace::PipelinePhase::Resample)
+            .in_scope(|| resample(&samples, sample_rate, WHISPER_SAMPLE_RATE))
     } else {
-        Ok(samples)
+        samples
+    };
+    if pre_roll.is_empty() || samples.is_empty() {
+        return Ok(samples);
     }
+    tracing::info!(target: "audio", "stop_recording: prepended {} pre-roll samples", pre_roll.len());
+    let mut joined = pre_roll;
+    joined.extend(samples);
+    Ok(joined)
+}
+
+/// A continuous capture started by `start_listening`. The wake word
+/// (`kws.rs`), ambient mode (`ambient.rs`), pre-roll (`pre_roll.rs`), and the
+/// microphone test (`mic_test.rs`) each run one beside any recording, without
+/// touching the recording buffer or the crash journal. Dropping the handle
+/// stops the stream.
+pub struct ListeningCapture {
+    command_sender: Sender<AudioCommand>,
+    thread_handle: Option<JoinHandle<()>>,
+    device_name: String,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl ListeningCapture {
+    /// Name of the device that was opened, after any fallback to the default.
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    /// The device's own sample rate, before resampling to 16 kHz.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The device's channel count, before mixing to mono.
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Stop the stream and wait for its thread.
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for ListeningCapture {
+    fn drop(&mut self) {
+        let _ = self.command_sender.send(AudioCommand::Stop);
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Like `build_mono_input_stream!`, but hands each mono buffer to `$tx`
+/// instead of appending it to a shared recording buffer.
+macro_rules! build_mono_sender_stream {
+    ($device:expr, $config:expr, $tx:expr, $channels:expr, $err_fn:expr, $sample_type:ty) => {{
+        let tx = $tx.clone();
+        $device
+            .build_input_stream(
+                &$config.into(),
+                move |data: &[$sample_type], _: &_| {
+                    let mono: Vec<f32> = data
+                        .chunks($channels)
+                        .map(|chunk| {
+                            let sum: f32 = chunk.iter().map(|&s| s.to_float_sample()).sum();
+                            sum / $channels as f32
+                        })
+                        .collect();
+                    let _ = tx.send(mono);
+                },
+                $err_fn,
+                None,
+            )
+            .map_err(|e| format!("Failed to build stream: {}", e))?
+    }};
+}
+
+/// Start a continuous capture. Audio is mixed to mono, resampled to 16 kHz,
+/// and sent to `frames` in blocks of about `frame_len` samples until the
+/// returned handle is stopped or the receiver is dropped.
+pub fn start_listening(
+    device_name: Option<String>,
+    frame_len: usize,
+    frames: Sender<Vec<f32>>,
+) -> Result<ListeningCapture, String> {
+    let (cmd_tx, cmd_rx) = channel::<AudioCommand>();
+    let (ready_tx, ready_rx) = channel::<Result<(String, u32, u16), String>>();
+    let handle = thread::spawn(move || {
+        if let Err(e) =
+            run_listening_capture(cmd_rx, ready_tx.clone(), device_name, frame_len, frames)
+        {
+            tracing::error!(target: "audio", "Listening capture error: {}", e);
+            let _ = ready_tx.send(Err(e));
+        }
+    });
+
+    match ready_rx.recv_timeout(std::time::Duration::from_secs(5)) {
+        Ok(Ok((device_name, sample_rate, channels))) => Ok(ListeningCapture {
+            command_sender: cmd_tx,
+            thread_handle: Some(handle),
+            device_name,
+            sample_rate,
+            channels,
+        }),
+        Ok(Err(e)) => {
+            let _ = handle.join();
+            Err(e)
+        }
+        Err(_) => {
+            let _ = cmd_tx.send(AudioCommand::Stop);
+            Err("Listening audio thread failed to initialize within timeout".to_string())
+        }
+    }
+}
+
+fn run_listening_capture(
+    cmd_rx: Receiver<AudioCommand>,
+    ready_tx: Sender<Result<(String, u32, u16), String>>,
+    device_name: Option<String>,
+    frame_len: usize,
+    frames: Sender<Vec<f32>>,
+) -> Result<(), String> {
+    let device = input_device(device_name.as_deref())?;
+    let actual_name = device.name().unwrap_or_else(|_| "unknown".to_string());
+    let config = device
+        .default_input_config()
+        .map_err(|e| format!("Failed to get input config: {}", e))?;
+
+    let device_sample_rate = config.sample_rate().0;
+    let sample_format = config.sample_format();
+    let device_channels = config.channels();
+    let channels = device_channels as usize;
+    tracing::info!(target: "audio", "run_listening_capture: sample_rate={}, channels={}, format={:?}",
+        device_sample_rate, channels, sample_format);
+
+    let (raw_tx, raw_rx) = channel::<Vec<f32>>();
+    let err_fn = |err| tracing::error!(target: "audio", "Listening stream error: {}", err);
+    let stream = match sample_format {
+        SampleFormat::F32 => {
+            build_mono_sender_stream!(device, config, raw_tx, channels, err_fn, f32)
+        }
+        SampleFormat::I16 => {
+            build_mono_sender_stream!(device, config, raw_tx, channels, err_fn, i16)
+        }
+        _ => return Err(format!("Unsupported sample format: {:?}", sample_format)),
+    };
+    stream
+        .play()
+        .map_err(|e| format!("Failed to start stream: {}", e))?;
+    let _ = ready_tx.send(Ok((actual_name, device_sample_rate, device_channels)));
+
+    // Resample whole frames rather than each callback buffer, so the
+    // interpolation seams fall once per frame instead of every few ms.
+    let raw_frame_len =
+        (frame_len as u64 * device_sample_rate as u64 / WHISPER_SAMPLE_RATE as u64).max(1) as usize;
+    let mut pending: Vec<f32> = Vec::with_capacity(raw_frame_len * 2);
+    loop {
+        match cmd_rx.try_recv() {
+            Ok(AudioCommand::Stop) | Err(std::sync::mpsc::TryRecvError::Disconnected) => break,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
+        match raw_rx.recv_timeout(std::time::Duration::from_millis(100)) {
+            Ok(mono) => pending.extend(mono),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        while pending.len() >= raw_frame_len {
+            let frame = resample(
+                &pending[..raw_frame_len],
+                device_sample_rate,
+                WHISPER_SAMPLE_RATE,
+            );
+            pending.drain(..raw_frame_len);
+            if frames.send(frame).is_err() {
+                let _ = stream.pause();
+                return Ok(());
+            }
+        }
+    }
+
+    let _ = stream.pause();
+    Ok(())
 }
 
-#[allow(dead_code)]
 pub fn is_recording() -> bool {
     if let Some(state) = RECORDING_STATE.get() {
         if let Ok(guard) = state.lock() {
@@ -369,7 +871,7 @@ pub fn is_recording() -> bool {
 pub fn last_device_name() -> Option<String> {
     if let Some(state) = RECORDING_STATE.get() {
         if let Ok(guard) = state.lock() {
-            return guard.device_name.clone();
+            return guard.device.lock_or_recover().name.clone();
         }
     }
     None
@@ -438,6 +940,72 @@ mod tests {
         let samples = vec![0.1f32, -0.8, 0.3, 0.2];
         assert!((compute_peak(&samples) - 0.8).abs() < 1e-6);
     }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn the_first_connected_ranked_device_wins() {
+        let ranking = names(&["AirPods", "MacBook Microphone"]);
+        let available = names(&["MacBook Microphone", "USB Mic"]);
+        let choice = choose_device(&ranking, &available, &[], Some("USB Mic"));
+        assert_eq!(
+            choice,
+            Some(DeviceChoice {
+                index: 0,
+                rank: Some(1)
+            })
+        );
+    }
+
+    #[test]
+    fn without_a_ranked_device_the_default_then_any_input_is_used() {
+        let available = names(&["MacBook Microphone", "USB Mic"]);
+        let ranking = names(&["AirPods"]);
+        assert_eq!(
+            choose_device(&ranking, &available, &[], Some("USB Mic")),
+            Some(DeviceChoice {
+                index: 1,
+                rank: None
+            })
+        );
+        let lost = names(&["USB Mic"]);
+        assert_eq!(
+            choose_device(&ranking, &available, &lost, Some("USB Mic")),
+            Some(DeviceChoice {
+                index: 0,
+                rank: None
+            })
+        );
+        assert_eq!(choose_device(&ranking, &[], &[], None), None);
+    }
+
+    #[test]
+    fn a_lost_device_fails_over_to_the_next_ranked_one() {
+        let ranking = names(&["AirPods", "MacBook Microphone"]);
+        // The lost device can linger in the enumeration for a moment.
+        let available = names(&["AirPods", "MacBook Microphone"]);
+        let lost = names(&["AirPods"]);
+        assert_eq!(
+            choose_device(&ranking, &available, &lost, Some("AirPods")),
+            Some(DeviceChoice {
+                index: 1,
+                rank: Some(1)
+            })
+        );
+    }
+
+    #[test]
+    fn the_requested_device_ranks_ahead_of_the_preferences() {
+        set_device_preferences(names(&["AirPods", "USB Mic"]));
+        assert_eq!(
+            device_ranking(Some("USB Mic".into())),
+            names(&["USB Mic", "AirPods"])
+        );
+        assert_eq!(device_ranking(None), names(&["AirPods", "USB Mic"]));
+        set_device_preferences(Vec::new());
+    }
 }
 
 /// Linear-interpolation resample from `from_rate` to `to_rate`.
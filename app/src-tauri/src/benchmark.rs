@@ -1,8 +1,11 @@
 use crate::correction::CorrectionMatcher;
 use crate::model_runtime;
 use crate::resource_monitor::get_process_rss_mb;
+use crate::text_metrics::{character_errors, edit_distance, error_rate, word_errors, words};
+use crate::transcriber::whisper::MAX_THREADS;
 use crate::transcriber::{
-    TranscriptionBackend, COREML_MODEL_NAME, WHISPER_SAMPLE_RATE,
+    performance_cores, TranscriptionBackend, WhisperDecoding, COREML_MODEL_NAME,
+    WHISPER_SAMPLE_RATE,
 };
 use crate::transcript_transform::{
     transform_transcript, TranscriptContext, TranscriptSource, TranscriptStageConfig,
@@ -16,12 +19,24 @@ use std::time::Instant;
 use tauri::Emitter;
 
 const BALANCED_ACCURACY_WINDOW: f64 = 0.02;
+/// Timed decodes per thread count in the thread sweep.
+const THREAD_SWEEP_ITERATIONS: usize = 3;
+/// Thread counts whose median is within this fraction of the fastest count
+/// as tied; the fewest threads wins, leaving cores for everything else.
+const THREAD_TIE_BAND: f64 = 0.05;
 const BENCHMARK_REPORT_VERSION: u32 = 2;
 
 /// Whisper model names ordered smallest-to-largest. Used to pick the
 /// cheapest selected whisper model for the untimed shared-init warm-up.
-const WHISPER_SIZE_ORDER: &[&str] =
-    &["tiny.en", "base.en", "small.en", "medium.en", "large-v3-turbo"];
+const WHISPER_SIZE_ORDER: &[&str] = &[
+    "tiny.en",
+    "base.en",
+    "small.en",
+    "distil-medium.en",
+    "medium.en",
+    "distil-large-v3",
+    "large-v3-turbo",
+];
 struct Fixture {
     id: &'static str,
     label: &'static str,
@@ -112,6 +127,14 @@ const FIXTURES: &[Fixture] = &[
     },
 ];
 
+/// The accuracy self-test uses the three shortest original clips.
+const SELF_TEST_FIXTURE_COUNT: usize = 3;
+
+/// Highest corpus normalized WER at which the self-test passes. Every shipped
+/// model transcribes these clean clips almost perfectly, so a result above this
+/// points at a damaged model file rather than a weaker model.
+const SELF_TEST_MAX_NORMALIZED_WER: f64 = 0.2;
+
 /// Number of Standard-tier fixtures (a prefix of `FIXTURES`): the four original
 /// clips plus jargon/numbers/disfluent. Thorough adds the remaining entries.
 const STANDARD_FIXTURE_COUNT: usize = 7;
@@ -237,6 +260,28 @@ pub struct Recommendations {
     pub fastest: Option<String>,
     pub most_accurate: Option<String>,
     pub balanced: Option<String>,
+    /// Whisper decode threads, when a Whisper model was benchmarked.
+    pub threads: Option<ThreadRecommendation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadTiming {
+    pub threads: u32,
+    pub warm_median_ms: f64,
+}
+
+/// The decode thread count that ran one clip fastest on this machine.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadRecommendation {
+    /// The Whisper model the counts were timed with.
+    pub model_name: String,
+    pub fixture_id: String,
+    /// What `threads: 0` (automatic) uses.
+    pub performance_cores: u32,
+    pub threads: u32,
+    pub timings: Vec<ThreadTiming>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -270,6 +315,8 @@ pub struct BenchmarkConfiguration {
     pub percentile_method: &'static str,
     pub model_run_order: Vec<String>,
     pub shared_init_order: Vec<String>,
+    /// Decode threads the Whisper runs used (the automatic count).
+    pub whisper_threads: u32,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -413,14 +460,6 @@ fn backend_for(model_name: &str) -> Result<Box<dyn TranscriptionBackend>, String
     model_runtime::create_backend(model_name)
 }
 
-fn words(text: &str) -> Vec<String> {
-    text.to_lowercase()
-        .split(|character: char| !character.is_alphanumeric() && character != '\'')
-        .filter(|word| !word.is_empty())
-        .map(ToString::to_string)
-        .collect()
-}
-
 // --- Text normalization for WER scoring -------------------------------------
 //
 // Raw WER punishes formatting and inverse-text-normalization (ITN) differences
@@ -647,27 +686,6 @@ fn normalized_words(text: &str) -> Vec<String> {
         .collect()
 }
 
-fn edit_distance(reference: &[String], hypothesis: &[String]) -> usize {
-    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
-    for (row, reference_word) in reference.iter().enumerate() {
-        let mut current = vec![row + 1; hypothesis.len() + 1];
-        for (column, hypothesis_word) in hypothesis.iter().enumerate() {
-            current[column + 1] = (previous[column + 1] + 1)
-                .min(current[column] + 1)
-                .min(previous[column] + usize::from(reference_word != hypothesis_word));
-        }
-        previous = current;
-    }
-    previous[hypothesis.len()]
-}
-
-/// Raw WER: lowercase + punctuation split only. Returns (errors, reference words).
-pub(crate) fn word_errors(reference: &str, hypothesis: &str) -> (usize, usize) {
-    let reference = words(reference);
-    let hypothesis = words(hypothesis);
-    (edit_distance(&reference, &hypothesis), reference.len())
-}
-
 /// Normalized WER: applies `normalized_words` before scoring so formatting/ITN
 /// differences do not count. Returns (errors, normalized reference words).
 pub(crate) fn normalized_word_errors(reference: &str, hypothesis: &str) -> (usize, usize) {
@@ -751,7 +769,7 @@ fn command_output(program: &str, args: &[&str]) -> Option<String> {
     (!value.is_empty()).then(|| value.to_string())
 }
 
-fn benchmark_environment() -> BenchmarkEnvironment {
+pub(crate) fn benchmark_environment() -> BenchmarkEnvironment {
     #[cfg(target_os = "macos")]
     {
         let memory_mb = command_output("/usr/sbin/sysctl", &["-n", "hw.memsize"])
@@ -802,6 +820,7 @@ fn benchmark_configuration(
         percentile_method: "nearest-rank over measured warm iterations",
         model_run_order,
         shared_init_order,
+        whisper_threads: WhisperDecoding::default().decode_threads(),
     }
 }
 
@@ -821,15 +840,16 @@ fn benchmark_configuration(
 //      active. Changing which of these are shipped ON by default is out of scope
 //      (issue #271 point 3); this only measures the capability.
 
+fn is_whisper(model_name: &str) -> bool {
+    model_runtime::model_definition(model_name)
+        .is_ok_and(|definition| definition.backend == model_runtime::BackendKind::Whisper)
+}
+
 /// Whisper backends bias decoding toward a supplied initial prompt. Feed them
 /// the built-in developer dictionary; return None for backends that ignore the
 /// prompt. See issue #271.
 fn whisper_initial_prompt(model_name: &str) -> Option<String> {
-    matches!(
-        model_name,
-        "tiny.en" | "base.en" | "small.en" | "medium.en" | "large-v3-turbo"
-    )
-    .then(crate::vocab::builtin_terms_prompt)
+    is_whisper(model_name).then(crate::vocab::builtin_terms_prompt)
 }
 
 /// Build the transcript-transform context a default out-of-the-box install
@@ -843,10 +863,14 @@ fn default_delivery_context() -> TranscriptContext {
         global: &global,
         prompt: None,
         correction_matcher: None,
+        redactor: None,
+        replacement_rules: None,
         ide_context_index: None,
         vocabulary_version: 0,
         voice_commands: None,
         session_overrides: crate::dictation_context::SessionOverrides::default(),
+        model_installed: crate::model_runtime::model_installed,
+        power_pressure: None,
     });
     TranscriptContext {
         session_id: 0,
@@ -1095,9 +1119,99 @@ fn recommendations(results: &[ModelResult]) -> Recommendations {
         fastest,
         most_accurate,
         balanced,
+        threads: None,
     }
 }
 
+// --- Decode threads ----------------------------------------------------------
+//
+// After the model runs, one Whisper model decodes one clip again at a few
+// thread counts: 2 and 4, one per performance core (the automatic default),
+// and one per logical CPU, which adds the efficiency cores.
+
+fn thread_candidates(performance_cores: u32, logical_cpus: u32) -> Vec<u32> {
+    let mut candidates = [2, 4, performance_cores, logical_cpus]
+        .map(|threads| threads.clamp(1, MAX_THREADS))
+        .to_vec();
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates
+}
+
+fn pick_threads(timings: &[ThreadTiming]) -> Option<u32> {
+    let fastest = timings
+        .iter()
+        .map(|timing| timing.warm_median_ms)
+        .min_by(f64::total_cmp)?;
+    timings
+        .iter()
+        .filter(|timing| timing.warm_median_ms <= fastest * (1.0 + THREAD_TIE_BAND))
+        .map(|timing| timing.threads)
+        .min()
+}
+
+/// The Balanced model when it is Whisper, otherwise the first Whisper model
+/// that finished.
+fn thread_sweep_model<'a>(
+    results: &'a [ModelResult],
+    recommendations: &Recommendations,
+) -> Option<&'a ModelResult> {
+    let successful = || {
+        results
+            .iter()
+            .filter(|result| result.error.is_none() && is_whisper(&result.model_name))
+    };
+    successful()
+        .find(|result| recommendations.balanced.as_ref() == Some(&result.model_name))
+        .or_else(|| successful().next())
+}
+
+fn sweep_threads<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    coordinator: &BenchmarkCoordinator,
+    model: &BenchmarkModel,
+    prepared: &PreparedFixture,
+    candidates: &[u32],
+    completed: &mut usize,
+    total_steps: usize,
+) -> Result<Vec<ThreadTiming>, String> {
+    let label = Some(prepared.fixture.label);
+    emit_progress(app, *completed, total_steps, model, label, "tuning");
+    let mut backend = backend_for(&model.model_name)?;
+    backend.load_model(&model.model_name)?;
+    let initial_prompt = whisper_initial_prompt(&model.model_name);
+    let prompt_ref = initial_prompt.as_deref();
+    let result = (|| {
+        backend.transcribe(&prepared.samples, "en", prompt_ref, true)?;
+        *completed += 1;
+        let mut timings = Vec::with_capacity(candidates.len());
+        for &threads in candidates {
+            backend.set_whisper_decoding(&WhisperDecoding {
+                threads: Some(threads),
+                ..WhisperDecoding::default()
+            });
+            let mut warm = Vec::with_capacity(THREAD_SWEEP_ITERATIONS);
+            for _ in 0..THREAD_SWEEP_ITERATIONS {
+                if coordinator.is_cancelled() {
+                    return Err("Benchmark cancelled".to_string());
+                }
+                emit_progress(app, *completed, total_steps, model, label, "tuning");
+                let started = Instant::now();
+                backend.transcribe(&prepared.samples, "en", prompt_ref, true)?;
+                warm.push(started.elapsed().as_secs_f64() * 1000.0);
+                *completed += 1;
+            }
+            timings.push(ThreadTiming {
+                threads,
+                warm_median_ms: percentile(&warm, 0.5).unwrap_or(0.0),
+            });
+        }
+        Ok(timings)
+    })();
+    backend.reset();
+    result
+}
+
 pub fn run<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
     coordinator: &BenchmarkCoordinator,
@@ -1139,7 +1253,17 @@ pub fn run<R: tauri::Runtime>(
         .map(|model| model.model_name.clone())
         .collect();
     let shared_init_order = warmup_targets.clone();
-    let total_steps = selected.len() * steps_per_model + warmup_targets.len();
+    let logical_cpus = std::thread::available_parallelism()
+        .map(|count| count.get() as u32)
+        .unwrap_or(1);
+    let thread_counts = thread_candidates(performance_cores(), logical_cpus);
+    // One step loads and warms the model; the rest are timed decodes.
+    let sweep_steps = if selected.iter().any(|model| is_whisper(&model.model_name)) {
+        1 + thread_counts.len() * THREAD_SWEEP_ITERATIONS
+    } else {
+        0
+    };
+    let total_steps = selected.len() * steps_per_model + warmup_targets.len() + sweep_steps;
     let mut completed = 0;
     let mut results = Vec::with_capacity(selected.len());
 
@@ -1395,6 +1519,45 @@ pub fn run<R: tauri::Runtime>(
         });
     }
 
+    let mut recommendations = recommendations(&results);
+    // The Medium clip, which every preset includes.
+    let sweep_fixture = fixtures.get(1).or(fixtures.first());
+    if let (Some(result), Some(prepared)) = (
+        thread_sweep_model(&results, &recommendations),
+        sweep_fixture,
+    ) {
+        let model = catalog
+            .iter()
+            .find(|model| model.model_name == result.model_name)
+            .expect("benchmarked models come from the catalog");
+        match sweep_threads(
+            app,
+            coordinator,
+            model,
+            prepared,
+            &thread_counts,
+            &mut completed,
+            total_steps,
+        ) {
+            Ok(timings) => {
+                recommendations.threads =
+                    pick_threads(&timings).map(|threads| ThreadRecommendation {
+                        model_name: model.model_name.clone(),
+                        fixture_id: prepared.fixture.id.to_string(),
+                        performance_cores: performance_cores(),
+                        threads,
+                        timings,
+                    });
+            }
+            Err(_) if coordinator.is_cancelled() => {
+                return Err("Benchmark cancelled".to_string());
+            }
+            Err(error) => {
+                tracing::warn!(target: "system", model = %model.model_name, "benchmark thread sweep failed: {}", error);
+            }
+        }
+    }
+
     emit_progress(
         app,
         total_steps,
@@ -1410,7 +1573,6 @@ pub fn run<R: tauri::Runtime>(
         None,
         "complete",
     );
-    let recommendations = recommendations(&results);
     Ok(BenchmarkReport {
         report_version: BENCHMARK_REPORT_VERSION,
         created_at: chrono::Utc::now().to_rfc3339(),
@@ -1427,6 +1589,104 @@ pub fn run<R: tauri::Runtime>(
     })
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestClip {
+    pub fixture_id: String,
+    pub label: String,
+    pub reference: String,
+    pub transcript: String,
+    pub word_error_rate: f64,
+    pub normalized_word_error_rate: f64,
+    pub character_error_rate: f64,
+}
+
+/// Result of `run_accuracy_self_test`: one decode per bundled clip, scored
+/// against its ground truth.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    pub model_name: String,
+    pub label: String,
+    pub created_at: String,
+    pub elapsed_ms: f64,
+    /// Corpus rates: total errors over total reference words or characters.
+    pub word_error_rate: f64,
+    pub normalized_word_error_rate: f64,
+    pub character_error_rate: f64,
+    pub max_normalized_word_error_rate: f64,
+    pub passed: bool,
+    pub clips: Vec<SelfTestClip>,
+}
+
+/// Transcribe the self-test clips with `backend`, which must already hold
+/// `model_name`. Decodes without an initial prompt so the score reflects the
+/// model alone.
+pub fn self_test(
+    backend: &mut dyn TranscriptionBackend,
+    model_name: &str,
+) -> Result<SelfTestReport, String> {
+    let definition = model_runtime::model_definition(model_name)?;
+    let fixtures = prepare_fixtures(&FIXTURES[..SELF_TEST_FIXTURE_COUNT], 0.5)?;
+    let started = Instant::now();
+    let mut clips = Vec::with_capacity(fixtures.len());
+    let (mut errors, mut reference_words) = (0, 0);
+    let (mut normalized_errors, mut normalized_reference_words) = (0, 0);
+    let (mut character_errors_total, mut reference_characters) = (0, 0);
+    for prepared in &fixtures {
+        let fixture = prepared.fixture;
+        let transcript = backend.transcribe(&prepared.samples, "en", None, true)?;
+        let raw = word_errors(fixture.reference, &transcript);
+        let normalized = normalized_word_errors(fixture.reference, &transcript);
+        let characters = character_errors(fixture.reference, &transcript);
+        errors += raw.0;
+        reference_words += raw.1;
+        normalized_errors += normalized.0;
+        normalized_reference_words += normalized.1;
+        character_errors_total += characters.0;
+        reference_characters += characters.1;
+        clips.push(SelfTestClip {
+            fixture_id: fixture.id.to_string(),
+            label: fixture.label.to_string(),
+            reference: fixture.reference.trim().to_string(),
+            transcript: transcript.trim().to_string(),
+            word_error_rate: error_rate(raw.0, raw.1),
+            normalized_word_error_rate: error_rate(normalized.0, normalized.1),
+            character_error_rate: error_rate(characters.0, characters.1),
+        });
+    }
+    let normalized_word_error_rate = error_rate(normalized_errors, normalized_reference_words);
+    Ok(SelfTestReport {
+        model_name: model_name.to_string(),
+        label: definition.label.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+        word_error_rate: error_rate(errors, reference_words),
+        normalized_word_error_rate,
+        character_error_rate: error_rate(character_errors_total, reference_characters),
+        max_normalized_word_error_rate: SELF_TEST_MAX_NORMALIZED_WER,
+        passed: normalized_word_error_rate <= SELF_TEST_MAX_NORMALIZED_WER,
+        clips,
+    })
+}
+
+/// Self-test a model on a backend of its own, leaving the dictation runtime
+/// untouched. Used for models other than the selected one and by headless runs.
+pub fn self_test_model(model_name: &str) -> Result<SelfTestReport, String> {
+    let definition = model_runtime::model_definition(model_name)?;
+    if !model_runtime::model_supported(definition) || !model_runtime::model_installed(model_name) {
+        return Err(format!(
+            "{} is not installed on this machine",
+            definition.label
+        ));
+    }
+    let mut backend = backend_for(model_name)?;
+    backend.load_model(model_name)?;
+    let report = self_test(backend.as_mut(), model_name);
+    backend.reset();
+    report
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1488,6 +1748,10 @@ mod tests {
         );
         assert_eq!(configuration.model_run_order, ["tiny.en", "base.en"]);
         assert_eq!(configuration.shared_init_order, ["tiny.en"]);
+        assert_eq!(
+            configuration.whisper_threads,
+            performance_cores().clamp(1, MAX_THREADS)
+        );
     }
 
     #[test]
@@ -1840,6 +2104,59 @@ mod tests {
         assert_eq!(recommendations.balanced.as_deref(), Some("valid"));
     }
 
+    #[test]
+    fn thread_candidates_cover_the_core_counts_within_limits() {
+        assert_eq!(thread_candidates(4, 8), [2, 4, 8]);
+        assert_eq!(thread_candidates(10, 14), [2, 4, 10, 14]);
+        assert_eq!(thread_candidates(24, 32), [2, 4, MAX_THREADS]);
+        assert_eq!(thread_candidates(1, 1), [1, 2, 4]);
+    }
+
+    #[test]
+    fn pick_threads_prefers_fewer_threads_within_the_tie_band() {
+        let timings = |pairs: &[(u32, f64)]| {
+            pairs
+                .iter()
+                .map(|&(threads, warm_median_ms)| ThreadTiming {
+                    threads,
+                    warm_median_ms,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            pick_threads(&timings(&[(2, 400.0), (4, 210.0), (8, 200.0)])),
+            Some(4)
+        );
+        assert_eq!(
+            pick_threads(&timings(&[(2, 400.0), (4, 260.0), (8, 200.0)])),
+            Some(8)
+        );
+        assert_eq!(pick_threads(&[]), None);
+    }
+
+    #[test]
+    fn thread_sweep_uses_the_balanced_model_only_when_it_is_whisper() {
+        let parakeet = model_result(model_runtime::PARAKEET_CPU_MODEL, 50.0, 0.01, 0.01, 1);
+        let base = model_result("base.en", 100.0, 0.02, 0.05, 1);
+        let small = model_result("small.en", 200.0, 0.04, 0.02, 1);
+        let results = [parakeet, base, small];
+
+        let mut recommendations = recommendations(&results);
+        recommendations.balanced = Some("small.en".to_string());
+        let picked = thread_sweep_model(&results, &recommendations);
+        assert_eq!(
+            picked.map(|result| result.model_name.as_str()),
+            Some("small.en")
+        );
+
+        recommendations.balanced = Some(model_runtime::PARAKEET_CPU_MODEL.to_string());
+        let picked = thread_sweep_model(&results, &recommendations);
+        assert_eq!(
+            picked.map(|result| result.model_name.as_str()),
+            Some("base.en")
+        );
+    }
+
     #[test]
     fn exact_speed_ties_use_model_name_for_fastest() {
         let recommendations = recommendations(&[
@@ -1864,7 +2181,14 @@ mod tests {
 
     #[test]
     fn whisper_models_get_the_dev_prompt_and_others_do_not() {
-        for whisper in ["tiny.en", "base.en", "small.en", "medium.en", "large-v3-turbo"] {
+        for whisper in [
+            "tiny.en",
+            "base.en",
+            "small.en",
+            "medium.en",
+            "large-v3-turbo",
+            "distil-large-v3",
+        ] {
             let prompt = whisper_initial_prompt(whisper)
                 .unwrap_or_else(|| panic!("{whisper} should receive an initial prompt"));
             assert!(
@@ -1956,6 +2280,18 @@ mod tests {
         assert_eq!(score.word_errors, 0);
     }
 
+    #[test]
+    #[ignore = "requires tiny.en + Silero VAD; run on macOS with --ignored"]
+    fn self_test_passes_for_tiny_en() {
+        let report = self_test_model("tiny.en").expect("self-test tiny.en");
+        assert_eq!(report.clips.len(), SELF_TEST_FIXTURE_COUNT);
+        println!(
+            "tiny.en WER {:.3} normalized {:.3} CER {:.3}",
+            report.word_error_rate, report.normalized_word_error_rate, report.character_error_rate
+        );
+        assert!(report.passed);
+    }
+
     #[test]
     #[ignore = "requires tiny.en + Silero VAD; run on macOS with --ignored"]
     fn delivered_path_smoke_tiny_en_end_to_end() {
@@ -3,7 +3,11 @@
 //! Pure, deterministic text tidying — no LLM, no network. Each rule is
 //! deliberately conservative: it only touches filler tokens it recognizes and
 //! never drops a real word. Filler removal and capitalization are independently
-//! toggleable so users can opt into only the parts they trust.
+//! toggleable so users can opt into only the parts they trust. Which words are
+//! filler, how sentences capitalize, and how spacing and decimals are written
+//! come from the recording's [`LanguageRules`].
+
+use crate::language_rules::{self, LanguageRules};
 
 /// Options controlling which cleanup rules run.
 ///
@@ -16,25 +20,28 @@ pub struct CleanupOptions {
     pub remove_filler: bool,
     /// Capitalize sentence starts.
     pub capitalize: bool,
+    /// The recording's language rules.
+    pub language: &'static LanguageRules,
 }
 
 impl Default for CleanupOptions {
     fn default() -> Self {
-        Self { remove_filler: true, capitalize: true }
+        Self {
+            remove_filler: true,
+            capitalize: true,
+            language: &language_rules::ENGLISH,
+        }
     }
 }
 
-/// Filler tokens removed when they appear as standalone words. Kept short and
-/// unambiguous so we never strip a real word (e.g. "Erm" the name, or "her").
-const FILLER_TOKENS: &[&str] = &["um", "uh", "er", "hmm", "uhh", "umm", "erm"];
-
 /// Clean a transcript according to `opts`.
 ///
 /// Pipeline order (each step operates on the previous step's output):
 /// 1. remove standalone filler tokens (if enabled),
 /// 2. collapse immediate duplicate words ("the the" -> "the"),
-/// 3. normalize whitespace and spacing around punctuation,
-/// 4. capitalize sentence starts (if enabled).
+/// 3. normalize whitespace and spacing around punctuation, then apply the
+///    language's spacing and decimal conventions,
+/// 4. capitalize sentence starts (if enabled and the language has case).
 ///
 /// Terminal punctuation present in the input is preserved.
 pub fn clean_transcript(text: &str, opts: CleanupOptions) -> String {
@@ -44,15 +51,25 @@ pub fn clean_transcript(text: &str, opts: CleanupOptions) -> String {
 
     let mut out = text.to_string();
 
+    let rules = opts.language;
     if opts.remove_filler {
-        out = remove_filler(&out);
+        out = remove_filler(&out, rules.fillers);
     }
 
-    out = collapse_duplicates(&out);
+    out = collapse_duplicates(&out, rules.stutter_words);
     out = normalize_spacing(&out);
+    if !rules.spaced {
+        out = language_rules::join_unspaced(&out);
+    }
+    if !rules.spaced_punctuation.is_empty() {
+        out = language_rules::space_before(&out, rules.spaced_punctuation);
+    }
+    if rules.decimal_comma {
+        out = language_rules::localize_decimals(&out, rules.clock_words);
+    }
 
-    if opts.capitalize {
-        out = capitalize_sentences(&out);
+    if opts.capitalize && rules.capitalize {
+        out = capitalize_sentences(&out, rules.dotted_i);
     }
 
     out
@@ -78,32 +95,16 @@ fn word_key(token: &str) -> String {
     word.to_lowercase()
 }
 
-/// Closed-class function words whose immediate repetition is overwhelmingly a
-/// recognition/stutter artifact rather than real speech. Only immediate
-/// duplicates of these words collapse.
-///
-/// This preserves the "never drop a real word" guarantee for legitimate
-/// consecutive repeats that English genuinely produces — e.g. "had had no
-/// effect", "the things that that man said", "all that I had had" — because
-/// those content/relative words are *not* on this list. The list is kept
-/// deliberately small and excludes any word that can validly double:
-///   - "that" is excluded ("that that" is valid),
-///   - "had"/"is"/"do"/"has" are excluded (valid as repeated auxiliaries).
-const COLLAPSIBLE_STUTTER_WORDS: &[&str] = &[
-    "i", "the", "a", "an", "and", "to", "of", "it", "in", "on",
-    "we", "you", "so", "but", "for", "with", "my", "he", "she", "they",
-];
-
 /// Remove filler tokens that stand alone as whole words. A token is filler only
-/// when, stripped of trailing punctuation, it case-insensitively matches a known
-/// filler. Punctuation attached to a removed filler is dropped with it.
-fn remove_filler(text: &str) -> String {
+/// when, stripped of trailing punctuation, it case-insensitively matches one of
+/// `fillers`. Punctuation attached to a removed filler is dropped with it.
+fn remove_filler(text: &str, fillers: &[&str]) -> String {
     let kept: Vec<&str> = text
         .split_whitespace()
         .filter(|token| {
             let (word, _) = split_trailing_punct(token);
             let lower = word.to_lowercase();
-            !FILLER_TOKENS.contains(&lower.as_str())
+            !fillers.contains(&lower.as_str())
         })
         .collect();
     kept.join(" ")
@@ -114,10 +115,10 @@ fn remove_filler(text: &str) -> String {
 /// the *first* occurrence is kept so any trailing punctuation on it survives.
 ///
 /// Collapsing is intentionally conservative: only a repeat whose word is in
-/// [`COLLAPSIBLE_STUTTER_WORDS`] is dropped. A legitimate doubled content word
+/// the language's `stutter_words` is dropped. A legitimate doubled content word
 /// such as "had had" or "that that" is left untouched, honoring the module's
 /// "never drop a real word" guarantee.
-fn collapse_duplicates(text: &str) -> String {
+fn collapse_duplicates(text: &str, stutter_words: &[&str]) -> String {
     let mut out: Vec<&str> = Vec::new();
     let mut prev_key: Option<String> = None;
     for token in text.split_whitespace() {
@@ -125,7 +126,7 @@ fn collapse_duplicates(text: &str) -> String {
         let is_repeat = !key.is_empty() && prev_key.as_deref() == Some(key.as_str());
         // Only collapse an immediate repeat when the word is a known stutter
         // artifact; real repeated words (e.g. "had had") are preserved.
-        if is_repeat && COLLAPSIBLE_STUTTER_WORDS.contains(&key.as_str()) {
+        if is_repeat && stutter_words.contains(&key.as_str()) {
             continue;
         }
         out.push(token);
@@ -168,16 +169,21 @@ fn normalize_spacing(text: &str) -> String {
 
 /// Capitalize the first alphabetic character of each sentence. Sentence
 /// boundaries are the start of the string and the position after a terminal
-/// `.`/`!`/`?`. Only the leading letter is touched — interior casing (acronyms,
-/// proper nouns) is left untouched so we never corrupt real words.
-fn capitalize_sentences(text: &str) -> String {
+/// `.`/`!`/`?`; Spanish opening `¿`/`¡` keep the sentence start. Only the
+/// leading letter is touched — interior casing (acronyms, proper nouns) is left
+/// untouched so we never corrupt real words. With `dotted_i`, `i` becomes `İ`.
+fn capitalize_sentences(text: &str, dotted_i: bool) -> String {
     let mut result = String::with_capacity(text.len());
     let mut at_sentence_start = true;
 
     for ch in text.chars() {
         if at_sentence_start && ch.is_alphabetic() {
-            for upper in ch.to_uppercase() {
-                result.push(upper);
+            if dotted_i && ch == 'i' {
+                result.push('İ');
+            } else {
+                for upper in ch.to_uppercase() {
+                    result.push(upper);
+                }
             }
             at_sentence_start = false;
             continue;
@@ -187,7 +193,7 @@ fn capitalize_sentences(text: &str) -> String {
 
         if matches!(ch, '.' | '!' | '?') {
             at_sentence_start = true;
-        } else if !ch.is_whitespace() {
+        } else if !ch.is_whitespace() && !matches!(ch, '¿' | '¡') {
             // A non-terminal, non-space char means we're inside a sentence.
             at_sentence_start = false;
         }
@@ -203,7 +209,7 @@ mod tests {
     use super::*;
 
     fn full() -> CleanupOptions {
-        CleanupOptions { remove_filler: true, capitalize: true }
+        CleanupOptions::default()
     }
 
     #[test]
@@ -236,7 +242,11 @@ mod tests {
 
     #[test]
     fn filler_disabled_keeps_tokens() {
-        let opts = CleanupOptions { remove_filler: false, capitalize: false };
+        let opts = CleanupOptions {
+            remove_filler: false,
+            capitalize: false,
+            ..CleanupOptions::default()
+        };
         assert_eq!(clean_transcript("um hello", opts), "um hello");
     }
 
@@ -287,7 +297,11 @@ mod tests {
 
     #[test]
     fn capitalize_disabled_leaves_case() {
-        let opts = CleanupOptions { remove_filler: false, capitalize: false };
+        let opts = CleanupOptions {
+            remove_filler: false,
+            capitalize: false,
+            ..CleanupOptions::default()
+        };
         assert_eq!(clean_transcript("hello. world.", opts), "hello. world.");
     }
 
@@ -323,11 +337,77 @@ mod tests {
     #[test]
     fn independent_toggles() {
         // Filler removed, but capitalization left off.
-        let opts = CleanupOptions { remove_filler: true, capitalize: false };
+        let opts = CleanupOptions {
+            remove_filler: true,
+            capitalize: false,
+            ..CleanupOptions::default()
+        };
         assert_eq!(clean_transcript("um hello", opts), "hello");
 
         // Capitalization on, filler left in.
-        let opts = CleanupOptions { remove_filler: false, capitalize: true };
+        let opts = CleanupOptions {
+            remove_filler: false,
+            capitalize: true,
+            ..CleanupOptions::default()
+        };
         assert_eq!(clean_transcript("um hello", opts), "Um hello");
     }
+
+    fn in_language(code: &str) -> CleanupOptions {
+        CleanupOptions {
+            language: LanguageRules::for_code(code),
+            ..CleanupOptions::default()
+        }
+    }
+
+    #[test]
+    fn fillers_and_stutters_follow_the_language() {
+        // "er" is Swedish for "you"; only English treats it as filler.
+        assert_eq!(
+            clean_transcript("er kommer er kommer", in_language("sv")),
+            "Er kommer er kommer"
+        );
+        assert_eq!(
+            clean_transcript("ähm ich die die Katze", in_language("de")),
+            "Ich die die Katze"
+        );
+        assert_eq!(clean_transcript("euh bonjour", in_language("fr")), "Bonjour");
+    }
+
+    #[test]
+    fn chinese_and_japanese_drop_spaces_and_skip_capitalization() {
+        assert_eq!(
+            clean_transcript("我 用 iphone 打字 。 好", in_language("zh")),
+            "我用iphone打字。好"
+        );
+        assert_eq!(
+            clean_transcript("今日は 晴れ です", in_language("ja")),
+            "今日は晴れです"
+        );
+    }
+
+    #[test]
+    fn capitalization_follows_the_language() {
+        assert_eq!(
+            clean_transcript("hola. ¿cómo estás?", in_language("es")),
+            "Hola. ¿Cómo estás?"
+        );
+        assert_eq!(
+            clean_transcript("iyi günler. istanbul güzel.", in_language("tr")),
+            "İyi günler. İstanbul güzel."
+        );
+    }
+
+    #[test]
+    fn french_spacing_and_decimal_commas() {
+        assert_eq!(
+            clean_transcript("vraiment ? il fait 3.5 degrés", in_language("fr")),
+            "Vraiment\u{a0}? Il fait 3,5 degrés"
+        );
+        assert_eq!(
+            clean_transcript("um 10.30 Uhr kostet es 2.50", in_language("de")),
+            "Um 10.30 Uhr kostet es 2,50"
+        );
+        assert_eq!(clean_transcript("it is 3.5 degrees", full()), "It is 3.5 degrees");
+    }
 }
@@ -0,0 +1,224 @@
+//! Locate an audio file the user just copied or selected, for the
+//! "transcribe clipboard audio" action (voice memos saved out of chat apps).
+//!
+//! Sources are tried in order: file URLs on the pasteboard (Finder Copy), a
+//! plain-text path or `file://` URL on the clipboard, then the current Finder
+//! selection. Only extensions `audio_decode` can read are accepted. Paths are
+//! returned to the caller and never logged.
+
+use std::path::{Path, PathBuf};
+
+/// Extensions enabled in the symphonia feature set (see `audio_decode`).
+const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "mp4", "aac"];
+
+/// Which source produced the path. Logged as a stable code; the path is not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFileSource {
+    PasteboardFileUrl,
+    ClipboardText,
+    FinderSelection,
+}
+
+impl AudioFileSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PasteboardFileUrl => "pasteboard_file_url",
+            Self::ClipboardText => "clipboard_text",
+            Self::FinderSelection => "finder_selection",
+        }
+    }
+}
+
+fn is_supported_audio(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            SUPPORTED_EXTENSIONS
+                .iter()
+                .any(|supported| supported.eq_ignore_ascii_case(ext))
+        })
+}
+
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = input.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Interpret one clipboard line as an audio file path. Accepts absolute paths,
+/// `~/` paths, and `file://` URLs (percent-encoded, optional `localhost` host).
+/// Surrounding whitespace and quotes from "Copy as Pathname" are ignored. The
+/// file's existence is checked separately by the caller.
+fn audio_path_from_text(text: &str, home: Option<&Path>) -> Option<PathBuf> {
+    let trimmed = text.trim().trim_matches(|c| c == '"' || c == '\'');
+    let path = if let Some(rest) = trimmed.strip_prefix("file://") {
+        let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+        if !rest.starts_with('/') {
+            return None;
+        }
+        PathBuf::from(percent_decode(rest)?)
+    } else if let Some(rest) = trimmed.strip_prefix("~/") {
+        home?.join(rest)
+    } else if trimmed.starts_with('/') {
+        PathBuf::from(trimmed)
+    } else {
+        return None;
+    };
+    is_supported_audio(&path).then_some(path)
+}
+
+/// First existing, supported path among `candidates`.
+fn first_audio_path<I, S>(candidates: I, exists: impl Fn(&Path) -> bool) -> Option<PathBuf>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let home = dirs::home_dir();
+    candidates
+        .into_iter()
+        .filter_map(|candidate| audio_path_from_text(candidate.as_ref(), home.as_deref()))
+        .find(|path| exists(path))
+}
+
+#[cfg(target_os = "macos")]
+fn pasteboard_file_urls() -> Vec<String> {
+    use objc2_app_kit::NSPasteboard;
+    use objc2_foundation::NSString;
+
+    let pb = NSPasteboard::generalPasteboard();
+    let Some(items) = pb.pasteboardItems() else {
+        return Vec::new();
+    };
+    let file_url_type = NSString::from_str("public.file-url");
+    items
+        .iter()
+        .filter_map(|item| item.stringForType(&file_url_type))
+        .map(|url| url.to_string())
+        .collect()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn pasteboard_file_urls() -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(target_os = "macos")]
+fn finder_selection_paths() -> Vec<String> {
+    const SCRIPT: &str = r#"tell application "Finder"
+set selectedItems to selection
+set output to ""
+repeat with selectedItem in selectedItems
+set output to output & POSIX path of (selectedItem as alias) & linefeed
+end repeat
+return output
+end tell"#;
+    match crate::injector::run_osascript_with_timeout(SCRIPT) {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect(),
+        Ok(_) | Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn finder_selection_paths() -> Vec<String> {
+    Vec::new()
+}
+
+/// Find the audio file to transcribe. Returns a user-facing error when no
+/// source holds a readable, supported audio file.
+pub fn locate_audio_file() -> Result<(String, AudioFileSource), String> {
+    let exists = |path: &Path| path.is_file();
+    if let Some(path) = first_audio_path(pasteboard_file_urls(), exists) {
+        return Ok((
+            path.to_string_lossy().to_string(),
+            AudioFileSource::PasteboardFileUrl,
+        ));
+    }
+    if let Ok(text) = crate::injector::read_clipboard_text() {
+        if let Some(path) = first_audio_path(text.lines(), exists) {
+            return Ok((
+                path.to_string_lossy().to_string(),
+                AudioFileSource::ClipboardText,
+            ));
+        }
+    }
+    if let Some(path) = first_audio_path(finder_selection_paths(), exists) {
+        return Ok((
+            path.to_string_lossy().to_string(),
+            AudioFileSource::FinderSelection,
+        ));
+    }
+    Err(format!(
+        "Copy or select an audio file ({}) first.",
+        SUPPORTED_EXTENSIONS.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_absolute_home_and_file_url_paths() {
+        let home = Path::new("/Users/me");
+        assert_eq!(
+            audio_path_from_text("/tmp/memo.m4a", Some(home)),
+            Some(PathBuf::from("/tmp/memo.m4a"))
+        );
+        assert_eq!(
+            audio_path_from_text("  \"~/Downloads/Voice Memo.MP3\"\n", Some(home)),
+            Some(PathBuf::from("/Users/me/Downloads/Voice Memo.MP3"))
+        );
+        assert_eq!(
+            audio_path_from_text("file:///Users/me/Voice%20Memo.wav", None),
+            Some(PathBuf::from("/Users/me/Voice Memo.wav"))
+        );
+        assert_eq!(
+            audio_path_from_text("file://localhost/tmp/a.aac", None),
+            Some(PathBuf::from("/tmp/a.aac"))
+        );
+    }
+
+    #[test]
+    fn rejects_relative_unsupported_and_malformed_inputs() {
+        assert_eq!(audio_path_from_text("memo.m4a", None), None);
+        assert_eq!(audio_path_from_text("/tmp/notes.txt", None), None);
+        assert_eq!(audio_path_from_text("/tmp/no-extension", None), None);
+        assert_eq!(audio_path_from_text("~/memo.wav", None), None);
+        assert_eq!(
+            audio_path_from_text("file://server/share/a.wav", None),
+            None
+        );
+        assert_eq!(audio_path_from_text("file:///tmp/bad%zz.wav", None), None);
+        assert_eq!(
+            audio_path_from_text("https://example.com/a.mp3", None),
+            None
+        );
+    }
+
+    #[test]
+    fn first_existing_candidate_wins() {
+        let candidates = [
+            "/tmp/missing.wav",
+            "hello",
+            "/tmp/present.mp3",
+            "/tmp/also.wav",
+        ];
+        let found = first_audio_path(candidates, |path| path != Path::new("/tmp/missing.wav"));
+        assert_eq!(found, Some(PathBuf::from("/tmp/present.mp3")));
+        assert_eq!(first_audio_path(candidates, |_| false), None);
+    }
+}
@@ -1,11 +1,12 @@
 use crate::benchmark::{
-    self, BenchmarkCoordinator, BenchmarkModel, BenchmarkReport, BenchmarkRequest,
+    self, BenchmarkCoordinator, BenchmarkModel, BenchmarkReport, BenchmarkRequest, SelfTestReport,
 };
-use crate::state::DictationStatus;
+use crate::model_runtime::PreparationReason;
 use crate::{MutexExt, State};
 use serde::Serialize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use tauri::Manager;
 
 struct BenchmarkRunGuard(Arc<BenchmarkCoordinator>);
 
@@ -35,12 +36,13 @@ pub fn get_benchmark_activity(state: tauri::State<'_, State>) -> BenchmarkActivi
     }
 }
 
-#[tauri::command]
-pub async fn run_benchmark(
-    app_handle: tauri::AppHandle,
-    state: tauri::State<'_, State>,
-    request: BenchmarkRequest,
-) -> Result<BenchmarkReport, String> {
+/// Claim the benchmark slot for `entry`, refusing while anything else holds
+/// the backend, microphone, or transform surface.
+fn claim_benchmark_slot(
+    app_handle: &tauri::AppHandle,
+    state: &tauri::State<'_, State>,
+    entry: &'static str,
+) -> Result<BenchmarkRunGuard, String> {
     let coordinator = state.benchmark.clone();
     // Auto-dismiss a parked transform review, refuse on an active transform
     // (issue #338 — this path previously ignored the transform status
@@ -48,13 +50,13 @@ pub async fn run_benchmark(
     // in-lock guard below stays as a race guard.
     {
         let fx = crate::transform_flow::TauriFlowEffects {
-            app: &app_handle,
-            state: &state,
+            app: app_handle,
+            state,
         };
         crate::transform_flow::clear_parked_review_for_pipeline_work(
             &state.app_state,
             &fx,
-            "run_benchmark",
+            entry,
             "Wait for the transform to finish before benchmarking",
         )?;
     }
@@ -66,7 +68,7 @@ pub async fn run_benchmark(
         if state.app_state.transform_status().blocks_recording() {
             return Err("Wait for the transform to finish before benchmarking".to_string());
         }
-        if dictation.status != DictationStatus::Idle {
+        if !dictation.status.is_settled() {
             return Err("Stop recording before running a benchmark".to_string());
         }
         if state.app_state.file_transcribing.load(Ordering::SeqCst) {
@@ -84,7 +86,17 @@ pub async fn run_benchmark(
     // helper before benchmarking (fail-fast no-op while a transform is in
     // flight). The benchmark slot is already claimed above.
     state.transform_runtime.shutdown();
-    let guard = BenchmarkRunGuard(coordinator.clone());
+    Ok(BenchmarkRunGuard(coordinator))
+}
+
+#[tauri::command]
+pub async fn run_benchmark(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    request: BenchmarkRequest,
+) -> Result<BenchmarkReport, String> {
+    let guard = claim_benchmark_slot(&app_handle, &state, "run_benchmark")?;
+    let coordinator = guard.0.clone();
     super::models::ensure_vad_model(&app_handle)
         .await
         .map_err(|error| format!("Could not prepare speech filtering: {error}"))?;
@@ -97,6 +109,57 @@ pub async fn run_benchmark(
     .map_err(|error| format!("Benchmark task failed: {error}"))?
 }
 
+/// Transcribe the bundled reference clips and score WER/CER. Defaults to the
+/// selected model, which runs on the dictation runtime so a damaged download
+/// fails here the same way it would when dictating.
+#[tauri::command]
+pub async fn run_accuracy_self_test(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    model_name: Option<String>,
+) -> Result<SelfTestReport, String> {
+    let guard = claim_benchmark_slot(&app_handle, &state, "run_accuracy_self_test")?;
+    let selected = state
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .model_name
+        .clone();
+    let model_name = model_name.unwrap_or_else(|| selected.clone());
+    super::models::ensure_vad_model(&app_handle)
+        .await
+        .map_err(|error| format!("Could not prepare speech filtering: {error}"))?;
+
+    let report = tokio::task::spawn_blocking(move || {
+        let _guard = guard;
+        if model_name != selected {
+            return benchmark::self_test_model(&model_name);
+        }
+        let state = app_handle.state::<State>();
+        state
+            .app_state
+            .model_runtime
+            .with_ready_backend(
+                Some(&app_handle),
+                &model_name,
+                PreparationReason::SelfTest,
+                |backend| benchmark::self_test(backend, &model_name),
+            )
+            .map(|(report, _)| report)
+    })
+    .await
+    .map_err(|error| format!("Self-test task failed: {error}"))??;
+    tracing::info!(
+        target: "pipeline",
+        model = %report.model_name,
+        passed = report.passed,
+        normalized_wer = report.normalized_word_error_rate,
+        cer = report.character_error_rate,
+        "accuracy self-test complete"
+    );
+    Ok(report)
+}
+
 #[tauri::command]
 pub fn cancel_benchmark(state: tauri::State<'_, State>) -> bool {
     let running = state.benchmark.is_running();
@@ -1,8 +1,16 @@
-use crate::{keyboard, injector};
+use crate::events::{self, HotkeyEvent};
+use crate::{injector, instance, keyboard};
 use tauri::Emitter;
 
+/// Start (or retarget) the dictation listener. `timing` defaults to the
+/// built-in thresholds and is rejected when out of bounds.
 #[tauri::command]
-pub fn start_keyboard_listener(app_handle: tauri::AppHandle, hotkey: String, mode: String) -> Result<(), String> {
+pub fn start_keyboard_listener(
+    app_handle: tauri::AppHandle,
+    hotkey: String,
+    mode: String,
+    timing: Option<keyboard::HotkeyTiming>,
+) -> Result<(), String> {
     const VALID_MODES: &[&str] = &["double_tap", "hold_down", "both"];
     if !VALID_MODES.contains(&mode.as_str()) {
         tracing::error!(target: "keyboard", "Invalid keyboard listener mode: {}", mode);
@@ -11,14 +19,25 @@ pub fn start_keyboard_listener(app_handle: tauri::AppHandle, hotkey: String, mod
     if !injector::is_accessibility_enabled() {
         return Err("Accessibility permission is required. Please grant it in System Settings.".to_string());
     }
-    keyboard::start_listener(app_handle, &hotkey, &mode);
-    tracing::info!(target: "keyboard", "Keyboard listener started: mode={}, key={}, accessibility={}", mode, hotkey, injector::is_accessibility_enabled());
+    let timing = timing.unwrap_or_default();
+    // Only one running copy (dev or release) may listen for the hotkey.
+    if let Err(error) = instance::claim_keyboard_listener() {
+        tracing::warn!(target: "keyboard", "Keyboard listener not started: {}", error);
+        return Err(error);
+    }
+    if let Err(error) = keyboard::start_listener(app_handle, &hotkey, &mode, timing) {
+        tracing::error!(target: "keyboard", "Invalid hotkey timing: {}", error);
+        instance::release_keyboard_listener();
+        return Err(error);
+    }
+    tracing::info!(target: "keyboard", "Keyboard listener started: mode={}, key={}, timing={:?}, accessibility={}", mode, hotkey, timing, injector::is_accessibility_enabled());
     Ok(())
 }
 
 #[tauri::command]
 pub fn stop_keyboard_listener() {
     keyboard::stop_listener();
+    instance::release_keyboard_listener();
     tracing::info!(target: "keyboard", "Keyboard listener stopped: accessibility={}", injector::is_accessibility_enabled());
 }
 
@@ -26,13 +45,171 @@ pub fn stop_keyboard_listener() {
 pub fn update_keyboard_key(app_handle: tauri::AppHandle, hotkey: String) {
     let should_stop = keyboard::set_target_key(&hotkey);
     if should_stop {
-        let _ = app_handle.emit("hold-down-stop", ());
+        events::emit_hotkey(&app_handle, HotkeyEvent::HoldDownStop);
         tracing::info!(target: "keyboard", "Keyboard key changed while held — emitted stop; updated to: {}", hotkey);
     } else {
         tracing::info!(target: "keyboard", "Keyboard key updated to: {}", hotkey);
     }
 }
 
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileKeyBinding {
+    profile_id: String,
+    hotkey: String,
+}
+
+/// Bind each dictation profile to its own hold-down key on the shared
+/// listener. The settings UI keeps these keys clear of the dictation and
+/// transform hotkeys; an empty list unbinds them all.
+#[tauri::command]
+pub fn set_dictation_profile_keys(app_handle: tauri::AppHandle, bindings: Vec<ProfileKeyBinding>) {
+    let bindings: Vec<(String, String)> = bindings
+        .into_iter()
+        .map(|binding| (binding.profile_id, binding.hotkey))
+        .collect();
+    for profile_id in keyboard::set_profile_keys(&bindings) {
+        let _ = app_handle.emit(
+            "profile-hold-stop",
+            serde_json::json!({ "profileId": profile_id }),
+        );
+    }
+    tracing::info!(target: "keyboard", count = bindings.len(), "Dictation profile keys updated");
+}
+
+/// Bind the append-mode commit key on the shared listener, or unbind it with
+/// `None`. The settings UI keeps it clear of the other hotkeys.
+#[tauri::command]
+pub fn set_append_commit_key(hotkey: Option<String>) {
+    let bound = keyboard::set_append_commit_key(hotkey.as_deref());
+    tracing::info!(target: "keyboard", bound, "Append commit key updated");
+}
+
+/// Bind the key that pastes the last transcription again, or unbind it with
+/// `None`. The settings UI keeps it clear of the other hotkeys.
+#[tauri::command]
+pub fn set_paste_last_key(hotkey: Option<String>) {
+    let bound = keyboard::set_paste_last_key(hotkey.as_deref());
+    tracing::info!(target: "keyboard", bound, "Paste last key updated");
+}
+
+/// Bind the key that transcribes the copied or Finder-selected audio file,
+/// or unbind it with `None`. The settings UI keeps it clear of the other
+/// hotkeys.
+#[tauri::command]
+pub fn set_clipboard_audio_key(hotkey: Option<String>) {
+    let bound = keyboard::set_clipboard_audio_key(hotkey.as_deref());
+    tracing::info!(target: "keyboard", bound, "Clipboard audio key updated");
+}
+
+/// Bind the key that marks a section break in the current recording, or
+/// unbind it with `None`. The settings UI keeps it clear of the other hotkeys.
+#[tauri::command]
+pub fn set_marker_key(hotkey: Option<String>) {
+    let bound = keyboard::set_marker_key(hotkey.as_deref());
+    tracing::info!(target: "keyboard", bound, "Marker key updated");
+}
+
+/// Record the raw key events Murmur receives for `duration_ms` (default 5 s,
+/// clamped to 1–10 s) while the user presses `hotkey`, and report how each
+/// was identified. Modifiers only; other keys are reported as "other".
+#[tauri::command]
+pub async fn diagnose_hotkey(
+    app_handle: tauri::AppHandle,
+    hotkey: String,
+    duration_ms: Option<u64>,
+) -> Result<keyboard::HotkeyDiagnostic, String> {
+    if !injector::is_accessibility_enabled() {
+        return Err("Accessibility permission is required. Please grant it in System Settings.".to_string());
+    }
+    let duration_ms = duration_ms.unwrap_or(5_000).clamp(1_000, 10_000);
+    keyboard::begin_key_capture(app_handle, &hotkey);
+    tokio::time::sleep(std::time::Duration::from_millis(duration_ms)).await;
+    let diagnostic = keyboard::finish_key_capture(&hotkey);
+    tracing::info!(
+        target: "keyboard",
+        key = %hotkey,
+        events = diagnostic.events.len(),
+        matched = diagnostic.events.iter().filter(|event| event.matches_hotkey).count(),
+        listener_active = diagnostic.listener_active,
+        "hotkey diagnostic complete"
+    );
+    Ok(diagnostic)
+}
+
+/// Measure the user's natural double-tap: capture `hotkey` for `duration_ms`
+/// (default 6 s, clamped to 3–10 s) while they double-tap it a few times,
+/// and suggest a timing that fits. Nothing is applied; the settings UI saves
+/// the suggestion if the user accepts it.
+#[tauri::command]
+pub async fn calibrate_hotkey_timing(
+    app_handle: tauri::AppHandle,
+    hotkey: String,
+    duration_ms: Option<u64>,
+) -> Result<keyboard::TimingCalibration, String> {
+    if !injector::is_accessibility_enabled() {
+        return Err(
+            "Accessibility permission is required. Please grant it in System Settings.".to_string(),
+        );
+    }
+    let duration_ms = duration_ms.unwrap_or(6_000).clamp(3_000, 10_000);
+    keyboard::begin_key_capture(app_handle, &hotkey);
+    tokio::time::sleep(std::time::Duration::from_millis(duration_ms)).await;
+    let diagnostic = keyboard::finish_key_capture(&hotkey);
+    let calibration = keyboard::calibrate_timing(&diagnostic.events, keyboard::timing())?;
+    tracing::info!(
+        target: "keyboard",
+        key = %hotkey,
+        taps = calibration.taps,
+        longest_tap_ms = calibration.longest_tap_ms,
+        longest_gap_ms = calibration.longest_gap_ms,
+        "hotkey timing calibrated"
+    );
+    Ok(calibration)
+}
+
+/// Record every key event for `duration_secs` (default 15, clamped to 5–60)
+/// with the detector states around it, which detectors consumed it, and what
+/// was emitted, for attaching to a bug report about missed hotkeys.
+#[tauri::command]
+pub async fn start_keyboard_diagnostics(
+    app_handle: tauri::AppHandle,
+    duration_secs: Option<u64>,
+) -> Result<keyboard::KeyboardDiagnosticsReport, String> {
+    if !injector::is_accessibility_enabled() {
+        return Err(
+            "Accessibility permission is required. Please grant it in System Settings.".to_string(),
+        );
+    }
+    let duration_secs = duration_secs.unwrap_or(15).clamp(5, 60);
+    keyboard::begin_keyboard_diagnostics(app_handle);
+    tokio::time::sleep(std::time::Duration::from_secs(duration_secs)).await;
+    let report = keyboard::finish_keyboard_diagnostics();
+    tracing::info!(
+        target: "keyboard",
+        entries = report.entries.len(),
+        dropped = report.dropped,
+        mode = report.mode,
+        "keyboard diagnostics recorded"
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn get_keyboard_listener_health() -> Option<keyboard::ListenerDegraded> {
+    keyboard::listener_degraded()
+}
+
+#[tauri::command]
+pub fn retry_keyboard_listener(app_handle: tauri::AppHandle) -> Result<(), String> {
+    if !injector::is_accessibility_enabled() {
+        return Err("Accessibility permission is required. Please grant it in System Settings.".to_string());
+    }
+    keyboard::retry_listener(app_handle);
+    tracing::info!(target: "keyboard", "Keyboard listener retry requested");
+    Ok(())
+}
+
 #[tauri::command]
 pub fn set_keyboard_recording(recording: bool) {
     keyboard::set_recording_state(recording);
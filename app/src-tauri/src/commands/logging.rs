@@ -5,6 +5,13 @@ pub fn get_log_contents(lines: usize) -> String {
     crate::telemetry::read_pretty_log_tail(lines)
 }
 
+/// Filtered, paged view over the structured event log (active and rotated
+/// JSONL), so the log viewer never has to pull whole files across IPC.
+#[tauri::command]
+pub fn query_logs(filters: crate::telemetry::LogQuery) -> crate::telemetry::LogQueryPage {
+    crate::telemetry::query_logs(&filters)
+}
+
 #[tauri::command]
 pub fn clear_logs() -> Result<(), String> {
     crate::telemetry::clear_all_logs()?;
@@ -12,8 +19,22 @@ pub fn clear_logs() -> Result<(), String> {
     Ok(())
 }
 
+/// Shred the transcript logs and plain-text app logs on disk. History lives
+/// in the frontend, which clears it alongside this call.
+#[tauri::command]
+pub fn purge_all_transcripts() -> Result<usize, String> {
+    let purged = crate::telemetry::purge_transcript_logs()?;
+    tracing::info!(target: "system", purged, "transcript logs purged");
+    Ok(purged)
+}
+
+/// Forward a frontend log line. Dropped in privacy mode: the frontend logs
+/// transcripts and errors verbatim, and nothing sanitizes them.
 #[tauri::command]
 pub fn log_frontend(level: String, message: String, transform_pass_id: Option<u64>) {
+    if crate::telemetry::privacy_mode() {
+        return;
+    }
     match (level.to_uppercase().as_str(), transform_pass_id) {
         ("WARN", Some(transform_pass_id)) => {
             tracing::warn!(target: "system", source = "frontend", transform_pass_id, "{}", message)
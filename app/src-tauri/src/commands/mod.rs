@@ -6,10 +6,15 @@ pub mod logging;
 pub mod models;
 pub(crate) mod native_window;
 pub mod overlay;
+pub mod payloads;
 pub mod performance;
 pub mod permissions;
+pub mod profiles;
 pub mod recording;
+pub mod replacement_rules;
+pub mod rewrite_templates;
 pub mod transform_diagnostics;
 pub mod transform_model;
 pub mod transform_popover;
 pub mod tray;
+pub mod wake_word;
@@ -1,9 +1,13 @@
+use crate::download_network;
+use crate::download_queue;
+use crate::events::{self, DownloadProgress};
 use crate::model_runtime::{self, InstallKind, InstallState};
+use crate::storage;
 use crate::transcriber::{self, TranscriptionBackend};
 use crate::vad;
+use crate::MutexExt;
 use crate::State;
 use std::sync::LazyLock;
-use tauri::Emitter;
 
 static VAD_INSTALL_LOCK: LazyLock<tokio::sync::Mutex<()>> =
     LazyLock::new(|| tokio::sync::Mutex::new(()));
@@ -20,6 +24,41 @@ pub fn get_model_runtime_catalog(
     state.app_state.model_runtime.catalog()
 }
 
+/// A shipped model with its download size and speed/accuracy ratings, so the
+/// picker can show trade-offs between sizes and quantized variants.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCatalogEntry {
+    pub model_name: &'static str,
+    pub label: &'static str,
+    pub size: &'static str,
+    pub backend: model_runtime::BackendKind,
+    #[serde(flatten)]
+    pub tradeoffs: model_runtime::ModelTradeoffs,
+    pub supported: bool,
+    pub installed: bool,
+}
+
+#[tauri::command]
+pub fn get_model_catalog(state: tauri::State<'_, State>) -> Vec<ModelCatalogEntry> {
+    model_runtime::MODEL_DEFINITIONS
+        .iter()
+        .map(|definition| ModelCatalogEntry {
+            model_name: definition.model_name,
+            label: definition.label,
+            size: definition.size,
+            backend: definition.backend,
+            tradeoffs: definition.tradeoffs,
+            supported: model_runtime::model_supported(definition),
+            installed: state
+                .app_state
+                .model_runtime
+                .snapshot(definition.model_name)
+                .is_ok_and(|snapshot| snapshot.install_state == InstallState::Installed),
+        })
+        .collect()
+}
+
 #[tauri::command]
 pub fn get_model_runtime_status(
     state: tauri::State<'_, State>,
@@ -28,6 +67,11 @@ pub fn get_model_runtime_status(
     state.app_state.model_runtime.snapshot(&model_name)
 }
 
+#[tauri::command]
+pub fn get_loaded_backends(state: tauri::State<'_, State>) -> Vec<model_runtime::LoadedBackend> {
+    state.app_state.model_runtime.loaded_backends()
+}
+
 #[tauri::command]
 pub fn check_specific_model_exists(state: tauri::State<'_, State>, model_name: String) -> bool {
     if !is_safe_model_identifier(&model_name) {
@@ -55,11 +99,26 @@ pub async fn download_model(
     if !model_runtime::model_supported(definition) {
         return Err("This model is not supported on the current platform".to_string());
     }
+    if model_runtime::custom_model_file(&model_name).is_some() {
+        return Err("Custom models are downloaded with download_custom_model".to_string());
+    }
+    if definition.install_kind == InstallKind::External {
+        return Err(
+            "Transcription plugins are added with register_transcription_plugin".to_string(),
+        );
+    }
+    if definition.install_kind == InstallKind::Cloud {
+        return Err("Cloud transcription is set up with set_cloud_api_key".to_string());
+    }
 
     // The entire existence-check/download/install transaction is single-flight
     // per model. Different models may still download concurrently.
     let install_lock = state.app_state.model_runtime.install_lock(&model_name)?;
     let _install_guard = install_lock.lock().await;
+    if !model_runtime::model_installed(&model_name) {
+        let models_dir = transcriber::WhisperBackend::new().models_dir()?;
+        storage::ensure_model_space(&app_handle, &model_name, &models_dir)?;
+    }
     if !state
         .app_state
         .model_runtime
@@ -80,11 +139,7 @@ pub async fn download_model(
             .map_err(|e| format!("Failed to create models directory: {}", e))?;
 
         if definition.install_kind == InstallKind::Coreml {
-            let _ = app_handle.emit("download-progress", serde_json::json!({
-                "received": 0,
-                "total": 0,
-                "phase": "installing"
-            }));
+            events::emit_download_progress(&app_handle, DownloadProgress::installing(0, 0));
             #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
             {
                 let model_name = model_name.clone();
@@ -92,11 +147,7 @@ pub async fn download_model(
                     .await
                     .map_err(|error| format!("Core ML setup task failed: {error}"))??;
             }
-            let _ = app_handle.emit("download-progress", serde_json::json!({
-                "received": 1,
-                "total": 1,
-                "phase": "installing"
-            }));
+            events::emit_download_progress(&app_handle, DownloadProgress::installing(1, 1));
         } else if definition.install_kind == InstallKind::Parakeet {
             download_parakeet_model(&app_handle, &model_name, &models_dir).await?;
         } else {
@@ -128,6 +179,415 @@ pub async fn download_model(
             &model_name,
             InstallState::Installed,
         ),
+        Err(error) => {
+            // A paused queue download keeps its partial file and isn't a failure.
+            let install_state = if error == download_queue::PAUSED {
+                InstallState::NotInstalled
+            } else {
+                InstallState::Invalid
+            };
+            let _ = state.app_state.model_runtime.set_install_state(
+                Some(&app_handle),
+                &model_name,
+                install_state,
+            );
+            Err(error)
+        }
+    }
+}
+
+/// whisper.cpp's `GGML_FILE_MAGIC` ("ggml"), stored little-endian at offset 0.
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+
+/// `(n_vocab, n_audio_state, n_text_layer)` from the GGML header of each catalog
+/// Whisper model, so an import can't register one size under another's name.
+/// Quantized variants share the dimensions of their full-precision model.
+fn whisper_header_dims(model_name: &str) -> Option<(i32, i32, i32)> {
+    let quantization = model_runtime::model_definition(model_name)
+        .ok()
+        .and_then(|definition| definition.tradeoffs.quantization);
+    let base_model = quantization
+        .and_then(|quantization| model_name.strip_suffix(quantization)?.strip_suffix('-'))
+        .unwrap_or(model_name);
+    match base_model {
+        "tiny.en" => Some((51864, 384, 4)),
+        "base.en" => Some((51864, 512, 6)),
+        "small.en" => Some((51864, 768, 12)),
+        "medium.en" => Some((51864, 1024, 24)),
+        "large-v3-turbo" => Some((51866, 1280, 4)),
+        "distil-medium.en" => Some((51864, 1024, 2)),
+        "distil-large-v3" => Some((51866, 1280, 2)),
+        _ => None,
+    }
+}
+
+/// Read the magic bytes and hyperparameters at the start of a GGML file.
+/// The header is the magic followed by eleven little-endian `i32`s: n_vocab,
+/// n_audio_ctx, n_audio_state, n_audio_head, n_audio_layer, n_text_ctx,
+/// n_text_state, n_text_head, n_text_layer, n_mels, ftype.
+fn read_whisper_header(header: &[u8]) -> Result<(i32, i32, i32), String> {
+    let word = |index: usize| {
+        header
+            .get(index * 4..index * 4 + 4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+    if word(0) != Some(GGML_MAGIC) {
+        return Err("This file is not a whisper.cpp GGML model".to_string());
+    }
+    let (Some(n_vocab), Some(n_audio_state), Some(n_text_layer)) = (word(1), word(3), word(9))
+    else {
+        return Err("This GGML file is truncated".to_string());
+    };
+    let dims = (n_vocab as i32, n_audio_state as i32, n_text_layer as i32);
+    if dims.0 <= 0 || dims.1 <= 0 || dims.2 <= 0 {
+        return Err("This GGML file has an invalid Whisper header".to_string());
+    }
+    Ok(dims)
+}
+
+/// Check that a GGML header matches the catalog model it is installed as.
+/// Custom models have no expected size, so only the header itself is checked.
+fn check_whisper_header(header: &[u8], model_name: &str) -> Result<(), String> {
+    let dims = read_whisper_header(header)?;
+    if model_runtime::custom_model_file(model_name).is_some() {
+        return Ok(());
+    }
+    let expected = whisper_header_dims(model_name)
+        .ok_or_else(|| format!("'{}' is not a Whisper model", model_name))?;
+    if dims != expected {
+        return Err(format!(
+            "This GGML file is a different Whisper model than '{}'",
+            model_name
+        ));
+    }
+    Ok(())
+}
+
+fn validate_whisper_file(path: &std::path::Path, model_name: &str) -> Result<(), String> {
+    use std::io::Read;
+    let mut header = Vec::with_capacity(48);
+    std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open model file: {}", e))?
+        .take(48)
+        .read_to_end(&mut header)
+        .map_err(|e| format!("Failed to read model file: {}", e))?;
+    check_whisper_header(&header, model_name)
+}
+
+/// Remove whatever sits at `path`: a symlink, a file, or a directory.
+fn remove_existing(path: &std::path::Path) -> std::io::Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => std::fs::remove_dir_all(path),
+        Ok(_) => std::fs::remove_file(path),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+fn copy_dir_all(source: &std::path::Path, dest: &std::path::Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dest)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn link_into_models_dir(source: &std::path::Path, dest: &std::path::Path) -> Result<(), String> {
+    std::os::unix::fs::symlink(source, dest).map_err(|e| format!("Failed to link model: {}", e))
+}
+
+#[cfg(not(unix))]
+fn link_into_models_dir(_source: &std::path::Path, _dest: &std::path::Path) -> Result<(), String> {
+    Err("Linking models is not supported on this platform; import a copy instead".to_string())
+}
+
+/// Validate `source` for `model_name` and publish it under the name the backend
+/// loads from. Copies go through a staging path and a rename, so a failed copy
+/// never leaves a model that looks installed.
+fn import_model_files(
+    source: &std::path::Path,
+    models_dir: &std::path::Path,
+    model_name: &str,
+    install_kind: InstallKind,
+    link: bool,
+) -> Result<(), String> {
+    let source = source
+        .canonicalize()
+        .map_err(|e| format!("Can't read '{}': {}", source.display(), e))?;
+    let (dest, staging) = match install_kind {
+        InstallKind::Whisper => {
+            if !source.is_file() {
+                return Err("Choose a ggml .bin file for Whisper models".to_string());
+            }
+            validate_whisper_file(&source, model_name)?;
+            let filename = format!("ggml-{}.bin", model_name);
+            (
+                models_dir.join(&filename),
+                models_dir.join(format!("{}.import", filename)),
+            )
+        }
+        InstallKind::Parakeet => {
+            if !source.is_dir() {
+                return Err("Choose the extracted model folder for Parakeet models".to_string());
+            }
+            if !transcriber::parakeet::bundle_dir_complete(model_name, &source) {
+                return Err(
+                    "This folder is missing Parakeet model files (encoder, decoder, joiner, tokens.txt)"
+                        .to_string(),
+                );
+            }
+            let (_, dir_name) = transcriber::parakeet::download_spec(model_name)
+                .ok_or_else(|| format!("Unknown Parakeet model '{}'", model_name))?;
+            (
+                models_dir.join(&dir_name),
+                models_dir.join(format!(".{}.importing", dir_name)),
+            )
+        }
+        InstallKind::Coreml => {
+            return Err("Core ML models are set up by FluidAudio and can't be imported".to_string())
+        }
+        InstallKind::External => {
+            return Err("Transcription plugins are registered, not imported".to_string())
+        }
+        InstallKind::Cloud => {
+            return Err("Cloud transcription has no model files to import".to_string())
+        }
+    };
+    if source == dest {
+        return Ok(());
+    }
+
+    // Anything left at the destination failed validation (the caller only
+    // imports models that aren't installed), so it is safe to replace.
+    remove_existing(&dest).map_err(|e| format!("Failed to remove incomplete model: {}", e))?;
+    if link {
+        return link_into_models_dir(&source, &dest);
+    }
+
+    let _ = remove_existing(&staging);
+    let copied = if source.is_dir() {
+        copy_dir_all(&source, &staging)
+    } else {
+        std::fs::copy(&source, &staging).map(|_| ())
+    };
+    let published = copied
+        .map_err(|e| format!("Failed to copy model: {}", e))
+        .and_then(|()| {
+            std::fs::rename(&staging, &dest)
+                .map_err(|e| format!("Failed to finalize import: {}", e))
+        });
+    if published.is_err() {
+        let _ = remove_existing(&staging);
+    }
+    published
+}
+
+/// Install a model from a file or folder the user already has, for machines
+/// that can't reach the download hosts. `link` symlinks instead of copying.
+#[tauri::command]
+pub async fn import_model(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    model_name: String,
+    path: String,
+    link: Option<bool>,
+) -> Result<(), String> {
+    let definition = model_runtime::model_definition(&model_name)?;
+    if !model_runtime::model_supported(definition) {
+        return Err("This model is not supported on the current platform".to_string());
+    }
+    if model_runtime::custom_model_file(&model_name).is_some() {
+        return Err("Custom models are added from a URL, not imported".to_string());
+    }
+    if definition.install_kind == InstallKind::External {
+        return Err(
+            "Transcription plugins are added with register_transcription_plugin".to_string(),
+        );
+    }
+    if definition.install_kind == InstallKind::Cloud {
+        return Err("Cloud transcription has no model files to import".to_string());
+    }
+    if definition.install_kind == InstallKind::Coreml {
+        return Err("Core ML models are set up by FluidAudio and can't be imported".to_string());
+    }
+
+    let install_lock = state.app_state.model_runtime.install_lock(&model_name)?;
+    let _install_guard = install_lock.lock().await;
+    if model_runtime::model_installed(&model_name) {
+        return Err(format!("'{}' is already installed", definition.label));
+    }
+    state.app_state.model_runtime.set_install_state(
+        Some(&app_handle),
+        &model_name,
+        InstallState::Installing,
+    )?;
+
+    let link = link.unwrap_or(false);
+    let install_result: Result<(), String> = async {
+        let models_dir = transcriber::WhisperBackend::new().models_dir()?;
+        tokio::fs::create_dir_all(&models_dir)
+            .await
+            .map_err(|e| format!("Failed to create models directory: {}", e))?;
+        emit_installing(&app_handle);
+        let source = std::path::PathBuf::from(&path);
+        let name = model_name.clone();
+        let install_kind = definition.install_kind;
+        tokio::task::spawn_blocking(move || {
+            import_model_files(&source, &models_dir, &name, install_kind, link)
+        })
+        .await
+        .map_err(|error| format!("Import task failed: {error}"))??;
+
+        state.app_state.model_runtime.set_install_state(
+            Some(&app_handle),
+            &model_name,
+            InstallState::Validating,
+        )?;
+        if !model_runtime::model_installed(&model_name) {
+            return Err("Model import completed but validation failed".to_string());
+        }
+        Ok(())
+    }
+    .await;
+
+    match install_result {
+        Ok(()) => {
+            tracing::info!(target: "system", model = %model_name, link, "model imported");
+            state.app_state.model_runtime.set_install_state(
+                Some(&app_handle),
+                &model_name,
+                InstallState::Installed,
+            )
+        }
+        Err(error) => {
+            let _ = state.app_state.model_runtime.set_install_state(
+                Some(&app_handle),
+                &model_name,
+                InstallState::Invalid,
+            );
+            Err(error)
+        }
+    }
+}
+
+/// Resolve a custom model source to `(url, file name)`. `source` is either an
+/// http(s) URL or a Hugging Face `owner/repo/path/to/file.bin`, which is read
+/// from the repo's `main` branch.
+fn custom_model_url(source: &str) -> Result<(String, String), String> {
+    let source = source.trim();
+    let url = if source.starts_with("https://") || source.starts_with("http://") {
+        let parsed =
+            reqwest::Url::parse(source).map_err(|_| "Custom model URL is not valid".to_string())?;
+        if parsed.host_str().is_none() {
+            return Err("Custom model URL is not valid".to_string());
+        }
+        parsed.to_string()
+    } else {
+        let parts: Vec<&str> = source.split('/').collect();
+        if parts.len() < 3 || parts.iter().any(|part| part.is_empty() || *part == "..") {
+            return Err(
+                "Enter a URL or a Hugging Face path like owner/repo/ggml-model.bin".to_string(),
+            );
+        }
+        format!(
+            "https://huggingface.co/{}/{}/resolve/main/{}",
+            parts[0],
+            parts[1],
+            parts[2..].join("/")
+        )
+    };
+    let file = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or_default()
+        .to_string();
+    let model_name = format!("{}{}", model_runtime::CUSTOM_MODEL_PREFIX, file);
+    if model_runtime::custom_model_file(&model_name).is_none() {
+        return Err("Custom models must be a GGML .bin file with a plain file name".to_string());
+    }
+    Ok((url, file))
+}
+
+/// Download a Whisper GGML file the catalog doesn't ship. The file is checked
+/// for a GGML header before it is published, and the returned `custom:<file>`
+/// name can be passed to `configure_dictation` like any catalog model.
+#[tauri::command]
+pub async fn download_custom_model(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    source: String,
+) -> Result<String, String> {
+    let (url, file) = custom_model_url(&source)?;
+    let model_name = format!("{}{}", model_runtime::CUSTOM_MODEL_PREFIX, file);
+
+    let install_lock = state.app_state.model_runtime.install_lock(&model_name)?;
+    let _install_guard = install_lock.lock().await;
+    if model_runtime::model_installed(&model_name) {
+        return Err(format!(
+            "A custom model named '{}' is already installed",
+            file
+        ));
+    }
+    state.app_state.model_runtime.set_install_state(
+        Some(&app_handle),
+        &model_name,
+        InstallState::Installing,
+    )?;
+
+    let install_result: Result<(), String> = async {
+        let custom_dir = transcriber::whisper::custom_models_dir()
+            .ok_or_else(|| "Could not find application data directory".to_string())?;
+        tokio::fs::create_dir_all(&custom_dir)
+            .await
+            .map_err(|e| format!("Failed to create models directory: {}", e))?;
+        let dest_path = custom_dir.join(&file);
+        let temp_path = custom_dir.join(format!("{}.tmp", file));
+
+        let received = stream_download(&app_handle, &url, &temp_path).await?;
+        state.app_state.model_runtime.set_install_state(
+            Some(&app_handle),
+            &model_name,
+            InstallState::Validating,
+        )?;
+        let check_path = temp_path.clone();
+        let name = model_name.clone();
+        let validated =
+            tokio::task::spawn_blocking(move || validate_whisper_file(&check_path, &name))
+                .await
+                .map_err(|error| format!("Validation task failed: {error}"))
+                .and_then(|result| result);
+        if let Err(error) = validated {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(error);
+        }
+        tokio::fs::rename(&temp_path, &dest_path)
+            .await
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp_path);
+                format!("Failed to finalize download: {}", e)
+            })?;
+        tracing::info!(target: "system", "Custom model downloaded: {} ({} bytes)", file, received);
+        Ok(())
+    }
+    .await;
+
+    match install_result {
+        Ok(()) => {
+            state.app_state.model_runtime.set_install_state(
+                Some(&app_handle),
+                &model_name,
+                InstallState::Installed,
+            )?;
+            Ok(model_name)
+        }
         Err(error) => {
             let _ = state.app_state.model_runtime.set_install_state(
                 Some(&app_handle),
@@ -139,6 +599,123 @@ pub async fn download_model(
     }
 }
 
+/// Registered transcription plugins, for Settings. Each one is also in the
+/// runtime catalog as `external:<id>`.
+#[tauri::command]
+pub fn list_transcription_plugins() -> Vec<transcriber::external::PluginManifest> {
+    transcriber::external::registered_plugins()
+}
+
+/// Stop `model_name`'s plugin if it is running, so the next take starts the
+/// registered command. Nothing is swapped mid-dictation.
+fn release_plugin(
+    app_handle: &tauri::AppHandle,
+    state: &tauri::State<'_, State>,
+    model_name: &str,
+) -> Result<(), String> {
+    let status = state.app_state.dictation.lock_or_recover().status;
+    if !status.is_settled() {
+        return Err("Transcription plugins can't be changed while dictating".to_string());
+    }
+    let resident = state
+        .app_state
+        .model_runtime
+        .loaded_backends()
+        .iter()
+        .any(|loaded| loaded.model_name == model_name);
+    if resident {
+        state
+            .app_state
+            .model_runtime
+            .unload(Some(app_handle), model_runtime::UnloadReason::PluginChanged)?;
+    }
+    Ok(())
+}
+
+/// Register the plugin whose `plugin.json` is at `manifest_path` once it has
+/// started and agreed to the protocol. The returned `external:<id>` name can
+/// be passed to `configure_dictation` like any catalog model.
+#[tauri::command]
+pub async fn register_transcription_plugin(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    manifest_path: String,
+) -> Result<String, String> {
+    let path = std::path::PathBuf::from(manifest_path);
+    let id = transcriber::external::read_manifest(&path)?.id;
+    let model_name = format!("{}{}", model_runtime::EXTERNAL_MODEL_PREFIX, id);
+    release_plugin(&app_handle, &state, &model_name)?;
+    tokio::task::spawn_blocking(move || transcriber::external::register(&path))
+        .await
+        .map_err(|error| format!("Plugin registration task failed: {error}"))??;
+    state.app_state.model_runtime.set_install_state(
+        Some(&app_handle),
+        &model_name,
+        InstallState::Installed,
+    )?;
+    Ok(model_name)
+}
+
+#[tauri::command]
+pub fn unregister_transcription_plugin(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    id: String,
+) -> Result<(), String> {
+    let model_name = format!("{}{}", model_runtime::EXTERNAL_MODEL_PREFIX, id);
+    model_runtime::model_definition(&model_name)?;
+    release_plugin(&app_handle, &state, &model_name)?;
+    transcriber::external::unregister(&id)?;
+    state.app_state.model_runtime.set_install_state(
+        Some(&app_handle),
+        &model_name,
+        InstallState::NotInstalled,
+    )
+}
+
+/// Apply the `cloudTranscription` setting. Rejects an invalid endpoint or
+/// model and keeps the previous settings.
+#[tauri::command]
+pub fn set_cloud_transcription(
+    settings: transcriber::cloud::CloudTranscriptionSettings,
+) -> Result<(), String> {
+    transcriber::cloud::configure(settings)
+}
+
+/// Whether a cloud API key is stored. The key itself never leaves the
+/// Keychain.
+#[tauri::command]
+pub fn get_cloud_api_key_stored() -> bool {
+    transcriber::cloud::api_key_stored()
+}
+
+#[tauri::command]
+pub fn set_cloud_api_key(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    key: String,
+) -> Result<(), String> {
+    transcriber::cloud::set_api_key(&key)?;
+    state.app_state.model_runtime.set_install_state(
+        Some(&app_handle),
+        transcriber::cloud::CLOUD_MODEL_NAME,
+        InstallState::Installed,
+    )
+}
+
+#[tauri::command]
+pub fn clear_cloud_api_key(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+) -> Result<(), String> {
+    transcriber::cloud::clear_api_key()?;
+    state.app_state.model_runtime.set_install_state(
+        Some(&app_handle),
+        transcriber::cloud::CLOUD_MODEL_NAME,
+        InstallState::NotInstalled,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +869,178 @@ mod tests {
         fs::remove_dir_all(root).unwrap();
     }
 
+    fn ggml_header(n_vocab: i32, n_audio_state: i32, n_text_layer: i32) -> Vec<u8> {
+        let mut header = GGML_MAGIC.to_le_bytes().to_vec();
+        for value in [
+            n_vocab,
+            1500,
+            n_audio_state,
+            6,
+            4,
+            448,
+            n_audio_state,
+            6,
+            n_text_layer,
+            80,
+            1,
+        ] {
+            header.extend_from_slice(&value.to_le_bytes());
+        }
+        header
+    }
+
+    #[test]
+    fn whisper_import_checks_magic_and_model_size() {
+        assert!(check_whisper_header(&ggml_header(51864, 512, 6), "base.en").is_ok());
+        assert!(check_whisper_header(&ggml_header(51866, 1280, 4), "large-v3-turbo").is_ok());
+        assert!(check_whisper_header(&ggml_header(51864, 384, 4), "tiny.en-q5_1").is_ok());
+        // distil-large-v3 keeps large-v3's encoder but only two decoder layers.
+        assert!(check_whisper_header(&ggml_header(51866, 1280, 2), "distil-large-v3").is_ok());
+        assert!(check_whisper_header(&ggml_header(51866, 1280, 4), "distil-large-v3").is_err());
+        assert!(check_whisper_header(&ggml_header(51864, 512, 6), "small.en-q8_0").is_err());
+        // tiny.en renamed to base.en must not register as base.en.
+        assert!(check_whisper_header(&ggml_header(51864, 384, 4), "base.en").is_err());
+        assert!(check_whisper_header(b"GGUF\x03\x00\x00\x00", "base.en").is_err());
+        assert!(check_whisper_header(&GGML_MAGIC.to_le_bytes(), "base.en").is_err());
+    }
+
+    #[test]
+    fn custom_model_sources_resolve_to_a_safe_file_name() {
+        assert_eq!(
+            custom_model_url("distil-whisper/distil-large-v3/ggml-distil-large-v3.bin").unwrap(),
+            (
+                "https://huggingface.co/distil-whisper/distil-large-v3/resolve/main/ggml-distil-large-v3.bin"
+                    .to_string(),
+                "ggml-distil-large-v3.bin".to_string()
+            )
+        );
+        let (url, file) =
+            custom_model_url(" https://example.com/models/ggml-custom.bin?download=1 ").unwrap();
+        assert_eq!(url, "https://example.com/models/ggml-custom.bin?download=1");
+        assert_eq!(file, "ggml-custom.bin");
+
+        assert!(custom_model_url("owner/ggml-model.bin").is_err());
+        assert!(custom_model_url("owner/repo/../ggml-model.bin").is_err());
+        assert!(custom_model_url("owner/repo/model.onnx").is_err());
+        assert!(custom_model_url("ftp://example.com/ggml-model.bin").is_err());
+        assert!(custom_model_url("https://example.com/").is_err());
+    }
+
+    #[test]
+    fn custom_models_only_require_a_ggml_header() {
+        assert!(
+            check_whisper_header(&ggml_header(51865, 1280, 2), "custom:ggml-distil.bin").is_ok()
+        );
+        assert!(check_whisper_header(b"GGUF\x03\x00\x00\x00", "custom:ggml-distil.bin").is_err());
+        assert!(check_whisper_header(&ggml_header(0, 0, 0), "custom:ggml-distil.bin").is_err());
+    }
+
+    #[test]
+    fn whisper_import_copies_under_the_catalog_filename() {
+        let root = test_dir("whisper-import");
+        let models_dir = root.join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+        let source = root.join("downloaded-elsewhere.bin");
+        let mut bytes = ggml_header(51864, 384, 4);
+        bytes.extend_from_slice(b"weights");
+        fs::write(&source, &bytes).unwrap();
+
+        import_model_files(&source, &models_dir, "tiny.en", InstallKind::Whisper, false).unwrap();
+
+        assert_eq!(
+            fs::read(models_dir.join("ggml-tiny.en.bin")).unwrap(),
+            bytes
+        );
+        assert!(!models_dir.join("ggml-tiny.en.bin.import").exists());
+        assert!(source.exists(), "import must not move the user's file");
+        let error = import_model_files(
+            &source,
+            &models_dir,
+            "small.en",
+            InstallKind::Whisper,
+            false,
+        )
+        .unwrap_err();
+        assert!(error.contains("different Whisper model"), "{error}");
+        assert!(!models_dir.join("ggml-small.en.bin").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn parakeet_import_requires_a_complete_bundle() {
+        let root = test_dir("parakeet-import");
+        let models_dir = root.join("models");
+        let bundle = root.join("my-parakeet");
+        fs::create_dir_all(&models_dir).unwrap();
+        fs::create_dir_all(&bundle).unwrap();
+        let model_name = "parakeet-tdt-0.6b-v2-fp16";
+        for file in ["encoder.fp16.onnx", "decoder.fp16.onnx", "tokens.txt"] {
+            fs::write(bundle.join(file), b"data").unwrap();
+        }
+
+        assert!(import_model_files(
+            &bundle,
+            &models_dir,
+            model_name,
+            InstallKind::Parakeet,
+            false
+        )
+        .is_err());
+        fs::write(bundle.join("joiner.fp16.onnx"), b"data").unwrap();
+        #[cfg(unix)]
+        {
+            import_model_files(
+                &bundle,
+                &models_dir,
+                model_name,
+                InstallKind::Parakeet,
+                true,
+            )
+            .unwrap();
+            let (_, dir_name) = transcriber::parakeet::download_spec(model_name).unwrap();
+            assert!(fs::symlink_metadata(models_dir.join(&dir_name))
+                .unwrap()
+                .file_type()
+                .is_symlink());
+        }
+        #[cfg(not(unix))]
+        import_model_files(
+            &bundle,
+            &models_dir,
+            model_name,
+            InstallKind::Parakeet,
+            false,
+        )
+        .unwrap();
+        assert!(transcriber::parakeet::specific_model_exists_in(
+            model_name,
+            &models_dir
+        ));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn resume_validators_prefer_a_strong_etag() {
+        use reqwest::header::{HeaderMap, HeaderValue, ETAG, LAST_MODIFIED};
+        let mut headers = HeaderMap::new();
+        assert_eq!(range_validator(&headers), None);
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2026 07:28:00 GMT"),
+        );
+        headers.insert(ETAG, HeaderValue::from_static("W/\"weak\""));
+        assert_eq!(
+            range_validator(&headers).as_deref(),
+            Some("Wed, 21 Oct 2026 07:28:00 GMT")
+        );
+        headers.insert(ETAG, HeaderValue::from_static("\"abc123\""));
+        assert_eq!(range_validator(&headers).as_deref(), Some("\"abc123\""));
+        assert_eq!(
+            validator_path(std::path::Path::new("/models/ggml-base.en.bin.tmp")),
+            std::path::Path::new("/models/ggml-base.en.bin.tmp.validator")
+        );
+    }
+
     #[test]
     fn model_install_locks_are_keyed_and_reused() {
         let manager = model_runtime::ModelRuntimeManager::default();
@@ -304,21 +1053,26 @@ mod tests {
     }
 }
 
-/// Download a single whisper ggml .bin file from Hugging Face.
+/// Download a single whisper ggml .bin file from Hugging Face, saved under the
+/// catalog filename whatever the upstream name.
 async fn download_whisper_model(
     app_handle: &tauri::AppHandle,
     model_name: &str,
     models_dir: &std::path::Path,
 ) -> Result<(), String> {
     let filename = format!("ggml-{}.bin", model_name);
-    let url = format!(
-        "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{}",
-        filename
-    );
+    let url = transcriber::whisper::download_url(model_name);
     let dest_path = models_dir.join(&filename);
     let temp_path = models_dir.join(format!("{}.tmp", filename));
 
     let received = stream_download(app_handle, &url, &temp_path).await?;
+    if !storage::matches_catalog_size(model_name, received) {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        return Err(format!(
+            "Downloaded model is {} bytes, far from its catalog size; try again",
+            received
+        ));
+    }
 
     tokio::fs::rename(&temp_path, &dest_path)
         .await
@@ -451,11 +1205,7 @@ async fn extract_parakeet_archive_on_worker(
 }
 
 fn emit_installing(app_handle: &tauri::AppHandle) {
-    let _ = app_handle.emit("download-progress", serde_json::json!({
-        "received": 0,
-        "total": 0,
-        "phase": "installing"
-    }));
+    events::emit_download_progress(app_handle, DownloadProgress::installing(0, 0));
 }
 
 fn extract_parakeet_archive(
@@ -568,35 +1318,107 @@ pub(crate) async fn ensure_vad_model(app_handle: &tauri::AppHandle) -> Result<()
     Ok(())
 }
 
+/// Where `stream_download` keeps the validator of a partial queue download.
+fn validator_path(dest: &std::path::Path) -> std::path::PathBuf {
+    let mut path = dest.as_os_str().to_owned();
+    path.push(".validator");
+    path.into()
+}
+
+/// What `If-Range` accepts from `headers`: a strong ETag, or else the
+/// Last-Modified date.
+fn range_validator(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    header(reqwest::header::ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(reqwest::header::LAST_MODIFIED))
+        .map(str::to_string)
+}
+
 /// Stream a file download with progress events. Returns total bytes received.
+/// Only downloads the queue runs are resumable: their partial file at `dest`
+/// is kept when interrupted, and resumed with a range request whose `If-Range`
+/// carries the ETag or Last-Modified the first response had, so a file that
+/// changed upstream is sent whole instead of stitched. Any other download
+/// starts over and leaves nothing behind when it fails.
 pub(crate) async fn stream_download(
     app_handle: &tauri::AppHandle,
     url: &str,
     dest: &std::path::Path,
 ) -> Result<u64, String> {
-    let client = reqwest::Client::builder()
+    let client = download_network::client_builder()?
         .connect_timeout(std::time::Duration::from_secs(30))
         .timeout(std::time::Duration::from_secs(15 * 60))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| format!("Download request failed: {}", e))?;
+    let url = download_network::resolve_url(url);
+    let resumable = download_queue::in_queue();
+    let validator_path = validator_path(dest);
+    let mut validator = if resumable {
+        tokio::fs::read_to_string(&validator_path).await.ok()
+    } else {
+        None
+    };
+    let mut resume_from = match validator {
+        Some(_) => tokio::fs::metadata(dest)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0),
+        None => 0,
+    };
+    let response = loop {
+        let mut request = client.get(&url);
+        if let (true, Some(validator)) = (resume_from > 0, &validator) {
+            request = request
+                .header(reqwest::header::RANGE, format!("bytes={}-", resume_from))
+                .header(reqwest::header::IF_RANGE, validator.as_str());
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Download request failed: {}", e))?;
+        if resume_from > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            // The partial file isn't a prefix of what the server has now.
+            let _ = tokio::fs::remove_file(dest).await;
+            resume_from = 0;
+            validator = None;
+            continue;
+        }
+        break response;
+    };
 
     if !response.status().is_success() {
         return Err(format!("Download failed with status: {}", response.status()));
     }
 
-    let total = response.content_length().unwrap_or(0);
-    let mut received: u64 = 0;
+    // A server whose file no longer matches the validator answers 200 with all
+    // of it, which overwrites the partial file below.
+    let resumed = resume_from > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    if resumable && !resumed {
+        match range_validator(response.headers()) {
+            Some(validator) => {
+                let _ = tokio::fs::write(&validator_path, validator).await;
+            }
+            None => {
+                let _ = tokio::fs::remove_file(&validator_path).await;
+            }
+        }
+    }
+    let start = if resumed { resume_from } else { 0 };
+    let total = response.content_length().map_or(0, |length| start + length);
+    let mut received: u64 = start;
+    if resumed {
+        tracing::info!(target: "system", resume_from, "resuming partial download");
+    }
 
     use tokio::io::AsyncWriteExt;
-    let mut file = tokio::fs::File::create(dest)
-        .await
-        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+    let mut file = if resumed {
+        tokio::fs::OpenOptions::new().append(true).open(dest).await
+    } else {
+        tokio::fs::File::create(dest).await
+    }
+    .map_err(|e| format!("Failed to create temp file: {}", e))?;
 
     let mut stream = response.bytes_stream();
     use futures_util::StreamExt;
@@ -607,30 +1429,37 @@ pub(crate) async fn stream_download(
                 .await
                 .map_err(|e| format!("Failed to write to file: {}", e))?;
             received += chunk.len() as u64;
-            let _ = app_handle.emit("download-progress", serde_json::json!({
-                "received": received,
-                "total": total,
-                "phase": "downloading"
-            }));
+            events::emit_download_progress(
+                app_handle,
+                DownloadProgress::downloading(received, total),
+            );
+            if download_queue::pause_requested() {
+                return Err(download_queue::PAUSED.to_string());
+            }
         }
-        file.flush()
-            .await
-            .map_err(|e| format!("Failed to flush file: {}", e))?;
         Ok::<(), String>(())
     }.await;
-
-    if let Err(e) = stream_result {
-        let _ = tokio::fs::remove_file(dest).await;
+    let flushed = file
+        .flush()
+        .await
+        .map_err(|e| format!("Failed to flush file: {}", e));
+    if let Err(e) = stream_result.and(flushed) {
+        if !resumable {
+            let _ = tokio::fs::remove_file(dest).await;
+        }
         return Err(e);
     }
 
     if total > 0 && received != total {
-        let _ = tokio::fs::remove_file(dest).await;
+        if !resumable || received > total {
+            let _ = tokio::fs::remove_file(dest).await;
+        }
         return Err(format!(
             "Download ended early: received {} of {} bytes",
             received, total
         ));
     }
 
+    let _ = tokio::fs::remove_file(&validator_path).await;
     Ok(received)
 }
@@ -1,3 +1,4 @@
+use crate::state::DictationStatus;
 use crate::{MutexExt, State};
 use tauri::Emitter;
 use tauri::Manager;
@@ -15,6 +16,9 @@ pub struct OverlayGeometry {
     pub pill_margin_active: f64,
     pub dropdown_h: f64,
     pub wing_w: f64,
+    /// Floating pill (fully rounded, free-standing) rather than a notch island
+    /// (flat top against the screen edge).
+    pub floating: bool,
 }
 
 /// The window frame `set_overlay_expanded` actually applied. Returned so the
@@ -46,6 +50,14 @@ const WING: f64 = 36.0;
 const DROPDOWN_H: f64 = 44.0;
 const FALLBACK_NOTCH_W: f64 = 80.0;
 const FALLBACK_NOTCH_H: f64 = 37.0;
+// The floating pill has no notch to straddle, so its center gap only needs to
+// leave room for the dropdown's three quick-setting buttons.
+const PILL_GAP_W: f64 = 64.0;
+const PILL_H: f64 = 32.0;
+/// Default distance of a floating pill from its display's top edge: clears
+/// the menu bar with a little air.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const PILL_DEFAULT_TOP: f64 = 36.0;
 
 fn geometry_for(notch: Option<(f64, f64)>) -> OverlayGeometry {
     let (notch_w, notch_h) = notch.unwrap_or((FALLBACK_NOTCH_W, FALLBACK_NOTCH_H));
@@ -61,6 +73,251 @@ fn geometry_for(notch: Option<(f64, f64)>) -> OverlayGeometry {
         pill_margin_active: 0.0,
         dropdown_h: DROPDOWN_H,
         wing_w: WING,
+        floating: false,
+    }
+}
+
+fn pill_geometry() -> OverlayGeometry {
+    let window_w = PILL_GAP_W + 2.0 * WING;
+    OverlayGeometry {
+        window_w,
+        collapsed_h: PILL_H,
+        expanded_h: PILL_H + DROPDOWN_H,
+        pill_idle_w: window_w,
+        pill_active_w: window_w,
+        pill_margin_idle: 0.0,
+        pill_margin_active: 0.0,
+        dropdown_h: DROPDOWN_H,
+        wing_w: WING,
+        floating: true,
+    }
+}
+
+/// How the overlay is drawn on a display.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum OverlayStyle {
+    /// Notch island on a notched display, floating pill elsewhere.
+    #[default]
+    Auto,
+    Notch,
+    Pill,
+}
+
+impl OverlayStyle {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "notch" => Some(Self::Notch),
+            "pill" => Some(Self::Pill),
+            _ => None,
+        }
+    }
+}
+
+/// Where a dragged floating pill sits, as fractions (0–1) of the room its
+/// display leaves around it. Relative, so it survives resolution changes and
+/// applies to every display in "all displays" mode.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PillPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// What the overlay does on a display where the frontmost app is fullscreen
+/// and the menu bar is hidden.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum OverlayFullscreenMode {
+    /// Keep showing it as usual.
+    Show,
+    /// Hide it until the app leaves fullscreen.
+    #[default]
+    Hide,
+    /// Draw it as a floating pill until the app leaves fullscreen.
+    Pill,
+}
+
+impl OverlayFullscreenMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "show" => Some(Self::Show),
+            "hide" => Some(Self::Hide),
+            "pill" => Some(Self::Pill),
+            _ => None,
+        }
+    }
+}
+
+/// The style `display` is drawn in: the configured one, or a floating pill
+/// over a fullscreen app when `fullscreen` asks for it.
+fn style_on_display(
+    display: &DisplayInfo,
+    style: OverlayStyle,
+    fullscreen: OverlayFullscreenMode,
+) -> OverlayStyle {
+    if display.fullscreen && fullscreen == OverlayFullscreenMode::Pill {
+        OverlayStyle::Pill
+    } else {
+        style
+    }
+}
+
+/// The overlay's geometry on `display` for the current style settings.
+fn geometry_on_display(state: &State, display: &DisplayInfo) -> OverlayGeometry {
+    let style = style_on_display(
+        display,
+        *state.overlay_style.lock_or_recover(),
+        *state.overlay_fullscreen_mode.lock_or_recover(),
+    );
+    geometry_for_display(display, style)
+}
+
+fn geometry_for_display(display: &DisplayInfo, style: OverlayStyle) -> OverlayGeometry {
+    match (style, display.notch) {
+        (OverlayStyle::Pill, _) | (OverlayStyle::Auto, None) => pill_geometry(),
+        (OverlayStyle::Notch, notch) | (OverlayStyle::Auto, notch) => geometry_for(notch),
+    }
+}
+
+/// Room a pill can move in on `display`. Uses the expanded height so the
+/// dropdown always stays on screen.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn pill_free_space(display: &DisplayInfo, g: &OverlayGeometry) -> (f64, f64) {
+    (
+        (display.width - g.window_w).max(0.0),
+        (display.height - g.expanded_h).max(0.0),
+    )
+}
+
+/// Top-left origin of a floating pill: the saved position, or centered just
+/// below the menu bar.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn pill_origin(
+    display: &DisplayInfo,
+    g: &OverlayGeometry,
+    position: Option<PillPosition>,
+) -> (f64, f64) {
+    let (free_w, free_h) = pill_free_space(display, g);
+    match position {
+        Some(p) => (
+            display.x + p.x.clamp(0.0, 1.0) * free_w,
+            display.y + p.y.clamp(0.0, 1.0) * free_h,
+        ),
+        None => (
+            display.x + free_w / 2.0,
+            display.y + PILL_DEFAULT_TOP.min(free_h),
+        ),
+    }
+}
+
+/// Inverse of `pill_origin`: the saved position for a pill dragged to `origin`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn pill_position_for(
+    display: &DisplayInfo,
+    g: &OverlayGeometry,
+    origin: (f64, f64),
+) -> PillPosition {
+    let (free_w, free_h) = pill_free_space(display, g);
+    let fraction = |offset: f64, free: f64| {
+        if free > 0.0 {
+            (offset / free).clamp(0.0, 1.0)
+        } else {
+            0.5
+        }
+    };
+    PillPosition {
+        x: fraction(origin.0 - display.x, free_w),
+        y: fraction(origin.1 - display.y, free_h),
+    }
+}
+
+/// Which displays get a notch overlay when more than one is attached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum OverlayDisplayMode {
+    /// The built-in (notched) display, or the primary display on a desktop Mac.
+    #[default]
+    BuiltIn,
+    /// Whichever display has keyboard focus; follows it as focus moves.
+    Active,
+    /// One overlay per display.
+    All,
+}
+
+impl OverlayDisplayMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "built-in" => Some(Self::BuiltIn),
+            "active" => Some(Self::Active),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+/// One attached display, in top-left-origin logical points with the primary
+/// display's top-left corner at (0, 0).
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DisplayInfo {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+    pub(crate) width: f64,
+    pub(crate) height: f64,
+    /// (notch_width, menu_bar_height) when this display has a notch.
+    pub(crate) notch: Option<(f64, f64)>,
+    pub(crate) built_in: bool,
+    /// Holds keyboard focus (`NSScreen.mainScreen`).
+    pub(crate) active: bool,
+    /// The frontmost app is fullscreen here, so the menu bar is hidden.
+    pub(crate) fullscreen: bool,
+}
+
+/// Window label for the overlay hosted on the `index`-th placement target.
+/// Index 0 is the `overlay` window from tauri.conf.json; the rest are mirrors
+/// created on demand for "all displays".
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn overlay_label(index: usize) -> String {
+    if index == 0 {
+        "overlay".to_string()
+    } else {
+        format!("overlay-{index}")
+    }
+}
+
+fn is_overlay_label(label: &str) -> bool {
+    label == "overlay" || label.starts_with("overlay-")
+}
+
+/// The display the overlay calls home: built-in first, then any notched
+/// display, then the primary (first) display.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn home_display(displays: &[DisplayInfo]) -> Option<&DisplayInfo> {
+    displays
+        .iter()
+        .find(|d| d.built_in)
+        .or_else(|| displays.iter().find(|d| d.notch.is_some()))
+        .or_else(|| displays.first())
+}
+
+/// Displays that should host an overlay for `mode`, in window order. The home
+/// display always comes first so the configured `overlay` window stays put
+/// when mirrors come and go.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn overlay_targets(
+    displays: &[DisplayInfo],
+    mode: OverlayDisplayMode,
+) -> Vec<DisplayInfo> {
+    let Some(home) = home_display(displays).copied() else {
+        return Vec::new();
+    };
+    match mode {
+        OverlayDisplayMode::BuiltIn => vec![home],
+        OverlayDisplayMode::Active => {
+            vec![displays.iter().find(|d| d.active).copied().unwrap_or(home)]
+        }
+        OverlayDisplayMode::All => std::iter::once(home)
+            .chain(displays.iter().copied().filter(|d| *d != home))
+            .collect(),
     }
 }
 
@@ -105,45 +362,220 @@ pub(crate) fn detect_notch_info() -> Option<(f64, f64)> {
     None
 }
 
-/// Subscribe to macOS display configuration changes (plug/unplug monitor, lid open/close).
-/// Re-detects notch info, repositions the overlay, and notifies the frontend.
+/// Enumerate every attached display with its notch (if any), whether it is the
+/// built-in panel, and whether it holds keyboard focus. Must run on the main
+/// thread (NSScreen).
 #[cfg(target_os = "macos")]
-pub(crate) fn register_screen_change_observer(app_handle: tauri::AppHandle) {
-    use objc2_foundation::{
-        NSNotification, NSNotificationCenter, NSNotificationName, NSOperationQueue,
+pub(crate) fn detect_displays() -> Vec<DisplayInfo> {
+    use core_graphics::display::CGDisplay;
+    use objc2_app_kit::NSScreen;
+    use objc2_foundation::MainThreadMarker;
+
+    // SAFETY: only called from setup() and from NSNotificationCenter blocks
+    // registered on the main queue (see `register_screen_change_observer`).
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+    let screens = NSScreen::screens(mtm);
+    // Cocoa frames are bottom-left-origin relative to the primary screen; CG
+    // bounds and window positions are top-left-origin. Flip with its height.
+    let Some(primary_h) = screens.iter().next().map(|s| s.frame().size.height) else {
+        return Vec::new();
     };
+    let main_frame = NSScreen::mainScreen(mtm).map(|s| s.frame());
+    // A fullscreen app fills the display that holds keyboard focus. An app
+    // trigger can keep the overlay over its app anyway.
+    let frontmost_fullscreen =
+        crate::frontmost::frontmost_is_fullscreen() && !crate::app_triggers::overlay_pinned();
+    let built_in_origins: Vec<(f64, f64)> = CGDisplay::active_displays()
+        .unwrap_or_default()
+        .into_iter()
+        .map(CGDisplay::new)
+        .filter(|d| d.is_builtin())
+        .map(|d| (d.bounds().origin.x, d.bounds().origin.y))
+        .collect();
 
-    let notification_name =
-        NSNotificationName::from_str("NSApplicationDidChangeScreenParametersNotification");
-
-    let block = block2::RcBlock::new(move |_notification: std::ptr::NonNull<NSNotification>| {
-        tracing::info!(target: "system", "screen parameters changed — re-detecting notch info");
-        let notch = detect_notch_info();
-        let handle = &app_handle;
-        // Update cached notch info
-        {
-            let state = handle.state::<State>();
-            *state.notch_info.lock_or_recover() = notch;
+    screens
+        .iter()
+        .map(|screen| {
+            let frame = screen.frame();
+            let x = frame.origin.x;
+            let y = primary_h - (frame.origin.y + frame.size.height);
+            let insets = screen.safeAreaInsets();
+            let notch = (insets.top > 0.0).then(|| {
+                let left_w = screen.auxiliaryTopLeftArea().size.width;
+                let right_w = screen.auxiliaryTopRightArea().size.width;
+                (frame.size.width - left_w - right_w, insets.top)
+            });
+            let active = main_frame.is_some_and(|m| m == frame);
+            DisplayInfo {
+                x,
+                y,
+                width: frame.size.width,
+                height: frame.size.height,
+                notch,
+                built_in: built_in_origins
+                    .iter()
+                    .any(|&(bx, by)| (bx - x).abs() < 1.0 && (by - y).abs() < 1.0),
+                active,
+                fullscreen: active && frontmost_fullscreen,
+            }
+        })
+        .collect()
+}
+
+/// Re-detect displays and place one overlay per target for the configured
+/// `OverlayDisplayMode`: reposition/resize each window, create or close
+/// "all displays" mirrors, and send each window its own geometry. An overlay
+/// over a fullscreen app is hidden or turned into a pill per
+/// `OverlayFullscreenMode`, and comes back when the app leaves fullscreen.
+/// With `force == false` nothing happens unless the placement actually
+/// changed, so focus-change notifications stay cheap. Must run on the main
+/// thread.
+#[cfg(target_os = "macos")]
+pub(crate) fn refresh_overlay_placement(app: &tauri::AppHandle, force: bool) {
+    let displays = detect_displays();
+    let state = app.state::<State>();
+    let mode = *state.overlay_display_mode.lock_or_recover();
+    let targets = overlay_targets(&displays, mode);
+    let placements: std::collections::HashMap<String, DisplayInfo> = targets
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (overlay_label(i), *d))
+        .collect();
+    if !force && *state.overlay_placements.lock_or_recover() == placements {
+        return;
+    }
+    tracing::info!(target: "system", "refresh_overlay_placement: mode={:?}, displays={}, overlays={}", mode, displays.len(), targets.len());
+    *state.notch_info.lock_or_recover() = targets.first().and_then(|d| d.notch);
+    *state.overlay_placements.lock_or_recover() = placements.clone();
+
+    // Mirrors follow the configured overlay's visibility. One hidden only for
+    // a fullscreen app still counts as shown.
+    let visible = app
+        .get_webview_window("overlay")
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false)
+        || *state.overlay_hidden_for_fullscreen.lock_or_recover();
+    let fullscreen_mode = *state.overlay_fullscreen_mode.lock_or_recover();
+    let mut hidden_for_fullscreen = false;
+    for (label, window) in app.webview_windows() {
+        if label != "overlay" && is_overlay_label(&label) && !placements.contains_key(&label) {
+            let _ = window.close();
         }
-        // Reposition overlay window
-        if let Some(overlay) = handle.get_webview_window("overlay") {
-            position_overlay_default(&overlay, notch);
+    }
+    for (index, display) in targets.iter().enumerate() {
+        let label = overlay_label(index);
+        let window = match app.get_webview_window(&label) {
+            Some(window) => window,
+            None => match create_overlay_mirror(app, &label) {
+                Ok(window) => window,
+                Err(e) => {
+                    tracing::warn!(target: "system", "refresh_overlay_placement: creating {} failed: {}", label, e);
+                    continue;
+                }
+            },
+        };
+        position_overlay_default(&window);
+        if display.fullscreen && fullscreen_mode == OverlayFullscreenMode::Hide {
+            hidden_for_fullscreen |= visible;
+            let _ = window.hide();
+        } else if visible && (index > 0 || !window.is_visible().unwrap_or(true)) {
+            let _ = window.show();
+            let _ = window.set_ignore_cursor_events(false);
         }
-        // Notify frontend
-        let _ = handle.emit("overlay-geometry-changed", geometry_for(notch));
-    });
-
-    unsafe {
-        let center = NSNotificationCenter::defaultCenter();
-        let observer = center.addObserverForName_object_queue_usingBlock(
-            Some(&notification_name),
-            None,
-            Some(&NSOperationQueue::mainQueue()),
-            &block,
+        let _ = app.emit_to(
+            label.as_str(),
+            "overlay-geometry-changed",
+            geometry_on_display(&state, display),
         );
-        // App-lifetime observer — intentionally leak to avoid premature deallocation
-        std::mem::forget(observer);
     }
+    *state.overlay_hidden_for_fullscreen.lock_or_recover() = hidden_for_fullscreen;
+}
+
+/// Build an "all displays" overlay mirroring the configured `overlay` window.
+/// It loads the same page, so it renders the same live state from the same
+/// broadcast events; only its geometry is per-window.
+#[cfg(target_os = "macos")]
+fn create_overlay_mirror(
+    app: &tauri::AppHandle,
+    label: &str,
+) -> tauri::Result<tauri::WebviewWindow> {
+    tauri::WebviewWindowBuilder::new(app, label, tauri::WebviewUrl::App("overlay.html".into()))
+        .title("")
+        .decorations(false)
+        .always_on_top(true)
+        .transparent(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .resizable(false)
+        .focusable(false)
+        .visible_on_all_workspaces(true)
+        .build()
+}
+
+/// Show every overlay window and re-enable its mouse events.
+#[cfg(target_os = "macos")]
+fn show_overlay_windows(app: &tauri::AppHandle) -> Result<(), String> {
+    for (label, window) in app.webview_windows() {
+        if is_overlay_label(&label) {
+            window.show().map_err(|e| e.to_string())?;
+            let _ = window.set_ignore_cursor_events(false);
+        }
+    }
+    Ok(())
+}
+
+/// Subscribe to macOS display configuration changes (plug/unplug monitor, lid
+/// open/close) and to focus moving between displays or spaces, which is also
+/// how an app entering or leaving fullscreen shows up. Each re-runs
+/// `refresh_overlay_placement`, which re-detects notches, repositions the
+/// overlays, and notifies each overlay window of its geometry.
+#[cfg(target_os = "macos")]
+pub(crate) fn register_screen_change_observer(app_handle: tauri::AppHandle) {
+    use objc2_foundation::{
+        NSNotification, NSNotificationCenter, NSNotificationName, NSOperationQueue,
+    };
+
+    let observe = |center: &NSNotificationCenter, name: &str, force: bool| {
+        let handle = app_handle.clone();
+        let name = NSNotificationName::from_str(name);
+        let block =
+            block2::RcBlock::new(move |_notification: std::ptr::NonNull<NSNotification>| {
+                refresh_overlay_placement(&handle, force);
+            });
+        unsafe {
+            let observer = center.addObserverForName_object_queue_usingBlock(
+                Some(&name),
+                None,
+                Some(&NSOperationQueue::mainQueue()),
+                &block,
+            );
+            // App-lifetime observer — intentionally leak to avoid premature deallocation
+            std::mem::forget(observer);
+        }
+    };
+
+    // Display configuration changed: always re-place and re-size everything.
+    let center = NSNotificationCenter::defaultCenter();
+    observe(
+        &center,
+        "NSApplicationDidChangeScreenParametersNotification",
+        true,
+    );
+
+    // Keyboard focus may have moved to another display, or into or out of a
+    // fullscreen app's space. These only re-place when the active display or
+    // its fullscreen state actually changed.
+    let workspace_center = objc2_app_kit::NSWorkspace::sharedWorkspace().notificationCenter();
+    observe(
+        &workspace_center,
+        "NSWorkspaceDidActivateApplicationNotification",
+        false,
+    );
+    observe(
+        &workspace_center,
+        "NSWorkspaceActiveSpaceDidChangeNotification",
+        false,
+    );
 }
 
 #[cfg(not(target_os = "macos"))]
@@ -174,18 +606,23 @@ fn raise_window_above_menubar(overlay: &tauri::WebviewWindow) {
     }
 }
 
-/// Position and size the overlay to match the notch, anchored at the top of the screen.
-/// The window is notch-height tall and wide enough for horizontal expansion.
-/// Takes cached notch_info to avoid calling NSScreen APIs off the main thread.
+/// Position and size the overlay for its display: a notch island centered at
+/// the top edge, or a floating pill at its saved (or default) position.
+/// Reads the display `refresh_overlay_placement` assigned to this window from
+/// `State`, so it never calls NSScreen APIs; before the first placement it
+/// centers a notch island on the window's current monitor.
 #[cfg(target_os = "macos")]
-pub(crate) fn position_overlay_default(
-    overlay: &tauri::WebviewWindow,
-    notch_info: Option<(f64, f64)>,
-) {
-    let g = geometry_for(notch_info);
+pub(crate) fn position_overlay_default(overlay: &tauri::WebviewWindow) {
+    let state = overlay.state::<State>();
+    let g = geometry_for_window(&state, overlay.label());
+    let placement = state
+        .overlay_placements
+        .lock_or_recover()
+        .get(overlay.label())
+        .copied();
     let overlay_w = g.window_w;
     let overlay_h = g.collapsed_h;
-    tracing::info!(target: "system", "position_overlay_default: notch_info={:?}, overlay_w={}, overlay_h={}", notch_info, overlay_w, overlay_h);
+    tracing::info!(target: "system", "position_overlay_default: floating={}, overlay_w={}, overlay_h={}", g.floating, overlay_w, overlay_h);
 
     // Resize window to match notch area
     if let Err(e) = overlay.set_size(tauri::LogicalSize::new(overlay_w, overlay_h)) {
@@ -195,7 +632,17 @@ pub(crate) fn position_overlay_default(
     // Raise above the menu bar so the window can overlap the notch
     raise_window_above_menubar(overlay);
 
-    if let Some(monitor) = overlay.current_monitor().ok().flatten() {
+    if let Some(display) = placement {
+        let (x, y) = if g.floating {
+            pill_origin(&display, &g, *state.overlay_pill_position.lock_or_recover())
+        } else {
+            (display.x + (display.width - overlay_w) / 2.0, display.y)
+        };
+        tracing::info!(target: "system", "position_overlay_default: x={}, y={} (placed display)", x, y);
+        if let Err(e) = overlay.set_position(tauri::LogicalPosition::new(x, y)) {
+            tracing::warn!(target: "system", "position_overlay_default: set_position({}, {}) failed: {}", x, y, e);
+        }
+    } else if let Some(monitor) = overlay.current_monitor().ok().flatten() {
         let size = monitor.size();
         let sf = monitor.scale_factor();
         let x = (size.width as f64 / sf - overlay_w) / 2.0;
@@ -209,29 +656,238 @@ pub(crate) fn position_overlay_default(
     }
 }
 
-/// Return the current overlay geometry so the frontend can size the island.
+/// A monitor's work area (physical pixels, taskbar excluded) as a notchless
+/// display in logical points.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn display_from_work_area(
+    position: (i32, i32),
+    size: (u32, u32),
+    scale_factor: f64,
+) -> DisplayInfo {
+    let scale = if scale_factor > 0.0 {
+        scale_factor
+    } else {
+        1.0
+    };
+    DisplayInfo {
+        x: position.0 as f64 / scale,
+        y: position.1 as f64 / scale,
+        width: size.0 as f64 / scale,
+        height: size.1 as f64 / scale,
+        notch: None,
+        built_in: false,
+        active: true,
+        fullscreen: false,
+    }
+}
+
+/// Windows has no notch or menu bar: the one overlay floats as a pill on the
+/// primary monitor's work area, at the saved pill position or top center.
+#[cfg(target_os = "windows")]
+pub(crate) fn place_overlay_on_primary(app: &tauri::AppHandle) {
+    let Some(overlay) = app.get_webview_window("overlay") else {
+        tracing::warn!(target: "system", "place_overlay_on_primary: overlay window not found");
+        return;
+    };
+    let Some(monitor) = overlay.primary_monitor().ok().flatten() else {
+        tracing::warn!(target: "system", "place_overlay_on_primary: no primary monitor");
+        return;
+    };
+    let area = monitor.work_area();
+    let display = display_from_work_area(
+        (area.position.x, area.position.y),
+        (area.size.width, area.size.height),
+        monitor.scale_factor(),
+    );
+    let state = app.state::<State>();
+    let g = geometry_on_display(&state, &display);
+    let (x, y) = if g.floating {
+        pill_origin(&display, &g, *state.overlay_pill_position.lock_or_recover())
+    } else {
+        (display.x + (display.width - g.window_w) / 2.0, display.y)
+    };
+    tracing::info!(target: "system", "place_overlay_on_primary: x={}, y={}, floating={}", x, y, g.floating);
+    *state.overlay_placements.lock_or_recover() =
+        std::iter::once((overlay.label().to_string(), display)).collect();
+    if let Err(e) = overlay.set_size(tauri::LogicalSize::new(g.window_w, g.collapsed_h)) {
+        tracing::warn!(target: "system", "place_overlay_on_primary: set_size failed: {}", e);
+    }
+    if let Err(e) = overlay.set_position(tauri::LogicalPosition::new(x, y)) {
+        tracing::warn!(target: "system", "place_overlay_on_primary: set_position failed: {}", e);
+    }
+    let _ = app.emit_to("overlay", "overlay-geometry-changed", g);
+}
+
+/// Geometry for the calling overlay window: from its placed display and the
+/// overlay style, or from the cached `notch_info` before the first placement.
+fn geometry_for_window(state: &State, label: &str) -> OverlayGeometry {
+    let placement = state
+        .overlay_placements
+        .lock_or_recover()
+        .get(label)
+        .copied();
+    match placement {
+        Some(display) => geometry_on_display(state, &display),
+        None => geometry_for(*state.notch_info.lock_or_recover()),
+    }
+}
+
+/// Return the calling overlay window's geometry so the frontend can size the island.
 #[tauri::command]
-pub fn get_overlay_geometry(state: tauri::State<'_, State>) -> OverlayGeometry {
-    geometry_for(*state.notch_info.lock_or_recover())
+pub fn get_overlay_geometry(
+    webview_window: tauri::WebviewWindow,
+    state: tauri::State<'_, State>,
+) -> OverlayGeometry {
+    geometry_for_window(&state, webview_window.label())
 }
 
-/// Show the always-on-top overlay window (macOS notch overlay; no-op on Linux).
+/// Choose which displays show the overlay: `built-in`, `active`, or `all`.
 #[tauri::command]
-pub fn show_overlay(app: tauri::AppHandle, state: tauri::State<'_, State>) -> Result<(), String> {
+pub fn set_overlay_display(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    mode: String,
+) -> Result<(), String> {
+    let mode = OverlayDisplayMode::parse(&mode)
+        .ok_or_else(|| format!("Unknown overlay display mode '{mode}'"))?;
+    *state.overlay_display_mode.lock_or_recover() = mode;
+
+    #[cfg(target_os = "macos")]
+    {
+        // NSScreen and window creation need the main thread.
+        let handle = app.clone();
+        app.run_on_main_thread(move || refresh_overlay_placement(&handle, true))
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = &app;
+    Ok(())
+}
+
+/// Switch the overlay style at runtime (`auto`, `notch`, or `pill`) and set
+/// where floating pills sit (`None` = centered below the menu bar).
+#[tauri::command]
+pub fn set_overlay_style(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    style: String,
+    pill_position: Option<PillPosition>,
+) -> Result<(), String> {
+    let style =
+        OverlayStyle::parse(&style).ok_or_else(|| format!("Unknown overlay style '{style}'"))?;
+    *state.overlay_style.lock_or_recover() = style;
+    *state.overlay_pill_position.lock_or_recover() = pill_position;
+
+    #[cfg(target_os = "macos")]
+    {
+        let handle = app.clone();
+        app.run_on_main_thread(move || refresh_overlay_placement(&handle, true))
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(target_os = "windows")]
+    place_overlay_on_primary(&app);
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let _ = &app;
+    Ok(())
+}
+
+/// Choose what the overlay does over a fullscreen app: `show`, `hide`, or `pill`.
+#[tauri::command]
+pub fn set_overlay_fullscreen(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    mode: String,
+) -> Result<(), String> {
+    let mode = OverlayFullscreenMode::parse(&mode)
+        .ok_or_else(|| format!("Unknown overlay fullscreen mode '{mode}'"))?;
+    *state.overlay_fullscreen_mode.lock_or_recover() = mode;
+
+    #[cfg(target_os = "macos")]
+    {
+        let handle = app.clone();
+        app.run_on_main_thread(move || refresh_overlay_placement(&handle, true))
+            .map_err(|e| e.to_string())?;
+    }
     #[cfg(not(target_os = "macos"))]
+    let _ = &app;
+    Ok(())
+}
+
+/// Record where the user dragged the calling floating pill and return it for
+/// the frontend to persist. The other overlays are re-placed to match, and the
+/// dragged one snaps back inside its display if it was dropped partly off it.
+#[tauri::command]
+pub fn commit_overlay_pill_position(
+    webview_window: tauri::WebviewWindow,
+    state: tauri::State<'_, State>,
+) -> Result<PillPosition, String> {
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (&webview_window, &state);
+        return Err("floating overlay is only available on macOS".to_string());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let display = state
+            .overlay_placements
+            .lock_or_recover()
+            .get(webview_window.label())
+            .copied()
+            .ok_or_else(|| "overlay is not placed on a display".to_string())?;
+        let g = geometry_on_display(&state, &display);
+        if !g.floating {
+            return Err("overlay is not a floating pill".to_string());
+        }
+        let scale = webview_window.scale_factor().map_err(|e| e.to_string())?;
+        let origin = webview_window
+            .outer_position()
+            .map_err(|e| e.to_string())?
+            .to_logical::<f64>(scale);
+        let position = pill_position_for(&display, &g, (origin.x, origin.y));
+        *state.overlay_pill_position.lock_or_recover() = Some(position);
+
+        let app = webview_window.app_handle().clone();
+        let handle = app.clone();
+        app.run_on_main_thread(move || refresh_overlay_placement(&handle, true))
+            .map_err(|e| e.to_string())?;
+        Ok(position)
+    }
+}
+
+/// Show the always-on-top overlay window (macOS notch overlay, Windows
+/// floating pill; no-op on Linux).
+#[tauri::command]
+pub fn show_overlay(app: tauri::AppHandle, state: tauri::State<'_, State>) -> Result<(), String> {
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
         let _ = (&app, &state);
         return Ok(());
     }
 
+    #[cfg(target_os = "windows")]
+    {
+        let _ = &state;
+        let Some(overlay) = app.get_webview_window("overlay") else {
+            tracing::warn!(target: "system", "show_overlay: overlay window not found — skipping");
+            return Ok(());
+        };
+        place_overlay_on_primary(&app);
+        overlay.show().map_err(|e| e.to_string())?;
+        let _ = app.emit("overlay-visible-changed", true);
+        Ok(())
+    }
+
     #[cfg(target_os = "macos")]
     {
-        let notch = *state.notch_info.lock_or_recover();
         match app.get_webview_window("overlay") {
-            Some(overlay) => {
-                position_overlay_default(&overlay, notch);
-                overlay.show().map_err(|e| e.to_string())?;
-                let _ = overlay.set_ignore_cursor_events(false);
+            Some(_) => {
+                for (label, window) in app.webview_windows() {
+                    if is_overlay_label(&label) {
+                        position_overlay_default(&window);
+                    }
+                }
+                show_overlay_windows(&app)?;
                 // Tell the overlay it is visible so it can gate cursor polling.
                 let _ = app.emit("overlay-visible-changed", true);
                 Ok(())
@@ -259,39 +915,50 @@ pub fn show_overlay(app: tauri::AppHandle, state: tauri::State<'_, State>) -> Re
 /// whole frame, which would create a click dead-zone below the notch when idle.
 #[tauri::command]
 pub fn set_overlay_expanded(
-    app: tauri::AppHandle,
+    webview_window: tauri::WebviewWindow,
     state: tauri::State<'_, State>,
     expanded: bool,
 ) -> Result<AppliedSurface, String> {
-    #[cfg(not(target_os = "macos"))]
+    // Resize the calling window: with "all displays" each mirror expands on
+    // its own, against its own display's notch.
+    let g = geometry_for_window(&state, webview_window.label());
+    let applied = applied_surface_for(&g, expanded);
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
-        let _ = &app;
-        // Off macOS the window is never resized, but the controller still needs
-        // a resolved frame to treat as an ack. Report the geometry it would apply.
-        let g = geometry_for(*state.notch_info.lock_or_recover());
-        return Ok(applied_surface_for(&g, expanded));
+        let _ = &webview_window;
+        // Without an overlay the window is never resized, but the controller
+        // still needs a resolved frame to treat as an ack. Report the geometry
+        // it would apply.
+        return Ok(applied);
     }
 
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
     {
-        let notch = *state.notch_info.lock_or_recover();
-        match app.get_webview_window("overlay") {
-            Some(overlay) => {
-                let g = geometry_for(notch);
-                let applied = applied_surface_for(&g, expanded);
-                overlay
-                    .set_size(tauri::LogicalSize::new(applied.window_w, applied.window_h))
-                    .map_err(|e| e.to_string())?;
-                Ok(applied)
-            }
-            None => {
-                tracing::warn!(target: "system", "set_overlay_expanded: overlay window not found — skipping");
-                Err("overlay window not found".to_string())
-            }
+        if !is_overlay_label(webview_window.label()) {
+            tracing::warn!(target: "system", "set_overlay_expanded: called from a non-overlay window — skipping");
+            return Err("overlay window not found".to_string());
         }
+        webview_window
+            .set_size(tauri::LogicalSize::new(applied.window_w, applied.window_h))
+            .map_err(|e| e.to_string())?;
+        Ok(applied)
     }
 }
 
+/// Push a state to every overlay window over the `overlay-state` channel:
+/// `idle`, `recording`, `level`, `processing` (optional partial text), or `error`.
+#[tauri::command]
+pub fn set_overlay_state(
+    app: tauri::AppHandle,
+    state: String,
+    payload: Option<serde_json::Value>,
+) -> Result<(), String> {
+    let state = crate::events::OverlayState::from_parts(&state, payload)?;
+    crate::events::emit_overlay_state(&app, &state);
+    Ok(())
+}
+
 /// Show and focus the main app window.
 ///
 /// The overlay uses this instead of frontend window APIs so it does not need
@@ -310,12 +977,128 @@ pub fn show_main_window(app: tauri::AppHandle) -> Result<(), String> {
     }
 }
 
+/// A click on the overlay that Rust carries out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OverlayAction {
+    /// Stop a recording, or ask the main window to start one.
+    ToggleRecording,
+    /// Discard the recording in progress.
+    Cancel,
+    OpenMainWindow,
+    /// Copy the newest history entry.
+    CopyLast,
+    /// Accept an app trigger's offer of ambient transcription.
+    StartAmbient,
+    DismissAmbientPrompt,
+}
+
+impl OverlayAction {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "toggle_recording" => Some(Self::ToggleRecording),
+            "cancel" => Some(Self::Cancel),
+            "open_main_window" => Some(Self::OpenMainWindow),
+            "copy_last" => Some(Self::CopyLast),
+            "start_ambient" => Some(Self::StartAmbient),
+            "dismiss_ambient_prompt" => Some(Self::DismissAmbientPrompt),
+            _ => None,
+        }
+    }
+
+    /// Whether the action is meant to bring Murmur forward. Every other one
+    /// must leave the app the user was typing into frontmost, since that is
+    /// where a dictation gets pasted.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    fn activates_app(self) -> bool {
+        self == Self::OpenMainWindow
+    }
+}
+
+/// Carry out an overlay click: `toggle_recording`, `cancel`,
+/// `open_main_window`, `copy_last`, `start_ambient`, or
+/// `dismiss_ambient_prompt`.
+///
+/// The overlay is a non-activating window, so a click reaches it without
+/// taking focus from the app the user is in. Routing the action through Rust
+/// keeps it that way: nothing here touches a window except
+/// `open_main_window`, and the calling overlay is re-marked non-activating
+/// afterwards in case a show or resize reset it. Starting a recording goes
+/// through the main window (`overlay-start-requested`) so it uses the
+/// selected microphone and profile like a hotkey does, and so does turning
+/// ambient mode on, since the main window owns that setting.
+#[tauri::command]
+pub async fn overlay_action(
+    app: tauri::AppHandle,
+    webview_window: tauri::WebviewWindow,
+    state: tauri::State<'_, State>,
+    action: String,
+) -> Result<(), String> {
+    let Some(action) = OverlayAction::parse(&action) else {
+        return Err(format!("Unknown overlay action: {}", action));
+    };
+    if !is_overlay_label(webview_window.label()) {
+        tracing::warn!(target: "system", "overlay_action: called from a non-overlay window — skipping");
+        return Err("overlay window not found".to_string());
+    }
+    let status = state.app_state.dictation.lock_or_recover().status;
+    tracing::info!(target: "system", ?action, ?status, "overlay action");
+
+    let result = match action {
+        OverlayAction::ToggleRecording => match status {
+            DictationStatus::Recording => {
+                super::recording::stop_native_recording(app.clone(), state)
+                    .await
+                    .map(|_| ())
+            }
+            DictationStatus::Idle | DictationStatus::Buffering => app
+                .emit("overlay-start-requested", ())
+                .map_err(|e| e.to_string()),
+            // Already transcribing; the click has nothing to toggle.
+            DictationStatus::Processing => Ok(()),
+        },
+        OverlayAction::Cancel => {
+            if status == DictationStatus::Recording {
+                super::recording::cancel_native_recording(app.clone(), state).await
+            } else {
+                Ok(())
+            }
+        }
+        OverlayAction::OpenMainWindow => show_main_window(app.clone()),
+        OverlayAction::CopyLast => super::recording::copy_last_transcription(state),
+        OverlayAction::StartAmbient => {
+            crate::app_triggers::accept_prompt(&app);
+            Ok(())
+        }
+        OverlayAction::DismissAmbientPrompt => {
+            crate::app_triggers::dismiss_prompt(&app);
+            Ok(())
+        }
+    };
+
+    #[cfg(target_os = "macos")]
+    if !action.activates_app() {
+        super::native_window::set_window_level_and_activation(
+            &webview_window,
+            super::native_window::ABOVE_MENU_BAR_LEVEL,
+            true,
+        );
+    }
+    result
+}
+
 /// Hide the always-on-top overlay window.
 #[tauri::command]
 pub fn hide_overlay(app: tauri::AppHandle) -> Result<(), String> {
     match app.get_webview_window("overlay") {
-        Some(overlay) => {
-            overlay.hide().map_err(|e| e.to_string())?;
+        Some(_) => {
+            // Hidden on purpose now, so leaving fullscreen must not bring it back.
+            let state = app.state::<State>();
+            *state.overlay_hidden_for_fullscreen.lock_or_recover() = false;
+            for (label, window) in app.webview_windows() {
+                if is_overlay_label(&label) {
+                    window.hide().map_err(|e| e.to_string())?;
+                }
+            }
             // Tell the overlay it is hidden so it can stop cursor polling.
             let _ = app.emit("overlay-visible-changed", false);
             Ok(())
@@ -330,9 +1113,31 @@ pub fn hide_overlay(app: tauri::AppHandle) -> Result<(), String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn overlay_actions_parse_and_only_opening_the_window_activates() {
+        let actions = [
+            "toggle_recording",
+            "cancel",
+            "open_main_window",
+            "copy_last",
+            "start_ambient",
+            "dismiss_ambient_prompt",
+        ]
+        .map(|value| OverlayAction::parse(value).unwrap());
+        assert_eq!(
+            actions.map(OverlayAction::activates_app),
+            [false, false, true, false, false, false]
+        );
+        assert_eq!(OverlayAction::parse("paste_last"), None);
+    }
+
     #[test]
     fn invariants() {
-        for g in [geometry_for(Some((185.0, 32.0))), geometry_for(None)] {
+        for g in [
+            geometry_for(Some((185.0, 32.0))),
+            geometry_for(None),
+            pill_geometry(),
+        ] {
             assert!(g.window_w >= g.pill_active_w + g.pill_margin_active);
             assert!(g.window_w >= g.pill_idle_w + g.pill_margin_idle);
             assert_eq!(g.expanded_h, g.collapsed_h + g.dropdown_h);
@@ -367,6 +1172,7 @@ mod tests {
         struct F {
             notched: OverlayGeometry,
             fallback: OverlayGeometry,
+            pill: OverlayGeometry,
         }
         let f: F = serde_json::from_str(include_str!(
             "../../../src/components/overlay/overlay-geometry.fixture.json"
@@ -374,6 +1180,7 @@ mod tests {
         .unwrap();
         assert_eq!(geometry_for(Some((185.0, 32.0))), f.notched);
         assert_eq!(geometry_for(None), f.fallback);
+        assert_eq!(pill_geometry(), f.pill);
     }
 
     #[test]
@@ -420,4 +1227,149 @@ mod tests {
             }
         );
     }
+
+    fn display(x: f64, notch: bool, built_in: bool, active: bool) -> DisplayInfo {
+        DisplayInfo {
+            x,
+            y: 0.0,
+            width: 1512.0,
+            height: 982.0,
+            notch: notch.then_some((185.0, 32.0)),
+            built_in,
+            active,
+            fullscreen: false,
+        }
+    }
+
+    #[test]
+    fn overlay_targets_follow_the_display_mode() {
+        let external = display(-1920.0, false, false, true);
+        let laptop = display(0.0, true, true, false);
+        let displays = [external, laptop];
+
+        assert_eq!(
+            overlay_targets(&displays, OverlayDisplayMode::BuiltIn),
+            vec![laptop]
+        );
+        assert_eq!(
+            overlay_targets(&displays, OverlayDisplayMode::Active),
+            vec![external]
+        );
+        // The home display keeps the configured `overlay` window.
+        assert_eq!(
+            overlay_targets(&displays, OverlayDisplayMode::All),
+            vec![laptop, external]
+        );
+        assert_eq!(overlay_label(0), "overlay");
+        assert_eq!(overlay_label(1), "overlay-1");
+
+        // A desktop Mac has no built-in panel: the primary display is home.
+        let desktop = [
+            display(0.0, false, false, false),
+            display(1512.0, false, false, false),
+        ];
+        assert_eq!(
+            overlay_targets(&desktop, OverlayDisplayMode::Active),
+            vec![desktop[0]]
+        );
+        assert!(overlay_targets(&[], OverlayDisplayMode::All).is_empty());
+        assert_eq!(
+            OverlayDisplayMode::parse("all"),
+            Some(OverlayDisplayMode::All)
+        );
+        assert_eq!(OverlayDisplayMode::parse("primary"), None);
+    }
+
+    #[test]
+    fn auto_style_floats_a_pill_on_displays_without_a_notch() {
+        let laptop = display(0.0, true, true, false);
+        let external = display(1512.0, false, false, true);
+        assert!(!geometry_for_display(&laptop, OverlayStyle::Auto).floating);
+        assert!(geometry_for_display(&external, OverlayStyle::Auto).floating);
+        assert!(geometry_for_display(&laptop, OverlayStyle::Pill).floating);
+        // Forcing the notch style on an external display keeps the old strip.
+        assert_eq!(
+            geometry_for_display(&external, OverlayStyle::Notch),
+            geometry_for(None)
+        );
+        assert_eq!(OverlayStyle::parse("pill"), Some(OverlayStyle::Pill));
+        assert_eq!(OverlayStyle::parse("floating"), None);
+    }
+
+    #[test]
+    fn a_fullscreen_display_floats_a_pill_only_in_pill_mode() {
+        let laptop = display(0.0, true, true, true);
+        let fullscreen = DisplayInfo {
+            fullscreen: true,
+            ..laptop
+        };
+        let pill = OverlayFullscreenMode::Pill;
+        assert_eq!(
+            style_on_display(&fullscreen, OverlayStyle::Notch, pill),
+            OverlayStyle::Pill
+        );
+        assert_eq!(
+            style_on_display(&laptop, OverlayStyle::Notch, pill),
+            OverlayStyle::Notch
+        );
+        for mode in [OverlayFullscreenMode::Show, OverlayFullscreenMode::Hide] {
+            assert_eq!(
+                style_on_display(&fullscreen, OverlayStyle::Auto, mode),
+                OverlayStyle::Auto
+            );
+        }
+        assert_eq!(
+            OverlayFullscreenMode::parse("hide"),
+            Some(OverlayFullscreenMode::Hide)
+        );
+        assert_eq!(OverlayFullscreenMode::parse("minimize"), None);
+    }
+
+    #[test]
+    fn pill_positions_round_trip_and_stay_on_their_display() {
+        let external = display(1512.0, false, false, true);
+        let g = pill_geometry();
+
+        let (x, y) = pill_origin(&external, &g, None);
+        assert_eq!(x, 1512.0 + (1512.0 - g.window_w) / 2.0);
+        assert_eq!(y, PILL_DEFAULT_TOP);
+
+        let dragged = (1512.0 + 300.0, 500.0);
+        let position = pill_position_for(&external, &g, dragged);
+        assert_eq!(pill_origin(&external, &g, Some(position)), dragged);
+
+        // Dropped past the bottom-right corner: clamped so the dropdown fits.
+        let clamped = pill_position_for(&external, &g, (9000.0, 9000.0));
+        assert_eq!(clamped, PillPosition { x: 1.0, y: 1.0 });
+        assert_eq!(
+            pill_origin(&external, &g, Some(clamped)),
+            (1512.0 + 1512.0 - g.window_w, 982.0 - g.expanded_h)
+        );
+    }
+
+    #[test]
+    fn work_area_becomes_a_notchless_display_in_logical_points() {
+        let display = display_from_work_area((0, 0), (2880, 1680), 1.5);
+        assert_eq!(
+            display,
+            DisplayInfo {
+                x: 0.0,
+                y: 0.0,
+                width: 1920.0,
+                height: 1120.0,
+                notch: None,
+                built_in: false,
+                active: true,
+            }
+        );
+        assert_eq!(
+            geometry_for_display(&display, OverlayStyle::Auto),
+            pill_geometry()
+        );
+
+        // A secondary-left primary work area and a bogus scale factor.
+        let shifted = display_from_work_area((-1920, 40), (1920, 1040), 0.0);
+        assert_eq!((shifted.x, shifted.y), (-1920.0, 40.0));
+        assert_eq!((shifted.width, shifted.height), (1920.0, 1040.0));
+    }
 }
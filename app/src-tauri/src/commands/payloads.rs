@@ -0,0 +1,378 @@
+//! Typed request and response payloads for the dictation commands.
+//!
+//! These structs are the wire contract with the frontend. `ConfigureOptions`
+//! rejects unknown keys, so a misspelled option such as `autopaste` fails the
+//! whole `configure_dictation` call instead of being silently dropped.
+//! Nested settings that have their own lenient parsers (profiles, redaction,
+//! decoding, ...) stay as raw JSON here and are parsed in `recording.rs`.
+//!
+//! `cargo test` regenerates the TypeScript bindings in `src/lib/bindings/`.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::state::DictationStatus;
+
+/// Options accepted by `configure_dictation`. Every field is optional; a
+/// missing or null field leaves the current setting unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[ts(export, export_to = "../../src/lib/bindings/")]
+pub struct ConfigureOptions {
+    #[ts(optional)]
+    pub model: Option<String>,
+    #[ts(optional)]
+    pub language: Option<String>,
+    #[ts(optional)]
+    pub auto_paste: Option<bool>,
+    #[ts(optional, type = "number")]
+    pub auto_paste_delay_ms: Option<u64>,
+    #[ts(optional, type = "number")]
+    pub vad_sensitivity: Option<u64>,
+    #[ts(optional, type = "number")]
+    pub idle_timeout_minutes: Option<u64>,
+    #[ts(optional, type = "number")]
+    pub backend_keep_alive_mb: Option<u64>,
+    #[ts(optional)]
+    pub custom_vocabulary: Option<String>,
+    #[ts(optional, type = "Array<unknown>")]
+    pub vocabulary_entries: Option<serde_json::Value>,
+    #[ts(optional)]
+    pub smart_punctuation: Option<bool>,
+    #[ts(optional)]
+    pub save_transcript: Option<bool>,
+    #[ts(optional)]
+    pub save_audio: Option<bool>,
+    #[ts(optional)]
+    pub output_dir: Option<String>,
+    #[ts(optional, type = "Array<unknown>")]
+    pub app_profiles: Option<Vec<serde_json::Value>>,
+    #[ts(optional, type = "Array<unknown>")]
+    pub dictation_profiles: Option<Vec<serde_json::Value>>,
+    #[ts(optional, type = "Array<unknown>")]
+    pub rewrite_templates: Option<Vec<serde_json::Value>>,
+    #[ts(optional, type = "Array<unknown>")]
+    pub replacement_rules: Option<Vec<serde_json::Value>>,
+    #[ts(optional, type = "unknown")]
+    pub dictation_macros: Option<serde_json::Value>,
+    #[ts(optional, type = "unknown")]
+    pub context_biasing: Option<serde_json::Value>,
+    #[ts(optional)]
+    pub inject_blocklist: Option<Vec<String>>,
+    #[ts(optional)]
+    pub voice_commands_enabled: Option<bool>,
+    #[ts(optional, type = "Array<unknown>")]
+    pub voice_commands: Option<Vec<serde_json::Value>>,
+    #[ts(optional)]
+    pub cleanup_enabled: Option<bool>,
+    #[ts(optional)]
+    pub smart_formatting_enabled: Option<bool>,
+    #[ts(optional)]
+    pub cleanup_remove_filler: Option<bool>,
+    #[ts(optional)]
+    pub cleanup_capitalize: Option<bool>,
+    #[ts(optional)]
+    pub code_vocab_enabled: Option<bool>,
+    #[ts(optional)]
+    pub code_vocab_folder: Option<String>,
+    #[ts(optional)]
+    pub correction_enabled: Option<bool>,
+    #[ts(optional)]
+    pub correction_fuzzy: Option<bool>,
+    #[ts(optional)]
+    pub notification_policy: Option<String>,
+    #[ts(optional, type = "unknown")]
+    pub focus_filter: Option<serde_json::Value>,
+    #[ts(optional)]
+    pub interrupted_recording_action: Option<String>,
+    #[ts(optional, type = "unknown")]
+    pub whisper_decoding: Option<serde_json::Value>,
+    #[ts(optional)]
+    pub hallucination_filter: Option<bool>,
+    #[ts(optional, type = "unknown")]
+    pub redaction: Option<serde_json::Value>,
+    #[ts(optional, type = "unknown")]
+    pub adaptive_model: Option<serde_json::Value>,
+    #[ts(optional, type = "unknown")]
+    pub latency_budget: Option<serde_json::Value>,
+    #[ts(optional, type = "unknown")]
+    pub dual_model: Option<serde_json::Value>,
+    #[ts(optional, type = "unknown")]
+    pub confidence_retry: Option<serde_json::Value>,
+    #[ts(optional, type = "unknown")]
+    pub alternatives: Option<serde_json::Value>,
+    #[ts(optional)]
+    pub learn_from_corrections: Option<bool>,
+    #[ts(optional)]
+    pub privacy_mode: Option<bool>,
+    #[ts(optional)]
+    pub keep_audio_on_device: Option<bool>,
+    #[ts(optional)]
+    pub append_mode: Option<bool>,
+    #[ts(optional)]
+    pub legacy_automation: Option<bool>,
+    #[ts(optional)]
+    pub preview_before_paste: Option<bool>,
+    #[ts(optional)]
+    pub cursor_context: Option<bool>,
+    #[ts(optional)]
+    pub trailing_behavior: Option<String>,
+    #[ts(optional)]
+    pub notes_target: Option<String>,
+    #[ts(optional)]
+    pub selection_dictation: Option<String>,
+}
+
+impl ConfigureOptions {
+    /// Number of options actually set, for configuration telemetry.
+    pub fn present_count(&self) -> u64 {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|value| {
+                value
+                    .as_object()
+                    .map(|object| object.values().filter(|v| !v.is_null()).count() as u64)
+            })
+            .unwrap_or(0)
+    }
+}
+
+/// Reply to `get_status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../../src/lib/bindings/")]
+pub struct StatusResponse {
+    #[serde(rename = "type")]
+    #[ts(type = "\"status\"")]
+    pub kind: &'static str,
+    pub state: String,
+    pub model: String,
+    pub language: String,
+}
+
+impl StatusResponse {
+    pub fn new(state: &str, model: &str, language: &str) -> Self {
+        Self {
+            kind: "status",
+            state: state.to_string(),
+            model: model.to_string(),
+            language: language.to_string(),
+        }
+    }
+}
+
+/// What a [`StartRecordingResult`] reports. Serialized as its `type` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../../src/lib/bindings/")]
+pub enum StartRecordingKind {
+    /// The microphone is open and the recording has begun.
+    RecordingStarted,
+    /// Murmur is disabled for the frontmost app.
+    AppDisabled,
+    /// A file transcription holds the shared backend.
+    BusyTranscribingFile,
+    /// A model benchmark holds the shared backend.
+    BusyBenchmarking,
+    /// A transform pass or the local-LLM runtime is in flight.
+    BusyTransforming,
+    /// A recording is already in progress.
+    AlreadyRecording,
+    /// `process_audio` is transcribing inline.
+    AlreadyProcessing,
+}
+
+/// Reply to `start_native_recording`. Every kind other than
+/// `recording_started` means the start was refused.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export, export_to = "../../src/lib/bindings/")]
+pub struct StartRecordingResult {
+    #[serde(rename = "type")]
+    pub kind: StartRecordingKind,
+    /// Dictation state after the command settled.
+    pub state: String,
+}
+
+impl StartRecordingResult {
+    pub fn new(kind: StartRecordingKind, state: DictationStatus) -> Self {
+        Self {
+            kind,
+            state: state.as_str().to_string(),
+        }
+    }
+}
+
+/// What a [`TranscriptionResult`] reports. Serialized as its `type` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../../src/lib/bindings/")]
+pub enum TranscriptionKind {
+    /// A dictation finished; `text` may be empty when nothing was heard.
+    Transcription,
+    /// `transcribe_file` finished.
+    FileTranscription,
+    /// The append buffer was pasted.
+    Committed,
+    /// A stop arrived with no take recording while earlier takes were
+    /// still transcribing.
+    AlreadyProcessing,
+    /// A stop arrived with no recording in progress.
+    NotRecording,
+    /// The recording was handed to the transcription worker. Its result
+    /// arrives as `transcription-complete`, `no-speech-detected`, or
+    /// `transcription-failed` for `jobId`.
+    Queued,
+}
+
+/// Reply to the commands that finish a dictation: `process_audio`,
+/// `stop_native_recording`, `transcribe_file`, and `commit_append_buffer`.
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "../../src/lib/bindings/")]
+pub struct TranscriptionResult {
+    #[serde(rename = "type")]
+    pub kind: TranscriptionKind,
+    pub text: Option<String>,
+    /// Dictation state after the command settled.
+    pub state: Option<String>,
+    #[ts(type = "number | null")]
+    pub file_run_id: Option<u64>,
+    /// Recording id of a queued transcription; matches the `recordingId` of
+    /// its result event.
+    #[ts(type = "number | null")]
+    pub job_id: Option<u64>,
+    /// Decoded audio length in seconds (file transcription only).
+    pub duration: Option<f64>,
+    /// Where the file was transcribed (file transcription only).
+    #[ts(type = "'device' | 'cloud' | 'plugin' | null")]
+    pub processed_on: Option<crate::model_runtime::ProcessingLocation>,
+    /// The cloud host or plugin name, when not on this Mac.
+    pub processed_by: Option<String>,
+}
+
+impl TranscriptionResult {
+    pub fn new(kind: TranscriptionKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: Some(text.into()),
+            state: None,
+            file_run_id: None,
+            job_id: None,
+            duration: None,
+            processed_on: None,
+            processed_by: None,
+        }
+    }
+
+    /// A stop that found nothing to transcribe.
+    pub fn declined(kind: TranscriptionKind, state: &str) -> Self {
+        Self {
+            kind,
+            text: None,
+            state: Some(state.to_string()),
+            file_run_id: None,
+            job_id: None,
+            duration: None,
+            processed_on: None,
+            processed_by: None,
+        }
+    }
+
+    /// A stop whose transcription continues on the worker.
+    pub fn queued(job_id: u64) -> Self {
+        Self {
+            kind: TranscriptionKind::Queued,
+            text: None,
+            state: Some(DictationStatus::Processing.as_str().to_string()),
+            file_run_id: None,
+            job_id: Some(job_id),
+            duration: None,
+            processed_on: None,
+            processed_by: None,
+        }
+    }
+
+    pub fn with_state(mut self, state: &str) -> Self {
+        self.state = Some(state.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configure_options_reject_unknown_keys() {
+        let error = serde_json::from_value::<ConfigureOptions>(serde_json::json!({
+            "autopaste": true
+        }))
+        .unwrap_err();
+        assert!(error.to_string().contains("autopaste"));
+    }
+
+    #[test]
+    fn configure_options_treat_null_as_unset_and_count_present_keys() {
+        let options: ConfigureOptions = serde_json::from_value(serde_json::json!({
+            "autoPaste": false,
+            "model": null,
+            "appProfiles": [],
+        }))
+        .unwrap();
+        assert_eq!(options.auto_paste, Some(false));
+        assert_eq!(options.model, None);
+        assert_eq!(options.present_count(), 2);
+        assert!(
+            serde_json::from_value::<ConfigureOptions>(serde_json::json!({
+                "autoPaste": "yes"
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn start_recording_result_serializes_the_wire_shape() {
+        assert_eq!(
+            serde_json::to_value(StartRecordingResult::new(
+                StartRecordingKind::BusyTranscribingFile,
+                DictationStatus::Idle,
+            ))
+            .unwrap(),
+            serde_json::json!({ "type": "busy_transcribing_file", "state": "idle" })
+        );
+    }
+
+    #[test]
+    fn transcription_result_serializes_the_wire_shape() {
+        let result = TranscriptionResult {
+            processed_on: Some(crate::model_runtime::ProcessingLocation::Plugin),
+            processed_by: Some("Local server".to_string()),
+            ..TranscriptionResult::new(TranscriptionKind::FileTranscription, "hi")
+        };
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "type": "file_transcription",
+                "text": "hi",
+                "state": null,
+                "fileRunId": null,
+                "jobId": null,
+                "duration": null,
+                "processedOn": "plugin",
+                "processedBy": "Local server",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(TranscriptionResult::queued(4)).unwrap(),
+            serde_json::json!({
+                "type": "queued",
+                "text": null,
+                "state": "processing",
+                "fileRunId": null,
+                "jobId": 4,
+                "duration": null,
+                "processedOn": null,
+                "processedBy": null,
+            })
+        );
+    }
+}
@@ -1,23 +1,50 @@
+use crate::permissions::{self, PermissionState};
 use crate::{audio, injector};
+use crate::State;
 
+/// Open a Privacy & Security pane through `NSWorkspace`, or by spawning `open`
+/// when legacy automation is on.
 #[cfg(target_os = "macos")]
-fn open_system_preference_pane(pane: &str) -> Result<(), String> {
-    std::process::Command::new("open")
-        .arg(format!(
-            "x-apple.systempreferences:com.apple.preference.security?{}",
-            pane
-        ))
-        .spawn()
-        .map_err(|e| format!("Failed to open System Settings: {}", e))?;
-    Ok(())
+fn open_system_preference_pane(state: &State, pane: &str) -> Result<(), String> {
+    use crate::MutexExt;
+
+    let url = format!(
+        "x-apple.systempreferences:com.apple.preference.security?{}",
+        pane
+    );
+    if state.app_state.dictation.lock_or_recover().legacy_automation {
+        std::process::Command::new("open")
+            .arg(&url)
+            .spawn()
+            .map_err(|e| format!("Failed to open System Settings: {}", e))?;
+        return Ok(());
+    }
+    open_url_native(&url)
+}
+
+#[cfg(target_os = "macos")]
+fn open_url_native(url: &str) -> Result<(), String> {
+    use objc2_app_kit::NSWorkspace;
+    use objc2_foundation::{NSString, NSURL};
+
+    let url = NSURL::URLWithString(&NSString::from_str(url))
+        .ok_or_else(|| format!("Invalid System Settings URL: {}", url))?;
+    if NSWorkspace::sharedWorkspace().openURL(&url) {
+        Ok(())
+    } else {
+        Err("Failed to open System Settings".to_string())
+    }
 }
 
 #[tauri::command]
-pub fn open_system_preferences() -> Result<(), String> {
+pub fn open_system_preferences(state: tauri::State<'_, State>) -> Result<(), String> {
     #[cfg(target_os = "macos")]
-    { return open_system_preference_pane("Privacy_Microphone"); }
+    { return open_system_preference_pane(&state, "Privacy_Microphone"); }
     #[cfg(not(target_os = "macos"))]
-    { Err("System preferences shortcut not supported on this platform".to_string()) }
+    {
+        let _ = state;
+        Err("System preferences shortcut not supported on this platform".to_string())
+    }
 }
 
 /// Check if accessibility permission is granted (macOS)
@@ -28,17 +55,20 @@ pub fn check_accessibility_permission() -> bool {
 
 /// Request accessibility permission (triggers system prompt + opens System Settings on macOS)
 #[tauri::command]
-pub fn request_accessibility_permission() -> Result<(), String> {
+pub fn request_accessibility_permission(state: tauri::State<'_, State>) -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
         // Trigger the system dialog and register the app in the Accessibility list.
         // Return value is the current trust status — we proceed to open System Settings
         // regardless, so the result is intentionally discarded here.
         let _ = injector::request_accessibility_prompt();
-        return open_system_preference_pane("Privacy_Accessibility");
+        return open_system_preference_pane(&state, "Privacy_Accessibility");
     }
     #[cfg(not(target_os = "macos"))]
-    { Ok(()) }
+    {
+        let _ = state;
+        Ok(())
+    }
 }
 
 /// Read the running process's bundle identifier (macOS).
@@ -79,7 +109,7 @@ fn current_bundle_identifier() -> Option<String> {
 /// `tccutil reset Accessibility <bundle-id>` — never all apps. macOS still requires
 /// the user to re-enable the app manually afterward; this only clears the stale entry.
 #[tauri::command]
-pub fn reset_accessibility_permission() -> Result<(), String> {
+pub fn reset_accessibility_permission(state: tauri::State<'_, State>) -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
         let bundle_id = current_bundle_identifier()
@@ -107,63 +137,116 @@ pub fn reset_accessibility_permission() -> Result<(), String> {
             return Err(format!("tccutil reset failed: {}", stderr.trim()));
         }
 
-        return open_system_preference_pane("Privacy_Accessibility");
+        return open_system_preference_pane(&state, "Privacy_Accessibility");
     }
     #[cfg(not(target_os = "macos"))]
     {
+        let _ = state;
         Err("Accessibility reset is only supported on macOS".to_string())
     }
 }
 
-/// Request microphone permission (opens System Settings on macOS)
+/// How long `request_microphone_permission` waits for an answer to the native
+/// prompt before returning the status as it stands.
+#[cfg(target_os = "macos")]
+const MIC_PROMPT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Call `AVCaptureDevice.requestAccessForMediaType:completionHandler:`.
+///
+/// When the status is `notDetermined` this shows the system dialog and
+/// registers the app in the Microphone pane — without opening the device, so it
+/// cannot duck other apps' audio (issue #177). When the status is already
+/// determined the completion fires immediately and no dialog appears.
+/// `on_answer` runs on an arbitrary dispatch queue.
+#[cfg(target_os = "macos")]
+fn request_microphone_prompt(on_answer: impl Fn(bool) + 'static) -> Result<(), String> {
+    use objc2::msg_send;
+    use objc2::runtime::{AnyClass, Bool};
+    use objc2_foundation::NSString;
+
+    unsafe {
+        let Some(cls) = AnyClass::get(c"AVCaptureDevice") else {
+            return Err("AVCaptureDevice is unavailable".to_string());
+        };
+        // AVMediaTypeAudio == @"soun"
+        let media = NSString::from_str("soun");
+        let handler = block2::RcBlock::new(move |granted: Bool| on_answer(granted.as_bool()));
+        let _: () = msg_send![
+            cls,
+            requestAccessForMediaType: &*media,
+            completionHandler: &*handler
+        ];
+    }
+    Ok(())
+}
+
+/// Request microphone permission and return the resulting status.
+///
+/// A first request (`notDetermined`) shows the native prompt and waits for the
+/// user's answer. Once access has been denied or restricted macOS never shows
+/// the prompt again, so this opens the Microphone pane instead and returns
+/// "denied"; the permission watcher reports a later grant.
+///
+/// Returns one of: "granted" | "denied" | "notDetermined" | "unknown".
 #[tauri::command]
-pub fn request_microphone_permission() -> Result<(), String> {
+pub async fn request_microphone_permission(
+    state: tauri::State<'_, State>,
+) -> Result<String, String> {
     #[cfg(target_os = "macos")]
-    { return open_system_preference_pane("Privacy_Microphone"); }
+    {
+        use crate::MutexExt;
+
+        let mic = permissions::microphone_state();
+        match mic {
+            PermissionState::Granted => return Ok(mic.as_str().to_string()),
+            PermissionState::Denied => {
+                open_system_preference_pane(&state, "Privacy_Microphone")?;
+                return Ok(mic.as_str().to_string());
+            }
+            PermissionState::NotDetermined | PermissionState::Unknown => {}
+        }
+
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        let sender = std::sync::Mutex::new(Some(sender));
+        request_microphone_prompt(move |granted| {
+            if let Some(sender) = sender.lock_or_recover().take() {
+                let _ = sender.send(granted);
+            }
+        })?;
+        let answered = tokio::time::timeout(MIC_PROMPT_TIMEOUT, receiver).await.is_ok();
+        let state = permissions::microphone_state();
+        tracing::info!(
+            target: "system",
+            answered,
+            state = state.as_str(),
+            "microphone permission requested"
+        );
+        Ok(state.as_str().to_string())
+    }
     #[cfg(not(target_os = "macos"))]
-    { Ok(()) }
+    {
+        let _ = state;
+        Ok(PermissionState::Granted.as_str().to_string())
+    }
 }
 
 /// Trigger the native macOS microphone permission prompt (TCC) in-flow.
 ///
-/// Calls `AVCaptureDevice.requestAccessForMediaType:completionHandler:`. When the
-/// status is `notDetermined` this shows the system dialog and registers the app in
-/// the Microphone pane — without opening the device, so it cannot duck other apps'
-/// audio (issue #177). When the status is already determined the completion fires
-/// immediately and no dialog appears. Fire-and-forget: callers observe the outcome
-/// by polling `check_microphone_permission_status`, so this never blocks on the
-/// user's answer.
+/// Fire-and-forget counterpart of [`request_microphone_permission`]: callers
+/// observe the outcome through `permission-changed` or by polling
+/// `check_microphone_permission_status`, so this never blocks on the user's
+/// answer.
 #[tauri::command]
 pub fn request_microphone_access() -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
-        use objc2::msg_send;
-        use objc2::runtime::{AnyClass, Bool};
-        use objc2_foundation::NSString;
-
-        unsafe {
-            let Some(cls) = AnyClass::get(c"AVCaptureDevice") else {
-                return Err("AVCaptureDevice is unavailable".to_string());
-            };
-            // AVMediaTypeAudio == @"soun"
-            let media = NSString::from_str("soun");
-            // The completion handler runs on an arbitrary dispatch queue after the
-            // user answers; the block only logs, all state reads go through the
-            // polling commands.
-            let handler = block2::RcBlock::new(|granted: Bool| {
-                tracing::info!(
-                    target: "system",
-                    "microphone access request completed: granted={}",
-                    granted.as_bool()
-                );
-            });
-            let _: () = msg_send![
-                cls,
-                requestAccessForMediaType: &*media,
-                completionHandler: &*handler
-            ];
-        }
-        Ok(())
+        request_microphone_prompt(|granted| {
+            tracing::info!(
+                target: "system",
+                "microphone access request completed: granted={}",
+                granted
+            );
+        })
     }
     #[cfg(not(target_os = "macos"))]
     {
@@ -180,7 +263,7 @@ pub fn request_microphone_access() -> Result<(), String> {
 /// identifier via `tccutil reset Microphone <bundle-id>` — never all apps. macOS
 /// re-prompts on next mic use afterward; this only clears the stale entry.
 #[tauri::command]
-pub fn reset_microphone_permission() -> Result<(), String> {
+pub fn reset_microphone_permission(state: tauri::State<'_, State>) -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
         let bundle_id = current_bundle_identifier()
@@ -208,10 +291,11 @@ pub fn reset_microphone_permission() -> Result<(), String> {
             return Err(format!("tccutil reset failed: {}", stderr.trim()));
         }
 
-        return open_system_preference_pane("Privacy_Microphone");
+        return open_system_preference_pane(&state, "Privacy_Microphone");
     }
     #[cfg(not(target_os = "macos"))]
     {
+        let _ = state;
         Err("Microphone reset is only supported on macOS".to_string())
     }
 }
@@ -224,86 +308,20 @@ pub fn reset_microphone_permission() -> Result<(), String> {
 /// opened VPIO on every window focus, ducking other system audio each time.)
 #[tauri::command]
 pub fn check_microphone_permission() -> bool {
-    #[cfg(target_os = "macos")]
-    {
-        use objc2::msg_send;
-        use objc2::runtime::AnyClass;
-        use objc2_foundation::NSString;
-
-        // AVAuthorizationStatusAuthorized == 3
-        const AUTHORIZED: isize = 3;
-        unsafe {
-            let Some(cls) = AnyClass::get(c"AVCaptureDevice") else {
-                return false;
-            };
-            // AVMediaTypeAudio == @"soun"
-            let media = NSString::from_str("soun");
-            let status: isize = msg_send![cls, authorizationStatusForMediaType: &*media];
-            status == AUTHORIZED
-        }
-    }
-    #[cfg(not(target_os = "macos"))]
-    {
-        true
-    }
-}
-
-/// Map a raw macOS `AVAuthorizationStatus` value to a banner-state string.
-///
-/// Pure helper so the status-enum -> banner-state mapping is unit-testable
-/// without a live TCC database. The returned string is consumed by the frontend
-/// permissions banner, which distinguishes a hard "denied" (show red, offer the
-/// reset path) from a transient "notDetermined"/"unknown" state (do NOT show a
-/// hard denied banner — see issue #190, false-negatives after a dev rebuild or
-/// app move when TCC drops the entry and the status reads not-determined).
-///
-/// `AVAuthorizationStatus` (AVFoundation):
-///   0 = notDetermined, 1 = restricted, 2 = denied, 3 = authorized.
-/// Any unexpected value (e.g. a future/probe-failure sentinel) maps to "unknown"
-/// rather than "denied" so a transient probe glitch never hard-fails the banner.
-fn mic_status_to_banner_state(status: isize) -> &'static str {
-    match status {
-        3 => "granted",
-        2 | 1 => "denied",
-        0 => "notDetermined",
-        _ => "unknown",
-    }
+    permissions::microphone_state() == PermissionState::Granted
 }
 
 /// Read the *current* microphone authorization status as a banner-state string.
 ///
-/// Queries `AVCaptureDevice.authorizationStatus(for: .audio)` live at call-time
-/// (never a cached value) and maps it via [`mic_status_to_banner_state`]. Unlike
-/// the boolean [`check_microphone_permission`], this preserves the distinction
-/// between a genuine "denied" and a transient "notDetermined"/"unknown" state so
-/// the banner doesn't false-negative after a rebuild/move (issue #190).
+/// Queries the live status (never a cached value). Unlike the boolean
+/// [`check_microphone_permission`], this preserves the distinction between a
+/// genuine "denied" and a transient "notDetermined"/"unknown" state so the
+/// banner doesn't false-negative after a rebuild/move (issue #190).
 ///
 /// Returns one of: "granted" | "denied" | "notDetermined" | "unknown".
-/// Like the bool probe, this only reads TCC state — it never opens the device,
-/// so it cannot duck other apps' audio (issue #177).
 #[tauri::command]
 pub fn check_microphone_permission_status() -> String {
-    #[cfg(target_os = "macos")]
-    {
-        use objc2::msg_send;
-        use objc2::runtime::AnyClass;
-        use objc2_foundation::NSString;
-
-        unsafe {
-            let Some(cls) = AnyClass::get(c"AVCaptureDevice") else {
-                // Class lookup failed: a probe glitch, not a real denial.
-                return "unknown".to_string();
-            };
-            // AVMediaTypeAudio == @"soun"
-            let media = NSString::from_str("soun");
-            let status: isize = msg_send![cls, authorizationStatusForMediaType: &*media];
-            mic_status_to_banner_state(status).to_string()
-        }
-    }
-    #[cfg(not(target_os = "macos"))]
-    {
-        "granted".to_string()
-    }
+    permissions::microphone_state().as_str().to_string()
 }
 
 #[tauri::command]
@@ -311,42 +329,19 @@ pub fn list_audio_devices() -> Result<Vec<String>, String> {
     audio::list_input_devices()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::mic_status_to_banner_state;
-
-    #[test]
-    fn authorized_status_maps_to_granted() {
-        // AVAuthorizationStatusAuthorized
-        assert_eq!(mic_status_to_banner_state(3), "granted");
-    }
-
-    #[test]
-    fn denied_and_restricted_map_to_denied() {
-        // AVAuthorizationStatusDenied
-        assert_eq!(mic_status_to_banner_state(2), "denied");
-        // AVAuthorizationStatusRestricted (e.g. MDM/parental controls) — still a
-        // genuine block, so it must read as denied, not a transient state.
-        assert_eq!(mic_status_to_banner_state(1), "denied");
-    }
-
-    #[test]
-    fn not_determined_is_not_a_hard_denial() {
-        // AVAuthorizationStatusNotDetermined: TCC has no entry yet (common right
-        // after a rebuild/move). Must NOT collapse to "denied" (issue #190).
-        let state = mic_status_to_banner_state(0);
-        assert_eq!(state, "notDetermined");
-        assert_ne!(state, "denied");
-    }
+/// Ordered input devices for recordings to try after the selected one. A
+/// recording falls over to the next connected one if its device disappears.
+#[tauri::command]
+pub fn set_input_device_preferences(devices: Vec<String>) {
+    audio::set_device_preferences(devices);
+}
 
-    #[test]
-    fn unexpected_values_map_to_unknown_not_denied() {
-        // Any future/sentinel value from a probe glitch must degrade to "unknown",
-        // never a hard "denied" — we never want to false-negative the banner.
-        for v in [-1isize, 4, 99, isize::MAX, isize::MIN] {
-            let state = mic_status_to_banner_state(v);
-            assert_eq!(state, "unknown", "value {v} should map to unknown");
-            assert_ne!(state, "denied", "value {v} must not map to denied");
-        }
-    }
+/// `warn` or `use_built_in`: what a recording does when its device is a
+/// Bluetooth headset on the low-quality hands-free profile.
+#[tauri::command]
+pub fn set_low_quality_input_action(action: String) -> Result<(), String> {
+    let action = crate::input_quality::LowQualityInputAction::parse(&action)
+        .ok_or_else(|| format!("Unknown low-quality input action '{action}'"))?;
+    crate::input_quality::set_action(action);
+    Ok(())
 }
@@ -0,0 +1,99 @@
+use crate::commands::recording::{app_profile_json, parse_app_profile, reconcile_app_profiles};
+use crate::{MutexExt, State};
+use tauri::Emitter;
+
+/// Publish the full list so the settings UI can persist it. The frontend owns
+/// persistence; these commands only keep the live backend copy authoritative
+/// between `configure_dictation` pushes.
+fn publish_profiles(app_handle: &tauri::AppHandle, profiles: &[crate::state::AppProfile]) {
+    let payload = profiles.iter().map(app_profile_json).collect::<Vec<_>>();
+    let _ = app_handle.emit("app-profiles-changed", payload);
+}
+
+#[tauri::command]
+pub fn list_app_profiles(state: tauri::State<'_, State>) -> Vec<serde_json::Value> {
+    state
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .app_profiles
+        .iter()
+        .map(app_profile_json)
+        .collect()
+}
+
+/// Insert or replace the profile for `profile.bundleId`. Every duplicate entry
+/// for that bundle id is collapsed into the new one so the edited values are
+/// the ones the resolver sees.
+#[tauri::command]
+pub fn upsert_app_profile(
+    app_handle: tauri::AppHandle,
+    profile: serde_json::Value,
+    state: tauri::State<'_, State>,
+) -> Result<serde_json::Value, String> {
+    let profile = parse_app_profile(&profile)
+        .ok_or_else(|| "An app profile needs a non-empty bundle identifier.".to_string())?;
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let position = dictation
+        .app_profiles
+        .iter()
+        .position(|existing| existing.bundle_id == profile.bundle_id);
+    let bundle_id = profile.bundle_id.clone();
+    dictation
+        .app_profiles
+        .retain(|existing| existing.bundle_id != bundle_id);
+    // `position` is the first duplicate, so every entry before it survived the
+    // retain and the replacement keeps the original list order.
+    match position {
+        Some(index) => dictation.app_profiles.insert(index, profile),
+        None => dictation.app_profiles.push(profile),
+    }
+    reconcile_app_profiles(&app_handle, &state.app_state, &dictation.app_profiles);
+    state.app_state.bump_settings_revision();
+    tracing::info!(
+        target: "pipeline",
+        replaced = position.is_some(),
+        profile_count = dictation.app_profiles.len(),
+        "app profile upserted"
+    );
+    let stored = dictation
+        .app_profiles
+        .iter()
+        .find(|existing| existing.bundle_id == bundle_id)
+        .map(app_profile_json)
+        .unwrap_or_default();
+    publish_profiles(&app_handle, &dictation.app_profiles);
+    Ok(stored)
+}
+
+/// Remove every profile for `bundle_id`. Returns whether anything was removed.
+#[tauri::command]
+pub fn delete_app_profile(
+    app_handle: tauri::AppHandle,
+    bundle_id: String,
+    state: tauri::State<'_, State>,
+) -> bool {
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let before = dictation.app_profiles.len();
+    dictation
+        .app_profiles
+        .retain(|profile| profile.bundle_id != bundle_id.trim());
+    let removed = dictation.app_profiles.len() != before;
+    if removed {
+        reconcile_app_profiles(&app_handle, &state.app_state, &dictation.app_profiles);
+        state.app_state.bump_settings_revision();
+        publish_profiles(&app_handle, &dictation.app_profiles);
+    }
+    tracing::info!(target: "pipeline", removed, "app profile delete requested");
+    removed
+}
+
+/// The shipped do-not-inject list, for a "restore defaults" action. The live
+/// list is pushed through `configure_dictation`'s `injectBlocklist`.
+#[tauri::command]
+pub fn get_default_inject_blocklist() -> Vec<String> {
+    crate::state::DEFAULT_INJECT_BLOCKLIST
+        .iter()
+        .map(|bundle_id| bundle_id.to_string())
+        .collect()
+}
@@ -1,17 +1,46 @@
-use crate::dictation_context::{self, DictationContextSnapshot, ResolverInputs, SessionOverrides};
+use crate::append_edit::{self, join_takes};
+use crate::commands::payloads::{
+    ConfigureOptions, StartRecordingKind, StartRecordingResult, StatusResponse, TranscriptionKind,
+    TranscriptionResult,
+};
+use crate::confidence_retry::{self, RetryOutcome};
+use crate::cursor_context::{self, Caret};
+use crate::dictation_context::{
+    self, DeliverySettings, DictationContextSnapshot, ResolverInputs, SessionOverrides,
+};
+use crate::dictation_macros;
+use crate::events;
+use crate::gpu_memory::{self, GpuFallback};
+use crate::hallucination::{self, NoSpeechReason};
+use crate::injection_preview::{
+    self, InjectionPending, InjectionResolved, PendingInjection, PreviewOutcome,
+};
+use crate::language_rules::LanguageRules;
+use crate::llm_sidecar::CancelToken;
+use crate::markers::{self, Markers, TranscriptSegment};
 use crate::model_runtime::{self, PreparationReason};
 use crate::performance_metrics::{
     AcceleratorV1, ContentFreeInputSummaryV1, ModelWarmStateV1, PerformanceStageV1,
     PerformanceRunGuard, RunCorrelationV1, RunOutcomeV1, RuntimeBackendV1, RuntimeIdentityV1,
     RuntimeRoleV1, StableRunErrorV1, StageOutcomeV1, StageTimingV1,
 };
-use crate::state::{AppState, DictationStatus};
+use crate::pipeline_trace::{self, PipelinePhase};
+use crate::rewrite_templates::{self, RewriteTemplate};
+use crate::selection::{self, TransformSnapshot};
+use crate::state::{
+    AppState, DictationStatus, InterruptedRecordingAction, NotesTarget, NotificationPolicy,
+    SelectionDictation, TrailingBehavior,
+};
 use crate::transcriber;
-use crate::{audio, audio_decode, injector, keyboard, vad};
+use crate::transform_apply::{self, ApplyError};
+use crate::transform_flow::DEFAULT_TRANSFORM_DEADLINE;
+use crate::{audio, audio_decode, injector, keyboard, notes, vad};
 use crate::{MutexExt, State};
+use std::ops::Range;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tauri::{Emitter, Manager};
+use tracing::Instrument;
 
 /// Max number of code identifiers fed to Whisper as an initial prompt. Whisper
 /// truncates the prompt to its ~224-token context window anyway, so this keeps
@@ -149,6 +178,7 @@ fn resolve_live_context(
     app_state: &AppState,
     knowledge: &crate::knowledge_store::KnowledgeStore,
     bundle_id: Option<&str>,
+    session_overrides: SessionOverrides,
 ) -> Arc<DictationContextSnapshot> {
     let repository_voice_commands = match knowledge.voice_commands_for_context(bundle_id) {
         Ok(entries) => Some(crate::voice_commands::commands_from_knowledge(entries)),
@@ -176,12 +206,14 @@ fn resolve_live_context(
             &dictation.app_profiles,
         )
         .replace('\0', "");
-        let prompt = combine_prompts(&sanitized, &code_vocab);
+        let prompt = combine_prompts(&with_learned_terms(&sanitized, &dictation), &code_vocab);
         let correction_matcher = app_state
             .correction_matcher
             .lock_or_recover()
             .as_ref()
             .map(|matchers| matchers.select(bundle_id));
+        let redactor = app_state.redactor.lock_or_recover().clone();
+        let replacement_rules = app_state.replacement_rules.lock_or_recover().clone();
         let ide_context_index = bundle_id.and_then(|bundle_id| {
             dictation
                 .app_profiles
@@ -201,14 +233,57 @@ fn resolve_live_context(
             global: &dictation,
             prompt,
             correction_matcher,
+            redactor,
+            replacement_rules,
             ide_context_index,
             vocabulary_version,
             voice_commands: repository_voice_commands.clone(),
-            session_overrides: SessionOverrides::default(),
+            session_overrides: session_overrides.clone(),
+            model_installed: model_runtime::model_installed,
+            power_pressure: crate::system_status::power_pressure(),
         }));
     }
 }
 
+/// Payload of `model-downgraded`.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelDowngradedEvent<'a> {
+    recording_id: u64,
+    from_model: &'a str,
+    to_model: &'a str,
+    reason: crate::system_status::PowerPressure,
+}
+
+/// Say when this recording runs on the adaptive fallback model, so a weaker
+/// transcript isn't a mystery.
+fn announce_model_downgrade(
+    app_handle: &tauri::AppHandle,
+    recording_id: u64,
+    context: &DictationContextSnapshot,
+) {
+    let Some(downgrade) = &context.transcription.model_downgrade else {
+        return;
+    };
+    tracing::info!(
+        target: "pipeline",
+        recording_id,
+        from_model = downgrade.from_model.as_str(),
+        to_model = context.transcription.model_name.as_str(),
+        reason = downgrade.reason.as_str(),
+        "model_downgraded"
+    );
+    let _ = app_handle.emit(
+        "model-downgraded",
+        ModelDowngradedEvent {
+            recording_id,
+            from_model: &downgrade.from_model,
+            to_model: &context.transcription.model_name,
+            reason: downgrade.reason,
+        },
+    );
+}
+
 /// Merge the manual custom-vocabulary string and the code-aware vocabulary into a
 /// single Whisper initial prompt. Returns `None` when both are blank so the
 /// backend skips setting a prompt entirely.
@@ -245,6 +320,16 @@ fn dedupe_prompt_terms(prompt: &str) -> String {
         .join(" ")
 }
 
+/// The hand-typed vocabulary followed by the phrases learned from history
+/// corrections, when learning is on. Learned phrases come second so they
+/// never crowd out typed terms when Whisper truncates the prompt.
+fn with_learned_terms(custom: &str, dictation: &crate::state::DictationState) -> String {
+    if !dictation.learn_from_corrections {
+        return custom.to_string();
+    }
+    format!("{} {}", custom, crate::correction_feedback::prompt_terms())
+}
+
 /// Split the free-text custom-vocabulary field into individual written terms.
 /// Entries are separated by commas or newlines (not spaces) so multi-word terms
 /// like "API Gateway" survive as one entry. Blank entries are dropped.
@@ -329,16 +414,32 @@ impl Drop for IdleGuard<'_> {
             // a concurrent start advances the ID between our check and the
             // status write.
             let mut dictation = self.app_state.dictation.lock_or_recover();
+            dictation.finish_job(self.recording_id);
             let current_rid = self.app_state.recording_id.load(Ordering::SeqCst);
             if current_rid != self.recording_id {
                 return;
             }
-            dictation.status = DictationStatus::Idle;
+            dictation.status = dictation.settled_status();
             keyboard::set_processing(false);
         }
     }
 }
 
+/// Status a finished or abandoned take settles to: `Processing` while earlier
+/// takes are still queued, then `Idle`, or `Buffering` while append-mode takes
+/// await commit.
+fn settled_status(app_state: &AppState) -> DictationStatus {
+    app_state.dictation.lock_or_recover().settled_status()
+}
+
+/// Take a stopped recording that will not be transcribed off the queue and
+/// return the status it leaves behind.
+fn abandon_job(app_state: &AppState, recording_id: u64) -> DictationStatus {
+    let mut dictation = app_state.dictation.lock_or_recover();
+    dictation.finish_job(recording_id);
+    dictation.settled_status()
+}
+
 /// RAII guard that clears `file_transcribing` on drop, so a file transcription
 /// releases its mutual-exclusion claim on every return path (early errors, `?`,
 /// or success).
@@ -402,13 +503,14 @@ fn spawn_model_preparation(app_handle: tauri::AppHandle, model_name: String, rec
         if !is_still_active {
             return;
         }
+        let Some(context) = state.app_state.active_context(recording_id) else {
+            return;
+        };
+        let (runtime, lifecycle_app) =
+            transcription_runtime(&state.app_state, &app_handle, &context.transcription);
 
         let rss_before_mb = crate::resource_monitor::get_process_rss_mb();
-        let result = state.app_state.model_runtime.prepare(
-            Some(&app_handle),
-            &model_name,
-            PreparationReason::Recording,
-        );
+        let result = runtime.prepare(lifecycle_app, &model_name, PreparationReason::Recording);
         let rss_after_mb = crate::resource_monitor::get_process_rss_mb();
         let total_ms = queued_at.elapsed().as_millis() as u64;
         match result {
@@ -481,7 +583,7 @@ fn spawn_idle_model_preparation(
         let state = app_handle.state::<State>();
         let is_current = {
             let dictation = state.app_state.dictation.lock_or_recover();
-            dictation.status == DictationStatus::Idle && dictation.model_name == model_name
+            dictation.status.is_settled() && dictation.model_name == model_name
         };
         if !is_current {
             return;
@@ -489,7 +591,7 @@ fn spawn_idle_model_preparation(
 
         let is_still_current = {
             let dictation = state.app_state.dictation.lock_or_recover();
-            dictation.status == DictationStatus::Idle && dictation.model_name == model_name
+            dictation.status.is_settled() && dictation.model_name == model_name
         };
         if !is_still_current {
             return;
@@ -525,12 +627,67 @@ fn spawn_idle_model_preparation(
     });
 }
 
+/// The GPU ran out of memory loading or running the take's model: decode it
+/// again on the CPU, then with a smaller installed model, and say which
+/// worked with `gpu-memory-pressure`. The original error stands when neither
+/// does.
+fn recover_from_gpu_memory<T>(
+    app_handle: &tauri::AppHandle,
+    recording_id: u64,
+    runtime: &model_runtime::ModelRuntimeManager,
+    lifecycle_app: Option<&tauri::AppHandle>,
+    transcription: &dictation_context::TranscriptionSettings,
+    error: String,
+    mut decode: impl FnMut(&str) -> Result<T, String>,
+) -> Result<T, String> {
+    let model = transcription.model_name.as_str();
+    tracing::warn!(target: "pipeline", recording_id, model, "decode ran out of GPU memory; retrying on the CPU");
+    if let Err(fallback_error) = runtime.fall_back_to_cpu(lifecycle_app) {
+        tracing::warn!(target: "pipeline", recording_id, "CPU fallback unload failed: {}", fallback_error);
+    }
+    match decode(model) {
+        Ok(decoded) => {
+            gpu_memory::report(app_handle, recording_id, model, GpuFallback::Cpu, None);
+            return Ok(decoded);
+        }
+        Err(cpu_error) => {
+            tracing::warn!(target: "pipeline", recording_id, "CPU retry failed: {}", cpu_error);
+        }
+    }
+    if let Some(smaller) = gpu_memory::smaller_model(
+        model,
+        &transcription.language,
+        model_runtime::model_installed,
+    ) {
+        match decode(smaller) {
+            Ok(decoded) => {
+                gpu_memory::report(
+                    app_handle,
+                    recording_id,
+                    model,
+                    GpuFallback::SmallerModel,
+                    Some(smaller),
+                );
+                return Ok(decoded);
+            }
+            Err(smaller_error) => {
+                tracing::warn!(target: "pipeline", recording_id, smaller, "smaller model retry failed: {}", smaller_error);
+            }
+        }
+    }
+    gpu_memory::report(app_handle, recording_id, model, GpuFallback::Failed, None);
+    Err(error)
+}
+
 #[derive(Default)]
 pub(crate) struct PipelineTimings {
     pub vad_ms: u64,
     pub model_queue_ms: u64,
     pub model_load_ms: u64,
     pub decode_ms: u64,
+    /// Decode time of the pass whose confidence was kept, so tokens per
+    /// second divides one decode's tokens by that same decode's time.
+    pub token_decode_ms: u64,
     pub inference_ms: u64,
     pub transform_ms: u64,
     pub transform_stages: Vec<StageTimingV1>,
@@ -545,7 +702,7 @@ pub(crate) struct PipelineTimings {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PipelineTerminal {
     Success,
-    NoSpeech,
+    NoSpeech(NoSpeechReason),
     Cancelled(PerformanceStageV1),
 }
 
@@ -553,6 +710,54 @@ struct PipelineResult {
     text: String,
     timings: PipelineTimings,
     terminal: PipelineTerminal,
+    /// The speech the draft was transcribed from, kept for the background
+    /// refinement. Only set for a delivered dual-model draft.
+    refine_audio: Option<RefineAudio>,
+    /// Template the text is still to be rewritten with; the text was not
+    /// delivered and `spawn_rewrite` delivers it once the pipeline settles.
+    rewrite: Option<RewriteTemplate>,
+    /// Selection the text is still to replace or rewrite; the text was not
+    /// delivered and `spawn_selection_delivery` does it once the pipeline settles.
+    selection: Option<TransformSnapshot>,
+    /// How sure the model was of the raw transcript, for backends that say.
+    confidence: Option<transcriber::TranscriptConfidence>,
+    /// The speech a delivered Whisper transcript came from, kept for the
+    /// background alternatives decode. Only set when alternatives are on.
+    alternatives_audio: Option<AlternativesAudio>,
+    /// The text split at markers; None when the dictation had none.
+    segments: Option<Vec<TranscriptSegment>>,
+}
+
+struct AlternativesAudio {
+    samples: Vec<f32>,
+    vad_trimmed: bool,
+}
+
+struct RefineAudio {
+    samples: Vec<f32>,
+    vad_trimmed: bool,
+    /// The draft was auto-pasted into the focused app.
+    pasted: bool,
+    /// The caret the draft was delivered to, so the refined text is fitted
+    /// the same way.
+    caret: Caret,
+}
+
+/// The runtime that owns `transcription.model_name`, and the handle its
+/// lifecycle events go to. Dual-model drafts use the quiet draft runtime.
+fn transcription_runtime<'a>(
+    app_state: &'a AppState,
+    app_handle: &'a tauri::AppHandle,
+    transcription: &dictation_context::TranscriptionSettings,
+) -> (
+    &'a model_runtime::ModelRuntimeManager,
+    Option<&'a tauri::AppHandle>,
+) {
+    if transcription.refinement.is_some() {
+        (&app_state.draft_runtime, None)
+    } else {
+        (&app_state.model_runtime, Some(app_handle))
+    }
 }
 
 fn runtime_identity(model_name: &str, warm_state: ModelWarmStateV1) -> Vec<RuntimeIdentityV1> {
@@ -571,6 +776,8 @@ pub(crate) fn runtime_identity_for_role(
         model_runtime::BackendKind::Whisper => RuntimeBackendV1::Whisper,
         model_runtime::BackendKind::Parakeet => RuntimeBackendV1::Parakeet,
         model_runtime::BackendKind::Coreml => RuntimeBackendV1::Coreml,
+        model_runtime::BackendKind::External => RuntimeBackendV1::External,
+        model_runtime::BackendKind::Cloud => RuntimeBackendV1::Cloud,
     };
     let accelerator = match model_runtime::model_accelerator(definition) {
         "CPU" => AcceleratorV1::Cpu,
@@ -643,6 +850,18 @@ fn pipeline_stages(timings: &PipelineTimings, total_ms: u64) -> Vec<StageTimingV
     stages
 }
 
+/// Tell the UI why a recording ended without text, so it can say so instead of
+/// silently returning to idle. The payload carries a stable reason code only.
+fn emit_no_speech(app_handle: &tauri::AppHandle, recording_id: u64, reason: NoSpeechReason) {
+    let _ = app_handle.emit(
+        "no-speech-detected",
+        serde_json::json!({
+            "recordingId": recording_id,
+            "reason": reason.as_str()
+        }),
+    );
+}
+
 #[allow(clippy::too_many_arguments)]
 fn transcribe_with_coreml_vad_retry(
     backend: &mut dyn transcriber::TranscriptionBackend,
@@ -675,159 +894,78 @@ fn transcribe_with_coreml_vad_retry(
     Ok(text)
 }
 
-/// Shared transcription pipeline: model init -> transcribe -> inject text -> set idle.
-/// `recording_id` is checked against `app_state.cancelled_id` at checkpoints;
-/// if cancelled, returns empty text without clipboard write or paste.
-async fn run_transcription_pipeline(
+/// VAD over each marked section on its own, so the markers still fall
+/// between sections of the trimmed audio. Returns the trimmed audio and each
+/// section's range in it, empty for a section without speech.
+fn filter_sections(
+    vad_path: &str,
     samples: &[f32],
-    app_handle: &tauri::AppHandle,
-    app_state: &AppState,
-    recording_id: u64,
-    performance_guard: &mut PerformanceRunGuard,
-    context: Arc<DictationContextSnapshot>,
-) -> Result<PipelineResult, String> {
-    // Guard resets status to Idle on any return path (error or success),
-    // but only if this recording is still the active one
-    let _guard = IdleGuard::new(app_state, recording_id);
-
-    let transcription = &context.transcription;
-    let transformations = &context.transformations;
-    let delivery = &context.delivery;
-    // When saving to a file, suppress auto-paste into the focused app. The
-    // clipboard write inside `inject_text` is unconditional, so text remains
-    // copyable regardless of these toggles.
-    let effective_auto_paste =
-        delivery.auto_paste && !(delivery.save_transcript || delivery.save_audio);
-
-    // Pre-VAD signal level logging for mic diagnosis
-    let rms = audio::compute_rms(samples);
-    let peak = audio::compute_peak(samples);
-    let device = audio::last_device_name().unwrap_or_else(|| "unknown".to_string());
-    tracing::info!(target: "pipeline", "audio rms={:.4} peak={:.4} (device={})", rms, peak, device);
-
-    // Checkpoint 1: cancelled before VAD?
-    if app_state.is_cancelled(recording_id) {
-        tracing::info!(target: "pipeline", "cancelled before VAD (recording_id={})", recording_id);
-        return Ok(PipelineResult {
-            text: String::new(),
-            timings: PipelineTimings::default(),
-            terminal: PipelineTerminal::Cancelled(PerformanceStageV1::Vad),
-        });
+    sections: &[Range<usize>],
+    threshold: f32,
+) -> Result<(vad::VadResult, Vec<Range<usize>>), String> {
+    let mut trimmed = Vec::with_capacity(samples.len());
+    let mut ranges = Vec::with_capacity(sections.len());
+    for section in sections {
+        let start = trimmed.len();
+        if let vad::VadResult::Speech(speech) =
+            vad::filter_speech(vad_path, &samples[section.clone()], threshold)?
+        {
+            trimmed.extend(speech);
+        }
+        ranges.push(start..trimmed.len());
     }
+    if trimmed.is_empty() {
+        return Ok((vad::VadResult::NoSpeech, Vec::new()));
+    }
+    Ok((vad::VadResult::Speech(trimmed), ranges))
+}
 
-    // Every backend uses one authoritative full-buffer VAD + inference pass
-    // after recording stops.
-    let vad_threshold = 1.0 - (transcription.vad_sensitivity as f32 / 100.0);
-    performance_guard.enter(PerformanceStageV1::Vad);
-    let t_vad = std::time::Instant::now();
-    let (samples_for_transcription, vad_trimmed) = match vad::vad_model_path() {
-        Some(vad_path) if vad_path.exists() => {
-            let vad_path_str = vad_path.to_string_lossy().to_string();
-            let samples_owned = samples.to_vec();
-            let vad_result = tokio::task::spawn_blocking(move || {
-                vad::filter_speech(&vad_path_str, &samples_owned, vad_threshold)
-            })
-            .await
-            .unwrap_or_else(|e| Err(format!("VAD task panicked: {}", e)));
-
-            match vad_result {
-                Ok(vad::VadResult::NoSpeech) => {
-                    tracing::info!(target: "pipeline", "VAD detected no speech ({} samples, {:?}), skipping transcription",
-                            samples.len(), t_vad.elapsed());
-                    return Ok(PipelineResult {
-                        text: String::new(),
-                        timings: PipelineTimings {
-                            vad_ms: t_vad.elapsed().as_millis() as u64,
-                            ..PipelineTimings::default()
-                        },
-                        terminal: PipelineTerminal::NoSpeech,
-                    });
-                }
-                Ok(vad::VadResult::Speech(trimmed)) => {
-                    tracing::info!(target: "pipeline", "VAD trimmed {} -> {} samples ({:.0}% speech, {:?})",
-                            samples.len(), trimmed.len(),
-                            trimmed.len() as f64 / samples.len() as f64 * 100.0,
-                            t_vad.elapsed());
-                    let vad_trimmed = trimmed.len() != samples.len();
-                    (trimmed, vad_trimmed)
-                }
-                Err(e) => {
-                    tracing::warn!(target: "pipeline", "VAD failed ({}), proceeding without filtering", e);
-                    (samples.to_vec(), false)
-                }
-            }
+/// Decode each marked section on its own. A section without speech comes
+/// back empty; the confidence covers every section.
+fn transcribe_sections(
+    backend: &mut dyn transcriber::TranscriptionBackend,
+    samples: &[f32],
+    sections: &[Range<usize>],
+    language: &str,
+    prompt: Option<&str>,
+    smart_punctuation: bool,
+) -> Result<(Vec<String>, Option<transcriber::TranscriptConfidence>), String> {
+    let mut texts = Vec::with_capacity(sections.len());
+    let mut confidence = Vec::new();
+    for section in sections {
+        if section.is_empty() {
+            texts.push(String::new());
+            continue;
         }
-        _ => {
-            let handle = app_handle.clone();
-            tokio::spawn(async move {
-                if let Err(e) = super::models::ensure_vad_model(&handle).await {
-                    tracing::warn!(target: "pipeline", "VAD model download failed ({}), skipping VAD", e);
-                }
-            });
-            (samples.to_vec(), false)
+        texts.push(backend.transcribe(
+            &samples[section.clone()],
+            language,
+            prompt,
+            smart_punctuation,
+        )?);
+        if let Some(section_confidence) = backend.last_confidence() {
+            confidence.extend(section_confidence.segments);
         }
-    };
-    let vad_ms = t_vad.elapsed().as_millis() as u64;
-
-    if app_state.is_cancelled(recording_id) {
-        tracing::info!(target: "pipeline", "cancelled before transcription (recording_id={})", recording_id);
-        return Ok(PipelineResult {
-            text: String::new(),
-            timings: PipelineTimings {
-                vad_ms,
-                ..PipelineTimings::default()
-            },
-            terminal: PipelineTerminal::Cancelled(PerformanceStageV1::InferenceDecode),
-        });
     }
+    Ok((
+        texts,
+        transcriber::TranscriptConfidence::from_segments(confidence),
+    ))
+}
 
-    let rss_before_mb = crate::resource_monitor::get_process_rss_mb();
-    performance_guard.enter(PerformanceStageV1::InferenceDecode);
-    let t_transcribe = std::time::Instant::now();
-    let mut decode_ms = 0;
-    let (text, load_report) = app_state.model_runtime.with_ready_backend(
-        Some(app_handle),
-        &transcription.model_name,
-        PreparationReason::Pipeline,
-        |backend| {
-            let decode_started = std::time::Instant::now();
-            let result = transcribe_with_coreml_vad_retry(
-                backend,
-                &transcription.model_name,
-                &samples_for_transcription,
-                samples,
-                vad_trimmed,
-                &transcription.language,
-                transcription.prompt.as_deref(),
-                transcription.smart_punctuation,
-            );
-            decode_ms = decode_started.elapsed().as_millis() as u64;
-            result
-        },
-    )?;
-    let model_load_ms = load_report.load_ms;
-    let inference_ms = t_transcribe.elapsed().as_millis() as u64;
-    let rss_after_mb = crate::resource_monitor::get_process_rss_mb();
-    tracing::info!(target: "pipeline", "transcription ({} samples): {:?}", samples_for_transcription.len(), t_transcribe.elapsed());
-    let mut timings = PipelineTimings {
-        vad_ms,
-        model_queue_ms: load_report.lock_wait_ms,
-        model_load_ms,
-        decode_ms,
-        inference_ms,
-        rss_before_mb,
-        rss_after_mb,
-        warm_state: Some(if load_report.cache_hit {
-            ModelWarmStateV1::Warm
-        } else {
-            ModelWarmStateV1::ColdLoaded
-        }),
-        ..PipelineTimings::default()
-    };
-
-    // Post-recognition transformation is backend-neutral and ordered in one
-    // authoritative entry point. Its stage config and resources come from the
-    // immutable recording-start snapshot rather than mutable app settings.
+/// Run the live post-recognition transforms over `text` with the stage config
+/// and resources from the recording-start snapshot. `detected_language` is
+/// what the decode reported when the language is auto, and picks the cleanup
+/// rules.
+fn transform_live_transcript(
+    app_state: &AppState,
+    context: &DictationContextSnapshot,
+    recording_id: u64,
+    text: String,
+    detected_language: Option<&str>,
+) -> Result<crate::transcript_transform::TranscriptPipelineOutput, String> {
+    let transcription = &context.transcription;
+    let transformations = &context.transformations;
     let custom_commands: Vec<(String, String)> = transformations
         .voice_commands
         .iter()
@@ -860,42 +998,1392 @@ async fn run_transcription_pipeline(
         cli_lexicon,
         ide_context_index: transformations.ide_context_index.clone(),
         voice_command_runtime: None,
+        redactor: transformations.redactor.clone(),
+        replacement_rules: transformations.replacement_rules.clone(),
+        language: LanguageRules::resolve(&transcription.language, detected_language),
     };
-    let transform_started = std::time::Instant::now();
-    performance_guard.enter(PerformanceStageV1::TranscriptTransform);
-    let transformed = crate::transcript_transform::transform_transcript(
-        text,
-        &transform_context,
-        transform_resources,
-    )
-    .map_err(|error| error.to_string())?;
-    let transform_ms = transform_started.elapsed().as_millis() as u64;
-    tracing::info!(
-        target: "pipeline",
-        changed = transformed.was_changed(),
-        "transcript_transform_complete"
-    );
-    let correction_ms =
-        transformed.stage_duration_ms(crate::transcript_transform::SMART_CORRECTION_STAGE);
-    let transform_stages = transformed
-        .stages
-        .iter()
-        .filter_map(transcript_stage_timing)
-        .collect();
-    let text = transformed.text;
+    crate::transcript_transform::transform_transcript(text, &transform_context, transform_resources)
+        .map_err(|error| error.to_string())
+}
 
-    // Update last_transcription_at for idle timeout tracking
-    *app_state.last_transcription_at.lock_or_recover() = Some(std::time::Instant::now());
-    // Checkpoint 3: cancelled before text injection?
-    if app_state.is_cancelled(recording_id) {
-        tracing::info!(target: "pipeline", "cancelled before injection (recording_id={})", recording_id);
-        timings.correction_ms = correction_ms;
-        timings.transform_ms = transform_ms;
-        timings.transform_stages = transform_stages;
+/// Run the live transforms over each segment of a marked transcript on its
+/// own, so no stage joins text across a marker, and join the results with a
+/// section break. Stage timings add up across segments; segments the
+/// transforms emptied are dropped.
+fn transform_segments(
+    app_state: &AppState,
+    context: &DictationContextSnapshot,
+    recording_id: u64,
+    segments: Vec<TranscriptSegment>,
+    detected_language: Option<&str>,
+) -> Result<
+    (
+        crate::transcript_transform::TranscriptPipelineOutput,
+        Vec<TranscriptSegment>,
+    ),
+    String,
+> {
+    let mut original_texts = Vec::with_capacity(segments.len());
+    let mut rules_inputs = Vec::with_capacity(segments.len());
+    let mut stages: Vec<crate::transcript_transform::StageReport> = Vec::new();
+    let mut transformed = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let output = transform_live_transcript(
+            app_state,
+            context,
+            recording_id,
+            segment.text,
+            detected_language,
+        )?;
+        for report in output.stages {
+            match stages.iter_mut().find(|total| total.stage == report.stage) {
+                Some(total) => {
+                    total.duration_us += report.duration_us;
+                    total.changed |= report.changed;
+                }
+                None => stages.push(report),
+            }
+        }
+        original_texts.push(output.original_text);
+        rules_inputs.push(output.rules_input);
+        if !output.text.trim().is_empty() {
+            transformed.push(TranscriptSegment {
+                text: output.text,
+                start_ms: segment.start_ms,
+                end_ms: segment.end_ms,
+            });
+        }
+    }
+    let text = transformed
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(markers::SECTION_BREAK);
+    let output = crate::transcript_transform::TranscriptPipelineOutput {
+        original_text: original_texts.join(markers::SECTION_BREAK),
+        rules_input: rules_inputs.join(markers::SECTION_BREAK),
+        text,
+        stages,
+    };
+    Ok((output, transformed))
+}
+
+/// Payload of `transcription-refined`.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptionRefinedEvent<'a> {
+    recording_id: u64,
+    draft_model: &'a str,
+    model: &'a str,
+    draft: &'a str,
+    text: &'a str,
+    diff: Vec<crate::text_metrics::DiffSpan>,
+    /// Whether the pasted draft was replaced in the focused app.
+    replaced: bool,
+}
+
+/// Shows a draft as the overlay's partial text while it is refined, and puts
+/// the overlay back to idle on drop unless another recording has started.
+struct RefiningOverlay<'a> {
+    app_handle: &'a tauri::AppHandle,
+    app_state: &'a AppState,
+    recording_id: u64,
+}
+
+impl<'a> RefiningOverlay<'a> {
+    fn show(
+        app_handle: &'a tauri::AppHandle,
+        app_state: &'a AppState,
+        recording_id: u64,
+        draft: &str,
+    ) -> Option<Self> {
+        let overlay = Self {
+            app_handle,
+            app_state,
+            recording_id,
+        };
+        if !overlay.is_latest() {
+            return None;
+        }
+        events::emit_overlay_state(
+            app_handle,
+            &events::OverlayState::Processing {
+                partial: Some(draft.to_string()),
+            },
+        );
+        Some(overlay)
+    }
+
+    fn is_latest(&self) -> bool {
+        let dictation = self.app_state.dictation.lock_or_recover();
+        self.app_state.recording_id.load(Ordering::SeqCst) == self.recording_id
+            && dictation.status == DictationStatus::Idle
+    }
+}
+
+impl Drop for RefiningOverlay<'_> {
+    fn drop(&mut self) {
+        if self.is_latest() {
+            events::emit_overlay_state(self.app_handle, &events::OverlayState::Idle);
+        }
+    }
+}
+
+/// Dual-model mode: re-transcribe a delivered draft's audio with the chosen
+/// model in the background and emit `transcription-refined` when the text
+/// changes. With `replaceDraft`, the pasted draft is swapped for the refined
+/// text unless another recording has started since.
+fn spawn_refinement(
+    app_handle: tauri::AppHandle,
+    recording_id: u64,
+    context: Arc<DictationContextSnapshot>,
+    draft: String,
+    original_samples: Vec<f32>,
+    audio: RefineAudio,
+) {
+    let Some(refinement) = context.transcription.refinement.clone() else {
+        return;
+    };
+    let _ = tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<State>();
+        let _overlay = RefiningOverlay::show(&app_handle, &state.app_state, recording_id, &draft);
+        let transcription = &context.transcription;
+        let started = std::time::Instant::now();
+        let result = state.app_state.model_runtime.with_ready_backend(
+            Some(&app_handle),
+            &refinement.model_name,
+            PreparationReason::Pipeline,
+            |backend| {
+                backend.set_whisper_decoding(&transcription.whisper_decoding);
+                transcribe_with_coreml_vad_retry(
+                    backend,
+                    &refinement.model_name,
+                    &audio.samples,
+                    &original_samples,
+                    audio.vad_trimmed,
+                    &transcription.language,
+                    transcription.prompt.as_deref(),
+                    transcription.smart_punctuation,
+                )
+                .map(|text| (text, backend.last_language()))
+            },
+        );
+        let (text, detected_language) = match result {
+            Ok(((text, detected_language), _)) if !text.trim().is_empty() => {
+                (text, detected_language)
+            }
+            Ok(_) => return,
+            Err(error) => {
+                tracing::warn!(target: "pipeline", recording_id, "refinement failed: {}", error);
+                return;
+            }
+        };
+        let refined = match transform_live_transcript(
+            &state.app_state,
+            &context,
+            recording_id,
+            text,
+            detected_language,
+        ) {
+            Ok(transformed) => transformed.text,
+            Err(error) => {
+                tracing::warn!(target: "pipeline", recording_id, "refinement transform failed: {}", error);
+                return;
+            }
+        };
+        *state.app_state.last_transcription_at.lock_or_recover() = Some(std::time::Instant::now());
+        let changed = refined != draft;
+
+        let delivery = &context.delivery;
+        let latest = {
+            let dictation = state.app_state.dictation.lock_or_recover();
+            state.app_state.recording_id.load(Ordering::SeqCst) == recording_id
+                && dictation.status == DictationStatus::Idle
+        };
+        let mut replaced = false;
+        if changed && refinement.replace_draft && audio.pasted && latest {
+            let previous = audio.caret.deliver(&draft, delivery.trailing);
+            let replacement = audio.caret.deliver(&refined, delivery.trailing);
+            let method = delivery.injection_method;
+            let (tx, rx) = std::sync::mpsc::channel();
+            if app_handle
+                .run_on_main_thread(move || {
+                    let _ = tx.send(injector::replace_recent_text(
+                        &previous,
+                        &replacement,
+                        method,
+                    ));
+                })
+                .is_ok()
+            {
+                match rx.recv_timeout(std::time::Duration::from_secs(2)) {
+                    Ok(Ok(())) => replaced = true,
+                    Ok(Err(error)) => {
+                        tracing::warn!(target: "pipeline", recording_id, "draft replacement failed: {}", error)
+                    }
+                    Err(_) => {
+                        tracing::warn!(target: "pipeline", recording_id, "draft replacement timed out")
+                    }
+                }
+            }
+        }
+        // How far the draft was from the refined text, as counts only.
+        let (draft_errors, refined_words) = crate::text_metrics::word_errors(&refined, &draft);
+        tracing::info!(
+            target: "pipeline",
+            recording_id,
+            draft_model = transcription.model_name.as_str(),
+            model = refinement.model_name.as_str(),
+            total_ms = started.elapsed().as_millis() as u64,
+            changed,
+            replaced,
+            draft_wer = crate::text_metrics::error_rate(draft_errors, refined_words),
+            "refinement complete"
+        );
+        if changed {
+            let _ = app_handle.emit(
+                "transcription-refined",
+                TranscriptionRefinedEvent {
+                    recording_id,
+                    draft_model: &transcription.model_name,
+                    model: &refinement.model_name,
+                    draft: &draft,
+                    text: &refined,
+                    diff: crate::text_metrics::word_diff(&draft, &refined),
+                    replaced,
+                },
+            );
+        }
+    });
+}
+
+/// Decode a delivered Whisper transcript's audio again at rising temperatures
+/// in the background and emit `transcription-alternatives` with the words the
+/// hypotheses read differently. Aborts, mid-decode included, once another
+/// recording starts, so it never holds the model while the next dictation
+/// waits for it, and emits nothing for a superseded recording.
+fn spawn_alternatives(
+    app_handle: tauri::AppHandle,
+    recording_id: u64,
+    context: Arc<DictationContextSnapshot>,
+    text: String,
+    original_samples: Vec<f32>,
+    audio: AlternativesAudio,
+) {
+    let _ = tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<State>();
+        let transcription = &context.transcription;
+        let started = std::time::Instant::now();
+        let superseded: transcriber::AbortCheck = {
+            let app_handle = app_handle.clone();
+            Arc::new(move || {
+                app_handle
+                    .state::<State>()
+                    .app_state
+                    .recording_id
+                    .load(Ordering::SeqCst)
+                    != recording_id
+            })
+        };
+        let mut hypotheses = Vec::new();
+        for decoding in crate::alternatives::sampling_passes(
+            transcription.whisper_decoding,
+            transcription.alternatives,
+        ) {
+            if superseded() {
+                break;
+            }
+            let result = state.app_state.model_runtime.with_ready_backend(
+                Some(&app_handle),
+                &transcription.model_name,
+                PreparationReason::Pipeline,
+                |backend| {
+                    if superseded() {
+                        return Err("Superseded by a new recording".to_string());
+                    }
+                    backend.set_whisper_decoding(&decoding);
+                    backend.set_abort(Some(superseded.clone()));
+                    let result = transcribe_with_coreml_vad_retry(
+                        backend,
+                        &transcription.model_name,
+                        &audio.samples,
+                        &original_samples,
+                        audio.vad_trimmed,
+                        &transcription.language,
+                        transcription.prompt.as_deref(),
+                        transcription.smart_punctuation,
+                    );
+                    backend.set_abort(None);
+                    backend.set_whisper_decoding(&transcription.whisper_decoding);
+                    result.map(|text| (text, backend.last_language()))
+                },
+            );
+            let (hypothesis, detected_language) = match result {
+                Ok(((hypothesis, detected_language), _)) if !hypothesis.trim().is_empty() => {
+                    (hypothesis, detected_language)
+                }
+                Ok(_) => continue,
+                Err(_) if superseded() => break,
+                Err(error) => {
+                    tracing::warn!(target: "pipeline", recording_id, "alternatives decode failed: {}", error);
+                    break;
+                }
+            };
+            match transform_live_transcript(
+                &state.app_state,
+                &context,
+                recording_id,
+                hypothesis,
+                detected_language,
+            ) {
+                Ok(transformed) => hypotheses.push(transformed.text),
+                Err(error) => {
+                    tracing::warn!(target: "pipeline", recording_id, "alternatives transform failed: {}", error);
+                }
+            }
+        }
+        if superseded() {
+            tracing::info!(target: "pipeline", recording_id, "alternatives abandoned: a new recording started");
+            return;
+        }
+        let spans = crate::alternatives::spans(&text, &hypotheses);
+        tracing::info!(
+            target: "pipeline",
+            recording_id,
+            hypotheses = hypotheses.len(),
+            spans = spans.len(),
+            total_ms = started.elapsed().as_millis() as u64,
+            "alternatives complete"
+        );
+        if !spans.is_empty() {
+            crate::alternatives::emit(
+                &app_handle,
+                &crate::alternatives::TranscriptionAlternatives {
+                    recording_id,
+                    text,
+                    spans,
+                },
+            );
+        }
+    });
+}
+
+/// The rewrite template for a finished transcript and the text to rewrite. A
+/// spoken "format as <name>" prefix wins over the dictation profile's
+/// template and is dropped from the text.
+fn select_rewrite(
+    transformations: &dictation_context::TransformationSettings,
+    text: String,
+) -> (Option<RewriteTemplate>, String) {
+    match rewrite_templates::match_spoken_prefix(&text, &transformations.rewrite_templates) {
+        Some((template, body)) => (Some(template.clone()), body),
+        None => (transformations.rewrite_template.clone(), text),
+    }
+}
+
+/// Payload of `transcription-rewritten`.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptionRewrittenEvent<'a> {
+    recording_id: u64,
+    template: &'a str,
+    original: &'a str,
+    text: &'a str,
+}
+
+/// Rewrite a finished transcript with `template` on the local transform model,
+/// then deliver it the way the pipeline would have. Runs once the pipeline has
+/// settled, because the transform runtime refuses while the ASR model is busy.
+/// When the rewrite fails the original text is delivered instead and
+/// `rewrite-template-failed` says so.
+fn spawn_rewrite(
+    app_handle: tauri::AppHandle,
+    recording_id: u64,
+    context: Arc<DictationContextSnapshot>,
+    template: RewriteTemplate,
+    original: String,
+) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<State>();
+        let started = std::time::Instant::now();
+        let rewritten = match state
+            .transform_runtime
+            .transform(
+                &template.instruction,
+                &original,
+                DEFAULT_TRANSFORM_DEADLINE,
+                CancelToken::new(),
+            )
+            .await
+        {
+            Ok(output) if !output.output.trim().is_empty() => {
+                Some(output.output.trim().to_string())
+            }
+            Ok(_) => {
+                tracing::warn!(target: "pipeline", recording_id, "rewrite returned no text");
+                None
+            }
+            Err(error) => {
+                tracing::warn!(target: "pipeline", recording_id, error = error.as_str(), "rewrite failed");
+                None
+            }
+        };
+        tracing::info!(
+            target: "pipeline",
+            recording_id,
+            template = template.name.as_str(),
+            total_ms = started.elapsed().as_millis() as u64,
+            rewritten = rewritten.is_some(),
+            "rewrite complete"
+        );
+        if rewritten.is_none() {
+            let _ = app_handle.emit(
+                "rewrite-template-failed",
+                format!(
+                    "Couldn't rewrite with \"{}\". The original text was used instead.",
+                    template.name
+                ),
+            );
+        }
+        let text = rewritten.as_deref().unwrap_or(&original);
+
+        let delivery = &context.delivery;
+        if delivers_to_notes(&app_handle, delivery).await {
+            save_to_notes(&app_handle, text, delivery);
+        } else if delivery.preview {
+            hold_for_preview(
+                &app_handle,
+                &state.app_state,
+                PendingInjection::new(recording_id, text, delivery),
+            );
+        } else {
+            let auto_paste =
+                delivery.auto_paste && !(delivery.save_transcript || delivery.save_audio);
+            let text_to_inject = read_caret(&app_handle, delivery, auto_paste)
+                .await
+                .deliver(text, delivery.trailing);
+            if let Err(e) = deliver_text(
+                &app_handle,
+                &state.app_state,
+                "rewrite",
+                text_to_inject,
+                auto_paste,
+                delivery.paste_delay_ms,
+                delivery.injection_method,
+            )
+            .await
+            {
+                tracing::warn!(target: "pipeline", recording_id, "rewrite: {}", e);
+                report_paste_failure(&app_handle, &state.app_state, None);
+            }
+            if delivery.injection_blocked {
+                let _ = app_handle.emit(
+                    "injection-blocked",
+                    "Murmur doesn't paste into this app. Text is in your clipboard.",
+                );
+            }
+        }
+        if let Some(rewritten) = rewritten.as_deref() {
+            let _ = app_handle.emit(
+                "transcription-rewritten",
+                TranscriptionRewrittenEvent {
+                    recording_id,
+                    template: &template.name,
+                    original: &original,
+                    text: rewritten,
+                },
+            );
+        }
+    });
+}
+
+/// Buffered append-mode takes, sent with `append-buffer-changed` and returned
+/// by `get_append_buffer`.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppendBuffer {
+    takes: usize,
+    text: String,
+}
+
+impl AppendBuffer {
+    fn from_takes(takes: &[String]) -> Self {
+        Self {
+            takes: append_edit::take_count(takes),
+            text: join_takes(takes),
+        }
+    }
+}
+
+fn emit_append_buffer(app_handle: &tauri::AppHandle, takes: &[String]) {
+    let _ = app_handle.emit("append-buffer-changed", AppendBuffer::from_takes(takes));
+}
+
+/// Read the selection for `selectionDictation` while the take records. A
+/// failed read leaves the dictation to be delivered as usual.
+fn spawn_selection_capture(app_handle: tauri::AppHandle, recording_id: u64) {
+    tauri::async_runtime::spawn(async move {
+        match selection::capture_selection_for_dictation(&app_handle).await {
+            Ok(snapshot) => {
+                tracing::info!(
+                    target: "pipeline",
+                    recording_id,
+                    length_bucket = selection::length_bucket(snapshot.text.len()),
+                    "selection read for dictation"
+                );
+                app_handle
+                    .state::<State>()
+                    .app_state
+                    .set_dictation_selection(recording_id, snapshot);
+            }
+            Err(error) => {
+                tracing::info!(target: "pipeline", recording_id, outcome = error.as_str(), "no selection to dictate over");
+            }
+        }
+    });
+}
+
+/// Read context-biasing terms from the allowlisted frontmost app while the
+/// take records. Skipped for models that ignore the prompt.
+fn spawn_context_biasing_capture(
+    app_handle: tauri::AppHandle,
+    recording_id: u64,
+    context: &DictationContextSnapshot,
+) {
+    if !crate::context_biasing::model_takes_prompt(&context.transcription.model_name) {
+        return;
+    }
+    let sources = crate::context_biasing::ContextSources {
+        window_title: context.context_capture.prompt_window_title,
+        selected_text: context.context_capture.prompt_selected_text,
+    };
+    let settings = app_handle
+        .state::<State>()
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .context_biasing
+        .clone();
+    tauri::async_runtime::spawn(crate::context_biasing::capture(
+        app_handle,
+        recording_id,
+        sources,
+        settings,
+    ));
+}
+
+/// Replace the selection read when the recording started with `text`, or in
+/// rewrite mode with the local model's rewrite of the selection following
+/// `text` as the instruction. Runs once the pipeline has settled.
+fn spawn_selection_delivery(
+    app_handle: tauri::AppHandle,
+    recording_id: u64,
+    context: Arc<DictationContextSnapshot>,
+    selection: TransformSnapshot,
+    text: String,
+) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<State>();
+        let replacement = if context.delivery.selection == SelectionDictation::Rewrite {
+            // The dictation is the instruction and the selection the input.
+            let rewritten = match state
+                .transform_runtime
+                .transform(
+                    &text,
+                    &selection.text,
+                    DEFAULT_TRANSFORM_DEADLINE,
+                    CancelToken::new(),
+                )
+                .await
+            {
+                Ok(output) if !output.output.trim().is_empty() => {
+                    Some(output.output.trim().to_string())
+                }
+                Ok(_) => {
+                    tracing::warn!(target: "pipeline", recording_id, "selection rewrite returned no text");
+                    None
+                }
+                Err(error) => {
+                    tracing::warn!(target: "pipeline", recording_id, error = error.as_str(), "selection rewrite failed");
+                    None
+                }
+            };
+            let Some(rewritten) = rewritten else {
+                let _ = app_handle.emit(
+                    "selection-replace-failed",
+                    "Couldn't rewrite the selection. It was left as it was.",
+                );
+                return;
+            };
+            rewritten
+        } else {
+            text
+        };
+        match transform_apply::replace_selection(
+            &app_handle,
+            &state.app_state,
+            &selection,
+            replacement,
+        )
+        .await
+        {
+            Ok(via) => {
+                tracing::info!(target: "pipeline", recording_id, via = via.as_str(), "selection replaced");
+            }
+            Err(error) => {
+                tracing::warn!(target: "pipeline", recording_id, error = error.as_str(), "selection replace failed");
+                let _ = app_handle.emit(
+                    "selection-replace-failed",
+                    selection_replace_failed_hint(error),
+                );
+            }
+        }
+    });
+}
+
+fn selection_replace_failed_hint(error: ApplyError) -> &'static str {
+    match error {
+        ApplyError::SelectionChanged | ApplyError::TargetGone => {
+            "The selection changed before Murmur could replace it. Text is in your clipboard."
+        }
+        ApplyError::ClipboardUnavailable => "Couldn't replace the selection.",
+        _ => "Couldn't replace the selection. Text is in your clipboard.",
+    }
+}
+
+/// Tell the user an auto-paste failed and that the text is on the clipboard.
+/// A paste refused for lack of Automation permission gets its own event and
+/// hint, since only granting that permission fixes it. A paste the guard
+/// skipped because no text field had focus is reported as `no-text-target`.
+fn report_paste_failure(app_handle: &tauri::AppHandle, app_state: &AppState, error: Option<&str>) {
+    let hint = if error.is_some_and(injector::is_no_text_target) {
+        tracing::info!(target: "pipeline", "auto-paste skipped: no text field is focused");
+        let hint = events::no_text_target_hint();
+        events::emit_no_text_target(app_handle, hint);
+        hint
+    } else if error.is_some_and(injector::is_automation_denial) {
+        tracing::warn!(target: "pipeline", "auto-paste blocked: Automation permission for System Events is denied");
+        let hint = events::automation_denied_hint();
+        events::emit_automation_permission_denied(app_handle, hint);
+        hint
+    } else {
+        let hint = events::paste_failed_hint();
+        events::emit_auto_paste_failed(app_handle, hint);
+        hint
+    };
+    events::emit_overlay_state(
+        app_handle,
+        &events::OverlayState::Error {
+            message: hint.to_string(),
+        },
+    );
+    crate::notifications::dictation_failed(app_handle, app_state, hint);
+}
+
+/// Write `text` to the clipboard and, with `auto_paste`, paste it into the
+/// frontmost app on the main thread. A paste that fails or doesn't finish
+/// within 2 s is logged under `site` and reported through
+/// `report_paste_failure`. Returns whether it completed; errors only when the
+/// main thread can't be reached.
+async fn deliver_text(
+    app_handle: &tauri::AppHandle,
+    app_state: &AppState,
+    site: &'static str,
+    text: String,
+    auto_paste: bool,
+    paste_delay_ms: u64,
+    injection_method: crate::state::InjectionMethod,
+) -> Result<bool, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<(), String>>();
+    app_handle
+        .run_on_main_thread(move || {
+            let _ = tx.send(injector::inject_text(
+                &text,
+                auto_paste,
+                paste_delay_ms,
+                injection_method,
+            ));
+        })
+        .map_err(|e| format!("Failed to dispatch to main thread: {}", e))?;
+    let error = match tokio::time::timeout(std::time::Duration::from_secs(2), rx).await {
+        Ok(Ok(Ok(()))) => return Ok(true),
+        Ok(Ok(Err(e))) => {
+            tracing::error!(target: "pipeline", site, "Text injection failed: {}", e);
+            Some(e)
+        }
+        Ok(Err(_)) => {
+            tracing::warn!(target: "pipeline", site, "Text injection sender dropped");
+            None
+        }
+        Err(_) => {
+            tracing::warn!(target: "pipeline", site, "Text injection timed out");
+            None
+        }
+    };
+    report_paste_failure(app_handle, app_state, error.as_deref());
+    Ok(false)
+}
+
+/// Hold `pending` for Accept or Discard instead of delivering it. An
+/// unanswered older preview is replaced, and this one is discarded if it is
+/// still pending after `PREVIEW_TIMEOUT`.
+fn hold_for_preview(
+    app_handle: &tauri::AppHandle,
+    app_state: &AppState,
+    pending: PendingInjection,
+) {
+    let id = pending.id;
+    let payload = InjectionPending::from(&pending);
+    let replaced = app_state
+        .pending_injection
+        .lock_or_recover()
+        .replace(pending);
+    if let Some(replaced) = replaced {
+        emit_injection_resolved(app_handle, replaced.id, PreviewOutcome::Replaced);
+    }
+    tracing::info!(target: "pipeline", recording_id = id, "delivery held for preview");
+    let _ = app_handle.emit("injection-pending", payload);
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(injection_preview::PREVIEW_TIMEOUT).await;
+        let state = app_handle.state::<State>();
+        let expired = {
+            let mut slot = state.app_state.pending_injection.lock_or_recover();
+            injection_preview::take_if(&mut slot, id)
+        };
+        if expired.is_some() {
+            tracing::info!(target: "pipeline", recording_id = id, "preview expired unanswered");
+            emit_injection_resolved(&app_handle, id, PreviewOutcome::Expired);
+        }
+    });
+}
+
+fn emit_injection_resolved(app_handle: &tauri::AppHandle, id: u64, outcome: PreviewOutcome) {
+    let _ = app_handle.emit("injection-resolved", InjectionResolved { id, outcome });
+}
+
+/// Whether this dictation goes to today's note instead of the frontmost app.
+/// The no-text-field reading runs on the main thread, like the paste guard.
+async fn delivers_to_notes(app_handle: &tauri::AppHandle, delivery: &DeliverySettings) -> bool {
+    match delivery.notes {
+        NotesTarget::Off => false,
+        NotesTarget::Always => true,
+        NotesTarget::NoTextField => {
+            let (tx, rx) = tokio::sync::oneshot::channel::<bool>();
+            let dispatched = app_handle.run_on_main_thread(move || {
+                let _ = tx.send(injector::no_text_field_focused());
+            });
+            dispatched.is_ok()
+                && matches!(
+                    tokio::time::timeout(std::time::Duration::from_secs(1), rx).await,
+                    Ok(Ok(true))
+                )
+        }
+    }
+}
+
+/// The text around the caret a paste from `delivery` depends on, read on the
+/// main thread like the paste guard. Nothing is read unless the text will be
+/// pasted: a clipboard-only delivery lands somewhere else later.
+async fn read_caret(
+    app_handle: &tauri::AppHandle,
+    delivery: &DeliverySettings,
+    auto_paste: bool,
+) -> Caret {
+    read_caret_parts(
+        app_handle,
+        delivery.cursor_context && auto_paste,
+        delivery.trailing == TrailingBehavior::Smart && auto_paste,
+    )
+    .await
+}
+
+async fn read_caret_parts(app_handle: &tauri::AppHandle, before: bool, after: bool) -> Caret {
+    if !before && !after {
+        return Caret::default();
+    }
+    let (tx, rx) = tokio::sync::oneshot::channel::<Caret>();
+    if app_handle
+        .run_on_main_thread(move || {
+            let _ = tx.send(cursor_context::read_caret(before, after));
+        })
+        .is_err()
+    {
+        return Caret::default();
+    }
+    match tokio::time::timeout(std::time::Duration::from_secs(1), rx).await {
+        Ok(Ok(caret)) => caret,
+        _ => Caret::default(),
+    }
+}
+
+/// Append `text` to today's note. If the note can't be written the text goes
+/// to the clipboard instead, so the dictation isn't lost.
+fn save_to_notes(app_handle: &tauri::AppHandle, text: &str, delivery: &DeliverySettings) {
+    let Err(e) = notes::record_dictation(app_handle, text) else {
+        return;
+    };
+    tracing::warn!(target: "pipeline", "notes target failed: {}", e);
+    let text = text.to_string();
+    let injection_method = delivery.injection_method;
+    let _ = app_handle.run_on_main_thread(move || {
+        let _ = injector::inject_text(&text, false, 0, injection_method);
+    });
+    let _ = app_handle.emit(
+        "note-save-failed",
+        "Couldn't save to your Murmur note. Text is in your clipboard.",
+    );
+}
+
+/// The audio a first pass decoded, for decoding again.
+struct RetryAudio<'a> {
+    samples: &'a [f32],
+    original_samples: &'a [f32],
+    vad_trimmed: bool,
+}
+
+/// Decode an unsure first pass again as the snapshot's `confidence_retry`
+/// says, keeping whichever pass Whisper was surer of, along with that pass's
+/// decode time. The first pass comes back untouched when no retry is
+/// configured or needed, or when the retry fails or runs out of budget.
+#[allow(clippy::too_many_arguments)]
+fn retry_low_confidence(
+    app_handle: &tauri::AppHandle,
+    app_state: &AppState,
+    recording_id: u64,
+    transcription: &dictation_context::TranscriptionSettings,
+    audio: RetryAudio<'_>,
+    first_decode_ms: u64,
+    text: String,
+    confidence: Option<transcriber::TranscriptConfidence>,
+) -> (String, Option<transcriber::TranscriptConfidence>, u64) {
+    let Some(retry) = transcription.confidence_retry.as_ref() else {
+        return (text, confidence, first_decode_ms);
+    };
+    let Some(first) = confidence
+        .as_ref()
+        .filter(|first| confidence_retry::needs_retry(first, retry.threshold))
+        .cloned()
+    else {
+        return (text, confidence, first_decode_ms);
+    };
+    if app_state.is_cancelled(recording_id) {
+        return (text, confidence, first_decode_ms);
+    }
+    // A larger model is only used when it is already resident: loading it
+    // would evict the dictation model and spend the budget on the load.
+    let needs_load = retry.model_name != transcription.model_name
+        && !app_state
+            .model_runtime
+            .loaded_backends()
+            .iter()
+            .any(|loaded| loaded.model_name == retry.model_name);
+    if first_decode_ms >= retry.budget_ms || needs_load {
+        confidence_retry::report(
+            app_handle,
+            recording_id,
+            retry,
+            RetryOutcome::Skipped,
+            first.score,
+            None,
+            0,
+        );
+        return (text, confidence, first_decode_ms);
+    }
+
+    let started = std::time::Instant::now();
+    let deadline = started + std::time::Duration::from_millis(retry.budget_ms);
+    // Beam search reuses the first pass's runtime; a resident larger model
+    // runs on the main one, as it would for a dictation that chose it.
+    let (runtime, lifecycle_app) = if retry.model_name == transcription.model_name {
+        transcription_runtime(app_state, app_handle, transcription)
+    } else {
+        (&app_state.model_runtime, Some(app_handle))
+    };
+    let result = runtime.with_ready_backend(
+        lifecycle_app,
+        &retry.model_name,
+        PreparationReason::Pipeline,
+        |backend| {
+            if std::time::Instant::now() >= deadline {
+                return Err("Budget spent waiting for the retry model".to_string());
+            }
+            backend.set_whisper_decoding(&retry.whisper_decoding);
+            backend.set_deadline(Some(deadline));
+            let decode_started = std::time::Instant::now();
+            let result = transcribe_with_coreml_vad_retry(
+                backend,
+                &retry.model_name,
+                audio.samples,
+                audio.original_samples,
+                audio.vad_trimmed,
+                &transcription.language,
+                transcription.prompt.as_deref(),
+                transcription.smart_punctuation,
+            );
+            let decode_ms = decode_started.elapsed().as_millis() as u64;
+            backend.set_deadline(None);
+            backend.set_whisper_decoding(&transcription.whisper_decoding);
+            result.map(|text| (text, backend.last_confidence(), decode_ms))
+        },
+    );
+    let retry_ms = started.elapsed().as_millis() as u64;
+
+    let (outcome, retry_score, kept) = match result {
+        Ok(((retry_text, retry_confidence, decode_ms), _)) => {
+            let outcome =
+                confidence_retry::compare(&text, &first, &retry_text, retry_confidence.as_ref());
+            let retry_score = retry_confidence.as_ref().map(|retry| retry.score);
+            let kept = (outcome != RetryOutcome::KeptFirst).then_some((
+                retry_text,
+                retry_confidence,
+                decode_ms,
+            ));
+            (outcome, retry_score, kept)
+        }
+        Err(error) => {
+            let outcome = if std::time::Instant::now() >= deadline {
+                RetryOutcome::OverBudget
+            } else {
+                RetryOutcome::Failed
+            };
+            tracing::warn!(target: "pipeline", recording_id, ?outcome, "confidence retry discarded: {}", error);
+            (outcome, None, None)
+        }
+    };
+    confidence_retry::report(
+        app_handle,
+        recording_id,
+        retry,
+        outcome,
+        first.score,
+        retry_score,
+        retry_ms,
+    );
+    kept.unwrap_or((text, confidence, first_decode_ms))
+}
+
+/// Shared transcription pipeline: model init -> transcribe -> inject text -> set idle.
+/// `recording_id` is checked against `app_state.cancelled_id` at checkpoints;
+/// if cancelled, returns empty text without clipboard write or paste.
+async fn run_transcription_pipeline(
+    samples: &[f32],
+    app_handle: &tauri::AppHandle,
+    app_state: &AppState,
+    recording_id: u64,
+    performance_guard: &mut PerformanceRunGuard,
+    context: Arc<DictationContextSnapshot>,
+    markers: &Markers,
+) -> Result<PipelineResult, String> {
+    // Guard resets status to Idle on any return path (error or success),
+    // but only if this recording is still the active one
+    let _guard = IdleGuard::new(app_state, recording_id);
+
+    // Terms context biasing read while the take recorded lead its prompt.
+    let bias_terms = crate::context_biasing::take(recording_id);
+    let context = if bias_terms.is_empty() {
+        context
+    } else {
+        let mut biased = (*context).clone();
+        biased.transcription.prompt = crate::context_biasing::merge_prompt(
+            biased.transcription.prompt.as_deref(),
+            &bias_terms,
+        );
+        Arc::new(biased)
+    };
+
+    let transcription = &context.transcription;
+    let delivery = &context.delivery;
+    // When saving to a file, suppress auto-paste into the focused app. The
+    // clipboard write inside `inject_text` is unconditional, so text remains
+    // copyable regardless of these toggles.
+    let effective_auto_paste =
+        delivery.auto_paste && !(delivery.save_transcript || delivery.save_audio);
+
+    // Pre-VAD signal level logging for mic diagnosis
+    let preprocess = pipeline_trace::phase(PipelinePhase::Preprocess);
+    let rms = audio::compute_rms(samples);
+    let peak = audio::compute_peak(samples);
+    let device = audio::last_device_name().unwrap_or_else(|| "unknown".to_string());
+    tracing::info!(target: "pipeline", "audio rms={:.4} peak={:.4} (device={})", rms, peak, device);
+
+    // Checkpoint 1: cancelled before VAD?
+    if app_state.is_cancelled(recording_id) {
+        tracing::info!(target: "pipeline", "cancelled before VAD (recording_id={})", recording_id);
+        return Ok(PipelineResult {
+            text: String::new(),
+            timings: PipelineTimings::default(),
+            terminal: PipelineTerminal::Cancelled(PerformanceStageV1::Vad),
+            refine_audio: None,
+            rewrite: None,
+            selection: None,
+            confidence: None,
+            alternatives_audio: None,
+            segments: None,
+        });
+    }
+
+    // A recording at the noise floor is where models invent text, so don't
+    // give them the chance.
+    if transcription.hallucination_filter && hallucination::is_silent(rms) {
+        tracing::info!(target: "pipeline", rms, "recording at noise floor, skipping transcription");
+        emit_no_speech(app_handle, recording_id, NoSpeechReason::Silence);
+        return Ok(PipelineResult {
+            text: String::new(),
+            timings: PipelineTimings::default(),
+            terminal: PipelineTerminal::NoSpeech(NoSpeechReason::Silence),
+            refine_audio: None,
+            rewrite: None,
+            selection: None,
+            confidence: None,
+            alternatives_audio: None,
+            segments: None,
+        });
+    }
+
+    // Every backend uses one authoritative full-buffer VAD + inference pass
+    // after recording stops.
+    let vad_threshold = 1.0 - (transcription.vad_sensitivity as f32 / 100.0);
+    performance_guard.enter(PerformanceStageV1::Vad);
+    let t_vad = std::time::Instant::now();
+    // Sections between marker key presses, as ranges of `samples`; the
+    // matching ranges of the trimmed audio are decoded one by one.
+    let sections = markers.sections(samples.len());
+    let (samples_for_transcription, vad_trimmed, decode_sections) = match vad::vad_model_path() {
+        Some(vad_path) if vad_path.exists() => {
+            let vad_path_str = vad_path.to_string_lossy().to_string();
+            let samples_owned = samples.to_vec();
+            let sections_owned = sections.clone();
+            let vad_span = preprocess.clone();
+            let vad_result = tokio::task::spawn_blocking(move || {
+                vad_span.in_scope(|| {
+                    filter_sections(
+                        &vad_path_str,
+                        &samples_owned,
+                        &sections_owned,
+                        vad_threshold,
+                    )
+                })
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("VAD task panicked: {}", e)));
+
+            match vad_result {
+                Ok((vad::VadResult::NoSpeech, _)) => {
+                    tracing::info!(target: "pipeline", "VAD detected no speech ({} samples, {:?}), skipping transcription",
+                            samples.len(), t_vad.elapsed());
+                    emit_no_speech(app_handle, recording_id, NoSpeechReason::Vad);
+                    return Ok(PipelineResult {
+                        text: String::new(),
+                        timings: PipelineTimings {
+                            vad_ms: t_vad.elapsed().as_millis() as u64,
+                            ..PipelineTimings::default()
+                        },
+                        terminal: PipelineTerminal::NoSpeech(NoSpeechReason::Vad),
+                        refine_audio: None,
+                        rewrite: None,
+                        selection: None,
+                        confidence: None,
+                        alternatives_audio: None,
+                        segments: None,
+                    });
+                }
+                Ok((vad::VadResult::Speech(trimmed), ranges)) => {
+                    tracing::info!(target: "pipeline", "VAD trimmed {} -> {} samples ({:.0}% speech, {:?})",
+                            samples.len(), trimmed.len(),
+                            trimmed.len() as f64 / samples.len() as f64 * 100.0,
+                            t_vad.elapsed());
+                    let vad_trimmed = trimmed.len() != samples.len();
+                    (trimmed, vad_trimmed, ranges)
+                }
+                Err(e) => {
+                    tracing::warn!(target: "pipeline", "VAD failed ({}), proceeding without filtering", e);
+                    (samples.to_vec(), false, sections.clone())
+                }
+            }
+        }
+        _ => {
+            let handle = app_handle.clone();
+            tokio::spawn(async move {
+                if let Err(e) = super::models::ensure_vad_model(&handle).await {
+                    tracing::warn!(target: "pipeline", "VAD model download failed ({}), skipping VAD", e);
+                }
+            });
+            (samples.to_vec(), false, sections.clone())
+        }
+    };
+    let vad_ms = t_vad.elapsed().as_millis() as u64;
+    drop(preprocess);
+    let speech_ratio = hallucination::speech_ratio(samples_for_transcription.len(), samples.len());
+
+    if app_state.is_cancelled(recording_id) {
+        tracing::info!(target: "pipeline", "cancelled before transcription (recording_id={})", recording_id);
+        return Ok(PipelineResult {
+            text: String::new(),
+            timings: PipelineTimings {
+                vad_ms,
+                ..PipelineTimings::default()
+            },
+            terminal: PipelineTerminal::Cancelled(PerformanceStageV1::InferenceDecode),
+            refine_audio: None,
+            rewrite: None,
+            selection: None,
+            confidence: None,
+            alternatives_audio: None,
+            segments: None,
+        });
+    }
+
+    let inference = pipeline_trace::phase(PipelinePhase::Inference);
+    let rss_before_mb = crate::resource_monitor::get_process_rss_mb();
+    performance_guard.enter(PerformanceStageV1::InferenceDecode);
+    let t_transcribe = std::time::Instant::now();
+    let mut decode_ms = 0;
+    let (runtime, lifecycle_app) = transcription_runtime(app_state, app_handle, transcription);
+    let mut decode = |model_name: &str| {
+        inference.in_scope(|| {
+            runtime.with_ready_backend(
+                lifecycle_app,
+                model_name,
+                PreparationReason::Pipeline,
+                |backend| {
+                    backend.set_whisper_decoding(&transcription.whisper_decoding);
+                    let decode_started = std::time::Instant::now();
+                    let result = if decode_sections.len() > 1 {
+                        transcribe_sections(
+                            backend,
+                            &samples_for_transcription,
+                            &decode_sections,
+                            &transcription.language,
+                            transcription.prompt.as_deref(),
+                            transcription.smart_punctuation,
+                        )
+                    } else {
+                        transcribe_with_coreml_vad_retry(
+                            backend,
+                            model_name,
+                            &samples_for_transcription,
+                            samples,
+                            vad_trimmed,
+                            &transcription.language,
+                            transcription.prompt.as_deref(),
+                            transcription.smart_punctuation,
+                        )
+                        .map(|text| (vec![text], backend.last_confidence()))
+                    };
+                    decode_ms = decode_started.elapsed().as_millis() as u64;
+                    result.map(|(texts, confidence)| (texts, confidence, backend.last_language()))
+                },
+            )
+        })
+    };
+    // A take recovered on the CPU or another model says nothing about the
+    // chosen model's usual speed, so only a first-try decode is measured.
+    let mut first_try = true;
+    let decoded = match decode(transcription.model_name.as_str()) {
+        Err(error) if transcriber::is_gpu_memory_error(&error) => {
+            first_try = false;
+            recover_from_gpu_memory(
+                app_handle,
+                recording_id,
+                runtime,
+                lifecycle_app,
+                transcription,
+                error,
+                &mut decode,
+            )
+        }
+        decoded => decoded,
+    };
+    let ((section_texts, confidence, detected_language), load_report) = decoded?;
+    if let Some(budget) = transcription.latency_budget.as_ref().filter(|_| first_try) {
+        crate::latency_budget::observe(
+            app_handle,
+            recording_id,
+            &transcription.model_name,
+            &transcription.language,
+            budget,
+            samples_for_transcription.len(),
+            decode_ms,
+        );
+    }
+    let model_load_ms = load_report.load_ms;
+    let inference_ms = t_transcribe.elapsed().as_millis() as u64;
+    let rss_after_mb = crate::resource_monitor::get_process_rss_mb();
+    tracing::info!(target: "pipeline", "transcription ({} samples): {:?}", samples_for_transcription.len(), t_transcribe.elapsed());
+    let mut timings = PipelineTimings {
+        vad_ms,
+        model_queue_ms: load_report.lock_wait_ms,
+        model_load_ms,
+        decode_ms,
+        inference_ms,
+        rss_before_mb,
+        rss_after_mb,
+        warm_state: Some(if load_report.cache_hit {
+            ModelWarmStateV1::Warm
+        } else {
+            ModelWarmStateV1::ColdLoaded
+        }),
+        ..PipelineTimings::default()
+    };
+    let text = markers::join_sections(&section_texts);
+    // A retry decodes the whole take again, which would lose the sections.
+    let (text, confidence, token_decode_ms) = if section_texts.len() > 1 {
+        (text, confidence, decode_ms)
+    } else {
+        inference.in_scope(|| {
+            retry_low_confidence(
+                app_handle,
+                app_state,
+                recording_id,
+                transcription,
+                RetryAudio {
+                    samples: &samples_for_transcription,
+                    original_samples: samples,
+                    vad_trimmed,
+                },
+                decode_ms,
+                text,
+                confidence,
+            )
+        })
+    };
+    timings.token_decode_ms = token_decode_ms;
+    drop(inference);
+    let postprocess = pipeline_trace::phase(PipelinePhase::Postprocess);
+
+    if transcription.hallucination_filter
+        && hallucination::is_hallucination(&text, speech_ratio, rms)
+    {
+        tracing::info!(target: "pipeline", speech_ratio, rms, "dropped transcript matching known hallucination phrases");
+        emit_no_speech(app_handle, recording_id, NoSpeechReason::Hallucination);
+        return Ok(PipelineResult {
+            text: String::new(),
+            timings,
+            terminal: PipelineTerminal::NoSpeech(NoSpeechReason::Hallucination),
+            refine_audio: None,
+            rewrite: None,
+            selection: None,
+            confidence: None,
+            alternatives_audio: None,
+            segments: None,
+        });
+    }
+
+    // Append mode: a take that is only a buffer edit ("scratch that") changes
+    // the buffered takes and delivers nothing itself. Matched on the raw
+    // transcript, before voice commands would rewrite the same phrases.
+    if delivery.append && !app_state.is_cancelled(recording_id) {
+        if let Some(command) = append_edit::parse_buffer_command(&text) {
+            let (changed, takes) = {
+                let mut dictation = app_state.dictation.lock_or_recover();
+                let changed =
+                    append_edit::apply_buffer_command(&mut dictation.append_takes, &command);
+                (changed, dictation.append_takes.clone())
+            };
+            tracing::info!(target: "pipeline", changed, takes = takes.len(), "append buffer edit applied");
+            emit_append_buffer(app_handle, &takes);
+            return Ok(PipelineResult {
+                text: String::new(),
+                timings,
+                terminal: PipelineTerminal::Success,
+                refine_audio: None,
+                rewrite: None,
+                selection: None,
+                confidence: None,
+                alternatives_audio: None,
+                segments: None,
+            });
+        }
+    }
+
+    // Dictation macros: a take that is only a macro phrase runs its action
+    // and delivers nothing. Also matched on the raw transcript, so a macro
+    // phrase is never rewritten by the transforms first.
+    if let Some(macros) = &delivery.macros {
+        if !app_state.is_cancelled(recording_id) {
+            if let Some(found) = dictation_macros::match_phrase(&text, &macros.macros) {
+                dictation_macros::dispatch(app_handle, recording_id, found.clone(), macros.confirm);
+                return Ok(PipelineResult {
+                    text: String::new(),
+                    timings,
+                    terminal: PipelineTerminal::Success,
+                    refine_audio: None,
+                    rewrite: None,
+                    selection: None,
+                    confidence: None,
+                    alternatives_audio: None,
+                    segments: None,
+                });
+            }
+        }
+    }
+
+    // Post-recognition transformation is backend-neutral and ordered in one
+    // authoritative entry point. Its stage config and resources come from the
+    // immutable recording-start snapshot rather than mutable app settings.
+    let transform_started = std::time::Instant::now();
+    performance_guard.enter(PerformanceStageV1::TranscriptTransform);
+    let decoded = if section_texts.len() > 1 {
+        section_texts
+    } else {
+        vec![text.clone()]
+    };
+    let segments = markers::segments(&decoded, &sections, markers.spoken);
+    let (transformed, segments) = postprocess.in_scope(|| match segments {
+        Some(segments) => transform_segments(
+            app_state,
+            &context,
+            recording_id,
+            segments,
+            detected_language,
+        )
+        .map(|(transformed, segments)| (transformed, Some(segments))),
+        None => {
+            transform_live_transcript(app_state, &context, recording_id, text, detected_language)
+                .map(|transformed| (transformed, None))
+        }
+    })?;
+    let transform_ms = transform_started.elapsed().as_millis() as u64;
+    tracing::info!(
+        target: "pipeline",
+        changed = transformed.was_changed(),
+        "transcript_transform_complete"
+    );
+    *app_state.last_rules_input.lock_or_recover() = Some(transformed.rules_input.clone());
+    let correction_ms =
+        transformed.stage_duration_ms(crate::transcript_transform::SMART_CORRECTION_STAGE);
+    let transform_stages = transformed
+        .stages
+        .iter()
+        .filter_map(transcript_stage_timing)
+        .collect();
+    let (rewrite, text) = if delivery.append {
+        (None, transformed.text)
+    } else {
+        select_rewrite(&context.transformations, transformed.text)
+    };
+    let rewrite = rewrite.filter(|_| !text.is_empty());
+
+    // Update last_transcription_at for idle timeout tracking
+    *app_state.last_transcription_at.lock_or_recover() = Some(std::time::Instant::now());
+    // Checkpoint 3: cancelled before text injection?
+    if app_state.is_cancelled(recording_id) {
+        tracing::info!(target: "pipeline", "cancelled before injection (recording_id={})", recording_id);
+        timings.correction_ms = correction_ms;
+        timings.transform_ms = transform_ms;
+        timings.transform_stages = transform_stages;
         return Ok(PipelineResult {
             text: String::new(),
             timings,
             terminal: PipelineTerminal::Cancelled(PerformanceStageV1::ClipboardPaste),
+            refine_audio: None,
+            rewrite: None,
+            selection: None,
+            confidence: None,
+            alternatives_audio: None,
+            segments: None,
         });
     }
 
@@ -925,55 +2413,129 @@ async fn run_transcription_pipeline(
         0
     };
 
+    drop(postprocess);
+
     // Phase: Text injection (clipboard write + optional osascript paste)
+    let inject = pipeline_trace::phase(PipelinePhase::Inject);
     let t_inject = std::time::Instant::now();
     performance_guard.enter(PerformanceStageV1::ClipboardPaste);
-    if !text.is_empty() {
-        let text_to_inject = text.clone();
-        let paste_delay_ms = delivery.paste_delay_ms;
-        let (tx, rx) = tokio::sync::oneshot::channel::<Result<(), String>>();
-        app_handle
-            .run_on_main_thread(move || {
-                let _ = tx.send(injector::inject_text(
-                    &text_to_inject,
-                    effective_auto_paste,
-                    paste_delay_ms,
-                ));
-            })
-            .map_err(|e| format!("Failed to dispatch to main thread: {}", e))?;
-        let paste_hint = if cfg!(target_os = "macos") {
-            "Text is in your clipboard -- press Cmd+V to paste manually."
-        } else {
-            "Text is in your clipboard -- press Ctrl+V to paste manually."
+    let mut pasted = false;
+    let mut caret = Caret::default();
+    let to_notes = !delivery.append
+        && rewrite.is_none()
+        && !text.is_empty()
+        && delivers_to_notes(app_handle, delivery).await;
+    // A selection read at the start is only worth writing to when the text
+    // would have been pasted into that app.
+    let selection = app_state
+        .take_dictation_selection(recording_id)
+        .filter(|_| {
+            !delivery.append
+                && rewrite.is_none()
+                && !to_notes
+                && !text.is_empty()
+                && effective_auto_paste
+        });
+    if delivery.append && !text.is_empty() {
+        // Append mode: hold the take for commit instead of pasting a fragment.
+        let takes = {
+            let mut dictation = app_state.dictation.lock_or_recover();
+            dictation.append_takes.push(text.clone());
+            dictation.append_takes.clone()
         };
-        match tokio::time::timeout(std::time::Duration::from_secs(2), rx).await {
-            Ok(Ok(Err(e))) => {
-                tracing::error!(target: "pipeline", "Text injection failed: {}", e);
-                let _ = app_handle.emit("auto-paste-failed", paste_hint);
-            }
-            Ok(Err(_)) => {
-                tracing::warn!(target: "pipeline", "Text injection sender dropped");
-                let _ = app_handle.emit("auto-paste-failed", paste_hint);
-            }
-            Err(_) => {
-                tracing::warn!(target: "pipeline", "Text injection timed out");
-                let _ = app_handle.emit("auto-paste-failed", paste_hint);
-            }
-            Ok(Ok(Ok(()))) => {}
+        tracing::info!(target: "pipeline", takes = takes.len(), "take buffered for append commit");
+        emit_append_buffer(app_handle, &takes);
+    } else if let Some(template) = &rewrite {
+        tracing::info!(target: "pipeline", template = template.name.as_str(), "delivery deferred for rewrite");
+    } else if to_notes {
+        save_to_notes(app_handle, &text, delivery);
+    } else if selection.is_some() {
+        tracing::info!(target: "pipeline", mode = ?delivery.selection, "delivery deferred for selection");
+    } else if delivery.preview && !text.is_empty() {
+        hold_for_preview(
+            app_handle,
+            app_state,
+            PendingInjection::new(recording_id, &text, delivery),
+        );
+    } else if !text.is_empty() {
+        // The trailing text and the fit to the caret are delivery concerns:
+        // they reach the clipboard/paste but not saved transcripts or the
+        // returned text.
+        caret = read_caret(app_handle, delivery, effective_auto_paste).await;
+        let text_to_inject = caret.deliver(&text, delivery.trailing);
+        let completed = deliver_text(
+            app_handle,
+            app_state,
+            "dictation",
+            text_to_inject,
+            effective_auto_paste,
+            delivery.paste_delay_ms,
+            delivery.injection_method,
+        )
+        .await?;
+        pasted = effective_auto_paste && completed;
+        if delivery.injection_blocked {
+            tracing::info!(target: "pipeline", "auto-paste suppressed by do-not-inject list");
+            let _ = app_handle.emit(
+                "injection-blocked",
+                "Murmur doesn't paste into this app. Text is in your clipboard.",
+            );
         }
     }
     let paste_ms = t_inject.elapsed().as_millis() as u64;
     tracing::info!(target: "pipeline", "inject (clipboard + paste): {:?}", t_inject.elapsed());
+    drop(inject);
 
     timings.correction_ms = correction_ms;
     timings.transform_ms = transform_ms;
     timings.transform_stages = transform_stages;
     timings.file_output_ms = file_output_ms;
     timings.paste_ms = paste_ms;
+    // A rewritten transcript replaces the draft wholesale, so it is not refined;
+    // neither is a previewed one, which the user may already be reading, nor one
+    // already saved to a note or written over a selection.
+    let refines = transcription.refinement.is_some()
+        && rewrite.is_none()
+        && !delivery.preview
+        && !to_notes
+        && selection.is_none()
+        && !text.is_empty();
+    // Alternatives index into the text history keeps, so a take still
+    // buffered for an append commit or waiting to be rewritten gets none.
+    let offers_alternatives = transcription.alternatives > 0
+        && !delivery.append
+        && rewrite.is_none()
+        && selection.is_none()
+        && !text.is_empty()
+        && model_runtime::model_definition(&transcription.model_name)
+            .is_ok_and(|definition| definition.backend == model_runtime::BackendKind::Whisper);
+    let (refine_audio, alternatives_audio) = if refines {
+        let audio = RefineAudio {
+            samples: samples_for_transcription,
+            vad_trimmed,
+            pasted,
+            caret,
+        };
+        (Some(audio), None)
+    } else if offers_alternatives {
+        let audio = AlternativesAudio {
+            samples: samples_for_transcription,
+            vad_trimmed,
+        };
+        (None, Some(audio))
+    } else {
+        (None, None)
+    };
     Ok(PipelineResult {
         text,
         timings,
         terminal: PipelineTerminal::Success,
+        refine_audio,
+        rewrite,
+        selection,
+        confidence,
+        alternatives_audio,
+        segments,
     })
     // _guard drops here, setting status to Idle
 }
@@ -992,7 +2554,7 @@ pub async fn process_audio(
     app_handle: tauri::AppHandle,
     audio_data: String,
     state: tauri::State<'_, State>,
-) -> Result<serde_json::Value, String> {
+) -> Result<TranscriptionResult, String> {
     // Auto-dismiss a parked transform review, refuse on an active transform
     // (issue #338 — same policy as start_native_recording). The in-lock
     // transform guard below stays as a race guard.
@@ -1037,16 +2599,22 @@ pub async fn process_audio(
             tracing::warn!(target: "pipeline", "process_audio: blocked — transform runtime busy");
             return Err("Cannot process audio while a text transform is in progress.".to_string());
         }
-        if dictation.status != DictationStatus::Idle {
+        if !dictation.status.is_settled() {
             return Err("Cannot process audio while live dictation is active.".to_string());
         }
         dictation.status = DictationStatus::Processing;
         state.app_state.next_recording_id()
     };
     keyboard::set_processing(true);
-    let _ = app_handle.emit("recording-status-changed", "processing");
+    events::emit_recording_status(&app_handle, DictationStatus::Processing);
     let bundle_id = crate::frontmost::frontmost_bundle_id();
-    let context = resolve_live_context(&state.app_state, &state.knowledge, bundle_id.as_deref());
+    let context = resolve_live_context(
+        &state.app_state,
+        &state.knowledge,
+        bundle_id.as_deref(),
+        SessionOverrides::default(),
+    );
+    announce_model_downgrade(&app_handle, rid, &context);
     if let Err(error) = state.performance.begin_dictation(
         rid,
         runtime_identity(&context.transcription.model_name, ModelWarmStateV1::Unknown),
@@ -1071,13 +2639,13 @@ pub async fn process_audio(
     let wav_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &audio_data)
         .map_err(|e| {
             if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
-                let _ = app_handle.emit("recording-status-changed", "idle");
+                events::emit_recording_status(&app_handle, settled_status(&state.app_state));
             }
             format!("Failed to decode base64: {}", e)
         })?;
     let samples = transcriber::parse_wav_to_samples(&wav_bytes).map_err(|e| {
         if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
-            let _ = app_handle.emit("recording-status-changed", "idle");
+            events::emit_recording_status(&app_handle, settled_status(&state.app_state));
         }
         e
     })?;
@@ -1099,30 +2667,29 @@ pub async fn process_audio(
         rid,
         &mut performance_guard,
         Arc::clone(&context),
+        &Markers::default(),
     )
+    .instrument(pipeline_trace::pipeline_span(rid))
     .await;
     // Only emit idle if this recording wasn't cancelled/superseded.
     // Hold the dictation lock across the check+emit to prevent a concurrent
     // start from interleaving a "recording" status between our check and emit.
     {
-        let _dictation = state.app_state.dictation.lock_or_recover();
+        let dictation = state.app_state.dictation.lock_or_recover();
         if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
             keyboard::set_processing(false);
-            let _ = app_handle.emit("recording-status-changed", "idle");
+            events::emit_recording_status(&app_handle, dictation.settled_status());
         }
     }
     let pipeline = pipeline_result?;
     let text = pipeline.text;
     let timings = pipeline.timings;
+    let tokens = pipeline.confidence.as_ref().map(|c| c.tokens() as u64);
 
     let total_ms = t_total.elapsed().as_millis() as u64;
     let audio_secs = samples.len() as f64 / 16_000.0;
-    let word_count = if text.trim().is_empty() {
-        0
-    } else {
-        text.split_whitespace().count()
-    };
-    let char_count = text.len();
+    let word_count = crate::text_metrics::word_count(&text);
+    let char_count = text.chars().count();
     let model_name = context.transcription.model_name.clone();
     let backend_name = model_runtime::model_definition(&model_name)?
         .backend
@@ -1141,6 +2708,8 @@ pub async fn process_audio(
         audio_secs = audio_secs,
         word_count = word_count,
         char_count = char_count,
+        tokens,
+        tokens_per_sec = tokens.and_then(|t| crate::dictation_stats::tokens_per_sec(t, timings.token_decode_ms)),
         rss_before_mb = timings.rss_before_mb,
         rss_after_mb = timings.rss_after_mb,
         model = model_name.as_str(),
@@ -1150,9 +2719,18 @@ pub async fn process_audio(
 
     let outcome = match pipeline.terminal {
         PipelineTerminal::Success => RunOutcomeV1::Success,
-        PipelineTerminal::NoSpeech => RunOutcomeV1::NoSpeech,
+        PipelineTerminal::NoSpeech(_) => RunOutcomeV1::NoSpeech,
         PipelineTerminal::Cancelled(stage) => RunOutcomeV1::Cancelled { stage },
     };
+    if outcome == RunOutcomeV1::Success && word_count > 0 {
+        crate::dictation_stats::record(
+            word_count,
+            (audio_secs * 1_000.0).round() as u64,
+            &model_name,
+            tokens,
+            timings.token_decode_ms,
+        );
+    }
     let warm_state = timings.warm_state.unwrap_or(ModelWarmStateV1::Unknown);
     let _ = performance_guard.finish(
         outcome,
@@ -1163,22 +2741,41 @@ pub async fn process_audio(
         )),
         Some(runtime_identity(&model_name, warm_state)),
     );
+    if let Some(audio) = pipeline.refine_audio {
+        spawn_refinement(
+            app_handle.clone(),
+            rid,
+            context,
+            text.clone(),
+            samples,
+            audio,
+        );
+    } else if let Some(audio) = pipeline.alternatives_audio {
+        spawn_alternatives(
+            app_handle.clone(),
+            rid,
+            context,
+            text.clone(),
+            samples,
+            audio,
+        );
+    } else if let Some(template) = pipeline.rewrite {
+        spawn_rewrite(app_handle.clone(), rid, context, template, text.clone());
+    } else if let Some(selection) = pipeline.selection {
+        spawn_selection_delivery(app_handle.clone(), rid, context, selection, text.clone());
+    }
 
-    Ok(serde_json::json!({
-        "type": "transcription",
-        "text": text
-    }))
+    Ok(TranscriptionResult::new(TranscriptionKind::Transcription, text))
 }
 
 #[tauri::command]
-pub async fn get_status(state: tauri::State<'_, State>) -> Result<serde_json::Value, String> {
+pub async fn get_status(state: tauri::State<'_, State>) -> Result<StatusResponse, String> {
     let dictation = state.app_state.dictation.lock_or_recover();
-    Ok(serde_json::json!({
-        "type": "status",
-        "state": dictation.status,
-        "model": dictation.model_name,
-        "language": dictation.language
-    }))
+    Ok(StatusResponse::new(
+        dictation.status.as_str(),
+        &dictation.model_name,
+        &dictation.language,
+    ))
 }
 
 /// Current phase of the AX-selection transform pipeline (issue #312).
@@ -1202,32 +2799,31 @@ struct ConfigurationLogMetadata {
 }
 
 impl ConfigurationLogMetadata {
-    fn from_options(options: &serde_json::Value) -> Self {
+    fn from_options(options: &ConfigureOptions) -> Self {
+        let vocabulary_entry_count = options
+            .vocabulary_entries
+            .as_ref()
+            .and_then(serde_json::Value::as_array)
+            .map_or(0, |entries| entries.len() as u64);
         Self {
-            option_count: options.as_object().map_or(0, |object| object.len() as u64),
+            option_count: options.present_count(),
             app_profile_count: options
-                .get("appProfiles")
-                .and_then(serde_json::Value::as_array)
+                .app_profiles
+                .as_ref()
                 .map_or(0, |profiles| profiles.len() as u64),
             voice_command_count: options
-                .get("voiceCommands")
-                .and_then(serde_json::Value::as_array)
+                .voice_commands
+                .as_ref()
                 .map_or(0, |commands| commands.len() as u64),
             custom_vocabulary_present: options
-                .get("customVocabulary")
-                .and_then(serde_json::Value::as_str)
+                .custom_vocabulary
+                .as_deref()
                 .is_some_and(|vocabulary| !vocabulary.trim().is_empty())
-                || options
-                    .get("vocabularyEntries")
-                    .and_then(serde_json::Value::as_array)
-                    .is_some_and(|entries| !entries.is_empty()),
-            vocabulary_entry_count: options
-                .get("vocabularyEntries")
-                .and_then(serde_json::Value::as_array)
-                .map_or(0, |entries| entries.len() as u64),
+                || vocabulary_entry_count > 0,
+            vocabulary_entry_count,
             output_directory_present: options
-                .get("outputDir")
-                .and_then(serde_json::Value::as_str)
+                .output_dir
+                .as_deref()
                 .is_some_and(|directory| !directory.trim().is_empty()),
         }
     }
@@ -1246,6 +2842,357 @@ fn parse_writing_style(value: Option<&serde_json::Value>) -> Option<crate::state
     }
 }
 
+/// Parse the `whisperDecoding` settings object. The frontend always sends the
+/// whole object, so a missing or malformed field falls back to its shipped
+/// default instead of keeping a stale value. Numbers are clamped to ranges
+/// whisper.cpp handles sensibly.
+fn parse_whisper_decoding(value: &serde_json::Value) -> transcriber::WhisperDecoding {
+    use transcriber::whisper::{SingleSegmentMode, WhisperSampling, MAX_BEAM_SIZE, MAX_THREADS};
+
+    let defaults = transcriber::WhisperDecoding::default();
+    let sampling = match value.get("sampling").and_then(|v| v.as_str()) {
+        Some("beam_search") => WhisperSampling::BeamSearch,
+        _ => WhisperSampling::Greedy,
+    };
+    let single_segment = match value.get("singleSegment").and_then(|v| v.as_str()) {
+        Some("always") => SingleSegmentMode::Always,
+        Some("never") => SingleSegmentMode::Never,
+        _ => SingleSegmentMode::Auto,
+    };
+    let unit_interval = |key: &str, default: f32| {
+        value
+            .get(key)
+            .and_then(|v| v.as_f64())
+            .filter(|v| v.is_finite())
+            .map_or(default, |v| v.clamp(0.0, 1.0) as f32)
+    };
+    transcriber::WhisperDecoding {
+        sampling,
+        beam_size: value
+            .get("beamSize")
+            .and_then(|v| v.as_u64())
+            .map_or(defaults.beam_size, |v| {
+                v.clamp(1, u64::from(MAX_BEAM_SIZE)) as u32
+            }),
+        temperature: unit_interval("temperature", defaults.temperature),
+        no_speech_threshold: unit_interval("noSpeechThreshold", defaults.no_speech_threshold),
+        // 0 (or absent) means one thread per performance core.
+        threads: value
+            .get("threads")
+            .and_then(|v| v.as_u64())
+            .filter(|v| *v > 0)
+            .map(|v| v.min(u64::from(MAX_THREADS)) as u32),
+        single_segment,
+    }
+}
+
+/// Parse the `redaction` settings object. Blank list entries are dropped here;
+/// whether the patterns compile is checked by `Redactor::build`.
+fn parse_redaction_settings(value: &serde_json::Value) -> crate::state::RedactionSettings {
+    let defaults = crate::state::RedactionSettings::default();
+    let strings = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str())
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+    crate::state::RedactionSettings {
+        enabled: value
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.enabled),
+        mode: value
+            .get("mode")
+            .and_then(|v| v.as_str())
+            .and_then(crate::state::RedactionMode::parse)
+            .unwrap_or(defaults.mode),
+        include_profanity: value
+            .get("includeProfanity")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.include_profanity),
+        words: strings("words"),
+        patterns: strings("patterns"),
+    }
+}
+
+/// Parse the `focusFilter` settings object. Blank mode identifiers are dropped.
+fn parse_focus_filter(value: &serde_json::Value) -> crate::state::FocusFilterSettings {
+    let defaults = crate::state::FocusFilterSettings::default();
+    crate::state::FocusFilterSettings {
+        suppress_notifications: value
+            .get("suppressNotifications")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.suppress_notifications),
+        disable_hotkey: value
+            .get("disableHotkey")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.disable_hotkey),
+        modes: value
+            .get("modes")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str())
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn parse_adaptive_model(value: &serde_json::Value) -> crate::state::AdaptiveModelSettings {
+    let defaults = crate::state::AdaptiveModelSettings::default();
+    crate::state::AdaptiveModelSettings {
+        enabled: value
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.enabled),
+        fallback_model: value
+            .get("fallbackModel")
+            .and_then(|v| v.as_str())
+            .filter(|model| model_runtime::model_definition(model).is_ok())
+            .map(str::to_string)
+            .unwrap_or(defaults.fallback_model),
+    }
+}
+
+fn parse_latency_budget(value: &serde_json::Value) -> crate::state::LatencyBudgetSettings {
+    let defaults = crate::state::LatencyBudgetSettings::default();
+    crate::state::LatencyBudgetSettings {
+        enabled: value
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.enabled),
+        max_rtf: value
+            .get("maxRtf")
+            .and_then(|v| v.as_f64())
+            .filter(|max_rtf| max_rtf.is_finite())
+            .map(|max_rtf| (max_rtf as f32).clamp(0.1, 5.0))
+            .unwrap_or(defaults.max_rtf),
+        auto_switch: value
+            .get("autoSwitch")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.auto_switch),
+    }
+}
+
+fn parse_dual_model(value: &serde_json::Value) -> crate::state::DualModelSettings {
+    let defaults = crate::state::DualModelSettings::default();
+    crate::state::DualModelSettings {
+        enabled: value
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.enabled),
+        draft_model: value
+            .get("draftModel")
+            .and_then(|v| v.as_str())
+            .filter(|model| model_runtime::model_definition(model).is_ok())
+            .map(str::to_string)
+            .unwrap_or(defaults.draft_model),
+        replace_draft: value
+            .get("replaceDraft")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.replace_draft),
+    }
+}
+
+fn parse_confidence_retry(value: &serde_json::Value) -> crate::state::ConfidenceRetrySettings {
+    let defaults = crate::state::ConfidenceRetrySettings::default();
+    crate::state::ConfidenceRetrySettings {
+        enabled: value
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.enabled),
+        threshold: value
+            .get("threshold")
+            .and_then(|v| v.as_f64())
+            .filter(|threshold| threshold.is_finite())
+            .map(|threshold| (threshold as f32).clamp(0.05, 0.95))
+            .unwrap_or(defaults.threshold),
+        // Only Whisper reports confidence, so only a Whisper retry can be
+        // compared with the first pass.
+        model: value
+            .get("model")
+            .and_then(|v| v.as_str())
+            .filter(|model| {
+                model_runtime::model_definition(model).is_ok_and(|definition| {
+                    definition.backend == model_runtime::BackendKind::Whisper
+                })
+            })
+            .map(str::to_string),
+        budget_ms: value
+            .get("budgetMs")
+            .and_then(|v| v.as_u64())
+            .map(|budget| budget.clamp(500, 10_000))
+            .unwrap_or(defaults.budget_ms),
+    }
+}
+
+fn parse_alternatives(value: &serde_json::Value) -> crate::state::AlternativesSettings {
+    let defaults = crate::state::AlternativesSettings::default();
+    crate::state::AlternativesSettings {
+        enabled: value
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.enabled),
+        count: value
+            .get("count")
+            .and_then(|v| v.as_u64())
+            .map(|count| count.clamp(1, crate::alternatives::MAX_ALTERNATIVES as u64) as u32)
+            .unwrap_or(defaults.count),
+    }
+}
+
+/// Parse one camelCase profile object from the settings payload. Entries
+/// without a bundleId are skipped; a missing/null override means "use global".
+/// Shared by `configure_dictation` and the profile CRUD commands so both accept
+/// exactly the same shape.
+pub(crate) fn parse_app_profile(p: &serde_json::Value) -> Option<crate::state::AppProfile> {
+    let bundle_id = p
+        .get("bundleId")
+        .and_then(|v| v.as_str())?
+        .trim()
+        .to_string();
+    if bundle_id.is_empty() {
+        return None;
+    }
+    let label = p
+        .get("label")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    // null/absent -> None (use global); otherwise the boolean override.
+    let auto_paste_override = p.get("autoPasteOverride").and_then(|v| v.as_bool());
+    let cleanup_override = p.get("cleanupOverride").and_then(|v| v.as_bool());
+    let cli_formatting_override = p.get("cliFormattingOverride").and_then(|v| v.as_bool());
+    let smart_formatting_override = p.get("smartFormattingOverride").and_then(|v| v.as_bool());
+    let writing_style = parse_writing_style(p.get("writingStyle"));
+    let ide_context_enabled = p
+        .get("ideContextEnabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let ide_project_roots = p
+        .get("ideProjectRoots")
+        .and_then(|value| value.as_array())
+        .map(|roots| {
+            let roots = roots
+                .iter()
+                .filter_map(|root| root.as_str())
+                .map(str::trim)
+                .filter(|root| !root.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            crate::ide_context::normalize_config_roots(&roots)
+        })
+        .unwrap_or_default();
+    let injection_method = p.get("injectionMethodOverride").and_then(|v| v.as_str());
+    let injection_method_override = match injection_method {
+        Some("native") => Some(crate::state::InjectionMethod::Native),
+        Some("system_events") => Some(crate::state::InjectionMethod::SystemEvents),
+        _ => None,
+    };
+    let trailing_override = p
+        .get("trailingOverride")
+        .and_then(|v| v.as_str())
+        .and_then(TrailingBehavior::parse);
+    // Unknown or unsupported model names are dropped rather than rejecting the
+    // whole settings payload; the profile then inherits the global model.
+    let model_override = p
+        .get("modelOverride")
+        .and_then(|v| v.as_str())
+        .filter(|name| {
+            model_runtime::model_definition(name).is_ok_and(model_runtime::model_supported)
+        })
+        .map(str::to_string);
+    Some(crate::state::AppProfile {
+        bundle_id,
+        label,
+        auto_paste_override,
+        cleanup_override,
+        cli_formatting_override,
+        smart_formatting_override,
+        writing_style,
+        ide_context_enabled,
+        ide_project_roots,
+        injection_method_override,
+        trailing_override,
+        model_override,
+    })
+}
+
+pub(crate) fn parse_dictation_profile(
+    p: &serde_json::Value,
+) -> Option<crate::state::DictationProfile> {
+    let id = p.get("id").and_then(|v| v.as_str())?.trim().to_string();
+    if id.is_empty() {
+        return None;
+    }
+    let name = p
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let model_override = p
+        .get("modelOverride")
+        .and_then(|v| v.as_str())
+        .filter(|name| {
+            model_runtime::model_definition(name).is_ok_and(model_runtime::model_supported)
+        })
+        .map(str::to_string);
+    let language_override = p
+        .get("languageOverride")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+        .map(str::to_string);
+    let rewrite_template = p
+        .get("rewriteTemplate")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|template| !template.is_empty())
+        .map(str::to_string);
+    Some(crate::state::DictationProfile {
+        id,
+        name,
+        model_override,
+        language_override,
+        auto_paste_override: p.get("autoPasteOverride").and_then(|v| v.as_bool()),
+        cleanup_override: p.get("cleanupOverride").and_then(|v| v.as_bool()),
+        smart_formatting_override: p.get("smartFormattingOverride").and_then(|v| v.as_bool()),
+        rewrite_template,
+    })
+}
+
+/// Inverse of [`parse_app_profile`]: the camelCase shape the settings UI stores.
+pub(crate) fn app_profile_json(profile: &crate::state::AppProfile) -> serde_json::Value {
+    serde_json::json!({
+        "bundleId": profile.bundle_id,
+        "label": profile.label,
+        "autoPasteOverride": profile.auto_paste_override,
+        "cleanupOverride": profile.cleanup_override,
+        "cliFormattingOverride": profile.cli_formatting_override,
+        "smartFormattingOverride": profile.smart_formatting_override,
+        "writingStyle": profile.writing_style.map(crate::state::WritingStyle::as_str),
+        "ideContextEnabled": profile.ide_context_enabled,
+        "ideProjectRoots": profile.ide_project_roots,
+        "injectionMethodOverride": profile.injection_method_override.map(crate::state::InjectionMethod::as_str),
+        "trailingOverride": profile.trailing_override.map(TrailingBehavior::as_str),
+        "modelOverride": profile.model_override,
+    })
+}
+
 struct StagedVocabularyConfiguration {
     voice_commands: Option<Vec<crate::state::VoiceCommand>>,
     entries: Option<Vec<crate::state::VocabularyEntry>>,
@@ -1279,39 +3226,37 @@ fn legacy_vocabulary_entries(value: &str) -> Vec<crate::state::VocabularyEntry>
 }
 
 fn stage_vocabulary_configuration(
-    options: &serde_json::Value,
+    options: &ConfigureOptions,
     dictation: &crate::state::DictationState,
     repository_commands: &[crate::state::VoiceCommand],
 ) -> Result<StagedVocabularyConfiguration, String> {
-    let voice_commands = options
-        .get("voiceCommands")
-        .and_then(serde_json::Value::as_array)
-        .map(|pairs| {
-            pairs
-                .iter()
-                .filter_map(|pair| {
-                    let phrase = pair
-                        .get("phrase")
-                        .and_then(serde_json::Value::as_str)?
-                        .trim()
-                        .to_string();
-                    if phrase.is_empty() {
-                        return None;
-                    }
-                    let replacement = pair
-                        .get("replacement")
-                        .and_then(serde_json::Value::as_str)
-                        .unwrap_or("")
-                        .to_string();
-                    Some(crate::state::VoiceCommand {
-                        phrase,
-                        replacement,
-                    })
+    let voice_commands = options.voice_commands.as_ref().map(|pairs| {
+        pairs
+            .iter()
+            .filter_map(|pair| {
+                let phrase = pair
+                    .get("phrase")
+                    .and_then(serde_json::Value::as_str)?
+                    .trim()
+                    .to_string();
+                if phrase.is_empty() {
+                    return None;
+                }
+                let replacement = pair
+                    .get("replacement")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("")
+                    .to_string();
+                Some(crate::state::VoiceCommand {
+                    phrase,
+                    replacement,
                 })
-                .collect::<Vec<_>>()
-        });
+            })
+            .collect::<Vec<_>>()
+    });
     let entries = options
-        .get("vocabularyEntries")
+        .vocabulary_entries
+        .as_ref()
         .map(|value| {
             serde_json::from_value::<Vec<crate::state::VocabularyEntry>>(value.clone())
                 .map_err(|_| "Vocabulary entries have an invalid shape.".to_string())
@@ -1319,8 +3264,8 @@ fn stage_vocabulary_configuration(
         .transpose()?
         .or_else(|| {
             options
-                .get("customVocabulary")
-                .and_then(serde_json::Value::as_str)
+                .custom_vocabulary
+                .as_deref()
                 .map(legacy_vocabulary_entries)
         });
 
@@ -1347,7 +3292,7 @@ fn stage_vocabulary_configuration(
 
 #[tauri::command]
 pub async fn configure_dictation(
-    options: serde_json::Value,
+    options: ConfigureOptions,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, State>,
 ) -> Result<serde_json::Value, String> {
@@ -1363,10 +3308,7 @@ pub async fn configure_dictation(
         "configure_dictation"
     );
 
-    if let Some(pairs) = options
-        .get("voiceCommands")
-        .and_then(serde_json::Value::as_array)
-    {
+    if let Some(pairs) = &options.voice_commands {
         let legacy = pairs
             .iter()
             .filter_map(|pair| {
@@ -1398,14 +3340,8 @@ pub async fn configure_dictation(
         }
     }
 
-    let model = options
-        .get("model")
-        .and_then(|v| v.as_str())
-        .map(String::from);
-    let language = options
-        .get("language")
-        .and_then(|v| v.as_str())
-        .map(String::from);
+    let model = options.model.clone();
+    let language = options.language.clone();
     if let Some(requested) = model.as_deref() {
         let definition = model_runtime::model_definition(requested)?;
         if !model_runtime::model_supported(definition) {
@@ -1413,6 +3349,34 @@ pub async fn configure_dictation(
         }
     }
 
+    // Compile redaction before taking the settings lock so an invalid pattern
+    // rejects the whole update instead of leaving the filter half-applied.
+    let staged_redaction = options
+        .redaction
+        .as_ref()
+        .filter(|v| v.is_object())
+        .map(|value| {
+            let settings = parse_redaction_settings(value);
+            let redactor = crate::redaction::Redactor::build(&settings)?;
+            Ok::<_, String>((settings, redactor.map(Arc::new)))
+        })
+        .transpose()?;
+
+    // Turning audio saving on checks free space first, like a model download.
+    if options.save_audio == Some(true) {
+        let (saving, output_dir) = {
+            let dictation = state.app_state.dictation.lock_or_recover();
+            let output_dir = options
+                .output_dir
+                .clone()
+                .unwrap_or_else(|| dictation.output_dir.clone());
+            (dictation.save_audio, output_dir)
+        };
+        if !saving {
+            crate::storage::ensure_recording_space(&app_handle, &output_dir)?;
+        }
+    }
+
     let repository_commands = state
         .knowledge
         .all_voice_commands()
@@ -1427,7 +3391,7 @@ pub async fn configure_dictation(
         })
         .collect::<Vec<_>>();
     let mut dictation = state.app_state.dictation.lock_or_recover();
-    let backend_change_can_apply_now = dictation.status == DictationStatus::Idle;
+    let backend_change_can_apply_now = dictation.status.is_settled();
     let staged_vocabulary =
         stage_vocabulary_configuration(&options, &dictation, &repository_commands)?;
 
@@ -1465,126 +3429,212 @@ pub async fn configure_dictation(
         dictation.language = l;
     }
 
-    if let Some(auto_paste) = options.get("autoPaste").and_then(|v| v.as_bool()) {
+    if let Some(auto_paste) = options.auto_paste {
         dictation.auto_paste = auto_paste;
     }
 
-    if let Some(delay) = options.get("autoPasteDelayMs").and_then(|v| v.as_u64()) {
+    if let Some(delay) = options.auto_paste_delay_ms {
         dictation.auto_paste_delay_ms = delay.clamp(10, 500);
     }
 
-    if let Some(sensitivity) = options.get("vadSensitivity").and_then(|v| v.as_u64()) {
+    if let Some(sensitivity) = options.vad_sensitivity {
         dictation.vad_sensitivity = (sensitivity as u32).clamp(0, 100);
     }
 
-    if let Some(sp) = options.get("smartPunctuation").and_then(|v| v.as_bool()) {
+    if let Some(sp) = options.smart_punctuation {
         dictation.smart_punctuation = sp;
     }
 
-    if let Some(vc) = options
-        .get("voiceCommandsEnabled")
-        .and_then(|v| v.as_bool())
-    {
+    if let Some(vc) = options.voice_commands_enabled {
         dictation.voice_commands_enabled = vc;
     }
 
-    if let Some(save_transcript) = options.get("saveTranscript").and_then(|v| v.as_bool()) {
+    if let Some(save_transcript) = options.save_transcript {
         dictation.save_transcript = save_transcript;
     }
 
-    if let Some(save_audio) = options.get("saveAudio").and_then(|v| v.as_bool()) {
+    if let Some(save_audio) = options.save_audio {
         dictation.save_audio = save_audio;
     }
 
-    if let Some(output_dir) = options.get("outputDir").and_then(|v| v.as_str()) {
+    if let Some(output_dir) = options.output_dir.as_deref() {
         dictation.output_dir = output_dir.to_string();
     }
 
     // Per-app profiles carry nullable delivery/transformation overrides. A
     // missing/null value means "no override". Entries without a bundleId are
     // skipped. Replaces the whole list when the key is present.
-    if let Some(profiles) = options.get("appProfiles").and_then(|v| v.as_array()) {
-        dictation.app_profiles = profiles
+    if let Some(profiles) = &options.app_profiles {
+        dictation.app_profiles = profiles.iter().filter_map(parse_app_profile).collect();
+        reconcile_app_profiles(&app_handle, &state.app_state, &dictation.app_profiles);
+    }
+
+    // Hotkey-bound dictation profiles. Same null-means-inherit contract as
+    // app profiles; entries without an id are skipped.
+    if let Some(profiles) = &options.dictation_profiles {
+        dictation.dictation_profiles = profiles
             .iter()
-            .filter_map(|p| {
-                let bundle_id = p
-                    .get("bundleId")
-                    .and_then(|v| v.as_str())?
-                    .trim()
-                    .to_string();
-                if bundle_id.is_empty() {
-                    return None;
-                }
-                let label = p
-                    .get("label")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                // null/absent -> None (use global); otherwise the boolean override.
-                let auto_paste_override = p.get("autoPasteOverride").and_then(|v| v.as_bool());
-                let cleanup_override = p.get("cleanupOverride").and_then(|v| v.as_bool());
-                let cli_formatting_override =
-                    p.get("cliFormattingOverride").and_then(|v| v.as_bool());
-                let smart_formatting_override =
-                    p.get("smartFormattingOverride").and_then(|v| v.as_bool());
-                let writing_style = parse_writing_style(p.get("writingStyle"));
-                let ide_context_enabled = p
-                    .get("ideContextEnabled")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let ide_project_roots = p
-                    .get("ideProjectRoots")
-                    .and_then(|value| value.as_array())
-                    .map(|roots| {
-                        let roots = roots
-                            .iter()
-                            .filter_map(|root| root.as_str())
-                            .map(str::trim)
-                            .filter(|root| !root.is_empty())
-                            .map(str::to_string)
-                            .collect::<Vec<_>>();
-                        crate::ide_context::normalize_config_roots(&roots)
-                    })
-                    .unwrap_or_default();
-                Some(crate::state::AppProfile {
-                    bundle_id,
-                    label,
-                    auto_paste_override,
-                    cleanup_override,
-                    cli_formatting_override,
-                    smart_formatting_override,
-                    writing_style,
-                    ide_context_enabled,
-                    ide_project_roots,
-                })
-            })
+            .filter_map(parse_dictation_profile)
             .collect();
-        let requests = state
-            .app_state
-            .ide_context
-            .lock_or_recover()
-            .reconcile_profiles(&dictation.app_profiles);
-        for request in requests {
-            schedule_ide_scan(app_handle.clone(), request);
+    }
+
+    // Rewrite templates: replaces the whole list when present.
+    if let Some(templates) = &options.rewrite_templates {
+        dictation.rewrite_templates = rewrite_templates::parse_templates(templates);
+    }
+
+    // Replacement rules: replaces the whole list when present, keeping order.
+    if let Some(rules) = &options.replacement_rules {
+        dictation.replacement_rules = crate::replacement_rules::parse_rules(rules);
+        crate::commands::replacement_rules::compile_rules(
+            &state.app_state,
+            &dictation.replacement_rules,
+        );
+    }
+
+    // Dictation macros: replaces the whole settings object when present.
+    if let Some(macros) = &options.dictation_macros {
+        dictation.dictation_macros = crate::dictation_macros::parse_settings(macros);
+    }
+
+    // Context biasing: replaces the whole settings object when present.
+    if let Some(biasing) = &options.context_biasing {
+        dictation.context_biasing = crate::context_biasing::parse_settings(biasing);
+    }
+
+    // Do-not-inject list: replaces the whole list when present, so an empty
+    // array deliberately clears the shipped defaults.
+    if let Some(blocklist) = &options.inject_blocklist {
+        let mut entries = Vec::new();
+        for bundle_id in blocklist
+            .iter()
+            .map(|bundle_id| bundle_id.trim())
+            .filter(|bundle_id| !bundle_id.is_empty())
+        {
+            if !entries
+                .iter()
+                .any(|existing: &String| existing == bundle_id)
+            {
+                entries.push(bundle_id.to_string());
+            }
         }
+        dictation.inject_blocklist = entries;
     }
 
-    if let Some(cleanup_enabled) = options.get("cleanupEnabled").and_then(|v| v.as_bool()) {
-        dictation.cleanup_enabled = cleanup_enabled;
+    if let Some(enabled) = options.privacy_mode {
+        crate::telemetry::set_privacy_mode(enabled);
     }
 
-    if let Some(enabled) = options
-        .get("smartFormattingEnabled")
-        .and_then(|v| v.as_bool())
+    if let Some(keep) = options.keep_audio_on_device {
+        crate::transcriber::set_keep_audio_on_device(keep);
+    }
+
+    if let Some(enabled) = options.hallucination_filter {
+        dictation.hallucination_filter = enabled;
+    }
+
+    if let Some(enabled) = options.learn_from_corrections {
+        dictation.learn_from_corrections = enabled;
+    }
+
+    if let Some(enabled) = options.append_mode {
+        dictation.append_mode = enabled;
+    }
+
+    if let Some(enabled) = options.legacy_automation {
+        dictation.legacy_automation = enabled;
+    }
+
+    if let Some(enabled) = options.preview_before_paste {
+        dictation.preview_before_paste = enabled;
+    }
+
+    if let Some(enabled) = options.cursor_context {
+        dictation.cursor_context = enabled;
+    }
+
+    if let Some(trailing) = options
+        .trailing_behavior
+        .as_deref()
+        .and_then(TrailingBehavior::parse)
+    {
+        dictation.trailing_behavior = trailing;
+    }
+
+    if let Some(decoding) = options.whisper_decoding.as_ref().filter(|v| v.is_object()) {
+        dictation.whisper_decoding = parse_whisper_decoding(decoding);
+    }
+
+    if let Some(policy) = options
+        .notification_policy
+        .as_deref()
+        .and_then(NotificationPolicy::parse)
+    {
+        dictation.notification_policy = policy;
+    }
+
+    if let Some(filter) = options.focus_filter.as_ref().filter(|v| v.is_object()) {
+        dictation.focus_filter = parse_focus_filter(filter);
+    }
+
+    if let Some(adaptive) = options.adaptive_model.as_ref().filter(|v| v.is_object()) {
+        dictation.adaptive_model = parse_adaptive_model(adaptive);
+    }
+
+    if let Some(budget) = options.latency_budget.as_ref().filter(|v| v.is_object()) {
+        dictation.latency_budget = parse_latency_budget(budget);
+    }
+
+    if let Some(dual) = options.dual_model.as_ref().filter(|v| v.is_object()) {
+        dictation.dual_model = parse_dual_model(dual);
+    }
+
+    if let Some(retry) = options.confidence_retry.as_ref().filter(|v| v.is_object()) {
+        dictation.confidence_retry = parse_confidence_retry(retry);
+    }
+
+    if let Some(alternatives) = options.alternatives.as_ref().filter(|v| v.is_object()) {
+        dictation.alternatives = parse_alternatives(alternatives);
+    }
+
+    if let Some(action) = options
+        .interrupted_recording_action
+        .as_deref()
+        .and_then(InterruptedRecordingAction::parse)
+    {
+        dictation.interrupted_recording_action = action;
+    }
+
+    if let Some(target) = options.notes_target.as_deref().and_then(NotesTarget::parse) {
+        dictation.notes_target = target;
+    }
+
+    if let Some(mode) = options
+        .selection_dictation
+        .as_deref()
+        .and_then(SelectionDictation::parse)
     {
+        dictation.selection_dictation = mode;
+    }
+
+    if let Some((settings, redactor)) = staged_redaction {
+        dictation.redaction = settings;
+        *state.app_state.redactor.lock_or_recover() = redactor;
+    }
+
+    if let Some(cleanup_enabled) = options.cleanup_enabled {
+        dictation.cleanup_enabled = cleanup_enabled;
+    }
+
+    if let Some(enabled) = options.smart_formatting_enabled {
         dictation.smart_formatting_enabled = enabled;
     }
 
-    if let Some(v) = options.get("cleanupRemoveFiller").and_then(|v| v.as_bool()) {
+    if let Some(v) = options.cleanup_remove_filler {
         dictation.cleanup_remove_filler = v;
     }
 
-    if let Some(v) = options.get("cleanupCapitalize").and_then(|v| v.as_bool()) {
+    if let Some(v) = options.cleanup_capitalize {
         dictation.cleanup_capitalize = v;
     }
 
@@ -1593,13 +3643,13 @@ pub async fn configure_dictation(
     // (or the explicit prebuild below) rescans. Disabling clears the cache so we
     // don't hold a stale prompt in memory.
     let mut code_vocab_dirty = false;
-    if let Some(enabled) = options.get("codeVocabEnabled").and_then(|v| v.as_bool()) {
+    if let Some(enabled) = options.code_vocab_enabled {
         if enabled != dictation.code_vocab_enabled {
             dictation.code_vocab_enabled = enabled;
             code_vocab_dirty = true;
         }
     }
-    if let Some(folder) = options.get("codeVocabFolder").and_then(|v| v.as_str()) {
+    if let Some(folder) = options.code_vocab_folder.as_deref() {
         if folder != dictation.code_vocab_folder {
             dictation.code_vocab_folder = folder.to_string();
             code_vocab_dirty = true;
@@ -1618,10 +3668,10 @@ pub async fn configure_dictation(
     }
 
     // Post-model correction toggles.
-    if let Some(v) = options.get("correctionEnabled").and_then(|v| v.as_bool()) {
+    if let Some(v) = options.correction_enabled {
         dictation.correction_enabled = v;
     }
-    if let Some(v) = options.get("correctionFuzzy").and_then(|v| v.as_bool()) {
+    if let Some(v) = options.correction_fuzzy {
         dictation.correction_fuzzy = v;
     }
 
@@ -1629,8 +3679,11 @@ pub async fn configure_dictation(
     // correction settings. Built here on settings-change, never per-utterance.
     rebuild_correction_matcher(&state.app_state, &dictation);
     state.app_state.bump_settings_revision();
+    let tray_auto_paste = dictation.auto_paste;
+    let tray_model = dictation.model_name.clone();
+    let focus_filter = dictation.focus_filter.clone();
 
-    if let Some(idle_timeout) = options.get("idleTimeoutMinutes").and_then(|v| v.as_u64()) {
+    if let Some(idle_timeout) = options.idle_timeout_minutes {
         let normalized = match idle_timeout {
             0 | 5 | 15 => idle_timeout as u32,
             _ => 5, // fall back to default
@@ -1638,12 +3691,25 @@ pub async fn configure_dictation(
         *state.app_state.idle_timeout_minutes.lock_or_recover() = normalized;
     }
 
-    // If model changed, swap/reset the backend so the next transcription loads
-    // the right engine for the selected model.
+    if let Some(keep_alive) = options.backend_keep_alive_mb {
+        let normalized = match keep_alive {
+            0 | 1024 | 2048 | 4096 => keep_alive,
+            _ => model_runtime::DEFAULT_KEEP_ALIVE_MB,
+        };
+        state.app_state.model_runtime.set_keep_alive_mb(normalized);
+    }
+
+    // If model changed, switch the backend so the next transcription loads
+    // the right engine for the selected model. A loaded engine of another kind
+    // stays parked while it fits the keep-alive budget.
     let mut idle_preparation = None;
     if model_changed && backend_change_can_apply_now {
         let new_model = dictation.model_name.clone();
-        if model_runtime::model_definition(&new_model)?.warm_on_startup {
+        // A model picked from the tray loads now so its "Loading…" state
+        // resolves instead of waiting for the next recording.
+        if model_runtime::model_definition(&new_model)?.warm_on_startup
+            || crate::commands::tray::model_requested(&new_model)
+        {
             idle_preparation = Some(new_model.clone());
         }
         drop(dictation); // Release dictation lock first
@@ -1663,7 +3729,7 @@ pub async fn configure_dictation(
         // immediately release the model this preparation is about to load.
         *state.app_state.last_transcription_at.lock_or_recover() = Some(std::time::Instant::now());
         spawn_idle_model_preparation(
-            app_handle,
+            app_handle.clone(),
             model_name,
             model_change_guard
                 .take()
@@ -1671,6 +3737,10 @@ pub async fn configure_dictation(
         );
     }
 
+    crate::commands::tray::sync_settings(&app_handle, tray_auto_paste, &tray_model);
+    crate::crash_reports::set_model(&tray_model);
+    crate::system_status::apply_focus_filter(&focus_filter);
+
     Ok(serde_json::json!({
         "type": "configured"
     }))
@@ -1995,6 +4065,22 @@ pub async fn scan_code_vocab(
     Ok(summary)
 }
 
+/// Bring the memory-only IDE indexes in line with a replaced profile list and
+/// start any scans the new opt-ins require.
+pub(crate) fn reconcile_app_profiles(
+    app_handle: &tauri::AppHandle,
+    app_state: &AppState,
+    profiles: &[crate::state::AppProfile],
+) {
+    let requests = app_state
+        .ide_context
+        .lock_or_recover()
+        .reconcile_profiles(profiles);
+    for request in requests {
+        schedule_ide_scan(app_handle.clone(), request);
+    }
+}
+
 /// Run a memory-only IDE project scan off the async runtime. Completion adopts
 /// only the still-current generation; root/profile changes and Clear invalidate
 /// the request before it can publish stale symbols.
@@ -2156,14 +4242,18 @@ pub async fn start_native_recording(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, State>,
     device_name: Option<String>,
-) -> Result<serde_json::Value, String> {
+    profile_id: Option<String>,
+) -> Result<StartRecordingResult, String> {
     // Hold through cpal readiness and the recording event. A quick release can
     // invoke stop while start_recording is waiting for its capture thread; the
     // stop command must observe the fully-started recorder, never a midpoint.
     let _transition = state.app_state.recording_transition.lock().await;
     if keyboard::is_app_disabled() {
         tracing::info!(target: "pipeline", "start_native_recording: app disabled — ignoring");
-        return Ok(serde_json::json!({ "type": "app_disabled", "state": "idle" }));
+        return Ok(StartRecordingResult::new(
+            StartRecordingKind::AppDisabled,
+            settled_status(&state.app_state),
+        ));
     }
     // A transform review parked at ReviewPending — failed (#327) or ready
     // (#329) — is auto-dismissed so the dictation key just works instead of
@@ -2200,60 +4290,63 @@ pub async fn start_native_recording(
     state.transform_runtime.shutdown();
     // Check and update status in one lock; assign recording ID in the same
     // critical section so no concurrent cancel/start can slip between them.
-    let rid = {
+    let (rid, session_overrides) = {
         let mut dictation = state.app_state.dictation.lock_or_recover();
         // Refuse if a file transcription holds the shared Whisper backend.
         // Checked under the dictation lock (which `transcribe_file` takes only
         // after claiming the flag) so the two paths can't both start.
         if state.app_state.file_transcribing.load(Ordering::SeqCst) {
             tracing::warn!(target: "pipeline", "start_native_recording: blocked — file transcription in progress");
-            return Ok(serde_json::json!({
-                "type": "busy_transcribing_file",
-                "state": "idle"
-            }));
+            return Ok(StartRecordingResult::new(
+                StartRecordingKind::BusyTranscribingFile,
+                dictation.settled_status(),
+            ));
         }
         if state.benchmark.is_running() {
             tracing::warn!(target: "pipeline", "start_native_recording: blocked — benchmark in progress");
-            return Ok(serde_json::json!({
-                "type": "busy_benchmarking",
-                "state": "idle"
-            }));
+            return Ok(StartRecordingResult::new(
+                StartRecordingKind::BusyBenchmarking,
+                dictation.settled_status(),
+            ));
         }
         // Refuse while a transform pass (issue #312) holds the shared Whisper
         // backend / clipboard / AX surface. Checked under the dictation lock,
         // same as the two guards above.
         if state.app_state.transform_status().blocks_recording() {
             tracing::warn!(target: "pipeline", "start_native_recording: blocked — transform in progress");
-            return Ok(serde_json::json!({
-                "type": "busy_transforming",
-                "state": "idle"
-            }));
+            return Ok(StartRecordingResult::new(
+                StartRecordingKind::BusyTransforming,
+                dictation.settled_status(),
+            ));
         }
         // Only one heavy inference runtime may be resident: refuse to record
         // while a local-LLM transform is in flight (supervisor busy).
         if state.transform_runtime.is_transform_busy() {
             tracing::warn!(target: "pipeline", "start_native_recording: blocked — transform runtime busy");
-            return Ok(serde_json::json!({
-                "type": "busy_transforming",
-                "state": "idle"
-            }));
+            return Ok(StartRecordingResult::new(
+                StartRecordingKind::BusyTransforming,
+                dictation.settled_status(),
+            ));
         }
         match dictation.status {
             DictationStatus::Recording => {
                 tracing::warn!(target: "pipeline", "start_native_recording: already recording");
-                return Ok(serde_json::json!({
-                    "type": "already_recording",
-                    "state": "recording"
-                }));
+                return Ok(StartRecordingResult::new(
+                    StartRecordingKind::AlreadyRecording,
+                    DictationStatus::Recording,
+                ));
             }
-            DictationStatus::Processing => {
+            // `process_audio` runs its pipeline inline, off the queue.
+            DictationStatus::Processing if dictation.queued_jobs.is_empty() => {
                 tracing::warn!(target: "pipeline", "start_native_recording: currently processing");
-                return Ok(serde_json::json!({
-                    "type": "already_processing",
-                    "state": "processing"
-                }));
+                return Ok(StartRecordingResult::new(
+                    StartRecordingKind::AlreadyProcessing,
+                    DictationStatus::Processing,
+                ));
             }
-            DictationStatus::Idle => {
+            // Otherwise Processing only means earlier takes are still
+            // transcribing on the queue; the new take records alongside them.
+            DictationStatus::Idle | DictationStatus::Buffering | DictationStatus::Processing => {
                 // A new recording invalidates any stale transform session
                 // (issue #312 PR-B2) — the guards above already ensure no
                 // transform is in progress, but a completed one (applied or
@@ -2265,16 +4358,41 @@ pub async fn start_native_recording(
                 crate::transform_apply::clear_session(&state.app_state);
                 let rid = state.app_state.next_recording_id();
                 dictation.status = DictationStatus::Recording;
-                rid
+                // An unknown id (a profile deleted mid-hold) records with the
+                // plain settings rather than refusing.
+                let session_overrides = profile_id
+                    .as_deref()
+                    .and_then(|id| {
+                        dictation
+                            .dictation_profiles
+                            .iter()
+                            .find(|profile| profile.id == id)
+                    })
+                    .map(SessionOverrides::from_dictation_profile)
+                    .unwrap_or_default();
+                (rid, session_overrides)
             }
         }
     };
     let bundle_id = crate::frontmost::frontmost_bundle_id();
     refresh_expired_ide_context(&app_handle, &state.app_state, bundle_id.as_deref());
-    let context = resolve_live_context(&state.app_state, &state.knowledge, bundle_id.as_deref());
+    let dictation_profile = session_overrides != SessionOverrides::default();
+    let context = resolve_live_context(
+        &state.app_state,
+        &state.knowledge,
+        bundle_id.as_deref(),
+        session_overrides,
+    );
+    announce_model_downgrade(&app_handle, rid, &context);
     state
         .app_state
         .set_active_context(rid, Arc::clone(&context));
+    if context.context_capture.selected_text {
+        spawn_selection_capture(app_handle.clone(), rid);
+    }
+    if context.context_capture.prompt_window_title || context.context_capture.prompt_selected_text {
+        spawn_context_biasing_capture(app_handle.clone(), rid, &context);
+    }
     if let Err(error) = state.performance.begin_dictation(
         rid,
         runtime_identity(&context.transcription.model_name, ModelWarmStateV1::Unknown),
@@ -2291,13 +4409,16 @@ pub async fn start_native_recording(
         recording_id = rid,
         frontmost_app_detected = context.app.bundle_id.is_some(),
         matched_profile = context.matched_profile.is_some(),
+        dictation_profile,
         writing_style = context.writing_style.as_str(),
         writing_style_code = context.writing_style.code(),
         vocabulary_version = context.vocabulary.version,
         context_reads_enabled = context.context_capture.selected_text
             || context.context_capture.surrounding_screen_text
             || context.context_capture.clipboard
-            || context.context_capture.local_project_index,
+            || context.context_capture.local_project_index
+            || context.context_capture.prompt_window_title
+            || context.context_capture.prompt_selected_text,
         "dictation context resolved"
     );
     tracing::info!(target: "pipeline", "start_native_recording: device={} recording_id={}", device_name.as_deref().unwrap_or("system_default"), rid);
@@ -2306,7 +4427,7 @@ pub async fn start_native_recording(
         state.app_state.clear_active_context(rid);
         let mut dictation = state.app_state.dictation.lock_or_recover();
         if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
-            dictation.status = DictationStatus::Idle;
+            dictation.status = dictation.settled_status();
         }
         let _ = state.performance.complete(
             &RunCorrelationV1::Dictation { recording_id: rid },
@@ -2321,7 +4442,8 @@ pub async fn start_native_recording(
         return Err(e);
     }
     *state.app_state.last_transcription_at.lock_or_recover() = Some(std::time::Instant::now());
-    let _ = app_handle.emit("recording-status-changed", "recording");
+    markers::begin(rid);
+    events::emit_recording_status(&app_handle, DictationStatus::Recording);
     tracing::info!(target: "pipeline", "start_native_recording: started");
     spawn_model_preparation(
         app_handle.clone(),
@@ -2329,38 +4451,40 @@ pub async fn start_native_recording(
         rid,
     );
 
-    Ok(serde_json::json!({
-        "type": "recording_started",
-        "state": "recording"
-    }))
+    Ok(StartRecordingResult::new(
+        StartRecordingKind::RecordingStarted,
+        DictationStatus::Recording,
+    ))
 }
 
 #[tauri::command]
 pub async fn stop_native_recording(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, State>,
-) -> Result<serde_json::Value, String> {
+) -> Result<TranscriptionResult, String> {
     let transition = state.app_state.recording_transition.lock().await;
     // Atomic check-and-set + rid capture in a single lock to avoid TOCTOU gap
     let rid = {
         let mut dictation = state.app_state.dictation.lock_or_recover();
         match dictation.status {
             DictationStatus::Processing => {
-                return Ok(serde_json::json!({
-                    "type": "already_processing",
-                    "state": "processing"
-                }))
+                return Ok(TranscriptionResult::declined(
+                    TranscriptionKind::AlreadyProcessing,
+                    "processing",
+                ))
             }
-            DictationStatus::Idle => {
+            DictationStatus::Idle | DictationStatus::Buffering => {
                 tracing::warn!(target: "pipeline", "stop_native_recording: not recording");
-                return Ok(serde_json::json!({
-                    "type": "not_recording",
-                    "state": "idle"
-                }));
+                return Ok(TranscriptionResult::declined(
+                    TranscriptionKind::NotRecording,
+                    dictation.status.as_str(),
+                ));
             }
             DictationStatus::Recording => {
+                let rid = state.app_state.recording_id.load(Ordering::SeqCst);
                 dictation.status = DictationStatus::Processing;
-                state.app_state.recording_id.load(Ordering::SeqCst)
+                dictation.queued_jobs.push(rid);
+                rid
             }
         }
     };
@@ -2372,17 +4496,18 @@ pub async fn stop_native_recording(
         Some(context) => context,
         None => {
             let mut dictation = state.app_state.dictation.lock_or_recover();
+            dictation.finish_job(rid);
             if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
-                dictation.status = DictationStatus::Idle;
+                dictation.status = dictation.settled_status();
             }
             keyboard::set_processing(false);
-            let _ = app_handle.emit("recording-status-changed", "idle");
+            events::emit_recording_status(&app_handle, dictation.settled_status());
             return Err(format!("Missing dictation context for recording {rid}"));
         }
     };
     keyboard::set_processing(true);
     tracing::info!(target: "pipeline", "stop_native_recording: stopping");
-    let _ = app_handle.emit("recording-status-changed", "processing");
+    events::emit_recording_status(&app_handle, DictationStatus::Processing);
 
     // Guard resets status to Idle if stop_recording fails or samples are empty;
     // disarmed before handing off to run_transcription_pipeline (which has its own guard)
@@ -2390,16 +4515,24 @@ pub async fn stop_native_recording(
 
     // Phase: Audio teardown + 16kHz resample
     let t_total = std::time::Instant::now();
-    let samples = audio::stop_recording().map_err(|e| {
-        tracing::error!(target: "audio", "stop_native_recording: stop_recording failed: {}", e);
-        if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
-            let _ = app_handle.emit("recording-status-changed", "idle");
-        }
-        e
-    })?;
+    let pipeline = pipeline_trace::pipeline_span(rid);
+    let samples = pipeline
+        .in_scope(|| {
+            let _capture = pipeline_trace::phase(PipelinePhase::Capture).entered();
+            audio::stop_recording()
+        })
+        .map_err(|e| {
+            tracing::error!(target: "audio", "stop_native_recording: stop_recording failed: {}", e);
+            markers::discard(rid);
+            if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
+                events::emit_recording_status(&app_handle, abandon_job(&state.app_state, rid));
+            }
+            e
+        })?;
     // Audio is now detached from the recorder state. Let cancel or another
     // rejected start inspect Processing while inference continues.
     drop(transition);
+    let markers = markers::finish(rid, t_total, samples.len());
     tracing::info!(target: "pipeline", "audio teardown + resample: {:?}", t_total.elapsed());
     performance_guard.record(StageTimingV1::measured(
         PerformanceStageV1::CaptureFinalization,
@@ -2410,7 +4543,7 @@ pub async fn stop_native_recording(
         tracing::info!(target: "pipeline", "stop_native_recording: no audio captured");
         // guard drops on return, resetting status to Idle
         if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
-            let _ = app_handle.emit("recording-status-changed", "idle");
+            events::emit_recording_status(&app_handle, abandon_job(&state.app_state, rid));
         }
         let _ = performance_guard.finish(
             RunOutcomeV1::NoSpeech,
@@ -2421,11 +4554,10 @@ pub async fn stop_native_recording(
             Some(ContentFreeInputSummaryV1::audio(samples.len() as u64 / 16)),
             None,
         );
-        return Ok(serde_json::json!({
-            "type": "transcription",
-            "text": "",
-            "state": "idle"
-        }));
+        return Ok(
+            TranscriptionResult::new(TranscriptionKind::Transcription, "")
+                .with_state(settled_status(&state.app_state).as_str()),
+        );
     }
 
     /// Minimum recording duration to process. Recordings shorter than this
@@ -2436,7 +4568,7 @@ pub async fn stop_native_recording(
         tracing::info!(target: "pipeline", "stop_native_recording: recording too short ({}ms), discarding",
             samples.len() / 16); // samples / 16_000 * 1000
         if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
-            let _ = app_handle.emit("recording-status-changed", "idle");
+            events::emit_recording_status(&app_handle, abandon_job(&state.app_state, rid));
         }
         let _ = performance_guard.finish(
             RunOutcomeV1::NoSpeech,
@@ -2447,54 +4579,135 @@ pub async fn stop_native_recording(
             Some(ContentFreeInputSummaryV1::audio(samples.len() as u64 / 16)),
             None,
         );
-        return Ok(serde_json::json!({
-            "type": "transcription",
-            "text": "",
-            "state": "idle"
-        }));
+        return Ok(
+            TranscriptionResult::new(TranscriptionKind::Transcription, "")
+                .with_state(settled_status(&state.app_state).as_str()),
+        );
     }
 
-    // Hand off status management to the pipeline's own guard
-    guard.disarm();
     performance_guard.enter(PerformanceStageV1::Vad);
+    if let Err(error) = spawn_transcription_job(
+        app_handle.clone(),
+        rid,
+        samples,
+        markers,
+        context,
+        performance_guard,
+        t_total,
+        pipeline,
+    ) {
+        drop(guard);
+        keyboard::set_processing(false);
+        events::emit_recording_status(&app_handle, settled_status(&state.app_state));
+        return Err(error);
+    }
+    // Hand off status management to the pipeline's own guard, and let the
+    // hotkey start the next take while this one transcribes.
+    guard.disarm();
+    keyboard::set_processing(false);
+    tracing::info!(target: "pipeline", recording_id = rid, "stop_native_recording: transcription queued");
+    Ok(TranscriptionResult::queued(rid))
+}
+
+/// Queue a stopped recording's transcription behind any earlier ones.
+///
+/// Inference holds the model backend for the whole decode, so it must not run
+/// on the command's async task. `stop_native_recording` returns the job id at
+/// once and the result reaches every window as an event:
+/// `transcription-complete`, `no-speech-detected`, or `transcription-failed`,
+/// each with the job id as `recordingId`. `cancel_native_recording` stops
+/// every queued job at the pipeline's next checkpoint.
+fn spawn_transcription_job(
+    app_handle: tauri::AppHandle,
+    recording_id: u64,
+    samples: Vec<f32>,
+    markers: Markers,
+    context: Arc<DictationContextSnapshot>,
+    performance_guard: PerformanceRunGuard,
+    t_total: std::time::Instant,
+    pipeline: tracing::Span,
+) -> Result<(), String> {
+    let state = app_handle.state::<State>();
+    let job_handle = app_handle.clone();
+    state.app_state.transcription_queue.submit(move || {
+        let result = tauri::async_runtime::block_on(
+            finish_transcription_job(
+                &job_handle,
+                recording_id,
+                samples,
+                markers,
+                context,
+                performance_guard,
+                t_total,
+            )
+            .instrument(pipeline),
+        );
+        if let Err(error) = result {
+            tracing::error!(target: "pipeline", recording_id, "transcription job failed: {}", error);
+            let state = job_handle.state::<State>();
+            crate::notifications::dictation_failed(
+                &job_handle,
+                &state.app_state,
+                "Dictation failed. Open Murmur for details.",
+            );
+            events::emit_transcription_failed(&job_handle, recording_id, &error);
+            events::emit_overlay_state(
+                &job_handle,
+                &events::OverlayState::Error {
+                    message: "Dictation failed".to_string(),
+                },
+            );
+        }
+    })
+}
+
+async fn finish_transcription_job(
+    app_handle: &tauri::AppHandle,
+    rid: u64,
+    samples: Vec<f32>,
+    markers: Markers,
+    context: Arc<DictationContextSnapshot>,
+    mut performance_guard: PerformanceRunGuard,
+    t_total: std::time::Instant,
+) -> Result<(), String> {
+    let state = app_handle.state::<State>();
 
     let pipeline_result = run_transcription_pipeline(
         &samples,
-        &app_handle,
+        app_handle,
         &state.app_state,
         rid,
         &mut performance_guard,
         Arc::clone(&context),
+        &markers,
     )
     .await;
-    // Only emit idle if this recording wasn't cancelled/superseded by a new one.
+    // Emit the settled status if this is still the newest recording, or if it
+    // was the last queued job behind a newer recording that was cancelled.
     // Hold the dictation lock across the check+emit to prevent a concurrent
     // start from interleaving a "recording" status between our check and emit.
     {
-        let _dictation = state.app_state.dictation.lock_or_recover();
-        if state.app_state.recording_id.load(Ordering::SeqCst) == rid {
+        let mut dictation = state.app_state.dictation.lock_or_recover();
+        dictation.finish_job(rid);
+        let newest = state.app_state.recording_id.load(Ordering::SeqCst) == rid;
+        let settled = dictation.settled_status();
+        let drained = dictation.status == DictationStatus::Processing && settled.is_settled();
+        if newest || drained {
+            dictation.status = settled;
             keyboard::set_processing(false);
-            let _ = app_handle.emit("recording-status-changed", "idle");
+            events::emit_recording_status(app_handle, dictation.status);
         }
     }
-    let pipeline = match pipeline_result {
-        Ok(result) => result,
-        Err(error) => {
-            tracing::error!(target: "pipeline", "stop_native_recording: pipeline failed: {}", error);
-            return Err(error);
-        }
-    };
+    let pipeline = pipeline_result?;
     let text = pipeline.text;
     let timings = pipeline.timings;
+    let confidence = pipeline.confidence;
+    let tokens = confidence.as_ref().map(|c| c.tokens() as u64);
 
     let total_ms = t_total.elapsed().as_millis() as u64;
     let audio_secs = samples.len() as f64 / 16_000.0;
-    let word_count = if text.trim().is_empty() {
-        0
-    } else {
-        text.split_whitespace().count()
-    };
-    let char_count = text.len();
+    let word_count = crate::text_metrics::word_count(&text);
+    let char_count = text.chars().count();
     let model_name = context.transcription.model_name.clone();
     let backend_name = model_runtime::model_definition(&model_name)?
         .backend
@@ -2514,18 +4727,32 @@ pub async fn stop_native_recording(
         audio_secs = audio_secs,
         word_count = word_count,
         char_count = char_count,
+        tokens,
+        tokens_per_sec = tokens.and_then(|t| crate::dictation_stats::tokens_per_sec(t, timings.token_decode_ms)),
         rss_before_mb = timings.rss_before_mb,
         rss_after_mb = timings.rss_after_mb,
         model = model_name.as_str(),
         backend = backend_name.as_str(),
+        avg_logprob = confidence.as_ref().map(|c| c.avg_logprob),
+        no_speech_prob = confidence.as_ref().map(|c| c.no_speech_prob),
+        low_confidence = confidence.as_ref().is_some_and(|c| c.low),
         "transcription complete"
     );
 
     let outcome = match pipeline.terminal {
         PipelineTerminal::Success => RunOutcomeV1::Success,
-        PipelineTerminal::NoSpeech => RunOutcomeV1::NoSpeech,
+        PipelineTerminal::NoSpeech(_) => RunOutcomeV1::NoSpeech,
         PipelineTerminal::Cancelled(stage) => RunOutcomeV1::Cancelled { stage },
     };
+    if outcome == RunOutcomeV1::Success && word_count > 0 {
+        crate::dictation_stats::record(
+            word_count,
+            (audio_secs * 1_000.0).round() as u64,
+            &model_name,
+            tokens,
+            timings.token_decode_ms,
+        );
+    }
     let warm_state = timings.warm_state.unwrap_or(ModelWarmStateV1::Unknown);
     let _ = performance_guard.finish(
         outcome,
@@ -2539,58 +4766,82 @@ pub async fn stop_native_recording(
 
     // Broadcast transcription result to all windows (so the main window can update
     // its history even when recording was initiated from the overlay).
-    let recording_secs = samples.len() / 16_000;
+    let recording_secs = samples.len() as u64 / 16_000;
     if !text.is_empty() {
         let teaching_context = crate::correct_and_teach::teaching_context(
             context.app.bundle_id.as_deref(),
             context.matched_profile.as_ref().map(|profile| profile.label.as_str()),
             context.teaching_project_root.as_deref(),
         );
-        let _ = app_handle.emit("transcription-complete", serde_json::json!({
-            "recordingId": rid,
-            "text": text,
-            "duration": recording_secs,
-            "teachingContext": teaching_context
-        }));
+        let (processed_on, processed_by) = model_runtime::processed_on(&model_name);
+        events::emit_transcription_complete(
+            app_handle,
+            &events::TranscriptionComplete {
+                recording_id: rid,
+                text: text.clone(),
+                duration: recording_secs,
+                teaching_context,
+                confidence,
+                tokens,
+                decode_ms: timings.token_decode_ms,
+                markers: markers.times_ms(samples.len()),
+                segments: pipeline.segments,
+                processed_on,
+                processed_by,
+            },
+        );
+        crate::notifications::transcription_complete(app_handle, &state.app_state, &text);
+    }
+    if let Some(audio) = pipeline.refine_audio {
+        spawn_refinement(app_handle.clone(), rid, context, text, samples, audio);
+    } else if let Some(audio) = pipeline.alternatives_audio {
+        spawn_alternatives(app_handle.clone(), rid, context, text, samples, audio);
+    } else if let Some(template) = pipeline.rewrite {
+        spawn_rewrite(app_handle.clone(), rid, context, template, text);
+    } else if let Some(selection) = pipeline.selection {
+        spawn_selection_delivery(app_handle.clone(), rid, context, selection, text);
     }
 
-    Ok(serde_json::json!({
-        "type": "transcription",
-        "text": text,
-        "state": "idle"
-    }))
+    Ok(())
 }
 
 /// Cancel an in-progress recording or transcription.
 ///
-/// - **Recording**: stops audio capture, discards samples, resets to Idle.
-/// - **Processing**: marks the current recording_id as cancelled so the
-///   pipeline discards its result at the next checkpoint; immediately
+/// - **Recording**: stops audio capture, discards samples, resets to Idle (or
+///   Processing while earlier takes are still queued).
+/// - **Processing**: marks every queued recording as cancelled so the
+///   pipeline discards their results at the next checkpoint; immediately
 ///   emits idle status so the UI resets without waiting for whisper.
-/// - **Idle**: no-op.
+/// - **Idle** / **Buffering**: no-op; buffered takes survive a cancelled take.
 #[tauri::command]
 pub async fn cancel_native_recording(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, State>,
 ) -> Result<(), String> {
     let _transition = state.app_state.recording_transition.lock().await;
-    let (prev_status, rid) = {
+    let (prev_status, rid, settled) = {
         let mut dictation = state.app_state.dictation.lock_or_recover();
         let prev = dictation.status;
         let rid = state.app_state.recording_id.load(Ordering::SeqCst);
         match prev {
-            DictationStatus::Idle => return Ok(()),
-            DictationStatus::Recording | DictationStatus::Processing => {
-                dictation.status = DictationStatus::Idle;
+            DictationStatus::Idle | DictationStatus::Buffering => return Ok(()),
+            DictationStatus::Recording => {
+                dictation.status = dictation.settled_status();
+            }
+            DictationStatus::Processing => {
+                // `cancel_recording(rid)` below covers every earlier id too.
+                dictation.queued_jobs.clear();
+                dictation.status = dictation.settled_status();
             }
         }
-        (prev, rid)
+        (prev, rid, dictation.status)
     };
     state.app_state.clear_active_context(rid);
 
     let stop_err = match prev_status {
         DictationStatus::Recording => {
             // Stop audio capture and discard samples
+            markers::discard(rid);
             if let Err(e) = audio::stop_recording() {
                 tracing::error!(target: "audio", "cancel_native_recording: stop_recording failed: {}", e);
                 Some(e)
@@ -2605,12 +4856,12 @@ pub async fn cancel_native_recording(
             tracing::info!(target: "pipeline", "cancel_native_recording: processing cancelled (recording_id={})", rid);
             None
         }
-        DictationStatus::Idle => unreachable!(),
+        DictationStatus::Idle | DictationStatus::Buffering => unreachable!(),
     };
 
     // Always emit feedback so the UI resets, even if stop_recording failed
     keyboard::set_processing(false);
-    let _ = app_handle.emit("recording-status-changed", "idle");
+    events::emit_recording_status(&app_handle, settled);
     let _ = app_handle.emit(
         "recording-cancelled",
         serde_json::json!({ "recordingId": rid }),
@@ -2619,7 +4870,7 @@ pub async fn cancel_native_recording(
     let stage = match prev_status {
         DictationStatus::Recording => PerformanceStageV1::CaptureFinalization,
         DictationStatus::Processing => PerformanceStageV1::InferenceDecode,
-        DictationStatus::Idle => unreachable!(),
+        DictationStatus::Idle | DictationStatus::Buffering => unreachable!(),
     };
     let outcome = if stop_err.is_some() {
         RunOutcomeV1::Failed {
@@ -2688,7 +4939,7 @@ pub async fn transcribe_file(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, State>,
     file_path: String,
-) -> Result<serde_json::Value, String> {
+) -> Result<TranscriptionResult, String> {
     // Mutual exclusion with live dictation: both share one Whisper backend.
     // Claim the slot first (so a racing `start_native_recording` is blocked),
     // then refuse if a live recording/processing is already underway. The guard
@@ -2742,7 +4993,7 @@ pub async fn transcribe_file(
         if state.app_state.transform_status().blocks_recording() {
             return Err("Wait for the transform to finish before transcribing a file.".to_string());
         }
-        if dictation.status != DictationStatus::Idle {
+        if !dictation.status.is_settled() {
             return Err(
                 "Can't transcribe a file while recording or processing live audio. \
                  Stop the current recording first."
@@ -2798,14 +5049,22 @@ pub async fn transcribe_file(
     ));
 
     // Read the settings shared with live dictation in one lock.
-    let (model_name, language, vad_sensitivity, custom_vocabulary, smart_punctuation) = {
+    let (
+        model_name,
+        language,
+        vad_sensitivity,
+        custom_vocabulary,
+        smart_punctuation,
+        whisper_decoding,
+    ) = {
         let dictation = state.app_state.dictation.lock_or_recover();
         (
             dictation.model_name.clone(),
             dictation.language.clone(),
             dictation.vad_sensitivity,
-            dictation.custom_vocabulary.clone(),
+            with_learned_terms(&dictation.custom_vocabulary, &dictation),
             dictation.smart_punctuation,
+            dictation.whisper_decoding,
         )
     };
     let _ = state.performance.update_active(
@@ -2849,9 +5108,10 @@ pub async fn transcribe_file(
                         )),
                         Some(runtime_identity(&model_name, ModelWarmStateV1::Unknown)),
                     );
-                    return Ok(serde_json::json!({
-                        "type": "file_transcription", "text": "", "duration": duration_secs
-                    }));
+                    return Ok(TranscriptionResult {
+                        duration: Some(duration_secs),
+                        ..TranscriptionResult::new(TranscriptionKind::FileTranscription, "")
+                    });
                 }
                 Ok(vad::VadResult::Speech(trimmed)) => {
                     let vad_trimmed = trimmed.len() != samples.len();
@@ -2888,6 +5148,7 @@ pub async fn transcribe_file(
         &model_name,
         PreparationReason::FileTranscription,
         |backend| {
+            backend.set_whisper_decoding(&whisper_decoding);
             let decode_started = std::time::Instant::now();
             let result = transcribe_with_coreml_vad_retry(
                 backend,
@@ -2906,7 +5167,8 @@ pub async fn transcribe_file(
     let model_load_ms = load_report.load_ms;
     // Imported files retain their existing raw-ASR output. They still pass through
     // the same authoritative transformation entry point with every stage disabled,
-    // leaving delivery/UI behavior byte-for-byte unchanged.
+    // leaving delivery/UI behavior byte-for-byte unchanged. The one exception is
+    // redaction: a user who opted in expects it on everything that reaches history.
     let transform_context = crate::transcript_transform::TranscriptContext {
         session_id: state.app_state.next_transcript_session_id(),
         source: crate::transcript_transform::TranscriptSource::File,
@@ -2919,7 +5181,10 @@ pub async fn transcribe_file(
     let transformed = crate::transcript_transform::transform_transcript(
         text,
         &transform_context,
-        crate::transcript_transform::TranscriptTransformResources::empty(),
+        crate::transcript_transform::TranscriptTransformResources {
+            redactor: state.app_state.redactor.lock_or_recover().clone(),
+            ..crate::transcript_transform::TranscriptTransformResources::empty()
+        },
     )
     .map_err(|error| error.to_string())?;
     let transform_ms = transform_started.elapsed().as_millis() as u64;
@@ -2932,11 +5197,7 @@ pub async fn transcribe_file(
 
     *state.app_state.last_transcription_at.lock_or_recover() = Some(std::time::Instant::now());
 
-    let word_count = if text.trim().is_empty() {
-        0
-    } else {
-        text.split_whitespace().count()
-    };
+    let word_count = crate::text_metrics::word_count(&text);
     tracing::info!(
         target: "pipeline",
         file_run_id,
@@ -2978,12 +5239,309 @@ pub async fn transcribe_file(
         Some(runtime_identity(&model_name, warm_state)),
     );
 
-    Ok(serde_json::json!({
-        "type": "file_transcription",
-        "fileRunId": file_run_id,
-        "text": text,
-        "duration": duration_secs
-    }))
+    let (processed_on, processed_by) = model_runtime::processed_on(&model_name);
+    Ok(TranscriptionResult {
+        file_run_id: Some(file_run_id),
+        duration: Some(duration_secs),
+        processed_on: Some(processed_on),
+        processed_by,
+        ..TranscriptionResult::new(TranscriptionKind::FileTranscription, text)
+    })
+}
+
+/// Transcribe the audio file on the clipboard (or selected in Finder) and
+/// deliver the text like a dictation: clipboard write plus auto-paste into the
+/// frontmost app, honoring the global paste settings, the app's injection
+/// method, and the do-not-inject list. Shares `transcribe_file`'s exclusion
+/// guards and verbatim output. Failures are also emitted as
+/// `clipboard-audio-failed`, since the tray and the hotkey have no caller to
+/// show them.
+#[tauri::command]
+pub async fn transcribe_clipboard_audio(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+) -> Result<TranscriptionResult, String> {
+    let result = deliver_clipboard_audio(&app_handle, state).await;
+    if let Err(e) = &result {
+        tracing::warn!(target: "pipeline", "transcribe_clipboard_audio failed: {}", e);
+        let _ = app_handle.emit("clipboard-audio-failed", e);
+    }
+    result
+}
+
+async fn deliver_clipboard_audio(
+    app_handle: &tauri::AppHandle,
+    state: tauri::State<'_, State>,
+) -> Result<TranscriptionResult, String> {
+    // The Finder fallback runs `osascript`, which can block for seconds.
+    let (file_path, source) =
+        tauri::async_runtime::spawn_blocking(crate::clipboard_audio::locate_audio_file)
+            .await
+            .map_err(|e| format!("Failed to look for copied audio: {}", e))??;
+    tracing::info!(target: "pipeline", source = source.as_str(), "transcribe_clipboard_audio: file located");
+    let result = transcribe_file(app_handle.clone(), state.clone(), file_path).await?;
+    let text = result.text.clone().unwrap_or_default();
+    if text.trim().is_empty() {
+        return Ok(result);
+    }
+
+    let bundle_id = crate::frontmost::frontmost_bundle_id();
+    let (auto_paste, paste_delay_ms, injection_method) = {
+        let dictation = state.app_state.dictation.lock_or_recover();
+        let blocked = bundle_id.as_deref().is_some_and(|bundle_id| {
+            dictation
+                .inject_blocklist
+                .iter()
+                .any(|blocked| blocked == bundle_id)
+        });
+        (
+            dictation.auto_paste && !blocked,
+            dictation.auto_paste_delay_ms,
+            crate::dictation_context::injection_method_for(&dictation, bundle_id.as_deref()),
+        )
+    };
+    deliver_text(
+        app_handle,
+        &state.app_state,
+        "transcribe_clipboard_audio",
+        text,
+        auto_paste,
+        paste_delay_ms,
+        injection_method,
+    )
+    .await?;
+    Ok(result)
+}
+
+/// Mirror the newest history entry, or clear it with `None`. The frontend
+/// calls this whenever its history changes, so the tray items and the
+/// paste-last hotkey follow edits, refinements, and Clear History.
+#[tauri::command]
+pub fn set_last_transcription(state: tauri::State<'_, State>, text: Option<String>) {
+    let text = text.filter(|text| !text.trim().is_empty());
+    crate::commands::tray::show_last_transcription(text.as_deref());
+    *state.app_state.last_transcript.lock_or_recover() = text;
+}
+
+fn last_transcription(app_state: &AppState) -> Result<String, String> {
+    app_state
+        .last_transcript
+        .lock_or_recover()
+        .clone()
+        .ok_or_else(|| "There is no transcription yet.".to_string())
+}
+
+/// Copy the newest history entry to the clipboard.
+#[tauri::command]
+pub fn copy_last_transcription(state: tauri::State<'_, State>) -> Result<(), String> {
+    injector::write_clipboard_text(&last_transcription(&state.app_state)?)
+}
+
+/// Paste the newest history entry into the frontmost app, so a failed
+/// auto-paste never costs the user the dictation. Pastes even with auto-paste
+/// off, but honors the paste delay, legacy automation, and the do-not-inject
+/// list; the text is on the clipboard either way.
+#[tauri::command]
+pub async fn paste_last_transcription(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+) -> Result<(), String> {
+    let text = last_transcription(&state.app_state)?;
+    let bundle_id = crate::frontmost::frontmost_bundle_id();
+    let (blocked, paste_delay_ms, injection_method) = {
+        let dictation = state.app_state.dictation.lock_or_recover();
+        let blocked = bundle_id.as_deref().is_some_and(|bundle_id| {
+            dictation
+                .inject_blocklist
+                .iter()
+                .any(|blocked| blocked == bundle_id)
+        });
+        let injection_method = if dictation.legacy_automation {
+            crate::state::InjectionMethod::SystemEvents
+        } else {
+            crate::state::InjectionMethod::Native
+        };
+        (blocked, dictation.auto_paste_delay_ms, injection_method)
+    };
+    deliver_text(
+        &app_handle,
+        &state.app_state,
+        "paste_last_transcription",
+        text,
+        !blocked,
+        paste_delay_ms,
+        injection_method,
+    )
+    .await?;
+    if blocked {
+        let _ = app_handle.emit(
+            "injection-blocked",
+            "Murmur doesn't paste into this app. Text is in your clipboard.",
+        );
+    }
+    Ok(())
+}
+
+/// The dictation preview mode is holding, if any, so a freshly opened
+/// overlay can show it.
+#[tauri::command]
+pub fn get_pending_injection(state: tauri::State<'_, State>) -> Option<InjectionPending> {
+    state
+        .app_state
+        .pending_injection
+        .lock_or_recover()
+        .as_ref()
+        .map(InjectionPending::from)
+}
+
+/// Accept preview `id`: paste it into the frontmost app with the delivery
+/// settings it was held with. Refused once the preview was discarded,
+/// replaced, or expired.
+#[tauri::command]
+pub async fn confirm_injection(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+    id: u64,
+) -> Result<(), String> {
+    let pending = {
+        let mut slot = state.app_state.pending_injection.lock_or_recover();
+        injection_preview::take_if(&mut slot, id)
+    }
+    .ok_or_else(|| "That preview is no longer pending.".to_string())?;
+    emit_injection_resolved(&app_handle, id, PreviewOutcome::Accepted);
+    tracing::info!(target: "pipeline", recording_id = id, auto_paste = pending.auto_paste, "preview accepted");
+
+    let PendingInjection {
+        text,
+        auto_paste,
+        injection_blocked,
+        paste_delay_ms,
+        injection_method,
+        trailing,
+        cursor_context: fit_to_caret,
+        ..
+    } = pending;
+    let text_to_inject = read_caret_parts(
+        &app_handle,
+        fit_to_caret && auto_paste,
+        trailing == TrailingBehavior::Smart && auto_paste,
+    )
+    .await
+    .deliver(&text, trailing);
+    deliver_text(
+        &app_handle,
+        &state.app_state,
+        "confirm_injection",
+        text_to_inject,
+        auto_paste,
+        paste_delay_ms,
+        injection_method,
+    )
+    .await?;
+    if injection_blocked {
+        let _ = app_handle.emit(
+            "injection-blocked",
+            "Murmur doesn't paste into this app. Text is in your clipboard.",
+        );
+    }
+    Ok(())
+}
+
+/// Discard preview `id` without touching the clipboard. A preview that is no
+/// longer pending is left alone.
+#[tauri::command]
+pub fn discard_injection(app_handle: tauri::AppHandle, state: tauri::State<'_, State>, id: u64) {
+    let discarded = {
+        let mut slot = state.app_state.pending_injection.lock_or_recover();
+        injection_preview::take_if(&mut slot, id)
+    };
+    if discarded.is_some() {
+        tracing::info!(target: "pipeline", recording_id = id, "preview discarded");
+        emit_injection_resolved(&app_handle, id, PreviewOutcome::Discarded);
+    }
+}
+
+#[tauri::command]
+pub fn get_append_buffer(state: tauri::State<'_, State>) -> AppendBuffer {
+    AppendBuffer::from_takes(&state.app_state.dictation.lock_or_recover().append_takes)
+}
+
+/// Paste the buffered append-mode takes as one dictation into the frontmost
+/// app, with that app's delivery settings, and return to Idle. Refused while
+/// a take is still recording or transcribing.
+#[tauri::command]
+pub async fn commit_append_buffer(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+) -> Result<TranscriptionResult, String> {
+    let _transition = state.app_state.recording_transition.lock().await;
+    let takes = {
+        let mut dictation = state.app_state.dictation.lock_or_recover();
+        match dictation.status {
+            DictationStatus::Recording | DictationStatus::Processing => {
+                return Err("Finish the current take before committing.".to_string());
+            }
+            DictationStatus::Idle | DictationStatus::Buffering => {
+                dictation.status = DictationStatus::Idle;
+                std::mem::take(&mut dictation.append_takes)
+            }
+        }
+    };
+    let text = join_takes(&takes);
+    if takes.is_empty() {
+        return Ok(TranscriptionResult::new(TranscriptionKind::Committed, text).with_state("idle"));
+    }
+    events::emit_recording_status(&app_handle, DictationStatus::Idle);
+    emit_append_buffer(&app_handle, &[]);
+
+    let bundle_id = crate::frontmost::frontmost_bundle_id();
+    let context = resolve_live_context(
+        &state.app_state,
+        &state.knowledge,
+        bundle_id.as_deref(),
+        SessionOverrides::default(),
+    );
+    let delivery = &context.delivery;
+    if delivers_to_notes(&app_handle, delivery).await {
+        save_to_notes(&app_handle, &text, delivery);
+        return Ok(TranscriptionResult::new(TranscriptionKind::Committed, text).with_state("idle"));
+    }
+    let auto_paste = delivery.auto_paste && !(delivery.save_transcript || delivery.save_audio);
+    tracing::info!(target: "pipeline", takes = takes.len(), auto_paste, "committing append buffer");
+    let text_to_inject = read_caret(&app_handle, delivery, auto_paste)
+        .await
+        .deliver(&text, delivery.trailing);
+    deliver_text(
+        &app_handle,
+        &state.app_state,
+        "commit_append_buffer",
+        text_to_inject,
+        auto_paste,
+        delivery.paste_delay_ms,
+        delivery.injection_method,
+    )
+    .await?;
+    if delivery.injection_blocked {
+        let _ = app_handle.emit(
+            "injection-blocked",
+            "Murmur doesn't paste into this app. Text is in your clipboard.",
+        );
+    }
+    Ok(TranscriptionResult::new(TranscriptionKind::Committed, text).with_state("idle"))
+}
+
+/// Drop the buffered append-mode takes without pasting them. A take still in
+/// flight is unaffected and settles on its own.
+#[tauri::command]
+pub fn discard_append_buffer(app_handle: tauri::AppHandle, state: tauri::State<'_, State>) {
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let discarded = std::mem::take(&mut dictation.append_takes).len();
+    if dictation.status == DictationStatus::Buffering {
+        dictation.status = DictationStatus::Idle;
+        events::emit_recording_status(&app_handle, DictationStatus::Idle);
+    }
+    tracing::info!(target: "pipeline", discarded, "append buffer discarded");
+    emit_append_buffer(&app_handle, &[]);
 }
 
 #[cfg(test)]
@@ -3066,6 +5624,7 @@ mod tests {
             }],
             "outputDir": "/Users/private/CustomerFiles"
         });
+        let options: ConfigureOptions = serde_json::from_value(options).unwrap();
 
         let metadata = ConfigurationLogMetadata::from_options(&options);
         assert_eq!(metadata.option_count, 5);
@@ -3110,10 +5669,11 @@ mod tests {
             replacement: "deploy".to_string(),
         }];
         let before = dictation.clone();
-        let options = serde_json::json!({
+        let options: ConfigureOptions = serde_json::from_value(serde_json::json!({
             "voiceCommands": [{ "phrase": "Tori", "replacement": "override" }],
             "vocabularyEntries": dictation.vocabulary_entries.clone(),
-        });
+        }))
+        .unwrap();
 
         let result = stage_vocabulary_configuration(&options, &dictation, &[])
             .map(|staged| staged.commit(&mut dictation));
@@ -3165,6 +5725,224 @@ mod tests {
         assert_eq!(parse_writing_style(None), None);
     }
 
+    #[test]
+    fn app_profile_parser_round_trips_delivery_and_model_overrides() {
+        let value = serde_json::json!({
+            "bundleId": " com.apple.mail ",
+            "label": "Mail",
+            "autoPasteOverride": true,
+            "injectionMethodOverride": "system_events",
+            "trailingOverride": "newline",
+            "modelOverride": "small.en",
+        });
+        let profile = parse_app_profile(&value).unwrap();
+        assert_eq!(profile.bundle_id, "com.apple.mail");
+        assert_eq!(
+            profile.injection_method_override,
+            Some(crate::state::InjectionMethod::SystemEvents)
+        );
+        assert_eq!(
+            profile.trailing_override,
+            Some(crate::state::TrailingBehavior::Newline)
+        );
+        assert_eq!(profile.model_override.as_deref(), Some("small.en"));
+
+        let reparsed = parse_app_profile(&app_profile_json(&profile)).unwrap();
+        assert_eq!(reparsed.auto_paste_override, Some(true));
+        assert_eq!(
+            reparsed.injection_method_override,
+            profile.injection_method_override
+        );
+        assert_eq!(reparsed.trailing_override, profile.trailing_override);
+        assert_eq!(reparsed.model_override, profile.model_override);
+    }
+
+    #[test]
+    fn app_profile_parser_drops_unknown_values_and_missing_bundle_ids() {
+        let profile = parse_app_profile(&serde_json::json!({
+            "bundleId": "com.tinyspeck.slackmacgap",
+            "injectionMethodOverride": "teleport",
+            "trailingOverride": 3,
+            "modelOverride": "future-unknown-model",
+        }))
+        .unwrap();
+        assert_eq!(profile.injection_method_override, None);
+        assert_eq!(profile.trailing_override, None);
+        assert_eq!(profile.model_override, None);
+
+        assert!(parse_app_profile(&serde_json::json!({ "bundleId": "  " })).is_none());
+        assert!(parse_app_profile(&serde_json::json!({ "label": "No id" })).is_none());
+    }
+
+    #[test]
+    fn dictation_profile_parser_keeps_overrides_and_drops_unknown_values() {
+        let profile = parse_dictation_profile(&serde_json::json!({
+            "id": "spanish",
+            "name": "Spanish",
+            "modelOverride": "future-unknown-model",
+            "languageOverride": " es ",
+            "autoPasteOverride": false,
+            "cleanupOverride": null,
+            "rewriteTemplate": " email ",
+        }))
+        .unwrap();
+        assert_eq!(profile.name, "Spanish");
+        assert_eq!(profile.model_override, None);
+        assert_eq!(profile.language_override.as_deref(), Some("es"));
+        assert_eq!(profile.auto_paste_override, Some(false));
+        assert_eq!(profile.cleanup_override, None);
+        assert_eq!(profile.smart_formatting_override, None);
+        assert_eq!(profile.rewrite_template.as_deref(), Some("email"));
+
+        assert!(parse_dictation_profile(&serde_json::json!({ "id": "" })).is_none());
+        assert!(parse_dictation_profile(&serde_json::json!({ "name": "No id" })).is_none());
+    }
+
+    #[test]
+    fn whisper_decoding_parser_clamps_values_and_defaults_missing_fields() {
+        use crate::transcriber::whisper::{SingleSegmentMode, WhisperSampling};
+
+        let parsed = parse_whisper_decoding(&serde_json::json!({
+            "sampling": "beam_search",
+            "beamSize": 40,
+            "temperature": 1.7,
+            "noSpeechThreshold": -0.2,
+            "threads": 64,
+            "singleSegment": "never"
+        }));
+        assert_eq!(parsed.sampling, WhisperSampling::BeamSearch);
+        assert_eq!(parsed.beam_size, 8);
+        assert_eq!(parsed.temperature, 1.0);
+        assert_eq!(parsed.no_speech_threshold, 0.0);
+        assert_eq!(parsed.threads, Some(16));
+        assert_eq!(parsed.single_segment, SingleSegmentMode::Never);
+
+        let fallback = parse_whisper_decoding(&serde_json::json!({
+            "sampling": "nucleus",
+            "threads": 0,
+            "singleSegment": 3
+        }));
+        assert_eq!(fallback, transcriber::WhisperDecoding::default());
+    }
+
+    #[test]
+    fn redaction_parser_drops_blank_entries_and_defaults_unknown_mode() {
+        let parsed = parse_redaction_settings(&serde_json::json!({
+            "enabled": true,
+            "mode": "remove",
+            "includeProfanity": false,
+            "words": [" Falcon ", "", 3],
+            "patterns": ["\\d{4}", "  "]
+        }));
+        assert!(parsed.enabled);
+        assert_eq!(parsed.mode, crate::state::RedactionMode::Remove);
+        assert!(!parsed.include_profanity);
+        assert_eq!(parsed.words, vec!["Falcon".to_string()]);
+        assert_eq!(parsed.patterns, vec!["\\d{4}".to_string()]);
+
+        let fallback = parse_redaction_settings(&serde_json::json!({ "mode": "shred" }));
+        assert!(!fallback.enabled);
+        assert_eq!(fallback.mode, crate::state::RedactionMode::Mask);
+        assert!(fallback.include_profanity);
+    }
+
+    #[test]
+    fn focus_filter_parser_drops_blank_modes_and_keeps_defaults() {
+        let parsed = parse_focus_filter(&serde_json::json!({
+            "suppressNotifications": false,
+            "disableHotkey": true,
+            "modes": [" com.apple.focus.work ", "", 7]
+        }));
+        assert!(!parsed.suppress_notifications);
+        assert!(parsed.disable_hotkey);
+        assert_eq!(parsed.modes, vec!["com.apple.focus.work".to_string()]);
+
+        let fallback = parse_focus_filter(&serde_json::json!({}));
+        assert!(fallback.suppress_notifications);
+        assert!(!fallback.disable_hotkey);
+        assert!(fallback.modes.is_empty());
+    }
+
+    #[test]
+    fn adaptive_model_parser_rejects_unknown_fallbacks() {
+        let parsed = parse_adaptive_model(&serde_json::json!({
+            "enabled": true,
+            "fallbackModel": "small.en"
+        }));
+        assert!(parsed.enabled);
+        assert_eq!(parsed.fallback_model, "small.en");
+
+        let fallback = parse_adaptive_model(&serde_json::json!({ "fallbackModel": "huge.en" }));
+        assert_eq!(fallback, crate::state::AdaptiveModelSettings::default());
+    }
+
+    #[test]
+    fn latency_budget_parser_clamps_the_factor() {
+        let parsed = parse_latency_budget(&serde_json::json!({
+            "enabled": true,
+            "maxRtf": 40.0,
+            "autoSwitch": true
+        }));
+        assert!(parsed.enabled);
+        assert_eq!(parsed.max_rtf, 5.0);
+        assert!(parsed.auto_switch);
+
+        let fallback = parse_latency_budget(&serde_json::json!({
+            "maxRtf": "fast",
+            "autoSwitch": 1
+        }));
+        assert_eq!(fallback, crate::state::LatencyBudgetSettings::default());
+    }
+
+    #[test]
+    fn dual_model_parser_defaults_unknown_fields() {
+        let parsed = parse_dual_model(&serde_json::json!({
+            "enabled": true,
+            "draftModel": "base.en",
+            "replaceDraft": true
+        }));
+        assert!(parsed.enabled);
+        assert_eq!(parsed.draft_model, "base.en");
+        assert!(parsed.replace_draft);
+
+        let fallback = parse_dual_model(
+            &serde_json::json!({ "draftModel": "moonshine-tiny", "replaceDraft": "yes" }),
+        );
+        assert_eq!(fallback, crate::state::DualModelSettings::default());
+    }
+
+    #[test]
+    fn confidence_retry_parser_clamps_and_keeps_only_whisper_models() {
+        let parsed = parse_confidence_retry(&serde_json::json!({
+            "enabled": true,
+            "threshold": 2.0,
+            "model": "medium.en",
+            "budgetMs": 60_000
+        }));
+        assert!(parsed.enabled);
+        assert_eq!(parsed.threshold, 0.95);
+        assert_eq!(parsed.model.as_deref(), Some("medium.en"));
+        assert_eq!(parsed.budget_ms, 10_000);
+
+        let fallback = parse_confidence_retry(&serde_json::json!({
+            "threshold": "low",
+            "model": crate::transcriber::COREML_MODEL_NAME,
+            "budgetMs": -1
+        }));
+        assert_eq!(fallback, crate::state::ConfidenceRetrySettings::default());
+    }
+
+    #[test]
+    fn alternatives_parser_clamps_the_count() {
+        let parsed = parse_alternatives(&serde_json::json!({ "enabled": true, "count": 12 }));
+        assert!(parsed.enabled);
+        assert_eq!(parsed.count, crate::alternatives::MAX_ALTERNATIVES);
+
+        let fallback = parse_alternatives(&serde_json::json!({ "enabled": "yes", "count": 0 }));
+        assert_eq!(fallback.count, 1);
+        assert!(!fallback.enabled);
+    }
+
     #[test]
     fn empty_coreml_result_after_vad_retries_original_audio_once() {
         let filtered = vec![0.0; 8_000];
@@ -3574,4 +6352,17 @@ mod tests {
         assert_eq!(id2, 2);
         assert_eq!(id3, 3);
     }
+
+    #[test]
+    fn append_takes_commit_as_one_space_separated_text() {
+        let takes = vec![
+            "First thought.".to_string(),
+            "  ".to_string(),
+            " And the second. ".to_string(),
+        ];
+        assert_eq!(join_takes(&takes), "First thought. And the second.");
+        let buffer = AppendBuffer::from_takes(&takes);
+        assert_eq!(buffer.takes, 3);
+        assert_eq!(join_takes(&[]), "");
+    }
 }
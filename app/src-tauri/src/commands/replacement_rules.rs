@@ -0,0 +1,152 @@
+use crate::replacement_rules::{self, ReplacementPreview, ReplacementRule, ReplacementRules};
+use crate::state::AppState;
+use crate::{MutexExt, State};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::Emitter;
+
+/// Rebuild `AppState::replacement_rules` from `rules`, so each recording
+/// snapshot shares the compiled list instead of compiling its own.
+pub(crate) fn compile_rules(app_state: &AppState, rules: &[ReplacementRule]) {
+    *app_state.replacement_rules.lock_or_recover() = ReplacementRules::build(rules).map(Arc::new);
+}
+
+/// Publish the full list so the settings UI can persist it. Like rewrite
+/// templates, the frontend owns persistence and these commands keep the live
+/// copy current.
+fn publish_rules(app_handle: &tauri::AppHandle, rules: &[ReplacementRule]) {
+    let _ = app_handle.emit("replacement-rules-changed", rules);
+}
+
+#[tauri::command]
+pub fn list_replacement_rules(state: tauri::State<'_, State>) -> Vec<ReplacementRule> {
+    state
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .replacement_rules
+        .clone()
+}
+
+/// Replace the rule with `rule.id` where it stands, or append it.
+#[tauri::command]
+pub fn upsert_replacement_rule(
+    app_handle: tauri::AppHandle,
+    rule: ReplacementRule,
+    state: tauri::State<'_, State>,
+) -> Result<ReplacementRule, String> {
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let stored = replacement_rules::upsert(&mut dictation.replacement_rules, &rule)?;
+    compile_rules(&state.app_state, &dictation.replacement_rules);
+    state.app_state.bump_settings_revision();
+    tracing::info!(
+        target: "pipeline",
+        rule_count = dictation.replacement_rules.len(),
+        "replacement rule upserted"
+    );
+    publish_rules(&app_handle, &dictation.replacement_rules);
+    Ok(stored)
+}
+
+/// Remove the rule with `id`. Returns whether anything was removed.
+#[tauri::command]
+pub fn delete_replacement_rule(
+    app_handle: tauri::AppHandle,
+    id: String,
+    state: tauri::State<'_, State>,
+) -> bool {
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let removed = replacement_rules::remove(&mut dictation.replacement_rules, &id);
+    if removed {
+        compile_rules(&state.app_state, &dictation.replacement_rules);
+        state.app_state.bump_settings_revision();
+        publish_rules(&app_handle, &dictation.replacement_rules);
+    }
+    tracing::info!(target: "pipeline", removed, "replacement rule delete requested");
+    removed
+}
+
+/// Move the rules in `ids` to the top, in that order. Returns the new list.
+#[tauri::command]
+pub fn reorder_replacement_rules(
+    app_handle: tauri::AppHandle,
+    ids: Vec<String>,
+    state: tauri::State<'_, State>,
+) -> Vec<ReplacementRule> {
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    replacement_rules::reorder(&mut dictation.replacement_rules, &ids);
+    compile_rules(&state.app_state, &dictation.replacement_rules);
+    state.app_state.bump_settings_revision();
+    publish_rules(&app_handle, &dictation.replacement_rules);
+    dictation.replacement_rules.clone()
+}
+
+/// Dry-run `rules` (the live list when omitted) over `text`, or, when no text
+/// is given, over the last dictation as it reached the rules, before they ran.
+/// Nothing is saved.
+#[tauri::command]
+pub fn preview_replacement_rules(
+    rules: Option<Vec<ReplacementRule>>,
+    text: Option<String>,
+    state: tauri::State<'_, State>,
+) -> Result<ReplacementPreview, String> {
+    let source = match text {
+        Some(text) => text,
+        None => state
+            .app_state
+            .last_rules_input
+            .lock_or_recover()
+            .clone()
+            .ok_or_else(|| "Nothing has been dictated yet.".to_string())?,
+    };
+    let rules = rules.unwrap_or_else(|| {
+        state
+            .app_state
+            .dictation
+            .lock_or_recover()
+            .replacement_rules
+            .clone()
+    });
+    replacement_rules::preview(&rules, &source)
+}
+
+/// Write every rule to `path`. Returns how many were exported.
+#[tauri::command]
+pub fn export_replacement_rules(
+    path: String,
+    state: tauri::State<'_, State>,
+) -> Result<usize, String> {
+    let rules = state
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .replacement_rules
+        .clone();
+    replacement_rules::export_to_file(&PathBuf::from(path), &rules)
+}
+
+/// Merge the rules in `path` into the list, replacing any with the same id.
+/// Returns the merged list.
+#[tauri::command]
+pub fn import_replacement_rules(
+    app_handle: tauri::AppHandle,
+    path: String,
+    state: tauri::State<'_, State>,
+) -> Result<Vec<ReplacementRule>, String> {
+    let imported = replacement_rules::read_import(&PathBuf::from(path))?;
+    if imported.is_empty() {
+        return Err("The file has no replacement rules.".to_string());
+    }
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let merged = replacement_rules::merge_import(&mut dictation.replacement_rules, &imported);
+    compile_rules(&state.app_state, &dictation.replacement_rules);
+    state.app_state.bump_settings_revision();
+    tracing::info!(
+        target: "pipeline",
+        imported = imported.len(),
+        merged,
+        "replacement rules imported"
+    );
+    publish_rules(&app_handle, &dictation.replacement_rules);
+    Ok(dictation.replacement_rules.clone())
+}
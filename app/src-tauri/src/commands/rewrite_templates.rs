@@ -0,0 +1,98 @@
+use crate::rewrite_templates::{self, RewriteTemplate};
+use crate::{MutexExt, State};
+use std::path::PathBuf;
+use tauri::Emitter;
+
+/// Publish the full list so the settings UI can persist it. Like app profiles,
+/// the frontend owns persistence and these commands keep the live copy current.
+fn publish_templates(app_handle: &tauri::AppHandle, templates: &[RewriteTemplate]) {
+    let _ = app_handle.emit("rewrite-templates-changed", templates);
+}
+
+#[tauri::command]
+pub fn list_rewrite_templates(state: tauri::State<'_, State>) -> Vec<RewriteTemplate> {
+    state
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .rewrite_templates
+        .clone()
+}
+
+/// Insert or replace the template with `template.id`. A different template
+/// with the same name is replaced too, so a spoken prefix stays unambiguous.
+#[tauri::command]
+pub fn upsert_rewrite_template(
+    app_handle: tauri::AppHandle,
+    template: RewriteTemplate,
+    state: tauri::State<'_, State>,
+) -> Result<RewriteTemplate, String> {
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let stored = rewrite_templates::upsert(&mut dictation.rewrite_templates, &template)?;
+    state.app_state.bump_settings_revision();
+    tracing::info!(
+        target: "pipeline",
+        template_count = dictation.rewrite_templates.len(),
+        "rewrite template upserted"
+    );
+    publish_templates(&app_handle, &dictation.rewrite_templates);
+    Ok(stored)
+}
+
+/// Remove the template with `id`. Returns whether anything was removed.
+/// Profiles that selected it fall back to no rewrite.
+#[tauri::command]
+pub fn delete_rewrite_template(
+    app_handle: tauri::AppHandle,
+    id: String,
+    state: tauri::State<'_, State>,
+) -> bool {
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let removed = rewrite_templates::remove(&mut dictation.rewrite_templates, &id);
+    if removed {
+        state.app_state.bump_settings_revision();
+        publish_templates(&app_handle, &dictation.rewrite_templates);
+    }
+    tracing::info!(target: "pipeline", removed, "rewrite template delete requested");
+    removed
+}
+
+/// Write every template to `path`. Returns how many were exported.
+#[tauri::command]
+pub fn export_rewrite_templates(
+    path: String,
+    state: tauri::State<'_, State>,
+) -> Result<usize, String> {
+    let templates = state
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .rewrite_templates
+        .clone();
+    rewrite_templates::export_to_file(&PathBuf::from(path), &templates)
+}
+
+/// Merge the templates in `path` into the list, replacing any with the same id
+/// or name. Returns the merged list.
+#[tauri::command]
+pub fn import_rewrite_templates(
+    app_handle: tauri::AppHandle,
+    path: String,
+    state: tauri::State<'_, State>,
+) -> Result<Vec<RewriteTemplate>, String> {
+    let imported = rewrite_templates::read_import(&PathBuf::from(path))?;
+    if imported.is_empty() {
+        return Err("The file has no rewrite templates.".to_string());
+    }
+    let mut dictation = state.app_state.dictation.lock_or_recover();
+    let merged = rewrite_templates::merge_import(&mut dictation.rewrite_templates, &imported);
+    state.app_state.bump_settings_revision();
+    tracing::info!(
+        target: "pipeline",
+        imported = imported.len(),
+        merged,
+        "rewrite templates imported"
+    );
+    publish_templates(&app_handle, &dictation.rewrite_templates);
+    Ok(dictation.rewrite_templates.clone())
+}
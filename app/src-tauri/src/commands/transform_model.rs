@@ -169,14 +169,15 @@ async fn stream_verified_download(
     use sha2::{Digest, Sha256};
     use tokio::io::AsyncWriteExt;
 
-    let client = reqwest::Client::builder()
+    // Mirrors are safe here: the stream is hashed against the pinned digest.
+    let client = crate::download_network::client_builder()?
         .connect_timeout(std::time::Duration::from_secs(30))
         .timeout(std::time::Duration::from_secs(30 * 60))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
     let response = client
-        .get(TRANSFORM_MODEL_URL)
+        .get(crate::download_network::resolve_url(TRANSFORM_MODEL_URL))
         .send()
         .await
         .map_err(|e| format!("Download request failed: {}", e))?;
@@ -1,47 +1,212 @@
-/// Generate 66×66 RGBA pixel data for an audio-bar tray icon (static white).
-/// 66px = 3× resolution for a 22pt menu-bar icon (crisp on Retina).
-/// Draws 5 vertical capsule bars at varying heights (waveform / equalizer style).
-pub(crate) fn make_tray_icon_data() -> Vec<u8> {
-    let (r, g, b): (u8, u8, u8) = (255, 255, 255);
-    const SIZE: u32 = 66;
-    let mut data = vec![0u8; (SIZE * SIZE * 4) as usize];
-
-    // 5 vertical bars: (x-center, height) — all coords in 3× pixel space
-    let bars: [(f64, f64); 5] = [
-        (9.0, 18.0),
-        (21.0, 36.0),
-        (33.0, 48.0),
-        (45.0, 30.0),
-        (57.0, 18.0),
-    ];
-    let half_w: f64 = 3.0; // 6px wide bars (2pt at 3×)
-    let cy: f64 = 33.0;    // vertical center of canvas
-    let rr: f64 = 3.0;     // corner rounding (= half_w → capsule ends)
-    let aa: f64 = 1.0;     // anti-alias transition width
-
-    for y in 0..SIZE {
-        for x in 0..SIZE {
-            let px = x as f64 + 0.5;
-            let py = y as f64 + 0.5;
-            let mut alpha: f64 = 0.0;
-
-            for &(cx, h) in &bars {
-                let half_h = h / 2.0;
-                // Rounded-rect signed distance (capsule when rr == half_w)
-                let qx = (px - cx).abs() - half_w + rr;
-                let qy = (py - cy).abs() - half_h + rr;
-                let outside = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt();
-                let inside = qx.max(qy).min(0.0);
-                let sdf = outside + inside - rr;
-                let a = (1.0 - sdf.max(0.0) / aa).max(0.0);
-                alpha = alpha.max(a);
-            }
+//! Menu-bar tray: the template icon plus a live menu.
+//!
+//! The menu is no longer static. A small state manager keeps handles to the
+//! items that change: a status line that ticks while recording, "Copy Last"
+//! and "Paste Last" items for the newest history entry, and quick toggles for
+//! auto-paste and the model. Status updates come from the app's own
+//! `recording-status-changed` event, so the pipeline needs no tray-specific
+//! calls; the last-transcript items follow `set_last_transcription`, which
+//! the frontend calls as its history changes. Settings are owned by the
+//! frontend, so the toggles ask the main window to apply a change
+//! (`tray-settings-request`) and the checks only move once
+//! `configure_dictation` has accepted it ([`sync_settings`]).
+//!
+//! The Model submenu lists installed models and is rebuilt whenever
+//! `model-runtime-status-changed` changes what it would show. A model picked
+//! here is loaded right away, and the submenu reads "Loading…" (with the other
+//! models disabled) until it is ready, fails, or the change is rejected.
+//!
+//! The Presets submenu lists the named configuration presets mirrored by
+//! `set_config_presets`, checking the one the current settings match. Picking
+//! one asks the main window to apply it, like the other toggles.
+
+use crate::events;
+use crate::model_runtime::{InstallState, LifecycleState, ModelRuntimeSnapshot};
+use crate::{MutexExt, State};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::menu::{
+    CheckMenuItem, CheckMenuItemBuilder, Menu, MenuBuilder, MenuItem, MenuItemBuilder, Submenu,
+    SubmenuBuilder,
+};
+use tauri::{Emitter, Listener, Manager, Wry};
+
+const COPY_LAST_ID: &str = "copy_last";
+const PASTE_LAST_ID: &str = "paste_last";
+const AUTO_PASTE_ID: &str = "toggle_auto_paste";
+const MODEL_ID_PREFIX: &str = "model:";
+const PRESET_ID_PREFIX: &str = "preset:";
+
+/// Characters of the last transcript shown in the "Copy Last" item.
+const PREVIEW_CHARS: usize = 40;
+
+/// How long a model picked in the tray may take to be accepted and loaded
+/// before the submenu stops showing it as loading.
+const MODEL_SWITCH_TIMEOUT: Duration = Duration::from_secs(120);
+
+struct TrayMenuState {
+    status: MenuItem<Wry>,
+    copy_last: MenuItem<Wry>,
+    paste_last: MenuItem<Wry>,
+    auto_paste: CheckMenuItem<Wry>,
+    model_menu: Submenu<Wry>,
+    models: Mutex<ModelMenu>,
+    preset_menu: Submenu<Wry>,
+    presets: Mutex<Vec<CheckMenuItem<Wry>>>,
+    /// Model picked in the tray that isn't loaded yet.
+    requested_model: Mutex<Option<String>>,
+    /// Bumped on every status change so a superseded timer thread exits.
+    status_generation: AtomicU64,
+}
+
+#[derive(Default)]
+struct ModelMenu {
+    shown: Vec<ModelEntry>,
+    items: Vec<CheckMenuItem<Wry>>,
+}
+
+/// One row of the Model submenu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ModelEntry {
+    model_name: String,
+    label: String,
+    checked: bool,
+    loading: bool,
+}
+
+/// What the Model submenu needs from a runtime snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TrayModel {
+    model_name: String,
+    label: String,
+    installed: bool,
+    loading: bool,
+}
+
+impl TrayModel {
+    fn from_snapshot(snapshot: &ModelRuntimeSnapshot) -> Option<Self> {
+        snapshot.supported.then(|| Self {
+            model_name: snapshot.model_name.clone(),
+            label: snapshot.label.clone(),
+            installed: snapshot.install_state == InstallState::Installed,
+            loading: matches!(
+                snapshot.lifecycle_state,
+                LifecycleState::Loading | LifecycleState::Warming
+            ),
+        })
+    }
+}
+
+/// Installed models plus the selected one, with the requested model (or the
+/// selected one while it loads) marked as loading.
+fn model_entries(models: &[TrayModel], current: &str, requested: Option<&str>) -> Vec<ModelEntry> {
+    let selected = requested.unwrap_or(current);
+    models
+        .iter()
+        .filter(|model| model.installed || model.model_name == selected)
+        .map(|model| ModelEntry {
+            model_name: model.model_name.clone(),
+            label: model.label.clone(),
+            checked: model.model_name == selected,
+            loading: model.model_name == selected && (requested.is_some() || model.loading),
+        })
+        .collect()
+}
+
+static TRAY_MENU: OnceLock<TrayMenuState> = OnceLock::new();
+
+/// Icon canvas edge in pixels: 3× resolution for a 22pt menu-bar icon.
+const ICON_SIZE: u32 = 66;
+
+/// Frames in one recording pulse cycle, and how long each is shown.
+const PULSE_FRAMES: usize = 10;
+const PULSE_FRAME_INTERVAL: Duration = Duration::from_millis(120);
+
+/// Centre of the corner badge that marks recording/processing.
+const BADGE_CENTER: (f64, f64) = (54.0, 54.0);
+
+/// What the menu-bar icon shows. Badges are shapes, not colours: the icon is a
+/// template image, so macOS tints it to match a light or dark menu bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrayIconState {
+    Idle,
+    Recording,
+    Processing,
+    Disabled,
+}
+
+impl TrayIconState {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "idle" => Some(Self::Idle),
+            "recording" => Some(Self::Recording),
+            "processing" => Some(Self::Processing),
+            "disabled" => Some(Self::Disabled),
+            _ => None,
+        }
+    }
+}
+
+fn segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (abx, aby) = (b.0 - a.0, b.1 - a.1);
+    let (apx, apy) = (p.0 - a.0, p.1 - a.1);
+    let t = ((apx * abx + apy * aby) / (abx * abx + aby * aby)).clamp(0.0, 1.0);
+    ((apx - abx * t).powi(2) + (apy - aby * t).powi(2)).sqrt()
+}
+
+fn point_distance(p: (f64, f64), c: (f64, f64)) -> f64 {
+    ((p.0 - c.0).powi(2) + (p.1 - c.1).powi(2)).sqrt()
+}
 
+/// Anti-aliased coverage for a signed distance (negative inside).
+fn coverage(sdf: f64) -> f64 {
+    (0.5 - sdf).clamp(0.0, 1.0)
+}
+
+/// Signed distance to the microphone glyph: capsule head, U-shaped yoke,
+/// stem, and base.
+fn microphone_sdf(p: (f64, f64)) -> f64 {
+    let head = segment_distance(p, (33.0, 18.0), (33.0, 30.0)) - 9.0;
+    let yoke = if p.1 >= 30.0 {
+        (point_distance(p, (33.0, 30.0)) - 15.0).abs() - 2.0
+    } else {
+        point_distance(p, (18.0, 30.0)).min(point_distance(p, (48.0, 30.0))) - 2.0
+    };
+    let stem = segment_distance(p, (33.0, 45.0), (33.0, 53.0)) - 2.0;
+    let base = segment_distance(p, (24.0, 55.0), (42.0, 55.0)) - 2.0;
+    head.min(yoke).min(stem).min(base)
+}
+
+/// Render one RGBA template frame. `pulse` (0–1) sizes the recording dot.
+fn render_icon(state: TrayIconState, pulse: f64) -> Vec<u8> {
+    let mut data = vec![0u8; (ICON_SIZE * ICON_SIZE * 4) as usize];
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let p = (x as f64 + 0.5, y as f64 + 0.5);
+            let mut alpha = coverage(microphone_sdf(p));
+            // Each badge clears a gap around itself so it reads separately
+            // from the glyph at 22pt.
+            let (badge, gap) = match state {
+                TrayIconState::Idle => (0.0, 0.0),
+                TrayIconState::Recording => {
+                    let radius = 6.5 + 2.5 * pulse;
+                    let d = point_distance(p, BADGE_CENTER);
+                    (coverage(d - radius), coverage(d - radius - 3.0))
+                }
+                TrayIconState::Processing => {
+                    let d = point_distance(p, BADGE_CENTER);
+                    (coverage((d - 7.5).abs() - 1.5), coverage(d - 12.0))
+                }
+                TrayIconState::Disabled => {
+                    let d = segment_distance(p, (10.0, 10.0), (56.0, 56.0));
+                    (coverage(d - 2.5), coverage(d - 5.5))
+                }
+            };
+            alpha = (alpha * (1.0 - gap)).max(badge);
             if alpha > 0.0 {
-                let idx = ((y * SIZE + x) * 4) as usize;
-                data[idx] = r;
-                data[idx + 1] = g;
-                data[idx + 2] = b;
+                // Template images use alpha only; black is the convention.
+                let idx = ((y * ICON_SIZE + x) * 4) as usize;
                 data[idx + 3] = (alpha * 255.0).round() as u8;
             }
         }
@@ -49,9 +214,455 @@ pub(crate) fn make_tray_icon_data() -> Vec<u8> {
     data
 }
 
-/// No-op — tray icon is static white. Kept so the registered command doesn't break.
+/// RGBA data for the idle tray icon, used when the tray is created.
+pub(crate) fn make_tray_icon_data() -> Vec<u8> {
+    render_icon(TrayIconState::Idle, 0.0)
+}
+
+/// Recording frames, rendered once: the dot eases out and back per cycle.
+fn pulse_frames() -> &'static [Vec<u8>] {
+    static FRAMES: OnceLock<Vec<Vec<u8>>> = OnceLock::new();
+    FRAMES.get_or_init(|| {
+        (0..PULSE_FRAMES)
+            .map(|frame| {
+                let phase = frame as f64 / PULSE_FRAMES as f64 * std::f64::consts::TAU;
+                render_icon(TrayIconState::Recording, 0.5 - 0.5 * phase.cos())
+            })
+            .collect()
+    })
+}
+
+fn set_icon_frame(app: &tauri::AppHandle, rgba: &[u8]) {
+    if let Some(tray) = app.tray_by_id("main-tray") {
+        let _ = tray.set_icon(Some(tauri::image::Image::new(rgba, ICON_SIZE, ICON_SIZE)));
+    }
+}
+
+fn set_icon_state(app: &tauri::AppHandle, state: TrayIconState) {
+    match state {
+        TrayIconState::Recording => set_icon_frame(app, &pulse_frames()[0]),
+        _ => set_icon_frame(app, &render_icon(state, 0.0)),
+    }
+}
+
+/// Build the tray menu and register its live items. Called once from setup.
+pub(crate) fn build_menu(app: &tauri::App) -> tauri::Result<Menu<Wry>> {
+    let auto_paste_on = app
+        .state::<State>()
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .auto_paste;
+
+    let status = MenuItemBuilder::with_id("tray_status", status_label("idle", None))
+        .enabled(false)
+        .build(app)?;
+    let copy_last = MenuItemBuilder::with_id(COPY_LAST_ID, "Copy Last Transcription")
+        .enabled(false)
+        .build(app)?;
+    let paste_last = MenuItemBuilder::with_id(PASTE_LAST_ID, "Paste Last Transcription")
+        .enabled(false)
+        .build(app)?;
+    let show_item = MenuItemBuilder::with_id("show", "Show Murmur").build(app)?;
+    let clipboard_audio_item =
+        MenuItemBuilder::with_id("transcribe_clipboard_audio", "Transcribe Copied Audio")
+            .build(app)?;
+    let auto_paste = CheckMenuItemBuilder::with_id(AUTO_PASTE_ID, "Auto-Paste")
+        .checked(auto_paste_on)
+        .build(app)?;
+
+    let model_menu = SubmenuBuilder::new(app, "Model").build()?;
+    let preset_menu = SubmenuBuilder::new(app, "Presets").build()?;
+
+    let disabled_item = CheckMenuItemBuilder::with_id("toggle_disabled", "Disable Murmur")
+        .checked(false)
+        .build(app)?;
+    let quit_item = MenuItemBuilder::with_id("quit", "Quit Murmur").build(app)?;
+    let menu = MenuBuilder::new(app)
+        .item(&status)
+        .item(&copy_last)
+        .item(&paste_last)
+        .separator()
+        .item(&show_item)
+        .item(&clipboard_audio_item)
+        .separator()
+        .item(&auto_paste)
+        .item(&model_menu)
+        .item(&preset_menu)
+        .item(&disabled_item)
+        .separator()
+        .item(&quit_item)
+        .build()?;
+    super::keyboard::register_tray_disabled_item(disabled_item);
+
+    let _ = TRAY_MENU.set(TrayMenuState {
+        status,
+        copy_last,
+        paste_last,
+        auto_paste,
+        model_menu,
+        models: Mutex::new(ModelMenu::default()),
+        preset_menu,
+        presets: Mutex::new(Vec::new()),
+        requested_model: Mutex::new(None),
+        status_generation: AtomicU64::new(0),
+    });
+    refresh_models(app.handle());
+    refresh_presets(app.handle());
+    watch_app_events(app.handle());
+    Ok(menu)
+}
+
+/// Rebuild the Model submenu when what it shows has changed.
+fn refresh_models(app: &tauri::AppHandle) {
+    let Some(menu) = TRAY_MENU.get() else {
+        return;
+    };
+    let state = app.state::<State>();
+    let current = state
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .model_name
+        .clone();
+    let models: Vec<TrayModel> = state
+        .app_state
+        .model_runtime
+        .catalog()
+        .iter()
+        .filter_map(TrayModel::from_snapshot)
+        .collect();
+    let requested = menu.requested_model.lock_or_recover().clone();
+    let entries = model_entries(&models, &current, requested.as_deref());
+
+    let mut shown = menu.models.lock_or_recover();
+    if shown.shown == entries && !shown.items.is_empty() {
+        return;
+    }
+    for item in shown.items.drain(..) {
+        let _ = menu.model_menu.remove(&item);
+    }
+    let loading = entries.iter().any(|entry| entry.loading);
+    let _ = menu.model_menu.set_text(if loading {
+        "Model (Loading…)"
+    } else {
+        "Model"
+    });
+    for entry in &entries {
+        let label = if entry.loading {
+            format!("{} — Loading…", entry.label)
+        } else {
+            entry.label.clone()
+        };
+        let Ok(item) =
+            CheckMenuItemBuilder::with_id(format!("{MODEL_ID_PREFIX}{}", entry.model_name), label)
+                .checked(entry.checked)
+                .enabled(!loading)
+                .build(app)
+        else {
+            continue;
+        };
+        let _ = menu.model_menu.append(&item);
+        shown.items.push(item);
+    }
+    if entries.is_empty() {
+        if let Ok(item) = CheckMenuItemBuilder::new("No Models Installed")
+            .enabled(false)
+            .build(app)
+        {
+            let _ = menu.model_menu.append(&item);
+            shown.items.push(item);
+        }
+    }
+    shown.shown = entries;
+}
+
+/// Rebuild the Presets submenu from the presets mirrored in
+/// `config_presets`.
+pub(crate) fn refresh_presets(app: &tauri::AppHandle) {
+    let Some(menu) = TRAY_MENU.get() else {
+        return;
+    };
+    let (presets, active) = crate::config_presets::snapshot();
+    let mut items = menu.presets.lock_or_recover();
+    for item in items.drain(..) {
+        let _ = menu.preset_menu.remove(&item);
+    }
+    for preset in &presets {
+        let Ok(item) =
+            CheckMenuItemBuilder::with_id(format!("{PRESET_ID_PREFIX}{}", preset.id), &preset.name)
+                .checked(active.as_deref() == Some(preset.id.as_str()))
+                .build(app)
+        else {
+            continue;
+        };
+        let _ = menu.preset_menu.append(&item);
+        items.push(item);
+    }
+    if presets.is_empty() {
+        if let Ok(item) = CheckMenuItemBuilder::new("No Presets Saved")
+            .enabled(false)
+            .build(app)
+        {
+            let _ = menu.preset_menu.append(&item);
+            items.push(item);
+        }
+    }
+}
+
+/// Stop showing `model_name` as loading, and redraw the Model submenu.
+fn finish_model_request(app: &tauri::AppHandle, model_name: &str) {
+    let Some(menu) = TRAY_MENU.get() else {
+        return;
+    };
+    {
+        let mut requested = menu.requested_model.lock_or_recover();
+        if requested.as_deref() == Some(model_name) {
+            *requested = None;
+        }
+    }
+    refresh_models(app);
+}
+
+/// Whether `model_name` was just picked in the tray, so `configure_dictation`
+/// should load it now rather than on the next recording.
+pub(crate) fn model_requested(model_name: &str) -> bool {
+    TRAY_MENU
+        .get()
+        .is_some_and(|menu| menu.requested_model.lock_or_recover().as_deref() == Some(model_name))
+}
+
+fn watch_app_events(app: &tauri::AppHandle) {
+    let handle = app.clone();
+    app.listen_any(events::RECORDING_STATUS_CHANGED, move |event| {
+        if let Ok(status) = serde_json::from_str::<String>(event.payload()) {
+            on_status_changed(&handle, &status);
+        }
+    });
+    let handle = app.clone();
+    app.listen_any("model-runtime-status-changed", move |event| {
+        let snapshot = serde_json::from_str::<serde_json::Value>(event.payload()).ok();
+        let settled_model = snapshot
+            .filter(|snapshot| {
+                matches!(
+                    snapshot["lifecycleState"].as_str(),
+                    Some("ready" | "failed")
+                )
+            })
+            .and_then(|snapshot| snapshot["modelName"].as_str().map(str::to_string));
+        // Runtime transitions are published with runtime and dictation locks
+        // held further up the stack; rebuild the menu off that thread.
+        let handle = handle.clone();
+        tauri::async_runtime::spawn_blocking(move || match settled_model {
+            Some(model_name) => finish_model_request(&handle, &model_name),
+            None => refresh_models(&handle),
+        });
+    });
+    let handle = app.clone();
+    app.listen_any("app-disabled-changed", move |event| {
+        if serde_json::from_str::<bool>(event.payload()).is_ok() {
+            set_icon_state(&handle, resting_icon_state());
+        }
+    });
+    let handle = app.clone();
+    app.listen_any("quiet-hours-changed", move |_| {
+        set_icon_state(&handle, resting_icon_state());
+    });
+}
+
+/// The idle icon, dimmed while the hotkey is off by hand or for quiet hours.
+fn resting_icon_state() -> TrayIconState {
+    if crate::keyboard::is_app_disabled() || crate::quiet_hours::is_active() {
+        TrayIconState::Disabled
+    } else {
+        TrayIconState::Idle
+    }
+}
+
+fn on_status_changed(app: &tauri::AppHandle, status: &str) {
+    let Some(menu) = TRAY_MENU.get() else {
+        return;
+    };
+    let generation = menu.status_generation.fetch_add(1, Ordering::SeqCst) + 1;
+    let icon_state = match TrayIconState::parse(status) {
+        _ if crate::keyboard::is_app_disabled() => TrayIconState::Disabled,
+        Some(TrayIconState::Idle) | None => resting_icon_state(),
+        Some(state) => state,
+    };
+    set_icon_state(app, icon_state);
+    if status != "recording" {
+        let _ = menu.status.set_text(status_label(status, None));
+        return;
+    }
+    let started = Instant::now();
+    let _ = menu
+        .status
+        .set_text(status_label(status, Some(Duration::ZERO)));
+    // One thread drives both the icon pulse and the elapsed-time label, and
+    // exits as soon as any later status change bumps the generation.
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut frame = 0;
+        let mut shown_secs = 0;
+        loop {
+            std::thread::sleep(PULSE_FRAME_INTERVAL);
+            if menu.status_generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            if icon_state == TrayIconState::Recording {
+                frame = (frame + 1) % PULSE_FRAMES;
+                set_icon_frame(&app, &pulse_frames()[frame]);
+            }
+            let elapsed = started.elapsed();
+            if elapsed.as_secs() != shown_secs {
+                shown_secs = elapsed.as_secs();
+                let _ = menu
+                    .status
+                    .set_text(status_label("recording", Some(elapsed)));
+            }
+        }
+    });
+}
+
+/// Preview the newest history entry in "Copy Last", or disable both
+/// last-transcript items when there is none.
+pub(crate) fn show_last_transcription(text: Option<&str>) {
+    let Some(menu) = TRAY_MENU.get() else {
+        return;
+    };
+    let label = text.map_or_else(|| "Copy Last Transcription".to_string(), copy_last_label);
+    let _ = menu.copy_last.set_text(label);
+    let _ = menu.copy_last.set_enabled(text.is_some());
+    let _ = menu.paste_last.set_enabled(text.is_some());
+}
+
+/// Handle the tray items this module owns; other ids are ignored.
+pub(crate) fn handle_menu_event(app: &tauri::AppHandle, id: &str) {
+    let Some(menu) = TRAY_MENU.get() else {
+        return;
+    };
+    if id == COPY_LAST_ID {
+        if let Err(e) = super::recording::copy_last_transcription(app.state::<State>()) {
+            tracing::warn!(target: "system", "tray copy last failed: {}", e);
+        }
+        return;
+    }
+    if id == PASTE_LAST_ID {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<State>();
+            if let Err(e) = super::recording::paste_last_transcription(app.clone(), state).await {
+                tracing::warn!(target: "system", "tray paste last failed: {}", e);
+            }
+        });
+        return;
+    }
+    if let Some(preset_id) = id.strip_prefix(PRESET_ID_PREFIX) {
+        if let Err(e) = crate::config_presets::request(app, preset_id) {
+            tracing::warn!(target: "system", "tray preset switch failed: {}", e);
+        }
+        // The native check flips on click; redraw until the frontend confirms.
+        refresh_presets(app);
+        return;
+    }
+    let (auto_paste, current_model) = {
+        let dictation = app.state::<State>().app_state.dictation.lock_or_recover();
+        (dictation.auto_paste, dictation.model_name.clone())
+    };
+    if id == AUTO_PASTE_ID {
+        // The native check flips on click; put it back until configure confirms.
+        let _ = menu.auto_paste.set_checked(auto_paste);
+        let _ = app.emit(
+            "tray-settings-request",
+            serde_json::json!({ "autoPaste": !auto_paste }),
+        );
+        return;
+    }
+    if let Some(model_name) = id.strip_prefix(MODEL_ID_PREFIX) {
+        let idle = menu.requested_model.lock_or_recover().is_none();
+        if idle && model_name != current_model {
+            *menu.requested_model.lock_or_recover() = Some(model_name.to_string());
+            let _ = app.emit(
+                "tray-settings-request",
+                serde_json::json!({ "model": model_name }),
+            );
+            // Don't show loading forever if the frontend rejects the change.
+            let app = app.clone();
+            let model_name = model_name.to_string();
+            std::thread::spawn(move || {
+                std::thread::sleep(MODEL_SWITCH_TIMEOUT);
+                finish_model_request(&app, &model_name);
+            });
+        }
+        // The native check flips on click; redraw from state.
+        menu.models.lock_or_recover().shown.clear();
+        refresh_models(app);
+    }
+}
+
+/// Reflect accepted settings in the tray toggles. Called at the end of
+/// `configure_dictation`; also refreshes the Model submenu.
+pub(crate) fn sync_settings(app: &tauri::AppHandle, auto_paste: bool, model_name: &str) {
+    let Some(menu) = TRAY_MENU.get() else {
+        return;
+    };
+    let _ = menu.auto_paste.set_checked(auto_paste);
+    // A model restored from the keep-alive budget is already Ready and
+    // publishes nothing further.
+    let ready = app
+        .state::<State>()
+        .app_state
+        .model_runtime
+        .snapshot(model_name)
+        .is_ok_and(|snapshot| snapshot.lifecycle_state == LifecycleState::Ready);
+    {
+        let mut requested = menu.requested_model.lock_or_recover();
+        if requested
+            .as_ref()
+            .is_some_and(|requested| requested != model_name || ready)
+        {
+            *requested = None;
+        }
+    }
+    refresh_models(app);
+}
+
+fn status_label(status: &str, elapsed: Option<Duration>) -> String {
+    match (status, elapsed) {
+        ("recording", Some(elapsed)) => format!("Recording  {}", format_elapsed(elapsed)),
+        ("recording", None) => "Recording".to_string(),
+        ("processing", _) => "Transcribing…".to_string(),
+        ("buffering", _) => "Takes Waiting to Commit".to_string(),
+        _ => "Ready".to_string(),
+    }
+}
+
+/// `m:ss`, or `h:mm:ss` once a recording passes an hour.
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+fn copy_last_label(text: &str) -> String {
+    format!(
+        "Copy Last: “{}”",
+        crate::notifications::preview_line(text, PREVIEW_CHARS)
+    )
+}
+
+/// Show a state on the tray icon: `idle`, `recording`, `processing`, or
+/// `disabled`. The tray already follows status events on its own; this is for
+/// surfaces that need to force a state.
 #[tauri::command]
-pub fn update_tray_icon(_app: tauri::AppHandle, _icon_state: String) -> Result<(), String> {
+pub fn update_tray_icon(app: tauri::AppHandle, icon_state: String) -> Result<(), String> {
+    let state = TrayIconState::parse(&icon_state)
+        .ok_or_else(|| format!("Unknown tray icon state '{icon_state}'"))?;
+    set_icon_state(&app, state);
     Ok(())
 }
 
@@ -68,15 +679,36 @@ mod tests {
     }
 
     #[test]
-    fn tray_icon_center_pixel_is_opaque_white() {
+    fn tray_icon_center_pixel_is_opaque_template_black() {
         let data = make_tray_icon_data();
         let idx = (33 * SIZE + 33) * 4;
-        assert_eq!(data[idx],     255, "R");
-        assert_eq!(data[idx + 1], 255, "G");
-        assert_eq!(data[idx + 2], 255, "B");
+        assert_eq!(&data[idx..idx + 3], &[0, 0, 0], "template RGB");
         assert_eq!(data[idx + 3], 255, "A should be opaque");
     }
 
+    #[test]
+    fn badges_mark_state_at_the_corner_and_pulse_while_recording() {
+        let badge = ((BADGE_CENTER.1 as usize) * SIZE + BADGE_CENTER.0 as usize) * 4 + 3;
+        assert_eq!(render_icon(TrayIconState::Idle, 0.0)[badge], 0);
+        assert_eq!(render_icon(TrayIconState::Recording, 0.0)[badge], 255);
+        // The processing ring is hollow; its stroke sits 7.5px out.
+        assert_eq!(render_icon(TrayIconState::Processing, 0.0)[badge], 0);
+        assert_eq!(
+            render_icon(TrayIconState::Processing, 0.0)[badge + 7 * 4],
+            255
+        );
+
+        let frames = pulse_frames();
+        assert_eq!(frames.len(), PULSE_FRAMES);
+        let inked = |frame: &[u8]| frame.iter().skip(3).step_by(4).filter(|a| **a > 0).count();
+        assert!(inked(&frames[PULSE_FRAMES / 2]) > inked(&frames[0]));
+        assert_eq!(
+            TrayIconState::parse("disabled"),
+            Some(TrayIconState::Disabled)
+        );
+        assert_eq!(TrayIconState::parse("amber"), None);
+    }
+
     #[test]
     fn tray_icon_corner_pixel_is_transparent() {
         let data = make_tray_icon_data();
@@ -85,4 +717,66 @@ mod tests {
             assert_eq!(data[idx + 3], 0, "corner ({row},{col}) alpha should be 0 (transparent)");
         }
     }
+
+    #[test]
+    fn status_labels_tick_in_minutes_and_hours() {
+        assert_eq!(status_label("idle", None), "Ready");
+        assert_eq!(status_label("processing", None), "Transcribing…");
+        assert_eq!(status_label("buffering", None), "Takes Waiting to Commit");
+        assert_eq!(
+            status_label("recording", Some(Duration::from_secs(65))),
+            "Recording  1:05"
+        );
+        assert_eq!(format_elapsed(Duration::from_secs(3_725)), "1:02:05");
+    }
+
+    fn tray_model(model_name: &str, installed: bool, loading: bool) -> TrayModel {
+        TrayModel {
+            model_name: model_name.to_string(),
+            label: model_name.to_uppercase(),
+            installed,
+            loading,
+        }
+    }
+
+    #[test]
+    fn model_menu_lists_installed_models_and_marks_the_swap_as_loading() {
+        let models = [
+            tray_model("base.en", true, false),
+            tray_model("small.en", false, false),
+            tray_model("large-v3-turbo", true, false),
+        ];
+        let entries = model_entries(&models, "base.en", None);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.model_name.as_str(), entry.checked, entry.loading))
+                .collect::<Vec<_>>(),
+            [("base.en", true, false), ("large-v3-turbo", false, false)]
+        );
+
+        let switching = model_entries(&models, "base.en", Some("large-v3-turbo"));
+        assert!(switching[1].checked && switching[1].loading);
+        assert!(!switching[0].checked && !switching[0].loading);
+
+        // The selected model stays listed even when it isn't installed, and a
+        // load started by a recording shows too.
+        let models = [tray_model("small.en", false, true)];
+        let entries = model_entries(&models, "small.en", None);
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].loading);
+    }
+
+    #[test]
+    fn copy_last_label_previews_the_first_line() {
+        assert_eq!(
+            copy_last_label("Ship it\nsecond line"),
+            "Copy Last: “Ship it”"
+        );
+        let long = "a".repeat(60);
+        assert_eq!(
+            copy_last_label(&long).chars().count(),
+            "Copy Last: “”".chars().count() + PREVIEW_CHARS
+        );
+    }
 }
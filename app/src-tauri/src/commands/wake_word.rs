@@ -0,0 +1,96 @@
+use crate::commands::models::stream_download;
+use crate::events::{self, DownloadProgress};
+use crate::kws;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WakeWordStatus {
+    model_installed: bool,
+    listening: bool,
+}
+
+/// Start listening for "Hey Murmur" on `device_name` (None = system default).
+#[tauri::command]
+pub async fn start_wake_word(
+    app_handle: tauri::AppHandle,
+    device_name: Option<String>,
+) -> Result<(), String> {
+    kws::start(app_handle, device_name).map_err(|e| {
+        tracing::warn!(target: "audio", "wake word detector failed to start: {}", e);
+        e
+    })
+}
+
+#[tauri::command]
+pub async fn stop_wake_word() {
+    kws::stop();
+}
+
+#[tauri::command]
+pub fn get_wake_word_status() -> WakeWordStatus {
+    WakeWordStatus {
+        model_installed: kws::model_exists(),
+        listening: kws::is_listening(),
+    }
+}
+
+/// Download and unpack the wake word model (a few MB) beside the
+/// transcription models. Progress arrives on `download-progress`.
+#[tauri::command]
+pub async fn download_wake_word_model(app_handle: tauri::AppHandle) -> Result<(), String> {
+    if kws::model_exists() {
+        return Ok(());
+    }
+    let final_dir =
+        kws::kws_model_dir().ok_or_else(|| "Could not determine models directory".to_string())?;
+    let models_dir = final_dir
+        .parent()
+        .ok_or_else(|| "Could not determine models directory".to_string())?
+        .to_path_buf();
+    tokio::fs::create_dir_all(&models_dir)
+        .await
+        .map_err(|e| format!("Failed to create models directory: {}", e))?;
+
+    let archive_path = models_dir.join(format!("{}.tar.bz2.download", kws::KWS_MODEL_DIR));
+    let received = stream_download(&app_handle, &kws::download_url(), &archive_path).await?;
+    tracing::info!(target: "system", "Wake word archive downloaded ({} bytes)", received);
+
+    events::emit_download_progress(&app_handle, DownloadProgress::installing(0, 0));
+    let archive = archive_path.clone();
+    let result = tokio::task::spawn_blocking(move || extract_bundle(&archive, &models_dir))
+        .await
+        .map_err(|e| format!("Extraction task failed: {}", e))?;
+    let _ = tokio::fs::remove_file(&archive_path).await;
+    result?;
+
+    tracing::info!(target: "system", "Wake word model installed: {}", kws::KWS_MODEL_DIR);
+    Ok(())
+}
+
+/// Unpack into a staging directory and publish the bundle only once it is complete.
+fn extract_bundle(
+    archive_path: &std::path::Path,
+    models_dir: &std::path::Path,
+) -> Result<(), String> {
+    let final_dir = models_dir.join(kws::KWS_MODEL_DIR);
+    let staging_root = models_dir.join(format!(".{}.extracting", kws::KWS_MODEL_DIR));
+    let staged_dir = staging_root.join(kws::KWS_MODEL_DIR);
+    let _ = std::fs::remove_dir_all(&staging_root);
+
+    let extraction = (|| {
+        let file = std::fs::File::open(archive_path)
+            .map_err(|e| format!("Failed to open archive: {}", e))?;
+        tar::Archive::new(bzip2::read::BzDecoder::new(file))
+            .unpack(&staging_root)
+            .map_err(|e| format!("Failed to extract archive: {}", e))?;
+        if !kws::bundle_complete(&staged_dir) {
+            return Err("Extracted wake word bundle is incomplete".to_string());
+        }
+        let _ = std::fs::remove_dir_all(&final_dir);
+        std::fs::rename(&staged_dir, &final_dir)
+            .map_err(|e| format!("Failed to publish wake word model: {}", e))
+    })();
+
+    let _ = std::fs::remove_dir_all(&staging_root);
+    extraction
+}
@@ -0,0 +1,217 @@
+//! Low-confidence retry: decode an unsure Whisper transcript again before it
+//! is delivered.
+//!
+//! Opt-in (`confidenceRetry`). When the first pass scores below the
+//! threshold, the same audio is decoded again with a larger installed Whisper
+//! model, or with beam search on the same one, and whichever pass Whisper was
+//! surer of is kept. A time budget bounds it: no retry runs when the first
+//! decode alone took that long, and a retry still decoding at the deadline is
+//! aborted and the first pass delivered. Every retry is reported as
+//! `confidence-retry` with running totals, so how often retrying changes the
+//! output can be measured.
+
+use crate::dictation_context::ConfidenceRetry;
+use crate::transcriber::TranscriptConfidence;
+use crate::MutexExt;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::Emitter;
+
+pub const CONFIDENCE_RETRY: &str = "confidence-retry";
+
+/// What one retry did to the delivered text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryOutcome {
+    /// The retry was surer and its text replaced a different first pass.
+    Changed,
+    /// The retry was surer but read the audio the same way.
+    Unchanged,
+    /// The first pass was at least as sure, so it was kept.
+    KeptFirst,
+    /// The first decode alone used the budget; no retry ran.
+    Skipped,
+    /// The retry ran past its deadline and was discarded.
+    OverBudget,
+    Failed,
+}
+
+/// Retries since launch, by outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryStats {
+    /// Transcripts that scored below the threshold.
+    pub triggered: u64,
+    pub changed: u64,
+    pub unchanged: u64,
+    pub kept_first: u64,
+    pub skipped: u64,
+    pub over_budget: u64,
+    pub failed: u64,
+}
+
+impl RetryStats {
+    fn record(&mut self, outcome: RetryOutcome) {
+        self.triggered += 1;
+        let count = match outcome {
+            RetryOutcome::Changed => &mut self.changed,
+            RetryOutcome::Unchanged => &mut self.unchanged,
+            RetryOutcome::KeptFirst => &mut self.kept_first,
+            RetryOutcome::Skipped => &mut self.skipped,
+            RetryOutcome::OverBudget => &mut self.over_budget,
+            RetryOutcome::Failed => &mut self.failed,
+        };
+        *count += 1;
+    }
+}
+
+/// Payload of `confidence-retry`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryReport {
+    pub recording_id: u64,
+    pub model_name: String,
+    pub beam_search: bool,
+    pub outcome: RetryOutcome,
+    pub first_score: f32,
+    /// None when the retry didn't finish or reported no confidence.
+    pub retry_score: Option<f32>,
+    pub retry_ms: u64,
+    pub totals: RetryStats,
+}
+
+static STATS: Mutex<RetryStats> = Mutex::new(RetryStats {
+    triggered: 0,
+    changed: 0,
+    unchanged: 0,
+    kept_first: 0,
+    skipped: 0,
+    over_budget: 0,
+    failed: 0,
+});
+
+/// Whether a first pass is unsure enough to retry.
+pub fn needs_retry(confidence: &TranscriptConfidence, threshold: f32) -> bool {
+    confidence.score < threshold
+}
+
+/// Compare a finished retry with the first pass. A retry without confidence
+/// can't be shown to be better, so the first pass stands.
+pub fn compare(
+    first_text: &str,
+    first: &TranscriptConfidence,
+    retry_text: &str,
+    retry: Option<&TranscriptConfidence>,
+) -> RetryOutcome {
+    if !retry.is_some_and(|retry| retry.score > first.score) {
+        RetryOutcome::KeptFirst
+    } else if retry_text.trim() == first_text.trim() {
+        RetryOutcome::Unchanged
+    } else {
+        RetryOutcome::Changed
+    }
+}
+
+/// Count a retry, log it, and emit `confidence-retry`.
+pub fn report(
+    app: &tauri::AppHandle,
+    recording_id: u64,
+    retry: &ConfidenceRetry,
+    outcome: RetryOutcome,
+    first_score: f32,
+    retry_score: Option<f32>,
+    retry_ms: u64,
+) {
+    let totals = {
+        let mut stats = STATS.lock_or_recover();
+        stats.record(outcome);
+        *stats
+    };
+    let beam_search =
+        retry.whisper_decoding.sampling == crate::transcriber::whisper::WhisperSampling::BeamSearch;
+    tracing::info!(
+        target: "pipeline",
+        recording_id,
+        model = %retry.model_name,
+        beam_search,
+        ?outcome,
+        first_score,
+        retry_score,
+        retry_ms,
+        triggered = totals.triggered,
+        changed = totals.changed,
+        "confidence_retry"
+    );
+    let _ = app.emit(
+        CONFIDENCE_RETRY,
+        RetryReport {
+            recording_id,
+            model_name: retry.model_name.clone(),
+            beam_search,
+            outcome,
+            first_score,
+            retry_score,
+            retry_ms,
+            totals,
+        },
+    );
+}
+
+#[tauri::command]
+pub fn get_confidence_retry_stats() -> RetryStats {
+    *STATS.lock_or_recover()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confidence(score: f32) -> TranscriptConfidence {
+        TranscriptConfidence {
+            score,
+            avg_logprob: score.ln(),
+            no_speech_prob: 0.0,
+            low: false,
+            segments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn only_transcripts_below_the_threshold_are_retried() {
+        assert!(needs_retry(&confidence(0.3), 0.4));
+        assert!(!needs_retry(&confidence(0.4), 0.4));
+    }
+
+    #[test]
+    fn the_surer_pass_wins_and_text_changes_are_told_apart() {
+        let first = confidence(0.3);
+        assert_eq!(
+            compare("their", &first, "there", Some(&confidence(0.6))),
+            RetryOutcome::Changed
+        );
+        assert_eq!(
+            compare("there", &first, " there ", Some(&confidence(0.6))),
+            RetryOutcome::Unchanged
+        );
+        assert_eq!(
+            compare("their", &first, "there", Some(&confidence(0.2))),
+            RetryOutcome::KeptFirst
+        );
+        assert_eq!(
+            compare("their", &first, "there", None),
+            RetryOutcome::KeptFirst
+        );
+    }
+
+    #[test]
+    fn stats_count_every_trigger_by_outcome() {
+        let mut stats = RetryStats::default();
+        stats.record(RetryOutcome::Changed);
+        stats.record(RetryOutcome::OverBudget);
+        stats.record(RetryOutcome::Changed);
+        assert_eq!(stats.triggered, 3);
+        assert_eq!(stats.changed, 2);
+        assert_eq!(stats.over_budget, 1);
+        assert_eq!(stats.kept_first, 0);
+    }
+}
@@ -0,0 +1,149 @@
+//! Named configuration presets ("Meetings", "Coding", "Spanish email").
+//!
+//! Presets are settings, so the frontend owns them: it stores each preset's
+//! values, applies one through the normal settings path, and guards edits
+//! with a revision check. This module only mirrors their ids and names, sent
+//! by `set_config_presets`, so the tray can list them and so a preset can be
+//! asked for from the tray, `apply_config_preset`, or a dictation macro
+//! without a round trip. Asking emits `config-preset-requested`; the main
+//! window applies it and the tray's check follows the next
+//! `set_config_presets`.
+
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::Emitter;
+
+pub const CONFIG_PRESET_REQUESTED: &str = "config-preset-requested";
+
+/// Mirrors `CONFIG_PRESETS_MAX` in `settings.ts`.
+const MAX_PRESETS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetSummary {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Default)]
+struct Presets {
+    presets: Vec<PresetSummary>,
+    /// The preset the current settings match, if any.
+    active: Option<String>,
+}
+
+static PRESETS: Mutex<Presets> = Mutex::new(Presets {
+    presets: Vec::new(),
+    active: None,
+});
+
+/// Blank ids and names are dropped, as are repeated ids; the list is capped.
+fn normalized(presets: Vec<PresetSummary>) -> Vec<PresetSummary> {
+    let mut kept: Vec<PresetSummary> = Vec::new();
+    for preset in presets {
+        let id = preset.id.trim();
+        let name = preset.name.trim();
+        if id.is_empty() || name.is_empty() || kept.iter().any(|existing| existing.id == id) {
+            continue;
+        }
+        kept.push(PresetSummary {
+            id: id.to_string(),
+            name: name.to_string(),
+        });
+        if kept.len() == MAX_PRESETS {
+            break;
+        }
+    }
+    kept
+}
+
+/// The presets in menu order, and the active one's id.
+pub(crate) fn snapshot() -> (Vec<PresetSummary>, Option<String>) {
+    let presets = PRESETS.lock_or_recover();
+    (presets.presets.clone(), presets.active.clone())
+}
+
+/// A preset's name, for the macro confirmation alert.
+pub(crate) fn name_of(id: &str) -> Option<String> {
+    PRESETS
+        .lock_or_recover()
+        .presets
+        .iter()
+        .find(|preset| preset.id == id)
+        .map(|preset| preset.name.clone())
+}
+
+/// Ask the main window to apply preset `id`.
+pub(crate) fn request(app: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    let Some(name) = name_of(id) else {
+        return Err("That preset no longer exists.".to_string());
+    };
+    tracing::info!(target: "system", id, "config preset requested");
+    let _ = app.emit(
+        CONFIG_PRESET_REQUESTED,
+        PresetSummary {
+            id: id.to_string(),
+            name,
+        },
+    );
+    Ok(())
+}
+
+/// Replace the mirrored preset list and redraw the tray's Presets submenu.
+#[tauri::command]
+pub fn set_config_presets(
+    app: tauri::AppHandle,
+    presets: Vec<PresetSummary>,
+    active: Option<String>,
+) {
+    let presets = normalized(presets);
+    let active = active.filter(|id| presets.iter().any(|preset| &preset.id == id));
+    tracing::info!(target: "system", count = presets.len(), active = active.is_some(), "set_config_presets");
+    *PRESETS.lock_or_recover() = Presets { presets, active };
+    crate::commands::tray::refresh_presets(&app);
+}
+
+/// Switch to preset `id`, as the tray does. Errors when there is no such
+/// preset.
+#[tauri::command]
+pub fn apply_config_preset(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    request(&app, &id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(id: &str, name: &str) -> PresetSummary {
+        PresetSummary {
+            id: id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn normalized_drops_blank_and_repeated_presets() {
+        assert_eq!(
+            normalized(vec![
+                preset(" meetings ", " Meetings "),
+                preset("meetings", "Meetings again"),
+                preset("", "No id"),
+                preset("coding", "  "),
+                preset("spanish", "Spanish email"),
+            ]),
+            vec![
+                preset("meetings", "Meetings"),
+                preset("spanish", "Spanish email")
+            ]
+        );
+    }
+
+    #[test]
+    fn normalized_caps_the_list() {
+        let many = (0..MAX_PRESETS + 5)
+            .map(|index| preset(&format!("p{index}"), &format!("Preset {index}")))
+            .collect();
+        assert_eq!(normalized(many).len(), MAX_PRESETS);
+    }
+}
@@ -0,0 +1,380 @@
+//! Context biasing: names from the app being dictated into, fed to Whisper's
+//! initial prompt so they come out spelled right.
+//!
+//! Opt-in (`contextBiasing`) and limited to the apps on its allowlist. When a
+//! dictation starts over one of them, the focused window's title and, if
+//! allowed separately, the selected text are read over Accessibility. Only a
+//! short list of name-like terms survives — capitalized words, identifiers,
+//! words with digits — so replying to "Re: Q3 plan — Priya Raman" biases
+//! Whisper toward "Priya Raman" and "Q3" without sending the whole thread. The
+//! selection read is the one `selectionDictation` uses, so secure fields are
+//! refused the same way.
+//!
+//! The terms live only until the take's transcription starts, and neither the
+//! title, the selection, nor the terms are ever logged; only counts are.
+
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Most context terms added to one take's prompt, so the context never
+/// crowds the vocabulary out of Whisper's prompt budget.
+pub const MAX_CONTEXT_TERMS: usize = 24;
+const MAX_APPS: usize = 50;
+const MAX_TERM_CHARS: usize = 40;
+/// Captured terms waiting for their take's transcription. Takes queue behind
+/// one another, so a few are kept; the oldest goes first.
+const MAX_PENDING: usize = 4;
+
+/// Words that are capitalized only because they start a title or sentence,
+/// or that every mail and chat window carries.
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "are", "as", "at", "be", "but", "by", "can",
+    "cc", "dear", "do", "draft", "fw", "fwd", "for", "from", "good", "hello", "hey", "hi", "how",
+    "i", "if", "in", "inbox", "is", "it", "just", "let", "me", "message", "new", "no", "not", "of",
+    "ok", "on", "or", "our", "please", "re", "reply", "so", "thank", "thanks", "that", "the",
+    "this", "to", "untitled", "up", "we", "what", "when", "will", "with", "yes", "you", "your",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextBiasingSettings {
+    pub enabled: bool,
+    /// Read the focused window's title.
+    pub window_title: bool,
+    /// Read the selected text too.
+    pub selected_text: bool,
+    /// Bundle identifiers of the apps context is read from.
+    pub apps: Vec<String>,
+}
+
+impl Default for ContextBiasingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_title: true,
+            selected_text: false,
+            apps: Vec::new(),
+        }
+    }
+}
+
+/// What a take may read for its prompt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContextSources {
+    pub window_title: bool,
+    pub selected_text: bool,
+}
+
+impl ContextBiasingSettings {
+    /// The reads allowed while `bundle_id` is frontmost: none unless biasing
+    /// is on and the app is on the allowlist.
+    pub fn sources_for(&self, bundle_id: Option<&str>) -> ContextSources {
+        if !self.enabled || !bundle_id.is_some_and(|id| self.allows(id)) {
+            return ContextSources::default();
+        }
+        ContextSources {
+            window_title: self.window_title,
+            selected_text: self.selected_text,
+        }
+    }
+
+    pub fn allows(&self, bundle_id: &str) -> bool {
+        self.apps
+            .iter()
+            .any(|app| app.eq_ignore_ascii_case(bundle_id))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AllowedApp {
+    bundle_id: String,
+}
+
+/// Parse the `contextBiasing` settings object. Apps without a bundle id and
+/// repeated ones are skipped.
+pub fn parse_settings(value: &serde_json::Value) -> ContextBiasingSettings {
+    let defaults = ContextBiasingSettings::default();
+    let mut apps: Vec<String> = Vec::new();
+    for app in value
+        .get("apps")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|value| serde_json::from_value::<AllowedApp>(value.clone()).ok())
+    {
+        let bundle_id = app.bundle_id.trim();
+        if bundle_id.is_empty()
+            || apps
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(bundle_id))
+        {
+            continue;
+        }
+        apps.push(bundle_id.to_string());
+        if apps.len() == MAX_APPS {
+            break;
+        }
+    }
+    ContextBiasingSettings {
+        enabled: value
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.enabled),
+        window_title: value
+            .get("windowTitle")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.window_title),
+        selected_text: value
+            .get("selectedText")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.selected_text),
+        apps,
+    }
+}
+
+/// Whether `model_name` reads the initial prompt at all. Only Whisper does,
+/// so other backends never trigger a context read.
+pub fn model_takes_prompt(model_name: &str) -> bool {
+    crate::model_runtime::model_definition(model_name)
+        .is_ok_and(|definition| definition.backend == crate::model_runtime::BackendKind::Whisper)
+}
+
+fn is_term_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '\'' | '’' | '.' | '-' | '_' | '+' | '&')
+}
+
+/// A word worth biasing toward: it has a letter, and it is capitalized, has
+/// a capital inside (`iPhone`, `McKinsey`), or mixes in digits (`Q3`).
+fn is_name_like(word: &str) -> bool {
+    let mut chars = word.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let length = word.chars().count();
+    if !(2..=MAX_TERM_CHARS).contains(&length) || !word.chars().any(char::is_alphabetic) {
+        return false;
+    }
+    // "I'll" and "We're" are judged by the word before the apostrophe.
+    let lower = word.to_lowercase();
+    let base = lower.split(['\'', '’']).next().unwrap_or_default();
+    if STOPWORDS.contains(&base) {
+        return false;
+    }
+    first.is_uppercase()
+        || chars.any(char::is_uppercase)
+        || word.chars().any(|c| c.is_ascii_digit())
+}
+
+/// The name-like terms of `texts`, in order of first appearance, each once
+/// (case-insensitively), at most [`MAX_CONTEXT_TERMS`].
+pub fn prompt_terms(texts: &[&str]) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for text in texts {
+        for word in text
+            .split(|c: char| !is_term_char(c))
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        {
+            if !is_name_like(word) || !seen.insert(word.to_lowercase()) {
+                continue;
+            }
+            terms.push(word.to_string());
+            if terms.len() == MAX_CONTEXT_TERMS {
+                return terms;
+            }
+        }
+    }
+    terms
+}
+
+/// `prompt` with the context terms in front, repeats dropped. The context
+/// goes first even though typed vocabulary usually leads: it is a short,
+/// capped list about this very take, and Whisper keeps the start of a prompt
+/// that runs long.
+pub fn merge_prompt(prompt: Option<&str>, terms: &[String]) -> Option<String> {
+    if terms.is_empty() {
+        return prompt.map(str::to_string);
+    }
+    let mut seen = std::collections::HashSet::new();
+    let merged = terms
+        .iter()
+        .map(String::as_str)
+        .chain(prompt.unwrap_or_default().split_whitespace())
+        .filter(|term| seen.insert(term.to_lowercase()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(merged)
+}
+
+static PENDING: Mutex<Vec<(u64, Vec<String>)>> = Mutex::new(Vec::new());
+
+/// Keep `terms` for recording `recording_id` until its transcription starts.
+pub fn store(recording_id: u64, terms: Vec<String>) {
+    let mut pending = PENDING.lock_or_recover();
+    pending.retain(|(id, _)| *id != recording_id);
+    if pending.len() == MAX_PENDING {
+        pending.remove(0);
+    }
+    pending.push((recording_id, terms));
+}
+
+/// The terms captured for `recording_id`, once. Empty when the capture found
+/// nothing, was not allowed, or hasn't finished by the time the take is
+/// transcribed.
+pub fn take(recording_id: u64) -> Vec<String> {
+    let mut pending = PENDING.lock_or_recover();
+    match pending.iter().position(|(id, _)| *id == recording_id) {
+        Some(index) => pending.remove(index).1,
+        None => Vec::new(),
+    }
+}
+
+/// Read what `sources` allows from the frontmost app and keep its terms for
+/// `recording_id`. The app is checked against the allowlist again at read
+/// time, since focus can move between the start of the take and the read.
+pub async fn capture(
+    app_handle: tauri::AppHandle,
+    recording_id: u64,
+    sources: ContextSources,
+    settings: ContextBiasingSettings,
+) {
+    let mut texts: Vec<String> = Vec::new();
+    if sources.window_title {
+        if let Some((Some(bundle_id), title)) =
+            crate::selection::capture_window_title(&app_handle).await
+        {
+            if settings.allows(&bundle_id) {
+                texts.push(title);
+            }
+        }
+    }
+    if sources.selected_text {
+        if let Ok(snapshot) = crate::selection::capture_selection_for_dictation(&app_handle).await {
+            if snapshot
+                .bundle_id
+                .as_deref()
+                .is_some_and(|bundle_id| settings.allows(bundle_id))
+            {
+                texts.push(snapshot.text);
+            }
+        }
+    }
+    let terms = prompt_terms(&texts.iter().map(String::as_str).collect::<Vec<_>>());
+    tracing::info!(
+        target: "pipeline",
+        recording_id,
+        sources = texts.len(),
+        terms = terms.len(),
+        "context biasing captured"
+    );
+    if !terms.is_empty() {
+        store(recording_id, terms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(apps: &[&str]) -> ContextBiasingSettings {
+        ContextBiasingSettings {
+            enabled: true,
+            apps: apps.iter().map(|app| app.to_string()).collect(),
+            ..ContextBiasingSettings::default()
+        }
+    }
+
+    #[test]
+    fn parse_settings_defaults_off_and_skips_bad_apps() {
+        assert_eq!(
+            parse_settings(&serde_json::json!("nope")),
+            ContextBiasingSettings::default()
+        );
+        let parsed = parse_settings(&serde_json::json!({
+            "enabled": true,
+            "selectedText": true,
+            "apps": [
+                { "bundleId": " com.apple.mail ", "name": "Mail" },
+                { "bundleId": "COM.APPLE.MAIL", "name": "Mail again" },
+                { "bundleId": "  ", "name": "Blank" },
+                { "name": "No id" },
+                { "bundleId": "com.tinyspeck.slackmacgap", "name": "Slack" }
+            ]
+        }));
+        assert!(parsed.enabled);
+        assert!(parsed.window_title);
+        assert!(parsed.selected_text);
+        assert_eq!(
+            parsed.apps,
+            vec!["com.apple.mail", "com.tinyspeck.slackmacgap"]
+        );
+    }
+
+    #[test]
+    fn sources_require_enabled_and_an_allowlisted_app() {
+        let mail = settings(&["com.apple.mail"]);
+        assert_eq!(
+            mail.sources_for(Some("com.apple.Mail")),
+            ContextSources {
+                window_title: true,
+                selected_text: false,
+            }
+        );
+        assert_eq!(
+            mail.sources_for(Some("com.apple.Safari")),
+            ContextSources::default()
+        );
+        assert_eq!(mail.sources_for(None), ContextSources::default());
+
+        let off = ContextBiasingSettings {
+            enabled: false,
+            ..mail
+        };
+        assert_eq!(
+            off.sources_for(Some("com.apple.mail")),
+            ContextSources::default()
+        );
+    }
+
+    #[test]
+    fn prompt_terms_keep_names_and_drop_filler() {
+        assert_eq!(
+            prompt_terms(&[
+                "Re: Q3 plan — Priya Raman",
+                "Thanks Priya, I'll loop in deShawn at McKinsey."
+            ]),
+            vec!["Q3", "Priya", "Raman", "deShawn", "McKinsey"]
+        );
+        assert!(prompt_terms(&["re: the new message", "Inbox"]).is_empty());
+    }
+
+    #[test]
+    fn prompt_terms_are_capped() {
+        let text = (0..MAX_CONTEXT_TERMS + 10)
+            .map(|index| format!("Name{index}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(prompt_terms(&[&text]).len(), MAX_CONTEXT_TERMS);
+    }
+
+    #[test]
+    fn merge_prompt_puts_context_first_without_repeats() {
+        let terms = vec!["Priya".to_string(), "Tauri".to_string()];
+        assert_eq!(
+            merge_prompt(Some("tauri serde"), &terms).as_deref(),
+            Some("Priya Tauri serde")
+        );
+        assert_eq!(merge_prompt(None, &terms).as_deref(), Some("Priya Tauri"));
+        assert_eq!(merge_prompt(Some("serde"), &[]).as_deref(), Some("serde"));
+        assert_eq!(merge_prompt(None, &[]), None);
+    }
+
+    #[test]
+    fn pending_terms_are_taken_once() {
+        store(9_001, vec!["Priya".to_string()]);
+        assert_eq!(take(9_001), vec!["Priya"]);
+        assert!(take(9_001).is_empty());
+    }
+}
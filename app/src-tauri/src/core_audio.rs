@@ -0,0 +1,158 @@
+//! CoreAudio device queries shared by `input_quality.rs` and
+//! `mic_privacy.rs`: the device list, which devices can capture, and
+//! individual device properties.
+
+use std::ffi::{c_char, c_void};
+
+type CFTypeRef = *const c_void;
+
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+#[repr(C)]
+struct AudioObjectPropertyAddress {
+    selector: u32,
+    scope: u32,
+    element: u32,
+}
+
+#[link(name = "CoreAudio", kind = "framework")]
+extern "C" {
+    fn AudioObjectGetPropertyDataSize(
+        object_id: u32,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const c_void,
+        data_size: *mut u32,
+    ) -> i32;
+    fn AudioObjectGetPropertyData(
+        object_id: u32,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const c_void,
+        data_size: *mut u32,
+        data: *mut c_void,
+    ) -> i32;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFStringGetCString(
+        string: CFTypeRef,
+        buffer: *mut c_char,
+        size: isize,
+        encoding: u32,
+    ) -> bool;
+    fn CFRelease(value: CFTypeRef);
+}
+
+const SYSTEM_OBJECT: u32 = 1;
+const ELEMENT_MAIN: u32 = 0;
+pub(crate) const SCOPE_GLOBAL: u32 = u32::from_be_bytes(*b"glob");
+const SCOPE_INPUT: u32 = u32::from_be_bytes(*b"inpt");
+const HARDWARE_DEVICES: u32 = u32::from_be_bytes(*b"dev#");
+const DEVICE_STREAMS: u32 = u32::from_be_bytes(*b"stm#");
+/// `kAudioDevicePropertyDeviceNameCFString`, the name cpal reports.
+const DEVICE_NAME: u32 = u32::from_be_bytes(*b"name");
+
+fn address(selector: u32, scope: u32) -> AudioObjectPropertyAddress {
+    AudioObjectPropertyAddress {
+        selector,
+        scope,
+        element: ELEMENT_MAIN,
+    }
+}
+
+fn data_size(object_id: u32, address: &AudioObjectPropertyAddress) -> Option<u32> {
+    let mut size = 0u32;
+    let status = unsafe {
+        AudioObjectGetPropertyDataSize(object_id, address, 0, std::ptr::null(), &mut size)
+    };
+    (status == 0).then_some(size)
+}
+
+fn device_ids() -> Option<Vec<u32>> {
+    let address = address(HARDWARE_DEVICES, SCOPE_GLOBAL);
+    let size = data_size(SYSTEM_OBJECT, &address)?;
+    let mut ids = vec![0u32; size as usize / std::mem::size_of::<u32>()];
+    let mut size = (ids.len() * std::mem::size_of::<u32>()) as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            SYSTEM_OBJECT,
+            &address,
+            0,
+            std::ptr::null(),
+            &mut size,
+            ids.as_mut_ptr() as *mut c_void,
+        )
+    };
+    if status != 0 {
+        return None;
+    }
+    ids.truncate(size as usize / std::mem::size_of::<u32>());
+    Some(ids)
+}
+
+fn has_input(device: u32) -> bool {
+    data_size(device, &address(DEVICE_STREAMS, SCOPE_INPUT)).is_some_and(|size| size > 0)
+}
+
+/// Every device with at least one input stream. `None` when the device list
+/// can't be read.
+pub(crate) fn input_devices() -> Option<Vec<u32>> {
+    Some(
+        device_ids()?
+            .into_iter()
+            .filter(|&id| has_input(id))
+            .collect(),
+    )
+}
+
+/// A 32-bit property of `device`, such as its transport type.
+pub(crate) fn u32_property(device: u32, selector: u32, scope: u32) -> Option<u32> {
+    let mut value = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            device,
+            &address(selector, scope),
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut value as *mut u32 as *mut c_void,
+        )
+    };
+    (status == 0).then_some(value)
+}
+
+pub(crate) fn device_name(device: u32) -> Option<String> {
+    let mut string: CFTypeRef = std::ptr::null();
+    let mut size = std::mem::size_of::<CFTypeRef>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            device,
+            &address(DEVICE_NAME, SCOPE_GLOBAL),
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut string as *mut CFTypeRef as *mut c_void,
+        )
+    };
+    if status != 0 || string.is_null() {
+        return None;
+    }
+    let mut buffer = [0 as c_char; 256];
+    let ok = unsafe {
+        CFStringGetCString(
+            string,
+            buffer.as_mut_ptr(),
+            buffer.len() as isize,
+            CF_STRING_ENCODING_UTF8,
+        )
+    };
+    unsafe { CFRelease(string) };
+    ok.then(|| {
+        unsafe { std::ffi::CStr::from_ptr(buffer.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    })
+}
@@ -844,6 +844,9 @@ mod tests {
             writing_style: None,
             ide_context_enabled: true,
             ide_project_roots: vec!["/project".to_string()],
+            injection_method_override: None,
+            trailing_override: None,
+            model_override: None,
         });
         let outcome = state.propose(
             CorrectionProposalRequest {
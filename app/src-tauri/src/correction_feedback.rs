@@ -0,0 +1,360 @@
+//! Learning from history edits: words the user keeps correcting transcripts
+//! to are fed back into Whisper's initial prompt.
+//!
+//! `correct_transcription` is called when a history entry's text is edited.
+//! The edit is word-diffed against the transcript, and each short replaced
+//! run is kept as a heard/written pair in `corrections.json` in the app data
+//! directory; whole transcripts are not stored. Re-editing an entry replaces
+//! what it taught. A written phrase that corrected at least two entries joins
+//! the prompt after the hand-typed vocabulary (`learnFromCorrections`, off by
+//! default), so a repeated mistake stops recurring. Only the newest
+//! corrections are kept, so ones the user no longer makes age out.
+
+use crate::text_metrics::{word_diff, DiffOp};
+use crate::{MutexExt, State};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+const CORRECTIONS_FILE: &str = "corrections.json";
+/// Corrected entries kept; older ones are dropped.
+const MAX_RECORDS: usize = 200;
+/// Longer replaced runs are rewrites rather than misheard words.
+const MAX_PHRASE_WORDS: usize = 4;
+/// An edit changing more runs than this rewrote the transcript and teaches
+/// nothing.
+const MAX_PAIRS_PER_EDIT: usize = 6;
+/// Entries a written phrase must have corrected before it joins the prompt.
+pub const MIN_REPEATS: u32 = 2;
+/// Learned phrases in the prompt, most frequent first. The prompt is
+/// token-bound and the hand-typed vocabulary comes ahead of them.
+const MAX_PROMPT_TERMS: usize = 24;
+
+static CORRECTIONS_PATH: OnceLock<PathBuf> = OnceLock::new();
+static RECORDS: Mutex<Vec<CorrectionRecord>> = Mutex::new(Vec::new());
+
+/// A run of words the model heard and what the user wrote instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionPair {
+    pub heard: String,
+    pub written: String,
+}
+
+/// What one history entry's edits taught.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CorrectionRecord {
+    entry_id: String,
+    corrected_at_ms: i64,
+    pairs: Vec<CorrectionPair>,
+}
+
+/// A correction and how many entries it was made in. Returned by
+/// `get_learned_corrections`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LearnedCorrection {
+    pub heard: String,
+    pub written: String,
+    pub count: u32,
+    /// Counted often enough to be in the prompt.
+    pub in_prompt: bool,
+}
+
+/// Lowercase letters and digits only, so punctuation and case edits teach
+/// nothing.
+fn core(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn trim_punctuation(text: &str) -> &str {
+    text.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+/// The heard/written pairs an edit of `original` into `corrected` teaches.
+pub fn pairs(original: &str, corrected: &str) -> Vec<CorrectionPair> {
+    // (deleted, inserted) words of each changed run
+    let mut runs: Vec<(Vec<String>, Vec<String>)> = Vec::new();
+    let mut in_run = false;
+    for span in word_diff(original, corrected) {
+        if span.op == DiffOp::Equal {
+            in_run = false;
+            continue;
+        }
+        if !in_run {
+            runs.push((Vec::new(), Vec::new()));
+            in_run = true;
+        }
+        if let Some((deleted, inserted)) = runs.last_mut() {
+            let words = span.text.split_whitespace().map(str::to_string);
+            if span.op == DiffOp::Delete {
+                deleted.extend(words);
+            } else {
+                inserted.extend(words);
+            }
+        }
+    }
+    if runs.len() > MAX_PAIRS_PER_EDIT {
+        return Vec::new();
+    }
+    let mut pairs: Vec<CorrectionPair> = Vec::new();
+    for (deleted, inserted) in runs {
+        if deleted.is_empty()
+            || inserted.is_empty()
+            || deleted.len() > MAX_PHRASE_WORDS
+            || inserted.len() > MAX_PHRASE_WORDS
+        {
+            continue;
+        }
+        let heard = trim_punctuation(&deleted.join(" ")).to_string();
+        let written = trim_punctuation(&inserted.join(" ")).to_string();
+        if written.is_empty() || core(&heard) == core(&written) {
+            continue;
+        }
+        let pair = CorrectionPair { heard, written };
+        if !pairs.contains(&pair) {
+            pairs.push(pair);
+        }
+    }
+    pairs
+}
+
+/// Corrections across entries, most frequent first, then most recent.
+fn learned(records: &[CorrectionRecord]) -> Vec<LearnedCorrection> {
+    let mut counts: HashMap<(String, String), (usize, u32)> = HashMap::new();
+    let mut order: Vec<(String, String)> = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        for pair in &record.pairs {
+            let key = (pair.heard.to_lowercase(), pair.written.clone());
+            let entry = counts.entry(key.clone()).or_insert_with(|| {
+                order.push(key);
+                (index, 0)
+            });
+            entry.0 = index;
+            entry.1 += 1;
+        }
+    }
+    let mut learned: Vec<(usize, LearnedCorrection)> = order
+        .into_iter()
+        .map(|key| {
+            let (last, count) = counts[&key];
+            (
+                last,
+                LearnedCorrection {
+                    heard: key.0,
+                    written: key.1,
+                    count,
+                    in_prompt: count >= MIN_REPEATS,
+                },
+            )
+        })
+        .collect();
+    learned.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(b.0.cmp(&a.0)));
+    learned.into_iter().map(|(_, learned)| learned).collect()
+}
+
+/// Written phrases corrected often enough to bias Whisper toward, space
+/// separated and deduped case-insensitively.
+fn prompt_terms_from(records: &[CorrectionRecord]) -> String {
+    let mut seen = std::collections::HashSet::new();
+    learned(records)
+        .into_iter()
+        .filter(|learned| learned.in_prompt && seen.insert(learned.written.to_lowercase()))
+        .take(MAX_PROMPT_TERMS)
+        .map(|learned| learned.written)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Replace what `entry_id` taught with `pairs`, keeping the newest records.
+fn apply(
+    records: &mut Vec<CorrectionRecord>,
+    entry_id: &str,
+    pairs: Vec<CorrectionPair>,
+    now_ms: i64,
+) {
+    records.retain(|record| record.entry_id != entry_id);
+    if !pairs.is_empty() {
+        records.push(CorrectionRecord {
+            entry_id: entry_id.to_string(),
+            corrected_at_ms: now_ms,
+            pairs,
+        });
+    }
+    let excess = records.len().saturating_sub(MAX_RECORDS);
+    records.drain(..excess);
+}
+
+fn read_records(path: &Path) -> Vec<CorrectionRecord> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save(records: &[CorrectionRecord]) -> Result<(), String> {
+    let Some(path) = CORRECTIONS_PATH.get() else {
+        return Ok(());
+    };
+    let contents = serde_json::to_string(records).map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| format!("Failed to save corrections: {}", e))
+}
+
+/// Set the file location and load it. Call once from setup.
+pub fn init(app_data_dir: &Path) {
+    let path = app_data_dir.join(CORRECTIONS_FILE);
+    *RECORDS.lock_or_recover() = read_records(&path);
+    let _ = CORRECTIONS_PATH.set(path);
+}
+
+/// The learned phrases for the Whisper prompt; empty until a correction has
+/// repeated.
+pub fn prompt_terms() -> String {
+    prompt_terms_from(&RECORDS.lock_or_recover())
+}
+
+/// Learn from a history entry edited from `original` to `text`. Returns the
+/// pairs kept, none when learning is off or the edit was a rewrite.
+#[tauri::command]
+pub fn correct_transcription(
+    state: tauri::State<'_, State>,
+    id: String,
+    original: String,
+    text: String,
+) -> Result<Vec<CorrectionPair>, String> {
+    if !state
+        .app_state
+        .dictation
+        .lock_or_recover()
+        .learn_from_corrections
+    {
+        return Ok(Vec::new());
+    }
+    let learned = pairs(&original, &text);
+    let prompt_changed = {
+        let mut records = RECORDS.lock_or_recover();
+        let before = prompt_terms_from(&records);
+        apply(
+            &mut records,
+            &id,
+            learned.clone(),
+            Local::now().timestamp_millis(),
+        );
+        save(&records)?;
+        prompt_terms_from(&records) != before
+    };
+    tracing::info!(
+        target: "pipeline",
+        pairs = learned.len(),
+        prompt_changed,
+        "transcription correction recorded"
+    );
+    if prompt_changed {
+        state.app_state.bump_settings_revision();
+    }
+    Ok(learned)
+}
+
+#[tauri::command]
+pub fn get_learned_corrections() -> Vec<LearnedCorrection> {
+    learned(&RECORDS.lock_or_recover())
+}
+
+/// Forget every recorded correction.
+#[tauri::command]
+pub fn clear_learned_corrections(state: tauri::State<'_, State>) -> Result<(), String> {
+    RECORDS.lock_or_recover().clear();
+    if let Some(path) = CORRECTIONS_PATH.get() {
+        match std::fs::remove_file(path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to clear corrections: {}", e)),
+        }
+    }
+    state.app_state.bump_settings_revision();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(heard: &str, written: &str) -> CorrectionPair {
+        CorrectionPair {
+            heard: heard.to_string(),
+            written: written.to_string(),
+        }
+    }
+
+    #[test]
+    fn edits_teach_short_replaced_runs_without_punctuation() {
+        assert_eq!(
+            pairs(
+                "push it to get hub, then ping kuber netties.",
+                "push it to GitHub, then ping Kubernetes."
+            ),
+            vec![
+                pair("get hub", "GitHub"),
+                pair("kuber netties", "Kubernetes")
+            ]
+        );
+        // Punctuation, case, pure insertions, and deletions teach nothing.
+        assert!(pairs("send it now", "Send it, now!").is_empty());
+        assert!(pairs("send it now", "send it right now").is_empty());
+        assert!(pairs("send it right now", "send it now").is_empty());
+    }
+
+    #[test]
+    fn rewrites_teach_nothing() {
+        assert!(pairs(
+            "one two three four five six seven eight nine ten eleven twelve thirteen fourteen",
+            "one 2 three 4 five 6 seven 8 nine 10 eleven 12 thirteen 14"
+        )
+        .is_empty());
+        assert!(pairs(
+            "please send the report",
+            "could you mail over those figures today"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn repeated_corrections_join_the_prompt_and_re_edits_replace_what_an_entry_taught() {
+        let mut records = Vec::new();
+        apply(&mut records, "1", vec![pair("get hub", "GitHub")], 1);
+        assert_eq!(prompt_terms_from(&records), "");
+
+        apply(&mut records, "2", vec![pair("Get hub", "GitHub")], 2);
+        apply(&mut records, "3", vec![pair("murmer", "Murmur")], 3);
+        assert_eq!(prompt_terms_from(&records), "GitHub");
+        let learned = learned(&records);
+        assert_eq!(learned[0].written, "GitHub");
+        assert_eq!(learned[0].count, 2);
+        assert!(!learned[1].in_prompt);
+
+        apply(&mut records, "2", Vec::new(), 4);
+        assert_eq!(records.len(), 2);
+        assert_eq!(prompt_terms_from(&records), "");
+    }
+
+    #[test]
+    fn only_the_newest_records_are_kept() {
+        let mut records = Vec::new();
+        for id in 0..MAX_RECORDS + 5 {
+            apply(
+                &mut records,
+                &id.to_string(),
+                vec![pair("a", "b")],
+                id as i64,
+            );
+        }
+        assert_eq!(records.len(), MAX_RECORDS);
+        assert_eq!(records[0].entry_id, "5");
+    }
+}
@@ -0,0 +1,474 @@
+//! Crash reports for panics and fatal signals.
+//!
+//! The panic hook writes a report straight away: the panic message and
+//! location, a backtrace, the last 200 lines of the pretty log, and the app
+//! version, selected model, and OS version. A fatal signal (SIGSEGV, SIGBUS,
+//! SIGILL, SIGFPE, SIGABRT) can't safely do any of that inside its handler, so
+//! the handler only writes a marker holding the signal number; the next launch
+//! turns the marker into a full report from the log tail and the context the
+//! crashed session left in `session.txt`. Reports stay in `crashes/` next to
+//! the logs and only leave it through `export_crash_report`, which requires the
+//! user's explicit consent.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const LOG_TAIL_LINES: usize = 200;
+/// Oldest reports are pruned beyond this many.
+const MAX_REPORTS: usize = 20;
+const SESSION_FILE: &str = "session.txt";
+const SIGNAL_MARKER: &str = "pending-signal";
+
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+static OS_VERSION: OnceLock<String> = OnceLock::new();
+static MODEL: Mutex<String> = Mutex::new(String::new());
+/// Set by the panic hook so the SIGABRT that follows a panic in release
+/// (`panic = "abort"`) doesn't produce a second, emptier report.
+static PANICKED: AtomicBool = AtomicBool::new(false);
+
+/// One report, as listed in Settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportSummary {
+    pub id: String,
+    /// `panic` or `signal`.
+    pub kind: String,
+    pub created_at_ms: u64,
+    /// First line of the panic message, or the signal name.
+    pub message: String,
+}
+
+fn crash_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("local-dictation").join("crashes"))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(target_os = "macos")]
+fn detect_os_version() -> String {
+    std::process::Command::new("/usr/bin/sw_vers")
+        .arg("-productVersion")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| format!("macOS {}", version.trim()))
+        .unwrap_or_else(|| "macOS (unknown version)".to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn detect_os_version() -> String {
+    format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+fn signal_name(signal: i32) -> &'static str {
+    match signal {
+        4 => "SIGILL",
+        6 => "SIGABRT",
+        7 | 10 => "SIGBUS",
+        8 => "SIGFPE",
+        11 => "SIGSEGV",
+        _ => "signal",
+    }
+}
+
+/// `app_version`/`model`/`os_version` lines shared by `session.txt` and the
+/// report header.
+fn context_lines(model: &str) -> String {
+    format!(
+        "app_version: {}\nmodel: {}\nos_version: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        if model.is_empty() { "unknown" } else { model },
+        OS_VERSION.get().map(String::as_str).unwrap_or("unknown"),
+    )
+}
+
+fn render_report(
+    kind: &str,
+    created_at_ms: u64,
+    context: &str,
+    message: &str,
+    details: &str,
+    log_tail: &str,
+) -> String {
+    format!(
+        "Murmur crash report\nkind: {kind}\ntime_ms: {created_at_ms}\n{context}message: {message}\n{details}\n-- last {LOG_TAIL_LINES} log lines --\n{log_tail}\n"
+    )
+}
+
+fn parse_summary(id: &str, report: &str) -> Option<CrashReportSummary> {
+    let field = |name: &str| {
+        report
+            .lines()
+            .take(12)
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix(": "))
+    };
+    Some(CrashReportSummary {
+        id: id.to_string(),
+        kind: field("kind")?.to_string(),
+        created_at_ms: field("time_ms")?.parse().ok()?,
+        message: field("message").unwrap_or_default().to_string(),
+    })
+}
+
+/// Report ids are bare file names of the form `crash-<ms>-<kind>.txt`, so a
+/// command can never be pointed outside `crashes/`.
+fn is_report_id(id: &str) -> bool {
+    id.starts_with("crash-")
+        && id.ends_with(".txt")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+}
+
+fn write_report(dir: &Path, kind: &str, created_at_ms: u64, report: &str) -> Option<String> {
+    let id = format!("crash-{created_at_ms}-{kind}.txt");
+    std::fs::write(dir.join(&id), report).ok()?;
+    prune_reports(dir, MAX_REPORTS);
+    Some(id)
+}
+
+fn report_ids(dir: &Path) -> Vec<String> {
+    let mut ids = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter(|name| is_report_id(name))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // `crash-<ms>-…` with same-width millisecond stamps sorts chronologically.
+    ids.sort_by(|left, right| right.cmp(left));
+    ids
+}
+
+fn prune_reports(dir: &Path, keep: usize) {
+    for id in report_ids(dir).into_iter().skip(keep) {
+        let _ = std::fs::remove_file(dir.join(id));
+    }
+}
+
+fn list_in(dir: &Path) -> Vec<CrashReportSummary> {
+    report_ids(dir)
+        .into_iter()
+        .filter_map(|id| {
+            let report = std::fs::read_to_string(dir.join(&id)).ok()?;
+            parse_summary(&id, &report)
+        })
+        .collect()
+}
+
+/// Turn a marker left by a fatal signal in the previous session into a report,
+/// using that session's context and the log tail it left behind.
+fn collect_signal_marker(dir: &Path, log_tail: &str) -> Option<String> {
+    let marker = dir.join(SIGNAL_MARKER);
+    let signal = std::fs::read_to_string(&marker).ok()?;
+    let _ = std::fs::remove_file(&marker);
+    let signal = signal.trim().parse::<i32>().unwrap_or(0);
+    let created_at_ms = std::fs::metadata(dir.join(SESSION_FILE))
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_else(now_ms);
+    let context = std::fs::read_to_string(dir.join(SESSION_FILE))
+        .unwrap_or_else(|_| "app_version: unknown\nmodel: unknown\nos_version: unknown\n".into());
+    let report = render_report(
+        "signal",
+        created_at_ms,
+        &context,
+        &format!("{} (signal {})", signal_name(signal), signal),
+        "\n-- backtrace --\nunavailable: the process was terminated by a fatal signal\n",
+        log_tail,
+    );
+    write_report(dir, "signal", created_at_ms, &report)
+}
+
+fn write_session(dir: &Path, model: &str) {
+    let _ = std::fs::write(dir.join(SESSION_FILE), context_lines(model));
+}
+
+fn write_panic_report(info: &std::panic::PanicHookInfo<'_>) {
+    let Some(dir) = CRASH_DIR.get() else {
+        return;
+    };
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic with a non-string payload".to_string());
+    let location = info
+        .location()
+        .map(|location| format!("{}:{}", location.file(), location.line()))
+        .unwrap_or_else(|| "unknown".to_string());
+    let thread = std::thread::current()
+        .name()
+        .unwrap_or("unnamed")
+        .to_string();
+    // try_lock: a panic while the model is being updated must not deadlock.
+    let model = MODEL
+        .try_lock()
+        .map(|model| model.clone())
+        .unwrap_or_default();
+    let details = format!(
+        "location: {location}\nthread: {thread}\n\n-- backtrace --\n{}\n",
+        std::backtrace::Backtrace::force_capture()
+    );
+    let created_at_ms = now_ms();
+    let report = render_report(
+        "panic",
+        created_at_ms,
+        &context_lines(&model),
+        message.lines().next().unwrap_or(""),
+        &details,
+        &crate::telemetry::read_pretty_log_tail(LOG_TAIL_LINES),
+    );
+    write_report(dir, "panic", created_at_ms, &report);
+}
+
+#[cfg(unix)]
+mod signals {
+    use super::*;
+    use std::os::unix::ffi::OsStrExt;
+
+    const FATAL_SIGNALS: [libc::c_int; 5] = [
+        libc::SIGSEGV,
+        libc::SIGBUS,
+        libc::SIGILL,
+        libc::SIGFPE,
+        libc::SIGABRT,
+    ];
+
+    static MARKER_PATH: OnceLock<std::ffi::CString> = OnceLock::new();
+    static PREVIOUS: OnceLock<Vec<(libc::c_int, libc::sigaction)>> = OnceLock::new();
+
+    /// Async-signal-safe: no allocation, no locks, only open/write/close and
+    /// sigaction. Restores the previous handler and returns, so the fault
+    /// re-fires into it (Rust's stack-overflow handler, or the default
+    /// action), and `abort` re-raises SIGABRT itself.
+    extern "C" fn on_fatal_signal(signal: libc::c_int) {
+        if !PANICKED.load(Ordering::SeqCst) {
+            if let Some(path) = MARKER_PATH.get() {
+                let mut digits = [0u8; 12];
+                let mut len = 0;
+                let mut value = signal.unsigned_abs();
+                loop {
+                    digits[len] = b'0' + (value % 10) as u8;
+                    len += 1;
+                    value /= 10;
+                    if value == 0 || len == digits.len() {
+                        break;
+                    }
+                }
+                digits[..len].reverse();
+                unsafe {
+                    let fd = libc::open(
+                        path.as_ptr(),
+                        libc::O_CREAT | libc::O_WRONLY | libc::O_TRUNC,
+                        0o600 as libc::c_uint,
+                    );
+                    if fd >= 0 {
+                        libc::write(fd, digits.as_ptr().cast(), len);
+                        libc::close(fd);
+                    }
+                }
+            }
+        }
+        if let Some(previous) = PREVIOUS.get() {
+            for (sig, action) in previous {
+                if *sig == signal {
+                    unsafe {
+                        libc::sigaction(signal, action, std::ptr::null_mut());
+                    }
+                    return;
+                }
+            }
+        }
+        unsafe {
+            libc::signal(signal, libc::SIG_DFL);
+        }
+    }
+
+    pub(super) fn install(dir: &Path) {
+        let Ok(path) = std::ffi::CString::new(dir.join(SIGNAL_MARKER).as_os_str().as_bytes())
+        else {
+            return;
+        };
+        let _ = MARKER_PATH.set(path);
+        let mut previous = Vec::with_capacity(FATAL_SIGNALS.len());
+        for signal in FATAL_SIGNALS {
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_fatal_signal as extern "C" fn(libc::c_int) as usize;
+                action.sa_flags = libc::SA_ONSTACK;
+                libc::sigemptyset(&mut action.sa_mask);
+                let mut old: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(signal, &action, &mut old) == 0 {
+                    previous.push((signal, old));
+                }
+            }
+        }
+        let _ = PREVIOUS.set(previous);
+    }
+}
+
+/// Install the panic hook and fatal-signal handlers, and turn a marker left by
+/// a crashed session into a report. Call once at startup, before telemetry
+/// starts appending this session to the log, so a signal report gets the
+/// crashed session's log tail.
+pub fn init() {
+    let Some(dir) = crash_dir() else {
+        return;
+    };
+    if std::fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    let _ = OS_VERSION.set(detect_os_version());
+    let _ = collect_signal_marker(
+        &dir,
+        &crate::telemetry::read_pretty_log_tail(LOG_TAIL_LINES),
+    );
+    write_session(&dir, "");
+    let _ = CRASH_DIR.set(dir.clone());
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        PANICKED.store(true, Ordering::SeqCst);
+        write_panic_report(info);
+        previous(info);
+    }));
+
+    #[cfg(unix)]
+    signals::install(&dir);
+}
+
+/// Record the selected model for future reports.
+pub fn set_model(model: &str) {
+    let mut current = MODEL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if *current == model {
+        return;
+    }
+    *current = model.to_string();
+    drop(current);
+    if let Some(dir) = CRASH_DIR.get() {
+        write_session(dir, model);
+    }
+}
+
+/// Crash reports on disk, newest first.
+#[tauri::command]
+pub fn list_crash_reports() -> Vec<CrashReportSummary> {
+    CRASH_DIR.get().map(|dir| list_in(dir)).unwrap_or_default()
+}
+
+/// Full text of one report, so the user can review it before sharing.
+#[tauri::command]
+pub fn read_crash_report(id: String) -> Result<String, String> {
+    let dir = CRASH_DIR
+        .get()
+        .ok_or_else(|| "Crash reports are not available.".to_string())?;
+    if !is_report_id(&id) {
+        return Err("Unknown crash report.".to_string());
+    }
+    std::fs::read_to_string(dir.join(&id)).map_err(|_| "Unknown crash report.".to_string())
+}
+
+/// Copy a report to a user-chosen file for sharing. Refuses unless the user
+/// has reviewed it and consented, since it includes the log tail.
+#[tauri::command]
+pub fn export_crash_report(id: String, path: String, consent: bool) -> Result<(), String> {
+    if !consent {
+        return Err("Sharing a crash report needs your consent.".to_string());
+    }
+    let report = read_crash_report(id)?;
+    std::fs::write(&path, report).map_err(|e| format!("Failed to export crash report: {}", e))?;
+    tracing::info!(target: "system", "crash report exported");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_round_trip_through_their_summary() {
+        let report = render_report(
+            "panic",
+            1_700_000_000_123,
+            &context_lines("base.en"),
+            "index out of bounds",
+            "location: src/audio.rs:12\n",
+            "INFO system: app setup",
+        );
+        assert!(report.contains("model: base.en\n"));
+        assert!(report.contains("-- last 200 log lines --\nINFO system: app setup"));
+        assert_eq!(
+            parse_summary("crash-1700000000123-panic.txt", &report),
+            Some(CrashReportSummary {
+                id: "crash-1700000000123-panic.txt".to_string(),
+                kind: "panic".to_string(),
+                created_at_ms: 1_700_000_000_123,
+                message: "index out of bounds".to_string(),
+            })
+        );
+        assert_eq!(parse_summary("x", "not a report"), None);
+    }
+
+    #[test]
+    fn report_ids_cannot_escape_the_crash_directory() {
+        assert!(is_report_id("crash-1700000000123-signal.txt"));
+        assert!(!is_report_id("../crash-1.txt"));
+        assert!(!is_report_id("crash-1/../../etc.txt"));
+        assert!(!is_report_id("session.txt"));
+        assert!(!is_report_id("crash-1.wav"));
+    }
+
+    #[test]
+    fn signal_marker_becomes_a_report_with_the_previous_session_context() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(SESSION_FILE),
+            "app_version: 9.9.9\nmodel: large-v3-turbo\nos_version: macOS 15.1\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join(SIGNAL_MARKER), "11").unwrap();
+
+        let id = collect_signal_marker(dir.path(), "last line").unwrap();
+        assert!(!dir.path().join(SIGNAL_MARKER).exists());
+        let report = std::fs::read_to_string(dir.path().join(&id)).unwrap();
+        assert!(report.contains("model: large-v3-turbo\n"));
+        assert!(report.contains("message: SIGSEGV (signal 11)\n"));
+
+        let listed = list_in(dir.path());
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].kind, "signal");
+        assert_eq!(collect_signal_marker(dir.path(), ""), None);
+    }
+
+    #[test]
+    fn only_the_newest_reports_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        for ms in 0..5u64 {
+            write_report(dir.path(), "panic", 1_700_000_000_000 + ms, "kind: panic\n");
+        }
+        prune_reports(dir.path(), 3);
+        assert_eq!(
+            report_ids(dir.path()),
+            vec![
+                "crash-1700000000004-panic.txt",
+                "crash-1700000000003-panic.txt",
+                "crash-1700000000002-panic.txt",
+            ]
+        );
+    }
+}
@@ -0,0 +1,483 @@
+//! Cursor-context delivery: fit a dictation to the text already before the
+//! caret, so consecutive dictations join up without manual fixes.
+//!
+//! `read_caret` reads at most `MAX_CONTEXT_UNITS` UTF-16 units before the
+//! insertion point of the focused element, and `MAX_AFTER_UNITS` after it,
+//! through the Accessibility API (macOS only). `fit` then adds or drops the
+//! leading space and capitalizes the first letter after sentence-ending
+//! punctuation, and the text after the caret decides the smart trailing
+//! space. Any doubt (no AX answer, a secure field, another platform) reads
+//! as `None`, which leaves the text unchanged and trails a smart suffix with
+//! a space.
+//!
+//! Privacy: the text read here is never logged or sent anywhere; it only
+//! decides the first and last characters of the paste.
+
+use crate::state::TrailingBehavior;
+
+/// Characters that open a phrase: the dictation attaches to them directly.
+const OPENING: &[char] = &['(', '[', '{', '“', '‘', '¿', '¡'];
+/// Characters a dictation may start with that attach to the previous word.
+const ATTACHING: &[char] = &[
+    '.', ',', ';', ':', '!', '?', ')', ']', '}', '”', '’', '…', '%',
+];
+/// Closers allowed between a sentence end and the caret (`."`, `?)`).
+const CLOSERS: &[char] = &['"', '\'', '”', '’', ')', ']'];
+const SENTENCE_ENDS: &[char] = &['.', '!', '?', '…'];
+
+/// The text around the caret a paste is delivered to. A part is `None` when
+/// it wasn't asked for or couldn't be read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Caret {
+    /// Text just before the caret, for `fit`.
+    pub before: Option<String>,
+    /// Text just after the caret (after the selection, which the paste
+    /// replaces), for `TrailingBehavior::Smart`.
+    pub after: Option<String>,
+}
+
+impl Caret {
+    /// `text` as it reaches the clipboard: fitted to the text before the
+    /// caret and followed by `trailing`'s suffix.
+    pub fn deliver(&self, text: &str, trailing: TrailingBehavior) -> String {
+        format!(
+            "{}{}",
+            fit(text, self.before.as_deref()),
+            trailing.suffix(self.after.as_deref())
+        )
+    }
+}
+
+/// Whether `after`, the text following the caret, already separates the
+/// paste from what comes next: it starts with whitespace or with punctuation
+/// that attaches to the previous word. The end of a field and an unreadable
+/// caret don't.
+pub(crate) fn separates_next(after: Option<&str>) -> bool {
+    after
+        .and_then(|after| after.chars().next())
+        .is_some_and(|next| next.is_whitespace() || ATTACHING.contains(&next))
+}
+
+/// Fit `text` to `before`, the text just before the caret (possibly cut to
+/// its last few characters). `None` means the caret couldn't be read and
+/// returns `text` unchanged; `Some("")` is the start of an empty field.
+pub(crate) fn fit(text: &str, before: Option<&str>) -> String {
+    let Some(before) = before else {
+        return text.to_string();
+    };
+    let body = text.trim_start_matches(' ');
+    let Some(first) = body.chars().next() else {
+        return text.to_string();
+    };
+    let body = if starts_sentence(before) {
+        capitalize_first(body)
+    } else {
+        body.to_string()
+    };
+    if needs_space(before, first) {
+        format!(" {}", body)
+    } else {
+        body
+    }
+}
+
+/// A space goes between the caret and the dictation unless the caret already
+/// follows whitespace or an opener, or the dictation starts with punctuation
+/// that attaches to the previous word.
+fn needs_space(before: &str, first: char) -> bool {
+    let mut tail = before.chars().rev();
+    let Some(last) = tail.next() else {
+        return false;
+    };
+    if last.is_whitespace() || OPENING.contains(&last) {
+        return false;
+    }
+    // A straight quote after whitespace (or at the start) opens a quotation.
+    if (last == '"' || last == '\'') && tail.next().is_none_or(char::is_whitespace) {
+        return false;
+    }
+    !(first.is_whitespace() || ATTACHING.contains(&first))
+}
+
+/// Whether the caret is where a new sentence begins: at the start of the
+/// field, after only whitespace, or after sentence-ending punctuation.
+fn starts_sentence(before: &str) -> bool {
+    let trimmed = before.trim_end();
+    if trimmed.is_empty() {
+        return true;
+    }
+    if before[trimmed.len()..].contains('\n') {
+        return true;
+    }
+    trimmed
+        .trim_end_matches(CLOSERS)
+        .chars()
+        .last()
+        .is_some_and(|c| SENTENCE_ENDS.contains(&c))
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if first.is_lowercase() => first.to_uppercase().chain(chars).collect(),
+        _ => text.to_string(),
+    }
+}
+
+/// The text before and/or after the caret in the focused element, leaving
+/// out what can't be read safely. Call on the main thread, like the paste
+/// guard.
+#[cfg(target_os = "macos")]
+pub(crate) fn read_caret(before: bool, after: bool) -> Caret {
+    if !before && !after {
+        return Caret::default();
+    }
+    match native::read_caret(before, after) {
+        Ok(caret) => caret,
+        Err(reason) => {
+            tracing::info!(target: "pipeline", "cursor context unavailable: {}", reason);
+            Caret::default()
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn read_caret(_before: bool, _after: bool) -> Caret {
+    Caret::default()
+}
+
+#[cfg(target_os = "macos")]
+mod native {
+    use objc2_app_kit::NSWorkspace;
+    use std::ffi::{c_char, c_void, CStr, CString};
+
+    type AXUIElementRef = *const c_void;
+    type CFTypeRef = *const c_void;
+    type CFIndex = isize;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateApplication(pid: i32) -> AXUIElementRef;
+        fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFTypeRef,
+            value: *mut CFTypeRef,
+        ) -> i32;
+        fn AXUIElementCopyParameterizedAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFTypeRef,
+            parameter: CFTypeRef,
+            value: *mut CFTypeRef,
+        ) -> i32;
+        fn AXUIElementSetMessagingTimeout(element: AXUIElementRef, timeout: f32) -> i32;
+        fn AXValueCreate(value_type: u32, value_ptr: *const c_void) -> CFTypeRef;
+        fn AXValueGetType(value: CFTypeRef) -> u32;
+        fn AXValueGetValue(value: CFTypeRef, value_type: u32, value_ptr: *mut c_void) -> bool;
+        fn CFStringCreateWithCString(
+            allocator: CFTypeRef,
+            string: *const c_char,
+            encoding: u32,
+        ) -> CFTypeRef;
+        fn CFStringGetLength(string: CFTypeRef) -> CFIndex;
+        fn CFStringGetMaximumSizeForEncoding(length: CFIndex, encoding: u32) -> CFIndex;
+        fn CFStringGetCString(
+            string: CFTypeRef,
+            buffer: *mut c_char,
+            buffer_size: CFIndex,
+            encoding: u32,
+        ) -> bool;
+        fn CFRelease(value: CFTypeRef);
+    }
+
+    const AX_SUCCESS: i32 = 0;
+    const AX_QUERY_TIMEOUT_SECONDS: f32 = 0.025;
+    const UTF8_ENCODING: u32 = 0x0800_0100;
+    // kAXValueCFRangeType from the AXValueType enum (HIServices AXValue.h).
+    const AX_VALUE_CFRANGE_TYPE: u32 = 4;
+    /// UTF-16 units read before the caret: enough for `."  ` and an opener.
+    const MAX_CONTEXT_UNITS: CFIndex = 8;
+    /// UTF-16 units read after the caret: only the next character matters.
+    const MAX_AFTER_UNITS: CFIndex = 2;
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy)]
+    struct CFRange {
+        location: CFIndex,
+        length: CFIndex,
+    }
+
+    /// Releases a Copy/Create-rule CF object on drop.
+    struct CFGuard(CFTypeRef);
+    impl Drop for CFGuard {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                unsafe { CFRelease(self.0) };
+            }
+        }
+    }
+
+    fn cfstring(s: &str) -> Result<CFGuard, String> {
+        let c = CString::new(s).map_err(|_| "attribute name contains NUL".to_string())?;
+        let raw = unsafe { CFStringCreateWithCString(std::ptr::null(), c.as_ptr(), UTF8_ENCODING) };
+        if raw.is_null() {
+            return Err(format!("could not create {} attribute", s));
+        }
+        Ok(CFGuard(raw))
+    }
+
+    fn cfstring_to_string(value: CFTypeRef) -> Result<String, String> {
+        let length = unsafe { CFStringGetLength(value) };
+        let max_size = unsafe { CFStringGetMaximumSizeForEncoding(length, UTF8_ENCODING) };
+        if max_size <= 0 {
+            return Ok(String::new());
+        }
+        let mut buffer = vec![0 as c_char; (max_size + 1) as usize];
+        let converted = unsafe {
+            CFStringGetCString(
+                value,
+                buffer.as_mut_ptr(),
+                buffer.len() as CFIndex,
+                UTF8_ENCODING,
+            )
+        };
+        if !converted {
+            return Err("could not convert AX string to UTF-8".to_string());
+        }
+        Ok(unsafe { CStr::from_ptr(buffer.as_ptr()) }
+            .to_string_lossy()
+            .into_owned())
+    }
+
+    fn set_timeout(element: AXUIElementRef) -> Result<(), String> {
+        let status = unsafe { AXUIElementSetMessagingTimeout(element, AX_QUERY_TIMEOUT_SECONDS) };
+        if status != AX_SUCCESS {
+            return Err(format!("AX timeout configuration returned {}", status));
+        }
+        Ok(())
+    }
+
+    fn copy_attribute(element: AXUIElementRef, name: &str) -> Result<CFGuard, String> {
+        let attr = cfstring(name)?;
+        let mut value: CFTypeRef = std::ptr::null();
+        let status = unsafe { AXUIElementCopyAttributeValue(element, attr.0, &mut value) };
+        let value = CFGuard(value);
+        if status != AX_SUCCESS || value.0.is_null() {
+            return Err(format!("AX {} query returned {}", name, status));
+        }
+        Ok(value)
+    }
+
+    fn selected_range(element: AXUIElementRef) -> Result<CFRange, String> {
+        let value = copy_attribute(element, "AXSelectedTextRange")?;
+        if unsafe { AXValueGetType(value.0) } != AX_VALUE_CFRANGE_TYPE {
+            return Err("AXSelectedTextRange is not a range".to_string());
+        }
+        let mut range = CFRange {
+            location: 0,
+            length: 0,
+        };
+        let ok = unsafe {
+            AXValueGetValue(
+                value.0,
+                AX_VALUE_CFRANGE_TYPE,
+                &mut range as *mut CFRange as *mut c_void,
+            )
+        };
+        if !ok || range.location < 0 || range.length < 0 {
+            return Err("AXSelectedTextRange could not be decoded".to_string());
+        }
+        Ok(range)
+    }
+
+    /// `AXStringForRange`, or a slice of `AXValue` for elements (often web
+    /// views) that don't implement the parameterized attribute.
+    fn string_for_range(element: AXUIElementRef, range: CFRange) -> Result<String, String> {
+        let parameter = CFGuard(unsafe {
+            AXValueCreate(
+                AX_VALUE_CFRANGE_TYPE,
+                &range as *const CFRange as *const c_void,
+            )
+        });
+        if !parameter.0.is_null() {
+            let attr = cfstring("AXStringForRange")?;
+            let mut value: CFTypeRef = std::ptr::null();
+            let status = unsafe {
+                AXUIElementCopyParameterizedAttributeValue(element, attr.0, parameter.0, &mut value)
+            };
+            let value = CFGuard(value);
+            if status == AX_SUCCESS && !value.0.is_null() {
+                return cfstring_to_string(value.0);
+            }
+        }
+        let whole = cfstring_to_string(copy_attribute(element, "AXValue")?.0)?;
+        let units: Vec<u16> = whole.encode_utf16().collect();
+        let start = range.location as usize;
+        let end = (start + range.length as usize).min(units.len());
+        units
+            .get(start..end)
+            .map(String::from_utf16_lossy)
+            .ok_or_else(|| "caret is past the end of AXValue".to_string())
+    }
+
+    fn text_before(element: AXUIElementRef, location: CFIndex) -> Result<String, String> {
+        if location == 0 {
+            return Ok(String::new());
+        }
+        let start = location.saturating_sub(MAX_CONTEXT_UNITS);
+        string_for_range(
+            element,
+            CFRange {
+                location: start,
+                length: location - start,
+            },
+        )
+    }
+
+    /// One side of the caret that failed to read is left out on its own.
+    fn logged(side: &str, read: Result<String, String>) -> Option<String> {
+        read.map_err(|reason| {
+            tracing::info!(target: "pipeline", "text {} caret unavailable: {}", side, reason)
+        })
+        .ok()
+    }
+
+    pub(super) fn read_caret(before: bool, after: bool) -> Result<super::Caret, String> {
+        let frontmost = NSWorkspace::sharedWorkspace()
+            .frontmostApplication()
+            .ok_or_else(|| "no frontmost application".to_string())?;
+        let app = CFGuard(unsafe { AXUIElementCreateApplication(frontmost.processIdentifier()) });
+        if app.0.is_null() {
+            return Err("could not create frontmost AX application".to_string());
+        }
+        set_timeout(app.0)?;
+        let focused = copy_attribute(app.0, "AXFocusedUIElement")?;
+        set_timeout(focused.0)?;
+
+        // Never read from a password field, whatever the caret says.
+        let role = cfstring_to_string(copy_attribute(focused.0, "AXRole")?.0)?;
+        let subrole = copy_attribute(focused.0, "AXSubrole")
+            .and_then(|value| cfstring_to_string(value.0))
+            .unwrap_or_default();
+        if role == "AXSecureTextField" || subrole == "AXSecureTextField" {
+            return Err("focused element is a secure text field".to_string());
+        }
+
+        let selected = selected_range(focused.0)?;
+        let before = before
+            .then(|| logged("before", text_before(focused.0, selected.location)))
+            .flatten();
+        // The paste replaces the selection, so what follows it comes next.
+        let after = after
+            .then(|| {
+                let location = selected.location + selected.length;
+                let range = CFRange {
+                    location,
+                    length: MAX_AFTER_UNITS,
+                };
+                logged("after", string_for_range(focused.0, range))
+            })
+            .flatten();
+        Ok(super::Caret { before, after })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreadable_caret_leaves_text_unchanged() {
+        assert_eq!(fit(" hello there", None), " hello there");
+    }
+
+    #[test]
+    fn adds_a_space_after_a_word_and_drops_one_after_whitespace() {
+        assert_eq!(fit("and then", Some("I went")), " and then");
+        assert_eq!(fit(" and then", Some("I went ")), "and then");
+        assert_eq!(fit("and then", Some("line one\n")), "And then");
+    }
+
+    #[test]
+    fn capitalizes_after_sentence_ending_punctuation() {
+        assert_eq!(fit("then we left.", Some("It rained. ")), "Then we left.");
+        assert_eq!(fit("then we left.", Some("It rained.")), " Then we left.");
+        assert_eq!(fit("really", Some("he said \"no.\"")), " Really");
+        assert_eq!(fit("and then", Some("we left,")), " and then");
+    }
+
+    #[test]
+    fn start_of_field_is_a_new_sentence_without_a_space() {
+        assert_eq!(fit(" hello", Some("")), "Hello");
+        assert_eq!(fit("hello", Some("   ")), "Hello");
+    }
+
+    #[test]
+    fn attaching_punctuation_and_openers_take_no_space() {
+        assert_eq!(fit(", and more", Some("this")), ", and more");
+        assert_eq!(fit("aside", Some("(")), "aside");
+        assert_eq!(fit("quoted", Some("said \"")), "quoted");
+        assert_eq!(fit("word", Some("end\"")), " word");
+    }
+
+    #[test]
+    fn leading_newlines_are_kept() {
+        assert_eq!(fit("\nnext line", Some("text")), "\nnext line");
+    }
+
+    fn caret_before(after: Option<&str>) -> Caret {
+        Caret {
+            before: None,
+            after: after.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn fixed_trailing_modes_ignore_the_text_after_the_caret() {
+        let caret = caret_before(Some(" rest"));
+        assert_eq!(caret.deliver("hello", TrailingBehavior::None), "hello");
+        assert_eq!(caret.deliver("hello", TrailingBehavior::Space), "hello ");
+        assert_eq!(caret.deliver("hello", TrailingBehavior::Newline), "hello\n");
+    }
+
+    #[test]
+    fn smart_trailing_spaces_unless_the_next_character_separates() {
+        let smart = TrailingBehavior::Smart;
+        assert_eq!(
+            caret_before(Some("world")).deliver("hello", smart),
+            "hello "
+        );
+        assert_eq!(
+            caret_before(Some(", world")).deliver("hello", smart),
+            "hello"
+        );
+        assert_eq!(caret_before(Some(".")).deliver("hello", smart), "hello");
+        assert_eq!(
+            caret_before(Some(" world")).deliver("hello", smart),
+            "hello"
+        );
+        assert_eq!(
+            caret_before(Some("\nnext")).deliver("hello", smart),
+            "hello"
+        );
+        assert_eq!(
+            caret_before(Some("(aside)")).deliver("hello", smart),
+            "hello "
+        );
+    }
+
+    #[test]
+    fn smart_trailing_spaces_at_the_end_of_a_field_or_an_unread_caret() {
+        let smart = TrailingBehavior::Smart;
+        assert_eq!(caret_before(Some("")).deliver("hello", smart), "hello ");
+        assert_eq!(caret_before(None).deliver("hello", smart), "hello ");
+    }
+
+    #[test]
+    fn delivery_fits_the_start_and_trails_the_end() {
+        let caret = Caret {
+            before: Some("It rained.".to_string()),
+            after: Some("!".to_string()),
+        };
+        assert_eq!(caret.deliver("then", TrailingBehavior::Smart), " Then");
+    }
+}
@@ -7,8 +7,18 @@
 
 use crate::cli_command::CliFormattingMode;
 use crate::correction::CorrectionMatcher;
+use crate::dictation_macros::DictationMacrosSettings;
 use crate::ide_context::IdeContextIndex;
-use crate::state::{AppProfile, DictationState, WritingStyle};
+use crate::redaction::Redactor;
+use crate::replacement_rules::ReplacementRules;
+use crate::rewrite_templates::RewriteTemplate;
+use crate::state::{
+    AppProfile, DictationProfile, DictationState, InjectionMethod, NotesTarget, SelectionDictation,
+    TrailingBehavior, WritingStyle,
+};
+use crate::system_status::PowerPressure;
+use crate::transcriber::whisper::WhisperSampling;
+use crate::transcriber::WhisperDecoding;
 use crate::voice_commands::ResolvedVoiceCommand;
 use std::sync::Arc;
 
@@ -27,6 +37,9 @@ pub struct MatchedAppProfile {
     pub smart_formatting_override: Option<bool>,
     pub writing_style: Option<WritingStyle>,
     pub ide_context_enabled: bool,
+    pub injection_method_override: Option<InjectionMethod>,
+    pub trailing_override: Option<TrailingBehavior>,
+    pub model_override: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -63,15 +76,76 @@ pub struct ContextCapturePermissions {
     /// Permission to use the configured local project roots. This never grants
     /// screen, selection, or clipboard reads.
     pub local_project_index: bool,
+    /// Context biasing may read the focused window's title into Whisper's
+    /// prompt.
+    pub prompt_window_title: bool,
+    /// Context biasing may read the selection into Whisper's prompt. Unlike
+    /// `selected_text`, the selection itself is never delivered or rewritten.
+    pub prompt_selected_text: bool,
+}
+
+/// Model heavy enough to be worth swapping out under thermal or Low Power
+/// Mode pressure.
+pub const ADAPTIVE_DOWNGRADE_MODEL: &str = "large-v3-turbo";
+
+/// A per-recording swap to the adaptive fallback model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelDowngrade {
+    /// The model this recording would otherwise have used.
+    pub from_model: String,
+    pub reason: PowerPressure,
+}
+
+/// The realtime-factor budget this recording's decode is measured against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyBudget {
+    pub max_rtf: f32,
+    /// A faster model may replace the chosen one, because this recording
+    /// used it rather than a profile's or session's model.
+    pub auto_switch: bool,
+}
+
+/// Background re-transcription of a dual-model draft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refinement {
+    /// The model the settings and profiles chose.
+    pub model_name: String,
+    /// Replace the pasted draft with the refined text.
+    pub replace_draft: bool,
+}
+
+/// A second decode for transcripts the first pass was unsure of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfidenceRetry {
+    /// Retry when the first pass's confidence score is below this.
+    pub threshold: f32,
+    pub model_name: String,
+    pub whisper_decoding: WhisperDecoding,
+    pub budget_ms: u64,
 }
 
 #[derive(Debug, Clone)]
 pub struct TranscriptionSettings {
     pub model_name: String,
+    /// Set when `model_name` is the adaptive fallback rather than the model
+    /// the settings and profiles chose.
+    pub model_downgrade: Option<ModelDowngrade>,
+    /// Dual-model mode: `model_name` is the fast draft model and this is how
+    /// the audio is re-transcribed in the background.
+    pub refinement: Option<Refinement>,
+    /// Set when unsure transcripts are decoded again before delivery.
+    pub confidence_retry: Option<ConfidenceRetry>,
+    /// Set when the decode's speed is tracked against `latencyBudget`.
+    pub latency_budget: Option<LatencyBudget>,
+    /// Extra hypotheses decoded after delivery for the alternative picker;
+    /// 0 when off.
+    pub alternatives: u32,
     pub language: String,
     pub vad_sensitivity: u32,
     pub prompt: Option<String>,
     pub smart_punctuation: bool,
+    pub whisper_decoding: WhisperDecoding,
+    pub hallucination_filter: bool,
 }
 
 #[derive(Clone)]
@@ -87,15 +161,44 @@ pub struct TransformationSettings {
     pub smart_formatting_enabled: bool,
     pub ide_context_enabled: bool,
     pub ide_context_index: Option<Arc<IdeContextIndex>>,
+    /// Compiled redaction filter; `None` when redaction is off.
+    pub redactor: Option<Arc<Redactor>>,
+    /// Enabled replacement rules, compiled in order; `None` when there are
+    /// none.
+    pub replacement_rules: Option<Arc<ReplacementRules>>,
+    /// Templates a "format as <name>" prefix can select.
+    pub rewrite_templates: Vec<RewriteTemplate>,
+    /// The session's own template, applied when no prefix selects one.
+    pub rewrite_template: Option<RewriteTemplate>,
 }
 
 #[derive(Debug, Clone)]
 pub struct DeliverySettings {
     pub auto_paste: bool,
+    /// The active app is on the do-not-inject blocklist. `auto_paste` is
+    /// already forced off; the flag lets the pipeline tell the user why.
+    pub injection_blocked: bool,
     pub paste_delay_ms: u64,
+    pub injection_method: InjectionMethod,
+    pub trailing: TrailingBehavior,
     pub save_transcript: bool,
     pub save_audio: bool,
     pub output_dir: String,
+    /// Append mode: buffer the take for `commit_append_buffer` instead of
+    /// injecting it.
+    pub append: bool,
+    /// Preview mode: hold the text for Accept or Discard instead of
+    /// delivering it.
+    pub preview: bool,
+    /// Fit the paste to the text before the caret (`cursor_context.rs`).
+    pub cursor_context: bool,
+    /// Notes target: save the text to today's note instead of delivering it.
+    pub notes: NotesTarget,
+    /// Replace or rewrite the selection read when the recording started.
+    pub selection: SelectionDictation,
+    /// Phrases that run an action instead of delivering; `None` when macros
+    /// are off or none are defined.
+    pub macros: Option<DictationMacrosSettings>,
 }
 
 #[derive(Clone)]
@@ -115,15 +218,34 @@ pub struct DictationContextSnapshot {
     pub writing_style: WritingStyle,
 }
 
-/// Ephemeral overrides supplied by the recording trigger. No caller supplies
-/// them today, but keeping them explicit makes precedence testable and avoids a
-/// second resolution path when session-specific behavior is introduced.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Ephemeral overrides supplied by the recording trigger — today, the dictation
+/// profile whose hotkey started the recording. They sit above app profiles in
+/// the precedence chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct SessionOverrides {
     pub auto_paste: Option<bool>,
     pub cleanup_enabled: Option<bool>,
     pub cli_formatting_enabled: Option<bool>,
     pub smart_formatting_enabled: Option<bool>,
+    /// Falls through to the app profile / global model when not installed.
+    pub model_name: Option<String>,
+    pub language: Option<String>,
+    /// Rewrite template id; an id with no matching template is ignored.
+    pub rewrite_template: Option<String>,
+}
+
+impl SessionOverrides {
+    pub fn from_dictation_profile(profile: &DictationProfile) -> Self {
+        Self {
+            auto_paste: profile.auto_paste_override,
+            cleanup_enabled: profile.cleanup_override,
+            cli_formatting_enabled: None,
+            smart_formatting_enabled: profile.smart_formatting_override,
+            model_name: profile.model_override.clone(),
+            language: profile.language_override.clone(),
+            rewrite_template: profile.rewrite_template.clone(),
+        }
+    }
 }
 
 pub struct ResolverInputs<'a> {
@@ -131,12 +253,20 @@ pub struct ResolverInputs<'a> {
     pub global: &'a DictationState,
     pub prompt: Option<String>,
     pub correction_matcher: Option<Arc<CorrectionMatcher>>,
+    pub redactor: Option<Arc<Redactor>>,
+    /// Compiled from `global.replacement_rules` when the list last changed.
+    pub replacement_rules: Option<Arc<ReplacementRules>>,
     pub ide_context_index: Option<Arc<IdeContextIndex>>,
     pub vocabulary_version: u64,
     /// Repository-backed commands already filtered for the active app. `None`
     /// preserves legacy in-memory pairs when the local store is unavailable.
     pub voice_commands: Option<Vec<ResolvedVoiceCommand>>,
     pub session_overrides: SessionOverrides,
+    /// Install check for a profile's model override. Injected so resolution
+    /// stays free of filesystem access in tests.
+    pub model_installed: fn(&str) -> bool,
+    /// Thermal / Low Power Mode pressure at recording start.
+    pub power_pressure: Option<PowerPressure>,
 }
 
 /// Resolve global defaults -> matching app profiles -> one-session overrides.
@@ -161,14 +291,23 @@ pub fn resolve(inputs: ResolverInputs<'_>) -> DictationContextSnapshot {
         })
         .unwrap_or(WritingStyle::Inherit);
     let style = StylePolicy::for_style(writing_style);
-    let auto_paste = inputs.session_overrides.auto_paste.unwrap_or_else(|| {
-        resolve_profile_override(
-            global.auto_paste,
-            inputs.bundle_id,
-            &global.app_profiles,
-            |profile| profile.auto_paste_override,
-        )
+    // The blocklist is a safety boundary, not a preference: it sits above
+    // profile and session overrides rather than in the precedence chain.
+    let injection_blocked = inputs.bundle_id.is_some_and(|bundle_id| {
+        global
+            .inject_blocklist
+            .iter()
+            .any(|blocked| blocked == bundle_id)
     });
+    let auto_paste = !injection_blocked
+        && inputs.session_overrides.auto_paste.unwrap_or_else(|| {
+            resolve_profile_override(
+                global.auto_paste,
+                inputs.bundle_id,
+                &global.app_profiles,
+                |profile| profile.auto_paste_override,
+            )
+        });
     let cleanup_enabled = inputs.session_overrides.cleanup_enabled.unwrap_or_else(|| {
         resolve_profile_override(
             style.cleanup_enabled.unwrap_or(global.cleanup_enabled),
@@ -177,6 +316,98 @@ pub fn resolve(inputs: ResolverInputs<'_>) -> DictationContextSnapshot {
             |profile| profile.cleanup_override,
         )
     });
+    let injection_method = injection_method_for(global, inputs.bundle_id);
+    let trailing = resolve_profile_optional(inputs.bundle_id, &global.app_profiles, |profile| {
+        profile.trailing_override
+    })
+    .unwrap_or(global.trailing_behavior);
+    // Model overrides are owned strings, so they bypass the `Copy` helpers but
+    // keep the same first-supplying-duplicate-wins rule among installed
+    // models. A model that is not installed falls through to the next
+    // duplicate, then the global model, rather than failing the recording.
+    let model_name = inputs
+        .session_overrides
+        .model_name
+        .as_deref()
+        .filter(|model| (inputs.model_installed)(model))
+        .or_else(|| {
+            inputs.bundle_id.and_then(|bundle_id| {
+                global
+                    .app_profiles
+                    .iter()
+                    .filter(|profile| profile.bundle_id == bundle_id)
+                    .filter_map(|profile| profile.model_override.as_deref())
+                    .find(|model| (inputs.model_installed)(model))
+            })
+        })
+        .unwrap_or(global.model_name.as_str())
+        .to_string();
+    let adaptive = &global.adaptive_model;
+    let (model_name, model_downgrade) = match inputs.power_pressure {
+        Some(reason)
+            if adaptive.enabled
+                && model_name == ADAPTIVE_DOWNGRADE_MODEL
+                && adaptive.fallback_model != model_name
+                && (inputs.model_installed)(&adaptive.fallback_model) =>
+        {
+            (
+                adaptive.fallback_model.clone(),
+                Some(ModelDowngrade {
+                    from_model: model_name,
+                    reason,
+                }),
+            )
+        }
+        _ => (model_name, None),
+    };
+    let dual = &global.dual_model;
+    let (model_name, refinement) = if dual.enabled
+        && dual.draft_model != model_name
+        && (inputs.model_installed)(&dual.draft_model)
+    {
+        (
+            dual.draft_model.clone(),
+            Some(Refinement {
+                model_name,
+                replace_draft: dual.replace_draft,
+            }),
+        )
+    } else {
+        (model_name, None)
+    };
+    // A larger retry model is skipped in dual-model mode, where the refinement
+    // already re-runs the chosen model; beam search on the draft still applies.
+    let retry = &global.confidence_retry;
+    let larger_model = retry.model.as_ref().filter(|model| {
+        refinement.is_none() && **model != model_name && (inputs.model_installed)(model)
+    });
+    let confidence_retry = match larger_model {
+        _ if !retry.enabled => None,
+        Some(model) => Some((model.clone(), global.whisper_decoding)),
+        None if global.whisper_decoding.sampling == WhisperSampling::Greedy => Some((
+            model_name.clone(),
+            WhisperDecoding {
+                sampling: WhisperSampling::BeamSearch,
+                ..global.whisper_decoding
+            },
+        )),
+        // Already beam search on the same model: nothing stronger to try.
+        None => None,
+    }
+    .map(|(model_name, whisper_decoding)| ConfidenceRetry {
+        threshold: retry.threshold,
+        model_name,
+        whisper_decoding,
+        budget_ms: retry.budget_ms,
+    });
+    // A draft is meant to be fast and a downgrade is already a fallback, so
+    // neither is measured against the budget.
+    let budget = &global.latency_budget;
+    let latency_budget = (budget.enabled && model_downgrade.is_none() && refinement.is_none())
+        .then(|| LatencyBudget {
+            max_rtf: budget.max_rtf,
+            auto_switch: budget.auto_switch && model_name == global.model_name,
+        });
     let cli_override = inputs.session_overrides.cli_formatting_enabled.or_else(|| {
         resolve_profile_optional(inputs.bundle_id, &global.app_profiles, |profile| {
             profile.cli_formatting_override
@@ -214,6 +445,9 @@ pub fn resolve(inputs: ResolverInputs<'_>) -> DictationContextSnapshot {
         smart_formatting_override: profile.smart_formatting_override,
         writing_style: profile.writing_style,
         ide_context_enabled: profile.ide_context_enabled,
+        injection_method_override: profile.injection_method_override,
+        trailing_override: profile.trailing_override,
+        model_override: profile.model_override.clone(),
     });
     let teaching_project_root = explicit_profile
         .filter(|profile| profile.ide_context_enabled && profile.ide_project_roots.len() == 1)
@@ -254,6 +488,7 @@ pub fn resolve(inputs: ResolverInputs<'_>) -> DictationContextSnapshot {
             .iter()
             .any(|command| command.allow_clipboard_read);
     let custom_voice_commands = voice_commands && !resolved_voice_commands.is_empty();
+    let biasing_sources = global.context_biasing.sources_for(inputs.bundle_id);
 
     DictationContextSnapshot {
         app: ActiveAppIdentity {
@@ -262,11 +497,27 @@ pub fn resolve(inputs: ResolverInputs<'_>) -> DictationContextSnapshot {
         matched_profile,
         teaching_project_root,
         transcription: TranscriptionSettings {
-            model_name: global.model_name.clone(),
-            language: global.language.clone(),
+            model_name,
+            model_downgrade,
+            refinement,
+            confidence_retry,
+            latency_budget,
+            // A refinement replaces the text the spans would index into.
+            alternatives: if global.alternatives.enabled && refinement.is_none() {
+                global.alternatives.count
+            } else {
+                0
+            },
+            language: inputs
+                .session_overrides
+                .language
+                .clone()
+                .unwrap_or_else(|| global.language.clone()),
             vad_sensitivity: global.vad_sensitivity,
             prompt: inputs.prompt,
             smart_punctuation: global.smart_punctuation,
+            whisper_decoding: global.whisper_decoding,
+            hallucination_filter: global.hallucination_filter,
         },
         transformations: TransformationSettings {
             cleanup_enabled,
@@ -290,13 +541,37 @@ pub fn resolve(inputs: ResolverInputs<'_>) -> DictationContextSnapshot {
             } else {
                 None
             },
+            redactor: inputs.redactor,
+            replacement_rules: inputs.replacement_rules,
+            rewrite_templates: global.rewrite_templates.clone(),
+            rewrite_template: inputs
+                .session_overrides
+                .rewrite_template
+                .as_deref()
+                .and_then(|id| {
+                    global
+                        .rewrite_templates
+                        .iter()
+                        .find(|template| template.id == id)
+                })
+                .cloned(),
         },
         delivery: DeliverySettings {
             auto_paste,
+            injection_blocked,
             paste_delay_ms: global.auto_paste_delay_ms,
+            injection_method,
+            trailing,
             save_transcript: global.save_transcript,
             save_audio: global.save_audio,
             output_dir: global.output_dir.clone(),
+            append: global.append_mode,
+            preview: global.preview_before_paste,
+            cursor_context: global.cursor_context,
+            notes: global.notes_target,
+            selection: global.selection_dictation,
+            macros: (global.dictation_macros.enabled && !global.dictation_macros.macros.is_empty())
+                .then(|| global.dictation_macros.clone()),
         },
         vocabulary: VocabularyIdentity {
             source,
@@ -307,10 +582,15 @@ pub fn resolve(inputs: ResolverInputs<'_>) -> DictationContextSnapshot {
             custom_voice_commands,
         },
         // Clipboard input is granted only when an applicable command explicitly
-        // opts in; selected/screen text remain denied. Project indexing is separate.
+        // opts in, selected text only by `selection_dictation`, and prompt
+        // context only by context biasing in an allowlisted app; screen text
+        // remains denied. Project indexing is separate.
         context_capture: ContextCapturePermissions {
+            selected_text: global.selection_dictation != SelectionDictation::Off,
             clipboard: clipboard_read_allowed,
             local_project_index: ide_context_enabled,
+            prompt_window_title: biasing_sources.window_title,
+            prompt_selected_text: biasing_sources.selected_text,
             ..ContextCapturePermissions::default()
         },
         writing_style,
@@ -390,6 +670,20 @@ impl StylePolicy {
     }
 }
 
+/// The injection method for `bundle_id`: its app override, else the global
+/// `legacy_automation` choice. Shared with deliveries that don't go through
+/// `resolve`, like copied-audio transcription.
+pub fn injection_method_for(global: &DictationState, bundle_id: Option<&str>) -> InjectionMethod {
+    resolve_profile_optional(bundle_id, &global.app_profiles, |profile| {
+        profile.injection_method_override
+    })
+    .unwrap_or(if global.legacy_automation {
+        InjectionMethod::SystemEvents
+    } else {
+        InjectionMethod::Native
+    })
+}
+
 fn resolve_profile_optional<T: Copy>(
     bundle_id: Option<&str>,
     profiles: &[AppProfile],
@@ -463,6 +757,9 @@ mod tests {
             writing_style: None,
             ide_context_enabled: false,
             ide_project_roots: Vec::new(),
+            injection_method_override: None,
+            trailing_override: None,
+            model_override: None,
         }
     }
 
@@ -476,10 +773,14 @@ mod tests {
             global,
             prompt: None,
             correction_matcher: None,
+            redactor: None,
+            replacement_rules: None,
             ide_context_index: None,
             vocabulary_version: 7,
             voice_commands: None,
             session_overrides,
+            model_installed: |model| model != "medium.en",
+            power_pressure: None,
         })
     }
 
@@ -580,6 +881,380 @@ mod tests {
         assert_eq!(snapshot.matched_profile.unwrap().label, "first match");
     }
 
+    #[test]
+    fn delivery_and_model_overrides_follow_matching_profile() {
+        let mut global = DictationState {
+            model_name: "base.en".to_string(),
+            ..DictationState::default()
+        };
+        let mut mail = profile("com.apple.mail", None, None);
+        mail.injection_method_override = Some(InjectionMethod::SystemEvents);
+        mail.trailing_override = Some(TrailingBehavior::Newline);
+        mail.model_override = Some("large-v3-turbo".to_string());
+        global.app_profiles = vec![mail];
+
+        let matched = resolve_test(&global, Some("com.apple.mail"), SessionOverrides::default());
+        assert_eq!(
+            matched.delivery.injection_method,
+            InjectionMethod::SystemEvents
+        );
+        assert_eq!(matched.delivery.trailing, TrailingBehavior::Newline);
+        assert_eq!(matched.transcription.model_name, "large-v3-turbo");
+
+        let unmatched = resolve_test(
+            &global,
+            Some("com.tinyspeck.slackmacgap"),
+            SessionOverrides::default(),
+        );
+        assert_eq!(unmatched.delivery.injection_method, InjectionMethod::Native);
+        assert_eq!(unmatched.delivery.trailing, TrailingBehavior::None);
+        assert_eq!(unmatched.transcription.model_name, "base.en");
+
+        // Legacy automation changes only the default; the profile still wins.
+        global.legacy_automation = true;
+        let legacy = resolve_test(
+            &global,
+            Some("com.tinyspeck.slackmacgap"),
+            SessionOverrides::default(),
+        );
+        assert_eq!(
+            legacy.delivery.injection_method,
+            InjectionMethod::SystemEvents
+        );
+        global.app_profiles[0].injection_method_override = Some(InjectionMethod::Native);
+        let matched = resolve_test(&global, Some("com.apple.mail"), SessionOverrides::default());
+        assert_eq!(matched.delivery.injection_method, InjectionMethod::Native);
+    }
+
+    #[test]
+    fn trailing_behavior_defaults_to_global_and_a_profile_overrides_it() {
+        let mut global = DictationState {
+            trailing_behavior: TrailingBehavior::Smart,
+            ..DictationState::default()
+        };
+        let mut terminal = profile("com.apple.Terminal", None, None);
+        terminal.trailing_override = Some(TrailingBehavior::None);
+        global.app_profiles = vec![terminal];
+
+        let elsewhere = resolve_test(&global, Some("com.apple.mail"), SessionOverrides::default());
+        assert_eq!(elsewhere.delivery.trailing, TrailingBehavior::Smart);
+        let terminal = resolve_test(
+            &global,
+            Some("com.apple.Terminal"),
+            SessionOverrides::default(),
+        );
+        assert_eq!(terminal.delivery.trailing, TrailingBehavior::None);
+    }
+
+    #[test]
+    fn uninstalled_model_override_falls_through_to_next_duplicate_then_global() {
+        let mut global = DictationState {
+            model_name: "base.en".to_string(),
+            ..DictationState::default()
+        };
+        let mut missing = profile("com.apple.mail", None, None);
+        missing.model_override = Some("medium.en".to_string());
+        let mut installed = profile("com.apple.mail", None, None);
+        installed.model_override = Some("small.en".to_string());
+        global.app_profiles = vec![missing.clone(), installed];
+
+        let snapshot = resolve_test(&global, Some("com.apple.mail"), SessionOverrides::default());
+        assert_eq!(snapshot.transcription.model_name, "small.en");
+
+        global.app_profiles = vec![missing];
+        let snapshot = resolve_test(&global, Some("com.apple.mail"), SessionOverrides::default());
+        assert_eq!(snapshot.transcription.model_name, "base.en");
+    }
+
+    #[test]
+    fn adaptive_fallback_replaces_large_model_only_under_pressure() {
+        let mut global = DictationState {
+            model_name: ADAPTIVE_DOWNGRADE_MODEL.to_string(),
+            ..DictationState::default()
+        };
+        global.adaptive_model.enabled = true;
+        global.adaptive_model.fallback_model = "small.en".to_string();
+        let resolve_with = |global: &DictationState, pressure| {
+            resolve(ResolverInputs {
+                bundle_id: None,
+                global,
+                prompt: None,
+                correction_matcher: None,
+                redactor: None,
+                replacement_rules: None,
+                ide_context_index: None,
+                vocabulary_version: 0,
+                voice_commands: None,
+                session_overrides: SessionOverrides::default(),
+                model_installed: |model| model != "medium.en",
+                power_pressure: pressure,
+            })
+        };
+
+        let calm = resolve_with(&global, None);
+        assert_eq!(calm.transcription.model_name, ADAPTIVE_DOWNGRADE_MODEL);
+        assert_eq!(calm.transcription.model_downgrade, None);
+
+        let hot = resolve_with(&global, Some(PowerPressure::Thermal));
+        assert_eq!(hot.transcription.model_name, "small.en");
+        assert_eq!(
+            hot.transcription.model_downgrade,
+            Some(ModelDowngrade {
+                from_model: ADAPTIVE_DOWNGRADE_MODEL.to_string(),
+                reason: PowerPressure::Thermal,
+            })
+        );
+
+        global.adaptive_model.fallback_model = "medium.en".to_string();
+        let missing = resolve_with(&global, Some(PowerPressure::LowPowerMode));
+        assert_eq!(missing.transcription.model_name, ADAPTIVE_DOWNGRADE_MODEL);
+
+        global.adaptive_model.fallback_model = "small.en".to_string();
+        global.adaptive_model.enabled = false;
+        let disabled = resolve_with(&global, Some(PowerPressure::Thermal));
+        assert_eq!(disabled.transcription.model_name, ADAPTIVE_DOWNGRADE_MODEL);
+
+        global.adaptive_model.enabled = true;
+        global.model_name = "base.en".to_string();
+        let light = resolve_with(&global, Some(PowerPressure::Thermal));
+        assert_eq!(light.transcription.model_name, "base.en");
+    }
+
+    #[test]
+    fn dual_model_drafts_with_the_fast_model_and_refines_with_the_chosen_one() {
+        let mut global = DictationState {
+            model_name: "small.en".to_string(),
+            ..DictationState::default()
+        };
+        let off = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(off.transcription.model_name, "small.en");
+        assert_eq!(off.transcription.refinement, None);
+
+        global.dual_model.enabled = true;
+        let on = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(on.transcription.model_name, "tiny.en");
+        assert_eq!(
+            on.transcription.refinement,
+            Some(Refinement {
+                model_name: "small.en".to_string(),
+                replace_draft: false,
+            })
+        );
+
+        global.dual_model.draft_model = "medium.en".to_string();
+        let missing = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(missing.transcription.model_name, "small.en");
+        assert_eq!(missing.transcription.refinement, None);
+
+        global.dual_model.draft_model = "small.en".to_string();
+        let same = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(same.transcription.refinement, None);
+    }
+
+    #[test]
+    fn confidence_retry_prefers_an_installed_larger_model_then_beam_search() {
+        let mut global = DictationState {
+            model_name: "base.en".to_string(),
+            ..DictationState::default()
+        };
+        let off = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(off.transcription.confidence_retry, None);
+
+        global.confidence_retry.enabled = true;
+        global.confidence_retry.model = Some("small.en".to_string());
+        let larger = resolve_test(&global, None, SessionOverrides::default())
+            .transcription
+            .confidence_retry
+            .expect("retry resolved");
+        assert_eq!(larger.model_name, "small.en");
+        assert_eq!(larger.whisper_decoding.sampling, WhisperSampling::Greedy);
+
+        global.confidence_retry.model = Some("medium.en".to_string());
+        let missing = resolve_test(&global, None, SessionOverrides::default())
+            .transcription
+            .confidence_retry
+            .expect("retry resolved");
+        assert_eq!(missing.model_name, "base.en");
+        assert_eq!(
+            missing.whisper_decoding.sampling,
+            WhisperSampling::BeamSearch
+        );
+
+        global.whisper_decoding.sampling = WhisperSampling::BeamSearch;
+        let nothing_stronger = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(nothing_stronger.transcription.confidence_retry, None);
+    }
+
+    #[test]
+    fn latency_budget_switches_only_the_chosen_model() {
+        let mut global = DictationState {
+            model_name: "small.en".to_string(),
+            ..DictationState::default()
+        };
+        let off = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(off.transcription.latency_budget, None);
+
+        global.latency_budget.enabled = true;
+        global.latency_budget.auto_switch = true;
+        let on = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(
+            on.transcription.latency_budget,
+            Some(LatencyBudget {
+                max_rtf: 1.0,
+                auto_switch: true,
+            })
+        );
+
+        let session = resolve_test(
+            &global,
+            None,
+            SessionOverrides {
+                model_name: Some("base.en".to_string()),
+                ..SessionOverrides::default()
+            },
+        );
+        assert_eq!(
+            session
+                .transcription
+                .latency_budget
+                .map(|budget| budget.auto_switch),
+            Some(false)
+        );
+
+        global.dual_model.enabled = true;
+        let dual = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(dual.transcription.latency_budget, None);
+    }
+
+    #[test]
+    fn alternatives_are_off_for_refined_drafts() {
+        let mut global = DictationState {
+            model_name: "small.en".to_string(),
+            ..DictationState::default()
+        };
+        global.alternatives.enabled = true;
+        let on = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(on.transcription.alternatives, 3);
+
+        global.dual_model.enabled = true;
+        let dual = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(dual.transcription.alternatives, 0);
+    }
+
+    #[test]
+    fn dictation_profile_overrides_model_and_language_above_app_profiles() {
+        let mut global = DictationState {
+            model_name: "base.en".to_string(),
+            language: "en".to_string(),
+            ..DictationState::default()
+        };
+        let mut mail = profile("com.apple.mail", None, None);
+        mail.model_override = Some("small.en".to_string());
+        global.app_profiles = vec![mail];
+        let spanish = DictationProfile {
+            id: "spanish".to_string(),
+            name: "Spanish".to_string(),
+            model_override: Some("large-v3-turbo".to_string()),
+            language_override: Some("es".to_string()),
+            auto_paste_override: Some(false),
+            cleanup_override: None,
+            smart_formatting_override: None,
+            rewrite_template: None,
+        };
+
+        let snapshot = resolve_test(
+            &global,
+            Some("com.apple.mail"),
+            SessionOverrides::from_dictation_profile(&spanish),
+        );
+        assert_eq!(snapshot.transcription.model_name, "large-v3-turbo");
+        assert_eq!(snapshot.transcription.language, "es");
+        assert!(!snapshot.delivery.auto_paste);
+
+        let uninstalled = DictationProfile {
+            model_override: Some("medium.en".to_string()),
+            language_override: None,
+            ..spanish
+        };
+        let snapshot = resolve_test(
+            &global,
+            Some("com.apple.mail"),
+            SessionOverrides::from_dictation_profile(&uninstalled),
+        );
+        assert_eq!(snapshot.transcription.model_name, "small.en");
+        assert_eq!(snapshot.transcription.language, "en");
+    }
+
+    #[test]
+    fn session_rewrite_template_resolves_by_id() {
+        let email = RewriteTemplate {
+            id: "email".to_string(),
+            name: "Email".to_string(),
+            instruction: "Rewrite as an email.".to_string(),
+        };
+        let global = DictationState {
+            rewrite_templates: vec![email.clone()],
+            ..DictationState::default()
+        };
+        let with_template = |id: &str| SessionOverrides {
+            rewrite_template: Some(id.to_string()),
+            ..SessionOverrides::default()
+        };
+
+        let snapshot = resolve_test(&global, None, with_template("email"));
+        assert_eq!(
+            snapshot.transformations.rewrite_template,
+            Some(email.clone())
+        );
+        assert_eq!(snapshot.transformations.rewrite_templates, vec![email]);
+        let snapshot = resolve_test(&global, None, with_template("deleted"));
+        assert_eq!(snapshot.transformations.rewrite_template, None);
+        let snapshot = resolve_test(&global, None, SessionOverrides::default());
+        assert_eq!(snapshot.transformations.rewrite_template, None);
+    }
+
+    #[test]
+    fn blocklisted_app_never_auto_pastes_even_with_overrides() {
+        let mut global = DictationState {
+            auto_paste: true,
+            ..DictationState::default()
+        };
+        global.inject_blocklist = vec!["com.apple.Terminal".to_string()];
+        global.app_profiles = vec![profile("com.apple.Terminal", Some(true), None)];
+
+        let snapshot = resolve_test(
+            &global,
+            Some("com.apple.Terminal"),
+            SessionOverrides {
+                auto_paste: Some(true),
+                ..SessionOverrides::default()
+            },
+        );
+        assert!(snapshot.delivery.injection_blocked);
+        assert!(!snapshot.delivery.auto_paste);
+
+        for bundle_id in [None, Some("com.apple.mail")] {
+            let snapshot = resolve_test(&global, bundle_id, SessionOverrides::default());
+            assert!(!snapshot.delivery.injection_blocked);
+            assert!(snapshot.delivery.auto_paste);
+        }
+    }
+
+    #[test]
+    fn default_blocklist_covers_password_managers() {
+        let global = DictationState {
+            auto_paste: true,
+            ..DictationState::default()
+        };
+        let snapshot = resolve_test(
+            &global,
+            Some("com.1password.1password"),
+            SessionOverrides::default(),
+        );
+        assert!(snapshot.delivery.injection_blocked);
+        assert!(!snapshot.delivery.auto_paste);
+    }
+
     #[test]
     fn resolved_snapshot_does_not_follow_later_settings_changes() {
         let mut global = DictationState {
@@ -694,6 +1369,45 @@ mod tests {
         assert!(snapshot.delivery.auto_paste);
     }
 
+    #[test]
+    fn context_biasing_grants_prompt_reads_only_in_allowlisted_apps() {
+        let global = DictationState {
+            context_biasing: crate::context_biasing::ContextBiasingSettings {
+                enabled: true,
+                selected_text: true,
+                apps: vec!["com.apple.mail".to_string()],
+                ..Default::default()
+            },
+            ..DictationState::default()
+        };
+
+        let mail = resolve_test(&global, Some("com.apple.mail"), SessionOverrides::default());
+        assert!(mail.context_capture.prompt_window_title);
+        assert!(mail.context_capture.prompt_selected_text);
+        assert!(!mail.context_capture.selected_text);
+        assert!(!mail.context_capture.surrounding_screen_text);
+
+        let other = resolve_test(
+            &global,
+            Some("com.apple.Safari"),
+            SessionOverrides::default(),
+        );
+        assert_eq!(other.context_capture, ContextCapturePermissions::default());
+    }
+
+    #[test]
+    fn selection_dictation_is_the_only_grant_for_selected_text() {
+        let global = DictationState {
+            selection_dictation: SelectionDictation::Rewrite,
+            ..DictationState::default()
+        };
+        let snapshot = resolve_test(&global, None, SessionOverrides::default());
+
+        assert!(snapshot.context_capture.selected_text);
+        assert!(!snapshot.context_capture.surrounding_screen_text);
+        assert_eq!(snapshot.delivery.selection, SelectionDictation::Rewrite);
+    }
+
     #[test]
     fn ide_context_requires_explicit_matching_profile_and_bypasses_prose() {
         let mut global = DictationState {
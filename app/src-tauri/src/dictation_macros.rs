@@ -0,0 +1,341 @@
+//! Dictation macros: spoken phrases that run an app action instead of being
+//! pasted ("open history", "switch to large model", "disable auto paste",
+//! "switch to coding").
+//!
+//! Opt-in (`dictationMacros`). A take matches only when the whole utterance is
+//! a macro phrase, compared without case, punctuation, or hyphens, so dictating a
+//! sentence that merely contains one still pastes. The match runs on the raw
+//! transcript before any transform, and a matched take delivers nothing. With
+//! `confirm` on (the default) a native Run / Cancel alert asks first.
+//!
+//! Settings are owned by the frontend, so actions that change one (model,
+//! auto-paste, a configuration preset) or move the main window's view are
+//! carried out there from the `dictation-macro` event, like the tray's quick
+//! toggles. The backend shows the main window and copies the last transcript
+//! itself. Phrases are never logged; only the action kind is.
+
+use crate::transform_presets::normalize;
+use crate::State;
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+
+pub const DICTATION_MACRO: &str = "dictation-macro";
+
+/// Most macros kept; extra entries are dropped on load.
+pub const MAX_MACROS: usize = 50;
+const MAX_PHRASE_CHARS: usize = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MacroAction {
+    OpenHistory,
+    OpenSettings,
+    CopyLastTranscription,
+    SetAutoPaste { enabled: bool },
+    SetModel { model: String },
+    /// Apply a configuration preset, by id.
+    ApplyPreset {
+        preset: String,
+    },
+}
+
+impl MacroAction {
+    /// Stable name for logs.
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::OpenHistory => "open_history",
+            Self::OpenSettings => "open_settings",
+            Self::CopyLastTranscription => "copy_last_transcription",
+            Self::SetAutoPaste { .. } => "set_auto_paste",
+            Self::SetModel { .. } => "set_model",
+            Self::ApplyPreset { .. } => "apply_preset",
+        }
+    }
+
+    /// What the confirmation alert asks to do.
+    fn describe(&self) -> String {
+        match self {
+            Self::OpenHistory => "Open dictation history".to_string(),
+            Self::OpenSettings => "Open Settings".to_string(),
+            Self::CopyLastTranscription => "Copy the last transcription".to_string(),
+            Self::SetAutoPaste { enabled: true } => "Turn on auto-paste".to_string(),
+            Self::SetAutoPaste { enabled: false } => "Turn off auto-paste".to_string(),
+            Self::SetModel { model } => format!("Switch the model to {model}"),
+            Self::ApplyPreset { preset } => format!(
+                "Switch to the {} preset",
+                crate::config_presets::name_of(preset).unwrap_or_else(|| preset.clone())
+            ),
+        }
+    }
+
+    fn shows_main_window(&self) -> bool {
+        matches!(self, Self::OpenHistory | Self::OpenSettings)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationMacro {
+    pub id: String,
+    pub phrase: String,
+    pub action: MacroAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationMacrosSettings {
+    pub enabled: bool,
+    /// Ask before running a matched macro.
+    pub confirm: bool,
+    pub macros: Vec<DictationMacro>,
+}
+
+impl Default for DictationMacrosSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            confirm: true,
+            macros: Vec::new(),
+        }
+    }
+}
+
+/// Payload of `dictation-macro`, sent once a macro's action runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationMacroRan {
+    pub recording_id: u64,
+    pub id: String,
+    pub action: MacroAction,
+}
+
+/// Comparison key for a phrase: Whisper writes "auto-paste" as often as
+/// "auto paste", so hyphens count as spaces.
+fn phrase_key(s: &str) -> String {
+    normalize(&s.replace('-', " "))
+}
+
+/// Parse the `dictationMacros` settings object. Invalid macros, blank or
+/// over-long phrases, and phrases an earlier macro already claimed are
+/// skipped.
+pub fn parse_settings(value: &serde_json::Value) -> DictationMacrosSettings {
+    let defaults = DictationMacrosSettings::default();
+    let mut macros: Vec<DictationMacro> = Vec::new();
+    for found in value
+        .get("macros")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|value| serde_json::from_value::<DictationMacro>(value.clone()).ok())
+    {
+        let phrase = found.phrase.trim();
+        if macros.len() == MAX_MACROS {
+            break;
+        }
+        if found.id.trim().is_empty()
+            || phrase_key(phrase).is_empty()
+            || phrase.chars().count() > MAX_PHRASE_CHARS
+            || macros
+                .iter()
+                .any(|existing| phrase_key(&existing.phrase) == phrase_key(phrase))
+        {
+            continue;
+        }
+        if matches!(&found.action, MacroAction::SetModel { model } if model.trim().is_empty())
+            || matches!(&found.action, MacroAction::ApplyPreset { preset } if preset.trim().is_empty())
+        {
+            continue;
+        }
+        macros.push(DictationMacro {
+            phrase: phrase.to_string(),
+            ..found
+        });
+    }
+    DictationMacrosSettings {
+        enabled: value
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.enabled),
+        confirm: value
+            .get("confirm")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(defaults.confirm),
+        macros,
+    }
+}
+
+/// The macro whose phrase is the whole of `text`, ignoring case, punctuation,
+/// and hyphens.
+pub fn match_phrase<'a>(text: &str, macros: &'a [DictationMacro]) -> Option<&'a DictationMacro> {
+    let spoken = phrase_key(text);
+    if spoken.is_empty() {
+        return None;
+    }
+    macros
+        .iter()
+        .find(|found| phrase_key(&found.phrase) == spoken)
+}
+
+/// Run `found`, after a Run / Cancel alert when `confirm` is set. Returns at
+/// once; the alert answers on its own.
+pub fn dispatch(app: &tauri::AppHandle, recording_id: u64, found: DictationMacro, confirm: bool) {
+    tracing::info!(
+        target: "pipeline",
+        recording_id,
+        action = found.action.kind(),
+        confirm,
+        "dictation macro matched"
+    );
+    if !confirm {
+        run(app, recording_id, &found);
+        return;
+    }
+    let handle = app.clone();
+    app.dialog()
+        .message(format!("{}?", found.action.describe()))
+        .title(format!("Run \"{}\"", found.phrase))
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Run".to_string(),
+            "Cancel".to_string(),
+        ))
+        .show(move |confirmed| {
+            if confirmed {
+                run(&handle, recording_id, &found);
+            } else {
+                tracing::info!(
+                    target: "pipeline",
+                    recording_id,
+                    action = found.action.kind(),
+                    "dictation macro cancelled"
+                );
+            }
+        });
+}
+
+fn run(app: &tauri::AppHandle, recording_id: u64, found: &DictationMacro) {
+    if found.action.shows_main_window() {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+    if found.action == MacroAction::CopyLastTranscription {
+        if let Err(e) = crate::commands::recording::copy_last_transcription(app.state::<State>()) {
+            tracing::warn!(target: "pipeline", "dictation macro copy failed: {}", e);
+        }
+    }
+    tracing::info!(
+        target: "pipeline",
+        recording_id,
+        action = found.action.kind(),
+        "dictation macro ran"
+    );
+    let _ = app.emit(
+        DICTATION_MACRO,
+        DictationMacroRan {
+            recording_id,
+            id: found.id.clone(),
+            action: found.action.clone(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictation_macro(id: &str, phrase: &str, action: MacroAction) -> DictationMacro {
+        DictationMacro {
+            id: id.to_string(),
+            phrase: phrase.to_string(),
+            action,
+        }
+    }
+
+    #[test]
+    fn parse_keeps_valid_macros_and_defaults_to_confirming() {
+        let settings = parse_settings(&serde_json::json!({
+            "enabled": true,
+            "macros": [
+                {"id": "history", "phrase": " Open history ", "action": {"type": "open_history"}},
+                {"id": "large", "phrase": "switch to large model", "action": {"type": "set_model", "model": "large-v3-turbo"}},
+                {"id": "dup", "phrase": "open, HISTORY", "action": {"type": "open_settings"}},
+                {"id": "blank", "phrase": " ... ", "action": {"type": "open_settings"}},
+                {"id": "unknown", "phrase": "reboot", "action": {"type": "reboot"}},
+                {"id": "no-model", "phrase": "switch model", "action": {"type": "set_model", "model": " "}},
+                {"id": "coding", "phrase": "switch to coding", "action": {"type": "apply_preset", "preset": "preset-coding"}},
+                {"id": "no-preset", "phrase": "switch preset", "action": {"type": "apply_preset", "preset": ""}},
+                {"id": "", "phrase": "no id", "action": {"type": "open_settings"}}
+            ]
+        }));
+        assert!(settings.enabled);
+        assert!(settings.confirm);
+        assert_eq!(
+            settings.macros,
+            vec![
+                dictation_macro("history", "Open history", MacroAction::OpenHistory),
+                dictation_macro(
+                    "large",
+                    "switch to large model",
+                    MacroAction::SetModel {
+                        model: "large-v3-turbo".to_string()
+                    }
+                ),
+                dictation_macro(
+                    "coding",
+                    "switch to coding",
+                    MacroAction::ApplyPreset {
+                        preset: "preset-coding".to_string()
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            parse_settings(&serde_json::json!("nope")),
+            DictationMacrosSettings::default()
+        );
+    }
+
+    #[test]
+    fn only_a_whole_utterance_matches_ignoring_case_and_punctuation() {
+        let macros = vec![
+            dictation_macro("history", "open history", MacroAction::OpenHistory),
+            dictation_macro(
+                "paste-off",
+                "disable auto paste",
+                MacroAction::SetAutoPaste { enabled: false },
+            ),
+        ];
+        assert_eq!(
+            match_phrase("Open history.", &macros).map(|found| found.id.as_str()),
+            Some("history")
+        );
+        assert_eq!(
+            match_phrase("  Disable auto-paste! ", &macros).map(|found| found.id.as_str()),
+            Some("paste-off")
+        );
+        assert!(match_phrase("please open history for me", &macros).is_none());
+        assert!(match_phrase("...", &macros).is_none());
+    }
+
+    #[test]
+    fn action_payload_is_tagged_by_type() {
+        let payload = serde_json::to_value(DictationMacroRan {
+            recording_id: 4,
+            id: "large".to_string(),
+            action: MacroAction::SetModel {
+                model: "large-v3-turbo".to_string(),
+            },
+        })
+        .unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "recordingId": 4,
+                "id": "large",
+                "action": {"type": "set_model", "model": "large-v3-turbo"}
+            })
+        );
+    }
+}
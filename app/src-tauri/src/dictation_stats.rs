@@ -0,0 +1,444 @@
+//! Content-free usage log behind the daily and weekly dictation statistics.
+//!
+//! Each completed dictation appends one JSON line to `dictation-stats.jsonl`
+//! in the app data directory: when it finished, how many words it produced,
+//! how long the audio was, which model ran, and, for Whisper, how many tokens
+//! it decoded and how long that took. No text is stored, so the log
+//! is independent of history encryption and of the 50-entry history cap.
+//! `get_dictation_stats` folds it into totals, time saved against a typing
+//! baseline, busiest hours, and a per-model split with decode throughput, so
+//! every view of the numbers agrees.
+
+use crate::MutexExt;
+use chrono::{DateTime, Duration, Local, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+const STATS_FILE: &str = "dictation-stats.jsonl";
+/// Records older than this are dropped at launch.
+const RETENTION_DAYS: i64 = 400;
+/// Typing speed assumed when the caller doesn't pass one.
+pub const DEFAULT_TYPING_WPM: u32 = 40;
+const TYPING_WPM_RANGE: std::ops::RangeInclusive<u32> = 10..=200;
+const BUSIEST_HOURS: usize = 3;
+
+static STATS_PATH: OnceLock<PathBuf> = OnceLock::new();
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DictationRecord {
+    finished_at_ms: i64,
+    words: u64,
+    audio_ms: u64,
+    model: String,
+    /// Text tokens decoded; absent for backends that don't report them and
+    /// on records written before they were logged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tokens: Option<u64>,
+    #[serde(default)]
+    decode_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsRange {
+    /// Since local midnight.
+    Day,
+    /// The last seven local calendar days, including today.
+    Week,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayUsage {
+    /// Local `YYYY-MM-DD`.
+    pub date: String,
+    pub dictations: u64,
+    pub words: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelUsage {
+    pub model: String,
+    pub dictations: u64,
+    pub words: u64,
+    /// Fraction of the range's words, 0–1.
+    pub share: f64,
+    /// Text tokens decoded, from dictations that reported them.
+    pub tokens: u64,
+    /// Tokens decoded per second of decode time. None when no dictation in
+    /// the range reported tokens.
+    pub tokens_per_sec: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationStats {
+    pub range: StatsRange,
+    pub dictations: u64,
+    pub words: u64,
+    /// Text tokens decoded, from dictations that reported them.
+    pub tokens: u64,
+    pub audio_secs: f64,
+    pub typing_wpm: u32,
+    /// Time typing the same words would have taken, minus the time spent
+    /// speaking them. Never negative.
+    pub time_saved_secs: f64,
+    /// Every day in the range, oldest first, including empty ones.
+    pub days: Vec<DayUsage>,
+    /// Words per local hour of day, index 0 = midnight.
+    pub hourly_words: Vec<u64>,
+    /// Up to three hours with the most words, busiest first.
+    pub busiest_hours: Vec<u8>,
+    /// Models by words dictated, most first.
+    pub models: Vec<ModelUsage>,
+}
+
+fn read_records(path: &Path) -> Vec<DictationRecord> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    // A line torn by a crash mid-append is skipped rather than failing the log.
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn append_in(path: &Path, record: &DictationRecord) -> Result<(), String> {
+    let mut line =
+        serde_json::to_string(record).map_err(|e| format!("Failed to encode stats: {}", e))?;
+    line.push('\n');
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let _guard = WRITE_LOCK.lock_or_recover();
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| format!("Failed to write dictation stats: {}", e))
+}
+
+/// Rewrite the log without records older than the retention window.
+fn prune_in(path: &Path, now_ms: i64) -> Result<(), String> {
+    let _guard = WRITE_LOCK.lock_or_recover();
+    let records = read_records(path);
+    let cutoff = now_ms - RETENTION_DAYS * 24 * 60 * 60 * 1000;
+    if records.iter().all(|record| record.finished_at_ms >= cutoff) {
+        return Ok(());
+    }
+    let mut out = String::new();
+    for record in records
+        .iter()
+        .filter(|record| record.finished_at_ms >= cutoff)
+    {
+        out.push_str(
+            &serde_json::to_string(record).map_err(|e| format!("Failed to encode stats: {}", e))?,
+        );
+        out.push('\n');
+    }
+    let temp = path.with_extension("jsonl.tmp");
+    std::fs::write(&temp, out).map_err(|e| format!("Failed to prune dictation stats: {}", e))?;
+    std::fs::rename(&temp, path).map_err(|e| format!("Failed to prune dictation stats: {}", e))
+}
+
+/// Decode throughput in tokens per second, or None without a measured decode.
+pub fn tokens_per_sec(tokens: u64, decode_ms: u64) -> Option<f64> {
+    (decode_ms > 0).then(|| tokens as f64 * 1000.0 / decode_ms as f64)
+}
+
+#[derive(Default)]
+struct ModelTotals {
+    dictations: u64,
+    words: u64,
+    tokens: u64,
+    /// Decode time of the dictations that reported tokens.
+    token_decode_ms: u64,
+}
+
+/// Fold `records` into stats for `range` ending at `now`, in `now`'s zone.
+fn summarize<Tz: TimeZone>(
+    records: &[DictationRecord],
+    range: StatsRange,
+    now: DateTime<Tz>,
+    typing_wpm: u32,
+) -> DictationStats {
+    let zone = now.timezone();
+    let today = now.date_naive();
+    let first_day = match range {
+        StatsRange::Day => today,
+        StatsRange::Week => today - Duration::days(6),
+    };
+    let mut days: Vec<DayUsage> = first_day
+        .iter_days()
+        .take_while(|day| *day <= today)
+        .map(|day| DayUsage {
+            date: day.format("%Y-%m-%d").to_string(),
+            dictations: 0,
+            words: 0,
+        })
+        .collect();
+    let mut hourly_words = vec![0u64; 24];
+    let mut by_model: HashMap<&str, ModelTotals> = HashMap::new();
+    let (mut dictations, mut words, mut tokens, mut audio_ms) = (0u64, 0u64, 0u64, 0u64);
+
+    for record in records {
+        let Some(local) = zone.timestamp_millis_opt(record.finished_at_ms).single() else {
+            continue;
+        };
+        let day = local.date_naive();
+        if day < first_day || day > today {
+            continue;
+        }
+        let index = (day - first_day).num_days() as usize;
+        days[index].dictations += 1;
+        days[index].words += record.words;
+        hourly_words[local.hour() as usize] += record.words;
+        let model = by_model.entry(record.model.as_str()).or_default();
+        model.dictations += 1;
+        model.words += record.words;
+        if let Some(record_tokens) = record.tokens {
+            model.tokens += record_tokens;
+            model.token_decode_ms += record.decode_ms;
+            tokens += record_tokens;
+        }
+        dictations += 1;
+        words += record.words;
+        audio_ms += record.audio_ms;
+    }
+
+    let mut busiest: Vec<u8> = (0..24u8)
+        .filter(|hour| hourly_words[*hour as usize] > 0)
+        .collect();
+    busiest.sort_by_key(|hour| std::cmp::Reverse(hourly_words[*hour as usize]));
+    busiest.truncate(BUSIEST_HOURS);
+
+    let mut models: Vec<ModelUsage> = by_model
+        .into_iter()
+        .map(|(model, totals)| ModelUsage {
+            model: model.to_string(),
+            dictations: totals.dictations,
+            words: totals.words,
+            share: if words == 0 {
+                0.0
+            } else {
+                totals.words as f64 / words as f64
+            },
+            tokens: totals.tokens,
+            tokens_per_sec: tokens_per_sec(totals.tokens, totals.token_decode_ms),
+        })
+        .collect();
+    models.sort_by(|a, b| b.words.cmp(&a.words).then_with(|| a.model.cmp(&b.model)));
+
+    let audio_secs = audio_ms as f64 / 1000.0;
+    let typing_secs = words as f64 / typing_wpm as f64 * 60.0;
+    DictationStats {
+        range,
+        dictations,
+        words,
+        tokens,
+        audio_secs,
+        typing_wpm,
+        time_saved_secs: (typing_secs - audio_secs).max(0.0),
+        days,
+        hourly_words,
+        busiest_hours: busiest,
+        models,
+    }
+}
+
+/// Set the log location and drop expired records. Call once from setup.
+pub fn init(app_data_dir: &Path) {
+    let path = app_data_dir.join(STATS_FILE);
+    if let Err(e) = prune_in(&path, Local::now().timestamp_millis()) {
+        tracing::warn!(target: "system", "dictation stats prune failed: {}", e);
+    }
+    let _ = STATS_PATH.set(path);
+}
+
+/// Log one delivered dictation. `tokens` is what the decoder reported, if
+/// anything. Failures are logged and otherwise ignored.
+pub fn record(words: usize, audio_ms: u64, model: &str, tokens: Option<u64>, decode_ms: u64) {
+    let Some(path) = STATS_PATH.get() else {
+        return;
+    };
+    let record = DictationRecord {
+        finished_at_ms: Local::now().timestamp_millis(),
+        words: words as u64,
+        audio_ms,
+        model: model.to_string(),
+        tokens,
+        decode_ms,
+    };
+    if let Err(e) = append_in(path, &record) {
+        tracing::warn!(target: "system", "dictation stats not recorded: {}", e);
+    }
+}
+
+/// Usage for today or the last seven days. `typing_wpm` is the baseline for
+/// time saved; it defaults to 40 and is clamped to 10–200.
+#[tauri::command]
+pub fn get_dictation_stats(range: StatsRange, typing_wpm: Option<u32>) -> DictationStats {
+    let records = STATS_PATH
+        .get()
+        .map(|path| read_records(path))
+        .unwrap_or_default();
+    let typing_wpm = typing_wpm
+        .unwrap_or(DEFAULT_TYPING_WPM)
+        .clamp(*TYPING_WPM_RANGE.start(), *TYPING_WPM_RANGE.end());
+    summarize(&records, range, Local::now(), typing_wpm)
+}
+
+/// Delete the usage log, for "Reset Stats".
+#[tauri::command]
+pub fn clear_dictation_stats() -> Result<(), String> {
+    let Some(path) = STATS_PATH.get() else {
+        return Ok(());
+    };
+    let _guard = WRITE_LOCK.lock_or_recover();
+    match std::fs::remove_file(path) {
+        Ok(()) => {
+            tracing::info!(target: "system", "dictation stats cleared");
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to clear dictation stats: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn at(zone: &FixedOffset, day: u32, hour: u32) -> i64 {
+        zone.with_ymd_and_hms(2026, 10, day, hour, 15, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn record(finished_at_ms: i64, words: u64, audio_ms: u64, model: &str) -> DictationRecord {
+        DictationRecord {
+            finished_at_ms,
+            words,
+            audio_ms,
+            model: model.to_string(),
+            tokens: None,
+            decode_ms: 0,
+        }
+    }
+
+    #[test]
+    fn week_stats_bucket_by_local_day_and_hour() {
+        let zone = FixedOffset::west_opt(7 * 3600).unwrap();
+        let now = zone.with_ymd_and_hms(2026, 10, 16, 18, 0, 0).unwrap();
+        let records = vec![
+            record(at(&zone, 9, 9), 500, 60_000, "base.en"), // outside the week
+            record(at(&zone, 10, 9), 120, 40_000, "base.en"),
+            record(at(&zone, 16, 9), 80, 30_000, "large-v3-turbo"),
+            record(at(&zone, 16, 14), 200, 60_000, "base.en"),
+        ];
+
+        let week = summarize(&records, StatsRange::Week, now, 40);
+        assert_eq!(week.dictations, 3);
+        assert_eq!(week.words, 400);
+        assert_eq!(week.days.len(), 7);
+        assert_eq!(week.days[0].date, "2026-10-10");
+        assert_eq!(week.days[0].words, 120);
+        assert_eq!(week.days[6].words, 280);
+        assert_eq!(week.hourly_words[9], 200);
+        assert_eq!(week.busiest_hours, vec![9, 14]);
+        assert_eq!(week.models[0].model, "base.en");
+        assert!((week.models[0].share - 0.8).abs() < 1e-9);
+        // 400 words at 40 WPM is 600 s of typing against 130 s of speech.
+        assert!((week.time_saved_secs - 470.0).abs() < 1e-9);
+
+        let day = summarize(&records, StatsRange::Day, now, 40);
+        assert_eq!(day.dictations, 2);
+        assert_eq!(day.days.len(), 1);
+        assert_eq!(day.busiest_hours, vec![14, 9]);
+    }
+
+    #[test]
+    fn model_throughput_counts_only_dictations_with_tokens() {
+        let zone = FixedOffset::east_opt(0).unwrap();
+        let now = zone.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let decoded = |tokens, decode_ms| DictationRecord {
+            tokens: Some(tokens),
+            decode_ms,
+            ..record(at(&zone, 16, 10), 30, 10_000, "base.en")
+        };
+        let records = vec![
+            decoded(40, 500),
+            decoded(60, 1_500),
+            // Logged before tokens were: no count, so no throughput either.
+            DictationRecord {
+                decode_ms: 900,
+                ..record(at(&zone, 16, 11), 20, 8_000, "base.en")
+            },
+            record(at(&zone, 16, 11), 50, 9_000, "parakeet-tdt-0.6b-v3-coreml"),
+        ];
+
+        let day = summarize(&records, StatsRange::Day, now, 40);
+        assert_eq!(day.tokens, 100);
+        let base = day.models.iter().find(|m| m.model == "base.en").unwrap();
+        assert_eq!(base.tokens, 100);
+        assert_eq!(base.tokens_per_sec, Some(50.0));
+        let parakeet = day.models.iter().find(|m| m.model != "base.en").unwrap();
+        assert_eq!(parakeet.tokens, 0);
+        assert_eq!(parakeet.tokens_per_sec, None);
+        assert_eq!(tokens_per_sec(10, 0), None);
+
+        let line = serde_json::to_string(&records[3]).unwrap();
+        assert!(!line.contains("tokens"));
+        let old: DictationRecord =
+            serde_json::from_str(r#"{"finishedAtMs":1,"words":2,"audioMs":3,"model":"tiny.en"}"#)
+                .unwrap();
+        assert_eq!(old.tokens, None);
+        assert_eq!(old.decode_ms, 0);
+    }
+
+    #[test]
+    fn time_saved_never_goes_negative() {
+        let zone = FixedOffset::east_opt(0).unwrap();
+        let now = zone.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        let slow = vec![record(at(&zone, 16, 10), 10, 120_000, "tiny.en")];
+        assert_eq!(
+            summarize(&slow, StatsRange::Day, now, 200).time_saved_secs,
+            0.0
+        );
+    }
+
+    #[test]
+    fn log_appends_skips_torn_lines_and_prunes_old_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATS_FILE);
+        let now_ms = 1_800_000_000_000;
+        let old = now_ms - (RETENTION_DAYS + 1) * 24 * 60 * 60 * 1000;
+        append_in(&path, &record(old, 5, 1_000, "tiny.en")).unwrap();
+        append_in(&path, &record(now_ms, 7, 2_000, "base.en")).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"finishedAtMs\":")
+            .unwrap();
+        assert_eq!(read_records(&path).len(), 2);
+
+        prune_in(&path, now_ms).unwrap();
+        assert_eq!(
+            read_records(&path),
+            vec![record(now_ms, 7, 2_000, "base.en")]
+        );
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("text"));
+    }
+}
@@ -0,0 +1,560 @@
+//! Background queue for model downloads.
+//!
+//! `queue_model_downloads` lines models up to install one after another
+//! through `download_model`, so onboarding can start a small model and a large
+//! one together and move on instead of waiting for a 1.5 GB file. The queue is
+//! saved to `download-queue.json` in the app data directory and picked up
+//! shortly after launch; `stream_download` keeps the partial `.tmp` file of an
+//! interrupted queue download and resumes it with a range request guarded by
+//! `If-Range`, so a restart only costs a reconnect. Downloads started outside
+//! the queue always start over.
+//!
+//! When `downloadNetwork` asks for it, the queue pauses while the connection
+//! looks metered or the default route goes through a VPN, and carries on once
+//! it doesn't. Detection is best effort: a phone hotspot gateway or a
+//! NetworkManager metered flag, and a tunnel interface on the default route.
+//! Other platforms never pause. When the queue installs the first model that
+//! can transcribe, `first-model-ready` and a notification say dictation works.
+
+use crate::commands::models::download_model;
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const QUEUE_FILE: &str = "download-queue.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Wait before resuming a saved queue, so the frontend has sent
+/// `downloadNetwork` and its pause settings first.
+const STARTUP_DELAY: Duration = Duration::from_secs(10);
+
+/// The error `stream_download` returns when a queued download stops for a
+/// pause. Its partial file is kept.
+pub(crate) const PAUSED: &str = "Download paused";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PauseReason {
+    /// Paused from the downloads list.
+    User,
+    Metered,
+    Vpn,
+}
+
+/// Sent with `download-queue-changed` and returned by the queue commands.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadQueueStatus {
+    /// The model downloading now, or next when paused.
+    pub active: Option<String>,
+    /// Models after `active`, in order.
+    pub pending: Vec<String>,
+    pub paused: Option<PauseReason>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SavedQueue {
+    models: Vec<String>,
+    user_paused: bool,
+}
+
+struct Queue {
+    /// The front model is the one downloading.
+    models: VecDeque<String>,
+    user_paused: bool,
+    network_pause: Option<PauseReason>,
+    worker_running: bool,
+}
+
+impl Queue {
+    const fn new() -> Self {
+        Self {
+            models: VecDeque::new(),
+            user_paused: false,
+            network_pause: None,
+            worker_running: false,
+        }
+    }
+
+    fn paused(&self) -> Option<PauseReason> {
+        if self.user_paused {
+            Some(PauseReason::User)
+        } else {
+            self.network_pause
+        }
+    }
+
+    fn status(&self) -> DownloadQueueStatus {
+        DownloadQueueStatus {
+            active: self.models.front().cloned(),
+            pending: self.models.iter().skip(1).cloned().collect(),
+            paused: self.paused(),
+        }
+    }
+
+    /// Append the models not already queued. Returns how many were added.
+    fn enqueue(&mut self, model_names: impl IntoIterator<Item = String>) -> usize {
+        let before = self.models.len();
+        for model_name in model_names {
+            if !self.models.contains(&model_name) {
+                self.models.push_back(model_name);
+            }
+        }
+        self.models.len() - before
+    }
+}
+
+static QUEUE: Mutex<Queue> = Mutex::new(Queue::new());
+static QUEUE_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+tokio::task_local! {
+    /// Set while the queue's worker runs a download, so a pause only stops
+    /// downloads the queue started.
+    static QUEUED_DOWNLOAD: ();
+}
+
+/// Whether the download running on this task was started by the queue.
+pub(crate) fn in_queue() -> bool {
+    QUEUED_DOWNLOAD.try_with(|_| ()).is_ok()
+}
+
+/// Whether the download running on this task should stop for a pause.
+pub(crate) fn pause_requested() -> bool {
+    in_queue() && QUEUE.lock_or_recover().paused().is_some()
+}
+
+fn read_saved(path: &Path) -> SavedQueue {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save(queue: &Queue) {
+    let Some(path) = QUEUE_PATH.get() else {
+        return;
+    };
+    let saved = SavedQueue {
+        models: queue.models.iter().cloned().collect(),
+        user_paused: queue.user_paused,
+    };
+    let written = serde_json::to_vec(&saved)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            std::fs::write(path, bytes).map_err(|e| e.to_string())
+        });
+    if let Err(error) = written {
+        tracing::warn!(target: "system", "download queue not saved: {}", error);
+    }
+}
+
+fn publish(app: &tauri::AppHandle, status: DownloadQueueStatus) {
+    let _ = app.emit("download-queue-changed", status);
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct DefaultRoute {
+    gateway: Option<Ipv4Addr>,
+    interface: String,
+}
+
+/// The default route from `route -n get default` (macOS) or
+/// `ip route show default` (Linux).
+fn parse_default_route(output: &str) -> Option<DefaultRoute> {
+    let mut gateway = None;
+    let mut interface = None;
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(value) = line.strip_prefix("gateway:") {
+            gateway = value.trim().parse().ok();
+        } else if let Some(value) = line.strip_prefix("interface:") {
+            interface = Some(value.trim().to_string());
+        } else if line.starts_with("default") && interface.is_none() {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let after = |key: &str| {
+                words
+                    .iter()
+                    .position(|word| *word == key)
+                    .and_then(|index| words.get(index + 1))
+            };
+            gateway = after("via").and_then(|value| value.parse().ok());
+            interface = after("dev").map(|value| value.to_string());
+        }
+    }
+    Some(DefaultRoute {
+        gateway,
+        interface: interface.filter(|name| !name.is_empty())?,
+    })
+}
+
+/// Tunnel interfaces that VPN clients route through.
+fn is_vpn_interface(name: &str) -> bool {
+    ["utun", "tun", "tap", "ppp", "ipsec", "wg"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// Gateways phone hotspots hand out: iPhone Personal Hotspot always uses
+/// 172.20.10.0/28, and Android before 11 used 192.168.43.0/24.
+fn is_hotspot_gateway(gateway: Ipv4Addr) -> bool {
+    let [a, b, c, d] = gateway.octets();
+    ((a, b, c) == (172, 20, 10) && d < 16) || (a, b, c) == (192, 168, 43)
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    pub(super) fn default_route_output() -> Option<String> {
+        let output = std::process::Command::new("/sbin/route")
+            .args(["-n", "get", "default"])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    pub(super) fn metered_interface(_interface: &str) -> bool {
+        false
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    pub(super) fn default_route_output() -> Option<String> {
+        let output = std::process::Command::new("ip")
+            .args(["route", "show", "default"])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// NetworkManager's metered flag: `yes`, `yes (guessed)`, `no`, or
+    /// `unknown`. Without NetworkManager the interface counts as unmetered.
+    pub(super) fn metered_interface(interface: &str) -> bool {
+        std::process::Command::new("nmcli")
+            .args(["-t", "-g", "GENERAL.METERED", "device", "show", interface])
+            .output()
+            .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).starts_with("yes"))
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+mod platform {
+    pub(super) fn default_route_output() -> Option<String> {
+        None
+    }
+
+    pub(super) fn metered_interface(_interface: &str) -> bool {
+        false
+    }
+}
+
+/// Why the network should hold the queue, under the current settings.
+fn network_pause() -> Option<PauseReason> {
+    let (pause_on_metered, pause_on_vpn) = crate::download_network::pause_settings();
+    if !pause_on_metered && !pause_on_vpn {
+        return None;
+    }
+    let route = parse_default_route(&platform::default_route_output()?)?;
+    if pause_on_vpn && is_vpn_interface(&route.interface) {
+        return Some(PauseReason::Vpn);
+    }
+    let metered = route.gateway.is_some_and(is_hotspot_gateway)
+        || platform::metered_interface(&route.interface);
+    (pause_on_metered && metered).then_some(PauseReason::Metered)
+}
+
+/// Start the worker unless it is running, paused, or has nothing to do.
+fn ensure_worker(app: &tauri::AppHandle) {
+    {
+        let mut queue = QUEUE.lock_or_recover();
+        if queue.worker_running || queue.models.is_empty() || queue.paused().is_some() {
+            return;
+        }
+        queue.worker_running = true;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(QUEUED_DOWNLOAD.scope((), run_worker(app)));
+}
+
+/// Install queued models front to back until the queue is empty or paused.
+async fn run_worker(app: tauri::AppHandle) {
+    loop {
+        let next = {
+            let mut queue = QUEUE.lock_or_recover();
+            let next = match queue.paused() {
+                None => queue.models.front().cloned(),
+                Some(_) => None,
+            };
+            if next.is_none() {
+                queue.worker_running = false;
+            }
+            next
+        };
+        let Some(model_name) = next else {
+            return;
+        };
+
+        let state = app.state::<crate::State>();
+        let had_usable_model = state.app_state.model_runtime.any_model_installed();
+        tracing::info!(target: "system", model = model_name.as_str(), "queued download started");
+        let result = download_model(app.clone(), state, model_name.clone()).await;
+
+        let status = {
+            let mut queue = QUEUE.lock_or_recover();
+            match &result {
+                Err(error) if error == PAUSED => {}
+                _ => {
+                    queue.models.retain(|queued| *queued != model_name);
+                    save(&queue);
+                }
+            }
+            queue.status()
+        };
+        match result {
+            Ok(()) => {
+                tracing::info!(target: "system", model = model_name.as_str(), "queued download installed");
+                if !had_usable_model {
+                    first_model_ready(&app, &model_name);
+                }
+            }
+            Err(error) if error == PAUSED => {
+                tracing::info!(target: "system", model = model_name.as_str(), paused = ?status.paused, "queued download paused");
+            }
+            Err(error) => {
+                tracing::warn!(target: "system", model = model_name.as_str(), "queued download failed: {}", error);
+            }
+        }
+        publish(&app, status);
+    }
+}
+
+fn first_model_ready(app: &tauri::AppHandle, model_name: &str) {
+    let label = crate::model_runtime::model_definition(model_name)
+        .map(|definition| crate::model_runtime::model_label(definition, model_name))
+        .unwrap_or_else(|_| model_name.to_string());
+    let _ = app.emit("first-model-ready", model_name);
+    let state = app.state::<crate::State>();
+    crate::notifications::model_ready(app, &state.app_state, &label);
+}
+
+/// Re-check the network and wake the worker when a pause lifts.
+fn poll(app: &tauri::AppHandle) {
+    if QUEUE.lock_or_recover().models.is_empty() {
+        return;
+    }
+    let reason = network_pause();
+    let changed = {
+        let mut queue = QUEUE.lock_or_recover();
+        let changed = queue.network_pause != reason;
+        queue.network_pause = reason;
+        changed.then(|| queue.status())
+    };
+    if let Some(status) = changed {
+        tracing::info!(target: "system", paused = ?reason, "download queue network check changed");
+        publish(app, status);
+    }
+    ensure_worker(app);
+}
+
+/// Load the saved queue and start the network poller, which resumes it after
+/// [`STARTUP_DELAY`]. Call once from setup.
+pub(crate) fn init(app: &tauri::AppHandle, app_data_dir: &Path) {
+    let path = app_data_dir.join(QUEUE_FILE);
+    let saved = read_saved(&path);
+    let _ = QUEUE_PATH.set(path);
+    {
+        let mut queue = QUEUE.lock_or_recover();
+        queue.user_paused = saved.user_paused;
+        queue.enqueue(saved.models);
+        if !queue.models.is_empty() {
+            tracing::info!(target: "system", models = queue.models.len(), "saved download queue found");
+        }
+    }
+    let handle = app.clone();
+    if let Err(error) = std::thread::Builder::new()
+        .name("download-queue".to_string())
+        .spawn(move || {
+            std::thread::sleep(STARTUP_DELAY);
+            loop {
+                poll(&handle);
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        })
+    {
+        tracing::warn!(target: "system", "download queue poller failed to start: {}", error);
+    }
+}
+
+/// Queue catalog models for download after any already queued. Installed and
+/// already queued models are skipped. The network check shells out, so it
+/// runs off the async runtime.
+#[tauri::command]
+pub async fn queue_model_downloads(
+    app_handle: tauri::AppHandle,
+    model_names: Vec<String>,
+) -> Result<DownloadQueueStatus, String> {
+    let mut wanted = Vec::with_capacity(model_names.len());
+    for model_name in model_names {
+        let definition = crate::model_runtime::model_definition(&model_name)?;
+        if !crate::model_runtime::model_supported(definition) {
+            return Err(format!(
+                "{} is not supported on the current platform",
+                definition.label
+            ));
+        }
+        if crate::model_runtime::custom_model_file(&model_name).is_some() {
+            return Err("Custom models are downloaded with download_custom_model".to_string());
+        }
+        if definition.install_kind == crate::model_runtime::InstallKind::External {
+            return Err(
+                "Transcription plugins are added with register_transcription_plugin".to_string(),
+            );
+        }
+        if definition.install_kind == crate::model_runtime::InstallKind::Cloud {
+            return Err("Cloud transcription is set up with set_cloud_api_key".to_string());
+        }
+        if !crate::model_runtime::model_installed(&model_name) {
+            wanted.push(model_name);
+        }
+    }
+    let reason = tauri::async_runtime::spawn_blocking(network_pause)
+        .await
+        .map_err(|e| format!("Network check failed: {}", e))?;
+    let status = {
+        let mut queue = QUEUE.lock_or_recover();
+        queue.network_pause = reason;
+        let added = queue.enqueue(wanted);
+        if added > 0 {
+            save(&queue);
+            tracing::info!(target: "system", added, queued = queue.models.len(), "model downloads queued");
+        }
+        queue.status()
+    };
+    publish(&app_handle, status.clone());
+    ensure_worker(&app_handle);
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn get_download_queue() -> DownloadQueueStatus {
+    QUEUE.lock_or_recover().status()
+}
+
+/// Drop a model that is waiting in the queue. The active download is paused,
+/// not removed, so its partial file isn't orphaned.
+#[tauri::command]
+pub fn remove_queued_download(
+    app_handle: tauri::AppHandle,
+    model_name: String,
+) -> Result<DownloadQueueStatus, String> {
+    let status = {
+        let mut queue = QUEUE.lock_or_recover();
+        let Some(index) = queue.models.iter().position(|queued| *queued == model_name) else {
+            return Err(format!("'{}' is not in the download queue", model_name));
+        };
+        if index == 0 && queue.worker_running {
+            return Err("Pause the queue before removing the active download".to_string());
+        }
+        queue.models.remove(index);
+        save(&queue);
+        queue.status()
+    };
+    publish(&app_handle, status.clone());
+    Ok(status)
+}
+
+/// Pause or resume the queue. Pausing stops the active download at the next
+/// chunk and keeps what it has; resuming continues from there.
+#[tauri::command]
+pub fn set_download_queue_paused(
+    app_handle: tauri::AppHandle,
+    paused: bool,
+) -> DownloadQueueStatus {
+    let status = {
+        let mut queue = QUEUE.lock_or_recover();
+        queue.user_paused = paused;
+        save(&queue);
+        queue.status()
+    };
+    tracing::info!(target: "system", paused, "download queue pause set");
+    publish(&app_handle, status.clone());
+    ensure_worker(&app_handle);
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enqueue_keeps_order_and_skips_duplicates() {
+        let mut queue = Queue::new();
+        assert_eq!(
+            queue.enqueue(["base.en".to_string(), "large-v3-turbo".to_string()]),
+            2
+        );
+        assert_eq!(
+            queue.enqueue(["large-v3-turbo".to_string(), "small.en".to_string()]),
+            1
+        );
+        let status = queue.status();
+        assert_eq!(status.active.as_deref(), Some("base.en"));
+        assert_eq!(status.pending, vec!["large-v3-turbo", "small.en"]);
+        assert_eq!(status.paused, None);
+
+        queue.network_pause = Some(PauseReason::Metered);
+        assert_eq!(queue.paused(), Some(PauseReason::Metered));
+        queue.user_paused = true;
+        assert_eq!(queue.paused(), Some(PauseReason::User));
+    }
+
+    #[test]
+    fn parses_macos_and_linux_default_routes() {
+        let macos = "   route to: default\ndestination: default\n       mask: default\n    gateway: 172.20.10.1\n  interface: en0\n      flags: <UP,GATEWAY,DONE,STATIC,PRCLONING>\n";
+        assert_eq!(
+            parse_default_route(macos),
+            Some(DefaultRoute {
+                gateway: Some(Ipv4Addr::new(172, 20, 10, 1)),
+                interface: "en0".to_string(),
+            })
+        );
+        let linux = "default via 192.168.1.1 dev wlp2s0 proto dhcp src 192.168.1.20 metric 600\n";
+        assert_eq!(
+            parse_default_route(linux),
+            Some(DefaultRoute {
+                gateway: Some(Ipv4Addr::new(192, 168, 1, 1)),
+                interface: "wlp2s0".to_string(),
+            })
+        );
+        let wireguard = "default dev wg0 scope link\n";
+        assert_eq!(
+            parse_default_route(wireguard).map(|route| route.interface),
+            Some("wg0".to_string())
+        );
+        assert_eq!(parse_default_route(""), None);
+    }
+
+    #[test]
+    fn classifies_tunnels_and_hotspot_gateways() {
+        assert!(is_vpn_interface("utun4"));
+        assert!(is_vpn_interface("wg0"));
+        assert!(!is_vpn_interface("en0"));
+        assert!(is_hotspot_gateway(Ipv4Addr::new(172, 20, 10, 1)));
+        assert!(is_hotspot_gateway(Ipv4Addr::new(192, 168, 43, 1)));
+        assert!(!is_hotspot_gateway(Ipv4Addr::new(172, 20, 10, 17)));
+        assert!(!is_hotspot_gateway(Ipv4Addr::new(192, 168, 1, 1)));
+    }
+}
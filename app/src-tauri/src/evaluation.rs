@@ -665,6 +665,8 @@ fn validate_fixture(
         crate::transcript_transform::SMART_FORMATTING_STAGE,
         crate::transcript_transform::IDE_CONTEXT_STAGE,
         crate::transcript_transform::CLI_COMMAND_STAGE,
+        crate::transcript_transform::REPLACEMENT_RULES_STAGE,
+        crate::transcript_transform::REDACTION_STAGE,
     ];
     const OUTCOMES: &[&str] = &["applied", "skipped", "fallback", "failed"];
     let mut expected_stages = HashSet::new();
@@ -1153,6 +1155,9 @@ fn run_transform(
                 now: fixed_now,
                 clipboard: fixture.context.clipboard_text.clone(),
             })),
+            redactor: None,
+            replacement_rules: None,
+            language: &crate::language_rules::ENGLISH,
         },
         &mut observer,
     );
@@ -1169,7 +1174,7 @@ fn score_recognition(
 ) -> RecognitionMetrics {
     let (normalized_errors, normalized_words) =
         crate::benchmark::normalized_word_errors(reference, actual);
-    let (raw_errors, raw_words) = crate::benchmark::word_errors(reference, actual);
+    let (raw_errors, raw_words) = crate::text_metrics::word_errors(reference, actual);
     let (char_errors, reference_characters) = character_errors(reference, actual);
     RecognitionMetrics {
         expected_raw: Some(reference.to_string()),
@@ -0,0 +1,423 @@
+//! Names and payloads of the core events the backend emits to the frontend.
+//!
+//! Each event here has one typed payload and one `emit_*` helper, so every
+//! emitter sends the same shape and the shapes can be unit-tested without a
+//! running app. Event names are kebab-case and payload fields camelCase; both
+//! are part of the frontend contract (see `docs/reference/events.md`). The
+//! hotkey names match the ones the legacy `ui/src-tauri` keyboard listener
+//! emits.
+
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::correct_and_teach::TeachingContext;
+use crate::markers::TranscriptSegment;
+use crate::model_runtime::ProcessingLocation;
+use crate::state::DictationStatus;
+use crate::transcriber::TranscriptConfidence;
+
+pub const RECORDING_STATUS_CHANGED: &str = "recording-status-changed";
+pub const TRANSCRIPTION_COMPLETE: &str = "transcription-complete";
+pub const TRANSCRIPTION_FAILED: &str = "transcription-failed";
+pub const DOWNLOAD_PROGRESS: &str = "download-progress";
+pub const AUTO_PASTE_FAILED: &str = "auto-paste-failed";
+pub const AUTOMATION_PERMISSION_DENIED: &str = "automation-permission-denied";
+pub const NO_TEXT_TARGET: &str = "no-text-target";
+pub const OVERLAY_STATE: &str = "overlay-state";
+
+/// Payload of `transcription-complete`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionComplete {
+    pub recording_id: u64,
+    pub text: String,
+    /// Whole seconds of recorded audio.
+    pub duration: u64,
+    pub teaching_context: Option<TeachingContext>,
+    /// Whisper's confidence in the raw transcript; None for other backends.
+    pub confidence: Option<TranscriptConfidence>,
+    /// Text tokens the decoder produced; None for backends that don't say.
+    pub tokens: Option<u64>,
+    /// Time spent on the decode `tokens` came from, for tokens per second.
+    pub decode_ms: u64,
+    /// Marker key presses, in milliseconds from the start of the audio.
+    pub markers: Vec<u64>,
+    /// The text split at markers; None when the dictation had none.
+    pub segments: Option<Vec<TranscriptSegment>>,
+    /// Whether the audio was transcribed on this Mac, by a cloud service, or
+    /// by a plugin.
+    pub processed_on: ProcessingLocation,
+    /// The cloud host or plugin name; None on this Mac.
+    pub processed_by: Option<String>,
+}
+
+/// Payload of `transcription-failed`: a queued transcription ended in an
+/// error instead of a result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionFailed {
+    pub recording_id: u64,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPhase {
+    Downloading,
+    Installing,
+}
+
+/// Payload of `download-progress`. `total` is 0 when the size is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadProgress {
+    pub received: u64,
+    pub total: u64,
+    pub phase: DownloadPhase,
+}
+
+impl DownloadProgress {
+    pub fn downloading(received: u64, total: u64) -> Self {
+        Self {
+            received,
+            total,
+            phase: DownloadPhase::Downloading,
+        }
+    }
+
+    pub fn installing(received: u64, total: u64) -> Self {
+        Self {
+            received,
+            total,
+            phase: DownloadPhase::Installing,
+        }
+    }
+}
+
+/// Payload of `overlay-state`: the content the overlay shows beyond its
+/// status icon, on one channel tagged by `state` with the fields in
+/// `payload`. `emit_recording_status` keeps it in step with the status;
+/// `commands::overlay::set_overlay_state` lets other producers drive it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", content = "payload", rename_all = "snake_case")]
+pub enum OverlayState {
+    Idle,
+    /// Recording since `started_at_ms` (Unix ms); the overlay runs the timer.
+    Recording {
+        #[serde(rename = "startedAtMs")]
+        started_at_ms: u64,
+    },
+    /// Microphone level (RMS, 0.0-1.0) while recording, ~60 per second.
+    Level {
+        rms: f32,
+    },
+    /// Transcribing. `partial` is text known before the final result, such
+    /// as a dual-model draft while it is refined.
+    Processing {
+        partial: Option<String>,
+    },
+    /// A short error message the overlay shows for a few seconds.
+    Error {
+        message: String,
+    },
+}
+
+impl OverlayState {
+    /// Parse the `(state, payload)` pair `set_overlay_state` is called with.
+    pub fn from_parts(state: &str, payload: Option<serde_json::Value>) -> Result<Self, String> {
+        let mut value = serde_json::json!({ "state": state });
+        if let Some(payload) = payload.filter(|payload| !payload.is_null()) {
+            value["payload"] = payload;
+        }
+        serde_json::from_value(value).map_err(|e| format!("Invalid overlay state: {}", e))
+    }
+}
+
+/// Global hotkey gestures. Each is its own payload-less event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyEvent {
+    DoubleTapToggle,
+    HoldDownStart,
+    HoldDownStop,
+}
+
+impl HotkeyEvent {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DoubleTapToggle => "double-tap-toggle",
+            Self::HoldDownStart => "hold-down-start",
+            Self::HoldDownStop => "hold-down-stop",
+        }
+    }
+}
+
+/// Wake-word detector signals (`kws.rs`). Each is its own payload-less event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeWordEvent {
+    /// The wake phrase was heard; the frontend starts a recording.
+    Detected,
+    /// A wake-started recording went quiet; the frontend stops it.
+    SilenceStop,
+}
+
+impl WakeWordEvent {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Detected => "wake-word-detected",
+            Self::SilenceStop => "wake-word-silence",
+        }
+    }
+}
+
+/// The payload is the bare status string (`"idle"`, `"recording"`, ...).
+/// The overlay also gets the matching `overlay-state`.
+pub fn emit_recording_status(app: &tauri::AppHandle, status: DictationStatus) {
+    let _ = app.emit(RECORDING_STATUS_CHANGED, status);
+    emit_overlay_state(app, &overlay_state_for(status, now_ms()));
+}
+
+/// The `overlay-state` a status change starts. Buffered takes have nothing
+/// in flight, so buffering reads as idle.
+fn overlay_state_for(status: DictationStatus, now_ms: u64) -> OverlayState {
+    match status {
+        DictationStatus::Recording => OverlayState::Recording {
+            started_at_ms: now_ms,
+        },
+        DictationStatus::Processing => OverlayState::Processing { partial: None },
+        DictationStatus::Idle | DictationStatus::Buffering => OverlayState::Idle,
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Broadcast, like the status, so every overlay mirror renders the same state.
+pub fn emit_overlay_state(app: &tauri::AppHandle, state: &OverlayState) {
+    let _ = app.emit(OVERLAY_STATE, state);
+}
+
+pub fn emit_transcription_complete(app: &tauri::AppHandle, payload: &TranscriptionComplete) {
+    let _ = app.emit(TRANSCRIPTION_COMPLETE, payload);
+}
+
+pub fn emit_transcription_failed(app: &tauri::AppHandle, recording_id: u64, error: &str) {
+    let _ = app.emit(
+        TRANSCRIPTION_FAILED,
+        TranscriptionFailed {
+            recording_id,
+            error: error.to_string(),
+        },
+    );
+}
+
+pub fn emit_download_progress(app: &tauri::AppHandle, progress: DownloadProgress) {
+    let _ = app.emit(DOWNLOAD_PROGRESS, progress);
+}
+
+/// The hint tells the user how to paste the text that is on the clipboard.
+pub fn emit_auto_paste_failed(app: &tauri::AppHandle, hint: &str) {
+    let _ = app.emit(AUTO_PASTE_FAILED, hint);
+}
+
+/// Hint for `auto-paste-failed` on this platform.
+pub fn paste_failed_hint() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "Text is in your clipboard -- press Cmd+V to paste manually."
+    } else {
+        "Text is in your clipboard -- press Ctrl+V to paste manually."
+    }
+}
+
+/// Sent instead of `auto-paste-failed` when the paste failed because
+/// Automation of System Events is denied. The payload is the hint.
+pub fn emit_automation_permission_denied(app: &tauri::AppHandle, hint: &str) {
+    let _ = app.emit(AUTOMATION_PERMISSION_DENIED, hint);
+}
+
+/// Hint for `automation-permission-denied`.
+pub fn automation_denied_hint() -> &'static str {
+    "Murmur isn't allowed to control System Events, so it couldn't paste. \
+     Allow it in System Settings > Privacy & Security > Automation. Text is in your clipboard."
+}
+
+/// Sent instead of `auto-paste-failed` when the paste was skipped on purpose
+/// because the focused element doesn't accept text. The payload is the hint.
+pub fn emit_no_text_target(app: &tauri::AppHandle, hint: &str) {
+    let _ = app.emit(NO_TEXT_TARGET, hint);
+}
+
+/// Hint for `no-text-target`.
+pub fn no_text_target_hint() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "No text field is focused, so Murmur didn't paste. Text is in your clipboard -- press Cmd+V where you want it."
+    } else {
+        "No text field is focused, so Murmur didn't paste. Text is in your clipboard -- press Ctrl+V where you want it."
+    }
+}
+
+pub fn emit_hotkey(app: &tauri::AppHandle, event: HotkeyEvent) {
+    let _ = app.emit(event.name(), ());
+}
+
+pub fn emit_wake_word(app: &tauri::AppHandle, event: WakeWordEvent) {
+    let _ = app.emit(event.name(), ());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcription_complete_uses_camel_case_fields() {
+        let payload = TranscriptionComplete {
+            recording_id: 7,
+            text: "hello\n\nagain".to_string(),
+            duration: 3,
+            teaching_context: None,
+            confidence: None,
+            tokens: Some(4),
+            decode_ms: 80,
+            markers: vec![2_500],
+            segments: Some(vec![
+                TranscriptSegment {
+                    text: "hello".to_string(),
+                    start_ms: Some(0),
+                    end_ms: Some(2_500),
+                },
+                TranscriptSegment {
+                    text: "again".to_string(),
+                    start_ms: Some(2_500),
+                    end_ms: None,
+                },
+            ]),
+            processed_on: ProcessingLocation::Cloud,
+            processed_by: Some("api.openai.com".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            serde_json::json!({
+                "recordingId": 7,
+                "text": "hello\n\nagain",
+                "duration": 3,
+                "teachingContext": null,
+                "confidence": null,
+                "tokens": 4,
+                "decodeMs": 80,
+                "markers": [2500],
+                "segments": [
+                    { "text": "hello", "startMs": 0, "endMs": 2500 },
+                    { "text": "again", "startMs": 2500, "endMs": null },
+                ],
+                "processedOn": "cloud",
+                "processedBy": "api.openai.com",
+            })
+        );
+        let parsed: TranscriptionComplete =
+            serde_json::from_str(&serde_json::to_string(&payload).unwrap()).unwrap();
+        assert_eq!(parsed, payload);
+    }
+
+    #[test]
+    fn transcription_failed_carries_the_job_id() {
+        let payload = TranscriptionFailed {
+            recording_id: 9,
+            error: "model missing".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            serde_json::json!({ "recordingId": 9, "error": "model missing" })
+        );
+    }
+
+    #[test]
+    fn download_progress_names_its_phase() {
+        assert_eq!(
+            serde_json::to_value(DownloadProgress::downloading(5, 10)).unwrap(),
+            serde_json::json!({ "received": 5, "total": 10, "phase": "downloading" })
+        );
+        assert_eq!(
+            serde_json::to_value(DownloadProgress::installing(0, 0)).unwrap(),
+            serde_json::json!({ "received": 0, "total": 0, "phase": "installing" })
+        );
+    }
+
+    #[test]
+    fn overlay_state_is_tagged_with_its_fields_in_payload() {
+        assert_eq!(
+            serde_json::to_value(OverlayState::Recording {
+                started_at_ms: 1_700_000_000_000
+            })
+            .unwrap(),
+            serde_json::json!({ "state": "recording", "payload": { "startedAtMs": 1_700_000_000_000u64 } })
+        );
+        assert_eq!(
+            serde_json::to_value(OverlayState::Processing {
+                partial: Some("draft".to_string())
+            })
+            .unwrap(),
+            serde_json::json!({ "state": "processing", "payload": { "partial": "draft" } })
+        );
+        assert_eq!(
+            serde_json::to_value(OverlayState::Idle).unwrap(),
+            serde_json::json!({ "state": "idle" })
+        );
+    }
+
+    #[test]
+    fn overlay_state_parses_from_a_command_pair() {
+        assert_eq!(
+            OverlayState::from_parts("error", Some(serde_json::json!({ "message": "no mic" }))),
+            Ok(OverlayState::Error {
+                message: "no mic".to_string()
+            })
+        );
+        assert_eq!(
+            OverlayState::from_parts("idle", None),
+            Ok(OverlayState::Idle)
+        );
+        assert_eq!(
+            OverlayState::from_parts("idle", Some(serde_json::Value::Null)),
+            Ok(OverlayState::Idle)
+        );
+        assert!(OverlayState::from_parts("recording", None).is_err());
+        assert!(OverlayState::from_parts("celebrating", None).is_err());
+    }
+
+    #[test]
+    fn each_status_starts_its_overlay_state() {
+        assert_eq!(
+            overlay_state_for(DictationStatus::Recording, 42),
+            OverlayState::Recording { started_at_ms: 42 }
+        );
+        assert_eq!(
+            overlay_state_for(DictationStatus::Processing, 42),
+            OverlayState::Processing { partial: None }
+        );
+        assert_eq!(
+            overlay_state_for(DictationStatus::Idle, 42),
+            OverlayState::Idle
+        );
+        assert_eq!(
+            overlay_state_for(DictationStatus::Buffering, 42),
+            OverlayState::Idle
+        );
+    }
+
+    #[test]
+    fn recording_status_payload_is_the_status_name() {
+        for status in [
+            DictationStatus::Idle,
+            DictationStatus::Recording,
+            DictationStatus::Processing,
+            DictationStatus::Buffering,
+        ] {
+            assert_eq!(
+                serde_json::to_value(status).unwrap(),
+                serde_json::json!(status.as_str())
+            );
+        }
+    }
+}
@@ -15,19 +15,29 @@ use std::path::{Path, PathBuf};
 /// Resolve the output directory: the user-chosen `output_dir` if non-empty,
 /// otherwise `<Documents>/Murmur` (falling back to `<home>/Murmur`).
 pub(crate) fn resolve_output_dir(output_dir: &str) -> Result<PathBuf, String> {
-    let dir = if !output_dir.trim().is_empty() {
-        PathBuf::from(output_dir)
-    } else {
-        let base = dirs::document_dir()
-            .or_else(dirs::home_dir)
-            .ok_or_else(|| "Could not determine a default output directory".to_string())?;
-        base.join("Murmur")
-    };
+    let dir = output_dir_path(output_dir)?;
     std::fs::create_dir_all(&dir)
         .map_err(|e| format!("Failed to create output directory: {}", e))?;
     Ok(dir)
 }
 
+/// Where [`resolve_output_dir`] puts files, without creating it.
+pub(crate) fn output_dir_path(output_dir: &str) -> Result<PathBuf, String> {
+    if !output_dir.trim().is_empty() {
+        return Ok(PathBuf::from(output_dir));
+    }
+    let base = dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| "Could not determine a default output directory".to_string())?;
+    Ok(base.join("Murmur"))
+}
+
+/// Whether `name` is a recording Murmur saved (`murmur-0001.wav`, or an older
+/// timestamped `murmur-*.wav`).
+pub(crate) fn is_recording_file(name: &str) -> bool {
+    name.starts_with("murmur-") && name.ends_with(".wav")
+}
+
 /// Parse the sequence number from a `murmur-NNNN` file stem. Returns `None`
 /// for anything that isn't exactly `murmur-<digits>` (e.g. older timestamped
 /// names, which carry extra `-` separators).
@@ -239,6 +239,101 @@ pub fn frontmost_bundle_id() -> Option<String> {
     None
 }
 
+/// Whether the frontmost app's focused window is in fullscreen, where the
+/// menu bar (and the notch overlay's place in it) is hidden. Reads the
+/// window's `AXFullScreen` attribute, so it is `false` without the
+/// Accessibility permission or when the app does not answer in time.
+#[cfg(target_os = "macos")]
+pub(crate) fn frontmost_is_fullscreen() -> bool {
+    use objc2_app_kit::NSWorkspace;
+
+    let Some(app) = NSWorkspace::sharedWorkspace().frontmostApplication() else {
+        return false;
+    };
+    if app.processIdentifier() == std::process::id() as i32 {
+        return false;
+    }
+    ax::focused_window_is_fullscreen(app.processIdentifier())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn frontmost_is_fullscreen() -> bool {
+    false
+}
+
+#[cfg(target_os = "macos")]
+mod ax {
+    use std::ffi::{c_char, c_void, CString};
+
+    type AXUIElementRef = *const c_void;
+    type CFTypeRef = *const c_void;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateApplication(pid: i32) -> AXUIElementRef;
+        fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFTypeRef,
+            value: *mut CFTypeRef,
+        ) -> i32;
+        fn AXUIElementSetMessagingTimeout(element: AXUIElementRef, timeout: f32) -> i32;
+        fn CFStringCreateWithCString(
+            allocator: CFTypeRef,
+            string: *const c_char,
+            encoding: u32,
+        ) -> CFTypeRef;
+        fn CFGetTypeID(value: CFTypeRef) -> usize;
+        fn CFBooleanGetTypeID() -> usize;
+        fn CFBooleanGetValue(value: CFTypeRef) -> bool;
+        fn CFRelease(value: CFTypeRef);
+    }
+
+    const AX_SUCCESS: i32 = 0;
+    const AX_QUERY_TIMEOUT_SECONDS: f32 = 0.025;
+    const UTF8_ENCODING: u32 = 0x0800_0100;
+
+    /// Releases a Copy/Create-rule CF object on drop.
+    struct CFGuard(CFTypeRef);
+    impl Drop for CFGuard {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                unsafe { CFRelease(self.0) };
+            }
+        }
+    }
+
+    fn copy_attribute(element: AXUIElementRef, name: &str) -> Option<CFGuard> {
+        let name = CString::new(name).ok()?;
+        let attr = CFGuard(unsafe {
+            CFStringCreateWithCString(std::ptr::null(), name.as_ptr(), UTF8_ENCODING)
+        });
+        if attr.0.is_null() {
+            return None;
+        }
+        let mut value: CFTypeRef = std::ptr::null();
+        let status = unsafe { AXUIElementCopyAttributeValue(element, attr.0, &mut value) };
+        let value = CFGuard(value);
+        (status == AX_SUCCESS && !value.0.is_null()).then_some(value)
+    }
+
+    pub(super) fn focused_window_is_fullscreen(pid: i32) -> bool {
+        let app = CFGuard(unsafe { AXUIElementCreateApplication(pid) });
+        if app.0.is_null() {
+            return false;
+        }
+        unsafe { AXUIElementSetMessagingTimeout(app.0, AX_QUERY_TIMEOUT_SECONDS) };
+        let Some(window) = copy_attribute(app.0, "AXFocusedWindow") else {
+            return false;
+        };
+        let Some(fullscreen) = copy_attribute(window.0, "AXFullScreen") else {
+            return false;
+        };
+        unsafe {
+            CFGetTypeID(fullscreen.0) == CFBooleanGetTypeID() && CFBooleanGetValue(fullscreen.0)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
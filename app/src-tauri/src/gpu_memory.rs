@@ -0,0 +1,162 @@
+//! Recovering a take when the GPU runs out of memory.
+//!
+//! On an 8 GB Mac, `large-v3-turbo` next to a few browser tabs can fail to
+//! get its Metal buffers. whisper.cpp only reports that as a failed load or
+//! decode, which the Whisper backend tags with
+//! [`crate::transcriber::GPU_MEMORY_ERROR`]. The live pipeline then decodes
+//! the take again on the CPU and, if that fails too, with the largest
+//! installed Whisper model smaller than the one chosen, and says what it did
+//! with `gpu-memory-pressure` so the slower or weaker take isn't a mystery.
+
+use crate::model_runtime::{self, BackendKind, MODEL_DEFINITIONS};
+use serde::Serialize;
+use tauri::Emitter;
+
+pub const GPU_MEMORY_PRESSURE: &str = "gpu-memory-pressure";
+
+/// What a take fell back to after the GPU ran out of memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GpuFallback {
+    /// The same model, loaded without the GPU.
+    Cpu,
+    /// A smaller installed model.
+    SmallerModel,
+    /// Nothing worked; the take failed.
+    Failed,
+}
+
+impl GpuFallback {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::SmallerModel => "smaller_model",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Payload of `gpu-memory-pressure`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuMemoryPressure {
+    pub recording_id: u64,
+    /// The model that didn't fit.
+    pub model: String,
+    pub fallback: GpuFallback,
+    /// The model used instead, for `SmallerModel`.
+    pub fallback_model: Option<String>,
+    /// What the user can do about it, as a sentence for the main window.
+    pub guidance: String,
+}
+
+/// The largest installed Whisper model that holds less memory than
+/// `model_name`. Unless the take is in English, a multilingual model is only
+/// swapped for another multilingual one.
+pub fn smaller_model(
+    model_name: &str,
+    language: &str,
+    installed: impl Fn(&str) -> bool,
+) -> Option<&'static str> {
+    let current = model_runtime::model_definition(model_name).ok()?;
+    MODEL_DEFINITIONS
+        .iter()
+        .filter(|definition| {
+            definition.backend == BackendKind::Whisper
+                && model_runtime::model_supported(definition)
+                && definition.resident_mb < current.resident_mb
+                && (language == "en"
+                    || !current.capabilities.multilingual
+                    || definition.capabilities.multilingual)
+                && installed(definition.model_name)
+        })
+        .max_by_key(|definition| definition.resident_mb)
+        .map(|definition| definition.model_name)
+}
+
+fn display_name(model: &str) -> String {
+    model_runtime::model_definition(model)
+        .map(|definition| model_runtime::model_label(definition, model))
+        .unwrap_or_else(|_| model.to_string())
+}
+
+pub fn guidance(model: &str, fallback: GpuFallback, fallback_model: Option<&str>) -> String {
+    let label = display_name(model);
+    match (fallback, fallback_model.map(display_name)) {
+        (GpuFallback::Cpu, _) => format!(
+            "{label} ran out of GPU memory, so it is running on the CPU until it is next unloaded, which is slower. Quit memory-heavy apps such as browsers, or choose a smaller model in Settings."
+        ),
+        (GpuFallback::SmallerModel, Some(smaller)) => format!(
+            "{label} ran out of GPU memory, so this recording used {smaller}. Quit memory-heavy apps such as browsers, or choose a smaller model in Settings."
+        ),
+        _ => format!(
+            "{label} ran out of GPU memory. Quit memory-heavy apps such as browsers and try again, or choose a smaller model in Settings."
+        ),
+    }
+}
+
+/// Log and emit what recording `recording_id` fell back to.
+pub fn report(
+    app: &tauri::AppHandle,
+    recording_id: u64,
+    model: &str,
+    fallback: GpuFallback,
+    fallback_model: Option<&str>,
+) {
+    tracing::warn!(
+        target: "pipeline",
+        recording_id,
+        model,
+        fallback = fallback.as_str(),
+        fallback_model,
+        "gpu_memory_pressure"
+    );
+    let _ = app.emit(
+        GPU_MEMORY_PRESSURE,
+        GpuMemoryPressure {
+            recording_id,
+            model: model.to_string(),
+            fallback,
+            fallback_model: fallback_model.map(str::to_string),
+            guidance: guidance(model, fallback, fallback_model),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smaller_model_picks_the_largest_installed_model_that_fits() {
+        let installed = |name: &str| matches!(name, "tiny.en" | "small.en" | "large-v3-turbo-q5_0");
+        assert_eq!(
+            smaller_model("large-v3-turbo", "en", installed),
+            Some("large-v3-turbo-q5_0")
+        );
+        assert_eq!(smaller_model("small.en", "en", installed), Some("tiny.en"));
+        assert_eq!(smaller_model("tiny.en", "en", installed), None);
+    }
+
+    #[test]
+    fn smaller_model_keeps_non_english_takes_multilingual() {
+        let installed = |name: &str| matches!(name, "small.en" | "medium.en");
+        assert_eq!(smaller_model("large-v3-turbo", "de", installed), None);
+        assert_eq!(
+            smaller_model("large-v3-turbo", "en", installed),
+            Some("medium.en")
+        );
+    }
+
+    #[test]
+    fn guidance_names_what_happened() {
+        assert!(guidance("large-v3-turbo", GpuFallback::Cpu, None).contains("on the CPU"));
+        assert!(guidance(
+            "large-v3-turbo",
+            GpuFallback::SmallerModel,
+            Some("small.en")
+        )
+        .contains("this recording used Whisper Small"));
+        assert!(guidance("large-v3-turbo", GpuFallback::Failed, None).contains("try again"));
+    }
+}
@@ -0,0 +1,153 @@
+//! Guard against Whisper-style hallucinations on silent or noise-only clips.
+//!
+//! Two checks run around inference. Before it, a recording whose RMS sits at
+//! the noise floor is not transcribed at all. After it, a transcript made up
+//! entirely of phrases models are known to invent ("Thank you for watching.")
+//! is dropped, but only when the audio carried weak speech evidence — a user
+//! who clearly says "thank you" still gets their text.
+
+/// Below this whole-recording RMS the clip is treated as silence and never
+/// reaches the model. Typical speech sits well above 0.01.
+const SILENCE_RMS: f32 = 0.0015;
+
+/// Speech evidence is "weak" when VAD kept less than this share of samples...
+const WEAK_SPEECH_RATIO: f32 = 0.2;
+
+/// ...or when the whole recording is this quiet.
+const WEAK_SPEECH_RMS: f32 = 0.006;
+
+/// Lowercased, punctuation-free phrases that models emit on silence or noise.
+const KNOWN_PHRASES: &[&str] = &[
+    "thank you",
+    "thank you so much",
+    "thank you for watching",
+    "thank you so much for watching",
+    "thanks for watching",
+    "thank you very much",
+    "please subscribe",
+    "like and subscribe",
+    "subscribe to my channel",
+    "please like and subscribe",
+    "see you in the next video",
+    "see you next time",
+    "bye",
+    "bye bye",
+    "you",
+];
+
+/// Why a recording produced no text. Stable codes for events and logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoSpeechReason {
+    /// Voice activity detection found no speech.
+    Vad,
+    /// The recording was at the noise floor; inference was skipped.
+    Silence,
+    /// The model's output was dropped as a known hallucination.
+    Hallucination,
+}
+
+impl NoSpeechReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Vad => "vad",
+            Self::Silence => "silence",
+            Self::Hallucination => "hallucination",
+        }
+    }
+}
+
+/// Whether a recording is quiet enough to skip inference outright.
+pub fn is_silent(rms: f32) -> bool {
+    rms < SILENCE_RMS
+}
+
+/// Share of the original samples VAD kept as speech. Callers pass the full
+/// length for both arguments when VAD did not run.
+pub fn speech_ratio(speech_samples: usize, total_samples: usize) -> f32 {
+    if total_samples == 0 {
+        return 0.0;
+    }
+    (speech_samples as f32 / total_samples as f32).min(1.0)
+}
+
+/// Remove non-speech annotations such as `[BLANK_AUDIO]`, `(music)` and `♪`.
+fn strip_annotations(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut closing: Option<char> = None;
+    for c in text.chars() {
+        match (closing, c) {
+            (Some(close), c) if c == close => closing = None,
+            (Some(_), _) => {}
+            (None, '[') => closing = Some(']'),
+            (None, '(') => closing = Some(')'),
+            (None, '♪' | '♫') => {}
+            (None, c) => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+fn normalize(sentence: &str) -> String {
+    sentence
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether `text` consists only of annotations and known hallucination
+/// phrases. Empty text is not a hallucination.
+fn only_known_phrases(text: &str) -> bool {
+    if text.trim().is_empty() {
+        return false;
+    }
+    strip_annotations(text)
+        .split(['.', '!', '?', '\n'])
+        .map(normalize)
+        .filter(|sentence| !sentence.is_empty())
+        .all(|sentence| KNOWN_PHRASES.contains(&sentence.as_str()))
+}
+
+/// Whether a raw model transcript should be discarded as a hallucination.
+pub fn is_hallucination(text: &str, speech_ratio: f32, rms: f32) -> bool {
+    let weak_speech = speech_ratio < WEAK_SPEECH_RATIO || rms < WEAK_SPEECH_RMS;
+    weak_speech && only_known_phrases(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_phrases_and_annotations_are_hallucinations_on_weak_speech() {
+        assert!(is_hallucination("Thank you for watching!", 0.05, 0.02));
+        assert!(is_hallucination(" Thank you. Bye.", 1.0, 0.003));
+        assert!(is_hallucination("[BLANK_AUDIO]", 0.1, 0.02));
+        assert!(is_hallucination("♪ ♪", 0.0, 0.0));
+        assert!(is_hallucination("(music) Thanks for watching.", 0.1, 0.02));
+    }
+
+    #[test]
+    fn strong_speech_or_real_content_is_kept() {
+        assert!(!is_hallucination("Thank you.", 0.6, 0.05));
+        assert!(!is_hallucination(
+            "Thank you for watching the deploy logs.",
+            0.05,
+            0.002
+        ));
+        assert!(!is_hallucination("Thank you. Ship it.", 0.05, 0.002));
+        assert!(!is_hallucination("", 0.0, 0.0));
+    }
+
+    #[test]
+    fn silence_gate_and_speech_ratio_bounds() {
+        assert!(is_silent(0.0005));
+        assert!(!is_silent(0.02));
+        assert_eq!(speech_ratio(0, 0), 0.0);
+        assert_eq!(speech_ratio(4_000, 16_000), 0.25);
+        assert_eq!(speech_ratio(16_000, 16_000), 1.0);
+    }
+}
@@ -0,0 +1,212 @@
+//! Encrypted at-rest storage for dictation history.
+//!
+//! History normally lives in the frontend's localStorage as plaintext. With
+//! `encryptHistory` on, the frontend hands its entries to these commands
+//! instead, which seal them with AES-256-GCM into `history.enc` in the app
+//! data directory. The key is generated once and kept in the macOS Keychain,
+//! so the file is unreadable without the user's login keychain. Entries are
+//! opaque JSON here; their text is never logged.
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+const HISTORY_FILE: &str = "history.enc";
+/// File prefix: format tag followed by a version byte.
+const MAGIC: &[u8] = b"MURH\x01";
+const KEY_LEN: usize = 32;
+
+fn seal(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "Failed to generate a nonce.".to_string())?;
+    let key = LessSafeKey::new(
+        UnboundKey::new(&AES_256_GCM, key).map_err(|_| "Invalid history key.".to_string())?,
+    );
+    let mut sealed = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(MAGIC),
+        &mut sealed,
+    )
+    .map_err(|_| "Failed to encrypt history.".to_string())?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + sealed.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&sealed);
+    Ok(out)
+}
+
+fn open(key: &[u8; KEY_LEN], data: &[u8]) -> Result<Vec<u8>, String> {
+    let rest = data
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| "Encrypted history has an unknown format.".to_string())?;
+    if rest.len() < NONCE_LEN {
+        return Err("Encrypted history is truncated.".to_string());
+    }
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| "Encrypted history is truncated.".to_string())?;
+    let key = LessSafeKey::new(
+        UnboundKey::new(&AES_256_GCM, key).map_err(|_| "Invalid history key.".to_string())?,
+    );
+    let mut buffer = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(MAGIC), &mut buffer)
+        .map_err(|_| "Encrypted history could not be decrypted.".to_string())?;
+    Ok(plaintext.to_vec())
+}
+
+#[cfg(target_os = "macos")]
+mod keychain {
+    use super::KEY_LEN;
+    use ring::rand::{SecureRandom, SystemRandom};
+    use security_framework::passwords::{get_generic_password, set_generic_password};
+
+    const SERVICE: &str = "com.localdictation.history";
+    const ACCOUNT: &str = "history-encryption-key";
+    /// errSecItemNotFound
+    const ITEM_NOT_FOUND: i32 = -25300;
+
+    /// The history key, created and stored on first use.
+    pub(super) fn key() -> Result<[u8; KEY_LEN], String> {
+        match get_generic_password(SERVICE, ACCOUNT) {
+            Ok(bytes) => bytes
+                .try_into()
+                .map_err(|_| "The history key in the Keychain is malformed.".to_string()),
+            Err(e) if e.code() == ITEM_NOT_FOUND => {
+                let mut key = [0u8; KEY_LEN];
+                SystemRandom::new()
+                    .fill(&mut key)
+                    .map_err(|_| "Failed to generate a history key.".to_string())?;
+                set_generic_password(SERVICE, ACCOUNT, &key).map_err(|e| {
+                    format!("Failed to store the history key in the Keychain: {}", e)
+                })?;
+                tracing::info!(target: "system", "history encryption key created");
+                Ok(key)
+            }
+            Err(e) => Err(format!(
+                "Failed to read the history key from the Keychain: {}",
+                e
+            )),
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod keychain {
+    use super::KEY_LEN;
+
+    pub(super) fn key() -> Result<[u8; KEY_LEN], String> {
+        Err("Encrypted history needs the macOS Keychain.".to_string())
+    }
+}
+
+pub(crate) fn history_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?
+        .join(HISTORY_FILE))
+}
+
+fn load_from(path: &Path, key: &[u8; KEY_LEN]) -> Result<serde_json::Value, String> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(serde_json::Value::Array(Vec::new()))
+        }
+        Err(e) => return Err(format!("Failed to read encrypted history: {}", e)),
+    };
+    serde_json::from_slice(&open(key, &data)?)
+        .map_err(|e| format!("Encrypted history is corrupt: {}", e))
+}
+
+fn save_to(path: &Path, key: &[u8; KEY_LEN], entries: &serde_json::Value) -> Result<(), String> {
+    let plaintext =
+        serde_json::to_vec(entries).map_err(|e| format!("Failed to serialize history: {}", e))?;
+    let sealed = seal(key, &plaintext)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let temp = path.with_extension("enc.tmp");
+    std::fs::write(&temp, sealed)
+        .map_err(|e| format!("Failed to write encrypted history: {}", e))?;
+    std::fs::rename(&temp, path).map_err(|e| format!("Failed to write encrypted history: {}", e))
+}
+
+/// Decrypt the stored history. An empty list when nothing is stored yet.
+#[tauri::command]
+pub fn load_encrypted_history(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
+    load_from(&history_path(&app)?, &keychain::key()?)
+}
+
+/// Replace the stored history with `entries`, encrypted.
+#[tauri::command]
+pub fn save_encrypted_history(
+    app: tauri::AppHandle,
+    entries: serde_json::Value,
+) -> Result<(), String> {
+    if !entries.is_array() {
+        return Err("History must be a list of entries.".to_string());
+    }
+    save_to(&history_path(&app)?, &keychain::key()?, &entries)
+}
+
+/// Delete the encrypted history file. The Keychain key is kept so turning
+/// encryption back on doesn't prompt again.
+#[tauri::command]
+pub fn delete_encrypted_history(app: tauri::AppHandle) -> Result<(), String> {
+    match std::fs::remove_file(history_path(&app)?) {
+        Ok(()) => {
+            tracing::info!(target: "system", "encrypted history deleted");
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to delete encrypted history: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; KEY_LEN] = [7; KEY_LEN];
+
+    #[test]
+    fn history_round_trips_and_is_not_plaintext_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE);
+        assert_eq!(load_from(&path, &KEY).unwrap(), serde_json::json!([]));
+
+        let entries = serde_json::json!([{ "id": "1", "text": "meeting notes for Friday" }]);
+        save_to(&path, &KEY, &entries).unwrap();
+        let raw = std::fs::read(&path).unwrap();
+        assert!(raw.starts_with(MAGIC));
+        assert!(!raw.windows(7).any(|window| window == b"meeting"));
+        assert!(!dir.path().join("history.enc.tmp").exists());
+        assert_eq!(load_from(&path, &KEY).unwrap(), entries);
+    }
+
+    #[test]
+    fn history_fails_closed_with_the_wrong_key_or_tampering() {
+        let sealed = seal(&KEY, b"[]").unwrap();
+        assert_eq!(open(&KEY, &sealed).unwrap(), b"[]");
+        assert!(open(&[8; KEY_LEN], &sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(open(&KEY, &tampered).is_err());
+        assert!(open(&KEY, b"[]").is_err());
+        assert!(open(&KEY, MAGIC).is_err());
+    }
+
+    #[test]
+    fn each_save_uses_a_fresh_nonce() {
+        assert_ne!(seal(&KEY, b"[]").unwrap(), seal(&KEY, b"[]").unwrap());
+    }
+}
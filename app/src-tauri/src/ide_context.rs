@@ -1077,6 +1077,9 @@ mod tests {
             writing_style: None,
             ide_context_enabled: enabled,
             ide_project_roots: roots,
+            injection_method_override: None,
+            trailing_override: None,
+            model_override: None,
         }
     }
 
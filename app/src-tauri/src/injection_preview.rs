@@ -0,0 +1,155 @@
+//! Preview mode: a finished dictation is held for Accept or Discard before it
+//! touches the clipboard or the focused app.
+//!
+//! Only one preview is pending at a time. A newer dictation replaces an
+//! unanswered one, and a preview nobody answers is discarded after
+//! `PREVIEW_TIMEOUT`. Every decision names the preview it answers, so a late
+//! click or timer can't act on a newer dictation.
+
+use crate::dictation_context::DeliverySettings;
+use crate::state::{InjectionMethod, TrailingBehavior};
+use std::time::Duration;
+
+/// How long a preview waits for a decision before it is discarded.
+pub(crate) const PREVIEW_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A delivery held back for confirmation, with the settings it was resolved
+/// with when the dictation finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PendingInjection {
+    /// The recording that produced the text.
+    pub id: u64,
+    /// The transcript as shown to the user, without the trailing suffix.
+    pub text: String,
+    /// What follows `text` on Accept; a smart suffix looks at the caret then.
+    pub trailing: TrailingBehavior,
+    pub auto_paste: bool,
+    pub injection_blocked: bool,
+    pub paste_delay_ms: u64,
+    pub injection_method: InjectionMethod,
+    /// Fit `text` to the caret when it is accepted.
+    pub cursor_context: bool,
+}
+
+impl PendingInjection {
+    /// Hold `text` from recording `id` with the delivery it would have had.
+    pub fn new(id: u64, text: &str, delivery: &DeliverySettings) -> Self {
+        Self {
+            id,
+            text: text.to_string(),
+            trailing: delivery.trailing,
+            // File output suppresses auto-paste, exactly as direct delivery does.
+            auto_paste: delivery.auto_paste && !(delivery.save_transcript || delivery.save_audio),
+            injection_blocked: delivery.injection_blocked,
+            paste_delay_ms: delivery.paste_delay_ms,
+            injection_method: delivery.injection_method,
+            cursor_context: delivery.cursor_context,
+        }
+    }
+}
+
+/// Sent with `injection-pending` and returned by `get_pending_injection`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectionPending {
+    id: u64,
+    text: String,
+    timeout_ms: u64,
+}
+
+impl From<&PendingInjection> for InjectionPending {
+    fn from(pending: &PendingInjection) -> Self {
+        Self {
+            id: pending.id,
+            text: pending.text.clone(),
+            timeout_ms: PREVIEW_TIMEOUT.as_millis() as u64,
+        }
+    }
+}
+
+/// How a preview left the pending slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PreviewOutcome {
+    Accepted,
+    Discarded,
+    Expired,
+    Replaced,
+}
+
+/// Sent with `injection-resolved` once a preview is no longer pending.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InjectionResolved {
+    pub id: u64,
+    pub outcome: PreviewOutcome,
+}
+
+/// Take the pending preview out of `slot` only if it is still `id`.
+pub(crate) fn take_if(slot: &mut Option<PendingInjection>, id: u64) -> Option<PendingInjection> {
+    if slot.as_ref().is_some_and(|pending| pending.id == id) {
+        slot.take()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(id: u64) -> PendingInjection {
+        PendingInjection {
+            id,
+            text: "hello world".to_string(),
+            trailing: TrailingBehavior::Space,
+            auto_paste: true,
+            injection_blocked: false,
+            paste_delay_ms: 50,
+            injection_method: InjectionMethod::Native,
+            cursor_context: false,
+        }
+    }
+
+    #[test]
+    fn take_if_takes_matching_preview() {
+        let mut slot = Some(pending(7));
+        assert_eq!(take_if(&mut slot, 7), Some(pending(7)));
+        assert_eq!(slot, None);
+    }
+
+    #[test]
+    fn take_if_leaves_newer_preview_alone() {
+        let mut slot = Some(pending(8));
+        assert_eq!(take_if(&mut slot, 7), None);
+        assert_eq!(slot, Some(pending(8)));
+    }
+
+    #[test]
+    fn take_if_on_empty_slot_is_none() {
+        let mut slot = None;
+        assert_eq!(take_if(&mut slot, 7), None);
+    }
+
+    #[test]
+    fn pending_payload_shows_text_without_suffix() {
+        let payload = serde_json::to_value(InjectionPending::from(&pending(3))).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({ "id": 3, "text": "hello world", "timeoutMs": 30000 })
+        );
+    }
+
+    #[test]
+    fn resolved_outcome_serializes_lowercase() {
+        let payload = serde_json::to_value(InjectionResolved {
+            id: 3,
+            outcome: PreviewOutcome::Expired,
+        })
+        .unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({ "id": 3, "outcome": "expired" })
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::state::InjectionMethod;
 use arboard::Clipboard;
 use std::time::Instant;
 
@@ -15,6 +16,7 @@ pub(crate) fn read_clipboard_text() -> Result<String, String> {
 /// house-rule write (proposed text, before anything else is attempted) and
 /// the later restore of the user's original clipboard contents — instead of
 /// duplicating the `arboard` call.
+#[cfg(not(target_os = "linux"))]
 pub(crate) fn write_clipboard_text(text: &str) -> Result<(), String> {
     let mut clipboard =
         Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?;
@@ -23,12 +25,72 @@ pub(crate) fn write_clipboard_text(text: &str) -> Result<(), String> {
         .map_err(|e| format!("Failed to copy to clipboard: {}", e))
 }
 
+/// Linux selections are served by the process that set them, and arboard
+/// stops serving when its last `Clipboard` drops, so one is kept for the
+/// whole session.
+#[cfg(target_os = "linux")]
+static LINUX_CLIPBOARD: std::sync::Mutex<Option<Clipboard>> = std::sync::Mutex::new(None);
+
+/// Linux variant: Wayland sessions copy through `wl-copy`, which serves the
+/// selection from its own background process and reaches native Wayland
+/// apps. Without it (or on X11) arboard writes the X11 selection, which
+/// XWayland bridges for Wayland apps.
+#[cfg(target_os = "linux")]
+pub(crate) fn write_clipboard_text(text: &str) -> Result<(), String> {
+    use crate::MutexExt;
+
+    if is_wayland_session(|key| std::env::var_os(key)) {
+        match wl_copy(text) {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => return Err(format!("wl-copy failed: {}", status)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                tracing::info!(target: "pipeline", "wl-copy not installed, using the X11 clipboard");
+            }
+            Err(e) => return Err(format!("Failed to run wl-copy: {}", e)),
+        }
+    }
+
+    let mut slot = LINUX_CLIPBOARD.lock_or_recover();
+    let clipboard = match slot.take() {
+        Some(clipboard) => clipboard,
+        None => Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?,
+    };
+    let clipboard = slot.insert(clipboard);
+    clipboard
+        .set_text(text)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
+
+/// Pipe `text` into `wl-copy`. Its output is discarded rather than captured:
+/// the forked server keeps inherited pipes open until the next copy.
+#[cfg(target_os = "linux")]
+fn wl_copy(text: &str) -> std::io::Result<std::process::ExitStatus> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("wl-copy")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    child.wait()
+}
+
 /// Copy text to clipboard and optionally simulate Cmd+V paste.
 /// `delay_ms` controls the pause before pasting (window focus settling).
+/// `method` selects the keystroke source (per-app profile override).
 /// On paste failure, retries once after a 100ms backoff.
-pub fn inject_text(text: &str, auto_paste: bool, delay_ms: u64) -> Result<(), String> {
+pub fn inject_text(
+    text: &str,
+    auto_paste: bool,
+    delay_ms: u64,
+    method: InjectionMethod,
+) -> Result<(), String> {
     let inject_started = Instant::now();
-    tracing::info!(target: "pipeline", "inject_text called with auto_paste={}, delay_ms={}, text_len={}", auto_paste, delay_ms, text.len());
+    tracing::info!(target: "pipeline", "inject_text called with auto_paste={}, delay_ms={}, method={}, text_len={}", auto_paste, delay_ms, method.as_str(), text.len());
 
     // Skip if text is empty
     if text.trim().is_empty() {
@@ -88,19 +150,20 @@ pub fn inject_text(text: &str, auto_paste: bool, delay_ms: u64) -> Result<(), St
                 "inject timing"
             );
             tracing::warn!(target: "pipeline", "inject_text: focused element is not an editable text field — skipping paste, text in clipboard only");
-            return Err("No editable text field is focused".to_string());
+            return Err(NO_TEXT_TARGET.to_string());
         }
 
         // Simulate paste keystroke, retry once on failure
         let key_event_started = Instant::now();
-        let result = match simulate_paste() {
+        let result = match simulate_paste_with(method) {
             Ok(()) => Ok(()),
+            // A denied Automation permission won't change within 100ms.
+            Err(first_err) if is_automation_denial(&first_err) => Err(first_err),
             Err(first_err) => {
                 tracing::warn!(target: "pipeline", "inject_text: first paste attempt failed: {}, retrying in 100ms", first_err);
                 thread::sleep(Duration::from_millis(100));
-                simulate_paste().map_err(|retry_err| {
-                    format!("Auto-paste failed after retry: {}", retry_err)
-                })
+                simulate_paste_with(method)
+                    .map_err(|retry_err| format!("Auto-paste failed after retry: {}", retry_err))
             }
         };
         tracing::info!(
@@ -138,6 +201,144 @@ pub(crate) fn simulate_paste() -> Result<(), String> {
     }
 }
 
+/// Dispatch the paste keystroke through the requested source. System Events
+/// skips the native attempt entirely for apps that ignore synthetic HID input.
+#[cfg(target_os = "macos")]
+fn simulate_paste_with(method: InjectionMethod) -> Result<(), String> {
+    match method {
+        InjectionMethod::Native => simulate_paste(),
+        InjectionMethod::SystemEvents => simulate_paste_osascript(),
+    }
+}
+
+/// Linux and Windows have a single keystroke path (xdotool/wtype/ydotool or
+/// SendInput); the method is a macOS distinction.
+#[cfg(not(target_os = "macos"))]
+fn simulate_paste_with(_method: InjectionMethod) -> Result<(), String> {
+    simulate_paste()
+}
+
+/// Longest text, in caret steps, `replace_recent_text` will select back over.
+#[cfg(target_os = "macos")]
+const MAX_REPLACE_CHARS: usize = 2_000;
+
+/// Whether `c` joins the character before it into one caret step: combining
+/// marks, variation selectors, emoji skin tones and tags, and zero-width
+/// joiners.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn extends_cluster(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}' // Combining diacritics
+        | '\u{0483}'..='\u{0489}' // Cyrillic combining marks
+        | '\u{0591}'..='\u{05C7}' // Hebrew points
+        | '\u{0610}'..='\u{061A}' | '\u{064B}'..='\u{065F}' | '\u{0670}' // Arabic marks
+        | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}' | '\u{20D0}'..='\u{20FF}'
+        | '\u{3099}'..='\u{309A}' // Combining kana voicing marks
+        | '\u{200D}' // Zero-width joiner
+        | '\u{FE00}'..='\u{FE0F}' | '\u{FE20}'..='\u{FE2F}'
+        | '\u{1F3FB}'..='\u{1F3FF}' // Emoji skin tones
+        | '\u{E0020}'..='\u{E007F}' // Emoji tag sequences
+        | '\u{E0100}'..='\u{E01EF}'
+    )
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// How many Left presses select `text`. A caret steps over a whole
+/// user-perceived character, not a `char` or UTF-16 unit: `é` written as `e`
+/// plus a combining accent, a flag, and a family emoji joined by zero-width
+/// joiners are one step each. An approximation of Unicode grapheme clusters
+/// covering what dictation produces.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn caret_steps(text: &str) -> usize {
+    let mut steps = 0;
+    let mut previous: Option<char> = None;
+    let mut open_flag = false;
+    for c in text.chars() {
+        let joined = previous == Some('\u{200D}') || (c == '\n' && previous == Some('\r'));
+        if previous.is_some() && (extends_cluster(c) || joined) {
+            previous = Some(c);
+            continue;
+        }
+        if is_regional_indicator(c) && open_flag {
+            open_flag = false;
+            previous = Some(c);
+            continue;
+        }
+        open_flag = is_regional_indicator(c);
+        steps += 1;
+        previous = Some(c);
+    }
+    steps
+}
+
+/// Whether `text` has any right-to-left letters (Hebrew, Arabic, Syriac,
+/// Thaana, N'Ko, and their presentation forms). Arrow keys move the caret
+/// visually through bidirectional text, so Shift+Left from the end of it
+/// doesn't select what was pasted.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn has_right_to_left(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(c,
+            '\u{0590}'..='\u{08FF}'
+            | '\u{FB1D}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFF}'
+            | '\u{10800}'..='\u{10FFF}'
+            | '\u{1E800}'..='\u{1EFFF}'
+        )
+    })
+}
+
+/// Replace `previous`, just before the caret, with `text`: select it with
+/// Shift+Left, one press per [`caret_steps`], then paste over the selection.
+/// Assumes the caret hasn't moved since `previous` was pasted. Refuses
+/// right-to-left text, where Left doesn't walk back through it. Used to swap
+/// a dual-model draft for its refinement.
+#[cfg(target_os = "macos")]
+pub(crate) fn replace_recent_text(
+    previous: &str,
+    text: &str,
+    method: InjectionMethod,
+) -> Result<(), String> {
+    use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation, KeyCode};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let previous_chars = caret_steps(previous);
+    if previous_chars == 0 || previous_chars > MAX_REPLACE_CHARS {
+        return Err("Text is too long to replace in place".to_string());
+    }
+    if has_right_to_left(previous) {
+        return Err("Right-to-left text can't be replaced in place".to_string());
+    }
+    if !is_accessibility_enabled() {
+        return Err("Accessibility permission is not granted".to_string());
+    }
+    let source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|_| "could not create CGEvent source".to_string())?;
+    for _ in 0..previous_chars {
+        for key_down in [true, false] {
+            let event = CGEvent::new_keyboard_event(source.clone(), KeyCode::LEFT_ARROW, key_down)
+                .map_err(|_| "could not create Shift+Left event".to_string())?;
+            event.set_flags(CGEventFlags::CGEventFlagShift);
+            event.post(CGEventTapLocation::HID);
+        }
+    }
+    write_clipboard_text(text)?;
+    simulate_paste_with(method)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn replace_recent_text(
+    _previous: &str,
+    _text: &str,
+    _method: InjectionMethod,
+) -> Result<(), String> {
+    Err("Replacing pasted text is only supported on macOS".to_string())
+}
+
 #[cfg(target_os = "macos")]
 fn create_native_paste_events() -> Result<
     (core_graphics::event::CGEvent, core_graphics::event::CGEvent),
@@ -327,6 +528,12 @@ fn is_non_editable_desktop_role(role: &str) -> bool {
     )
 }
 
+/// Whether the frontmost app positively has no text field focused, by the
+/// same reading the paste guard uses. Any uncertainty answers `false`.
+pub(crate) fn no_text_field_focused() -> bool {
+    focused_field_state() == FocusedFieldState::NonEditable
+}
+
 /// Determine whether the frontmost app currently has an editable text element
 /// focused, returning a tri-state so callers can apply a false-negative bias.
 ///
@@ -551,8 +758,20 @@ fn classify_focused_role(role: &str) -> FocusedFieldState {
     }
 }
 
-/// Simulate Ctrl+V keystroke on Linux, supporting both X11 (xdotool) and Wayland (wtype).
-/// Detects Wayland via WAYLAND_DISPLAY; falls back gracefully when tools are not installed.
+/// Whether this is a Wayland session: `WAYLAND_DISPLAY` is set and non-empty.
+#[cfg(target_os = "linux")]
+fn is_wayland_session<F>(env_get: F) -> bool
+where
+    F: Fn(&str) -> Option<std::ffi::OsString>,
+{
+    env_get("WAYLAND_DISPLAY")
+        .map(|v| !v.is_empty())
+        .unwrap_or(false)
+}
+
+/// Simulate Ctrl+V keystroke on Linux, supporting both X11 (xdotool) and Wayland
+/// (wtype, then ydotool). Detects Wayland via WAYLAND_DISPLAY; falls back
+/// gracefully when tools are not installed.
 #[cfg(target_os = "linux")]
 fn simulate_paste() -> Result<(), String> {
     simulate_paste_linux(
@@ -567,12 +786,14 @@ where
     F: Fn(&str) -> Option<std::ffi::OsString>,
     G: FnMut(&str, &[&str]) -> std::io::Result<std::process::Output>,
 {
-    let is_wayland = env_get("WAYLAND_DISPLAY")
-        .map(|v| !v.is_empty())
-        .unwrap_or(false);
+    let is_wayland = is_wayland_session(env_get);
 
-    let wayland_candidates: [(&str, &[&str]); 2] = [
+    // ydotool covers compositors without the virtual-keyboard protocol wtype
+    // needs (GNOME, older KDE). It types through uinput, so it needs its
+    // `ydotoold` daemon; key codes are KEY_LEFTCTRL (29) and KEY_V (47).
+    let wayland_candidates: [(&str, &[&str]); 3] = [
         ("wtype", &["-M", "ctrl", "-k", "v"]),
+        ("ydotool", &["key", "29:1", "47:1", "47:0", "29:0"]),
         ("xdotool", &["key", "ctrl+v"]),
     ];
     let x11_candidates: [(&str, &[&str]); 1] = [("xdotool", &["key", "ctrl+v"])];
@@ -613,11 +834,86 @@ where
 
     tracing::warn!(
         target: "pipeline",
-        "simulate_paste: no paste tool available (install xdotool or wtype) — text remains in clipboard"
+        "simulate_paste: no paste tool available (install xdotool, wtype, or ydotool) — text remains in clipboard"
     );
     Ok(())
 }
 
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const VK_CONTROL: u16 = 0x11;
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const VK_V: u16 = 0x56;
+/// Ctrl+V as Windows virtual-key events, in order: `(virtual key, key up)`.
+/// Platform-neutral so the sequence is unit tested on every target.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const PASTE_KEY_SEQUENCE: [(u16, bool); 4] = [
+    (VK_CONTROL, false),
+    (VK_V, false),
+    (VK_V, true),
+    (VK_CONTROL, true),
+];
+
+/// Simulate Ctrl+V on Windows with one `SendInput` batch, so no physical
+/// keystroke can interleave. Windows has no Accessibility gate, but UIPI
+/// silently drops input aimed at an elevated window; the text stays on the
+/// clipboard either way.
+#[cfg(target_os = "windows")]
+fn simulate_paste() -> Result<(), String> {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP,
+    };
+
+    let inputs: Vec<INPUT> = PASTE_KEY_SEQUENCE
+        .iter()
+        .map(|&(vk, key_up)| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: vk,
+                    wScan: 0,
+                    dwFlags: if key_up { KEYEVENTF_KEYUP } else { 0 },
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        })
+        .collect();
+    // SAFETY: `inputs` is a live, correctly sized array of INPUT structs for
+    // the duration of the call.
+    let sent = unsafe {
+        SendInput(
+            inputs.len() as u32,
+            inputs.as_ptr(),
+            std::mem::size_of::<INPUT>() as i32,
+        )
+    };
+    if sent as usize == inputs.len() {
+        tracing::info!(target: "pipeline", "simulate_paste: SendInput completed");
+        Ok(())
+    } else {
+        Err(format!(
+            "SendInput delivered {} of {} key events",
+            sent,
+            inputs.len()
+        ))
+    }
+}
+
+/// Error `inject_text` returns when the paste guard skipped the paste because
+/// the focused element doesn't accept text. The text is on the clipboard.
+pub const NO_TEXT_TARGET: &str = "No editable text field is focused";
+
+/// Whether a paste error is the paste guard's skip rather than a failed paste.
+pub fn is_no_text_target(error: &str) -> bool {
+    error == NO_TEXT_TARGET
+}
+
+/// Whether a paste error came from macOS refusing osascript's Apple Events to
+/// System Events (`errAEEventNotPermitted`), i.e. Automation is denied.
+pub fn is_automation_denial(error: &str) -> bool {
+    error.contains("-1743") || error.contains("Not authorized to send Apple events")
+}
+
 /// Check if accessibility permission is granted (macOS)
 pub fn is_accessibility_enabled() -> bool {
     #[cfg(target_os = "macos")]
@@ -733,7 +1029,7 @@ mod tests {
     }
 
     #[test]
-    fn wayland_falls_back_to_xdotool_when_wtype_missing() {
+    fn wayland_falls_back_to_ydotool_when_wtype_missing() {
         let calls: RefCell<Vec<(String, Vec<String>)>> = RefCell::new(Vec::new());
         let result = simulate_paste_linux(env_with("WAYLAND_DISPLAY", "wayland-0"), |program, args| {
             calls
@@ -749,12 +1045,36 @@ mod tests {
         let calls = calls.borrow();
         assert_eq!(calls.len(), 2);
         assert_eq!(calls[0].0, "wtype");
-        assert_eq!(calls[1].0, "xdotool");
-        assert_eq!(calls[1].1, vec!["key", "ctrl+v"]);
+        assert_eq!(calls[1].0, "ydotool");
+        assert_eq!(calls[1].1, vec!["key", "29:1", "47:1", "47:0", "29:0"]);
     }
 
     #[test]
-    fn wayland_both_missing_is_graceful_ok() {
+    fn wayland_falls_back_to_xdotool_when_wtype_and_ydotool_missing() {
+        let calls: RefCell<Vec<(String, Vec<String>)>> = RefCell::new(Vec::new());
+        let result =
+            simulate_paste_linux(env_with("WAYLAND_DISPLAY", "wayland-0"), |program, args| {
+                calls.borrow_mut().push((
+                    program.to_string(),
+                    args.iter().map(|s| s.to_string()).collect(),
+                ));
+                if program == "xdotool" {
+                    ok_output()
+                } else {
+                    not_found_err()
+                }
+            });
+        assert!(result.is_ok());
+        let calls = calls.borrow();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0].0, "wtype");
+        assert_eq!(calls[1].0, "ydotool");
+        assert_eq!(calls[2].0, "xdotool");
+        assert_eq!(calls[2].1, vec!["key", "ctrl+v"]);
+    }
+
+    #[test]
+    fn wayland_all_missing_is_graceful_ok() {
         let calls: RefCell<Vec<String>> = RefCell::new(Vec::new());
         let result = simulate_paste_linux(env_with("WAYLAND_DISPLAY", "wayland-0"), |program, _args| {
             calls.borrow_mut().push(program.to_string());
@@ -762,9 +1082,30 @@ mod tests {
         });
         assert!(result.is_ok());
         let calls = calls.borrow();
-        assert_eq!(calls.len(), 2);
+        assert_eq!(calls.len(), 3);
         assert_eq!(calls[0], "wtype");
-        assert_eq!(calls[1], "xdotool");
+        assert_eq!(calls[1], "ydotool");
+        assert_eq!(calls[2], "xdotool");
+    }
+
+    #[test]
+    fn wayland_ydotool_exit_failure_surfaces() {
+        let result = simulate_paste_linux(
+            env_with("WAYLAND_DISPLAY", "wayland-0"),
+            |program, _args| {
+                if program == "wtype" {
+                    not_found_err()
+                } else {
+                    fail_output("failed to connect socket")
+                }
+            },
+        );
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("ydotool failed"),
+            "expected 'ydotool failed' in: {}",
+            msg
+        );
     }
 
     #[test]
@@ -1018,6 +1359,65 @@ mod focus_tests {
     }
 }
 
+#[cfg(test)]
+mod paste_tests {
+    use super::*;
+
+    #[test]
+    fn automation_denial_is_recognised_from_osascript_errors() {
+        assert!(is_automation_denial(
+            "osascript failed: 0:50: execution error: Not authorized to send Apple events to System Events. (-1743)"
+        ));
+        assert!(is_automation_denial(
+            "Auto-paste failed after retry: osascript failed: (-1743)"
+        ));
+        assert!(!is_automation_denial("could not create CGEvent source"));
+        assert!(!is_automation_denial("No editable text field is focused"));
+    }
+
+    #[test]
+    fn no_text_target_is_told_apart_from_paste_failures() {
+        assert!(is_no_text_target(NO_TEXT_TARGET));
+        assert!(!is_no_text_target(
+            "Auto-paste failed after retry: osascript failed: (-1743)"
+        ));
+        assert!(!is_no_text_target("could not create CGEvent source"));
+    }
+
+    #[test]
+    fn windows_paste_presses_and_releases_ctrl_around_v() {
+        assert_eq!(
+            PASTE_KEY_SEQUENCE,
+            [(0x11, false), (0x56, false), (0x56, true), (0x11, true)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod caret_tests {
+    use super::*;
+
+    #[test]
+    fn caret_steps_count_perceived_characters() {
+        assert_eq!(caret_steps("hello "), 6);
+        assert_eq!(caret_steps("今日は晴れです。"), 8);
+        // Outside the BMP: one char, two UTF-16 units, one step.
+        assert_eq!(caret_steps("𠮷野家"), 3);
+        assert_eq!(caret_steps("cafe\u{301}"), 4);
+        assert_eq!(caret_steps("ok 👍🏽"), 4);
+        assert_eq!(caret_steps("👨\u{200D}👩\u{200D}👧 🇯🇵🇫🇷"), 4);
+        assert_eq!(caret_steps("line\r\n"), 5);
+        assert_eq!(caret_steps(""), 0);
+    }
+
+    #[test]
+    fn right_to_left_scripts_are_detected() {
+        assert!(has_right_to_left("שלום עולם"));
+        assert!(has_right_to_left("send it to مرحبا"));
+        assert!(!has_right_to_left("Привет, 世界, καλημέρα 👋"));
+    }
+}
+
 /// Trigger the macOS accessibility permission prompt.
 /// Registers the app in System Settings > Privacy & Security > Accessibility
 /// and shows the system dialog. Returns current trust status.
@@ -0,0 +1,156 @@
+//! Guard against low-quality Bluetooth capture.
+//!
+//! When AirPods or another headset are both the input and the output, macOS
+//! moves them to the hands-free profile (HFP), which captures at 8 or 16 kHz
+//! through a narrowband codec, and transcription accuracy drops sharply. A
+//! recording's device is checked when it opens: Bluetooth at 16 kHz or less
+//! is reported as `degraded-input-warning`, and with `use_built_in` the
+//! recording moves to the built-in microphone instead.
+
+use crate::MutexExt;
+use std::sync::Mutex;
+
+/// Bluetooth captures at or below this rate are on the hands-free profile.
+const HANDS_FREE_MAX_RATE: u32 = 16_000;
+
+/// How a device is attached, from CoreAudio's transport type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transport {
+    BuiltIn,
+    Bluetooth,
+    Usb,
+    Other,
+}
+
+/// What to do when a recording opens a degraded device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LowQualityInputAction {
+    /// Record from it anyway and warn.
+    #[default]
+    Warn,
+    /// Switch to the built-in microphone when there is one.
+    UseBuiltIn,
+}
+
+impl LowQualityInputAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "warn" => Some(Self::Warn),
+            "use_built_in" => Some(Self::UseBuiltIn),
+            _ => None,
+        }
+    }
+}
+
+static ACTION: Mutex<LowQualityInputAction> = Mutex::new(LowQualityInputAction::Warn);
+
+pub fn set_action(action: LowQualityInputAction) {
+    *ACTION.lock_or_recover() = action;
+}
+
+pub fn action() -> LowQualityInputAction {
+    *ACTION.lock_or_recover()
+}
+
+/// Payload of `degraded-input-warning`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DegradedInput {
+    pub device_name: String,
+    pub sample_rate: u32,
+    pub transport: Transport,
+    /// The built-in microphone the recording moved to, if it did.
+    pub switched_to: Option<String>,
+}
+
+fn is_degraded(transport: Option<Transport>, sample_rate: u32) -> bool {
+    transport == Some(Transport::Bluetooth) && sample_rate <= HANDS_FREE_MAX_RATE
+}
+
+/// Check an opened device. `None` when it is fine or its transport can't be
+/// read.
+pub fn check(device_name: &str, sample_rate: u32) -> Option<DegradedInput> {
+    let transport = native::transport(device_name);
+    is_degraded(transport, sample_rate).then(|| DegradedInput {
+        device_name: device_name.to_string(),
+        sample_rate,
+        transport: Transport::Bluetooth,
+        switched_to: None,
+    })
+}
+
+/// Index of the first built-in device among `names`.
+pub fn built_in_index(names: &[String]) -> Option<usize> {
+    names
+        .iter()
+        .position(|name| native::transport(name) == Some(Transport::BuiltIn))
+}
+
+#[cfg(target_os = "macos")]
+mod native {
+    use super::Transport;
+    use crate::core_audio::{self, SCOPE_GLOBAL};
+
+    const TRANSPORT_TYPE: u32 = u32::from_be_bytes(*b"tran");
+    const TRANSPORT_BUILT_IN: u32 = u32::from_be_bytes(*b"bltn");
+    const TRANSPORT_BLUETOOTH: u32 = u32::from_be_bytes(*b"blue");
+    const TRANSPORT_BLUETOOTH_LE: u32 = u32::from_be_bytes(*b"blea");
+    const TRANSPORT_USB: u32 = u32::from_be_bytes(*b"usb ");
+
+    /// Transport of the input device cpal calls `name`.
+    pub(super) fn transport(name: &str) -> Option<Transport> {
+        let device = core_audio::input_devices()?
+            .into_iter()
+            .find(|&id| core_audio::device_name(id).as_deref() == Some(name))?;
+        Some(
+            match core_audio::u32_property(device, TRANSPORT_TYPE, SCOPE_GLOBAL)? {
+                TRANSPORT_BUILT_IN => Transport::BuiltIn,
+                TRANSPORT_BLUETOOTH | TRANSPORT_BLUETOOTH_LE => Transport::Bluetooth,
+                TRANSPORT_USB => Transport::Usb,
+                _ => Transport::Other,
+            },
+        )
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod native {
+    use super::Transport;
+
+    pub(super) fn transport(_name: &str) -> Option<Transport> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bluetooth_on_the_hands_free_profile_is_degraded() {
+        assert!(is_degraded(Some(Transport::Bluetooth), 8_000));
+        assert!(is_degraded(Some(Transport::Bluetooth), 16_000));
+        assert!(!is_degraded(Some(Transport::Bluetooth), 24_000));
+    }
+
+    #[test]
+    fn wired_and_unknown_devices_are_never_degraded() {
+        assert!(!is_degraded(Some(Transport::BuiltIn), 16_000));
+        assert!(!is_degraded(Some(Transport::Usb), 8_000));
+        assert!(!is_degraded(None, 8_000));
+    }
+
+    #[test]
+    fn actions_parse_from_their_setting_values() {
+        assert_eq!(
+            LowQualityInputAction::parse("warn"),
+            Some(LowQualityInputAction::Warn)
+        );
+        assert_eq!(
+            LowQualityInputAction::parse("use_built_in"),
+            Some(LowQualityInputAction::UseBuiltIn)
+        );
+        assert_eq!(LowQualityInputAction::parse("off"), None);
+    }
+}
@@ -0,0 +1,367 @@
+//! One running copy per build, and one keyboard listener per user.
+//!
+//! A second launch of the same build finds `instance.lock` in the app data
+//! directory held, asks the running copy to show its main window over
+//! `instance.sock`, and exits before opening any window, microphone, or
+//! hotkey. Dev and release builds have different identifiers, so they can
+//! run side by side; the hotkey is kept to one of them by a second lock,
+//! shared by every build, that `start_keyboard_listener` claims. Its file
+//! names the owner so the other copy can say who holds the hotkey.
+//!
+//! Both are `flock` locks, or on Windows files opened without write sharing:
+//! the kernel drops them when their process exits, so a crash never leaves a
+//! stale owner behind. Windows has no activation socket, so a second launch
+//! there exits without bringing the first forward.
+
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+const INSTANCE_LOCK: &str = "instance.lock";
+const ACTIVATION_SOCKET: &str = "instance.sock";
+/// Shared by every build, unlike the per-identifier app data directory.
+const SHARED_DIR: &str = "com.localdictation.shared";
+const KEYBOARD_LOCK: &str = "keyboard-listener.lock";
+const ACTIVATE: &[u8] = b"activate\n";
+
+/// A running copy of the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceInfo {
+    pub pid: u32,
+    pub identifier: String,
+    pub product_name: String,
+}
+
+/// Returned by `get_instance_status`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceStatus {
+    pub this_instance: InstanceInfo,
+    pub owns_keyboard_listener: bool,
+    /// The copy holding the keyboard listener, when one does.
+    pub keyboard_owner: Option<InstanceInfo>,
+}
+
+/// Whether this process should keep running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Claim {
+    Primary,
+    /// Another copy of this build is running and was asked to come forward.
+    Forwarded,
+}
+
+static THIS_INSTANCE: OnceLock<InstanceInfo> = OnceLock::new();
+/// Held for the life of the process.
+static INSTANCE_GUARD: Mutex<Option<File>> = Mutex::new(None);
+/// Held while this copy's keyboard listener runs.
+static KEYBOARD_GUARD: Mutex<Option<File>> = Mutex::new(None);
+#[cfg(unix)]
+static ACTIVATION_LISTENER: Mutex<Option<std::os::unix::net::UnixListener>> = Mutex::new(None);
+
+fn this_instance() -> InstanceInfo {
+    THIS_INSTANCE
+        .get()
+        .cloned()
+        .unwrap_or_else(|| InstanceInfo {
+            pid: std::process::id(),
+            identifier: String::new(),
+            product_name: String::new(),
+        })
+}
+
+fn shared_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(SHARED_DIR))
+}
+
+/// Become the running copy of `identifier`, or hand activation to the one
+/// that already is. Runs before the Tauri builder, so it reports problems on
+/// stderr; any failure other than a held lock lets this copy start.
+pub fn claim(identifier: &str, product_name: &str) -> Claim {
+    let _ = THIS_INSTANCE.set(InstanceInfo {
+        pid: std::process::id(),
+        identifier: identifier.to_string(),
+        product_name: product_name.to_string(),
+    });
+    let Some(dir) = dirs::data_dir().map(|dir| dir.join(identifier)) else {
+        return Claim::Primary;
+    };
+    if let Err(error) = std::fs::create_dir_all(&dir) {
+        eprintln!("instance: cannot create {}: {}", dir.display(), error);
+        return Claim::Primary;
+    }
+    match native::try_lock(&dir.join(INSTANCE_LOCK)) {
+        Ok(Some(file)) => {
+            *INSTANCE_GUARD.lock_or_recover() = Some(file);
+            native::bind_activation(&dir.join(ACTIVATION_SOCKET));
+            Claim::Primary
+        }
+        Ok(None) => {
+            if let Err(error) = native::send_activation(&dir.join(ACTIVATION_SOCKET)) {
+                eprintln!(
+                    "instance: {} is already running but did not answer: {}",
+                    product_name, error
+                );
+            }
+            Claim::Forwarded
+        }
+        Err(error) => {
+            eprintln!("instance: cannot lock {}: {}", dir.display(), error);
+            Claim::Primary
+        }
+    }
+}
+
+/// Show the main window whenever a second launch asks for it.
+pub fn listen_for_activation(app: tauri::AppHandle) {
+    native::listen_for_activation(app);
+}
+
+fn write_owner(file: &File, owner: &str) -> std::io::Result<()> {
+    use std::io::{Seek, SeekFrom, Write};
+    let mut file = file;
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(owner.as_bytes())
+}
+
+fn parse_owner(contents: &str) -> Option<InstanceInfo> {
+    serde_json::from_str(contents.trim()).ok()
+}
+
+fn read_owner(path: &Path) -> Option<InstanceInfo> {
+    parse_owner(&std::fs::read_to_string(path).ok()?)
+}
+
+fn owner_error(owner: Option<InstanceInfo>) -> String {
+    match owner {
+        Some(owner) => format!(
+            "The hotkey is in use by {} (pid {}). Quit it to use the hotkey here.",
+            owner.product_name, owner.pid
+        ),
+        None => "The hotkey is in use by another copy of Murmur. Quit it to use the hotkey here."
+            .to_string(),
+    }
+}
+
+/// Take the keyboard listener for this copy. Succeeds when this copy already
+/// holds it; fails naming the owner when another copy does.
+pub fn claim_keyboard_listener() -> Result<(), String> {
+    let mut guard = KEYBOARD_GUARD.lock_or_recover();
+    if guard.is_some() {
+        return Ok(());
+    }
+    let Some(dir) = shared_dir() else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    let path = dir.join(KEYBOARD_LOCK);
+    match native::try_lock(&path) {
+        Ok(Some(file)) => {
+            let owner = serde_json::to_string(&this_instance()).map_err(|e| e.to_string())?;
+            write_owner(&file, &owner)
+                .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+            *guard = Some(file);
+            Ok(())
+        }
+        Ok(None) => Err(owner_error(read_owner(&path))),
+        Err(error) => {
+            tracing::warn!(target: "keyboard", "keyboard listener lock unavailable: {}", error);
+            Ok(())
+        }
+    }
+}
+
+/// Let another copy take the keyboard listener.
+pub fn release_keyboard_listener() {
+    let Some(file) = KEYBOARD_GUARD.lock_or_recover().take() else {
+        return;
+    };
+    let _ = write_owner(&file, "");
+    // Closing the file drops the lock.
+    drop(file);
+}
+
+#[tauri::command]
+pub fn get_instance_status() -> InstanceStatus {
+    let owns_keyboard_listener = KEYBOARD_GUARD.lock_or_recover().is_some();
+    let keyboard_owner = if owns_keyboard_listener {
+        Some(this_instance())
+    } else {
+        shared_dir().and_then(|dir| {
+            let path = dir.join(KEYBOARD_LOCK);
+            // An owner file left by a copy that has exited is not a claim.
+            match native::try_lock(&path) {
+                Ok(None) => read_owner(&path),
+                _ => None,
+            }
+        })
+    };
+    InstanceStatus {
+        this_instance: this_instance(),
+        owns_keyboard_listener,
+        keyboard_owner,
+    }
+}
+
+#[cfg(unix)]
+mod native {
+    use super::ACTIVATE;
+    use crate::MutexExt;
+    use std::fs::{File, OpenOptions};
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::Path;
+    use tauri::Manager;
+
+    /// The locked file, or None when another process holds the lock.
+    pub(super) fn try_lock(path: &Path) -> std::io::Result<Option<File>> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+            return Ok(Some(file));
+        }
+        let error = std::io::Error::last_os_error();
+        if error.raw_os_error() == Some(libc::EWOULDBLOCK) {
+            Ok(None)
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Called with the instance lock held, so a socket file already there was
+    /// left by a copy that has exited.
+    pub(super) fn bind_activation(path: &Path) {
+        let _ = std::fs::remove_file(path);
+        match UnixListener::bind(path) {
+            Ok(listener) => *super::ACTIVATION_LISTENER.lock_or_recover() = Some(listener),
+            Err(error) => eprintln!("instance: cannot listen on {}: {}", path.display(), error),
+        }
+    }
+
+    pub(super) fn send_activation(path: &Path) -> std::io::Result<()> {
+        UnixStream::connect(path)?.write_all(ACTIVATE)
+    }
+
+    pub(super) fn listen_for_activation(app: tauri::AppHandle) {
+        let Some(listener) = super::ACTIVATION_LISTENER.lock_or_recover().take() else {
+            return;
+        };
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut request = [0u8; ACTIVATE.len()];
+                if stream.read_exact(&mut request).is_err() || request != ACTIVATE {
+                    continue;
+                }
+                tracing::info!(target: "system", "second launch forwarded — showing main window");
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.unminimize();
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+        });
+    }
+}
+
+#[cfg(windows)]
+mod native {
+    use std::fs::{File, OpenOptions};
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::path::Path;
+
+    /// `FILE_SHARE_READ`: other processes may read the owner but not open the
+    /// file for writing while this handle is open.
+    const FILE_SHARE_READ: u32 = 0x1;
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+
+    /// The locked file, or None when another process holds the lock.
+    pub(super) fn try_lock(path: &Path) -> std::io::Result<Option<File>> {
+        match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .share_mode(FILE_SHARE_READ)
+            .open(path)
+        {
+            Ok(file) => Ok(Some(file)),
+            Err(error) if error.raw_os_error() == Some(ERROR_SHARING_VIOLATION) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub(super) fn bind_activation(_path: &Path) {}
+
+    pub(super) fn send_activation(_path: &Path) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn listen_for_activation(_app: tauri::AppHandle) {}
+}
+
+/// No lock: every copy is the primary one.
+#[cfg(not(any(unix, windows)))]
+mod native {
+    use std::fs::{File, OpenOptions};
+    use std::path::Path;
+
+    pub(super) fn try_lock(path: &Path) -> std::io::Result<Option<File>> {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map(Some)
+    }
+
+    pub(super) fn bind_activation(_path: &Path) {}
+
+    pub(super) fn send_activation(_path: &Path) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn listen_for_activation(_app: tauri::AppHandle) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owner_files_round_trip_and_tolerate_being_empty() {
+        let owner = InstanceInfo {
+            pid: 4242,
+            identifier: "com.localdictation.dev".to_string(),
+            product_name: "Local Dictation Dev".to_string(),
+        };
+        let contents = serde_json::to_string(&owner).unwrap();
+        assert_eq!(parse_owner(&contents), Some(owner.clone()));
+        assert_eq!(parse_owner(""), None);
+        assert!(owner_error(Some(owner)).contains("Local Dictation Dev (pid 4242)"));
+    }
+
+    #[cfg(any(unix, windows))]
+    #[test]
+    fn a_held_lock_is_reported_as_taken_until_released() {
+        let dir = std::env::temp_dir().join(format!("murmur-instance-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(KEYBOARD_LOCK);
+        let held = native::try_lock(&path)
+            .unwrap()
+            .expect("first lock succeeds");
+        assert!(native::try_lock(&path).unwrap().is_none());
+        drop(held);
+        assert!(native::try_lock(&path).unwrap().is_some());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
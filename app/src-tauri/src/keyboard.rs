@@ -12,30 +12,116 @@
 //!
 //! Both modes reject modifier+letter combos (e.g. Shift+A).
 
+use crate::events::{self, HotkeyEvent};
 use crate::MutexExt;
 #[cfg(target_os = "macos")]
 use rdev::set_is_main_thread;
 use rdev::{listen, Event, EventType, Key};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tauri::{Emitter, Manager};
 
-/// Max duration a single tap can be held before it's rejected
+/// Default max duration a single tap can be held before it's rejected
 const MAX_HOLD_DURATION_MS: u128 = 200;
 
-/// Max gap between first key-up and second key-down
+/// Default max gap between first key-up and second key-down
 const DOUBLE_TAP_WINDOW_MS: u128 = 400;
 
-/// Cooldown after firing to prevent triple-tap spam
+/// Default cooldown after firing to prevent triple-tap spam
 const COOLDOWN_MS: u128 = 50;
 
-/// Cooldown after hold-down stop to prevent accidental re-trigger
+/// Default cooldown after hold-down stop to prevent accidental re-trigger
 const HOLD_DOWN_COOLDOWN_MS: u128 = 50;
 
+/// Bounds `HotkeyTiming::validate` accepts.
+const TAP_MS_RANGE: std::ops::RangeInclusive<u64> = 100..=600;
+const DOUBLE_TAP_WINDOW_MS_RANGE: std::ops::RangeInclusive<u64> = 150..=1_000;
+const COOLDOWN_MS_RANGE: std::ops::RangeInclusive<u64> = 0..=500;
+const HOLD_PROMOTION_MS_MAX: u64 = 1_000;
+
 /// Warn if the active listener sees no callbacks for this long.
 const TAP_SILENCE_WARNING_MS: u64 = 5 * 60 * 1000;
 
+/// How often the health monitor checks the listener thread and permission.
+const HEALTH_CHECK_INTERVAL_SECS: u64 = 10;
+
+/// Automatic re-registrations of a dead listener before asking the user.
+const MAX_LISTENER_RESTARTS: u32 = 3;
+
+/// User-tunable thresholds for the dictation hotkey. The defaults suit most
+/// people; fast typists hit false double-taps with a long window, and slow
+/// tappers get their taps rejected as holds with a short one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyTiming {
+    /// Longest press that still counts as a tap.
+    pub max_tap_ms: u64,
+    /// Longest gap between the two taps of a double-tap.
+    pub double_tap_window_ms: u64,
+    /// Quiet period after a double-tap fires.
+    pub cooldown_ms: u64,
+    /// Quiet period after a hold-down recording stops.
+    pub hold_cooldown_ms: u64,
+    /// In Both mode, how long a press must last before it becomes a hold.
+    pub hold_promotion_ms: u64,
+}
+
+impl HotkeyTiming {
+    pub const DEFAULT: Self = Self {
+        max_tap_ms: MAX_HOLD_DURATION_MS as u64,
+        double_tap_window_ms: DOUBLE_TAP_WINDOW_MS as u64,
+        cooldown_ms: COOLDOWN_MS as u64,
+        hold_cooldown_ms: HOLD_DOWN_COOLDOWN_MS as u64,
+        hold_promotion_ms: MAX_HOLD_DURATION_MS as u64,
+    };
+
+    /// Reject values outside the supported bounds. The promotion delay can't
+    /// be shorter than a tap, or a Both-mode tap would start a hold.
+    pub fn validate(&self) -> Result<(), String> {
+        let check = |name: &str, value: u64, range: &std::ops::RangeInclusive<u64>| {
+            if range.contains(&value) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{} must be between {} and {} ms, got {}",
+                    name,
+                    range.start(),
+                    range.end(),
+                    value
+                ))
+            }
+        };
+        check("maxTapMs", self.max_tap_ms, &TAP_MS_RANGE)?;
+        check(
+            "doubleTapWindowMs",
+            self.double_tap_window_ms,
+            &DOUBLE_TAP_WINDOW_MS_RANGE,
+        )?;
+        check("cooldownMs", self.cooldown_ms, &COOLDOWN_MS_RANGE)?;
+        check("holdCooldownMs", self.hold_cooldown_ms, &COOLDOWN_MS_RANGE)?;
+        check(
+            "holdPromotionMs",
+            self.hold_promotion_ms,
+            &(self.max_tap_ms..=HOLD_PROMOTION_MS_MAX),
+        )
+    }
+}
+
+impl Default for HotkeyTiming {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Timing of the running dictation listener, for the timers spawned off the
+/// rdev thread. The detectors hold their own copy.
+static TIMING: Mutex<HotkeyTiming> = Mutex::new(HotkeyTiming::DEFAULT);
+
+pub fn timing() -> HotkeyTiming {
+    *TIMING.lock_or_recover()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum DetectorState {
     Idle,
@@ -72,6 +158,7 @@ struct DoubleTapDetector {
     state_entered_at: Instant,
     last_fired_at: Option<Instant>,
     last_rejection: Option<RejectionReason>,
+    timing: HotkeyTiming,
 }
 
 impl DoubleTapDetector {
@@ -83,6 +170,7 @@ impl DoubleTapDetector {
             state_entered_at: Instant::now(),
             last_fired_at: None,
             last_rejection: None,
+            timing: HotkeyTiming::DEFAULT,
         }
     }
 
@@ -105,14 +193,23 @@ impl DoubleTapDetector {
         self.state_entered_at.elapsed().as_millis()
     }
 
+    fn held_too_long(&self) -> bool {
+        self.elapsed_ms() > u128::from(self.timing.max_tap_ms)
+    }
+
+    fn window_expired(&self) -> bool {
+        self.elapsed_ms() > u128::from(self.timing.double_tap_window_ms)
+    }
+
     fn in_cooldown(&self) -> bool {
         self.last_fired_at
-            .map(|t| t.elapsed().as_millis() < COOLDOWN_MS)
+            .map(|t| t.elapsed().as_millis() < u128::from(self.timing.cooldown_ms))
             .unwrap_or(false)
     }
 
     fn log_rejection(&mut self, reason: RejectionReason, event_type: &EventType) {
         self.last_rejection = Some(reason);
+        note_rejected(reason);
         tracing::info!(
             target: "keyboard",
             detector = "double_tap",
@@ -151,7 +248,7 @@ impl DoubleTapDetector {
             DetectorState::WaitingFirstUp => {
                 match event_type {
                     EventType::KeyRelease(key) if is_same_modifier(*key, target) => {
-                        if self.elapsed_ms() <= MAX_HOLD_DURATION_MS {
+                        if !self.held_too_long() {
                             if self.recording {
                                 // Single tap to stop — fire immediately
                                 self.last_fired_at = Some(Instant::now());
@@ -173,14 +270,14 @@ impl DoubleTapDetector {
                     EventType::KeyPress(key) if is_same_modifier(*key, target) => {
                         // Key repeat event — ignore, stay in same state
                         // But check if we've been held too long
-                        if self.elapsed_ms() > MAX_HOLD_DURATION_MS {
+                        if self.held_too_long() {
                             self.log_rejection(RejectionReason::HeldTooLong, event_type);
                             self.reset();
                         }
                     }
                     _ => {
                         // Check timeout
-                        if self.elapsed_ms() > MAX_HOLD_DURATION_MS {
+                        if self.held_too_long() {
                             self.log_rejection(RejectionReason::HeldTooLong, event_type);
                             self.reset();
                         }
@@ -190,7 +287,7 @@ impl DoubleTapDetector {
             }
 
             DetectorState::WaitingSecondDown => {
-                if self.elapsed_ms() > DOUBLE_TAP_WINDOW_MS {
+                if self.window_expired() {
                     self.log_rejection(RejectionReason::SecondTapExpired, event_type);
                     self.reset();
                     return false;
@@ -212,7 +309,7 @@ impl DoubleTapDetector {
             DetectorState::WaitingSecondUp => {
                 match event_type {
                     EventType::KeyRelease(key) if is_same_modifier(*key, target) => {
-                        if self.elapsed_ms() <= MAX_HOLD_DURATION_MS {
+                        if !self.held_too_long() {
                             // Double-tap detected!
                             self.last_fired_at = Some(Instant::now());
                             self.reset();
@@ -229,13 +326,13 @@ impl DoubleTapDetector {
                     }
                     EventType::KeyPress(key) if is_same_modifier(*key, target) => {
                         // Key repeat — check timeout
-                        if self.elapsed_ms() > MAX_HOLD_DURATION_MS {
+                        if self.held_too_long() {
                             self.log_rejection(RejectionReason::HeldTooLong, event_type);
                             self.reset();
                         }
                     }
                     _ => {
-                        if self.elapsed_ms() > MAX_HOLD_DURATION_MS {
+                        if self.held_too_long() {
                             self.log_rejection(RejectionReason::HeldTooLong, event_type);
                             self.reset();
                         }
@@ -257,7 +354,7 @@ impl DoubleTapDetector {
     fn expire_second_tap_wait(&mut self, started_at: Instant) -> Option<u64> {
         if self.state != DetectorState::WaitingSecondDown
             || self.state_entered_at != started_at
-            || self.elapsed_ms() <= DOUBLE_TAP_WINDOW_MS
+            || !self.window_expired()
         {
             return None;
         }
@@ -268,10 +365,63 @@ impl DoubleTapDetector {
     }
 }
 
+// -- Physical key identification --
+
+/// Virtual key codes of the modifiers Murmur can bind. rdev already names
+/// these keys from the physical code rather than the layout, but some layouts
+/// and external keyboards still surface one as `Key::Unknown(code)`; matching
+/// goes through [`physical_key`] so those hit the same hotkey.
+#[cfg(target_os = "macos")]
+const MODIFIER_SCANCODES: &[(u32, Key)] = &[
+    (56, Key::ShiftLeft),
+    (60, Key::ShiftRight),
+    (58, Key::Alt),
+    (61, Key::AltGr),
+    (59, Key::ControlLeft),
+    (62, Key::ControlRight),
+    (55, Key::MetaLeft),
+    (54, Key::MetaRight),
+];
+/// X11 keycodes (evdev + 8) for the same modifiers.
+#[cfg(not(target_os = "macos"))]
+const MODIFIER_SCANCODES: &[(u32, Key)] = &[
+    (50, Key::ShiftLeft),
+    (62, Key::ShiftRight),
+    (64, Key::Alt),
+    (108, Key::AltGr),
+    (37, Key::ControlLeft),
+    (105, Key::ControlRight),
+    (133, Key::MetaLeft),
+    (134, Key::MetaRight),
+];
+
+/// Name an unrecognized key by its physical code when it is a known modifier.
+fn physical_key(key: Key) -> Key {
+    match key {
+        Key::Unknown(code) => MODIFIER_SCANCODES
+            .iter()
+            .find(|(scancode, _)| *scancode == code)
+            .map(|(_, named)| *named)
+            .unwrap_or(key),
+        _ => key,
+    }
+}
+
+/// The physical code behind a key, when known.
+fn scancode(key: Key) -> Option<u32> {
+    match key {
+        Key::Unknown(code) => Some(code),
+        _ => MODIFIER_SCANCODES
+            .iter()
+            .find(|(_, named)| *named == key)
+            .map(|(scancode, _)| *scancode),
+    }
+}
+
 /// Check if a key is any modifier key
 fn is_modifier(key: Key) -> bool {
     matches!(
-        key,
+        physical_key(key),
         Key::ShiftLeft
             | Key::ShiftRight
             | Key::Alt
@@ -283,9 +433,10 @@ fn is_modifier(key: Key) -> bool {
     )
 }
 
-/// Check if two keys are the same modifier, using strict equality
+/// Check if two keys are the same physical modifier. Left and right stay
+/// distinct.
 fn is_same_modifier(a: Key, b: Key) -> bool {
-    a == b
+    physical_key(a) == physical_key(b)
 }
 
 // -- Hold-down detector --
@@ -307,6 +458,7 @@ struct HoldDownDetector {
     state: HoldState,
     target_key: Option<Key>,
     last_stopped_at: Option<Instant>,
+    cooldown_ms: u64,
 }
 
 impl HoldDownDetector {
@@ -315,6 +467,7 @@ impl HoldDownDetector {
             state: HoldState::Idle,
             target_key: None,
             last_stopped_at: None,
+            cooldown_ms: HOLD_DOWN_COOLDOWN_MS as u64,
         }
     }
 
@@ -336,11 +489,12 @@ impl HoldDownDetector {
 
     fn in_cooldown(&self) -> bool {
         self.last_stopped_at
-            .map(|t| t.elapsed().as_millis() < HOLD_DOWN_COOLDOWN_MS)
+            .map(|t| t.elapsed().as_millis() < u128::from(self.cooldown_ms))
             .unwrap_or(false)
     }
 
     fn log_rejection(&self, reason: RejectionReason, event_type: &EventType) {
+        note_rejected(reason);
         tracing::info!(
             target: "keyboard",
             detector = "hold_down",
@@ -419,6 +573,12 @@ fn should_surface_hotkey_rejection(reason: RejectionReason, mode: DetectorMode)
         && matches!(mode, DetectorMode::DoubleTap | DetectorMode::Both)
 }
 
+/// Emit a dictation hotkey event, noting it in a diagnostics recording.
+fn emit_hotkey(app_handle: &tauri::AppHandle, event: HotkeyEvent) {
+    note_emitted(event.name());
+    events::emit_hotkey(app_handle, event);
+}
+
 fn emit_hotkey_rejection(
     app_handle: &tauri::AppHandle,
     reason: RejectionReason,
@@ -427,6 +587,7 @@ fn emit_hotkey_rejection(
     if !should_surface_hotkey_rejection(reason, mode) {
         return;
     }
+    note_emitted("hotkey-tap-rejected");
 
     let _ = app_handle.emit(
         "hotkey-tap-rejected",
@@ -445,7 +606,7 @@ fn schedule_second_tap_expiry(
 ) {
     std::thread::spawn(move || {
         std::thread::sleep(std::time::Duration::from_millis(
-            DOUBLE_TAP_WINDOW_MS as u64 + 5,
+            timing().double_tap_window_ms + 5,
         ));
 
         if !listener_context_matches(mode, listener_generation) {
@@ -591,11 +752,14 @@ fn trace_raw_callback(event: &Event, mode: DetectorMode) {
         modifier_edge = modifier_edge(&event.event_type),
         is_processing = IS_PROCESSING.load(Ordering::SeqCst),
         app_disabled = APP_DISABLED.load(Ordering::SeqCst),
+        focus_gated = FOCUS_GATED.load(Ordering::SeqCst),
+        quiet_hours_gated = QUIET_HOURS_GATED.load(Ordering::SeqCst),
         "raw rdev callback"
     );
 }
 
 fn log_rejection(reason: RejectionReason, mode: DetectorMode, event_type: &EventType) {
+    note_rejected(reason);
     let (double_tap_state, hold_state) = detector_state_snapshot();
     tracing::info!(
         target: "keyboard",
@@ -623,6 +787,13 @@ static IS_PROCESSING: AtomicBool = AtomicBool::new(false);
 /// (which still cancels in-progress recordings). Set by the `set_app_disabled`
 /// Tauri command. Thread-safe, lock-free.
 static APP_DISABLED: AtomicBool = AtomicBool::new(false);
+/// When true, the hotkey is gated like `APP_DISABLED` because a Focus mode the
+/// user chose is on. Kept separate so a Focus ending never re-enables an app the
+/// user disabled by hand. Set by `system_status`.
+static FOCUS_GATED: AtomicBool = AtomicBool::new(false);
+/// When true, the hotkey is gated because the scheduled quiet hours are in
+/// effect. Set by `quiet_hours`.
+static QUIET_HOURS_GATED: AtomicBool = AtomicBool::new(false);
 static LAST_RDEV_CALLBACK_AT_MS: AtomicU64 = AtomicU64::new(0);
 static LAST_TAP_SILENCE_WARNING_AT_MS: AtomicU64 = AtomicU64::new(0);
 
@@ -689,18 +860,7 @@ pub fn set_app_disabled(disabled: bool) {
     let was_disabled = APP_DISABLED.swap(disabled, Ordering::SeqCst);
     if !was_disabled && disabled {
         // Transitioning false → true: clean up any partial detector state
-        HOLD_PROMOTED.store(false, Ordering::SeqCst);
-        HOLD_PRESS_COUNTER.fetch_add(1, Ordering::SeqCst);
-        if let Ok(mut det) = HOLD_DOWN_DETECTOR.lock() {
-            if let Some(d) = det.as_mut() {
-                d.reset();
-            }
-        }
-        if let Ok(mut det) = DOUBLE_TAP_DETECTOR.lock() {
-            if let Some(d) = det.as_mut() {
-                d.reset();
-            }
-        }
+        reset_gated_detectors();
         tracing::info!(target: "keyboard", "app disabled: hotkey events gated");
     } else if was_disabled && !disabled {
         tracing::info!(target: "keyboard", "app enabled: hotkey events resumed");
@@ -712,6 +872,47 @@ pub fn is_app_disabled() -> bool {
     APP_DISABLED.load(Ordering::SeqCst)
 }
 
+/// Gate or release the hotkey for the active Focus mode. Same detector cleanup
+/// as `set_app_disabled`, but independent of the user's own disabled toggle.
+pub fn set_focus_gated(gated: bool) {
+    let was_gated = FOCUS_GATED.swap(gated, Ordering::SeqCst);
+    if !was_gated && gated {
+        reset_gated_detectors();
+        tracing::info!(target: "keyboard", "focus mode: hotkey events gated");
+    } else if was_gated && !gated {
+        tracing::info!(target: "keyboard", "focus mode ended: hotkey events resumed");
+    }
+}
+
+/// Gate or release the hotkey for scheduled quiet hours. Same detector cleanup
+/// as `set_app_disabled`.
+pub fn set_quiet_hours_gated(gated: bool) {
+    let was_gated = QUIET_HOURS_GATED.swap(gated, Ordering::SeqCst);
+    if !was_gated && gated {
+        reset_gated_detectors();
+        tracing::info!(target: "keyboard", "quiet hours: hotkey events gated");
+    } else if was_gated && !gated {
+        tracing::info!(target: "keyboard", "quiet hours ended: hotkey events resumed");
+    }
+}
+
+/// Invalidate pending hold-promotion timers and partial detector state so
+/// lifting a gate doesn't produce phantom events.
+fn reset_gated_detectors() {
+    HOLD_PROMOTED.store(false, Ordering::SeqCst);
+    HOLD_PRESS_COUNTER.fetch_add(1, Ordering::SeqCst);
+    if let Ok(mut det) = HOLD_DOWN_DETECTOR.lock() {
+        if let Some(d) = det.as_mut() {
+            d.reset();
+        }
+    }
+    if let Ok(mut det) = DOUBLE_TAP_DETECTOR.lock() {
+        if let Some(d) = det.as_mut() {
+            d.reset();
+        }
+    }
+}
+
 // -- Global listener state --
 
 static LISTENER_ACTIVE: AtomicBool = AtomicBool::new(false);
@@ -744,11 +945,54 @@ static TRANSFORM_HOLD_CONTEXT: Mutex<Option<(u64, Instant)>> = Mutex::new(None);
 /// listener.
 static TRANSFORM_ACTIVE: AtomicBool = AtomicBool::new(false);
 
+// -- Dictation profile hotkeys --
+//
+// One plain hold-down detector per hotkey-bound dictation profile, fed from
+// the same shared rdev callback as the dictation detectors and only while the
+// dictation listener is active. Each detector emits `profile-hold-start` /
+// `profile-hold-stop` carrying its profile id so the recording starts with
+// that profile's overrides. Profile keys are hold-down in every mode; they
+// never take part in the main hotkey's double-tap or Both-mode timing.
+static PROFILE_DETECTORS: Mutex<Vec<(String, HoldDownDetector)>> = Mutex::new(Vec::new());
+
+// -- Append-mode commit hotkey --
+//
+// A plain hold-down detector fed alongside the profile detectors. Pressing its
+// key emits `append-commit-key`; the frontend commits the buffered takes. Only
+// bound while append mode is on.
+static APPEND_COMMIT_DETECTOR: Mutex<Option<HoldDownDetector>> = Mutex::new(None);
+
+// -- Paste-last hotkey --
+//
+// Same shape as the commit key: pressing it emits `paste-last-key` and the
+// frontend pastes the newest history entry again.
+static PASTE_LAST_DETECTOR: Mutex<Option<HoldDownDetector>> = Mutex::new(None);
+
+// -- Copied-audio hotkey --
+//
+// Pressing it emits `clipboard-audio-key` and the frontend transcribes the
+// audio file on the clipboard or selected in Finder.
+static CLIPBOARD_AUDIO_DETECTOR: Mutex<Option<HoldDownDetector>> = Mutex::new(None);
+
+// -- Marker hotkey --
+//
+// Pressing it during a recording notes a section break (`markers.rs`). Handled
+// here rather than in the frontend so the press is timed where it happened.
+static MARKER_DETECTOR: Mutex<Option<HoldDownDetector>> = Mutex::new(None);
+
 /// Start the keyboard listener. Spawns the rdev listener thread if not already running.
 /// If already running, just updates the target key, mode, and re-enables.
 ///
-/// `mode` should be `"double_tap"`, `"hold_down"`, or `"both"`.
-pub fn start_listener(app_handle: tauri::AppHandle, hotkey: &str, mode: &str) {
+/// `mode` should be `"double_tap"`, `"hold_down"`, or `"both"`. Fails, leaving
+/// the listener as it was, when `timing` is out of bounds.
+pub fn start_listener(
+    app_handle: tauri::AppHandle,
+    hotkey: &str,
+    mode: &str,
+    timing: HotkeyTiming,
+) -> Result<(), String> {
+    timing.validate()?;
+    *TIMING.lock_or_recover() = timing;
     LISTENER_GENERATION.fetch_add(1, Ordering::SeqCst);
     let target = hotkey_to_rdev_key(hotkey);
 
@@ -770,55 +1014,31 @@ pub fn start_listener(app_handle: tauri::AppHandle, hotkey: &str, mode: &str) {
             let mut det = DOUBLE_TAP_DETECTOR
                 .lock()
                 .unwrap_or_else(|p| p.into_inner());
-            match det.as_mut() {
-                Some(d) => d.set_target(target),
-                None => {
-                    let mut d = DoubleTapDetector::new();
-                    d.set_target(target);
-                    *det = Some(d);
-                }
-            }
+            let d = det.get_or_insert_with(DoubleTapDetector::new);
+            d.timing = timing;
+            d.set_target(target);
         }
         DetectorMode::HoldDown => {
             let mut det = HOLD_DOWN_DETECTOR.lock().unwrap_or_else(|p| p.into_inner());
-            match det.as_mut() {
-                Some(d) => {
-                    let _ = d.set_target(target);
-                }
-                None => {
-                    let mut d = HoldDownDetector::new();
-                    let _ = d.set_target(target);
-                    *det = Some(d);
-                }
-            }
+            let d = det.get_or_insert_with(HoldDownDetector::new);
+            d.cooldown_ms = timing.hold_cooldown_ms;
+            let _ = d.set_target(target);
         }
         DetectorMode::Both => {
             // Initialize both detectors with the same target key
             {
                 let mut det = HOLD_DOWN_DETECTOR.lock().unwrap_or_else(|p| p.into_inner());
-                match det.as_mut() {
-                    Some(d) => {
-                        let _ = d.set_target(target);
-                    }
-                    None => {
-                        let mut d = HoldDownDetector::new();
-                        let _ = d.set_target(target);
-                        *det = Some(d);
-                    }
-                }
+                let d = det.get_or_insert_with(HoldDownDetector::new);
+                d.cooldown_ms = timing.hold_cooldown_ms;
+                let _ = d.set_target(target);
             }
             {
                 let mut det = DOUBLE_TAP_DETECTOR
                     .lock()
                     .unwrap_or_else(|p| p.into_inner());
-                match det.as_mut() {
-                    Some(d) => d.set_target(target),
-                    None => {
-                        let mut d = DoubleTapDetector::new();
-                        d.set_target(target);
-                        *det = Some(d);
-                    }
-                }
+                let d = det.get_or_insert_with(DoubleTapDetector::new);
+                d.timing = timing;
+                d.set_target(target);
             }
         }
     }
@@ -828,6 +1048,7 @@ pub fn start_listener(app_handle: tauri::AppHandle, hotkey: &str, mode: &str) {
     LAST_TAP_SILENCE_WARNING_AT_MS.store(0, Ordering::SeqCst);
 
     ensure_listener_thread_spawned(app_handle);
+    Ok(())
 }
 
 /// Spawn the single shared `rdev::listen()` thread if it hasn't been spawned
@@ -854,7 +1075,12 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
             set_is_main_thread(false);
             tracing::info!(target: "keyboard", "rdev listener thread started");
 
-            let callback = move |event: Event| {
+            let handle_event = move |event: &Event| {
+                // A hotkey diagnostic sees events even while both listeners
+                // are off, so it can tell a dead tap from a wrong key.
+                if let Some(capture) = KEY_CAPTURE.lock_or_recover().as_mut() {
+                    capture.record(&event.event_type);
+                }
                 // The dictation listener (LISTENER_ACTIVE) and the transform
                 // hotkey (TRANSFORM_ACTIVE) are independent; either one being
                 // active is enough to keep processing events on this thread.
@@ -865,13 +1091,16 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                 }
                 LAST_RDEV_CALLBACK_AT_MS.store(now_unix_ms(), Ordering::SeqCst);
                 LAST_TAP_SILENCE_WARNING_AT_MS.store(0, Ordering::SeqCst);
+                if LISTENER_RESTARTS.load(Ordering::Relaxed) != 0 {
+                    LISTENER_RESTARTS.store(0, Ordering::Relaxed);
+                }
 
                 let mode = {
                     let m = ACTIVE_MODE.lock_or_recover();
                     *m
                 };
                 let listener_generation = LISTENER_GENERATION.load(Ordering::SeqCst);
-                trace_raw_callback(&event, mode);
+                trace_raw_callback(event, mode);
 
                 // Escape key: cancel recording/transcription regardless of mode.
                 // Must be checked before mode-specific logic so it works even
@@ -908,6 +1137,10 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                             d.last_stopped_at = Some(Instant::now());
                         }
                     }
+                    for (_, d) in PROFILE_DETECTORS.lock_or_recover().iter_mut() {
+                        d.reset();
+                        d.last_stopped_at = Some(Instant::now());
+                    }
                     let held_transform_pass_id =
                         take_transform_hold_context().map(|(pass_id, elapsed_ms)| {
                             crate::transform_trace::key_stop(pass_id, elapsed_ms, "escape");
@@ -927,6 +1160,7 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                     HOLD_PRESS_COUNTER.fetch_add(1, Ordering::SeqCst);
 
                     tracing::info!(target: "keyboard", "Escape pressed — emitting escape-cancel");
+                    note_emitted("escape-cancel");
                     let _ = handle.emit(
                         "escape-cancel",
                         serde_json::json!({ "transformPassId": transform_pass_id }),
@@ -934,7 +1168,10 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                     return;
                 }
 
-                if APP_DISABLED.load(Ordering::SeqCst) {
+                if APP_DISABLED.load(Ordering::SeqCst)
+                    || FOCUS_GATED.load(Ordering::SeqCst)
+                    || QUIET_HOURS_GATED.load(Ordering::SeqCst)
+                {
                     return;
                 }
 
@@ -1060,6 +1297,7 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                                 }
                                 _ => {}
                             }
+                            note_emitted("transform-key-pressed");
                             let _ = handle.emit(
                                 "transform-key-pressed",
                                 serde_json::json!({ "transformPassId": pass_id }),
@@ -1073,6 +1311,7 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                                     _ => "detector_stop",
                                 };
                                 crate::transform_trace::key_stop(pass_id, elapsed_ms, reason);
+                                note_emitted("transform-key-released");
                                 let _ = handle.emit(
                                     "transform-key-released",
                                     serde_json::json!({ "transformPassId": pass_id }),
@@ -1091,6 +1330,63 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                     return;
                 }
 
+                let profile_events = {
+                    let mut detectors = PROFILE_DETECTORS.lock_or_recover();
+                    detectors
+                        .iter_mut()
+                        .filter_map(|(profile_id, d)| match d.handle_event(&event.event_type) {
+                            HoldDownEvent::None => None,
+                            result => Some((profile_id.clone(), result)),
+                        })
+                        .collect::<Vec<_>>()
+                };
+                let commit_pressed = APPEND_COMMIT_DETECTOR
+                    .lock_or_recover()
+                    .as_mut()
+                    .is_some_and(|d| d.handle_event(&event.event_type) == HoldDownEvent::Start);
+                if commit_pressed {
+                    tracing::info!(target: "keyboard", "append commit hotkey");
+                    note_emitted("append-commit-key");
+                    let _ = handle.emit("append-commit-key", ());
+                }
+                let paste_last_pressed = PASTE_LAST_DETECTOR
+                    .lock_or_recover()
+                    .as_mut()
+                    .is_some_and(|d| d.handle_event(&event.event_type) == HoldDownEvent::Start);
+                if paste_last_pressed {
+                    tracing::info!(target: "keyboard", "paste last hotkey");
+                    note_emitted("paste-last-key");
+                    let _ = handle.emit("paste-last-key", ());
+                }
+                let clipboard_audio_pressed = CLIPBOARD_AUDIO_DETECTOR
+                    .lock_or_recover()
+                    .as_mut()
+                    .is_some_and(|d| d.handle_event(&event.event_type) == HoldDownEvent::Start);
+                if clipboard_audio_pressed {
+                    tracing::info!(target: "keyboard", "clipboard audio hotkey");
+                    note_emitted("clipboard-audio-key");
+                    let _ = handle.emit("clipboard-audio-key", ());
+                }
+                let marker_pressed = MARKER_DETECTOR
+                    .lock_or_recover()
+                    .as_mut()
+                    .is_some_and(|d| d.handle_event(&event.event_type) == HoldDownEvent::Start);
+                if marker_pressed {
+                    tracing::info!(target: "keyboard", "marker hotkey");
+                    note_emitted("recording-marker");
+                    crate::markers::press(&handle);
+                }
+                for (profile_id, result) in profile_events {
+                    let name = if result == HoldDownEvent::Start {
+                        "profile-hold-start"
+                    } else {
+                        "profile-hold-stop"
+                    };
+                    tracing::info!(target: "keyboard", event = name, "dictation profile hotkey");
+                    note_emitted(name);
+                    let _ = handle.emit(name, serde_json::json!({ "profileId": profile_id }));
+                }
+
                 match mode {
                     DetectorMode::DoubleTap => {
                         let (fired, rejection, wait_started_at) = {
@@ -1118,7 +1414,7 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                             );
                         }
                         if fired {
-                            let _ = handle.emit("double-tap-toggle", ());
+                            emit_hotkey(&handle, HotkeyEvent::DoubleTapToggle);
                         }
                     }
                     DetectorMode::HoldDown => {
@@ -1133,10 +1429,10 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                         };
                         match result {
                             HoldDownEvent::Start => {
-                                let _ = handle.emit("hold-down-start", ());
+                                emit_hotkey(&handle, HotkeyEvent::HoldDownStart);
                             }
                             HoldDownEvent::Stop => {
-                                let _ = handle.emit("hold-down-stop", ());
+                                emit_hotkey(&handle, HotkeyEvent::HoldDownStop);
                             }
                             HoldDownEvent::None => {}
                         }
@@ -1153,7 +1449,7 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                         }
 
                         // Deferred hold: on press, start a background timer.
-                        // After MAX_HOLD_DURATION_MS, if the key is still held,
+                        // After the hold promotion delay, if the key is still held,
                         // the timer emits hold-down-start (promoting to a real hold).
                         // Short taps never start recording → no state thrash during double-tap.
 
@@ -1168,7 +1464,7 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                                         d.state,
                                         DetectorState::WaitingSecondDown
                                             | DetectorState::WaitingSecondUp
-                                    ) && d.elapsed_ms() <= DOUBLE_TAP_WINDOW_MS
+                                    ) && !d.window_expired()
                                 })
                                 .unwrap_or(false)
                         };
@@ -1215,14 +1511,15 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                         match hold_result {
                             HoldDownEvent::Start => {
                                 // Don't emit hold-down-start yet — start a timer.
-                                // The timer will promote after MAX_HOLD_DURATION_MS.
+                                // The timer will promote after the hold promotion delay.
                                 HOLD_PROMOTED.store(false, Ordering::SeqCst);
                                 let press_id =
                                     HOLD_PRESS_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
                                 let timer_handle = handle.clone();
+                                let promotion_ms = timing().hold_promotion_ms;
                                 std::thread::spawn(move || {
                                     std::thread::sleep(std::time::Duration::from_millis(
-                                        MAX_HOLD_DURATION_MS as u64,
+                                        promotion_ms,
                                     ));
                                     if HOLD_PRESS_COUNTER.load(Ordering::SeqCst) == press_id {
                                         let still_held = {
@@ -1236,7 +1533,7 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                                         if still_held {
                                             HOLD_PROMOTED.store(true, Ordering::SeqCst);
                                             tracing::info!(target: "keyboard", "BOTH -> timer promoted to hold-down-start");
-                                            let _ = timer_handle.emit("hold-down-start", ());
+                                            emit_hotkey(&timer_handle, HotkeyEvent::HoldDownStart);
                                         }
                                     }
                                 });
@@ -1251,11 +1548,11 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                                     // Recorder transitions are serialized, so a stop safely
                                     // waits for an in-flight start even on an immediate release.
                                     tracing::info!(target: "keyboard", "BOTH -> emit hold-down-stop (promoted hold)");
-                                    let _ = handle.emit("hold-down-stop", ());
+                                    emit_hotkey(&handle, HotkeyEvent::HoldDownStop);
                                 } else if dtap_fired {
                                     // Double-tap completed
                                     tracing::info!(target: "keyboard", "BOTH -> emit double-tap-toggle");
-                                    let _ = handle.emit("double-tap-toggle", ());
+                                    emit_hotkey(&handle, HotkeyEvent::DoubleTapToggle);
                                 } else {
                                     log_rejection(
                                         RejectionReason::SingleShortTapNoop,
@@ -1267,29 +1564,196 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                             HoldDownEvent::None => {
                                 if dtap_fired {
                                     tracing::info!(target: "keyboard", "BOTH -> emit double-tap-toggle (hold=None)");
-                                    let _ = handle.emit("double-tap-toggle", ());
+                                    emit_hotkey(&handle, HotkeyEvent::DoubleTapToggle);
                                 }
                             }
                         }
                     }
                 }
             };
+            // A diagnostics recording brackets each event with the detector
+            // states around it; everything noted in between belongs to it.
+            let callback = move |event: Event| {
+                diagnostics_begin(&event.event_type);
+                handle_event(&event);
+                diagnostics_finish();
+            };
 
-            if let Err(e) = listen(callback) {
-                tracing::error!(target: "keyboard", "rdev listener error: {:?}", e);
-                LISTENER_THREAD_SPAWNED.store(false, Ordering::SeqCst);
-                LISTENER_ACTIVE.store(false, Ordering::SeqCst);
-                let _ = error_handle.emit("keyboard-listener-error", format!("{:?}", e));
+            // listen() normally blocks for the app's lifetime. If it returns,
+            // with an error or because the tap's run loop ended, the thread is
+            // gone: clear the spawn flag so the health monitor can re-register.
+            // LISTENER_ACTIVE stays as the user left it, so the monitor knows a
+            // listener is still wanted.
+            match listen(callback) {
+                Err(e) => {
+                    tracing::error!(target: "keyboard", "rdev listener error: {:?}", e);
+                    LISTENER_THREAD_SPAWNED.store(false, Ordering::SeqCst);
+                    let _ = error_handle.emit("keyboard-listener-error", format!("{:?}", e));
+                }
+                Ok(()) => {
+                    tracing::warn!(target: "keyboard", "rdev listener returned — event tap ended");
+                    LISTENER_THREAD_SPAWNED.store(false, Ordering::SeqCst);
+                }
             }
         });
+    }
+    ensure_health_monitor_spawned(app_handle);
+}
+
+// -- Listener health --
+//
+// The event tap can die without an error reaching us: macOS invalidates it
+// when Accessibility is toggled, and rdev's run loop then simply returns. A
+// monitor thread checks every `HEALTH_CHECK_INTERVAL_SECS` whether a wanted
+// listener still has a live thread and permission, re-registers a dead one up
+// to `MAX_LISTENER_RESTARTS` times, and otherwise reports
+// `keyboard-listener-degraded` with what the user can do about it.
+
+static HEALTH_MONITOR_SPAWNED: AtomicBool = AtomicBool::new(false);
+/// Re-registrations since the last event reached the callback.
+static LISTENER_RESTARTS: AtomicU32 = AtomicU32::new(0);
+/// Last condition reported to the frontend; `None` while healthy.
+static LISTENER_DEGRADED: Mutex<Option<ListenerDegraded>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradedReason {
+    /// The rdev thread exited and automatic re-registration gave up.
+    ThreadExited,
+    /// Accessibility permission was revoked; the tap cannot receive events.
+    PermissionRevoked,
+    /// Permission came back but no event has arrived since, so the tap
+    /// registered before the revocation is likely dead.
+    TapSilent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    GrantAccessibility,
+    /// `retry_keyboard_listener` resets the restart budget and re-registers.
+    RetryListener,
+    /// Only a fresh process can replace a tap whose thread is still blocked.
+    RestartApp,
+}
+
+/// Payload of `keyboard-listener-degraded`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenerDegraded {
+    pub reason: DegradedReason,
+    pub action: RecoveryAction,
+    pub restart_attempts: u32,
+}
+
+/// What the monitor saw on one tick.
+#[derive(Debug, Clone, Copy)]
+struct HealthSnapshot {
+    /// The dictation listener or transform hotkey is enabled.
+    wanted: bool,
+    thread_alive: bool,
+    accessibility: bool,
+    restart_attempts: u32,
+    /// Time since Accessibility was re-granted, while no event has arrived
+    /// since then.
+    silent_since_regrant_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HealthVerdict {
+    Healthy,
+    Restart,
+    Degraded(ListenerDegraded),
+}
+
+fn assess_listener_health(snapshot: &HealthSnapshot) -> HealthVerdict {
+    let degraded = |reason, action| {
+        HealthVerdict::Degraded(ListenerDegraded {
+            reason,
+            action,
+            restart_attempts: snapshot.restart_attempts,
+        })
+    };
+    if !snapshot.wanted {
+        return HealthVerdict::Healthy;
+    }
+    // Without permission a re-registered tap would fail again, so restarts
+    // wait until it is granted.
+    if !snapshot.accessibility {
+        return degraded(
+            DegradedReason::PermissionRevoked,
+            RecoveryAction::GrantAccessibility,
+        );
+    }
+    if !snapshot.thread_alive {
+        return if snapshot.restart_attempts < MAX_LISTENER_RESTARTS {
+            HealthVerdict::Restart
+        } else {
+            degraded(DegradedReason::ThreadExited, RecoveryAction::RetryListener)
+        };
+    }
+    if snapshot
+        .silent_since_regrant_ms
+        .is_some_and(|silent_ms| silent_ms >= TAP_SILENCE_WARNING_MS)
+    {
+        return degraded(DegradedReason::TapSilent, RecoveryAction::RestartApp);
+    }
+    HealthVerdict::Healthy
+}
+
+/// Record the current condition and tell the frontend when it changes.
+fn report_listener_health(app_handle: &tauri::AppHandle, current: Option<ListenerDegraded>) {
+    let previous = std::mem::replace(&mut *LISTENER_DEGRADED.lock_or_recover(), current);
+    if previous == current {
+        return;
+    }
+    match current {
+        Some(degraded) => {
+            tracing::warn!(
+                target: "keyboard",
+                reason = ?degraded.reason,
+                action = ?degraded.action,
+                restart_attempts = degraded.restart_attempts,
+                "keyboard listener degraded"
+            );
+            let _ = app_handle.emit("keyboard-listener-degraded", degraded);
+        }
+        None => {
+            tracing::info!(target: "keyboard", "keyboard listener recovered");
+            let _ = app_handle.emit("keyboard-listener-recovered", ());
+        }
+    }
+}
+
+fn ensure_health_monitor_spawned(app_handle: tauri::AppHandle) {
+    if HEALTH_MONITOR_SPAWNED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return;
+    }
+    std::thread::spawn(move || {
+        let mut permission_lost = false;
+        let mut regained_at_ms = 0;
+        loop {
+            std::thread::sleep(std::time::Duration::from_secs(HEALTH_CHECK_INTERVAL_SECS));
+            let now = now_unix_ms();
+            let accessibility = crate::injector::is_accessibility_enabled();
+            if !accessibility {
+                permission_lost = true;
+                regained_at_ms = 0;
+            } else if permission_lost {
+                permission_lost = false;
+                regained_at_ms = now;
+            }
+            let last_callback_at = LAST_RDEV_CALLBACK_AT_MS.load(Ordering::SeqCst);
+            if regained_at_ms != 0 && last_callback_at >= regained_at_ms {
+                regained_at_ms = 0;
+            }
+            let wanted =
+                LISTENER_ACTIVE.load(Ordering::SeqCst) || TRANSFORM_ACTIVE.load(Ordering::SeqCst);
 
-        // Heartbeat monitor: logs every 60 s while the listener is supposed to
-        // be active, so app.log shows a gap if the thread goes silent.
-        std::thread::spawn(|| loop {
-            std::thread::sleep(std::time::Duration::from_secs(60));
             if LISTENER_ACTIVE.load(Ordering::SeqCst) {
-                let now = now_unix_ms();
-                let last_callback_at = LAST_RDEV_CALLBACK_AT_MS.load(Ordering::SeqCst);
                 let silent_for_ms = now.saturating_sub(last_callback_at);
                 let last_warning_at = LAST_TAP_SILENCE_WARNING_AT_MS.load(Ordering::SeqCst);
                 let warning_due = last_warning_at == 0
@@ -1300,16 +1764,50 @@ fn ensure_listener_thread_spawned(app_handle: tauri::AppHandle) {
                         target: "keyboard",
                         silent_for_ms = silent_for_ms,
                         threshold_ms = TAP_SILENCE_WARNING_MS,
-                        "listener heartbeat — no rdev callbacks observed"
+                        "listener health — no rdev callbacks observed"
                     );
                 }
-                tracing::trace!(target: "keyboard", "listener heartbeat — active");
-            } else if !LISTENER_THREAD_SPAWNED.load(Ordering::SeqCst) {
-                // Listener thread has exited; stop monitoring.
-                break;
             }
-        });
-    }
+
+            let snapshot = HealthSnapshot {
+                wanted,
+                thread_alive: LISTENER_THREAD_SPAWNED.load(Ordering::SeqCst),
+                accessibility,
+                restart_attempts: LISTENER_RESTARTS.load(Ordering::SeqCst),
+                silent_since_regrant_ms: (regained_at_ms != 0)
+                    .then(|| now.saturating_sub(regained_at_ms)),
+            };
+            match assess_listener_health(&snapshot) {
+                HealthVerdict::Healthy => report_listener_health(&app_handle, None),
+                HealthVerdict::Restart => {
+                    let attempt = LISTENER_RESTARTS.fetch_add(1, Ordering::SeqCst) + 1;
+                    tracing::warn!(
+                        target: "keyboard",
+                        attempt = attempt,
+                        max_attempts = MAX_LISTENER_RESTARTS,
+                        "listener thread gone — re-registering event tap"
+                    );
+                    ensure_listener_thread_spawned(app_handle.clone());
+                }
+                HealthVerdict::Degraded(degraded) => {
+                    report_listener_health(&app_handle, Some(degraded))
+                }
+            }
+        }
+    });
+}
+
+/// The condition last reported by `keyboard-listener-degraded`, if still
+/// unresolved. Lets a window that mounts late show it.
+pub fn listener_degraded() -> Option<ListenerDegraded> {
+    *LISTENER_DEGRADED.lock_or_recover()
+}
+
+/// User-requested recovery: reset the restart budget and re-register the tap
+/// now. The monitor clears the degraded state on its next healthy tick.
+pub fn retry_listener(app_handle: tauri::AppHandle) {
+    LISTENER_RESTARTS.store(0, Ordering::SeqCst);
+    ensure_listener_thread_spawned(app_handle);
 }
 
 /// Publish cancellation for the exact physical transform pass whose hold
@@ -1401,6 +1899,21 @@ pub fn stop_listener() {
             d.reset();
         }
     }
+    for (_, d) in PROFILE_DETECTORS.lock_or_recover().iter_mut() {
+        d.reset();
+    }
+    if let Some(d) = APPEND_COMMIT_DETECTOR.lock_or_recover().as_mut() {
+        d.reset();
+    }
+    if let Some(d) = PASTE_LAST_DETECTOR.lock_or_recover().as_mut() {
+        d.reset();
+    }
+    if let Some(d) = CLIPBOARD_AUDIO_DETECTOR.lock_or_recover().as_mut() {
+        d.reset();
+    }
+    if let Some(d) = MARKER_DETECTOR.lock_or_recover().as_mut() {
+        d.reset();
+    }
     HOLD_PROMOTED.store(false, Ordering::SeqCst);
     HOLD_PRESS_COUNTER.fetch_add(1, Ordering::SeqCst); // invalidate pending timers
 }
@@ -1465,6 +1978,84 @@ pub fn set_recording_state(recording: bool) {
     }
 }
 
+/// Replace the dictation profile hotkeys with `bindings` (profile id, hotkey
+/// id). Returns the ids of profiles that were mid-hold and lost their
+/// detector or key; the caller should emit `profile-hold-stop` for each.
+pub fn set_profile_keys(bindings: &[(String, String)]) -> Vec<String> {
+    rebind_profile_detectors(&mut PROFILE_DETECTORS.lock_or_recover(), bindings)
+}
+
+/// Detectors whose profile id and key are unchanged keep their state, so
+/// saving an unrelated profile edit mid-hold doesn't cut the recording short.
+/// Unknown hotkey ids are skipped, and a key already claimed by an earlier
+/// binding is not bound twice.
+fn rebind_profile_detectors(
+    detectors: &mut Vec<(String, HoldDownDetector)>,
+    bindings: &[(String, String)],
+) -> Vec<String> {
+    let mut previous = std::mem::take(detectors);
+    for (profile_id, hotkey) in bindings {
+        let Some(key) = hotkey_to_rdev_key(hotkey) else {
+            continue;
+        };
+        if detectors.iter().any(|(_, d)| d.target_key == Some(key)) {
+            continue;
+        }
+        let detector = match previous
+            .iter()
+            .position(|(id, d)| id == profile_id && d.target_key == Some(key))
+        {
+            Some(index) => previous.swap_remove(index).1,
+            None => {
+                let mut d = HoldDownDetector::new();
+                let _ = d.set_target(Some(key));
+                d
+            }
+        };
+        detectors.push((profile_id.clone(), detector));
+    }
+    previous
+        .into_iter()
+        .filter(|(_, d)| d.state == HoldState::Held)
+        .map(|(profile_id, _)| profile_id)
+        .collect()
+}
+
+/// Bind the append-mode commit key, or unbind it with `None`. Unknown hotkey
+/// ids unbind. Returns whether a key is bound.
+pub fn set_append_commit_key(hotkey: Option<&str>) -> bool {
+    bind_key_detector(&APPEND_COMMIT_DETECTOR, hotkey)
+}
+
+/// Bind the paste-last key, or unbind it with `None`. Unknown hotkey ids
+/// unbind. Returns whether a key is bound.
+pub fn set_paste_last_key(hotkey: Option<&str>) -> bool {
+    bind_key_detector(&PASTE_LAST_DETECTOR, hotkey)
+}
+
+/// Bind the copied-audio key, or unbind it with `None`. Unknown hotkey ids
+/// unbind. Returns whether a key is bound.
+pub fn set_clipboard_audio_key(hotkey: Option<&str>) -> bool {
+    bind_key_detector(&CLIPBOARD_AUDIO_DETECTOR, hotkey)
+}
+
+/// Bind the marker key, or unbind it with `None`. Unknown hotkey ids unbind.
+/// Returns whether a key is bound.
+pub fn set_marker_key(hotkey: Option<&str>) -> bool {
+    bind_key_detector(&MARKER_DETECTOR, hotkey)
+}
+
+fn bind_key_detector(slot: &Mutex<Option<HoldDownDetector>>, hotkey: Option<&str>) -> bool {
+    let key = hotkey.and_then(hotkey_to_rdev_key);
+    let mut detector = slot.lock_or_recover();
+    *detector = key.map(|key| {
+        let mut d = HoldDownDetector::new();
+        let _ = d.set_target(Some(key));
+        d
+    });
+    detector.is_some()
+}
+
 // -- Transform hotkey lifecycle (issue #312, PR-B1) --
 //
 // Independent of `start_listener` / `stop_listener` / `set_target_key` above:
@@ -1535,6 +2126,461 @@ pub fn take_transform_hold_context() -> Option<(u64, u64)> {
         .map(|(pass_id, started)| (pass_id, started.elapsed().as_millis() as u64))
 }
 
+// -- Hotkey diagnostics --
+
+/// Upper bound on events kept by one capture.
+const MAX_CAPTURED_KEY_EVENTS: usize = 64;
+
+/// One key event seen during a hotkey diagnostic. Only modifiers are
+/// identified; any other key is reported as `"other"` with no code, so a
+/// capture never records what was typed.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedKeyEvent {
+    pub elapsed_ms: u64,
+    /// `"press"` or `"release"`.
+    pub kind: &'static str,
+    /// rdev's name for the key as delivered.
+    pub reported_key: String,
+    /// The key after physical-code normalization; what hotkey matching uses.
+    pub key: String,
+    pub scancode: Option<u32>,
+    pub matches_hotkey: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyDiagnostic {
+    pub hotkey: String,
+    pub expected_key: Option<String>,
+    pub expected_scancode: Option<u32>,
+    /// Whether the dictation listener was processing events during capture.
+    pub listener_active: bool,
+    pub events: Vec<CapturedKeyEvent>,
+}
+
+struct KeyCapture {
+    started_at: Instant,
+    target: Option<Key>,
+    events: Vec<CapturedKeyEvent>,
+}
+
+impl KeyCapture {
+    fn new(target: Option<Key>) -> Self {
+        Self {
+            started_at: Instant::now(),
+            target,
+            events: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, event_type: &EventType) {
+        if self.events.len() >= MAX_CAPTURED_KEY_EVENTS {
+            return;
+        }
+        if let Some(event) = describe_key_event(self.started_at, self.target, event_type) {
+            self.events.push(event);
+        }
+    }
+}
+
+/// A key event as diagnostics report it, timed from `started_at`. `None` for
+/// anything but a press or release.
+fn describe_key_event(
+    started_at: Instant,
+    target: Option<Key>,
+    event_type: &EventType,
+) -> Option<CapturedKeyEvent> {
+    let (kind, reported) = match event_type {
+        EventType::KeyPress(key) => ("press", *key),
+        EventType::KeyRelease(key) => ("release", *key),
+        _ => return None,
+    };
+    let modifier = is_modifier(reported) || matches!(reported, Key::Unknown(_));
+    let (reported_key, key, scancode) = if modifier {
+        (
+            format!("{:?}", reported),
+            format!("{:?}", physical_key(reported)),
+            scancode(reported),
+        )
+    } else {
+        ("other".to_string(), "other".to_string(), None)
+    };
+    Some(CapturedKeyEvent {
+        elapsed_ms: started_at.elapsed().as_millis() as u64,
+        kind,
+        reported_key,
+        key,
+        scancode,
+        matches_hotkey: target.is_some_and(|target| is_same_modifier(reported, target)),
+    })
+}
+
+/// A capture in progress, fed at the top of the shared rdev callback.
+static KEY_CAPTURE: Mutex<Option<KeyCapture>> = Mutex::new(None);
+
+/// Start recording raw key events for `hotkey`, replacing any capture in
+/// progress, and make sure the rdev thread is running to deliver them.
+pub fn begin_key_capture(app_handle: tauri::AppHandle, hotkey: &str) {
+    *KEY_CAPTURE.lock_or_recover() = Some(KeyCapture::new(hotkey_to_rdev_key(hotkey)));
+    ensure_listener_thread_spawned(app_handle);
+}
+
+/// End the capture and report what arrived.
+pub fn finish_key_capture(hotkey: &str) -> HotkeyDiagnostic {
+    let events = KEY_CAPTURE
+        .lock_or_recover()
+        .take()
+        .map(|capture| capture.events)
+        .unwrap_or_default();
+    let expected = hotkey_to_rdev_key(hotkey);
+    HotkeyDiagnostic {
+        hotkey: hotkey.to_string(),
+        expected_key: expected.map(|key| format!("{:?}", key)),
+        expected_scancode: expected.and_then(scancode),
+        listener_active: LISTENER_ACTIVE.load(Ordering::SeqCst),
+        events,
+    }
+}
+
+// -- Keyboard event diagnostics --
+
+/// Upper bound on entries kept by one diagnostics recording.
+const MAX_DIAGNOSTIC_ENTRIES: usize = 500;
+
+/// The dictation and transform detectors' states, by name.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectorStates {
+    pub double_tap: Option<String>,
+    pub hold_down: Option<String>,
+    pub transform: Option<String>,
+}
+
+impl DetectorStates {
+    fn current() -> Self {
+        let (double_tap, hold_down) = detector_state_snapshot();
+        let transform = TRANSFORM_DETECTOR
+            .lock_or_recover()
+            .as_ref()
+            .map(|d| d.state);
+        Self {
+            double_tap: double_tap.map(|state| format!("{:?}", state)),
+            hold_down: hold_down.map(|state| format!("{:?}", state)),
+            transform: transform.map(|state| format!("{:?}", state)),
+        }
+    }
+
+    /// Detectors whose state differs in `after`.
+    fn changed(&self, after: &Self) -> Vec<&'static str> {
+        [
+            ("double_tap", &self.double_tap, &after.double_tap),
+            ("hold_down", &self.hold_down, &after.hold_down),
+            ("transform", &self.transform, &after.transform),
+        ]
+        .into_iter()
+        .filter(|(_, before, after)| before != after)
+        .map(|(name, _, _)| name)
+        .collect()
+    }
+}
+
+/// One line of a diagnostics report: a key event and what the listener did
+/// with it, or a timer (hold promotion, second-tap expiry) acting on its own.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticEntry {
+    pub elapsed_ms: u64,
+    /// The key event; `None` for a timer.
+    pub event: Option<CapturedKeyEvent>,
+    /// Detector states around the event; `None` for a timer.
+    pub before: Option<DetectorStates>,
+    pub after: Option<DetectorStates>,
+    /// Detectors whose state the event changed.
+    pub consumed_by: Vec<&'static str>,
+    /// Events emitted to the frontend.
+    pub emitted: Vec<&'static str>,
+    /// Rejection reasons the detectors logged.
+    pub rejected: Vec<&'static str>,
+}
+
+/// What `start_keyboard_diagnostics` saw, for attaching to a bug report. Key
+/// names follow the hotkey diagnostic: typed keys are only `"other"`.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyboardDiagnosticsReport {
+    pub duration_ms: u64,
+    pub mode: &'static str,
+    pub hotkey: Option<String>,
+    pub timing: HotkeyTiming,
+    pub listener_active: bool,
+    pub app_disabled: bool,
+    pub focus_gated: bool,
+    pub quiet_hours_gated: bool,
+    pub entries: Vec<DiagnosticEntry>,
+    /// Entries past `MAX_DIAGNOSTIC_ENTRIES` that were not kept.
+    pub dropped: usize,
+}
+
+struct DiagnosticsRecorder {
+    started_at: Instant,
+    entries: Vec<DiagnosticEntry>,
+    dropped: usize,
+    /// The entry the rdev thread is filling in while it handles an event.
+    open: Option<(std::thread::ThreadId, DiagnosticEntry)>,
+}
+
+impl DiagnosticsRecorder {
+    fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            entries: Vec::new(),
+            dropped: 0,
+            open: None,
+        }
+    }
+
+    fn push(&mut self, entry: DiagnosticEntry) {
+        if self.entries.len() < MAX_DIAGNOSTIC_ENTRIES {
+            self.entries.push(entry);
+        } else {
+            self.dropped += 1;
+        }
+    }
+
+    fn begin(&mut self, event: CapturedKeyEvent, before: DetectorStates) {
+        let entry = DiagnosticEntry {
+            elapsed_ms: event.elapsed_ms,
+            event: Some(event),
+            before: Some(before),
+            after: None,
+            consumed_by: Vec::new(),
+            emitted: Vec::new(),
+            rejected: Vec::new(),
+        };
+        self.open = Some((std::thread::current().id(), entry));
+    }
+
+    fn finish(&mut self, after: DetectorStates) {
+        let Some((_, mut entry)) = self.open.take() else {
+            return;
+        };
+        if let Some(before) = &entry.before {
+            entry.consumed_by = before.changed(&after);
+        }
+        entry.after = Some(after);
+        self.push(entry);
+    }
+
+    /// Attach to the event this thread is handling, or record a timer entry
+    /// when the note comes from another thread.
+    fn note(&mut self, note: impl FnOnce(&mut DiagnosticEntry)) {
+        let current = std::thread::current().id();
+        match self.open.as_mut() {
+            Some((thread, entry)) if *thread == current => note(entry),
+            _ => {
+                let mut entry = DiagnosticEntry {
+                    elapsed_ms: self.started_at.elapsed().as_millis() as u64,
+                    event: None,
+                    before: None,
+                    after: None,
+                    consumed_by: Vec::new(),
+                    emitted: Vec::new(),
+                    rejected: Vec::new(),
+                };
+                note(&mut entry);
+                self.push(entry);
+            }
+        }
+    }
+}
+
+/// A diagnostics recording in progress, fed around every rdev callback.
+static KEYBOARD_DIAGNOSTICS: Mutex<Option<DiagnosticsRecorder>> = Mutex::new(None);
+/// The last finished recording, for the support bundle.
+static LAST_KEYBOARD_DIAGNOSTICS: Mutex<Option<KeyboardDiagnosticsReport>> = Mutex::new(None);
+
+/// The dictation detectors' target key.
+fn dictation_target() -> Option<Key> {
+    DOUBLE_TAP_DETECTOR
+        .lock_or_recover()
+        .as_ref()
+        .and_then(|d| d.target_key)
+        .or_else(|| {
+            HOLD_DOWN_DETECTOR
+                .lock_or_recover()
+                .as_ref()
+                .and_then(|d| d.target_key)
+        })
+}
+
+// Detector states are read before taking the recorder lock: notes arrive
+// with a detector lock held, so the recorder lock is always taken last.
+fn diagnostics_begin(event_type: &EventType) {
+    let Some(started_at) = KEYBOARD_DIAGNOSTICS
+        .lock_or_recover()
+        .as_ref()
+        .map(|recorder| recorder.started_at)
+    else {
+        return;
+    };
+    let Some(event) = describe_key_event(started_at, dictation_target(), event_type) else {
+        return;
+    };
+    let before = DetectorStates::current();
+    if let Some(recorder) = KEYBOARD_DIAGNOSTICS.lock_or_recover().as_mut() {
+        recorder.begin(event, before);
+    }
+}
+
+fn diagnostics_finish() {
+    let recording = KEYBOARD_DIAGNOSTICS
+        .lock_or_recover()
+        .as_ref()
+        .is_some_and(|recorder| recorder.open.is_some());
+    if !recording {
+        return;
+    }
+    let after = DetectorStates::current();
+    if let Some(recorder) = KEYBOARD_DIAGNOSTICS.lock_or_recover().as_mut() {
+        recorder.finish(after);
+    }
+}
+
+fn note_emitted(name: &'static str) {
+    if let Some(recorder) = KEYBOARD_DIAGNOSTICS.lock_or_recover().as_mut() {
+        recorder.note(|entry| entry.emitted.push(name));
+    }
+}
+
+fn note_rejected(reason: RejectionReason) {
+    if let Some(recorder) = KEYBOARD_DIAGNOSTICS.lock_or_recover().as_mut() {
+        recorder.note(|entry| entry.rejected.push(reason.as_str()));
+    }
+}
+
+/// Start recording every key event with what the detectors made of it,
+/// replacing any recording in progress, and make sure the rdev thread is
+/// running to deliver them.
+pub fn begin_keyboard_diagnostics(app_handle: tauri::AppHandle) {
+    *KEYBOARD_DIAGNOSTICS.lock_or_recover() = Some(DiagnosticsRecorder::new());
+    ensure_listener_thread_spawned(app_handle);
+}
+
+/// End the recording and report it with the listener's configuration.
+pub fn finish_keyboard_diagnostics() -> KeyboardDiagnosticsReport {
+    let recorder = KEYBOARD_DIAGNOSTICS
+        .lock_or_recover()
+        .take()
+        .unwrap_or_else(DiagnosticsRecorder::new);
+    let report = KeyboardDiagnosticsReport {
+        duration_ms: recorder.started_at.elapsed().as_millis() as u64,
+        mode: ACTIVE_MODE.lock_or_recover().as_str(),
+        hotkey: dictation_target().map(|key| format!("{:?}", key)),
+        timing: timing(),
+        listener_active: LISTENER_ACTIVE.load(Ordering::SeqCst),
+        app_disabled: APP_DISABLED.load(Ordering::SeqCst),
+        focus_gated: FOCUS_GATED.load(Ordering::SeqCst),
+        quiet_hours_gated: QUIET_HOURS_GATED.load(Ordering::SeqCst),
+        entries: recorder.entries,
+        dropped: recorder.dropped,
+    };
+    *LAST_KEYBOARD_DIAGNOSTICS.lock_or_recover() = Some(report.clone());
+    report
+}
+
+/// The most recent `finish_keyboard_diagnostics` report this session.
+pub fn last_keyboard_diagnostics() -> Option<KeyboardDiagnosticsReport> {
+    LAST_KEYBOARD_DIAGNOSTICS.lock_or_recover().clone()
+}
+
+// -- Timing calibration --
+
+/// Gaps longer than this are pauses between double-taps, not the gap inside
+/// one.
+const CALIBRATION_MAX_GAP_MS: u64 = 1_000;
+
+/// Complete taps a calibration needs: two double-taps.
+const CALIBRATION_MIN_TAPS: usize = 4;
+
+/// What a calibration measured, and the timing it suggests.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimingCalibration {
+    pub taps: usize,
+    pub longest_tap_ms: u64,
+    pub longest_gap_ms: u64,
+    pub suggested: HotkeyTiming,
+}
+
+/// Half again the slowest measurement, kept inside `range`.
+fn with_headroom(measured_ms: u64, range: &std::ops::RangeInclusive<u64>) -> u64 {
+    (measured_ms * 3 / 2)
+        .div_ceil(10)
+        .saturating_mul(10)
+        .clamp(*range.start(), *range.end())
+}
+
+/// Suggest a timing from the user double-tapping their hotkey during a
+/// capture. Tap length and gap come from the hotkey's own presses; key
+/// repeats and presses too long to be taps are ignored. Cooldowns carry over
+/// from `current`.
+pub fn calibrate_timing(
+    events: &[CapturedKeyEvent],
+    current: HotkeyTiming,
+) -> Result<TimingCalibration, String> {
+    let mut taps = Vec::new();
+    let mut gaps = Vec::new();
+    let mut pressed_at: Option<u64> = None;
+    let mut released_at: Option<u64> = None;
+    for event in events.iter().filter(|event| event.matches_hotkey) {
+        match event.kind {
+            "press" if pressed_at.is_none() => {
+                if let Some(released_at) = released_at.take() {
+                    let gap = event.elapsed_ms.saturating_sub(released_at);
+                    if gap <= CALIBRATION_MAX_GAP_MS {
+                        gaps.push(gap);
+                    }
+                }
+                pressed_at = Some(event.elapsed_ms);
+            }
+            "release" => {
+                if let Some(pressed_at) = pressed_at.take() {
+                    let held = event.elapsed_ms.saturating_sub(pressed_at);
+                    if held <= *TAP_MS_RANGE.end() {
+                        taps.push(held);
+                        released_at = Some(event.elapsed_ms);
+                    }
+                }
+            }
+            // A press while already down is key repeat.
+            _ => {}
+        }
+    }
+
+    let (Some(&longest_tap_ms), Some(&longest_gap_ms)) = (taps.iter().max(), gaps.iter().max())
+    else {
+        return Err("No double-taps were detected. Double-tap your key a few times.".to_string());
+    };
+    if taps.len() < CALIBRATION_MIN_TAPS {
+        return Err("Too few taps to calibrate. Double-tap your key at least twice.".to_string());
+    }
+
+    let max_tap_ms = with_headroom(longest_tap_ms, &TAP_MS_RANGE);
+    let suggested = HotkeyTiming {
+        max_tap_ms,
+        double_tap_window_ms: with_headroom(longest_gap_ms, &DOUBLE_TAP_WINDOW_MS_RANGE),
+        hold_promotion_ms: current.hold_promotion_ms.max(max_tap_ms),
+        ..current
+    };
+    Ok(TimingCalibration {
+        taps: taps.len(),
+        longest_tap_ms,
+        longest_gap_ms,
+        suggested,
+    })
+}
+
 /// Whether the transform hotkey is currently enabled (a listener was started
 /// and not since stopped). Test/diagnostic surface only.
 #[cfg(test)]
@@ -1628,6 +2674,20 @@ mod tests {
         assert_eq!(d.state, DetectorState::Idle);
     }
 
+    #[test]
+    fn a_longer_max_tap_accepts_a_slow_tap() {
+        let mut d = make_detector(Key::ShiftLeft);
+        d.timing = HotkeyTiming {
+            max_tap_ms: 500,
+            ..HotkeyTiming::DEFAULT
+        };
+
+        assert!(!d.handle_event(&press(Key::ShiftLeft)));
+        sleep(Duration::from_millis(350));
+        assert!(!d.handle_event(&release(Key::ShiftLeft)));
+        assert_eq!(d.state, DetectorState::WaitingSecondDown);
+    }
+
     #[test]
     fn slow_gap_between_taps_rejects() {
         let mut d = make_detector(Key::ShiftLeft);
@@ -2692,4 +3752,300 @@ mod tests {
         }
         reset_transform_state();
     }
+
+    fn binding(profile_id: &str, hotkey: &str) -> (String, String) {
+        (profile_id.to_string(), hotkey.to_string())
+    }
+
+    #[test]
+    fn profile_rebind_keeps_unchanged_holds_and_reports_dropped_ones() {
+        let mut detectors = Vec::new();
+        let dropped = rebind_profile_detectors(
+            &mut detectors,
+            &[
+                binding("english", "ctrl_r"),
+                binding("spanish", "alt_l"),
+                binding("duplicate", "alt_l"),
+                binding("unknown", "not_a_real_key"),
+            ],
+        );
+        assert!(dropped.is_empty());
+        let ids: Vec<&str> = detectors.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["english", "spanish"]);
+
+        for (_, d) in detectors.iter_mut() {
+            let key = d.target_key.unwrap();
+            assert_eq!(d.handle_event(&press(key)), HoldDownEvent::Start);
+        }
+
+        // English keeps its key and its hold; Spanish moves to a new key.
+        let dropped = rebind_profile_detectors(
+            &mut detectors,
+            &[binding("english", "ctrl_r"), binding("spanish", "shift_r")],
+        );
+        assert_eq!(dropped, vec!["spanish".to_string()]);
+        assert_eq!(detectors[0].1.state, HoldState::Held);
+        assert_eq!(detectors[1].1.state, HoldState::Idle);
+        assert_eq!(
+            detectors[0].1.handle_event(&release(Key::ControlRight)),
+            HoldDownEvent::Stop
+        );
+
+        let dropped = rebind_profile_detectors(&mut detectors, &[]);
+        assert!(dropped.is_empty());
+        assert!(detectors.is_empty());
+    }
+
+    #[test]
+    fn unknown_keys_match_modifiers_by_physical_code() {
+        for (code, named) in MODIFIER_SCANCODES {
+            assert!(is_same_modifier(Key::Unknown(*code), *named));
+            assert!(is_modifier(Key::Unknown(*code)));
+            assert_eq!(scancode(*named), Some(*code));
+        }
+        assert!(!is_same_modifier(Key::ShiftLeft, Key::ShiftRight));
+        assert!(!is_modifier(Key::Unknown(9_999)));
+        assert_eq!(physical_key(Key::Unknown(9_999)), Key::Unknown(9_999));
+
+        let (code, _) = MODIFIER_SCANCODES
+            .iter()
+            .find(|(_, named)| *named == Key::ControlRight)
+            .unwrap();
+        let mut d = HoldDownDetector::new();
+        let _ = d.set_target(Some(Key::ControlRight));
+        assert_eq!(
+            d.handle_event(&press(Key::Unknown(*code))),
+            HoldDownEvent::Start
+        );
+        assert_eq!(
+            d.handle_event(&release(Key::ControlRight)),
+            HoldDownEvent::Stop
+        );
+    }
+
+    #[test]
+    fn key_capture_identifies_modifiers_but_never_typed_keys() {
+        let mut capture = KeyCapture::new(Some(Key::Alt));
+        capture.record(&press(Key::Alt));
+        capture.record(&press(Key::KeyA));
+        capture.record(&release(Key::KeyA));
+        capture.record(&release(Key::ShiftLeft));
+        capture.record(&EventType::MouseMove { x: 1.0, y: 2.0 });
+
+        let kinds: Vec<&str> = capture.events.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, vec!["press", "press", "release", "release"]);
+        assert!(capture.events[0].matches_hotkey);
+        assert_eq!(capture.events[0].key, "Alt");
+        assert_eq!(capture.events[0].scancode, scancode(Key::Alt));
+        for typed in &capture.events[1..3] {
+            assert_eq!(typed.key, "other");
+            assert_eq!(typed.reported_key, "other");
+            assert_eq!(typed.scancode, None);
+            assert!(!typed.matches_hotkey);
+        }
+        assert!(!capture.events[3].matches_hotkey);
+
+        for _ in 0..MAX_CAPTURED_KEY_EVENTS {
+            capture.record(&press(Key::Alt));
+        }
+        assert_eq!(capture.events.len(), MAX_CAPTURED_KEY_EVENTS);
+    }
+
+    #[test]
+    fn timing_outside_its_bounds_is_rejected() {
+        assert!(HotkeyTiming::DEFAULT.validate().is_ok());
+        let too_short = HotkeyTiming {
+            max_tap_ms: 20,
+            ..HotkeyTiming::DEFAULT
+        };
+        assert!(too_short.validate().unwrap_err().contains("maxTapMs"));
+        let promotes_taps = HotkeyTiming {
+            max_tap_ms: 300,
+            hold_promotion_ms: 250,
+            ..HotkeyTiming::DEFAULT
+        };
+        assert!(promotes_taps
+            .validate()
+            .unwrap_err()
+            .contains("holdPromotionMs"));
+    }
+
+    fn timed_events(events: &[(&'static str, u64)]) -> Vec<CapturedKeyEvent> {
+        events
+            .iter()
+            .map(|&(kind, elapsed_ms)| CapturedKeyEvent {
+                elapsed_ms,
+                kind,
+                reported_key: "Alt".to_string(),
+                key: "Alt".to_string(),
+                scancode: None,
+                matches_hotkey: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn calibration_fits_the_timing_to_the_slowest_tap_and_gap() {
+        let events = timed_events(&[
+            ("press", 0),
+            ("release", 180),
+            ("press", 600),
+            ("press", 650),
+            ("release", 840),
+            ("press", 2_500),
+            ("release", 2_640),
+            ("press", 2_950),
+            ("release", 3_100),
+        ]);
+        let calibration = calibrate_timing(&events, HotkeyTiming::DEFAULT).unwrap();
+        assert_eq!(calibration.taps, 4);
+        assert_eq!(calibration.longest_tap_ms, 240);
+        assert_eq!(calibration.longest_gap_ms, 420);
+        assert_eq!(calibration.suggested.max_tap_ms, 360);
+        assert_eq!(calibration.suggested.double_tap_window_ms, 630);
+        assert_eq!(calibration.suggested.hold_promotion_ms, 360);
+        assert_eq!(
+            calibration.suggested.cooldown_ms,
+            HotkeyTiming::DEFAULT.cooldown_ms
+        );
+        assert!(calibration.suggested.validate().is_ok());
+    }
+
+    #[test]
+    fn calibration_needs_two_double_taps() {
+        let one_double_tap = timed_events(&[
+            ("press", 0),
+            ("release", 100),
+            ("press", 300),
+            ("release", 400),
+        ]);
+        assert!(calibrate_timing(&one_double_tap, HotkeyTiming::DEFAULT).is_err());
+        assert!(calibrate_timing(&[], HotkeyTiming::DEFAULT).is_err());
+    }
+
+    fn double_tap_states(state: &str) -> DetectorStates {
+        DetectorStates {
+            double_tap: Some(state.to_string()),
+            hold_down: None,
+            transform: None,
+        }
+    }
+
+    #[test]
+    fn diagnostics_attach_notes_to_the_event_being_handled() {
+        let mut recorder = DiagnosticsRecorder::new();
+        let event =
+            describe_key_event(recorder.started_at, Some(Key::Alt), &release(Key::Alt)).unwrap();
+        recorder.begin(event, double_tap_states("WaitingSecondUp"));
+        recorder.note(|entry| entry.emitted.push("double-tap-toggle"));
+        recorder.finish(double_tap_states("Idle"));
+
+        assert_eq!(recorder.entries.len(), 1);
+        let entry = &recorder.entries[0];
+        assert!(entry.event.as_ref().unwrap().matches_hotkey);
+        assert_eq!(entry.consumed_by, vec!["double_tap"]);
+        assert_eq!(entry.emitted, vec!["double-tap-toggle"]);
+        assert!(entry.rejected.is_empty());
+    }
+
+    #[test]
+    fn diagnostics_record_timer_notes_as_their_own_entries() {
+        let mut recorder = DiagnosticsRecorder::new();
+        let event =
+            describe_key_event(recorder.started_at, Some(Key::Alt), &press(Key::Alt)).unwrap();
+        recorder.begin(event, double_tap_states("WaitingSecondDown"));
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                recorder.note(|entry| entry.rejected.push("second_tap_expired"));
+            });
+        });
+        recorder.finish(double_tap_states("WaitingSecondDown"));
+
+        assert_eq!(recorder.entries.len(), 2);
+        let timer = &recorder.entries[0];
+        assert!(timer.event.is_none());
+        assert_eq!(timer.rejected, vec!["second_tap_expired"]);
+        let key = &recorder.entries[1];
+        assert!(key.consumed_by.is_empty());
+        assert!(key.rejected.is_empty());
+    }
+
+    fn healthy_snapshot() -> HealthSnapshot {
+        HealthSnapshot {
+            wanted: true,
+            thread_alive: true,
+            accessibility: true,
+            restart_attempts: 0,
+            silent_since_regrant_ms: None,
+        }
+    }
+
+    #[test]
+    fn listener_health_restarts_a_dead_thread_then_asks_for_a_retry() {
+        assert_eq!(
+            assess_listener_health(&healthy_snapshot()),
+            HealthVerdict::Healthy
+        );
+        let dead = HealthSnapshot {
+            thread_alive: false,
+            ..healthy_snapshot()
+        };
+        assert_eq!(assess_listener_health(&dead), HealthVerdict::Restart);
+        let exhausted = HealthSnapshot {
+            restart_attempts: MAX_LISTENER_RESTARTS,
+            ..dead
+        };
+        assert_eq!(
+            assess_listener_health(&exhausted),
+            HealthVerdict::Degraded(ListenerDegraded {
+                reason: DegradedReason::ThreadExited,
+                action: RecoveryAction::RetryListener,
+                restart_attempts: MAX_LISTENER_RESTARTS,
+            })
+        );
+        // Nobody wants a listener: a dead thread is not a problem.
+        let unwanted = HealthSnapshot {
+            wanted: false,
+            ..exhausted
+        };
+        assert_eq!(assess_listener_health(&unwanted), HealthVerdict::Healthy);
+    }
+
+    #[test]
+    fn listener_health_waits_for_permission_before_restarting() {
+        let revoked = HealthSnapshot {
+            thread_alive: false,
+            accessibility: false,
+            ..healthy_snapshot()
+        };
+        assert_eq!(
+            assess_listener_health(&revoked),
+            HealthVerdict::Degraded(ListenerDegraded {
+                reason: DegradedReason::PermissionRevoked,
+                action: RecoveryAction::GrantAccessibility,
+                restart_attempts: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn long_silence_after_a_permission_regrant_suggests_restarting_the_app() {
+        let recent = HealthSnapshot {
+            silent_since_regrant_ms: Some(TAP_SILENCE_WARNING_MS - 1),
+            ..healthy_snapshot()
+        };
+        assert_eq!(assess_listener_health(&recent), HealthVerdict::Healthy);
+        let silent = HealthSnapshot {
+            silent_since_regrant_ms: Some(TAP_SILENCE_WARNING_MS),
+            ..healthy_snapshot()
+        };
+        assert_eq!(
+            assess_listener_health(&silent),
+            HealthVerdict::Degraded(ListenerDegraded {
+                reason: DegradedReason::TapSilent,
+                action: RecoveryAction::RestartApp,
+                restart_attempts: 0,
+            })
+        );
+    }
 }
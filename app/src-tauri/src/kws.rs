@@ -0,0 +1,545 @@
+//! Wake-word activation: a small streaming keyword spotter (sherpa-onnx KWS)
+//! listens for "Hey Murmur" and starts an ordinary recording when it hears it.
+//!
+//! The detector has to run all day, so it is budgeted to stay near idle:
+//!   - the spotter gets one inference thread;
+//!   - audio arrives in 100 ms frames at 16 kHz from `audio::start_listening`;
+//!   - `SpeechGate` drops frames quieter than `SPEECH_RMS_FLOOR`, so a quiet
+//!     room costs one RMS per frame and no inference at all;
+//!   - nothing is decoded while any recording is running.
+//!
+//! A recording started by the wake word has no key to release, so
+//! `WakeSession` ends it after `END_SILENCE_MS` of quiet following speech
+//! (or `NO_SPEECH_MS` if nothing is said at all). Recordings started by a
+//! hotkey are never stopped by the detector.
+
+use crate::audio::ListeningCapture;
+use crate::events::{self, WakeWordEvent};
+use crate::state::{DictationStatus, WHISPER_SAMPLE_RATE};
+use crate::MutexExt;
+use sherpa_onnx::{
+    KeywordSpotter, KeywordSpotterConfig, OnlineStream, OnlineTransducerModelConfig,
+};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use tauri::Manager;
+
+/// sherpa-onnx release folder of the English KWS model (~13 MB unpacked).
+pub const KWS_MODEL_DIR: &str = "sherpa-onnx-kws-zipformer-gigaspeech-3.3M-2024-01-01";
+const KWS_ENCODER: &str = "encoder-epoch-12-avg-2-chunk-16-left-64.int8.onnx";
+const KWS_DECODER: &str = "decoder-epoch-12-avg-2-chunk-16-left-64.onnx";
+const KWS_JOINER: &str = "joiner-epoch-12-avg-2-chunk-16-left-64.int8.onnx";
+const KWS_TOKENS: &str = "tokens.txt";
+/// Written next to the model at load time; sherpa-onnx reads keywords from a file.
+const KEYWORDS_FILE: &str = "murmur-keywords.txt";
+
+/// "Hey Murmur" spelled in the model's BPE tokens, as `text2token` produces
+/// it. The `@` suffix is the label the spotter reports on a match.
+const WAKE_PHRASE_TOKENS: &str = "▁HE Y ▁M UR M UR";
+const WAKE_PHRASE_LABEL: &str = "HEY_MURMUR";
+
+/// Detection threshold for the wake phrase. Higher means fewer false wakes
+/// and more missed ones.
+const KEYWORDS_THRESHOLD: f32 = 0.25;
+
+/// 100 ms at 16 kHz.
+pub const FRAME_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize / 10;
+const FRAME_MS: u64 = 100;
+
+/// Frames below this RMS (about -40 dBFS) are treated as silence.
+const SPEECH_RMS_FLOOR: f32 = 0.01;
+/// Quiet frames still decoded after speech, so the spotter sees the end of
+/// the phrase.
+const HANGOVER_FRAMES: usize = 5;
+
+/// How long a detection waits for the recording to actually start.
+const START_GRACE_MS: u64 = 2_000;
+/// Quiet after speech that ends a wake-started recording.
+const END_SILENCE_MS: u64 = 1_500;
+/// Quiet with no speech at all that ends a wake-started recording.
+const NO_SPEECH_MS: u64 = 5_000;
+/// Pause after a wake-started recording ends before the detector re-arms.
+const COOLDOWN_MS: u64 = 1_000;
+
+/// Where the KWS model bundle lives, beside the transcription models.
+pub fn kws_model_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("local-dictation").join("models").join(KWS_MODEL_DIR))
+}
+
+/// True if every model file exists with non-zero size directly in `dir`.
+pub fn bundle_complete(dir: &Path) -> bool {
+    [KWS_ENCODER, KWS_DECODER, KWS_JOINER, KWS_TOKENS]
+        .iter()
+        .all(|f| {
+            let p = dir.join(f);
+            p.is_file() && p.metadata().is_ok_and(|m| m.len() > 0)
+        })
+}
+
+pub fn model_exists() -> bool {
+    kws_model_dir().is_some_and(|dir| bundle_complete(&dir))
+}
+
+/// The sherpa-onnx release archive, which unpacks to `KWS_MODEL_DIR/`.
+pub fn download_url() -> String {
+    format!(
+        "https://github.com/k2-fsa/sherpa-onnx/releases/download/kws-models/{}.tar.bz2",
+        KWS_MODEL_DIR
+    )
+}
+
+/// The single line of the keywords file: tokens, threshold, then label.
+fn keywords_line() -> String {
+    format!(
+        "{} #{} @{}",
+        WAKE_PHRASE_TOKENS, KEYWORDS_THRESHOLD, WAKE_PHRASE_LABEL
+    )
+}
+
+/// Phrase tokens absent from the model's `tokens.txt`. sherpa-onnx only logs
+/// and drops a keyword it can't spell, which would leave a detector that
+/// never fires, so loading refuses instead.
+fn missing_tokens<'a>(phrase_tokens: &'a str, tokens_txt: &str) -> Vec<&'a str> {
+    let vocabulary: std::collections::HashSet<&str> = tokens_txt
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .collect();
+    phrase_tokens
+        .split_whitespace()
+        .filter(|token| !vocabulary.contains(token))
+        .collect()
+}
+
+/// Decides which frames reach the spotter. Quiet frames are dropped unless
+/// they trail speech by at most `HANGOVER_FRAMES`; the last quiet frame is
+/// kept as pre-roll so the first syllable of the phrase isn't clipped.
+#[derive(Debug, Default)]
+pub(crate) struct SpeechGate {
+    pre_roll: Option<Vec<f32>>,
+    hangover: usize,
+}
+
+impl SpeechGate {
+    /// The frames to decode now, oldest first. Usually zero or one.
+    pub fn admit(&mut self, frame: Vec<f32>, rms: f32) -> Vec<Vec<f32>> {
+        if rms >= SPEECH_RMS_FLOOR {
+            self.hangover = HANGOVER_FRAMES;
+            let mut admitted: Vec<Vec<f32>> = self.pre_roll.take().into_iter().collect();
+            admitted.push(frame);
+            admitted
+        } else if self.hangover > 0 {
+            self.hangover -= 1;
+            vec![frame]
+        } else {
+            self.pre_roll = Some(frame);
+            Vec::new()
+        }
+    }
+}
+
+/// What the detector loop should do after a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WakeAction {
+    None,
+    /// End the recording the wake word started.
+    Stop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WakePhase {
+    Listening {
+        cooldown_ms: u64,
+    },
+    /// Detected; waiting for the frontend to start the recording.
+    Starting {
+        waited_ms: u64,
+    },
+    /// A wake-started recording is running.
+    Dictating {
+        heard_speech: bool,
+        quiet_ms: u64,
+    },
+}
+
+/// Tracks a wake-started recording from detection to its silence stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct WakeSession {
+    phase: WakePhase,
+}
+
+impl Default for WakeSession {
+    fn default() -> Self {
+        Self {
+            phase: WakePhase::Listening { cooldown_ms: 0 },
+        }
+    }
+}
+
+impl WakeSession {
+    /// True when the spotter should be decoding.
+    pub fn is_armed(&self) -> bool {
+        self.phase == WakePhase::Listening { cooldown_ms: 0 }
+    }
+
+    pub fn on_detect(&mut self) {
+        self.phase = WakePhase::Starting { waited_ms: 0 };
+    }
+
+    /// Advance by one frame. `recording` is whether any recording is running.
+    pub fn on_frame(&mut self, frame_ms: u64, rms: f32, recording: bool) -> WakeAction {
+        match self.phase {
+            WakePhase::Listening { cooldown_ms } => {
+                self.phase = WakePhase::Listening {
+                    cooldown_ms: cooldown_ms.saturating_sub(frame_ms),
+                };
+            }
+            WakePhase::Starting { waited_ms } => {
+                self.phase = if recording {
+                    WakePhase::Dictating {
+                        heard_speech: false,
+                        quiet_ms: 0,
+                    }
+                } else if waited_ms + frame_ms >= START_GRACE_MS {
+                    // The start was refused (busy, disabled); re-arm.
+                    WakePhase::Listening {
+                        cooldown_ms: COOLDOWN_MS,
+                    }
+                } else {
+                    WakePhase::Starting {
+                        waited_ms: waited_ms + frame_ms,
+                    }
+                };
+            }
+            WakePhase::Dictating {
+                heard_speech,
+                quiet_ms,
+            } => {
+                if !recording {
+                    // Stopped some other way (hotkey, Escape, lock).
+                    self.phase = WakePhase::Listening {
+                        cooldown_ms: COOLDOWN_MS,
+                    };
+                    return WakeAction::None;
+                }
+                let speaking = rms >= SPEECH_RMS_FLOOR;
+                let heard_speech = heard_speech || speaking;
+                let quiet_ms = if speaking { 0 } else { quiet_ms + frame_ms };
+                let limit = if heard_speech {
+                    END_SILENCE_MS
+                } else {
+                    NO_SPEECH_MS
+                };
+                if quiet_ms >= limit {
+                    self.phase = WakePhase::Listening {
+                        cooldown_ms: COOLDOWN_MS,
+                    };
+                    return WakeAction::Stop;
+                }
+                self.phase = WakePhase::Dictating {
+                    heard_speech,
+                    quiet_ms,
+                };
+            }
+        }
+        WakeAction::None
+    }
+}
+
+/// The sherpa-onnx spotter and its one stream.
+struct WakeWordSpotter {
+    spotter: KeywordSpotter,
+    stream: OnlineStream,
+}
+
+impl WakeWordSpotter {
+    fn load(model_dir: &Path) -> Result<Self, String> {
+        if !bundle_complete(model_dir) {
+            return Err(format!(
+                "Wake word model not found or incomplete at {}",
+                model_dir.display()
+            ));
+        }
+        let tokens_txt = std::fs::read_to_string(model_dir.join(KWS_TOKENS))
+            .map_err(|e| format!("Failed to read wake word tokens: {}", e))?;
+        let missing = missing_tokens(WAKE_PHRASE_TOKENS, &tokens_txt);
+        if !missing.is_empty() {
+            return Err(format!(
+                "Wake phrase tokens not in the model vocabulary: {}",
+                missing.join(" ")
+            ));
+        }
+        let keywords_path = model_dir.join(KEYWORDS_FILE);
+        std::fs::write(&keywords_path, format!("{}\n", keywords_line()))
+            .map_err(|e| format!("Failed to write wake word keywords: {}", e))?;
+
+        let to_str = |p: PathBuf| -> Result<String, String> {
+            p.to_str()
+                .ok_or_else(|| "Model path contains invalid UTF-8 characters".to_string())
+                .map(|s| s.to_string())
+        };
+
+        let mut config = KeywordSpotterConfig::default();
+        config.model_config.transducer = OnlineTransducerModelConfig {
+            encoder: Some(to_str(model_dir.join(KWS_ENCODER))?),
+            decoder: Some(to_str(model_dir.join(KWS_DECODER))?),
+            joiner: Some(to_str(model_dir.join(KWS_JOINER))?),
+        };
+        config.model_config.tokens = Some(to_str(model_dir.join(KWS_TOKENS))?);
+        config.model_config.num_threads = 1;
+        config.model_config.provider = Some("cpu".to_string());
+        config.keywords_file = Some(to_str(keywords_path)?);
+        config.keywords_threshold = KEYWORDS_THRESHOLD;
+
+        let spotter = KeywordSpotter::create(&config).ok_or_else(|| {
+            "Failed to create wake word spotter (sherpa-onnx returned null)".to_string()
+        })?;
+        let stream = spotter.create_stream();
+        Ok(Self { spotter, stream })
+    }
+
+    /// Feed one frame; true if the wake phrase finished in it.
+    fn accept(&self, frame: &[f32]) -> bool {
+        self.stream.accept_waveform(WHISPER_SAMPLE_RATE as i32, frame);
+        let mut detected = false;
+        while self.spotter.is_ready(&self.stream) {
+            self.spotter.decode(&self.stream);
+            let keyword = self
+                .spotter
+                .get_result(&self.stream)
+                .map(|r| r.keyword)
+                .unwrap_or_default();
+            if !keyword.is_empty() {
+                // Must reset right after a hit or the same keyword repeats.
+                self.spotter.reset(&self.stream);
+                detected = true;
+            }
+        }
+        detected
+    }
+}
+
+/// The running detector: its capture and the thread the spotter lives on.
+struct WakeWorker {
+    capture: ListeningCapture,
+    detector: JoinHandle<()>,
+}
+
+static WAKE_WORKER: Mutex<Option<WakeWorker>> = Mutex::new(None);
+
+/// Start listening for the wake phrase on `device_name` (None = system
+/// default). Replaces a detector that is already running.
+pub fn start(app_handle: tauri::AppHandle, device_name: Option<String>) -> Result<(), String> {
+    stop();
+    let model_dir = kws_model_dir()
+        .filter(|dir| bundle_complete(dir))
+        .ok_or_else(|| "Wake word model is not downloaded".to_string())?;
+
+    // sherpa-onnx objects stay on the detector thread; load there and report.
+    let (frame_tx, frame_rx) = channel::<Vec<f32>>();
+    let (ready_tx, ready_rx) = channel::<Result<(), String>>();
+    let handle = thread::spawn(move || {
+        let spotter = match WakeWordSpotter::load(&model_dir) {
+            Ok(spotter) => {
+                let _ = ready_tx.send(Ok(()));
+                spotter
+            }
+            Err(e) => {
+                let _ = ready_tx.send(Err(e));
+                return;
+            }
+        };
+        run_detector(&app_handle, &spotter, frame_rx);
+    });
+
+    let loaded = ready_rx
+        .recv()
+        .unwrap_or_else(|_| Err("Wake word thread exited during setup".to_string()));
+    // On failure the sender drops with this scope and the thread exits.
+    let started =
+        loaded.and_then(|()| crate::audio::start_listening(device_name, FRAME_SAMPLES, frame_tx));
+    match started {
+        Ok(capture) => {
+            *WAKE_WORKER.lock_or_recover() = Some(WakeWorker {
+                capture,
+                detector: handle,
+            });
+            tracing::info!(target: "audio", "wake word detector started");
+            Ok(())
+        }
+        Err(e) => {
+            let _ = handle.join();
+            Err(e)
+        }
+    }
+}
+
+/// Stop the detector and release the microphone.
+pub fn stop() {
+    let Some(worker) = WAKE_WORKER.lock_or_recover().take() else {
+        return;
+    };
+    // Closing the capture drops the frame sender, which ends the detector loop.
+    worker.capture.stop();
+    let _ = worker.detector.join();
+    tracing::info!(target: "audio", "wake word detector stopped");
+}
+
+pub fn is_listening() -> bool {
+    // The loop also ends on its own if the capture dies (device unplugged).
+    WAKE_WORKER
+        .lock_or_recover()
+        .as_ref()
+        .is_some_and(|worker| !worker.detector.is_finished())
+}
+
+fn any_recording(app_handle: &tauri::AppHandle) -> bool {
+    let state = app_handle.state::<crate::State>();
+    let status = state.app_state.dictation.lock_or_recover().status;
+    status == DictationStatus::Recording
+}
+
+fn run_detector(
+    app_handle: &tauri::AppHandle,
+    spotter: &WakeWordSpotter,
+    frames: Receiver<Vec<f32>>,
+) {
+    let mut gate = SpeechGate::default();
+    let mut session = WakeSession::default();
+    let mut fed_since_reset = false;
+    for frame in frames {
+        let rms = crate::audio::compute_rms(&frame);
+        let recording = any_recording(app_handle);
+        if session.on_frame(FRAME_MS, rms, recording) == WakeAction::Stop {
+            tracing::info!(target: "pipeline", "wake word: silence ended the recording");
+            events::emit_wake_word(app_handle, WakeWordEvent::SilenceStop);
+        }
+
+        if !session.is_armed()
+            || recording
+            || crate::keyboard::is_app_disabled()
+            || crate::quiet_hours::is_active()
+        {
+            if fed_since_reset {
+                spotter.spotter.reset(&spotter.stream);
+                gate = SpeechGate::default();
+                fed_since_reset = false;
+            }
+            continue;
+        }
+
+        let mut detected = false;
+        for admitted in gate.admit(frame, rms) {
+            fed_since_reset = true;
+            detected |= spotter.accept(&admitted);
+        }
+        if detected {
+            tracing::info!(target: "pipeline", "wake word detected");
+            gate = SpeechGate::default();
+            fed_since_reset = false;
+            session.on_detect();
+            events::emit_wake_word(app_handle, WakeWordEvent::Detected);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Vec<f32> {
+        vec![0.0; FRAME_SAMPLES]
+    }
+
+    #[test]
+    fn gate_drops_silence_and_prepends_pre_roll() {
+        let mut gate = SpeechGate::default();
+        assert!(gate.admit(frame(), 0.001).is_empty());
+        assert!(gate.admit(frame(), 0.001).is_empty());
+        assert_eq!(gate.admit(frame(), 0.1).len(), 2);
+        assert_eq!(gate.admit(frame(), 0.1).len(), 1);
+    }
+
+    #[test]
+    fn gate_keeps_hangover_frames_after_speech() {
+        let mut gate = SpeechGate::default();
+        gate.admit(frame(), 0.1);
+        for _ in 0..HANGOVER_FRAMES {
+            assert_eq!(gate.admit(frame(), 0.0).len(), 1);
+        }
+        assert!(gate.admit(frame(), 0.0).is_empty());
+    }
+
+    #[test]
+    fn wake_recording_stops_after_silence_following_speech() {
+        let mut session = WakeSession::default();
+        session.on_detect();
+        assert!(!session.is_armed());
+        assert_eq!(session.on_frame(FRAME_MS, 0.0, true), WakeAction::None);
+        assert_eq!(session.on_frame(FRAME_MS, 0.1, true), WakeAction::None);
+        let quiet_frames = END_SILENCE_MS / FRAME_MS;
+        for _ in 1..quiet_frames {
+            assert_eq!(session.on_frame(FRAME_MS, 0.0, true), WakeAction::None);
+        }
+        assert_eq!(session.on_frame(FRAME_MS, 0.0, true), WakeAction::Stop);
+    }
+
+    #[test]
+    fn wake_recording_without_speech_stops_after_longer_wait() {
+        let mut session = WakeSession::default();
+        session.on_detect();
+        session.on_frame(FRAME_MS, 0.0, true);
+        let frames = NO_SPEECH_MS / FRAME_MS;
+        for _ in 1..frames {
+            assert_eq!(session.on_frame(FRAME_MS, 0.0, true), WakeAction::None);
+        }
+        assert_eq!(session.on_frame(FRAME_MS, 0.0, true), WakeAction::Stop);
+    }
+
+    #[test]
+    fn hotkey_recordings_are_never_stopped() {
+        let mut session = WakeSession::default();
+        for _ in 0..(NO_SPEECH_MS / FRAME_MS) * 2 {
+            assert_eq!(session.on_frame(FRAME_MS, 0.0, true), WakeAction::None);
+        }
+    }
+
+    #[test]
+    fn refused_start_rearms_after_cooldown() {
+        let mut session = WakeSession::default();
+        session.on_detect();
+        for _ in 0..(START_GRACE_MS / FRAME_MS) {
+            session.on_frame(FRAME_MS, 0.0, false);
+        }
+        assert!(!session.is_armed());
+        for _ in 0..(COOLDOWN_MS / FRAME_MS) {
+            session.on_frame(FRAME_MS, 0.0, false);
+        }
+        assert!(session.is_armed());
+    }
+
+    #[test]
+    fn recording_stopped_elsewhere_returns_to_listening() {
+        let mut session = WakeSession::default();
+        session.on_detect();
+        session.on_frame(FRAME_MS, 0.0, true);
+        assert_eq!(session.on_frame(FRAME_MS, 0.1, false), WakeAction::None);
+        for _ in 0..(COOLDOWN_MS / FRAME_MS) {
+            session.on_frame(FRAME_MS, 0.0, false);
+        }
+        assert!(session.is_armed());
+    }
+
+    #[test]
+    fn missing_tokens_lists_unknown_phrase_tokens() {
+        let tokens_txt = "<blk> 0\n▁HE 1\nY 2\n▁M 3\n";
+        assert_eq!(missing_tokens("▁HE Y ▁M UR", tokens_txt), vec!["UR"]);
+        assert!(missing_tokens("▁HE Y", tokens_txt).is_empty());
+    }
+
+    #[test]
+    fn keywords_line_has_threshold_and_label() {
+        assert_eq!(keywords_line(), "▁HE Y ▁M UR M UR #0.25 @HEY_MURMUR");
+    }
+}
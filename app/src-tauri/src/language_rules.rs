@@ -0,0 +1,289 @@
+//! Per-language rule tables for transcript cleanup.
+//!
+//! Cleanup was written for English: its filler words, stutter collapsing, and
+//! sentence capitalization all assume it. These tables record how other
+//! languages differ, keyed by the Whisper language code a recording used: the
+//! configured language, or the one Whisper detected when set to auto. A
+//! language without a table gets [`GENERIC`], which only tidies spacing and
+//! capitalizes, so cleanup never drops a word it doesn't know to be filler.
+
+/// How cleanup treats one language.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LanguageRules {
+    /// Whisper language code, or empty for [`GENERIC`].
+    pub code: &'static str,
+    /// Standalone filler tokens removed when filler removal is on.
+    pub fillers: &'static [&'static str],
+    /// Closed-class words whose immediate repeat is a stutter, collapsed to
+    /// one. Empty where no list is known to be safe.
+    pub stutter_words: &'static [&'static str],
+    /// Capitalize sentence starts. Off for Chinese and Japanese, where the
+    /// only cased letters are embedded Latin words.
+    pub capitalize: bool,
+    /// Uppercase `i` as `İ` (Turkish and Azerbaijani).
+    pub dotted_i: bool,
+    /// Words are separated by spaces. Off for Chinese and Japanese, where a
+    /// space between two characters is a decoder artifact.
+    pub spaced: bool,
+    /// Punctuation that takes a no-break space before it (French `; : ! ?`).
+    pub spaced_punctuation: &'static [char],
+    /// The decimal separator is a comma, so `3.5` is written `3,5`.
+    pub decimal_comma: bool,
+    /// Words after which `10.30` is a time of day, left as written.
+    pub clock_words: &'static [&'static str],
+}
+
+/// English, and the fallback when auto-detect reported nothing (Parakeet and
+/// CoreML models are English-only).
+pub const ENGLISH: LanguageRules = LanguageRules {
+    code: "en",
+    // Kept short and unambiguous so we never strip a real word (e.g. "Erm"
+    // the name, or "her").
+    fillers: &["um", "uh", "er", "hmm", "uhh", "umm", "erm"],
+    // Only words whose immediate repetition is overwhelmingly a recognition
+    // or stutter artifact. "that" ("that that" is valid) and the auxiliaries
+    // "had"/"is"/"do"/"has" ("had had no effect") are deliberately absent.
+    stutter_words: &[
+        "i", "the", "a", "an", "and", "to", "of", "it", "in", "on", "we", "you", "so", "but",
+        "for", "with", "my", "he", "she", "they",
+    ],
+    capitalize: true,
+    dotted_i: false,
+    spaced: true,
+    spaced_punctuation: &[],
+    decimal_comma: false,
+    clock_words: &[],
+};
+
+/// Languages without a table of their own.
+pub const GENERIC: LanguageRules = LanguageRules {
+    code: "",
+    fillers: &[],
+    stutter_words: &[],
+    capitalize: true,
+    dotted_i: false,
+    spaced: true,
+    spaced_punctuation: &[],
+    decimal_comma: false,
+    clock_words: &[],
+};
+
+const fn decimal_comma(code: &'static str, fillers: &'static [&'static str]) -> LanguageRules {
+    LanguageRules {
+        code,
+        fillers,
+        decimal_comma: true,
+        ..GENERIC
+    }
+}
+
+const fn unspaced(code: &'static str) -> LanguageRules {
+    LanguageRules {
+        code,
+        capitalize: false,
+        spaced: false,
+        ..GENERIC
+    }
+}
+
+const TABLES: &[LanguageRules] = &[
+    ENGLISH,
+    decimal_comma("es", &["eh", "ehm", "mmm"]),
+    LanguageRules {
+        spaced_punctuation: &[';', ':', '!', '?'],
+        ..decimal_comma("fr", &["euh", "heu", "hum"])
+    },
+    LanguageRules {
+        clock_words: &["uhr"],
+        ..decimal_comma("de", &["äh", "ähm", "öhm", "hm"])
+    },
+    decimal_comma("it", &["ehm", "uhm", "mh"]),
+    decimal_comma("pt", &["hã", "ahn", "hum"]),
+    LanguageRules {
+        clock_words: &["uur"],
+        ..decimal_comma("nl", &["eh", "ehm", "uhm"])
+    },
+    decimal_comma("ru", &["э", "эм", "ммм"]),
+    decimal_comma("pl", &["yyy", "eee", "hmm"]),
+    LanguageRules {
+        dotted_i: true,
+        ..decimal_comma("tr", &["ıı", "hımm"])
+    },
+    LanguageRules {
+        dotted_i: true,
+        ..decimal_comma("az", &[])
+    },
+    unspaced("zh"),
+    unspaced("ja"),
+];
+
+impl LanguageRules {
+    /// Rules for a Whisper language code such as `de` or `zh`. Region
+    /// suffixes (`pt-BR`) are ignored. `auto` and an empty code mean English,
+    /// matching cleanup before it knew about languages.
+    pub fn for_code(code: &str) -> &'static LanguageRules {
+        let base = code
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if base.is_empty() || base == "auto" {
+            return &ENGLISH;
+        }
+        TABLES
+            .iter()
+            .find(|rules| rules.code == base)
+            .unwrap_or(&GENERIC)
+    }
+
+    /// Rules for a recording: the configured language, or the one the model
+    /// detected when the setting is auto.
+    pub fn resolve(configured: &str, detected: Option<&str>) -> &'static LanguageRules {
+        let configured = configured.trim();
+        if configured.is_empty() || configured.eq_ignore_ascii_case("auto") {
+            detected.map_or(&ENGLISH, Self::for_code)
+        } else {
+            Self::for_code(configured)
+        }
+    }
+}
+
+/// Chinese and Japanese characters and their full-width punctuation.
+pub(crate) fn is_unspaced_char(c: char) -> bool {
+    matches!(c,
+        '\u{3000}'..='\u{303F}' // CJK punctuation
+        | '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{FF00}'..='\u{FF65}' // Full-width forms
+        | '\u{20000}'..='\u{2FA1F}' // Extensions B-F, outside the BMP
+        | '\u{30000}'..='\u{3134F}' // Extension G
+    )
+}
+
+/// Drop spaces next to a Chinese or Japanese character, keeping those
+/// between two Latin words ("iPhone 15").
+pub(crate) fn join_unspaced(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    for (index, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let before = out.chars().next_back();
+            let after = chars[index + 1..].iter().find(|next| **next != ' ');
+            if before.is_some_and(is_unspaced_char) || after.is_some_and(|c| is_unspaced_char(*c)) {
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Put a no-break space before each of `marks` that ends a word, as French
+/// typography does ("Vraiment ?"). A mark between two characters, as in
+/// `10:30` or `https://`, is left alone.
+pub(crate) fn space_before(text: &str, marks: &[char]) -> String {
+    let mut out = String::with_capacity(text.len() + 8);
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let ends_word = chars
+            .peek()
+            .is_none_or(|next| next.is_whitespace() || marks.contains(next));
+        let after_word = out
+            .chars()
+            .next_back()
+            .is_some_and(|prev| !prev.is_whitespace() && !marks.contains(&prev));
+        if marks.contains(&c) && ends_word && after_word {
+            out.push('\u{a0}');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Write `3.5` as `3,5`. Only a number with a single point is changed, and
+/// not when three digits follow it (`1.000` is a thousands group), when it is
+/// a time before one of `clock_words`, or inside a longer token like `2.0.1`.
+pub(crate) fn localize_decimals(text: &str, clock_words: &[&str]) -> String {
+    let tokens: Vec<&str> = text.split(' ').collect();
+    let mut out = Vec::with_capacity(tokens.len());
+    for (index, token) in tokens.iter().enumerate() {
+        let number = token.trim_end_matches(['.', ',', '!', '?', ';', ':']);
+        let number = if number.len() < token.len() && number.ends_with(|c: char| c.is_ascii_digit())
+        {
+            number
+        } else {
+            token
+        };
+        let is_decimal = number.split_once('.').is_some_and(|(whole, fraction)| {
+            !whole.is_empty()
+                && whole.chars().all(|c| c.is_ascii_digit())
+                && !fraction.is_empty()
+                && fraction.chars().all(|c| c.is_ascii_digit())
+                && fraction.len() != 3
+        });
+        let before_clock_word = tokens.get(index + 1).is_some_and(|next| {
+            let next = next.trim_end_matches(|c: char| !c.is_alphanumeric());
+            clock_words
+                .iter()
+                .any(|word| next.eq_ignore_ascii_case(word))
+        });
+        if is_decimal && !before_clock_word {
+            out.push(format!(
+                "{}{}",
+                number.replacen('.', ",", 1),
+                &token[number.len()..]
+            ));
+        } else {
+            out.push(token.to_string());
+        }
+    }
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_configured_then_detected_language() {
+        assert_eq!(LanguageRules::resolve("de", Some("en")).code, "de");
+        assert_eq!(LanguageRules::resolve("auto", Some("ja")).code, "ja");
+        assert_eq!(LanguageRules::resolve("auto", None).code, "en");
+        assert_eq!(LanguageRules::resolve("", None).code, "en");
+        assert_eq!(LanguageRules::for_code("pt-BR").code, "pt");
+        assert_eq!(LanguageRules::for_code("sv"), &GENERIC);
+    }
+
+    #[test]
+    fn join_unspaced_keeps_spaces_between_latin_words() {
+        assert_eq!(join_unspaced("今日は 晴れ です 。"), "今日は晴れです。");
+        assert_eq!(join_unspaced("我 用 iPhone 15 打字"), "我用iPhone 15打字");
+        assert_eq!(join_unspaced("plain english text"), "plain english text");
+    }
+
+    #[test]
+    fn space_before_only_touches_word_final_marks() {
+        let marks = [';', ':', '!', '?'];
+        assert_eq!(
+            space_before("Vraiment? Oui! Voici: 10:30 et https://exemple.fr", &marks),
+            "Vraiment\u{a0}? Oui\u{a0}! Voici\u{a0}: 10:30 et https://exemple.fr"
+        );
+        assert_eq!(space_before("Quoi?!", &marks), "Quoi\u{a0}?!");
+    }
+
+    #[test]
+    fn localize_decimals_skips_thousands_versions_and_times() {
+        assert_eq!(
+            localize_decimals("Es sind 3.5 Grad und 2.25.", &["uhr"]),
+            "Es sind 3,5 Grad und 2,25."
+        );
+        assert_eq!(
+            localize_decimals("1.000 Leute um 10.30 Uhr mit Version 2.0.1", &["uhr"]),
+            "1.000 Leute um 10.30 Uhr mit Version 2.0.1"
+        );
+        assert_eq!(localize_decimals("Ende.", &[]), "Ende.");
+    }
+}
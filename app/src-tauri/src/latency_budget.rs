@@ -0,0 +1,322 @@
+//! Latency budget: notice when a model keeps decoding slower than the user
+//! asked for, and name a faster one.
+//!
+//! Opt-in (`latencyBudget`). Each live take's realtime factor (decode time
+//! over the length of the speech decoded) is kept for the last few takes per
+//! model. When most of them blew the budget, including the latest, the
+//! pipeline emits `latency-budget-exceeded` naming a faster installed model,
+//! such as a quantized variant of the same one. With `autoSwitch` on, and
+//! when the slow model is the one chosen in Settings, the frontend switches
+//! to it; otherwise it only says so.
+
+use crate::dictation_context::LatencyBudget;
+use crate::model_runtime::{self, MODEL_DEFINITIONS};
+use crate::MutexExt;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use tauri::Emitter;
+
+pub const LATENCY_BUDGET_EXCEEDED: &str = "latency-budget-exceeded";
+
+/// Takes remembered per model.
+const WINDOW: usize = 5;
+/// Takes in the window that must be over budget before anything is said.
+const OVER_BUDGET_TAKES: usize = 3;
+/// Shorter takes are dominated by fixed per-decode overhead, so their factor
+/// says little about the model.
+const MIN_SPEECH_MS: u64 = 1_000;
+
+/// Payload of `latency-budget-exceeded`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyBudgetExceeded {
+    pub recording_id: u64,
+    /// The model that kept missing the budget.
+    pub model: String,
+    /// Median realtime factor of its recent takes.
+    pub realtime_factor: f32,
+    pub max_realtime_factor: f32,
+    /// An installed model expected to fit, if there is one.
+    pub faster_model: Option<String>,
+    /// The frontend should switch the chosen model to `faster_model`.
+    pub switch: bool,
+    /// What happened and what the user can do, as a sentence for the main window.
+    pub guidance: String,
+}
+
+/// Recent realtime factor of one model, for Settings.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelLatency {
+    pub model: String,
+    pub realtime_factor: f32,
+    pub takes: usize,
+}
+
+#[derive(Debug, Default)]
+struct RecentFactors {
+    by_model: BTreeMap<String, VecDeque<f32>>,
+}
+
+impl RecentFactors {
+    /// Remember a take and say whether `model` has now repeatedly blown the
+    /// budget; the model's window is cleared when it has, so the next notice
+    /// needs fresh evidence.
+    fn record(&mut self, model: &str, factor: f32, max_factor: f32) -> Option<f32> {
+        let recent = self.by_model.entry(model.to_string()).or_default();
+        if recent.len() == WINDOW {
+            recent.pop_front();
+        }
+        recent.push_back(factor);
+        let over = recent.iter().filter(|&&f| f > max_factor).count();
+        if factor <= max_factor || over < OVER_BUDGET_TAKES {
+            return None;
+        }
+        let factor = median(recent);
+        recent.clear();
+        Some(factor)
+    }
+
+    fn median(&self, model: &str) -> Option<f32> {
+        self.by_model
+            .get(model)
+            .filter(|recent| !recent.is_empty())
+            .map(median)
+    }
+}
+
+fn median(recent: &VecDeque<f32>) -> f32 {
+    let mut sorted: Vec<f32> = recent.iter().copied().collect();
+    sorted.sort_by(f32::total_cmp);
+    sorted[sorted.len() / 2]
+}
+
+static RECENT: Mutex<RecentFactors> = Mutex::new(RecentFactors {
+    by_model: BTreeMap::new(),
+});
+
+/// The most accurate installed model on `model_name`'s backend that is
+/// smaller and rated at least as fast, so a quantized variant of the same
+/// model counts, skipping any seen over budget itself. Unless the take is in
+/// English, a multilingual model is only swapped for another multilingual
+/// one. Unrated models (custom files, plugins, cloud) get none.
+pub fn faster_model(
+    model_name: &str,
+    language: &str,
+    over_budget: impl Fn(&str) -> bool,
+    installed: impl Fn(&str) -> bool,
+) -> Option<&'static str> {
+    let current = model_runtime::model_definition(model_name).ok()?;
+    if current.tradeoffs.speed == 0 {
+        return None;
+    }
+    MODEL_DEFINITIONS
+        .iter()
+        .filter(|definition| {
+            definition.backend == current.backend
+                && model_runtime::model_supported(definition)
+                && definition.tradeoffs.speed >= current.tradeoffs.speed
+                && definition.resident_mb < current.resident_mb
+                && (language == "en"
+                    || !current.capabilities.multilingual
+                    || definition.capabilities.multilingual)
+                && installed(definition.model_name)
+                && !over_budget(definition.model_name)
+        })
+        .max_by_key(|definition| {
+            (
+                definition.tradeoffs.accuracy,
+                definition.tradeoffs.speed,
+                std::cmp::Reverse(definition.resident_mb),
+            )
+        })
+        .map(|definition| definition.model_name)
+}
+
+fn display_name(model: &str) -> String {
+    model_runtime::model_definition(model)
+        .map(|definition| model_runtime::model_label(definition, model))
+        .unwrap_or_else(|_| model.to_string())
+}
+
+/// `1.5` as "1.5×", `0.25` as "0.25×".
+fn times(factor: f32) -> String {
+    format!("{}×", (factor * 100.0).round() / 100.0)
+}
+
+pub fn guidance(
+    model: &str,
+    factor: f32,
+    max_factor: f32,
+    faster_model: Option<&str>,
+    switch: bool,
+) -> String {
+    let label = display_name(model);
+    let slow = format!(
+        "{label} has been taking about {} the length of your recordings, over your {} budget.",
+        times(factor),
+        times(max_factor)
+    );
+    match faster_model.map(display_name) {
+        Some(faster) if switch => format!("{slow} Switched to {faster}."),
+        Some(faster) => {
+            format!("{slow} {faster} should be faster; choose it in Settings › Transcription.")
+        }
+        None => format!(
+            "{slow} No faster model is installed; download a smaller or quantized one in Settings › Transcription."
+        ),
+    }
+}
+
+/// Record one live take's decode speed and, when `budget`'s model has
+/// repeatedly missed it, log and emit `latency-budget-exceeded`.
+pub fn observe(
+    app: &tauri::AppHandle,
+    recording_id: u64,
+    model: &str,
+    language: &str,
+    budget: &LatencyBudget,
+    speech_samples: usize,
+    decode_ms: u64,
+) {
+    let speech_ms = speech_samples as u64 * 1_000 / 16_000;
+    if speech_ms < MIN_SPEECH_MS {
+        return;
+    }
+    let factor = decode_ms as f32 / speech_ms as f32;
+    let (factor, faster) = {
+        let mut recent = RECENT.lock_or_recover();
+        let Some(factor) = recent.record(model, factor, budget.max_rtf) else {
+            return;
+        };
+        let over_budget =
+            |candidate: &str| recent.median(candidate).is_some_and(|f| f > budget.max_rtf);
+        let faster = faster_model(model, language, over_budget, model_runtime::model_installed);
+        (factor, faster)
+    };
+    let switch = budget.auto_switch && faster.is_some();
+    tracing::info!(
+        target: "pipeline",
+        recording_id,
+        model,
+        realtime_factor = factor,
+        max_realtime_factor = budget.max_rtf,
+        faster_model = faster,
+        switch,
+        "latency_budget_exceeded"
+    );
+    let _ = app.emit(
+        LATENCY_BUDGET_EXCEEDED,
+        LatencyBudgetExceeded {
+            recording_id,
+            model: model.to_string(),
+            realtime_factor: factor,
+            max_realtime_factor: budget.max_rtf,
+            faster_model: faster.map(str::to_string),
+            switch,
+            guidance: guidance(model, factor, budget.max_rtf, faster, switch),
+        },
+    );
+}
+
+/// Median recent realtime factor of every model that has live takes since
+/// launch (or since its last notice), fastest first.
+#[tauri::command]
+pub fn get_latency_budget_stats() -> Vec<ModelLatency> {
+    let recent = RECENT.lock_or_recover();
+    let mut stats: Vec<ModelLatency> = recent
+        .by_model
+        .iter()
+        .filter(|(_, takes)| !takes.is_empty())
+        .map(|(model, takes)| ModelLatency {
+            model: model.clone(),
+            realtime_factor: median(takes),
+            takes: takes.len(),
+        })
+        .collect();
+    stats.sort_by(|a, b| a.realtime_factor.total_cmp(&b.realtime_factor));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_repeatedly_blown_budget_is_reported() {
+        let mut recent = RecentFactors::default();
+        assert_eq!(recent.record("large-v3-turbo", 2.0, 1.5), None);
+        assert_eq!(recent.record("large-v3-turbo", 0.5, 1.5), None);
+        assert_eq!(recent.record("large-v3-turbo", 1.8, 1.5), None);
+        assert_eq!(recent.record("large-v3-turbo", 1.6, 1.5), Some(1.8));
+        // The window starts over after a notice.
+        assert_eq!(recent.median("large-v3-turbo"), None);
+        assert_eq!(recent.record("large-v3-turbo", 0.4, 1.5), None);
+        assert_eq!(recent.record("large-v3-turbo", 1.9, 1.5), None);
+        assert_eq!(recent.record("large-v3-turbo", 2.0, 1.5), None);
+        assert_eq!(recent.record("large-v3-turbo", 0.3, 1.5), None);
+        assert_eq!(recent.median("large-v3-turbo"), Some(1.9));
+        assert_eq!(recent.median("small.en"), None);
+    }
+
+    #[test]
+    fn old_takes_fall_out_of_the_window() {
+        let mut recent = RecentFactors::default();
+        for factor in [3.0, 3.0, 0.1, 0.1, 0.1, 0.1] {
+            assert_eq!(recent.record("medium.en", factor, 1.0), None);
+        }
+        assert_eq!(recent.by_model["medium.en"].len(), WINDOW);
+        assert_eq!(recent.record("medium.en", 3.0, 1.0), None);
+    }
+
+    #[test]
+    fn faster_model_prefers_accuracy_on_the_same_backend() {
+        let installed = |name: &str| matches!(name, "tiny.en" | "small.en" | "large-v3-turbo-q5_0");
+        let never = |_: &str| false;
+        assert_eq!(
+            faster_model("large-v3-turbo", "en", never, installed),
+            Some("large-v3-turbo-q5_0")
+        );
+        assert_eq!(
+            faster_model(
+                "large-v3-turbo",
+                "en",
+                |name| name == "large-v3-turbo-q5_0",
+                installed
+            ),
+            Some("small.en")
+        );
+        assert_eq!(
+            faster_model("large-v3-turbo-q5_0", "en", never, installed),
+            Some("small.en")
+        );
+        assert_eq!(faster_model("tiny.en", "en", never, installed), None);
+        assert_eq!(
+            faster_model("custom:mine.bin", "en", never, installed),
+            None
+        );
+    }
+
+    #[test]
+    fn faster_model_keeps_non_english_takes_multilingual() {
+        let installed = |name: &str| matches!(name, "small.en" | "base.en");
+        assert_eq!(
+            faster_model("large-v3-turbo", "de", |_| false, installed),
+            None
+        );
+    }
+
+    #[test]
+    fn guidance_says_what_was_done() {
+        let switched = guidance("large-v3-turbo", 1.84, 1.5, Some("small.en"), true);
+        assert!(switched.contains("about 1.84× the length"));
+        assert!(switched.contains("your 1.5× budget"));
+        assert!(switched.ends_with("Switched to Whisper Small (English)."));
+        assert!(
+            guidance("large-v3-turbo", 2.0, 1.0, Some("small.en"), false)
+                .contains("choose it in Settings")
+        );
+        assert!(guidance("large-v3-turbo", 2.0, 1.0, None, false).contains("No faster model"));
+    }
+}
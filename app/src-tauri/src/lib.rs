@@ -1,5 +1,9 @@
 #[cfg(target_os = "macos")]
 mod alloc;
+mod alternatives;
+mod ambient;
+mod app_triggers;
+mod append_edit;
 mod audio;
 mod audio_decode;
 // `pub` so the headless benchmark runner (tests/headless_benchmark.rs) can
@@ -8,33 +12,79 @@ mod audio_decode;
 pub mod benchmark;
 mod cleanup;
 mod cli_command;
+mod clipboard_audio;
 mod commands;
+mod confidence_retry;
+mod config_presets;
+mod context_biasing;
+#[cfg(target_os = "macos")]
+mod core_audio;
 mod correct_and_teach;
 mod correction;
+mod correction_feedback;
+mod crash_reports;
+mod cursor_context;
 mod dictation_context;
+mod dictation_macros;
+mod dictation_stats;
+mod download_network;
+mod download_queue;
 pub mod evaluation;
+mod events;
 mod file_output;
 mod frontmost;
+mod gpu_memory;
+mod hallucination;
+mod history_store;
 mod ide_context;
+mod injection_preview;
 mod injector;
+mod input_quality;
+mod instance;
 mod keyboard;
 mod knowledge_store;
+mod kws;
+mod language_rules;
+mod latency_budget;
 pub mod llm_sidecar;
+mod markers;
+mod meeting;
+mod mic_privacy;
+mod mic_test;
 mod model_runtime;
+mod notes;
+mod notifications;
 mod performance_metrics;
+mod permissions;
+mod pipeline_trace;
 mod platform;
+mod portable;
+mod power;
+mod pre_roll;
+mod quiet_hours;
+mod recording_journal;
+mod redaction;
+mod replacement_rules;
 mod resource_monitor;
+mod retention;
+mod rewrite_templates;
 mod selection;
 mod smart_formatting;
 mod state;
+mod storage;
+mod support_bundle;
+mod system_status;
 pub mod telemetry;
+mod text_metrics;
 pub mod transcriber;
 mod transcript_transform;
+mod transcription_queue;
 mod transform_apply;
 mod transform_diagnostics;
 pub mod transform_flow;
 mod transform_presets;
 mod transform_trace;
+mod updater;
 mod vad;
 mod vocab;
 mod vocabulary_alias;
@@ -88,8 +138,8 @@ pub fn ffi_heap_bytes() -> u64 {
 
 use state::AppState;
 use std::sync::{Mutex, MutexGuard};
-use tauri::menu::{MenuBuilder, MenuItemBuilder};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::Emitter;
 use tauri::Manager;
 #[cfg(target_os = "macos")]
 use tauri::RunEvent;
@@ -117,6 +167,21 @@ pub(crate) struct State {
     pub(crate) transform_diagnostics: transform_diagnostics::TransformDiagnostics,
     /// Cached notch dimensions (notch_width, menu_bar_height) from setup (main thread).
     pub(crate) notch_info: Mutex<Option<(f64, f64)>>,
+    /// Which displays show the notch overlay (Settings → General).
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) overlay_display_mode: Mutex<commands::overlay::OverlayDisplayMode>,
+    /// Notch island vs floating pill, and where dragged pills sit.
+    pub(crate) overlay_style: Mutex<commands::overlay::OverlayStyle>,
+    /// What the overlay does over a fullscreen app, and whether it is
+    /// currently hidden for one (rather than by `hide_overlay`).
+    pub(crate) overlay_fullscreen_mode: Mutex<commands::overlay::OverlayFullscreenMode>,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) overlay_hidden_for_fullscreen: Mutex<bool>,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) overlay_pill_position: Mutex<Option<commands::overlay::PillPosition>>,
+    /// The display each overlay window (by label) was last placed on.
+    pub(crate) overlay_placements:
+        Mutex<std::collections::HashMap<String, commands::overlay::DisplayInfo>>,
     /// The selection-bounds anchor from the most recent `show_transform_popover`
     /// call, so `set_transform_popover_expanded` can resize/reposition for a
     /// new size class without the caller re-supplying the anchor.
@@ -155,7 +220,8 @@ impl llm_sidecar::HostGuard for AppHostGuard {
         {
             return Some("fileTranscription");
         }
-        if state.app_state.dictation.lock_or_recover().status != state::DictationStatus::Idle {
+        let status = state.app_state.dictation.lock_or_recover().status;
+        if !status.is_settled() {
             return Some("recording");
         }
         None
@@ -205,6 +271,18 @@ pub fn run() {
     #[cfg(target_os = "linux")]
     apply_linux_webkit_env_defaults(|k| std::env::var_os(k), |k, v| std::env::set_var(k, v));
 
+    // A second launch of the same build brings the first one forward
+    // instead of opening its own windows, microphone, and hotkey.
+    let context = tauri::generate_context!();
+    let product_name = context
+        .config()
+        .product_name
+        .clone()
+        .unwrap_or_else(|| "Murmur".to_string());
+    if instance::claim(&context.config().identifier, &product_name) == instance::Claim::Forwarded {
+        return;
+    }
+
     let app = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_autostart::init(
@@ -223,6 +301,12 @@ pub fn run() {
             performance: performance_metrics::PerformanceMetrics::default(),
             transform_diagnostics: transform_diagnostics::TransformDiagnostics::default(),
             notch_info: Mutex::new(None),
+            overlay_display_mode: Mutex::new(Default::default()),
+            overlay_style: Mutex::new(Default::default()),
+            overlay_fullscreen_mode: Mutex::new(Default::default()),
+            overlay_hidden_for_fullscreen: Mutex::new(false),
+            overlay_pill_position: Mutex::new(None),
+            overlay_placements: Mutex::new(Default::default()),
             transform_popover_anchor: Mutex::new(None),
             transform_main_was_visible: Mutex::new(None),
             transform_runtime: std::sync::Arc::new(llm_sidecar::LlmSidecar::new()),
@@ -238,11 +322,37 @@ pub fn run() {
             commands::recording::count_vocab_tokens,
             commands::recording::preview_vocabulary_aliases,
             commands::recording::transcribe_file,
+            commands::recording::transcribe_clipboard_audio,
+            commands::recording::get_append_buffer,
+            commands::recording::set_last_transcription,
+            commands::recording::copy_last_transcription,
+            commands::recording::paste_last_transcription,
+            commands::recording::commit_append_buffer,
+            commands::recording::discard_append_buffer,
+            commands::recording::get_pending_injection,
+            commands::recording::confirm_injection,
+            commands::recording::discard_injection,
             commands::recording::scan_code_vocab,
             commands::recording::cancel_code_vocab_scan,
             commands::recording::get_ide_context_status,
             commands::recording::refresh_ide_context,
             commands::recording::clear_ide_context,
+            commands::profiles::list_app_profiles,
+            commands::profiles::upsert_app_profile,
+            commands::profiles::delete_app_profile,
+            commands::profiles::get_default_inject_blocklist,
+            commands::rewrite_templates::list_rewrite_templates,
+            commands::rewrite_templates::upsert_rewrite_template,
+            commands::rewrite_templates::delete_rewrite_template,
+            commands::rewrite_templates::export_rewrite_templates,
+            commands::rewrite_templates::import_rewrite_templates,
+            commands::replacement_rules::list_replacement_rules,
+            commands::replacement_rules::upsert_replacement_rule,
+            commands::replacement_rules::delete_replacement_rule,
+            commands::replacement_rules::reorder_replacement_rules,
+            commands::replacement_rules::preview_replacement_rules,
+            commands::replacement_rules::export_replacement_rules,
+            commands::replacement_rules::import_replacement_rules,
             commands::correct_and_teach::propose_learned_correction,
             commands::correct_and_teach::propose_specific_learned_correction,
             commands::correct_and_teach::confirm_learned_correction,
@@ -257,15 +367,33 @@ pub fn run() {
             commands::permissions::check_microphone_permission_status,
             commands::permissions::reset_microphone_permission,
             commands::permissions::list_audio_devices,
+            commands::permissions::set_input_device_preferences,
+            commands::permissions::set_low_quality_input_action,
             commands::keyboard::start_keyboard_listener,
             commands::keyboard::stop_keyboard_listener,
             commands::keyboard::update_keyboard_key,
             commands::keyboard::set_keyboard_recording,
+            commands::keyboard::set_dictation_profile_keys,
+            commands::keyboard::set_append_commit_key,
+            commands::keyboard::set_paste_last_key,
+            commands::keyboard::set_clipboard_audio_key,
+            commands::keyboard::set_marker_key,
+            markers::set_spoken_markers,
+            commands::keyboard::diagnose_hotkey,
+            commands::keyboard::calibrate_hotkey_timing,
+            commands::keyboard::start_keyboard_diagnostics,
+            instance::get_instance_status,
+            commands::keyboard::get_keyboard_listener_health,
+            commands::keyboard::retry_keyboard_listener,
             commands::keyboard::set_app_disabled,
             commands::keyboard::get_app_disabled,
             commands::keyboard::start_transform_listener,
             commands::keyboard::stop_transform_listener,
             commands::keyboard::set_transform_key,
+            commands::wake_word::start_wake_word,
+            commands::wake_word::stop_wake_word,
+            commands::wake_word::get_wake_word_status,
+            commands::wake_word::download_wake_word_model,
             commands::recording::transform_status,
             transform_apply::apply_transform_result,
             transform_apply::undo_transform,
@@ -289,7 +417,9 @@ pub fn run() {
             commands::knowledge::import_knowledge_from_file,
             commands::knowledge::delete_all_knowledge,
             commands::logging::get_log_contents,
+            commands::logging::query_logs,
             commands::logging::clear_logs,
+            commands::logging::purge_all_transcripts,
             commands::logging::log_frontend,
             commands::logging::open_log_viewer,
             commands::performance::list_performance_runs,
@@ -304,17 +434,99 @@ pub fn run() {
             commands::transform_diagnostics::delete_transform_diagnostic_capture,
             commands::models::check_model_exists,
             commands::models::check_specific_model_exists,
+            commands::models::get_model_catalog,
             commands::models::get_model_runtime_catalog,
             commands::models::get_model_runtime_status,
+            commands::models::get_loaded_backends,
             commands::models::download_model,
+            commands::models::download_custom_model,
+            commands::models::list_transcription_plugins,
+            commands::models::register_transcription_plugin,
+            commands::models::unregister_transcription_plugin,
+            commands::models::set_cloud_transcription,
+            commands::models::get_cloud_api_key_stored,
+            commands::models::set_cloud_api_key,
+            commands::models::clear_cloud_api_key,
+            commands::models::import_model,
+            download_network::set_download_network,
+            download_network::test_download_connectivity,
+            download_queue::queue_model_downloads,
+            download_queue::get_download_queue,
+            download_queue::remove_queued_download,
+            download_queue::set_download_queue_paused,
             commands::transform_model::transform_model_status,
             commands::transform_model::download_transform_model,
             commands::transform_model::remove_transform_model,
             commands::transform_model::reset_transform_runtime,
             frontmost::list_running_applications,
+            permissions::get_permission_report,
+            system_status::get_system_status,
+            updater::check_for_update,
+            updater::download_update,
+            updater::install_update_on_quit,
+            system_status::list_focus_modes,
+            quiet_hours::set_quiet_hours,
+            quiet_hours::get_quiet_hours_status,
+            power::get_power_status,
+            recording_journal::get_orphaned_recording,
+            recording_journal::transcribe_orphaned_recording,
+            recording_journal::discard_orphaned_recording,
+            crash_reports::list_crash_reports,
+            crash_reports::read_crash_report,
+            crash_reports::export_crash_report,
+            support_bundle::generate_support_bundle,
+            support_bundle::report_issue,
+            storage::get_disk_usage_report,
+            storage::purge_storage,
+            retention::set_storage_retention,
+            retention::run_storage_cleanup,
+            dictation_stats::get_dictation_stats,
+            dictation_stats::clear_dictation_stats,
+            app_triggers::set_app_triggers,
+            app_triggers::get_ambient_prompt,
+            ambient::start_ambient_mode,
+            ambient::stop_ambient_mode,
+            ambient::is_ambient_mode_active,
+            ambient::get_ambient_log,
+            ambient::clear_ambient_log,
+            meeting::start_meeting,
+            meeting::stop_meeting,
+            meeting::get_meeting_status,
+            mic_privacy::get_recording_privacy_report,
+            mic_privacy::kill_microphone,
+            mic_test::start_mic_test,
+            mic_test::stop_mic_test,
+            pre_roll::start_pre_roll,
+            pre_roll::stop_pre_roll,
+            confidence_retry::get_confidence_retry_stats,
+            latency_budget::get_latency_budget_stats,
+            pipeline_trace::get_last_pipeline_trace,
+            correction_feedback::correct_transcription,
+            correction_feedback::get_learned_corrections,
+            correction_feedback::clear_learned_corrections,
+            notes::list_notes,
+            notes::get_note,
+            notes::add_note_entry,
+            notes::update_note_entry,
+            notes::delete_note_entry,
+            notes::delete_note,
+            notes::copy_note,
+            notes::export_note,
+            history_store::load_encrypted_history,
+            history_store::save_encrypted_history,
+            history_store::delete_encrypted_history,
+            portable::export_settings,
+            portable::import_settings,
+            portable::export_history,
+            portable::import_history,
+            portable::read_data_folder,
+            portable::write_data_folder,
+            config_presets::set_config_presets,
+            config_presets::apply_config_preset,
             commands::benchmark::get_benchmark_models,
             commands::benchmark::get_benchmark_activity,
             commands::benchmark::run_benchmark,
+            commands::benchmark::run_accuracy_self_test,
             commands::benchmark::cancel_benchmark,
             commands::benchmark::save_benchmark_report,
             commands::benchmark::open_benchmark_output_folder,
@@ -322,8 +534,14 @@ pub fn run() {
             commands::overlay::show_overlay,
             commands::overlay::hide_overlay,
             commands::overlay::set_overlay_expanded,
+            commands::overlay::set_overlay_state,
             commands::overlay::show_main_window,
+            commands::overlay::overlay_action,
             commands::overlay::get_overlay_geometry,
+            commands::overlay::set_overlay_display,
+            commands::overlay::set_overlay_style,
+            commands::overlay::set_overlay_fullscreen,
+            commands::overlay::commit_overlay_pill_position,
             commands::transform_popover::get_transform_popover_geometry,
             commands::transform_popover::show_transform_popover,
             commands::transform_popover::hide_transform_popover,
@@ -332,7 +550,8 @@ pub fn run() {
             commands::transform_popover::get_transform_review_content,
             telemetry::get_event_history,
             telemetry::clear_event_history,
-            resource_monitor::get_resource_usage
+            resource_monitor::get_resource_usage,
+            resource_monitor::get_resource_timeline
         ])
         .on_window_event(|window, event| {
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
@@ -345,7 +564,10 @@ pub fn run() {
             }
         })
         .setup(|app| {
+            // Before telemetry, so a report for a crashed session gets its log tail.
+            crash_reports::init();
             telemetry::init(app.handle().clone());
+            instance::listen_for_activation(app.handle().clone());
 
             let performance_root = app.path().app_data_dir()?.join("diagnostics");
             if let Err(error) = app
@@ -388,6 +610,20 @@ pub fn run() {
                 "personal knowledge store initialized"
             );
 
+            dictation_stats::init(&app.path().app_data_dir()?);
+            correction_feedback::init(&app.path().app_data_dir()?);
+            ambient::init(&app.path().app_data_dir()?);
+            notes::init(&app.path().app_data_dir()?);
+            download_queue::init(app.handle(), &app.path().app_data_dir()?);
+            retention::init(app.handle());
+
+            // Adopt a recording journal left by a crash before any new
+            // recording can overwrite it; the main window offers recovery.
+            let journal_root = app.path().app_data_dir()?.join("recording-journal");
+            if let Some(orphan) = recording_journal::init(journal_root) {
+                let _ = app.emit("orphaned-recording-found", orphan);
+            }
+
             tracing::info!(target: "system", "app setup — Murmur v{}", env!("CARGO_PKG_VERSION"));
 
             // Emit startup baseline memory snapshot
@@ -432,10 +668,13 @@ pub fn run() {
             // focusable:false sets ignoresMouseEvents=true on macOS;
             // we override that while keeping the window non-activating.
             // On Linux, skip the overlay — it's designed for the macOS notch.
+            // Windows shows it as a floating pill instead (below).
             #[cfg(target_os = "macos")]
             if let Some(overlay_win) = app.get_webview_window("overlay") {
                 tracing::info!(target: "system", "setup: overlay window found, enabling cursor events");
-                commands::overlay::position_overlay_default(&overlay_win, notch);
+                // Places the overlay on the right display for the default
+                // mode; the frontend applies the saved mode once it loads.
+                commands::overlay::refresh_overlay_placement(app.handle(), true);
                 let _ = overlay_win.show();
                 if let Err(e) = overlay_win.set_ignore_cursor_events(false) {
                     tracing::warn!(target: "system", "Failed to set overlay cursor events: {}", e);
@@ -443,11 +682,32 @@ pub fn run() {
             } else {
                 tracing::warn!(target: "system", "setup: overlay window NOT found");
             }
+            // Windows floats the overlay as a pill; there is no notch to find.
+            #[cfg(target_os = "windows")]
+            if let Some(overlay_win) = app.get_webview_window("overlay") {
+                commands::overlay::place_overlay_on_primary(app.handle());
+                let _ = overlay_win.show();
+            }
 
             // Listen for display config changes (monitor plug/unplug, lid open/close)
             // to re-detect notch info and reposition the overlay.
             commands::overlay::register_screen_change_observer(app.handle().clone());
 
+            // Poll the macOS Focus mode and watch for screen lock.
+            system_status::start(app.handle());
+
+            // Gate the hotkeys during scheduled quiet hours.
+            quiet_hours::start(app.handle());
+
+            // React when an app with a trigger comes forward.
+            app_triggers::start(app.handle());
+
+            // Watch microphone, Accessibility, and Input Monitoring status.
+            permissions::start(app.handle());
+
+            // Poll the power source for per-source setting defaults.
+            power::start(app.handle());
+
             // Overwrite the transform-review window's initial size from Rust's
             // COMPACT_W/COMPACT_H so tauri.conf.json's matching literal is only
             // ever a startup-flash guard, never the source of truth.
@@ -455,22 +715,11 @@ pub fn run() {
 
             // Restore tray icon (removed by PR #63 overlay work).
             let idle_icon_data = commands::tray::make_tray_icon_data();
-            let show_item = MenuItemBuilder::with_id("show", "Show Murmur").build(app)?;
-            let disabled_item = tauri::menu::CheckMenuItemBuilder::with_id("toggle_disabled", "Disable Murmur")
-                .checked(false)
-                .build(app)?;
-            let quit_item = MenuItemBuilder::with_id("quit", "Quit Murmur").build(app)?;
-            let tray_menu = MenuBuilder::new(app)
-                .item(&show_item)
-                .item(&disabled_item)
-                .separator()
-                .item(&quit_item)
-                .build()?;
-            commands::keyboard::register_tray_disabled_item(disabled_item.clone());
+            let tray_menu = commands::tray::build_menu(app)?;
             let handle = app.handle().clone();
             TrayIconBuilder::with_id("main-tray")
                 .icon(tauri::image::Image::new(&idle_icon_data, 66, 66))
-                .icon_as_template(false)
+                .icon_as_template(true)
                 .tooltip("Murmur")
                 .menu(&tray_menu)
                 .show_menu_on_left_click(false)
@@ -482,6 +731,18 @@ pub fn run() {
                                 let _ = win.set_focus();
                             }
                         }
+                        "transcribe_clipboard_audio" => {
+                            let app_handle = app_handle.clone();
+                            tauri::async_runtime::spawn(async move {
+                                let state = app_handle.state::<State>();
+                                // Failures are logged and emitted by the command.
+                                let _ = commands::recording::transcribe_clipboard_audio(
+                                    app_handle.clone(),
+                                    state,
+                                )
+                                .await;
+                            });
+                        }
                         "toggle_disabled" => {
                             let new_disabled = !keyboard::is_app_disabled();
                             if let Err(e) = commands::keyboard::set_app_disabled(app_handle.clone(), new_disabled) {
@@ -491,7 +752,9 @@ pub fn run() {
                         "quit" => {
                             app_handle.exit(0);
                         }
-                        _ => {}
+                        other => {
+                            commands::tray::handle_menu_event(app_handle, other);
+                        }
                     }
                 })
                 .on_tray_icon_event(move |_tray, event| {
@@ -510,7 +773,7 @@ pub fn run() {
 
             Ok(())
         })
-        .build(tauri::generate_context!())
+        .build(context)
         .expect("error while building tauri application");
 
     app.run(|_app_handle, _event| {
@@ -533,6 +796,11 @@ pub fn run() {
             }
         }
 
+        // Apply an update the user chose to install on quit.
+        if let RunEvent::Exit = &_event {
+            updater::install_on_exit();
+        }
+
         // App-exit teardown: stop any resident local-LLM helper so it never
         // outlives the app (no-op when no child is running).
         #[cfg(target_os = "macos")]
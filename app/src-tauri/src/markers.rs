@@ -0,0 +1,348 @@
+//! Section markers in long dictations.
+//!
+//! While a recording runs, pressing the marker key (`markerKey`) notes the
+//! moment; with `spokenMarkers` on, saying "marker" as a sentence of its own
+//! does the same in the text. At stop the key presses become sample offsets
+//! into the recorded audio, measured back from the end so pre-roll prepended
+//! at the start doesn't shift them. The pipeline filters and decodes each
+//! marked section on its own, splits the sections again at spoken markers,
+//! and joins them with a paragraph break. `transcription-complete` carries
+//! the marker times and the per-section segments.
+
+use crate::state::WHISPER_SAMPLE_RATE;
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::Emitter;
+
+/// Joins the sections of a marked transcript.
+pub const SECTION_BREAK: &str = "\n\n";
+/// Presses closer than this to each other or to either end of the recording
+/// would leave a section too short to decode, so they are dropped.
+const MIN_SECTION_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize / 2;
+const SAMPLES_PER_MS: usize = WHISPER_SAMPLE_RATE as usize / 1_000;
+
+struct Session {
+    recording_id: u64,
+    started: Instant,
+    presses: Vec<Instant>,
+    spoken: bool,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static SPOKEN_MARKERS: AtomicBool = AtomicBool::new(false);
+
+/// Payload of `recording-marker`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordingMarker {
+    recording_id: u64,
+    /// Markers in this recording so far, this one included.
+    count: usize,
+    /// Time since the recording started.
+    at_ms: u64,
+}
+
+/// The markers of a stopped recording.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Markers {
+    /// Key presses as sample offsets into the recorded audio, ascending.
+    pub offsets: Vec<usize>,
+    /// Whether "marker" in the transcript splits it too.
+    pub spoken: bool,
+}
+
+impl Markers {
+    /// The recording's sections between key markers, as sample ranges.
+    pub fn sections(&self, sample_count: usize) -> Vec<Range<usize>> {
+        let mut sections = Vec::with_capacity(self.offsets.len() + 1);
+        let mut start = 0;
+        for &offset in &self.offsets {
+            if offset >= start + MIN_SECTION_SAMPLES && offset + MIN_SECTION_SAMPLES <= sample_count
+            {
+                sections.push(start..offset);
+                start = offset;
+            }
+        }
+        sections.push(start..sample_count);
+        sections
+    }
+
+    /// The key marker times, in milliseconds from the start of the audio.
+    pub fn times_ms(&self, sample_count: usize) -> Vec<u64> {
+        self.sections(sample_count)
+            .iter()
+            .skip(1)
+            .map(|section| (section.start / SAMPLES_PER_MS) as u64)
+            .collect()
+    }
+}
+
+/// One section of a marked transcript. The times are offsets into the
+/// recorded audio; a boundary that came from a spoken marker has none.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSegment {
+    pub text: String,
+    pub start_ms: Option<u64>,
+    pub end_ms: Option<u64>,
+}
+
+/// Start collecting markers for a recording that just started.
+pub(crate) fn begin(recording_id: u64) {
+    *SESSION.lock_or_recover() = Some(Session {
+        recording_id,
+        started: Instant::now(),
+        presses: Vec::new(),
+        spoken: SPOKEN_MARKERS.load(Ordering::SeqCst),
+    });
+}
+
+/// Note a marker key press. Ignored when nothing is recording.
+pub(crate) fn press(app: &tauri::AppHandle) {
+    let payload = {
+        let mut session = SESSION.lock_or_recover();
+        let Some(session) = session.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+        session.presses.push(now);
+        RecordingMarker {
+            recording_id: session.recording_id,
+            count: session.presses.len(),
+            at_ms: now.duration_since(session.started).as_millis() as u64,
+        }
+    };
+    tracing::info!(
+        target: "pipeline",
+        recording_id = payload.recording_id,
+        count = payload.count,
+        at_ms = payload.at_ms,
+        "recording marker"
+    );
+    let _ = app.emit("recording-marker", &payload);
+}
+
+/// The markers of `recording_id`, whose audio stopped at `stopped` with
+/// `sample_count` samples. Clears the session.
+pub(crate) fn finish(recording_id: u64, stopped: Instant, sample_count: usize) -> Markers {
+    let mut session = SESSION.lock_or_recover();
+    match session.take() {
+        Some(session) if session.recording_id == recording_id => Markers {
+            offsets: offsets_from_end(&session.presses, stopped, sample_count),
+            spoken: session.spoken,
+        },
+        other => {
+            *session = other;
+            Markers::default()
+        }
+    }
+}
+
+/// Drop the markers of a recording that won't be transcribed.
+pub(crate) fn discard(recording_id: u64) {
+    let mut session = SESSION.lock_or_recover();
+    if session
+        .as_ref()
+        .is_some_and(|session| session.recording_id == recording_id)
+    {
+        *session = None;
+    }
+}
+
+fn offsets_from_end(presses: &[Instant], stopped: Instant, sample_count: usize) -> Vec<usize> {
+    presses
+        .iter()
+        .map(|&press| {
+            let before_end = stopped.saturating_duration_since(press).as_millis() as usize;
+            sample_count.saturating_sub(before_end.saturating_mul(SAMPLES_PER_MS))
+        })
+        .collect()
+}
+
+/// Split `text` where "marker" stands as a sentence of its own: at the start
+/// or after `.`, `!`, or `?`, and followed only by punctuation before the
+/// next word. Empty pieces are dropped.
+pub fn split_spoken(text: &str) -> Vec<String> {
+    const WORD: &str = "marker";
+    let mut pieces = Vec::new();
+    let mut piece_start = 0;
+    let mut search = 0;
+    while let Some(found) = text[search..].to_ascii_lowercase().find(WORD) {
+        let at = search + found;
+        let mut end = at + WORD.len();
+        search = end;
+        let before = text[piece_start..at].trim_end();
+        let starts_sentence = before.is_empty() || before.ends_with(['.', '!', '?']);
+        let trailing = text[end..]
+            .chars()
+            .take_while(|c| matches!(c, '.' | '!' | '?' | ',' | ';' | ':'))
+            .map(char::len_utf8)
+            .sum::<usize>();
+        end += trailing;
+        let ends_sentence = text[end..].chars().next().is_none_or(char::is_whitespace);
+        if starts_sentence && ends_sentence {
+            pieces.push(before.to_string());
+            piece_start = end;
+            search = end;
+        }
+    }
+    pieces.push(text[piece_start..].to_string());
+    pieces
+        .into_iter()
+        .map(|piece| piece.trim().to_string())
+        .filter(|piece| !piece.is_empty())
+        .collect()
+}
+
+/// The decoded sections as one transcript. A single section is kept as
+/// decoded; otherwise sections without text are skipped.
+pub fn join_sections(texts: &[String]) -> String {
+    match texts {
+        [text] => text.clone(),
+        _ => texts
+            .iter()
+            .map(|text| text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(SECTION_BREAK),
+    }
+}
+
+/// The segments of a transcript: each section's text, split again at spoken
+/// markers when `spoken`. `sections` are the sample ranges the texts were
+/// decoded from. None when neither kind of marker split anything.
+pub fn segments(
+    texts: &[String],
+    sections: &[Range<usize>],
+    spoken: bool,
+) -> Option<Vec<TranscriptSegment>> {
+    let mut segments = Vec::new();
+    for (text, section) in texts.iter().zip(sections) {
+        let pieces = if spoken {
+            split_spoken(text)
+        } else {
+            Some(text.trim().to_string())
+                .filter(|text| !text.is_empty())
+                .into_iter()
+                .collect()
+        };
+        let last = pieces.len().saturating_sub(1);
+        segments.extend(
+            pieces
+                .into_iter()
+                .enumerate()
+                .map(|(index, text)| TranscriptSegment {
+                    text,
+                    start_ms: (index == 0).then_some((section.start / SAMPLES_PER_MS) as u64),
+                    end_ms: (index == last).then_some((section.end / SAMPLES_PER_MS) as u64),
+                }),
+        );
+    }
+    (sections.len() > 1 || segments.len() > 1).then_some(segments)
+}
+
+/// Whether "marker" in a dictation starts a new section. Applies from the
+/// next recording.
+#[tauri::command]
+pub fn set_spoken_markers(enabled: bool) {
+    SPOKEN_MARKERS.store(enabled, Ordering::SeqCst);
+    tracing::info!(target: "pipeline", enabled, "Spoken markers updated");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const SECOND: usize = WHISPER_SAMPLE_RATE as usize;
+
+    #[test]
+    fn presses_are_measured_back_from_the_end_of_the_audio() {
+        let stopped = Instant::now();
+        let presses = [
+            stopped - Duration::from_secs(3),
+            stopped - Duration::from_millis(500),
+        ];
+        // Ten seconds of audio, two of them pre-roll before the recording.
+        assert_eq!(
+            offsets_from_end(&presses, stopped, 10 * SECOND),
+            [7 * SECOND, 10 * SECOND - SECOND / 2]
+        );
+    }
+
+    #[test]
+    fn sections_skip_markers_too_close_to_each_other_or_the_ends() {
+        let markers = Markers {
+            offsets: vec![
+                SECOND / 4,
+                3 * SECOND,
+                3 * SECOND + 100,
+                6 * SECOND,
+                10 * SECOND - 100,
+            ],
+            spoken: false,
+        };
+        assert_eq!(
+            markers.sections(10 * SECOND),
+            [
+                0..3 * SECOND,
+                3 * SECOND..6 * SECOND,
+                6 * SECOND..10 * SECOND
+            ]
+        );
+        assert_eq!(markers.times_ms(10 * SECOND), [3_000, 6_000]);
+        assert_eq!(Markers::default().sections(SECOND), [0..SECOND]);
+    }
+
+    #[test]
+    fn spoken_marker_splits_only_as_its_own_sentence() {
+        assert_eq!(
+            split_spoken("Intro is done. Marker. Now the budget."),
+            ["Intro is done.", "Now the budget."]
+        );
+        assert_eq!(
+            split_spoken("marker, first point! MARKER second point"),
+            ["first point!", "second point"]
+        );
+        assert_eq!(
+            split_spoken("Grab a marker. Markers are cheap."),
+            ["Grab a marker. Markers are cheap."]
+        );
+        assert_eq!(split_spoken("Done. Marker."), ["Done."]);
+    }
+
+    #[test]
+    fn segments_carry_section_times_and_skip_silent_sections() {
+        let texts = [
+            " Intro. Marker. Budget.".to_string(),
+            String::new(),
+            " Wrap up.".to_string(),
+        ];
+        let sections = [
+            0..2 * SECOND,
+            2 * SECOND..3 * SECOND,
+            3 * SECOND..5 * SECOND,
+        ];
+        let segment = |text: &str, start_ms, end_ms| TranscriptSegment {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+        };
+        assert_eq!(
+            segments(&texts, &sections, true).unwrap(),
+            [
+                segment("Intro.", Some(0), None),
+                segment("Budget.", None, Some(2_000)),
+                segment("Wrap up.", Some(3_000), Some(5_000)),
+            ]
+        );
+        assert_eq!(join_sections(&texts), "Intro. Marker. Budget.\n\nWrap up.");
+        // One unsplit section is no segmented transcript at all.
+        assert_eq!(segments(&texts[..1], &sections[..1], false), None);
+        assert_eq!(join_sections(&texts[2..]), " Wrap up.");
+    }
+}
@@ -0,0 +1,384 @@
+//! Meeting mode: near-live notes for long sessions.
+//!
+//! A meeting runs its own listening capture, like ambient mode, but keeps
+//! everything it hears. A `Chunker` slices the stream every `chunk_seconds`
+//! at the next quiet stretch (or `MAX_OVERRUN_SECS` later when nobody
+//! pauses), so words aren't cut in half. Each chunk is transcribed in the
+//! background with the dictation model as the backend frees up, and appended
+//! to today's Murmur note as an entry stamped with the chunk's start time.
+//! Only the open chunk and at most `MAX_PENDING_CHUNKS` closed ones are held
+//! in memory, so an hour-long meeting costs no more than a few minutes. When
+//! dictation keeps the backend busy past that, the oldest waiting chunk is
+//! dropped, counted, and announced with `meeting-chunk-dropped`.
+//!
+//! Chunks stay out of history: a meeting would evict every dictation from it
+//! within minutes.
+
+use crate::ambient::{self, FRAME_MS, FRAME_SAMPLES, SPEECH_RMS_FLOOR};
+use crate::audio::{self, ListeningCapture};
+use crate::model_runtime::PreparationReason;
+use crate::state::WHISPER_SAMPLE_RATE;
+use crate::MutexExt;
+use chrono::{Local, TimeZone};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use tauri::Emitter;
+
+const DEFAULT_CHUNK_SECS: u32 = 60;
+const MIN_CHUNK_SECS: u32 = 15;
+const MAX_CHUNK_SECS: u32 = 300;
+/// Quiet that counts as a place to cut once a chunk is long enough.
+const BOUNDARY_QUIET_MS: u64 = 500;
+/// How far past its length a chunk may run while waiting for a pause.
+const MAX_OVERRUN_SECS: u32 = 15;
+/// Chunks with less speech than this are silence and aren't transcribed.
+const MIN_SPEECH_MS: u64 = 400;
+const MAX_PENDING_CHUNKS: usize = 4;
+
+/// A closed slice of the meeting waiting for transcription.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Chunk {
+    pub started_at_ms: i64,
+    pub samples: Vec<f32>,
+}
+
+#[derive(Debug)]
+struct OpenChunk {
+    started_at_ms: i64,
+    samples: Vec<f32>,
+    speech_ms: u64,
+    quiet_ms: u64,
+}
+
+impl OpenChunk {
+    fn close(self) -> Option<Chunk> {
+        (self.speech_ms >= MIN_SPEECH_MS).then_some(Chunk {
+            started_at_ms: self.started_at_ms,
+            samples: self.samples,
+        })
+    }
+}
+
+/// Slices a continuous stream of frames into chunks of about a set length,
+/// cut on quiet.
+#[derive(Debug)]
+pub(crate) struct Chunker {
+    target_samples: usize,
+    max_samples: usize,
+    open: Option<OpenChunk>,
+}
+
+impl Chunker {
+    pub fn new(chunk_secs: u32) -> Self {
+        let samples_per_sec = WHISPER_SAMPLE_RATE as usize;
+        Self {
+            target_samples: chunk_secs as usize * samples_per_sec,
+            max_samples: (chunk_secs + MAX_OVERRUN_SECS) as usize * samples_per_sec,
+            open: None,
+        }
+    }
+
+    /// Add one frame that ended at `now_ms`; returns a chunk when one closes.
+    pub fn push(&mut self, frame: Vec<f32>, rms: f32, now_ms: i64) -> Option<Chunk> {
+        let open = self.open.get_or_insert_with(|| OpenChunk {
+            started_at_ms: now_ms - FRAME_MS as i64,
+            samples: Vec::new(),
+            speech_ms: 0,
+            quiet_ms: 0,
+        });
+        open.samples.extend(frame);
+        if rms >= SPEECH_RMS_FLOOR {
+            open.speech_ms += FRAME_MS;
+            open.quiet_ms = 0;
+        } else {
+            open.quiet_ms += FRAME_MS;
+        }
+        let at_pause =
+            open.samples.len() >= self.target_samples && open.quiet_ms >= BOUNDARY_QUIET_MS;
+        if at_pause || open.samples.len() >= self.max_samples {
+            return self.open.take().and_then(OpenChunk::close);
+        }
+        None
+    }
+
+    /// Close the open chunk, for when the capture ends.
+    pub fn finish(&mut self) -> Option<Chunk> {
+        self.open.take().and_then(OpenChunk::close)
+    }
+}
+
+/// Payload of `meeting-status-changed` and the meeting commands.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingStatus {
+    pub active: bool,
+    pub started_at_ms: Option<i64>,
+    pub chunk_seconds: u32,
+    /// Chunks of this meeting saved to notes so far.
+    pub chunks_saved: u32,
+    /// Chunks dropped untranscribed because the backend stayed busy.
+    pub chunks_dropped: u32,
+}
+
+/// Payload of `meeting-chunk`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MeetingChunk {
+    meeting_started_at_ms: i64,
+    /// 1-based position among the meeting's saved chunks.
+    index: u32,
+    started_at_ms: i64,
+    duration_ms: u64,
+    text: String,
+}
+
+/// Payload of `meeting-chunk-dropped`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DroppedChunk {
+    meeting_started_at_ms: i64,
+    started_at_ms: i64,
+    duration_ms: u64,
+}
+
+/// The running capture and the thread that chunks its frames.
+struct MeetingWorker {
+    app_handle: tauri::AppHandle,
+    capture: ListeningCapture,
+    chunker: JoinHandle<()>,
+    started_at_ms: i64,
+    chunk_seconds: u32,
+    chunks_saved: Arc<AtomicU32>,
+    chunks_dropped: Arc<AtomicU32>,
+}
+
+impl MeetingWorker {
+    fn status(&self, active: bool) -> MeetingStatus {
+        MeetingStatus {
+            active,
+            started_at_ms: Some(self.started_at_ms),
+            chunk_seconds: self.chunk_seconds,
+            chunks_saved: self.chunks_saved.load(Ordering::SeqCst),
+            chunks_dropped: self.chunks_dropped.load(Ordering::SeqCst),
+        }
+    }
+}
+
+static MEETING_WORKER: Mutex<Option<MeetingWorker>> = Mutex::new(None);
+
+fn run_chunker(frames: Receiver<Vec<f32>>, chunks: Sender<Chunk>, chunk_secs: u32) {
+    let mut chunker = Chunker::new(chunk_secs);
+    for frame in frames {
+        let rms = audio::compute_rms(&frame);
+        if let Some(chunk) = chunker.push(frame, rms, Local::now().timestamp_millis()) {
+            let _ = chunks.send(chunk);
+        }
+    }
+    if let Some(chunk) = chunker.finish() {
+        let _ = chunks.send(chunk);
+    }
+}
+
+fn transcribe_chunk(
+    app_handle: &tauri::AppHandle,
+    chunk: Chunk,
+    meeting_started_at_ms: i64,
+    chunks_saved: &AtomicU32,
+) {
+    let duration_ms = chunk.samples.len() as u64 * 1_000 / WHISPER_SAMPLE_RATE as u64;
+    let Some(text) =
+        ambient::transcribe_background(app_handle, &chunk.samples, PreparationReason::Meeting)
+    else {
+        return;
+    };
+    let Some(at) = Local.timestamp_millis_opt(chunk.started_at_ms).single() else {
+        return;
+    };
+    if let Err(e) = crate::notes::record_at(app_handle, at, &text) {
+        tracing::warn!(target: "pipeline", "meeting chunk not saved: {}", e);
+        return;
+    }
+    let index = chunks_saved.fetch_add(1, Ordering::SeqCst) + 1;
+    tracing::info!(target: "pipeline", index, duration_ms, "meeting chunk saved to notes");
+    let _ = app_handle.emit(
+        "meeting-chunk",
+        &MeetingChunk {
+            meeting_started_at_ms,
+            index,
+            started_at_ms: chunk.started_at_ms,
+            duration_ms,
+            text,
+        },
+    );
+}
+
+fn drop_chunk(
+    app_handle: &tauri::AppHandle,
+    chunk: Chunk,
+    meeting_started_at_ms: i64,
+    chunks_dropped: &AtomicU32,
+) {
+    let duration_ms = chunk.samples.len() as u64 * 1_000 / WHISPER_SAMPLE_RATE as u64;
+    let dropped = chunks_dropped.fetch_add(1, Ordering::SeqCst) + 1;
+    tracing::warn!(target: "pipeline", dropped, duration_ms, "meeting chunk dropped untranscribed");
+    let _ = app_handle.emit(
+        "meeting-chunk-dropped",
+        &DroppedChunk {
+            meeting_started_at_ms,
+            started_at_ms: chunk.started_at_ms,
+            duration_ms,
+        },
+    );
+}
+
+/// Start transcribing a meeting from `device_name` (None = system default)
+/// into today's note, a chunk about every `chunk_seconds` (clamped to
+/// 15-300).
+#[tauri::command]
+pub async fn start_meeting(
+    app_handle: tauri::AppHandle,
+    device_name: Option<String>,
+    chunk_seconds: Option<u32>,
+) -> Result<MeetingStatus, String> {
+    let mut worker = MEETING_WORKER.lock_or_recover();
+    // A worker whose capture already died is replaced.
+    if worker
+        .as_ref()
+        .is_some_and(|worker| !worker.chunker.is_finished())
+    {
+        return Err("A meeting is already being transcribed.".to_string());
+    }
+    let chunk_seconds = chunk_seconds
+        .unwrap_or(DEFAULT_CHUNK_SECS)
+        .clamp(MIN_CHUNK_SECS, MAX_CHUNK_SECS);
+    let (frame_tx, frame_rx) = channel::<Vec<f32>>();
+    let capture = audio::start_listening(device_name, FRAME_SAMPLES, frame_tx)?;
+
+    let started_at_ms = Local::now().timestamp_millis();
+    let chunks_saved = Arc::new(AtomicU32::new(0));
+    let (chunk_tx, chunk_rx) = channel::<Chunk>();
+    let transcriber_handle = app_handle.clone();
+    let chunks_dropped = Arc::new(AtomicU32::new(0));
+    let transcriber_saved = chunks_saved.clone();
+    let transcriber_dropped = chunks_dropped.clone();
+    thread::spawn(move || {
+        ambient::run_transcriber(
+            &transcriber_handle,
+            chunk_rx,
+            MAX_PENDING_CHUNKS,
+            "meeting chunk",
+            |app_handle, chunk| {
+                transcribe_chunk(app_handle, chunk, started_at_ms, &transcriber_saved)
+            },
+            |app_handle, chunk| drop_chunk(app_handle, chunk, started_at_ms, &transcriber_dropped),
+        )
+    });
+    let chunker = thread::spawn(move || run_chunker(frame_rx, chunk_tx, chunk_seconds));
+
+    let started = MeetingWorker {
+        app_handle,
+        capture,
+        chunker,
+        started_at_ms,
+        chunk_seconds,
+        chunks_saved,
+        chunks_dropped,
+    };
+    let status = started.status(true);
+    let _ = started.app_handle.emit("meeting-status-changed", &status);
+    *worker = Some(started);
+    tracing::info!(target: "audio", chunk_seconds, "meeting started");
+    Ok(status)
+}
+
+/// End the meeting, if one is running. The last chunk and any still waiting
+/// are transcribed in the background afterwards.
+pub(crate) fn stop() -> Option<MeetingStatus> {
+    let worker = MEETING_WORKER.lock_or_recover().take()?;
+    // Closing the capture ends the chunker, which flushes its open chunk and
+    // closes the transcriber's channel.
+    worker.capture.stop();
+    let _ = worker.chunker.join();
+    let status = worker.status(false);
+    let _ = worker.app_handle.emit("meeting-status-changed", &status);
+    tracing::info!(target: "audio", chunks_saved = status.chunks_saved, "meeting stopped");
+    Some(status)
+}
+
+/// Returns the final status, or an inactive one when no meeting was running.
+#[tauri::command]
+pub async fn stop_meeting() -> MeetingStatus {
+    stop().unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_meeting_status() -> MeetingStatus {
+    MEETING_WORKER
+        .lock_or_recover()
+        .as_ref()
+        .map(|worker| worker.status(!worker.chunker.is_finished()))
+        .unwrap_or_default()
+}
+
+pub(crate) fn is_active() -> bool {
+    get_meeting_status().active
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAMES_PER_SEC: usize = 1_000 / FRAME_MS as usize;
+
+    fn frame() -> Vec<f32> {
+        vec![0.0; FRAME_SAMPLES]
+    }
+
+    #[test]
+    fn chunk_is_cut_at_the_first_pause_after_its_length() {
+        let mut chunker = Chunker::new(MIN_CHUNK_SECS);
+        // Speech right up to the chunk length, then a breath too short to cut at.
+        for _ in 0..MIN_CHUNK_SECS as usize * FRAMES_PER_SEC {
+            assert!(chunker.push(frame(), 0.1, 1_000).is_none());
+        }
+        assert!(chunker.push(frame(), 0.0, 1_000).is_none());
+        assert!(chunker.push(frame(), 0.1, 1_000).is_none());
+        let pause = (BOUNDARY_QUIET_MS / FRAME_MS) as usize;
+        for _ in 1..pause {
+            assert!(chunker.push(frame(), 0.0, 1_000).is_none());
+        }
+        let chunk = chunker.push(frame(), 0.0, 1_000).unwrap();
+        assert_eq!(
+            chunk.samples.len(),
+            (MIN_CHUNK_SECS as usize * FRAMES_PER_SEC + 2 + pause) * FRAME_SAMPLES
+        );
+        assert_eq!(chunk.started_at_ms, 1_000 - FRAME_MS as i64);
+    }
+
+    #[test]
+    fn unbroken_speech_is_cut_at_the_overrun_cap() {
+        let mut chunker = Chunker::new(MIN_CHUNK_SECS);
+        let frames = (MIN_CHUNK_SECS + MAX_OVERRUN_SECS) as usize * FRAMES_PER_SEC;
+        for _ in 1..frames {
+            assert!(chunker.push(frame(), 0.1, 0).is_none());
+        }
+        let chunk = chunker.push(frame(), 0.1, 0).unwrap();
+        assert_eq!(chunk.samples.len(), frames * FRAME_SAMPLES);
+        // The next chunk starts fresh.
+        assert!(chunker.push(frame(), 0.1, 0).is_none());
+        assert_eq!(chunker.finish().unwrap().samples.len(), FRAME_SAMPLES);
+    }
+
+    #[test]
+    fn silent_chunks_are_dropped() {
+        let mut chunker = Chunker::new(MIN_CHUNK_SECS);
+        chunker.push(frame(), 0.1, 0);
+        for _ in 1..MIN_CHUNK_SECS as usize * FRAMES_PER_SEC + 10 {
+            assert!(chunker.push(frame(), 0.0, 0).is_none());
+        }
+        assert!(chunker.finish().is_none());
+    }
+}
@@ -0,0 +1,118 @@
+//! What holds the microphone, and a hard stop for all of it.
+//!
+//! macOS shows its orange microphone indicator while any process has an
+//! input device running. The report pairs that system-wide signal with the
+//! captures Murmur itself owns (dictation, the wake word, ambient mode, a
+//! meeting, the pre-roll buffer), so the UI can say whether the dot is
+//! Murmur's. `kill_microphone` closes every one of those streams at once and
+//! discards what they had captured.
+
+use crate::commands::recording::cancel_native_recording;
+use crate::state::DictationStatus;
+use crate::{ambient, audio, kws, meeting, mic_test, pre_roll, MutexExt, State};
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingPrivacyReport {
+    /// A dictation stream is open.
+    pub recording: bool,
+    /// The wake word detector is listening.
+    pub wake_word_listening: bool,
+    /// Ambient mode is transcribing.
+    pub ambient_listening: bool,
+    /// A meeting is being transcribed.
+    pub meeting_listening: bool,
+    /// The pre-roll buffer is keeping the last seconds of audio.
+    pub pre_roll_listening: bool,
+    /// Any of the above: Murmur has a microphone open.
+    pub microphone_in_use: bool,
+    /// Whether some process has an input device running, which is when
+    /// macOS shows the microphone indicator. `None` where it can't be read.
+    pub system_indicator: Option<bool>,
+}
+
+fn privacy_report() -> RecordingPrivacyReport {
+    let recording = audio::is_recording();
+    let wake_word_listening = kws::is_listening();
+    let ambient_listening = ambient::is_ambient_mode_active();
+    let meeting_listening = meeting::is_active();
+    let pre_roll_listening = pre_roll::is_listening();
+    RecordingPrivacyReport {
+        recording,
+        wake_word_listening,
+        ambient_listening,
+        meeting_listening,
+        pre_roll_listening,
+        microphone_in_use: recording
+            || wake_word_listening
+            || ambient_listening
+            || meeting_listening
+            || pre_roll_listening,
+        system_indicator: native::input_running_somewhere(),
+    }
+}
+
+#[tauri::command]
+pub fn get_recording_privacy_report() -> RecordingPrivacyReport {
+    privacy_report()
+}
+
+/// Close every microphone stream Murmur holds right away: cancel a dictation
+/// in progress without transcribing it, and stop the wake word, ambient mode,
+/// a meeting, the pre-roll buffer, and a microphone test. Returns the report
+/// taken afterwards.
+#[tauri::command]
+pub async fn kill_microphone(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+) -> Result<RecordingPrivacyReport, String> {
+    let status = state.app_state.dictation.lock_or_recover().status;
+    if status == DictationStatus::Recording {
+        cancel_native_recording(app_handle, state).await?;
+    }
+    // A stream left without a matching status is dropped along with its samples.
+    if audio::is_recording() {
+        audio::stop_recording()?;
+    }
+    kws::stop();
+    ambient::stop();
+    meeting::stop();
+    pre_roll::stop();
+    mic_test::stop();
+    let report = privacy_report();
+    tracing::info!(
+        target: "audio",
+        microphone_in_use = report.microphone_in_use,
+        "microphone killed"
+    );
+    Ok(report)
+}
+
+#[cfg(target_os = "macos")]
+mod native {
+    use crate::core_audio::{self, SCOPE_GLOBAL};
+
+    const DEVICE_IS_RUNNING_SOMEWHERE: u32 = u32::from_be_bytes(*b"gone");
+
+    /// Whether any input-capable device is running in any process. A headset
+    /// that is only playing audio counts too; CoreAudio reports the device,
+    /// not the direction.
+    pub(super) fn input_running_somewhere() -> Option<bool> {
+        let mut known = false;
+        for device in core_audio::input_devices()? {
+            match core_audio::u32_property(device, DEVICE_IS_RUNNING_SOMEWHERE, SCOPE_GLOBAL) {
+                Some(0) => known = true,
+                Some(_) => return Some(true),
+                None => {}
+            }
+        }
+        known.then_some(false)
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod native {
+    pub(super) fn input_running_somewhere() -> Option<bool> {
+        None
+    }
+}
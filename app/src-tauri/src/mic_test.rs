@@ -0,0 +1,184 @@
+//! Microphone test for Settings: open an input device and meter it without
+//! recording.
+//!
+//! The test runs on a listening capture (`audio::start_listening`), so it never
+//! touches the recording buffer or the crash journal and nothing it hears is
+//! transcribed. Each 50 ms frame is emitted as `mic-test-level`; stopping the
+//! test returns what it saw, including whether the level ever reached speech.
+//! That answers "my transcriptions are empty" reports: a muted or wrong device
+//! never gets there.
+
+use crate::audio::{self, compute_peak, compute_rms, ListeningCapture};
+use crate::state::WHISPER_SAMPLE_RATE;
+use crate::MutexExt;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
+use tauri::Emitter;
+
+/// 50 ms at 16 kHz, so the meter moves at 20 fps.
+const FRAME_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize / 20;
+/// A frame at or above this RMS (about -40 dBFS) counts as speech, matching
+/// the wake word and ambient mode.
+const SPEECH_RMS: f32 = 0.01;
+
+/// The device a test opened.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MicTestInfo {
+    pub device_name: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Payload of `mic-test-level`, one per frame.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MicTestLevel {
+    pub rms: f32,
+    pub peak: f32,
+    pub max_rms: f32,
+    pub heard_speech: bool,
+}
+
+/// What a finished test saw.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MicTestSummary {
+    pub max_rms: f32,
+    pub max_peak: f32,
+    pub heard_speech: bool,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct Meter {
+    max_rms: f32,
+    max_peak: f32,
+}
+
+impl Meter {
+    fn add(&mut self, frame: &[f32]) -> MicTestLevel {
+        let rms = compute_rms(frame);
+        let peak = compute_peak(frame);
+        self.max_rms = self.max_rms.max(rms);
+        self.max_peak = self.max_peak.max(peak);
+        MicTestLevel {
+            rms,
+            peak,
+            max_rms: self.max_rms,
+            heard_speech: self.heard_speech(),
+        }
+    }
+
+    fn heard_speech(&self) -> bool {
+        self.max_rms >= SPEECH_RMS
+    }
+}
+
+struct MicTest {
+    capture: ListeningCapture,
+    meter_thread: JoinHandle<()>,
+    meter: Arc<Mutex<Meter>>,
+    started_at: Instant,
+}
+
+static MIC_TEST: Mutex<Option<MicTest>> = Mutex::new(None);
+
+fn run_meter(app_handle: &tauri::AppHandle, frames: Receiver<Vec<f32>>, meter: &Mutex<Meter>) {
+    while let Ok(frame) = frames.recv() {
+        let level = meter.lock_or_recover().add(&frame);
+        let _ = app_handle.emit("mic-test-level", level);
+    }
+}
+
+/// Start metering `device_name` (None = system default), replacing a test
+/// that is already running.
+#[tauri::command]
+pub async fn start_mic_test(
+    app_handle: tauri::AppHandle,
+    device_name: Option<String>,
+) -> Result<MicTestInfo, String> {
+    stop();
+    let (frame_tx, frame_rx) = channel::<Vec<f32>>();
+    let capture = audio::start_listening(device_name, FRAME_SAMPLES, frame_tx)?;
+    let info = MicTestInfo {
+        device_name: capture.device_name().to_string(),
+        sample_rate: capture.sample_rate(),
+        channels: capture.channels(),
+    };
+
+    let meter = Arc::new(Mutex::new(Meter::default()));
+    let thread_meter = Arc::clone(&meter);
+    let meter_thread = thread::spawn(move || run_meter(&app_handle, frame_rx, &thread_meter));
+
+    *MIC_TEST.lock_or_recover() = Some(MicTest {
+        capture,
+        meter_thread,
+        meter,
+        started_at: Instant::now(),
+    });
+    tracing::info!(
+        target: "audio",
+        sample_rate = info.sample_rate,
+        channels = info.channels,
+        "mic test started"
+    );
+    Ok(info)
+}
+
+/// Close the test's stream. Returns `None` when no test was running.
+pub(crate) fn stop() -> Option<MicTestSummary> {
+    let test = MIC_TEST.lock_or_recover().take()?;
+    // Closing the capture drops the frame sender, which ends the meter thread.
+    test.capture.stop();
+    let _ = test.meter_thread.join();
+    let meter = test.meter.lock_or_recover();
+    let summary = MicTestSummary {
+        max_rms: meter.max_rms,
+        max_peak: meter.max_peak,
+        heard_speech: meter.heard_speech(),
+        duration_ms: test.started_at.elapsed().as_millis() as u64,
+    };
+    tracing::info!(
+        target: "audio",
+        max_rms = summary.max_rms,
+        heard_speech = summary.heard_speech,
+        "mic test stopped"
+    );
+    Some(summary)
+}
+
+#[tauri::command]
+pub async fn stop_mic_test() -> Option<MicTestSummary> {
+    stop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_meter_keeps_the_loudest_frame() {
+        let mut meter = Meter::default();
+        let loud = meter.add(&[0.5, -0.5, 0.5, -0.5]);
+        assert_eq!(loud.rms, 0.5);
+        assert_eq!(loud.peak, 0.5);
+
+        let quiet = meter.add(&[0.001; 4]);
+        assert!(quiet.rms < 0.01);
+        assert_eq!(quiet.max_rms, 0.5);
+        assert!(quiet.heard_speech);
+    }
+
+    #[test]
+    fn a_silent_device_never_reaches_speech() {
+        let mut meter = Meter::default();
+        for _ in 0..10 {
+            meter.add(&[0.0005; 800]);
+        }
+        assert!(!meter.heard_speech());
+        assert_eq!(meter.max_peak, 0.0005);
+    }
+}
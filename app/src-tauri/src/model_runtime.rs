@@ -1,8 +1,10 @@
+use crate::transcriber::cloud::CLOUD_MODEL_NAME;
 use crate::transcriber::{
-    ParakeetBackend, TranscriptionBackend, WhisperBackend, COREML_MODEL_NAME,
+    CloudBackend, ExternalBackend, ParakeetBackend, TranscriptionBackend, WhisperBackend,
+    COREML_MODEL_NAME,
 };
 use crate::MutexExt;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -10,6 +12,12 @@ use tauri::Emitter;
 
 pub const PARAKEET_CPU_MODEL: &str = "parakeet-tdt-0.6b-v2-fp16";
 
+/// Settings value prefix for a user-supplied GGML file, e.g. `custom:ggml-distil.bin`.
+pub const CUSTOM_MODEL_PREFIX: &str = "custom:";
+
+/// Settings value prefix for a registered transcription plugin, e.g. `external:whisper-server`.
+pub const EXTERNAL_MODEL_PREFIX: &str = "external:";
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelCapabilities {
@@ -28,6 +36,8 @@ pub enum BackendKind {
     Whisper,
     Parakeet,
     Coreml,
+    External,
+    Cloud,
 }
 
 impl BackendKind {
@@ -36,6 +46,8 @@ impl BackendKind {
             Self::Whisper => "whisper",
             Self::Parakeet => "parakeet",
             Self::Coreml => "coreml",
+            Self::External => "external",
+            Self::Cloud => "cloud",
         }
     }
 }
@@ -45,6 +57,8 @@ pub enum InstallKind {
     Whisper,
     Parakeet,
     Coreml,
+    External,
+    Cloud,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -53,17 +67,36 @@ enum PlatformRequirement {
     AppleSiliconMac,
 }
 
+/// Coarse trade-offs for the model picker. `speed` and `accuracy` are 1–5
+/// ratings relative to the rest of the catalog, not benchmark results; `0`
+/// means unrated (custom models).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelTradeoffs {
+    /// ggml quantization of the weights; `None` for the full-precision file.
+    pub quantization: Option<&'static str>,
+    pub download_mb: u64,
+    pub speed: u8,
+    pub accuracy: u8,
+    /// Real-time factor on Apple Silicon: measured by the 2026-07-20 benchmark
+    /// for the original seven models, estimated for Distil-Whisper.
+    pub expected_rtf: Option<f32>,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct ModelDefinition {
     pub model_name: &'static str,
     pub label: &'static str,
     pub size: &'static str,
+    /// Rough memory held while loaded, for the keep-alive budget.
+    pub resident_mb: u64,
     pub backend: BackendKind,
     pub accelerator: &'static str,
     pub capabilities: ModelCapabilities,
     pub install_kind: InstallKind,
     pub warm_on_startup: bool,
     pub retry_unfiltered_on_empty: bool,
+    pub tradeoffs: ModelTradeoffs,
     platform: PlatformRequirement,
 }
 
@@ -104,100 +137,470 @@ const COREML_CAPABILITIES: ModelCapabilities = ModelCapabilities {
     punctuation_control: true,
 };
 
+/// A quantized ggml variant of a catalog Whisper model. The weights are
+/// smaller, so resident memory tracks the download size.
+#[allow(clippy::too_many_arguments)]
+const fn quantized_whisper(
+    model_name: &'static str,
+    label: &'static str,
+    size: &'static str,
+    capabilities: ModelCapabilities,
+    quantization: &'static str,
+    download_mb: u64,
+    speed: u8,
+    accuracy: u8,
+) -> ModelDefinition {
+    ModelDefinition {
+        model_name,
+        label,
+        size,
+        resident_mb: download_mb,
+        backend: BackendKind::Whisper,
+        accelerator: "Metal GPU",
+        capabilities,
+        install_kind: InstallKind::Whisper,
+        warm_on_startup: false,
+        retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: Some(quantization),
+            download_mb,
+            speed,
+            accuracy,
+            expected_rtf: None,
+        },
+        platform: PlatformRequirement::Desktop,
+    }
+}
+
 pub const MODEL_DEFINITIONS: &[ModelDefinition] = &[
     ModelDefinition {
         model_name: COREML_MODEL_NAME,
         label: "Parakeet Core ML",
         size: "~470 MB",
+        resident_mb: 470,
         backend: BackendKind::Coreml,
         accelerator: "Apple Neural Engine",
         capabilities: COREML_CAPABILITIES,
         install_kind: InstallKind::Coreml,
         warm_on_startup: true,
         retry_unfiltered_on_empty: true,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 470,
+            speed: 5,
+            accuracy: 4,
+            expected_rtf: Some(0.0079),
+        },
         platform: PlatformRequirement::AppleSiliconMac,
     },
     ModelDefinition {
         model_name: PARAKEET_CPU_MODEL,
         label: "Parakeet TDT 0.6B (English, fast)",
         size: "~1.2 GB",
+        resident_mb: 1200,
         backend: BackendKind::Parakeet,
         accelerator: "CPU",
         capabilities: PARAKEET_CPU_CAPABILITIES,
         install_kind: InstallKind::Parakeet,
         warm_on_startup: false,
         retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 1200,
+            speed: 3,
+            accuracy: 4,
+            expected_rtf: Some(0.047),
+        },
         platform: PlatformRequirement::Desktop,
     },
     ModelDefinition {
         model_name: "tiny.en",
         label: "Whisper Tiny (English)",
         size: "~75 MB",
+        resident_mb: 75,
         backend: BackendKind::Whisper,
         accelerator: "Metal GPU",
         capabilities: WHISPER_EN_CAPABILITIES,
         install_kind: InstallKind::Whisper,
         warm_on_startup: false,
         retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 75,
+            speed: 5,
+            accuracy: 1,
+            expected_rtf: Some(0.0092),
+        },
         platform: PlatformRequirement::Desktop,
     },
     ModelDefinition {
         model_name: "base.en",
         label: "Whisper Base (English)",
         size: "~150 MB",
+        resident_mb: 150,
         backend: BackendKind::Whisper,
         accelerator: "Metal GPU",
         capabilities: WHISPER_EN_CAPABILITIES,
         install_kind: InstallKind::Whisper,
         warm_on_startup: false,
         retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 150,
+            speed: 4,
+            accuracy: 2,
+            expected_rtf: Some(0.0148),
+        },
         platform: PlatformRequirement::Desktop,
     },
     ModelDefinition {
         model_name: "small.en",
         label: "Whisper Small (English)",
         size: "~500 MB",
+        resident_mb: 500,
         backend: BackendKind::Whisper,
         accelerator: "Metal GPU",
         capabilities: WHISPER_EN_CAPABILITIES,
         install_kind: InstallKind::Whisper,
         warm_on_startup: false,
         retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 500,
+            speed: 3,
+            accuracy: 3,
+            expected_rtf: Some(0.041),
+        },
         platform: PlatformRequirement::Desktop,
     },
     ModelDefinition {
         model_name: "medium.en",
         label: "Whisper Medium (English)",
         size: "~1.5 GB",
+        resident_mb: 1500,
         backend: BackendKind::Whisper,
         accelerator: "Metal GPU",
         capabilities: WHISPER_EN_CAPABILITIES,
         install_kind: InstallKind::Whisper,
         warm_on_startup: false,
         retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 1500,
+            speed: 2,
+            accuracy: 4,
+            expected_rtf: Some(0.107),
+        },
         platform: PlatformRequirement::Desktop,
     },
     ModelDefinition {
         model_name: "large-v3-turbo",
         label: "Whisper Large Turbo",
         size: "~3 GB",
+        resident_mb: 3000,
         backend: BackendKind::Whisper,
         accelerator: "Metal GPU",
         capabilities: WHISPER_MULTILINGUAL_CAPABILITIES,
         install_kind: InstallKind::Whisper,
         warm_on_startup: false,
         retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 3000,
+            speed: 2,
+            accuracy: 5,
+            expected_rtf: Some(0.112),
+        },
         platform: PlatformRequirement::Desktop,
     },
+    // Distil-Whisper: large and medium encoders with a two-layer decoder,
+    // published in their own Hugging Face repos. English only.
+    ModelDefinition {
+        model_name: "distil-medium.en",
+        label: "Distil-Whisper Medium (English)",
+        size: "~800 MB",
+        resident_mb: 800,
+        backend: BackendKind::Whisper,
+        accelerator: "Metal GPU",
+        capabilities: WHISPER_EN_CAPABILITIES,
+        install_kind: InstallKind::Whisper,
+        warm_on_startup: false,
+        retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 800,
+            speed: 3,
+            accuracy: 3,
+            expected_rtf: Some(0.05),
+        },
+        platform: PlatformRequirement::Desktop,
+    },
+    ModelDefinition {
+        model_name: "distil-large-v3",
+        label: "Distil-Whisper Large v3 (English)",
+        size: "~1.5 GB",
+        resident_mb: 1500,
+        backend: BackendKind::Whisper,
+        accelerator: "Metal GPU",
+        capabilities: WHISPER_EN_CAPABILITIES,
+        install_kind: InstallKind::Whisper,
+        warm_on_startup: false,
+        retry_unfiltered_on_empty: false,
+        tradeoffs: ModelTradeoffs {
+            quantization: None,
+            download_mb: 1500,
+            speed: 2,
+            accuracy: 4,
+            expected_rtf: Some(0.10),
+        },
+        platform: PlatformRequirement::Desktop,
+    },
+    // Quantized variants of the ggerganov/whisper.cpp files above.
+    quantized_whisper(
+        "tiny.en-q5_1",
+        "Whisper Tiny (English, Q5)",
+        "~31 MB",
+        WHISPER_EN_CAPABILITIES,
+        "q5_1",
+        31,
+        5,
+        1,
+    ),
+    quantized_whisper(
+        "tiny.en-q8_0",
+        "Whisper Tiny (English, Q8)",
+        "~42 MB",
+        WHISPER_EN_CAPABILITIES,
+        "q8_0",
+        42,
+        5,
+        1,
+    ),
+    quantized_whisper(
+        "base.en-q5_1",
+        "Whisper Base (English, Q5)",
+        "~57 MB",
+        WHISPER_EN_CAPABILITIES,
+        "q5_1",
+        57,
+        4,
+        2,
+    ),
+    quantized_whisper(
+        "base.en-q8_0",
+        "Whisper Base (English, Q8)",
+        "~78 MB",
+        WHISPER_EN_CAPABILITIES,
+        "q8_0",
+        78,
+        4,
+        2,
+    ),
+    quantized_whisper(
+        "small.en-q5_1",
+        "Whisper Small (English, Q5)",
+        "~181 MB",
+        WHISPER_EN_CAPABILITIES,
+        "q5_1",
+        181,
+        3,
+        3,
+    ),
+    quantized_whisper(
+        "small.en-q8_0",
+        "Whisper Small (English, Q8)",
+        "~252 MB",
+        WHISPER_EN_CAPABILITIES,
+        "q8_0",
+        252,
+        3,
+        3,
+    ),
+    quantized_whisper(
+        "medium.en-q5_0",
+        "Whisper Medium (English, Q5)",
+        "~514 MB",
+        WHISPER_EN_CAPABILITIES,
+        "q5_0",
+        514,
+        2,
+        4,
+    ),
+    quantized_whisper(
+        "medium.en-q8_0",
+        "Whisper Medium (English, Q8)",
+        "~785 MB",
+        WHISPER_EN_CAPABILITIES,
+        "q8_0",
+        785,
+        2,
+        4,
+    ),
+    quantized_whisper(
+        "large-v3-turbo-q5_0",
+        "Whisper Large Turbo (Q5)",
+        "~547 MB",
+        WHISPER_MULTILINGUAL_CAPABILITIES,
+        "q5_0",
+        547,
+        2,
+        5,
+    ),
+    quantized_whisper(
+        "large-v3-turbo-q8_0",
+        "Whisper Large Turbo (Q8)",
+        "~834 MB",
+        WHISPER_MULTILINGUAL_CAPABILITIES,
+        "q8_0",
+        834,
+        2,
+        5,
+    ),
 ];
 
-pub fn model_definition(model_name: &str) -> Result<&'static ModelDefinition, String> {
-    MODEL_DEFINITIONS
+/// Shared definition for every custom Whisper file. Capabilities assume a
+/// multilingual model; an English-only file simply ignores the language hint.
+static CUSTOM_WHISPER_DEFINITION: ModelDefinition = ModelDefinition {
+    model_name: CUSTOM_MODEL_PREFIX,
+    label: "Custom Whisper model",
+    size: "Custom",
+    resident_mb: 1500,
+    backend: BackendKind::Whisper,
+    accelerator: "Metal GPU",
+    capabilities: WHISPER_MULTILINGUAL_CAPABILITIES,
+    install_kind: InstallKind::Whisper,
+    warm_on_startup: false,
+    retry_unfiltered_on_empty: false,
+    tradeoffs: ModelTradeoffs {
+        quantization: None,
+        download_mb: 0,
+        speed: 0,
+        accuracy: 0,
+        expected_rtf: None,
+    },
+    platform: PlatformRequirement::Desktop,
+};
+
+/// Shared definition for every transcription plugin. Plugins declare no
+/// capabilities; the language and prompt are sent for them to use or ignore.
+static EXTERNAL_DEFINITION: ModelDefinition = ModelDefinition {
+    model_name: EXTERNAL_MODEL_PREFIX,
+    label: "Transcription plugin",
+    size: "Plugin",
+    resident_mb: 0,
+    backend: BackendKind::External,
+    accelerator: "External",
+    capabilities: ModelCapabilities {
+        partial_results: false,
+        initial_prompts: false,
+        multilingual: true,
+        translation: false,
+        timestamps: false,
+        confidence: false,
+        punctuation_control: false,
+    },
+    install_kind: InstallKind::External,
+    warm_on_startup: false,
+    retry_unfiltered_on_empty: false,
+    tradeoffs: ModelTradeoffs {
+        quantization: None,
+        download_mb: 0,
+        speed: 0,
+        accuracy: 0,
+        expected_rtf: None,
+    },
+    platform: PlatformRequirement::Desktop,
+};
+
+/// The cloud speech-to-text service. Capabilities are those every provider
+/// has; the prompt is still sent to OpenAI-style services, which use it.
+static CLOUD_DEFINITION: ModelDefinition = ModelDefinition {
+    model_name: CLOUD_MODEL_NAME,
+    label: "Cloud transcription",
+    size: "Cloud",
+    resident_mb: 0,
+    backend: BackendKind::Cloud,
+    accelerator: "Cloud",
+    capabilities: ModelCapabilities {
+        partial_results: false,
+        initial_prompts: false,
+        multilingual: true,
+        translation: false,
+        timestamps: false,
+        confidence: false,
+        punctuation_control: false,
+    },
+    install_kind: InstallKind::Cloud,
+    warm_on_startup: false,
+    retry_unfiltered_on_empty: false,
+    tradeoffs: ModelTradeoffs {
+        quantization: None,
+        download_mb: 0,
+        speed: 0,
+        accuracy: 0,
+        expected_rtf: None,
+    },
+    platform: PlatformRequirement::Desktop,
+};
+
+/// File name of a custom model, or `None` when `model_name` isn't a
+/// well-formed `custom:<file>.bin`. The name is joined onto the custom models
+/// directory, so anything that could escape it is rejected.
+pub fn custom_model_file(model_name: &str) -> Option<&str> {
+    let file = model_name.strip_prefix(CUSTOM_MODEL_PREFIX)?;
+    let safe = file.len() <= 128
+        && file.ends_with(".bin")
+        && !file.starts_with('.')
+        && file
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    safe.then_some(file)
+}
+
+/// Plugin id of an external model, or `None` when `model_name` isn't a
+/// well-formed `external:<id>`.
+pub fn external_plugin_id(model_name: &str) -> Option<&str> {
+    model_name
+        .strip_prefix(EXTERNAL_MODEL_PREFIX)
+        .filter(|id| crate::transcriber::external::is_plugin_id(id))
+}
+
+fn find_definition(
+    definitions: &'static [ModelDefinition],
+    model_name: &str,
+) -> Result<&'static ModelDefinition, String> {
+    if custom_model_file(model_name).is_some() {
+        return Ok(&CUSTOM_WHISPER_DEFINITION);
+    }
+    if external_plugin_id(model_name).is_some() {
+        return Ok(&EXTERNAL_DEFINITION);
+    }
+    if model_name == CLOUD_MODEL_NAME {
+        return Ok(&CLOUD_DEFINITION);
+    }
+    definitions
         .iter()
         .find(|definition| definition.model_name == model_name)
         .ok_or_else(|| format!("Unknown transcription model '{model_name}'"))
 }
 
+pub fn model_definition(model_name: &str) -> Result<&'static ModelDefinition, String> {
+    find_definition(MODEL_DEFINITIONS, model_name)
+}
+
+/// Display label for `model_name`; custom models show their file name and
+/// plugins the name in their manifest.
+pub fn model_label(definition: &ModelDefinition, model_name: &str) -> String {
+    if definition.install_kind == InstallKind::External {
+        return crate::transcriber::external::plugin_name(model_name)
+            .or_else(|| external_plugin_id(model_name).map(str::to_string))
+            .unwrap_or_else(|| definition.label.to_string());
+    }
+    custom_model_file(model_name)
+        .unwrap_or(definition.label)
+        .to_string()
+}
+
 pub fn model_supported(definition: &ModelDefinition) -> bool {
     match definition.platform {
         PlatformRequirement::Desktop => true,
@@ -222,14 +625,57 @@ fn supported_platforms(definition: &ModelDefinition) -> Vec<String> {
     }
 }
 
+/// Where a model's audio is transcribed, as shown in history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessingLocation {
+    Device,
+    Cloud,
+    /// A transcription plugin, which may or may not send audio elsewhere.
+    Plugin,
+}
+
+pub fn processing_location(definition: &ModelDefinition) -> ProcessingLocation {
+    match definition.backend {
+        BackendKind::Whisper | BackendKind::Parakeet | BackendKind::Coreml => {
+            ProcessingLocation::Device
+        }
+        BackendKind::External => ProcessingLocation::Plugin,
+        BackendKind::Cloud => ProcessingLocation::Cloud,
+    }
+}
+
+/// Where `model_name` transcribes, with the cloud host or plugin name when
+/// that isn't this Mac.
+pub fn processed_on(model_name: &str) -> (ProcessingLocation, Option<String>) {
+    let Ok(definition) = model_definition(model_name) else {
+        return (ProcessingLocation::Device, None);
+    };
+    let location = processing_location(definition);
+    let by = match location {
+        ProcessingLocation::Device => None,
+        ProcessingLocation::Cloud => crate::transcriber::cloud::endpoint_host(),
+        ProcessingLocation::Plugin => Some(model_label(definition, model_name)),
+    };
+    (location, by)
+}
+
 pub fn create_backend(model_name: &str) -> Result<Box<dyn TranscriptionBackend>, String> {
-    let definition = model_definition(model_name)?;
+    backend_for(model_definition(model_name)?)
+}
+
+fn backend_for(definition: &ModelDefinition) -> Result<Box<dyn TranscriptionBackend>, String> {
     if !model_supported(definition) {
         return Err("This model is not supported on the current platform".to_string());
     }
+    if processing_location(definition) != ProcessingLocation::Device {
+        crate::transcriber::ensure_audio_may_leave_device()?;
+    }
     match definition.backend {
         BackendKind::Whisper => Ok(Box::new(WhisperBackend::new())),
         BackendKind::Parakeet => Ok(Box::new(ParakeetBackend::new())),
+        BackendKind::External => Ok(Box::new(ExternalBackend::new())),
+        BackendKind::Cloud => Ok(Box::new(CloudBackend::new())),
         BackendKind::Coreml => {
             #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
             {
@@ -253,6 +699,8 @@ pub fn model_installed(model_name: &str) -> bool {
     match definition.install_kind {
         InstallKind::Whisper => crate::transcriber::whisper::specific_model_exists(model_name),
         InstallKind::Parakeet => crate::transcriber::parakeet::specific_model_exists(model_name),
+        InstallKind::External => crate::transcriber::external::specific_model_exists(model_name),
+        InstallKind::Cloud => crate::transcriber::cloud::specific_model_exists(model_name),
         InstallKind::Coreml => {
             #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
             {
@@ -293,15 +741,21 @@ pub enum PreparationReason {
     StartupWarm,
     Pipeline,
     FileTranscription,
+    SelfTest,
+    Ambient,
+    Meeting,
 }
 
 impl PreparationReason {
-    fn as_str(self) -> &'static str {
+    pub(crate) fn as_str(self) -> &'static str {
         match self {
             Self::Recording => "recording",
             Self::StartupWarm => "startupWarm",
             Self::Pipeline => "pipeline",
             Self::FileTranscription => "fileTranscription",
+            Self::SelfTest => "selfTest",
+            Self::Ambient => "ambient",
+            Self::Meeting => "meeting",
         }
     }
 }
@@ -310,9 +764,14 @@ impl PreparationReason {
 pub enum UnloadReason {
     ModelChanged,
     IdleTimeout,
-    #[allow(dead_code)]
-    // Public lifecycle seam; automatic pressure policy is intentionally out of scope.
+    /// Evicted to keep resident backends within the keep-alive budget.
     MemoryPressure,
+    /// Models were deleted from Settings > Storage.
+    StoragePurged,
+    /// Reloaded on the CPU after the GPU ran out of memory.
+    GpuMemory,
+    /// Its transcription plugin was registered again or removed.
+    PluginChanged,
 }
 
 impl UnloadReason {
@@ -321,6 +780,9 @@ impl UnloadReason {
             Self::ModelChanged => "modelChanged",
             Self::IdleTimeout => "idleTimeout",
             Self::MemoryPressure => "memoryPressure",
+            Self::StoragePurged => "storagePurged",
+            Self::GpuMemory => "gpuMemory",
+            Self::PluginChanged => "pluginChanged",
         }
     }
 }
@@ -358,9 +820,33 @@ impl Default for RuntimeStatus {
     }
 }
 
+/// A backend of another kind that still holds its model, kept so switching
+/// back skips the reload.
+struct ParkedBackend {
+    backend: Box<dyn TranscriptionBackend>,
+    model_name: String,
+}
+
 struct RuntimeInner {
     backend: Box<dyn TranscriptionBackend>,
     active_model: Option<String>,
+    /// At most one per backend kind, least recently used first.
+    parked: Vec<ParkedBackend>,
+}
+
+/// Default memory budget for the active model plus parked backends.
+pub const DEFAULT_KEEP_ALIVE_MB: u64 = 2048;
+
+/// One resident model, as returned by `get_loaded_backends`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedBackend {
+    pub backend: BackendKind,
+    pub model_name: String,
+    pub label: String,
+    pub resident_mb: u64,
+    /// False for a parked backend kept warm for a switch back.
+    pub active: bool,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -377,6 +863,7 @@ pub struct ModelRuntimeManager {
     install_states: Mutex<HashMap<String, InstallState>>,
     install_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
     generation: AtomicU64,
+    keep_alive_mb: AtomicU64,
 }
 
 impl Default for ModelRuntimeManager {
@@ -386,21 +873,20 @@ impl Default for ModelRuntimeManager {
             inner: Mutex::new(RuntimeInner {
                 backend: Box::new(WhisperBackend::new()),
                 active_model: None,
+                parked: Vec::new(),
             }),
             statuses: Mutex::new(HashMap::new()),
             install_states: Mutex::new(HashMap::new()),
             install_locks: Mutex::new(HashMap::new()),
             generation: AtomicU64::new(0),
+            keep_alive_mb: AtomicU64::new(DEFAULT_KEEP_ALIVE_MB),
         }
     }
 }
 
 impl ModelRuntimeManager {
     fn definition(&self, model_name: &str) -> Result<&'static ModelDefinition, String> {
-        self.definitions
-            .iter()
-            .find(|definition| definition.model_name == model_name)
-            .ok_or_else(|| format!("Unknown transcription model '{model_name}'"))
+        find_definition(self.definitions, model_name)
     }
 
     fn current_install_state(&self, model_name: &str) -> InstallState {
@@ -430,8 +916,8 @@ impl ModelRuntimeManager {
         let supported = model_supported(definition);
         Ok(ModelRuntimeSnapshot {
             generation: self.generation.load(Ordering::SeqCst),
-            model_name: definition.model_name.to_string(),
-            label: definition.label.to_string(),
+            model_name: model_name.to_string(),
+            label: model_label(definition, model_name),
             size: definition.size.to_string(),
             backend: definition.backend,
             accelerator: model_accelerator(definition).to_string(),
@@ -445,10 +931,16 @@ impl ModelRuntimeManager {
         })
     }
 
+    /// Shipped models followed by any custom Whisper files on disk, any
+    /// registered transcription plugins, and cloud transcription.
     pub fn catalog(&self) -> Vec<ModelRuntimeSnapshot> {
         self.definitions
             .iter()
-            .filter_map(|definition| self.snapshot(definition.model_name).ok())
+            .map(|definition| definition.model_name.to_string())
+            .chain(crate::transcriber::whisper::custom_model_names())
+            .chain(crate::transcriber::external::external_model_names())
+            .chain(std::iter::once(CLOUD_MODEL_NAME.to_string()))
+            .filter_map(|model_name| self.snapshot(&model_name).ok())
             .collect()
     }
 
@@ -456,6 +948,9 @@ impl ModelRuntimeManager {
         self.definitions
             .iter()
             .any(|definition| model_installed(definition.model_name))
+            || !crate::transcriber::whisper::custom_model_names().is_empty()
+            || !crate::transcriber::external::registered_plugins().is_empty()
+            || model_installed(CLOUD_MODEL_NAME)
     }
 
     pub fn install_lock(&self, model_name: &str) -> Result<Arc<tokio::sync::Mutex<()>>, String> {
@@ -548,6 +1043,116 @@ impl ModelRuntimeManager {
         self.publish(app, model_name, reason)
     }
 
+    pub fn set_keep_alive_mb(&self, megabytes: u64) {
+        self.keep_alive_mb.store(megabytes, Ordering::SeqCst);
+    }
+
+    fn resident_mb(&self, model_name: &str) -> u64 {
+        self.definition(model_name)
+            .map_or(0, |definition| definition.resident_mb)
+    }
+
+    fn release(
+        &self,
+        app: Option<&tauri::AppHandle>,
+        backend: &mut dyn TranscriptionBackend,
+        model_name: &str,
+        reason: UnloadReason,
+    ) -> Result<(), String> {
+        self.set_lifecycle(
+            app,
+            model_name,
+            LifecycleState::Unloading,
+            false,
+            reason.as_str(),
+        )?;
+        backend.reset();
+        self.set_lifecycle(
+            app,
+            model_name,
+            LifecycleState::Unloaded,
+            false,
+            reason.as_str(),
+        )
+    }
+
+    /// Make `target`'s backend kind current. A parked backend of that kind is
+    /// restored with whatever it still holds; otherwise a fresh one is made.
+    /// The outgoing backend is parked with its model when the budget allows,
+    /// and the oldest parked backends are evicted until everything fits.
+    fn switch_backend(
+        &self,
+        app: Option<&tauri::AppHandle>,
+        inner: &mut RuntimeInner,
+        target: &ModelDefinition,
+    ) -> Result<(), String> {
+        let (incoming, incoming_model) = match inner
+            .parked
+            .iter()
+            .position(|parked| parked.backend.name() == target.backend.as_str())
+        {
+            Some(index) => {
+                let parked = inner.parked.remove(index);
+                (parked.backend, Some(parked.model_name))
+            }
+            None => (backend_for(target)?, None),
+        };
+        let mut outgoing = std::mem::replace(&mut inner.backend, incoming);
+        if let Some(model_name) = std::mem::replace(&mut inner.active_model, incoming_model) {
+            inner.parked.push(ParkedBackend {
+                backend: outgoing,
+                model_name,
+            });
+        } else {
+            outgoing.reset();
+        }
+
+        let budget = self.keep_alive_mb.load(Ordering::SeqCst);
+        let reason = if budget == 0 {
+            UnloadReason::ModelChanged
+        } else {
+            UnloadReason::MemoryPressure
+        };
+        let mut resident = target.resident_mb
+            + inner
+                .parked
+                .iter()
+                .map(|parked| self.resident_mb(&parked.model_name))
+                .sum::<u64>();
+        while resident > budget && !inner.parked.is_empty() {
+            let mut evicted = inner.parked.remove(0);
+            resident -= self.resident_mb(&evicted.model_name);
+            self.release(app, evicted.backend.as_mut(), &evicted.model_name, reason)?;
+        }
+        Ok(())
+    }
+
+    pub fn loaded_backends(&self) -> Vec<LoadedBackend> {
+        let inner = self.inner.lock_or_recover();
+        let parked = inner
+            .parked
+            .iter()
+            .rev()
+            .map(|parked| (parked.model_name.as_str(), false));
+        inner
+            .active_model
+            .as_deref()
+            .map(|model_name| (model_name, true))
+            .into_iter()
+            .chain(parked)
+            .filter_map(|(model_name, active)| {
+                let definition = self.definition(model_name).ok()?;
+                Some(LoadedBackend {
+                    backend: definition.backend,
+                    model_name: model_name.to_string(),
+                    label: model_label(definition, model_name),
+                    resident_mb: definition.resident_mb,
+                    active,
+                })
+            })
+            .collect()
+    }
+
     fn ensure_backend<'a>(
         &self,
         app: Option<&tauri::AppHandle>,
@@ -559,6 +1164,11 @@ impl ModelRuntimeManager {
             return Err("This model is not supported on the current platform".to_string());
         }
 
+        if inner.backend.name() != definition.backend.as_str() {
+            self.switch_backend(app, inner, definition)?;
+        }
+
+        // One backend holds one model at a time.
         if let Some(active) = inner
             .active_model
             .as_deref()
@@ -582,11 +1192,6 @@ impl ModelRuntimeManager {
                 UnloadReason::ModelChanged.as_str(),
             )?;
         }
-
-        if inner.backend.name() != definition.backend.as_str() {
-            inner.backend.reset();
-            inner.backend = create_backend(model_name)?;
-        }
         Ok(&mut inner.backend)
     }
 
@@ -688,8 +1293,15 @@ impl ModelRuntimeManager {
         app: Option<&tauri::AppHandle>,
         model_name: &str,
     ) -> Result<(), String> {
-        self.definition(model_name)?;
+        let definition = self.definition(model_name)?;
         let mut inner = self.inner.lock_or_recover();
+        if inner.backend.name() != definition.backend.as_str() {
+            self.switch_backend(app, &mut inner, definition)?;
+        }
+        if inner.active_model.as_deref() == Some(model_name) {
+            // Restored from a parked backend; already Ready.
+            return Ok(());
+        }
         if let Some(active) = inner.active_model.take() {
             self.set_lifecycle(
                 app,
@@ -707,9 +1319,6 @@ impl ModelRuntimeManager {
                 UnloadReason::ModelChanged.as_str(),
             )?;
         }
-        if inner.backend.name() != self.definition(model_name)?.backend.as_str() {
-            inner.backend = create_backend(model_name)?;
-        }
         self.set_lifecycle(
             app,
             model_name,
@@ -725,6 +1334,9 @@ impl ModelRuntimeManager {
         reason: UnloadReason,
     ) -> Result<Option<String>, String> {
         let mut inner = self.inner.lock_or_recover();
+        for mut parked in std::mem::take(&mut inner.parked) {
+            self.release(app, parked.backend.as_mut(), &parked.model_name, reason)?;
+        }
         let Some(model_name) = inner.active_model.take() else {
             return Ok(None);
         };
@@ -750,6 +1362,21 @@ impl ModelRuntimeManager {
     pub fn token_count(&self, text: &str) -> Option<usize> {
         self.inner.lock_or_recover().backend.token_count(text)
     }
+
+    /// Unload the active model so the next preparation loads it without the
+    /// GPU. It stays on the CPU until it is unloaded or another model is
+    /// loaded, when the GPU is tried again.
+    pub fn fall_back_to_cpu(&self, app: Option<&tauri::AppHandle>) -> Result<(), String> {
+        let mut inner = self.inner.lock_or_recover();
+        let reason = UnloadReason::GpuMemory.as_str();
+        let Some(model_name) = inner.active_model.take() else {
+            inner.backend.set_gpu_enabled(false);
+            return Ok(());
+        };
+        self.set_lifecycle(app, &model_name, LifecycleState::Unloading, false, reason)?;
+        inner.backend.set_gpu_enabled(false);
+        self.set_lifecycle(app, &model_name, LifecycleState::Unloaded, false, reason)
+    }
 }
 
 #[cfg(test)]
@@ -767,12 +1394,74 @@ mod tests {
             .iter()
             .map(|model| model.model_name)
             .collect::<HashSet<_>>();
-        assert_eq!(names.len(), 7);
+        assert_eq!(names.len(), 19);
         assert!(model_definition("base.en").is_ok());
         assert!(model_definition("future-unknown-model").is_err());
         assert!(create_backend("future-unknown-model").is_err());
     }
 
+    #[test]
+    fn custom_model_names_resolve_to_whisper_without_escaping_the_models_dir() {
+        let definition = model_definition("custom:ggml-distil-large-v3.bin").unwrap();
+        assert_eq!(definition.backend, BackendKind::Whisper);
+        assert_eq!(
+            model_label(definition, "custom:ggml-distil-large-v3.bin"),
+            "ggml-distil-large-v3.bin"
+        );
+        for name in [
+            "custom:",
+            "custom:.bin",
+            "custom:../ggml-base.en.bin",
+            "custom:dir/ggml.bin",
+            "custom:ggml-model.gguf",
+        ] {
+            assert!(custom_model_file(name).is_none(), "{name}");
+            assert!(model_definition(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn external_model_names_resolve_to_the_plugin_backend() {
+        let definition = model_definition("external:whisper-server").unwrap();
+        assert_eq!(definition.backend, BackendKind::External);
+        assert_eq!(
+            external_plugin_id("external:whisper-server"),
+            Some("whisper-server")
+        );
+        assert_eq!(
+            model_label(definition, "external:unregistered-plugin"),
+            "unregistered-plugin"
+        );
+        for name in [
+            "external:",
+            "external:../x",
+            "external:Upper",
+            "external:a/b",
+        ] {
+            assert!(external_plugin_id(name).is_none(), "{name}");
+            assert!(model_definition(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn processing_location_follows_the_backend() {
+        let cloud = model_definition(CLOUD_MODEL_NAME).unwrap();
+        assert_eq!(cloud.backend, BackendKind::Cloud);
+        assert_eq!(processing_location(cloud), ProcessingLocation::Cloud);
+        assert_eq!(
+            processed_on("external:unregistered-plugin"),
+            (
+                ProcessingLocation::Plugin,
+                Some("unregistered-plugin".to_string())
+            )
+        );
+        assert_eq!(processed_on("base.en"), (ProcessingLocation::Device, None));
+        assert_eq!(
+            processing_location(model_definition("custom:ggml-distil.bin").unwrap()),
+            ProcessingLocation::Device
+        );
+    }
+
     #[test]
     fn shipped_capabilities_match_current_backend_facts() {
         assert!(
@@ -823,34 +1512,61 @@ mod tests {
         punctuation_control: false,
     };
 
+    const FAKE_TRADEOFFS: ModelTradeoffs = ModelTradeoffs {
+        quantization: None,
+        download_mb: 0,
+        speed: 0,
+        accuracy: 0,
+        expected_rtf: None,
+    };
+
     static FAKE_DEFINITIONS: &[ModelDefinition] = &[
         ModelDefinition {
             model_name: "fake-translation",
             label: "Fake translation backend",
             size: "0 MB",
+            resident_mb: 0,
             backend: BackendKind::Whisper,
             accelerator: "Test",
             capabilities: FAKE_CAPABILITIES,
             install_kind: InstallKind::Whisper,
             warm_on_startup: false,
             retry_unfiltered_on_empty: false,
+            tradeoffs: FAKE_TRADEOFFS,
             platform: PlatformRequirement::Desktop,
         },
         ModelDefinition {
             model_name: "fake-second",
             label: "Second fake model",
             size: "0 MB",
+            resident_mb: 0,
             backend: BackendKind::Whisper,
             accelerator: "Test",
             capabilities: FAKE_CAPABILITIES,
             install_kind: InstallKind::Whisper,
             warm_on_startup: false,
             retry_unfiltered_on_empty: false,
+            tradeoffs: FAKE_TRADEOFFS,
+            platform: PlatformRequirement::Desktop,
+        },
+        ModelDefinition {
+            model_name: "fake-parakeet",
+            label: "Fake second engine",
+            size: "0 MB",
+            resident_mb: 0,
+            backend: BackendKind::Parakeet,
+            accelerator: "Test",
+            capabilities: FAKE_CAPABILITIES,
+            install_kind: InstallKind::Parakeet,
+            warm_on_startup: false,
+            retry_unfiltered_on_empty: false,
+            tradeoffs: FAKE_TRADEOFFS,
             platform: PlatformRequirement::Desktop,
         },
     ];
 
     struct FakeBackend {
+        name: &'static str,
         active: Arc<AtomicUsize>,
         maximum: Arc<AtomicUsize>,
         loaded: bool,
@@ -859,7 +1575,7 @@ mod tests {
 
     impl TranscriptionBackend for FakeBackend {
         fn name(&self) -> &str {
-            "whisper"
+            self.name
         }
         fn load_model(&mut self, _model_name: &str) -> Result<(), String> {
             let now = self.active.fetch_add(1, Ordering::SeqCst) + 1;
@@ -908,12 +1624,14 @@ mod tests {
             definitions: FAKE_DEFINITIONS,
             inner: Mutex::new(RuntimeInner {
                 backend: Box::new(FakeBackend {
+                    name: "whisper",
                     active,
                     maximum,
                     loaded: false,
                     load_error,
                 }),
                 active_model: None,
+                parked: Vec::new(),
             }),
             ..ModelRuntimeManager::default()
         }
@@ -996,6 +1714,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn switching_engines_parks_the_loaded_backend_within_budget() {
+        let manager = fake_manager(
+            Arc::new(AtomicUsize::new(0)),
+            Arc::new(AtomicUsize::new(0)),
+            None,
+        );
+        manager
+            .prepare(None, "fake-translation", PreparationReason::Recording)
+            .unwrap();
+        manager.inner.lock_or_recover().parked.push(ParkedBackend {
+            backend: Box::new(FakeBackend {
+                name: "parakeet",
+                active: Arc::new(AtomicUsize::new(0)),
+                maximum: Arc::new(AtomicUsize::new(0)),
+                loaded: true,
+                load_error: None,
+            }),
+            model_name: "fake-parakeet".to_string(),
+        });
+
+        let report = manager
+            .prepare(None, "fake-parakeet", PreparationReason::Recording)
+            .unwrap();
+        assert!(report.cache_hit);
+        let loaded = manager.loaded_backends();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].model_name, "fake-parakeet");
+        assert!(loaded[0].active);
+        assert_eq!(loaded[1].model_name, "fake-translation");
+        assert!(!loaded[1].active);
+
+        // Switching back is a cache hit too, until the budget is zero.
+        assert!(
+            manager
+                .prepare(None, "fake-translation", PreparationReason::Recording)
+                .unwrap()
+                .cache_hit
+        );
+        manager.set_keep_alive_mb(0);
+        manager.select_model(None, "fake-parakeet").unwrap();
+        assert_eq!(manager.loaded_backends().len(), 1);
+        assert_eq!(
+            manager
+                .snapshot("fake-translation")
+                .unwrap()
+                .lifecycle_state,
+            LifecycleState::Unloaded
+        );
+
+        manager.unload(None, UnloadReason::IdleTimeout).unwrap();
+        assert!(manager.loaded_backends().is_empty());
+    }
+
     #[test]
     fn existing_install_is_reused_without_reentering_installing() {
         let manager = fake_manager(
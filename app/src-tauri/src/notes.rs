@@ -0,0 +1,441 @@
+//! Murmur notes: a delivery target that keeps dictations instead of pasting.
+//!
+//! With `notes_target` on (always, or only when no text field has focus) a
+//! finished dictation is appended to today's note rather than injected. Each
+//! local day is one markdown file, `notes/YYYY-MM-DD.md` in the app data
+//! directory, with one `## HH:MM:SS` section per entry, so the files stay
+//! readable and editable outside the app. Entries are addressed by their
+//! position in the day; text before the first section is kept as written.
+
+use crate::MutexExt;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+
+const NOTES_DIR: &str = "notes";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const ENTRY_PREFIX: &str = "## ";
+/// Characters of the newest entry shown in `list_notes`.
+const PREVIEW_CHARS: usize = 80;
+
+static NOTES_PATH: OnceLock<PathBuf> = OnceLock::new();
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteEntry {
+    /// Local `HH:MM:SS` the entry was added.
+    pub time: String,
+    pub text: String,
+}
+
+/// One day's note.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    /// Local `YYYY-MM-DD`.
+    pub date: String,
+    pub entries: Vec<NoteEntry>,
+    /// The file as stored, for copy and export.
+    pub markdown: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteSummary {
+    pub date: String,
+    pub entries: usize,
+    /// Start of the newest entry.
+    pub preview: String,
+}
+
+/// A day file split into the text before its first entry and the entries.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct NoteFile {
+    preamble: String,
+    entries: Vec<NoteEntry>,
+}
+
+impl NoteFile {
+    fn parse(markdown: &str) -> Self {
+        let mut file = NoteFile::default();
+        let mut preamble = Vec::new();
+        let mut body: Vec<&str> = Vec::new();
+        let mut current: Option<String> = None;
+        for line in markdown.lines() {
+            if let Some(time) = line.strip_prefix(ENTRY_PREFIX) {
+                if let Some(time) = current.replace(time.trim().to_string()) {
+                    file.entries.push(entry_from(time, &body));
+                }
+                body.clear();
+            } else if current.is_some() {
+                body.push(line);
+            } else {
+                preamble.push(line);
+            }
+        }
+        if let Some(time) = current {
+            file.entries.push(entry_from(time, &body));
+        }
+        file.preamble = preamble.join("\n").trim().to_string();
+        file
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        if !self.preamble.is_empty() {
+            out.push_str(&self.preamble);
+            out.push_str("\n\n");
+        }
+        for entry in &self.entries {
+            out.push_str(ENTRY_PREFIX);
+            out.push_str(&entry.time);
+            out.push_str("\n\n");
+            for line in entry.text.lines() {
+                // A dictated line that looks like a heading would otherwise
+                // split the entry when the file is read back.
+                if line.starts_with('#') || line.starts_with('\\') {
+                    out.push('\\');
+                }
+                out.push_str(line);
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn entry_from(time: String, body: &[&str]) -> NoteEntry {
+    let text = body
+        .iter()
+        .map(|line| line.strip_prefix('\\').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    NoteEntry {
+        time,
+        text: text.trim().to_string(),
+    }
+}
+
+/// Set the notes location. Call once from setup.
+pub fn init(app_data_dir: &Path) {
+    let _ = NOTES_PATH.set(app_data_dir.join(NOTES_DIR));
+}
+
+fn notes_dir() -> Result<&'static PathBuf, String> {
+    NOTES_PATH
+        .get()
+        .ok_or_else(|| "Notes are not available yet".to_string())
+}
+
+/// The day file for `date`, refusing anything that isn't a calendar date so a
+/// caller can't reach outside the notes directory.
+fn note_path(dir: &Path, date: &str) -> Result<PathBuf, String> {
+    NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .map_err(|_| format!("Not a note date: {}", date))?;
+    Ok(dir.join(format!("{}.md", date)))
+}
+
+fn read_file(path: &Path) -> NoteFile {
+    std::fs::read_to_string(path)
+        .map(|markdown| NoteFile::parse(&markdown))
+        .unwrap_or_default()
+}
+
+fn write_file(path: &Path, file: &NoteFile) -> Result<(), String> {
+    if file.entries.is_empty() && file.preamble.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to delete note: {}", e))
+            }
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create notes directory: {}", e))?;
+    }
+    std::fs::write(path, file.render()).map_err(|e| format!("Failed to save note: {}", e))
+}
+
+fn note_from(date: &str, file: NoteFile) -> Note {
+    let markdown = file.render();
+    Note {
+        date: date.to_string(),
+        entries: file.entries,
+        markdown,
+    }
+}
+
+/// Read `date`'s file, apply `edit`, and write it back under the write lock.
+fn edit_in<F>(dir: &Path, date: &str, edit: F) -> Result<Note, String>
+where
+    F: FnOnce(&mut NoteFile) -> Result<(), String>,
+{
+    let path = note_path(dir, date)?;
+    let _guard = WRITE_LOCK.lock_or_recover();
+    let mut file = read_file(&path);
+    edit(&mut file)?;
+    write_file(&path, &file)?;
+    Ok(note_from(date, file))
+}
+
+fn entry_mut(file: &mut NoteFile, index: usize) -> Result<&mut NoteEntry, String> {
+    file.entries
+        .get_mut(index)
+        .ok_or_else(|| "That note entry no longer exists.".to_string())
+}
+
+fn append_in(dir: &Path, date: &str, time: &str, text: &str) -> Result<Note, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("A note entry needs some text.".to_string());
+    }
+    edit_in(dir, date, |file| {
+        file.entries.push(NoteEntry {
+            time: time.to_string(),
+            text: text.to_string(),
+        });
+        Ok(())
+    })
+}
+
+fn summaries_in(dir: &Path) -> Vec<NoteSummary> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dates: Vec<String> = read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let date = name.strip_suffix(".md")?;
+            NaiveDate::parse_from_str(date, DATE_FORMAT).ok()?;
+            Some(date.to_string())
+        })
+        .collect();
+    dates.sort_unstable_by(|a, b| b.cmp(a));
+    dates
+        .into_iter()
+        .map(|date| {
+            let file = read_file(&dir.join(format!("{}.md", date)));
+            let preview = file
+                .entries
+                .last()
+                .map(|entry| entry.text.chars().take(PREVIEW_CHARS).collect())
+                .unwrap_or_default();
+            NoteSummary {
+                date,
+                entries: file.entries.len(),
+                preview,
+            }
+        })
+        .collect()
+}
+
+fn today() -> String {
+    Local::now().format(DATE_FORMAT).to_string()
+}
+
+/// Append a finished dictation to today's note and tell the notes view.
+pub fn record_dictation(app_handle: &tauri::AppHandle, text: &str) -> Result<(), String> {
+    record_at(app_handle, Local::now(), text)?;
+    tracing::info!(target: "pipeline", "dictation saved to notes");
+    Ok(())
+}
+
+/// Append `text` to the note of the day `at` falls on, as an entry stamped
+/// `at`, and tell the notes view.
+pub fn record_at(
+    app_handle: &tauri::AppHandle,
+    at: DateTime<Local>,
+    text: &str,
+) -> Result<(), String> {
+    let date = at.format(DATE_FORMAT).to_string();
+    append_in(
+        notes_dir()?,
+        &date,
+        &at.format("%H:%M:%S").to_string(),
+        text,
+    )?;
+    let _ = app_handle.emit("note-updated", &date);
+    Ok(())
+}
+
+/// Days that have a note, newest first.
+#[tauri::command]
+pub fn list_notes() -> Vec<NoteSummary> {
+    notes_dir().map(|dir| summaries_in(dir)).unwrap_or_default()
+}
+
+/// `date`'s note (today when omitted); empty if nothing was saved that day.
+#[tauri::command]
+pub fn get_note(date: Option<String>) -> Result<Note, String> {
+    let date = date.unwrap_or_else(today);
+    let path = note_path(notes_dir()?, &date)?;
+    Ok(note_from(&date, read_file(&path)))
+}
+
+/// Add a typed entry to today's note.
+#[tauri::command]
+pub fn add_note_entry(text: String) -> Result<Note, String> {
+    let now = Local::now();
+    append_in(
+        notes_dir()?,
+        &now.format(DATE_FORMAT).to_string(),
+        &now.format("%H:%M:%S").to_string(),
+        &text,
+    )
+}
+
+/// Replace the text of entry `index` of `date`'s note. Empty text deletes it.
+#[tauri::command]
+pub fn update_note_entry(date: String, index: usize, text: String) -> Result<Note, String> {
+    edit_in(notes_dir()?, &date, |file| {
+        if text.trim().is_empty() {
+            entry_mut(file, index)?;
+            file.entries.remove(index);
+        } else {
+            entry_mut(file, index)?.text = text.trim().to_string();
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn delete_note_entry(date: String, index: usize) -> Result<Note, String> {
+    edit_in(notes_dir()?, &date, |file| {
+        entry_mut(file, index)?;
+        file.entries.remove(index);
+        Ok(())
+    })
+}
+
+/// Delete `date`'s whole note.
+#[tauri::command]
+pub fn delete_note(date: String) -> Result<(), String> {
+    let path = note_path(notes_dir()?, &date)?;
+    let _guard = WRITE_LOCK.lock_or_recover();
+    write_file(&path, &NoteFile::default())
+}
+
+/// Copy `date`'s note (today when omitted) to the clipboard as markdown.
+#[tauri::command]
+pub fn copy_note(date: Option<String>) -> Result<(), String> {
+    let note = get_note(date)?;
+    if note.entries.is_empty() {
+        return Err("That note is empty.".to_string());
+    }
+    crate::injector::write_clipboard_text(&note.markdown)
+}
+
+/// Write `date`'s note to `path`, a file the user picked.
+#[tauri::command]
+pub fn export_note(date: String, path: String) -> Result<(), String> {
+    let note = get_note(Some(date))?;
+    if note.entries.is_empty() {
+        return Err("That note is empty.".to_string());
+    }
+    std::fs::write(&path, note.markdown).map_err(|e| format!("Failed to export note: {}", e))?;
+    tracing::info!(target: "system", "note exported");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("murmur-notes-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn entries_round_trip_through_markdown() {
+        let file = NoteFile {
+            preamble: "# Standup".to_string(),
+            entries: vec![
+                NoteEntry {
+                    time: "09:01:00".to_string(),
+                    text: "Ship the release.\n## not a heading".to_string(),
+                },
+                NoteEntry {
+                    time: "09:05:30".to_string(),
+                    text: "Call Sam".to_string(),
+                },
+            ],
+        };
+        let markdown = file.render();
+        assert!(markdown.contains("\\## not a heading"));
+        assert_eq!(NoteFile::parse(&markdown), file);
+    }
+
+    #[test]
+    fn text_before_the_first_entry_is_kept() {
+        let file = NoteFile::parse("My own notes\n\n## 10:00:00\n\nDictated\n");
+        assert_eq!(file.preamble, "My own notes");
+        assert_eq!(file.entries.len(), 1);
+        assert!(file.render().starts_with("My own notes\n\n## 10:00:00"));
+    }
+
+    #[test]
+    fn dates_outside_the_calendar_are_refused() {
+        let dir = Path::new("/notes");
+        assert!(note_path(dir, "2026-10-16").is_ok());
+        assert!(note_path(dir, "../secrets").is_err());
+        assert!(note_path(dir, "2026-13-01").is_err());
+    }
+
+    #[test]
+    fn editing_the_last_entry_away_deletes_the_day() {
+        let dir = temp_dir("edit");
+        append_in(&dir, "2026-10-16", "09:00:00", "first").unwrap();
+        let note = append_in(&dir, "2026-10-16", "09:01:00", "second").unwrap();
+        assert_eq!(note.entries.len(), 2);
+
+        let note = edit_in(&dir, "2026-10-16", |file| {
+            entry_mut(file, 0)?.text = "edited".to_string();
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(note.entries[0].text, "edited");
+        assert!(edit_in(&dir, "2026-10-16", |file| entry_mut(file, 5).map(|_| ())).is_err());
+
+        edit_in(&dir, "2026-10-16", |file| {
+            file.entries.clear();
+            Ok(())
+        })
+        .unwrap();
+        assert!(!dir.join("2026-10-16.md").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn summaries_list_newest_day_first() {
+        let dir = temp_dir("list");
+        append_in(&dir, "2026-10-15", "09:00:00", "older").unwrap();
+        append_in(&dir, "2026-10-16", "09:00:00", "newer").unwrap();
+        std::fs::write(dir.join("scratch.md"), "not a day").unwrap();
+        let summaries = summaries_in(&dir);
+        assert_eq!(
+            summaries
+                .iter()
+                .map(|s| s.date.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2026-10-16", "2026-10-15"]
+        );
+        assert_eq!(summaries[0].preview, "newer");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn blank_entries_are_refused() {
+        let dir = temp_dir("blank");
+        assert!(append_in(&dir, "2026-10-16", "09:00:00", "  ").is_err());
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,138 @@
+//! System notifications for dictation results while Murmur is out of sight.
+//!
+//! Notifications are opt-in (`NotificationPolicy`) and only shown when the main
+//! window is hidden, so they never duplicate the in-window banner. The desktop
+//! notification plugin exposes no action buttons or click callbacks, so there
+//! is no Copy/Open History action: the transcript is already on the clipboard,
+//! and history lives in the main window (tray "Show Murmur"). A Focus mode can
+//! hold notifications too; see `FocusFilterSettings`.
+
+use crate::state::{AppState, NotificationPolicy};
+use crate::MutexExt;
+use tauri::Manager;
+use tauri_plugin_notification::NotificationExt;
+
+/// Notification bodies carry at most this many characters of transcript.
+const PREVIEW_MAX_CHARS: usize = 120;
+
+/// First non-blank line of `text`, truncated on a char boundary with an
+/// ellipsis when it exceeds `max_chars`.
+pub(crate) fn preview_line(text: &str, max_chars: usize) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("");
+    if line.chars().count() <= max_chars {
+        return line.to_string();
+    }
+    let mut preview = line
+        .chars()
+        .take(max_chars.saturating_sub(1))
+        .collect::<String>()
+        .trim_end()
+        .to_string();
+    preview.push('…');
+    preview
+}
+
+fn main_window_hidden(app: &tauri::AppHandle) -> bool {
+    app.get_webview_window("main")
+        .map(|window| !window.is_visible().unwrap_or(false))
+        .unwrap_or(true)
+}
+
+fn policy(app_state: &AppState) -> NotificationPolicy {
+    app_state.dictation.lock_or_recover().notification_policy
+}
+
+/// Whether the active Focus mode holds notifications under the user's filter.
+fn held_for_focus(app_state: &AppState) -> bool {
+    let filter = app_state.dictation.lock_or_recover().focus_filter.clone();
+    let held = crate::system_status::suppresses_notifications(&filter);
+    if held {
+        tracing::info!(target: "system", "notification held for focus mode");
+    }
+    held
+}
+
+fn show(app: &tauri::AppHandle, title: &str, body: &str) {
+    if let Err(error) = app.notification().builder().title(title).body(body).show() {
+        tracing::warn!(target: "system", "notification failed: {}", error);
+    }
+}
+
+/// Announce a finished dictation when the policy allows completions and the
+/// main window is hidden.
+pub fn transcription_complete(app: &tauri::AppHandle, app_state: &AppState, text: &str) {
+    if !policy(app_state).notifies_completions()
+        || !main_window_hidden(app)
+        || held_for_focus(app_state)
+    {
+        return;
+    }
+    let preview = preview_line(text, PREVIEW_MAX_CHARS);
+    if preview.is_empty() {
+        return;
+    }
+    tracing::info!(target: "system", kind = "completion", "notification shown");
+    show(app, "Transcription copied", &preview);
+}
+
+/// Surface a dictation failure when any notifications are enabled and the main
+/// window is hidden. `message` must be user-facing and content-free.
+pub fn dictation_failed(app: &tauri::AppHandle, app_state: &AppState, message: &str) {
+    if !policy(app_state).notifies_errors() || !main_window_hidden(app) || held_for_focus(app_state)
+    {
+        return;
+    }
+    tracing::info!(target: "system", kind = "error", "notification shown");
+    show(app, "Murmur", message);
+}
+
+/// Announce that the download queue installed the first model that can
+/// transcribe. Shown under any policy but Off, since the user started the
+/// download and may have left the window while it ran.
+pub fn model_ready(app: &tauri::AppHandle, app_state: &AppState, label: &str) {
+    if !policy(app_state).notifies_errors() || !main_window_hidden(app) || held_for_focus(app_state)
+    {
+        return;
+    }
+    tracing::info!(target: "system", kind = "model_ready", "notification shown");
+    show(
+        app,
+        "Murmur is ready",
+        &format!("{} is installed. You can start dictating.", label),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_uses_first_non_blank_line() {
+        assert_eq!(
+            preview_line("\n  \nhello world\nsecond", 120),
+            "hello world"
+        );
+        assert_eq!(preview_line("   ", 120), "");
+    }
+
+    #[test]
+    fn preview_truncates_on_char_boundaries() {
+        assert_eq!(preview_line("abcdef", 6), "abcdef");
+        assert_eq!(preview_line("abcdefg", 6), "abcde…");
+        assert_eq!(preview_line("héllo wörld", 7), "héllo…");
+    }
+
+    #[test]
+    fn policy_gates_completions_and_errors_independently() {
+        assert!(!NotificationPolicy::Off.notifies_completions());
+        assert!(!NotificationPolicy::Off.notifies_errors());
+        assert!(!NotificationPolicy::ErrorsOnly.notifies_completions());
+        assert!(NotificationPolicy::ErrorsOnly.notifies_errors());
+        assert!(NotificationPolicy::All.notifies_completions());
+        assert!(NotificationPolicy::All.notifies_errors());
+    }
+}
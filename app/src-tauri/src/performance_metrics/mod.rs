@@ -222,6 +222,14 @@ impl PerformanceMetrics {
         self.repository()?.resource_window()
     }
 
+    pub(crate) fn resource_timeline(
+        &self,
+        since_ms: i64,
+        until_ms: i64,
+    ) -> Result<Vec<ResourceSampleV1>, String> {
+        self.repository()?.resource_timeline(since_ms, until_ms)
+    }
+
     pub(crate) fn clear(&self) -> Result<(), String> {
         self.repository()?.clear()?;
         let app_handle = self
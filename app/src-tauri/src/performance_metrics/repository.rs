@@ -303,8 +303,16 @@ impl PerformanceRepository {
     }
 
     pub(crate) fn resource_window(&self) -> Result<Vec<ResourceSampleV1>, String> {
+        self.resource_timeline(i64::MIN, i64::MAX)
+    }
+
+    pub(crate) fn resource_timeline(
+        &self,
+        since_ms: i64,
+        until_ms: i64,
+    ) -> Result<Vec<ResourceSampleV1>, String> {
         let connection = self.open()?;
-        resource_samples_tx(&connection, i64::MIN, i64::MAX)
+        resource_samples_tx(&connection, since_ms, until_ms)
     }
 
     pub(crate) fn clear(&self) -> Result<(), String> {
@@ -582,6 +590,10 @@ fn resource_summary_tx(
         .iter()
         .filter_map(|sample| sample.host.cpu_percent.value().copied())
         .collect::<Vec<_>>();
+    let host_gpu = samples
+        .iter()
+        .filter_map(|sample| sample.host.gpu_percent.value().copied())
+        .collect::<Vec<_>>();
     let main_cpu = samples
         .iter()
         .filter_map(|sample| sample.main_process.cpu_percent.value().copied())
@@ -621,6 +633,7 @@ fn resource_summary_tx(
         sample_count: samples.len().try_into().unwrap_or(u32::MAX),
         host: HostResourceSummaryV1 {
             cpu_percent: range_f32(&host_cpu),
+            gpu_percent: range_f32(&host_gpu),
         },
         main_process: ProcessResourceSummaryV1 {
             cpu_percent: range_f32(&main_cpu),
@@ -779,6 +792,7 @@ mod tests {
             observed_at_ms: at,
             host: HostResourceSampleV1 {
                 cpu_percent: cpu.clone(),
+                gpu_percent: cpu.clone(),
             },
             main_process: ProcessResourceSampleV1 {
                 cpu_percent: cpu,
@@ -874,6 +888,72 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn timeline_filters_by_time_and_run_summaries_peak_gpu() {
+        let (_temp, repository) = repository();
+        let active = repository
+            .begin(
+                PerformanceRunKindV1::Dictation,
+                correlation(2),
+                Vec::new(),
+                ContentFreeInputSummaryV1::audio(1_000),
+            )
+            .unwrap();
+        repository
+            .insert_resource_sample(&sample(
+                active.started_at_ms - 1,
+                MeasurementV1::measured(90.0),
+            ))
+            .unwrap();
+        for gpu in [12.0, 64.0, 30.0] {
+            repository
+                .insert_resource_sample(&sample(active.started_at_ms, MeasurementV1::measured(gpu)))
+                .unwrap();
+        }
+        let timeline = repository
+            .resource_timeline(active.started_at_ms, i64::MAX)
+            .unwrap();
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[1].host.gpu_percent, MeasurementV1::measured(64.0));
+
+        let run = repository
+            .complete(
+                &correlation(2),
+                RunOutcomeV1::Success,
+                Vec::new(),
+                None,
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.resources.sample_count, 3);
+        assert_eq!(
+            run.resources.host.gpu_percent.peak,
+            MeasurementV1::measured(64.0)
+        );
+    }
+
+    #[test]
+    fn samples_recorded_before_gpu_sampling_still_load() {
+        let legacy = serde_json::json!({
+            "schemaVersion": RESOURCE_SAMPLE_SCHEMA_VERSION,
+            "observedAtMs": 1,
+            "host": { "cpuPercent": { "status": "measured", "value": 5.0 } },
+            "mainProcess": {
+                "cpuPercent": { "status": "measured", "value": 1.0 },
+                "rssBytes": { "status": "measured", "value": 100 },
+                "rustHeapBytes": { "status": "measured", "value": 20 },
+                "ffiNativeHeapBytes": { "status": "measured", "value": 30 }
+            },
+            "sidecarProcess": {
+                "cpuPercent": { "status": "notApplicable" },
+                "rssBytes": { "status": "notApplicable" }
+            }
+        });
+        let sample: ResourceSampleV1 = serde_json::from_value(legacy).unwrap();
+        assert_eq!(sample.host.gpu_percent, MeasurementV1::not_recorded());
+    }
+
     #[test]
     fn transform_summarizes_measured_sidecar_and_bounds_follow_ups() {
         let (_temp, repository) = repository();
@@ -25,6 +25,13 @@ impl<T> MeasurementV1<T> {
         Self::Measured { value }
     }
 
+    /// For fields added after a record was written.
+    pub fn not_recorded() -> Self {
+        Self::Unavailable {
+            reason: UnavailableReasonV1::NoSamples,
+        }
+    }
+
     pub fn value(&self) -> Option<&T> {
         match self {
             Self::Measured { value } => Some(value),
@@ -151,6 +158,8 @@ pub enum RuntimeBackendV1 {
     Parakeet,
     Coreml,
     LlamaCpp,
+    External,
+    Cloud,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -269,12 +278,21 @@ impl<T> ResourceRangeV1<T> {
             end: MeasurementV1::NotApplicable,
         }
     }
+
+    /// For fields added after a record was written.
+    pub fn not_recorded() -> Self {
+        Self::unavailable(UnavailableReasonV1::NoSamples)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HostResourceSampleV1 {
     pub cpu_percent: MeasurementV1<f32>,
+    /// GPU device utilization (macOS). Apple Neural Engine load has no public
+    /// counter, so it isn't sampled.
+    #[serde(default = "MeasurementV1::not_recorded")]
+    pub gpu_percent: MeasurementV1<f32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -316,6 +334,8 @@ pub struct ResourceSampleV1 {
 #[serde(rename_all = "camelCase")]
 pub struct HostResourceSummaryV1 {
     pub cpu_percent: ResourceRangeV1<f32>,
+    #[serde(default = "ResourceRangeV1::not_recorded")]
+    pub gpu_percent: ResourceRangeV1<f32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -361,6 +381,7 @@ impl ResourceSummaryV1 {
             sample_count: 0,
             host: HostResourceSummaryV1 {
                 cpu_percent: ResourceRangeV1::unavailable(no_samples),
+                gpu_percent: ResourceRangeV1::unavailable(no_samples),
             },
             main_process: ProcessResourceSummaryV1 {
                 cpu_percent: ResourceRangeV1::unavailable(no_samples),
@@ -0,0 +1,407 @@
+//! Live status of the macOS privacy permissions Murmur depends on.
+//!
+//! A watcher thread reads the TCC state of the microphone, Accessibility,
+//! Input Monitoring, and Automation of System Events every `POLL_INTERVAL`
+//! and emits `permission-changed` when
+//! any of them flips, so onboarding and the permissions banner update the
+//! moment the user toggles a switch in System Settings. Every probe only reads
+//! authorization state: none opens the microphone (issue #177) or shows a
+//! prompt. Off macOS everything reports granted.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionState {
+    Granted,
+    Denied,
+    /// TCC has no entry yet; the system prompt can still be shown.
+    NotDetermined,
+    /// The probe failed or returned an unexpected value. Never treated as a
+    /// hard denial (issue #190).
+    Unknown,
+}
+
+impl PermissionState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PermissionState::Granted => "granted",
+            PermissionState::Denied => "denied",
+            PermissionState::NotDetermined => "notDetermined",
+            PermissionState::Unknown => "unknown",
+        }
+    }
+}
+
+/// Every permission at one instant. Returned by `get_permission_report` and
+/// sent with `permission-changed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionReport {
+    pub microphone: PermissionState,
+    /// `AXIsProcessTrusted` can't tell "never asked" from "denied", so this is
+    /// only ever granted or denied.
+    pub accessibility: PermissionState,
+    /// Needed by the keyboard event tap on some macOS versions.
+    pub input_monitoring: PermissionState,
+    /// Apple Events access to System Events, needed by the osascript paste
+    /// path. `Unknown` while System Events isn't running.
+    pub automation: PermissionState,
+    /// Only probed when a feature needs it. Nothing captures the screen yet,
+    /// so this is always `None`.
+    pub screen_recording: Option<PermissionState>,
+}
+
+/// Payload of `permission-changed`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionChange {
+    /// Report field names that differ from the previous poll.
+    pub changed: Vec<&'static str>,
+    pub report: PermissionReport,
+}
+
+/// Map a raw macOS `AVAuthorizationStatus` value to a permission state.
+///
+/// `AVAuthorizationStatus` (AVFoundation):
+///   0 = notDetermined, 1 = restricted, 2 = denied, 3 = authorized.
+/// Any unexpected value (e.g. a future/probe-failure sentinel) maps to
+/// `Unknown` rather than `Denied` so a transient probe glitch never hard-fails
+/// the banner (issue #190).
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn mic_status_to_state(status: isize) -> PermissionState {
+    match status {
+        3 => PermissionState::Granted,
+        2 | 1 => PermissionState::Denied,
+        0 => PermissionState::NotDetermined,
+        _ => PermissionState::Unknown,
+    }
+}
+
+/// Map an `IOHIDAccessType` (0 = granted, 1 = denied, 2 = unknown, meaning
+/// not yet asked) to a permission state.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn hid_access_to_state(access: u32) -> PermissionState {
+    match access {
+        0 => PermissionState::Granted,
+        1 => PermissionState::Denied,
+        2 => PermissionState::NotDetermined,
+        _ => PermissionState::Unknown,
+    }
+}
+
+/// Map an `AEDeterminePermissionToAutomateTarget` result to a permission
+/// state: `noErr` is granted, `errAEEventNotPermitted` (-1743) denied, and
+/// `errAEEventWouldRequireUserConsent` (-1744) not yet asked. Anything else,
+/// such as `procNotFound` (-600) when the target isn't running, is unknown.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn automation_status_to_state(status: i32) -> PermissionState {
+    match status {
+        0 => PermissionState::Granted,
+        -1743 => PermissionState::Denied,
+        -1744 => PermissionState::NotDetermined,
+        _ => PermissionState::Unknown,
+    }
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn changed_permissions(
+    previous: &PermissionReport,
+    current: &PermissionReport,
+) -> Vec<&'static str> {
+    let mut changed = Vec::new();
+    if previous.microphone != current.microphone {
+        changed.push("microphone");
+    }
+    if previous.accessibility != current.accessibility {
+        changed.push("accessibility");
+    }
+    if previous.input_monitoring != current.input_monitoring {
+        changed.push("inputMonitoring");
+    }
+    if previous.automation != current.automation {
+        changed.push("automation");
+    }
+    if previous.screen_recording != current.screen_recording {
+        changed.push("screenRecording");
+    }
+    changed
+}
+
+/// Live `AVCaptureDevice.authorizationStatus(for: .audio)`, never cached.
+#[cfg(target_os = "macos")]
+pub fn microphone_state() -> PermissionState {
+    use objc2::msg_send;
+    use objc2::runtime::AnyClass;
+    use objc2_foundation::NSString;
+
+    unsafe {
+        let Some(cls) = AnyClass::get(c"AVCaptureDevice") else {
+            // Class lookup failed: a probe glitch, not a real denial.
+            return PermissionState::Unknown;
+        };
+        // AVMediaTypeAudio == @"soun"
+        let media = NSString::from_str("soun");
+        let status: isize = msg_send![cls, authorizationStatusForMediaType: &*media];
+        mic_status_to_state(status)
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn microphone_state() -> PermissionState {
+    PermissionState::Granted
+}
+
+#[cfg(target_os = "macos")]
+fn input_monitoring_state() -> PermissionState {
+    /// `kIOHIDRequestTypeListenEvent`
+    const LISTEN_EVENT: u32 = 1;
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOHIDCheckAccess(request_type: u32) -> u32;
+    }
+    hid_access_to_state(unsafe { IOHIDCheckAccess(LISTEN_EVENT) })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn input_monitoring_state() -> PermissionState {
+    PermissionState::Granted
+}
+
+/// Whether Murmur may send Apple Events to System Events. Never prompts.
+#[cfg(target_os = "macos")]
+pub fn automation_state() -> PermissionState {
+    /// `typeApplicationBundleID`
+    const TYPE_BUNDLE_ID: u32 = u32::from_be_bytes(*b"bund");
+    /// `typeWildCard`: any event class and id.
+    const WILD_CARD: u32 = u32::from_be_bytes(*b"****");
+    const SYSTEM_EVENTS: &[u8] = b"com.apple.systemevents";
+
+    #[repr(C)]
+    struct AEDesc {
+        descriptor_type: u32,
+        data_handle: *mut std::ffi::c_void,
+    }
+
+    #[link(name = "CoreServices", kind = "framework")]
+    extern "C" {
+        fn AECreateDesc(
+            type_code: u32,
+            data: *const std::ffi::c_void,
+            size: isize,
+            result: *mut AEDesc,
+        ) -> i16;
+        fn AEDeterminePermissionToAutomateTarget(
+            target: *const AEDesc,
+            event_class: u32,
+            event_id: u32,
+            ask_user_if_needed: u8,
+        ) -> i32;
+        fn AEDisposeDesc(desc: *mut AEDesc) -> i16;
+    }
+
+    unsafe {
+        let mut target = AEDesc {
+            descriptor_type: 0,
+            data_handle: std::ptr::null_mut(),
+        };
+        if AECreateDesc(
+            TYPE_BUNDLE_ID,
+            SYSTEM_EVENTS.as_ptr().cast(),
+            SYSTEM_EVENTS.len() as isize,
+            &mut target,
+        ) != 0
+        {
+            return PermissionState::Unknown;
+        }
+        let status = AEDeterminePermissionToAutomateTarget(&target, WILD_CARD, WILD_CARD, 0);
+        AEDisposeDesc(&mut target);
+        automation_status_to_state(status)
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn automation_state() -> PermissionState {
+    PermissionState::Granted
+}
+
+pub fn report() -> PermissionReport {
+    PermissionReport {
+        microphone: microphone_state(),
+        accessibility: if crate::injector::is_accessibility_enabled() {
+            PermissionState::Granted
+        } else {
+            PermissionState::Denied
+        },
+        input_monitoring: input_monitoring_state(),
+        automation: automation_state(),
+        screen_recording: None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod watcher {
+    use super::*;
+    use crate::MutexExt;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tauri::Emitter;
+
+    const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+    /// What the watcher last saw; `None` until the first poll.
+    static LAST_REPORT: Mutex<Option<PermissionReport>> = Mutex::new(None);
+
+    fn poll(app: &tauri::AppHandle) {
+        let current = report();
+        let Some(previous) = LAST_REPORT.lock_or_recover().replace(current) else {
+            return;
+        };
+        let changed = changed_permissions(&previous, &current);
+        if changed.is_empty() {
+            return;
+        }
+        tracing::info!(
+            target: "system",
+            changed = ?changed,
+            microphone = current.microphone.as_str(),
+            accessibility = current.accessibility.as_str(),
+            input_monitoring = current.input_monitoring.as_str(),
+            automation = current.automation.as_str(),
+            "permission changed"
+        );
+        let _ = app.emit(
+            "permission-changed",
+            PermissionChange {
+                changed,
+                report: current,
+            },
+        );
+    }
+
+    pub(super) fn start(app: &tauri::AppHandle) {
+        let handle = app.clone();
+        if let Err(error) = std::thread::Builder::new()
+            .name("permission-watcher".to_string())
+            .spawn(move || loop {
+                poll(&handle);
+                std::thread::sleep(POLL_INTERVAL);
+            })
+        {
+            tracing::warn!(target: "system", "permission watcher failed to start: {}", error);
+        }
+    }
+}
+
+/// Start the permission watcher. Call once from setup.
+#[cfg(target_os = "macos")]
+pub(crate) fn start(app: &tauri::AppHandle) {
+    watcher::start(app);
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn start(_app: &tauri::AppHandle) {}
+
+#[tauri::command]
+pub fn get_permission_report() -> PermissionReport {
+    report()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn granted() -> PermissionReport {
+        PermissionReport {
+            microphone: PermissionState::Granted,
+            accessibility: PermissionState::Granted,
+            input_monitoring: PermissionState::Granted,
+            automation: PermissionState::Granted,
+            screen_recording: None,
+        }
+    }
+
+    #[test]
+    fn authorized_status_maps_to_granted() {
+        // AVAuthorizationStatusAuthorized
+        assert_eq!(mic_status_to_state(3), PermissionState::Granted);
+    }
+
+    #[test]
+    fn denied_and_restricted_map_to_denied() {
+        // AVAuthorizationStatusDenied
+        assert_eq!(mic_status_to_state(2), PermissionState::Denied);
+        // AVAuthorizationStatusRestricted (e.g. MDM/parental controls) — still a
+        // genuine block, so it must read as denied, not a transient state.
+        assert_eq!(mic_status_to_state(1), PermissionState::Denied);
+    }
+
+    #[test]
+    fn not_determined_is_not_a_hard_denial() {
+        // AVAuthorizationStatusNotDetermined: TCC has no entry yet (common right
+        // after a rebuild/move). Must NOT collapse to "denied" (issue #190).
+        let state = mic_status_to_state(0);
+        assert_eq!(state.as_str(), "notDetermined");
+        assert_ne!(state, PermissionState::Denied);
+    }
+
+    #[test]
+    fn unexpected_values_map_to_unknown_not_denied() {
+        // Any future/sentinel value from a probe glitch must degrade to "unknown",
+        // never a hard "denied" — we never want to false-negative the banner.
+        for v in [-1isize, 4, 99, isize::MAX, isize::MIN] {
+            let state = mic_status_to_state(v);
+            assert_eq!(
+                state,
+                PermissionState::Unknown,
+                "value {v} should map to unknown"
+            );
+            assert_ne!(
+                state,
+                PermissionState::Denied,
+                "value {v} must not map to denied"
+            );
+        }
+    }
+
+    #[test]
+    fn input_monitoring_unknown_means_not_yet_asked() {
+        assert_eq!(hid_access_to_state(0), PermissionState::Granted);
+        assert_eq!(hid_access_to_state(1), PermissionState::Denied);
+        assert_eq!(hid_access_to_state(2), PermissionState::NotDetermined);
+        assert_eq!(hid_access_to_state(7), PermissionState::Unknown);
+    }
+
+    #[test]
+    fn automation_status_codes_map_to_states() {
+        assert_eq!(automation_status_to_state(0), PermissionState::Granted);
+        assert_eq!(automation_status_to_state(-1743), PermissionState::Denied);
+        assert_eq!(
+            automation_status_to_state(-1744),
+            PermissionState::NotDetermined
+        );
+        // procNotFound: System Events isn't running, which says nothing about
+        // the permission.
+        assert_eq!(automation_status_to_state(-600), PermissionState::Unknown);
+    }
+
+    #[test]
+    fn only_flipped_permissions_are_reported_as_changed() {
+        assert!(changed_permissions(&granted(), &granted()).is_empty());
+        let revoked = PermissionReport {
+            accessibility: PermissionState::Denied,
+            input_monitoring: PermissionState::NotDetermined,
+            automation: PermissionState::Denied,
+            ..granted()
+        };
+        assert_eq!(
+            changed_permissions(&granted(), &revoked),
+            vec!["accessibility", "inputMonitoring", "automation"]
+        );
+        let report = serde_json::to_value(revoked).unwrap();
+        assert_eq!(report["inputMonitoring"], "notDetermined");
+        assert_eq!(report["screenRecording"], serde_json::Value::Null);
+    }
+}
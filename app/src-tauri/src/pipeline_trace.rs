@@ -0,0 +1,306 @@
+//! Tracing spans around each dictation's pipeline phases.
+//!
+//! A dictation runs inside one `pipeline` span whose `pipeline_id` is the
+//! recording id, the same id its events carry as `recordingId`. Each phase
+//! (capture, resample, preprocess, inference, postprocess, inject) is a
+//! `phase` span inside it, so the pretty log prefixes every line with both
+//! and structured app events gain `pipeline_id` and `phase` fields.
+//!
+//! [`PipelineTraceLayer`] times the spans. When a pipeline span closes, its
+//! phases become the trace `get_last_pipeline_trace` returns, so one slow
+//! dictation can be taken apart without lining up log timestamps. Phases a
+//! dictation never reached (no speech, cancelled) are absent. Only ids, phase
+//! names, and timings are kept.
+
+use crate::MutexExt;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+const PIPELINE_SPAN: &str = "pipeline";
+const PHASE_SPAN: &str = "phase";
+
+static LAST_TRACE: Mutex<Option<PipelineTrace>> = Mutex::new(None);
+/// Orders phases that began within the same millisecond.
+static PHASE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PipelinePhase {
+    /// Stopping the microphone and collecting the recording.
+    Capture,
+    /// Converting the device's sample rate to 16 kHz.
+    Resample,
+    /// Level checks and voice activity detection.
+    Preprocess,
+    /// Model queue, load, decode, and any low-confidence retry.
+    Inference,
+    /// Transcript transforms, corrections, and file output.
+    Postprocess,
+    /// Clipboard write and paste, or whatever delivery replaced them.
+    Inject,
+}
+
+impl PipelinePhase {
+    const ALL: [Self; 6] = [
+        Self::Capture,
+        Self::Resample,
+        Self::Preprocess,
+        Self::Inference,
+        Self::Postprocess,
+        Self::Inject,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Capture => "capture",
+            Self::Resample => "resample",
+            Self::Preprocess => "preprocess",
+            Self::Inference => "inference",
+            Self::Postprocess => "postprocess",
+            Self::Inject => "inject",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|phase| phase.as_str() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTiming {
+    pub phase: PipelinePhase,
+    /// When the phase began, in ms after the pipeline did.
+    pub start_ms: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineTrace {
+    pub pipeline_id: u64,
+    /// Wall-clock start, ms since the Unix epoch.
+    pub started_at_ms: i64,
+    pub total_ms: u64,
+    /// In the order they began. Resample runs inside capture, so the two
+    /// overlap.
+    pub phases: Vec<PhaseTiming>,
+}
+
+/// The span one dictation's pipeline runs in. Enter it, or instrument the
+/// pipeline's future with it; the trace is kept when its last handle drops.
+pub fn pipeline_span(pipeline_id: u64) -> tracing::Span {
+    tracing::info_span!(target: "pipeline", "pipeline", pipeline_id)
+}
+
+/// A phase of the pipeline in the current span. Its time runs from now until
+/// the span drops, whether or not it is entered in between.
+pub fn phase(phase: PipelinePhase) -> tracing::Span {
+    tracing::info_span!(target: "pipeline", "phase", phase = phase.as_str())
+}
+
+/// Phases of the most recently finished dictation pipeline.
+#[tauri::command]
+pub fn get_last_pipeline_trace() -> Option<PipelineTrace> {
+    LAST_TRACE.lock_or_recover().clone()
+}
+
+/// The pipeline id and innermost phase of the spans `event` is inside, for
+/// structured log events.
+pub(crate) fn event_context<S>(
+    ctx: &Context<'_, S>,
+    event: &tracing::Event<'_>,
+) -> (Option<u64>, Option<PipelinePhase>)
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let (mut pipeline_id, mut phase) = (None, None);
+    for span in ctx.event_scope(event).into_iter().flatten() {
+        let extensions = span.extensions();
+        if let Some(open) = extensions.get::<OpenPhase>() {
+            phase = phase.or(Some(open.phase));
+        }
+        if let Some(open) = extensions.get::<OpenPipeline>() {
+            pipeline_id = Some(open.pipeline_id);
+            break;
+        }
+    }
+    (pipeline_id, phase)
+}
+
+struct OpenPipeline {
+    pipeline_id: u64,
+    started: Instant,
+    started_at_ms: i64,
+    /// Closed phases with the sequence number they began at.
+    phases: Vec<(u64, PhaseTiming)>,
+}
+
+struct OpenPhase {
+    phase: PipelinePhase,
+    sequence: u64,
+    started: Instant,
+}
+
+#[derive(Default)]
+struct SpanFields {
+    pipeline_id: Option<u64>,
+    phase: Option<PipelinePhase>,
+}
+
+impl Visit for SpanFields {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "pipeline_id" {
+            self.pipeline_id = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "phase" {
+            self.phase = PipelinePhase::from_name(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+fn elapsed_ms(from: Instant, to: Instant) -> u64 {
+    to.saturating_duration_since(from).as_millis() as u64
+}
+
+/// Times `pipeline` and `phase` spans into the trace behind
+/// `get_last_pipeline_trace`. Other spans are ignored.
+pub struct PipelineTraceLayer;
+
+impl<S> tracing_subscriber::Layer<S> for PipelineTraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let name = attrs.metadata().name();
+        if name != PIPELINE_SPAN && name != PHASE_SPAN {
+            return;
+        }
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = SpanFields::default();
+        attrs.record(&mut fields);
+        let mut extensions = span.extensions_mut();
+        match (name, fields.pipeline_id, fields.phase) {
+            (PIPELINE_SPAN, Some(pipeline_id), _) => extensions.insert(OpenPipeline {
+                pipeline_id,
+                started: Instant::now(),
+                started_at_ms: chrono::Utc::now().timestamp_millis(),
+                phases: Vec::new(),
+            }),
+            (PHASE_SPAN, _, Some(phase)) => extensions.insert(OpenPhase {
+                phase,
+                sequence: PHASE_SEQUENCE.fetch_add(1, Ordering::Relaxed),
+                started: Instant::now(),
+            }),
+            _ => {}
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let closed = Instant::now();
+        if let Some(open) = span.extensions_mut().remove::<OpenPhase>() {
+            let Some(pipeline) = span
+                .scope()
+                .skip(1)
+                .find(|ancestor| ancestor.extensions().get::<OpenPipeline>().is_some())
+            else {
+                return;
+            };
+            let mut extensions = pipeline.extensions_mut();
+            if let Some(trace) = extensions.get_mut::<OpenPipeline>() {
+                trace.phases.push((
+                    open.sequence,
+                    PhaseTiming {
+                        phase: open.phase,
+                        start_ms: elapsed_ms(trace.started, open.started),
+                        duration_ms: elapsed_ms(open.started, closed),
+                    },
+                ));
+            }
+            return;
+        }
+        if let Some(open) = span.extensions_mut().remove::<OpenPipeline>() {
+            let mut phases = open.phases;
+            phases.sort_by_key(|(sequence, _)| *sequence);
+            let trace = PipelineTrace {
+                pipeline_id: open.pipeline_id,
+                started_at_ms: open.started_at_ms,
+                total_ms: elapsed_ms(open.started, closed),
+                phases: phases.into_iter().map(|(_, timing)| timing).collect(),
+            };
+            *LAST_TRACE.lock_or_recover() = Some(trace);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn closed_pipeline_span_becomes_the_last_trace() {
+        let subscriber = tracing_subscriber::registry().with(PipelineTraceLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            let root = pipeline_span(41);
+            root.in_scope(|| {
+                let capture = phase(PipelinePhase::Capture);
+                capture.in_scope(|| {
+                    let _resample = phase(PipelinePhase::Resample);
+                });
+                drop(capture);
+                let inference = phase(PipelinePhase::Inference);
+                std::thread::sleep(std::time::Duration::from_millis(5));
+                drop(inference);
+                // Spans outside the pipeline are ignored.
+                let _unrelated = tracing::info_span!("other").entered();
+            });
+            assert!(get_last_pipeline_trace().is_none_or(|trace| trace.pipeline_id != 41));
+            drop(root);
+        });
+
+        let trace = get_last_pipeline_trace().unwrap();
+        assert_eq!(trace.pipeline_id, 41);
+        let phases: Vec<PipelinePhase> = trace.phases.iter().map(|timing| timing.phase).collect();
+        assert_eq!(
+            phases,
+            vec![
+                PipelinePhase::Capture,
+                PipelinePhase::Resample,
+                PipelinePhase::Inference
+            ]
+        );
+        let inference = &trace.phases[2];
+        assert!(inference.duration_ms >= 5);
+        assert!(inference.start_ms + inference.duration_ms <= trace.total_ms);
+    }
+
+    #[test]
+    fn phase_names_round_trip() {
+        for phase in PipelinePhase::ALL {
+            assert_eq!(PipelinePhase::from_name(phase.as_str()), Some(phase));
+            assert_eq!(
+                serde_json::to_value(phase).unwrap(),
+                serde_json::json!(phase.as_str())
+            );
+        }
+        assert_eq!(PipelinePhase::from_name("total"), None);
+    }
+}
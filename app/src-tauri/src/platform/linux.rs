@@ -47,6 +47,10 @@ pub(super) fn cpu_percent() -> Option<f32> {
     update_cpu_percent(&mut PREVIOUS.lock_or_recover(), snapshot())
 }
 
+pub(super) fn gpu_percent() -> Option<f32> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
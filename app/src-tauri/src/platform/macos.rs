@@ -1,6 +1,7 @@
 use super::{update_cpu_percent, CpuTicks};
 use crate::MutexExt;
-use std::os::raw::c_int;
+use std::ffi::c_void;
+use std::os::raw::{c_char, c_int};
 use std::sync::Mutex;
 
 #[repr(C)]
@@ -47,3 +48,118 @@ pub(super) fn cpu_percent() -> Option<f32> {
     let current = snapshot();
     update_cpu_percent(&mut PREVIOUS.lock_or_recover(), current)
 }
+
+type CFTypeRef = *const c_void;
+
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+const CF_NUMBER_SINT64_TYPE: c_int = 4;
+/// `kIOMainPortDefault`
+const IO_MAIN_PORT_DEFAULT: u32 = 0;
+
+#[link(name = "IOKit", kind = "framework")]
+unsafe extern "C" {
+    fn IOServiceMatching(name: *const c_char) -> CFTypeRef;
+    fn IOServiceGetMatchingServices(
+        main_port: u32,
+        matching: CFTypeRef,
+        iterator: *mut u32,
+    ) -> c_int;
+    fn IOIteratorNext(iterator: u32) -> u32;
+    fn IORegistryEntryCreateCFProperty(
+        entry: u32,
+        key: CFTypeRef,
+        allocator: CFTypeRef,
+        options: u32,
+    ) -> CFTypeRef;
+    fn IOObjectRelease(object: u32) -> c_int;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+unsafe extern "C" {
+    fn CFStringCreateWithCString(
+        allocator: CFTypeRef,
+        value: *const c_char,
+        encoding: u32,
+    ) -> CFTypeRef;
+    fn CFDictionaryGetValue(dictionary: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+    fn CFGetTypeID(value: CFTypeRef) -> usize;
+    fn CFDictionaryGetTypeID() -> usize;
+    fn CFNumberGetTypeID() -> usize;
+    fn CFNumberGetValue(number: CFTypeRef, kind: c_int, value: *mut c_void) -> bool;
+    fn CFRelease(value: CFTypeRef);
+}
+
+fn cf_string(value: &std::ffi::CStr) -> Option<CFTypeRef> {
+    let string = unsafe {
+        CFStringCreateWithCString(std::ptr::null(), value.as_ptr(), CF_STRING_ENCODING_UTF8)
+    };
+    (!string.is_null()).then_some(string)
+}
+
+/// "Device Utilization %" from one accelerator's `PerformanceStatistics`.
+fn accelerator_utilization(
+    entry: u32,
+    statistics_key: CFTypeRef,
+    utilization_key: CFTypeRef,
+) -> Option<f32> {
+    let statistics =
+        unsafe { IORegistryEntryCreateCFProperty(entry, statistics_key, std::ptr::null(), 0) };
+    if statistics.is_null() {
+        return None;
+    }
+    let mut percent = None;
+    if unsafe { CFGetTypeID(statistics) == CFDictionaryGetTypeID() } {
+        let value = unsafe { CFDictionaryGetValue(statistics, utilization_key) };
+        if !value.is_null() && unsafe { CFGetTypeID(value) == CFNumberGetTypeID() } {
+            let mut number = 0_i64;
+            if unsafe {
+                CFNumberGetValue(
+                    value,
+                    CF_NUMBER_SINT64_TYPE,
+                    &mut number as *mut i64 as *mut c_void,
+                )
+            } {
+                percent = Some(number.clamp(0, 100) as f32);
+            }
+        }
+    }
+    unsafe { CFRelease(statistics) };
+    percent
+}
+
+/// Busiest GPU's utilization, read from the IOAccelerator registry entries
+/// the driver publishes. No privileges needed; None when nothing reports.
+pub(super) fn gpu_percent() -> Option<f32> {
+    let statistics_key = cf_string(c"PerformanceStatistics")?;
+    let Some(utilization_key) = cf_string(c"Device Utilization %") else {
+        unsafe { CFRelease(statistics_key) };
+        return None;
+    };
+
+    let mut percent: Option<f32> = None;
+    let matching = unsafe { IOServiceMatching(c"IOAccelerator".as_ptr()) };
+    let mut iterator = 0_u32;
+    // IOServiceGetMatchingServices consumes `matching`.
+    if !matching.is_null()
+        && unsafe { IOServiceGetMatchingServices(IO_MAIN_PORT_DEFAULT, matching, &mut iterator) }
+            == KERN_SUCCESS
+    {
+        loop {
+            let entry = unsafe { IOIteratorNext(iterator) };
+            if entry == 0 {
+                break;
+            }
+            if let Some(value) = accelerator_utilization(entry, statistics_key, utilization_key) {
+                percent = Some(percent.map_or(value, |current| current.max(value)));
+            }
+            unsafe { IOObjectRelease(entry) };
+        }
+        unsafe { IOObjectRelease(iterator) };
+    }
+
+    unsafe {
+        CFRelease(utilization_key);
+        CFRelease(statistics_key);
+    }
+    percent
+}
@@ -15,6 +15,11 @@ pub(crate) fn cpu_percent() -> Option<f32> {
     current::cpu_percent()
 }
 
+/// Host GPU utilization, where the platform exposes one.
+pub(crate) fn gpu_percent() -> Option<f32> {
+    current::gpu_percent()
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) struct CpuTicks {
     active: u64,
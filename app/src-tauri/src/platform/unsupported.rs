@@ -1,3 +1,7 @@
 pub(super) fn cpu_percent() -> Option<f32> {
     None
 }
+
+pub(super) fn gpu_percent() -> Option<f32> {
+    None
+}
@@ -0,0 +1,459 @@
+//! Portable settings and history: versioned archive files another Mac can
+//! import, and a sync folder (iCloud Drive, Syncthing, Dropbox) that holds
+//! the shared copies.
+//!
+//! Settings and plaintext history live in the webview's localStorage, so the
+//! frontend hands them over as JSON and sanitizes what comes back; this module
+//! owns the file format and the writes. Every archive is written to a
+//! temporary file next to its destination, flushed, and renamed over it, while
+//! a `.murmur-sync.lock` file in the same folder keeps writers apart. On this
+//! Mac the lock is an `flock` on that file, so an export and a sync never
+//! overlap and a live writer is never taken over. Another Mac only sees the
+//! file once the sync tool delivers it, so across Macs the lock is best
+//! effort: a lock no process here holds is taken over once it is older than
+//! [`LOCK_STALE`], and two Macs that write in the same moment resolve by
+//! `exportedAtMs`, the newer archive winning when each reads the folder.
+//!
+//! Learned corrections (`corrections.json`) and notes stay in each Mac's own
+//! data directory; they are neither exported nor synced.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Bumped when the archive layout changes. Newer archives are refused rather
+/// than half-read.
+const ARCHIVE_VERSION: u32 = 1;
+const LOCK_FILE: &str = ".murmur-sync.lock";
+/// How long a writer waits for another one to finish.
+const LOCK_WAIT: Duration = Duration::from_secs(3);
+/// A lock this old belongs to a writer that is gone.
+const LOCK_STALE: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Settings,
+    History,
+}
+
+impl ArchiveKind {
+    fn format(self) -> &'static str {
+        match self {
+            Self::Settings => "murmur-settings",
+            Self::History => "murmur-history",
+        }
+    }
+
+    /// File name inside a sync folder.
+    fn file_name(self) -> &'static str {
+        match self {
+            Self::Settings => "murmur-settings.json",
+            Self::History => "murmur-history.json",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Settings => "settings",
+            Self::History => "history",
+        }
+    }
+
+    /// Settings are one object; history is a list of entries.
+    fn check(self, data: &serde_json::Value) -> Result<(), String> {
+        match self {
+            Self::Settings if data.is_object() => Ok(()),
+            Self::History if data.is_array() => Ok(()),
+            Self::Settings => Err("Settings must be an object.".to_string()),
+            Self::History => Err("History must be a list of entries.".to_string()),
+        }
+    }
+}
+
+/// One archive file: settings or history plus where and when it was written.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Archive {
+    pub format: String,
+    pub version: u32,
+    pub exported_at_ms: u64,
+    /// The Murmur version that wrote it.
+    pub app_version: String,
+    pub data: serde_json::Value,
+}
+
+/// What `read_data_folder` found. A file that is missing, or that doesn't
+/// read as an archive yet (mid-sync), is `None`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataFolderContents {
+    pub settings: Option<Archive>,
+    pub history: Option<Archive>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn archive(kind: ArchiveKind, data: serde_json::Value, now: u64) -> Result<Archive, String> {
+    kind.check(&data)?;
+    Ok(Archive {
+        format: kind.format().to_string(),
+        version: ARCHIVE_VERSION,
+        exported_at_ms: now,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        data,
+    })
+}
+
+fn parse(kind: ArchiveKind, bytes: &[u8]) -> Result<Archive, String> {
+    let not_archive = || format!("The file is not a Murmur {} export.", kind.label());
+    let archive: Archive = serde_json::from_slice(bytes).map_err(|_| not_archive())?;
+    if archive.format != kind.format() {
+        return Err(not_archive());
+    }
+    if archive.version > ARCHIVE_VERSION {
+        return Err(format!(
+            "This {} export is from a newer version of Murmur. Update Murmur to import it.",
+            kind.label()
+        ));
+    }
+    kind.check(&archive.data).map_err(|_| not_archive())?;
+    Ok(archive)
+}
+
+fn read(kind: ArchiveKind, path: &Path) -> Result<Archive, String> {
+    let bytes =
+        fs::read(path).map_err(|e| format!("Couldn't read the {} file: {}", kind.label(), e))?;
+    parse(kind, &bytes)
+}
+
+/// `flock` `file`, blocking until it is free or, with `wait` false, returning
+/// false when another process holds it.
+#[cfg(unix)]
+fn flock(file: &File, wait: bool) -> std::io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+    let operation = if wait {
+        libc::LOCK_EX
+    } else {
+        libc::LOCK_EX | libc::LOCK_NB
+    };
+    if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+        return Ok(true);
+    }
+    let error = std::io::Error::last_os_error();
+    if error.raw_os_error() == Some(libc::EWOULDBLOCK) {
+        Ok(false)
+    } else {
+        Err(error)
+    }
+}
+
+/// No `flock`: the lock file alone keeps writers apart.
+#[cfg(not(unix))]
+fn flock(_file: &File, _wait: bool) -> std::io::Result<bool> {
+    Ok(true)
+}
+
+fn is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > LOCK_STALE)
+}
+
+/// Held while writing into a folder: the lock file, `flock`ed. Removes the
+/// file when dropped, before closing it releases the `flock`.
+struct FolderLock {
+    path: PathBuf,
+    _file: File,
+}
+
+impl FolderLock {
+    /// Take the folder's lock, waiting up to `wait` for another writer.
+    fn acquire(dir: &Path, wait: Duration) -> Result<Self, String> {
+        let path = dir.join(LOCK_FILE);
+        let io_error = |e: std::io::Error| format!("Couldn't write to {}: {}", dir.display(), e);
+        let started = Instant::now();
+        loop {
+            let (mut file, created) =
+                match OpenOptions::new().write(true).create_new(true).open(&path) {
+                    Ok(file) => (file, true),
+                    Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                        match OpenOptions::new().write(true).open(&path) {
+                            Ok(file) => (file, false),
+                            // Removed by its writer in between.
+                            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                            Err(e) => return Err(io_error(e)),
+                        }
+                    }
+                    Err(e) => return Err(io_error(e)),
+                };
+            // A file this call created can only be held for a moment, by a
+            // writer checking whether it is stale, so that wait is blocking.
+            let held_here = !flock(&file, created).map_err(io_error)?;
+            if !held_here && (created || is_stale(&path)) {
+                if !created {
+                    tracing::warn!(target: "system", path = %path.display(), "taking over a stale sync lock");
+                }
+                file.set_len(0)
+                    .and_then(|()| writeln!(file, "{}", std::process::id()))
+                    .map_err(io_error)?;
+                return Ok(Self { path, _file: file });
+            }
+            drop(file);
+            if started.elapsed() >= wait {
+                return Err(if held_here {
+                    "Murmur is already writing to this folder. Try again in a moment."
+                } else {
+                    "Another Mac is writing to this folder. Try again in a moment."
+                }
+                .to_string());
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+impl Drop for FolderLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Write `bytes` to a hidden temporary file next to `path`, flush it to disk,
+/// and rename it over `path`, so readers (and sync tools) never see half a file.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return Err("Choose a valid destination.".to_string());
+    };
+    let temp = parent.join(format!(".{}.tmp", name.to_string_lossy()));
+    let written = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp, path));
+    written.map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        format!("Couldn't write {}: {}", path.display(), e)
+    })
+}
+
+fn encode(archive: &Archive) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(archive).map_err(|e| format!("Failed to serialize the export: {}", e))
+}
+
+fn export(kind: ArchiveKind, path: &Path, data: serde_json::Value) -> Result<(), String> {
+    let bytes = encode(&archive(kind, data, now_ms())?)?;
+    let parent = path
+        .parent()
+        .ok_or_else(|| "Choose a valid destination.".to_string())?;
+    let _lock = FolderLock::acquire(parent, LOCK_WAIT)?;
+    write_atomic(path, &bytes)
+}
+
+/// Read the sync folder's archives. Missing or unreadable files are `None`.
+fn read_folder(folder: &Path) -> Result<DataFolderContents, String> {
+    if !folder.is_dir() {
+        return Err(format!(
+            "The sync folder {} is missing. Choose it again in Settings.",
+            folder.display()
+        ));
+    }
+    let load = |kind: ArchiveKind| {
+        let path = folder.join(kind.file_name());
+        if !path.exists() {
+            return None;
+        }
+        read(kind, &path)
+            .map_err(|e| tracing::warn!(target: "system", kind = kind.label(), "sync folder archive skipped: {}", e))
+            .ok()
+    };
+    Ok(DataFolderContents {
+        settings: load(ArchiveKind::Settings),
+        history: load(ArchiveKind::History),
+    })
+}
+
+/// Write whichever of `settings` and `history` are given into the sync
+/// folder under one lock. Returns the timestamp stamped on them.
+fn write_folder(
+    folder: &Path,
+    settings: Option<serde_json::Value>,
+    history: Option<serde_json::Value>,
+    now: u64,
+) -> Result<u64, String> {
+    let mut archives = Vec::new();
+    if let Some(settings) = settings {
+        archives.push((
+            ArchiveKind::Settings,
+            archive(ArchiveKind::Settings, settings, now)?,
+        ));
+    }
+    if let Some(history) = history {
+        archives.push((
+            ArchiveKind::History,
+            archive(ArchiveKind::History, history, now)?,
+        ));
+    }
+    fs::create_dir_all(folder)
+        .map_err(|e| format!("Couldn't create {}: {}", folder.display(), e))?;
+    let _lock = FolderLock::acquire(folder, LOCK_WAIT)?;
+    for (kind, archive) in &archives {
+        write_atomic(&folder.join(kind.file_name()), &encode(archive)?)?;
+    }
+    Ok(now)
+}
+
+/// Run folder I/O off the async runtime: a synced folder can stall on a
+/// network volume, and a writer may wait for the lock.
+async fn blocking<T: Send + 'static>(
+    task: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(task)
+        .await
+        .map_err(|e| format!("Sync task failed: {}", e))?
+}
+
+/// Write the frontend's settings to `path` as a versioned archive.
+#[tauri::command]
+pub async fn export_settings(path: String, settings: serde_json::Value) -> Result<(), String> {
+    blocking(move || export(ArchiveKind::Settings, Path::new(&path), settings)).await?;
+    tracing::info!(target: "system", "settings exported");
+    Ok(())
+}
+
+/// The settings object from an archive. The frontend sanitizes it like
+/// stored settings before applying it.
+#[tauri::command]
+pub async fn import_settings(path: String) -> Result<serde_json::Value, String> {
+    blocking(move || Ok(read(ArchiveKind::Settings, Path::new(&path))?.data)).await
+}
+
+/// Write history entries to `path` as a versioned archive. Returns the count.
+#[tauri::command]
+pub async fn export_history(path: String, entries: serde_json::Value) -> Result<usize, String> {
+    let count = entries.as_array().map_or(0, Vec::len);
+    blocking(move || export(ArchiveKind::History, Path::new(&path), entries)).await?;
+    tracing::info!(target: "system", count, "history exported");
+    Ok(count)
+}
+
+/// The history entries from an archive, for the frontend to merge by id.
+#[tauri::command]
+pub async fn import_history(path: String) -> Result<serde_json::Value, String> {
+    blocking(move || Ok(read(ArchiveKind::History, Path::new(&path))?.data)).await
+}
+
+#[tauri::command]
+pub async fn read_data_folder(folder: String) -> Result<DataFolderContents, String> {
+    blocking(move || read_folder(Path::new(&folder))).await
+}
+
+/// Write settings and/or history into the sync folder. Returns the
+/// `exportedAtMs` stamped on them; readers keep whichever archive has the
+/// newest stamp, so the last writer wins.
+#[tauri::command]
+pub async fn write_data_folder(
+    folder: String,
+    settings: Option<serde_json::Value>,
+    history: Option<serde_json::Value>,
+) -> Result<u64, String> {
+    blocking(move || write_folder(Path::new(&folder), settings, history, now_ms())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn archives_round_trip_and_reject_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        export(ArchiveKind::Settings, &path, json!({ "model": "base.en" })).unwrap();
+        let archive = read(ArchiveKind::Settings, &path).unwrap();
+        assert_eq!(archive.format, "murmur-settings");
+        assert_eq!(archive.version, ARCHIVE_VERSION);
+        assert_eq!(archive.data, json!({ "model": "base.en" }));
+        assert!(!dir.path().join(".settings.json.tmp").exists());
+        assert!(!dir.path().join(LOCK_FILE).exists());
+
+        assert!(read(ArchiveKind::History, &path).is_err());
+        assert!(parse(ArchiveKind::Settings, b"{\"model\":\"base.en\"}").is_err());
+        assert!(parse(ArchiveKind::Settings, b"not json").is_err());
+        assert!(export(ArchiveKind::History, &path, json!({})).is_err());
+    }
+
+    #[test]
+    fn newer_archives_are_refused() {
+        let mut newer = archive(ArchiveKind::History, json!([]), 1).unwrap();
+        newer.version = ARCHIVE_VERSION + 1;
+        let error = parse(ArchiveKind::History, &encode(&newer).unwrap()).unwrap_err();
+        assert!(error.contains("newer version"));
+    }
+
+    #[test]
+    fn folder_writes_both_archives_and_skips_unreadable_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("Murmur");
+        assert!(read_folder(&folder).is_err());
+
+        let stamp = write_folder(
+            &folder,
+            Some(json!({ "language": "en" })),
+            Some(json!([{ "id": "1" }])),
+            42,
+        )
+        .unwrap();
+        assert_eq!(stamp, 42);
+        let contents = read_folder(&folder).unwrap();
+        assert_eq!(contents.settings.unwrap().exported_at_ms, 42);
+        assert_eq!(contents.history.unwrap().data, json!([{ "id": "1" }]));
+
+        fs::write(folder.join("murmur-history.json"), b"{\"format\":").unwrap();
+        let contents = read_folder(&folder).unwrap();
+        assert!(contents.settings.is_some());
+        assert!(contents.history.is_none());
+    }
+
+    #[test]
+    fn a_held_lock_blocks_writers_until_it_goes_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        let make_stale = || {
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(SystemTime::now() - LOCK_STALE * 2)
+                .unwrap();
+        };
+        let held = FolderLock::acquire(dir.path(), Duration::ZERO).unwrap();
+        assert!(FolderLock::acquire(dir.path(), Duration::ZERO).is_err());
+        drop(held);
+        assert!(!path.exists());
+
+        // A lock file nothing here holds came from another Mac or a crash,
+        // and is taken over only once it is stale.
+        fs::write(&path, "1\n").unwrap();
+        assert!(FolderLock::acquire(dir.path(), Duration::ZERO).is_err());
+        make_stale();
+        let taken = FolderLock::acquire(dir.path(), Duration::ZERO).unwrap();
+
+        // A live writer on this Mac is never taken over, however old its lock.
+        if cfg!(unix) {
+            make_stale();
+            assert!(FolderLock::acquire(dir.path(), Duration::ZERO).is_err());
+        }
+        drop(taken);
+        assert!(FolderLock::acquire(dir.path(), Duration::ZERO).is_ok());
+    }
+}
@@ -0,0 +1,292 @@
+//! Power source detection: on the adapter or on battery, and how much charge
+//! is left.
+//!
+//! On macOS this reads IOKit's power source snapshot; on Linux, the
+//! `/sys/class/power_supply` entries. Other platforms report an unknown
+//! source. A background thread polls every 30 seconds and emits
+//! `power-status-changed` when the source or charge changes. The frontend owns
+//! the per-source defaults (`powerProfiles`) and applies them through the
+//! normal settings path, so nothing here changes dictation directly.
+
+use crate::MutexExt;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Emitter;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    /// No battery detected or the platform doesn't report one. Treated as AC
+    /// by the frontend.
+    #[default]
+    Unknown,
+}
+
+/// Snapshot sent with `power-status-changed` and returned by
+/// `get_power_status`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    pub source: PowerSource,
+    /// Charge of the internal battery, 0–100. `None` without a battery.
+    pub battery_percent: Option<u8>,
+}
+
+static LAST: Mutex<Option<PowerStatus>> = Mutex::new(None);
+
+/// Charge as a whole percentage of capacity. `None` for a zero capacity.
+fn percent_of(current: i64, max: i64) -> Option<u8> {
+    if max <= 0 {
+        return None;
+    }
+    Some((current.clamp(0, max) * 100 / max) as u8)
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{percent_of, PowerSource, PowerStatus};
+    use std::ffi::c_void;
+    use std::os::raw::{c_char, c_int};
+
+    type CFTypeRef = *const c_void;
+
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+    const CF_NUMBER_SINT64_TYPE: c_int = 4;
+
+    #[link(name = "IOKit", kind = "framework")]
+    unsafe extern "C" {
+        fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+        fn IOPSCopyPowerSourcesList(blob: CFTypeRef) -> CFTypeRef;
+        fn IOPSGetPowerSourceDescription(blob: CFTypeRef, source: CFTypeRef) -> CFTypeRef;
+        fn IOPSGetProvidingPowerSourceType(blob: CFTypeRef) -> CFTypeRef;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    unsafe extern "C" {
+        fn CFArrayGetCount(array: CFTypeRef) -> isize;
+        fn CFArrayGetValueAtIndex(array: CFTypeRef, index: isize) -> CFTypeRef;
+        fn CFDictionaryGetValue(dictionary: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+        fn CFNumberGetValue(number: CFTypeRef, kind: c_int, value: *mut c_void) -> bool;
+        fn CFStringCreateWithCString(
+            allocator: CFTypeRef,
+            value: *const c_char,
+            encoding: u32,
+        ) -> CFTypeRef;
+        fn CFStringGetCString(
+            string: CFTypeRef,
+            buffer: *mut c_char,
+            size: isize,
+            encoding: u32,
+        ) -> bool;
+        fn CFRelease(value: CFTypeRef);
+    }
+
+    fn string_value(string: CFTypeRef) -> Option<String> {
+        if string.is_null() {
+            return None;
+        }
+        let mut buffer = [0 as c_char; 64];
+        let ok = unsafe {
+            CFStringGetCString(
+                string,
+                buffer.as_mut_ptr(),
+                buffer.len() as isize,
+                CF_STRING_ENCODING_UTF8,
+            )
+        };
+        ok.then(|| {
+            unsafe { std::ffi::CStr::from_ptr(buffer.as_ptr()) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+
+    fn number_for(dictionary: CFTypeRef, key: &std::ffi::CStr) -> Option<i64> {
+        let key = unsafe {
+            CFStringCreateWithCString(std::ptr::null(), key.as_ptr(), CF_STRING_ENCODING_UTF8)
+        };
+        if key.is_null() {
+            return None;
+        }
+        let value = unsafe { CFDictionaryGetValue(dictionary, key) };
+        unsafe { CFRelease(key) };
+        if value.is_null() {
+            return None;
+        }
+        let mut number = 0_i64;
+        unsafe {
+            CFNumberGetValue(
+                value,
+                CF_NUMBER_SINT64_TYPE,
+                &mut number as *mut i64 as *mut c_void,
+            )
+        }
+        .then_some(number)
+    }
+
+    pub(super) fn read() -> PowerStatus {
+        let blob = unsafe { IOPSCopyPowerSourcesInfo() };
+        if blob.is_null() {
+            return PowerStatus::default();
+        }
+        let providing = string_value(unsafe { IOPSGetProvidingPowerSourceType(blob) });
+        let mut battery_percent = None;
+        let sources = unsafe { IOPSCopyPowerSourcesList(blob) };
+        if !sources.is_null() {
+            for index in 0..unsafe { CFArrayGetCount(sources) } {
+                let description = unsafe {
+                    IOPSGetPowerSourceDescription(blob, CFArrayGetValueAtIndex(sources, index))
+                };
+                if description.is_null() {
+                    continue;
+                }
+                if let (Some(current), Some(max)) = (
+                    number_for(description, c"Current Capacity"),
+                    number_for(description, c"Max Capacity"),
+                ) {
+                    battery_percent = percent_of(current, max);
+                    break;
+                }
+            }
+            unsafe { CFRelease(sources) };
+        }
+        unsafe { CFRelease(blob) };
+
+        let source = match (providing.as_deref(), battery_percent) {
+            (_, None) => PowerSource::Unknown,
+            (Some("Battery Power"), _) => PowerSource::Battery,
+            (Some(_), _) => PowerSource::Ac,
+            (None, _) => PowerSource::Unknown,
+        };
+        PowerStatus {
+            source,
+            battery_percent,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{percent_of, PowerSource, PowerStatus};
+    use std::path::Path;
+
+    fn read_trimmed(path: &Path) -> Option<String> {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|value| value.trim().to_string())
+    }
+
+    pub(super) fn read() -> PowerStatus {
+        let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+            return PowerStatus::default();
+        };
+        let mut battery = None;
+        let mut adapter_online = None;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match read_trimmed(&path.join("type")).as_deref() {
+                Some("Battery") if battery.is_none() => {
+                    let capacity = read_trimmed(&path.join("capacity"))
+                        .and_then(|value| value.parse::<i64>().ok());
+                    let status = read_trimmed(&path.join("status"));
+                    battery = Some((capacity.and_then(|value| percent_of(value, 100)), status));
+                }
+                Some("Mains") => {
+                    let online = read_trimmed(&path.join("online")).as_deref() == Some("1");
+                    adapter_online = Some(adapter_online.unwrap_or(false) || online);
+                }
+                _ => {}
+            }
+        }
+        let Some((battery_percent, status)) = battery else {
+            return PowerStatus::default();
+        };
+        let on_battery = match adapter_online {
+            Some(online) => !online,
+            None => status.as_deref() == Some("Discharging"),
+        };
+        PowerStatus {
+            source: if on_battery {
+                PowerSource::Battery
+            } else {
+                PowerSource::Ac
+            },
+            battery_percent,
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+mod platform {
+    use super::PowerStatus;
+
+    pub(super) fn read() -> PowerStatus {
+        PowerStatus::default()
+    }
+}
+
+pub fn current() -> PowerStatus {
+    platform::read()
+}
+
+fn poll(app: &tauri::AppHandle) {
+    let status = current();
+    let previous = LAST.lock_or_recover().replace(status);
+    if previous == Some(status) {
+        return;
+    }
+    if previous.map(|previous| previous.source) != Some(status.source) {
+        tracing::info!(target: "system", source = ?status.source, "power source changed");
+    }
+    let _ = app.emit("power-status-changed", status);
+}
+
+/// Start the power source poller. Call once from setup.
+pub(crate) fn start(app: &tauri::AppHandle) {
+    let handle = app.clone();
+    if let Err(error) = std::thread::Builder::new()
+        .name("power-monitor".to_string())
+        .spawn(move || loop {
+            poll(&handle);
+            std::thread::sleep(POLL_INTERVAL);
+        })
+    {
+        tracing::warn!(target: "system", "power monitor failed to start: {}", error);
+    }
+}
+
+#[tauri::command]
+pub fn get_power_status() -> PowerStatus {
+    current()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charge_is_a_clamped_whole_percentage() {
+        assert_eq!(percent_of(50, 100), Some(50));
+        assert_eq!(percent_of(2_000, 4_000), Some(50));
+        assert_eq!(percent_of(120, 100), Some(100));
+        assert_eq!(percent_of(-5, 100), Some(0));
+        assert_eq!(percent_of(10, 0), None);
+    }
+
+    #[test]
+    fn status_serializes_for_the_frontend() {
+        let status = PowerStatus {
+            source: PowerSource::Battery,
+            battery_percent: Some(42),
+        };
+        assert_eq!(
+            serde_json::to_value(status).unwrap(),
+            serde_json::json!({ "source": "battery", "batteryPercent": 42 })
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Pre-roll: keep the last second or two of microphone audio so a recording
+//! can begin a beat before the hotkey.
+//!
+//! Opt-in (`preRollSeconds`). While on, a listening capture
+//! (`audio::start_listening`) feeds 100 ms frames at 16 kHz into a ring held
+//! only in memory; nothing is written to disk or transcribed from it.
+//! `audio::start_recording` takes the ring and the recording is prepended
+//! with it when it stops. Taking empties the ring, and so does the end of a
+//! recording, so the tail of one dictation never leads the next. The
+//! microphone kill switch stops it.
+
+use crate::audio::{self, ListeningCapture};
+use crate::state::WHISPER_SAMPLE_RATE;
+use crate::MutexExt;
+use std::collections::VecDeque;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// 100 ms at 16 kHz.
+const FRAME_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize / 10;
+/// Longest pre-roll kept.
+const MAX_SECONDS: u32 = 2;
+
+/// The newest `capacity` samples.
+struct Ring {
+    samples: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl Ring {
+    fn new(seconds: u32) -> Self {
+        let capacity = (seconds * WHISPER_SAMPLE_RATE) as usize;
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn push(&mut self, frame: &[f32]) {
+        self.samples.extend(frame);
+        let excess = self.samples.len().saturating_sub(self.capacity);
+        self.samples.drain(..excess);
+    }
+
+    fn take(&mut self) -> Vec<f32> {
+        self.samples.drain(..).collect()
+    }
+}
+
+/// The running capture and the thread that fills the ring from it.
+struct PreRoll {
+    capture: ListeningCapture,
+    filler: JoinHandle<()>,
+    ring: Arc<Mutex<Ring>>,
+}
+
+static PRE_ROLL: Mutex<Option<PreRoll>> = Mutex::new(None);
+
+fn run_filler(frames: Receiver<Vec<f32>>, ring: &Mutex<Ring>) {
+    while let Ok(frame) = frames.recv() {
+        ring.lock_or_recover().push(&frame);
+    }
+}
+
+/// Keep the last `seconds` (1–2) of `device_name` (None = system default) in
+/// memory, replacing a pre-roll that is already running.
+#[tauri::command]
+pub async fn start_pre_roll(device_name: Option<String>, seconds: u32) -> Result<(), String> {
+    stop();
+    let seconds = seconds.clamp(1, MAX_SECONDS);
+    let (frame_tx, frame_rx) = channel::<Vec<f32>>();
+    let capture = audio::start_listening(device_name, FRAME_SAMPLES, frame_tx)?;
+    let ring = Arc::new(Mutex::new(Ring::new(seconds)));
+    let filler_ring = Arc::clone(&ring);
+    let filler = thread::spawn(move || run_filler(frame_rx, &filler_ring));
+    *PRE_ROLL.lock_or_recover() = Some(PreRoll {
+        capture,
+        filler,
+        ring,
+    });
+    tracing::info!(target: "audio", seconds, "pre-roll started");
+    Ok(())
+}
+
+/// Close the capture and drop whatever the ring held.
+pub(crate) fn stop() {
+    let Some(pre_roll) = PRE_ROLL.lock_or_recover().take() else {
+        return;
+    };
+    // Closing the capture drops the frame sender, which ends the filler.
+    pre_roll.capture.stop();
+    let _ = pre_roll.filler.join();
+    tracing::info!(target: "audio", "pre-roll stopped");
+}
+
+#[tauri::command]
+pub async fn stop_pre_roll() {
+    stop();
+}
+
+pub fn is_listening() -> bool {
+    PRE_ROLL
+        .lock_or_recover()
+        .as_ref()
+        .is_some_and(|pre_roll| !pre_roll.filler.is_finished())
+}
+
+/// The audio heard just before now, at 16 kHz, emptying the ring. Empty when
+/// pre-roll is off.
+pub(crate) fn take() -> Vec<f32> {
+    PRE_ROLL
+        .lock_or_recover()
+        .as_ref()
+        .map(|pre_roll| pre_roll.ring.lock_or_recover().take())
+        .unwrap_or_default()
+}
+
+/// Forget what the ring holds. Called when a recording ends.
+pub(crate) fn clear() {
+    if let Some(pre_roll) = PRE_ROLL.lock_or_recover().as_ref() {
+        pre_roll.ring.lock_or_recover().take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_ring_keeps_only_the_newest_samples() {
+        let mut ring = Ring::new(1);
+        ring.push(&vec![0.1; 10_000]);
+        ring.push(&vec![0.2; 10_000]);
+        let samples = ring.take();
+        assert_eq!(samples.len(), 16_000);
+        assert_eq!(samples[0], 0.1);
+        assert_eq!(samples[6_000], 0.2);
+        assert_eq!(samples.last(), Some(&0.2));
+    }
+
+    #[test]
+    fn taking_empties_the_ring() {
+        let mut ring = Ring::new(2);
+        ring.push(&[0.5; 1_600]);
+        assert_eq!(ring.take().len(), 1_600);
+        assert!(ring.take().is_empty());
+    }
+}
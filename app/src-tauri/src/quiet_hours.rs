@@ -0,0 +1,179 @@
+//! Quiet hours: a daily window (e.g. 22:00–08:00) when the dictation hotkeys
+//! and the wake word are ignored, so a stray key press at night doesn't start
+//! a recording.
+//!
+//! The schedule comes from the frontend via `set_quiet_hours`; a scheduler
+//! thread re-evaluates it against the local clock and gates the keyboard
+//! listener through `keyboard::set_quiet_hours_gated`, which is kept separate
+//! from the user's own "Disable Murmur" switch. Recordings started from the
+//! window or the overlay still work.
+
+use crate::MutexExt;
+use chrono::{Local, NaiveTime, Timelike};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Emitter;
+
+/// How often the scheduler compares the clock with the window. A boundary is
+/// crossed at most this late.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A daily window in minutes after local midnight. `start > end` spans
+/// midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Window {
+    start: u32,
+    end: u32,
+}
+
+impl Window {
+    fn parse(start: &str, end: &str) -> Result<Self, String> {
+        let (Some(start), Some(end)) = (parse_clock(start), parse_clock(end)) else {
+            return Err("Quiet hours need a start and end time like 22:00.".to_string());
+        };
+        if start == end {
+            return Err("Quiet hours can't start and end at the same time.".to_string());
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Whether `minute` (after midnight) falls in the window. The start is
+    /// inclusive and the end exclusive.
+    fn contains(&self, minute: u32) -> bool {
+        if self.start < self.end {
+            minute >= self.start && minute < self.end
+        } else {
+            minute >= self.start || minute < self.end
+        }
+    }
+}
+
+/// Parse `HH:MM` (24-hour) into minutes after midnight.
+fn parse_clock(value: &str) -> Option<u32> {
+    let (hour, minute) = value.trim().split_once(':')?;
+    let time = NaiveTime::from_hms_opt(hour.parse().ok()?, minute.parse().ok()?, 0)?;
+    Some(time.hour() * 60 + time.minute())
+}
+
+static SCHEDULE: Mutex<Option<Window>> = Mutex::new(None);
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Payload of `quiet-hours-changed` and the quiet-hours commands.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHoursStatus {
+    pub enabled: bool,
+    /// The window is in effect right now.
+    pub active: bool,
+}
+
+fn status() -> QuietHoursStatus {
+    QuietHoursStatus {
+        enabled: SCHEDULE.lock_or_recover().is_some(),
+        active: ACTIVE.load(Ordering::SeqCst),
+    }
+}
+
+fn minute_of_day() -> u32 {
+    let now = Local::now();
+    now.hour() * 60 + now.minute()
+}
+
+/// Gate or release the hotkeys when the window opens or closes.
+fn evaluate(app: &tauri::AppHandle) {
+    let active = SCHEDULE
+        .lock_or_recover()
+        .is_some_and(|window| window.contains(minute_of_day()));
+    if ACTIVE.swap(active, Ordering::SeqCst) == active {
+        return;
+    }
+    crate::keyboard::set_quiet_hours_gated(active);
+    tracing::info!(target: "system", active, "quiet hours changed");
+    let _ = app.emit("quiet-hours-changed", status());
+}
+
+/// Start the quiet-hours scheduler. Call once from setup.
+pub(crate) fn start(app: &tauri::AppHandle) {
+    let handle = app.clone();
+    if let Err(error) = std::thread::Builder::new()
+        .name("quiet-hours".to_string())
+        .spawn(move || loop {
+            evaluate(&handle);
+            std::thread::sleep(CHECK_INTERVAL);
+        })
+    {
+        tracing::warn!(target: "system", "quiet hours scheduler failed to start: {}", error);
+    }
+}
+
+/// Whether quiet hours are in effect right now.
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Set the quiet-hours window and apply it immediately. `start` and `end` are
+/// `HH:MM`; they are ignored when `enabled` is false.
+#[tauri::command]
+pub fn set_quiet_hours(
+    app: tauri::AppHandle,
+    enabled: bool,
+    start: String,
+    end: String,
+) -> Result<QuietHoursStatus, String> {
+    let window = if enabled {
+        Some(Window::parse(&start, &end)?)
+    } else {
+        None
+    };
+    *SCHEDULE.lock_or_recover() = window;
+    tracing::info!(target: "system", enabled, start = %start, end = %end, "set_quiet_hours");
+    evaluate(&app);
+    Ok(status())
+}
+
+#[tauri::command]
+pub fn get_quiet_hours_status() -> QuietHoursStatus {
+    status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(clock: &str) -> u32 {
+        parse_clock(clock).unwrap()
+    }
+
+    #[test]
+    fn overnight_window_spans_midnight() {
+        let window = Window::parse("22:00", "08:00").unwrap();
+        assert!(window.contains(at("22:00")));
+        assert!(window.contains(at("23:59")));
+        assert!(window.contains(at("00:00")));
+        assert!(window.contains(at("07:59")));
+        assert!(!window.contains(at("08:00")));
+        assert!(!window.contains(at("12:00")));
+        assert!(!window.contains(at("21:59")));
+    }
+
+    #[test]
+    fn same_day_window_excludes_its_end() {
+        let window = Window::parse("13:00", "14:30").unwrap();
+        assert!(window.contains(at("13:00")));
+        assert!(window.contains(at("14:29")));
+        assert!(!window.contains(at("14:30")));
+        assert!(!window.contains(at("12:59")));
+    }
+
+    #[test]
+    fn rejects_malformed_and_empty_windows() {
+        assert!(Window::parse("24:00", "08:00").is_err());
+        assert!(Window::parse("22:60", "08:00").is_err());
+        assert!(Window::parse("10pm", "08:00").is_err());
+        assert!(Window::parse("", "08:00").is_err());
+        assert!(Window::parse("08:00", "08:00").is_err());
+        assert_eq!(parse_clock(" 7:05 "), Some(7 * 60 + 5));
+    }
+}
@@ -0,0 +1,263 @@
+//! Crash-safe journal of the recording in progress.
+//!
+//! While recording, the capture thread appends new samples to a 16-bit WAV in
+//! the app data directory about once a second and rewrites its header, so the
+//! file is always a valid WAV of everything captured so far. A normal stop or
+//! cancel removes it. If Murmur dies mid-recording the file survives: the next
+//! launch moves it aside as an orphan (so a new recording can't overwrite it),
+//! announces it with `orphaned-recording-found`, and lets the user transcribe
+//! or discard it. The journal is raw microphone audio — it never leaves the
+//! app data directory and its path is never logged or sent to the frontend.
+
+use crate::State;
+use serde::Serialize;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// How often the capture thread appends to the journal. Bounds what a crash
+/// can lose.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+const JOURNAL_FILE: &str = "recording-journal.wav";
+pub(crate) const ORPHAN_FILE: &str = "recording-orphan.wav";
+
+static JOURNAL_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// An unfinished recording left behind by a crash.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedRecording {
+    pub duration_secs: f64,
+    /// When the journal was last written, in Unix milliseconds.
+    pub recorded_at_ms: u64,
+}
+
+/// Appends captured samples to the journal WAV.
+pub struct JournalWriter {
+    writer: hound::WavWriter<BufWriter<File>>,
+    written: usize,
+}
+
+impl JournalWriter {
+    fn create_in(dir: &Path, sample_rate: u32) -> Result<Self, String> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create journal directory: {}", e))?;
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let writer = hound::WavWriter::create(dir.join(JOURNAL_FILE), spec)
+            .map_err(|e| format!("Failed to create journal: {}", e))?;
+        Ok(Self { writer, written: 0 })
+    }
+
+    /// Start a journal for a recording at `sample_rate`. `None` when the
+    /// journal isn't set up or can't be created; recording goes on without it.
+    pub fn create(sample_rate: u32) -> Option<Self> {
+        let dir = JOURNAL_DIR.get()?;
+        match Self::create_in(dir, sample_rate) {
+            Ok(writer) => Some(writer),
+            Err(e) => {
+                tracing::warn!(target: "audio", "recording journal disabled: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Number of samples already journaled.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Append `samples` and rewrite the header so the file stays valid.
+    pub fn append(&mut self, samples: &[f32]) -> Result<(), String> {
+        if samples.is_empty() {
+            return Ok(());
+        }
+        for &s in samples {
+            let value = (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            self.writer
+                .write_sample(value)
+                .map_err(|e| format!("Failed to write journal: {}", e))?;
+        }
+        self.writer
+            .flush()
+            .map_err(|e| format!("Failed to flush journal: {}", e))?;
+        self.written += samples.len();
+        Ok(())
+    }
+}
+
+fn remove_in(dir: &Path, name: &str) {
+    let path = dir.join(name);
+    if let Err(e) = std::fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!(target: "audio", "failed to remove recording journal: {}", e);
+        }
+    }
+}
+
+/// Move a journal left by a crashed session aside as the orphan, replacing
+/// any older orphan. An empty or unreadable journal is dropped.
+fn adopt_orphan_in(dir: &Path) -> Option<OrphanedRecording> {
+    let journal = dir.join(JOURNAL_FILE);
+    if journal.exists() {
+        if let Err(e) = std::fs::rename(&journal, dir.join(ORPHAN_FILE)) {
+            tracing::warn!(target: "audio", "failed to adopt recording journal: {}", e);
+            remove_in(dir, JOURNAL_FILE);
+        }
+    }
+    orphan_in(dir)
+}
+
+fn orphan_in(dir: &Path) -> Option<OrphanedRecording> {
+    let path = dir.join(ORPHAN_FILE);
+    if !path.exists() {
+        return None;
+    }
+    let duration_secs = hound::WavReader::open(&path)
+        .ok()
+        .filter(|reader| reader.len() > 0 && reader.spec().sample_rate > 0)
+        .map(|reader| reader.len() as f64 / reader.spec().sample_rate as f64);
+    let Some(duration_secs) = duration_secs else {
+        remove_in(dir, ORPHAN_FILE);
+        return None;
+    };
+    let recorded_at_ms = std::fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0);
+    Some(OrphanedRecording {
+        duration_secs,
+        recorded_at_ms,
+    })
+}
+
+/// Set the journal directory and adopt any journal a crash left behind.
+/// Call once from setup, before the first recording can start.
+pub fn init(dir: PathBuf) -> Option<OrphanedRecording> {
+    let orphan = adopt_orphan_in(&dir);
+    if let Some(orphan) = &orphan {
+        tracing::info!(
+            target: "audio",
+            duration_secs = orphan.duration_secs,
+            "found recording journal from a previous session"
+        );
+    }
+    let _ = JOURNAL_DIR.set(dir);
+    orphan
+}
+
+/// Remove the journal of the recording that just stopped.
+pub fn remove() {
+    if let Some(dir) = JOURNAL_DIR.get() {
+        remove_in(dir, JOURNAL_FILE);
+    }
+}
+
+fn orphan_path() -> Result<PathBuf, String> {
+    let dir = JOURNAL_DIR
+        .get()
+        .ok_or_else(|| "Recording journal is not available.".to_string())?;
+    if orphan_in(dir).is_none() {
+        return Err("No unfinished recording to recover.".to_string());
+    }
+    Ok(dir.join(ORPHAN_FILE))
+}
+
+/// Path of the unfinished recording, if one is waiting.
+pub(crate) fn orphan_file() -> Option<PathBuf> {
+    orphan_path().ok()
+}
+
+/// The unfinished recording from a crashed session, if one is waiting.
+#[tauri::command]
+pub fn get_orphaned_recording() -> Option<OrphanedRecording> {
+    orphan_in(JOURNAL_DIR.get()?)
+}
+
+/// Delete the unfinished recording without transcribing it.
+#[tauri::command]
+pub fn discard_orphaned_recording() {
+    if let Some(dir) = JOURNAL_DIR.get() {
+        remove_in(dir, ORPHAN_FILE);
+        tracing::info!(target: "audio", "orphaned recording discarded");
+    }
+}
+
+/// Transcribe the unfinished recording the same way as a dropped file, then
+/// delete it. On failure the recording is kept so the user can retry.
+#[tauri::command]
+pub async fn transcribe_orphaned_recording(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, State>,
+) -> Result<crate::commands::payloads::TranscriptionResult, String> {
+    let path = orphan_path()?;
+    let path = path
+        .to_str()
+        .ok_or_else(|| "Recording journal path is not valid UTF-8.".to_string())?
+        .to_string();
+    let result = crate::commands::recording::transcribe_file(app_handle, state, path).await?;
+    discard_orphaned_recording();
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_is_a_valid_wav_after_every_append() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut journal = JournalWriter::create_in(dir.path(), 16_000).unwrap();
+        journal.append(&[0.5; 8_000]).unwrap();
+        journal.append(&[]).unwrap();
+        journal.append(&[-0.5; 8_000]).unwrap();
+        assert_eq!(journal.written(), 16_000);
+
+        // Read while the writer is still open, as after a crash.
+        let reader = hound::WavReader::open(dir.path().join(JOURNAL_FILE)).unwrap();
+        assert_eq!(reader.spec().sample_rate, 16_000);
+        assert_eq!(reader.len(), 16_000);
+    }
+
+    #[test]
+    fn leftover_journal_is_adopted_as_an_orphan_once() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut journal = JournalWriter::create_in(dir.path(), 48_000).unwrap();
+            journal.append(&[0.1; 24_000]).unwrap();
+        }
+
+        let orphan = adopt_orphan_in(dir.path()).unwrap();
+        assert_eq!(orphan.duration_secs, 0.5);
+        assert!(orphan.recorded_at_ms > 0);
+        assert!(!dir.path().join(JOURNAL_FILE).exists());
+
+        // A new recording's journal no longer touches the orphan.
+        JournalWriter::create_in(dir.path(), 48_000).unwrap();
+        assert_eq!(orphan_in(dir.path()), Some(orphan));
+
+        remove_in(dir.path(), ORPHAN_FILE);
+        assert_eq!(orphan_in(dir.path()), None);
+    }
+
+    #[test]
+    fn empty_or_corrupt_journals_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        JournalWriter::create_in(dir.path(), 16_000).unwrap();
+        assert_eq!(adopt_orphan_in(dir.path()), None);
+        assert!(!dir.path().join(ORPHAN_FILE).exists());
+
+        std::fs::write(dir.path().join(JOURNAL_FILE), b"not a wav").unwrap();
+        assert_eq!(adopt_orphan_in(dir.path()), None);
+        assert!(!dir.path().join(ORPHAN_FILE).exists());
+    }
+}
@@ -0,0 +1,238 @@
+//! Optional redaction of configured words and patterns, for users dictating
+//! in shared or recorded environments.
+//!
+//! Runs as the final transcript transform stage, so masked text is what gets
+//! pasted, saved to file, and recorded in history. Word lists match whole
+//! words case-insensitively; user patterns are regular expressions (e.g. card
+//! numbers). Neither the lists nor the matched text are ever logged.
+
+use crate::state::{RedactionMode, RedactionSettings};
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+/// Shipped profanity list, used when `include_profanity` is on.
+pub const DEFAULT_PROFANITY: &[&str] = &[
+    "asshole",
+    "bastard",
+    "bitch",
+    "bullshit",
+    "cunt",
+    "dick",
+    "fuck",
+    "fucked",
+    "fucker",
+    "fucking",
+    "motherfucker",
+    "piss",
+    "shit",
+    "shitty",
+];
+
+/// Compiled-size cap for user patterns, so a pathological expression fails at
+/// configure time instead of bloating every dictation.
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+pub struct Redactor {
+    matchers: Vec<Regex>,
+    mode: RedactionMode,
+}
+
+impl Redactor {
+    /// Compile `settings`. Returns `Ok(None)` when redaction is off or there is
+    /// nothing to match, and a user-facing error naming the first invalid
+    /// pattern by position (never by content).
+    pub fn build(settings: &RedactionSettings) -> Result<Option<Self>, String> {
+        if !settings.enabled {
+            return Ok(None);
+        }
+        let mut words = settings
+            .words
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if settings.include_profanity {
+            words.extend(DEFAULT_PROFANITY.iter().map(|word| word.to_string()));
+        }
+        // Longest first so "fucking" wins over its "fuck" prefix.
+        words.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()).then(a.cmp(b)));
+        words.dedup();
+
+        let mut matchers = Vec::new();
+        if !words.is_empty() {
+            let alternation = words
+                .iter()
+                .map(|word| regex::escape(word))
+                .collect::<Vec<_>>()
+                .join("|");
+            let pattern = format!(r"(?i)\b(?:{})\b", alternation);
+            matchers.push(
+                Regex::new(&pattern)
+                    .map_err(|_| "The redaction word list is too large.".to_string())?,
+            );
+        }
+        for (index, pattern) in settings.patterns.iter().enumerate() {
+            let pattern = pattern.trim();
+            if pattern.is_empty() {
+                continue;
+            }
+            let compiled = RegexBuilder::new(pattern)
+                .size_limit(PATTERN_SIZE_LIMIT)
+                .build()
+                .map_err(|_| {
+                    format!(
+                        "Redaction pattern {} is not a valid regular expression.",
+                        index + 1
+                    )
+                })?;
+            matchers.push(compiled);
+        }
+        if matchers.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            matchers,
+            mode: settings.mode,
+        }))
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let mut ranges: Vec<Range<usize>> = self
+            .matchers
+            .iter()
+            .flat_map(|matcher| matcher.find_iter(text).map(|found| found.range()))
+            .filter(|range| !range.is_empty())
+            .collect();
+        if ranges.is_empty() {
+            return text.to_string();
+        }
+        ranges.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        let mut output = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for range in merged {
+            output.push_str(&text[cursor..range.start]);
+            if self.mode == RedactionMode::Mask {
+                output.extend(text[range.clone()].chars().map(|c| {
+                    if c.is_whitespace() {
+                        c
+                    } else {
+                        '*'
+                    }
+                }));
+            }
+            cursor = range.end;
+        }
+        output.push_str(&text[cursor..]);
+        match self.mode {
+            RedactionMode::Mask => output,
+            RedactionMode::Remove => tidy_removed(&output),
+        }
+    }
+}
+
+/// Collapse the doubled spaces and stranded punctuation spacing that removal
+/// leaves behind ("that  , right" -> "that, right", "great, ." -> "great.").
+fn tidy_removed(text: &str) -> String {
+    let mut tidy = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == ' ' && (tidy.is_empty() || tidy.ends_with(' ')) {
+            continue;
+        }
+        if matches!(c, ',' | '.' | '!' | '?' | ';' | ':') {
+            if tidy.ends_with(' ') {
+                tidy.pop();
+            }
+            // A clause separator left dangling by a removed word gives way to
+            // whatever punctuation follows, and never opens the text.
+            if matches!(c, ',' | ';' | ':') && (tidy.is_empty() || tidy.ends_with([',', ';', ':']))
+            {
+                continue;
+            }
+            if tidy.ends_with([',', ';', ':']) {
+                tidy.pop();
+            }
+        }
+        tidy.push(c);
+    }
+    tidy.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(mode: RedactionMode, words: &[&str], patterns: &[&str]) -> RedactionSettings {
+        RedactionSettings {
+            enabled: true,
+            mode,
+            include_profanity: true,
+            words: words.iter().map(|word| word.to_string()).collect(),
+            patterns: patterns.iter().map(|pattern| pattern.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn masks_whole_words_case_insensitively_and_patterns() {
+        let redactor = Redactor::build(&settings(
+            RedactionMode::Mask,
+            &["Project Falcon"],
+            &[r"\b(?:\d[ -]?){13,16}\b"],
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            redactor.redact("Shit, project falcon slipped. Card 4111 1111 1111 1111 works."),
+            "****, ******* ****** slipped. Card **** **** **** **** works."
+        );
+        // Whole words only: "Dickens" and "passing" are untouched.
+        assert_eq!(
+            redactor.redact("Reading Dickens while passing"),
+            "Reading Dickens while passing"
+        );
+    }
+
+    #[test]
+    fn remove_mode_tidies_spacing_and_punctuation() {
+        let redactor = Redactor::build(&settings(RedactionMode::Remove, &[], &[]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            redactor.redact("That was fucking great, shit."),
+            "That was great."
+        );
+        assert_eq!(
+            redactor.redact("Well shit, the build broke."),
+            "Well, the build broke."
+        );
+        assert_eq!(redactor.redact("Shit, it broke."), "it broke.");
+        assert_eq!(redactor.redact("No profanity here."), "No profanity here.");
+    }
+
+    #[test]
+    fn disabled_or_empty_settings_build_nothing_and_bad_patterns_are_reported_by_position() {
+        let mut disabled = settings(RedactionMode::Mask, &["secret"], &[]);
+        disabled.enabled = false;
+        assert!(Redactor::build(&disabled).unwrap().is_none());
+
+        let mut empty = settings(RedactionMode::Mask, &["  "], &[""]);
+        empty.include_profanity = false;
+        assert!(Redactor::build(&empty).unwrap().is_none());
+
+        let error = Redactor::build(&settings(RedactionMode::Mask, &[], &["ok", "(unclosed"]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            "Redaction pattern 2 is not a valid regular expression."
+        );
+    }
+}
@@ -0,0 +1,538 @@
+//! User find/replace rules applied to every live transcript ("macbook" ->
+//! "MacBook", "slash dash" -> "--").
+//!
+//! Rules run in list order as the transcript transform stage just before
+//! redaction, so each rule sees the output of the ones above it and a rule
+//! can't reintroduce text redaction hides. A literal rule matches its phrase
+//! as whole words; a regex rule is a regular expression whose replacement may
+//! use `$1`-style groups. Both ignore case unless `matchCase` is set. The
+//! frontend owns persistence, like rewrite templates; rule text is never
+//! logged.
+
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Most rules kept; extra entries are dropped on load and import.
+pub const MAX_RULES: usize = 100;
+const MAX_FIND_CHARS: usize = 200;
+const MAX_REPLACE_CHARS: usize = 1_000;
+/// Compiled-size cap per rule, as for redaction patterns.
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+const EXPORT_FORMAT: &str = "murmur-replacement-rules";
+const EXPORT_VERSION: u32 = 1;
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplacementRule {
+    pub id: String,
+    pub find: String,
+    /// May be empty, to delete what `find` matches.
+    #[serde(default)]
+    pub replace: String,
+    /// `find` is a regular expression rather than a literal phrase.
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub match_case: bool,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+impl ReplacementRule {
+    /// Copy with a trimmed id and find, or an error when either is blank, a
+    /// field is over its length limit, or the pattern doesn't compile.
+    fn sanitized(&self) -> Result<Self, String> {
+        let id = self.id.trim();
+        // Literal phrases are matched as trimmed words; a regex keeps its
+        // spaces, which may be meaningful.
+        let find = if self.regex {
+            self.find.as_str()
+        } else {
+            self.find.trim()
+        };
+        if id.is_empty() || find.trim().is_empty() {
+            return Err("A replacement rule needs something to find.".to_string());
+        }
+        if find.chars().count() > MAX_FIND_CHARS || self.replace.chars().count() > MAX_REPLACE_CHARS
+        {
+            return Err(format!(
+                "Keep the find text under {MAX_FIND_CHARS} and the replacement under {MAX_REPLACE_CHARS} characters."
+            ));
+        }
+        let rule = Self {
+            id: id.to_string(),
+            find: find.to_string(),
+            ..self.clone()
+        };
+        rule.compile()?;
+        Ok(rule)
+    }
+
+    fn compile(&self) -> Result<Regex, String> {
+        let pattern = if self.regex {
+            self.find.clone()
+        } else {
+            // Word boundaries only where the phrase starts or ends with a
+            // word character, so "slash dash" can't match inside "backslash"
+            // but a symbol phrase like "->" still matches between words.
+            let escaped = self
+                .find
+                .split_whitespace()
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(r"\s+");
+            let word_char = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            format!(
+                "{}{}{}",
+                if word_char(self.find.chars().next()) {
+                    r"\b"
+                } else {
+                    ""
+                },
+                escaped,
+                if word_char(self.find.chars().last()) {
+                    r"\b"
+                } else {
+                    ""
+                }
+            )
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.match_case)
+            .size_limit(PATTERN_SIZE_LIMIT)
+            .build()
+            .map_err(|_| "The rule's pattern is not a valid regular expression.".to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleExport {
+    format: String,
+    version: u32,
+    rules: Vec<serde_json::Value>,
+}
+
+/// Parse the `replacementRules` array from `configure_dictation`. Invalid
+/// entries, including patterns that don't compile, are skipped; the first
+/// rule wins an id clash.
+pub fn parse_rules(values: &[serde_json::Value]) -> Vec<ReplacementRule> {
+    let mut rules: Vec<ReplacementRule> = Vec::new();
+    for rule in values
+        .iter()
+        .filter_map(|value| serde_json::from_value::<ReplacementRule>(value.clone()).ok())
+        .filter_map(|rule| rule.sanitized().ok())
+    {
+        if rules.len() == MAX_RULES {
+            break;
+        }
+        if !rules.iter().any(|existing| existing.id == rule.id) {
+            rules.push(rule);
+        }
+    }
+    rules
+}
+
+/// Replace the rule with `rule.id` in place, or append it. Returns the stored
+/// rule.
+pub fn upsert(
+    rules: &mut Vec<ReplacementRule>,
+    rule: &ReplacementRule,
+) -> Result<ReplacementRule, String> {
+    let rule = rule.sanitized()?;
+    match rules.iter().position(|existing| existing.id == rule.id) {
+        Some(index) => rules[index] = rule.clone(),
+        None if rules.len() == MAX_RULES => {
+            return Err(format!("Keep at most {MAX_RULES} replacement rules."));
+        }
+        None => rules.push(rule.clone()),
+    }
+    Ok(rule)
+}
+
+/// Remove the rule with `id`. Returns whether one was removed.
+pub fn remove(rules: &mut Vec<ReplacementRule>, id: &str) -> bool {
+    let before = rules.len();
+    rules.retain(|rule| rule.id != id.trim());
+    rules.len() != before
+}
+
+/// Put the rules named in `ids` first, in that order; rules not named keep
+/// their relative order after them. Unknown ids are ignored.
+pub fn reorder(rules: &mut Vec<ReplacementRule>, ids: &[String]) {
+    let mut remaining = std::mem::take(rules);
+    for id in ids {
+        if let Some(index) = remaining.iter().position(|rule| &rule.id == id) {
+            rules.push(remaining.remove(index));
+        }
+    }
+    rules.append(&mut remaining);
+}
+
+/// The enabled rules, compiled in order.
+pub struct ReplacementRules {
+    rules: Vec<CompiledRule>,
+}
+
+struct CompiledRule {
+    id: String,
+    matcher: Regex,
+    replace: String,
+    expand: bool,
+}
+
+/// How many times one rule matched during [`ReplacementRules::apply_traced`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleMatch {
+    pub id: String,
+    pub matches: usize,
+}
+
+impl ReplacementRules {
+    /// Compile the enabled rules. Returns `None` when none are enabled.
+    /// Rules were validated on the way in, so one that still fails to
+    /// compile is skipped.
+    pub fn build(rules: &[ReplacementRule]) -> Option<Self> {
+        let rules: Vec<CompiledRule> = rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| {
+                Some(CompiledRule {
+                    id: rule.id.clone(),
+                    matcher: rule.compile().ok()?,
+                    replace: rule.replace.clone(),
+                    expand: rule.regex,
+                })
+            })
+            .collect();
+        (!rules.is_empty()).then_some(Self { rules })
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        self.apply_traced(text).0
+    }
+
+    /// Apply every rule in order, counting each rule's matches against the
+    /// text the earlier rules produced.
+    pub fn apply_traced(&self, text: &str) -> (String, Vec<RuleMatch>) {
+        let mut text = text.to_string();
+        let mut trace = Vec::with_capacity(self.rules.len());
+        for rule in &self.rules {
+            let matches = rule.matcher.find_iter(&text).count();
+            if matches > 0 {
+                text = if rule.expand {
+                    rule.matcher.replace_all(&text, rule.replace.as_str())
+                } else {
+                    rule.matcher.replace_all(&text, NoExpand(&rule.replace))
+                }
+                .into_owned();
+            }
+            trace.push(RuleMatch {
+                id: rule.id.clone(),
+                matches,
+            });
+        }
+        (text, trace)
+    }
+}
+
+/// Result of a dry run: what the rules would make of `source`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplacementPreview {
+    pub source: String,
+    pub text: String,
+    /// One entry per enabled rule, in the order they ran.
+    pub matches: Vec<RuleMatch>,
+}
+
+/// Run `rules` over `source` without touching the live list. Unlike loading,
+/// an invalid rule is an error naming its position, so an editor can point
+/// at it before it is saved.
+pub fn preview(rules: &[ReplacementRule], source: &str) -> Result<ReplacementPreview, String> {
+    let mut checked = Vec::with_capacity(rules.len());
+    for (index, rule) in rules.iter().enumerate() {
+        checked.push(
+            rule.sanitized()
+                .map_err(|error| format!("Rule {}: {error}", index + 1))?,
+        );
+    }
+    let (text, matches) = ReplacementRules::build(&checked).map_or_else(
+        || (source.to_string(), Vec::new()),
+        |compiled| compiled.apply_traced(source),
+    );
+    Ok(ReplacementPreview {
+        source: source.to_string(),
+        text,
+        matches,
+    })
+}
+
+/// Write `rules` to `path` as a versioned JSON bundle. Returns the count.
+pub fn export_to_file(path: &Path, rules: &[ReplacementRule]) -> Result<usize, String> {
+    let bundle = RuleExport {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        rules: rules
+            .iter()
+            .filter_map(|rule| serde_json::to_value(rule).ok())
+            .collect(),
+    };
+    let bytes = serde_json::to_vec_pretty(&bundle)
+        .map_err(|_| "Replacement rules could not be serialized.".to_string())?;
+    std::fs::write(path, bytes)
+        .map_err(|e| format!("Couldn't write the replacement rule file: {e}"))?;
+    Ok(rules.len())
+}
+
+/// Read the rules from an export bundle, or from a bare rule array.
+pub fn read_import(path: &Path) -> Result<Vec<ReplacementRule>, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Couldn't read the replacement rule file: {e}"))?;
+    let value: serde_json::Value = serde_json::from_slice(&bytes)
+        .map_err(|_| "The file is not a replacement rule export.".to_string())?;
+    let values = match value {
+        serde_json::Value::Array(values) => values,
+        value => {
+            let bundle: RuleExport = serde_json::from_value(value)
+                .map_err(|_| "The file is not a replacement rule export.".to_string())?;
+            if bundle.format != EXPORT_FORMAT || bundle.version > EXPORT_VERSION {
+                return Err("The file is not a replacement rule export.".to_string());
+            }
+            bundle.rules
+        }
+    };
+    Ok(parse_rules(&values))
+}
+
+/// Upsert each imported rule into `rules`: a rule with a known id is replaced
+/// where it stands, new ones are appended in file order. Returns how many
+/// were added or replaced; imports past `MAX_RULES` are skipped.
+pub fn merge_import(rules: &mut Vec<ReplacementRule>, imported: &[ReplacementRule]) -> usize {
+    imported
+        .iter()
+        .filter(|rule| upsert(rules, rule).is_ok())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(id: &str, find: &str, replace: &str) -> ReplacementRule {
+        ReplacementRule {
+            id: id.to_string(),
+            find: find.to_string(),
+            replace: replace.to_string(),
+            regex: false,
+            match_case: false,
+            enabled: true,
+        }
+    }
+
+    fn regex(id: &str, find: &str, replace: &str) -> ReplacementRule {
+        ReplacementRule {
+            regex: true,
+            ..literal(id, find, replace)
+        }
+    }
+
+    fn apply(rules: &[ReplacementRule], text: &str) -> String {
+        ReplacementRules::build(rules)
+            .map_or_else(|| text.to_string(), |compiled| compiled.apply(text))
+    }
+
+    #[test]
+    fn literals_match_whole_words_ignoring_case() {
+        let rules = [
+            literal("mac", "macbook", "MacBook"),
+            literal("dash", "slash  dash", "--"),
+        ];
+        assert_eq!(
+            apply(
+                &rules,
+                "My Macbook runs cargo test slash dash nocapture, not macbooks."
+            ),
+            "My MacBook runs cargo test -- nocapture, not macbooks."
+        );
+        // "$1" in a literal replacement is text, not a group.
+        assert_eq!(
+            apply(&[literal("price", "five bucks", "$1 x5")], "five bucks"),
+            "$1 x5"
+        );
+    }
+
+    #[test]
+    fn regex_rules_expand_groups_and_respect_match_case() {
+        let rules = [
+            regex("ticket", r"ticket (\d+)", "#$1"),
+            ReplacementRule {
+                match_case: true,
+                ..literal("ok", "OK", "okay")
+            },
+        ];
+        assert_eq!(
+            apply(&rules, "Ticket 42 is OK but ok is not"),
+            "#42 is okay but ok is not"
+        );
+    }
+
+    #[test]
+    fn rules_apply_in_order_to_the_previous_rules_output() {
+        let first = literal("one", "colour", "color");
+        let second = literal("two", "color", "hue");
+        assert_eq!(
+            apply(&[first.clone(), second.clone()], "colour color"),
+            "hue hue"
+        );
+        assert_eq!(
+            apply(&[second.clone(), first.clone()], "colour color"),
+            "color hue"
+        );
+
+        let compiled = ReplacementRules::build(&[first, second]).unwrap();
+        let (text, trace) = compiled.apply_traced("colour color");
+        assert_eq!(text, "hue hue");
+        assert_eq!(
+            trace,
+            vec![
+                RuleMatch {
+                    id: "one".to_string(),
+                    matches: 1
+                },
+                RuleMatch {
+                    id: "two".to_string(),
+                    matches: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled_rules_are_skipped_and_none_enabled_builds_nothing() {
+        let off = ReplacementRule {
+            enabled: false,
+            ..literal("off", "macbook", "MacBook")
+        };
+        assert!(ReplacementRules::build(std::slice::from_ref(&off)).is_none());
+        assert_eq!(
+            apply(&[off, literal("on", "iphone", "iPhone")], "macbook iphone"),
+            "macbook iPhone"
+        );
+    }
+
+    #[test]
+    fn preview_reports_matches_and_names_an_invalid_rule() {
+        let dry_run = preview(
+            &[
+                literal("mac", "macbook", "MacBook"),
+                ReplacementRule {
+                    enabled: false,
+                    ..literal("off", "MacBook", "laptop")
+                },
+            ],
+            "macbook and MACBOOK",
+        )
+        .unwrap();
+        assert_eq!(dry_run.source, "macbook and MACBOOK");
+        assert_eq!(dry_run.text, "MacBook and MacBook");
+        assert_eq!(
+            dry_run.matches,
+            vec![RuleMatch {
+                id: "mac".to_string(),
+                matches: 2
+            }]
+        );
+
+        let error = preview(
+            &[literal("mac", "macbook", "MacBook"), regex("bad", "[", "x")],
+            "text",
+        )
+        .unwrap_err();
+        assert!(error.starts_with("Rule 2:"));
+    }
+
+    #[test]
+    fn parse_and_upsert_reject_blank_and_invalid_rules() {
+        let values = vec![
+            serde_json::json!({"id": "mac", "find": " macbook ", "replace": "MacBook"}),
+            serde_json::json!({"id": "bad", "find": "(unclosed", "replace": "x", "regex": true}),
+            serde_json::json!({"id": "blank", "find": "  ", "replace": "x"}),
+            serde_json::json!({"id": "mac", "find": "other", "replace": "x"}),
+            serde_json::json!({"find": "no id", "replace": "x"}),
+        ];
+        let rules = parse_rules(&values);
+        assert_eq!(rules, vec![literal("mac", "macbook", "MacBook")]);
+
+        let mut rules = rules;
+        assert!(upsert(&mut rules, &regex("bad", "(unclosed", "x")).is_err());
+        upsert(&mut rules, &literal("mac", "mac book", "MacBook")).unwrap();
+        upsert(&mut rules, &literal("dash", "slash dash", "--")).unwrap();
+        assert_eq!(rules[0].find, "mac book");
+        assert_eq!(rules.len(), 2);
+        assert!(remove(&mut rules, "mac"));
+        assert!(!remove(&mut rules, "mac"));
+    }
+
+    #[test]
+    fn reorder_moves_named_rules_first_and_keeps_the_rest() {
+        let mut rules = vec![
+            literal("a", "a", "1"),
+            literal("b", "b", "2"),
+            literal("c", "c", "3"),
+        ];
+        reorder(
+            &mut rules,
+            &["c".to_string(), "missing".to_string(), "a".to_string()],
+        );
+        let ids: Vec<&str> = rules.iter().map(|rule| rule.id.as_str()).collect();
+        assert_eq!(ids, ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn export_round_trips_and_import_merges_by_id() {
+        let path = std::env::temp_dir().join(format!(
+            "murmur-replacement-rules-{}.json",
+            std::process::id()
+        ));
+        let exported = vec![
+            literal("mac", "macbook", "MacBook"),
+            regex("ticket", r"ticket (\d+)", "#$1"),
+        ];
+        assert_eq!(export_to_file(&path, &exported).unwrap(), 2);
+        let imported = read_import(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(imported, exported);
+
+        let mut rules = vec![
+            literal("dash", "slash dash", "--"),
+            literal("mac", "mac", "Mac"),
+        ];
+        assert_eq!(merge_import(&mut rules, &imported), 2);
+        let ids: Vec<&str> = rules.iter().map(|rule| rule.id.as_str()).collect();
+        assert_eq!(ids, ["dash", "mac", "ticket"]);
+        assert_eq!(rules[1].find, "macbook");
+    }
+
+    #[test]
+    fn import_rejects_other_json() {
+        let path = std::env::temp_dir().join(format!(
+            "murmur-replacement-rules-bad-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            br#"{"format":"murmur-rewrite-templates","version":1,"rules":[]}"#,
+        )
+        .unwrap();
+        assert!(read_import(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -130,12 +130,20 @@ pub fn sample_resources(
             reason: UnavailableReasonV1::UnsupportedPlatform,
         }
     };
+    let gpu = if cfg!(target_os = "macos") {
+        measured_or_unavailable(crate::platform::gpu_percent())
+    } else {
+        MeasurementV1::Unavailable {
+            reason: UnavailableReasonV1::UnsupportedPlatform,
+        }
+    };
 
     ResourceSampleV1 {
         schema_version: RESOURCE_SAMPLE_SCHEMA_VERSION,
         observed_at_ms: chrono::Utc::now().timestamp_millis(),
         host: HostResourceSampleV1 {
             cpu_percent: measured_or_unavailable(crate::platform::cpu_percent()),
+            gpu_percent: gpu,
         },
         main_process: ProcessResourceSampleV1 {
             cpu_percent: measured_or_unavailable(process_cpu),
@@ -215,6 +223,10 @@ fn check_idle_timeout() {
                 .unload(Some(&handle), UnloadReason::IdleTimeout)
                 .ok()
                 .flatten();
+            let _ = state
+                .app_state
+                .draft_runtime
+                .unload(None, UnloadReason::IdleTimeout);
             *state.app_state.last_transcription_at.lock_or_recover() = None;
             let rss = get_process_rss_mb();
             let heap = crate::rust_heap_mb();
@@ -228,30 +240,45 @@ fn check_idle_timeout() {
 // Heartbeat task: periodic telemetry + idle timeout check
 // ---------------------------------------------------------------------------
 
+const HEARTBEAT_TICK: std::time::Duration = std::time::Duration::from_millis(500);
+/// Ticks between samples while idle (1 Hz). Recording, processing, and
+/// benchmarks sample every tick so short runs still get a usable peak.
+const IDLE_SAMPLE_EVERY: u64 = 2;
+/// Ticks between heartbeat log lines and idle-timeout checks (one minute).
+const HEARTBEAT_LOG_EVERY: u64 = 120;
+
+fn should_sample(ticks: u64, status: crate::state::DictationStatus, benchmarking: bool) -> bool {
+    benchmarking || status != crate::state::DictationStatus::Idle || ticks % IDLE_SAMPLE_EVERY == 0
+}
+
 pub fn start_heartbeat(app_handle: tauri::AppHandle) {
+    use crate::MutexExt;
     use tauri::Manager;
 
     set_idle_timeout(app_handle.clone());
 
     tauri::async_runtime::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        let mut interval = tokio::time::interval(HEARTBEAT_TICK);
         let mut ticks = 0_u64;
         loop {
             interval.tick().await;
             ticks = ticks.saturating_add(1);
 
             let state = app_handle.state::<crate::State>();
-            let sample = sample_resources(&state.transform_runtime);
-            if let Err(error) = state.performance.insert_resource_sample(&sample) {
-                tracing::warn!(
-                    target: "system",
-                    diagnostics_available = false,
-                    "performance resource sample not persisted: {}",
-                    error
-                );
+            let status = state.app_state.dictation.lock_or_recover().status;
+            if should_sample(ticks, status, state.benchmark.is_running()) {
+                let sample = sample_resources(&state.transform_runtime);
+                if let Err(error) = state.performance.insert_resource_sample(&sample) {
+                    tracing::warn!(
+                        target: "system",
+                        diagnostics_available = false,
+                        "performance resource sample not persisted: {}",
+                        error
+                    );
+                }
             }
 
-            if ticks % 60 == 0 {
+            if ticks % HEARTBEAT_LOG_EVERY == 0 {
                 let rss = get_process_rss_mb();
                 let rust = crate::rust_heap_mb();
                 let ffi = crate::ffi_heap_mb();
@@ -280,6 +307,19 @@ pub fn get_resource_usage(
     sample_resources(&state.transform_runtime)
 }
 
+/// Persisted samples observed between `since_ms` and `until_ms` (inclusive,
+/// either bound optional), oldest first.
+#[tauri::command]
+pub fn get_resource_timeline(
+    state: tauri::State<'_, crate::State>,
+    since_ms: Option<i64>,
+    until_ms: Option<i64>,
+) -> Result<Vec<crate::performance_metrics::ResourceSampleV1>, String> {
+    state
+        .performance
+        .resource_timeline(since_ms.unwrap_or(i64::MIN), until_ms.unwrap_or(i64::MAX))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,6 +378,15 @@ mod tests {
         assert!(matches!(second.cpu_percent, MeasurementV1::Measured { .. }));
     }
 
+    #[test]
+    fn sampling_speeds_up_while_dictation_or_benchmarks_run() {
+        assert!(!should_sample(1, DictationStatus::Idle, false));
+        assert!(should_sample(2, DictationStatus::Idle, false));
+        assert!(should_sample(1, DictationStatus::Recording, false));
+        assert!(should_sample(1, DictationStatus::Processing, false));
+        assert!(should_sample(1, DictationStatus::Idle, true));
+    }
+
     #[test]
     fn idle_release_respects_disabled_and_recent_activity() {
         assert!(!should_release_model(
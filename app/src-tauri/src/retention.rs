@@ -0,0 +1,352 @@
+//! Scheduled cleanup of old logs and, when allowed, saved recordings.
+//!
+//! Rotation keeps one `.1` file per log forever, and older versions left
+//! transcript logs and dated rolling files behind; saved recordings pile up in
+//! the output folder. Once a day (and shortly after launch) the files past the
+//! `storageRetention` age limit are deleted, then the oldest of the rest until
+//! they fit the size limit. Logs still being written are never touched, and
+//! transcript logs are shredded like `purge_all_transcripts` does. Recordings
+//! are only included when the user opts in. When anything was removed,
+//! `storage-cleaned` summarizes it per category.
+//!
+//! The frontend sends the setting through `set_storage_retention` at startup
+//! and on change; nothing runs before it arrives.
+
+use crate::storage::{PurgeResult, StorageCategory};
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{Emitter, Manager};
+
+/// Leaves launch-time work alone before the first cleanup.
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const RUN_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+const MB: u64 = 1024 * 1024;
+
+/// The `storageRetention` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionSettings {
+    pub enabled: bool,
+    /// Budget for everything cleanup manages, in megabytes.
+    pub max_total_mb: u64,
+    /// Files last written longer ago than this are removed. 0 keeps any age.
+    pub max_age_days: u64,
+    /// Also clean up `murmur-*.wav` files in the output folder.
+    pub include_recordings: bool,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_total_mb: 500,
+            max_age_days: 30,
+            include_recordings: false,
+        }
+    }
+}
+
+struct Schedule {
+    settings: Option<RetentionSettings>,
+    last_run: Option<Instant>,
+}
+
+static SCHEDULE: Mutex<Schedule> = Mutex::new(Schedule {
+    settings: None,
+    last_run: None,
+});
+
+/// Payload of `storage-cleaned`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageCleaned {
+    /// `startup`, `daily`, or `manual`.
+    pub trigger: &'static str,
+    /// Categories that lost files, in `StorageCategory` order.
+    pub removed: Vec<PurgeResult>,
+    pub removed_files: u64,
+    pub freed_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Candidate {
+    path: PathBuf,
+    category: StorageCategory,
+    bytes: u64,
+    modified: SystemTime,
+}
+
+/// The candidates to delete: everything past the age limit, then the oldest
+/// until the rest fit the size limit.
+fn select(
+    mut candidates: Vec<Candidate>,
+    settings: &RetentionSettings,
+    now: SystemTime,
+) -> Vec<Candidate> {
+    candidates.sort_by_key(|candidate| candidate.modified);
+    let max_age = Duration::from_secs(settings.max_age_days.saturating_mul(DAY.as_secs()));
+    let budget = settings.max_total_mb.saturating_mul(MB);
+    let mut total: u64 = candidates.iter().map(|candidate| candidate.bytes).sum();
+    let mut selected = Vec::new();
+    for candidate in candidates {
+        let age = now.duration_since(candidate.modified).unwrap_or_default();
+        let expired = settings.max_age_days > 0 && age > max_age;
+        if !expired && total <= budget {
+            // Everything after this one is newer.
+            break;
+        }
+        total -= candidate.bytes;
+        selected.push(candidate);
+    }
+    selected
+}
+
+fn files_in(
+    dir: &std::path::Path,
+    category: StorageCategory,
+    include: impl Fn(&str) -> bool,
+) -> Vec<Candidate> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| include(&entry.file_name().to_string_lossy()))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| Candidate {
+                path: entry.path(),
+                category,
+                bytes: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            })
+        })
+        .collect()
+}
+
+fn candidates(app: &tauri::AppHandle, settings: &RetentionSettings) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    if let Some(logs) = dirs::data_dir().map(|dir| dir.join("local-dictation").join("logs")) {
+        candidates.extend(files_in(&logs, StorageCategory::Logs, |name| {
+            !crate::telemetry::is_active_log(name)
+        }));
+    }
+    if settings.include_recordings {
+        let output_dir = app
+            .state::<crate::State>()
+            .app_state
+            .dictation
+            .lock_or_recover()
+            .output_dir
+            .clone();
+        if let Ok(dir) = crate::file_output::output_dir_path(&output_dir) {
+            candidates.extend(files_in(
+                &dir,
+                StorageCategory::Recordings,
+                crate::file_output::is_recording_file,
+            ));
+        }
+    }
+    candidates
+}
+
+fn remove(candidate: &Candidate) -> std::io::Result<()> {
+    let name = candidate
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    if crate::telemetry::is_transcript_log(&name) {
+        crate::telemetry::shred_file(&candidate.path)
+    } else {
+        std::fs::remove_file(&candidate.path)
+    }
+}
+
+/// Apply the retention setting now. `None` before the frontend has sent it.
+fn run(app: &tauri::AppHandle, trigger: &'static str) -> Option<StorageCleaned> {
+    let settings = {
+        let mut schedule = SCHEDULE.lock_or_recover();
+        let settings = schedule.settings?;
+        schedule.last_run = Some(Instant::now());
+        settings
+    };
+    let mut removed: Vec<PurgeResult> = Vec::new();
+    if settings.enabled {
+        for candidate in select(candidates(app, &settings), &settings, SystemTime::now()) {
+            if let Err(error) = remove(&candidate) {
+                tracing::warn!(target: "system", "retention cleanup skipped a file: {}", error);
+                continue;
+            }
+            match removed
+                .iter_mut()
+                .find(|entry| entry.category == candidate.category)
+            {
+                Some(entry) => {
+                    entry.removed_files += 1;
+                    entry.freed_bytes += candidate.bytes;
+                }
+                None => removed.push(PurgeResult {
+                    category: candidate.category,
+                    removed_files: 1,
+                    freed_bytes: candidate.bytes,
+                }),
+            }
+        }
+    }
+    removed.sort_by_key(|entry| entry.category);
+    let cleaned = StorageCleaned {
+        trigger,
+        removed_files: removed.iter().map(|entry| entry.removed_files).sum(),
+        freed_bytes: removed.iter().map(|entry| entry.freed_bytes).sum(),
+        removed,
+    };
+    if cleaned.removed_files > 0 {
+        tracing::info!(
+            target: "system",
+            trigger,
+            removed_files = cleaned.removed_files,
+            freed_mb = cleaned.freed_bytes / MB,
+            "storage cleaned"
+        );
+        let _ = app.emit("storage-cleaned", &cleaned);
+    }
+    Some(cleaned)
+}
+
+/// Start the daily cleanup. The first run waits for the setting to arrive.
+pub(crate) fn init(app: &tauri::AppHandle) {
+    let handle = app.clone();
+    if let Err(error) = std::thread::Builder::new()
+        .name("storage-retention".to_string())
+        .spawn(move || {
+            std::thread::sleep(STARTUP_DELAY);
+            loop {
+                let last_run = SCHEDULE.lock_or_recover().last_run;
+                match last_run {
+                    None => {
+                        run(&handle, "startup");
+                    }
+                    Some(at) if at.elapsed() >= RUN_INTERVAL => {
+                        run(&handle, "daily");
+                    }
+                    Some(_) => {}
+                }
+                std::thread::sleep(CHECK_INTERVAL);
+            }
+        })
+    {
+        tracing::warn!(target: "system", "storage retention failed to start: {}", error);
+    }
+}
+
+#[tauri::command]
+pub fn set_storage_retention(settings: RetentionSettings) {
+    tracing::info!(
+        target: "system",
+        enabled = settings.enabled,
+        max_total_mb = settings.max_total_mb,
+        max_age_days = settings.max_age_days,
+        include_recordings = settings.include_recordings,
+        "storage retention configured"
+    );
+    SCHEDULE.lock_or_recover().settings = Some(settings);
+}
+
+/// Clean up now instead of waiting for the daily run.
+#[tauri::command]
+pub fn run_storage_cleanup(app_handle: tauri::AppHandle) -> Result<StorageCleaned, String> {
+    run(&app_handle, "manual").ok_or_else(|| "Storage retention is not configured yet".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(name: &str, mb: u64, days_old: u64, now: SystemTime) -> Candidate {
+        Candidate {
+            path: PathBuf::from(name),
+            category: StorageCategory::Logs,
+            bytes: mb * MB,
+            modified: now - Duration::from_secs(days_old * DAY.as_secs()),
+        }
+    }
+
+    fn names(selected: &[Candidate]) -> Vec<&str> {
+        selected
+            .iter()
+            .map(|candidate| candidate.path.to_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn removes_expired_files_then_oldest_until_under_budget() {
+        let now = SystemTime::now();
+        let settings = RetentionSettings {
+            max_total_mb: 10,
+            max_age_days: 30,
+            ..RetentionSettings::default()
+        };
+        let files = vec![
+            candidate("new", 4, 1, now),
+            candidate("ancient", 1, 90, now),
+            candidate("middle", 6, 10, now),
+            candidate("older", 3, 20, now),
+        ];
+        // 14 MB total: the expired file goes, then the oldest until 10 MB fit.
+        assert_eq!(names(&select(files, &settings, now)), ["ancient", "older"]);
+    }
+
+    #[test]
+    fn keeps_everything_within_limits_and_zero_age_disables_expiry() {
+        let now = SystemTime::now();
+        let files = vec![candidate("a", 1, 400, now), candidate("b", 1, 2, now)];
+        let keep_forever = RetentionSettings {
+            max_age_days: 0,
+            ..RetentionSettings::default()
+        };
+        assert!(select(files.clone(), &keep_forever, now).is_empty());
+        assert_eq!(
+            names(&select(files, &RetentionSettings::default(), now)),
+            ["a"]
+        );
+    }
+
+    #[test]
+    fn only_inactive_logs_and_recordings_are_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "app.log",
+            "app.dev.log.1",
+            "events.jsonl.2026-03-02",
+            "murmur-0001.wav",
+        ] {
+            std::fs::write(dir.path().join(name), b"x").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+
+        let mut logs = files_in(dir.path(), StorageCategory::Logs, |name| {
+            name != "app.log" && name != "murmur-0001.wav"
+        });
+        logs.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(
+            logs.iter()
+                .map(|log| log.path.file_name().unwrap())
+                .collect::<Vec<_>>(),
+            ["app.dev.log.1", "events.jsonl.2026-03-02"]
+        );
+
+        let recordings = files_in(
+            dir.path(),
+            StorageCategory::Recordings,
+            crate::file_output::is_recording_file,
+        );
+        assert_eq!(recordings.len(), 1);
+        assert_eq!(recordings[0].category, StorageCategory::Recordings);
+    }
+}
@@ -0,0 +1,334 @@
+//! Named rewrite templates for local-model post-processing of dictation.
+//!
+//! A template is a name plus an instruction for the transform model. It
+//! applies when a dictation starts with "format as <name>" (the prefix is
+//! dropped before rewriting) or when the dictation profile that started the
+//! recording selects it. The frontend owns persistence; the backend keeps the
+//! live copy from `configure_dictation` and the CRUD commands, and reads and
+//! writes the import/export file.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::transform_presets::normalize;
+
+/// Most templates kept; extra entries are dropped on load and import.
+pub const MAX_TEMPLATES: usize = 50;
+const MAX_NAME_CHARS: usize = 60;
+const MAX_INSTRUCTION_CHARS: usize = 2_000;
+
+const EXPORT_FORMAT: &str = "murmur-rewrite-templates";
+const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewriteTemplate {
+    pub id: String,
+    pub name: String,
+    pub instruction: String,
+}
+
+impl RewriteTemplate {
+    /// Trimmed copy, or `None` when the id, name, or instruction is blank or
+    /// over its length limit.
+    fn sanitized(&self) -> Option<Self> {
+        let id = self.id.trim();
+        let name = self.name.trim();
+        let instruction = self.instruction.trim();
+        if id.is_empty()
+            || normalize(name).is_empty()
+            || instruction.is_empty()
+            || name.chars().count() > MAX_NAME_CHARS
+            || instruction.chars().count() > MAX_INSTRUCTION_CHARS
+        {
+            return None;
+        }
+        Some(Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            instruction: instruction.to_string(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateExport {
+    format: String,
+    version: u32,
+    templates: Vec<serde_json::Value>,
+}
+
+/// Parse the `rewriteTemplates` array from `configure_dictation`. Invalid
+/// entries are skipped; the first template wins an id or name clash.
+pub fn parse_templates(values: &[serde_json::Value]) -> Vec<RewriteTemplate> {
+    let mut templates = Vec::new();
+    for template in values
+        .iter()
+        .filter_map(|value| serde_json::from_value::<RewriteTemplate>(value.clone()).ok())
+        .filter_map(|template| template.sanitized())
+    {
+        if templates.len() == MAX_TEMPLATES {
+            break;
+        }
+        if find_clash(&templates, &template).is_none() {
+            templates.push(template);
+        }
+    }
+    templates
+}
+
+/// Insert `template`, or replace the one with its id. A different template
+/// with the same name is replaced too, so names stay unique for the spoken
+/// prefix. Returns the stored template.
+pub fn upsert(
+    templates: &mut Vec<RewriteTemplate>,
+    template: &RewriteTemplate,
+) -> Result<RewriteTemplate, String> {
+    let template = template
+        .sanitized()
+        .ok_or_else(|| "A rewrite template needs a name and an instruction.".to_string())?;
+    match find_clash(templates, &template) {
+        Some(index) => {
+            templates[index] = template.clone();
+            templates.retain(|existing| {
+                existing == &template
+                    || find_clash(std::slice::from_ref(existing), &template).is_none()
+            });
+        }
+        None if templates.len() == MAX_TEMPLATES => {
+            return Err(format!("Keep at most {MAX_TEMPLATES} rewrite templates."));
+        }
+        None => templates.push(template.clone()),
+    }
+    Ok(template)
+}
+
+/// Remove the template with `id`. Returns whether one was removed.
+pub fn remove(templates: &mut Vec<RewriteTemplate>, id: &str) -> bool {
+    let before = templates.len();
+    templates.retain(|template| template.id != id.trim());
+    templates.len() != before
+}
+
+/// Index of a template sharing `template`'s id or name.
+fn find_clash(templates: &[RewriteTemplate], template: &RewriteTemplate) -> Option<usize> {
+    let name = normalize(&template.name);
+    templates
+        .iter()
+        .position(|existing| existing.id == template.id || normalize(&existing.name) == name)
+}
+
+/// Match "format as <name>" at the start of `text`. Returns the template and
+/// the text after the prefix; the longest matching name wins so "bullet
+/// summary" beats "bullet". A prefix with nothing after it is not a match.
+pub fn match_spoken_prefix<'a>(
+    text: &str,
+    templates: &'a [RewriteTemplate],
+) -> Option<(&'a RewriteTemplate, String)> {
+    let words = word_spans(text);
+    if words.len() < 3 || normalize(words[0].1) != "format" || normalize(words[1].1) != "as" {
+        return None;
+    }
+    let (template, name_len) = templates
+        .iter()
+        .filter_map(|template| {
+            let name = normalize(&template.name);
+            let name_words: Vec<&str> = name.split(' ').collect();
+            let spoken = words.get(2..2 + name_words.len())?;
+            spoken
+                .iter()
+                .zip(&name_words)
+                .all(|((_, word), expected)| normalize(word) == *expected)
+                .then_some((template, name_words.len()))
+        })
+        .max_by_key(|(_, name_len)| *name_len)?;
+    let (start, last) = words[1 + name_len];
+    let body = text[start + last.len()..]
+        .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | ',' | '.' | ';' | '-'))
+        .trim_end();
+    (!body.is_empty()).then(|| (template, body.to_string()))
+}
+
+/// Byte offset and text of each whitespace-separated word.
+fn word_spans(text: &str) -> Vec<(usize, &str)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(begin)) => {
+                spans.push((begin, &text[begin..index]));
+                start = None;
+            }
+            (false, None) => start = Some(index),
+            _ => {}
+        }
+    }
+    if let Some(begin) = start {
+        spans.push((begin, &text[begin..]));
+    }
+    spans
+}
+
+/// Write `templates` to `path` as a versioned JSON bundle. Returns the count.
+pub fn export_to_file(path: &Path, templates: &[RewriteTemplate]) -> Result<usize, String> {
+    let bundle = TemplateExport {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        templates: templates
+            .iter()
+            .filter_map(|template| serde_json::to_value(template).ok())
+            .collect(),
+    };
+    let bytes = serde_json::to_vec_pretty(&bundle)
+        .map_err(|_| "Rewrite templates could not be serialized.".to_string())?;
+    std::fs::write(path, bytes)
+        .map_err(|e| format!("Couldn't write the rewrite template file: {e}"))?;
+    Ok(templates.len())
+}
+
+/// Read the templates from an export bundle, or from a bare template array.
+pub fn read_import(path: &Path) -> Result<Vec<RewriteTemplate>, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Couldn't read the rewrite template file: {e}"))?;
+    let value: serde_json::Value = serde_json::from_slice(&bytes)
+        .map_err(|_| "The file is not a rewrite template export.".to_string())?;
+    let values = match value {
+        serde_json::Value::Array(values) => values,
+        value => {
+            let bundle: TemplateExport = serde_json::from_value(value)
+                .map_err(|_| "The file is not a rewrite template export.".to_string())?;
+            if bundle.format != EXPORT_FORMAT || bundle.version > EXPORT_VERSION {
+                return Err("The file is not a rewrite template export.".to_string());
+            }
+            bundle.templates
+        }
+    };
+    Ok(parse_templates(&values))
+}
+
+/// Upsert each imported template into `templates`. Returns how many were
+/// added or replaced; imports past `MAX_TEMPLATES` are skipped.
+pub fn merge_import(templates: &mut Vec<RewriteTemplate>, imported: &[RewriteTemplate]) -> usize {
+    imported
+        .iter()
+        .filter(|template| upsert(templates, template).is_ok())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: &str, name: &str) -> RewriteTemplate {
+        RewriteTemplate {
+            id: id.to_string(),
+            name: name.to_string(),
+            instruction: format!("Rewrite as {name}."),
+        }
+    }
+
+    #[test]
+    fn parse_skips_blank_and_clashing_templates() {
+        let values = vec![
+            serde_json::json!({"id": "email", "name": " Email ", "instruction": " Be polite. "}),
+            serde_json::json!({"id": "blank", "name": "Blank", "instruction": "  "}),
+            serde_json::json!({"id": "email", "name": "Other", "instruction": "x"}),
+            serde_json::json!({"id": "mail", "name": "email", "instruction": "x"}),
+            serde_json::json!({"name": "No id", "instruction": "x"}),
+        ];
+        assert_eq!(
+            parse_templates(&values),
+            vec![RewriteTemplate {
+                id: "email".to_string(),
+                name: "Email".to_string(),
+                instruction: "Be polite.".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn upsert_replaces_by_id_or_name_and_remove_deletes() {
+        let mut templates = vec![template("email", "Email"), template("bullets", "Bullets")];
+        upsert(&mut templates, &template("email", "Email tone")).unwrap();
+        upsert(&mut templates, &template("new", "bullets")).unwrap();
+        upsert(&mut templates, &template("comment", "Code comment")).unwrap();
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Email tone", "bullets", "Code comment"]);
+        assert!(upsert(&mut templates, &template("", "Nameless id")).is_err());
+
+        assert!(remove(&mut templates, "comment"));
+        assert!(!remove(&mut templates, "comment"));
+        assert_eq!(templates.len(), 2);
+    }
+
+    #[test]
+    fn upsert_collapses_a_rename_onto_another_templates_name() {
+        let mut templates = vec![template("a", "Email"), template("b", "Bullets")];
+        upsert(&mut templates, &template("a", "Bullets")).unwrap();
+        assert_eq!(templates, vec![template("a", "Bullets")]);
+    }
+
+    #[test]
+    fn spoken_prefix_selects_the_longest_name_and_strips_it() {
+        let templates = vec![
+            template("bullets", "Bullet"),
+            template("summary", "Bullet summary"),
+            template("email", "Email"),
+        ];
+        let (matched, body) =
+            match_spoken_prefix("Format as bullet summary: ship it Friday.", &templates).unwrap();
+        assert_eq!(matched.id, "summary");
+        assert_eq!(body, "ship it Friday.");
+
+        let (matched, body) =
+            match_spoken_prefix("format as Email. Hi Sam, running late.", &templates).unwrap();
+        assert_eq!(matched.id, "email");
+        assert_eq!(body, "Hi Sam, running late.");
+    }
+
+    #[test]
+    fn text_without_a_known_prefix_or_body_is_left_alone() {
+        let templates = vec![template("email", "Email")];
+        assert!(match_spoken_prefix("Format as email.", &templates).is_none());
+        assert!(match_spoken_prefix("Format as memo: hi", &templates).is_none());
+        assert!(match_spoken_prefix("Please format as email: hi", &templates).is_none());
+        assert!(match_spoken_prefix("format", &templates).is_none());
+    }
+
+    #[test]
+    fn export_round_trips_and_import_merges() {
+        let path = std::env::temp_dir().join(format!(
+            "murmur-rewrite-templates-{}.json",
+            std::process::id()
+        ));
+        let exported = vec![
+            template("email", "Email"),
+            template("comment", "Code comment"),
+        ];
+        assert_eq!(export_to_file(&path, &exported).unwrap(), 2);
+        let imported = read_import(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(imported, exported);
+
+        let mut templates = vec![template("mine", "email"), template("bullets", "Bullets")];
+        assert_eq!(merge_import(&mut templates, &imported), 2);
+        let ids: Vec<&str> = templates.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["email", "bullets", "comment"]);
+    }
+
+    #[test]
+    fn import_rejects_other_json() {
+        let path = std::env::temp_dir().join(format!(
+            "murmur-rewrite-templates-bad-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            br#"{"format":"murmur-knowledge","version":1,"templates":[]}"#,
+        )
+        .unwrap();
+        assert!(read_import(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
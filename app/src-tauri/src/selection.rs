@@ -441,6 +441,65 @@ pub async fn capture_selection(
     }
 }
 
+/// One AX-only read of the selection when a dictation starts with
+/// `selectionDictation` on. Unlike `capture_selection` there is no warm-up
+/// retry and no clipboard fallback: starting a dictation must never post a
+/// synthetic Cmd+C, so anything short of a clean AX read is an error and the
+/// dictation is delivered as usual.
+pub async fn capture_selection_for_dictation(
+    app_handle: &tauri::AppHandle,
+) -> Result<TransformSnapshot, SelectionError> {
+    if !crate::injector::is_accessibility_enabled() {
+        return Err(SelectionError::AccessibilityDenied);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        app_handle
+            .run_on_main_thread(move || {
+                let _ = tx.send(native::capture_selection_native());
+            })
+            .map_err(|_| SelectionError::AxUnavailable)?;
+        rx.await.unwrap_or(Err(SelectionError::AxUnavailable))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_handle;
+        Err(SelectionError::AxUnavailable)
+    }
+}
+
+/// The frontmost app's bundle id and the title of its focused window, read
+/// over AX for context biasing. `None` when Accessibility is off, the app
+/// exposes no focused window, or the window has no title. The title can
+/// carry message subjects and names, so it is never logged.
+pub async fn capture_window_title(
+    app_handle: &tauri::AppHandle,
+) -> Option<(Option<String>, String)> {
+    if !crate::injector::is_accessibility_enabled() {
+        return None;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        app_handle
+            .run_on_main_thread(move || {
+                let _ = tx.send(native::focused_window_title_native());
+            })
+            .ok()?;
+        rx.await.ok().flatten()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app_handle;
+        None
+    }
+}
+
 /// Clipboard-based selection capture (issue #329), used when the AX path
 /// returned `NoSelection` (secure-field checks passed benignly, no
 /// `AXSelectedText` exposed) or `AxUnavailable` (AX queries failed/timed out —
@@ -1079,6 +1138,27 @@ mod native {
             captured_at: Instant::now(),
         })
     }
+
+    pub(super) fn focused_window_title_native() -> Option<(Option<String>, String)> {
+        let frontmost = NSWorkspace::sharedWorkspace().frontmostApplication()?;
+        let bundle_id = frontmost.bundleIdentifier().map(|value| value.to_string());
+
+        let app = unsafe { AXUIElementCreateApplication(frontmost.processIdentifier()) };
+        if app.is_null() {
+            return None;
+        }
+        let _app_guard = CFGuard(app);
+        set_timeout(app).ok()?;
+
+        let window = copy_attribute(app, "AXFocusedWindow").ok()?;
+        set_timeout(window.0).ok()?;
+        let title = copy_attribute_string(window.0, "AXTitle").ok()?;
+        let title = title.trim();
+        if title.is_empty() {
+            return None;
+        }
+        Some((bundle_id, title.to_string()))
+    }
 }
 
 #[cfg(test)]
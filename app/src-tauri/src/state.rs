@@ -1,4 +1,5 @@
 use crate::model_runtime::ModelRuntimeManager;
+use crate::transcriber::WhisperDecoding;
 use crate::MutexExt;
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -13,6 +14,9 @@ pub enum DictationStatus {
     Idle,
     Recording,
     Processing,
+    /// Append mode holds transcribed takes until they are committed or
+    /// discarded. Nothing is in flight, so a new take may start.
+    Buffering,
 }
 
 impl Default for DictationStatus {
@@ -21,6 +25,22 @@ impl Default for DictationStatus {
     }
 }
 
+impl DictationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Recording => "recording",
+            Self::Processing => "processing",
+            Self::Buffering => "buffering",
+        }
+    }
+
+    /// No recording or transcription is in flight.
+    pub fn is_settled(self) -> bool {
+        matches!(self, Self::Idle | Self::Buffering)
+    }
+}
+
 /// Status of the AX-selection transform pipeline (issue #312). Deliberately a
 /// separate field on `AppState`, NOT a `DictationStatus` variant — dictation
 /// recording/processing and a transform pass are independent activities that
@@ -111,6 +131,352 @@ impl WritingStyle {
     }
 }
 
+/// How the final paste keystroke is delivered. Both methods write the
+/// clipboard first; they differ only in the Cmd+V source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectionMethod {
+    /// Native CoreGraphics key events with the System Events fallback.
+    #[default]
+    Native,
+    /// System Events `keystroke` only. Some apps (remote desktops, a few
+    /// Electron builds) ignore synthetic HID events but accept AppleScript.
+    SystemEvents,
+}
+
+impl InjectionMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Native => "native",
+            Self::SystemEvents => "system_events",
+        }
+    }
+}
+
+/// Text appended after a non-empty transcript before it is delivered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingBehavior {
+    #[default]
+    None,
+    Space,
+    Newline,
+    /// A space, unless the text after the caret starts with whitespace or
+    /// punctuation.
+    Smart,
+}
+
+impl TrailingBehavior {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Space => "space",
+            Self::Newline => "newline",
+            Self::Smart => "smart",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(Self::None),
+            "space" => Some(Self::Space),
+            "newline" => Some(Self::Newline),
+            "smart" => Some(Self::Smart),
+            _ => None,
+        }
+    }
+
+    /// The text appended after the transcript, given `after_caret`, the text
+    /// following the insertion point when it could be read.
+    pub fn suffix(self, after_caret: Option<&str>) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Space => " ",
+            Self::Newline => "\n",
+            Self::Smart if crate::cursor_context::separates_next(after_caret) => "",
+            Self::Smart => " ",
+        }
+    }
+}
+
+/// Which dictation outcomes raise a system notification while the main window
+/// is hidden.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationPolicy {
+    #[default]
+    Off,
+    ErrorsOnly,
+    All,
+}
+
+impl NotificationPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "off" => Some(Self::Off),
+            "errors_only" => Some(Self::ErrorsOnly),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    pub fn notifies_completions(self) -> bool {
+        self == Self::All
+    }
+
+    pub fn notifies_errors(self) -> bool {
+        self != Self::Off
+    }
+}
+
+/// What happens to a recording cut short by screen lock, sleep, or a user
+/// switch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterruptedRecordingAction {
+    #[default]
+    Discard,
+    Transcribe,
+}
+
+impl InterruptedRecordingAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "discard" => Some(Self::Discard),
+            "transcribe" => Some(Self::Transcribe),
+            _ => None,
+        }
+    }
+}
+
+/// When a finished dictation goes to today's Murmur note (`notes.rs`)
+/// instead of the frontmost app.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotesTarget {
+    #[default]
+    Off,
+    Always,
+    /// Only when the focused element is positively not a text field.
+    NoTextField,
+}
+
+impl NotesTarget {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "off" => Some(Self::Off),
+            "always" => Some(Self::Always),
+            "no_text_field" => Some(Self::NoTextField),
+            _ => None,
+        }
+    }
+}
+
+/// What a dictation does to text that was selected when it started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionDictation {
+    /// Deliver as usual; nothing reads the selection.
+    #[default]
+    Off,
+    /// Replace the selection with the dictation, only if it is unchanged.
+    Replace,
+    /// Treat the dictation as an instruction and replace the selection with
+    /// the local model's rewrite of it.
+    Rewrite,
+}
+
+impl SelectionDictation {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "off" => Some(Self::Off),
+            "replace" => Some(Self::Replace),
+            "rewrite" => Some(Self::Rewrite),
+            _ => None,
+        }
+    }
+}
+
+/// What the redaction filter does with a matched span.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionMode {
+    /// Replace each character with `*`, keeping the text's shape.
+    #[default]
+    Mask,
+    /// Drop the span and tidy the surrounding spacing.
+    Remove,
+}
+
+impl RedactionMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "mask" => Some(Self::Mask),
+            "remove" => Some(Self::Remove),
+            _ => None,
+        }
+    }
+}
+
+/// Word lists and patterns masked before injection and history. Compiled into
+/// `AppState::redactor` by `configure_dictation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionSettings {
+    pub enabled: bool,
+    pub mode: RedactionMode,
+    /// Include the shipped profanity list alongside `words`.
+    pub include_profanity: bool,
+    /// Extra whole words or phrases, matched case-insensitively.
+    pub words: Vec<String>,
+    /// Regular expressions, e.g. for card or account numbers.
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: RedactionMode::Mask,
+            include_profanity: true,
+            words: Vec::new(),
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// What changes while a macOS Focus mode (e.g. Do Not Disturb) is on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusFilterSettings {
+    /// Hold dictation notifications while a matching Focus is on.
+    pub suppress_notifications: bool,
+    /// Ignore the dictation hotkey while a matching Focus is on.
+    pub disable_hotkey: bool,
+    /// Focus mode identifiers this applies to; empty means every Focus.
+    pub modes: Vec<String>,
+}
+
+impl FocusFilterSettings {
+    pub fn applies_to(&self, mode: &str) -> bool {
+        self.modes.is_empty() || self.modes.iter().any(|m| m == mode)
+    }
+}
+
+impl Default for FocusFilterSettings {
+    fn default() -> Self {
+        Self {
+            suppress_notifications: true,
+            disable_hotkey: false,
+            modes: Vec::new(),
+        }
+    }
+}
+
+/// Optional per-recording model downgrade while the Mac is thermally
+/// throttled or in Low Power Mode. The chosen model setting is never changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdaptiveModelSettings {
+    pub enabled: bool,
+    /// Model used instead of `large-v3-turbo` under pressure. Ignored when
+    /// it isn't installed.
+    pub fallback_model: String,
+}
+
+impl Default for AdaptiveModelSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fallback_model: "base.en".to_string(),
+        }
+    }
+}
+
+/// Watch live decode speed against a realtime-factor budget and name, or
+/// switch to, a faster model when it is repeatedly missed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyBudgetSettings {
+    pub enabled: bool,
+    /// Longest decode allowed, as a multiple of the speech's length.
+    pub max_rtf: f32,
+    /// Ask the frontend to switch the chosen model instead of only suggesting.
+    pub auto_switch: bool,
+}
+
+impl Default for LatencyBudgetSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_rtf: 1.0,
+            auto_switch: false,
+        }
+    }
+}
+
+/// Dual-model dictation: paste a fast draft, then re-transcribe the same audio
+/// with the chosen model in the background and announce the refined text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DualModelSettings {
+    pub enabled: bool,
+    /// Model used for the immediate draft. Ignored when it isn't installed or
+    /// is the chosen model itself.
+    pub draft_model: String,
+    /// Replace the pasted draft in place when the refined text differs.
+    pub replace_draft: bool,
+}
+
+impl Default for DualModelSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            draft_model: "tiny.en".to_string(),
+            replace_draft: false,
+        }
+    }
+}
+
+/// Extra Whisper hypotheses for each delivered transcript, offered in history
+/// as alternative readings of the words they differ on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlternativesSettings {
+    pub enabled: bool,
+    /// Hypotheses decoded besides the delivered one, up to
+    /// `alternatives::MAX_ALTERNATIVES`.
+    pub count: u32,
+}
+
+impl Default for AlternativesSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            count: 3,
+        }
+    }
+}
+
+/// Decode a Whisper transcript again before delivering it when the model was
+/// unsure of it, keeping whichever pass it was surer of.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceRetrySettings {
+    pub enabled: bool,
+    /// Retry transcripts whose confidence score is below this (0–1).
+    pub threshold: f32,
+    /// Whisper model for the retry. `None`, or a model that isn't installed,
+    /// re-runs the same model with beam search.
+    pub model: Option<String>,
+    /// Longest the retry may add to a dictation, model load included.
+    pub budget_ms: u64,
+}
+
+impl Default for ConfidenceRetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.4,
+            model: None,
+            budget_ms: 3000,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppProfile {
     pub bundle_id: String,
@@ -142,6 +508,41 @@ pub struct AppProfile {
     /// contents remain memory-only and are rebuilt locally.
     #[serde(default)]
     pub ide_project_roots: Vec<String>,
+    /// Override how the paste keystroke is delivered in this app.
+    #[serde(default)]
+    pub injection_method_override: Option<InjectionMethod>,
+    /// Override what is appended after the transcript in this app.
+    #[serde(default)]
+    pub trailing_override: Option<TrailingBehavior>,
+    /// Transcription model for this app (e.g. a larger model for Mail, a fast
+    /// one for chat). Only known, platform-supported names are accepted at
+    /// configuration time; an uninstalled model falls back to global.
+    #[serde(default)]
+    pub model_override: Option<String>,
+}
+
+/// A named dictation profile bound to its own hold-down hotkey. Holding that
+/// key records with the profile's overrides applied on top of the global
+/// settings and any matching app profile; `None` fields inherit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictationProfile {
+    pub id: String,
+    pub name: String,
+    /// Transcription model; validated like `AppProfile::model_override`.
+    #[serde(default)]
+    pub model_override: Option<String>,
+    /// Whisper language code, or "auto" to force detection.
+    #[serde(default)]
+    pub language_override: Option<String>,
+    #[serde(default)]
+    pub auto_paste_override: Option<bool>,
+    #[serde(default)]
+    pub cleanup_override: Option<bool>,
+    #[serde(default)]
+    pub smart_formatting_override: Option<bool>,
+    /// Id of the rewrite template applied to every dictation from this profile.
+    #[serde(default)]
+    pub rewrite_template: Option<String>,
 }
 
 /// A user-defined voice command: when `phrase` is spoken (matched
@@ -209,6 +610,9 @@ pub struct DictationState {
     pub output_dir: String,
     /// Per-app profiles resolved once from the frontmost app at recording start.
     pub app_profiles: Vec<AppProfile>,
+    /// Hotkey-triggered profiles, looked up by id when a recording starts.
+    #[serde(default)]
+    pub dictation_profiles: Vec<DictationProfile>,
     pub voice_commands_enabled: bool,
     /// User-defined voice commands applied after the built-in set.
     #[serde(default)]
@@ -244,6 +648,136 @@ pub struct DictationState {
     /// Tier 2 phonetic / edit-distance "sounds-like" matching. Gated under
     /// `correction_enabled`.
     pub correction_fuzzy: bool,
+    /// Bundle ids where Murmur never pastes, whatever the global setting,
+    /// profile, or session says. The transcript still reaches the clipboard.
+    #[serde(default = "default_inject_blocklist")]
+    pub inject_blocklist: Vec<String>,
+    /// Use the old subprocess automation: System Events paste for apps without
+    /// an injection override, and `open` for System Settings links. For Macs
+    /// where synthetic key events or `NSWorkspace` misbehave.
+    #[serde(default)]
+    pub legacy_automation: bool,
+    /// System notifications for completions/errors while the window is hidden.
+    #[serde(default)]
+    pub notification_policy: NotificationPolicy,
+    /// Notification and hotkey behaviour while a Focus mode is on.
+    #[serde(default)]
+    pub focus_filter: FocusFilterSettings,
+    /// Discard or transcribe a recording interrupted by lock or sleep.
+    #[serde(default)]
+    pub interrupted_recording_action: InterruptedRecordingAction,
+    /// Advanced Whisper decoding knobs. Defaults reproduce the shipped decode.
+    #[serde(default)]
+    pub whisper_decoding: WhisperDecoding,
+    /// Skip near-silent recordings and drop transcripts that are only known
+    /// hallucination phrases ("Thank you for watching").
+    #[serde(default = "default_true")]
+    pub hallucination_filter: bool,
+    /// Optional profanity/pattern redaction, off by default.
+    #[serde(default)]
+    pub redaction: RedactionSettings,
+    /// Thermal / Low Power Mode model fallback, off by default.
+    #[serde(default)]
+    pub adaptive_model: AdaptiveModelSettings,
+    /// Realtime-factor budget with faster-model suggestions, off by default.
+    #[serde(default)]
+    pub latency_budget: LatencyBudgetSettings,
+    /// Fast draft then background refinement, off by default.
+    #[serde(default)]
+    pub dual_model: DualModelSettings,
+    /// Re-decode unsure transcripts before injecting them, off by default.
+    #[serde(default)]
+    pub confidence_retry: ConfidenceRetrySettings,
+    /// N-best hypotheses for the history's alternative picker, off by default.
+    #[serde(default)]
+    pub alternatives: AlternativesSettings,
+    /// Feed phrases the user keeps correcting history entries to into the
+    /// Whisper prompt, off by default.
+    #[serde(default)]
+    pub learn_from_corrections: bool,
+    /// Hold each take's text in `append_takes` instead of injecting it, until
+    /// `commit_append_buffer` pastes them together.
+    #[serde(default)]
+    pub append_mode: bool,
+    /// Hold each dictation for Accept or Discard in the overlay before it
+    /// reaches the clipboard. Append mode buffers takes instead.
+    #[serde(default)]
+    pub preview_before_paste: bool,
+    /// Fit each paste to the text before the caret: leading space and
+    /// capitalization after a sentence end.
+    #[serde(default)]
+    pub cursor_context: bool,
+    /// What follows each paste, unless an app profile overrides it.
+    #[serde(default)]
+    pub trailing_behavior: TrailingBehavior,
+    /// Save dictations to today's note instead of pasting them.
+    #[serde(default)]
+    pub notes_target: NotesTarget,
+    /// Replace or rewrite text selected when a dictation starts.
+    #[serde(default)]
+    pub selection_dictation: SelectionDictation,
+    /// Transcribed takes awaiting commit, oldest first. Kept under the same
+    /// lock as `status` so settling to Idle or Buffering is one decision.
+    #[serde(skip)]
+    pub append_takes: Vec<String>,
+    /// Stopped recordings waiting on or running in the transcription queue,
+    /// oldest first. A new take may record while these transcribe; status
+    /// settles to Idle only once the last of them has finished.
+    #[serde(skip)]
+    pub queued_jobs: Vec<u64>,
+    /// Named local-model rewrites, chosen by a "format as <name>" prefix or a
+    /// dictation profile.
+    #[serde(default)]
+    pub rewrite_templates: Vec<crate::rewrite_templates::RewriteTemplate>,
+    /// Ordered find/replace rules applied to live transcripts.
+    #[serde(default)]
+    pub replacement_rules: Vec<crate::replacement_rules::ReplacementRule>,
+    /// Spoken phrases that run an app action instead of being delivered.
+    #[serde(default)]
+    pub dictation_macros: crate::dictation_macros::DictationMacrosSettings,
+    /// Names from allowlisted apps' window titles and selections, added to
+    /// Whisper's prompt.
+    #[serde(default)]
+    pub context_biasing: crate::context_biasing::ContextBiasingSettings,
+}
+
+impl DictationState {
+    /// Status once no take is recording: Processing while queued jobs remain,
+    /// then Buffering while takes await commit.
+    pub fn settled_status(&self) -> DictationStatus {
+        if !self.queued_jobs.is_empty() {
+            DictationStatus::Processing
+        } else if self.append_takes.is_empty() {
+            DictationStatus::Idle
+        } else {
+            DictationStatus::Buffering
+        }
+    }
+
+    /// Take `recording_id` off the transcription queue, whether its job ran
+    /// or was abandoned before it was queued. Unknown ids are a no-op.
+    pub fn finish_job(&mut self, recording_id: u64) {
+        self.queued_jobs.retain(|id| *id != recording_id);
+    }
+}
+
+/// Password managers and credential UIs shipped as the initial do-not-inject
+/// list. Users can remove any of these or add their own (e.g. Terminal).
+pub const DEFAULT_INJECT_BLOCKLIST: &[&str] = &[
+    "com.1password.1password",
+    "com.agilebits.onepassword7",
+    "com.bitwarden.desktop",
+    "com.lastpass.LastPass",
+    "com.dashlane.dashlanephonefinal",
+    "com.apple.keychainaccess",
+    "com.apple.Passwords",
+];
+
+fn default_inject_blocklist() -> Vec<String> {
+    DEFAULT_INJECT_BLOCKLIST
+        .iter()
+        .map(|bundle_id| bundle_id.to_string())
+        .collect()
 }
 
 impl Default for DictationState {
@@ -265,6 +799,7 @@ impl Default for DictationState {
             save_audio: false,
             output_dir: String::new(),
             app_profiles: Vec::new(),
+            dictation_profiles: Vec::new(),
             voice_commands_enabled: false,
             voice_command_pairs: Vec::new(),
             cleanup_enabled: false,
@@ -279,6 +814,32 @@ impl Default for DictationState {
             // actually work on the default Parakeet engine. No-op without vocab.
             correction_enabled: true,
             correction_fuzzy: true,
+            inject_blocklist: default_inject_blocklist(),
+            legacy_automation: false,
+            notification_policy: NotificationPolicy::Off,
+            focus_filter: FocusFilterSettings::default(),
+            interrupted_recording_action: InterruptedRecordingAction::Discard,
+            whisper_decoding: WhisperDecoding::default(),
+            hallucination_filter: true,
+            redaction: RedactionSettings::default(),
+            adaptive_model: AdaptiveModelSettings::default(),
+            latency_budget: LatencyBudgetSettings::default(),
+            dual_model: DualModelSettings::default(),
+            confidence_retry: ConfidenceRetrySettings::default(),
+            alternatives: AlternativesSettings::default(),
+            learn_from_corrections: false,
+            append_mode: false,
+            preview_before_paste: false,
+            cursor_context: false,
+            trailing_behavior: TrailingBehavior::None,
+            notes_target: NotesTarget::Off,
+            selection_dictation: SelectionDictation::Off,
+            append_takes: Vec::new(),
+            queued_jobs: Vec::new(),
+            rewrite_templates: Vec::new(),
+            replacement_rules: Vec::new(),
+            dictation_macros: Default::default(),
+            context_biasing: Default::default(),
         }
     }
 }
@@ -295,7 +856,19 @@ pub struct AppState {
     /// the recorder down until that startup has fully completed.
     pub recording_transition: tokio::sync::Mutex<()>,
     pub model_runtime: ModelRuntimeManager,
+    /// Holds the dual-model draft model, so refining with the chosen model
+    /// doesn't evict it. Quiet: it emits no lifecycle events.
+    pub draft_runtime: ModelRuntimeManager,
     pub last_transcription_at: Mutex<Option<Instant>>,
+    /// Newest history entry, mirrored from the frontend by
+    /// `set_last_transcription` for the paste/copy-last commands.
+    pub last_transcript: Mutex<Option<String>>,
+    /// The newest dictation as it reached the replacement rules, before they
+    /// ran, for `preview_replacement_rules`. Memory only.
+    pub last_rules_input: Mutex<Option<String>>,
+    /// Dictation held by preview mode for `confirm_injection` or
+    /// `discard_injection`.
+    pub pending_injection: Mutex<Option<crate::injection_preview::PendingInjection>>,
     pub idle_timeout_minutes: Mutex<u32>,
     /// Monotonically increasing ID assigned to each recording session.
     pub recording_id: AtomicU64,
@@ -311,9 +884,14 @@ pub struct AppState {
     pub settings_revision: AtomicU64,
     /// The immutable context owned by the active recording generation.
     active_context: Mutex<Option<ActiveDictationContext>>,
+    /// The selection read when recording `.0` started, for
+    /// `selection_dictation`. Never logged; see `selection.rs`.
+    dictation_selection: Mutex<Option<(u64, crate::selection::TransformSnapshot)>>,
     /// Set to the recording_id of a cancelled recording. Pipeline checks
     /// `cancelled_id >= my_id` at checkpoints to discard cancelled work.
     pub cancelled_id: AtomicU64,
+    /// Runs stopped recordings' transcriptions one at a time, oldest first.
+    pub transcription_queue: crate::transcription_queue::TranscriptionQueue,
     /// True while a file transcription is running. Live recording and file
     /// transcription share one Whisper backend, so they must be mutually
     /// exclusive — this flag lets each path refuse to start over the other.
@@ -323,6 +901,12 @@ pub struct AppState {
     /// Aho-Corasick automaton isn't serializable.
     pub correction_matcher:
         Mutex<Option<std::sync::Arc<crate::vocabulary_alias::CorrectionMatcherSet>>>,
+    /// Compiled redaction filter, rebuilt alongside `correction_matcher`.
+    /// `None` when redaction is off or has nothing to match.
+    pub redactor: Mutex<Option<Arc<crate::redaction::Redactor>>>,
+    /// Compiled `DictationState::replacement_rules`, rebuilt whenever the list
+    /// changes. `None` when no rule is enabled.
+    pub replacement_rules: Mutex<Option<Arc<crate::replacement_rules::ReplacementRules>>>,
     /// Enabled replacement rules from the local knowledge repository, ordered
     /// by its deterministic precedence. Refreshed only after repository writes;
     /// recording snapshots never query SQLite in the transform hot path.
@@ -414,6 +998,28 @@ impl AppState {
             .map(|active| Arc::clone(&active.snapshot))
     }
 
+    pub fn set_dictation_selection(
+        &self,
+        recording_id: u64,
+        snapshot: crate::selection::TransformSnapshot,
+    ) {
+        *self.dictation_selection.lock_or_recover() = Some((recording_id, snapshot));
+    }
+
+    /// Take the selection read when `recording_id` started. A newer
+    /// recording's selection is left in place.
+    pub fn take_dictation_selection(
+        &self,
+        recording_id: u64,
+    ) -> Option<crate::selection::TransformSnapshot> {
+        let mut slot = self.dictation_selection.lock_or_recover();
+        if slot.as_ref().is_some_and(|(id, _)| *id == recording_id) {
+            slot.take().map(|(_, snapshot)| snapshot)
+        } else {
+            None
+        }
+    }
+
     /// Clear only the snapshot owned by `recording_id`. A stale guard must not
     /// erase the context installed by a newer recording generation.
     pub fn clear_active_context(&self, recording_id: u64) -> bool {
@@ -557,16 +1163,24 @@ impl Default for AppState {
             dictation: Mutex::new(DictationState::default()),
             recording_transition: tokio::sync::Mutex::new(()),
             model_runtime: ModelRuntimeManager::default(),
+            draft_runtime: ModelRuntimeManager::default(),
             last_transcription_at: Mutex::new(None),
+            last_transcript: Mutex::new(None),
+            last_rules_input: Mutex::new(None),
+            pending_injection: Mutex::new(None),
             idle_timeout_minutes: Mutex::new(5),
             recording_id: AtomicU64::new(0),
             transcript_session_id: AtomicU64::new(0),
             file_run_id: AtomicU64::new(0),
             settings_revision: AtomicU64::new(0),
             active_context: Mutex::new(None),
+            dictation_selection: Mutex::new(None),
             cancelled_id: AtomicU64::new(0),
+            transcription_queue: crate::transcription_queue::TranscriptionQueue::default(),
             file_transcribing: AtomicBool::new(false),
             correction_matcher: Mutex::new(None),
+            redactor: Mutex::new(None),
+            replacement_rules: Mutex::new(None),
             knowledge_replacements: Mutex::new(Arc::new(Vec::new())),
             ide_context: Mutex::new(crate::ide_context::IdeContextStore::default()),
             transform_status: Mutex::new(TransformStatus::default()),
@@ -597,10 +1211,14 @@ mod tests {
             global: &settings,
             prompt: None,
             correction_matcher: None,
+            redactor: None,
+            replacement_rules: None,
             ide_context_index: None,
             vocabulary_version: 0,
             voice_commands: None,
             session_overrides: SessionOverrides::default(),
+            model_installed: |_| true,
+            power_pressure: None,
         }))
     }
 
@@ -775,6 +1393,67 @@ mod tests {
         assert_eq!(state.transform_status(), TransformStatus::Thinking);
     }
 
+    #[test]
+    fn buffered_takes_settle_to_buffering_and_still_accept_a_recording() {
+        let mut dictation = DictationState::default();
+        assert_eq!(dictation.settled_status(), DictationStatus::Idle);
+        dictation.append_takes.push("first thought".to_string());
+        assert_eq!(dictation.settled_status(), DictationStatus::Buffering);
+        assert!(DictationStatus::Buffering.is_settled());
+        assert!(!DictationStatus::Processing.is_settled());
+        assert_eq!(DictationStatus::Buffering.as_str(), "buffering");
+    }
+
+    #[test]
+    fn queued_jobs_hold_processing_until_the_last_one_finishes() {
+        let mut dictation = DictationState::default();
+        dictation.append_takes.push("first thought".to_string());
+        dictation.queued_jobs.extend([3, 4]);
+        assert_eq!(dictation.settled_status(), DictationStatus::Processing);
+        dictation.finish_job(3);
+        assert_eq!(dictation.queued_jobs, vec![4]);
+        assert_eq!(dictation.settled_status(), DictationStatus::Processing);
+        dictation.finish_job(9);
+        dictation.finish_job(4);
+        assert_eq!(dictation.settled_status(), DictationStatus::Buffering);
+    }
+
+    #[test]
+    fn notes_target_parses_known_values_only() {
+        assert_eq!(
+            NotesTarget::parse("no_text_field"),
+            Some(NotesTarget::NoTextField)
+        );
+        assert_eq!(NotesTarget::parse("sometimes"), None);
+        assert_eq!(DictationState::default().notes_target, NotesTarget::Off);
+    }
+
+    #[test]
+    fn selection_dictation_parses_known_values_only() {
+        assert_eq!(
+            SelectionDictation::parse("rewrite"),
+            Some(SelectionDictation::Rewrite)
+        );
+        assert_eq!(SelectionDictation::parse("append"), None);
+        assert_eq!(
+            DictationState::default().selection_dictation,
+            SelectionDictation::Off
+        );
+    }
+
+    #[test]
+    fn interrupted_recording_action_defaults_to_discard() {
+        assert_eq!(
+            InterruptedRecordingAction::parse("transcribe"),
+            Some(InterruptedRecordingAction::Transcribe)
+        );
+        assert_eq!(InterruptedRecordingAction::parse("keep"), None);
+        assert_eq!(
+            DictationState::default().interrupted_recording_action,
+            InterruptedRecordingAction::Discard
+        );
+    }
+
     #[test]
     fn transform_status_serde_form_matches_as_str() {
         // Regression guard: `#[serde(rename_all = "snake_case")]` must produce
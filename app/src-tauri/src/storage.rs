@@ -0,0 +1,550 @@
+//! Disk space preflight and a breakdown of what Murmur keeps on disk.
+//!
+//! `download_model` and turning on "save audio" first compare the free space
+//! on the volume they write to with what they need plus [`SAFETY_MARGIN_BYTES`],
+//! and fail before writing anything. The command returns a plain message;
+//! the numbers go out as a `disk-space-insufficient` event so the frontend can
+//! point at the storage report. Free space comes from `statvfs`, so other
+//! platforms skip the check.
+//!
+//! `get_disk_usage_report` totals the four kinds of data Murmur stores and
+//! `purge_storage` empties one of them. History is only the encrypted
+//! `history.enc`; plaintext history lives in the webview's localStorage, which
+//! the frontend measures and clears itself.
+
+use crate::model_runtime::{self, InstallKind, InstallState};
+use crate::transcriber::{self, TranscriptionBackend};
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{Emitter, Manager};
+
+const MB: u64 = 1024 * 1024;
+/// Left free on top of what a write needs, so the system and other apps
+/// don't run out because of Murmur.
+pub const SAFETY_MARGIN_BYTES: u64 = 512 * MB;
+/// Audio saving writes about 115 MB per hour; this covers a few hours.
+const RECORDINGS_BYTES: u64 = 512 * MB;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageCategory {
+    /// Transcription, VAD, and wake word models.
+    Models,
+    /// App, frontend, event, and transcript logs.
+    Logs,
+    /// Saved `murmur-*.wav` files and an unfinished recording from a crash.
+    Recordings,
+    /// Encrypted history.
+    History,
+}
+
+impl StorageCategory {
+    const ALL: [Self; 4] = [Self::Models, Self::Logs, Self::Recordings, Self::History];
+}
+
+/// Payload of `disk-space-insufficient`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpaceShortfall {
+    /// A model name, or `recordings`.
+    pub purpose: String,
+    /// What the write needs, including the safety margin.
+    pub required_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl std::fmt::Display for DiskSpaceShortfall {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "Not enough disk space: this needs {} free and {} is available. \
+             Free up space, or remove old Murmur files in Settings > Storage.",
+            format_bytes(self.required_bytes),
+            format_bytes(self.available_bytes)
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * MB {
+        format!("{:.1} GB", bytes as f64 / (1024 * MB) as f64)
+    } else {
+        format!("{} MB", bytes.div_ceil(MB))
+    }
+}
+
+/// Bytes from a catalog size like `~1.5 GB` or `~470 MB`. Zero for anything
+/// else (custom models).
+fn parse_size(size: &str) -> u64 {
+    let size = size.trim().trim_start_matches('~');
+    let Some((number, unit)) = size.split_once(' ') else {
+        return 0;
+    };
+    let Ok(number) = number.parse::<f64>() else {
+        return 0;
+    };
+    let unit_bytes = match unit {
+        "GB" => 1024 * MB,
+        "MB" => MB,
+        _ => return 0,
+    };
+    (number * unit_bytes as f64) as u64
+}
+
+/// Whether a downloaded file of `bytes` can be `model_name`'s: between half
+/// and twice its catalog size, which is only approximate. Models without a
+/// catalog size pass.
+pub(crate) fn matches_catalog_size(model_name: &str, bytes: u64) -> bool {
+    let expected = model_runtime::model_definition(model_name)
+        .map(|definition| parse_size(definition.size))
+        .unwrap_or(0);
+    expected == 0 || (expected / 2..=expected * 2).contains(&bytes)
+}
+
+/// Disk space a model install needs. Parakeet bundles are downloaded as an
+/// archive and extracted next to it, so both are on disk at once.
+fn model_install_bytes(size: &str, install_kind: InstallKind) -> u64 {
+    let bytes = parse_size(size);
+    match install_kind {
+        InstallKind::Parakeet => bytes * 2,
+        InstallKind::Whisper | InstallKind::Coreml => bytes,
+        InstallKind::External | InstallKind::Cloud => 0,
+    }
+}
+
+fn shortfall(purpose: &str, needed: u64, available: Option<u64>) -> Option<DiskSpaceShortfall> {
+    let available = available?;
+    let required = needed + SAFETY_MARGIN_BYTES;
+    (available < required).then(|| DiskSpaceShortfall {
+        purpose: purpose.to_string(),
+        required_bytes: required,
+        available_bytes: available,
+    })
+}
+
+/// Free space for unprivileged writes on the volume holding `path`, or the
+/// nearest ancestor that exists.
+#[cfg(unix)]
+// `statvfs` field widths differ between macOS and Linux.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn available_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let existing = path.ancestors().find(|ancestor| ancestor.exists())?;
+    let path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn available_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Fail when the volume holding `path` can't take `needed` bytes plus the
+/// safety margin. Unknown free space passes.
+fn ensure_space(
+    app: &tauri::AppHandle,
+    purpose: &str,
+    path: &Path,
+    needed: u64,
+) -> Result<(), String> {
+    let Some(shortfall) = shortfall(purpose, needed, available_bytes(path)) else {
+        return Ok(());
+    };
+    tracing::warn!(
+        target: "system",
+        purpose,
+        required_mb = shortfall.required_bytes / MB,
+        available_mb = shortfall.available_bytes / MB,
+        "disk space preflight failed"
+    );
+    let message = shortfall.to_string();
+    let _ = app.emit("disk-space-insufficient", shortfall);
+    Err(message)
+}
+
+/// Preflight for downloading `model_name` into `models_dir`.
+pub(crate) fn ensure_model_space(
+    app: &tauri::AppHandle,
+    model_name: &str,
+    models_dir: &Path,
+) -> Result<(), String> {
+    let definition = model_runtime::model_definition(model_name)?;
+    let needed = model_install_bytes(definition.size, definition.install_kind);
+    ensure_space(app, model_name, models_dir, needed)
+}
+
+/// Preflight for turning on audio saving into `output_dir`.
+pub(crate) fn ensure_recording_space(
+    app: &tauri::AppHandle,
+    output_dir: &str,
+) -> Result<(), String> {
+    let dir = crate::file_output::output_dir_path(output_dir)?;
+    ensure_space(app, "recordings", &dir, RECORDINGS_BYTES)
+}
+
+/// One category of `get_disk_usage_report`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryUsage {
+    pub category: StorageCategory,
+    pub bytes: u64,
+    pub files: u64,
+    /// Where the category lives, for "Show in Finder".
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageReport {
+    pub categories: Vec<CategoryUsage>,
+    pub total_bytes: u64,
+    /// Free space on the volume holding the models.
+    pub available_bytes: Option<u64>,
+}
+
+/// Files and bytes in `path` whose names pass `include`, descending into
+/// subdirectories only when `recursive`. Symlinks are counted as themselves,
+/// not followed.
+fn usage_in(path: &Path, include: &dyn Fn(&str) -> bool, recursive: bool) -> (u64, u64) {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return (0, 0);
+    };
+    if !metadata.is_dir() {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        return if include(&name) {
+            (1, metadata.len())
+        } else {
+            (0, 0)
+        };
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| recursive || !is_dir_entry(entry))
+                .fold((0, 0), |(files, bytes), entry| {
+                    let (more_files, more_bytes) = usage_in(&entry.path(), include, recursive);
+                    (files + more_files, bytes + more_bytes)
+                })
+        })
+        .unwrap_or((0, 0))
+}
+
+fn is_dir_entry(entry: &std::fs::DirEntry) -> bool {
+    entry.file_type().is_ok_and(|file_type| file_type.is_dir())
+}
+
+/// Where each category lives: one directory or file, plus what to count in it.
+struct Location {
+    paths: Vec<PathBuf>,
+    include: fn(&str) -> bool,
+    /// Whether subdirectories belong to the category. The recordings folder
+    /// is the user's own, so only Murmur's files directly inside it count.
+    recursive: bool,
+}
+
+fn any_file(_name: &str) -> bool {
+    true
+}
+
+fn recording_file(name: &str) -> bool {
+    crate::file_output::is_recording_file(name) || name == crate::recording_journal::ORPHAN_FILE
+}
+
+pub(crate) fn models_dir() -> Result<PathBuf, String> {
+    transcriber::WhisperBackend::new().models_dir()
+}
+
+fn logs_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("local-dictation").join("logs"))
+}
+
+fn location(
+    app: &tauri::AppHandle,
+    state: &crate::State,
+    category: StorageCategory,
+) -> Result<Location, String> {
+    Ok(match category {
+        StorageCategory::Models => Location {
+            paths: vec![models_dir()?],
+            include: any_file,
+            recursive: true,
+        },
+        StorageCategory::Logs => Location {
+            paths: logs_dir().into_iter().collect(),
+            include: any_file,
+            recursive: true,
+        },
+        StorageCategory::Recordings => {
+            let output_dir = state
+                .app_state
+                .dictation
+                .lock_or_recover()
+                .output_dir
+                .clone();
+            let mut paths = vec![crate::file_output::output_dir_path(&output_dir)?];
+            paths.extend(crate::recording_journal::orphan_file());
+            Location {
+                paths,
+                include: recording_file,
+                recursive: false,
+            }
+        }
+        StorageCategory::History => Location {
+            paths: vec![crate::history_store::history_path(app)?],
+            include: any_file,
+            recursive: false,
+        },
+    })
+}
+
+fn usage(app: &tauri::AppHandle, state: &crate::State, category: StorageCategory) -> CategoryUsage {
+    let Ok(location) = location(app, state, category) else {
+        return CategoryUsage {
+            category,
+            bytes: 0,
+            files: 0,
+            path: None,
+        };
+    };
+    let (files, bytes) = location
+        .paths
+        .iter()
+        .map(|path| usage_in(path, &location.include, location.recursive))
+        .fold((0, 0), |(files, bytes), (more_files, more_bytes)| {
+            (files + more_files, bytes + more_bytes)
+        });
+    CategoryUsage {
+        category,
+        bytes,
+        files,
+        path: location
+            .paths
+            .first()
+            .map(|path| path.to_string_lossy().into_owned()),
+    }
+}
+
+/// What Murmur stores, by category, and the free space left. The folders are
+/// walked off the async runtime.
+#[tauri::command]
+pub async fn get_disk_usage_report(
+    app_handle: tauri::AppHandle,
+) -> Result<DiskUsageReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<crate::State>();
+        let categories: Vec<CategoryUsage> = StorageCategory::ALL
+            .into_iter()
+            .map(|category| usage(&app_handle, &state, category))
+            .collect();
+        DiskUsageReport {
+            total_bytes: categories.iter().map(|usage| usage.bytes).sum(),
+            categories,
+            available_bytes: models_dir().ok().and_then(|dir| available_bytes(&dir)),
+        }
+    })
+    .await
+    .map_err(|e| format!("Disk usage task failed: {}", e))
+}
+
+/// Result of `purge_storage`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeResult {
+    pub category: StorageCategory,
+    pub removed_files: u64,
+    pub freed_bytes: u64,
+}
+
+/// Delete what `include` matches in `path`, descending into subdirectories
+/// only when `recursive`. Subdirectories left empty are removed; `path` itself
+/// never is.
+fn purge_in(path: &Path, include: &dyn Fn(&str) -> bool, recursive: bool) -> (u64, u64) {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return (0, 0);
+    };
+    if !metadata.is_dir() {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        if include(&name) && std::fs::remove_file(path).is_ok() {
+            return (1, metadata.len());
+        }
+        return (0, 0);
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries.flatten().fold((0, 0), |(files, bytes), entry| {
+                let is_dir = is_dir_entry(&entry);
+                if is_dir && !recursive {
+                    return (files, bytes);
+                }
+                let (more_files, more_bytes) = purge_in(&entry.path(), include, recursive);
+                if is_dir {
+                    // Fails, harmlessly, when something was kept.
+                    let _ = std::fs::remove_dir(entry.path());
+                }
+                (files + more_files, bytes + more_bytes)
+            })
+        })
+        .unwrap_or((0, 0))
+}
+
+/// Delete every file in one storage category. Models can't be removed while a
+/// dictation or install is running; the active model is unloaded first. The
+/// files are deleted off the async runtime.
+#[tauri::command]
+pub async fn purge_storage(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::State>,
+    category: StorageCategory,
+) -> Result<PurgeResult, String> {
+    let location = location(&app_handle, &state, category)?;
+    if category == StorageCategory::Models {
+        let status = state.app_state.dictation.lock_or_recover().status;
+        if !status.is_settled() {
+            return Err("Models can't be removed while dictating".to_string());
+        }
+        let installing = state.app_state.model_runtime.catalog().iter().any(|model| {
+            matches!(
+                model.install_state,
+                InstallState::Installing | InstallState::Validating
+            )
+        });
+        if installing {
+            return Err("Wait for model downloads to finish before removing models".to_string());
+        }
+        state.app_state.model_runtime.unload(
+            Some(&app_handle),
+            model_runtime::UnloadReason::StoragePurged,
+        )?;
+    }
+
+    let Location {
+        paths,
+        include,
+        recursive,
+    } = location;
+    let (removed_files, freed_bytes) = tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .iter()
+            .map(|path| purge_in(path, &include, recursive))
+            .fold((0, 0), |(files, bytes), (more_files, more_bytes)| {
+                (files + more_files, bytes + more_bytes)
+            })
+    })
+    .await
+    .map_err(|e| format!("Storage purge task failed: {}", e))?;
+
+    match category {
+        StorageCategory::Models => {
+            for model in state.app_state.model_runtime.catalog() {
+                if !model_runtime::model_installed(&model.model_name) {
+                    let _ = state.app_state.model_runtime.set_install_state(
+                        Some(&app_handle),
+                        &model.model_name,
+                        InstallState::NotInstalled,
+                    );
+                }
+            }
+        }
+        StorageCategory::Logs => crate::telemetry::clear_event_history(),
+        StorageCategory::Recordings | StorageCategory::History => {}
+    }
+    tracing::info!(
+        target: "system",
+        category = ?category,
+        removed_files,
+        freed_mb = freed_bytes / MB,
+        "storage purged"
+    );
+    Ok(PurgeResult {
+        category,
+        removed_files,
+        freed_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_sizes_parse_and_parakeet_counts_twice() {
+        assert_eq!(parse_size("~470 MB"), 470 * MB);
+        assert_eq!(parse_size("~1.5 GB"), 1536 * MB);
+        assert_eq!(parse_size("Custom"), 0);
+        assert_eq!(
+            model_install_bytes("~1.2 GB", InstallKind::Parakeet),
+            2 * parse_size("~1.2 GB")
+        );
+        assert_eq!(model_install_bytes("~75 MB", InstallKind::Whisper), 75 * MB);
+    }
+
+    #[test]
+    fn downloads_are_checked_against_the_catalog_size() {
+        assert!(matches_catalog_size("base.en", 148 * MB));
+        assert!(!matches_catalog_size("base.en", 20 * MB));
+        assert!(!matches_catalog_size("base.en", 400 * MB));
+        assert!(matches_catalog_size("not-in-catalog", 1));
+    }
+
+    #[test]
+    fn shortfall_adds_the_margin_and_skips_unknown_space() {
+        assert_eq!(shortfall("base.en", 150 * MB, None), None);
+        assert_eq!(shortfall("base.en", 150 * MB, Some(10 * 1024 * MB)), None);
+        let short = shortfall("large-v3-turbo", 1536 * MB, Some(1024 * MB)).unwrap();
+        assert_eq!(short.required_bytes, 1536 * MB + SAFETY_MARGIN_BYTES);
+        assert_eq!(
+            short.to_string(),
+            "Not enough disk space: this needs 2.0 GB free and 1.0 GB is available. \
+             Free up space, or remove old Murmur files in Settings > Storage."
+        );
+    }
+
+    #[test]
+    fn usage_and_purge_only_touch_matching_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("murmur-0001.wav"), [0u8; 10]).unwrap();
+        std::fs::write(dir.path().join("murmur-0001.txt"), [0u8; 4]).unwrap();
+        std::fs::write(dir.path().join("notes.wav"), [0u8; 3]).unwrap();
+        let nested = dir.path().join("2026");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("murmur-0002.wav"), [0u8; 5]).unwrap();
+
+        let include = recording_file;
+        assert_eq!(usage_in(dir.path(), &include, false), (1, 10));
+        assert_eq!(usage_in(dir.path(), &include, true), (2, 15));
+        assert_eq!(usage_in(dir.path(), &any_file, true), (4, 22));
+        assert_eq!(purge_in(dir.path(), &include, false), (1, 10));
+        assert!(dir.path().join("murmur-0001.txt").exists());
+        assert!(dir.path().join("notes.wav").exists());
+        assert!(nested.join("murmur-0002.wav").exists());
+        assert_eq!(
+            usage_in(&dir.path().join("missing"), &any_file, true),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn recursive_purge_removes_emptied_subdirectories() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("parakeet");
+        std::fs::create_dir(&bundle).unwrap();
+        std::fs::write(bundle.join("encoder.onnx"), [0u8; 4]).unwrap();
+        std::fs::write(dir.path().join("ggml-base.en.bin"), [0u8; 6]).unwrap();
+
+        assert_eq!(purge_in(dir.path(), &any_file, true), (2, 10));
+        assert!(!bundle.exists());
+        assert!(dir.path().exists());
+    }
+}
@@ -0,0 +1,647 @@
+//! Support bundles: one zip of self-diagnostics to attach to a GitHub issue.
+//!
+//! The user picks which sections go in, after Settings has described each
+//! one; nothing is collected for a section they left out. The bundle is
+//! written to the Desktop (Downloads or home when there is none) and never
+//! leaves the machine on its own. `manifest.json` is always included and
+//! records the app version and which sections were chosen. Paths under the
+//! home directory are written as `~` in the log section.
+//!
+//! `report_issue` turns a description into a GitHub issue with an anonymized
+//! [`SupportSummary`] under it: versions, hardware class, permission states,
+//! model names, and the last dictation's phase timings, never paths, device
+//! names, log lines, or transcript text. It opens a pre-filled new-issue page,
+//! or writes the issue and a copy of the bundle into a folder the user picks.
+
+use crate::State;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+const ISSUE_URL: &str = "https://github.com/georgenijo/murmur-app/issues/new";
+/// GitHub refuses new-issue URLs much longer than this; the description is
+/// shortened to fit.
+const MAX_ISSUE_URL_LEN: usize = 8_000;
+const MAX_TITLE_CHARS: usize = 80;
+const BUNDLE_PREFIX: &str = "murmur-support-";
+
+/// Pretty-log lines included in the `logs` section.
+const LOG_LINES: usize = 500;
+/// Performance runs included in the `metrics` section.
+const METRIC_RUNS: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BundleSection {
+    /// OS version, architecture, hardware model, chip, and memory.
+    System,
+    /// Microphone, Accessibility, Input Monitoring, and Automation states.
+    Permissions,
+    /// The model catalog's install states and the files in the models folder.
+    Models,
+    /// Recent performance runs, the last pipeline trace, and this week's
+    /// usage totals.
+    Metrics,
+    /// The last 500 lines of the app log, which can name apps, devices, and
+    /// folders.
+    Logs,
+    /// Keyboard listener health and the last keyboard diagnostics recording.
+    Keyboard,
+}
+
+impl BundleSection {
+    fn file_name(self) -> &'static str {
+        match self {
+            Self::System => "system.json",
+            Self::Permissions => "permissions.json",
+            Self::Models => "models.json",
+            Self::Metrics => "metrics.json",
+            Self::Logs => "logs.txt",
+            Self::Keyboard => "keyboard.json",
+        }
+    }
+}
+
+/// What `generate_support_bundle` wrote.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportBundle {
+    pub path: String,
+    pub sections: Vec<BundleSection>,
+    pub bytes: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest<'a> {
+    app_version: &'static str,
+    created_at: String,
+    sections: &'a [BundleSection],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelFile {
+    name: String,
+    bytes: u64,
+}
+
+/// Entries directly inside `dir`, with a folder's size summed over its files.
+fn model_files(dir: &Path) -> Vec<ModelFile> {
+    fn size(path: &Path) -> u64 {
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if meta.is_dir() => std::fs::read_dir(path)
+                .map(|entries| entries.flatten().map(|entry| size(&entry.path())).sum())
+                .unwrap_or(0),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        }
+    }
+    let mut files: Vec<ModelFile> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| ModelFile {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    bytes: size(&entry.path()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    files
+}
+
+/// Replace the home directory in `text` with `~`.
+fn redact_home(text: &str, home: Option<&Path>) -> String {
+    match home.and_then(Path::to_str).filter(|home| home.len() > 1) {
+        Some(home) => text.replace(home, "~"),
+        None => text.to_string(),
+    }
+}
+
+fn to_json(value: &impl Serialize) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(value).map_err(|e| format!("Failed to encode support bundle: {}", e))
+}
+
+fn collect_section(section: BundleSection, state: &State) -> Result<Vec<u8>, String> {
+    match section {
+        BundleSection::System => to_json(&crate::benchmark::benchmark_environment()),
+        BundleSection::Permissions => to_json(&crate::permissions::report()),
+        BundleSection::Models => to_json(&serde_json::json!({
+            "catalog": state.app_state.model_runtime.catalog(),
+            "files": crate::storage::models_dir()
+                .map(|dir| model_files(&dir))
+                .unwrap_or_default(),
+        })),
+        BundleSection::Metrics => to_json(&serde_json::json!({
+            "performanceRuns": state.performance.list(METRIC_RUNS).ok(),
+            "lastPipelineTrace": crate::pipeline_trace::get_last_pipeline_trace(),
+            "week": crate::dictation_stats::get_dictation_stats(
+                crate::dictation_stats::StatsRange::Week,
+                None,
+            ),
+        })),
+        BundleSection::Logs => Ok(redact_home(
+            &crate::telemetry::read_pretty_log_tail(LOG_LINES),
+            dirs::home_dir().as_deref(),
+        )
+        .into_bytes()),
+        BundleSection::Keyboard => to_json(&serde_json::json!({
+            "listenerDegraded": crate::keyboard::listener_degraded(),
+            "lastRecording": crate::keyboard::last_keyboard_diagnostics(),
+        })),
+    }
+}
+
+/// Write `files` into a new zip at `path`, uncompressed.
+fn write_zip(path: &Path, files: &[(&str, Vec<u8>)]) -> Result<u64, String> {
+    let fail = |e: &dyn std::fmt::Display| format!("Failed to write support bundle: {}", e);
+    let file = std::fs::File::create(path).map_err(|e| fail(&e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for (name, contents) in files {
+        zip.start_file(*name, options).map_err(|e| fail(&e))?;
+        zip.write_all(contents).map_err(|e| fail(&e))?;
+    }
+    let file = zip.finish().map_err(|e| fail(&e))?;
+    file.metadata().map(|meta| meta.len()).map_err(|e| fail(&e))
+}
+
+fn bundle_dir() -> Option<PathBuf> {
+    dirs::desktop_dir()
+        .or_else(dirs::download_dir)
+        .or_else(dirs::home_dir)
+}
+
+/// Collect the `sections` the user agreed to share into
+/// `murmur-support-<timestamp>.zip` on the Desktop. Collection walks the
+/// models folder and reads the log, so it runs off the async runtime with
+/// the zip write.
+#[tauri::command]
+pub async fn generate_support_bundle(
+    app_handle: tauri::AppHandle,
+    sections: Vec<BundleSection>,
+) -> Result<SupportBundle, String> {
+    let mut sections = sections;
+    sections.sort();
+    sections.dedup();
+    if sections.is_empty() {
+        return Err("Choose at least one section to include.".to_string());
+    }
+    let now = chrono::Local::now();
+    let manifest = Manifest {
+        app_version: env!("CARGO_PKG_VERSION"),
+        created_at: now.to_rfc3339(),
+        sections: &sections,
+    };
+    let manifest = to_json(&manifest)?;
+    let dir = bundle_dir().ok_or_else(|| "Could not find the Desktop folder.".to_string())?;
+    let path = dir.join(format!(
+        "{BUNDLE_PREFIX}{}.zip",
+        now.format("%Y%m%d-%H%M%S")
+    ));
+    let bytes = tauri::async_runtime::spawn_blocking({
+        let path = path.clone();
+        let sections = sections.clone();
+        move || {
+            let state = app_handle.state::<State>();
+            let mut files = vec![("manifest.json", manifest)];
+            for section in sections {
+                files.push((section.file_name(), collect_section(section, &state)?));
+            }
+            write_zip(&path, &files)
+        }
+    })
+    .await
+    .map_err(|e| format!("Support bundle task failed: {}", e))??;
+    tracing::info!(target: "system", sections = sections.len(), bytes, "support bundle written");
+    Ok(SupportBundle {
+        path: path.to_string_lossy().into_owned(),
+        sections,
+        bytes,
+    })
+}
+
+/// What `report_issue` attaches to an issue.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportSummary {
+    pub app_version: &'static str,
+    pub environment: crate::benchmark::BenchmarkEnvironment,
+    pub permissions: crate::permissions::PermissionReport,
+    pub installed_models: Vec<String>,
+    pub keyboard_degraded: Option<crate::keyboard::DegradedReason>,
+    pub last_pipeline: Option<crate::pipeline_trace::PipelineTrace>,
+}
+
+impl SupportSummary {
+    fn collect(state: &State) -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION"),
+            environment: crate::benchmark::benchmark_environment(),
+            permissions: crate::permissions::report(),
+            installed_models: state
+                .app_state
+                .model_runtime
+                .catalog()
+                .into_iter()
+                .filter(|model| {
+                    model.install_state == crate::model_runtime::InstallState::Installed
+                })
+                .map(|model| model.model_name)
+                .collect(),
+            keyboard_degraded: crate::keyboard::listener_degraded().map(|degraded| degraded.reason),
+            last_pipeline: crate::pipeline_trace::get_last_pipeline_trace(),
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        let env = &self.environment;
+        let known = |value: &Option<String>| value.clone().unwrap_or_else(|| "unknown".to_string());
+        let permissions = &self.permissions;
+        let models = if self.installed_models.is_empty() {
+            "none".to_string()
+        } else {
+            self.installed_models.join(", ")
+        };
+        let keyboard = match self.keyboard_degraded {
+            Some(reason) => format!("degraded ({:?})", reason),
+            None => "healthy".to_string(),
+        };
+        let last_dictation = match &self.last_pipeline {
+            Some(trace) => {
+                let phases: Vec<String> = trace
+                    .phases
+                    .iter()
+                    .map(|timing| format!("{} {}", timing.phase.as_str(), timing.duration_ms))
+                    .collect();
+                format!("{} ms ({})", trace.total_ms, phases.join(", "))
+            }
+            None => "none this session".to_string(),
+        };
+        format!(
+            "- Murmur: {}\n\
+             - OS: {} {} ({})\n\
+             - Hardware: {}, {}, {}\n\
+             - Permissions: microphone {}, accessibility {}, input monitoring {}, automation {}\n\
+             - Installed models: {}\n\
+             - Keyboard listener: {}\n\
+             - Last dictation: {}\n",
+            self.app_version,
+            env.os,
+            known(&env.os_version),
+            env.architecture,
+            known(&env.hardware_model),
+            known(&env.chip),
+            env.memory_mb
+                .map_or_else(|| "unknown memory".to_string(), |mb| format!("{} MB", mb)),
+            permissions.microphone.as_str(),
+            permissions.accessibility.as_str(),
+            permissions.input_monitoring.as_str(),
+            permissions.automation.as_str(),
+            models,
+            keyboard,
+            last_dictation,
+        )
+    }
+}
+
+/// Where `report_issue` sends the report.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum IssueDestination {
+    /// Open a pre-filled new-issue page in the browser.
+    Github,
+    /// Write the issue and a copy of the bundle into this folder.
+    Folder { path: String },
+}
+
+/// What `report_issue` did.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueReport {
+    /// The new-issue page that was opened.
+    pub url: Option<String>,
+    /// Files written into the chosen folder.
+    pub saved: Vec<String>,
+    pub summary: SupportSummary,
+}
+
+/// First non-empty line of the description, shortened for an issue title.
+fn issue_title(description: &str) -> String {
+    let line = description
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= MAX_TITLE_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(MAX_TITLE_CHARS - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn issue_body(description: &str, summary: &str, bundle_name: Option<&str>) -> String {
+    let attach = bundle_name.map_or_else(String::new, |name| {
+        format!("\n\nSupport bundle: `{}` (attach it to this issue)", name)
+    });
+    format!(
+        "{}{}\n\n<details><summary>Diagnostics</summary>\n\n{}\n</details>\n",
+        description.trim(),
+        attach,
+        summary
+    )
+}
+
+/// The new-issue URL, with the description cut short if the whole URL would
+/// be too long for GitHub.
+fn issue_url(description: &str, summary: &str, bundle_name: Option<&str>) -> tauri::Url {
+    let title = issue_title(description);
+    let description = description.trim();
+    let total = description.chars().count();
+    let mut keep = total;
+    loop {
+        let shown = if keep == total {
+            description.to_string()
+        } else {
+            let cut: String = description.chars().take(keep).collect();
+            format!("{}…\n\n(Description shortened to fit the link.)", cut)
+        };
+        let body = issue_body(&shown, summary, bundle_name);
+        let url = tauri::Url::parse_with_params(
+            ISSUE_URL,
+            [("title", title.as_str()), ("body", body.as_str())],
+        )
+        .expect("issue URL is valid");
+        if url.as_str().len() <= MAX_ISSUE_URL_LEN || keep == 0 {
+            return url;
+        }
+        keep = keep * 3 / 4;
+    }
+}
+
+/// A bundle `generate_support_bundle` wrote, by its path or file name. Only
+/// files of that name in the bundle folder are accepted.
+fn existing_bundle(path_or_name: &str) -> Result<PathBuf, String> {
+    let name = Path::new(path_or_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| name.starts_with(BUNDLE_PREFIX) && name.ends_with(".zip"))
+        .ok_or_else(|| "Unknown support bundle.".to_string())?;
+    let path = bundle_dir()
+        .ok_or_else(|| "Could not find the Desktop folder.".to_string())?
+        .join(name);
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err("The support bundle is no longer on the Desktop.".to_string())
+    }
+}
+
+/// Write the issue as `murmur-issue-<timestamp>.md` into `dir`, with a copy
+/// of `bundle` beside it. Returns the files written.
+fn save_report(dir: &Path, issue: &str, bundle: Option<&Path>) -> Result<Vec<String>, String> {
+    if !dir.is_dir() {
+        return Err("Choose a folder to save the report in.".to_string());
+    }
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let issue_path = dir.join(format!("murmur-issue-{}.md", stamp));
+    std::fs::write(&issue_path, issue).map_err(|e| format!("Failed to save the report: {}", e))?;
+    let mut saved = vec![issue_path.to_string_lossy().into_owned()];
+    if let Some(bundle) = bundle {
+        let copy = dir.join(bundle.file_name().unwrap_or_default());
+        std::fs::copy(bundle, &copy)
+            .map_err(|e| format!("Failed to copy the support bundle: {}", e))?;
+        saved.push(copy.to_string_lossy().into_owned());
+    }
+    Ok(saved)
+}
+
+/// File a bug report: `description` plus the anonymized summary, as a
+/// pre-filled GitHub issue or written into a folder with a copy of `bundle`.
+/// The summary probes permissions and the filesystem, so it and any folder
+/// writes run off the async runtime.
+#[tauri::command]
+pub async fn report_issue(
+    app_handle: tauri::AppHandle,
+    description: String,
+    bundle: Option<String>,
+    destination: IssueDestination,
+) -> Result<IssueReport, String> {
+    if description.trim().is_empty() {
+        return Err("Describe the problem first.".to_string());
+    }
+    let (bundle, summary) = tauri::async_runtime::spawn_blocking({
+        let app_handle = app_handle.clone();
+        move || {
+            let bundle = bundle.as_deref().map(existing_bundle).transpose()?;
+            let summary = SupportSummary::collect(&app_handle.state::<State>());
+            Ok::<_, String>((bundle, summary))
+        }
+    })
+    .await
+    .map_err(|e| format!("Issue report task failed: {}", e))??;
+    let bundle_name = bundle
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().into_owned());
+    let summary_markdown = summary.to_markdown();
+    match destination {
+        IssueDestination::Github => {
+            use tauri_plugin_opener::OpenerExt;
+            let url = issue_url(&description, &summary_markdown, bundle_name.as_deref());
+            app_handle
+                .opener()
+                .open_url(url.as_str(), None::<&str>)
+                .map_err(|e| format!("Failed to open GitHub: {}", e))?;
+            tracing::info!(target: "system", with_bundle = bundle.is_some(), "issue report opened");
+            Ok(IssueReport {
+                url: Some(url.to_string()),
+                saved: Vec::new(),
+                summary,
+            })
+        }
+        IssueDestination::Folder { path } => {
+            let issue = format!(
+                "# {}\n\n{}",
+                issue_title(&description),
+                issue_body(&description, &summary_markdown, bundle_name.as_deref())
+            );
+            let saved = tauri::async_runtime::spawn_blocking(move || {
+                save_report(Path::new(&path), &issue, bundle.as_deref())
+            })
+            .await
+            .map_err(|e| format!("Issue report task failed: {}", e))??;
+            tracing::info!(target: "system", files = saved.len(), "issue report saved to folder");
+            Ok(IssueReport {
+                url: None,
+                saved,
+                summary,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn zip_holds_each_file_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.zip");
+        let bytes = write_zip(
+            &path,
+            &[
+                ("manifest.json", b"{}".to_vec()),
+                ("logs.txt", b"INFO system: app setup".to_vec()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(bytes, std::fs::metadata(&path).unwrap().len());
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut logs = String::new();
+        archive
+            .by_name("logs.txt")
+            .unwrap()
+            .read_to_string(&mut logs)
+            .unwrap();
+        assert_eq!(logs, "INFO system: app setup");
+    }
+
+    #[test]
+    fn model_files_sum_folder_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ggml-base.en.bin"), [0u8; 10]).unwrap();
+        let bundle = dir.path().join("parakeet");
+        std::fs::create_dir(&bundle).unwrap();
+        std::fs::write(bundle.join("encoder.onnx"), [0u8; 4]).unwrap();
+        std::fs::write(bundle.join("tokens.txt"), [0u8; 3]).unwrap();
+
+        assert_eq!(
+            model_files(dir.path()),
+            vec![
+                ModelFile {
+                    name: "ggml-base.en.bin".to_string(),
+                    bytes: 10
+                },
+                ModelFile {
+                    name: "parakeet".to_string(),
+                    bytes: 7
+                },
+            ]
+        );
+        assert!(model_files(&dir.path().join("missing")).is_empty());
+    }
+
+    fn summary() -> SupportSummary {
+        use crate::permissions::{PermissionReport, PermissionState};
+        SupportSummary {
+            app_version: "0.21.2",
+            environment: crate::benchmark::BenchmarkEnvironment {
+                os: "macOS".to_string(),
+                os_version: Some("15.1".to_string()),
+                architecture: "aarch64".to_string(),
+                hardware_model: Some("Mac14,2".to_string()),
+                chip: Some("Apple M2".to_string()),
+                memory_mb: Some(16_384),
+            },
+            permissions: PermissionReport {
+                microphone: PermissionState::Granted,
+                accessibility: PermissionState::Denied,
+                input_monitoring: PermissionState::Granted,
+                automation: PermissionState::Unknown,
+                screen_recording: None,
+            },
+            installed_models: vec!["base.en".to_string()],
+            keyboard_degraded: Some(crate::keyboard::DegradedReason::PermissionRevoked),
+            last_pipeline: None,
+        }
+    }
+
+    #[test]
+    fn summary_markdown_lists_each_fact() {
+        let markdown = summary().to_markdown();
+        assert!(markdown.contains("- Murmur: 0.21.2\n"));
+        assert!(markdown.contains("- OS: macOS 15.1 (aarch64)\n"));
+        assert!(markdown.contains("- Hardware: Mac14,2, Apple M2, 16384 MB\n"));
+        assert!(markdown.contains("accessibility denied"));
+        assert!(markdown.contains("- Installed models: base.en\n"));
+        assert!(markdown.contains("- Keyboard listener: degraded (PermissionRevoked)\n"));
+        assert!(markdown.contains("- Last dictation: none this session\n"));
+    }
+
+    #[test]
+    fn issue_url_prefills_title_and_body_within_the_length_limit() {
+        let url = issue_url(
+            "\nHotkey stuck in processing\nAfter sleep it never finishes.",
+            "- Murmur: 0.21.2\n",
+            Some("murmur-support-20261016-101500.zip"),
+        );
+        let params: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["title"], "Hotkey stuck in processing");
+        assert!(params["body"].starts_with("Hotkey stuck in processing\nAfter sleep"));
+        assert!(params["body"].contains("`murmur-support-20261016-101500.zip`"));
+        assert!(params["body"].contains("- Murmur: 0.21.2"));
+
+        let long = "word ".repeat(5_000);
+        let url = issue_url(&long, "- Murmur: 0.21.2\n", None);
+        assert!(url.as_str().len() <= MAX_ISSUE_URL_LEN);
+        let body = url
+            .query_pairs()
+            .find(|(key, _)| key == "body")
+            .unwrap()
+            .1
+            .into_owned();
+        assert!(body.contains("(Description shortened to fit the link.)"));
+        assert!(body.contains("- Murmur: 0.21.2"));
+    }
+
+    #[test]
+    fn issue_titles_are_shortened() {
+        let title = issue_title(&"a".repeat(200));
+        assert_eq!(title.chars().count(), MAX_TITLE_CHARS);
+        assert!(title.ends_with('…'));
+    }
+
+    #[test]
+    fn reports_are_saved_with_a_copy_of_the_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("murmur-support-20261016-101500.zip");
+        std::fs::write(&bundle, b"zip").unwrap();
+        let out = dir.path().join("out");
+        assert!(save_report(&out, "# Stuck", None).is_err());
+
+        std::fs::create_dir(&out).unwrap();
+        let saved = save_report(&out, "# Stuck", Some(&bundle)).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(std::fs::read_to_string(&saved[0]).unwrap(), "# Stuck");
+        assert_eq!(
+            std::fs::read(out.join("murmur-support-20261016-101500.zip")).unwrap(),
+            b"zip"
+        );
+    }
+
+    #[test]
+    fn only_support_bundles_can_be_attached() {
+        assert!(existing_bundle("/etc/passwd").is_err());
+        assert!(existing_bundle("../murmur-support-1.txt").is_err());
+    }
+
+    #[test]
+    fn log_paths_hide_the_home_directory() {
+        let home = Path::new("/Users/someone");
+        assert_eq!(
+            redact_home("loaded /Users/someone/models/a.bin", Some(home)),
+            "loaded ~/models/a.bin"
+        );
+        assert_eq!(redact_home("/ stays", Some(Path::new("/"))), "/ stays");
+        assert_eq!(redact_home("unchanged", None), "unchanged");
+    }
+}
@@ -0,0 +1,549 @@
+//! System conditions that change how dictation behaves: the active macOS Focus
+//! mode, whether the screen is locked, and thermal / Low Power Mode pressure.
+//!
+//! macOS has no public API for the current Focus, so this polls the Do Not
+//! Disturb store in `~/Library/DoNotDisturb/DB`. Reading it needs Full Disk
+//! Access; without it Focus is reported as unavailable and the Focus settings
+//! have no effect. Only mode identifiers and names are read. Screen lock comes
+//! from the distributed `com.apple.screenIsLocked`/`Unlocked` notifications.
+//! Locking the screen, sleeping, or switching to another user stops any
+//! recording in progress and discards or transcribes it. Thermal state and Low
+//! Power Mode come from `NSProcessInfo` and drive the optional adaptive model
+//! fallback (`AdaptiveModelSettings`).
+
+use crate::state::FocusFilterSettings;
+use crate::MutexExt;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Snapshot sent with `system-status-changed` and returned by
+/// `get_system_status`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemStatus {
+    /// False when the Focus store can't be read (no Full Disk Access).
+    pub focus_available: bool,
+    /// Identifier of the active Focus, e.g. `com.apple.donotdisturb.mode.default`.
+    pub focus_mode: Option<String>,
+    /// Display name of the active Focus, when its configuration is readable.
+    pub focus_name: Option<String>,
+    pub screen_locked: bool,
+    pub thermal_state: ThermalState,
+    pub low_power_mode: bool,
+}
+
+/// `NSProcessInfo.thermalState`. Always nominal off macOS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThermalState {
+    #[default]
+    Nominal,
+    Fair,
+    Serious,
+    Critical,
+}
+
+/// Why the machine should avoid heavy models right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerPressure {
+    /// Thermal state is serious or critical.
+    Thermal,
+    LowPowerMode,
+}
+
+impl PowerPressure {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PowerPressure::Thermal => "thermal",
+            PowerPressure::LowPowerMode => "low_power_mode",
+        }
+    }
+}
+
+/// Heat wins over Low Power Mode: it is the more urgent reason to back off.
+fn pressure_from(thermal: ThermalState, low_power_mode: bool) -> Option<PowerPressure> {
+    if thermal >= ThermalState::Serious {
+        Some(PowerPressure::Thermal)
+    } else if low_power_mode {
+        Some(PowerPressure::LowPowerMode)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn thermal_state() -> ThermalState {
+    use objc2_foundation::{NSProcessInfo, NSProcessInfoThermalState};
+
+    match NSProcessInfo::processInfo().thermalState() {
+        NSProcessInfoThermalState::Fair => ThermalState::Fair,
+        NSProcessInfoThermalState::Serious => ThermalState::Serious,
+        NSProcessInfoThermalState::Critical => ThermalState::Critical,
+        _ => ThermalState::Nominal,
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn thermal_state() -> ThermalState {
+    ThermalState::Nominal
+}
+
+#[cfg(target_os = "macos")]
+fn low_power_mode() -> bool {
+    objc2_foundation::NSProcessInfo::processInfo().isLowPowerModeEnabled()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn low_power_mode() -> bool {
+    false
+}
+
+/// Current thermal / Low Power Mode pressure, read live.
+pub fn power_pressure() -> Option<PowerPressure> {
+    pressure_from(thermal_state(), low_power_mode())
+}
+
+/// A Focus mode the user has configured, for the Settings picker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusMode {
+    pub id: String,
+    pub name: String,
+}
+
+static SCREEN_LOCKED: AtomicBool = AtomicBool::new(false);
+/// Last Focus sample: `None` until the first poll, then `Some(Err(()))` when
+/// the store is unreadable or `Some(Ok(mode))` with the active mode, if any.
+static FOCUS: Mutex<Option<Result<Option<FocusMode>, ()>>> = Mutex::new(None);
+
+/// Identifier of the active Focus from `Assertions.json`. Manually enabled
+/// modes appear as store assertions; the newest one wins.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_focus_assertions(value: &Value) -> Option<String> {
+    value
+        .get("data")?
+        .as_array()?
+        .iter()
+        .filter_map(|entry| entry.get("storeAssertionRecords")?.as_array())
+        .flatten()
+        .filter_map(|record| {
+            record
+                .get("assertionDetails")?
+                .get("assertionDetailsModeIdentifier")?
+                .as_str()
+        })
+        .last()
+        .map(str::to_string)
+}
+
+/// Configured modes from `ModeConfigurations.json`, sorted by name.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_mode_configurations(value: &Value) -> Vec<FocusMode> {
+    let mut modes = value
+        .get("data")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("modeConfigurations")?.as_object())
+        .flat_map(|configurations| configurations.values())
+        .filter_map(|configuration| {
+            let mode = configuration.get("mode")?;
+            let id = mode.get("modeIdentifier")?.as_str()?.trim();
+            if id.is_empty() {
+                return None;
+            }
+            let name = mode
+                .get("name")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .unwrap_or(id);
+            Some(FocusMode {
+                id: id.to_string(),
+                name: name.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+    modes.sort_by(|left, right| left.name.to_lowercase().cmp(&right.name.to_lowercase()));
+    modes.dedup_by(|left, right| left.id == right.id);
+    modes
+}
+
+/// Whether the hotkey should be gated for `focus` under `filter`.
+fn hotkey_gated(filter: &FocusFilterSettings, focus: Option<&str>) -> bool {
+    focus.is_some_and(|mode| filter.disable_hotkey && filter.applies_to(mode))
+}
+
+fn active_focus() -> Option<String> {
+    match &*FOCUS.lock_or_recover() {
+        Some(Ok(Some(mode))) => Some(mode.id.clone()),
+        _ => None,
+    }
+}
+
+pub fn current() -> SystemStatus {
+    let focus = FOCUS.lock_or_recover().clone();
+    let (focus_available, mode) = match focus {
+        Some(Ok(mode)) => (true, mode),
+        _ => (false, None),
+    };
+    SystemStatus {
+        focus_available,
+        focus_mode: mode.as_ref().map(|mode| mode.id.clone()),
+        focus_name: mode.map(|mode| mode.name),
+        screen_locked: SCREEN_LOCKED.load(Ordering::SeqCst),
+        thermal_state: thermal_state(),
+        low_power_mode: low_power_mode(),
+    }
+}
+
+/// Whether notifications are held right now under `filter`.
+pub fn suppresses_notifications(filter: &FocusFilterSettings) -> bool {
+    active_focus().is_some_and(|mode| filter.suppress_notifications && filter.applies_to(&mode))
+}
+
+/// Re-evaluate the hotkey gate after the Focus settings change, without
+/// waiting for the next poll.
+pub fn apply_focus_filter(filter: &FocusFilterSettings) {
+    crate::keyboard::set_focus_gated(hotkey_gated(filter, active_focus().as_deref()));
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::*;
+    use crate::state::{DictationStatus, InterruptedRecordingAction};
+    use crate::State;
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use tauri::{Emitter, Manager};
+
+    const FOCUS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    fn focus_db() -> Option<PathBuf> {
+        Some(dirs::home_dir()?.join("Library/DoNotDisturb/DB"))
+    }
+
+    fn read_json(name: &str) -> std::io::Result<Value> {
+        let path = focus_db()
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?
+            .join(name);
+        let raw = std::fs::read(path)?;
+        serde_json::from_slice(&raw).map_err(std::io::Error::other)
+    }
+
+    pub(super) fn focus_modes() -> Vec<FocusMode> {
+        read_json("ModeConfigurations.json")
+            .map(|value| parse_mode_configurations(&value))
+            .unwrap_or_default()
+    }
+
+    /// `Err` when the store can't be read. A missing assertions file means no
+    /// Focus has been turned on yet.
+    fn sample_focus() -> Result<Option<FocusMode>, ()> {
+        let assertions = match read_json("Assertions.json") {
+            Ok(value) => value,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(_) => return Err(()),
+        };
+        Ok(parse_focus_assertions(&assertions).map(|id| {
+            let name = focus_modes()
+                .into_iter()
+                .find(|mode| mode.id == id)
+                .map(|mode| mode.name)
+                .unwrap_or_else(|| id.clone());
+            FocusMode { id, name }
+        }))
+    }
+
+    fn emit_status(app: &tauri::AppHandle) {
+        let _ = app.emit("system-status-changed", current());
+    }
+
+    fn poll_focus(app: &tauri::AppHandle) {
+        let sample = sample_focus();
+        let changed = {
+            let mut focus = FOCUS.lock_or_recover();
+            let changed = focus.as_ref() != Some(&sample);
+            *focus = Some(sample);
+            changed
+        };
+        let state = app.state::<State>();
+        let filter = state
+            .app_state
+            .dictation
+            .lock_or_recover()
+            .focus_filter
+            .clone();
+        apply_focus_filter(&filter);
+        if changed {
+            let status = current();
+            tracing::info!(
+                target: "system",
+                focus_available = status.focus_available,
+                focus_on = status.focus_mode.is_some(),
+                "focus mode changed"
+            );
+            emit_status(app);
+        }
+    }
+
+    fn on_screen_lock_changed(app: &tauri::AppHandle, locked: bool) {
+        if SCREEN_LOCKED.swap(locked, Ordering::SeqCst) == locked {
+            return;
+        }
+        tracing::info!(target: "system", locked, "screen lock changed");
+        emit_status(app);
+        if locked {
+            interrupt_recording(app, "screen_locked");
+        }
+    }
+
+    /// A recording left running behind a locked screen or a closed lid
+    /// captures whatever the room says next, possibly for hours. Stop it and
+    /// either discard it or transcribe what was said so far, per
+    /// `interrupted_recording_action`. A transcription already in flight
+    /// finishes either way.
+    fn interrupt_recording(app: &tauri::AppHandle, reason: &'static str) {
+        let action = {
+            let state = app.state::<State>();
+            let dictation = state.app_state.dictation.lock_or_recover();
+            if dictation.status != DictationStatus::Recording {
+                return;
+            }
+            dictation.interrupted_recording_action
+        };
+        tracing::info!(target: "pipeline", reason, action = ?action, "interrupting active recording");
+        let handle = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = match action {
+                InterruptedRecordingAction::Discard => {
+                    crate::commands::recording::cancel_native_recording(
+                        handle.clone(),
+                        handle.state(),
+                    )
+                    .await
+                }
+                InterruptedRecordingAction::Transcribe => {
+                    crate::commands::recording::stop_native_recording(
+                        handle.clone(),
+                        handle.state(),
+                    )
+                    .await
+                    .map(|_| ())
+                }
+            };
+            if let Err(error) = result {
+                tracing::warn!(target: "pipeline", reason, "interrupted recording stop failed: {}", error);
+            }
+        });
+    }
+
+    /// Add an app-lifetime observer for `name` on `center`, delivered on the
+    /// main queue.
+    fn observe(
+        center: &objc2_foundation::NSNotificationCenter,
+        name: &str,
+        handler: impl Fn() + 'static,
+    ) {
+        use objc2_foundation::{NSNotification, NSNotificationName, NSOperationQueue};
+
+        let name = NSNotificationName::from_str(name);
+        let block =
+            block2::RcBlock::new(move |_notification: std::ptr::NonNull<NSNotification>| {
+                handler();
+            });
+        unsafe {
+            let observer = center.addObserverForName_object_queue_usingBlock(
+                Some(&name),
+                None,
+                Some(&NSOperationQueue::mainQueue()),
+                &block,
+            );
+            // App-lifetime observer — intentionally leak to avoid premature deallocation
+            std::mem::forget(observer);
+        }
+    }
+
+    fn on_power_state_changed(app: &tauri::AppHandle) {
+        let status = current();
+        tracing::info!(
+            target: "system",
+            thermal_state = ?status.thermal_state,
+            low_power_mode = status.low_power_mode,
+            "power state changed"
+        );
+        emit_status(app);
+    }
+
+    fn register_observers(app: &tauri::AppHandle) {
+        let center = objc2_foundation::NSDistributedNotificationCenter::defaultCenter();
+        for (name, locked) in [
+            ("com.apple.screenIsLocked", true),
+            ("com.apple.screenIsUnlocked", false),
+        ] {
+            let handle = app.clone();
+            observe(&center, name, move || {
+                on_screen_lock_changed(&handle, locked)
+            });
+        }
+
+        // Lid closed or idle sleep, displays asleep, or fast user switching
+        // away from this session.
+        let workspace_center = objc2_app_kit::NSWorkspace::sharedWorkspace().notificationCenter();
+        for (name, reason) in [
+            ("NSWorkspaceWillSleepNotification", "system_sleep"),
+            ("NSWorkspaceScreensDidSleepNotification", "screens_slept"),
+            (
+                "NSWorkspaceSessionDidResignActiveNotification",
+                "session_resigned",
+            ),
+        ] {
+            let handle = app.clone();
+            observe(&workspace_center, name, move || {
+                interrupt_recording(&handle, reason)
+            });
+        }
+
+        let local_center = objc2_foundation::NSNotificationCenter::defaultCenter();
+        for name in [
+            "NSProcessInfoThermalStateDidChangeNotification",
+            "NSProcessInfoPowerStateDidChangeNotification",
+        ] {
+            let handle = app.clone();
+            observe(&local_center, name, move || on_power_state_changed(&handle));
+        }
+    }
+
+    pub(super) fn start(app: &tauri::AppHandle) {
+        register_observers(app);
+        let handle = app.clone();
+        if let Err(error) = std::thread::Builder::new()
+            .name("focus-monitor".to_string())
+            .spawn(move || loop {
+                poll_focus(&handle);
+                std::thread::sleep(FOCUS_POLL_INTERVAL);
+            })
+        {
+            tracing::warn!(target: "system", "focus monitor failed to start: {}", error);
+        }
+    }
+}
+
+/// Start the Focus poller and the screen-lock observer. Call once from setup.
+#[cfg(target_os = "macos")]
+pub(crate) fn start(app: &tauri::AppHandle) {
+    macos::start(app);
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn start(_app: &tauri::AppHandle) {}
+
+#[tauri::command]
+pub fn get_system_status() -> SystemStatus {
+    current()
+}
+
+/// Focus modes configured on this Mac, for the Settings picker. Empty when
+/// the Focus store is unreadable.
+#[tauri::command]
+#[cfg(target_os = "macos")]
+pub fn list_focus_modes() -> Vec<FocusMode> {
+    macos::focus_modes()
+}
+
+#[tauri::command]
+#[cfg(not(target_os = "macos"))]
+pub fn list_focus_modes() -> Vec<FocusMode> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn assertions_yield_the_newest_mode() {
+        let assertions = json!({
+            "data": [{
+                "storeAssertionRecords": [
+                    { "assertionDetails": { "assertionDetailsModeIdentifier": "com.apple.donotdisturb.mode.default" } },
+                    { "assertionDetails": { "assertionDetailsModeIdentifier": "com.apple.focus.work" } }
+                ]
+            }]
+        });
+        assert_eq!(
+            parse_focus_assertions(&assertions).as_deref(),
+            Some("com.apple.focus.work")
+        );
+        assert_eq!(parse_focus_assertions(&json!({ "data": [{}] })), None);
+        assert_eq!(parse_focus_assertions(&json!({})), None);
+    }
+
+    #[test]
+    fn mode_configurations_are_named_sorted_and_deduplicated() {
+        let configurations = json!({
+            "data": [{
+                "modeConfigurations": {
+                    "a": { "mode": { "name": "Work", "modeIdentifier": "com.apple.focus.work" } },
+                    "b": { "mode": { "name": "Do Not Disturb", "modeIdentifier": "com.apple.donotdisturb.mode.default" } },
+                    "c": { "mode": { "modeIdentifier": "com.example.unnamed" } },
+                    "d": { "mode": { "name": "Work", "modeIdentifier": "com.apple.focus.work" } },
+                    "e": { "mode": { "name": "Broken" } }
+                }
+            }]
+        });
+        let modes = parse_mode_configurations(&configurations);
+        let ids = modes
+            .iter()
+            .map(|mode| mode.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "com.example.unnamed",
+                "com.apple.donotdisturb.mode.default",
+                "com.apple.focus.work"
+            ]
+        );
+        assert_eq!(modes[0].name, "com.example.unnamed");
+    }
+
+    #[test]
+    fn hotkey_gate_needs_the_toggle_and_a_matching_mode() {
+        let mut filter = FocusFilterSettings::default();
+        assert!(!hotkey_gated(&filter, Some("com.apple.focus.work")));
+
+        filter.disable_hotkey = true;
+        assert!(hotkey_gated(&filter, Some("com.apple.focus.work")));
+        assert!(!hotkey_gated(&filter, None));
+
+        filter.modes = vec!["com.apple.donotdisturb.mode.default".to_string()];
+        assert!(!hotkey_gated(&filter, Some("com.apple.focus.work")));
+        assert!(hotkey_gated(
+            &filter,
+            Some("com.apple.donotdisturb.mode.default")
+        ));
+    }
+
+    #[test]
+    fn pressure_needs_serious_heat_or_low_power_and_prefers_heat() {
+        assert_eq!(pressure_from(ThermalState::Nominal, false), None);
+        assert_eq!(pressure_from(ThermalState::Fair, false), None);
+        assert_eq!(
+            pressure_from(ThermalState::Fair, true),
+            Some(PowerPressure::LowPowerMode)
+        );
+        assert_eq!(
+            pressure_from(ThermalState::Serious, true),
+            Some(PowerPressure::Thermal)
+        );
+        assert_eq!(
+            pressure_from(ThermalState::Critical, false),
+            Some(PowerPressure::Thermal)
+        );
+    }
+}
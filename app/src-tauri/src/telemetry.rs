@@ -2,6 +2,7 @@
 
 use std::collections::VecDeque;
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use tauri::Emitter;
 
@@ -27,6 +28,31 @@ fn get_event_buffer() -> Arc<Mutex<VecDeque<AppEvent>>> {
         .clone()
 }
 
+// ---------------------------------------------------------------------------
+// Privacy mode
+// ---------------------------------------------------------------------------
+
+/// When set, dev builds strip pipeline string fields like release builds do,
+/// the plain-text log skips pipeline and transform events entirely, and
+/// frontend log lines are dropped, so nothing free-form from the
+/// transcription pipeline reaches the logs.
+static PRIVACY_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_privacy_mode(enabled: bool) {
+    PRIVACY_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn privacy_mode() -> bool {
+    PRIVACY_MODE.load(Ordering::Relaxed)
+}
+
+/// Whether the plain-text log may record an event from `target`. Its fields
+/// are written verbatim, so in privacy mode the streams that carry transcript
+/// text are left to the sanitized JSONL.
+fn pretty_log_allows(target: &str) -> bool {
+    !privacy_mode() || !matches!(stream_for_target(target), "pipeline" | "transform")
+}
+
 // ---------------------------------------------------------------------------
 // JsonVisitor — collects tracing fields into serde_json values
 // ---------------------------------------------------------------------------
@@ -104,16 +130,15 @@ pub struct TauriEmitterLayer {
     jsonl_writer: Mutex<std::io::BufWriter<std::fs::File>>,
 }
 
-impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for TauriEmitterLayer {
-    fn on_event(
-        &self,
-        event: &tracing::Event<'_>,
-        _ctx: tracing_subscriber::layer::Context<'_, S>,
-    ) {
+impl<S> tracing_subscriber::Layer<S> for TauriEmitterLayer
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
         let meta = event.metadata();
 
         // Stream = target (e.g. "pipeline", "audio", "system")
-        let stream = meta.target().to_string();
+        let stream = stream_for_target(meta.target()).to_string();
 
         // Level
         let level = match *meta.level() {
@@ -130,9 +155,29 @@ impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for TauriEmitterLayer
         event.record(&mut visitor);
 
         let summary = visitor.message.unwrap_or_default();
-        let mut data = serde_json::Value::Object(visitor.fields);
+        let mut fields = visitor.fields;
+        if stream != meta.target() {
+            fields.insert("target".into(), meta.target().into());
+        }
+        // Inside a dictation's pipeline span: say which one, and which phase.
+        let (pipeline_id, phase) = crate::pipeline_trace::event_context(&ctx, event);
+        if let Some(pipeline_id) = pipeline_id {
+            fields
+                .entry("pipeline_id")
+                .or_insert_with(|| pipeline_id.into());
+        }
+        if let Some(phase) = phase {
+            fields
+                .entry("phase")
+                .or_insert_with(|| phase.as_str().into());
+        }
+        let mut data = serde_json::Value::Object(fields);
 
-        sanitize_event_data(&stream, &mut data, cfg!(debug_assertions));
+        sanitize_event_data(
+            &stream,
+            &mut data,
+            cfg!(debug_assertions) && !privacy_mode(),
+        );
 
         let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
 
@@ -166,6 +211,16 @@ impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for TauriEmitterLayer
     }
 }
 
+/// The log viewer filters on a fixed set of streams. Dependency crates log
+/// under their module path, so fold those into `system` rather than letting
+/// them vanish behind the stream chips.
+fn stream_for_target(target: &str) -> &str {
+    match target {
+        "pipeline" | "audio" | "keyboard" | "transform" | "system" => target,
+        _ => "system",
+    }
+}
+
 /// Enforce the structured-event privacy boundary independently of call-site
 /// discipline. Transform traces may retain only stable enum/bucket fields;
 /// arbitrary strings (and therefore transform content, paths, app/device
@@ -321,17 +376,25 @@ fn sanitize_event_data(stream: &str, data: &mut serde_json::Value, debug_build:
         return;
     };
     if !debug_build && stream == "pipeline" {
-        obj.retain(|_, value| !value.is_string());
+        obj.retain(|key, value| match value.as_str() {
+            Some(value) => is_pipeline_phase(key, value),
+            None => true,
+        });
         return;
     }
     if stream == "transform" {
         obj.retain(|key, value| match value.as_str() {
-            Some(value) => is_safe_transform_string(key, value),
+            Some(value) => is_safe_transform_string(key, value) || is_pipeline_phase(key, value),
             None => true,
         });
     }
 }
 
+/// The `phase` field added to events inside a dictation pipeline span.
+fn is_pipeline_phase(key: &str, value: &str) -> bool {
+    key == "phase" && crate::pipeline_trace::PipelinePhase::from_name(value).is_some()
+}
+
 // ---------------------------------------------------------------------------
 // init() — set up the global tracing subscriber
 // ---------------------------------------------------------------------------
@@ -418,7 +481,10 @@ pub fn init(app_handle: tauri::AppHandle) {
         .with_writer(pretty_writer)
         .with_target(true)
         .with_level(true)
-        .with_ansi(false);
+        .with_ansi(false)
+        .with_filter(tracing_subscriber::filter::filter_fn(|meta| {
+            pretty_log_allows(meta.target())
+        }));
 
     // Layer 2: Tauri event emitter (also writes JSONL)
     let emitter_layer = TauriEmitterLayer {
@@ -432,7 +498,8 @@ pub fn init(app_handle: tauri::AppHandle) {
     let subscriber = tracing_subscriber::registry()
         .with(filter)
         .with(pretty_layer)
-        .with(emitter_layer);
+        .with(emitter_layer)
+        .with(crate::pipeline_trace::PipelineTraceLayer);
 
     tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
 
@@ -504,6 +571,207 @@ pub fn clear_all_logs() -> Result<(), String> {
     Ok(())
 }
 
+/// Whether `name` is a log this build still writes to. Everything else in the
+/// log directory is rotated or left over from older versions.
+pub(crate) fn is_active_log(name: &str) -> bool {
+    if cfg!(debug_assertions) {
+        name == "app.dev.log" || name == "events.dev.jsonl"
+    } else {
+        name == "app.log" || name == "events.jsonl"
+    }
+}
+
+/// Legacy transcript logs that stored full dictation text in plaintext.
+pub(crate) fn is_transcript_log(name: &str) -> bool {
+    name.starts_with("transcriptions.") && name.contains(".jsonl")
+}
+
+/// Plain-text logs, which record pipeline and frontend lines verbatim.
+fn is_plain_text_log(name: &str) -> bool {
+    (name.starts_with("app.") || name.starts_with("frontend.")) && name.contains(".log")
+}
+
+/// Overwrite a file with zeros before unlinking it, so the text doesn't linger
+/// in the freed blocks. Best effort on copy-on-write filesystems like APFS.
+pub(crate) fn shred_file(path: &std::path::Path) -> std::io::Result<()> {
+    let len = std::fs::metadata(path)?.len();
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let zeros = [0u8; 8192];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all()?;
+    drop(file);
+    std::fs::remove_file(path)
+}
+
+/// Overwrite a log the running app still appends to with zeros, then empty
+/// it. Unlinking it would send the rest of this session's lines nowhere.
+fn wipe_file(path: &std::path::Path) -> std::io::Result<()> {
+    let len = std::fs::metadata(path)?.len();
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let zeros = [0u8; 8192];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.set_len(0)?;
+    file.sync_all()
+}
+
+fn purge_transcript_logs_in(dir: &std::path::Path) -> Result<usize, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("Failed to read log directory: {}", e)),
+    };
+    let mut purged = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let result = if is_transcript_log(&name) {
+            shred_file(&entry.path())
+        } else if is_plain_text_log(&name) && is_active_log(&name) {
+            wipe_file(&entry.path())
+        } else if is_plain_text_log(&name) {
+            shred_file(&entry.path())
+        } else {
+            continue;
+        };
+        result.map_err(|e| format!("Failed to purge {}: {}", name, e))?;
+        purged += 1;
+    }
+    Ok(purged)
+}
+
+/// Shred every transcript log and plain-text log in the log directory; the
+/// one this session writes to is emptied instead. Returns how many files
+/// were purged.
+pub fn purge_transcript_logs() -> Result<usize, String> {
+    match dirs::data_dir().map(|d| d.join("local-dictation").join("logs")) {
+        Some(dir) => purge_transcript_logs_in(&dir),
+        None => Ok(0),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Log queries
+// ---------------------------------------------------------------------------
+
+const DEFAULT_QUERY_LIMIT: usize = 200;
+const MAX_QUERY_LIMIT: usize = 1000;
+
+/// Filters for `query_logs`. Empty lists and `None` match everything.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LogQuery {
+    pub levels: Vec<String>,
+    pub streams: Vec<String>,
+    /// Case-insensitive substring of the summary or any field value.
+    pub search: Option<String>,
+    /// Inclusive bounds, in Unix milliseconds.
+    pub since_ms: Option<i64>,
+    pub until_ms: Option<i64>,
+    /// Matches to skip, counted from the newest.
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+/// One page of matching events, newest first.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQueryPage {
+    pub entries: Vec<AppEvent>,
+    /// Matches across all files, not just this page.
+    pub total: usize,
+    pub has_more: bool,
+}
+
+impl LogQuery {
+    fn matches(&self, event: &AppEvent, search: Option<&str>) -> bool {
+        if !self.levels.is_empty() && !self.levels.contains(&event.level) {
+            return false;
+        }
+        if !self.streams.is_empty() && !self.streams.contains(&event.stream) {
+            return false;
+        }
+        if self.since_ms.is_some() || self.until_ms.is_some() {
+            let Ok(at) = chrono::DateTime::parse_from_rfc3339(&event.timestamp) else {
+                return false;
+            };
+            let at = at.timestamp_millis();
+            if self.since_ms.is_some_and(|since| at < since)
+                || self.until_ms.is_some_and(|until| at > until)
+            {
+                return false;
+            }
+        }
+        match search {
+            Some(needle) => {
+                event.summary.to_lowercase().contains(needle)
+                    || event.data.to_string().to_lowercase().contains(needle)
+            }
+            None => true,
+        }
+    }
+}
+
+/// Scan `paths` oldest-first, keeping only the newest `offset + limit`
+/// matches in memory, so a query over megabytes of JSONL returns one page.
+fn query_log_files(paths: &[std::path::PathBuf], query: &LogQuery) -> LogQueryPage {
+    use std::io::BufRead;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_QUERY_LIMIT)
+        .clamp(1, MAX_QUERY_LIMIT);
+    let window = query.offset.saturating_add(limit);
+    let search = query
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|needle| !needle.is_empty())
+        .map(str::to_lowercase);
+    let mut newest = VecDeque::with_capacity(window.min(MAX_QUERY_LIMIT * 2));
+    let mut total = 0;
+    for path in paths {
+        let Ok(file) = std::fs::File::open(path) else {
+            continue;
+        };
+        for line in std::io::BufReader::new(file).lines().map_while(Result::ok) {
+            let Ok(event) = serde_json::from_str::<AppEvent>(&line) else {
+                continue;
+            };
+            if !query.matches(&event, search.as_deref()) {
+                continue;
+            }
+            total += 1;
+            if newest.len() == window {
+                newest.pop_front();
+            }
+            newest.push_back(event);
+        }
+    }
+    let entries: Vec<AppEvent> = newest.into_iter().rev().skip(query.offset).collect();
+    LogQueryPage {
+        has_more: total > query.offset + entries.len(),
+        entries,
+        total,
+    }
+}
+
+/// Query the rotated and active JSONL files.
+pub fn query_logs(query: &LogQuery) -> LogQueryPage {
+    let paths = jsonl_path()
+        .map(|path| vec![path.with_extension("jsonl.1"), path])
+        .unwrap_or_default();
+    query_log_files(&paths, query)
+}
+
 // ---------------------------------------------------------------------------
 // Tauri commands
 // ---------------------------------------------------------------------------
@@ -526,6 +794,194 @@ pub fn clear_event_history() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn purging_shreds_transcript_and_plain_text_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "transcriptions.jsonl",
+            "transcriptions.dev.jsonl.1",
+            "transcriptions.jsonl.2026-03-02",
+            "events.jsonl",
+            "app.log",
+            "app.dev.log",
+            "frontend.log.1",
+        ] {
+            std::fs::write(dir.path().join(name), "hello").unwrap();
+        }
+
+        assert_eq!(purge_transcript_logs_in(dir.path()), Ok(6));
+        let active = if cfg!(debug_assertions) {
+            "app.dev.log"
+        } else {
+            "app.log"
+        };
+        let mut left: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        let mut expected = vec![active, "events.jsonl"];
+        expected.sort();
+        assert_eq!(left, expected);
+        // The active log is kept for this session's lines, but emptied.
+        assert_eq!(std::fs::read(dir.path().join(active)).unwrap(), b"");
+        assert_eq!(purge_transcript_logs_in(&dir.path().join("missing")), Ok(0));
+    }
+
+    #[test]
+    fn privacy_mode_keeps_transcript_streams_out_of_the_plain_text_log() {
+        assert!(pretty_log_allows("pipeline"));
+        set_privacy_mode(true);
+        assert!(!pretty_log_allows("pipeline"));
+        assert!(!pretty_log_allows("transform"));
+        assert!(pretty_log_allows("keyboard"));
+        set_privacy_mode(false);
+    }
+
+    fn write_events(path: &std::path::Path, events: &[(&str, &str, &str, &str)]) {
+        let lines: Vec<String> = events
+            .iter()
+            .map(|(timestamp, stream, level, summary)| {
+                serde_json::to_string(&AppEvent {
+                    timestamp: timestamp.to_string(),
+                    stream: stream.to_string(),
+                    level: level.to_string(),
+                    summary: summary.to_string(),
+                    data: serde_json::json!({ "model": "base.en" }),
+                })
+                .unwrap()
+            })
+            .collect();
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn log_queries_filter_across_rotated_and_active_files_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotated = dir.path().join("events.jsonl.1");
+        let active = dir.path().join("events.jsonl");
+        write_events(
+            &rotated,
+            &[
+                (
+                    "2026-01-01T10:00:00.000Z",
+                    "pipeline",
+                    "info",
+                    "model loaded",
+                ),
+                ("2026-01-01T10:00:01.000Z", "audio", "warn", "device lost"),
+            ],
+        );
+        write_events(
+            &active,
+            &[
+                (
+                    "2026-01-01T10:00:02.000Z",
+                    "pipeline",
+                    "error",
+                    "transcription failed",
+                ),
+                (
+                    "2026-01-01T10:00:03.000Z",
+                    "pipeline",
+                    "info",
+                    "transcription done",
+                ),
+            ],
+        );
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&active)
+            .and_then(|mut file| writeln!(file, "not json"))
+            .unwrap();
+        let paths = [rotated, active];
+
+        let all = query_log_files(&paths, &LogQuery::default());
+        assert_eq!(all.total, 4);
+        assert_eq!(all.entries[0].summary, "transcription done");
+        assert_eq!(all.entries[3].summary, "model loaded");
+
+        let pipeline = query_log_files(
+            &paths,
+            &LogQuery {
+                streams: vec!["pipeline".into()],
+                levels: vec!["info".into()],
+                ..LogQuery::default()
+            },
+        );
+        let summaries: Vec<_> = pipeline
+            .entries
+            .iter()
+            .map(|e| e.summary.as_str())
+            .collect();
+        assert_eq!(summaries, ["transcription done", "model loaded"]);
+
+        let search = query_log_files(
+            &paths,
+            &LogQuery {
+                search: Some(" TRANSCRIPTION ".into()),
+                since_ms: Some(1_767_261_603_000),
+                ..LogQuery::default()
+            },
+        );
+        assert_eq!(search.total, 1);
+        assert_eq!(search.entries[0].summary, "transcription done");
+
+        let fields = query_log_files(
+            &paths,
+            &LogQuery {
+                search: Some("base.en".into()),
+                until_ms: Some(1_767_261_600_999),
+                ..LogQuery::default()
+            },
+        );
+        assert_eq!(fields.total, 1);
+        assert_eq!(fields.entries[0].summary, "model loaded");
+    }
+
+    #[test]
+    fn log_queries_page_from_the_newest_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        write_events(
+            &path,
+            &[
+                ("2026-01-01T10:00:00.000Z", "system", "info", "one"),
+                ("2026-01-01T10:00:01.000Z", "system", "info", "two"),
+                ("2026-01-01T10:00:02.000Z", "system", "info", "three"),
+            ],
+        );
+        let page = |offset| {
+            query_log_files(
+                std::slice::from_ref(&path),
+                &LogQuery {
+                    offset,
+                    limit: Some(2),
+                    ..LogQuery::default()
+                },
+            )
+        };
+
+        let first = page(0);
+        assert_eq!(first.entries.len(), 2);
+        assert_eq!(first.entries[0].summary, "three");
+        assert!(first.has_more);
+        let second = page(2);
+        assert_eq!(second.entries.len(), 1);
+        assert_eq!(second.entries[0].summary, "one");
+        assert!(!second.has_more);
+        assert_eq!(second.total, 3);
+        assert!(page(5).entries.is_empty());
+    }
+
+    #[test]
+    fn events_from_other_targets_fold_into_the_system_stream() {
+        assert_eq!(stream_for_target("pipeline"), "pipeline");
+        assert_eq!(stream_for_target("transform"), "transform");
+        assert_eq!(stream_for_target("tauri::manager"), "system");
+        assert_eq!(stream_for_target("ui::audio"), "system");
+    }
+
     #[test]
     fn transform_event_sanitizer_keeps_only_stable_string_fields() {
         let mut data = serde_json::json!({
@@ -617,4 +1073,23 @@ mod tests {
         assert!(!encoded.contains("/Users/private"));
         assert_eq!(data.as_object().unwrap().len(), 3);
     }
+
+    #[test]
+    fn release_pipeline_events_keep_only_the_pipeline_phase_string() {
+        let mut data = serde_json::json!({
+            "pipeline_id": 7,
+            "phase": "inference",
+            "decode_ms": 120,
+            "model": "SENTINEL model",
+        });
+        sanitize_event_data("pipeline", &mut data, false);
+        assert_eq!(
+            data,
+            serde_json::json!({ "pipeline_id": 7, "phase": "inference", "decode_ms": 120 })
+        );
+
+        let mut data = serde_json::json!({ "phase": "SENTINEL phase" });
+        sanitize_event_data("pipeline", &mut data, false);
+        assert_eq!(data, serde_json::json!({}));
+    }
 }
@@ -0,0 +1,302 @@
+//! Transcript comparison: word and character error rates and a word-level
+//! alignment diff.
+//!
+//! Shared by the benchmark and accuracy self-test (scoring against a known
+//! reference) and by dual-model refinement (draft against re-transcription).
+//! Everything here is pure and deterministic; callers decide what, if
+//! anything, to log.
+
+use crate::language_rules::is_unspaced_char;
+use serde::Serialize;
+
+/// Above this many word pairs the diff degrades to "replace everything"
+/// rather than filling a large LCS table.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Lowercase words split on anything but letters, digits, and apostrophes, so
+/// case and punctuation never count as errors.
+pub fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|character: char| !character.is_alphanumeric() && character != '\'')
+        .filter(|word| !word.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Word count for stats and logs. Whitespace separates words, except in
+/// Chinese and Japanese, which don't space them: there each character counts
+/// as a word, as word processors count them, and a Latin run beside one
+/// ("iPhone" in "我用iPhone") counts once.
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|token| {
+            let mut count = 0;
+            let mut in_run = false;
+            for c in token.chars() {
+                if is_unspaced_char(c) {
+                    count += usize::from(c.is_alphabetic());
+                    in_run = false;
+                } else if !in_run {
+                    count += 1;
+                    in_run = true;
+                }
+            }
+            count.max(1)
+        })
+        .sum()
+}
+
+/// Levenshtein distance: substitutions + deletions + insertions.
+pub fn edit_distance<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> usize {
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    for (row, reference_item) in reference.iter().enumerate() {
+        let mut current = vec![row + 1; hypothesis.len() + 1];
+        for (column, hypothesis_item) in hypothesis.iter().enumerate() {
+            current[column + 1] = (previous[column + 1] + 1)
+                .min(current[column] + 1)
+                .min(previous[column] + usize::from(reference_item != hypothesis_item));
+        }
+        previous = current;
+    }
+    previous[hypothesis.len()]
+}
+
+/// Raw WER: lowercase + punctuation split only. Returns (errors, reference words).
+pub fn word_errors(reference: &str, hypothesis: &str) -> (usize, usize) {
+    let reference = words(reference);
+    let hypothesis = words(hypothesis);
+    (edit_distance(&reference, &hypothesis), reference.len())
+}
+
+/// CER over the word stream rejoined with single spaces, so case and
+/// punctuation don't count. Returns (errors, reference characters).
+pub fn character_errors(reference: &str, hypothesis: &str) -> (usize, usize) {
+    let reference = words(reference).join(" ").chars().collect::<Vec<_>>();
+    let hypothesis = words(hypothesis).join(" ").chars().collect::<Vec<_>>();
+    (edit_distance(&reference, &hypothesis), reference.len())
+}
+
+/// `errors / reference`, or 0 for an empty reference.
+pub fn error_rate(errors: usize, reference: usize) -> f64 {
+    if reference == 0 {
+        0.0
+    } else {
+        errors as f64 / reference as f64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// A run of words sharing one operation, joined by single spaces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffSpan {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+fn push(spans: &mut Vec<DiffSpan>, op: DiffOp, word: &str) {
+    match spans.last_mut() {
+        Some(last) if last.op == op => {
+            last.text.push(' ');
+            last.text.push_str(word);
+        }
+        _ => spans.push(DiffSpan {
+            op,
+            text: word.to_string(),
+        }),
+    }
+}
+
+/// Word diff from `old` to `new` (longest common subsequence) on
+/// whitespace-separated words, keeping their case and punctuation for
+/// display. Deletes come before inserts within a changed run.
+pub fn word_diff(old: &str, new: &str) -> Vec<DiffSpan> {
+    let old: Vec<&str> = old.split_whitespace().collect();
+    let new: Vec<&str> = new.split_whitespace().collect();
+    let mut spans = Vec::new();
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        old.iter()
+            .for_each(|word| push(&mut spans, DiffOp::Delete, word));
+        new.iter()
+            .for_each(|word| push(&mut spans, DiffOp::Insert, word));
+        return spans;
+    }
+
+    // lcs[i][j]: common subsequence length of old[i..] and new[j..].
+    let width = new.len() + 1;
+    let mut lcs = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i * width + j] = if old[i] == new[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            push(&mut spans, DiffOp::Equal, old[i]);
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            push(&mut spans, DiffOp::Delete, old[i]);
+            i += 1;
+        } else {
+            push(&mut spans, DiffOp::Insert, new[j]);
+            j += 1;
+        }
+    }
+    old[i..]
+        .iter()
+        .for_each(|word| push(&mut spans, DiffOp::Delete, word));
+    new[j..]
+        .iter()
+        .for_each(|word| push(&mut spans, DiffOp::Insert, word));
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(op: DiffOp, text: &str) -> DiffSpan {
+        DiffSpan {
+            op,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn words_lowercase_and_split_on_punctuation_but_keep_apostrophes() {
+        assert_eq!(
+            words("Don't stop -- it's  FINE, ok?"),
+            vec!["don't", "stop", "it's", "fine", "ok"]
+        );
+        assert_eq!(
+            words("snake_case/path.rs"),
+            vec!["snake", "case", "path", "rs"]
+        );
+        assert!(words("  ... ").is_empty());
+    }
+
+    #[test]
+    fn word_count_counts_unspaced_scripts_by_character() {
+        assert_eq!(word_count("Send it -- now, please"), 5);
+        assert_eq!(word_count("今日は晴れです。"), 7);
+        assert_eq!(word_count("我用iPhone 15打字"), 6);
+        assert_eq!(word_count("「東京」へ"), 3);
+        assert_eq!(word_count("𠮷野家へ"), 4);
+        assert_eq!(word_count("مرحبا بالعالم"), 2);
+        assert_eq!(word_count("שלום 👋 עולם"), 3);
+        assert_eq!(word_count("안녕하세요 세계"), 2);
+        assert_eq!(word_count("  "), 0);
+    }
+
+    #[test]
+    fn edit_distance_counts_each_operation_once() {
+        let a = ["a", "b", "c"];
+        assert_eq!(edit_distance(&a, &a), 0);
+        assert_eq!(edit_distance(&a, &["a", "x", "c"]), 1);
+        assert_eq!(edit_distance(&a, &["a", "c"]), 1);
+        assert_eq!(edit_distance(&a, &["a", "b", "c", "d"]), 1);
+        assert_eq!(edit_distance(&a, &[]), 3);
+        assert_eq!(edit_distance::<&str>(&[], &["x", "y"]), 2);
+        assert_eq!(
+            edit_distance(
+                &["k", "i", "t", "t", "e", "n"],
+                &["s", "i", "t", "t", "i", "n", "g"]
+            ),
+            3
+        );
+    }
+
+    #[test]
+    fn word_errors_ignore_case_and_punctuation() {
+        assert_eq!(word_errors("Hello, World!", "hello world"), (0, 2));
+        assert_eq!(word_errors("one two three", "one four three"), (1, 3));
+        assert_eq!(word_errors("one two three", ""), (3, 3));
+        assert_eq!(word_errors("", "stray words"), (2, 0));
+    }
+
+    #[test]
+    fn character_errors_score_the_normalized_stream() {
+        assert_eq!(character_errors("Hello, world.", "hello world"), (0, 11));
+        assert_eq!(character_errors("the cat", "the hat"), (1, 7));
+        assert_eq!(character_errors("the  cat", "thecat"), (1, 7));
+        assert_eq!(character_errors("", ""), (0, 0));
+        assert_eq!(character_errors("naïve", "naive"), (1, 5));
+    }
+
+    #[test]
+    fn error_rate_is_zero_for_an_empty_reference() {
+        assert_eq!(error_rate(1, 4), 0.25);
+        assert_eq!(error_rate(0, 0), 0.0);
+        assert_eq!(error_rate(3, 0), 0.0);
+        assert_eq!(error_rate(5, 2), 2.5);
+    }
+
+    #[test]
+    fn diff_marks_changed_words_and_merges_runs() {
+        assert_eq!(
+            word_diff("send the report too Sam", "send the report to Sam today"),
+            vec![
+                span(DiffOp::Equal, "send the report"),
+                span(DiffOp::Delete, "too"),
+                span(DiffOp::Insert, "to"),
+                span(DiffOp::Equal, "Sam"),
+                span(DiffOp::Insert, "today"),
+            ]
+        );
+        assert_eq!(
+            word_diff("same  words", "same words"),
+            vec![span(DiffOp::Equal, "same words")]
+        );
+        assert_eq!(word_diff("", ""), Vec::new());
+        assert_eq!(word_diff("gone", ""), vec![span(DiffOp::Delete, "gone")]);
+        assert_eq!(word_diff("", "new"), vec![span(DiffOp::Insert, "new")]);
+    }
+
+    #[test]
+    fn diff_keeps_case_and_punctuation_for_display() {
+        assert_eq!(
+            word_diff("hello world", "Hello, world"),
+            vec![
+                span(DiffOp::Delete, "hello"),
+                span(DiffOp::Insert, "Hello,"),
+                span(DiffOp::Equal, "world"),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_round_trips_both_sides() {
+        let old = "the quick brown fox jumps over the lazy dog";
+        let new = "a quick red fox jumped over the dog again";
+        let spans = word_diff(old, new);
+        let side = |keep: DiffOp| {
+            spans
+                .iter()
+                .filter(|span| span.op == DiffOp::Equal || span.op == keep)
+                .map(|span| span.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert_eq!(side(DiffOp::Delete), old);
+        assert_eq!(side(DiffOp::Insert), new);
+        let equal_words = spans
+            .iter()
+            .filter(|span| span.op == DiffOp::Equal)
+            .map(|span| span.text.split(' ').count())
+            .sum::<usize>();
+        assert_eq!(equal_words, 5);
+    }
+}
@@ -0,0 +1,697 @@
+//! Opt-in transcription by a cloud speech-to-text service.
+//!
+//! The user picks a provider, optionally replaces its endpoint and model, and
+//! stores an API key, which lives only in the macOS Keychain. Two request
+//! shapes are supported, which between them cover most hosted services:
+//!
+//! - `openai`: multipart POST of the WAV file with `model`, `language`, and
+//!   `prompt` fields and a `Bearer` token; the reply's `text` is the
+//!   transcript. Groq, Fireworks, and self-hosted servers accept the same call.
+//! - `deepgram`: POST of the raw WAV with the model and language as query
+//!   parameters and a `Token` header; the transcript is the first channel's
+//!   first alternative.
+//!
+//! The `cloud` model counts as installed once a key is stored. Requests go
+//! through the download proxy settings. Neither the key, the audio, nor the
+//! text is logged; only the provider, host, status, and timings are.
+
+use super::external::encode_wav;
+use super::{strip_punctuation, TranscriptionBackend};
+use crate::MutexExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The catalog's single cloud model.
+pub const CLOUD_MODEL_NAME: &str = "cloud";
+
+const OPENAI_ENDPOINT: &str = "https://api.openai.com/v1/audio/transcriptions";
+const OPENAI_MODEL: &str = "whisper-1";
+const DEEPGRAM_ENDPOINT: &str = "https://api.deepgram.com/v1/listen";
+const DEEPGRAM_MODEL: &str = "nova-3";
+
+const MODEL_MAX: usize = 100;
+const API_KEY_MAX: usize = 512;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest a single request may take when no deadline is set.
+const TRANSCRIBE_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudProvider {
+    /// `/v1/audio/transcriptions`, as served by OpenAI and compatible hosts.
+    #[default]
+    Openai,
+    Deepgram,
+}
+
+impl CloudProvider {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Openai => "openai",
+            Self::Deepgram => "deepgram",
+        }
+    }
+
+    fn default_endpoint(self) -> &'static str {
+        match self {
+            Self::Openai => OPENAI_ENDPOINT,
+            Self::Deepgram => DEEPGRAM_ENDPOINT,
+        }
+    }
+
+    fn default_model(self) -> &'static str {
+        match self {
+            Self::Openai => OPENAI_MODEL,
+            Self::Deepgram => DEEPGRAM_MODEL,
+        }
+    }
+}
+
+/// The `cloudTranscription` setting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CloudTranscriptionSettings {
+    pub provider: CloudProvider,
+    /// Full URL requests are sent to; empty uses the provider's.
+    pub endpoint: String,
+    /// Model the service is asked for; empty uses the provider's default.
+    pub model: String,
+}
+
+impl CloudTranscriptionSettings {
+    /// Trim and validate every field so a bad value is rejected when it is
+    /// saved, not on the next dictation.
+    fn normalized(&self) -> Result<Self, String> {
+        let model = self.model.trim();
+        if model.chars().count() > MODEL_MAX || model.chars().any(char::is_control) {
+            return Err(format!(
+                "Cloud model names are at most {MODEL_MAX} characters"
+            ));
+        }
+        Ok(Self {
+            provider: self.provider,
+            endpoint: normalize_endpoint(&self.endpoint)?,
+            model: model.to_string(),
+        })
+    }
+
+    fn endpoint(&self) -> &str {
+        if self.endpoint.is_empty() {
+            self.provider.default_endpoint()
+        } else {
+            &self.endpoint
+        }
+    }
+
+    fn model(&self) -> &str {
+        if self.model.is_empty() {
+            self.provider.default_model()
+        } else {
+            &self.model
+        }
+    }
+}
+
+static SETTINGS: Mutex<CloudTranscriptionSettings> = Mutex::new(CloudTranscriptionSettings {
+    provider: CloudProvider::Openai,
+    endpoint: String::new(),
+    model: String::new(),
+});
+
+/// Whether the Keychain holds a key, so the catalog doesn't query it on every
+/// snapshot. `None` until first checked.
+static KEY_STORED: Mutex<Option<bool>> = Mutex::new(None);
+
+/// The API key travels in a header, so anything but a loopback server must be
+/// reached over HTTPS.
+fn normalize_endpoint(raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+    let url = reqwest::Url::parse(trimmed)
+        .map_err(|e| format!("Invalid cloud endpoint '{}': {}", trimmed, e))?;
+    let loopback = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    if url.scheme() != "https" && !(url.scheme() == "http" && loopback) {
+        return Err(format!(
+            "Cloud endpoint must use https:// (http:// only for localhost): '{}'",
+            trimmed
+        ));
+    }
+    if url.host_str().is_none() || url.query().is_some() || url.fragment().is_some() {
+        return Err(format!(
+            "Cloud endpoint needs a host and can't have a query or fragment: '{}'",
+            trimmed
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Apply the `cloudTranscription` setting. Rejects an invalid endpoint or
+/// model and keeps the previous settings.
+pub fn configure(settings: CloudTranscriptionSettings) -> Result<(), String> {
+    let settings = settings.normalized()?;
+    tracing::info!(
+        target: "system",
+        provider = settings.provider.as_str(),
+        custom_endpoint = !settings.endpoint.is_empty(),
+        custom_model = !settings.model.is_empty(),
+        "cloud transcription configured"
+    );
+    *SETTINGS.lock_or_recover() = settings;
+    Ok(())
+}
+
+fn settings() -> CloudTranscriptionSettings {
+    SETTINGS.lock_or_recover().clone()
+}
+
+/// Host the configured endpoint sends audio to, for history.
+pub fn endpoint_host() -> Option<String> {
+    reqwest::Url::parse(settings().endpoint())
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+}
+
+/// The cloud model is usable once an API key is stored.
+pub fn specific_model_exists(model_name: &str) -> bool {
+    model_name == CLOUD_MODEL_NAME && api_key_stored()
+}
+
+pub fn api_key_stored() -> bool {
+    let mut stored = KEY_STORED.lock_or_recover();
+    *stored.get_or_insert_with(|| matches!(keychain::api_key(), Ok(Some(_))))
+}
+
+/// Store `key` in the Keychain, replacing any earlier one.
+pub fn set_api_key(key: &str) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty()
+        || key.len() > API_KEY_MAX
+        || key.chars().any(|c| c.is_whitespace() || c.is_control())
+    {
+        return Err(format!(
+            "An API key is 1-{API_KEY_MAX} characters without spaces"
+        ));
+    }
+    keychain::set_api_key(key)?;
+    *KEY_STORED.lock_or_recover() = Some(true);
+    tracing::info!(target: "system", "cloud transcription API key stored");
+    Ok(())
+}
+
+pub fn clear_api_key() -> Result<(), String> {
+    keychain::delete_api_key()?;
+    *KEY_STORED.lock_or_recover() = Some(false);
+    tracing::info!(target: "system", "cloud transcription API key removed");
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+mod keychain {
+    use security_framework::passwords::{
+        delete_generic_password, get_generic_password, set_generic_password,
+    };
+
+    const SERVICE: &str = "com.localdictation.cloud-transcription";
+    const ACCOUNT: &str = "api-key";
+    /// errSecItemNotFound
+    const ITEM_NOT_FOUND: i32 = -25300;
+
+    pub(super) fn api_key() -> Result<Option<String>, String> {
+        match get_generic_password(SERVICE, ACCOUNT) {
+            Ok(bytes) => String::from_utf8(bytes)
+                .map(Some)
+                .map_err(|_| "The cloud API key in the Keychain is malformed.".to_string()),
+            Err(e) if e.code() == ITEM_NOT_FOUND => Ok(None),
+            Err(e) => Err(format!(
+                "Failed to read the cloud API key from the Keychain: {}",
+                e
+            )),
+        }
+    }
+
+    pub(super) fn set_api_key(key: &str) -> Result<(), String> {
+        set_generic_password(SERVICE, ACCOUNT, key.as_bytes())
+            .map_err(|e| format!("Failed to store the cloud API key in the Keychain: {}", e))
+    }
+
+    pub(super) fn delete_api_key() -> Result<(), String> {
+        match delete_generic_password(SERVICE, ACCOUNT) {
+            Ok(()) => Ok(()),
+            Err(e) if e.code() == ITEM_NOT_FOUND => Ok(()),
+            Err(e) => Err(format!(
+                "Failed to remove the cloud API key from the Keychain: {}",
+                e
+            )),
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod keychain {
+    pub(super) fn api_key() -> Result<Option<String>, String> {
+        Ok(None)
+    }
+
+    pub(super) fn set_api_key(_key: &str) -> Result<(), String> {
+        Err("Cloud transcription needs the macOS Keychain.".to_string())
+    }
+
+    pub(super) fn delete_api_key() -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// One transcription call, built without touching the network so its shape
+/// can be tested.
+struct CloudRequest {
+    url: reqwest::Url,
+    authorization: String,
+    content_type: String,
+    body: Vec<u8>,
+}
+
+fn build_request(
+    settings: &CloudTranscriptionSettings,
+    api_key: &str,
+    wav: Vec<u8>,
+    language: &str,
+    initial_prompt: Option<&str>,
+    boundary: &str,
+) -> Result<CloudRequest, String> {
+    let mut url = reqwest::Url::parse(settings.endpoint())
+        .map_err(|e| format!("Invalid cloud endpoint: {}", e))?;
+    let language = (language != "auto").then_some(language);
+    match settings.provider {
+        CloudProvider::Openai => {
+            let mut fields = vec![("model", settings.model()), ("response_format", "json")];
+            if let Some(language) = language {
+                fields.push(("language", language));
+            }
+            if let Some(prompt) = initial_prompt.filter(|prompt| !prompt.is_empty()) {
+                fields.push(("prompt", prompt));
+            }
+            Ok(CloudRequest {
+                url,
+                authorization: format!("Bearer {api_key}"),
+                content_type: format!("multipart/form-data; boundary={boundary}"),
+                body: multipart_body(&fields, &wav, boundary),
+            })
+        }
+        CloudProvider::Deepgram => {
+            {
+                let mut query = url.query_pairs_mut();
+                query
+                    .append_pair("model", settings.model())
+                    .append_pair("punctuate", "true")
+                    .append_pair("smart_format", "true");
+                match language {
+                    Some(language) => query.append_pair("language", language),
+                    None => query.append_pair("detect_language", "true"),
+                };
+            }
+            Ok(CloudRequest {
+                url,
+                authorization: format!("Token {api_key}"),
+                content_type: "audio/wav".to_string(),
+                body: wav,
+            })
+        }
+    }
+}
+
+fn multipart_body(fields: &[(&str, &str)], wav: &[u8], boundary: &str) -> Vec<u8> {
+    let mut body = Vec::with_capacity(wav.len() + 512);
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"audio.wav\"\r\nContent-Type: audio/wav\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(wav);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+    body
+}
+
+/// A multipart boundary that can't occur in the WAV by chance.
+fn boundary() -> Result<String, String> {
+    use ring::rand::SecureRandom;
+    let mut bytes = [0u8; 16];
+    ring::rand::SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "Failed to prepare the cloud request".to_string())?;
+    Ok(format!(
+        "murmur-{}",
+        bytes.iter().map(|b| format!("{b:02x}")).collect::<String>()
+    ))
+}
+
+fn parse_response(provider: CloudProvider, body: &[u8]) -> Result<String, String> {
+    let value: Value = serde_json::from_slice(body)
+        .map_err(|_| "The cloud service returned an unreadable response".to_string())?;
+    let text = match provider {
+        CloudProvider::Openai => value.get("text"),
+        CloudProvider::Deepgram => value.pointer("/results/channels/0/alternatives/0/transcript"),
+    };
+    text.and_then(Value::as_str)
+        .map(|text| text.trim().to_string())
+        .ok_or_else(|| "The cloud service returned no text".to_string())
+}
+
+/// An HTTP failure as the user should read it. Response bodies are not
+/// included: they can echo the request.
+fn status_error(status: reqwest::StatusCode) -> String {
+    match status.as_u16() {
+        401 | 403 => "The cloud service rejected the API key".to_string(),
+        413 => "The recording is too large for the cloud service".to_string(),
+        429 => "The cloud service is rate limiting requests; try again shortly".to_string(),
+        _ => format!("Cloud transcription failed: HTTP {}", status),
+    }
+}
+
+/// Send `request` and return the response body. `transcribe` can be called
+/// from inside the async runtime, so the request runs to completion on a
+/// thread of its own instead of blocking on the caller's.
+fn send(request: CloudRequest, timeout: Duration) -> Result<Vec<u8>, String> {
+    let client = crate::download_network::client_builder()?
+        .connect_timeout(CONNECT_TIMEOUT.min(timeout))
+        .timeout(timeout)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    std::thread::spawn(move || {
+        tauri::async_runtime::block_on(async move {
+            let response = client
+                .post(request.url)
+                .header(reqwest::header::AUTHORIZATION, request.authorization)
+                .header(reqwest::header::CONTENT_TYPE, request.content_type)
+                .body(request.body)
+                .send()
+                .await
+                .map_err(|e| {
+                    if e.is_timeout() {
+                        "The cloud service didn't answer in time".to_string()
+                    } else {
+                        "Couldn't reach the cloud service".to_string()
+                    }
+                })?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(status_error(status));
+            }
+            response
+                .bytes()
+                .await
+                .map(|bytes| bytes.to_vec())
+                .map_err(|_| "The cloud service's response was cut off".to_string())
+        })
+    })
+    .join()
+    .map_err(|_| "Cloud transcription request panicked".to_string())?
+}
+
+#[derive(Default)]
+pub struct CloudBackend {
+    loaded: bool,
+    deadline: Option<Instant>,
+}
+
+impl CloudBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TranscriptionBackend for CloudBackend {
+    fn name(&self) -> &str {
+        "cloud"
+    }
+
+    /// Nothing is loaded; this only checks the request can be made.
+    fn load_model(&mut self, model_name: &str) -> Result<(), String> {
+        super::ensure_audio_may_leave_device()?;
+        if model_name != CLOUD_MODEL_NAME {
+            return Err(format!("'{}' is not a cloud model", model_name));
+        }
+        if !api_key_stored() {
+            return Err("Add an API key for cloud transcription in Settings".to_string());
+        }
+        self.loaded = true;
+        Ok(())
+    }
+
+    fn is_model_loaded(&self, model_name: &str) -> bool {
+        self.loaded && model_name == CLOUD_MODEL_NAME
+    }
+
+    fn transcribe(
+        &mut self,
+        samples: &[f32],
+        language: &str,
+        initial_prompt: Option<&str>,
+        smart_punctuation: bool,
+    ) -> Result<String, String> {
+        // Checked on every take so turning the setting on stops a loaded backend.
+        super::ensure_audio_may_leave_device()?;
+        if !self.loaded {
+            return Err("Cloud transcription not loaded. Call load_model() first.".to_string());
+        }
+        let api_key = keychain::api_key()?
+            .ok_or_else(|| "Add an API key for cloud transcription in Settings".to_string())?;
+        let settings = settings();
+        let request = build_request(
+            &settings,
+            &api_key,
+            encode_wav(samples)?,
+            language,
+            initial_prompt,
+            &boundary()?,
+        )?;
+        let host = request.url.host_str().unwrap_or_default().to_string();
+        let timeout = self
+            .deadline
+            .map_or(TRANSCRIBE_TIMEOUT, |deadline| {
+                deadline.saturating_duration_since(Instant::now())
+            })
+            .min(TRANSCRIBE_TIMEOUT);
+        if timeout.is_zero() {
+            return Err("Cloud transcription deadline passed".to_string());
+        }
+        let started = Instant::now();
+        let result = send(request, timeout);
+        tracing::info!(
+            target: "pipeline",
+            provider = settings.provider.as_str(),
+            host = host.as_str(),
+            ok = result.is_ok(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "cloud_transcription_request"
+        );
+        let text = parse_response(settings.provider, &result?)?;
+        Ok(if smart_punctuation {
+            text
+        } else {
+            strip_punctuation(&text)
+        })
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    fn token_count(&self, _text: &str) -> Option<usize> {
+        None
+    }
+
+    fn model_exists(&self) -> bool {
+        api_key_stored()
+    }
+
+    fn models_dir(&self) -> Result<PathBuf, String> {
+        Err("Cloud transcription has no model files".to_string())
+    }
+
+    fn reset(&mut self) {
+        self.loaded = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(provider: CloudProvider) -> CloudTranscriptionSettings {
+        CloudTranscriptionSettings {
+            provider,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn endpoints_need_https_except_on_loopback() {
+        assert_eq!(normalize_endpoint("  ").unwrap(), "");
+        assert_eq!(
+            normalize_endpoint(" https://api.groq.com/openai/v1/audio/transcriptions ").unwrap(),
+            "https://api.groq.com/openai/v1/audio/transcriptions"
+        );
+        assert!(normalize_endpoint("http://127.0.0.1:8000/v1/audio/transcriptions").is_ok());
+        assert!(normalize_endpoint("http://localhost:8000/v1/listen").is_ok());
+        for bad in [
+            "http://api.example.com/v1/audio/transcriptions",
+            "ftp://api.example.com/",
+            "https://api.example.com/v1/listen?model=nova-3",
+            "not a url",
+        ] {
+            assert!(normalize_endpoint(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn settings_reject_long_models_and_fall_back_to_provider_defaults() {
+        let long = CloudTranscriptionSettings {
+            model: "m".repeat(MODEL_MAX + 1),
+            ..settings(CloudProvider::Openai)
+        };
+        assert!(long.normalized().is_err());
+
+        let defaults = settings(CloudProvider::Deepgram).normalized().unwrap();
+        assert_eq!(defaults.endpoint(), DEEPGRAM_ENDPOINT);
+        assert_eq!(defaults.model(), DEEPGRAM_MODEL);
+
+        let custom = CloudTranscriptionSettings {
+            endpoint: "https://api.groq.com/openai/v1/audio/transcriptions".to_string(),
+            model: " whisper-large-v3-turbo ".to_string(),
+            ..settings(CloudProvider::Openai)
+        }
+        .normalized()
+        .unwrap();
+        assert_eq!(custom.model(), "whisper-large-v3-turbo");
+        assert_eq!(
+            custom.endpoint(),
+            "https://api.groq.com/openai/v1/audio/transcriptions"
+        );
+    }
+
+    #[test]
+    fn openai_requests_are_multipart_with_a_bearer_token() {
+        let request = build_request(
+            &settings(CloudProvider::Openai),
+            "sk-test",
+            b"RIFFDATA".to_vec(),
+            "de",
+            Some("Murmur, Tauri"),
+            "b0undary",
+        )
+        .unwrap();
+        assert_eq!(request.url.as_str(), OPENAI_ENDPOINT);
+        assert_eq!(request.authorization, "Bearer sk-test");
+        assert_eq!(
+            request.content_type,
+            "multipart/form-data; boundary=b0undary"
+        );
+        let body = String::from_utf8(request.body).unwrap();
+        assert!(body.starts_with("--b0undary\r\n"));
+        assert!(body.contains("name=\"model\"\r\n\r\nwhisper-1\r\n"));
+        assert!(body.contains("name=\"language\"\r\n\r\nde\r\n"));
+        assert!(body.contains("name=\"prompt\"\r\n\r\nMurmur, Tauri\r\n"));
+        assert!(
+            body.contains("filename=\"audio.wav\"\r\nContent-Type: audio/wav\r\n\r\nRIFFDATA\r\n")
+        );
+        assert!(body.ends_with("--b0undary--\r\n"));
+
+        let auto = build_request(
+            &settings(CloudProvider::Openai),
+            "sk-test",
+            Vec::new(),
+            "auto",
+            Some(""),
+            "b",
+        )
+        .unwrap();
+        let body = String::from_utf8(auto.body).unwrap();
+        assert!(!body.contains("name=\"language\""));
+        assert!(!body.contains("name=\"prompt\""));
+    }
+
+    #[test]
+    fn deepgram_requests_send_raw_wav_with_query_options() {
+        let request = build_request(
+            &settings(CloudProvider::Deepgram),
+            "dg-test",
+            b"RIFF".to_vec(),
+            "en",
+            Some("ignored"),
+            "b",
+        )
+        .unwrap();
+        assert_eq!(request.authorization, "Token dg-test");
+        assert_eq!(request.content_type, "audio/wav");
+        assert_eq!(request.body, b"RIFF");
+        assert_eq!(
+            request.url.as_str(),
+            "https://api.deepgram.com/v1/listen?model=nova-3&punctuate=true&smart_format=true&language=en"
+        );
+
+        let auto = build_request(
+            &settings(CloudProvider::Deepgram),
+            "dg-test",
+            Vec::new(),
+            "auto",
+            None,
+            "b",
+        )
+        .unwrap();
+        assert!(auto.url.as_str().ends_with("&detect_language=true"));
+    }
+
+    #[test]
+    fn responses_are_read_per_provider() {
+        assert_eq!(
+            parse_response(CloudProvider::Openai, br#"{"text":" Hello world. "}"#).unwrap(),
+            "Hello world."
+        );
+        assert_eq!(
+            parse_response(
+                CloudProvider::Deepgram,
+                br#"{"results":{"channels":[{"alternatives":[{"transcript":"Hello world.","confidence":0.99}]}]}}"#
+            )
+            .unwrap(),
+            "Hello world."
+        );
+        assert_eq!(
+            parse_response(CloudProvider::Openai, br#"{"results":{}}"#).unwrap_err(),
+            "The cloud service returned no text"
+        );
+        assert!(parse_response(CloudProvider::Deepgram, b"<html>").is_err());
+    }
+
+    #[test]
+    fn status_errors_never_echo_the_response() {
+        assert_eq!(
+            status_error(reqwest::StatusCode::UNAUTHORIZED),
+            "The cloud service rejected the API key"
+        );
+        assert_eq!(
+            status_error(reqwest::StatusCode::BAD_GATEWAY),
+            "Cloud transcription failed: HTTP 502 Bad Gateway"
+        );
+    }
+
+    #[test]
+    fn boundaries_are_random() {
+        let first = boundary().unwrap();
+        assert!(first.starts_with("murmur-") && first.len() == 39);
+        assert_ne!(first, boundary().unwrap());
+    }
+}
@@ -5,7 +5,7 @@
 //! this the new-install default while keeping the existing Whisper and
 //! sherpa-onnx paths selectable.
 
-use super::TranscriptionBackend;
+use super::{strip_punctuation, TranscriptionBackend};
 use fluidaudio_rs::FluidAudio;
 use std::path::{Path, PathBuf};
 
@@ -227,20 +227,6 @@ impl TranscriptionBackend for CoreMlBackend {
     }
 }
 
-fn strip_punctuation(input: &str) -> String {
-    input
-        .chars()
-        .map(|character| match character {
-            '.' | ',' | '!' | '?' | ';' | ':' | '"' | '\u{201c}' | '\u{201d}' | '\u{2014}'
-            | '\u{2013}' | '\u{2026}' => ' ',
-            other => other,
-        })
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
 #[derive(Debug, PartialEq)]
 struct EmptyOutputDiagnostics {
     input_sample_count: usize,
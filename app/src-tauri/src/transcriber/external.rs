@@ -0,0 +1,717 @@
+//! Transcription engines that run outside Murmur.
+//!
+//! A plugin is an executable plus a `plugin.json` manifest. Murmur starts it
+//! as a child process and talks protocol 1 over its stdin and stdout: one JSON
+//! object per line, each request answered by the line carrying the same `id`.
+//!
+//! ```text
+//! → {"id":1,"method":"initialize","params":{"protocol":1,"sampleRate":16000}}
+//! ← {"id":1,"result":{"protocol":1,"name":"Example"}}
+//! → {"id":2,"method":"transcribe","params":{"audio":"<base64 WAV>","format":"wav",
+//!    "language":"en","initialPrompt":null,"smartPunctuation":true}}
+//! ← {"id":2,"result":{"text":"Hello world."}}
+//! ← {"id":2,"error":{"message":"Server unreachable"}}
+//! → {"id":3,"method":"shutdown","params":{}}
+//! ```
+//!
+//! Registering a plugin copies its manifest into the plugins directory, after
+//! one `initialize` handshake, and the plugin shows up in the model picker as
+//! `external:<id>`. Plugins get no stderr and a minimal environment, and
+//! neither audio nor text from them is logged.
+
+use super::{strip_punctuation, TranscriptionBackend, WHISPER_SAMPLE_RATE};
+use crate::model_runtime::{self, EXTERNAL_MODEL_PREFIX};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// The protocol version this build speaks.
+pub const PROTOCOL_VERSION: u64 = 1;
+
+const MANIFEST_MAX_BYTES: u64 = 64 * 1024;
+const PLUGIN_NAME_MAX: usize = 64;
+const PLUGIN_ARGS_MAX: usize = 32;
+/// Time to start and answer `initialize`, which may include loading a model.
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Longest a single `transcribe` may take when no deadline is set.
+const TRANSCRIBE_TIMEOUT: Duration = Duration::from_secs(120);
+/// Time a plugin gets to exit after `shutdown` before it is killed.
+const SHUTDOWN_GRACE: Duration = Duration::from_millis(500);
+/// The only variables a plugin inherits.
+const PASSED_ENV: &[&str] = &["PATH", "HOME", "LANG", "TMPDIR"];
+
+/// A plugin's `plugin.json`. Registered copies hold `command` as an absolute
+/// path; in the plugin's own folder it may be relative to the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    pub protocol: u64,
+    pub id: String,
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Plugin ids are 1-64 lowercase letters, digits, and dashes, starting with a
+/// letter or digit, so they are safe as file names.
+pub fn is_plugin_id(id: &str) -> bool {
+    id.len() <= 64
+        && id
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+pub(crate) fn plugins_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|data_dir| data_dir.join("local-dictation").join("plugins"))
+}
+
+fn registered_manifest_path(id: &str) -> Option<PathBuf> {
+    is_plugin_id(id)
+        .then(plugins_dir)
+        .flatten()
+        .map(|dir| dir.join(format!("{id}.json")))
+}
+
+/// Read and check the manifest at `path`, resolving `command` against the
+/// manifest's folder.
+pub fn read_manifest(path: &Path) -> Result<PluginManifest, String> {
+    let metadata =
+        std::fs::metadata(path).map_err(|e| format!("Can't read '{}': {}", path.display(), e))?;
+    if metadata.len() > MANIFEST_MAX_BYTES {
+        return Err("This plugin manifest is too large".to_string());
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Can't read '{}': {}", path.display(), e))?;
+    let mut manifest: PluginManifest = serde_json::from_str(&contents)
+        .map_err(|e| format!("This isn't a plugin manifest: {}", e))?;
+    if manifest.protocol != PROTOCOL_VERSION {
+        return Err(format!(
+            "This plugin speaks protocol {}, but Murmur speaks protocol {}",
+            manifest.protocol, PROTOCOL_VERSION
+        ));
+    }
+    if !is_plugin_id(&manifest.id) {
+        return Err("Plugin ids are up to 64 lowercase letters, digits, and dashes".to_string());
+    }
+    manifest.name = manifest.name.trim().to_string();
+    if manifest.name.is_empty() || manifest.name.chars().count() > PLUGIN_NAME_MAX {
+        return Err(format!(
+            "Plugin names are 1 to {} characters",
+            PLUGIN_NAME_MAX
+        ));
+    }
+    if manifest.args.len() > PLUGIN_ARGS_MAX {
+        return Err(format!(
+            "Plugins take at most {} arguments",
+            PLUGIN_ARGS_MAX
+        ));
+    }
+    if manifest.command.trim().is_empty() {
+        return Err("This plugin manifest has no command".to_string());
+    }
+    let folder = path.parent().unwrap_or_else(|| Path::new("."));
+    let command = folder
+        .join(&manifest.command)
+        .canonicalize()
+        .ok()
+        .filter(|command| command.is_file())
+        .ok_or_else(|| format!("The plugin command '{}' doesn't exist", manifest.command))?;
+    manifest.command = command.to_string_lossy().into_owned();
+    Ok(manifest)
+}
+
+/// The registered manifest for plugin `id`.
+pub fn load_manifest(id: &str) -> Result<PluginManifest, String> {
+    let path = registered_manifest_path(id)
+        .ok_or_else(|| format!("'{}' is not a transcription plugin", id))?;
+    if !path.is_file() {
+        return Err(format!(
+            "The transcription plugin '{}' isn't registered",
+            id
+        ));
+    }
+    let manifest = read_manifest(&path)?;
+    if manifest.id != id {
+        return Err(format!(
+            "The manifest registered as '{}' names another plugin",
+            id
+        ));
+    }
+    Ok(manifest)
+}
+
+/// Every registered plugin whose manifest still checks out, by id.
+pub fn registered_plugins() -> Vec<PluginManifest> {
+    let Some(entries) = plugins_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<PluginManifest> = entries
+        .filter_map(|entry| {
+            let file = entry.ok()?.file_name().into_string().ok()?;
+            load_manifest(file.strip_suffix(".json")?).ok()
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    plugins
+}
+
+/// `external:<id>` names for every registered plugin.
+pub(crate) fn external_model_names() -> Vec<String> {
+    registered_plugins()
+        .into_iter()
+        .map(|plugin| format!("{}{}", EXTERNAL_MODEL_PREFIX, plugin.id))
+        .collect()
+}
+
+pub fn specific_model_exists(model_name: &str) -> bool {
+    model_runtime::external_plugin_id(model_name).is_some_and(|id| load_manifest(id).is_ok())
+}
+
+/// The display name of the plugin behind `model_name`, if it is registered.
+pub fn plugin_name(model_name: &str) -> Option<String> {
+    let id = model_runtime::external_plugin_id(model_name)?;
+    load_manifest(id).ok().map(|manifest| manifest.name)
+}
+
+/// Check the manifest at `manifest_path`, start the plugin once to make sure
+/// it answers `initialize`, and register it. Registering an id again replaces
+/// the earlier manifest.
+pub fn register(manifest_path: &Path) -> Result<PluginManifest, String> {
+    let manifest = read_manifest(manifest_path)?;
+    let started = Instant::now();
+    drop(PluginProcess::start(&manifest)?);
+    tracing::info!(
+        target: "pipeline",
+        plugin = manifest.id.as_str(),
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "external_plugin_registered"
+    );
+
+    let path = registered_manifest_path(&manifest.id)
+        .ok_or_else(|| "Could not find application data directory".to_string())?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create plugins directory: {}", e))?;
+    }
+    let contents = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize plugin manifest: {}", e))?;
+    let staging = path.with_extension("json.tmp");
+    std::fs::write(&staging, contents)
+        .and_then(|()| std::fs::rename(&staging, &path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&staging);
+            format!("Failed to register plugin: {}", e)
+        })?;
+    Ok(manifest)
+}
+
+/// Forget plugin `id`. Its own files are left where they are.
+pub fn unregister(id: &str) -> Result<(), String> {
+    let path = registered_manifest_path(id)
+        .ok_or_else(|| format!("'{}' is not a transcription plugin", id))?;
+    match std::fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(format!(
+            "The transcription plugin '{}' isn't registered",
+            id
+        )),
+        Err(e) => Err(format!("Failed to remove plugin: {}", e)),
+    }
+}
+
+#[derive(Deserialize)]
+struct Response {
+    id: Option<u64>,
+    result: Option<Value>,
+    error: Option<ResponseError>,
+}
+
+#[derive(Deserialize)]
+struct ResponseError {
+    message: String,
+}
+
+/// Why a request failed.
+#[derive(Debug, PartialEq)]
+enum RequestError {
+    /// The plugin answered with an error; it can take the next request.
+    Reported(String),
+    /// The plugin exited, timed out, or broke the protocol.
+    Broken(String),
+}
+
+impl RequestError {
+    fn into_message(self) -> String {
+        match self {
+            Self::Reported(message) | Self::Broken(message) => message,
+        }
+    }
+}
+
+/// A running plugin. Dropping it asks the plugin to shut down and kills it if
+/// it doesn't.
+struct PluginProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    next_id: u64,
+}
+
+impl PluginProcess {
+    fn start(manifest: &PluginManifest) -> Result<Self, String> {
+        let command = Path::new(&manifest.command);
+        let mut builder = Command::new(command);
+        builder
+            .args(&manifest.args)
+            .current_dir(command.parent().unwrap_or_else(|| Path::new("/")))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            // Plugin diagnostics could quote what was said; they never reach
+            // Murmur's logs.
+            .stderr(Stdio::null())
+            .env_clear();
+        for key in PASSED_ENV {
+            if let Some(value) = std::env::var_os(key) {
+                builder.env(key, value);
+            }
+        }
+        let mut child = builder
+            .spawn()
+            .map_err(|e| format!("Failed to start plugin '{}': {}", manifest.id, e))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("Failed to start plugin '{}'", manifest.id));
+        };
+
+        let (tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut process = Self {
+            child,
+            stdin,
+            lines,
+            next_id: 0,
+        };
+        let result = process
+            .request(
+                "initialize",
+                json!({ "protocol": PROTOCOL_VERSION, "sampleRate": WHISPER_SAMPLE_RATE }),
+                INITIALIZE_TIMEOUT,
+            )
+            .map_err(RequestError::into_message)?;
+        let protocol = result.get("protocol").and_then(Value::as_u64);
+        if protocol != Some(PROTOCOL_VERSION) {
+            return Err(format!(
+                "The plugin '{}' didn't agree to protocol {}",
+                manifest.id, PROTOCOL_VERSION
+            ));
+        }
+        Ok(process)
+    }
+
+    /// Send one request and wait up to `timeout` for its response. Lines that
+    /// aren't a response to it are skipped.
+    fn request(
+        &mut self,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> Result<Value, RequestError> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let mut line = json!({ "id": id, "method": method, "params": params }).to_string();
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .and_then(|()| self.stdin.flush())
+            .map_err(|_| RequestError::Broken("The transcription plugin exited".to_string()))?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(remaining) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(RequestError::Broken(format!(
+                        "The transcription plugin didn't answer '{}' in time",
+                        method
+                    )))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(RequestError::Broken(
+                        "The transcription plugin exited".to_string(),
+                    ))
+                }
+            };
+            let Ok(response) = serde_json::from_str::<Response>(&line) else {
+                continue;
+            };
+            if response.id != Some(id) {
+                continue;
+            }
+            if let Some(error) = response.error {
+                let message: String = error.message.chars().take(200).collect();
+                return Err(RequestError::Reported(format!(
+                    "The transcription plugin failed: {}",
+                    message
+                )));
+            }
+            return response.result.ok_or_else(|| {
+                RequestError::Broken("The transcription plugin sent an empty response".to_string())
+            });
+        }
+    }
+}
+
+impl Drop for PluginProcess {
+    fn drop(&mut self) {
+        self.next_id += 1;
+        let shutdown = json!({ "id": self.next_id, "method": "shutdown", "params": {} });
+        let _ = writeln!(self.stdin, "{}", shutdown).and_then(|()| self.stdin.flush());
+        let deadline = Instant::now() + SHUTDOWN_GRACE;
+        while Instant::now() < deadline {
+            if matches!(self.child.try_wait(), Ok(Some(_))) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// `samples` as the 16-bit PCM mono WAV plugins and cloud services receive.
+pub(super) fn encode_wav(samples: &[f32]) -> Result<Vec<u8>, String> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut wav = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut wav, spec)
+        .map_err(|e| format!("Failed to encode audio: {}", e))?;
+    for &sample in samples {
+        writer
+            .write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .map_err(|e| format!("Failed to encode audio: {}", e))?;
+    }
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to encode audio: {}", e))?;
+    Ok(wav.into_inner())
+}
+
+#[derive(Default)]
+pub struct ExternalBackend {
+    process: Option<PluginProcess>,
+    loaded_model_name: Option<String>,
+    deadline: Option<Instant>,
+}
+
+impl ExternalBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TranscriptionBackend for ExternalBackend {
+    fn name(&self) -> &str {
+        "external"
+    }
+
+    fn load_model(&mut self, model_name: &str) -> Result<(), String> {
+        super::ensure_audio_may_leave_device()?;
+        if self.is_model_loaded(model_name) {
+            return Ok(());
+        }
+        self.reset();
+        let id = model_runtime::external_plugin_id(model_name)
+            .ok_or_else(|| format!("'{}' is not a transcription plugin", model_name))?;
+        let manifest = load_manifest(id)?;
+        let started = Instant::now();
+        self.process = Some(PluginProcess::start(&manifest)?);
+        self.loaded_model_name = Some(model_name.to_string());
+        tracing::info!(
+            target: "pipeline",
+            plugin = id,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "external_plugin_started"
+        );
+        Ok(())
+    }
+
+    fn is_model_loaded(&self, model_name: &str) -> bool {
+        self.process.is_some() && self.loaded_model_name.as_deref() == Some(model_name)
+    }
+
+    fn transcribe(
+        &mut self,
+        samples: &[f32],
+        language: &str,
+        initial_prompt: Option<&str>,
+        smart_punctuation: bool,
+    ) -> Result<String, String> {
+        // A plugin may forward audio anywhere, so it is held to the setting too.
+        super::ensure_audio_may_leave_device()?;
+        let audio = base64::engine::general_purpose::STANDARD.encode(encode_wav(samples)?);
+        let timeout = self
+            .deadline
+            .map_or(TRANSCRIBE_TIMEOUT, |deadline| {
+                deadline.saturating_duration_since(Instant::now())
+            })
+            .min(TRANSCRIBE_TIMEOUT);
+        let process = self.process.as_mut().ok_or_else(|| {
+            "Transcription plugin not loaded. Call load_model() first.".to_string()
+        })?;
+        let result = match process.request(
+            "transcribe",
+            json!({
+                "audio": audio,
+                "format": "wav",
+                "language": language,
+                "initialPrompt": initial_prompt,
+                "smartPunctuation": smart_punctuation,
+            }),
+            timeout,
+        ) {
+            Ok(result) => result,
+            Err(RequestError::Broken(message)) => {
+                // A plugin that stopped answering may still be busy with this
+                // take; the next take starts it fresh.
+                self.reset();
+                return Err(message);
+            }
+            Err(RequestError::Reported(message)) => return Err(message),
+        };
+        let text = result
+            .get("text")
+            .and_then(Value::as_str)
+            .ok_or_else(|| "The transcription plugin returned no text".to_string())?
+            .trim();
+        // Plugins may ignore `smartPunctuation`, so it is enforced here too.
+        Ok(if smart_punctuation {
+            text.to_string()
+        } else {
+            strip_punctuation(text)
+        })
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    fn token_count(&self, _text: &str) -> Option<usize> {
+        None
+    }
+
+    fn model_exists(&self) -> bool {
+        !registered_plugins().is_empty()
+    }
+
+    fn models_dir(&self) -> Result<PathBuf, String> {
+        plugins_dir().ok_or_else(|| "Could not find application data directory".to_string())
+    }
+
+    fn reset(&mut self) {
+        if let Some(model_name) = self.loaded_model_name.take() {
+            tracing::info!(target: "pipeline", model = model_name.as_str(), "external: stopping plugin");
+        }
+        self.process = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn test_dir(label: &str) -> PathBuf {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir =
+            std::env::temp_dir().join(format!("murmur-{label}-{}-{nonce}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_manifest(dir: &Path, manifest: Value) -> PathBuf {
+        let path = dir.join("plugin.json");
+        std::fs::write(&path, manifest.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn plugin_ids_are_safe_file_names() {
+        for id in ["whisper-server", "a", "0-fast"] {
+            assert!(is_plugin_id(id), "{id}");
+        }
+        let long = "a".repeat(65);
+        for id in [
+            "",
+            "-lead",
+            "Upper",
+            "dot.name",
+            "../x",
+            "a b",
+            long.as_str(),
+        ] {
+            assert!(!is_plugin_id(id), "{id}");
+        }
+    }
+
+    #[test]
+    fn manifests_resolve_the_command_next_to_them() {
+        let dir = test_dir("plugin-manifest");
+        std::fs::write(dir.join("run.sh"), "").unwrap();
+        let path = write_manifest(
+            &dir,
+            json!({ "protocol": 1, "id": "example", "name": " Example ", "command": "run.sh" }),
+        );
+        let manifest = read_manifest(&path).unwrap();
+        assert_eq!(manifest.name, "Example");
+        assert!(manifest.args.is_empty());
+        assert_eq!(
+            PathBuf::from(&manifest.command),
+            dir.join("run.sh").canonicalize().unwrap()
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn manifests_with_a_bad_field_are_rejected() {
+        let dir = test_dir("plugin-manifest-invalid");
+        std::fs::write(dir.join("run.sh"), "").unwrap();
+        for (manifest, error) in [
+            (
+                json!({ "protocol": 2, "id": "example", "name": "Example", "command": "run.sh" }),
+                "protocol 2",
+            ),
+            (
+                json!({ "protocol": 1, "id": "Example", "name": "Example", "command": "run.sh" }),
+                "Plugin ids",
+            ),
+            (
+                json!({ "protocol": 1, "id": "example", "name": "  ", "command": "run.sh" }),
+                "Plugin names",
+            ),
+            (
+                json!({ "protocol": 1, "id": "example", "name": "Example", "command": "missing.sh" }),
+                "doesn't exist",
+            ),
+            (
+                json!({ "protocol": 1, "id": "example", "name": "Example", "command": "." }),
+                "doesn't exist",
+            ),
+        ] {
+            let path = write_manifest(&dir, manifest);
+            let result = read_manifest(&path).unwrap_err();
+            assert!(result.contains(error), "{result}");
+        }
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    fn script_plugin(dir: &Path, script: &str) -> PluginManifest {
+        use std::os::unix::fs::PermissionsExt;
+        let command = dir.join("plugin.sh");
+        std::fs::write(&command, script).unwrap();
+        std::fs::set_permissions(&command, std::fs::Permissions::from_mode(0o755)).unwrap();
+        PluginManifest {
+            protocol: PROTOCOL_VERSION,
+            id: "script".to_string(),
+            name: "Script".to_string(),
+            command: command.to_string_lossy().into_owned(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Answers requests by counting them, so it needs no JSON parser.
+    #[cfg(unix)]
+    const SCRIPT: &str = r#"#!/bin/sh
+n=0
+while read -r line; do
+  n=$((n+1))
+  case "$line" in
+    *'"initialize"'*)
+      echo "starting up"
+      echo "{\"id\":$n,\"result\":{\"protocol\":1,\"name\":\"Script\"}}" ;;
+    *'"transcribe"'*'"en"'*)
+      echo "{\"id\":$n,\"result\":{\"text\":\" Hello, world. \"}}" ;;
+    *'"transcribe"'*)
+      echo "{\"id\":$n,\"error\":{\"message\":\"Only English\"}}" ;;
+    *'"shutdown"'*)
+      exit 0 ;;
+  esac
+done
+"#;
+
+    #[cfg(unix)]
+    #[test]
+    fn plugin_process_round_trips_requests_and_errors() {
+        let dir = test_dir("plugin-process");
+        let mut process = PluginProcess::start(&script_plugin(&dir, SCRIPT)).unwrap();
+        let params = json!({ "audio": "", "format": "wav", "language": "en" });
+        let result = process
+            .request("transcribe", params, Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(result["text"], " Hello, world. ");
+        let params = json!({ "audio": "", "format": "wav", "language": "de" });
+        assert_eq!(
+            process.request("transcribe", params, Duration::from_secs(10)),
+            Err(RequestError::Reported(
+                "The transcription plugin failed: Only English".to_string()
+            ))
+        );
+        drop(process);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn silent_or_incompatible_plugins_fail_to_start() {
+        let dir = test_dir("plugin-silent");
+        let silent = script_plugin(&dir, "#!/bin/sh\nexit 0\n");
+        assert!(PluginProcess::start(&silent)
+            .err()
+            .unwrap()
+            .contains("exited"));
+        let newer = script_plugin(
+            &dir,
+            "#!/bin/sh\nread -r line\necho '{\"id\":1,\"result\":{\"protocol\":2}}'\nread -r line\n",
+        );
+        assert!(PluginProcess::start(&newer)
+            .err()
+            .unwrap()
+            .contains("protocol 1"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn audio_is_sent_as_16k_mono_pcm() {
+        let wav = encode_wav(&[0.0, 1.0, -1.0]).unwrap();
+        let samples = super::super::parse_wav_to_samples(&wav).unwrap();
+        assert_eq!(samples.len(), 3);
+        assert!((samples[1] - 1.0).abs() < 1e-4);
+    }
+}
@@ -1,16 +1,24 @@
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 pub mod coreml;
+pub mod external;
 pub mod parakeet;
 pub mod whisper;
 
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 pub use coreml::CoreMlBackend;
+pub use external::ExternalBackend;
 pub use parakeet::ParakeetBackend;
-pub use whisper::WhisperBackend;
+pub use whisper::{WhisperBackend, WhisperDecoding};
 
 use hound::{SampleFormat, WavReader};
+use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// Polled during a decode; true stops it. See `TranscriptionBackend::set_abort`.
+pub type AbortCheck = std::sync::Arc<dyn Fn() -> bool + Send + Sync>;
 
 /// Stable settings value for FluidAudio's Parakeet v3 Core ML backend.
 /// Kept outside the macOS-only module so unsupported targets can reject a
@@ -21,9 +29,210 @@ pub fn is_coreml_model(model_name: &str) -> bool {
     model_name == COREML_MODEL_NAME
 }
 
+/// Start of a load or decode error that happened on the GPU. whisper.cpp
+/// reports a Metal or CUDA allocation or command-buffer failure only as a
+/// failed init or decode. A failed GPU load carries it only when the same file
+/// loads on the CPU; a failed GPU decode carries it, and the pipeline's CPU
+/// retry tells the two apart.
+pub const GPU_MEMORY_ERROR: &str = "Not enough GPU memory";
+
+pub(crate) fn gpu_memory_error(detail: String) -> String {
+    format!("{GPU_MEMORY_ERROR}: {detail}")
+}
+
+/// Whether `error` is a GPU failure that a CPU load or a smaller model may
+/// get past.
+pub fn is_gpu_memory_error(error: &str) -> bool {
+    error.starts_with(GPU_MEMORY_ERROR)
+}
+
+/// Strip sentence and quotation punctuation while preserving contractions
+/// (apostrophes between letters) and compound-word hyphens. Every backend
+/// applies it when smart punctuation is off, so the setting reads the same
+/// whichever model transcribed.
+pub(crate) fn strip_punctuation(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut result = String::with_capacity(input.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            // Apostrophe: keep if between two alphanumeric chars (contractions)
+            '\'' | '\u{2019}' => {
+                let prev_alnum = i > 0 && chars[i - 1].is_alphanumeric();
+                let next_alnum = i + 1 < chars.len() && chars[i + 1].is_alphanumeric();
+                if prev_alnum && next_alnum {
+                    result.push(c);
+                }
+            }
+            // Hyphen: keep if between two alphanumeric chars (compound words)
+            '-' => {
+                let prev_alnum = i > 0 && chars[i - 1].is_alphanumeric();
+                let next_alnum = i + 1 < chars.len() && chars[i + 1].is_alphanumeric();
+                if prev_alnum && next_alnum {
+                    result.push(c);
+                }
+            }
+            // Strip sentence and quotation punctuation
+            '.' | ',' | '!' | '?' | ';' | ':' | '"' | '\u{201C}' | '\u{201D}' | '\u{2018}'
+            | '\u{2014}' | '\u{2013}' | '\u{2026}' | '\u{AB}' | '\u{BB}' | '\u{BF}' | '\u{A1}'
+            | '\u{3002}' | '\u{3001}' | '\u{FF01}' | '\u{FF1F}' | '\u{30FB}' | '\u{300C}'
+            | '\u{300D}' | '\u{300E}' | '\u{300F}' => result.push(' '),
+            _ => result.push(c),
+        }
+    }
+
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Error from a backend that would send audio off this Mac while
+/// `keepAudioOnDevice` is on.
+pub const AUDIO_KEPT_ON_DEVICE: &str =
+    "Never Send Audio Off This Mac is on, so cloud transcription and plugins can't be used";
+
+/// The `keepAudioOnDevice` setting. Cloud and plugin backends check it before
+/// every load and transcription, so turning it on also stops a loaded one.
+static KEEP_AUDIO_ON_DEVICE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_keep_audio_on_device(keep: bool) {
+    KEEP_AUDIO_ON_DEVICE.store(keep, Ordering::Relaxed);
+}
+
+pub fn keep_audio_on_device() -> bool {
+    KEEP_AUDIO_ON_DEVICE.load(Ordering::Relaxed)
+}
+
+/// Fails while `keepAudioOnDevice` is on; for backends whose audio may leave
+/// this Mac.
+pub(crate) fn ensure_audio_may_leave_device() -> Result<(), String> {
+    if keep_audio_on_device() {
+        Err(AUDIO_KEPT_ON_DEVICE.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Performance cores on this machine, read once. Decode threads default to
+/// this: on Apple silicon, threads spilling onto the efficiency cores make
+/// whisper.cpp wait on the slowest core rather than finish sooner.
+pub fn performance_cores() -> u32 {
+    static CORES: std::sync::OnceLock<u32> = std::sync::OnceLock::new();
+    *CORES.get_or_init(|| {
+        #[cfg(target_os = "macos")]
+        if let Some(cores) = sysctl_count(b"hw.perflevel0.physicalcpu\0")
+            .or_else(|| sysctl_count(b"hw.physicalcpu\0"))
+        {
+            return cores;
+        }
+        std::thread::available_parallelism()
+            .map(|count| count.get() as u32)
+            .unwrap_or(4)
+    })
+}
+
+/// A positive integer sysctl such as `hw.physicalcpu`; `name` ends in NUL.
+#[cfg(target_os = "macos")]
+fn sysctl_count(name: &[u8]) -> Option<u32> {
+    let mut value: libc::c_int = 0;
+    let mut size = std::mem::size_of::<libc::c_int>();
+    // SAFETY: `name` is NUL-terminated, and `value`/`size` describe a live
+    // buffer of exactly one c_int that sysctl writes into.
+    let status = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr().cast(),
+            (&mut value as *mut libc::c_int).cast(),
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    (status == 0 && value > 0).then_some(value as u32)
+}
+
 /// Sample rate required by transcription models (16kHz).
 pub const WHISPER_SAMPLE_RATE: u32 = 16000;
 
+/// Average token log-probability below which a transcript is flagged as low
+/// confidence; whisper's reference decoder treats the same value as a failed
+/// decode.
+pub const LOW_CONFIDENCE_LOGPROB: f32 = -1.0;
+/// No-speech probability above which a transcript is flagged as low confidence.
+pub const LOW_CONFIDENCE_NO_SPEECH: f32 = 0.6;
+
+/// How sure whisper was of one segment.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentConfidence {
+    /// Mean log-probability of the segment's text tokens (0 is certain).
+    pub avg_logprob: f32,
+    /// Probability that the segment's window held no speech at all.
+    pub no_speech_prob: f32,
+    pub tokens: usize,
+}
+
+impl SegmentConfidence {
+    pub fn from_token_logprobs(logprobs: &[f32], no_speech_prob: f32) -> Self {
+        let avg_logprob = if logprobs.is_empty() {
+            0.0
+        } else {
+            logprobs.iter().sum::<f32>() / logprobs.len() as f32
+        };
+        Self {
+            avg_logprob,
+            no_speech_prob,
+            tokens: logprobs.len(),
+        }
+    }
+}
+
+/// How sure the model was of a whole transcript, from its segments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptConfidence {
+    /// `exp(avg_logprob)`: the geometric mean probability of the text
+    /// tokens, from 0 to 1.
+    pub score: f32,
+    /// Mean log-probability of every text token, across segments.
+    pub avg_logprob: f32,
+    /// The highest no-speech probability of any segment.
+    pub no_speech_prob: f32,
+    /// Below [`LOW_CONFIDENCE_LOGPROB`] or above [`LOW_CONFIDENCE_NO_SPEECH`]:
+    /// worth double-checking before trusting the text.
+    pub low: bool,
+    pub segments: Vec<SegmentConfidence>,
+}
+
+impl TranscriptConfidence {
+    /// Text tokens the decoder produced, across segments. Timestamps and
+    /// other special tokens aren't counted.
+    pub fn tokens(&self) -> usize {
+        self.segments.iter().map(|segment| segment.tokens).sum()
+    }
+
+    /// `None` when the segments hold no text tokens.
+    pub fn from_segments(segments: Vec<SegmentConfidence>) -> Option<Self> {
+        let tokens: usize = segments.iter().map(|segment| segment.tokens).sum();
+        if tokens == 0 {
+            return None;
+        }
+        let avg_logprob = segments
+            .iter()
+            .map(|segment| segment.avg_logprob * segment.tokens as f32)
+            .sum::<f32>()
+            / tokens as f32;
+        let no_speech_prob = segments
+            .iter()
+            .map(|segment| segment.no_speech_prob)
+            .fold(0.0, f32::max);
+        Some(Self {
+            score: avg_logprob.exp(),
+            avg_logprob,
+            no_speech_prob,
+            low: avg_logprob < LOW_CONFIDENCE_LOGPROB || no_speech_prob > LOW_CONFIDENCE_NO_SPEECH,
+            segments,
+        })
+    }
+}
+
 /// Abstraction over transcription engines (whisper, etc.)
 pub trait TranscriptionBackend: Send + Sync {
     /// Human-readable backend name (e.g., "whisper")
@@ -45,6 +254,35 @@ pub trait TranscriptionBackend: Send + Sync {
         smart_punctuation: bool,
     ) -> Result<String, String>;
 
+    /// Apply advanced Whisper decoding options to subsequent `transcribe`
+    /// calls. Backends without equivalent knobs ignore them.
+    fn set_whisper_decoding(&mut self, _decoding: &WhisperDecoding) {}
+
+    /// Load models without the GPU (`false`) until the next `reset`, which
+    /// turns it back on. A change unloads the current model. Backends without
+    /// a GPU path ignore it.
+    fn set_gpu_enabled(&mut self, _enabled: bool) {}
+
+    /// Fail subsequent `transcribe` calls that are still decoding at
+    /// `deadline`. Backends that can't interrupt a decode ignore it.
+    fn set_deadline(&mut self, _deadline: Option<Instant>) {}
+
+    /// Fail subsequent `transcribe` calls once `abort` returns true while
+    /// they decode. Backends that can't interrupt a decode ignore it.
+    fn set_abort(&mut self, _abort: Option<AbortCheck>) {}
+
+    /// Confidence of the last `transcribe` call. Backends that don't report
+    /// token probabilities return None.
+    fn last_confidence(&self) -> Option<TranscriptConfidence> {
+        None
+    }
+
+    /// Language code the last `transcribe` call detected when asked to
+    /// auto-detect. None when a language was set or the backend can't tell.
+    fn last_language(&self) -> Option<&'static str> {
+        None
+    }
+
     /// Count tokens in text using the model's tokenizer. Returns None if model not loaded.
     fn token_count(&self, text: &str) -> Option<usize>;
 
@@ -123,6 +361,45 @@ mod tests {
         buf
     }
 
+    #[test]
+    fn confidence_weights_segments_by_their_tokens() {
+        let confidence = TranscriptConfidence::from_segments(vec![
+            SegmentConfidence::from_token_logprobs(&[-0.1, -0.3], 0.01),
+            SegmentConfidence::from_token_logprobs(&[-1.0, -1.0, -1.0, -1.0, -1.0, -1.0], 0.2),
+        ])
+        .unwrap();
+        assert!((confidence.avg_logprob - -0.8).abs() < 1e-6);
+        assert!((confidence.score - (-0.8f32).exp()).abs() < 1e-6);
+        assert_eq!(confidence.no_speech_prob, 0.2);
+        assert!(!confidence.low);
+        assert_eq!(confidence.segments.len(), 2);
+    }
+
+    #[test]
+    fn unsure_or_speechless_transcripts_are_flagged_low() {
+        let unsure =
+            TranscriptConfidence::from_segments(vec![SegmentConfidence::from_token_logprobs(
+                &[-1.5, -0.9],
+                0.1,
+            )])
+            .unwrap();
+        assert!(unsure.low);
+        let speechless =
+            TranscriptConfidence::from_segments(vec![SegmentConfidence::from_token_logprobs(
+                &[-0.2],
+                0.8,
+            )])
+            .unwrap();
+        assert!(speechless.low);
+        assert_eq!(
+            TranscriptConfidence::from_segments(vec![SegmentConfidence::from_token_logprobs(
+                &[],
+                0.9
+            )]),
+            None
+        );
+    }
+
     #[test]
     fn parse_wav_silence() {
         let wav = make_test_wav(&[0i16; 160]);
@@ -162,9 +439,60 @@ mod tests {
         assert!(result.unwrap_err().contains("mono"));
     }
 
+    #[test]
+    fn gpu_memory_errors_are_recognized_by_their_prefix() {
+        assert!(is_gpu_memory_error(&gpu_memory_error(
+            "Failed to create whisper state: InitError".to_string()
+        )));
+        assert!(!is_gpu_memory_error(
+            "Failed to create whisper state: InitError"
+        ));
+    }
+
     #[test]
     fn parse_wav_rejects_garbage() {
         let result = parse_wav_to_samples(b"not a wav file");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn strip_basic_sentence_punctuation() {
+        assert_eq!(strip_punctuation("Hello, world!"), "Hello world");
+    }
+
+    #[test]
+    fn strip_preserves_apostrophe_in_contraction() {
+        assert_eq!(strip_punctuation("Don't do that."), "Don't do that");
+    }
+
+    #[test]
+    fn strip_preserves_hyphen_in_compound() {
+        assert_eq!(
+            strip_punctuation("It's state-of-the-art!"),
+            "It's state-of-the-art"
+        );
+    }
+
+    #[test]
+    fn strip_unicode_dashes_and_ellipsis() {
+        assert_eq!(
+            strip_punctuation("Hello\u{2026} world\u{2014}really?"),
+            "Hello world really"
+        );
+    }
+
+    #[test]
+    fn strip_empty_string() {
+        assert_eq!(strip_punctuation(""), "");
+    }
+
+    #[test]
+    fn strip_whitespace_only_with_punctuation() {
+        assert_eq!(strip_punctuation("   .   "), "");
+    }
+
+    #[test]
+    fn strip_preserves_french_contraction() {
+        assert_eq!(strip_punctuation("c'est la vie!"), "c'est la vie");
+    }
 }
@@ -1,8 +1,9 @@
 //! Parakeet (NVIDIA, via sherpa-onnx) transcription backend — self-contained spike.
 //!
 //! This module owns everything it needs (a variant registry mapping each
-//! selectable model to its on-disk bundle + decoding method, the `parakeet`
-//! classifier, and its own punctuation stripping) so it can be torn out cleanly.
+//! selectable model to its on-disk bundle + decoding method, and the
+//! `parakeet` classifier) so it can be torn out cleanly. Punctuation stripping
+//! is the shared `transcriber::strip_punctuation`, which stays behind.
 //!
 //! Variants are a small table in `variant_for` below: quantization (int8 / fp16)
 //! × decoding (greedy / beam). To add a combo, add a row here and a matching
@@ -22,16 +23,13 @@
 //!      `TranscriptionBackend`.
 //!   6. `Cargo.toml`: remove the `sherpa-onnx` dependency.
 
-use super::TranscriptionBackend;
+use super::{performance_cores, strip_punctuation, TranscriptionBackend};
 use sherpa_onnx::{OfflineRecognizer, OfflineRecognizerConfig, OfflineTransducerModelConfig};
 use std::path::{Path, PathBuf};
 
 /// Relative path under the platform data directory for app models.
 const APP_MODELS_REL: &[&str] = &["local-dictation", "models"];
 
-/// CPU inference threads for the recognizer.
-const NUM_THREADS: i32 = 4;
-
 /// Bundle directory name (sherpa-onnx release folder name) for the fp16 model.
 const FP16_DIR: &str = "sherpa-onnx-nemo-parakeet-tdt-0.6b-v2-fp16";
 
@@ -51,7 +49,11 @@ struct ParakeetVariant {
 impl ParakeetVariant {
     /// True if every required file exists with non-zero size under `models_dir`.
     fn is_complete(&self, models_dir: &Path) -> bool {
-        let dir = models_dir.join(self.dir);
+        self.bundle_complete(&models_dir.join(self.dir))
+    }
+
+    /// True if every required file exists with non-zero size directly in `dir`.
+    fn bundle_complete(&self, dir: &Path) -> bool {
         [self.encoder, self.decoder, self.joiner, "tokens.txt"]
             .iter()
             .all(|f| {
@@ -110,6 +112,12 @@ pub(crate) fn specific_model_exists_in(model_name: &str, models_dir: &Path) -> b
     variant_for(model_name).is_some_and(|variant| variant.is_complete(models_dir))
 }
 
+/// Check a bundle directory itself, whatever it is named. Model import uses this
+/// to validate a user-supplied bundle before copying or linking it.
+pub(crate) fn bundle_dir_complete(model_name: &str, bundle_dir: &Path) -> bool {
+    variant_for(model_name).is_some_and(|variant| variant.bundle_complete(bundle_dir))
+}
+
 /// Download info for a Parakeet model: `(tarball_url, extracted_dir_name)`.
 /// The sherpa-onnx release ships each bundle as `<dir>.tar.bz2` which unpacks
 /// to a top-level `<dir>/` folder. Returns None for unknown models.
@@ -183,7 +191,7 @@ impl TranscriptionBackend for ParakeetBackend {
         };
         config.model_config.tokens = Some(to_str(model_dir.join("tokens.txt"))?);
         config.model_config.model_type = Some("nemo_transducer".to_string());
-        config.model_config.num_threads = NUM_THREADS;
+        config.model_config.num_threads = performance_cores() as i32;
         config.model_config.provider = Some("cpu".to_string());
         config.decoding_method = Some(variant.decoding_method.to_string());
 
@@ -261,41 +269,6 @@ impl TranscriptionBackend for ParakeetBackend {
     }
 }
 
-/// Strip sentence and quotation punctuation while preserving contractions
-/// (apostrophes between letters) and compound-word hyphens. Kept local so this
-/// backend stays self-contained and removable.
-fn strip_punctuation(input: &str) -> String {
-    let chars: Vec<char> = input.chars().collect();
-    let mut result = String::with_capacity(input.len());
-
-    for (i, &c) in chars.iter().enumerate() {
-        match c {
-            '\'' | '\u{2019}' => {
-                let prev_alnum = i > 0 && chars[i - 1].is_alphanumeric();
-                let next_alnum = i + 1 < chars.len() && chars[i + 1].is_alphanumeric();
-                if prev_alnum && next_alnum {
-                    result.push(c);
-                }
-            }
-            '-' => {
-                let prev_alnum = i > 0 && chars[i - 1].is_alphanumeric();
-                let next_alnum = i + 1 < chars.len() && chars[i + 1].is_alphanumeric();
-                if prev_alnum && next_alnum {
-                    result.push(c);
-                }
-            }
-            '.' | ',' | '!' | '?' | ';' | ':' | '"' | '\u{201C}' | '\u{201D}'
-            | '\u{2018}' | '\u{2014}' | '\u{2013}' | '\u{2026}'
-            | '\u{AB}' | '\u{BB}' | '\u{BF}' | '\u{A1}'
-            | '\u{3002}' | '\u{3001}' | '\u{FF01}' | '\u{FF1F}'
-            | '\u{30FB}' | '\u{300C}' | '\u{300D}' | '\u{300E}' | '\u{300F}' => result.push(' '),
-            _ => result.push(c),
-        }
-    }
-
-    result.split_whitespace().collect::<Vec<_>>().join(" ")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,24 +302,4 @@ mod tests {
         assert_eq!(dir, FP16_DIR);
         assert!(download_spec("base.en").is_none());
     }
-
-    #[test]
-    fn strip_basic_sentence_punctuation() {
-        assert_eq!(strip_punctuation("Hello, world!"), "Hello world");
-    }
-
-    #[test]
-    fn strip_preserves_apostrophe_in_contraction() {
-        assert_eq!(strip_punctuation("Don't do that."), "Don't do that");
-    }
-
-    #[test]
-    fn strip_preserves_hyphen_in_compound() {
-        assert_eq!(strip_punctuation("It's state-of-the-art!"), "It's state-of-the-art");
-    }
-
-    #[test]
-    fn strip_empty_string() {
-        assert_eq!(strip_punctuation(""), "");
-    }
 }
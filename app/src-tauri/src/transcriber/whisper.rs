@@ -1,6 +1,10 @@
-use super::TranscriptionBackend;
+use super::{
+    strip_punctuation, AbortCheck, SegmentConfidence, TranscriptConfidence, TranscriptionBackend,
+};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::Once;
+use std::time::Instant;
 use whisper_rs::{
     install_logging_hooks, FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters,
     WhisperState,
@@ -13,6 +17,93 @@ static INIT_LOGGING: Once = Once::new();
 /// early end-of-text token.
 const SINGLE_SEGMENT_MAX_SAMPLES: usize = 12 * super::WHISPER_SAMPLE_RATE as usize;
 
+/// Upper bound for user-selected beam sizes; whisper.cpp allocates one decoder
+/// per beam, so larger values only add latency.
+pub const MAX_BEAM_SIZE: u32 = 8;
+
+/// Upper bound for an explicit decode thread count.
+pub const MAX_THREADS: u32 = 16;
+
+/// How Whisper picks tokens. Greedy is the shipped default; beam search trades
+/// latency for accuracy on hard audio.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WhisperSampling {
+    #[default]
+    Greedy,
+    BeamSearch,
+}
+
+/// Whether a decode runs in whisper.cpp's single-segment mode. `Auto` keeps
+/// the duration rule in [`should_use_single_segment`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SingleSegmentMode {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Advanced decoding knobs for power users. `Default` reproduces the decode
+/// Murmur has always run; other backends ignore these entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WhisperDecoding {
+    pub sampling: WhisperSampling,
+    /// Beams for `BeamSearch`, clamped to `1..=MAX_BEAM_SIZE`. Unused by greedy.
+    pub beam_size: u32,
+    /// Initial sampling temperature. 0.0 is deterministic.
+    pub temperature: f32,
+    /// Segments whose no-speech probability exceeds this are dropped.
+    pub no_speech_threshold: f32,
+    /// Decode threads. `None` uses one per performance core
+    /// (`transcriber::performance_cores`).
+    pub threads: Option<u32>,
+    pub single_segment: SingleSegmentMode,
+}
+
+impl Default for WhisperDecoding {
+    fn default() -> Self {
+        Self {
+            sampling: WhisperSampling::Greedy,
+            beam_size: 5,
+            temperature: 0.0,
+            // whisper.cpp's `whisper_full_default_params` value.
+            no_speech_threshold: 0.6,
+            threads: None,
+            single_segment: SingleSegmentMode::Auto,
+        }
+    }
+}
+
+impl WhisperDecoding {
+    fn sampling_strategy(&self) -> SamplingStrategy {
+        match self.sampling {
+            WhisperSampling::Greedy => SamplingStrategy::Greedy { best_of: 1 },
+            WhisperSampling::BeamSearch => SamplingStrategy::BeamSearch {
+                beam_size: self.beam_size.clamp(1, MAX_BEAM_SIZE) as i32,
+                patience: -1.0,
+            },
+        }
+    }
+
+    fn single_segment(&self, sample_count: usize) -> bool {
+        match self.single_segment {
+            SingleSegmentMode::Auto => should_use_single_segment(sample_count),
+            SingleSegmentMode::Always => true,
+            SingleSegmentMode::Never => false,
+        }
+    }
+
+    /// Threads for the next decode: the explicit count, or one per
+    /// performance core, within `1..=MAX_THREADS`.
+    pub fn decode_threads(&self) -> u32 {
+        self.threads
+            .unwrap_or_else(super::performance_cores)
+            .clamp(1, MAX_THREADS)
+    }
+}
+
 /// Relative path under the platform data directory for app models.
 const APP_MODELS_REL: &[&str] = &["local-dictation", "models"];
 
@@ -53,8 +144,54 @@ fn get_model_search_paths() -> Vec<PathBuf> {
     paths
 }
 
+/// Where a catalog Whisper file is published. Distil-Whisper files live in
+/// their own repos under upstream names and are saved as `ggml-<model>.bin`.
+pub fn download_url(model_name: &str) -> String {
+    match model_name {
+        "distil-large-v3" => "https://huggingface.co/distil-whisper/distil-large-v3-ggml/resolve/main/ggml-distil-large-v3.bin".to_string(),
+        "distil-medium.en" => "https://huggingface.co/distil-whisper/distil-medium.en/resolve/main/ggml-medium-32-2.en.bin".to_string(),
+        _ => format!(
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-{}.bin",
+            model_name
+        ),
+    }
+}
+
+/// Directory holding custom GGML files, beside the catalog models.
+pub(crate) fn custom_models_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|data_dir| app_models_dir(&data_dir).join("custom"))
+}
+
+/// `custom:<file>` names for every usable `.bin` in the custom models dir.
+pub(crate) fn custom_model_names() -> Vec<String> {
+    let Some(entries) = custom_models_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .map(|file| format!("{}{}", crate::model_runtime::CUSTOM_MODEL_PREFIX, file))
+        .filter(|name| crate::model_runtime::custom_model_file(name).is_some())
+        .collect();
+    names.sort();
+    names
+}
+
 /// Get the path to a specific model file, searching multiple locations.
 fn get_model_path(model_name: &str) -> Result<PathBuf, String> {
+    if let Some(file) = crate::model_runtime::custom_model_file(model_name) {
+        let path = custom_models_dir()
+            .ok_or_else(|| "Could not find application data directory".to_string())?
+            .join(file);
+        return if path.is_file() {
+            Ok(path)
+        } else {
+            Err(format!(
+                "Custom model '{}' not found at {}",
+                file,
+                path.display()
+            ))
+        };
+    }
     let filename = format!("ggml-{}.bin", model_name);
     let search_paths = get_model_search_paths();
 
@@ -72,8 +209,10 @@ fn get_model_path(model_name: &str) -> Result<PathBuf, String> {
         .join("\n");
 
     Err(format!(
-        "Model '{}' not found. Searched locations:\n{}\n\nDownload from: https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{}",
-        filename, searched_locations, filename
+        "Model '{}' not found. Searched locations:\n{}\n\nDownload from: {}",
+        filename,
+        searched_locations,
+        download_url(model_name)
     ))
 }
 
@@ -85,6 +224,55 @@ pub struct WhisperBackend {
     context: Option<WhisperContext>,
     state: Option<WhisperState>,
     loaded_model_name: Option<String>,
+    decoding: WhisperDecoding,
+    last_confidence: Option<TranscriptConfidence>,
+    last_language: Option<&'static str>,
+    deadline: Option<Instant>,
+    abort: Option<AbortCheck>,
+    /// Cleared after the GPU ran out of memory; `reset` sets it again.
+    use_gpu: bool,
+}
+
+/// Load `path` into a context and its decode state.
+fn load_context(path: &str, use_gpu: bool) -> Result<(WhisperContext, WhisperState), String> {
+    let mut params = WhisperContextParameters::default();
+    // Murmur consumes segment text, not DTW token timestamps. Flash
+    // attention therefore gives Metal/CUDA a fused, lower-memory path
+    // without removing any output the application uses.
+    params.flash_attn(true);
+    params.use_gpu(use_gpu);
+    let ctx = WhisperContext::new_with_params(path, params)
+        .map_err(|e| format!("Failed to load whisper model: {}", e))?;
+    let state = ctx
+        .create_state()
+        .map_err(|e| format!("Failed to create whisper state: {}", e))?;
+    Ok((ctx, state))
+}
+
+/// whisper.cpp reports a Metal allocation failure only as a failed init, just
+/// like a corrupt or truncated file. A GPU load failure is tagged as GPU
+/// memory only when `loads_on_cpu` shows the same file loads without the GPU.
+fn classify_load_failure(
+    detail: String,
+    on_gpu: bool,
+    loads_on_cpu: impl FnOnce() -> bool,
+) -> String {
+    if on_gpu && loads_on_cpu() {
+        super::gpu_memory_error(detail)
+    } else {
+        detail
+    }
+}
+
+/// The accelerator whisper.cpp was built for.
+fn gpu_backend() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "metal"
+    } else if cfg!(target_os = "linux") && std::path::Path::new("/dev/nvidia0").exists() {
+        "cuda"
+    } else {
+        "cpu"
+    }
 }
 
 impl WhisperBackend {
@@ -92,6 +280,10 @@ impl WhisperBackend {
         Self::default()
     }
 
+    fn on_gpu(&self) -> bool {
+        self.use_gpu && gpu_backend() != "cpu"
+    }
+
     fn transcribe_with_single_segment(
         &mut self,
         samples: &[f32],
@@ -100,13 +292,22 @@ impl WhisperBackend {
         smart_punctuation: bool,
         single_segment: bool,
     ) -> Result<String, String> {
+        self.last_confidence = None;
+        self.last_language = None;
+        // Token ids from end-of-text up are timestamps and other specials,
+        // which carry no evidence about the words.
+        let first_special_token = self.context.as_ref().map(|ctx| ctx.token_eot());
+        let on_gpu = self.on_gpu();
         let state = self
             .state
             .as_mut()
             .ok_or_else(|| "Whisper state not initialized. Call load_model() first.".to_string())?;
         tracing::info!(target: "pipeline", "whisper: reusing cached state for transcription");
 
-        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        let mut params = FullParams::new(self.decoding.sampling_strategy());
+        params.set_temperature(self.decoding.temperature);
+        params.set_no_speech_thold(self.decoding.no_speech_threshold);
+        params.set_n_threads(self.decoding.decode_threads() as i32);
         params.set_language(whisper_language_param(language));
         params.set_print_special(false);
         params.set_print_progress(false);
@@ -118,14 +319,37 @@ impl WhisperBackend {
             params.set_initial_prompt(prompt);
         }
         params.set_debug_mode(false);
+        let deadline = self.deadline;
+        let abort = self.abort.clone();
+        let aborted = move || {
+            deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                || abort.as_ref().is_some_and(|abort| abort())
+        };
+        if self.deadline.is_some() || self.abort.is_some() {
+            params.set_abort_callback_safe(aborted.clone());
+        }
 
-        state
-            .full(params, samples)
-            .map_err(|e| format!("Transcription failed: {}", e))?;
+        let result = state.full(params, samples);
+        // An aborted decode may still report success with partial text.
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err("Transcription ran past its deadline".to_string());
+        }
+        if aborted() {
+            return Err("Transcription was aborted".to_string());
+        }
+        result.map_err(|e| {
+            let detail = format!("Transcription failed: {}", e);
+            if on_gpu {
+                super::gpu_memory_error(detail)
+            } else {
+                detail
+            }
+        })?;
 
         let num_segments = state.full_n_segments();
 
         let mut text = String::new();
+        let mut segments = Vec::with_capacity(num_segments.max(0) as usize);
         for i in 0..num_segments {
             let segment = state
                 .get_segment(i)
@@ -134,6 +358,20 @@ impl WhisperBackend {
                 .to_str()
                 .map_err(|e| format!("Failed to get text for segment {}: {}", i, e))?;
             append_segment(&mut text, segment_text);
+            let logprobs: Vec<f32> = (0..segment.n_tokens())
+                .filter_map(|t| segment.get_token(t))
+                .map(|token| token.token_data())
+                .filter(|data| first_special_token.is_none_or(|special| data.id < special))
+                .map(|data| data.plog)
+                .collect();
+            segments.push(SegmentConfidence::from_token_logprobs(
+                &logprobs,
+                segment.no_speech_probability(),
+            ));
+        }
+        self.last_confidence = TranscriptConfidence::from_segments(segments);
+        if whisper_language_param(language).is_none() {
+            self.last_language = whisper_rs::get_lang_str(state.full_lang_id_from_state());
         }
 
         let trimmed = text.trim().to_string();
@@ -173,6 +411,12 @@ impl Default for WhisperBackend {
             context: None,
             state: None,
             loaded_model_name: None,
+            decoding: WhisperDecoding::default(),
+            last_confidence: None,
+            last_language: None,
+            deadline: None,
+            abort: None,
+            use_gpu: true,
         }
     }
 }
@@ -199,27 +443,16 @@ impl TranscriptionBackend for WhisperBackend {
             .to_str()
             .ok_or_else(|| "Model path contains invalid UTF-8 characters".to_string())?;
 
-        let mut params = WhisperContextParameters::default();
-        // Murmur consumes segment text, not DTW token timestamps. Flash
-        // attention therefore gives Metal/CUDA a fused, lower-memory path
-        // without removing any output the application uses.
-        params.flash_attn(true);
-
-        let gpu_backend = if cfg!(target_os = "macos") {
-            "metal"
-        } else if cfg!(target_os = "linux") && std::path::Path::new("/dev/nvidia0").exists() {
-            "cuda"
-        } else {
-            "cpu"
-        };
+        let gpu_backend = if self.use_gpu { gpu_backend() } else { "cpu" };
         tracing::info!(target: "pipeline", model = model_name, gpu = gpu_backend, "whisper_model_loading");
 
-        let ctx = WhisperContext::new_with_params(path_str, params)
-            .map_err(|e| format!("Failed to load whisper model: {}", e))?;
-
-        let state = ctx
-            .create_state()
-            .map_err(|e| format!("Failed to create whisper state: {}", e))?;
+        let (ctx, state) = load_context(path_str, self.use_gpu).map_err(|detail| {
+            classify_load_failure(detail, self.on_gpu(), || {
+                let loads = load_context(path_str, false).is_ok();
+                tracing::warn!(target: "pipeline", model = model_name, loads_on_cpu = loads, "whisper GPU load failed");
+                loads
+            })
+        })?;
         self.context = Some(ctx);
         self.state = Some(state);
         self.loaded_model_name = Some(model_name.to_string());
@@ -238,15 +471,43 @@ impl TranscriptionBackend for WhisperBackend {
         initial_prompt: Option<&str>,
         smart_punctuation: bool,
     ) -> Result<String, String> {
+        let single_segment = self.decoding.single_segment(samples.len());
         self.transcribe_with_single_segment(
             samples,
             language,
             initial_prompt,
             smart_punctuation,
-            should_use_single_segment(samples.len()),
+            single_segment,
         )
     }
 
+    fn set_whisper_decoding(&mut self, decoding: &WhisperDecoding) {
+        self.decoding = *decoding;
+    }
+
+    fn set_gpu_enabled(&mut self, enabled: bool) {
+        if enabled != self.use_gpu {
+            self.reset();
+        }
+        self.use_gpu = enabled;
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    fn set_abort(&mut self, abort: Option<AbortCheck>) {
+        self.abort = abort;
+    }
+
+    fn last_confidence(&self) -> Option<TranscriptConfidence> {
+        self.last_confidence.clone()
+    }
+
+    fn last_language(&self) -> Option<&'static str> {
+        self.last_language
+    }
+
     fn token_count(&self, text: &str) -> Option<usize> {
         let ctx = self.context.as_ref()?;
         ctx.tokenize(text, 1024).ok().map(|tokens| tokens.len())
@@ -277,6 +538,9 @@ impl TranscriptionBackend for WhisperBackend {
         drop(self.state.take());
         drop(self.context.take());
         self.loaded_model_name = None;
+        self.last_confidence = None;
+        self.last_language = None;
+        self.use_gpu = true;
     }
 }
 
@@ -292,47 +556,97 @@ fn whisper_language_param(language: &str) -> Option<&str> {
     }
 }
 
-fn strip_punctuation(input: &str) -> String {
-    let chars: Vec<char> = input.chars().collect();
-    let mut result = String::with_capacity(input.len());
-
-    for (i, &c) in chars.iter().enumerate() {
-        match c {
-            // Apostrophe: keep if between two alphanumeric chars (contractions)
-            '\'' | '\u{2019}' => {
-                let prev_alnum = i > 0 && chars[i - 1].is_alphanumeric();
-                let next_alnum = i + 1 < chars.len() && chars[i + 1].is_alphanumeric();
-                if prev_alnum && next_alnum {
-                    result.push(c);
-                }
-            }
-            // Hyphen: keep if between two alphanumeric chars (compound words)
-            '-' => {
-                let prev_alnum = i > 0 && chars[i - 1].is_alphanumeric();
-                let next_alnum = i + 1 < chars.len() && chars[i + 1].is_alphanumeric();
-                if prev_alnum && next_alnum {
-                    result.push(c);
-                }
-            }
-            // Strip sentence and quotation punctuation
-            '.' | ',' | '!' | '?' | ';' | ':' | '"' | '\u{201C}' | '\u{201D}' | '\u{2018}'
-            | '\u{2014}' | '\u{2013}' | '\u{2026}' | '\u{AB}' | '\u{BB}' | '\u{BF}' | '\u{A1}'
-            | '\u{3002}' | '\u{3001}' | '\u{FF01}' | '\u{FF1F}' | '\u{30FB}' | '\u{300C}'
-            | '\u{300D}' | '\u{300E}' | '\u{300F}' => result.push(' '),
-            _ => result.push(c),
-        }
-    }
-
-    result.split_whitespace().collect::<Vec<_>>().join(" ")
-}
-
 #[cfg(test)]
 mod tests {
     use super::{
-        append_segment, should_use_single_segment, specific_model_exists, strip_punctuation,
-        whisper_language_param, WhisperBackend, SINGLE_SEGMENT_MAX_SAMPLES,
+        append_segment, classify_load_failure, should_use_single_segment, specific_model_exists,
+        whisper_language_param, SingleSegmentMode, WhisperBackend, WhisperDecoding,
+        WhisperSampling, MAX_BEAM_SIZE, MAX_THREADS, SINGLE_SEGMENT_MAX_SAMPLES,
+    };
+    use crate::transcriber::{
+        is_gpu_memory_error, parse_wav_to_samples, performance_cores, TranscriptionBackend,
     };
-    use crate::transcriber::{parse_wav_to_samples, TranscriptionBackend};
+    use whisper_rs::SamplingStrategy;
+
+    // --- GPU fallback --------------------------------------------------------
+
+    #[test]
+    fn only_gpu_loads_the_cpu_can_do_are_tagged_as_gpu_memory_failures() {
+        let detail = || "Failed to create whisper state".to_string();
+        assert!(!is_gpu_memory_error(&classify_load_failure(
+            detail(),
+            false,
+            || unreachable!("a CPU load failure isn't probed again"),
+        )));
+        // A file the CPU can't load either is broken, not out of GPU memory.
+        assert!(!is_gpu_memory_error(&classify_load_failure(
+            detail(),
+            true,
+            || false
+        )));
+        assert!(is_gpu_memory_error(&classify_load_failure(
+            detail(),
+            true,
+            || true
+        )));
+
+        let mut backend = WhisperBackend::new();
+        backend.set_gpu_enabled(false);
+        assert!(!backend.on_gpu());
+
+        // Unloading puts the GPU back for the next load.
+        backend.reset();
+        assert!(backend.use_gpu);
+    }
+
+    // --- decoding options --------------------------------------------------
+
+    #[test]
+    fn default_decoding_matches_shipped_greedy_auto_segment() {
+        let decoding = WhisperDecoding::default();
+        assert!(matches!(
+            decoding.sampling_strategy(),
+            SamplingStrategy::Greedy { best_of: 1 }
+        ));
+        assert!(decoding.single_segment(SINGLE_SEGMENT_MAX_SAMPLES));
+        assert!(!decoding.single_segment(SINGLE_SEGMENT_MAX_SAMPLES + 1));
+    }
+
+    #[test]
+    fn beam_search_clamps_beam_size_and_forced_segment_modes_ignore_duration() {
+        let mut decoding = WhisperDecoding {
+            sampling: WhisperSampling::BeamSearch,
+            beam_size: 99,
+            single_segment: SingleSegmentMode::Always,
+            ..WhisperDecoding::default()
+        };
+        assert!(matches!(
+            decoding.sampling_strategy(),
+            SamplingStrategy::BeamSearch { beam_size, .. } if beam_size == MAX_BEAM_SIZE as i32
+        ));
+        assert!(decoding.single_segment(SINGLE_SEGMENT_MAX_SAMPLES * 4));
+
+        decoding.beam_size = 0;
+        decoding.single_segment = SingleSegmentMode::Never;
+        assert!(matches!(
+            decoding.sampling_strategy(),
+            SamplingStrategy::BeamSearch { beam_size: 1, .. }
+        ));
+        assert!(!decoding.single_segment(16_000));
+    }
+
+    #[test]
+    fn automatic_threads_follow_the_performance_cores() {
+        let mut decoding = WhisperDecoding::default();
+        assert_eq!(
+            decoding.decode_threads(),
+            performance_cores().clamp(1, MAX_THREADS)
+        );
+        decoding.threads = Some(64);
+        assert_eq!(decoding.decode_threads(), MAX_THREADS);
+        decoding.threads = Some(0);
+        assert_eq!(decoding.decode_threads(), 1);
+    }
 
     // --- append_segment ----------------------------------------------------
 
@@ -497,45 +811,4 @@ mod tests {
         assert_eq!(whisper_language_param("es"), Some("es"));
         assert_eq!(whisper_language_param("ja"), Some("ja"));
     }
-
-    #[test]
-    fn strip_basic_sentence_punctuation() {
-        assert_eq!(strip_punctuation("Hello, world!"), "Hello world");
-    }
-
-    #[test]
-    fn strip_preserves_apostrophe_in_contraction() {
-        assert_eq!(strip_punctuation("Don't do that."), "Don't do that");
-    }
-
-    #[test]
-    fn strip_preserves_hyphen_in_compound() {
-        assert_eq!(
-            strip_punctuation("It's state-of-the-art!"),
-            "It's state-of-the-art"
-        );
-    }
-
-    #[test]
-    fn strip_unicode_dashes_and_ellipsis() {
-        assert_eq!(
-            strip_punctuation("Hello\u{2026} world\u{2014}really?"),
-            "Hello world really"
-        );
-    }
-
-    #[test]
-    fn strip_empty_string() {
-        assert_eq!(strip_punctuation(""), "");
-    }
-
-    #[test]
-    fn strip_whitespace_only_with_punctuation() {
-        assert_eq!(strip_punctuation("   .   "), "");
-    }
-
-    #[test]
-    fn strip_preserves_french_contraction() {
-        assert_eq!(strip_punctuation("c'est la vie!"), "c'est la vie");
-    }
 }
@@ -12,6 +12,9 @@ use crate::cleanup::CleanupOptions;
 use crate::cli_command::{canonicalize_cli, is_cli_utterance, CliFormattingMode, CliLexicon};
 use crate::correction::CorrectionMatcher;
 use crate::ide_context::IdeContextIndex;
+use crate::language_rules::LanguageRules;
+use crate::redaction::Redactor;
+use crate::replacement_rules::ReplacementRules;
 
 pub(crate) const CLEANUP_STAGE: &str = "cleanup";
 pub(crate) const VOICE_COMMANDS_STAGE: &str = "voice_commands";
@@ -19,6 +22,8 @@ pub(crate) const SMART_CORRECTION_STAGE: &str = "smart_correction";
 pub(crate) const SMART_FORMATTING_STAGE: &str = "smart_formatting";
 pub(crate) const IDE_CONTEXT_STAGE: &str = "ide_context";
 pub(crate) const CLI_COMMAND_STAGE: &str = "cli_command";
+pub(crate) const REPLACEMENT_RULES_STAGE: &str = "replacement_rules";
+pub(crate) const REDACTION_STAGE: &str = "redaction";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum TranscriptSource {
@@ -200,6 +205,9 @@ pub(crate) struct TranscriptPipelineOutput {
     /// Original ASR text retained only for the lifetime of this pipeline result.
     /// It is never serialized, persisted, or logged.
     pub original_text: String,
+    /// The text as it reached the replacement rules stage, kept so the rules
+    /// can be previewed against what they actually saw.
+    pub rules_input: String,
     pub text: String,
     pub stages: Vec<StageReport>,
 }
@@ -241,9 +249,12 @@ impl TranscriptPipeline {
             cli_lexicon,
             ide_context_index,
             voice_command_runtime,
+            redactor,
+            replacement_rules,
+            language,
         } = resources;
         Self::new(vec![
-            Box::new(CleanupStage),
+            Box::new(CleanupStage { language }),
             Box::new(VoiceCommandsStage {
                 voice_commands: if voice_commands.is_empty() {
                     custom_commands
@@ -279,6 +290,10 @@ impl TranscriptPipeline {
             Box::new(CliCommandStage {
                 lexicon: cli_lexicon,
             }),
+            Box::new(ReplacementRulesStage {
+                rules: replacement_rules,
+            }),
+            Box::new(RedactionStage { redactor }),
         ])
     }
 
@@ -289,9 +304,13 @@ impl TranscriptPipeline {
         mut observer: Option<&mut dyn StageTextObserver>,
     ) -> Result<TranscriptPipelineOutput, TranscriptPipelineError> {
         let original_text = text.clone();
+        let mut rules_input = None;
         let mut reports = Vec::with_capacity(self.stages.len());
 
         for stage in &self.stages {
+            if stage.name() == REPLACEMENT_RULES_STAGE {
+                rules_input = Some(text.clone());
+            }
             let started = Instant::now();
             let policy = stage.failure_policy();
 
@@ -364,6 +383,7 @@ impl TranscriptPipeline {
 
         Ok(TranscriptPipelineOutput {
             original_text,
+            rules_input: rules_input.unwrap_or_else(|| text.clone()),
             text,
             stages: reports,
         })
@@ -396,6 +416,13 @@ pub(crate) struct TranscriptTransformResources {
     pub cli_lexicon: CliLexicon,
     pub ide_context_index: Option<Arc<IdeContextIndex>>,
     pub voice_command_runtime: Option<Arc<dyn crate::voice_commands::VoiceCommandRuntime>>,
+    /// Present only when the user enabled redaction; its presence enables the
+    /// final stage for both live and imported transcripts.
+    pub redactor: Option<Arc<Redactor>>,
+    /// The user's enabled replacement rules, compiled in order.
+    pub replacement_rules: Option<Arc<ReplacementRules>>,
+    /// Cleanup rules for the transcript's language.
+    pub language: &'static LanguageRules,
 }
 
 impl TranscriptTransformResources {
@@ -407,6 +434,9 @@ impl TranscriptTransformResources {
             cli_lexicon: CliLexicon::from_context(None, &[]),
             ide_context_index: None,
             voice_command_runtime: None,
+            redactor: None,
+            replacement_rules: None,
+            language: &crate::language_rules::ENGLISH,
         }
     }
 }
@@ -432,7 +462,9 @@ pub(crate) fn transform_transcript_observed(
     TranscriptPipeline::standard(resources).run(text, context, Some(observer))
 }
 
-struct CleanupStage;
+struct CleanupStage {
+    language: &'static LanguageRules,
+}
 
 impl TranscriptTransform for CleanupStage {
     fn name(&self) -> &'static str {
@@ -456,6 +488,7 @@ impl TranscriptTransform for CleanupStage {
             CleanupOptions {
                 remove_filler: context.stages.cleanup_remove_filler,
                 capitalize: context.stages.cleanup_capitalize,
+                language: self.language,
             },
         ))
     }
@@ -574,6 +607,61 @@ impl TranscriptTransform for CliCommandStage {
     }
 }
 
+/// Runs after every built-in stage so a user rule has the final say over
+/// wording, and before redaction so a rule can't reinsert hidden text.
+struct ReplacementRulesStage {
+    rules: Option<Arc<ReplacementRules>>,
+}
+
+impl TranscriptTransform for ReplacementRulesStage {
+    fn name(&self) -> &'static str {
+        REPLACEMENT_RULES_STAGE
+    }
+
+    fn failure_policy(&self) -> StageFailurePolicy {
+        StageFailurePolicy::Required
+    }
+
+    fn enabled(&self, context: &TranscriptContext) -> bool {
+        context.source == TranscriptSource::Live && self.rules.is_some()
+    }
+
+    fn transform(&self, text: &str, _context: &TranscriptContext) -> Result<String, StageError> {
+        Ok(self
+            .rules
+            .as_ref()
+            .map_or_else(|| text.to_string(), |rules| rules.apply(text)))
+    }
+}
+
+/// Runs last so nothing a later stage inserts (voice command content, CLI
+/// canonicalization) escapes the filter.
+struct RedactionStage {
+    redactor: Option<Arc<Redactor>>,
+}
+
+impl TranscriptTransform for RedactionStage {
+    fn name(&self) -> &'static str {
+        REDACTION_STAGE
+    }
+
+    fn failure_policy(&self) -> StageFailurePolicy {
+        // Falling back would deliver exactly the text the user asked to hide.
+        StageFailurePolicy::Required
+    }
+
+    fn enabled(&self, _context: &TranscriptContext) -> bool {
+        self.redactor.is_some()
+    }
+
+    fn transform(&self, text: &str, _context: &TranscriptContext) -> Result<String, StageError> {
+        Ok(self
+            .redactor
+            .as_ref()
+            .map_or_else(|| text.to_string(), |redactor| redactor.redact(text)))
+    }
+}
+
 impl TranscriptTransform for SmartCorrectionStage {
     fn name(&self) -> &'static str {
         SMART_CORRECTION_STAGE
@@ -671,6 +759,9 @@ mod tests {
             cli_lexicon: CliLexicon::from_context(None, &[]),
             ide_context_index: None,
             voice_command_runtime: None,
+            redactor: None,
+            replacement_rules: None,
+            language: &crate::language_rules::ENGLISH,
         }
     }
 
@@ -685,6 +776,7 @@ mod tests {
                 CleanupOptions {
                     remove_filler: stages.cleanup_remove_filler,
                     capitalize: stages.cleanup_capitalize,
+                    language: resources.language,
                 },
             )
         } else {
@@ -770,6 +862,8 @@ mod tests {
                 SMART_FORMATTING_STAGE,
                 IDE_CONTEXT_STAGE,
                 CLI_COMMAND_STAGE,
+                REPLACEMENT_RULES_STAGE,
+                REDACTION_STAGE,
             ]
         );
     }
@@ -901,13 +995,116 @@ mod tests {
         let output = transform_transcript(raw.to_string(), &context, resources(true)).unwrap();
         assert_eq!(output.text.as_bytes(), raw.as_bytes());
         assert_eq!(output.original_text.as_bytes(), raw.as_bytes());
-        assert_eq!(output.stages.len(), 6);
+        assert_eq!(output.stages.len(), 8);
         assert!(output
             .stages
             .iter()
             .all(|stage| stage.outcome == StageOutcome::Skipped));
     }
 
+    #[test]
+    fn redaction_runs_last_for_live_and_file_transcripts_once_configured() {
+        let redactor = Redactor::build(&crate::state::RedactionSettings {
+            enabled: true,
+            words: vec!["test@example.com".to_string()],
+            ..crate::state::RedactionSettings::default()
+        })
+        .unwrap()
+        .map(Arc::new);
+        let with_redactor = || TranscriptTransformResources {
+            redactor: redactor.clone(),
+            ..resources(false)
+        };
+
+        // The voice command inserts the address; redaction still catches it.
+        let live = transform_transcript(
+            "send it to my email".to_string(),
+            &live_context(TranscriptStageConfig {
+                voice_commands_enabled: true,
+                ..TranscriptStageConfig::verbatim()
+            }),
+            with_redactor(),
+        )
+        .unwrap();
+        assert_eq!(live.text, "send it to ****************");
+        assert_eq!(live.stages.last().unwrap().stage, REDACTION_STAGE);
+        assert_eq!(live.stages.last().unwrap().outcome, StageOutcome::Applied);
+
+        let file_context = TranscriptContext {
+            session_id: 12,
+            source: TranscriptSource::File,
+            context_handle: None,
+            cli_formatting_mode: CliFormattingMode::Auto,
+            stages: TranscriptStageConfig::verbatim(),
+        };
+        let file =
+            transform_transcript("well shit".to_string(), &file_context, with_redactor()).unwrap();
+        assert_eq!(file.text, "well ****");
+    }
+
+    #[test]
+    fn replacement_rules_follow_built_in_stages_and_precede_redaction_on_live_text() {
+        let rule =
+            |id: &str, find: &str, replace: &str| crate::replacement_rules::ReplacementRule {
+                id: id.to_string(),
+                find: find.to_string(),
+                replace: replace.to_string(),
+                regex: false,
+                match_case: false,
+                enabled: true,
+            };
+        let rules = ReplacementRules::build(&[
+            rule("address", "test@example.com", "me@example.org"),
+            rule("code", "code word", "secret"),
+        ])
+        .map(Arc::new);
+        let redactor = Redactor::build(&crate::state::RedactionSettings {
+            enabled: true,
+            include_profanity: false,
+            words: vec!["secret".to_string()],
+            ..crate::state::RedactionSettings::default()
+        })
+        .unwrap()
+        .map(Arc::new);
+        let with_rules = || TranscriptTransformResources {
+            replacement_rules: rules.clone(),
+            redactor: redactor.clone(),
+            ..resources(false)
+        };
+
+        // The rule rewrites what the voice command inserted, and redaction
+        // still hides what a rule inserted.
+        let live = transform_transcript(
+            "send my email the code word".to_string(),
+            &live_context(TranscriptStageConfig {
+                voice_commands_enabled: true,
+                ..TranscriptStageConfig::verbatim()
+            }),
+            with_rules(),
+        )
+        .unwrap();
+        assert_eq!(live.text, "send me@example.org the ******");
+        // The preview source is what the rules saw, voice command applied.
+        assert_eq!(live.rules_input, "send test@example.com the code word");
+        let rules_report = live
+            .stages
+            .iter()
+            .find(|report| report.stage == REPLACEMENT_RULES_STAGE)
+            .unwrap();
+        assert!(rules_report.changed);
+
+        let file_context = TranscriptContext {
+            session_id: 13,
+            source: TranscriptSource::File,
+            context_handle: None,
+            cli_formatting_mode: CliFormattingMode::Auto,
+            stages: TranscriptStageConfig::verbatim(),
+        };
+        let file =
+            transform_transcript("the code word".to_string(), &file_context, with_rules()).unwrap();
+        assert_eq!(file.text, "the code word");
+    }
+
     #[test]
     fn cli_stage_runs_after_correction_and_keeps_original_in_memory() {
         let stages = TranscriptStageConfig {
@@ -1139,4 +1336,28 @@ mod tests {
         assert_eq!(output.stages[0].outcome, StageOutcome::Fallback);
         assert!(!output.stages[0].changed);
     }
+
+    #[test]
+    fn cleanup_follows_the_transcript_language() {
+        let transform = |language| {
+            transform_transcript(
+                "um es sind 3.5 Grad".to_string(),
+                &live_context(all_stages()),
+                TranscriptTransformResources {
+                    language,
+                    ..resources(false)
+                },
+            )
+            .unwrap()
+            .text
+        };
+        assert_eq!(
+            transform(&crate::language_rules::ENGLISH),
+            "Es sind 3.5 Grad"
+        );
+        assert_eq!(
+            transform(crate::language_rules::LanguageRules::for_code("de")),
+            "Um es sind 3,5 Grad"
+        );
+    }
 }
@@ -0,0 +1,81 @@
+//! FIFO worker for dictation transcriptions.
+//!
+//! `stop_native_recording` hands each stopped recording to this queue and
+//! returns at once, so the next recording can start while earlier ones are
+//! still transcribing. One long-lived thread runs the jobs strictly in the
+//! order they were submitted: takes are pasted (or buffered) in the order they
+//! were spoken, and only one job at a time waits on the model backend.
+
+use crate::MutexExt;
+use std::sync::{mpsc, Mutex};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+#[derive(Default)]
+pub struct TranscriptionQueue {
+    sender: Mutex<Option<mpsc::Sender<Job>>>,
+}
+
+impl TranscriptionQueue {
+    /// Run `job` after every job submitted before it. The worker thread starts
+    /// on first use and lives for the rest of the process.
+    pub fn submit(&self, job: impl FnOnce() + Send + 'static) -> Result<(), String> {
+        let mut sender = self.sender.lock_or_recover();
+        let mut job: Job = Box::new(job);
+        if let Some(tx) = sender.as_ref() {
+            match tx.send(job) {
+                Ok(()) => return Ok(()),
+                // The worker is gone; start a new one below.
+                Err(mpsc::SendError(returned)) => job = returned,
+            }
+        }
+        let (tx, rx) = mpsc::channel::<Job>();
+        std::thread::Builder::new()
+            .name("transcription-queue".to_string())
+            .spawn(move || run_jobs(rx))
+            .map_err(|e| format!("Failed to start the transcription worker: {}", e))?;
+        tx.send(job)
+            .map_err(|_| "The transcription worker stopped".to_string())?;
+        *sender = Some(tx);
+        Ok(())
+    }
+}
+
+fn run_jobs(jobs: mpsc::Receiver<Job>) {
+    for job in jobs {
+        // A panicking job must not take the jobs queued behind it down too.
+        if std::panic::catch_unwind(std::panic::AssertUnwindSafe(job)).is_err() {
+            tracing::error!(target: "pipeline", "transcription job panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn jobs_run_in_submission_order_and_survive_a_panic() {
+        let queue = TranscriptionQueue::default();
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let (done_tx, done_rx) = mpsc::channel();
+        for id in 1..=3u64 {
+            let order = Arc::clone(&order);
+            queue
+                .submit(move || {
+                    // The first job is the slowest; later ones still wait.
+                    std::thread::sleep(Duration::from_millis(if id == 1 { 30 } else { 0 }));
+                    order.lock().unwrap().push(id);
+                    if id == 2 {
+                        panic!("job 2 failed");
+                    }
+                })
+                .unwrap();
+        }
+        queue.submit(move || done_tx.send(()).unwrap()).unwrap();
+        done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(*order.lock().unwrap(), vec![1, 2, 3]);
+    }
+}
@@ -668,6 +668,40 @@ pub async fn apply_transform(
     }
 }
 
+/// Replace `snapshot`'s selection with `replacement` outside any transform
+/// session, for a dictation over a selection (`selectionDictation`). Same
+/// write path and clipboard rules as `apply_transform`, so it fails closed
+/// with `SelectionChanged` or `TargetGone` if the user moved on since the
+/// selection was read, leaving `replacement` in the clipboard.
+pub async fn replace_selection(
+    app_handle: &tauri::AppHandle,
+    app_state: &AppState,
+    snapshot: &crate::selection::TransformSnapshot,
+    replacement: String,
+) -> Result<AppliedVia, ApplyError> {
+    #[cfg(target_os = "macos")]
+    {
+        let apply_epoch = app_state.next_transform_apply_epoch();
+        run_apply(
+            app_handle,
+            snapshot.pid,
+            snapshot.range.map(|(start, _)| start),
+            range_len_for(&snapshot.text),
+            snapshot.text.clone(),
+            replacement,
+            apply_epoch,
+            0,
+        )
+        .await
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app_handle, app_state, snapshot, replacement);
+        Err(ApplyError::Unsupported)
+    }
+}
+
 /// Undo the active session's applied text, restoring `snapshot.text` via the
 /// same apply machinery (AX-set-or-paste-fallback, clipboard save/restore).
 /// Valid only while `session.applied` is true; sets it back to `false` on
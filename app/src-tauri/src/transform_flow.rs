@@ -56,7 +56,7 @@ use crate::performance_metrics::{
     UnavailableReasonV1,
 };
 use crate::selection::{SelectionError, TransformSnapshot};
-use crate::state::{AppState, DictationStatus, TransformStatus};
+use crate::state::{AppState, TransformStatus};
 use crate::transform_apply::{self, ApplyError};
 use crate::MutexExt;
 
@@ -1283,12 +1283,13 @@ async fn transcribe_instruction(
         );
         return Err(InstructionFailure::AudioEmpty);
     }
-    let (model_name, language, smart_punctuation) = {
+    let (model_name, language, smart_punctuation, whisper_decoding) = {
         let dictation = state.app_state.dictation.lock_or_recover();
         (
             dictation.model_name.clone(),
             dictation.language.clone(),
             dictation.smart_punctuation,
+            dictation.whisper_decoding,
         )
     };
 
@@ -1296,7 +1297,10 @@ async fn transcribe_instruction(
         Some(app_handle),
         &model_name,
         crate::model_runtime::PreparationReason::Pipeline,
-        |backend| backend.transcribe(samples, &language, None, smart_punctuation),
+        |backend| {
+            backend.set_whisper_decoding(&whisper_decoding);
+            backend.transcribe(samples, &language, None, smart_punctuation)
+        },
     );
     let (raw, load_report) = match raw {
         Ok(pair) => pair,
@@ -1507,7 +1511,7 @@ pub(crate) async fn start_transform_capture(
             Err("cancelled")
         } else if state.app_state.active_transform_pass_id() != Some(transform_pass_id) {
             Err("stale_pass")
-        } else if dictation.status != DictationStatus::Idle {
+        } else if !dictation.status.is_settled() {
             tracing::info!(target: "transform", transform_pass_id, error_code = "dictation_active", "start_transform_capture ignored");
             Err("dictation_active")
         } else if state.benchmark.is_running() {
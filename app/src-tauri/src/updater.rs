@@ -0,0 +1,392 @@
+//! In-app update orchestration on top of tauri-plugin-updater.
+//!
+//! `check_for_update` reads the feed for the chosen channel (stable or beta)
+//! and applies two custom `latest-v2.json` fields the plugin ignores:
+//! `min_version` marks the update as required, and `rollout` (a percentage)
+//! stages it across installs by a bucket persisted in the app data directory.
+//! `download_update` keeps the signed bundle in memory and reports
+//! `update-download-progress`. `install_update_on_quit` installs it when the app
+//! exits or, when asked to relaunch, as soon as no recording, transcription, or
+//! transform is running, so an update never interrupts dictation.
+
+use crate::{MutexExt, State};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+const STABLE_ENDPOINT: &str =
+    "https://github.com/georgenijo/murmur-app/releases/latest/download/latest-v2.json";
+const BETA_ENDPOINT: &str =
+    "https://github.com/georgenijo/murmur-app/releases/download/beta/latest-v2.json";
+
+const ROLLOUT_BUCKET_FILE: &str = "update-rollout-bucket";
+
+/// Minimum gap between `update-download-progress` events.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often a deferred install checks whether dictation has gone idle.
+const DEFERRED_INSTALL_POLL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    fn endpoint(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => STABLE_ENDPOINT,
+            UpdateChannel::Beta => BETA_ENDPOINT,
+        }
+    }
+}
+
+/// Returned by `check_for_update` when an update is offered.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub version: String,
+    pub notes: String,
+    pub channel: UpdateChannel,
+    /// The running version is below the feed's `min_version`.
+    pub is_forced: bool,
+}
+
+/// Payload of `update-download-progress`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadProgress {
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+struct PendingUpdate {
+    update: Update,
+    /// The downloaded bundle, once `download_update` finishes.
+    bytes: Option<Vec<u8>>,
+    install_on_quit: bool,
+}
+
+/// The update last offered by `check_for_update`.
+static PENDING: Mutex<Option<PendingUpdate>> = Mutex::new(None);
+static DOWNLOADING: AtomicBool = AtomicBool::new(false);
+/// Set while a relaunch waits for dictation to go idle.
+static INSTALL_DEFERRED: AtomicBool = AtomicBool::new(false);
+static ROLLOUT_BUCKET: OnceLock<u8> = OnceLock::new();
+
+/// `major.minor.patch`, ignoring a leading `v` and any pre-release or build
+/// suffix.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim();
+    let core = version.strip_prefix('v').unwrap_or(version);
+    let core = core.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let parsed = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(parsed)
+}
+
+/// Whether `current` is below `min`. An unparseable version counts as below,
+/// so a malformed feed can't bypass a required update.
+fn below_min_version(current: &str, min: &str) -> bool {
+    match (parse_version(current), parse_version(min)) {
+        (Some(current), Some(min)) => current < min,
+        _ => true,
+    }
+}
+
+/// The feed's `rollout` percentage, clamped to 0–100. Absent or malformed
+/// means everyone.
+fn rollout_percent(raw: &Value) -> f64 {
+    raw.get("rollout")
+        .and_then(Value::as_f64)
+        .filter(|percent| percent.is_finite())
+        .map_or(100.0, |percent| percent.clamp(0.0, 100.0))
+}
+
+fn in_rollout(bucket: u8, percent: f64) -> bool {
+    f64::from(bucket) < percent
+}
+
+/// This install's rollout bucket in 0..100, chosen once at random and kept in
+/// the app data directory so an install stays in or out of a stage.
+fn rollout_bucket(app: &tauri::AppHandle) -> u8 {
+    *ROLLOUT_BUCKET.get_or_init(|| {
+        let path = app
+            .path()
+            .app_data_dir()
+            .ok()
+            .map(|dir| dir.join(ROLLOUT_BUCKET_FILE));
+        if let Some(bucket) = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|raw| raw.trim().parse::<u8>().ok())
+            .filter(|bucket| *bucket < 100)
+        {
+            return bucket;
+        }
+        use std::hash::{BuildHasher, Hasher};
+        let bucket = (std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish()
+            % 100) as u8;
+        if let Some(path) = path {
+            if let Err(error) = std::fs::create_dir_all(path.parent().unwrap_or(&path))
+                .and_then(|()| std::fs::write(&path, bucket.to_string()))
+            {
+                tracing::warn!(target: "system", "failed to persist update rollout bucket: {}", error);
+            }
+        }
+        bucket
+    })
+}
+
+/// Whether an update would interrupt dictation right now.
+fn dictation_busy(app: &tauri::AppHandle) -> bool {
+    let state = app.state::<State>();
+    let status = state.app_state.dictation.lock_or_recover().status;
+    !status.is_settled() || state.app_state.transform_status().blocks_recording()
+}
+
+#[tauri::command]
+pub async fn check_for_update(
+    app: tauri::AppHandle,
+    channel: Option<UpdateChannel>,
+) -> Result<Option<UpdateInfo>, String> {
+    let channel = channel.unwrap_or_default();
+    if DOWNLOADING.load(Ordering::SeqCst) {
+        return Err("An update is already downloading.".to_string());
+    }
+    let endpoint = channel
+        .endpoint()
+        .parse()
+        .map_err(|e| format!("Invalid update endpoint: {}", e))?;
+    let update = app
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to set up the updater: {}", e))?
+        .check()
+        .await
+        .map_err(|e| format!("Update check failed: {}", e))?;
+
+    let Some(update) = update else {
+        tracing::info!(target: "system", ?channel, "no update available");
+        *PENDING.lock_or_recover() = None;
+        return Ok(None);
+    };
+
+    let is_forced = update
+        .raw_json
+        .get("min_version")
+        .and_then(Value::as_str)
+        .is_some_and(|min| below_min_version(&update.current_version, min));
+    let rollout = rollout_percent(&update.raw_json);
+    let bucket = rollout_bucket(&app);
+    if !is_forced && !in_rollout(bucket, rollout) {
+        tracing::info!(
+            target: "system",
+            ?channel,
+            version = %update.version,
+            rollout,
+            bucket,
+            "update held back by staged rollout"
+        );
+        *PENDING.lock_or_recover() = None;
+        return Ok(None);
+    }
+
+    tracing::info!(
+        target: "system",
+        ?channel,
+        version = %update.version,
+        is_forced,
+        "update available"
+    );
+    let info = UpdateInfo {
+        version: update.version.clone(),
+        notes: update.body.clone().unwrap_or_default(),
+        channel,
+        is_forced,
+    };
+    *PENDING.lock_or_recover() = Some(PendingUpdate {
+        update,
+        bytes: None,
+        install_on_quit: false,
+    });
+    Ok(Some(info))
+}
+
+#[tauri::command]
+pub async fn download_update(app: tauri::AppHandle) -> Result<(), String> {
+    let update = PENDING
+        .lock_or_recover()
+        .as_ref()
+        .map(|pending| pending.update.clone())
+        .ok_or_else(|| "No update to download. Check for updates first.".to_string())?;
+    if DOWNLOADING.swap(true, Ordering::SeqCst) {
+        return Err("An update is already downloading.".to_string());
+    }
+
+    tracing::info!(target: "system", version = %update.version, "update download started");
+    let mut downloaded = 0u64;
+    let mut last_emit: Option<Instant> = None;
+    let result = update
+        .download(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                let done = total.is_some_and(|total| downloaded >= total);
+                if done || last_emit.is_none_or(|at| at.elapsed() >= PROGRESS_INTERVAL) {
+                    last_emit = Some(Instant::now());
+                    let _ = app.emit(
+                        "update-download-progress",
+                        DownloadProgress { downloaded, total },
+                    );
+                }
+            },
+            || {},
+        )
+        .await;
+    DOWNLOADING.store(false, Ordering::SeqCst);
+
+    let bytes = result.map_err(|e| {
+        tracing::warn!(target: "system", "update download failed: {}", e);
+        format!("Update download failed: {}", e)
+    })?;
+    tracing::info!(target: "system", version = %update.version, bytes = bytes.len(), "update downloaded");
+    let mut pending = PENDING.lock_or_recover();
+    match pending.as_mut() {
+        // A newer check may have replaced the update mid-download.
+        Some(pending) if pending.update.version == update.version => {
+            pending.bytes = Some(bytes);
+            Ok(())
+        }
+        _ => Err("The update changed while downloading. Check for updates again.".to_string()),
+    }
+}
+
+/// Install the downloaded update when Murmur quits. With `relaunch`, install
+/// and restart now instead, waiting first for any recording, transcription,
+/// or transform to finish (`update-install-deferred` tells the UI it is
+/// waiting).
+#[tauri::command]
+pub async fn install_update_on_quit(
+    app: tauri::AppHandle,
+    relaunch: Option<bool>,
+) -> Result<(), String> {
+    {
+        let mut pending = PENDING.lock_or_recover();
+        let pending = pending
+            .as_mut()
+            .filter(|pending| pending.bytes.is_some())
+            .ok_or_else(|| "No downloaded update to install.".to_string())?;
+        pending.install_on_quit = true;
+        tracing::info!(target: "system", version = %pending.update.version, "update will install on quit");
+    }
+    if !relaunch.unwrap_or(false) || INSTALL_DEFERRED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let mut announced = false;
+        while dictation_busy(&app) {
+            if !announced {
+                announced = true;
+                tracing::info!(target: "system", "update install deferred until dictation is idle");
+                let _ = app.emit("update-install-deferred", ());
+            }
+            tokio::time::sleep(DEFERRED_INSTALL_POLL).await;
+        }
+        INSTALL_DEFERRED.store(false, Ordering::SeqCst);
+        if install_pending() {
+            app.restart();
+        }
+    });
+    Ok(())
+}
+
+/// Install the update marked by `install_update_on_quit`, if any. Returns
+/// whether one was installed.
+fn install_pending() -> bool {
+    let Some(pending) = PENDING.lock_or_recover().take() else {
+        return false;
+    };
+    let (true, Some(bytes)) = (pending.install_on_quit, pending.bytes) else {
+        return false;
+    };
+    match pending.update.install(bytes) {
+        Ok(()) => {
+            tracing::info!(target: "system", version = %pending.update.version, "update installed");
+            true
+        }
+        Err(error) => {
+            tracing::warn!(target: "system", version = %pending.update.version, "update install failed: {}", error);
+            false
+        }
+    }
+}
+
+/// App-exit hook: install an update the user chose to apply on quit.
+pub(crate) fn install_on_exit() {
+    install_pending();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_parse_with_prefix_and_suffix() {
+        assert_eq!(parse_version("0.21.2"), Some((0, 21, 2)));
+        assert_eq!(parse_version(" v1.2.3-beta.1 "), Some((1, 2, 3)));
+        assert_eq!(parse_version("1.2.3+build"), Some((1, 2, 3)));
+        assert_eq!(parse_version("1.2"), None);
+        assert_eq!(parse_version("1.2.3.4"), None);
+        assert_eq!(parse_version("bad"), None);
+    }
+
+    #[test]
+    fn min_version_forces_only_older_or_unparseable_installs() {
+        assert!(below_min_version("0.6.0", "0.7.0"));
+        assert!(below_min_version("0.7.9", "0.10.0"));
+        assert!(!below_min_version("0.7.0", "0.7.0"));
+        assert!(!below_min_version("0.8.0", "0.7.0"));
+        assert!(below_min_version("bad", "0.7.0"));
+        assert!(below_min_version("0.7.0", "bad"));
+    }
+
+    #[test]
+    fn rollout_stages_installs_by_bucket() {
+        assert_eq!(rollout_percent(&serde_json::json!({})), 100.0);
+        assert_eq!(
+            rollout_percent(&serde_json::json!({ "rollout": "10" })),
+            100.0
+        );
+        assert_eq!(
+            rollout_percent(&serde_json::json!({ "rollout": 250 })),
+            100.0
+        );
+        assert_eq!(rollout_percent(&serde_json::json!({ "rollout": -5 })), 0.0);
+        let ten = rollout_percent(&serde_json::json!({ "rollout": 10 }));
+        assert!(in_rollout(0, ten));
+        assert!(in_rollout(9, ten));
+        assert!(!in_rollout(10, ten));
+        assert!(!in_rollout(0, 0.0));
+        assert!(in_rollout(99, 100.0));
+    }
+
+    #[test]
+    fn channels_deserialize_from_lowercase_names() {
+        let beta: UpdateChannel = serde_json::from_str("\"beta\"").unwrap();
+        assert_eq!(beta, UpdateChannel::Beta);
+        assert_eq!(beta.endpoint(), BETA_ENDPOINT);
+        assert_eq!(UpdateChannel::default().endpoint(), STABLE_ENDPOINT);
+        assert!(serde_json::from_str::<UpdateChannel>("\"nightly\"").is_err());
+    }
+}
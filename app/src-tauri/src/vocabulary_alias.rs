@@ -491,6 +491,9 @@ mod tests {
             writing_style: None,
             ide_context_enabled: true,
             ide_project_roots: vec!["/project".to_string()],
+            injection_method_override: None,
+            trailing_override: None,
+            model_override: None,
         };
         let knowledge = vec![
             learned(
@@ -564,6 +567,9 @@ mod tests {
             writing_style: None,
             ide_context_enabled: false,
             ide_project_roots: vec!["/project".to_string()],
+            injection_method_override: None,
+            trailing_override: None,
+            model_override: None,
         };
         let mut later_enabled_profile = disabled_profile.clone();
         later_enabled_profile.ide_context_enabled = true;
@@ -722,6 +728,9 @@ mod tests {
             writing_style: None,
             ide_context_enabled: false,
             ide_project_roots: vec!["/project/one".to_string()],
+            injection_method_override: None,
+            trailing_override: None,
+            model_override: None,
         };
         let disabled = CorrectionMatcherSet::build(
             &[],
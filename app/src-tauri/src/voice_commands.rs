@@ -410,7 +410,7 @@ impl VoiceCommandRuntime for PreviewVoiceCommandRuntime {
 
 /// Replace every word-boundary, case-insensitive occurrence of `phrase` in `text`
 /// with `replacement`. Mirrors the built-in matcher's lowercase-parallel scan.
-fn replace_phrase(text: &str, phrase: &str, replacement: &str) -> String {
+pub(crate) fn replace_phrase(text: &str, phrase: &str, replacement: &str) -> String {
     let lower = text.to_lowercase();
     let chars: Vec<char> = text.chars().collect();
     let lower_chars: Vec<char> = lower.chars().collect();
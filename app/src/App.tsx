@@ -2,12 +2,16 @@ import { useState, useEffect, lazy, Suspense, useCallback, useRef } from 'react'
 import { invoke } from '@tauri-apps/api/core';
 import { flog } from './lib/log';
 import { SettingsPanel } from './components/settings';
+import { KeyboardListenerBanner } from './components/KeyboardListenerBanner';
+import { OrphanedRecordingBanner } from './components/OrphanedRecordingBanner';
 import { PermissionsBanner } from './components/PermissionsBanner';
 import { AboutModal } from './components/AboutModal';
 import { StatusHeader } from './components/StatusHeader';
 import { RecordingControls } from './components/RecordingControls';
+import { AppendBufferPanel } from './components/AppendBufferPanel';
 import { TranscriptionView } from './components/TranscriptionView';
 import { FileTranscriptionPanel } from './components/FileTranscriptionPanel';
+import { NotesPanel } from './components/NotesPanel';
 import { useInitialization } from './lib/hooks/useInitialization';
 import { useSettings } from './lib/hooks/useSettings';
 import { useHistoryManagement } from './lib/hooks/useHistoryManagement';
@@ -16,9 +20,25 @@ import { useHoldDownToggle } from './lib/hooks/useHoldDownToggle';
 import { useDoubleTapToggle } from './lib/hooks/useDoubleTapToggle';
 import { useTransformFlow } from './lib/hooks/useTransformFlow';
 import { useCombinedToggle } from './lib/hooks/useCombinedToggle';
+import { useWakeWord } from './lib/hooks/useWakeWord';
+import { useOverlayStartRequests } from './lib/hooks/useOverlayStartRequests';
+import { useAmbientMode } from './lib/hooks/useAmbientMode';
+import { useAppTriggers } from './lib/hooks/useAppTriggers';
+import { useDataFolder } from './lib/hooks/useDataFolder';
+import { useConfigPresets } from './lib/hooks/useConfigPresets';
+import { usePreRoll } from './lib/hooks/usePreRoll';
+import { useDictationProfileHotkeys } from './lib/hooks/useDictationProfileHotkeys';
+import { useAppendBuffer } from './lib/hooks/useAppendBuffer';
+import { useLastTranscription } from './lib/hooks/useLastTranscription';
+import { useClipboardAudio } from './lib/hooks/useClipboardAudio';
+import { useRecordingMarkers } from './lib/hooks/useRecordingMarkers';
 import { useShowAboutListener } from './lib/hooks/useShowAboutListener';
 import { useOverlaySettingsSync } from './lib/hooks/useOverlaySettingsSync';
 import { useOpenSettingsListener } from './lib/hooks/useOpenSettingsListener';
+import { useTraySettingsRequests } from './lib/hooks/useTraySettingsRequests';
+import { useDictationMacros } from './lib/hooks/useDictationMacros';
+import { useLatencyBudget } from './lib/hooks/useLatencyBudget';
+import { usePowerProfiles } from './lib/hooks/usePowerProfiles';
 import { useEscapeCancel } from './lib/hooks/useEscapeCancel';
 import { useAutoUpdater } from './lib/hooks/useAutoUpdater';
 import { UpdateModal } from './components/UpdateModal';
@@ -27,6 +47,7 @@ import { StatsBar } from './components/StatsBar';
 const ResourceMonitor = lazy(() => import('./components/ResourceMonitor').then(m => ({ default: m.ResourceMonitor })));
 const UsageDashboard = lazy(() => import('./components/UsageDashboard').then(m => ({ default: m.UsageDashboard })));
 import { resetStats } from './lib/stats';
+import { historyStorageFor } from './lib/history';
 import { ModelDownloader } from './components/ModelDownloader';
 import { OnboardingFlow } from './components/onboarding/OnboardingFlow';
 import { isOnboardingComplete, markOnboardingComplete, resetOnboarding } from './lib/onboarding';
@@ -110,6 +131,11 @@ function App() {
 
   // Keep settings in sync when the overlay's quick controls change them.
   useOverlaySettingsSync(applyExternalSettings);
+  // Apply auto-paste and model quick toggles chosen from the menu-bar tray.
+  useTraySettingsRequests(updateSettings);
+  usePowerProfiles(settings.powerProfiles, updateSettings);
+  // List presets in the tray and apply the ones it, a command, or a macro asks for.
+  const { applyPreset } = useConfigPresets({ initialized, settings, update: updateSettings });
 
   // Track accessibility permission — when it transitions false→true the
   // double-tap listener restarts automatically (rdev silently does nothing
@@ -125,11 +151,20 @@ function App() {
     window.addEventListener('focus', check);
     return () => window.removeEventListener('focus', check);
   }, []);
-  const { historyEntries, addEntry, updateEntry, clearHistory } = useHistoryManagement();
+  const { historyEntries, error: historyError, addEntry, updateEntry, refineEntry, attachEntryAlternatives, chooseEntryAlternative, importEntries, clearHistory } = useHistoryManagement(historyStorageFor(settings));
+  const { error: dataFolderError } = useDataFolder({
+    initialized,
+    folder: settings.dataFolder,
+    settings,
+    historyEntries,
+    shareHistory: historyStorageFor(settings) === 'local',
+    onSettings: updateSettings,
+    onHistory: importEntries,
+  });
   const {
     status, recordingDuration, error: recordingError,
-    handleStart, handleStop, toggleRecording, statsVersion,
-  } = useRecordingState({ addEntry, microphone: settings.microphone });
+    handleStart, handleProfileStart, handleStop, toggleRecording, statsVersion,
+  } = useRecordingState({ addEntry, refineEntry, attachAlternatives: attachEntryAlternatives, microphone: settings.microphone });
   const [statsResetVersion, setStatsResetVersion] = useState(0);
   const combinedStatsVersion = statsVersion + statsResetVersion;
   const handleResetStats = () => { resetStats(); setStatsResetVersion(v => v + 1); };
@@ -137,9 +172,64 @@ function App() {
   // can be granted mid-wizard, and a hold/double-tap must not start a recording
   // behind the OnboardingFlow screen.
   const hotkeysArmed = onboardingState === 'done';
-  useHoldDownToggle({ enabled: hotkeysArmed && settings.recordingMode === 'hold_down', initialized, accessibilityGranted, holdDownKey: settings.doubleTapKey, onStart: handleStart, onStop: handleStop });
-  useDoubleTapToggle({ enabled: hotkeysArmed && settings.recordingMode === 'double_tap', initialized, accessibilityGranted, doubleTapKey: settings.doubleTapKey, status, onToggle: toggleRecording });
-  useCombinedToggle({ enabled: hotkeysArmed && settings.recordingMode === 'both', initialized, accessibilityGranted, triggerKey: settings.doubleTapKey, status, onStart: handleStart, onStop: handleStop, onToggle: toggleRecording });
+  useHoldDownToggle({ enabled: hotkeysArmed && settings.recordingMode === 'hold_down', initialized, accessibilityGranted, holdDownKey: settings.doubleTapKey, timing: settings.hotkeyTiming, onStart: handleStart, onStop: handleStop });
+  useDoubleTapToggle({ enabled: hotkeysArmed && settings.recordingMode === 'double_tap', initialized, accessibilityGranted, doubleTapKey: settings.doubleTapKey, timing: settings.hotkeyTiming, status, onToggle: toggleRecording });
+  useCombinedToggle({ enabled: hotkeysArmed && settings.recordingMode === 'both', initialized, accessibilityGranted, triggerKey: settings.doubleTapKey, timing: settings.hotkeyTiming, status, onStart: handleStart, onStop: handleStop, onToggle: toggleRecording });
+  useWakeWord({ enabled: hotkeysArmed && settings.wakeWord, initialized, microphone: settings.microphone, onStart: handleStart, onStop: handleStop });
+  useOverlayStartRequests({ enabled: hotkeysArmed, initialized, onStart: handleStart });
+  useAmbientMode({ enabled: hotkeysArmed && settings.ambientMode, initialized, microphone: settings.microphone });
+  useAppTriggers({ enabled: hotkeysArmed, initialized, triggers: settings.appTriggers, onStartAmbient: () => updateSettings({ ambientMode: true }) });
+  usePreRoll({ seconds: hotkeysArmed ? settings.preRollSeconds : 0, initialized, microphone: settings.microphone });
+  useDictationProfileHotkeys({
+    enabled: hotkeysArmed,
+    initialized,
+    accessibilityGranted,
+    profiles: settings.dictationProfiles,
+    dictationKey: settings.doubleTapKey,
+    transformKey: settings.transformHoldKey,
+    onStart: handleProfileStart,
+    onStop: handleStop,
+  });
+  const appendBuffer = useAppendBuffer({
+    enabled: hotkeysArmed && settings.appendMode,
+    initialized,
+    accessibilityGranted,
+    commitKey: settings.appendCommitKey,
+    dictationKey: settings.doubleTapKey,
+    transformKey: settings.transformHoldKey,
+  });
+  useLastTranscription({
+    historyEntries,
+    enabled: hotkeysArmed,
+    initialized,
+    accessibilityGranted,
+    pasteKey: settings.pasteLastKey,
+    dictationKey: settings.doubleTapKey,
+    transformKey: settings.transformHoldKey,
+    appendCommitKey: settings.appendMode ? settings.appendCommitKey : null,
+  });
+  useRecordingMarkers({
+    enabled: hotkeysArmed,
+    initialized,
+    accessibilityGranted,
+    markerKey: settings.markerKey,
+    spokenMarkers: settings.spokenMarkers,
+    dictationKey: settings.doubleTapKey,
+    transformKey: settings.transformHoldKey,
+    appendCommitKey: settings.appendMode ? settings.appendCommitKey : null,
+    pasteLastKey: settings.pasteLastKey,
+  });
+  useClipboardAudio({
+    enabled: hotkeysArmed,
+    initialized,
+    accessibilityGranted,
+    clipboardAudioKey: settings.clipboardAudioKey,
+    dictationKey: settings.doubleTapKey,
+    transformKey: settings.transformHoldKey,
+    appendCommitKey: settings.appendMode ? settings.appendCommitKey : null,
+    pasteLastKey: settings.pasteLastKey,
+    markerKey: settings.markerKey,
+  });
   useEscapeCancel({ status, enabled: hotkeysArmed && initialized && accessibilityGranted === true });
   // Independent AX-selection transform hotkey (issue #312). Enabled only when
   // the user has configured a transform key; drives capture -> instruction ->
@@ -152,7 +242,7 @@ function App() {
     microphone: settings.microphone,
   });
   const { showAbout, setShowAbout } = useShowAboutListener();
-  const updater = useAutoUpdater();
+  const updater = useAutoUpdater(settings.updateChannel);
 
   // DEV ONLY: cycle through mock update modal states for visual testing
   const devUpdateIndex = useRef(-1);
@@ -185,13 +275,20 @@ function App() {
   const startDownload = updater.startDownload;
 
   const [isSettingsOpen, setIsSettingsOpen] = useState(false);
-  const [mainTab, setMainTab] = useState<'record' | 'file'>('record');
+  const [mainTab, setMainTab] = useState<'record' | 'file' | 'notes'>('record');
 
   // Overlay gear button asks the main window to open the Settings panel.
   const openSettings = useCallback(() => setIsSettingsOpen(true), []);
   useOpenSettingsListener(openSettings);
+  // Spoken macros ("open history", "disable auto paste") land here.
+  const openHistory = useCallback(() => {
+    setIsSettingsOpen(false);
+    setMainTab('record');
+  }, []);
+  useDictationMacros({ update: updateSettings, openSettings, openHistory, applyPreset });
+  useLatencyBudget(settings.model, updateSettings);
 
-  const error = initError || recordingError;
+  const error = initError || recordingError || historyError;
 
   if (onboardingState === 'unknown' || modelReady === null) {
     return <div className="h-screen bg-background" />;
@@ -232,12 +329,16 @@ function App() {
 
       <PermissionsBanner />
 
+      <KeyboardListenerBanner />
+
+      <OrphanedRecordingBanner addEntry={addEntry} />
+
       <StatsBar statsVersion={combinedStatsVersion} />
 
       <div className="flex-1 flex overflow-hidden">
         <main className={`flex-1 flex-col overflow-hidden p-4 gap-4 ${isSettingsOpen ? 'hidden' : 'flex'}`}>
           <div className="shrink-0 flex gap-1 p-1 self-start bg-surface-container rounded-xl">
-            {(['record', 'file'] as const).map((tab) => (
+            {(['record', 'file', 'notes'] as const).map((tab) => (
               <button
                 key={tab}
                 onClick={() => setMainTab(tab)}
@@ -247,7 +348,7 @@ function App() {
                     : 'text-on-surface-variant hover:text-on-surface'
                 }`}
               >
-                {tab === 'record' ? 'Record' : 'Transcribe File'}
+                {tab === 'record' ? 'Record' : tab === 'file' ? 'Transcribe File' : 'Notes'}
               </button>
             ))}
           </div>
@@ -258,6 +359,8 @@ function App() {
                 historyEntries={historyEntries}
                 onClearHistory={clearHistory}
                 onUpdateHistoryEntry={updateEntry}
+                flagLowConfidence={settings.flagLowConfidence}
+                onChooseAlternative={chooseEntryAlternative}
               />
 
               {error && (
@@ -266,14 +369,24 @@ function App() {
                 </div>
               )}
 
+              <AppendBufferPanel
+                buffer={appendBuffer.buffer}
+                error={appendBuffer.error}
+                busy={status === 'recording' || status === 'processing'}
+                onCommit={appendBuffer.commit}
+                onDiscard={appendBuffer.discard}
+              />
+
               <RecordingControls status={status} initialized={initialized} onStart={handleStart} onStop={handleStop} triggerKey={settings.doubleTapKey} />
 
-              <Suspense fallback={null}><UsageDashboard statsVersion={combinedStatsVersion} /></Suspense>
+              <Suspense fallback={null}><UsageDashboard statsVersion={combinedStatsVersion} typingWpm={settings.typingWpm} /></Suspense>
 
               {import.meta.env.DEV && <Suspense fallback={null}><ResourceMonitor /></Suspense>}
             </>
-          ) : (
+          ) : mainTab === 'file' ? (
             <FileTranscriptionPanel addEntry={addEntry} />
+          ) : (
+            <NotesPanel microphone={settings.microphone} meetingChunkSeconds={settings.meetingChunkSeconds} />
           )}
         </main>
 
@@ -285,12 +398,20 @@ function App() {
           onUpdateSettings={updateSettings}
           status={status}
           onResetStats={handleResetStats}
+          onPurgeTranscripts={async () => {
+            await invoke('purge_all_transcripts');
+            clearHistory();
+          }}
+          onClearHistory={clearHistory}
           onViewLogs={() => invoke('open_log_viewer').catch((e: unknown) => flog.warn('main', 'Failed to open log viewer', { error: String(e) }))}
           onRerunSetup={() => {
             setIsSettingsOpen(false);
             resetOnboarding();
             setOnboardingState('needed');
           }}
+          historyEntries={historyEntries}
+          onImportHistory={importEntries}
+          dataFolderError={dataFolderError}
           accessibilityGranted={accessibilityGranted}
           onCheckForUpdate={checkForUpdate}
           updateStatus={updateStatus}
@@ -0,0 +1,42 @@
+import type { AppendBuffer } from '../lib/dictation';
+
+interface AppendBufferPanelProps {
+  buffer: AppendBuffer;
+  error: string | null;
+  busy: boolean;
+  onCommit: () => void;
+  onDiscard: () => void;
+}
+
+/** Buffered append-mode takes, with the commit and discard actions. */
+export function AppendBufferPanel({ buffer, error, busy, onCommit, onDiscard }: AppendBufferPanelProps) {
+  if (buffer.takes === 0) return null;
+
+  return (
+    <div className="shrink-0 flex flex-col gap-2 rounded-xl bg-surface-container-low px-4 py-3">
+      <div className="flex items-center justify-between">
+        <span className="text-xs font-semibold uppercase tracking-wide text-on-surface-variant">
+          {buffer.takes === 1 ? '1 take' : `${buffer.takes} takes`} waiting
+        </span>
+        <div className="flex gap-2">
+          <button
+            onClick={onDiscard}
+            disabled={busy}
+            className="rounded-md px-3 py-1 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container-high hover:text-on-surface disabled:cursor-not-allowed disabled:opacity-50"
+          >
+            Discard
+          </button>
+          <button
+            onClick={onCommit}
+            disabled={busy}
+            className="rounded-md bg-primary px-3 py-1 text-xs font-semibold text-on-primary transition-[filter] hover:brightness-105 disabled:cursor-not-allowed disabled:opacity-50"
+          >
+            Commit
+          </button>
+        </div>
+      </div>
+      <p className="max-h-24 overflow-y-auto whitespace-pre-wrap text-sm text-on-surface">{buffer.text}</p>
+      {error && <p className="text-xs text-red-600 dark:text-red-400">{error}</p>}
+    </div>
+  );
+}
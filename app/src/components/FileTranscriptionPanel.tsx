@@ -3,10 +3,12 @@ import { open } from '@tauri-apps/plugin-dialog';
 import { useFileTranscription } from '../lib/hooks/useFileTranscription';
 import type { QueueItem } from '../lib/hooks/useFileTranscription';
 import { flog } from '../lib/log';
+import type { TeachingContext } from '../lib/correctAndTeach';
+import type { HistoryConfidence, HistoryDecode, HistoryProcessing } from '../lib/history';
 
 interface FileTranscriptionPanelProps {
   /** Persist completed transcriptions to shared history. */
-  addEntry: (text: string, duration: number, source?: 'recording' | 'file', sourceName?: string) => void;
+  addEntry: (text: string, duration: number, source?: 'recording' | 'file', sourceName?: string, teachingContext?: TeachingContext, confidence?: HistoryConfidence, decode?: HistoryDecode, processing?: HistoryProcessing) => void;
 }
 
 /** Per-file status pill in the queue list. */
@@ -0,0 +1,41 @@
+import { useKeyboardListenerHealth, type KeyboardListenerDegraded } from '../lib/hooks/useKeyboardListenerHealth';
+
+const MESSAGES: Record<KeyboardListenerDegraded['reason'], string> = {
+  permission_revoked: 'Accessibility permission was turned off, so Murmur can no longer see your hotkey.',
+  thread_exited: 'The keyboard listener stopped and could not restart on its own.',
+  tap_silent: 'Murmur has not seen a key press since Accessibility was turned back on. If your hotkey does nothing, restart Murmur.',
+};
+
+const ACTION_LABELS: Record<KeyboardListenerDegraded['action'], string> = {
+  grant_accessibility: 'Grant Access',
+  retry_listener: 'Try Again',
+  restart_app: 'Restart Murmur',
+};
+
+/** Explains why the global hotkey stopped working and offers the fix Rust suggested. */
+export function KeyboardListenerBanner() {
+  const { degraded, busy, error, recover } = useKeyboardListenerHealth();
+
+  if (!degraded) {
+    return null;
+  }
+
+  return (
+    <div className="bg-amber-50 dark:bg-amber-900/30 px-4 py-3">
+      <h3 className="text-sm font-medium text-amber-800 dark:text-amber-200">
+        Hotkey Not Responding
+      </h3>
+      <p className="mt-1 text-sm text-amber-700 dark:text-amber-300">{MESSAGES[degraded.reason]}</p>
+      {error && <p className="mt-1 text-xs text-red-600 dark:text-red-400">{error}</p>}
+      <div className="mt-2 flex gap-3">
+        <button
+          onClick={() => void recover()}
+          disabled={busy}
+          className="text-xs text-amber-600 dark:text-amber-400 underline hover:no-underline disabled:opacity-50"
+        >
+          {ACTION_LABELS[degraded.action]}
+        </button>
+      </div>
+    </div>
+  );
+}
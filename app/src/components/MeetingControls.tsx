@@ -0,0 +1,89 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { getMeetingStatus, startMeeting, stopMeeting, type MeetingStatus } from '../lib/dictation';
+
+const buttonClass = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-1.5 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50';
+
+function elapsed(startedAtMs: number, now: number): string {
+  const minutes = Math.max(0, Math.floor((now - startedAtMs) / 60_000));
+  return minutes < 60 ? `${minutes} min` : `${Math.floor(minutes / 60)} h ${minutes % 60} min`;
+}
+
+/** Start and stop meeting mode, which transcribes into today's note as the meeting goes. */
+export function MeetingControls({ microphone, chunkSeconds }: { microphone: string; chunkSeconds: number }) {
+  const [status, setStatus] = useState<MeetingStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [now, setNow] = useState(() => Date.now());
+
+  useEffect(() => {
+    getMeetingStatus().then(setStatus).catch(() => {});
+    const unlisteners: (() => void)[] = [];
+    let disposed = false;
+    const keep = (fn: () => void) => {
+      if (disposed) fn();
+      else unlisteners.push(fn);
+    };
+    // Kill Microphone can end a meeting from Settings; chunks keep arriving after a stop.
+    listen<MeetingStatus>('meeting-status-changed', (event) => setStatus(event.payload)).then(keep);
+    listen<{ meetingStartedAtMs: number; index: number }>('meeting-chunk', (event) => {
+      setStatus((current) => current && current.startedAtMs === event.payload.meetingStartedAtMs
+        ? { ...current, chunksSaved: Math.max(current.chunksSaved, event.payload.index) }
+        : current);
+    }).then(keep);
+    listen<{ meetingStartedAtMs: number }>('meeting-chunk-dropped', (event) => {
+      setStatus((current) => current && current.startedAtMs === event.payload.meetingStartedAtMs
+        ? { ...current, chunksDropped: current.chunksDropped + 1 }
+        : current);
+    }).then(keep);
+    return () => {
+      disposed = true;
+      unlisteners.forEach((fn) => fn());
+    };
+  }, []);
+
+  const active = status?.active ?? false;
+  useEffect(() => {
+    if (!active) return;
+    const id = setInterval(() => setNow(Date.now()), 30_000);
+    return () => clearInterval(id);
+  }, [active]);
+
+  const toggle = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      setStatus(active ? await stopMeeting() : await startMeeting(microphone, chunkSeconds));
+      setNow(Date.now());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="shrink-0 space-y-1">
+      <div className="flex items-center gap-2">
+        <button type="button" className={buttonClass} disabled={busy} onClick={() => void toggle()}>
+          {active ? 'Stop Meeting' : 'Start Meeting'}
+        </button>
+        {active && status?.startedAtMs != null && (
+          <p className="text-xs text-on-surface-variant">
+            <span className="mr-1 inline-block h-2 w-2 rounded-full bg-orange-500" aria-hidden="true" />
+            Transcribing for {elapsed(status.startedAtMs, now)}, {status.chunksSaved} {status.chunksSaved === 1 ? 'part' : 'parts'} saved
+          </p>
+        )}
+        {!active && status && status.chunksSaved > 0 && (
+          <p className="text-xs text-on-surface-variant">Last meeting: {status.chunksSaved} {status.chunksSaved === 1 ? 'part' : 'parts'} saved to notes.</p>
+        )}
+      </div>
+      {status && status.chunksDropped > 0 && (
+        <p className="text-xs text-on-surface-variant">
+          {status.chunksDropped} {status.chunksDropped === 1 ? 'part was' : 'parts were'} skipped while dictation kept the model busy.
+        </p>
+      )}
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
@@ -7,6 +7,7 @@ import {
   modelDownloadPercent,
   type ModelDownloadProgress,
 } from '../lib/modelDownload';
+import { QUICK_START_MODEL, queueModelDownloads, waitForModelInstall } from '../lib/downloadQueue';
 
 const MODEL_DESCRIPTIONS: Record<string, string> = {
   'parakeet-tdt-0.6b-v3-coreml': 'Fastest on Apple Silicon — multilingual, Apple Neural Engine (recommended)',
@@ -70,7 +71,8 @@ export function ModelDownloadPanel({ initialModel, onComplete, onDownloadingChan
     };
   }, []);
 
-  const handleDownload = async () => {
+  // `install` resolves once `model` is on disk; progress events drive the bar meanwhile.
+  const runInstall = async (model: ModelOption, install: () => Promise<void>) => {
     onDownloadingChange?.(true);
     setDownloadState({
       phase: 'downloading',
@@ -92,11 +94,11 @@ export function ModelDownloadPanel({ initialModel, onComplete, onDownloadingChan
       );
       downloadUnlistenRef.current = unlisten;
 
-      await invoke('download_model', { modelName: selected });
+      await install();
       unlisten();
       downloadUnlistenRef.current = null;
       onDownloadingChange?.(false);
-      onComplete(selected);
+      onComplete(model);
     } catch (err) {
       downloadUnlistenRef.current?.();
       downloadUnlistenRef.current = null;
@@ -108,6 +110,20 @@ export function ModelDownloadPanel({ initialModel, onComplete, onDownloadingChan
     }
   };
 
+  const handleDownload = () => runInstall(selected, async () => {
+    await invoke('download_model', { modelName: selected });
+  });
+
+  // Finish onboarding on the small model while the chosen one keeps
+  // downloading through the queue, which also survives a restart.
+  const handleQuickStart = () => runInstall(QUICK_START_MODEL, async () => {
+    const installed = installedModels?.[QUICK_START_MODEL]
+      ? Promise.resolve()
+      : waitForModelInstall(QUICK_START_MODEL);
+    await queueModelDownloads([QUICK_START_MODEL, selected]);
+    await installed;
+  });
+
   const progress = downloadState.phase === 'downloading' ? downloadState.progress : null;
   const progressPercent = progress ? modelDownloadPercent(progress) : null;
 
@@ -204,6 +220,15 @@ export function ModelDownloadPanel({ initialModel, onComplete, onDownloadingChan
             ? 'Retry Download'
             : 'Download'}
         </button>
+        {!isDownloading && !selectedInstalled && selected !== QUICK_START_MODEL && (
+          <button
+            type="button"
+            onClick={handleQuickStart}
+            className="mt-2 w-full text-xs text-on-surface-variant underline hover:text-primary"
+          >
+            Start with {MODELS.find((model) => model.name === QUICK_START_MODEL)?.label ?? 'a small model'} now and finish this one in the background
+          </button>
+        )}
     </div>
   );
 }
@@ -0,0 +1,168 @@
+import { useCallback, useEffect, useRef, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { save } from '@tauri-apps/plugin-dialog';
+import {
+  addNoteEntry,
+  copyNote,
+  deleteNote,
+  deleteNoteEntry,
+  exportNote,
+  getNote,
+  listNotes,
+  updateNoteEntry,
+  type Note,
+  type NoteSummary,
+} from '../lib/dictation';
+import { MeetingControls } from './MeetingControls';
+
+const buttonClass = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-1.5 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50';
+
+/** Murmur notes: one markdown note per day, filled by the notes target, a meeting, or by typing. */
+export function NotesPanel({ microphone, meetingChunkSeconds }: { microphone: string; meetingChunkSeconds: number }) {
+  const [days, setDays] = useState<NoteSummary[]>([]);
+  const [note, setNote] = useState<Note | null>(null);
+  const [draft, setDraft] = useState('');
+  const [editing, setEditing] = useState<{ index: number; text: string } | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const openDate = useRef<string | null>(null);
+  useEffect(() => { openDate.current = note?.date ?? null; }, [note]);
+
+  const refresh = useCallback(async (date?: string) => {
+    try {
+      const [list, current] = await Promise.all([listNotes(), getNote(date)]);
+      setDays(list);
+      setNote(current);
+    } catch (err) {
+      setError(String(err));
+    }
+  }, []);
+
+  useEffect(() => { void refresh(); }, [refresh]);
+
+  // A dictation saved to a note refreshes the list, and the open note if it is that day.
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    listen<string>('note-updated', (event) => {
+      listNotes().then(setDays).catch(() => {});
+      if (openDate.current === event.payload) getNote(event.payload).then(setNote).catch(() => {});
+    }).then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const run = async (action: () => Promise<void>) => {
+    setNotice(null);
+    setError(null);
+    try {
+      await action();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const applied = async (updated: Note) => {
+    setNote(updated);
+    setDays(await listNotes());
+  };
+
+  const exportCurrent = (date: string) => run(async () => {
+    const path = await save({ defaultPath: `murmur-note-${date}.md`, filters: [{ name: 'Markdown', extensions: ['md'] }] });
+    if (typeof path !== 'string') return;
+    await exportNote(date, path);
+    setNotice('Note exported.');
+  });
+
+  if (!note) return null;
+
+  return (
+    <div className="flex-1 flex gap-4 overflow-hidden">
+      <ul className="w-40 shrink-0 space-y-1 overflow-auto">
+        {days.length === 0 && <li className="text-xs text-on-surface-variant">No notes yet.</li>}
+        {days.map((day) => (
+          <li key={day.date}>
+            <button
+              type="button"
+              onClick={() => { setEditing(null); void refresh(day.date); }}
+              aria-current={note.date === day.date}
+              className={`w-full rounded-lg border px-3 py-2 text-left text-xs transition-colors ${note.date === day.date ? 'border-primary bg-primary/10 text-on-surface' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}
+            >
+              <span className="block font-medium">{day.date}</span>
+              <span className="block truncate text-on-surface-variant">{day.entries} {day.entries === 1 ? 'entry' : 'entries'}</span>
+            </button>
+          </li>
+        ))}
+      </ul>
+
+      <section className="flex-1 flex flex-col gap-3 overflow-hidden">
+        <div className="shrink-0 flex items-center gap-2">
+          <h2 className="text-sm font-semibold text-on-surface">{note.date}</h2>
+          <div className="ml-auto flex gap-2">
+            <button type="button" className={buttonClass} disabled={note.entries.length === 0} onClick={() => void run(async () => { await copyNote(note.date); setNotice('Note copied to the clipboard.'); })}>Copy</button>
+            <button type="button" className={buttonClass} disabled={note.entries.length === 0} onClick={() => void exportCurrent(note.date)}>Export…</button>
+            <button type="button" className={buttonClass} disabled={note.entries.length === 0} onClick={() => void run(async () => { await deleteNote(note.date); await refresh(); })}>Delete Day</button>
+          </div>
+        </div>
+
+        <MeetingControls microphone={microphone} chunkSeconds={meetingChunkSeconds} />
+
+        <ol className="flex-1 space-y-2 overflow-auto">
+          {note.entries.length === 0 && <li className="text-xs text-on-surface-variant">Nothing saved this day.</li>}
+          {note.entries.map((entry, index) => (
+            <li key={`${entry.time}-${index}`} className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-sm">
+              <div className="flex items-center gap-2 text-xs text-on-surface-variant">
+                <span>{entry.time}</span>
+                <button type="button" className="ml-auto hover:text-primary" onClick={() => setEditing({ index, text: entry.text })}>Edit</button>
+                <button type="button" className="hover:text-error" onClick={() => void run(async () => applied(await deleteNoteEntry(note.date, index)))}>Delete</button>
+              </div>
+              {editing?.index === index ? (
+                <div className="mt-1 space-y-1">
+                  <textarea
+                    value={editing.text}
+                    onChange={(event) => setEditing({ index, text: event.target.value })}
+                    rows={3}
+                    aria-label={`Edit entry from ${entry.time}`}
+                    className="w-full rounded-lg border border-outline-variant/30 bg-surface px-2 py-1 text-sm text-on-surface focus:border-primary focus:outline-none"
+                  />
+                  <div className="flex gap-2">
+                    <button type="button" className={buttonClass} onClick={() => void run(async () => { await applied(await updateNoteEntry(note.date, index, editing.text)); setEditing(null); })}>Save</button>
+                    <button type="button" className={buttonClass} onClick={() => setEditing(null)}>Cancel</button>
+                  </div>
+                </div>
+              ) : (
+                <p className="mt-1 whitespace-pre-wrap text-on-surface">{entry.text}</p>
+              )}
+            </li>
+          ))}
+        </ol>
+
+        <form
+          className="shrink-0 flex gap-2"
+          onSubmit={(event) => {
+            event.preventDefault();
+            if (!draft.trim()) return;
+            void run(async () => { await applied(await addNoteEntry(draft)); setDraft(''); });
+          }}
+        >
+          <input
+            value={draft}
+            onChange={(event) => setDraft(event.target.value)}
+            placeholder="Add to today's note"
+            aria-label="Add to today's note"
+            className="min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-sm text-on-surface focus:border-primary focus:outline-none"
+          />
+          <button type="submit" className={buttonClass} disabled={!draft.trim()}>Add</button>
+        </form>
+
+        {notice && <p className="shrink-0 text-xs text-emerald-600 dark:text-emerald-400">{notice}</p>}
+        {error && <p role="alert" className="shrink-0 text-xs text-error">{error}</p>}
+      </section>
+    </div>
+  );
+}
@@ -0,0 +1,52 @@
+import { useOrphanedRecording } from '../lib/hooks/useOrphanedRecording';
+
+interface OrphanedRecordingBannerProps {
+  /** Persist the recovered transcription to shared history. */
+  addEntry: (text: string, duration: number, source?: 'recording' | 'file', sourceName?: string) => void;
+}
+
+function formatDuration(secs: number): string {
+  const total = Math.max(1, Math.round(secs));
+  const minutes = Math.floor(total / 60);
+  const seconds = total % 60;
+  return minutes > 0 ? `${minutes}m ${seconds}s` : `${seconds}s`;
+}
+
+/** Offers to transcribe or discard a recording a crash left unfinished. */
+export function OrphanedRecordingBanner({ addEntry }: OrphanedRecordingBannerProps) {
+  const { orphan, busy, error, transcribe, discard } = useOrphanedRecording({ addEntry });
+
+  if (!orphan) {
+    return null;
+  }
+
+  const when = orphan.recordedAtMs > 0 ? ` from ${new Date(orphan.recordedAtMs).toLocaleString()}` : '';
+
+  return (
+    <div className="bg-amber-50 dark:bg-amber-900/30 px-4 py-3">
+      <h3 className="text-sm font-medium text-amber-800 dark:text-amber-200">
+        Unfinished Recording
+      </h3>
+      <p className="mt-1 text-sm text-amber-700 dark:text-amber-300">
+        Murmur quit while recording. {formatDuration(orphan.durationSecs)} of audio{when} was saved.
+      </p>
+      {error && <p className="mt-1 text-xs text-red-600 dark:text-red-400">{error}</p>}
+      <div className="mt-2 flex gap-3">
+        <button
+          onClick={() => void transcribe()}
+          disabled={busy}
+          className="text-xs text-amber-600 dark:text-amber-400 underline hover:no-underline disabled:opacity-50"
+        >
+          {busy ? 'Transcribing…' : 'Transcribe'}
+        </button>
+        <button
+          onClick={() => void discard()}
+          disabled={busy}
+          className="text-xs text-amber-600 dark:text-amber-400 underline hover:no-underline disabled:opacity-50"
+        >
+          Discard
+        </button>
+      </div>
+    </div>
+  );
+}
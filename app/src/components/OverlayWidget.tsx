@@ -1,11 +1,27 @@
 import { useEffect, useRef, useState } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { flog } from '../lib/log';
+import {
+  commitAppendBuffer,
+  confirmInjection,
+  discardAppendBuffer,
+  discardInjection,
+  getAmbientPrompt,
+  getAppendBuffer,
+  getPendingInjection,
+  overlayAction,
+  type AmbientPrompt,
+  type AppendBuffer,
+  type InjectionResolved,
+  type PendingInjection,
+} from '../lib/dictation';
 import { isDictationStatus } from '../lib/types';
 import type { DictationStatus } from '../lib/types';
 import { useOverlayGeometry } from '../lib/hooks/useOverlayGeometry';
 import { useOverlayExpansion } from '../lib/hooks/useOverlayExpansion';
+import { useOverlayPillDrag } from '../lib/hooks/useOverlayPillDrag';
 import { useOverlayRuntime } from '../lib/hooks/useOverlayRuntime';
+import { useOverlayState } from '../lib/hooks/useOverlayState';
 import { useOverlaySettingsMirror } from '../lib/hooks/useOverlaySettingsMirror';
 import { useRecordingControls } from '../lib/hooks/useRecordingControls';
 import { useWaveform } from '../lib/hooks/useWaveform';
@@ -31,6 +47,9 @@ export function OverlayWidget() {
   // by the broadcast `transform-state-changed` event; the overlay is a
   // separate webview so it listens directly.
   const [transforming, setTransforming] = useState(false);
+  const [appendBuffer, setAppendBuffer] = useState<AppendBuffer>({ takes: 0, text: '' });
+  const [pendingInjection, setPendingInjection] = useState<PendingInjection | null>(null);
+  const [ambientPrompt, setAmbientPrompt] = useState<AmbientPrompt | null>(null);
   const hotkeyMissFeedbackRef = useRef(false);
   const statusRef = useRef<DictationStatus>('idle');
 
@@ -48,6 +67,11 @@ export function OverlayWidget() {
 
   const waveform = useWaveform(status);
 
+  // Recording start, partial text, and error toasts that Rust drives over `overlay-state`.
+  const overlayState = useOverlayState();
+
+  const pillDrag = useOverlayPillDrag(geometry?.floating ?? false);
+
   const recordingControls = useRecordingControls({
     status, statusRef, disabledRef: runtime.disabledRef, expandedRef,
   });
@@ -60,6 +84,10 @@ export function OverlayWidget() {
     transforming,
     runtime.showSecureField,
     runtime.showTransformBusy,
+    pendingInjection !== null,
+    overlayState.processing,
+    overlayState.error !== null,
+    ambientPrompt !== null,
   );
 
   // Track the transform flow's thinking phase for the overlay indicator.
@@ -76,6 +104,98 @@ export function OverlayWidget() {
     return () => { cancelled = true; unlisten?.(); };
   }, []);
 
+  // Mirror the append-mode buffer for the take count and commit/discard actions.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<AppendBuffer>('append-buffer-changed', (event) => {
+      if (event.payload) setAppendBuffer(event.payload);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    getAppendBuffer()
+      .then((buffer) => { if (!cancelled && buffer) setAppendBuffer(buffer); })
+      .catch(() => {});
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  const handleCommitBuffer = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    commitAppendBuffer().catch((err) => flog.warn('overlay', 'append commit failed', { error: String(err) }));
+  };
+  const handleDiscardBuffer = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    discardAppendBuffer().catch((err) => flog.warn('overlay', 'append discard failed', { error: String(err) }));
+  };
+
+  // Mirror the dictation preview mode is holding for the Accept/Discard actions.
+  useEffect(() => {
+    let cancelled = false;
+    let unlistenPending: (() => void) | null = null;
+    let unlistenResolved: (() => void) | null = null;
+    listen<PendingInjection>('injection-pending', (event) => {
+      if (event.payload) setPendingInjection(event.payload);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlistenPending = fn; }
+    });
+    // Only clear the preview the event names; a newer one may already be up.
+    listen<InjectionResolved>('injection-resolved', (event) => {
+      const id = event.payload?.id;
+      setPendingInjection((pending) => (pending && pending.id === id ? null : pending));
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlistenResolved = fn; }
+    });
+    getPendingInjection()
+      .then((pending) => { if (!cancelled) setPendingInjection(pending); })
+      .catch(() => {});
+    return () => { cancelled = true; unlistenPending?.(); unlistenResolved?.(); };
+  }, []);
+
+  const handleAcceptPreview = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    if (!pendingInjection) return;
+    confirmInjection(pendingInjection.id).catch((err) => flog.warn('overlay', 'preview accept failed', { error: String(err) }));
+  };
+  const handleDiscardPreview = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    if (!pendingInjection) return;
+    discardInjection(pendingInjection.id).catch((err) => flog.warn('overlay', 'preview discard failed', { error: String(err) }));
+  };
+
+  // An app trigger's offer of ambient transcription; Rust withdraws it when
+  // another app comes forward.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<AmbientPrompt | null>('ambient-prompt', (event) => {
+      setAmbientPrompt(event.payload ?? null);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    getAmbientPrompt()
+      .then((prompt) => { if (!cancelled) setAmbientPrompt(prompt); })
+      .catch(() => {});
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // Rust carries these out so the click never takes focus from the target app.
+  const handleCancelRecording = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    overlayAction('cancel').catch((err) => flog.warn('overlay', 'cancel failed', { error: String(err) }));
+  };
+  const handleCopyLast = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    overlayAction('copy_last').catch((err) => flog.warn('overlay', 'copy last failed', { error: String(err) }));
+  };
+  const handleStartAmbient = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    overlayAction('start_ambient').catch((err) => flog.warn('overlay', 'start ambient failed', { error: String(err) }));
+  };
+  const handleDismissAmbientPrompt = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    overlayAction('dismiss_ambient_prompt').catch((err) => flog.warn('overlay', 'dismiss ambient offer failed', { error: String(err) }));
+  };
+
   // Log mount/unmount.
   useEffect(() => {
     flog.info('overlay', 'mounted');
@@ -113,9 +233,8 @@ export function OverlayWidget() {
     refreshSettingsMirror();
   }, [phase, refreshSettingsMirror]);
 
-  // Restore saved position (Rust handles default positioning)
-  // TODO: re-enable after notch positioning is stable.
-  // Both save (onMoved) and restore are disabled to avoid saving programmatic repositions.
+  // Notch islands stay pinned to the notch. A floating pill's dragged position
+  // is saved by useOverlayPillDrag and restored by Rust when it places the window.
 
   // All hooks are above this line. The overlay window is transparent, so returning
   // null before geometry arrives (~1 IPC round-trip after mount) paints nothing
@@ -127,7 +246,7 @@ export function OverlayWidget() {
     <div
       className="w-full h-full flex"
       style={{ background: 'transparent' }}
-      onMouseDown={recordingControls.handleMouseDown}
+      onMouseDown={(e) => { recordingControls.handleMouseDown(e); pillDrag.handleMouseDown(); }}
       onDoubleClick={recordingControls.handleDoubleClick}
       onClick={recordingControls.handleClick}
       onMouseEnter={onHoverStart}
@@ -144,14 +263,18 @@ export function OverlayWidget() {
         onMouseLeave={onHoverEnd}
         style={{
           position: 'relative',
-          borderRadius: '0 0 12px 12px',
+          // A notch island hangs from the screen edge; a floating pill is
+          // rounded all round.
+          borderRadius: geometry.floating ? Math.min(16, topH / 2) : '0 0 12px 12px',
           // One constant island width in every state — the island IS the window.
           // Only height animates (see OVERLAY_ISLAND_TRANSITION).
           width: geometry.pillActiveW,
           height: topH + (expanded ? geometry.dropdownH : 0),
           marginLeft: geometry.pillMarginActive,
           background: 'rgba(20, 20, 20, 0.92)',
-          boxShadow: visual.showTapMissedLabel ? 'inset 0 -2px 0 rgba(245,158,11,0.9), 0 3px 16px rgba(245,158,11,0.22)' : 'none',
+          boxShadow: visual.showError
+            ? 'inset 0 -2px 0 rgba(239,68,68,0.9), 0 3px 16px rgba(239,68,68,0.22)'
+            : visual.showTapMissedLabel ? 'inset 0 -2px 0 rgba(245,158,11,0.9), 0 3px 16px rgba(245,158,11,0.22)' : 'none',
           backdropFilter: 'blur(40px)',
           WebkitBackdropFilter: 'blur(40px)',
           transition: OVERLAY_ISLAND_TRANSITION,
@@ -161,20 +284,36 @@ export function OverlayWidget() {
           geometry={geometry}
           visual={visual}
           status={status}
+          bufferedTakes={appendBuffer.takes}
           barRefs={waveform.barRefs}
         />
-        <NotchCalibrationBand geometry={geometry} />
+        {!geometry.floating && <NotchCalibrationBand geometry={geometry} />}
         <OverlayDropdown
           geometry={geometry}
           expanded={expanded}
           status={status}
+          recordingStartedAt={overlayState.recordingStartedAt}
+          partialText={overlayState.partial}
+          errorText={overlayState.error}
           showTapMissed={visual.showTapMissedLabel}
           disabled={runtime.disabled}
           autoPaste={settingsMirror.autoPaste}
           fileOutputEnabled={settingsMirror.fileOutputEnabled}
+          bufferedTakes={appendBuffer.takes}
+          bufferedText={appendBuffer.text}
+          previewText={pendingInjection?.text ?? null}
+          ambientPromptApp={ambientPrompt?.name ?? null}
           onToggleDisabled={settingsMirror.handleToggleDisabled}
           onToggleAutoPaste={settingsMirror.handleToggleAutoPaste}
           onOpenSettings={settingsMirror.handleOpenSettings}
+          onCommitBuffer={handleCommitBuffer}
+          onDiscardBuffer={handleDiscardBuffer}
+          onAcceptPreview={handleAcceptPreview}
+          onDiscardPreview={handleDiscardPreview}
+          onCancelRecording={handleCancelRecording}
+          onCopyLast={handleCopyLast}
+          onStartAmbient={handleStartAmbient}
+          onDismissAmbientPrompt={handleDismissAmbientPrompt}
         />
       </div>
     </div>
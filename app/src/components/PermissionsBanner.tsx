@@ -1,5 +1,6 @@
 import { useCallback, useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import {
   checkMicrophonePermissionStatus,
   resetAccessibilityPermission,
@@ -66,9 +67,18 @@ export function PermissionsBanner() {
   useEffect(() => {
     checkPermissions();
 
-    // Re-check when window gains focus (user might have granted permission)
+    // Re-check when window gains focus or Rust's watcher sees a permission
+    // flip (user might have granted permission)
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen('permission-changed', () => { checkPermissions(); })
+      .then((fn) => { if (cancelled) { fn(); } else { unlisten = fn; } });
     window.addEventListener('focus', checkPermissions);
-    return () => window.removeEventListener('focus', checkPermissions);
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      window.removeEventListener('focus', checkPermissions);
+    };
   }, [checkPermissions]);
 
   const handleOpenAccessibility = async () => {
@@ -32,15 +32,20 @@ export function RecordingControls({ status, initialized, onStart, onStop, trigge
       ) : (
         <button
           onClick={onStart}
-          disabled={!initialized || status === 'processing'}
+          disabled={!initialized}
           className="flex items-center gap-2 rounded-xl bg-[linear-gradient(135deg,var(--murmur-primary),var(--murmur-primary-dim))] px-7 py-3 font-semibold text-on-primary shadow-lg shadow-primary/20 transition-[filter,transform] hover:brightness-105 active:scale-[0.98] disabled:cursor-not-allowed disabled:opacity-50"
         >
           <svg className="w-5 h-5" fill="currentColor" viewBox="0 0 20 20">
             <circle cx="10" cy="10" r="6" />
           </svg>
-          {status === 'processing' ? 'Processing...' : 'Start Recording'}
+          Start Recording
         </button>
       )}
+      {status === 'processing' && (
+        <p className="text-xs text-on-surface-variant">
+          Transcribing — you can start the next take now.
+        </p>
+      )}
       {status === 'idle' && (
         <p className="text-xs text-on-surface-variant">
           Press <kbd className="font-[inherit] font-semibold text-on-surface">{HOTKEY_LABELS[triggerKey]}</kbd> to begin
@@ -142,4 +142,39 @@ describe('Sonic Canvas component details', () => {
     await act(async () => actions[0].click());
     expect((container.querySelector('[aria-label="Corrected transcript"]') as HTMLTextAreaElement).value).toBe('newest transcript');
   });
+
+  it('marks low-confidence entries only while flagging is on', async () => {
+    const entries = [
+      { id: 'sure', text: 'clear speech', timestamp: 1, duration: 1, confidence: { score: 0.9, low: false } },
+      { id: 'unsure', text: 'mumbled speech', timestamp: 2, duration: 1, confidence: { score: 0.3, low: true } },
+    ];
+    await act(async () => {
+      root.render(<HistoryPanel entries={entries} onClearHistory={vi.fn()} onUpdateEntry={vi.fn()} flagLowConfidence />);
+    });
+    const flags = Array.from(container.querySelectorAll('span')).filter((span) => span.textContent?.trim() === 'Unsure');
+    expect(flags).toHaveLength(1);
+    expect(flags[0].getAttribute('title')).toContain('30%');
+
+    await act(async () => {
+      root.render(<HistoryPanel entries={entries} onClearHistory={vi.fn()} onUpdateEntry={vi.fn()} />);
+    });
+    expect(Array.from(container.querySelectorAll('span')).some((span) => span.textContent?.trim() === 'Unsure')).toBe(false);
+  });
+
+  it('offers alternative readings of underlined words', async () => {
+    const onChooseAlternative = vi.fn();
+    const entries = [
+      { id: 'one', text: 'meet me at the key', timestamp: 1, duration: 1, alternatives: [{ start: 4, end: 5, options: ['quay'] }] },
+    ];
+    await act(async () => {
+      root.render(<HistoryPanel entries={entries} onClearHistory={vi.fn()} onUpdateEntry={vi.fn()} onChooseAlternative={onChooseAlternative} />);
+    });
+    const word = Array.from(container.querySelectorAll('button')).find((button) => button.textContent === 'key');
+    expect(container.querySelector('p')?.textContent).toBe('meet me at the key');
+    await act(async () => word!.click());
+    const option = container.querySelector('[role="menuitem"]') as HTMLButtonElement;
+    expect(option.textContent).toBe('quay');
+    await act(async () => option.click());
+    expect(onChooseAlternative).toHaveBeenCalledWith('one', 0, 'quay');
+  });
 });
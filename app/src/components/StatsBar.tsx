@@ -1,5 +1,5 @@
 import { useState, useEffect } from 'react';
-import { loadStats, getWPM, getApproxTokens } from '../lib/stats';
+import { loadStats, getWPM } from '../lib/stats';
 import type { DictationStats } from '../lib/stats';
 
 interface StatsBarProps {
@@ -10,14 +10,13 @@ export function StatsBar({ statsVersion }: StatsBarProps) {
   const [stats, setStats] = useState<DictationStats>(() => loadStats());
   useEffect(() => { setStats(loadStats()); }, [statsVersion]);
   const wpm = getWPM(stats);
-  const tokens = getApproxTokens(stats);
 
   return (
     <div className="grid shrink-0 grid-cols-4 gap-2 bg-background px-4 py-3">
       <StatCard label="Total Words" value={stats.totalWords.toLocaleString()} icon="words" />
       <StatCard label="Avg WPM" value={wpm > 0 ? wpm.toLocaleString() : '—'} icon="speed" />
       <StatCard label="Recordings" value={stats.totalRecordings.toLocaleString()} icon="recordings" />
-      <StatCard label="Approx Tokens" value={tokens > 0 ? tokens.toLocaleString() : '—'} icon="tokens" />
+      <StatCard label="Tokens" value={stats.totalTokens > 0 ? stats.totalTokens.toLocaleString() : '—'} icon="tokens" />
     </div>
   );
 }
@@ -11,6 +11,7 @@ interface StatusHeaderProps {
 function getStatusDotColor(status: DictationStatus, initialized: boolean): string {
   if (status === 'recording') return 'bg-error';
   if (status === 'processing') return 'bg-amber-500';
+  if (status === 'buffering') return 'bg-primary';
   if (initialized) return 'bg-emerald-500';
   return 'bg-outline-variant';
 }
@@ -18,6 +19,7 @@ function getStatusDotColor(status: DictationStatus, initialized: boolean): strin
 function getStatusText(status: DictationStatus, initialized: boolean, duration: number): string {
   if (status === 'recording') return `Recording ${duration}s`;
   if (status === 'processing') return 'Processing...';
+  if (status === 'buffering') return 'Takes waiting';
   if (initialized) return 'Ready';
   return 'Initializing...';
 }
@@ -5,15 +5,19 @@ interface TranscriptionViewProps {
   historyEntries: HistoryEntry[];
   onClearHistory: () => void;
   onUpdateHistoryEntry: (id: string, text: string) => void;
+  flagLowConfidence?: boolean;
+  onChooseAlternative?: (id: string, spanIndex: number, option: string) => void;
 }
 
-export function TranscriptionView({ historyEntries, onClearHistory, onUpdateHistoryEntry }: TranscriptionViewProps) {
+export function TranscriptionView({ historyEntries, onClearHistory, onUpdateHistoryEntry, flagLowConfidence, onChooseAlternative }: TranscriptionViewProps) {
   return (
     <div className="flex flex-1 flex-col overflow-hidden rounded-2xl bg-surface-container-low p-3">
       <HistoryPanel
         entries={historyEntries}
         onClearHistory={onClearHistory}
         onUpdateEntry={onUpdateHistoryEntry}
+        flagLowConfidence={flagLowConfidence}
+        onChooseAlternative={onChooseAlternative}
       />
     </div>
   );
@@ -104,7 +104,9 @@ export function UpdateModal({ status, onDownload, onSkip, onDismiss }: UpdateMod
           {/* Ready / installing state */}
           {isReady && (
             <p className="text-sm text-on-surface text-center mb-4">
-              Installing and relaunching...
+              {status.deferred
+                ? 'Murmur will install and relaunch when your current dictation finishes.'
+                : 'Installing and relaunching...'}
             </p>
           )}
 
@@ -4,7 +4,11 @@ import {
   getRecentDays,
   getHeatmapWeeks,
   getCurrentStreak,
+  getDictationStats,
+  formatDuration,
+  formatHour,
   type DaySummary,
+  type DictationStatsSummary,
 } from '../lib/stats';
 
 const STORAGE_KEY = 'usage-dashboard-collapsed';
@@ -55,11 +59,14 @@ function shortDay(d: Date): string {
 interface UsageDashboardProps {
   // Bumped by App when a recording finishes (or stats reset) — forces a re-read.
   statsVersion: number;
+  // Typing speed baseline for "time saved" (settings.typingWpm).
+  typingWpm: number;
 }
 
-export function UsageDashboard({ statsVersion }: UsageDashboardProps) {
+export function UsageDashboard({ statsVersion, typingWpm }: UsageDashboardProps) {
   const [isCollapsed, setIsCollapsed] = useState(loadCollapsed);
   const [version, setVersion] = useState(0);
+  const [week, setWeek] = useState<DictationStatsSummary | null>(null);
 
   // Re-read stats whenever localStorage changes from another window/tab, and
   // when the panel is expanded so it reflects recordings made while collapsed.
@@ -78,6 +85,17 @@ export function UsageDashboard({ statsVersion }: UsageDashboardProps) {
     [isCollapsed, version, statsVersion],
   );
 
+  // The weekly summary comes from the backend's usage log, refreshed on the
+  // same triggers as the local charts.
+  useEffect(() => {
+    if (isCollapsed) return;
+    let cancelled = false;
+    getDictationStats('week', typingWpm)
+      .then((summary) => { if (!cancelled) setWeek(summary); })
+      .catch((e) => console.error('Failed to load dictation stats:', e));
+    return () => { cancelled = true; };
+  }, [isCollapsed, version, statsVersion, typingWpm]);
+
   const streak = stats ? getCurrentStreak(stats) : 0;
   const weeks = stats ? getHeatmapWeeks(stats, HEATMAP_WEEKS) : [];
   const recent = stats ? getRecentDays(stats, RECENT_DAYS) : [];
@@ -126,6 +144,8 @@ export function UsageDashboard({ statsVersion }: UsageDashboardProps) {
 
       {!isCollapsed && stats && (
         <div className="px-3 pb-3 flex flex-col gap-4">
+          {week && week.dictations > 0 && <WeekSummary summary={week} />}
+
           {/* Heatmap — last ~8 weeks of words/day */}
           <Section title={`Activity · last ${HEATMAP_WEEKS} weeks`}>
             <svg
@@ -243,6 +263,35 @@ function wpmPoints(days: DaySummary[], maxWpm: number): string {
     .join(' ');
 }
 
+function WeekSummary({ summary }: { summary: DictationStatsSummary }) {
+  return (
+    <Section title="This week">
+      <div className="grid grid-cols-2 gap-x-3 gap-y-1 text-xs text-stone-600 dark:text-stone-300">
+        <span>{summary.words.toLocaleString()} words</span>
+        <span title={`Against typing at ${summary.typingWpm} WPM`}>{formatDuration(summary.timeSavedSecs)} saved</span>
+        {summary.busiestHours.length > 0 && (
+          <span className="col-span-2">Busiest: {summary.busiestHours.map(formatHour).join(', ')}</span>
+        )}
+      </div>
+      {summary.models.length > 0 && (
+        <ul className="mt-1.5 flex flex-col gap-0.5 text-[10px] text-stone-500 dark:text-stone-400">
+          {summary.models.map((usage) => (
+            <li key={usage.model} className="flex justify-between tabular-nums">
+              <span>{usage.model}</span>
+              <span>
+                {usage.tokensPerSec !== null && (
+                  <span title={`${usage.tokens.toLocaleString()} tokens decoded`} className="mr-2">{Math.round(usage.tokensPerSec)} tok/s</span>
+                )}
+                {Math.round(usage.share * 100)}%
+              </span>
+            </li>
+          ))}
+        </ul>
+      )}
+    </Section>
+  );
+}
+
 function Section({ title, children }: { title: string; children: React.ReactNode }) {
   return (
     <div>
@@ -0,0 +1,66 @@
+import { useState } from 'react';
+import type { AlternativeSpan } from '../../lib/dictation';
+
+type Piece = { text: string; span?: number };
+
+/** Split `text` into plain runs and the runs each span covers, keeping the
+ *  whitespace between words as it is. */
+function pieces(text: string, spans: AlternativeSpan[]): Piece[] {
+  const words = [...text.matchAll(/\S+/g)];
+  const result: Piece[] = [];
+  let offset = 0;
+  spans.forEach((span, index) => {
+    const first = words[span.start];
+    const last = words[span.end - 1];
+    if (!first || !last || first.index === undefined || last.index === undefined || first.index < offset) return;
+    const end = last.index + last[0].length;
+    if (first.index > offset) result.push({ text: text.slice(offset, first.index) });
+    result.push({ text: text.slice(first.index, end), span: index });
+    offset = end;
+  });
+  if (offset < text.length) result.push({ text: text.slice(offset) });
+  return result;
+}
+
+/** A history entry's text with the words Whisper could have read differently
+ *  underlined; tapping one lists the other readings to swap in. */
+export function AlternativeText({ text, spans, onChoose }: {
+  text: string;
+  spans: AlternativeSpan[];
+  onChoose: (spanIndex: number, option: string) => void;
+}) {
+  const [open, setOpen] = useState<number | null>(null);
+
+  return (
+    <>
+      {pieces(text, spans).map((piece, i) => piece.span === undefined ? piece.text : (
+        <span key={i} className="relative">
+          <button
+            type="button"
+            aria-haspopup="menu"
+            aria-expanded={open === piece.span}
+            onClick={() => setOpen(open === piece.span ? null : piece.span!)}
+            className="rounded-sm underline decoration-primary/50 decoration-dotted underline-offset-4 hover:bg-primary/10 focus:outline-none focus-visible:ring-2 focus-visible:ring-primary"
+          >
+            {piece.text}
+          </button>
+          {open === piece.span && (
+            <span role="menu" aria-label={`Alternatives for ${piece.text}`} className="absolute left-0 top-full z-10 mt-1 flex min-w-[8rem] flex-col rounded-lg bg-surface-container-high p-1 shadow-lg">
+              {spans[piece.span].options.map((option) => (
+                <button
+                  key={option}
+                  type="button"
+                  role="menuitem"
+                  onClick={() => { setOpen(null); onChoose(piece.span!, option); }}
+                  className="rounded-md px-2 py-1 text-left text-xs text-on-surface hover:bg-primary/10 focus:outline-none focus-visible:ring-2 focus-visible:ring-primary"
+                >
+                  {option || <span className="italic text-on-surface-variant">(remove)</span>}
+                </button>
+              ))}
+            </span>
+          )}
+        </span>
+      ))}
+    </>
+  );
+}
@@ -1,14 +1,20 @@
 import { useState } from 'react';
-import { HistoryEntry, formatTimestamp, clearHistory } from '../../lib/history';
+import { HistoryEntry, formatTimestamp, clearHistory, decodeSummary, processingSummary } from '../../lib/history';
+import { countWords } from '../../lib/stats';
 import { CorrectAndTeachDialog } from './CorrectAndTeachDialog';
+import { AlternativeText } from './AlternativeText';
 
 interface HistoryPanelProps {
   entries: HistoryEntry[];
   onClearHistory: () => void;
   onUpdateEntry: (id: string, text: string) => void;
+  /** Mark entries Whisper had low confidence in. */
+  flagLowConfidence?: boolean;
+  /** Swap an alternative reading into an entry; without it, alternatives aren't shown. */
+  onChooseAlternative?: (id: string, spanIndex: number, option: string) => void;
 }
 
-export function HistoryPanel({ entries, onClearHistory, onUpdateEntry }: HistoryPanelProps) {
+export function HistoryPanel({ entries, onClearHistory, onUpdateEntry, flagLowConfidence = false, onChooseAlternative }: HistoryPanelProps) {
   const [copiedId, setCopiedId] = useState<string | null>(null);
   const [teachingEntry, setTeachingEntry] = useState<HistoryEntry | null>(null);
 
@@ -54,7 +60,7 @@ export function HistoryPanel({ entries, onClearHistory, onUpdateEntry }: History
     <div className="flex flex-1 flex-col overflow-hidden">
       <div className="flex-1 space-y-3 overflow-y-auto px-0.5 py-0.5">
         {sortedEntries.map((entry, index) => {
-          const wordCount = entry.text.trim() ? entry.text.trim().split(/\s+/).length : 0;
+          const wordCount = countWords(entry.text);
           return (
             <article key={entry.id} className={`group w-full rounded-xl p-3.5 text-left shadow-sm transition-[box-shadow,background-color] hover:shadow-md ${copiedId === entry.id ? 'bg-emerald-50 dark:bg-emerald-950/40' : 'bg-surface-container-lowest hover:bg-surface-container-low'}`}>
               <div className="mb-1 flex items-center justify-between gap-2">
@@ -71,9 +77,19 @@ export function HistoryPanel({ entries, onClearHistory, onUpdateEntry }: History
                       Mic
                     </span>
                   )}
+                  {flagLowConfidence && entry.confidence?.low && (
+                    <span title={`Whisper was ${Math.round(entry.confidence.score * 100)}% sure of this text. Worth a second look.`} className="shrink-0 rounded-full bg-amber-500/15 px-2 py-0.5 text-[10px] font-medium text-amber-700 dark:text-amber-400">
+                      Unsure
+                    </span>
+                  )}
+                  {entry.processedOn && (
+                    <span title={processingSummary(entry) ?? undefined} className="shrink-0 rounded-full bg-sky-500/15 px-2 py-0.5 text-[10px] font-medium text-sky-700 dark:text-sky-400">
+                      {entry.processedOn === 'cloud' ? 'Cloud' : 'Plugin'}
+                    </span>
+                  )}
                 </div>
                 <div className="flex shrink-0 items-center gap-2">
-                  <span className="rounded-full bg-surface-container px-2 py-0.5 text-[10px] font-medium text-on-surface-variant">{wordCount} {wordCount === 1 ? 'word' : 'words'}</span>
+                  <span title={entry.decode ? decodeSummary(entry.decode) : undefined} className="rounded-full bg-surface-container px-2 py-0.5 text-[10px] font-medium text-on-surface-variant">{wordCount} {wordCount === 1 ? 'word' : 'words'}</span>
                   <span className="text-xs text-on-surface-variant">{formatDuration(entry.duration)}</span>
                   {copiedId === entry.id ? (
                     <span className="text-xs font-medium text-emerald-600 dark:text-emerald-400">Copied!</span>
@@ -82,7 +98,11 @@ export function HistoryPanel({ entries, onClearHistory, onUpdateEntry }: History
                   )}
                 </div>
               </div>
-              <p className="max-h-32 overflow-y-auto text-sm leading-relaxed text-on-surface">{entry.text}</p>
+              <p className="max-h-32 overflow-y-auto text-sm leading-relaxed text-on-surface">
+                {onChooseAlternative && entry.alternatives?.length ? (
+                  <AlternativeText text={entry.text} spans={entry.alternatives} onChoose={(spanIndex, option) => onChooseAlternative(entry.id, spanIndex, option)} />
+                ) : entry.text}
+              </p>
               {index === 0 && (
                 <div className="mt-3 border-t border-outline-variant/20 pt-2">
                   <button type="button" onClick={() => setTeachingEntry(entry)} className="rounded-md px-2 py-1 text-xs font-semibold text-primary hover:bg-primary/10 focus:outline-none focus-visible:ring-2 focus-visible:ring-primary">Correct and Teach</button>
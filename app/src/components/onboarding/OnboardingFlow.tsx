@@ -1,13 +1,15 @@
 import { useCallback, useEffect, useRef, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
 import {
-  checkAccessibilityPermission,
-  checkMicrophonePermissionStatus,
+  getPermissionReport,
   openMicrophoneSettings,
   requestAccessibilityPermission,
-  requestMicrophoneAccess,
+  requestMicrophonePermission,
   resetAccessibilityPermission,
   resetMicrophonePermission,
   type MicPermissionStatus,
+  type PermissionChange,
+  type PermissionReport,
 } from '../../lib/dictation';
 import { getModelRuntimeCatalog } from '../../lib/modelRuntime';
 import { DOWNLOAD_MODEL_KEYS, ModelDownloadPanel } from '../ModelDownloader';
@@ -39,11 +41,11 @@ interface Props {
  * download, replacing the old flow where the mic TCC prompt only fired on the
  * first recording attempt and permissions were a dismissible banner.
  *
- * Permission state is polled every second (plus on window focus) for the whole
- * wizard lifetime, so a grant made in System Settings flips the step live when
- * the user comes back. Both permission steps handle the "wishy-washy" TCC
+ * Permission state follows Rust's `permission-changed` events (plus a fresh
+ * report on mount and window focus) for the whole wizard lifetime, so a grant
+ * made in System Settings flips the step live. Both permission steps handle the "wishy-washy" TCC
  * states explicitly:
- * - mic `notDetermined`/`unknown` → in-app native prompt (request_microphone_access)
+ * - mic `notDetermined`/`unknown` → in-app native prompt (request_microphone_permission)
  * - mic `denied` → open System Settings, or reset the stale TCC entry, which
  *   returns the status to `notDetermined` so the in-app prompt works again
  * - accessibility listed-but-stale → reset entry + re-grant manually
@@ -68,37 +70,38 @@ export function OnboardingFlow({ initialModel, recordingMode, triggerKey, onComp
   // second concurrent download of the same file.
   const [modelDownloading, setModelDownloading] = useState(false);
 
-  // Monotonic sequence so an interval probe overlapping a focus probe can't
-  // apply an older TCC result over a newer one.
+  // Monotonic sequence so a slow focus probe can't apply an older TCC result
+  // over a newer report from an event or a later probe.
   const pollSeq = useRef(0);
+  const applyReport = useCallback((report: PermissionReport) => {
+    ++pollSeq.current;
+    setMicStatus(report.microphone);
+    setAxGranted(report.accessibility === 'granted');
+  }, []);
   const refreshPermissions = useCallback(async () => {
     const seq = ++pollSeq.current;
-    let mic: MicPermissionStatus = 'unknown';
-    let ax: boolean | null = null;
     try {
-      mic = await checkMicrophonePermissionStatus();
+      const report = await getPermissionReport();
+      if (seq !== pollSeq.current) return; // superseded by a newer report
+      applyReport(report);
     } catch {
-      mic = 'unknown';
+      // keep previous values; a probe glitch must not flip the UI
     }
-    try {
-      ax = await checkAccessibilityPermission();
-    } catch {
-      // keep previous value; a probe glitch must not flip the UI
-    }
-    if (seq !== pollSeq.current) return; // superseded by a newer probe
-    setMicStatus(mic);
-    if (ax !== null) setAxGranted(ax);
-  }, []);
+  }, [applyReport]);
 
   useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
     refreshPermissions();
-    const id = setInterval(refreshPermissions, 1000);
+    listen<PermissionChange>('permission-changed', (event) => applyReport(event.payload.report))
+      .then((fn) => { if (cancelled) { fn(); } else { unlisten = fn; } });
     window.addEventListener('focus', refreshPermissions);
     return () => {
-      clearInterval(id);
+      cancelled = true;
+      unlisten?.();
       window.removeEventListener('focus', refreshPermissions);
     };
-  }, [refreshPermissions]);
+  }, [applyReport, refreshPermissions]);
 
   // Probe every offered model when entering the model step (re-run of the
   // wizard, or a fresh webview data store next to an installed app), so any
@@ -149,9 +152,9 @@ export function OnboardingFlow({ initialModel, recordingMode, triggerKey, onComp
     setMicError(null);
     setMicRequested(true);
     try {
-      // Fires the native TCC dialog when the status is notDetermined; the
-      // 1s poll picks up the answer.
-      await requestMicrophoneAccess();
+      // Shows the native TCC dialog when the status is notDetermined and
+      // resolves with the user's answer.
+      setMicStatus(await requestMicrophonePermission());
     } catch (error) {
       setMicError(typeof error === 'string' ? error : 'Could not request microphone access.');
     }
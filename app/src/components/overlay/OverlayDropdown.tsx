@@ -1,4 +1,4 @@
-import { useEffect, useRef, useState } from 'react';
+import { useEffect, useState } from 'react';
 import type { OverlayGeometry } from '../../lib/overlayGeometry';
 import type { DictationStatus } from '../../lib/types';
 
@@ -42,67 +42,127 @@ function SlidersIcon({ stroke }: { stroke: string }) {
   );
 }
 
+function CheckIcon({ stroke }: { stroke: string }) {
+  return (
+    <svg width="13" height="13" viewBox="0 0 24 24" fill="none" stroke={stroke} strokeWidth="2.5" strokeLinecap="round" strokeLinejoin="round">
+      <polyline points="5 12 10 17 19 7" />
+    </svg>
+  );
+}
+
+function DiscardIcon({ stroke }: { stroke: string }) {
+  return (
+    <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke={stroke} strokeWidth="2.5" strokeLinecap="round" strokeLinejoin="round">
+      <line x1="6" y1="6" x2="18" y2="18" />
+      <line x1="18" y1="6" x2="6" y2="18" />
+    </svg>
+  );
+}
+
+function CopyIcon({ stroke }: { stroke: string }) {
+  return (
+    <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke={stroke} strokeWidth="2.5" strokeLinecap="round" strokeLinejoin="round">
+      <rect x="9" y="9" width="12" height="12" rx="2" />
+      <path d="M5 15H4a2 2 0 0 1-2-2V4a2 2 0 0 1 2-2h9a2 2 0 0 1 2 2v1" />
+    </svg>
+  );
+}
+
 interface OverlayDropdownProps {
   geometry: OverlayGeometry;
   expanded: boolean;
   status: DictationStatus;
+  /** Unix ms the recording started, from `overlay-state`; anchors the timer. */
+  recordingStartedAt: number | null;
+  /** Text known before the final result, such as a draft being refined. */
+  partialText: string | null;
+  /** An `overlay-state` error message, shown until it times out. */
+  errorText: string | null;
   showTapMissed: boolean;
   disabled: boolean;
   autoPaste: boolean;
   fileOutputEnabled: boolean;
+  /** Append-mode takes waiting to be committed, and their joined text. */
+  bufferedTakes: number;
+  bufferedText: string;
+  /** Dictation held by preview mode, or `null` when nothing is pending. */
+  previewText: string | null;
+  /** App whose trigger offers ambient transcription, or `null`. */
+  ambientPromptApp: string | null;
   onToggleDisabled: (e: React.MouseEvent) => void;
   onToggleAutoPaste: (e: React.MouseEvent) => void;
   onOpenSettings: (e: React.MouseEvent) => void;
+  onCommitBuffer: (e: React.MouseEvent) => void;
+  onDiscardBuffer: (e: React.MouseEvent) => void;
+  onAcceptPreview: (e: React.MouseEvent) => void;
+  onDiscardPreview: (e: React.MouseEvent) => void;
+  onCancelRecording: (e: React.MouseEvent) => void;
+  onCopyLast: (e: React.MouseEvent) => void;
+  onStartAmbient: (e: React.MouseEvent) => void;
+  onDismissAmbientPrompt: (e: React.MouseEvent) => void;
 }
 
 /**
  * The dropdown row revealed on hover-expand: the three quick-settings buttons
  * (centered), plus a left-anchored status slot that carries content too wide for
  * a wing — the recording `m:ss` timer (when recording) or the "Tap missed" label
- * (during a hotkey-miss flash). The slot is absolutely positioned so the buttons
- * stay centered regardless of it.
+ * (during a hotkey-miss flash), partial text while processing, or an error
+ * message. The slot is absolutely positioned so the buttons
+ * stay centered regardless of it. While append-mode takes are buffered, the slot
+ * shows the take count and a right-anchored pair commits or discards them. A
+ * dictation held by preview mode gets the same treatment: its text in the slot,
+ * and a pair that pastes or discards it. Otherwise the right slot cancels a
+ * recording, or copies the last transcription while idle. An app trigger's
+ * offer of ambient transcription takes both slots while idle with nothing
+ * else pending: the app name, and a pair that starts or dismisses.
  */
 export function OverlayDropdown({
   geometry,
   expanded,
   status,
+  recordingStartedAt,
+  partialText,
+  errorText,
   showTapMissed,
   disabled,
   autoPaste,
   fileOutputEnabled,
+  bufferedTakes,
+  bufferedText,
+  previewText,
+  ambientPromptApp,
   onToggleDisabled,
   onToggleAutoPaste,
   onOpenSettings,
+  onCommitBuffer,
+  onDiscardBuffer,
+  onAcceptPreview,
+  onDiscardPreview,
+  onCancelRecording,
+  onCopyLast,
+  onStartAmbient,
+  onDismissAmbientPrompt,
 }: OverlayDropdownProps) {
   const [elapsed, setElapsed] = useState(0);
-  const recordingStartRef = useRef<number | null>(null);
 
-  // Anchor the recording-start timestamp to the status->'recording' transition,
-  // independent of hover/expand. Recording commonly starts via hotkey while the
-  // card is collapsed; the user may hover much later, so elapsed must be measured
-  // from recording start — not from when the timer became visible.
+  // Rust stamps the recording start on `overlay-state`, independent of
+  // hover/expand. Recording commonly starts via hotkey while the card is
+  // collapsed; the user may hover much later, so elapsed is measured from that
+  // stamp — not from when the timer became visible — and every mirror agrees.
   useEffect(() => {
-    if (status === 'recording') {
-      recordingStartRef.current = Date.now();
-    } else {
-      recordingStartRef.current = null;
-      setElapsed(0);
-    }
-  }, [status]);
+    if (recordingStartedAt == null) setElapsed(0);
+  }, [recordingStartedAt]);
 
   // Only tick the interval while the row is actually visible-and-recording (no
   // ticks when collapsed), but always compute displayed elapsed from the anchor.
-  const recordingVisible = expanded && status === 'recording';
+  const recordingVisible = expanded && status === 'recording' && recordingStartedAt != null;
   useEffect(() => {
-    if (!recordingVisible) return;
-    const tick = () => {
-      const start = recordingStartRef.current;
-      if (start != null) setElapsed(Math.floor((Date.now() - start) / 1000));
-    };
+    if (!recordingVisible || recordingStartedAt == null) return;
+    const tick = () => setElapsed(Math.max(0, Math.floor((Date.now() - recordingStartedAt) / 1000)));
     tick();
     const id = setInterval(tick, 250);
     return () => clearInterval(id);
-  }, [recordingVisible]);
+  }, [recordingVisible, recordingStartedAt]);
   const effectiveAutoPaste = autoPaste && !fileOutputEnabled;
   const autoPastePaused = autoPaste && fileOutputEnabled;
   const autoPasteLabel = autoPastePaused
@@ -120,6 +180,10 @@ export function OverlayDropdown({
     : autoPastePaused
       ? 'rgba(245,158,11,0.14)'
       : 'rgba(255,255,255,0.06)';
+  // A new take may record while a preview waits; its timer takes the slot.
+  const showPreview = previewText !== null && status !== 'recording' && !showTapMissed;
+  const showPartial = partialText !== null && status !== 'recording' && !showTapMissed;
+  const showAmbientPrompt = ambientPromptApp !== null && status === 'idle' && !showPreview && !showPartial && !showTapMissed && errorText === null;
 
   return (
     <div
@@ -137,27 +201,157 @@ export function OverlayDropdown({
       }}
     >
       {/* Left status slot — content too wide for a wing renders here, below the
-          notch. Recording timer takes precedence; the "Tap missed" label shows
-          during a hotkey-miss flash. Absolutely positioned so the buttons stay
-          centered. */}
-      {(status === 'recording' || showTapMissed) && (
+          notch. An error message takes precedence, then the "Tap missed" label
+          during a hotkey-miss flash, then the recording timer. Absolutely
+          positioned so the buttons stay centered. */}
+      {(errorText !== null || status === 'recording' || showTapMissed || status === 'buffering' || showPreview || showPartial || showAmbientPrompt) && (
         <span
           className="absolute left-[10px] top-0 bottom-[6px] flex items-center pointer-events-none"
-          aria-live={showTapMissed ? 'polite' : undefined}
+          aria-live={showTapMissed || errorText !== null ? 'polite' : undefined}
         >
-          {/* Priority mirrors deriveVisual's indicator: hotkey-miss beats recording. */}
-          {showTapMissed ? (
+          {/* Priority mirrors deriveVisual's indicator: an error beats hotkey-miss, which beats recording. */}
+          {errorText !== null ? (
+            <span className="text-red-300 truncate" style={{ fontSize: 11, maxWidth: 'calc(50% - 64px)' }} title={errorText}>
+              {errorText}
+            </span>
+          ) : showTapMissed ? (
             <span className="text-amber-300 font-medium" style={{ fontSize: 11 }}>
               Tap missed
             </span>
-          ) : (
+          ) : status === 'recording' ? (
             <span className="text-white/60 tabular-nums" style={{ fontSize: 11 }}>
               {formatElapsed(elapsed)}
             </span>
+          ) : showPartial ? (
+            // Keep clear of the centered buttons (three 26px buttons, 12px gaps).
+            <span className="text-white/60 italic truncate" style={{ fontSize: 11, maxWidth: 'calc(50% - 64px)' }}>
+              {partialText}
+            </span>
+          ) : showPreview ? (
+            // Keep clear of the centered buttons (three 26px buttons, 12px gaps).
+            <span className="text-amber-200 truncate" style={{ fontSize: 11, maxWidth: 'calc(50% - 64px)' }}>
+              {previewText}
+            </span>
+          ) : showAmbientPrompt ? (
+            <span className="text-sky-300 truncate" style={{ fontSize: 11, maxWidth: 'calc(50% - 64px)' }}>
+              Ambient for {ambientPromptApp}?
+            </span>
+          ) : (
+            <span className="text-sky-300 tabular-nums" style={{ fontSize: 11 }}>
+              {bufferedTakes === 1 ? '1 take' : `${bufferedTakes} takes`}
+            </span>
           )}
         </span>
       )}
 
+      {/* Append buffer actions — right-anchored for the same reason. */}
+      {status === 'buffering' && (
+        <span className="absolute right-[10px] top-0 bottom-[6px] flex items-center gap-1.5">
+          <button
+            type="button"
+            aria-label="Discard buffered takes"
+            title="Discard buffered takes"
+            onClick={onDiscardBuffer}
+            className="shrink-0 flex items-center justify-center cursor-pointer rounded-[9px] transition-colors"
+            style={{ width: 22, height: 22, background: 'rgba(255,255,255,0.06)' }}
+          >
+            <DiscardIcon stroke="rgba(255,255,255,0.7)" />
+          </button>
+          <button
+            type="button"
+            aria-label="Commit buffered takes"
+            title={bufferedText}
+            onClick={onCommitBuffer}
+            className="shrink-0 flex items-center justify-center cursor-pointer rounded-[9px] transition-colors"
+            style={{ width: 22, height: 22, background: 'rgba(56,189,248,0.18)' }}
+          >
+            <CheckIcon stroke="#38bdf8" />
+          </button>
+        </span>
+      )}
+
+      {/* Preview actions — right-anchored like the buffer actions. */}
+      {showPreview && (
+        <span className="absolute right-[10px] top-0 bottom-[6px] flex items-center gap-1.5">
+          <button
+            type="button"
+            aria-label="Discard dictation"
+            title="Discard dictation"
+            onClick={onDiscardPreview}
+            className="shrink-0 flex items-center justify-center cursor-pointer rounded-[9px] transition-colors"
+            style={{ width: 22, height: 22, background: 'rgba(255,255,255,0.06)' }}
+          >
+            <DiscardIcon stroke="rgba(255,255,255,0.7)" />
+          </button>
+          <button
+            type="button"
+            aria-label="Paste dictation"
+            title={previewText ?? undefined}
+            onClick={onAcceptPreview}
+            className="shrink-0 flex items-center justify-center cursor-pointer rounded-[9px] transition-colors"
+            style={{ width: 22, height: 22, background: 'rgba(251,191,36,0.18)' }}
+          >
+            <CheckIcon stroke="#fbbf24" />
+          </button>
+        </span>
+      )}
+
+      {/* Recording and last-transcription actions — right-anchored, in the slot
+          the buffer and preview pairs use when they are up. */}
+      {status === 'recording' && (
+        <span className="absolute right-[10px] top-0 bottom-[6px] flex items-center">
+          <button
+            type="button"
+            aria-label="Cancel recording"
+            title="Cancel recording"
+            onClick={onCancelRecording}
+            className="shrink-0 flex items-center justify-center cursor-pointer rounded-[9px] transition-colors"
+            style={{ width: 22, height: 22, background: 'rgba(239,68,68,0.12)' }}
+          >
+            <DiscardIcon stroke="#ef4444" />
+          </button>
+        </span>
+      )}
+      {/* Ambient offer actions — in the right slot instead of Copy last. */}
+      {showAmbientPrompt && (
+        <span className="absolute right-[10px] top-0 bottom-[6px] flex items-center gap-1.5">
+          <button
+            type="button"
+            aria-label="Not now"
+            title="Not now"
+            onClick={onDismissAmbientPrompt}
+            className="shrink-0 flex items-center justify-center cursor-pointer rounded-[9px] transition-colors"
+            style={{ width: 22, height: 22, background: 'rgba(255,255,255,0.06)' }}
+          >
+            <DiscardIcon stroke="rgba(255,255,255,0.7)" />
+          </button>
+          <button
+            type="button"
+            aria-label="Start ambient transcription"
+            title="Start ambient transcription"
+            onClick={onStartAmbient}
+            className="shrink-0 flex items-center justify-center cursor-pointer rounded-[9px] transition-colors"
+            style={{ width: 22, height: 22, background: 'rgba(56,189,248,0.18)' }}
+          >
+            <CheckIcon stroke="#38bdf8" />
+          </button>
+        </span>
+      )}
+      {status === 'idle' && !showPreview && !showAmbientPrompt && (
+        <span className="absolute right-[10px] top-0 bottom-[6px] flex items-center">
+          <button
+            type="button"
+            aria-label="Copy last transcription"
+            title="Copy last transcription"
+            onClick={onCopyLast}
+            className="shrink-0 flex items-center justify-center cursor-pointer rounded-[9px] transition-colors"
+            style={{ width: 22, height: 22, background: 'rgba(255,255,255,0.06)' }}
+          >
+            <CopyIcon stroke="rgba(255,255,255,0.7)" />
+          </button>
+        </span>
+      )}
+
       {/* Global disable */}
       <button
         type="button"
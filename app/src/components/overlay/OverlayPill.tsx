@@ -7,6 +7,8 @@ interface OverlayPillProps {
   geometry: OverlayGeometry;
   visual: OverlayVisual;
   status: DictationStatus;
+  /** Append-mode takes waiting to be committed; shown by the buffering badge. */
+  bufferedTakes: number;
   barRefs: React.MutableRefObject<(HTMLDivElement | null)[]>;
 }
 
@@ -25,6 +27,7 @@ export function OverlayPill({
   geometry,
   visual,
   status,
+  bufferedTakes,
   barRefs,
 }: OverlayPillProps) {
   const topH = geometry.collapsedH;
@@ -46,6 +49,10 @@ export function OverlayPill({
               <line x1="6" y1="6" x2="18" y2="18" />
               <line x1="18" y1="6" x2="6" y2="18" />
             </svg>
+          ) : indicator.kind === 'error' ? (
+            <span className="w-3 h-3 rounded-full border border-red-400 text-red-300 text-[8px] leading-none flex items-center justify-center font-bold" aria-label="error">
+              !
+            </span>
           ) : indicator.kind === 'hotkeyMiss' ? (
             <span className="w-3 h-3 rounded-full border border-amber-400 text-amber-300 text-[8px] leading-none flex items-center justify-center font-bold">
               !
@@ -54,6 +61,17 @@ export function OverlayPill({
             <div className="w-2.5 h-2.5 rounded-full bg-red-500" style={{ animation: 'pulse 0.8s ease-in-out infinite' }} />
           ) : indicator.kind === 'processing' ? (
             <span className="w-3 h-3 border-[1.5px] border-white/20 border-t-white/70 rounded-full animate-spin block" />
+          ) : indicator.kind === 'buffering' ? (
+            // Append mode: takes are waiting to be committed.
+            <span className="min-w-3 h-3 px-[3px] rounded-full bg-sky-400/80 text-white text-[8px] leading-none flex items-center justify-center font-bold" aria-label={`${bufferedTakes} takes waiting`}>
+              {bufferedTakes}
+            </span>
+          ) : indicator.kind === 'preview' ? (
+            // Preview mode: a dictation is waiting for Accept or Discard.
+            <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="#fbbf24" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round" aria-label="preview waiting">
+              <path d="M2 12s3.5-7 10-7 10 7 10 7-3.5 7-10 7S2 12 2 12z" />
+              <circle cx="12" cy="12" r="3" />
+            </svg>
           ) : indicator.kind === 'secureField' ? (
             // Brief flash when a secure/password field is refused (issue #312).
             <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="#f59e0b" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round" aria-label="secure field">
@@ -70,6 +88,11 @@ export function OverlayPill({
           ) : indicator.kind === 'transforming' ? (
             // "Transforming…" — local LLM is thinking (issue #312).
             <span className="w-2.5 h-2.5 rounded-full bg-violet-400 block" style={{ animation: 'pulse 0.8s ease-in-out infinite' }} />
+          ) : indicator.kind === 'ambientPrompt' ? (
+            // An app trigger offers ambient transcription; hover for Start.
+            <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="#38bdf8" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round" aria-label="ambient transcription offered">
+              <path d="M2 12h2M6 8v8M10 5v14M14 8v8M18 10v4M22 12h0" />
+            </svg>
           ) : (
             <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="rgba(255,255,255,0.4)" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round" style={{ opacity: indicator.dimmed ? 0.15 : 1 }}>
               <rect x="9" y="1" width="6" height="12" rx="3" />
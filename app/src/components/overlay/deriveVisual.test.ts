@@ -2,12 +2,12 @@ import { describe, expect, it } from 'vitest';
 import { deriveVisual, type OverlayIndicator } from './deriveVisual';
 import type { DictationStatus } from '../../lib/types';
 
-const STATUSES: DictationStatus[] = ['idle', 'recording', 'processing'];
+const STATUSES: DictationStatus[] = ['idle', 'recording', 'processing', 'buffering'];
 const BOOLS = [false, true];
 
 /**
  * Independently encodes the expected priority (cancelled > hotkey-miss >
- * recording > processing > idle) so the test does not just re-derive the
+ * recording > processing > buffering > idle) so the test does not just re-derive the
  * same branches as the implementation under test.
  */
 function expectedIndicator(
@@ -20,6 +20,7 @@ function expectedIndicator(
   if (showHotkeyMiss) return { kind: 'hotkeyMiss' };
   if (status === 'recording') return { kind: 'recording' };
   if (status === 'processing') return { kind: 'processing' };
+  if (status === 'buffering') return { kind: 'buffering' };
   return { kind: 'idle', dimmed: disabled };
 }
 
@@ -124,4 +125,38 @@ describe('deriveVisual', () => {
     expect(visual.showTapMissedLabel).toBe(false);
     expect(visual.waveformVisible).toBe(false);
   });
+
+  it('buffered takes show above transforming but below an active status', () => {
+    expect(deriveVisual('buffering', false, false, false, true).indicator).toEqual({ kind: 'buffering' });
+    expect(deriveVisual('buffering', false, true, false).indicator).toEqual({ kind: 'hotkeyMiss' });
+    expect(deriveVisual('buffering', false, false, false).waveformVisible).toBe(false);
+  });
+
+  it('a pending preview shows above transforming but yields to a new take', () => {
+    expect(deriveVisual('idle', false, false, false, true, false, false, true).indicator).toEqual({ kind: 'preview' });
+    expect(deriveVisual('recording', false, false, false, false, false, false, true).indicator).toEqual({ kind: 'recording' });
+    expect(deriveVisual('processing', false, false, false, false, false, false, true).indicator).toEqual({ kind: 'processing' });
+    expect(deriveVisual('idle', false, true, false, false, false, false, true).indicator).toEqual({ kind: 'hotkeyMiss' });
+  });
+
+  it('a draft being refined shows the spinner while idle only', () => {
+    expect(deriveVisual('idle', false, false, false, false, false, false, false, true).indicator).toEqual({ kind: 'processing' });
+    expect(deriveVisual('recording', false, false, false, false, false, false, false, true).indicator).toEqual({ kind: 'recording' });
+    expect(deriveVisual('buffering', false, false, false, false, false, false, false, true).indicator).toEqual({ kind: 'buffering' });
+  });
+
+  it('an ambient offer shows only when nothing else is going on', () => {
+    expect(deriveVisual('idle', false, false, true, false, false, false, false, false, false, true).indicator).toEqual({ kind: 'ambientPrompt' });
+    expect(deriveVisual('idle', false, false, false, true, false, false, false, false, false, true).indicator).toEqual({ kind: 'transforming' });
+    expect(deriveVisual('idle', false, false, false, false, false, false, true, false, false, true).indicator).toEqual({ kind: 'preview' });
+    expect(deriveVisual('recording', false, false, false, false, false, false, false, false, false, true).indicator).toEqual({ kind: 'recording' });
+  });
+
+  it('an error beats every flash but cancelled and lights the red glow', () => {
+    const visual = deriveVisual('recording', false, true, false, true, true, true, true, true, true);
+    expect(visual.indicator).toEqual({ kind: 'error' });
+    expect(visual.showError).toBe(true);
+    expect(deriveVisual('idle', true, false, false, false, false, false, false, false, true).indicator).toEqual({ kind: 'cancelled' });
+    expect(deriveVisual('idle', false, false, false).showError).toBe(false);
+  });
 });
@@ -8,12 +8,16 @@ import type { DictationStatus } from '../../lib/types';
  */
 export type OverlayIndicator =
   | { kind: 'cancelled' }
+  | { kind: 'error' }
   | { kind: 'secureField' }
   | { kind: 'transformBusy' }
   | { kind: 'hotkeyMiss' }
   | { kind: 'recording' }
   | { kind: 'processing' }
+  | { kind: 'buffering' }
+  | { kind: 'preview' }
   | { kind: 'transforming' }
+  | { kind: 'ambientPrompt' }
   | { kind: 'idle'; dimmed: boolean };
 
 export interface OverlayVisual {
@@ -25,6 +29,8 @@ export interface OverlayVisual {
    * too wide for a wing). The `!` badge itself is carried by `indicator`.
    */
   showTapMissedLabel: boolean;
+  /** An `overlay-state` error is showing: the island glows red. */
+  showError: boolean;
   /** Waveform bars are visible (opacity 1) vs. hidden (opacity 0). */
   waveformVisible: boolean;
 }
@@ -38,8 +44,9 @@ export interface OverlayVisual {
  *   showCancelled ? X : showHotkeyMiss ? ! : status==='recording' ? dot
  *     : status==='processing' ? spinner : mic (dimmed if disabled)
  *
- * Priority: cancelled > secure-field flash > transform-busy flash >
- * hotkey-miss > recording > processing > transforming > idle. (Since `status`
+ * Priority: cancelled > error > secure-field flash > transform-busy flash >
+ * hotkey-miss > recording > processing > buffering > preview > transforming >
+ * ambient prompt > idle. (Since `status`
  * is a single enum value, recording and processing can never both be true, so
  * their relative order does not change behavior — only idle's position at the
  * end, after both, matters.)
@@ -49,8 +56,13 @@ export interface OverlayVisual {
  * the local LLM is thinking, and a brief flash when a password/secure field is
  * refused. `showTransformBusy` (issue #329) flashes when a transform keypress
  * was refused because dictation/benchmark/file-transcription/a mid-flight
- * transform owns the pipeline. All default off so the dictation call sites
- * are unchanged.
+ * transform owns the pipeline. `previewPending` marks a dictation held by
+ * preview mode for Accept or Discard; it yields to a new take recording.
+ * `refining` is an `overlay-state` processing that outlives the status, such
+ * as a dual-model draft being refined, and shows the spinner while idle.
+ * `showError` is an `overlay-state` error. `ambientPrompt` marks an app
+ * trigger's offer of ambient transcription, the least urgent of all. All
+ * default off so the dictation call sites are unchanged.
  */
 export function deriveVisual(
   status: DictationStatus,
@@ -60,26 +72,39 @@ export function deriveVisual(
   transforming: boolean = false,
   showSecureField: boolean = false,
   showTransformBusy: boolean = false,
+  previewPending: boolean = false,
+  refining: boolean = false,
+  showError: boolean = false,
+  ambientPrompt: boolean = false,
 ): OverlayVisual {
   const indicator: OverlayIndicator = showCancelled
     ? { kind: 'cancelled' }
-    : showSecureField
-      ? { kind: 'secureField' }
-      : showTransformBusy
-        ? { kind: 'transformBusy' }
-        : showHotkeyMiss
-          ? { kind: 'hotkeyMiss' }
-          : status === 'recording'
-            ? { kind: 'recording' }
-            : status === 'processing'
-              ? { kind: 'processing' }
-              : transforming
-                ? { kind: 'transforming' }
-                : { kind: 'idle', dimmed: disabled };
+    : showError
+      ? { kind: 'error' }
+      : showSecureField
+        ? { kind: 'secureField' }
+        : showTransformBusy
+          ? { kind: 'transformBusy' }
+          : showHotkeyMiss
+            ? { kind: 'hotkeyMiss' }
+            : status === 'recording'
+              ? { kind: 'recording' }
+              : status === 'processing' || (refining && status === 'idle')
+                ? { kind: 'processing' }
+                : status === 'buffering'
+                  ? { kind: 'buffering' }
+                  : previewPending
+                    ? { kind: 'preview' }
+                    : transforming
+                      ? { kind: 'transforming' }
+                      : ambientPrompt
+                        ? { kind: 'ambientPrompt' }
+                        : { kind: 'idle', dimmed: disabled };
 
   return {
     indicator,
     showTapMissedLabel: showHotkeyMiss,
+    showError,
     waveformVisible: status === 'recording',
   };
 }
@@ -6,12 +6,14 @@ import type { OverlayGeometry } from '../../lib/overlayGeometry';
 const entries: Array<[string, OverlayGeometry]> = [
   ['notched', fixture.notched],
   ['fallback', fixture.fallback],
+  ['pill', fixture.pill],
 ];
 
 describe('overlay geometry contract fixture', () => {
   it('validates as OverlayGeometry', () => {
     expect(isOverlayGeometry(fixture.notched)).toBe(true);
     expect(isOverlayGeometry(fixture.fallback)).toBe(true);
+    expect(isOverlayGeometry(fixture.pill)).toBe(true);
   });
 
   it.each(entries)('holds the geometry invariants (%s)', (_name, g) => {
@@ -28,17 +30,24 @@ describe('overlay geometry contract fixture', () => {
       windowW: 257, collapsedH: 32, expandedH: 76,
       pillIdleW: 257, pillActiveW: 257,
       pillMarginIdle: 0, pillMarginActive: 0,
-      dropdownH: 44, wingW: 36,
+      dropdownH: 44, wingW: 36, floating: false,
     });
     expect(fixture.fallback).toEqual({
       windowW: 152, collapsedH: 37, expandedH: 81,
       pillIdleW: 152, pillActiveW: 152,
       pillMarginIdle: 0, pillMarginActive: 0,
-      dropdownH: 44, wingW: 36,
+      dropdownH: 44, wingW: 36, floating: false,
+    });
+    expect(fixture.pill).toEqual({
+      windowW: 136, collapsedH: 32, expandedH: 76,
+      pillIdleW: 136, pillActiveW: 136,
+      pillMarginIdle: 0, pillMarginActive: 0,
+      dropdownH: 44, wingW: 36, floating: true,
     });
   });
 
   it('rejects unilateral shape drift', () => {
     expect(isOverlayGeometry({ ...fixture.notched, extraField: 1 })).toBe(false);
+    expect(isOverlayGeometry({ ...fixture.pill, floating: 'yes' })).toBe(false);
   });
 });
@@ -0,0 +1,78 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { clearAmbientLog, getAmbientLog, type AmbientEntry } from '../../lib/dictation';
+
+/** Searchable list of what ambient mode has transcribed, newest first. */
+export function AmbientLog() {
+  const [query, setQuery] = useState('');
+  const [entries, setEntries] = useState<AmbientEntry[]>([]);
+  const [revision, setRevision] = useState(0);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    listen('ambient-entry', () => setRevision((value) => value + 1)).then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    let disposed = false;
+    getAmbientLog(query)
+      .then((list) => { if (!disposed) setEntries(list); })
+      .catch((err) => { if (!disposed) setError(String(err)); });
+    return () => { disposed = true; };
+  }, [query, revision]);
+
+  const clear = async () => {
+    setError(null);
+    try {
+      await clearAmbientLog();
+      setEntries([]);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <input
+          type="search"
+          value={query}
+          onChange={(event) => setQuery(event.target.value)}
+          placeholder="Search the ambient log"
+          aria-label="Search the ambient log"
+          className="min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface focus:border-primary focus:outline-none"
+        />
+        <button
+          type="button"
+          onClick={() => void clear()}
+          disabled={entries.length === 0 && !query}
+          className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+        >
+          Clear
+        </button>
+      </div>
+      {entries.length === 0 ? (
+        <p className="text-xs text-on-surface-variant">{query ? 'Nothing matches.' : 'Nothing transcribed yet.'}</p>
+      ) : (
+        <ul className="max-h-64 space-y-1 overflow-auto">
+          {entries.map((entry) => (
+            <li key={`${entry.startedAtMs}-${entry.durationMs}`} className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs">
+              <span className="text-on-surface-variant">{new Date(entry.startedAtMs).toLocaleString()}</span>
+              <p className="mt-0.5 text-on-surface">{entry.text}</p>
+            </li>
+          ))}
+        </ul>
+      )}
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
@@ -15,6 +15,7 @@ const TERMINAL: AppProfile = {
   cleanupOverride: null,
   smartFormattingOverride: null,
   cliFormattingOverride: null,
+  trailingOverride: null,
   writingStyle: null,
   ideContextEnabled: false,
   ideProjectRoots: [],
@@ -83,4 +84,15 @@ describe('AppOverridesEditor', () => {
     });
     expect(onChange).toHaveBeenCalledWith([{ ...TERMINAL, cleanupOverride: false }]);
   });
+
+  it('overrides what follows a paste and can return it to the global setting', async () => {
+    await act(async () => root.render(<AppOverridesEditor profiles={[{ ...TERMINAL, trailingOverride: 'newline' }]} onChange={onChange} />));
+    const select = container.querySelector('[aria-label="After each paste for Terminal"]') as HTMLSelectElement;
+    expect(select.value).toBe('newline');
+    await act(async () => {
+      select.value = 'inherit';
+      select.dispatchEvent(new Event('change', { bubbles: true }));
+    });
+    expect(onChange).toHaveBeenCalledWith([TERMINAL]);
+  });
 });
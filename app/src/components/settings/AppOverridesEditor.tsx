@@ -3,8 +3,10 @@ import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
 import {
   type AppProfile,
+  type TrailingBehavior,
   type WritingStyle,
   type WritingStyleChoice,
+  TRAILING_BEHAVIOR_OPTIONS,
   WRITING_STYLE_OPTIONS,
 } from '../../lib/settings';
 import { Select } from '../ui/Select';
@@ -80,13 +82,14 @@ function newProfile(bundleId: string, label: string): AppProfile {
     cleanupOverride: null,
     smartFormattingOverride: null,
     cliFormattingOverride: null,
+    trailingOverride: null,
     writingStyle: null,
     ideContextEnabled: false,
     ideProjectRoots: [],
   };
 }
 
-function OverrideSelect({
+export function OverrideSelect({
   label,
   appLabel,
   value,
@@ -331,6 +334,20 @@ export function AppOverridesEditor({ profiles, onChange }: {
                   <OverrideSelect label="Transcript cleanup" appLabel={appLabel} value={profile.cleanupOverride} onChange={(value) => updateProfile(profile.bundleId, { cleanupOverride: value })} />
                   <OverrideSelect label="Structured writing" appLabel={appLabel} value={profile.smartFormattingOverride} onChange={(value) => updateProfile(profile.bundleId, { smartFormattingOverride: value })} />
                   <OverrideSelect label="Command formatting" appLabel={appLabel} value={profile.cliFormattingOverride} onChange={(value) => updateProfile(profile.bundleId, { cliFormattingOverride: value })} />
+                  <label className="block text-xs font-medium text-on-surface">
+                    After each paste
+                    <select
+                      aria-label={`After each paste for ${appLabel}`}
+                      value={profile.trailingOverride ?? 'inherit'}
+                      onChange={(event) => updateProfile(profile.bundleId, { trailingOverride: event.target.value === 'inherit' ? null : event.target.value as TrailingBehavior })}
+                      className="mt-1 w-full rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-2.5 py-2 text-xs text-on-surface outline-none focus:border-primary focus:ring-1 focus:ring-primary"
+                    >
+                      <option value="inherit">Use global setting</option>
+                      {TRAILING_BEHAVIOR_OPTIONS.map((option) => (
+                        <option key={option.value} value={option.value}>{option.label}</option>
+                      ))}
+                    </select>
+                  </label>
                 </div>
 
                 <div className="rounded-lg border border-outline-variant/30 bg-surface-container-low p-3">
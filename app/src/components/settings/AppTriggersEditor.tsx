@@ -0,0 +1,93 @@
+import { useEffect, useMemo, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { AppTrigger } from '../../lib/settings';
+import type { RunningApplication } from './AppOverridesEditor';
+
+/** Apps that keep the overlay up or offer ambient transcription when they come to the front. */
+export function AppTriggersEditor({ triggers, onChange }: {
+  triggers: AppTrigger[];
+  onChange: (next: AppTrigger[]) => void;
+}) {
+  const [runningApps, setRunningApps] = useState<RunningApplication[]>([]);
+  const [selectedApp, setSelectedApp] = useState('');
+
+  useEffect(() => {
+    let disposed = false;
+    invoke<RunningApplication[]>('list_running_applications')
+      .then((apps) => { if (!disposed) setRunningApps(apps); })
+      .catch(() => { if (!disposed) setRunningApps([]); });
+    return () => { disposed = true; };
+  }, []);
+
+  const availableApps = useMemo(() => {
+    const configured = new Set(triggers.map((trigger) => trigger.bundleId.toLocaleLowerCase()));
+    return runningApps.filter((app) => !configured.has(app.bundleId.toLocaleLowerCase()));
+  }, [triggers, runningApps]);
+
+  const addTrigger = () => {
+    const app = availableApps.find((candidate) => candidate.bundleId === selectedApp);
+    if (!app) return;
+    onChange([...triggers, { bundleId: app.bundleId, name: app.name, showOverlay: true, promptAmbient: false }]);
+    setSelectedApp('');
+  };
+
+  const updateTrigger = (bundleId: string, update: Partial<AppTrigger>) => {
+    onChange(triggers.map((trigger) => trigger.bundleId === bundleId ? { ...trigger, ...update } : trigger));
+  };
+
+  return (
+    <div className="space-y-2">
+      {triggers.map((trigger) => (
+        <div key={trigger.bundleId} className="rounded-lg border border-outline-variant/25 bg-surface-container-lowest p-2.5">
+          <div className="flex items-center gap-2">
+            <p className="min-w-0 flex-1 truncate text-xs font-medium text-on-surface" title={trigger.bundleId}>{trigger.name}</p>
+            <button
+              type="button"
+              onClick={() => onChange(triggers.filter((candidate) => candidate.bundleId !== trigger.bundleId))}
+              aria-label={`Remove trigger for ${trigger.name}`}
+              className="rounded-md px-2 py-0.5 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error"
+            >
+              Remove
+            </button>
+          </div>
+          <label className="mt-1 flex items-center gap-2 text-xs text-on-surface">
+            <input type="checkbox" checked={trigger.showOverlay} onChange={() => updateTrigger(trigger.bundleId, { showOverlay: !trigger.showOverlay })} className="accent-primary" />
+            Keep the overlay showing, even fullscreen
+          </label>
+          <label className="mt-1 flex items-center gap-2 text-xs text-on-surface">
+            <input type="checkbox" checked={trigger.promptAmbient} onChange={() => updateTrigger(trigger.bundleId, { promptAmbient: !trigger.promptAmbient })} className="accent-primary" />
+            Offer ambient transcription in the overlay
+          </label>
+        </div>
+      ))}
+      {availableApps.length > 0 ? (
+        <div className="flex gap-2">
+          <select
+            aria-label="Running app"
+            value={selectedApp}
+            onChange={(event) => setSelectedApp(event.target.value)}
+            className="min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface outline-none focus:border-primary focus:ring-1 focus:ring-primary"
+          >
+            <option value="">Choose a running app…</option>
+            {availableApps.map((app) => (
+              <option key={app.bundleId} value={app.bundleId}>{app.name}</option>
+            ))}
+          </select>
+          <button
+            type="button"
+            disabled={!selectedApp}
+            onClick={addTrigger}
+            className="shrink-0 rounded-lg bg-primary px-3 py-2 text-xs font-medium text-on-primary disabled:cursor-not-allowed disabled:opacity-50"
+          >
+            Add app
+          </button>
+        </div>
+      ) : (
+        <p className="text-xs text-on-surface-variant">Open the app you want to add, then come back here.</p>
+      )}
+      <p className="text-xs text-on-surface-variant">
+        Applies when one of these apps comes to the front. Nothing records until you click Start in the overlay, and an offer you pass on isn’t repeated for 10 minutes. macOS only.
+      </p>
+    </div>
+  );
+}
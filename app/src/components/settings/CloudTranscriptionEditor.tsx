@@ -0,0 +1,132 @@
+import { useEffect, useState } from 'react';
+import { clearCloudApiKey, getCloudApiKeyStored, setCloudApiKey, setCloudTranscription } from '../../lib/dictation';
+import { CLOUD_PROVIDER_OPTIONS, type CloudProvider, type CloudTranscriptionSettings } from '../../lib/settings';
+import { Select } from '../ui/Select';
+
+const INPUT_CLASS = 'rounded-xl border border-outline-variant/40 bg-surface-container-lowest px-3 py-2 font-mono text-xs text-on-surface outline-none focus:border-primary focus:ring-1 focus:ring-primary disabled:opacity-50';
+const BUTTON_CLASS = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50';
+
+/** Provider, endpoint, model, and Keychain API key for the `cloud` model.
+ * Fields commit on blur once Rust accepts them; the stored key is never shown. */
+export function CloudTranscriptionEditor({ value, disabled, onChange }: {
+  value: CloudTranscriptionSettings;
+  disabled: boolean;
+  onChange: (value: CloudTranscriptionSettings) => void;
+}) {
+  const [draft, setDraft] = useState(value);
+  const [key, setKey] = useState('');
+  const [keyStored, setKeyStored] = useState<boolean | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  useEffect(() => setDraft(value), [value]);
+  useEffect(() => {
+    let cancelled = false;
+    getCloudApiKeyStored()
+      .then((stored) => { if (!cancelled) setKeyStored(stored); })
+      .catch(() => { if (!cancelled) setKeyStored(false); });
+    return () => { cancelled = true; };
+  }, []);
+
+  const provider = CLOUD_PROVIDER_OPTIONS.find((option) => option.value === draft.provider) ?? CLOUD_PROVIDER_OPTIONS[0];
+
+  const commit = async (source: CloudTranscriptionSettings = draft) => {
+    const next = { provider: source.provider, endpoint: source.endpoint.trim(), model: source.model.trim() };
+    if (next.provider === value.provider && next.endpoint === value.endpoint && next.model === value.model) return;
+    try {
+      await setCloudTranscription(next);
+      setError(null);
+      onChange(next);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const saveKey = async () => {
+    try {
+      await setCloudApiKey(key);
+      setKey('');
+      setKeyStored(true);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const removeKey = async () => {
+    try {
+      await clearCloudApiKey();
+      setKeyStored(false);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <p className="text-xs text-on-surface-variant">
+        Choose Cloud transcription as the model to send each recording to this service instead of transcribing it on this Mac. The service receives the audio and your vocabulary prompt.
+      </p>
+      <div>
+        <span className="text-xs text-on-surface-variant">Provider</span>
+        <Select
+          value={draft.provider}
+          onChange={(value) => {
+            const next = { ...draft, provider: value as CloudProvider };
+            setDraft(next);
+            void commit(next);
+          }}
+          disabled={disabled}
+          items={CLOUD_PROVIDER_OPTIONS.map((option) => ({ value: option.value, label: option.label }))}
+        />
+      </div>
+      <label className="block">
+        <span className="text-xs text-on-surface-variant">Endpoint</span>
+        <input
+          type="text"
+          aria-label="Cloud endpoint"
+          value={draft.endpoint}
+          placeholder={provider.endpoint}
+          disabled={disabled}
+          spellCheck={false}
+          onChange={(event) => setDraft({ ...draft, endpoint: event.target.value })}
+          onBlur={() => void commit()}
+          className={`${INPUT_CLASS} mt-1 w-full`}
+        />
+        <span className="mt-1 block text-xs text-on-surface-variant">Leave empty for the provider's own API. Must be https, except on this Mac.</span>
+      </label>
+      <label className="block">
+        <span className="text-xs text-on-surface-variant">Model</span>
+        <input
+          type="text"
+          aria-label="Cloud model"
+          value={draft.model}
+          placeholder={provider.model}
+          disabled={disabled}
+          spellCheck={false}
+          onChange={(event) => setDraft({ ...draft, model: event.target.value })}
+          onBlur={() => void commit()}
+          className={`${INPUT_CLASS} mt-1 w-full`}
+        />
+      </label>
+      <div>
+        <span className="text-xs text-on-surface-variant">API key</span>
+        <div className="mt-1 flex items-center gap-2">
+          <input
+            type="password"
+            aria-label="Cloud API key"
+            value={key}
+            placeholder={keyStored ? 'Saved in Keychain' : 'Paste your API key'}
+            disabled={disabled}
+            autoComplete="off"
+            spellCheck={false}
+            onChange={(event) => setKey(event.target.value)}
+            className={`${INPUT_CLASS} min-w-0 flex-1`}
+          />
+          <button type="button" onClick={() => void saveKey()} disabled={disabled || !key.trim()} className={BUTTON_CLASS}>Save</button>
+          {keyStored && <button type="button" onClick={() => void removeKey()} disabled={disabled} className={BUTTON_CLASS}>Remove</button>}
+        </div>
+      </div>
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,89 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { getConfidenceRetryStats, type ConfidenceRetryReport, type ConfidenceRetryStats } from '../../lib/dictation';
+import { AVAILABLE_MODEL_OPTIONS, CONFIDENCE_RETRY_BOUNDS, type ConfidenceRetrySettings, type ModelOption } from '../../lib/settings';
+import { Select } from '../ui/Select';
+
+const SAME_MODEL = '';
+
+const MODEL_ITEMS = [
+  { value: SAME_MODEL, label: 'Same model, beam search' },
+  ...AVAILABLE_MODEL_OPTIONS
+    .filter((model) => model.backend === 'whisper')
+    .map((model) => ({ value: model.value as string, label: `${model.label} (${model.size})` })),
+];
+
+function RetrySlider({ label, value, min, max, step, format, description, onCommit }: {
+  label: string;
+  value: number;
+  min: number;
+  max: number;
+  step: number;
+  format: (value: number) => string;
+  description: string;
+  onCommit: (value: number) => void;
+}) {
+  const [draft, setDraft] = useState(value);
+  useEffect(() => setDraft(value), [value]);
+  return (
+    <div>
+      <div className="mb-1 flex items-center justify-between">
+        <label className="text-xs text-on-surface-variant">{label}</label>
+        <span className="text-xs font-medium text-on-surface">{format(draft)}</span>
+      </div>
+      <input
+        type="range"
+        aria-label={label}
+        min={min}
+        max={max}
+        step={step}
+        value={draft}
+        onChange={(event) => setDraft(Number(event.target.value))}
+        onPointerUp={() => onCommit(draft)}
+        className="h-1.5 w-full cursor-pointer appearance-none rounded-full bg-surface-container-highest accent-primary"
+      />
+      <p className="mt-1 text-xs text-on-surface-variant">{description}</p>
+    </div>
+  );
+}
+
+/** Threshold, retry model, and time budget for re-decoding unsure Whisper transcripts, with this session's retry counts. */
+export function ConfidenceRetryEditor({ value, disabled, onChange }: {
+  value: ConfidenceRetrySettings;
+  disabled: boolean;
+  onChange: (value: ConfidenceRetrySettings) => void;
+}) {
+  const [stats, setStats] = useState<ConfidenceRetryStats | null>(null);
+
+  useEffect(() => {
+    getConfidenceRetryStats().then(setStats).catch((err) => console.error('Failed to read confidence retry stats:', err));
+    const unlisten = listen<ConfidenceRetryReport>('confidence-retry', (event) => setStats(event.payload.totals));
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
+  const update = (patch: Partial<ConfidenceRetrySettings>) => onChange({ ...value, ...patch });
+  const { threshold, budgetMs } = CONFIDENCE_RETRY_BOUNDS;
+
+  return (
+    <div className="space-y-3">
+      <RetrySlider label="Confidence Threshold" value={value.threshold} min={threshold.min} max={threshold.max} step={0.05} format={(score) => score.toFixed(2)} description="Transcripts scoring below this are decoded again. Higher retries more often." onCommit={(next) => update({ threshold: next })} />
+      <div>
+        <label className="mb-1 block text-xs text-on-surface-variant">Retry With</label>
+        <Select
+          aria-label="Retry model"
+          value={value.model ?? SAME_MODEL}
+          onChange={(model) => update({ model: model === SAME_MODEL ? null : model as ModelOption })}
+          disabled={disabled}
+          items={MODEL_ITEMS}
+        />
+        <p className="mt-1 text-xs text-on-surface-variant">A larger model must be downloaded; otherwise, and in dual-model mode, the retry uses beam search on the same model.</p>
+      </div>
+      <RetrySlider label="Time Budget" value={value.budgetMs} min={budgetMs.min} max={budgetMs.max} step={250} format={(ms) => `${(ms / 1000).toFixed(2)}s`} description="The most a retry may add before pasting. A retry still running then is dropped and the first transcript is pasted." onCommit={(next) => update({ budgetMs: next })} />
+      {stats && stats.triggered > 0 && (
+        <p className="text-xs text-on-surface-variant">
+          This session: {stats.triggered} unsure, {stats.changed} changed by the retry, {stats.unchanged + stats.keptFirst} unchanged, {stats.skipped + stats.overBudget} over budget.
+        </p>
+      )}
+    </div>
+  );
+}
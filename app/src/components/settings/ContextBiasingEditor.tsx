@@ -0,0 +1,87 @@
+import { useEffect, useMemo, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { CONTEXT_BIASING_APPS_MAX, type ContextBiasingSettings } from '../../lib/settings';
+import type { RunningApplication } from './AppOverridesEditor';
+
+/** What context biasing reads, and the apps it may read from. */
+export function ContextBiasingEditor({ biasing, onChange }: {
+  biasing: ContextBiasingSettings;
+  onChange: (next: ContextBiasingSettings) => void;
+}) {
+  const [runningApps, setRunningApps] = useState<RunningApplication[]>([]);
+  const [selectedApp, setSelectedApp] = useState('');
+
+  useEffect(() => {
+    let disposed = false;
+    invoke<RunningApplication[]>('list_running_applications')
+      .then((apps) => { if (!disposed) setRunningApps(apps); })
+      .catch(() => { if (!disposed) setRunningApps([]); });
+    return () => { disposed = true; };
+  }, []);
+
+  const availableApps = useMemo(() => {
+    const allowed = new Set(biasing.apps.map((app) => app.bundleId.toLocaleLowerCase()));
+    return runningApps.filter((app) => !allowed.has(app.bundleId.toLocaleLowerCase()));
+  }, [biasing.apps, runningApps]);
+
+  const addApp = () => {
+    const app = availableApps.find((candidate) => candidate.bundleId === selectedApp);
+    if (!app) return;
+    onChange({ ...biasing, apps: [...biasing.apps, { bundleId: app.bundleId, name: app.name }] });
+    setSelectedApp('');
+  };
+
+  return (
+    <div className="space-y-2">
+      <label className="flex items-center gap-2 text-xs text-on-surface">
+        <input type="checkbox" checked={biasing.windowTitle} onChange={() => onChange({ ...biasing, windowTitle: !biasing.windowTitle })} className="accent-primary" />
+        Window title, such as an email subject or chat name
+      </label>
+      <label className="flex items-center gap-2 text-xs text-on-surface">
+        <input type="checkbox" checked={biasing.selectedText} onChange={() => onChange({ ...biasing, selectedText: !biasing.selectedText })} className="accent-primary" />
+        Selected text, such as the message you are replying to
+      </label>
+      {biasing.apps.map((app) => (
+        <div key={app.bundleId} className="flex items-center gap-2 rounded-lg border border-outline-variant/25 bg-surface-container-lowest p-2.5">
+          <p className="min-w-0 flex-1 truncate text-xs font-medium text-on-surface" title={app.bundleId}>{app.name}</p>
+          <button
+            type="button"
+            onClick={() => onChange({ ...biasing, apps: biasing.apps.filter((candidate) => candidate.bundleId !== app.bundleId) })}
+            aria-label={`Stop reading context from ${app.name}`}
+            className="rounded-md px-2 py-0.5 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error"
+          >
+            Remove
+          </button>
+        </div>
+      ))}
+      {availableApps.length > 0 && biasing.apps.length < CONTEXT_BIASING_APPS_MAX ? (
+        <div className="flex gap-2">
+          <select
+            aria-label="Running app"
+            value={selectedApp}
+            onChange={(event) => setSelectedApp(event.target.value)}
+            className="min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface outline-none focus:border-primary focus:ring-1 focus:ring-primary"
+          >
+            <option value="">Choose a running app…</option>
+            {availableApps.map((app) => (
+              <option key={app.bundleId} value={app.bundleId}>{app.name}</option>
+            ))}
+          </select>
+          <button
+            type="button"
+            disabled={!selectedApp}
+            onClick={addApp}
+            className="shrink-0 rounded-lg bg-primary px-3 py-2 text-xs font-medium text-on-primary disabled:cursor-not-allowed disabled:opacity-50"
+          >
+            Add app
+          </button>
+        </div>
+      ) : (
+        <p className="text-xs text-on-surface-variant">Open the app you want to add, then come back here.</p>
+      )}
+      <p className="text-xs text-on-surface-variant">
+        Only names and terms are kept, only for the dictation they were read for, and never logged. Password fields are never read. Whisper models only; needs Accessibility. macOS only.
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,90 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+
+interface CrashReportSummary {
+  id: string;
+  kind: 'panic' | 'signal';
+  createdAtMs: number;
+  message: string;
+}
+
+/** Lists local crash reports and exports one only after the user has reviewed it and consented. */
+export function CrashReportsPanel() {
+  const [reports, setReports] = useState<CrashReportSummary[]>([]);
+  const [selected, setSelected] = useState<string | null>(null);
+  const [content, setContent] = useState<string | null>(null);
+  const [consent, setConsent] = useState(false);
+  const [notice, setNotice] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let disposed = false;
+    invoke<CrashReportSummary[]>('list_crash_reports')
+      .then((list) => { if (!disposed) setReports(list ?? []); })
+      .catch(() => { if (!disposed) setReports([]); });
+    return () => { disposed = true; };
+  }, []);
+
+  const review = async (id: string) => {
+    setConsent(false);
+    setNotice(null);
+    setError(null);
+    if (selected === id) {
+      setSelected(null);
+      setContent(null);
+      return;
+    }
+    setSelected(id);
+    try {
+      setContent(await invoke<string>('read_crash_report', { id }));
+    } catch (cause) {
+      setContent(null);
+      setError(String(cause));
+    }
+  };
+
+  const exportReport = async () => {
+    if (!selected || !consent) return;
+    setError(null);
+    const path = await save({ defaultPath: `murmur-${selected}`, filters: [{ name: 'Text', extensions: ['txt'] }] });
+    if (typeof path !== 'string') return;
+    try {
+      await invoke('export_crash_report', { id: selected, path, consent });
+      setNotice('Crash report saved. Attach it to a bug report to share it.');
+    } catch (cause) {
+      setError(String(cause));
+    }
+  };
+
+  if (reports.length === 0) {
+    return <p className="text-xs text-on-surface-variant">No crashes recorded.</p>;
+  }
+
+  return (
+    <div className="space-y-2">
+      <ul className="space-y-1">
+        {reports.map((report) => (
+          <li key={report.id}>
+            <button type="button" onClick={() => void review(report.id)} aria-expanded={selected === report.id} className={`w-full rounded-lg border px-3 py-2 text-left text-xs transition-colors ${selected === report.id ? 'border-primary bg-primary/10 text-on-surface' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>
+              <span className="font-medium">{new Date(report.createdAtMs).toLocaleString()}</span>
+              <span className="ml-2 text-on-surface-variant">{report.kind === 'signal' ? 'Crashed' : 'Panicked'}: {report.message || 'no message'}</span>
+            </button>
+          </li>
+        ))}
+      </ul>
+      {selected && content !== null && (
+        <div className="space-y-2">
+          <pre className="max-h-48 overflow-auto whitespace-pre-wrap rounded-lg border border-outline-variant/30 bg-surface-container-lowest p-2 font-mono text-[11px] text-on-surface-variant">{content}</pre>
+          <label className="flex items-start gap-2 text-xs text-on-surface">
+            <input type="checkbox" checked={consent} onChange={() => setConsent(!consent)} className="mt-0.5 accent-primary" />
+            I’ve reviewed this report, including its recent log lines, and agree to share it.
+          </label>
+          <button type="button" onClick={() => void exportReport()} disabled={!consent} className="w-full rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50">Share Report…</button>
+        </div>
+      )}
+      {notice && <p className="text-xs text-emerald-600 dark:text-emerald-400">{notice}</p>}
+      {error && <p className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,67 @@
+import { useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { downloadCustomModel } from '../../lib/dictation';
+import { modelDownloadLabel, modelDownloadPercent, type ModelDownloadProgress } from '../../lib/modelDownload';
+import type { CustomModelOption } from '../../lib/settings';
+
+/** Download a Whisper GGML file outside the catalog and hand back its model name. */
+export function CustomModelDownloader({ disabled, onDownloaded }: {
+  disabled: boolean;
+  onDownloaded: (model: CustomModelOption) => void;
+}) {
+  const [source, setSource] = useState('');
+  const [progress, setProgress] = useState<ModelDownloadProgress | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const download = async () => {
+    if (!source.trim() || progress) return;
+    setError(null);
+    setProgress({ received: 0, total: 0, phase: 'downloading' });
+    let unlisten: (() => void) | null = null;
+    try {
+      unlisten = await listen<ModelDownloadProgress>('download-progress', (event) => setProgress(event.payload));
+      const model = await downloadCustomModel(source.trim());
+      setSource('');
+      onDownloaded(model);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      unlisten?.();
+      setProgress(null);
+    }
+  };
+
+  const percent = progress ? modelDownloadPercent(progress) : null;
+  return (
+    <div className="space-y-2">
+      <div className="flex gap-2">
+        <input
+          type="text"
+          aria-label="Custom model source"
+          value={source}
+          placeholder="owner/repo/ggml-model.bin or https://…"
+          disabled={disabled || progress !== null}
+          spellCheck={false}
+          onChange={(event) => setSource(event.target.value)}
+          onKeyDown={(event) => { if (event.key === 'Enter') void download(); }}
+          className="min-w-0 flex-1 rounded-xl border border-outline-variant/40 bg-surface-container-lowest px-3 py-2 font-mono text-xs text-on-surface outline-none focus:border-primary focus:ring-1 focus:ring-primary disabled:opacity-50"
+        />
+        <button
+          type="button"
+          onClick={() => void download()}
+          disabled={disabled || progress !== null || !source.trim()}
+          className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+        >
+          Download
+        </button>
+      </div>
+      {progress && (
+        <p className="text-xs text-on-surface-variant">
+          {modelDownloadLabel(progress)}{percent === null ? '…' : ` ${percent}%`}
+        </p>
+      )}
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+      <p className="text-xs text-on-surface-variant">A whisper.cpp GGML file from Hugging Face or any URL, such as a distilled or fine-tuned model.</p>
+    </div>
+  );
+}
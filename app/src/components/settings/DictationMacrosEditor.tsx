@@ -0,0 +1,141 @@
+import {
+  AVAILABLE_MODEL_OPTIONS,
+  DICTATION_MACRO_PHRASE_MAX,
+  DICTATION_MACROS_MAX,
+  macroPhraseKey,
+  type ConfigPreset,
+  type DictationMacro,
+  type MacroAction,
+  type ModelOption,
+} from '../../lib/settings';
+
+function newMacroId(): string {
+  return globalThis.crypto?.randomUUID?.() ?? `macro-${Date.now()}-${Math.random()}`;
+}
+
+const ACTION_OPTIONS: { value: string; label: string; action: MacroAction }[] = [
+  { value: 'open_history', label: 'Open history', action: { type: 'open_history' } },
+  { value: 'open_settings', label: 'Open Settings', action: { type: 'open_settings' } },
+  { value: 'copy_last_transcription', label: 'Copy last transcription', action: { type: 'copy_last_transcription' } },
+  { value: 'auto_paste_on', label: 'Turn on auto-paste', action: { type: 'set_auto_paste', enabled: true } },
+  { value: 'auto_paste_off', label: 'Turn off auto-paste', action: { type: 'set_auto_paste', enabled: false } },
+  { value: 'set_model', label: 'Switch model', action: { type: 'set_model', model: AVAILABLE_MODEL_OPTIONS[0].value } },
+];
+
+function actionValue(action: MacroAction): string {
+  if (action.type === 'set_auto_paste') return action.enabled ? 'auto_paste_on' : 'auto_paste_off';
+  return action.type;
+}
+
+const inputClass = 'min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface placeholder:text-on-surface-variant focus:outline-none focus:ring-2 focus:ring-primary';
+const selectClass = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-2 py-2 text-xs text-on-surface focus:outline-none focus:ring-2 focus:ring-primary';
+
+/**
+ * Spoken phrases that run an app action instead of being pasted. A phrase
+ * must be the whole dictation; case, punctuation, and hyphens are ignored.
+ * "Switch preset" is offered once a configuration preset exists.
+ */
+export function DictationMacrosEditor({ macros, presets, disabled = false, onChange }: {
+  macros: DictationMacro[];
+  presets: ConfigPreset[];
+  disabled?: boolean;
+  onChange: (macros: DictationMacro[]) => void;
+}) {
+  const updateMacro = (id: string, update: Partial<DictationMacro>) => {
+    onChange(macros.map((entry) => entry.id === id ? { ...entry, ...update } : entry));
+  };
+
+  const isDuplicate = (index: number) => {
+    const key = macroPhraseKey(macros[index].phrase);
+    return key !== '' && macros.slice(0, index).some((other) => macroPhraseKey(other.phrase) === key);
+  };
+
+  return (
+    <div className="space-y-3">
+      {macros.length > 0 && (
+        <ul className="space-y-2">
+          {macros.map((entry, index) => {
+            const label = entry.phrase || `macro ${index + 1}`;
+            const { action } = entry;
+            const presetMissing = action.type === 'apply_preset' && !presets.some((preset) => preset.id === action.preset);
+            return (
+              <li key={entry.id} className="space-y-1 rounded-xl border border-outline-variant/25 bg-surface-container-lowest p-3 shadow-sm">
+                <div className="flex items-center gap-2">
+                  <input
+                    type="text"
+                    value={entry.phrase}
+                    maxLength={DICTATION_MACRO_PHRASE_MAX}
+                    onChange={(event) => updateMacro(entry.id, { phrase: event.target.value })}
+                    aria-label={`Phrase for macro ${index + 1}`}
+                    placeholder="Spoken phrase"
+                    disabled={disabled}
+                    className={inputClass}
+                  />
+                  <select
+                    value={actionValue(entry.action)}
+                    onChange={(event) => {
+                      if (event.target.value === 'apply_preset' && presets.length > 0) {
+                        updateMacro(entry.id, { action: { type: 'apply_preset', preset: presets[0].id } });
+                        return;
+                      }
+                      const option = ACTION_OPTIONS.find((candidate) => candidate.value === event.target.value);
+                      if (option) updateMacro(entry.id, { action: option.action });
+                    }}
+                    aria-label={`Action for ${label}`}
+                    disabled={disabled}
+                    className={selectClass}
+                  >
+                    {ACTION_OPTIONS.map((option) => <option key={option.value} value={option.value}>{option.label}</option>)}
+                    {(presets.length > 0 || entry.action.type === 'apply_preset') && <option value="apply_preset">Switch preset</option>}
+                  </select>
+                  {entry.action.type === 'set_model' && (
+                    <select
+                      value={entry.action.model}
+                      onChange={(event) => updateMacro(entry.id, { action: { type: 'set_model', model: event.target.value as ModelOption } })}
+                      aria-label={`Model for ${label}`}
+                      disabled={disabled}
+                      className={selectClass}
+                    >
+                      {AVAILABLE_MODEL_OPTIONS.map((model) => <option key={model.value} value={model.value}>{model.label}</option>)}
+                    </select>
+                  )}
+                  {entry.action.type === 'apply_preset' && (
+                    <select
+                      value={entry.action.preset}
+                      onChange={(event) => updateMacro(entry.id, { action: { type: 'apply_preset', preset: event.target.value } })}
+                      aria-label={`Preset for ${label}`}
+                      disabled={disabled}
+                      className={selectClass}
+                    >
+                      {presetMissing && <option value={entry.action.preset} disabled>Deleted preset</option>}
+                      {presets.map((preset) => <option key={preset.id} value={preset.id}>{preset.name}</option>)}
+                    </select>
+                  )}
+                  <button
+                    type="button"
+                    onClick={() => onChange(macros.filter((candidate) => candidate.id !== entry.id))}
+                    aria-label={`Remove ${label}`}
+                    disabled={disabled}
+                    className="rounded-md px-2 py-1 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error focus:outline-none focus-visible:ring-2 focus-visible:ring-primary"
+                  >
+                    Remove
+                  </button>
+                </div>
+                {isDuplicate(index) && <p className="text-xs text-error">An earlier macro already uses this phrase; this one is ignored.</p>}
+                {presetMissing && <p className="text-xs text-error">That preset was deleted; pick another or this macro does nothing.</p>}
+              </li>
+            );
+          })}
+        </ul>
+      )}
+      <button
+        type="button"
+        onClick={() => onChange([...macros, { id: newMacroId(), phrase: '', action: { type: 'open_history' } }])}
+        disabled={disabled || macros.length >= DICTATION_MACROS_MAX}
+        className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+      >
+        Add Macro
+      </button>
+    </div>
+  );
+}
@@ -0,0 +1,138 @@
+import {
+  AVAILABLE_MODEL_OPTIONS,
+  LANGUAGE_OPTIONS,
+  PROFILE_KEY_OPTIONS,
+  type DictationProfile,
+  type DoubleTapKey,
+  type ModelOption,
+  type ProfileKey,
+  type RewriteTemplate,
+  type TransformKey,
+} from '../../lib/settings';
+import { Select } from '../ui/Select';
+import { OverrideSelect } from './AppOverridesEditor';
+
+const INHERIT = 'inherit';
+const NO_TEMPLATE = 'none';
+
+function newProfileId(): string {
+  return globalThis.crypto?.randomUUID?.() ?? `profile-${Date.now()}-${Math.random()}`;
+}
+
+/**
+ * Hold-to-talk profiles, each on its own key. Keys held by the dictation or
+ * transform hotkey, or by another profile, are not offered.
+ */
+export function DictationProfilesEditor({ profiles, rewriteTemplates, dictationKey, transformKey, disabled = false, onChange }: {
+  profiles: DictationProfile[];
+  rewriteTemplates: RewriteTemplate[];
+  dictationKey: DoubleTapKey;
+  transformKey: TransformKey | null;
+  disabled?: boolean;
+  onChange: (profiles: DictationProfile[]) => void;
+}) {
+  const reserved = new Set<ProfileKey>([dictationKey, ...(transformKey ? [transformKey] : [])]);
+  const freeKeys = (own?: ProfileKey) => PROFILE_KEY_OPTIONS.filter((option) =>
+    option.value === own || (!reserved.has(option.value) && !profiles.some((profile) => profile.hotkey === option.value)));
+  const nextKey = freeKeys()[0]?.value;
+
+  const updateProfile = (id: string, update: Partial<DictationProfile>) => {
+    onChange(profiles.map((profile) => profile.id === id ? { ...profile, ...update } : profile));
+  };
+
+  const addProfile = () => {
+    if (!nextKey) return;
+    onChange([...profiles, {
+      id: newProfileId(),
+      name: `Profile ${profiles.length + 1}`,
+      hotkey: nextKey,
+      modelOverride: null,
+      languageOverride: null,
+      autoPasteOverride: null,
+      cleanupOverride: null,
+      smartFormattingOverride: null,
+      rewriteTemplate: null,
+    }]);
+  };
+
+  return (
+    <div className="space-y-3">
+      {profiles.length > 0 && (
+        <ul className="space-y-3">
+          {profiles.map((profile) => {
+            const label = profile.name || 'Untitled profile';
+            const conflict = reserved.has(profile.hotkey);
+            return (
+              <li key={profile.id} className="space-y-2 rounded-xl border border-outline-variant/25 bg-surface-container-lowest p-3 shadow-sm">
+                <div className="flex items-center gap-2">
+                  <input
+                    type="text"
+                    value={profile.name}
+                    onChange={(event) => updateProfile(profile.id, { name: event.target.value })}
+                    aria-label="Profile name"
+                    placeholder="Profile name"
+                    disabled={disabled}
+                    className="min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface placeholder:text-on-surface-variant focus:outline-none focus:ring-2 focus:ring-primary"
+                  />
+                  <button
+                    type="button"
+                    onClick={() => onChange(profiles.filter((candidate) => candidate.id !== profile.id))}
+                    aria-label={`Remove ${label}`}
+                    disabled={disabled}
+                    className="rounded-md px-2 py-1 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error focus:outline-none focus-visible:ring-2 focus-visible:ring-primary"
+                  >
+                    Remove
+                  </button>
+                </div>
+                <div className="grid grid-cols-3 gap-2">
+                  <Select
+                    aria-label={`Hold key for ${label}`}
+                    value={profile.hotkey}
+                    onChange={(hotkey) => updateProfile(profile.id, { hotkey })}
+                    disabled={disabled}
+                    items={freeKeys(profile.hotkey)}
+                  />
+                  <Select
+                    aria-label={`Model for ${label}`}
+                    value={profile.modelOverride ?? INHERIT}
+                    onChange={(model) => updateProfile(profile.id, { modelOverride: model === INHERIT ? null : model as ModelOption })}
+                    disabled={disabled}
+                    items={[{ value: INHERIT, label: 'Global model' }, ...AVAILABLE_MODEL_OPTIONS.map((model) => ({ value: model.value, label: model.label }))]}
+                  />
+                  <Select
+                    aria-label={`Language for ${label}`}
+                    value={profile.languageOverride ?? INHERIT}
+                    onChange={(language) => updateProfile(profile.id, { languageOverride: language === INHERIT ? null : language })}
+                    disabled={disabled}
+                    items={[{ value: INHERIT, label: 'Global language' }, ...LANGUAGE_OPTIONS]}
+                  />
+                </div>
+                <div className="grid grid-cols-3 gap-2">
+                  <OverrideSelect label="Auto-paste" appLabel={label} value={profile.autoPasteOverride} onChange={(value) => updateProfile(profile.id, { autoPasteOverride: value })} />
+                  <OverrideSelect label="Cleanup" appLabel={label} value={profile.cleanupOverride} onChange={(value) => updateProfile(profile.id, { cleanupOverride: value })} />
+                  <OverrideSelect label="Smart formatting" appLabel={label} value={profile.smartFormattingOverride} onChange={(value) => updateProfile(profile.id, { smartFormattingOverride: value })} />
+                </div>
+                <Select
+                  aria-label={`Rewrite template for ${label}`}
+                  value={profile.rewriteTemplate && rewriteTemplates.some((template) => template.id === profile.rewriteTemplate) ? profile.rewriteTemplate : NO_TEMPLATE}
+                  onChange={(template) => updateProfile(profile.id, { rewriteTemplate: template === NO_TEMPLATE ? null : template })}
+                  disabled={disabled}
+                  items={[{ value: NO_TEMPLATE, label: 'No rewrite' }, ...rewriteTemplates.map((template) => ({ value: template.id, label: `Rewrite: ${template.name}` }))]}
+                />
+                {conflict && <p className="text-xs text-amber-600 dark:text-amber-400">This key is now used by another shortcut, so the profile is inactive. Pick a different key.</p>}
+              </li>
+            );
+          })}
+        </ul>
+      )}
+      <button
+        type="button"
+        onClick={addProfile}
+        disabled={disabled || !nextKey}
+        className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+      >
+        Add Profile
+      </button>
+    </div>
+  );
+}
@@ -0,0 +1,126 @@
+import { useEffect, useState } from 'react';
+import { setDownloadNetwork, testDownloadConnectivity, type EndpointCheck } from '../../lib/dictation';
+import type { DownloadNetworkSettings } from '../../lib/settings';
+
+const SOURCE_LABELS: Record<EndpointCheck['source'], string> = {
+  huggingFace: 'Hugging Face',
+  github: 'GitHub',
+};
+
+type TextField = 'huggingFaceMirror' | 'githubMirror' | 'proxy';
+type PauseField = 'pauseOnMetered' | 'pauseOnVpn';
+
+const FIELDS: { key: TextField; label: string; placeholder: string; description: string }[] = [
+  { key: 'huggingFaceMirror', label: 'Hugging Face mirror', placeholder: 'https://hf-mirror.com', description: 'Whisper, voice detection, and transform models.' },
+  { key: 'githubMirror', label: 'GitHub mirror', placeholder: 'https://ghproxy.example/https://github.com', description: 'Parakeet models.' },
+  { key: 'proxy', label: 'Proxy', placeholder: 'socks5://127.0.0.1:1080', description: 'Leave empty to use the system proxy, or enter none to connect directly.' },
+];
+
+const PAUSE_FIELDS: { key: PauseField; label: string }[] = [
+  { key: 'pauseOnMetered', label: 'Pause background downloads on metered connections (phone hotspots)' },
+  { key: 'pauseOnVpn', label: 'Pause background downloads while a VPN is connected' },
+];
+
+function describe(check: EndpointCheck): string {
+  if (check.reachable) return `reachable in ${check.latencyMs} ms`;
+  return check.error ?? 'unreachable';
+}
+
+/** Mirrors and proxy for model downloads. Fields commit on blur once Rust accepts them. */
+export function DownloadNetworkEditor({ value, disabled, onChange }: {
+  value: DownloadNetworkSettings;
+  disabled: boolean;
+  onChange: (value: DownloadNetworkSettings) => void;
+}) {
+  const [draft, setDraft] = useState(value);
+  const [error, setError] = useState<string | null>(null);
+  const [testing, setTesting] = useState(false);
+  const [checks, setChecks] = useState<EndpointCheck[] | null>(null);
+  useEffect(() => setDraft(value), [value]);
+
+  const commit = async (source: DownloadNetworkSettings = draft) => {
+    const next = {
+      huggingFaceMirror: source.huggingFaceMirror.trim(),
+      githubMirror: source.githubMirror.trim(),
+      proxy: source.proxy.trim(),
+      pauseOnMetered: source.pauseOnMetered,
+      pauseOnVpn: source.pauseOnVpn,
+    };
+    if (next.huggingFaceMirror === value.huggingFaceMirror && next.githubMirror === value.githubMirror && next.proxy === value.proxy
+      && next.pauseOnMetered === value.pauseOnMetered && next.pauseOnVpn === value.pauseOnVpn) return;
+    try {
+      await setDownloadNetwork(next);
+      setError(null);
+      setChecks(null);
+      onChange(next);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const test = async () => {
+    setTesting(true);
+    setError(null);
+    try {
+      setChecks(await testDownloadConnectivity());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setTesting(false);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      {FIELDS.map((field) => (
+        <label key={field.key} className="block">
+          <span className="text-xs text-on-surface-variant">{field.label}</span>
+          <input
+            type="text"
+            aria-label={field.label}
+            value={draft[field.key]}
+            placeholder={field.placeholder}
+            disabled={disabled}
+            spellCheck={false}
+            onChange={(event) => setDraft({ ...draft, [field.key]: event.target.value })}
+            onBlur={() => void commit()}
+            className="mt-1 w-full rounded-xl border border-outline-variant/40 bg-surface-container-lowest px-3 py-2 font-mono text-xs text-on-surface outline-none focus:border-primary focus:ring-1 focus:ring-primary disabled:opacity-50"
+          />
+          <span className="mt-1 block text-xs text-on-surface-variant">{field.description}</span>
+        </label>
+      ))}
+      {PAUSE_FIELDS.map((field) => (
+        <label key={field.key} className="flex items-center gap-2 text-xs text-on-surface-variant">
+          <input
+            type="checkbox"
+            checked={draft[field.key]}
+            onChange={(event) => {
+              const next = { ...draft, [field.key]: event.target.checked };
+              setDraft(next);
+              void commit(next);
+            }}
+          />
+          {field.label}
+        </label>
+      ))}
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+      <button
+        type="button"
+        onClick={() => void test()}
+        disabled={disabled || testing}
+        className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+      >
+        {testing ? 'Testing…' : 'Test Connection'}
+      </button>
+      {checks && (
+        <ul className="space-y-0.5 rounded-lg border border-outline-variant/30 bg-surface-container-lowest p-2 text-xs text-on-surface-variant">
+          {checks.map((check) => (
+            <li key={check.url} className={check.reachable ? 'text-primary' : 'text-error'}>
+              {SOURCE_LABELS[check.source]}{check.mirror ? ' mirror' : ''}: {describe(check)}
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,72 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import {
+  describePause,
+  getDownloadQueue,
+  removeQueuedDownload,
+  setDownloadQueuePaused,
+  type DownloadQueueStatus,
+} from '../../lib/downloadQueue';
+import { AVAILABLE_MODEL_OPTIONS } from '../../lib/settings';
+
+function modelLabel(modelName: string): string {
+  return AVAILABLE_MODEL_OPTIONS.find((model) => model.value === modelName)?.label ?? modelName;
+}
+
+/** Models downloading in the background, with pause and remove. Hidden while the queue is empty. */
+export function DownloadQueuePanel() {
+  const [status, setStatus] = useState<DownloadQueueStatus | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    getDownloadQueue().then((queue) => { if (!cancelled) setStatus(queue); }).catch(() => {});
+    listen<DownloadQueueStatus>('download-queue-changed', (event) => setStatus(event.payload))
+      .then((fn) => { if (cancelled) { fn(); } else { unlisten = fn; } })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const run = async (action: () => Promise<DownloadQueueStatus>) => {
+    try {
+      setStatus(await action());
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  if (!status?.active) return null;
+  const paused = status.paused !== null;
+  return (
+    <div className="space-y-2 rounded-lg border border-outline-variant/30 bg-surface-container-lowest p-3 text-xs text-on-surface-variant">
+      <div className="flex items-center gap-2">
+        <span className="font-medium text-on-surface">Background downloads</span>
+        {status.paused && <span>{describePause(status.paused)}</span>}
+        <button
+          type="button"
+          onClick={() => void run(() => setDownloadQueuePaused(status.paused !== 'user'))}
+          className="ml-auto underline hover:text-primary"
+        >
+          {status.paused === 'user' ? 'Resume' : 'Pause'}
+        </button>
+      </div>
+      <ul className="space-y-1">
+        <li>{modelLabel(status.active)} — {paused ? 'waiting' : 'downloading'}</li>
+        {status.pending.map((modelName) => (
+          <li key={modelName} className="flex items-center gap-2">
+            <span>{modelLabel(modelName)} — queued</span>
+            <button type="button" onClick={() => void run(() => removeQueuedDownload(modelName))} className="ml-auto underline hover:text-primary">
+              Remove
+            </button>
+          </li>
+        ))}
+      </ul>
+      {error && <p role="alert" className="text-error">{error}</p>}
+    </div>
+  );
+}
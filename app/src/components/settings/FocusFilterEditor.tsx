@@ -0,0 +1,73 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { FocusFilterSettings } from '../../lib/settings';
+
+interface FocusMode {
+  id: string;
+  name: string;
+}
+
+interface SystemStatus {
+  focusAvailable: boolean;
+  focusMode: string | null;
+  focusName: string | null;
+  screenLocked: boolean;
+}
+
+/** Focus-mode behavior plus the modes it applies to. An empty mode list means every Focus. */
+export function FocusFilterEditor({ value, onChange }: {
+  value: FocusFilterSettings;
+  onChange: (value: FocusFilterSettings) => void;
+}) {
+  const [modes, setModes] = useState<FocusMode[]>([]);
+  const [status, setStatus] = useState<SystemStatus | null>(null);
+  const update = (patch: Partial<FocusFilterSettings>) => onChange({ ...value, ...patch });
+
+  useEffect(() => {
+    let disposed = false;
+    invoke<FocusMode[]>('list_focus_modes')
+      .then((list) => { if (!disposed) setModes(list); })
+      .catch(() => { if (!disposed) setModes([]); });
+    invoke<SystemStatus>('get_system_status')
+      .then((next) => { if (!disposed) setStatus(next); })
+      .catch(() => {});
+    let unlisten: (() => void) | null = null;
+    listen<SystemStatus>('system-status-changed', (event) => setStatus(event.payload))
+      .then((fn) => { if (disposed) { fn(); } else { unlisten = fn; } });
+    return () => { disposed = true; unlisten?.(); };
+  }, []);
+
+  const toggleMode = (id: string) => update({
+    modes: value.modes.includes(id) ? value.modes.filter((mode) => mode !== id) : [...value.modes, id],
+  });
+
+  return (
+    <div className="space-y-2">
+      <label className="flex items-center gap-2 text-xs text-on-surface">
+        <input type="checkbox" checked={value.suppressNotifications} onChange={() => update({ suppressNotifications: !value.suppressNotifications })} className="accent-primary" />
+        Hold notifications during Focus
+      </label>
+      <label className="flex items-center gap-2 text-xs text-on-surface">
+        <input type="checkbox" checked={value.disableHotkey} onChange={() => update({ disableHotkey: !value.disableHotkey })} className="accent-primary" />
+        Ignore the dictation hotkey during Focus
+      </label>
+      {modes.length > 0 && (
+        <div className="ml-3 space-y-1 border-l border-outline-variant/30 pl-3" role="group" aria-label="Focus modes">
+          <p className="text-xs text-on-surface-variant">Apply to {value.modes.length === 0 ? 'every Focus, or only:' : 'only:'}</p>
+          {modes.map((mode) => (
+            <label key={mode.id} className="flex items-center gap-2 text-xs text-on-surface">
+              <input type="checkbox" checked={value.modes.includes(mode.id)} onChange={() => toggleMode(mode.id)} className="accent-primary" />
+              {mode.name}
+            </label>
+          ))}
+        </div>
+      )}
+      <p className="text-xs text-on-surface-variant">
+        {status && !status.focusAvailable
+          ? 'Focus can’t be detected. Grant Murmur Full Disk Access in System Settings › Privacy & Security to enable these options.'
+          : status?.focusMode ? `Focus on: ${status.focusName ?? status.focusMode}.` : 'No Focus is on.'}
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,23 @@
+import { useCallback, useEffect, useState } from 'react';
+import { getInstanceStatus, type InstanceStatus } from '../../lib/dictation';
+
+/** Shown when another running copy of Murmur (dev or release) holds the dictation hotkey, so hotkeys do nothing in this one. */
+export function HotkeyOwnerNotice() {
+  const [status, setStatus] = useState<InstanceStatus | null>(null);
+
+  const refresh = useCallback(() => {
+    getInstanceStatus().then(setStatus).catch((err) => console.error('Failed to read instance status:', err));
+  }, []);
+
+  useEffect(refresh, [refresh]);
+
+  const owner = status?.keyboardOwner;
+  if (!status || status.ownsKeyboardListener || !owner) return null;
+
+  return (
+    <div role="alert" className="flex items-center gap-2 rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-400">
+      <span>{owner.productName} (pid {owner.pid}) is also running and holds the hotkey. Quit it, then restart this copy to use the hotkey here.</span>
+      <button type="button" onClick={refresh} className="ml-auto shrink-0 underline">Check Again</button>
+    </div>
+  );
+}
@@ -0,0 +1,65 @@
+import { useState } from 'react';
+import { diagnoseHotkey, type HotkeyDiagnostic } from '../../lib/dictation';
+
+const CAPTURE_MS = 5000;
+
+function summary(result: HotkeyDiagnostic): string {
+  if (result.events.length === 0) {
+    return 'No key events arrived. Check Accessibility permission, then try again.';
+  }
+  if (result.events.some((event) => event.matchesHotkey)) {
+    return 'Your key was recognized.';
+  }
+  return 'Key events arrived, but none matched your key. The list below shows what your keyboard sends.';
+}
+
+/** Captures raw key events for a few seconds so a user can see whether their hotkey reaches Murmur. */
+export function HotkeyTester({ hotkey, disabled = false }: { hotkey: string; disabled?: boolean }) {
+  const [running, setRunning] = useState(false);
+  const [result, setResult] = useState<HotkeyDiagnostic | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async () => {
+    setRunning(true);
+    setError(null);
+    setResult(null);
+    try {
+      setResult(await diagnoseHotkey(hotkey, CAPTURE_MS));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <button
+        type="button"
+        onClick={run}
+        disabled={disabled || running}
+        className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+      >
+        {running ? 'Press your key now…' : 'Test Key'}
+      </button>
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+      {result && (
+        <div className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest p-2 text-xs text-on-surface-variant">
+          <p className="text-on-surface">{summary(result)}</p>
+          <p className="mt-1">Expecting {result.expectedKey ?? 'an unknown key'}{result.expectedScancode !== null && ` (code ${result.expectedScancode})`}.</p>
+          {result.events.length > 0 && (
+            <ul className="mt-1 max-h-32 space-y-0.5 overflow-y-auto font-mono text-[11px]">
+              {result.events.map((event, index) => (
+                <li key={index} className={event.matchesHotkey ? 'text-primary' : undefined}>
+                  +{event.elapsedMs}ms {event.kind} {event.key}
+                  {event.reportedKey !== event.key && ` (reported ${event.reportedKey})`}
+                  {event.scancode !== null && ` code ${event.scancode}`}
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,115 @@
+import { useEffect, useState } from 'react';
+import { calibrateHotkeyTiming, type TimingCalibration } from '../../lib/dictation';
+import { DEFAULT_SETTINGS, HOTKEY_TIMING_BOUNDS, type HotkeyTiming, type RecordingMode } from '../../lib/settings';
+
+const CALIBRATION_MS = 6000;
+
+const buttonClass = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50';
+
+function TimingSlider({ label, help, field, value, min, onCommit }: {
+  label: string;
+  help: string;
+  field: keyof HotkeyTiming;
+  value: number;
+  min?: number;
+  onCommit: (value: number) => void;
+}) {
+  const [draft, setDraft] = useState(value);
+  useEffect(() => setDraft(value), [value]);
+  const bounds = HOTKEY_TIMING_BOUNDS[field];
+  return (
+    <div>
+      <div className="mb-1 flex items-center justify-between">
+        <label className="text-xs text-on-surface-variant">{label}</label>
+        <span className="text-xs font-medium text-on-surface">{draft}ms</span>
+      </div>
+      <input
+        type="range"
+        min={min ?? bounds.min}
+        max={bounds.max}
+        step={10}
+        value={draft}
+        aria-label={label}
+        onChange={(event) => setDraft(Number(event.target.value))}
+        onPointerUp={() => onCommit(draft)}
+        className="h-1.5 w-full cursor-pointer appearance-none rounded-full bg-surface-container-highest accent-primary"
+      />
+      <p className="mt-1 text-xs text-on-surface-variant">{help}</p>
+    </div>
+  );
+}
+
+/** Tap, double-tap, and hold thresholds for the dictation hotkey, with a calibration that measures the user's own double-tap. */
+export function HotkeyTimingEditor({ hotkey, mode, value, disabled = false, onChange }: {
+  hotkey: string;
+  mode: RecordingMode;
+  value: HotkeyTiming;
+  disabled?: boolean;
+  onChange: (value: HotkeyTiming) => void;
+}) {
+  const [calibrating, setCalibrating] = useState(false);
+  const [calibration, setCalibration] = useState<TimingCalibration | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const taps = mode !== 'hold_down';
+
+  const calibrate = async () => {
+    setCalibrating(true);
+    setError(null);
+    setCalibration(null);
+    try {
+      setCalibration(await calibrateHotkeyTiming(hotkey, CALIBRATION_MS));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setCalibrating(false);
+    }
+  };
+
+  const apply = () => {
+    if (!calibration) return;
+    onChange(calibration.suggested);
+    setCalibration(null);
+  };
+
+  return (
+    <div className="space-y-3">
+      {taps && (
+        <>
+          <TimingSlider label="Longest Tap" field="maxTapMs" value={value.maxTapMs} help="Presses held longer than this are not taps." onCommit={(maxTapMs) => onChange({ ...value, maxTapMs, holdPromotionMs: Math.max(value.holdPromotionMs, maxTapMs) })} />
+          <TimingSlider label="Double-Tap Window" field="doubleTapWindowMs" value={value.doubleTapWindowMs} help="Lower this if fast typing triggers false double-taps." onCommit={(doubleTapWindowMs) => onChange({ ...value, doubleTapWindowMs })} />
+        </>
+      )}
+      {mode === 'both' && (
+        <TimingSlider label="Hold Delay" field="holdPromotionMs" value={value.holdPromotionMs} min={value.maxTapMs} help="How long a press lasts before it starts a hold-to-talk recording." onCommit={(holdPromotionMs) => onChange({ ...value, holdPromotionMs })} />
+      )}
+      {taps && (
+        <TimingSlider label="Double-Tap Cooldown" field="cooldownMs" value={value.cooldownMs} help="Taps right after a double-tap are ignored for this long." onCommit={(cooldownMs) => onChange({ ...value, cooldownMs })} />
+      )}
+      {mode !== 'double_tap' && (
+        <TimingSlider label="Hold Cooldown" field="holdCooldownMs" value={value.holdCooldownMs} help="Presses right after a hold ends are ignored for this long." onCommit={(holdCooldownMs) => onChange({ ...value, holdCooldownMs })} />
+      )}
+      <div className="flex gap-2">
+        {taps && (
+          <button type="button" onClick={calibrate} disabled={disabled || calibrating} className={buttonClass}>
+            {calibrating ? 'Double-tap your key a few times…' : 'Calibrate'}
+          </button>
+        )}
+        <button type="button" onClick={() => onChange(DEFAULT_SETTINGS.hotkeyTiming)} disabled={calibrating} className={buttonClass}>
+          Reset to Defaults
+        </button>
+      </div>
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+      {calibration && (
+        <div className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest p-2 text-xs text-on-surface-variant">
+          <p className="text-on-surface">
+            {calibration.taps} taps: longest held {calibration.longestTapMs}ms, longest gap {calibration.longestGapMs}ms.
+          </p>
+          <p className="mt-1">
+            Suggested longest tap {calibration.suggested.maxTapMs}ms and double-tap window {calibration.suggested.doubleTapWindowMs}ms.
+          </p>
+          <button type="button" onClick={apply} className="mt-2 underline hover:text-primary">Apply</button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,67 @@
+import { useState } from 'react';
+import { startKeyboardDiagnostics, type KeyboardDiagnosticsReport } from '../../lib/dictation';
+
+const RECORD_SECS = 15;
+
+const buttonClass = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50';
+
+function summary(report: KeyboardDiagnosticsReport): string {
+  const keyEvents = report.entries.filter((entry) => entry.event).length;
+  const emitted = report.entries.reduce((count, entry) => count + entry.emitted.length, 0);
+  const rejected = report.entries.reduce((count, entry) => count + entry.rejected.length, 0);
+  return `${keyEvents} key events, ${emitted} hotkey events sent, ${rejected} rejected.`;
+}
+
+/** Records the hotkey listener's view of the keyboard for a few seconds, as JSON a user can attach to a bug report. */
+export function KeyboardDiagnostics({ disabled = false }: { disabled?: boolean }) {
+  const [running, setRunning] = useState(false);
+  const [report, setReport] = useState<KeyboardDiagnosticsReport | null>(null);
+  const [copied, setCopied] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const record = async () => {
+    setRunning(true);
+    setError(null);
+    setReport(null);
+    try {
+      setReport(await startKeyboardDiagnostics(RECORD_SECS));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const copy = async () => {
+    if (!report) return;
+    try {
+      await navigator.clipboard.writeText(JSON.stringify(report, null, 2));
+      setCopied(true);
+      window.setTimeout(() => setCopied(false), 1600);
+    } catch (err) {
+      setError(`Could not copy report: ${String(err)}`);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex gap-2">
+        <button type="button" onClick={() => void record()} disabled={disabled || running} className={buttonClass}>
+          {running ? `Recording for ${RECORD_SECS} seconds…` : 'Record Keyboard Events'}
+        </button>
+        {report && (
+          <button type="button" onClick={() => void copy()} className={buttonClass}>
+            {copied ? 'Copied' : 'Copy Report'}
+          </button>
+        )}
+      </div>
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+      {report && (
+        <p className="text-xs text-on-surface">
+          {summary(report)}
+          {report.dropped > 0 && ` ${report.dropped} more were not kept.`}
+        </p>
+      )}
+    </div>
+  );
+}
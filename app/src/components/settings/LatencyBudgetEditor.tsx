@@ -0,0 +1,45 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { getLatencyBudgetStats, type ModelLatency } from '../../lib/dictation';
+import { AVAILABLE_MODEL_OPTIONS, LATENCY_BUDGET_OPTIONS, type LatencyBudgetSettings } from '../../lib/settings';
+import { Select } from '../ui/Select';
+
+function modelLabel(model: string): string {
+  return AVAILABLE_MODEL_OPTIONS.find((option) => option.value === model)?.label ?? model;
+}
+
+/** Realtime-factor budget for live takes, with each model's recent speed this session. */
+export function LatencyBudgetEditor({ value, disabled, onChange }: {
+  value: LatencyBudgetSettings;
+  disabled: boolean;
+  onChange: (value: LatencyBudgetSettings) => void;
+}) {
+  const [stats, setStats] = useState<ModelLatency[]>([]);
+
+  useEffect(() => {
+    const refresh = () => getLatencyBudgetStats().then(setStats).catch((err) => console.error('Failed to read latency budget stats:', err));
+    refresh();
+    // A notice clears the slow model's window, so re-read rather than patch.
+    const unlisten = listen('latency-budget-exceeded', refresh);
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
+  return (
+    <div className="space-y-2">
+      <label className="block text-xs font-medium text-on-surface">Results Within</label>
+      <Select
+        aria-label="Latency budget"
+        value={String(value.maxRtf)}
+        onChange={(maxRtf) => onChange({ ...value, maxRtf: Number(maxRtf) })}
+        disabled={disabled}
+        items={LATENCY_BUDGET_OPTIONS.map((option) => ({ value: String(option.value), label: option.label }))}
+      />
+      <p className="text-xs text-on-surface-variant">Measured from when transcription starts, against the speech in the recording. Recordings under a second aren't counted.</p>
+      {stats.length > 0 && (
+        <p className="text-xs text-on-surface-variant">
+          Recently: {stats.map((model) => `${modelLabel(model.model)} ${model.realtimeFactor.toFixed(2)}×`).join(', ')}.
+        </p>
+      )}
+    </div>
+  );
+}
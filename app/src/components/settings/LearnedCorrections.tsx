@@ -0,0 +1,42 @@
+import { useCallback, useEffect, useState } from 'react';
+import { clearLearnedCorrections, getLearnedCorrections, type LearnedCorrection } from '../../lib/dictation';
+
+const SHOWN = 8;
+
+/** The corrections learned from history edits, most frequent first, with a way to forget them. */
+export function LearnedCorrections({ active }: { active: boolean }) {
+  const [corrections, setCorrections] = useState<LearnedCorrection[]>([]);
+
+  const refresh = useCallback(() => {
+    getLearnedCorrections().then((list) => setCorrections(Array.isArray(list) ? list : [])).catch((err) => console.error('Failed to read learned corrections:', err));
+  }, []);
+
+  useEffect(() => {
+    if (active) refresh();
+  }, [active, refresh]);
+
+  const handleClear = () => {
+    if (!window.confirm('Forget every correction learned from history?')) return;
+    clearLearnedCorrections().then(refresh).catch((err) => console.error('Failed to clear learned corrections:', err));
+  };
+
+  if (corrections.length === 0) {
+    return <p className="text-xs text-on-surface-variant">Nothing learned yet. Edit a history entry to correct a misheard word.</p>;
+  }
+
+  return (
+    <div className="space-y-2">
+      <ul className="space-y-1">
+        {corrections.slice(0, SHOWN).map((correction) => (
+          <li key={`${correction.heard}\u0000${correction.written}`} className="flex items-center gap-2 text-xs">
+            <span className="text-on-surface-variant line-through">{correction.heard}</span>
+            <span className="text-on-surface-variant">→</span>
+            <span className="font-medium text-on-surface">{correction.written}</span>
+            <span className="ml-auto text-on-surface-variant">{correction.count}×{correction.inPrompt ? ', in prompt' : ''}</span>
+          </li>
+        ))}
+      </ul>
+      <button type="button" onClick={handleClear} className="text-xs font-medium text-on-surface-variant underline hover:text-primary">Forget All</button>
+    </div>
+  );
+}
@@ -0,0 +1,102 @@
+import { useEffect, useRef, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { startMicTest, stopMicTest, type MicTestInfo, type MicTestLevel, type MicTestSummary } from '../../lib/dictation';
+
+/** The meter spans -60 dBFS to full scale. */
+const FLOOR_DB = -60;
+
+function meterPercent(level: number): number {
+  if (level <= 0) return 0;
+  const db = 20 * Math.log10(level);
+  return Math.min(100, Math.max(0, ((db - FLOOR_DB) / -FLOOR_DB) * 100));
+}
+
+function verdict(summary: MicTestSummary): string {
+  if (summary.heardSpeech) return 'Your voice came through.';
+  if (summary.maxPeak === 0) return 'The microphone sent only silence. Check that it is not muted and that Murmur has microphone permission.';
+  return 'The level never reached speech. Speak closer to the microphone, raise its input volume, or pick another device.';
+}
+
+/** Meters the selected microphone without recording, so a user can see whether Murmur hears them. */
+export function MicTester({ deviceName, disabled = false }: { deviceName: string; disabled?: boolean }) {
+  const [info, setInfo] = useState<MicTestInfo | null>(null);
+  const [level, setLevel] = useState<MicTestLevel | null>(null);
+  const [summary, setSummary] = useState<MicTestSummary | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const running = useRef(false);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    listen<MicTestLevel>('mic-test-level', (event) => setLevel(event.payload)).then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+      // Leaving Settings, or switching devices, closes the test's stream.
+      if (running.current) {
+        running.current = false;
+        stopMicTest().catch(() => {});
+      }
+    };
+  }, [deviceName]);
+
+  const start = async () => {
+    setError(null);
+    setSummary(null);
+    setLevel(null);
+    try {
+      setInfo(await startMicTest(deviceName));
+      running.current = true;
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const stop = async () => {
+    running.current = false;
+    setInfo(null);
+    try {
+      setSummary(await stopMicTest());
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <button
+        type="button"
+        onClick={() => void (info ? stop() : start())}
+        disabled={disabled && !info}
+        className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+      >
+        {info ? 'Stop Test' : 'Test Microphone'}
+      </button>
+      {info && (
+        <div className="space-y-1">
+          <div className="h-1.5 overflow-hidden rounded-full bg-surface-container-highest">
+            <div
+              role="meter"
+              aria-label="Microphone level"
+              aria-valuenow={Math.round(meterPercent(level?.rms ?? 0))}
+              aria-valuemin={0}
+              aria-valuemax={100}
+              className={`h-full rounded-full transition-all duration-75 ${level?.heardSpeech ? 'bg-primary' : 'bg-amber-500'}`}
+              style={{ width: `${meterPercent(level?.rms ?? 0)}%` }}
+            />
+          </div>
+          <p className="text-xs text-on-surface-variant">
+            {info.deviceName} · {info.sampleRate} Hz · {info.channels === 1 ? 'mono' : `${info.channels} channels`}
+            {level && ` · peak ${Math.round(meterPercent(level.peak))}%`}
+          </p>
+          <p className="text-xs text-on-surface-variant">{level?.heardSpeech ? 'Speech detected.' : 'Say a few words. Nothing is recorded.'}</p>
+        </div>
+      )}
+      {summary && <p className="text-xs text-on-surface">{verdict(summary)}</p>}
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,53 @@
+import { Select } from '../ui/Select';
+
+const iconButtonClass = 'rounded px-1.5 py-0.5 text-xs text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-40';
+
+/** Ordered fallback microphones: a recording uses the first connected one and moves down the list if its device disconnects. */
+export function MicrophonePreferencesEditor({
+  preferences,
+  devices,
+  onChange,
+}: {
+  preferences: string[];
+  devices: string[];
+  onChange: (preferences: string[]) => void;
+}) {
+  const addable = devices.filter((name) => !preferences.includes(name));
+
+  const move = (index: number, offset: number) => {
+    const next = [...preferences];
+    [next[index], next[index + offset]] = [next[index + offset], next[index]];
+    onChange(next);
+  };
+
+  return (
+    <div className="space-y-2">
+      <p className="text-xs text-on-surface-variant">
+        Tried in order when the selected microphone is missing or disconnects mid-recording, then any other input.
+      </p>
+      {preferences.length > 0 && (
+        <ol className="space-y-1">
+          {preferences.map((name, index) => (
+            <li key={name} className="flex items-center gap-2 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-1.5 text-xs text-on-surface">
+              <span className="w-4 text-on-surface-variant">{index + 1}.</span>
+              <span className="min-w-0 flex-1 truncate">{name}</span>
+              {!devices.includes(name) && <span className="text-on-surface-variant">Not connected</span>}
+              <button type="button" className={iconButtonClass} disabled={index === 0} onClick={() => move(index, -1)} aria-label={`Move ${name} up`}>↑</button>
+              <button type="button" className={iconButtonClass} disabled={index === preferences.length - 1} onClick={() => move(index, 1)} aria-label={`Move ${name} down`}>↓</button>
+              <button type="button" className={`${iconButtonClass} hover:text-error`} onClick={() => onChange(preferences.filter((other) => other !== name))} aria-label={`Remove ${name}`}>✕</button>
+            </li>
+          ))}
+        </ol>
+      )}
+      {addable.length > 0 && (
+        <Select
+          value=""
+          onChange={(name) => onChange([...preferences, name])}
+          items={addable.map((name) => ({ value: name, label: name }))}
+          placeholder="Add a fallback microphone…"
+          aria-label="Add a fallback microphone"
+        />
+      )}
+    </div>
+  );
+}
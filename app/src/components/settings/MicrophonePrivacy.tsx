@@ -0,0 +1,65 @@
+import { useEffect, useState } from 'react';
+import { getRecordingPrivacyReport, killMicrophone, type RecordingPrivacyReport } from '../../lib/dictation';
+
+const REFRESH_MS = 2000;
+
+function describe(report: RecordingPrivacyReport): string {
+  const uses = [
+    report.recording && 'dictation',
+    report.wakeWordListening && 'the wake word',
+    report.ambientListening && 'ambient mode',
+    report.meetingListening && 'a meeting',
+    report.preRollListening && 'pre-roll',
+  ].filter(Boolean);
+  if (uses.length > 0) return `Murmur has the microphone open for ${uses.join(', ')}.`;
+  if (report.systemIndicator) return 'Murmur is not using the microphone. Another app is.';
+  return 'Murmur is not using the microphone.';
+}
+
+/** Who holds the microphone, and a button that closes every stream Murmur has open. */
+export function MicrophonePrivacy({ onKilled }: { onKilled: () => void }) {
+  const [report, setReport] = useState<RecordingPrivacyReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    const refresh = () => {
+      getRecordingPrivacyReport()
+        .then((next) => { if (!cancelled) setReport(next); })
+        .catch(() => {});
+    };
+    refresh();
+    const id = setInterval(refresh, REFRESH_MS);
+    return () => { cancelled = true; clearInterval(id); };
+  }, []);
+
+  const kill = async () => {
+    setError(null);
+    try {
+      setReport(await killMicrophone());
+      onKilled();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  if (!report) return null;
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        <span className={`h-2 w-2 shrink-0 rounded-full ${report.microphoneInUse ? 'bg-orange-500' : 'bg-outline-variant'}`} aria-hidden="true" />
+        <p className="text-xs text-on-surface-variant">{describe(report)}</p>
+      </div>
+      <button
+        type="button"
+        onClick={() => void kill()}
+        disabled={!report.microphoneInUse}
+        className="w-full rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-error disabled:cursor-not-allowed disabled:opacity-50"
+      >
+        Turn Off Microphone
+      </button>
+      <p className="text-xs text-on-surface-variant">Closes every microphone stream right away. A dictation in progress is discarded, a meeting ends, and the wake word, ambient mode, and pre-roll turn off.</p>
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
@@ -7,7 +7,9 @@ import {
   BenchmarkPreset,
   BenchmarkProgress,
   BenchmarkReport,
+  BenchmarkThreadRecommendation,
   MAX_SAVED_BENCHMARK_REPORTS,
+  SelfTestReport,
   addBenchmarkReport,
   cancelBenchmark,
   clearBenchmarkReports,
@@ -15,6 +17,7 @@ import {
   getBenchmarkModels,
   loadBenchmarkReports,
   openBenchmarkOutputFolder,
+  runAccuracySelfTest,
   runBenchmark,
   saveBenchmarkReport,
   saveBenchmarkReports,
@@ -51,6 +54,38 @@ function modelLabel(report: BenchmarkReport, modelName: string | null): string {
   return report.results.find((result) => result.modelName === modelName)?.label ?? modelName;
 }
 
+/** The `whisperDecoding.threads` value for a recommended count: automatic
+ * when that is one per performance core, so it follows the hardware. */
+function threadsSetting(recommendation: BenchmarkThreadRecommendation): number {
+  return recommendation.threads === recommendation.performanceCores ? 0 : recommendation.threads;
+}
+
+function ThreadRecommendationRow({ report, recommendation, settings, onUpdateSettings }: {
+  report: BenchmarkReport;
+  recommendation: BenchmarkThreadRecommendation;
+  settings: Settings;
+  onUpdateSettings: (updates: Partial<Settings>) => void;
+}) {
+  const value = threadsSetting(recommendation);
+  const inUse = settings.whisperDecoding.threads === value;
+  const timings = recommendation.timings.map((timing) => `${timing.threads} threads: ${milliseconds(timing.warmMedianMs)}`).join(', ');
+  return (
+    <div className="flex items-center gap-3">
+      <p className="min-w-0 flex-1 text-xs text-on-surface-variant" title={timings}>
+        {modelLabel(report, recommendation.modelName)} decoded fastest with <span className="font-semibold text-on-surface">{recommendation.threads} threads</span> ({recommendation.performanceCores} performance cores).
+      </p>
+      <button
+        type="button"
+        disabled={inUse}
+        onClick={() => onUpdateSettings({ whisperDecoding: { ...settings.whisperDecoding, threads: value } })}
+        className="shrink-0 rounded-md px-2 py-1 text-xs font-medium text-primary hover:bg-surface-container disabled:cursor-not-allowed disabled:text-on-surface-variant disabled:opacity-60"
+      >
+        {inUse ? 'In use' : 'Use for dictation'}
+      </button>
+    </div>
+  );
+}
+
 type LatencyResult = BenchmarkModelResult & { warmMedianMs: number; warmP95Ms: number };
 type AccuracyResult = BenchmarkModelResult & { normalizedWordErrorRate: number };
 
@@ -153,6 +188,8 @@ export function PerformanceLab({ status, settings, onUpdateSettings }: {
   const [downloading, setDownloading] = useState<string | null>(null);
   const [downloadProgress, setDownloadProgress] = useState<ModelDownloadProgress | null>(null);
   const [fileTranscribing, setFileTranscribing] = useState(false);
+  const [selfTest, setSelfTest] = useState<SelfTestReport | null>(null);
+  const [selfTesting, setSelfTesting] = useState(false);
   const mounted = useRef(true);
   const runningRef = useRef(false);
 
@@ -263,6 +300,20 @@ export function PerformanceLab({ status, settings, onUpdateSettings }: {
     }
   };
 
+  const handleSelfTest = async () => {
+    setError(null);
+    setSelfTest(null);
+    setSelfTesting(true);
+    try {
+      const next = await runAccuracySelfTest();
+      if (mounted.current) setSelfTest(next);
+    } catch (reason) {
+      if (mounted.current) setError(String(reason));
+    } finally {
+      if (mounted.current) setSelfTesting(false);
+    }
+  };
+
   const handleCancel = async () => {
     await cancelBenchmark();
   };
@@ -465,6 +516,43 @@ export function PerformanceLab({ status, settings, onUpdateSettings }: {
         )}
       </section>
 
+      <section className="border-t border-outline-variant/30 dark:border-outline-variant/30 pt-5">
+        <div className="flex items-end justify-between gap-4">
+          <div>
+            <h3 className="text-sm font-semibold text-on-surface">Accuracy Self-Test</h3>
+            <p className="mt-0.5 text-xs text-on-surface-variant dark:text-on-surface-variant">
+              Transcribes three bundled clips with the selected model and scores them against their known text. A failing score usually means a damaged download.
+            </p>
+          </div>
+          <button
+            type="button"
+            onClick={() => void handleSelfTest()}
+            disabled={selfTesting || running || status !== 'idle' || fileTranscribing}
+            className="shrink-0 px-2.5 py-1.5 text-xs font-medium border border-outline-variant/30 dark:border-outline-variant/30 rounded-md text-on-surface dark:text-on-surface-variant hover:bg-surface-container-low dark:hover:bg-primary/80 disabled:opacity-50"
+          >
+            {selfTesting ? 'Testing...' : 'Run Self-Test'}
+          </button>
+        </div>
+        {selfTest && (
+          <div className="mt-3 rounded-lg border border-outline-variant/30 bg-surface-container-low p-3 text-[11px] leading-relaxed text-on-surface-variant">
+            <p className={`font-medium ${selfTest.passed ? 'text-emerald-600 dark:text-emerald-400' : 'text-red-600 dark:text-red-400'}`}>
+              {selfTest.label}: {selfTest.passed ? 'Passed' : `Failed (normalized WER above ${percentage(selfTest.maxNormalizedWordErrorRate)})`}
+            </p>
+            <p className="mt-1 tabular-nums">
+              WER {percentage(selfTest.wordErrorRate)} / normalized {percentage(selfTest.normalizedWordErrorRate)} / CER {percentage(selfTest.characterErrorRate)} / {milliseconds(selfTest.elapsedMs)}
+            </p>
+            <ul className="mt-2 space-y-1">
+              {selfTest.clips.map((clip) => (
+                <li key={clip.fixtureId} title={clip.transcript}>
+                  <span className="text-on-surface">{clip.label}</span>
+                  <span className="tabular-nums">: WER {percentage(clip.wordErrorRate)}, CER {percentage(clip.characterErrorRate)}</span>
+                </li>
+              ))}
+            </ul>
+          </div>
+        )}
+      </section>
+
       <section className="border-t border-outline-variant/30 dark:border-outline-variant/30 pt-5">
         <h3 className="text-sm font-semibold text-on-surface">Report export</h3>
         <p className="mt-0.5 text-xs text-on-surface-variant dark:text-on-surface-variant">
@@ -580,6 +668,15 @@ export function PerformanceLab({ status, settings, onUpdateSettings }: {
             ))}
           </div>
 
+          {report.recommendations.threads && (
+            <ThreadRecommendationRow
+              report={report}
+              recommendation={report.recommendations.threads}
+              settings={settings}
+              onUpdateSettings={onUpdateSettings}
+            />
+          )}
+
           <div className="space-y-4 border-b border-outline-variant/30 dark:border-outline-variant/30 pb-4">
             <LatencyChart report={report} />
             <AccuracyChart report={report} />
@@ -0,0 +1,64 @@
+import { useEffect, useState } from 'react';
+import { Select } from '../ui/Select';
+import { AVAILABLE_MODEL_OPTIONS, IDLE_TIMEOUT_OPTIONS, type PowerProfile, type PowerProfilesSettings } from '../../lib/settings';
+
+function ProfileEditor({ title, value, disabled, onChange }: {
+  title: string;
+  value: PowerProfile;
+  disabled: boolean;
+  onChange: (value: PowerProfile) => void;
+}) {
+  const [vadDraft, setVadDraft] = useState(value.vadSensitivity);
+  useEffect(() => setVadDraft(value.vadSensitivity), [value.vadSensitivity]);
+  return (
+    <div className="space-y-2" role="group" aria-label={title}>
+      <p className="text-xs font-medium text-on-surface">{title}</p>
+      <Select
+        aria-label={`${title} model`}
+        value={value.model}
+        onChange={(model) => onChange({ ...value, model })}
+        disabled={disabled}
+        items={AVAILABLE_MODEL_OPTIONS.map((model) => ({ value: model.value, label: `${model.label} (${model.size})` }))}
+      />
+      <div>
+        <div className="mb-1 flex items-center justify-between">
+          <label className="text-xs text-on-surface-variant">VAD sensitivity</label>
+          <span className="text-xs font-medium text-on-surface">{vadDraft}%</span>
+        </div>
+        <input
+          type="range"
+          min={0}
+          max={100}
+          step={5}
+          value={vadDraft}
+          aria-label={`${title} VAD sensitivity`}
+          onChange={(event) => setVadDraft(Number(event.target.value))}
+          onPointerUp={() => onChange({ ...value, vadSensitivity: vadDraft })}
+          className="h-1.5 w-full cursor-pointer appearance-none rounded-full bg-surface-container-highest accent-primary"
+        />
+      </div>
+      <label className="block text-xs text-on-surface-variant">Release model after inactivity</label>
+      <Select
+        aria-label={`${title} release model after inactivity`}
+        value={String(value.idleTimeoutMinutes)}
+        onChange={(idle) => onChange({ ...value, idleTimeoutMinutes: Number(idle) })}
+        disabled={disabled}
+        items={IDLE_TIMEOUT_OPTIONS.map((option) => ({ value: String(option.value), label: option.label }))}
+      />
+    </div>
+  );
+}
+
+/** Model, VAD sensitivity, and idle release for the adapter and for battery. */
+export function PowerProfilesEditor({ value, disabled = false, onChange }: {
+  value: PowerProfilesSettings;
+  disabled?: boolean;
+  onChange: (value: PowerProfilesSettings) => void;
+}) {
+  return (
+    <div className="grid grid-cols-2 gap-3">
+      <ProfileEditor title="On Power Adapter" value={value.ac} disabled={disabled} onChange={(ac) => onChange({ ...value, ac })} />
+      <ProfileEditor title="On Battery" value={value.battery} disabled={disabled} onChange={(battery) => onChange({ ...value, battery })} />
+    </div>
+  );
+}
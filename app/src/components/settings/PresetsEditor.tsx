@@ -0,0 +1,130 @@
+import { useState } from 'react';
+import { capturePreset, matchingPreset, presetUpdates, removePreset, savePreset, type PresetDraft } from '../../lib/presets';
+import { CONFIG_PRESET_NAME_MAX, CONFIG_PRESETS_MAX, type ConfigPreset, type Settings } from '../../lib/settings';
+
+function newPresetId(): string {
+  return globalThis.crypto?.randomUUID?.() ?? `preset-${Date.now()}-${Math.random()}`;
+}
+
+const inputClass = 'min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface placeholder:text-on-surface-variant focus:outline-none focus:ring-2 focus:ring-primary';
+const smallButtonClass = 'rounded-md px-2 py-1 text-xs text-on-surface-variant hover:bg-surface-container hover:text-primary focus:outline-none focus-visible:ring-2 focus-visible:ring-primary disabled:cursor-not-allowed disabled:opacity-50';
+const buttonClass = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50';
+
+/**
+ * Named presets of the model, language, post-processing, and delivery
+ * settings. Saving or renaming checks the preset hasn't been changed
+ * elsewhere (the sync folder, another window) since it was shown here.
+ */
+export function PresetsEditor({ settings, disabled = false, onUpdateSettings }: {
+  settings: Settings;
+  disabled?: boolean;
+  onUpdateSettings: (updates: Partial<Settings>) => void;
+}) {
+  const presets = settings.configPresets;
+  const [newName, setNewName] = useState('');
+  // The preset being renamed, with the revision the rename started from.
+  const [renaming, setRenaming] = useState<{ id: string; name: string; baseUpdatedAt: number } | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const active = matchingPreset(settings, presets);
+
+  const save = (draft: PresetDraft) => {
+    const result = savePreset(presets, draft, Date.now());
+    if (!result.ok) {
+      setError(result.error);
+      return false;
+    }
+    setError(null);
+    onUpdateSettings({ configPresets: result.presets });
+    return true;
+  };
+
+  const addPreset = () => {
+    if (save({ id: newPresetId(), name: newName, settings: capturePreset(settings), baseUpdatedAt: null })) {
+      setNewName('');
+    }
+  };
+
+  const overwrite = (preset: ConfigPreset) => {
+    save({ id: preset.id, name: preset.name, settings: capturePreset(settings), baseUpdatedAt: preset.updatedAt });
+  };
+
+  const commitRename = () => {
+    if (!renaming) return;
+    const stored = presets.find((preset) => preset.id === renaming.id);
+    if (save({ id: renaming.id, name: renaming.name, settings: stored?.settings ?? {}, baseUpdatedAt: renaming.baseUpdatedAt })) {
+      setRenaming(null);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      {presets.length > 0 && (
+        <ul className="space-y-2">
+          {presets.map((preset) => (
+            <li key={preset.id} className="flex items-center gap-2 rounded-xl border border-outline-variant/25 bg-surface-container-lowest p-2.5 shadow-sm">
+              {renaming?.id === preset.id ? (
+                <input
+                  type="text"
+                  value={renaming.name}
+                  maxLength={CONFIG_PRESET_NAME_MAX}
+                  onChange={(event) => setRenaming({ ...renaming, name: event.target.value })}
+                  onKeyDown={(event) => {
+                    if (event.key === 'Enter') commitRename();
+                    if (event.key === 'Escape') setRenaming(null);
+                  }}
+                  aria-label={`New name for ${preset.name}`}
+                  disabled={disabled}
+                  className={inputClass}
+                  autoFocus
+                />
+              ) : (
+                <p className="min-w-0 flex-1 truncate text-xs text-on-surface">
+                  {preset.name}
+                  {active?.id === preset.id && <span className="ml-2 text-on-surface-variant">Active</span>}
+                </p>
+              )}
+              {renaming?.id === preset.id ? (
+                <>
+                  <button type="button" onClick={commitRename} disabled={disabled} className={smallButtonClass}>Save</button>
+                  <button type="button" onClick={() => setRenaming(null)} className={smallButtonClass}>Cancel</button>
+                </>
+              ) : (
+                <>
+                  <button type="button" onClick={() => onUpdateSettings(presetUpdates(settings, preset))} disabled={disabled || active?.id === preset.id} aria-label={`Apply ${preset.name}`} className={smallButtonClass}>Apply</button>
+                  <button type="button" onClick={() => overwrite(preset)} disabled={disabled} aria-label={`Update ${preset.name} with the current settings`} className={smallButtonClass}>Update</button>
+                  <button type="button" onClick={() => setRenaming({ id: preset.id, name: preset.name, baseUpdatedAt: preset.updatedAt })} disabled={disabled} aria-label={`Rename ${preset.name}`} className={smallButtonClass}>Rename</button>
+                  <button
+                    type="button"
+                    onClick={() => onUpdateSettings({ configPresets: removePreset(presets, preset.id) })}
+                    disabled={disabled}
+                    aria-label={`Delete ${preset.name}`}
+                    className="rounded-md px-2 py-1 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error focus:outline-none focus-visible:ring-2 focus-visible:ring-primary disabled:cursor-not-allowed disabled:opacity-50"
+                  >
+                    Delete
+                  </button>
+                </>
+              )}
+            </li>
+          ))}
+        </ul>
+      )}
+      <div className="flex items-center gap-2">
+        <input
+          type="text"
+          value={newName}
+          maxLength={CONFIG_PRESET_NAME_MAX}
+          onChange={(event) => setNewName(event.target.value)}
+          onKeyDown={(event) => { if (event.key === 'Enter') addPreset(); }}
+          aria-label="New preset name"
+          placeholder="Preset name, such as Meetings"
+          disabled={disabled}
+          className={inputClass}
+        />
+        <button type="button" onClick={addPreset} disabled={disabled || !newName.trim() || presets.length >= CONFIG_PRESETS_MAX} className={buttonClass}>
+          Save Current Settings
+        </button>
+      </div>
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,52 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { QuietHoursSettings } from '../../lib/settings';
+
+interface QuietHoursStatus {
+  enabled: boolean;
+  active: boolean;
+}
+
+const timeInputClass = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-2 py-1 text-xs text-on-surface disabled:opacity-50';
+
+/** The daily quiet-hours window, and whether it is in effect right now. */
+export function QuietHoursEditor({ value, onChange }: {
+  value: QuietHoursSettings;
+  onChange: (value: QuietHoursSettings) => void;
+}) {
+  const [status, setStatus] = useState<QuietHoursStatus | null>(null);
+  const update = (patch: Partial<QuietHoursSettings>) => onChange({ ...value, ...patch });
+
+  useEffect(() => {
+    let disposed = false;
+    invoke<QuietHoursStatus>('get_quiet_hours_status')
+      .then((next) => { if (!disposed) setStatus(next); })
+      .catch(() => {});
+    let unlisten: (() => void) | null = null;
+    listen<QuietHoursStatus>('quiet-hours-changed', (event) => setStatus(event.payload))
+      .then((fn) => { if (disposed) { fn(); } else { unlisten = fn; } });
+    return () => { disposed = true; unlisten?.(); };
+  }, []);
+
+  const empty = value.start === value.end;
+
+  return (
+    <div className="space-y-2">
+      <label className="flex items-center gap-2 text-xs text-on-surface">
+        <input type="checkbox" checked={value.enabled} onChange={() => update({ enabled: !value.enabled })} className="accent-primary" />
+        Ignore the hotkeys and wake word every day from
+        <input type="time" aria-label="Quiet hours start" value={value.start} disabled={!value.enabled} onChange={(event) => event.target.value && update({ start: event.target.value })} className={timeInputClass} />
+        to
+        <input type="time" aria-label="Quiet hours end" value={value.end} disabled={!value.enabled} onChange={(event) => event.target.value && update({ end: event.target.value })} className={timeInputClass} />
+      </label>
+      <p className="text-xs text-on-surface-variant">
+        {value.enabled && empty
+          ? 'Pick an end time different from the start.'
+          : status?.active
+            ? 'Quiet hours are on now. Recordings started from this window or the overlay still work.'
+            : 'A start after the end runs overnight. Recordings started from this window or the overlay still work.'}
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,65 @@
+import { useEffect, useState } from 'react';
+import type { RedactionSettings } from '../../lib/settings';
+
+const MODE_OPTIONS: { value: RedactionSettings['mode']; label: string }[] = [
+  { value: 'mask', label: 'Mask with ***' },
+  { value: 'remove', label: 'Remove' },
+];
+
+const toLines = (items: string[]) => items.join('\n');
+const fromLines = (text: string) => text.split('\n').map((line) => line.trim()).filter((line) => line !== '');
+
+/** One entry per line; committed on blur so typing a pattern never configures a half-written expression. */
+function LineListField({ label, value, placeholder, description, disabled, onCommit }: {
+  label: string;
+  value: string[];
+  placeholder: string;
+  description: string;
+  disabled: boolean;
+  onCommit: (value: string[]) => void;
+}) {
+  const [draft, setDraft] = useState(toLines(value));
+  useEffect(() => setDraft(toLines(value)), [value]);
+  return (
+    <label className="block">
+      <span className="text-xs text-on-surface-variant">{label}</span>
+      <textarea
+        aria-label={label}
+        value={draft}
+        placeholder={placeholder}
+        disabled={disabled}
+        spellCheck={false}
+        onChange={(event) => setDraft(event.target.value)}
+        onBlur={() => {
+          const next = fromLines(draft);
+          if (toLines(next) !== toLines(value)) onCommit(next);
+        }}
+        className="mt-1 min-h-20 w-full resize-y rounded-xl border border-outline-variant/40 bg-surface-container-lowest px-3 py-2 font-mono text-xs text-on-surface outline-none focus:border-primary focus:ring-1 focus:ring-primary disabled:opacity-50"
+      />
+      <span className="mt-1 block text-xs text-on-surface-variant">{description}</span>
+    </label>
+  );
+}
+
+export function RedactionEditor({ value, disabled, onChange }: {
+  value: RedactionSettings;
+  disabled: boolean;
+  onChange: (value: RedactionSettings) => void;
+}) {
+  const update = (patch: Partial<RedactionSettings>) => onChange({ ...value, ...patch });
+  return (
+    <div className="ml-3 space-y-3 border-l border-outline-variant/30 pl-3">
+      <div className="flex gap-2" role="group" aria-label="Redaction mode">
+        {MODE_OPTIONS.map((option) => (
+          <button key={option.value} type="button" disabled={disabled} onClick={() => update({ mode: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors disabled:opacity-50 ${value.mode === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+        ))}
+      </div>
+      <label className="flex items-center gap-2 text-xs text-on-surface">
+        <input type="checkbox" checked={value.includeProfanity} disabled={disabled} onChange={() => update({ includeProfanity: !value.includeProfanity })} className="accent-primary" />
+        Include built-in profanity list
+      </label>
+      <LineListField label="Words and phrases" value={value.words} placeholder={'Project Falcon\nacme-internal'} description="One per line. Matched as whole words, ignoring case." disabled={disabled} onCommit={(words) => update({ words })} />
+      <LineListField label="Patterns" value={value.patterns} placeholder={'\\b(?:\\d[ -]?){13,16}\\b'} description="One regular expression per line, e.g. card or account numbers." disabled={disabled} onCommit={(patterns) => update({ patterns })} />
+    </div>
+  );
+}
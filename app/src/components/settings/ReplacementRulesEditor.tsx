@@ -0,0 +1,201 @@
+import { useState } from 'react';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import {
+  REPLACEMENT_RULE_FIND_MAX,
+  REPLACEMENT_RULE_REPLACE_MAX,
+  REPLACEMENT_RULES_MAX,
+  type ReplacementRule,
+} from '../../lib/settings';
+import {
+  exportReplacementRules,
+  importReplacementRules,
+  previewReplacementRules,
+  type ReplacementPreview,
+} from '../../lib/dictation';
+
+function newRuleId(): string {
+  return globalThis.crypto?.randomUUID?.() ?? `rule-${Date.now()}-${Math.random()}`;
+}
+
+const inputClass = 'min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface placeholder:text-on-surface-variant focus:outline-none focus:ring-2 focus:ring-primary';
+const smallButtonClass = 'rounded-md px-2 py-1 text-xs text-on-surface-variant hover:bg-surface-container hover:text-primary focus:outline-none focus-visible:ring-2 focus-visible:ring-primary disabled:cursor-not-allowed disabled:opacity-50';
+const buttonClass = 'rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50';
+
+/**
+ * Ordered find/replace rules applied to every live transcript after the
+ * built-in cleanup and formatting. Each rule sees the text the rules above it
+ * produced, so order matters.
+ */
+export function ReplacementRulesEditor({ rules, disabled = false, onChange }: {
+  rules: ReplacementRule[];
+  disabled?: boolean;
+  onChange: (rules: ReplacementRule[]) => void;
+}) {
+  const [sample, setSample] = useState('');
+  const [preview, setPreview] = useState<ReplacementPreview | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const change = (next: ReplacementRule[]) => {
+    setPreview(null);
+    onChange(next);
+  };
+
+  const updateRule = (id: string, update: Partial<ReplacementRule>) => {
+    change(rules.map((rule) => rule.id === id ? { ...rule, ...update } : rule));
+  };
+
+  const moveRule = (index: number, offset: number) => {
+    const next = [...rules];
+    const [rule] = next.splice(index, 1);
+    next.splice(index + offset, 0, rule);
+    change(next);
+  };
+
+  const addRule = () => {
+    change([...rules, { id: newRuleId(), find: '', replace: '', regex: false, matchCase: false, enabled: true }]);
+  };
+
+  const runPreview = async () => {
+    setError(null);
+    setNotice(null);
+    try {
+      setPreview(await previewReplacementRules(rules, sample.trim() || undefined));
+    } catch (cause) {
+      setPreview(null);
+      setError(String(cause));
+    }
+  };
+
+  const exportRules = async () => {
+    setError(null);
+    setNotice(null);
+    const path = await save({ defaultPath: 'murmur-replacement-rules.json', filters: [{ name: 'JSON', extensions: ['json'] }] });
+    if (typeof path !== 'string') return;
+    try {
+      const count = await exportReplacementRules(path);
+      setNotice(`Exported ${count} ${count === 1 ? 'rule' : 'rules'}.`);
+    } catch (cause) {
+      setError(String(cause));
+    }
+  };
+
+  const importRules = async () => {
+    setError(null);
+    setNotice(null);
+    const path = await open({ multiple: false, directory: false, filters: [{ name: 'Murmur replacement rules', extensions: ['json'] }] });
+    if (typeof path !== 'string') return;
+    try {
+      change(await importReplacementRules(path));
+      setNotice('Rules imported. Rules with the same id were replaced in place.');
+    } catch (cause) {
+      setError(String(cause));
+    }
+  };
+
+  const matchesFor = (id: string) => preview?.matches.find((match) => match.id === id)?.matches;
+
+  return (
+    <div className="space-y-3">
+      {rules.length > 0 && (
+        <ol className="space-y-2">
+          {rules.map((rule, index) => {
+            const label = rule.find || `rule ${index + 1}`;
+            const matches = matchesFor(rule.id);
+            return (
+              <li key={rule.id} className="space-y-2 rounded-xl border border-outline-variant/25 bg-surface-container-lowest p-3 shadow-sm">
+                <div className="flex items-center gap-2">
+                  <input
+                    type="text"
+                    value={rule.find}
+                    maxLength={REPLACEMENT_RULE_FIND_MAX}
+                    onChange={(event) => updateRule(rule.id, { find: event.target.value })}
+                    aria-label={`Find for rule ${index + 1}`}
+                    placeholder={rule.regex ? 'Regular expression' : 'Find'}
+                    disabled={disabled}
+                    className={`${inputClass} ${rule.regex ? 'font-mono' : ''}`}
+                  />
+                  <span className="text-xs text-on-surface-variant" aria-hidden="true">→</span>
+                  <input
+                    type="text"
+                    value={rule.replace}
+                    maxLength={REPLACEMENT_RULE_REPLACE_MAX}
+                    onChange={(event) => updateRule(rule.id, { replace: event.target.value })}
+                    aria-label={`Replacement for ${label}`}
+                    placeholder="Replace with"
+                    disabled={disabled}
+                    className={inputClass}
+                  />
+                </div>
+                <div className="flex flex-wrap items-center gap-3 text-xs text-on-surface-variant">
+                  <label className="flex items-center gap-1">
+                    <input type="checkbox" checked={rule.enabled} disabled={disabled} onChange={() => updateRule(rule.id, { enabled: !rule.enabled })} />
+                    On
+                  </label>
+                  <label className="flex items-center gap-1">
+                    <input type="checkbox" checked={rule.regex} disabled={disabled} onChange={() => updateRule(rule.id, { regex: !rule.regex })} />
+                    Regex
+                  </label>
+                  <label className="flex items-center gap-1">
+                    <input type="checkbox" checked={rule.matchCase} disabled={disabled} onChange={() => updateRule(rule.id, { matchCase: !rule.matchCase })} />
+                    Match case
+                  </label>
+                  {matches !== undefined && <span role="status">{matches === 1 ? '1 match' : `${matches} matches`}</span>}
+                  <span className="ml-auto flex gap-1">
+                    <button type="button" onClick={() => moveRule(index, -1)} disabled={disabled || index === 0} aria-label={`Move ${label} up`} className={smallButtonClass}>↑</button>
+                    <button type="button" onClick={() => moveRule(index, 1)} disabled={disabled || index === rules.length - 1} aria-label={`Move ${label} down`} className={smallButtonClass}>↓</button>
+                    <button
+                      type="button"
+                      onClick={() => change(rules.filter((candidate) => candidate.id !== rule.id))}
+                      aria-label={`Remove ${label}`}
+                      disabled={disabled}
+                      className="rounded-md px-2 py-1 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error focus:outline-none focus-visible:ring-2 focus-visible:ring-primary"
+                    >
+                      Remove
+                    </button>
+                  </span>
+                </div>
+              </li>
+            );
+          })}
+        </ol>
+      )}
+      <div className="flex flex-wrap gap-2">
+        <button type="button" onClick={addRule} disabled={disabled || rules.length >= REPLACEMENT_RULES_MAX} className={buttonClass}>
+          Add Rule
+        </button>
+        <button type="button" onClick={() => void importRules()} disabled={disabled} className={buttonClass}>
+          Import…
+        </button>
+        <button type="button" onClick={() => void exportRules()} disabled={disabled || rules.length === 0} className={buttonClass}>
+          Export…
+        </button>
+      </div>
+      {rules.length > 0 && (
+        <div className="space-y-2">
+          <div className="flex items-center gap-2">
+            <input
+              type="text"
+              value={sample}
+              onChange={(event) => { setSample(event.target.value); setPreview(null); }}
+              aria-label="Preview text"
+              placeholder="Text to try, or leave blank for the last transcription"
+              className={inputClass}
+            />
+            <button type="button" onClick={() => void runPreview()} className={buttonClass}>
+              Preview
+            </button>
+          </div>
+          {preview && (
+            <div className="space-y-1 rounded-lg bg-surface-container-low p-3 text-xs" aria-label="Replacement preview">
+              <p className="text-on-surface-variant">{preview.source}</p>
+              <p className="font-medium text-on-surface">{preview.text === preview.source ? 'No rule changed this text.' : preview.text}</p>
+            </div>
+          )}
+        </div>
+      )}
+      {notice && <p className="text-xs text-on-surface-variant" role="status">{notice}</p>}
+      {error && <p className="text-xs text-error" role="alert">{error}</p>}
+    </div>
+  );
+}
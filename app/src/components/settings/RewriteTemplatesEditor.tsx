@@ -0,0 +1,146 @@
+import { useState } from 'react';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import {
+  REWRITE_TEMPLATE_INSTRUCTION_MAX,
+  REWRITE_TEMPLATE_NAME_MAX,
+  type RewriteTemplate,
+} from '../../lib/settings';
+import { exportRewriteTemplates, importRewriteTemplates } from '../../lib/dictation';
+
+function newTemplateId(): string {
+  return globalThis.crypto?.randomUUID?.() ?? `template-${Date.now()}-${Math.random()}`;
+}
+
+/**
+ * Named rewrite instructions for the local transform model. Say "format as
+ * <name>" at the start of a dictation, or pick a template on a dictation
+ * profile, to rewrite the take before it is pasted.
+ */
+export function RewriteTemplatesEditor({ templates, disabled = false, onChange }: {
+  templates: RewriteTemplate[];
+  disabled?: boolean;
+  onChange: (templates: RewriteTemplate[]) => void;
+}) {
+  const [notice, setNotice] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const updateTemplate = (id: string, update: Partial<RewriteTemplate>) => {
+    onChange(templates.map((template) => template.id === id ? { ...template, ...update } : template));
+  };
+
+  const addTemplate = () => {
+    onChange([...templates, {
+      id: newTemplateId(),
+      name: `Template ${templates.length + 1}`,
+      instruction: '',
+    }]);
+  };
+
+  const exportTemplates = async () => {
+    setError(null);
+    setNotice(null);
+    const path = await save({ defaultPath: 'murmur-rewrite-templates.json', filters: [{ name: 'JSON', extensions: ['json'] }] });
+    if (typeof path !== 'string') return;
+    try {
+      const count = await exportRewriteTemplates(path);
+      setNotice(`Exported ${count} ${count === 1 ? 'template' : 'templates'}.`);
+    } catch (cause) {
+      setError(String(cause));
+    }
+  };
+
+  const importTemplates = async () => {
+    setError(null);
+    setNotice(null);
+    const path = await open({ multiple: false, directory: false, filters: [{ name: 'Murmur rewrite templates', extensions: ['json'] }] });
+    if (typeof path !== 'string') return;
+    try {
+      const merged = await importRewriteTemplates(path);
+      onChange(merged);
+      setNotice('Templates imported. Templates with the same name were replaced.');
+    } catch (cause) {
+      setError(String(cause));
+    }
+  };
+
+  // Names must be unique, since the spoken prefix selects a template by name.
+  const duplicateName = (template: RewriteTemplate) => templates.some((other) =>
+    other.id !== template.id && other.name.trim().toLowerCase() === template.name.trim().toLowerCase());
+
+  return (
+    <div className="space-y-3">
+      {templates.length > 0 && (
+        <ul className="space-y-3">
+          {templates.map((template) => {
+            const label = template.name || 'Untitled template';
+            return (
+              <li key={template.id} className="space-y-2 rounded-xl border border-outline-variant/25 bg-surface-container-lowest p-3 shadow-sm">
+                <div className="flex items-center gap-2">
+                  <input
+                    type="text"
+                    value={template.name}
+                    maxLength={REWRITE_TEMPLATE_NAME_MAX}
+                    onChange={(event) => updateTemplate(template.id, { name: event.target.value })}
+                    aria-label="Template name"
+                    placeholder="Template name"
+                    disabled={disabled}
+                    className="min-w-0 flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface placeholder:text-on-surface-variant focus:outline-none focus:ring-2 focus:ring-primary"
+                  />
+                  <button
+                    type="button"
+                    onClick={() => onChange(templates.filter((candidate) => candidate.id !== template.id))}
+                    aria-label={`Remove ${label}`}
+                    disabled={disabled}
+                    className="rounded-md px-2 py-1 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error focus:outline-none focus-visible:ring-2 focus-visible:ring-primary"
+                  >
+                    Remove
+                  </button>
+                </div>
+                <textarea
+                  value={template.instruction}
+                  maxLength={REWRITE_TEMPLATE_INSTRUCTION_MAX}
+                  onChange={(event) => updateTemplate(template.id, { instruction: event.target.value })}
+                  aria-label={`Instruction for ${label}`}
+                  placeholder="Rewrite the text as…"
+                  rows={2}
+                  disabled={disabled}
+                  className="w-full resize-y rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface placeholder:text-on-surface-variant focus:outline-none focus:ring-2 focus:ring-primary"
+                />
+                {duplicateName(template) && <p className="text-xs text-amber-600 dark:text-amber-400">Another template has this name. Only the first one is kept.</p>}
+                {!template.instruction.trim() && <p className="text-xs text-amber-600 dark:text-amber-400">Add an instruction, or this template is not saved.</p>}
+              </li>
+            );
+          })}
+        </ul>
+      )}
+      <div className="flex flex-wrap gap-2">
+        <button
+          type="button"
+          onClick={addTemplate}
+          disabled={disabled}
+          className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+        >
+          Add Template
+        </button>
+        <button
+          type="button"
+          onClick={() => void importTemplates()}
+          disabled={disabled}
+          className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+        >
+          Import…
+        </button>
+        <button
+          type="button"
+          onClick={() => void exportTemplates()}
+          disabled={disabled || templates.length === 0}
+          className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+        >
+          Export…
+        </button>
+      </div>
+      {notice && <p className="text-xs text-on-surface-variant" role="status">{notice}</p>}
+      {error && <p className="text-xs text-error" role="alert">{error}</p>}
+    </div>
+  );
+}
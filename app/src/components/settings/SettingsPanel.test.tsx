@@ -15,13 +15,19 @@ vi.mock('@tauri-apps/api/app', () => ({ getVersion: vi.fn(async () => '0.18.0')
 vi.mock('@tauri-apps/api/core', () => ({ invoke: vi.fn(async (command: string) => command === 'list_audio_devices' ? [] : undefined) }));
 vi.mock('@tauri-apps/api/event', () => ({ listen: vi.fn(async () => () => {}) }));
 vi.mock('@tauri-apps/plugin-dialog', () => ({ open: vi.fn() }));
-vi.mock('../../lib/modelRuntime', () => ({ useModelRuntimeCatalog: () => ({ models: [], byName: new Map(), error: null }) }));
+vi.mock('../../lib/modelRuntime', () => ({
+  useModelRuntimeCatalog: () => ({ models: [], byName: new Map(), error: null }),
+  useLoadedBackends: () => [],
+}));
 vi.mock('../../lib/hooks/useVocabScan', () => ({
   useVocabScan: () => ({ status: 'idle', walker: null, stats: null, scan: vi.fn(), cancel: vi.fn() }),
 }));
 vi.mock('./AppOverridesEditor', () => ({ AppOverridesEditor: () => <div>App overrides editor</div> }));
+vi.mock('./CrashReportsPanel', () => ({ CrashReportsPanel: () => <div>Crash reports</div> }));
+vi.mock('./FocusFilterEditor', () => ({ FocusFilterEditor: () => <div>Focus filter editor</div> }));
 vi.mock('./KnowledgeManager', () => ({ KnowledgeManager: () => <div>Knowledge manager</div> }));
 vi.mock('./PerformanceLab', () => ({ PerformanceLab: () => <div>Performance lab</div> }));
+vi.mock('./StoragePanel', () => ({ StoragePanel: () => <div>Storage</div> }));
 vi.mock('./VocabularyAliasesEditor', () => ({ VocabularyAliasesEditor: () => <div>Vocabulary editor</div> }));
 vi.mock('./VoiceCommandsManager', () => ({ VoiceCommandsManager: () => <div>Voice commands editor</div> }));
 vi.mock('./TransformsManager', () => ({ TransformsManager: () => <div>Transforms manager</div> }));
@@ -62,8 +68,13 @@ describe('SettingsPanel information architecture', () => {
         onUpdateSettings={vi.fn()}
         status="idle"
         onResetStats={vi.fn()}
+        onPurgeTranscripts={vi.fn(async () => {})}
+        onClearHistory={vi.fn()}
         onViewLogs={vi.fn()}
         onRerunSetup={vi.fn()}
+        historyEntries={[]}
+        onImportHistory={vi.fn()}
+        dataFolderError={null}
         accessibilityGranted
         onCheckForUpdate={vi.fn(async () => {})}
         updateStatus={{ phase: 'idle' }}
@@ -141,8 +152,13 @@ describe('SettingsPanel transform block (#312 D1 round-2 findings 6-8)', () => {
         onUpdateSettings={vi.fn()}
         status="idle"
         onResetStats={vi.fn()}
+        onPurgeTranscripts={vi.fn(async () => {})}
+        onClearHistory={vi.fn()}
         onViewLogs={vi.fn()}
         onRerunSetup={vi.fn()}
+        historyEntries={[]}
+        onImportHistory={vi.fn()}
+        dataFolderError={null}
         accessibilityGranted
         onCheckForUpdate={vi.fn(async () => {})}
         updateStatus={{ phase: 'idle' }}
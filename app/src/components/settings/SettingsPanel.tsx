@@ -4,25 +4,50 @@ import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { open } from '@tauri-apps/plugin-dialog';
 import {
+  ALTERNATIVES_MAX,
   AVAILABLE_MODEL_OPTIONS,
   DEFAULT_SETTINGS,
   DOUBLE_TAP_KEY_OPTIONS,
   IDLE_TIMEOUT_OPTIONS,
+  INTERRUPTED_RECORDING_OPTIONS,
+  KEEP_ALIVE_OPTIONS,
   LANGUAGE_OPTIONS,
+  LOW_QUALITY_MIC_OPTIONS,
+  NOTES_TARGET_OPTIONS,
+  NOTIFICATION_POLICY_OPTIONS,
+  OVERLAY_DISPLAY_OPTIONS,
+  OVERLAY_FULLSCREEN_OPTIONS,
+  OVERLAY_STYLE_OPTIONS,
+  PRE_ROLL_OPTIONS,
+  MEETING_CHUNK_OPTIONS,
+  PROFILE_KEY_OPTIONS,
   RECORDING_MODE_OPTIONS,
+  SELECTION_DICTATION_OPTIONS,
+  TRAILING_BEHAVIOR_OPTIONS,
   TRANSFORM_KEY_OPTIONS,
+  TYPING_WPM_MAX,
+  TYPING_WPM_MIN,
+  UPDATE_CHANNEL_OPTIONS,
+  type ProfileKey,
   type RecordingMode,
   type Settings,
   type TransformKey,
+  isCloudModel,
+  isCustomModel,
+  isExternalModel,
   vocabularyPrompt,
 } from '../../lib/settings';
 import { useVocabScan } from '../../lib/hooks/useVocabScan';
-import { useModelRuntimeCatalog } from '../../lib/modelRuntime';
+import { describeTradeoffs, getModelCatalog, useLoadedBackends, useModelRuntimeCatalog, type ModelCatalogEntry } from '../../lib/modelRuntime';
 import {
   modelDownloadLabel,
   modelDownloadPercent,
   type ModelDownloadProgress,
 } from '../../lib/modelDownload';
+import { importModel, startWakeWord } from '../../lib/dictation';
+import { CustomModelDownloader } from './CustomModelDownloader';
+import { TranscriptionPluginsEditor } from './TranscriptionPluginsEditor';
+import { CloudTranscriptionEditor } from './CloudTranscriptionEditor';
 import {
   downloadTransformModel,
   removeTransformModel,
@@ -34,17 +59,48 @@ import {
   transformModelStatus,
   type TransformModelStatus,
 } from '../../lib/transformSettings';
-import type { DictationStatus } from '../../lib/types';
+import { isSettledStatus, type DictationStatus } from '../../lib/types';
 import type { UpdateStatus } from '../../lib/updater';
+import type { HistoryEntry } from '../../lib/history';
 import { Select } from '../ui/Select';
 import { AppOverridesEditor } from './AppOverridesEditor';
+import { DictationProfilesEditor } from './DictationProfilesEditor';
+import { DownloadNetworkEditor } from './DownloadNetworkEditor';
+import { DownloadQueuePanel } from './DownloadQueuePanel';
+import { ConfidenceRetryEditor } from './ConfidenceRetryEditor';
+import { LatencyBudgetEditor } from './LatencyBudgetEditor';
+import { LearnedCorrections } from './LearnedCorrections';
+import { CrashReportsPanel } from './CrashReportsPanel';
+import { FocusFilterEditor } from './FocusFilterEditor';
+import { QuietHoursEditor } from './QuietHoursEditor';
+import { AppTriggersEditor } from './AppTriggersEditor';
+import { ContextBiasingEditor } from './ContextBiasingEditor';
+import { HotkeyTester } from './HotkeyTester';
+import { HotkeyOwnerNotice } from './HotkeyOwnerNotice';
+import { HotkeyTimingEditor } from './HotkeyTimingEditor';
+import { KeyboardDiagnostics } from './KeyboardDiagnostics';
 import { KnowledgeManager } from './KnowledgeManager';
+import { MicTester } from './MicTester';
+import { MicrophonePreferencesEditor } from './MicrophonePreferencesEditor';
+import { MicrophonePrivacy } from './MicrophonePrivacy';
 import { PerformanceLab } from './PerformanceLab';
+import { PowerProfilesEditor } from './PowerProfilesEditor';
+import { PresetsEditor } from './PresetsEditor';
 import { SettingsSection } from './SettingsSection';
+import { SupportBundlePanel } from './SupportBundlePanel';
+import { StoragePanel } from './StoragePanel';
+import { SyncPanel } from './SyncPanel';
 import { TransformsManager } from './TransformsManager';
 import { VocabScanStrip } from './VocabScanStrip';
 import { VocabularyAliasesEditor } from './VocabularyAliasesEditor';
 import { VoiceCommandsManager } from './VoiceCommandsManager';
+import { WakeWordModelStatus } from './WakeWordModelStatus';
+import { AmbientLog } from './AmbientLog';
+import { RedactionEditor } from './RedactionEditor';
+import { RewriteTemplatesEditor } from './RewriteTemplatesEditor';
+import { ReplacementRulesEditor } from './ReplacementRulesEditor';
+import { DictationMacrosEditor } from './DictationMacrosEditor';
+import { WhisperDecodingEditor } from './WhisperDecodingEditor';
 
 function Toggle({ label, checked, onChange, disabled = false }: {
   label: string;
@@ -134,6 +190,30 @@ function VadSensitivitySlider({ value, onCommit }: { value: number; onCommit: (v
   );
 }
 
+function TypingSpeedSlider({ value, onCommit }: { value: number; onCommit: (value: number) => void }) {
+  const [draft, setDraft] = useState(value);
+  useEffect(() => setDraft(value), [value]);
+  return (
+    <div>
+      <div className="mb-1 flex items-center justify-between">
+        <label className="text-xs text-on-surface-variant">Typing Speed</label>
+        <span className="text-xs font-medium text-on-surface">{draft} WPM</span>
+      </div>
+      <input
+        type="range"
+        min={TYPING_WPM_MIN}
+        max={TYPING_WPM_MAX}
+        step={5}
+        value={draft}
+        onChange={(event) => setDraft(Number(event.target.value))}
+        onPointerUp={() => onCommit(draft)}
+        className="h-1.5 w-full cursor-pointer appearance-none rounded-full bg-surface-container-highest accent-primary"
+      />
+      <p className="mt-1 text-xs text-on-surface-variant">Insights counts time saved against typing at this speed.</p>
+    </div>
+  );
+}
+
 interface SettingsPanelProps {
   isOpen: boolean;
   onClose: () => void;
@@ -141,6 +221,14 @@ interface SettingsPanelProps {
   onUpdateSettings: (updates: Partial<Settings>) => void;
   status: DictationStatus;
   onResetStats: () => void;
+  /** Shred transcript logs on disk and clear history. */
+  onPurgeTranscripts: () => Promise<void>;
+  /** Clears history kept in the webview after its on-disk copy is purged. */
+  onClearHistory: () => void;
+  historyEntries: HistoryEntry[];
+  /** Merges imported history entries by id. */
+  onImportHistory: (entries: HistoryEntry[]) => void;
+  dataFolderError: string | null;
   onViewLogs: () => void;
   onRerunSetup: () => void;
   accessibilityGranted: boolean | null;
@@ -185,6 +273,11 @@ export function SettingsPanel({
   onUpdateSettings,
   status,
   onResetStats,
+  onPurgeTranscripts,
+  onClearHistory,
+  historyEntries,
+  onImportHistory,
+  dataFolderError,
   onViewLogs,
   onRerunSetup,
   accessibilityGranted,
@@ -192,10 +285,13 @@ export function SettingsPanel({
   updateStatus,
   configureError,
 }: SettingsPanelProps) {
-  const { byName: runtimeByName } = useModelRuntimeCatalog(isOpen);
+  const { models: runtimeModels, byName: runtimeByName } = useModelRuntimeCatalog(isOpen);
+  const loadedBackends = useLoadedBackends(isOpen);
   const [activeCat, setActiveCat] = useState<string>('recording');
   const [version, setVersion] = useState('');
   const [confirmReset, setConfirmReset] = useState(false);
+  const [confirmPurge, setConfirmPurge] = useState(false);
+  const [purgeNotice, setPurgeNotice] = useState<string | null>(null);
   const contentRef = useRef<HTMLDivElement>(null);
   const confirmResetTimeoutRef = useRef<ReturnType<typeof setTimeout> | null>(null);
 
@@ -239,6 +335,26 @@ export function SettingsPanel({
   };
 
   const selectedRuntime = runtimeByName.get(settings.model);
+  const [modelCatalog, setModelCatalog] = useState<ModelCatalogEntry[]>([]);
+  useEffect(() => {
+    if (!isOpen) return;
+    let cancelled = false;
+    getModelCatalog()
+      .then((entries) => { if (!cancelled) setModelCatalog(entries ?? []); })
+      .catch(() => {});
+    return () => { cancelled = true; };
+  }, [isOpen]);
+  const selectedTradeoffs = modelCatalog.find((entry) => entry.modelName === settings.model);
+  const customModelItems = runtimeModels
+    .filter((model) => isCustomModel(model.modelName) && (model.installState === 'installed' || model.modelName === settings.model))
+    .map((model) => ({ value: model.modelName, label: `${model.label} (Custom)` }));
+  const externalModelItems = runtimeModels
+    .filter((model) => isExternalModel(model.modelName) && (model.installState === 'installed' || model.modelName === settings.model))
+    .map((model) => ({ value: model.modelName, label: `${model.label} (Plugin)` }));
+  const cloudModelItems = runtimeModels
+    .filter((model) => isCloudModel(model.modelName) && (model.installState === 'installed' || model.modelName === settings.model))
+    .map((model) => ({ value: model.modelName, label: model.label }));
+  const leavesDevice = isCloudModel(settings.model) || isExternalModel(settings.model);
   const modelAvailable = selectedRuntime ? selectedRuntime.installState === 'installed' : null;
   const [modelDownload, setModelDownload] = useState<
     | { phase: 'idle' }
@@ -280,6 +396,31 @@ export function SettingsPanel({
     }
   }, [settings.model]);
 
+  const [linkImport, setLinkImport] = useState(false);
+  const importModelFrom = useCallback(async () => {
+    const modelName = settings.model;
+    const parakeet = runtimeByName.get(modelName)?.backend === 'parakeet';
+    let selected: string | string[] | null;
+    try {
+      selected = await open(parakeet
+        ? { directory: true, multiple: false }
+        : { multiple: false, filters: [{ name: 'Whisper GGML model', extensions: ['bin'] }] });
+    } catch {
+      return;
+    }
+    if (typeof selected !== 'string') return;
+    downloadModelRef.current = modelName;
+    setModelDownload({ phase: 'downloading', progress: { received: 0, total: 0, phase: 'installing' } });
+    try {
+      await importModel(modelName, selected, linkImport);
+      if (downloadModelRef.current === modelName) setModelDownload({ phase: 'idle' });
+    } catch (error) {
+      if (downloadModelRef.current === modelName) setModelDownload({ phase: 'error', message: String(error) });
+    } finally {
+      if (downloadModelRef.current === modelName) downloadModelRef.current = null;
+    }
+  }, [settings.model, runtimeByName, linkImport]);
+
   const [audioDevices, setAudioDevices] = useState<string[]>([]);
   useEffect(() => {
     if (!isOpen) return;
@@ -388,7 +529,7 @@ export function SettingsPanel({
     }
   };
 
-  const isRecording = status !== 'idle';
+  const isRecording = !isSettledStatus(status);
   const isDoubleTap = settings.recordingMode === 'double_tap';
   const isBoth = settings.recordingMode === 'both';
   const keyLabel = isBoth ? 'Trigger Key' : isDoubleTap ? 'Double-Tap Key' : 'Hold Key';
@@ -399,6 +540,7 @@ export function SettingsPanel({
     && audioDevices.length > 0
     && !audioDevices.includes(settings.microphone);
   const englishOnly = selectedRuntime ? !selectedRuntime.capabilities.multilingual : true;
+  const whisperSelected = isCustomModel(settings.model) || AVAILABLE_MODEL_OPTIONS.some((model) => model.value === settings.model && model.backend === 'whisper');
   const downloadProgress = modelDownload.phase === 'downloading'
     ? modelDownloadPercent(modelDownload.progress)
     : null;
@@ -420,6 +562,21 @@ export function SettingsPanel({
     }, 3000);
   };
 
+  const purgeTranscripts = async () => {
+    if (!confirmPurge) {
+      setPurgeNotice(null);
+      setConfirmPurge(true);
+      return;
+    }
+    setConfirmPurge(false);
+    try {
+      await onPurgeTranscripts();
+      setPurgeNotice('All transcripts were purged.');
+    } catch (e) {
+      setPurgeNotice(`Purge failed: ${String(e)}`);
+    }
+  };
+
   return (
     <div className="flex flex-1 overflow-hidden bg-surface text-on-surface">
       <nav aria-label="Settings pages" className="flex w-48 shrink-0 flex-col overflow-y-auto bg-surface-container-low">
@@ -453,6 +610,22 @@ export function SettingsPanel({
               <label className="mb-2 block text-sm font-medium text-on-surface">Microphone</label>
               <Select value={settings.microphone} onChange={(microphone) => onUpdateSettings({ microphone })} disabled={isRecording} items={[{ value: 'system_default', label: 'System Default' }, ...audioDevices.map((name) => ({ value: name, label: name }))]} />
               {missingDevice && <p className="mt-2 rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-400">Selected device not found — Murmur will use System Default.</p>}
+              <div className="mt-2">
+                <MicTester deviceName={settings.microphone} disabled={isRecording} />
+              </div>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Fallback Microphones</p>
+              <MicrophonePreferencesEditor preferences={settings.microphonePreferences} devices={audioDevices} onChange={(microphonePreferences) => onUpdateSettings({ microphonePreferences })} />
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Low-Quality Bluetooth Mic</p>
+              <div className="flex gap-2">
+                {LOW_QUALITY_MIC_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ lowQualityMicAction: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.lowQualityMicAction === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">AirPods and other headsets drop to a narrowband call codec when they are both the microphone and the speaker, and transcripts suffer.</p>
             </div>
             <div>
               <p className="mb-2 text-sm font-medium text-on-surface">Voice Detection</p>
@@ -473,12 +646,96 @@ export function SettingsPanel({
                 <button type="button" onClick={requestAccessibility} className="ml-auto underline">Grant</button>
               </div>
             )}
+            <HotkeyOwnerNotice />
             <div>
               <label className="mb-2 block text-sm font-medium text-on-surface">{keyLabel}</label>
               <Select value={settings.doubleTapKey} onChange={(doubleTapKey) => onUpdateSettings({ doubleTapKey })} disabled={isRecording} items={DOUBLE_TAP_KEY_OPTIONS} />
               <p className="mt-1 text-xs text-on-surface-variant">{keyHelp}</p>
+              <div className="mt-2">
+                <HotkeyTester hotkey={settings.doubleTapKey} disabled={isRecording || accessibilityGranted === false} />
+              </div>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Hotkey Timing</p>
+              <HotkeyTimingEditor hotkey={settings.doubleTapKey} mode={settings.recordingMode} value={settings.hotkeyTiming} disabled={isRecording || accessibilityGranted === false} onChange={(hotkeyTiming) => onUpdateSettings({ hotkeyTiming })} />
             </div>
             {(isDoubleTap || isBoth) && <SettingToggle title="Hotkey Timing Feedback" description="Flash the overlay when a tap misses the double-tap window." checked={settings.hotkeyMissFeedback} onChange={() => onUpdateSettings({ hotkeyMissFeedback: !settings.hotkeyMissFeedback })} />}
+            <SettingToggle title="Wake Word" description='Say "Hey Murmur" to start recording; it stops when you go quiet. Keeps the microphone open while on.' checked={settings.wakeWord} onChange={() => onUpdateSettings({ wakeWord: !settings.wakeWord })} />
+            {settings.wakeWord && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <WakeWordModelStatus onInstalled={() => { startWakeWord(settings.microphone).catch((err) => console.error('Failed to start wake word detector:', err)); }} />
+              </div>
+            )}
+            <SettingToggle title="Ambient Mode" description="Transcribe everything you say into a searchable log, in the background. Nothing is pasted. Keeps the microphone open while on." checked={settings.ambientMode} onChange={() => onUpdateSettings({ ambientMode: !settings.ambientMode })} />
+            {settings.ambientMode && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <AmbientLog />
+              </div>
+            )}
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">App Triggers</p>
+              <AppTriggersEditor triggers={settings.appTriggers} onChange={(appTriggers) => onUpdateSettings({ appTriggers })} />
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Pre-roll</p>
+              <div className="flex gap-2">
+                {PRE_ROLL_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ preRollSeconds: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.preRollSeconds === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">Catch words you start saying just before the hotkey. The last seconds of audio are kept in memory only, and dropped unless a recording starts. Keeps the microphone open while on.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Meeting Notes</p>
+              <div className="flex gap-2">
+                {MEETING_CHUNK_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ meetingChunkSeconds: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.meetingChunkSeconds === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">How often a meeting started from Notes is transcribed into today's note. Each part ends at the next pause. Applies from the next meeting.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Dictation Profiles</p>
+              <DictationProfilesEditor profiles={settings.dictationProfiles} rewriteTemplates={settings.rewriteTemplates} dictationKey={settings.doubleTapKey} transformKey={settings.transformHoldKey} disabled={isRecording} onChange={(dictationProfiles) => onUpdateSettings({ dictationProfiles })} />
+              <p className="mt-1 text-xs text-on-surface-variant">Hold a profile's key to dictate with its model, language, and overrides — for example Right Control for English and Left Option for Spanish.</p>
+            </div>
+            <SettingToggle title="Append Mode" description="Hold each take in a buffer instead of pasting it, then paste all of them as one dictation when you commit." checked={settings.appendMode} onChange={() => onUpdateSettings({ appendMode: !settings.appendMode })} />
+            {settings.appendMode && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <label className="mb-2 block text-sm font-medium text-on-surface">Commit key</label>
+                <Select
+                  value={settings.appendCommitKey ?? 'none'}
+                  onChange={(value) => onUpdateSettings({ appendCommitKey: value === 'none' ? null : value as ProfileKey })}
+                  items={[
+                    { value: 'none', label: 'None' },
+                    ...PROFILE_KEY_OPTIONS.filter((option) => option.value !== settings.doubleTapKey && option.value !== settings.transformHoldKey),
+                  ]}
+                />
+                <p className="mt-1 text-xs text-on-surface-variant">Press this key to paste the buffered takes. You can also commit or discard them from the overlay or the main window.</p>
+              </div>
+            )}
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Section Marker Key</label>
+              <Select
+                value={settings.markerKey ?? 'none'}
+                onChange={(value) => onUpdateSettings({ markerKey: value === 'none' ? null : value as ProfileKey })}
+                items={[
+                  { value: 'none', label: 'None' },
+                  ...PROFILE_KEY_OPTIONS.filter((option) => option.value !== settings.doubleTapKey && option.value !== settings.transformHoldKey && option.value !== settings.pasteLastKey && !(settings.appendMode && option.value === settings.appendCommitKey)),
+                ]}
+              />
+              <p className="mt-1 text-xs text-on-surface-variant">Tap this key while recording to start a new section. Each section is transcribed on its own and starts a new paragraph.</p>
+            </div>
+            <SettingToggle title="Spoken Markers" description='Say "marker" on its own, as in “…end of the intro. Marker. Next…”, to start a new section.' checked={settings.spokenMarkers} onChange={() => onUpdateSettings({ spokenMarkers: !settings.spokenMarkers })} />
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">On Lock or Sleep</p>
+              <div className="flex gap-2">
+                {INTERRUPTED_RECORDING_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ interruptedRecordingAction: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.interruptedRecordingAction === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">A recording still running when the screen locks, the Mac sleeps, or you switch users is stopped. Discard drops it; Transcribe keeps what was said so far.</p>
+            </div>
           </SettingsSection>
 
           <SettingsSection pageId="transform" activePage={activeCat} title="Transform" subtitle="Selected-text rewrite with a local on-device model">
@@ -600,6 +857,11 @@ export function SettingsPanel({
               <h2 className="mb-1 text-sm font-medium text-on-surface">Saved transforms</h2>
               <TransformsManager active={isOpen && activeCat === 'transform'} />
             </div>
+            <div className="border-t border-outline-variant/20 pt-4">
+              <h2 className="mb-1 text-sm font-medium text-on-surface">Rewrite templates</h2>
+              <p className="mb-2 text-xs text-on-surface-variant">Start a dictation with “format as” and a template name — for example “format as email: running late, start without me” — or pick a template on a dictation profile. The take is rewritten by the transform model before it is pasted.</p>
+              <RewriteTemplatesEditor templates={settings.rewriteTemplates} disabled={isRecording} onChange={(rewriteTemplates) => onUpdateSettings({ rewriteTemplates })} />
+            </div>
           </SettingsSection>
 
           <SettingsSection pageId="transcription" activePage={activeCat} title="Transcription" subtitle="Model, language, and runtime lifecycle">
@@ -609,16 +871,41 @@ export function SettingsPanel({
                 value={settings.model}
                 onChange={(model) => onUpdateSettings({ model })}
                 disabled={isRecording}
-                items={AVAILABLE_MODEL_OPTIONS.map((model) => ({ value: model.value, label: `${model.label}${model.backend === 'coreml' ? ' — Recommended' : ''} (${model.size})` }))}
+                items={[
+                  ...AVAILABLE_MODEL_OPTIONS.map((model) => ({ value: model.value, label: `${model.label}${model.backend === 'coreml' ? ' — Recommended' : ''} (${model.size})` })),
+                  ...customModelItems,
+                  ...externalModelItems,
+                  ...cloudModelItems,
+                ]}
               />
-              <p className="mt-1 text-xs text-on-surface-variant">Parakeet Core ML is recommended on supported Macs. Larger models can be more accurate but use more storage and memory.</p>
+              <p className="mt-1 text-xs text-on-surface-variant">Parakeet Core ML is recommended on supported Macs. Larger models can be more accurate but use more storage and memory. Q5 and Q8 variants are quantized: much smaller, with slightly lower accuracy.</p>
+              {selectedTradeoffs && <p className="mt-1 text-xs text-on-surface-variant" data-testid="model-tradeoffs">{describeTradeoffs(selectedTradeoffs)}</p>}
               {selectedRuntime && <p className="mt-1 text-xs text-on-surface-variant" data-testid="model-runtime-status">{selectedRuntime.label}: {selectedRuntime.backend} / {selectedRuntime.accelerator} / {selectedRuntime.size} · {selectedRuntime.installState} · {selectedRuntime.lifecycleState}</p>}
               {isRecording && <p className="mt-1 text-xs text-amber-600 dark:text-amber-400">Stop recording before changing model.</p>}
-              {modelAvailable === false && modelDownload.phase === 'idle' && (
+              {modelAvailable === false && modelDownload.phase === 'idle' && isCustomModel(settings.model) && (
+                <div className="mt-2 rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-400">Custom model file is missing. Download it again below.</div>
+              )}
+              {modelAvailable === false && modelDownload.phase === 'idle' && isExternalModel(settings.model) && (
+                <div className="mt-2 rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-400">This transcription plugin isn't registered. Add it again below.</div>
+              )}
+              {modelAvailable === false && isCloudModel(settings.model) && (
+                <div className="mt-2 rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-400">Cloud transcription has no API key. Add one below.</div>
+              )}
+              {settings.keepAudioOnDevice && leavesDevice && (
+                <div className="mt-2 rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-400">Never Send Audio Off This Mac is on, so this model won't transcribe. Choose an on-device model or turn the setting off.</div>
+              )}
+              {modelAvailable === false && modelDownload.phase === 'idle' && !isCustomModel(settings.model) && !isExternalModel(settings.model) && !isCloudModel(settings.model) && (
                 <div className="mt-2 flex items-center rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-400">
                   <span>Model not downloaded</span><button type="button" onClick={() => void downloadModel()} className="ml-auto underline">Download</button>
+                  {selectedRuntime?.backend !== 'coreml' && <button type="button" onClick={() => void importModelFrom()} className="ml-3 underline">Import…</button>}
                 </div>
               )}
+              {modelAvailable === false && modelDownload.phase === 'idle' && selectedRuntime?.backend !== 'coreml' && !isCustomModel(settings.model) && !isExternalModel(settings.model) && !isCloudModel(settings.model) && (
+                <label className="mt-1 flex items-center gap-2 text-xs text-on-surface-variant">
+                  <input type="checkbox" checked={linkImport} onChange={() => setLinkImport(!linkImport)} className="accent-primary" />
+                  Link the imported model instead of copying it (the original must stay in place)
+                </label>
+              )}
               {modelDownload.phase === 'downloading' && (
                 <div className="mt-2">
                   <div className="mb-1 flex justify-between text-xs text-on-surface-variant"><span>{modelDownloadLabel(modelDownload.progress)}</span><span>{downloadProgress === null ? 'Working…' : `${downloadProgress}%`}</span></div>
@@ -627,6 +914,19 @@ export function SettingsPanel({
               )}
               {modelDownload.phase === 'error' && <div className="mt-2 flex items-center rounded-lg border border-error/30 bg-error/10 px-3 py-2 text-xs text-error"><span>{modelDownload.message}</span><button type="button" onClick={() => void downloadModel()} className="ml-auto underline">Retry</button></div>}
             </div>
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Custom Whisper Model</label>
+              <CustomModelDownloader disabled={isRecording} onDownloaded={(model) => onUpdateSettings({ model })} />
+            </div>
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Transcription Plugins</label>
+              <TranscriptionPluginsEditor disabled={isRecording} onRegistered={(model) => onUpdateSettings({ model })} />
+            </div>
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Cloud Transcription</label>
+              <CloudTranscriptionEditor value={settings.cloudTranscription} disabled={isRecording} onChange={(cloudTranscription) => onUpdateSettings({ cloudTranscription })} />
+            </div>
+            <SettingToggle title="Never Send Audio Off This Mac" description="Refuse cloud transcription and transcription plugins, even when one is selected, so recordings are only ever transcribed here." checked={settings.keepAudioOnDevice} onChange={() => onUpdateSettings({ keepAudioOnDevice: !settings.keepAudioOnDevice })} />
             <div>
               <label className="mb-2 block text-sm font-medium text-on-surface">Language</label>
               <Select value={settings.language} onChange={(language) => onUpdateSettings({ language })} disabled={isRecording || englishOnly} items={LANGUAGE_OPTIONS} />
@@ -637,6 +937,79 @@ export function SettingsPanel({
               <Select value={String(settings.idleTimeoutMinutes)} onChange={(value) => onUpdateSettings({ idleTimeoutMinutes: Number(value) })} disabled={isRecording} items={IDLE_TIMEOUT_OPTIONS.map((option) => ({ value: String(option.value), label: option.label }))} />
               <p className="mt-1 text-xs text-on-surface-variant">Free memory by unloading an idle model; choose Never to keep it ready.</p>
             </div>
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Keep Other Engines Loaded</label>
+              <Select value={String(settings.backendKeepAliveMb)} onChange={(value) => onUpdateSettings({ backendKeepAliveMb: Number(value) })} disabled={isRecording} items={KEEP_ALIVE_OPTIONS.map((option) => ({ value: String(option.value), label: option.label }))} />
+              <p className="mt-1 text-xs text-on-surface-variant">
+                Switching between Whisper and Parakeet keeps the previous engine in memory, up to this budget, so switching back is instant.
+                {loadedBackends.length > 0 && ` Loaded now: ${loadedBackends.map((backend) => backend.active ? `${backend.label} (in use)` : backend.label).join(', ')}.`}
+              </p>
+            </div>
+            <SettingToggle title="Adapt to Heat and Low Power" description="Use a smaller model for Whisper Large Turbo recordings while the Mac is hot or in Low Power Mode." checked={settings.adaptiveModel.enabled} onChange={() => onUpdateSettings({ adaptiveModel: { ...settings.adaptiveModel, enabled: !settings.adaptiveModel.enabled } })} />
+            {settings.adaptiveModel.enabled && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <label className="mb-2 block text-xs font-medium text-on-surface">Fallback Model</label>
+                <Select
+                  value={settings.adaptiveModel.fallbackModel}
+                  onChange={(fallbackModel) => onUpdateSettings({ adaptiveModel: { ...settings.adaptiveModel, fallbackModel } })}
+                  items={AVAILABLE_MODEL_OPTIONS.filter((model) => model.value !== 'large-v3-turbo').map((model) => ({ value: model.value, label: `${model.label} (${model.size})` }))}
+                />
+                <p className="mt-1 text-xs text-on-surface-variant">Must be downloaded; otherwise Murmur keeps the selected model.</p>
+              </div>
+            )}
+            <SettingToggle title="Latency Budget" description="Watch how long each model takes compared with your recordings, and point out a faster installed model when the selected one keeps running over." checked={settings.latencyBudget.enabled} onChange={() => onUpdateSettings({ latencyBudget: { ...settings.latencyBudget, enabled: !settings.latencyBudget.enabled } })} />
+            {settings.latencyBudget.enabled && (
+              <div className="ml-3 space-y-3 border-l border-outline-variant/30 pl-3">
+                <LatencyBudgetEditor value={settings.latencyBudget} disabled={isRecording} onChange={(latencyBudget) => onUpdateSettings({ latencyBudget })} />
+                <SettingToggle title="Switch Automatically" description="Change the selected model to the faster one instead of only suggesting it. Profile and app-specific models are never changed." checked={settings.latencyBudget.autoSwitch} onChange={() => onUpdateSettings({ latencyBudget: { ...settings.latencyBudget, autoSwitch: !settings.latencyBudget.autoSwitch } })} />
+              </div>
+            )}
+            <SettingToggle title="Draft, Then Refine" label="Dual-model mode" description="Paste a fast draft right away, then re-transcribe the recording with the selected model in the background." checked={settings.dualModel.enabled} onChange={() => onUpdateSettings({ dualModel: { ...settings.dualModel, enabled: !settings.dualModel.enabled } })} />
+            {settings.dualModel.enabled && (
+              <div className="ml-3 space-y-3 border-l border-outline-variant/30 pl-3">
+                <div>
+                  <label className="mb-2 block text-xs font-medium text-on-surface">Draft Model</label>
+                  <Select
+                    value={settings.dualModel.draftModel}
+                    onChange={(draftModel) => onUpdateSettings({ dualModel: { ...settings.dualModel, draftModel } })}
+                    disabled={isRecording}
+                    items={AVAILABLE_MODEL_OPTIONS.map((model) => ({ value: model.value, label: `${model.label} (${model.size})` }))}
+                  />
+                  <p className="mt-1 text-xs text-on-surface-variant">Must be downloaded and differ from the selected model; otherwise recordings use the selected model only.</p>
+                </div>
+                <SettingToggle title="Replace the Draft" description="When the refined text differs, select the pasted draft and paste the refined text over it. Skipped if you've started another recording." checked={settings.dualModel.replaceDraft} onChange={() => onUpdateSettings({ dualModel: { ...settings.dualModel, replaceDraft: !settings.dualModel.replaceDraft } })} />
+              </div>
+            )}
+            <SettingToggle title="Power Profiles" description="Switch model, VAD sensitivity, and model release timing when the Mac moves between the power adapter and battery." checked={settings.powerProfiles.enabled} onChange={() => onUpdateSettings({ powerProfiles: { ...settings.powerProfiles, enabled: !settings.powerProfiles.enabled } })} />
+            {settings.powerProfiles.enabled && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <PowerProfilesEditor value={settings.powerProfiles} disabled={isRecording} onChange={(powerProfiles) => onUpdateSettings({ powerProfiles })} />
+                <p className="mt-2 text-xs text-on-surface-variant">The profile for the current power source applies right away and again on every switch.</p>
+              </div>
+            )}
+            <SettingToggle title="Silence Guard" label="Hallucination filter" description="Skip silent recordings and drop phantom phrases like “Thank you for watching.”" checked={settings.hallucinationFilter} onChange={() => onUpdateSettings({ hallucinationFilter: !settings.hallucinationFilter })} />
+            {whisperSelected && <SettingToggle title="Flag Unsure Transcripts" description="Mark history entries Whisper had low confidence in, so you know which ones to double-check." checked={settings.flagLowConfidence} onChange={() => onUpdateSettings({ flagLowConfidence: !settings.flagLowConfidence })} />}
+            {whisperSelected && <SettingToggle title="Retry Unsure Transcripts" description="Decode a transcript Whisper was unsure of again before pasting it, and paste whichever pass it was surer of." checked={settings.confidenceRetry.enabled} onChange={() => onUpdateSettings({ confidenceRetry: { ...settings.confidenceRetry, enabled: !settings.confidenceRetry.enabled } })} />}
+            {whisperSelected && settings.confidenceRetry.enabled && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <ConfidenceRetryEditor value={settings.confidenceRetry} disabled={isRecording} onChange={(confidenceRetry) => onUpdateSettings({ confidenceRetry })} />
+              </div>
+            )}
+            {whisperSelected && <SettingToggle title="Offer Alternatives" description="After pasting, decode the recording a few more times in the background and underline words in history that Whisper could have heard differently. Tap one to swap in another reading." checked={settings.alternatives.enabled} onChange={() => onUpdateSettings({ alternatives: { ...settings.alternatives, enabled: !settings.alternatives.enabled } })} />}
+            {whisperSelected && settings.alternatives.enabled && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <label className="mb-1 block text-xs text-on-surface-variant">Extra Decodes</label>
+                <Select aria-label="Alternative decodes" value={String(settings.alternatives.count)} onChange={(count) => onUpdateSettings({ alternatives: { ...settings.alternatives, count: Number(count) } })} disabled={isRecording} items={Array.from({ length: ALTERNATIVES_MAX }, (_, i) => ({ value: String(i + 1), label: String(i + 1) }))} />
+                <p className="mt-1 text-xs text-on-surface-variant">More decodes find more alternatives but keep the model busy longer after each dictation. Not available in dual-model mode.</p>
+              </div>
+            )}
+            {whisperSelected && <WhisperDecodingEditor value={settings.whisperDecoding} disabled={isRecording} onChange={(whisperDecoding) => onUpdateSettings({ whisperDecoding })} />}
+            <DownloadQueuePanel />
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Download Network</label>
+              <DownloadNetworkEditor value={settings.downloadNetwork} disabled={modelDownload.phase === 'downloading'} onChange={(downloadNetwork) => onUpdateSettings({ downloadNetwork })} />
+              <p className="mt-1 text-xs text-on-surface-variant">For networks where Hugging Face or GitHub is blocked or slow. Mirrors must serve the same paths as the original site.</p>
+            </div>
           </SettingsSection>
 
           <SettingsSection pageId="text-vocabulary" activePage={activeCat} title="Text & Vocabulary" subtitle="Cleanup, preferred terms, structured writing, and knowledge">
@@ -653,6 +1026,18 @@ export function SettingsPanel({
               <p className="mt-1 mb-3 text-xs text-on-surface-variant">Teach Murmur preferred spellings and exact spoken variants.</p>
               <VocabularyAliasesEditor entries={settings.vocabularyEntries} voiceCommands={settings.voiceCommands} onChange={(vocabularyEntries) => onUpdateSettings({ vocabularyEntries, customVocabulary: vocabularyPrompt(vocabularyEntries) })} />
             </div>
+            <SettingToggle title="Learn From Corrections" description="When you fix a misheard word in history, remember it. Words you correct more than once bias Whisper toward the right spelling." checked={settings.learnFromCorrections} onChange={() => onUpdateSettings({ learnFromCorrections: !settings.learnFromCorrections })} />
+            {settings.learnFromCorrections && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <LearnedCorrections active={isOpen && activeCat === 'text-vocabulary'} />
+              </div>
+            )}
+            <SettingToggle title="Context From the Active App" description="Bias Whisper toward names in the window you are dictating into, like the people on an email thread. Off until you choose the apps it may read." checked={settings.contextBiasing.enabled} onChange={() => onUpdateSettings({ contextBiasing: { ...settings.contextBiasing, enabled: !settings.contextBiasing.enabled } })} />
+            {settings.contextBiasing.enabled && (
+              <div className="ml-3 border-l border-outline-variant/30 pl-3">
+                <ContextBiasingEditor biasing={settings.contextBiasing} onChange={(contextBiasing) => onUpdateSettings({ contextBiasing })} />
+              </div>
+            )}
             <SettingToggle title="Developer Terms" description="Bias recognition toward built-in development terms and, optionally, identifiers from one project folder." checked={settings.codeVocabEnabled} onChange={() => onUpdateSettings({ codeVocabEnabled: !settings.codeVocabEnabled })} />
             {settings.codeVocabEnabled && (
               <div className="ml-3 space-y-2 border-l border-outline-variant/30 pl-3">
@@ -674,6 +1059,20 @@ export function SettingsPanel({
               <p className="mt-1 mb-3 text-xs text-on-surface-variant">Create exact spoken phrases that insert replacement text or a multiline snippet.</p>
               <VoiceCommandsManager active={isOpen && activeCat === 'text-vocabulary'} globallyEnabled={settings.voiceCommandsEnabled} profiles={settings.appProfiles} />
             </div>
+            <div className="border-t border-outline-variant/20 pt-4">
+              <h2 className="text-sm font-medium text-on-surface">Replacement Rules</h2>
+              <p className="mt-1 mb-3 text-xs text-on-surface-variant">Find and replace words or patterns in every dictation, top to bottom, such as “macbook” to “MacBook”.</p>
+              <ReplacementRulesEditor rules={settings.replacementRules} disabled={isRecording} onChange={(replacementRules) => onUpdateSettings({ replacementRules })} />
+            </div>
+            <SettingToggle title="Dictation Macros" label="Dictation macros" description="Say a phrase such as “open history” or “disable auto paste” on its own to run it instead of pasting it." checked={settings.dictationMacros.enabled} onChange={() => onUpdateSettings({ dictationMacros: { ...settings.dictationMacros, enabled: !settings.dictationMacros.enabled } })} />
+            {settings.dictationMacros.enabled && (
+              <div className="ml-3 space-y-3 border-l border-outline-variant/30 pl-3">
+                <SettingToggle title="Confirm Before Running" label="Confirm dictation macros" description="Ask before a macro runs, so a stray phrase never changes settings." checked={settings.dictationMacros.confirm} onChange={() => onUpdateSettings({ dictationMacros: { ...settings.dictationMacros, confirm: !settings.dictationMacros.confirm } })} />
+                <DictationMacrosEditor macros={settings.dictationMacros.macros} presets={settings.configPresets} disabled={isRecording} onChange={(macros) => onUpdateSettings({ dictationMacros: { ...settings.dictationMacros, macros } })} />
+              </div>
+            )}
+            <SettingToggle title="Redaction" label="Redaction" description="Mask profanity, listed words, and patterns before pasting, saving, and history." checked={settings.redaction.enabled} onChange={() => onUpdateSettings({ redaction: { ...settings.redaction, enabled: !settings.redaction.enabled } })} />
+            {settings.redaction.enabled && <RedactionEditor value={settings.redaction} disabled={isRecording} onChange={(redaction) => onUpdateSettings({ redaction })} />}
             <div className="border-t border-outline-variant/20 pt-4">
               <h2 className="mb-3 text-sm font-medium text-on-surface">Knowledge</h2>
               <KnowledgeManager active={isOpen && activeCat === 'text-vocabulary'} profiles={settings.appProfiles} />
@@ -689,6 +1088,56 @@ export function SettingsPanel({
             {settings.autoPaste && saveToFile && <p role="status" className="rounded-lg border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-400">Auto-paste is paused; the stored preference remains on.</p>}
             {autoPasteOn && accessibilityGranted !== null && <div className={`flex items-center gap-2 text-xs ${accessibilityGranted ? 'text-emerald-600 dark:text-emerald-400' : 'text-amber-600 dark:text-amber-400'}`}><span>{accessibilityGranted ? 'Accessibility permission granted' : 'Accessibility permission required'}</span>{accessibilityGranted === false && <button type="button" onClick={requestAccessibility} className="underline">Grant</button>}</div>}
             {autoPasteOn && <PasteDelaySlider value={settings.autoPasteDelayMs} onCommit={(autoPasteDelayMs) => onUpdateSettings({ autoPasteDelayMs })} />}
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Paste Last Transcription Key</label>
+              <Select
+                value={settings.pasteLastKey ?? 'none'}
+                onChange={(value) => onUpdateSettings({ pasteLastKey: value === 'none' ? null : value as ProfileKey })}
+                items={[
+                  { value: 'none', label: 'None' },
+                  ...PROFILE_KEY_OPTIONS.filter((option) => option.value !== settings.doubleTapKey && option.value !== settings.transformHoldKey && !(settings.appendMode && option.value === settings.appendCommitKey)),
+                ]}
+              />
+              <p className="mt-1 text-xs text-on-surface-variant">Press this key to paste your latest transcription again, for example after an auto-paste failed. The menu bar icon also has Copy Last and Paste Last.</p>
+            </div>
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Transcribe Copied Audio Key</label>
+              <Select
+                value={settings.clipboardAudioKey ?? 'none'}
+                onChange={(value) => onUpdateSettings({ clipboardAudioKey: value === 'none' ? null : value as ProfileKey })}
+                items={[
+                  { value: 'none', label: 'None' },
+                  ...PROFILE_KEY_OPTIONS.filter((option) => option.value !== settings.doubleTapKey && option.value !== settings.transformHoldKey && option.value !== settings.pasteLastKey && option.value !== settings.markerKey && !(settings.appendMode && option.value === settings.appendCommitKey)),
+                ]}
+              />
+              <p className="mt-1 text-xs text-on-surface-variant">Press this key after copying an audio file, or with one selected in Finder, to transcribe it and paste the text. The menu bar icon also has Transcribe Copied Audio.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Dictate Into Murmur Note</p>
+              <div className="flex gap-2">
+                {NOTES_TARGET_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ notesTarget: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.notesTarget === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">Save dictations to today's note in the Notes tab instead of pasting them. No Text Field saves only when the focused element clearly can't take text, such as the desktop.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Dictate Over Selected Text</p>
+              <div className="flex gap-2">
+                {SELECTION_DICTATION_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ selectionDictation: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.selectionDictation === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">When text is selected as you start dictating, Replace writes the dictation over it and Rewrite treats the dictation as an instruction, such as "make this shorter", and writes the local model's rewrite over it. macOS only; Rewrite needs the local model.</p>
+            </div>
+            <SettingToggle title="Preview Before Paste" description="Show each dictation in the overlay with Accept and Discard before it reaches the clipboard. Unanswered previews are discarded after 30 seconds." checked={settings.previewBeforePaste} onChange={() => onUpdateSettings({ previewBeforePaste: !settings.previewBeforePaste })} />
+            <SettingToggle title="Match Cursor Context" description="Read the characters before the cursor and fit each paste to them: add or drop the leading space, and capitalize after a sentence end. macOS only." checked={settings.cursorContext} onChange={() => onUpdateSettings({ cursorContext: !settings.cursorContext })} />
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">After Each Paste</label>
+              <Select value={settings.trailingBehavior} onChange={(trailingBehavior) => onUpdateSettings({ trailingBehavior })} items={TRAILING_BEHAVIOR_OPTIONS} />
+              <p className="mt-1 text-xs text-on-surface-variant">What Murmur adds after the dictated text. Smart space adds a space unless the text after the cursor already starts with a space or punctuation (macOS; elsewhere it always adds one). App overrides can change this per app.</p>
+            </div>
+            <SettingToggle title="Legacy Automation" description="Paste through System Events and open System Settings with the open command. Turn on only if pasting or permission links fail." checked={settings.legacyAutomation} onChange={() => onUpdateSettings({ legacyAutomation: !settings.legacyAutomation })} />
             <SettingToggle title="Save Transcript to File" description="Write each completed transcription to a .txt file." checked={settings.saveTranscript} onChange={() => onUpdateSettings({ saveTranscript: !settings.saveTranscript })} />
             <SettingToggle title="Save Audio to File" description="Write each recording to a .wav file." checked={settings.saveAudio} onChange={() => onUpdateSettings({ saveAudio: !settings.saveAudio })} />
             {saveToFile && (
@@ -699,6 +1148,24 @@ export function SettingsPanel({
                 <p className="mt-2 text-xs text-on-surface-variant">{fileOutputDeliveryDescription(settings)}</p>
               </div>
             )}
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Notifications</p>
+              <div className="flex gap-2">
+                {NOTIFICATION_POLICY_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ notificationPolicy: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.notificationPolicy === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">Shown only while the Murmur window is hidden. Completion notifications show the first line of the transcript, which is already on your clipboard.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Focus Modes</p>
+              <FocusFilterEditor value={settings.focusFilter} onChange={(focusFilter) => onUpdateSettings({ focusFilter })} />
+            </div>
+            <SettingToggle title="Disable Murmur" description="Ignore the hotkeys and wake word and refuse new recordings until you turn this off. Same as Disable Murmur in the menu bar." checked={settings.disabled} onChange={() => onUpdateSettings({ disabled: !settings.disabled })} />
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Quiet Hours</p>
+              <QuietHoursEditor value={settings.quietHours} onChange={(quietHours) => onUpdateSettings({ quietHours })} />
+            </div>
             <div className="border-t border-outline-variant/20 pt-4">
               <h2 className="text-sm font-medium text-on-surface">App Overrides</h2>
               <p className="mt-1 mb-3 text-xs text-on-surface-variant">Override delivery and writing behavior for the frontmost macOS app.</p>
@@ -712,10 +1179,83 @@ export function SettingsPanel({
 
           <SettingsSection pageId="general" activePage={activeCat} title="General" subtitle="Startup, support, updates, and app information">
             <SettingToggle title="Launch at Login" description="Start Murmur automatically when you log in." checked={settings.launchAtLogin} onChange={() => onUpdateSettings({ launchAtLogin: !settings.launchAtLogin })} />
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Presets</p>
+              <PresetsEditor settings={settings} disabled={isRecording} onUpdateSettings={onUpdateSettings} />
+              <p className="mt-1 text-xs text-on-surface-variant">Save the model, language, text cleanup, and paste settings under a name, then switch from here, the menu bar, or a dictation macro. Hotkeys, microphones, and privacy settings stay as they are.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Show Overlay On</p>
+              <div className="flex gap-2">
+                {OVERLAY_DISPLAY_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ overlayDisplay: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.overlayDisplay === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">With more than one display: the built-in (notched) screen, whichever screen you are working on, or every screen.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Overlay Style</p>
+              <div className="flex gap-2">
+                {OVERLAY_STYLE_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ overlayStyle: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.overlayStyle === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">Automatic uses the notch where there is one and a floating pill on other screens. Drag the pill to move it.</p>
+              {settings.overlayPillPosition && <button type="button" onClick={() => onUpdateSettings({ overlayPillPosition: null })} className="mt-2 text-xs font-medium text-on-surface-variant underline hover:text-primary">Reset pill position</button>}
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">In Fullscreen Apps</p>
+              <div className="flex gap-2">
+                {OVERLAY_FULLSCREEN_OPTIONS.map((option) => (
+                  <button key={option.value} type="button" onClick={() => onUpdateSettings({ overlayFullscreen: option.value })} className={`flex-1 rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${settings.overlayFullscreen === option.value ? 'border-primary bg-primary text-on-primary' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface hover:bg-surface-container'}`}>{option.label}</button>
+                ))}
+              </div>
+              <p className="mt-1 text-xs text-on-surface-variant">When the app you are using goes fullscreen and hides the menu bar. The overlay comes back when it leaves fullscreen.</p>
+            </div>
             <button type="button" onClick={onRerunSetup} className="w-full rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-primary">Run Setup Assistant</button>
             <p className="-mt-3 text-xs text-on-surface-variant">Re-check permissions and model setup after a permission is revoked or stops working.</p>
+            <div className="space-y-3 border-t border-outline-variant/20 pt-4">
+              <h2 className="text-sm font-medium text-on-surface">Privacy</h2>
+              <MicrophonePrivacy onKilled={() => onUpdateSettings({ wakeWord: false, ambientMode: false, preRollSeconds: 0 })} />
+              <SettingToggle title="Privacy Mode" description="Keep transcript details out of the logs, including in development builds." checked={settings.privacyMode} onChange={() => onUpdateSettings({ privacyMode: !settings.privacyMode })} />
+              <SettingToggle title="Keep History" description="Save dictation history between launches. When off, history is cleared when Murmur quits." checked={settings.persistHistory} onChange={() => onUpdateSettings({ persistHistory: !settings.persistHistory })} />
+              <SettingToggle title="Encrypt History" description="Store saved history encrypted with a key kept in your Keychain instead of as plain text." checked={settings.encryptHistory} disabled={!settings.persistHistory} onChange={() => onUpdateSettings({ encryptHistory: !settings.encryptHistory })} />
+              <button type="button" aria-label={confirmPurge ? 'Confirm purge all transcripts' : 'Purge all transcripts'} onClick={() => void purgeTranscripts()} onBlur={() => setConfirmPurge(false)} className={`w-full rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${confirmPurge ? 'border-error/40 bg-error/10 text-error' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface-variant hover:bg-surface-container hover:text-error'}`}>{confirmPurge ? 'Confirm Purge' : 'Purge All Transcripts'}</button>
+              <p className="-mt-2 text-xs text-on-surface-variant">{purgeNotice ?? 'Deletes history and overwrites transcript logs on disk. This can’t be undone.'}</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Storage</p>
+              <StoragePanel retention={settings.storageRetention} onRetentionChange={(storageRetention) => onUpdateSettings({ storageRetention })} onHistoryPurged={onClearHistory} />
+              <p className="mt-1 text-xs text-on-surface-variant">Purging can’t be undone. Downloads and audio saving are refused when the disk is nearly full. Daily cleanup never touches models or history, and logs still in use are kept.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Sync &amp; Backup</p>
+              <SyncPanel settings={settings} onUpdateSettings={onUpdateSettings} historyEntries={historyEntries} onImportHistory={onImportHistory} syncError={dataFolderError} />
+              <p className="mt-1 text-xs text-on-surface-variant">When a folder from another Mac is chosen, its settings replace these and its history is merged in. After that the newest settings win. Models and logs stay on each Mac.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Crash Reports</p>
+              <CrashReportsPanel />
+              <p className="mt-1 text-xs text-on-surface-variant">Reports stay on this Mac. Nothing is shared until you review a report and save a copy.</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Keyboard Diagnostics</p>
+              <KeyboardDiagnostics disabled={accessibilityGranted === false} />
+              <p className="mt-1 text-xs text-on-surface-variant">Reproduce a missed double-tap or hold while recording, then attach the copied report to your bug report. Only modifier keys are named; anything you type shows as "other".</p>
+            </div>
+            <div>
+              <p className="mb-2 text-sm font-medium text-on-surface">Support Bundle</p>
+              <SupportBundlePanel />
+              <p className="mt-1 text-xs text-on-surface-variant">Only the sections you tick are collected. The zip stays on your Desktop until you attach it somewhere. A report adds versions, permission states, model names, and timings under your description, never logs or transcripts.</p>
+            </div>
             <button type="button" onClick={onViewLogs} className="w-full rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-primary">View Logs</button>
             <button type="button" aria-label={confirmReset ? 'Confirm reset statistics' : 'Reset statistics'} onClick={resetStats} className={`w-full rounded-lg border px-3 py-2 text-xs font-medium transition-colors ${confirmReset ? 'border-error/40 bg-error/10 text-error' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface-variant hover:bg-surface-container hover:text-primary'}`}>{confirmReset ? 'Confirm Reset' : 'Reset Stats'}</button>
+            <TypingSpeedSlider value={settings.typingWpm} onCommit={(typingWpm) => onUpdateSettings({ typingWpm })} />
+            <div>
+              <label className="mb-2 block text-sm font-medium text-on-surface">Update Channel</label>
+              <Select value={settings.updateChannel} onChange={(updateChannel) => onUpdateSettings({ updateChannel })} items={UPDATE_CHANNEL_OPTIONS} />
+              <p className="mt-1 text-xs text-on-surface-variant">Beta builds arrive sooner and may be less stable. Updates install only when no dictation is running.</p>
+            </div>
             <div>
               <button type="button" onClick={() => void onCheckForUpdate()} disabled={updateStatus.phase === 'checking' || updateStatus.phase === 'downloading'} className="w-full rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50">{updateStatus.phase === 'checking' ? 'Checking…' : 'Check for Updates'}</button>
               {updateStatus.phase === 'up-to-date' && <p className="mt-1.5 text-xs text-emerald-600 dark:text-emerald-400">You’re up to date.</p>}
@@ -0,0 +1,115 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { Select } from '../ui/Select';
+import { RETENTION_AGE_OPTIONS, RETENTION_SIZE_OPTIONS, type StorageRetentionSettings } from '../../lib/settings';
+import { describeCleanup, formatStorageSize, getDiskUsageReport, purgeStorage, runStorageCleanup, STORAGE_CATEGORIES, type DiskUsageReport, type StorageCategory, type StorageCleaned } from '../../lib/storage';
+
+/** How much each kind of Murmur data takes up, with a purge button per category and the automatic cleanup limits. */
+export function StoragePanel({ retention, onRetentionChange, onHistoryPurged }: {
+  retention: StorageRetentionSettings;
+  onRetentionChange: (retention: StorageRetentionSettings) => void;
+  onHistoryPurged: () => void;
+}) {
+  const [report, setReport] = useState<DiskUsageReport | null>(null);
+  const [confirming, setConfirming] = useState<StorageCategory | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = () => getDiskUsageReport().then(setReport).catch((cause: unknown) => setError(String(cause)));
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    void refresh();
+    listen<StorageCleaned>('storage-cleaned', (event) => {
+      setNotice(describeCleanup(event.payload));
+      void refresh();
+    })
+      .then((fn) => { if (cancelled) { fn(); } else { unlisten = fn; } })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const cleanUpNow = async () => {
+    setError(null);
+    try {
+      setNotice(describeCleanup(await runStorageCleanup()));
+    } catch (cause) {
+      setError(String(cause));
+    }
+    void refresh();
+  };
+
+  const purge = async (category: StorageCategory) => {
+    if (confirming !== category) {
+      setConfirming(category);
+      setNotice(null);
+      return;
+    }
+    setConfirming(null);
+    setError(null);
+    try {
+      const result = await purgeStorage(category);
+      if (category === 'history') onHistoryPurged();
+      setNotice(`Freed ${formatStorageSize(result.freedBytes)} from ${result.removedFiles} ${result.removedFiles === 1 ? 'file' : 'files'}.`);
+    } catch (cause) {
+      setError(String(cause));
+    }
+    void refresh();
+  };
+
+  return (
+    <div className="space-y-2">
+      <ul className="space-y-2">
+        {STORAGE_CATEGORIES.map(({ category, label, description }) => {
+          const usage = report?.categories.find((entry) => entry.category === category);
+          return (
+            <li key={category} className="flex items-start gap-2 text-xs">
+              <span className="flex-1">
+                <span className="font-medium text-on-surface">{label}</span>{' '}
+                <span className="text-on-surface-variant">{usage ? formatStorageSize(usage.bytes) : '…'}</span>
+                <span className="block text-on-surface-variant">{description}</span>
+              </span>
+              <button type="button" aria-label={confirming === category ? `Confirm purge ${label}` : `Purge ${label}`} onClick={() => void purge(category)} onBlur={() => setConfirming(null)} disabled={!usage?.bytes} className={`rounded-lg border px-2 py-1 font-medium transition-colors disabled:cursor-not-allowed disabled:opacity-50 ${confirming === category ? 'border-error/40 bg-error/10 text-error' : 'border-outline-variant/30 bg-surface-container-lowest text-on-surface-variant hover:bg-surface-container hover:text-error'}`}>
+                {confirming === category ? 'Confirm' : 'Purge'}
+              </button>
+            </li>
+          );
+        })}
+      </ul>
+      {report && (
+        <p className="text-xs text-on-surface-variant">
+          Murmur uses {formatStorageSize(report.totalBytes)}{report.availableBytes !== null && `; ${formatStorageSize(report.availableBytes)} free on disk`}.
+        </p>
+      )}
+      <label className="flex items-start gap-2 text-xs text-on-surface">
+        <input type="checkbox" checked={retention.enabled} onChange={() => onRetentionChange({ ...retention, enabled: !retention.enabled })} className="mt-0.5 accent-primary" />
+        <span>Clean up old logs every day</span>
+      </label>
+      {retention.enabled && (
+        <div className="ml-3 space-y-2 border-l border-outline-variant/30 pl-3">
+          <div className="flex gap-2">
+            <div className="flex-1">
+              <label className="mb-1 block text-xs text-on-surface-variant">Keep at most</label>
+              <Select aria-label="Storage size limit" value={String(retention.maxTotalMb)} onChange={(value) => onRetentionChange({ ...retention, maxTotalMb: Number(value) })} items={RETENTION_SIZE_OPTIONS.map((option) => ({ value: String(option.value), label: option.label }))} />
+            </div>
+            <div className="flex-1">
+              <label className="mb-1 block text-xs text-on-surface-variant">Remove files older than</label>
+              <Select aria-label="Storage age limit" value={String(retention.maxAgeDays)} onChange={(value) => onRetentionChange({ ...retention, maxAgeDays: Number(value) })} items={RETENTION_AGE_OPTIONS.map((option) => ({ value: String(option.value), label: option.label }))} />
+            </div>
+          </div>
+          <label className="flex items-start gap-2 text-xs text-on-surface">
+            <input type="checkbox" checked={retention.includeRecordings} onChange={() => onRetentionChange({ ...retention, includeRecordings: !retention.includeRecordings })} className="mt-0.5 accent-primary" />
+            <span>Include saved recordings</span>
+          </label>
+          <button type="button" onClick={() => void cleanUpNow()} className="text-xs font-medium text-on-surface-variant underline hover:text-primary">Clean Up Now</button>
+        </div>
+      )}
+      {notice && <p className="text-xs text-emerald-600 dark:text-emerald-400">{notice}</p>}
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
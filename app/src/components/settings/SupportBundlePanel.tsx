@@ -0,0 +1,79 @@
+import { useState } from 'react';
+import { open } from '@tauri-apps/plugin-dialog';
+import { BUNDLE_SECTIONS, generateSupportBundle, reportIssue, type BundleSection, type IssueDestination, type SupportBundle } from '../../lib/supportBundle';
+
+const buttonClass = 'w-full rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50';
+
+/** Asks which diagnostics to include, writes them into a support bundle on the Desktop, and files an issue with it. */
+export function SupportBundlePanel() {
+  const [chosen, setChosen] = useState<BundleSection[]>([]);
+  const [running, setRunning] = useState(false);
+  const [bundle, setBundle] = useState<SupportBundle | null>(null);
+  const [description, setDescription] = useState('');
+  const [notice, setNotice] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const toggle = (section: BundleSection) => {
+    setBundle(null);
+    setChosen((current) => current.includes(section) ? current.filter((s) => s !== section) : [...current, section]);
+  };
+
+  const generate = async () => {
+    setRunning(true);
+    setError(null);
+    setNotice(null);
+    setBundle(null);
+    try {
+      setBundle(await generateSupportBundle(chosen));
+    } catch (cause) {
+      setError(String(cause));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const report = async (kind: IssueDestination['kind']) => {
+    setError(null);
+    setNotice(null);
+    let destination: IssueDestination = { kind: 'github' };
+    if (kind === 'folder') {
+      const path = await open({ directory: true, multiple: false });
+      if (typeof path !== 'string') return;
+      destination = { kind: 'folder', path };
+    }
+    try {
+      const result = await reportIssue(description, destination, bundle?.path);
+      setNotice(result.url
+        ? `Opened a new GitHub issue.${bundle ? ' Drag the support bundle into it before submitting.' : ''}`
+        : `Saved ${result.saved.length === 1 ? 'the report' : 'the report and bundle'} to the folder.`);
+    } catch (cause) {
+      setError(String(cause));
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <ul className="space-y-1">
+        {BUNDLE_SECTIONS.map(({ section, label, description }) => (
+          <li key={section}>
+            <label className="flex items-start gap-2 text-xs text-on-surface">
+              <input type="checkbox" checked={chosen.includes(section)} onChange={() => toggle(section)} className="mt-0.5 accent-primary" />
+              <span><span className="font-medium">{label}</span> <span className="text-on-surface-variant">{description}</span></span>
+            </label>
+          </li>
+        ))}
+      </ul>
+      <button type="button" onClick={() => void generate()} disabled={running || chosen.length === 0} className={buttonClass}>
+        {running ? 'Creating Bundle…' : 'Create Support Bundle'}
+      </button>
+      {bundle && <p className="text-xs text-emerald-600 dark:text-emerald-400">Saved {bundle.path}. Attach it to your GitHub issue.</p>}
+      <textarea value={description} onChange={(event) => setDescription(event.target.value)} rows={3} placeholder="What went wrong, and what were you doing?" aria-label="Issue description" className="w-full rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs text-on-surface" />
+      <div className="flex gap-2">
+        <button type="button" onClick={() => void report('github')} disabled={!description.trim()} className={buttonClass}>Report on GitHub</button>
+        <button type="button" onClick={() => void report('folder')} disabled={!description.trim()} className={buttonClass}>Save to Folder…</button>
+      </div>
+      {notice && <p className="text-xs text-emerald-600 dark:text-emerald-400">{notice}</p>}
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,103 @@
+import { useState } from 'react';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import type { HistoryEntry } from '../../lib/history';
+import { exportHistory, exportSettings, importHistory, importSettings } from '../../lib/portable';
+import type { Settings } from '../../lib/settings';
+
+const buttonClass = 'flex-1 rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant transition-colors hover:bg-surface-container hover:text-primary';
+const ARCHIVE_FILTERS = [{ name: 'Murmur export', extensions: ['json'] }];
+
+/** Export and import settings and history, and the sync folder shared with other Macs. */
+export function SyncPanel({ settings, onUpdateSettings, historyEntries, onImportHistory, syncError }: {
+  settings: Settings;
+  onUpdateSettings: (updates: Partial<Settings>) => void;
+  historyEntries: HistoryEntry[];
+  onImportHistory: (entries: HistoryEntry[]) => void;
+  syncError: string | null;
+}) {
+  const [notice, setNotice] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async (action: () => Promise<string | null>) => {
+    setNotice(null);
+    setError(null);
+    try {
+      setNotice(await action());
+    } catch (cause) {
+      setError(String(cause));
+    }
+  };
+
+  const chooseExportSettings = () => run(async () => {
+    const path = await save({ defaultPath: 'murmur-settings.json', filters: ARCHIVE_FILTERS });
+    if (typeof path !== 'string') return null;
+    await exportSettings(path, settings);
+    return 'Settings exported.';
+  });
+
+  const chooseImportSettings = () => run(async () => {
+    const path = await open({ multiple: false, directory: false, filters: ARCHIVE_FILTERS });
+    if (typeof path !== 'string') return null;
+    onUpdateSettings(await importSettings(path));
+    return 'Settings imported. Microphones, folders, and the overlay position on this Mac were kept.';
+  });
+
+  const chooseExportHistory = () => run(async () => {
+    const path = await save({ defaultPath: 'murmur-history.json', filters: ARCHIVE_FILTERS });
+    if (typeof path !== 'string') return null;
+    const count = await exportHistory(path, historyEntries);
+    return `Exported ${count} ${count === 1 ? 'entry' : 'entries'}.`;
+  });
+
+  const chooseImportHistory = () => run(async () => {
+    const path = await open({ multiple: false, directory: false, filters: ARCHIVE_FILTERS });
+    if (typeof path !== 'string') return null;
+    const entries = await importHistory(path);
+    onImportHistory(entries);
+    return `Merged ${entries.length} ${entries.length === 1 ? 'entry' : 'entries'} into history.`;
+  });
+
+  const chooseFolder = () => run(async () => {
+    const folder = await open({ directory: true, multiple: false });
+    if (typeof folder !== 'string') return null;
+    onUpdateSettings({ dataFolder: folder });
+    return null;
+  });
+
+  const historyShared = settings.persistHistory && !settings.encryptHistory;
+
+  return (
+    <div className="space-y-2">
+      <div className="flex gap-2">
+        <button type="button" onClick={() => void chooseExportSettings()} className={buttonClass}>Export Settings…</button>
+        <button type="button" onClick={() => void chooseImportSettings()} className={buttonClass}>Import Settings…</button>
+      </div>
+      <div className="flex gap-2">
+        <button type="button" onClick={() => void chooseExportHistory()} className={buttonClass}>Export History…</button>
+        <button type="button" onClick={() => void chooseImportHistory()} className={buttonClass}>Import History…</button>
+      </div>
+      <div className="rounded-lg border border-outline-variant/25 bg-surface-container-lowest p-2.5">
+        <div className="flex items-center gap-2">
+          <p className="min-w-0 flex-1 truncate text-xs text-on-surface" title={settings.dataFolder || undefined}>
+            {settings.dataFolder || 'No sync folder'}
+          </p>
+          <button type="button" onClick={() => void chooseFolder()} className="rounded-md px-2 py-0.5 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary">
+            {settings.dataFolder ? 'Change…' : 'Choose Folder…'}
+          </button>
+          {settings.dataFolder && (
+            <button type="button" onClick={() => onUpdateSettings({ dataFolder: '' })} className="rounded-md px-2 py-0.5 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error">
+              Stop Syncing
+            </button>
+          )}
+        </div>
+        <p className="mt-1 text-xs text-on-surface-variant">
+          {settings.dataFolder && !historyShared
+            ? 'Settings, vocabulary, and rules sync. History stays on this Mac while it is encrypted or not kept.'
+            : 'Pick a folder in iCloud Drive, Syncthing, or Dropbox on each Mac to share settings, vocabulary, rules, and history.'}
+        </p>
+      </div>
+      {notice && <p className="text-xs text-emerald-600 dark:text-emerald-400">{notice}</p>}
+      {(error ?? syncError) && <p role="alert" className="text-xs text-error">{error ?? syncError}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,81 @@
+import { useCallback, useEffect, useState } from 'react';
+import { open } from '@tauri-apps/plugin-dialog';
+import { listTranscriptionPlugins, registerTranscriptionPlugin, unregisterTranscriptionPlugin, type TranscriptionPlugin } from '../../lib/dictation';
+import type { ExternalModelOption } from '../../lib/settings';
+
+/** Register and remove external transcription engines; hands back a newly registered plugin's model name. */
+export function TranscriptionPluginsEditor({ disabled, onRegistered }: {
+  disabled: boolean;
+  onRegistered: (model: ExternalModelOption) => void;
+}) {
+  const [plugins, setPlugins] = useState<TranscriptionPlugin[]>([]);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(() => {
+    listTranscriptionPlugins()
+      .then((registered) => setPlugins(registered ?? []))
+      .catch(() => setPlugins([]));
+  }, []);
+  useEffect(refresh, [refresh]);
+
+  const addPlugin = async () => {
+    let selected: string | string[] | null;
+    try {
+      selected = await open({ multiple: false, filters: [{ name: 'Plugin manifest', extensions: ['json'] }] });
+    } catch {
+      return;
+    }
+    if (typeof selected !== 'string') return;
+    setError(null);
+    setBusy(true);
+    try {
+      const model = await registerTranscriptionPlugin(selected);
+      refresh();
+      onRegistered(model);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const removePlugin = async (plugin: TranscriptionPlugin) => {
+    setError(null);
+    try {
+      await unregisterTranscriptionPlugin(plugin.id);
+      refresh();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      {plugins.map((plugin) => (
+        <div key={plugin.id} className="flex items-center gap-2 rounded-lg border border-outline-variant/25 bg-surface-container-lowest p-2.5">
+          <p className="min-w-0 flex-1 truncate text-xs font-medium text-on-surface" title={plugin.command}>{plugin.name}</p>
+          <button
+            type="button"
+            onClick={() => void removePlugin(plugin)}
+            disabled={disabled || busy}
+            aria-label={`Remove ${plugin.name}`}
+            className="rounded-md px-2 py-0.5 text-xs text-on-surface-variant hover:bg-surface-container hover:text-error disabled:cursor-not-allowed disabled:opacity-50"
+          >
+            Remove
+          </button>
+        </div>
+      ))}
+      <button
+        type="button"
+        onClick={() => void addPlugin()}
+        disabled={disabled || busy}
+        className="rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 text-xs font-medium text-on-surface-variant hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+      >
+        {busy ? 'Starting plugin…' : 'Add Plugin…'}
+      </button>
+      {error && <p role="alert" className="text-xs text-error">{error}</p>}
+      <p className="text-xs text-on-surface-variant">Choose a plugin's plugin.json. Murmur starts the plugin and sends it each recording; whether audio leaves this Mac depends on the plugin.</p>
+    </div>
+  );
+}
@@ -62,7 +62,7 @@ describe('VoiceCommandsManager', () => {
     root = createRoot(container);
     await act(async () => root.render(<VoiceCommandsManager active globallyEnabled profiles={[{
       bundleId: 'com.apple.mail', label: 'Mail', autoPasteOverride: null, cleanupOverride: null,
-      smartFormattingOverride: null, cliFormattingOverride: null, writingStyle: null,
+      smartFormattingOverride: null, cliFormattingOverride: null, trailingOverride: null, writingStyle: null,
       ideContextEnabled: false, ideProjectRoots: [],
     }]} />));
   });
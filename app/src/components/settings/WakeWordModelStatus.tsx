@@ -0,0 +1,57 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { downloadWakeWordModel, getWakeWordStatus, type WakeWordStatus } from '../../lib/dictation';
+import { modelDownloadLabel, modelDownloadPercent, type ModelDownloadProgress } from '../../lib/modelDownload';
+
+/** Shows whether the wake word detector is running and offers the model download it needs. */
+export function WakeWordModelStatus({ onInstalled }: { onInstalled: () => void }) {
+  const [status, setStatus] = useState<WakeWordStatus | null>(null);
+  const [progress, setProgress] = useState<ModelDownloadProgress | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getWakeWordStatus().then(setStatus).catch(() => {});
+  }, []);
+
+  const download = async () => {
+    if (progress) return;
+    setError(null);
+    setProgress({ received: 0, total: 0, phase: 'downloading' });
+    let unlisten: (() => void) | null = null;
+    try {
+      unlisten = await listen<ModelDownloadProgress>('download-progress', (event) => setProgress(event.payload));
+      await downloadWakeWordModel();
+      onInstalled();
+      setStatus(await getWakeWordStatus());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      unlisten?.();
+      setProgress(null);
+    }
+  };
+
+  if (!status) return null;
+  const percent = progress ? modelDownloadPercent(progress) : null;
+  return (
+    <div className="space-y-2 text-xs text-on-surface-variant">
+      {status.modelInstalled ? (
+        <p>{status.listening ? 'Listening for "Hey Murmur".' : 'Wake word model installed.'}</p>
+      ) : (
+        <div className="flex items-center gap-2">
+          <span>The wake word needs a small model (about 13 MB).</span>
+          <button
+            type="button"
+            onClick={() => void download()}
+            disabled={progress !== null}
+            className="ml-auto rounded-lg border border-outline-variant/30 bg-surface-container-lowest px-3 py-2 font-medium hover:bg-surface-container hover:text-primary disabled:cursor-not-allowed disabled:opacity-50"
+          >
+            Download
+          </button>
+        </div>
+      )}
+      {progress && <p>{modelDownloadLabel(progress)}{percent === null ? '…' : ` ${percent}%`}</p>}
+      {error && <p role="alert" className="text-error">{error}</p>}
+    </div>
+  );
+}
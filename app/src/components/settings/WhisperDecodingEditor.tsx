@@ -0,0 +1,89 @@
+import { useEffect, useState } from 'react';
+import { DEFAULT_SETTINGS, type WhisperDecodingSettings } from '../../lib/settings';
+import { Select } from '../ui/Select';
+
+const SAMPLING_OPTIONS: { value: WhisperDecodingSettings['sampling']; label: string }[] = [
+  { value: 'greedy', label: 'Greedy (fastest)' },
+  { value: 'beam_search', label: 'Beam search (more accurate)' },
+];
+
+const SINGLE_SEGMENT_OPTIONS: { value: WhisperDecodingSettings['singleSegment']; label: string }[] = [
+  { value: 'auto', label: 'Auto (clips up to 12s)' },
+  { value: 'always', label: 'Always' },
+  { value: 'never', label: 'Never' },
+];
+
+const BEAM_SIZE_OPTIONS = [2, 3, 4, 5, 6, 7, 8].map((size) => ({ value: String(size), label: String(size) }));
+
+const THREAD_OPTIONS = [
+  { value: '0', label: 'Automatic (one per performance core)' },
+  ...Array.from({ length: 16 }, (_, index) => ({ value: String(index + 1), label: String(index + 1) })),
+];
+
+function UnitSlider({ label, value, description, onCommit }: {
+  label: string;
+  value: number;
+  description: string;
+  onCommit: (value: number) => void;
+}) {
+  const [draft, setDraft] = useState(value);
+  useEffect(() => setDraft(value), [value]);
+  return (
+    <div>
+      <div className="mb-1 flex items-center justify-between">
+        <label className="text-xs text-on-surface-variant">{label}</label>
+        <span className="text-xs font-medium text-on-surface">{draft.toFixed(2)}</span>
+      </div>
+      <input
+        type="range"
+        aria-label={label}
+        min={0}
+        max={1}
+        step={0.05}
+        value={draft}
+        onChange={(event) => setDraft(Number(event.target.value))}
+        onPointerUp={() => onCommit(draft)}
+        className="h-1.5 w-full cursor-pointer appearance-none rounded-full bg-surface-container-highest accent-primary"
+      />
+      <p className="mt-1 text-xs text-on-surface-variant">{description}</p>
+    </div>
+  );
+}
+
+export function WhisperDecodingEditor({ value, disabled, onChange }: {
+  value: WhisperDecodingSettings;
+  disabled: boolean;
+  onChange: (value: WhisperDecodingSettings) => void;
+}) {
+  const update = (patch: Partial<WhisperDecodingSettings>) => onChange({ ...value, ...patch });
+  return (
+    <details className="rounded-xl border border-outline-variant/30 p-3">
+      <summary className="cursor-pointer text-sm font-medium text-on-surface">Advanced Whisper Decoding</summary>
+      <div className="mt-3 space-y-4">
+        <div>
+          <label className="mb-1 block text-xs text-on-surface-variant">Sampling</label>
+          <Select aria-label="Sampling" value={value.sampling} onChange={(sampling) => update({ sampling })} disabled={disabled} items={SAMPLING_OPTIONS} />
+        </div>
+        {value.sampling === 'beam_search' && (
+          <div>
+            <label className="mb-1 block text-xs text-on-surface-variant">Beam Size</label>
+            <Select aria-label="Beam size" value={String(value.beamSize)} onChange={(beamSize) => update({ beamSize: Number(beamSize) })} disabled={disabled} items={BEAM_SIZE_OPTIONS} />
+            <p className="mt-1 text-xs text-on-surface-variant">More beams improve hard audio but slow every decode.</p>
+          </div>
+        )}
+        <UnitSlider label="Temperature" value={value.temperature} description="0 is deterministic; higher values add variety." onCommit={(temperature) => update({ temperature })} />
+        <UnitSlider label="No-Speech Threshold" value={value.noSpeechThreshold} description="Lower values drop more segments that look like silence." onCommit={(noSpeechThreshold) => update({ noSpeechThreshold })} />
+        <div>
+          <label className="mb-1 block text-xs text-on-surface-variant">Threads</label>
+          <Select aria-label="Threads" value={String(value.threads)} onChange={(threads) => update({ threads: Number(threads) })} disabled={disabled} items={THREAD_OPTIONS} />
+          <p className="mt-1 text-xs text-on-surface-variant">Run a benchmark in Performance to find the fastest count for this Mac.</p>
+        </div>
+        <div>
+          <label className="mb-1 block text-xs text-on-surface-variant">Single Segment</label>
+          <Select aria-label="Single segment" value={value.singleSegment} onChange={(singleSegment) => update({ singleSegment })} disabled={disabled} items={SINGLE_SEGMENT_OPTIONS} />
+        </div>
+        <button type="button" disabled={disabled} onClick={() => onChange(DEFAULT_SETTINGS.whisperDecoding)} className="text-xs font-medium text-on-surface-variant underline hover:text-primary disabled:cursor-not-allowed disabled:opacity-50">Reset to defaults</button>
+      </div>
+    </details>
+  );
+}
@@ -93,6 +93,24 @@ describe('benchmark report storage', () => {
     expect(loadBenchmarkReports()).toEqual([previous]);
   });
 
+  it('keeps a thread recommendation and drops a malformed one', () => {
+    const tuned = versionedReport('2026-07-20T12:00:00Z');
+    tuned.configuration = { ...tuned.configuration!, whisperThreads: 8 };
+    tuned.recommendations = {
+      ...tuned.recommendations,
+      threads: {
+        modelName: 'tiny.en', fixtureId: 'medium', performanceCores: 8, threads: 4,
+        timings: [{ threads: 4, warmMedianMs: 210 }, { threads: 8, warmMedianMs: 205 }],
+      },
+    };
+    const malformed = {
+      ...versionedReport('2026-07-20T13:00:00Z'),
+      recommendations: { fastest: null, mostAccurate: null, balanced: null, threads: { threads: 'four' } },
+    };
+    localStorage.setItem('murmur-benchmark-reports', JSON.stringify([tuned, malformed]));
+    expect(loadBenchmarkReports()).toEqual([tuned]);
+  });
+
   it('rejects incomplete or unknown versioned metadata instead of presenting it as trustworthy', () => {
     const incomplete = { ...versionedReport('2026-07-20T12:00:00Z'), configuration: undefined };
     const unknown = { ...versionedReport('2026-07-20T13:00:00Z'), reportVersion: 3 };
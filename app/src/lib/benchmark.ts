@@ -19,7 +19,7 @@ export interface BenchmarkProgress {
   modelName: string;
   modelLabel: string;
   fixture: string | null;
-  phase: 'priming' | 'loading' | 'warming' | 'measuring' | 'complete';
+  phase: 'priming' | 'loading' | 'warming' | 'measuring' | 'tuning' | 'complete';
 }
 
 export interface BenchmarkActivity {
@@ -120,15 +120,29 @@ export interface BenchmarkReport {
     percentileMethod: string;
     modelRunOrder: string[];
     sharedInitOrder: string[];
+    /** Decode threads the Whisper runs used; absent before thread tuning. */
+    whisperThreads?: number;
   };
   results: BenchmarkModelResult[];
   recommendations: {
     fastest: string | null;
     mostAccurate: string | null;
     balanced: string | null;
+    /** Absent before thread tuning; null when no Whisper model finished. */
+    threads?: BenchmarkThreadRecommendation | null;
   };
 }
 
+/** The Whisper decode thread count that ran one clip fastest. */
+export interface BenchmarkThreadRecommendation {
+  modelName: string;
+  fixtureId: string;
+  /** What automatic threads (`whisperDecoding.threads` 0) uses. */
+  performanceCores: number;
+  threads: number;
+  timings: { threads: number; warmMedianMs: number }[];
+}
+
 export const MAX_SAVED_BENCHMARK_REPORTS = 10;
 const REPORT_KEY = 'murmur-benchmark-report';
 const REPORTS_KEY = 'murmur-benchmark-reports';
@@ -225,7 +239,19 @@ function isConfiguration(value: unknown): boolean {
     && Array.isArray(value.modelRunOrder)
     && value.modelRunOrder.every((model) => typeof model === 'string')
     && Array.isArray(value.sharedInitOrder)
-    && value.sharedInitOrder.every((model) => typeof model === 'string');
+    && value.sharedInitOrder.every((model) => typeof model === 'string')
+    && (value.whisperThreads === undefined || isNumber(value.whisperThreads));
+}
+
+function isThreadRecommendation(value: unknown): boolean {
+  if (value === undefined || value === null) return true;
+  return isRecord(value)
+    && typeof value.modelName === 'string'
+    && typeof value.fixtureId === 'string'
+    && isNumber(value.performanceCores)
+    && isNumber(value.threads)
+    && Array.isArray(value.timings)
+    && value.timings.every((timing) => isRecord(timing) && isNumber(timing.threads) && isNumber(timing.warmMedianMs));
 }
 
 function isBenchmarkReport(value: unknown): value is BenchmarkReport {
@@ -248,7 +274,8 @@ function isBenchmarkReport(value: unknown): value is BenchmarkReport {
     && value.results.every(isModelResult)
     && isNullableString(value.recommendations.fastest)
     && isNullableString(value.recommendations.mostAccurate)
-    && isNullableString(value.recommendations.balanced);
+    && isNullableString(value.recommendations.balanced)
+    && isThreadRecommendation(value.recommendations.threads);
 }
 
 export function addBenchmarkReport(
@@ -354,6 +381,35 @@ export function runBenchmark(modelNames: string[], preset: BenchmarkPreset): Pro
   return invoke('run_benchmark', { request: { modelNames, preset } });
 }
 
+export interface SelfTestClip {
+  fixtureId: string;
+  label: string;
+  reference: string;
+  transcript: string;
+  wordErrorRate: number;
+  normalizedWordErrorRate: number;
+  characterErrorRate: number;
+}
+
+/** Bundled reference clips transcribed once by one model and scored. */
+export interface SelfTestReport {
+  modelName: string;
+  label: string;
+  createdAt: string;
+  elapsedMs: number;
+  wordErrorRate: number;
+  normalizedWordErrorRate: number;
+  characterErrorRate: number;
+  maxNormalizedWordErrorRate: number;
+  passed: boolean;
+  clips: SelfTestClip[];
+}
+
+/** Omit `modelName` to test the selected model on the dictation runtime. */
+export function runAccuracySelfTest(modelName?: string): Promise<SelfTestReport> {
+  return invoke('run_accuracy_self_test', { modelName: modelName ?? null });
+}
+
 export function cancelBenchmark(): Promise<boolean> {
   return invoke('cancel_benchmark');
 }
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Options accepted by `configure_dictation`. Every field is optional; a
+ * missing or null field leaves the current setting unchanged.
+ */
+export type ConfigureOptions = { model?: string, language?: string, autoPaste?: boolean, autoPasteDelayMs?: number, vadSensitivity?: number, idleTimeoutMinutes?: number, backendKeepAliveMb?: number, customVocabulary?: string, vocabularyEntries?: Array<unknown>, smartPunctuation?: boolean, saveTranscript?: boolean, saveAudio?: boolean, outputDir?: string, appProfiles?: Array<unknown>, dictationProfiles?: Array<unknown>, rewriteTemplates?: Array<unknown>, replacementRules?: Array<unknown>, dictationMacros?: unknown, contextBiasing?: unknown, injectBlocklist?: Array<string>, voiceCommandsEnabled?: boolean, voiceCommands?: Array<unknown>, cleanupEnabled?: boolean, smartFormattingEnabled?: boolean, cleanupRemoveFiller?: boolean, cleanupCapitalize?: boolean, codeVocabEnabled?: boolean, codeVocabFolder?: string, correctionEnabled?: boolean, correctionFuzzy?: boolean, notificationPolicy?: string, focusFilter?: unknown, interruptedRecordingAction?: string, whisperDecoding?: unknown, hallucinationFilter?: boolean, redaction?: unknown, adaptiveModel?: unknown, latencyBudget?: unknown, dualModel?: unknown, confidenceRetry?: unknown, alternatives?: unknown, learnFromCorrections?: boolean, privacyMode?: boolean, keepAudioOnDevice?: boolean, appendMode?: boolean, legacyAutomation?: boolean, previewBeforePaste?: boolean, cursorContext?: boolean, trailingBehavior?: string, notesTarget?: string, selectionDictation?: string, };
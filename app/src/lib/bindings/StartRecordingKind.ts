@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a [`StartRecordingResult`] reports. Serialized as its `type` field.
+ */
+export type StartRecordingKind = "recording_started" | "app_disabled" | "busy_transcribing_file" | "busy_benchmarking" | "busy_transforming" | "already_recording" | "already_processing";
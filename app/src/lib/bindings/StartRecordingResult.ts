@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StartRecordingKind } from "./StartRecordingKind";
+
+/**
+ * Reply to `start_native_recording`. Every kind other than
+ * `recording_started` means the start was refused.
+ */
+export type StartRecordingResult = { type: StartRecordingKind, 
+/**
+ * Dictation state after the command settled.
+ */
+state: string, };
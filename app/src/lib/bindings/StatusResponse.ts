@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Reply to `get_status`.
+ */
+export type StatusResponse = { type: "status", state: string, model: string, language: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a [`TranscriptionResult`] reports. Serialized as its `type` field.
+ */
+export type TranscriptionKind = "transcription" | "file_transcription" | "committed" | "already_processing" | "not_recording" | "queued";
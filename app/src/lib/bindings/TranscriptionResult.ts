@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TranscriptionKind } from "./TranscriptionKind";
+
+/**
+ * Reply to the commands that finish a dictation: `process_audio`,
+ * `stop_native_recording`, `transcribe_file`, and `commit_append_buffer`.
+ */
+export type TranscriptionResult = { type: TranscriptionKind, text: string | null, 
+/**
+ * Dictation state after the command settled.
+ */
+state: string | null, fileRunId: number | null, 
+/**
+ * Recording id of a queued transcription; matches the `recordingId` of
+ * its result event.
+ */
+jobId: number | null, 
+/**
+ * Decoded audio length in seconds (file transcription only).
+ */
+duration: number | null, 
+/**
+ * Where the file was transcribed (file transcription only).
+ */
+processedOn: 'device' | 'cloud' | 'plugin' | null, 
+/**
+ * The cloud host or plugin name, when not on this Mac.
+ */
+processedBy: string | null, };
@@ -16,6 +16,7 @@ describe('buildConfigureOptions', () => {
           cleanupOverride: null,
           smartFormattingOverride: false,
           cliFormattingOverride: true,
+          trailingOverride: null,
           writingStyle: 'code_technical',
           ideContextEnabled: false,
           ideProjectRoots: [],
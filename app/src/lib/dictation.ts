@@ -1,24 +1,37 @@
 import { invoke } from '@tauri-apps/api/core';
-import { DEFAULT_SETTINGS, Settings, AppProfile, VoiceCommand, VocabularyEntry } from './settings';
+import { DEFAULT_SETTINGS, Settings, AppProfile, DictationProfile, RewriteTemplate, ReplacementRule, DictationMacrosSettings, ContextBiasingSettings, MacroAction, VoiceCommand, VocabularyEntry, NotificationPolicy, FocusFilterSettings, InterruptedRecordingAction, NotesTarget, SelectionDictation, TrailingBehavior, WhisperDecodingSettings, RedactionSettings, AdaptiveModelSettings, LatencyBudgetSettings, DualModelSettings, ConfidenceRetrySettings, AlternativesSettings, DownloadNetworkSettings, CloudTranscriptionSettings, CustomModelOption, ExternalModelOption, HotkeyTiming } from './settings';
+import type { ConfigureOptions as ConfigurePayload } from './bindings/ConfigureOptions';
+import type { StartRecordingResult } from './bindings/StartRecordingResult';
+import type { StatusResponse } from './bindings/StatusResponse';
+import type { TranscriptionResult } from './bindings/TranscriptionResult';
 
 export interface DictationResponse {
   type: string;
   state?: string;
   text?: string;
-  model?: string;
   error?: string;
-  /** Decoded audio length in seconds (file transcription only). */
-  duration?: number;
 }
 
+/** Synthesized by the wrappers below when the command itself rejects. */
+export interface DictationError {
+  type: 'error';
+  error: string;
+}
+
+export type TranscriptionResponse = TranscriptionResult | DictationError;
+
+export type StartRecordingResponse = StartRecordingResult | DictationError;
+
 export async function initDictation(): Promise<DictationResponse> {
   return await invoke('init_dictation');
 }
 
-export async function startRecording(deviceName?: string): Promise<DictationResponse> {
+/** `profileId` names the dictation profile whose hotkey started this recording. */
+export async function startRecording(deviceName?: string, profileId?: string): Promise<StartRecordingResponse> {
   try {
     return await invoke('start_native_recording', {
       deviceName: deviceName && deviceName !== DEFAULT_SETTINGS.microphone ? deviceName : null,
+      profileId: profileId ?? null,
     });
   } catch (err) {
     const errorMessage = err instanceof Error ? err.message : String(err);
@@ -38,9 +51,10 @@ export async function startRecording(deviceName?: string): Promise<DictationResp
   }
 }
 
-export async function stopRecording(): Promise<DictationResponse> {
+export async function stopRecording(): Promise<TranscriptionResponse> {
   try {
-    // Native recording handles transcription and returns the result directly
+    // Returns as soon as the audio is handed to the transcription worker;
+    // the text arrives as a `transcription-complete` event.
     return await invoke('stop_native_recording');
   } catch (err) {
     return {
@@ -54,34 +68,194 @@ export async function cancelRecording(): Promise<void> {
   await invoke('cancel_native_recording');
 }
 
-export async function getStatus(): Promise<DictationResponse> {
+export async function getStatus(): Promise<StatusResponse> {
   return await invoke('get_status');
 }
 
-export interface ConfigureOptions {
-  model?: string;
-  language?: string;
-  autoPaste?: boolean;
-  autoPasteDelayMs?: number;
-  vadSensitivity?: number;
-  idleTimeoutMinutes?: number;
-  customVocabulary?: string;
+/** Append-mode takes waiting to be committed, joined as they will be pasted. */
+export interface AppendBuffer {
+  takes: number;
+  text: string;
+}
+
+export async function getAppendBuffer(): Promise<AppendBuffer> {
+  return await invoke('get_append_buffer');
+}
+
+/** Paste the buffered takes into the frontmost app as one dictation. */
+export async function commitAppendBuffer(): Promise<TranscriptionResult> {
+  return await invoke('commit_append_buffer');
+}
+
+export async function discardAppendBuffer(): Promise<void> {
+  await invoke('discard_append_buffer');
+}
+
+/** A dictation held by preview mode, waiting for Accept or Discard. */
+export interface PendingInjection {
+  id: number;
+  text: string;
+  /** How long the preview waits before it is discarded. */
+  timeoutMs: number;
+}
+
+/** How a preview stopped being pending, sent with `injection-resolved`. */
+export interface InjectionResolved {
+  id: number;
+  outcome: 'accepted' | 'discarded' | 'expired' | 'replaced';
+}
+
+export async function getPendingInjection(): Promise<PendingInjection | null> {
+  return await invoke('get_pending_injection');
+}
+
+/** Paste preview `id` into the frontmost app. */
+export async function confirmInjection(id: number): Promise<void> {
+  await invoke('confirm_injection', { id });
+}
+
+export async function discardInjection(id: number): Promise<void> {
+  await invoke('discard_injection', { id });
+}
+
+/** Mirror the newest history entry to Rust for the tray and paste-last key. */
+export async function setLastTranscription(text: string | null): Promise<void> {
+  await invoke('set_last_transcription', { text });
+}
+
+/** Copy the newest history entry. Rejects when there is none. */
+export async function copyLastTranscription(): Promise<void> {
+  await invoke('copy_last_transcription');
+}
+
+/** Paste the newest history entry into the frontmost app. Rejects when there is none. */
+export async function pasteLastTranscription(): Promise<void> {
+  await invoke('paste_last_transcription');
+}
+
+/** Transcribe the copied or Finder-selected audio file and paste the text.
+ * Rejects, and emits `clipboard-audio-failed`, when there is no such file. */
+export async function transcribeClipboardAudio(): Promise<TranscriptionResult> {
+  return await invoke('transcribe_clipboard_audio');
+}
+
+export type OverlayAction = 'toggle_recording' | 'cancel' | 'open_main_window' | 'copy_last' | 'start_ambient' | 'dismiss_ambient_prompt';
+
+/**
+ * Carry out an overlay click in Rust, which keeps the overlay from taking
+ * focus. Only `open_main_window` brings Murmur forward.
+ */
+export async function overlayAction(action: OverlayAction): Promise<void> {
+  await invoke('overlay_action', { action });
+}
+
+/** An app trigger's offer of ambient transcription, shown in the overlay. */
+export interface AmbientPrompt {
+  bundleId: string;
+  name: string;
+}
+
+export async function getAmbientPrompt(): Promise<AmbientPrompt | null> {
+  return await invoke('get_ambient_prompt');
+}
+
+/**
+ * The generated `configure_dictation` payload, with the nested settings that
+ * Rust parses leniently typed from `settings.ts`. Rust rejects unknown keys.
+ */
+export type ConfigureOptions = Omit<
+  ConfigurePayload,
+  | 'vocabularyEntries'
+  | 'appProfiles'
+  | 'dictationProfiles'
+  | 'rewriteTemplates'
+  | 'replacementRules'
+  | 'dictationMacros'
+  | 'contextBiasing'
+  | 'voiceCommands'
+  | 'notificationPolicy'
+  | 'focusFilter'
+  | 'interruptedRecordingAction'
+  | 'trailingBehavior'
+  | 'notesTarget'
+  | 'selectionDictation'
+  | 'whisperDecoding'
+  | 'redaction'
+  | 'adaptiveModel'
+  | 'latencyBudget'
+  | 'dualModel'
+  | 'confidenceRetry'
+  | 'alternatives'
+> & {
   vocabularyEntries?: VocabularyEntry[];
-  smartPunctuation?: boolean;
-  saveTranscript?: boolean;
-  saveAudio?: boolean;
-  outputDir?: string;
   appProfiles?: AppProfile[];
-  voiceCommandsEnabled?: boolean;
+  dictationProfiles?: DictationProfile[];
+  rewriteTemplates?: RewriteTemplate[];
+  replacementRules?: ReplacementRule[];
+  dictationMacros?: DictationMacrosSettings;
+  contextBiasing?: ContextBiasingSettings;
   voiceCommands?: VoiceCommand[];
-  cleanupEnabled?: boolean;
-  smartFormattingEnabled?: boolean;
-  cleanupRemoveFiller?: boolean;
-  cleanupCapitalize?: boolean;
-  codeVocabEnabled?: boolean;
-  codeVocabFolder?: string;
-  correctionEnabled?: boolean;
-  correctionFuzzy?: boolean;
+  notificationPolicy?: NotificationPolicy;
+  focusFilter?: FocusFilterSettings;
+  interruptedRecordingAction?: InterruptedRecordingAction;
+  trailingBehavior?: TrailingBehavior;
+  notesTarget?: NotesTarget;
+  selectionDictation?: SelectionDictation;
+  whisperDecoding?: WhisperDecodingSettings;
+  redaction?: RedactionSettings;
+  adaptiveModel?: AdaptiveModelSettings;
+  latencyBudget?: LatencyBudgetSettings;
+  dualModel?: DualModelSettings;
+  confidenceRetry?: ConfidenceRetrySettings;
+  alternatives?: AlternativesSettings;
+};
+
+/** Write the live rewrite templates to `path`; returns how many were written. */
+export async function exportRewriteTemplates(path: string): Promise<number> {
+  return await invoke('export_rewrite_templates', { path });
+}
+
+/** Merge the templates in `path` into the live list and return the result. */
+export async function importRewriteTemplates(path: string): Promise<RewriteTemplate[]> {
+  return await invoke('import_rewrite_templates', { path });
+}
+
+/** How many times one rule matched in a dry run. */
+export interface RuleMatch {
+  id: string;
+  matches: number;
+}
+
+/** What the rules would make of `source`; nothing is delivered or saved. */
+export interface ReplacementPreview {
+  source: string;
+  text: string;
+  matches: RuleMatch[];
+}
+
+/**
+ * Dry-run `rules` over `text`, or over the last dictation as it reached the
+ * rules, before they ran, when no text is given. Rejects with a message naming the first invalid rule.
+ */
+export async function previewReplacementRules(rules: ReplacementRule[], text?: string): Promise<ReplacementPreview> {
+  return await invoke('preview_replacement_rules', { rules, text: text ?? null });
+}
+
+/** Write the live replacement rules to `path`; returns how many were written. */
+export async function exportReplacementRules(path: string): Promise<number> {
+  return await invoke('export_replacement_rules', { path });
+}
+
+/** Merge the rules in `path` into the live list and return the result. */
+export async function importReplacementRules(path: string): Promise<ReplacementRule[]> {
+  return await invoke('import_replacement_rules', { path });
+}
+
+/** Payload of `dictation-macro`, sent once a matched macro's action runs. */
+export interface DictationMacroRan {
+  recordingId: number;
+  id: string;
+  action: MacroAction;
 }
 
 export async function configure(options: ConfigureOptions): Promise<DictationResponse> {
@@ -101,6 +275,7 @@ export function buildConfigureOptions(s: Settings): ConfigureOptions {
     autoPasteDelayMs: s.autoPasteDelayMs,
     vadSensitivity: s.vadSensitivity,
     idleTimeoutMinutes: s.idleTimeoutMinutes,
+    backendKeepAliveMb: s.backendKeepAliveMb,
     customVocabulary: s.customVocabulary,
     vocabularyEntries: s.vocabularyEntries,
     smartPunctuation: s.smartPunctuation,
@@ -108,6 +283,10 @@ export function buildConfigureOptions(s: Settings): ConfigureOptions {
     saveAudio: s.saveAudio,
     outputDir: s.outputDir,
     appProfiles: s.appProfiles,
+    dictationProfiles: s.dictationProfiles,
+    rewriteTemplates: s.rewriteTemplates,
+    replacementRules: s.replacementRules,
+    dictationMacros: s.dictationMacros,
     voiceCommandsEnabled: s.voiceCommandsEnabled,
     voiceCommands: s.voiceCommands,
     cleanupEnabled: s.cleanupEnabled,
@@ -118,6 +297,28 @@ export function buildConfigureOptions(s: Settings): ConfigureOptions {
     codeVocabFolder: s.codeVocabFolder,
     correctionEnabled: s.correctionEnabled,
     correctionFuzzy: s.correctionFuzzy,
+    learnFromCorrections: s.learnFromCorrections,
+    contextBiasing: s.contextBiasing,
+    notificationPolicy: s.notificationPolicy,
+    focusFilter: s.focusFilter,
+    interruptedRecordingAction: s.interruptedRecordingAction,
+    whisperDecoding: s.whisperDecoding,
+    hallucinationFilter: s.hallucinationFilter,
+    redaction: s.redaction,
+    adaptiveModel: s.adaptiveModel,
+    latencyBudget: s.latencyBudget,
+    dualModel: s.dualModel,
+    confidenceRetry: s.confidenceRetry,
+    alternatives: s.alternatives,
+    privacyMode: s.privacyMode,
+    keepAudioOnDevice: s.keepAudioOnDevice,
+    appendMode: s.appendMode,
+    legacyAutomation: s.legacyAutomation,
+    previewBeforePaste: s.previewBeforePaste,
+    cursorContext: s.cursorContext,
+    trailingBehavior: s.trailingBehavior,
+    notesTarget: s.notesTarget,
+    selectionDictation: s.selectionDictation,
   };
 }
 
@@ -144,11 +345,93 @@ export async function downloadModel(modelName: string): Promise<void> {
   await invoke('download_model', { modelName });
 }
 
+/**
+ * Download a Whisper GGML file from a URL or a Hugging Face
+ * `owner/repo/path/file.bin`. Resolves to the `custom:<file>` model name.
+ */
+export async function downloadCustomModel(source: string): Promise<CustomModelOption> {
+  return invoke<CustomModelOption>('download_custom_model', { source });
+}
+
+/** A registered transcription plugin's manifest; `command` is absolute. */
+export interface TranscriptionPlugin {
+  protocol: number;
+  id: string;
+  name: string;
+  command: string;
+  args: string[];
+}
+
+export async function listTranscriptionPlugins(): Promise<TranscriptionPlugin[]> {
+  return invoke<TranscriptionPlugin[]>('list_transcription_plugins');
+}
+
+/**
+ * Register the plugin described by a `plugin.json`, after checking it starts
+ * and speaks protocol 1. Resolves to the `external:<id>` model name.
+ */
+export async function registerTranscriptionPlugin(manifestPath: string): Promise<ExternalModelOption> {
+  return invoke<ExternalModelOption>('register_transcription_plugin', { manifestPath });
+}
+
+export async function unregisterTranscriptionPlugin(id: string): Promise<void> {
+  await invoke('unregister_transcription_plugin', { id });
+}
+
+/** Apply the cloud transcription provider, endpoint, and model. Rejects an
+ *  invalid endpoint. */
+export async function setCloudTranscription(settings: CloudTranscriptionSettings): Promise<void> {
+  await invoke('set_cloud_transcription', { settings });
+}
+
+/** Whether a cloud API key is in the Keychain. The key is never read back. */
+export async function getCloudApiKeyStored(): Promise<boolean> {
+  return invoke<boolean>('get_cloud_api_key_stored');
+}
+
+export async function setCloudApiKey(key: string): Promise<void> {
+  await invoke('set_cloud_api_key', { key });
+}
+
+export async function clearCloudApiKey(): Promise<void> {
+  await invoke('clear_cloud_api_key');
+}
+
+/**
+ * Install a model from a ggml `.bin` (Whisper) or an extracted bundle folder
+ * (Parakeet) the user already has. `link` symlinks it instead of copying.
+ */
+export async function importModel(modelName: string, path: string, link: boolean): Promise<void> {
+  await invoke('import_model', { modelName, path, link });
+}
+
+/** Apply the model download mirrors and proxy. Rejects invalid URLs. */
+export async function setDownloadNetwork(settings: DownloadNetworkSettings): Promise<void> {
+  await invoke('set_download_network', { settings });
+}
+
+/** One endpoint probed by `test_download_connectivity`. */
+export interface EndpointCheck {
+  source: 'huggingFace' | 'github';
+  url: string;
+  /** The probe went to the configured mirror rather than the origin. */
+  mirror: boolean;
+  reachable: boolean;
+  status: number | null;
+  latencyMs: number | null;
+  error: string | null;
+}
+
+/** Probe each download source (mirror and origin) through the configured proxy. */
+export async function testDownloadConnectivity(): Promise<EndpointCheck[]> {
+  return invoke<EndpointCheck[]>('test_download_connectivity');
+}
+
 /**
  * Transcribe an existing audio file (WAV/MP3/M4A) via the Rust `transcribe_file`
  * command. Unlike live recording this returns the text directly (no auto-paste).
  */
-export async function transcribeFile(filePath: string): Promise<DictationResponse> {
+export async function transcribeFile(filePath: string): Promise<TranscriptionResponse> {
   try {
     return await invoke('transcribe_file', { filePath });
   } catch (err) {
@@ -190,9 +473,18 @@ export async function requestMicrophoneAccess(): Promise<void> {
   return await invoke('request_microphone_access');
 }
 
+/**
+ * Ask for microphone access and resolve with the resulting status. Shows the
+ * native prompt on a first request and waits for the answer; once access was
+ * denied it opens the Microphone pane instead and resolves 'denied'.
+ */
+export async function requestMicrophonePermission(): Promise<MicPermissionStatus> {
+  return await invoke('request_microphone_permission');
+}
+
 /** Open System Settings at the Microphone privacy pane. */
 export async function openMicrophoneSettings(): Promise<void> {
-  return await invoke('request_microphone_permission');
+  return await invoke('open_system_preferences');
 }
 
 /** Check whether a specific model's files already exist on disk. */
@@ -213,6 +505,32 @@ export async function checkMicrophonePermissionStatus(): Promise<MicPermissionSt
   return await invoke('check_microphone_permission_status');
 }
 
+export type PermissionState = MicPermissionStatus;
+
+/** Every privacy permission at once, from Rust's permission watcher. */
+export interface PermissionReport {
+  microphone: PermissionState;
+  /** Only ever 'granted' or 'denied'; macOS can't report "never asked" here. */
+  accessibility: PermissionState;
+  inputMonitoring: PermissionState;
+  /** Apple Events access to System Events, used by the osascript paste path.
+   * 'unknown' while System Events isn't running. */
+  automation: PermissionState;
+  /** null while no feature needs screen capture. */
+  screenRecording: PermissionState | null;
+}
+
+/** Payload of `permission-changed`: which fields flipped, and the full report. */
+export interface PermissionChange {
+  changed: Array<'microphone' | 'accessibility' | 'inputMonitoring' | 'automation' | 'screenRecording'>;
+  report: PermissionReport;
+}
+
+/** Read every permission now. `permission-changed` reports later flips. */
+export async function getPermissionReport(): Promise<PermissionReport> {
+  return await invoke('get_permission_report');
+}
+
 /**
  * Reset this app's stale macOS Microphone TCC entry, then reopen the Microphone
  * settings pane. Rejects if the reset fails (see Rust `reset_microphone_permission`).
@@ -220,3 +538,418 @@ export async function checkMicrophonePermissionStatus(): Promise<MicPermissionSt
 export async function resetMicrophonePermission(): Promise<void> {
   return await invoke('reset_microphone_permission');
 }
+
+/** One key event seen by `diagnose_hotkey`. Non-modifier keys are reported as "other". */
+export interface CapturedKeyEvent {
+  elapsedMs: number;
+  kind: 'press' | 'release';
+  reportedKey: string;
+  key: string;
+  scancode: number | null;
+  matchesHotkey: boolean;
+}
+
+export interface HotkeyDiagnostic {
+  hotkey: string;
+  expectedKey: string | null;
+  expectedScancode: number | null;
+  listenerActive: boolean;
+  events: CapturedKeyEvent[];
+}
+
+/** Record the raw key events Murmur receives for a few seconds while the user presses `hotkey`. */
+export async function diagnoseHotkey(hotkey: string, durationMs?: number): Promise<HotkeyDiagnostic> {
+  return await invoke('diagnose_hotkey', { hotkey, durationMs: durationMs ?? null });
+}
+
+export interface DetectorStates {
+  doubleTap: string | null;
+  holdDown: string | null;
+  transform: string | null;
+}
+
+/** One key event and what the listener did with it; `event` is null for a timer (hold promotion, second-tap expiry). */
+export interface KeyboardDiagnosticEntry {
+  elapsedMs: number;
+  event: CapturedKeyEvent | null;
+  before: DetectorStates | null;
+  after: DetectorStates | null;
+  consumedBy: string[];
+  emitted: string[];
+  rejected: string[];
+}
+
+export interface KeyboardDiagnosticsReport {
+  durationMs: number;
+  mode: 'double_tap' | 'hold_down' | 'both';
+  hotkey: string | null;
+  timing: HotkeyTiming;
+  listenerActive: boolean;
+  appDisabled: boolean;
+  focusGated: boolean;
+  quietHoursGated: boolean;
+  entries: KeyboardDiagnosticEntry[];
+  dropped: number;
+}
+
+/** Record every key event for `durationSecs` with what the hotkey detectors made of it, for a bug report. */
+export async function startKeyboardDiagnostics(durationSecs?: number): Promise<KeyboardDiagnosticsReport> {
+  return await invoke('start_keyboard_diagnostics', { durationSecs: durationSecs ?? null });
+}
+
+/** What `calibrate_hotkey_timing` measured, and the timing it suggests. */
+export interface TimingCalibration {
+  taps: number;
+  longestTapMs: number;
+  longestGapMs: number;
+  suggested: HotkeyTiming;
+}
+
+/** Capture a few seconds of the user double-tapping `hotkey` and suggest a timing that fits. Rejects when too few double-taps arrived. */
+export async function calibrateHotkeyTiming(hotkey: string, durationMs?: number): Promise<TimingCalibration> {
+  return await invoke('calibrate_hotkey_timing', { hotkey, durationMs: durationMs ?? null });
+}
+
+/** How sure Whisper was of one segment of a transcript. */
+export interface SegmentConfidence {
+  avgLogprob: number;
+  noSpeechProb: number;
+  tokens: number;
+}
+
+/** `confidence` of `transcription-complete`; null for non-Whisper models. */
+export interface TranscriptConfidence {
+  /** Geometric mean token probability, 0–1. */
+  score: number;
+  avgLogprob: number;
+  noSpeechProb: number;
+  /** Average log-probability under -1 or no-speech probability over 0.6. */
+  low: boolean;
+  segments: SegmentConfidence[];
+}
+
+/** Words of a delivered transcript that another Whisper hypothesis read
+ *  differently, by whitespace-separated word index, `end` exclusive. */
+export interface AlternativeSpan {
+  start: number;
+  end: number;
+  /** Other readings, in hypothesis order; '' drops the words. */
+  options: string[];
+}
+
+/** Payload of `transcription-alternatives`. */
+export interface TranscriptionAlternatives {
+  recordingId: number;
+  /** The delivered text the spans index into. */
+  text: string;
+  spans: AlternativeSpan[];
+}
+
+/** A run of words the model heard and what it was corrected to. */
+export interface CorrectionPair {
+  heard: string;
+  written: string;
+}
+
+/** A correction and how many history entries it was made in. */
+export interface LearnedCorrection extends CorrectionPair {
+  count: number;
+  /** Made often enough to bias Whisper's prompt. */
+  inPrompt: boolean;
+}
+
+/** Learn from a history entry edited from `original` to `text`; returns the
+ *  pairs kept, none while `learnFromCorrections` is off. */
+export async function correctTranscription(id: string, original: string, text: string): Promise<CorrectionPair[]> {
+  return await invoke('correct_transcription', { id, original, text });
+}
+
+export async function getLearnedCorrections(): Promise<LearnedCorrection[]> {
+  return await invoke('get_learned_corrections');
+}
+
+export async function clearLearnedCorrections(): Promise<void> {
+  await invoke('clear_learned_corrections');
+}
+
+/** Low-confidence retries since launch, by outcome. */
+export interface ConfidenceRetryStats {
+  /** Transcripts that scored below the threshold. */
+  triggered: number;
+  changed: number;
+  unchanged: number;
+  keptFirst: number;
+  skipped: number;
+  overBudget: number;
+  failed: number;
+}
+
+export type ConfidenceRetryOutcome = 'changed' | 'unchanged' | 'kept_first' | 'skipped' | 'over_budget' | 'failed';
+
+/** Payload of `confidence-retry`. */
+export interface ConfidenceRetryReport {
+  recordingId: number;
+  modelName: string;
+  beamSearch: boolean;
+  outcome: ConfidenceRetryOutcome;
+  firstScore: number;
+  retryScore: number | null;
+  retryMs: number;
+  totals: ConfidenceRetryStats;
+}
+
+export async function getConfidenceRetryStats(): Promise<ConfidenceRetryStats> {
+  return await invoke('get_confidence_retry_stats');
+}
+
+/** Payload of `latency-budget-exceeded`, sent when a model keeps decoding
+ * slower than `latencyBudget` allows. */
+export interface LatencyBudgetExceeded {
+  recordingId: number;
+  model: string;
+  /** Median realtime factor of the model's recent takes. */
+  realtimeFactor: number;
+  maxRealtimeFactor: number;
+  fasterModel: string | null;
+  /** Switch the selected model to `fasterModel`. */
+  switch: boolean;
+  guidance: string;
+}
+
+/** Recent realtime factor of one model since launch or its last notice. */
+export interface ModelLatency {
+  model: string;
+  realtimeFactor: number;
+  takes: number;
+}
+
+export async function getLatencyBudgetStats(): Promise<ModelLatency[]> {
+  return await invoke('get_latency_budget_stats');
+}
+
+/** A running copy of the app. */
+export interface InstanceInfo {
+  pid: number;
+  identifier: string;
+  productName: string;
+}
+
+export interface InstanceStatus {
+  thisInstance: InstanceInfo;
+  ownsKeyboardListener: boolean;
+  /** The copy holding the hotkey (this one included), or null when none does. */
+  keyboardOwner: InstanceInfo | null;
+}
+
+/** Which running copy (dev or release) holds the dictation hotkey. */
+export async function getInstanceStatus(): Promise<InstanceStatus> {
+  return await invoke('get_instance_status');
+}
+
+export interface WakeWordStatus {
+  modelInstalled: boolean;
+  listening: boolean;
+}
+
+/** Start listening for "Hey Murmur". Fails if the wake word model isn't downloaded. */
+export async function startWakeWord(deviceName?: string): Promise<void> {
+  return await invoke('start_wake_word', {
+    deviceName: deviceName && deviceName !== DEFAULT_SETTINGS.microphone ? deviceName : null,
+  });
+}
+
+export async function stopWakeWord(): Promise<void> {
+  return await invoke('stop_wake_word');
+}
+
+export async function getWakeWordStatus(): Promise<WakeWordStatus> {
+  return await invoke('get_wake_word_status');
+}
+
+/** Progress arrives on `download-progress`, like transcription models. */
+export async function downloadWakeWordModel(): Promise<void> {
+  return await invoke('download_wake_word_model');
+}
+
+/** Keep the last `seconds` of the microphone in memory, to prepend to each recording. */
+export async function startPreRoll(deviceName: string | undefined, seconds: number): Promise<void> {
+  return await invoke('start_pre_roll', {
+    deviceName: deviceName && deviceName !== DEFAULT_SETTINGS.microphone ? deviceName : null,
+    seconds,
+  });
+}
+
+export async function stopPreRoll(): Promise<void> {
+  return await invoke('stop_pre_roll');
+}
+
+/** The device a microphone test opened. */
+export interface MicTestInfo {
+  deviceName: string;
+  sampleRate: number;
+  channels: number;
+}
+
+/** Payload of `mic-test-level`, about 20 per second. */
+export interface MicTestLevel {
+  rms: number;
+  peak: number;
+  maxRms: number;
+  /** The level has reached speech at least once since the test started. */
+  heardSpeech: boolean;
+}
+
+export interface MicTestSummary {
+  maxRms: number;
+  maxPeak: number;
+  heardSpeech: boolean;
+  durationMs: number;
+}
+
+/** Meter the microphone without recording. Levels arrive as `mic-test-level`. */
+export async function startMicTest(deviceName?: string): Promise<MicTestInfo> {
+  return await invoke('start_mic_test', {
+    deviceName: deviceName && deviceName !== DEFAULT_SETTINGS.microphone ? deviceName : null,
+  });
+}
+
+/** `null` when no test was running. */
+export async function stopMicTest(): Promise<MicTestSummary | null> {
+  return await invoke('stop_mic_test');
+}
+
+/** Which microphone captures Murmur holds, beside the system's indicator. */
+export interface RecordingPrivacyReport {
+  recording: boolean;
+  wakeWordListening: boolean;
+  ambientListening: boolean;
+  meetingListening: boolean;
+  preRollListening: boolean;
+  microphoneInUse: boolean;
+  /** Some process has an input device running (the macOS orange dot). `null` where unknown. */
+  systemIndicator: boolean | null;
+}
+
+export async function getRecordingPrivacyReport(): Promise<RecordingPrivacyReport> {
+  return await invoke('get_recording_privacy_report');
+}
+
+/** Close every microphone stream at once, discarding a dictation in progress. */
+export async function killMicrophone(): Promise<RecordingPrivacyReport> {
+  return await invoke('kill_microphone');
+}
+
+/** One transcribed stretch of speech in the ambient log. */
+export interface AmbientEntry {
+  startedAtMs: number;
+  durationMs: number;
+  text: string;
+}
+
+/** Start transcribing the microphone into the ambient log. */
+export async function startAmbientMode(deviceName?: string): Promise<void> {
+  return await invoke('start_ambient_mode', {
+    deviceName: deviceName && deviceName !== DEFAULT_SETTINGS.microphone ? deviceName : null,
+  });
+}
+
+export async function stopAmbientMode(): Promise<void> {
+  return await invoke('stop_ambient_mode');
+}
+
+export async function isAmbientModeActive(): Promise<boolean> {
+  return await invoke('is_ambient_mode_active');
+}
+
+/** Newest first. `query` keeps entries containing every one of its words. */
+export async function getAmbientLog(query?: string, limit?: number): Promise<AmbientEntry[]> {
+  return await invoke('get_ambient_log', { query: query?.trim() || null, limit: limit ?? null });
+}
+
+export async function clearAmbientLog(): Promise<void> {
+  return await invoke('clear_ambient_log');
+}
+
+/** A meeting being transcribed into today's note, or the last one once it stopped. */
+export interface MeetingStatus {
+  active: boolean;
+  startedAtMs: number | null;
+  chunkSeconds: number;
+  /** Chunks saved to notes so far. */
+  chunksSaved: number;
+  /** Chunks dropped untranscribed because dictation kept the model busy. */
+  chunksDropped: number;
+}
+
+/** Start transcribing a meeting into today's note, a chunk about every `chunkSeconds`. */
+export async function startMeeting(deviceName: string | undefined, chunkSeconds: number): Promise<MeetingStatus> {
+  return await invoke('start_meeting', {
+    deviceName: deviceName && deviceName !== DEFAULT_SETTINGS.microphone ? deviceName : null,
+    chunkSeconds,
+  });
+}
+
+/** The last chunk is still transcribed after this returns. */
+export async function stopMeeting(): Promise<MeetingStatus> {
+  return await invoke('stop_meeting');
+}
+
+export async function getMeetingStatus(): Promise<MeetingStatus> {
+  return await invoke('get_meeting_status');
+}
+
+export interface NoteEntry {
+  /** Local `HH:MM:SS`. */
+  time: string;
+  text: string;
+}
+
+/** One day's Murmur note. */
+export interface Note {
+  /** Local `YYYY-MM-DD`. */
+  date: string;
+  entries: NoteEntry[];
+  markdown: string;
+}
+
+export interface NoteSummary {
+  date: string;
+  entries: number;
+  preview: string;
+}
+
+/** Days that have a note, newest first. */
+export async function listNotes(): Promise<NoteSummary[]> {
+  return await invoke('list_notes');
+}
+
+/** `date`'s note, or today's when omitted. */
+export async function getNote(date?: string): Promise<Note> {
+  return await invoke('get_note', { date: date ?? null });
+}
+
+export async function addNoteEntry(text: string): Promise<Note> {
+  return await invoke('add_note_entry', { text });
+}
+
+/** Empty text deletes the entry. */
+export async function updateNoteEntry(date: string, index: number, text: string): Promise<Note> {
+  return await invoke('update_note_entry', { date, index, text });
+}
+
+export async function deleteNoteEntry(date: string, index: number): Promise<Note> {
+  return await invoke('delete_note_entry', { date, index });
+}
+
+export async function deleteNote(date: string): Promise<void> {
+  return await invoke('delete_note', { date });
+}
+
+/** Copy the note as markdown. */
+export async function copyNote(date?: string): Promise<void> {
+  return await invoke('copy_note', { date: date ?? null });
+}
+
+export async function exportNote(date: string, path: string): Promise<void> {
+  return await invoke('export_note', { date, path });
+}
@@ -0,0 +1,45 @@
+import { describe, expect, it } from 'vitest';
+import { describePause, installOutcome } from './downloadQueue';
+import type { ModelRuntimeSnapshot } from './modelRuntime';
+
+function snapshot(modelName: string, installState: ModelRuntimeSnapshot['installState']): ModelRuntimeSnapshot {
+  return {
+    generation: 1,
+    modelName,
+    label: 'Base',
+    size: '142 MB',
+    backend: 'whisper',
+    accelerator: 'Metal',
+    capabilities: {
+      partialResults: true,
+      initialPrompts: true,
+      multilingual: false,
+      translation: false,
+      timestamps: false,
+      confidence: false,
+      punctuationControl: true,
+    },
+    supportedPlatforms: ['macos'],
+    supported: true,
+    unavailableReason: null,
+    installState,
+    lifecycleState: 'unloaded',
+    failurePresent: false,
+  };
+}
+
+describe('download queue', () => {
+  it('settles only on the watched model reaching installed or invalid', () => {
+    expect(installOutcome(snapshot('base.en', 'installing'), 'base.en')).toBeNull();
+    expect(installOutcome(snapshot('base.en', 'installed'), 'base.en')).toBe(true);
+    expect(installOutcome(snapshot('base.en', 'invalid'), 'base.en')).toBe(false);
+    expect(installOutcome(snapshot('large-v3-turbo', 'installed'), 'base.en')).toBeNull();
+    // A paused download returns to notInstalled and keeps waiting.
+    expect(installOutcome(snapshot('base.en', 'notInstalled'), 'base.en')).toBeNull();
+  });
+
+  it('explains why the queue is paused', () => {
+    expect(describePause('metered')).toBe('Paused on a metered connection');
+    expect(describePause('vpn')).toBe('Paused while a VPN is connected');
+  });
+});
@@ -0,0 +1,73 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { ModelRuntimeSnapshot } from './modelRuntime';
+import type { ModelOption } from './settings';
+
+/** Small model installed first so dictation works while a larger one downloads. */
+export const QUICK_START_MODEL: ModelOption = 'base.en';
+
+export type DownloadPauseReason = 'user' | 'metered' | 'vpn';
+
+/** Payload of `download-queue-changed`. */
+export interface DownloadQueueStatus {
+  /** Downloading now, or next when paused. */
+  active: string | null;
+  pending: string[];
+  paused: DownloadPauseReason | null;
+}
+
+/**
+ * Queue catalog models to download one after another in the background.
+ * The queue survives restarts and resumes partial downloads.
+ */
+export async function queueModelDownloads(modelNames: string[]): Promise<DownloadQueueStatus> {
+  return await invoke('queue_model_downloads', { modelNames });
+}
+
+export async function getDownloadQueue(): Promise<DownloadQueueStatus> {
+  return await invoke('get_download_queue');
+}
+
+/** Drop a waiting model from the queue. */
+export async function removeQueuedDownload(modelName: string): Promise<DownloadQueueStatus> {
+  return await invoke('remove_queued_download', { modelName });
+}
+
+export async function setDownloadQueuePaused(paused: boolean): Promise<DownloadQueueStatus> {
+  return await invoke('set_download_queue_paused', { paused });
+}
+
+export function describePause(reason: DownloadPauseReason): string {
+  switch (reason) {
+    case 'user': return 'Paused';
+    case 'metered': return 'Paused on a metered connection';
+    case 'vpn': return 'Paused while a VPN is connected';
+  }
+}
+
+/** Whether a runtime update settles `modelName`'s install: true installed, false failed. */
+export function installOutcome(snapshot: ModelRuntimeSnapshot, modelName: string): boolean | null {
+  if (snapshot.modelName !== modelName) return null;
+  if (snapshot.installState === 'installed') return true;
+  if (snapshot.installState === 'invalid') return false;
+  return null;
+}
+
+/**
+ * Resolve once a queued model is installed; reject if its install fails.
+ * Call before queueing it so the install can't finish unseen.
+ */
+export async function waitForModelInstall(modelName: string): Promise<void> {
+  const listener: { unlisten?: () => void } = {};
+  try {
+    await new Promise<void>((resolve, reject) => {
+      listen<ModelRuntimeSnapshot>('model-runtime-status-changed', (event) => {
+        const outcome = installOutcome(event.payload, modelName);
+        if (outcome === true) resolve();
+        if (outcome === false) reject(new Error(`${event.payload.label} failed to install`));
+      }).then((fn) => { listener.unlisten = fn; }, reject);
+    });
+  } finally {
+    listener.unlisten?.();
+  }
+}
@@ -1,3 +1,5 @@
+import { invoke } from '@tauri-apps/api/core';
+
 export interface AppEvent {
   timestamp: string;
   stream: StreamName;
@@ -27,3 +29,27 @@ export const LEVEL_COLORS: Record<LevelName, string> = {
   warn:  'text-amber-600 dark:text-amber-400',
   error: 'text-red-600 dark:text-red-400',
 };
+
+/** Filters for `query_logs`. Empty lists and omitted fields match everything. */
+export interface LogQuery {
+  levels?: LevelName[];
+  streams?: StreamName[];
+  search?: string;
+  sinceMs?: number;
+  untilMs?: number;
+  /** Matches to skip, counted from the newest. */
+  offset?: number;
+  limit?: number;
+}
+
+export interface LogQueryPage {
+  /** Newest first. */
+  entries: AppEvent[];
+  total: number;
+  hasMore: boolean;
+}
+
+/** Search the structured log on disk (active and rotated files) one page at a time. */
+export function queryLogs(filters: LogQuery): Promise<LogQueryPage> {
+  return invoke<LogQueryPage>('query_logs', { filters });
+}
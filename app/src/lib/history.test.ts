@@ -0,0 +1,88 @@
+import { describe, expect, it } from 'vitest';
+import { addHistoryEntry, attachAlternatives, chooseAlternative, decodeSummary, processingSummary, updateHistoryEntry, type HistoryEntry } from './history';
+
+function entry(id: string, text: string, extra: Partial<HistoryEntry> = {}): HistoryEntry {
+  return { id, text, timestamp: Number(id), duration: 1, ...extra };
+}
+
+describe('history alternatives', () => {
+  it('attaches spans to the newest entry holding the delivered text', () => {
+    const spans = [{ start: 1, end: 2, options: ['too'] }];
+    const entries = [entry('1', 'send it to Sam'), entry('2', 'send it to Sam'), entry('3', 'other')];
+    const attached = attachAlternatives(entries, 'send it to Sam', spans);
+    expect(attached[0].alternatives).toBeUndefined();
+    expect(attached[1].alternatives).toEqual(spans);
+    expect(attachAlternatives(entries, 'missing', spans)).toBe(entries);
+  });
+
+  it('swaps in a reading, keeps the old one as an option, and shifts later spans', () => {
+    const entries = [entry('1', 'meet me  at the key', {
+      confidence: { score: 0.3, low: true },
+      alternatives: [
+        { start: 0, end: 1, options: ['meat', 'greet'] },
+        { start: 4, end: 5, options: ['quay'] },
+      ],
+    })];
+    const [chosen] = chooseAlternative(entries, '1', 0, 'meet up with');
+    expect(chosen.text).toBe('meet up with me  at the key');
+    expect(chosen.confidence).toBeUndefined();
+    expect(chosen.alternatives).toEqual([
+      { start: 0, end: 3, options: ['meet', 'meat', 'greet'] },
+      { start: 6, end: 7, options: ['quay'] },
+    ]);
+
+    const [undone] = chooseAlternative([chosen], '1', 0, 'meet');
+    expect(undone.text).toBe('meet me  at the key');
+    expect(undone.alternatives?.[1]).toEqual({ start: 4, end: 5, options: ['quay'] });
+  });
+
+  it('drops the words and their span when the chosen reading is empty', () => {
+    const entries = [entry('1', 'send it right now', {
+      alternatives: [
+        { start: 2, end: 3, options: [''] },
+        { start: 3, end: 4, options: ['later'] },
+      ],
+    })];
+    const [chosen] = chooseAlternative(entries, '1', 0, '');
+    expect(chosen.text).toBe('send it now');
+    expect(chosen.alternatives).toEqual([{ start: 2, end: 3, options: ['later'] }]);
+  });
+
+  it('forgets alternatives once the text is edited by hand', () => {
+    const entries = [entry('1', 'meet me at the key', { alternatives: [{ start: 4, end: 5, options: ['quay'] }] })];
+    expect(updateHistoryEntry(entries, '1', 'meet me at the quay')[0].alternatives).toBeUndefined();
+  });
+});
+
+describe('history decode stats', () => {
+  it('keeps the token count with the entry', () => {
+    const [entry] = addHistoryEntry([], 'hello there', 2, 'recording', undefined, undefined, undefined, { tokens: 3, decodeMs: 150 });
+    expect(entry.decode).toEqual({ tokens: 3, decodeMs: 150 });
+    expect(addHistoryEntry([], 'hi', 1)[0]).not.toHaveProperty('decode');
+  });
+
+  it('summarizes throughput when the decode was timed', () => {
+    expect(decodeSummary({ tokens: 42, decodeMs: 500 })).toBe('42 tokens at 84 tokens/s');
+    expect(decodeSummary({ tokens: 1, decodeMs: 0 })).toBe('1 token');
+  });
+});
+
+describe('history processing location', () => {
+  it('records where off-device entries were transcribed and keeps it through edits', () => {
+    const [cloud] = addHistoryEntry([], 'hello', 1, 'recording', undefined, undefined, undefined, undefined, { on: 'cloud', by: 'api.openai.com' });
+    expect(cloud).toMatchObject({ processedOn: 'cloud', processedBy: 'api.openai.com' });
+    expect(processingSummary(cloud)).toBe('Sent to api.openai.com for transcription');
+    expect(updateHistoryEntry([cloud], cloud.id, 'hello there')[0].processedOn).toBe('cloud');
+
+    const [plugin] = addHistoryEntry([], 'hi', 1, 'file', 'memo.m4a', undefined, undefined, undefined, { on: 'plugin', by: null });
+    expect(plugin.processedOn).toBe('plugin');
+    expect(plugin).not.toHaveProperty('processedBy');
+    expect(processingSummary(plugin)).toBe('Transcribed by a plugin');
+  });
+
+  it('leaves on-device entries unmarked', () => {
+    const [entry] = addHistoryEntry([], 'hi', 1, 'recording', undefined, undefined, undefined, undefined, { on: 'device', by: null });
+    expect(entry).not.toHaveProperty('processedOn');
+    expect(processingSummary(entry)).toBeNull();
+  });
+});
@@ -1,5 +1,8 @@
 /** Where a history entry's text came from. */
+import { invoke } from '@tauri-apps/api/core';
 import type { TeachingContext } from './correctAndTeach';
+import type { AlternativeSpan } from './dictation';
+import type { Settings } from './settings';
 
 export type HistorySource = 'recording' | 'file';
 
@@ -15,6 +18,56 @@ export interface HistoryEntry {
   sourceName?: string;
   /** Local recording-start scope metadata used only for explicit teaching. */
   teachingContext?: TeachingContext;
+  /** Whisper's confidence in the text as transcribed. Dropped once the text is
+   *  refined or corrected, since it no longer describes it. */
+  confidence?: HistoryConfidence;
+  /** How much decoding produced the text, for backends that count tokens.
+   *  Dropped with `confidence` once the text changes. */
+  decode?: HistoryDecode;
+  /** Other readings of some of the words, from `transcription-alternatives`.
+   *  Dropped when the text is edited, since the word indices no longer hold. */
+  alternatives?: AlternativeSpan[];
+  /** Set when the audio left this Mac: sent to a cloud service or handed to
+   *  a transcription plugin. Absent for on-device transcription. */
+  processedOn?: 'cloud' | 'plugin';
+  /** The cloud host or plugin name. */
+  processedBy?: string;
+}
+
+/** Where a transcription ran, as reported by `transcription-complete` and
+ *  `transcribe_file`. */
+export interface HistoryProcessing {
+  on: 'device' | 'cloud' | 'plugin';
+  by?: string | null;
+}
+
+/** "Transcribed by api.openai.com", for an off-device entry's badge. */
+export function processingSummary(entry: Pick<HistoryEntry, 'processedOn' | 'processedBy'>): string | null {
+  if (!entry.processedOn) return null;
+  if (entry.processedOn === 'cloud') {
+    return entry.processedBy ? `Sent to ${entry.processedBy} for transcription` : 'Sent to a cloud service for transcription';
+  }
+  return entry.processedBy ? `Transcribed by the ${entry.processedBy} plugin` : 'Transcribed by a plugin';
+}
+
+/** Text tokens the decoder produced and the time it took. */
+export interface HistoryDecode {
+  tokens: number;
+  decodeMs: number;
+}
+
+/** "42 tokens at 84 tokens/s", for an entry's tooltip. */
+export function decodeSummary(decode: HistoryDecode): string {
+  const tokens = `${decode.tokens} ${decode.tokens === 1 ? 'token' : 'tokens'}`;
+  if (decode.decodeMs <= 0) return tokens;
+  return `${tokens} at ${Math.round((decode.tokens * 1000) / decode.decodeMs)} tokens/s`;
+}
+
+/** The part of a transcript's confidence kept with its history entry. */
+export interface HistoryConfidence {
+  /** Geometric mean token probability, 0–1. */
+  score: number;
+  low: boolean;
 }
 
 const STORAGE_KEY = 'dictation-history';
@@ -49,7 +102,11 @@ export function addHistoryEntry(
   source: HistorySource = 'recording',
   sourceName?: string,
   teachingContext?: TeachingContext,
+  confidence?: HistoryConfidence,
+  decode?: HistoryDecode,
+  processing?: HistoryProcessing,
 ): HistoryEntry[] {
+  const offDevice = processing && processing.on !== 'device' ? processing : undefined;
   const newEntry: HistoryEntry = {
     id: Date.now().toString(),
     text,
@@ -58,6 +115,9 @@ export function addHistoryEntry(
     source,
     ...(sourceName ? { sourceName } : {}),
     ...(teachingContext ? { teachingContext } : {}),
+    ...(confidence ? { confidence } : {}),
+    ...(decode ? { decode } : {}),
+    ...(offDevice ? { processedOn: offDevice.on, ...(offDevice.by ? { processedBy: offDevice.by } : {}) } : {}),
   };
   return [...entries, newEntry].slice(-MAX_ENTRIES);
 }
@@ -67,13 +127,105 @@ export function updateHistoryEntry(
   id: string,
   text: string,
 ): HistoryEntry[] {
-  return entries.map((entry) => entry.id === id ? { ...entry, text } : entry);
+  return entries.map((entry) => entry.id === id ? { ...entry, text, confidence: undefined, decode: undefined, alternatives: undefined } : entry);
+}
+
+/** Swap a dual-model draft for its refined text in the newest entry that
+ *  still holds the draft. */
+export function refineHistoryEntry(
+  entries: HistoryEntry[],
+  draft: string,
+  text: string,
+): HistoryEntry[] {
+  const index = entries.map((entry) => entry.text).lastIndexOf(draft);
+  if (index === -1) return entries;
+  return entries.map((entry, i) => i === index ? { ...entry, text, confidence: undefined, decode: undefined } : entry);
+}
+
+/** Attach alternative readings to the newest entry that still holds the text
+ *  they index into. */
+export function attachAlternatives(
+  entries: HistoryEntry[],
+  text: string,
+  spans: AlternativeSpan[],
+): HistoryEntry[] {
+  const index = entries.map((entry) => entry.text).lastIndexOf(text);
+  if (index === -1 || spans.length === 0) return entries;
+  return entries.map((entry, i) => i === index ? { ...entry, alternatives: spans } : entry);
+}
+
+/** Replace the words of one alternative span with the chosen reading. The
+ *  replaced words become an option in its place, so the pick can be undone,
+ *  and later spans shift by the change in word count. Choosing '' drops the
+ *  words and the span with them. */
+export function chooseAlternative(
+  entries: HistoryEntry[],
+  id: string,
+  spanIndex: number,
+  option: string,
+): HistoryEntry[] {
+  return entries.map((entry) => {
+    const span = entry.id === id ? entry.alternatives?.[spanIndex] : undefined;
+    if (!span) return entry;
+    const words = [...entry.text.matchAll(/\S+/g)];
+    const first = words[span.start];
+    const last = words[span.end - 1];
+    if (!first || !last || first.index === undefined || last.index === undefined) return entry;
+    const before = entry.text.slice(0, first.index);
+    const after = entry.text.slice(last.index + last[0].length);
+    const replaced = entry.text.slice(first.index, last.index + last[0].length);
+    const optionWords = option.split(/\s+/).filter(Boolean).length;
+    const shift = optionWords - (span.end - span.start);
+    const alternatives = entry.alternatives!.flatMap((other, i) => {
+      if (i === spanIndex) {
+        if (optionWords === 0) return [];
+        const options = [replaced, ...other.options.filter((candidate) => candidate !== option)];
+        return [{ start: other.start, end: other.start + optionWords, options }];
+      }
+      return other.start >= span.end ? [{ ...other, start: other.start + shift, end: other.end + shift }] : [other];
+    });
+    const text = optionWords > 0
+      ? before + option + after
+      : [before.trimEnd(), after.trimStart()].filter(Boolean).join(' ');
+    return { ...entry, text, confidence: undefined, decode: undefined, alternatives };
+  });
 }
 
 export function clearHistory(): void {
   localStorage.removeItem(STORAGE_KEY);
 }
 
+/** Where history is kept: in memory only, in localStorage, or encrypted on disk
+ *  with a Keychain key. */
+export type HistoryStorage = 'memory' | 'local' | 'encrypted';
+
+export function historyStorageFor(settings: Pick<Settings, 'persistHistory' | 'encryptHistory'>): HistoryStorage {
+  if (!settings.persistHistory) return 'memory';
+  return settings.encryptHistory ? 'encrypted' : 'local';
+}
+
+export async function loadEncryptedHistory(): Promise<HistoryEntry[]> {
+  const entries = await invoke<HistoryEntry[]>('load_encrypted_history');
+  return Array.isArray(entries) ? entries : [];
+}
+
+export async function saveEncryptedHistory(entries: HistoryEntry[]): Promise<void> {
+  await invoke('save_encrypted_history', { entries: entries.slice(-MAX_ENTRIES) });
+}
+
+export async function deleteEncryptedHistory(): Promise<void> {
+  await invoke('delete_encrypted_history');
+}
+
+/** Combine two histories by id, oldest first, keeping the newest MAX_ENTRIES. */
+export function mergeHistory(a: HistoryEntry[], b: HistoryEntry[]): HistoryEntry[] {
+  const byId = new Map<string, HistoryEntry>();
+  for (const entry of [...a, ...b]) byId.set(entry.id, entry);
+  return [...byId.values()]
+    .sort((left, right) => left.timestamp - right.timestamp)
+    .slice(-MAX_ENTRIES);
+}
+
 export function formatTimestamp(timestamp: number): string {
   const date = new Date(timestamp);
   return date.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
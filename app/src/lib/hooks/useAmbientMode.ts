@@ -0,0 +1,30 @@
+import { useEffect } from 'react';
+import { startAmbientMode, stopAmbientMode } from '../dictation';
+
+interface UseAmbientModeProps {
+  enabled: boolean;
+  initialized: boolean;
+  microphone: string;
+}
+
+/**
+ * Keeps ambient transcription running while enabled. Entries land in the
+ * ambient log and arrive on `ambient-entry`; nothing is pasted.
+ */
+export function useAmbientMode({ enabled, initialized, microphone }: UseAmbientModeProps) {
+  useEffect(() => {
+    if (!enabled || !initialized) return;
+
+    let cancelled = false;
+    startAmbientMode(microphone)
+      .then(() => {
+        if (cancelled) stopAmbientMode().catch(() => {});
+      })
+      .catch((err) => console.error('Failed to start ambient mode:', err));
+
+    return () => {
+      cancelled = true;
+      stopAmbientMode().catch(() => {});
+    };
+  }, [enabled, initialized, microphone]);
+}
@@ -0,0 +1,45 @@
+import { useEffect, useRef } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { AppTrigger } from '../settings';
+
+interface UseAppTriggersProps {
+  enabled: boolean;
+  initialized: boolean;
+  triggers: AppTrigger[];
+  /** Turns ambient mode on when the overlay's offer is accepted. */
+  onStartAmbient: () => void;
+}
+
+/**
+ * Sends the app triggers to Rust, which watches app activations, and turns
+ * ambient mode on when the overlay's offer is accepted
+ * (`ambient-start-requested`). Disabled triggers are sent as an empty list.
+ */
+export function useAppTriggers({ enabled, initialized, triggers, onStartAmbient }: UseAppTriggersProps) {
+  const onStartAmbientRef = useRef(onStartAmbient);
+  useEffect(() => { onStartAmbientRef.current = onStartAmbient; }, [onStartAmbient]);
+
+  useEffect(() => {
+    if (!initialized) return;
+    invoke('set_app_triggers', { triggers: enabled ? triggers : [] })
+      .catch((err) => console.error('Failed to apply app triggers:', err));
+  }, [enabled, initialized, triggers]);
+
+  useEffect(() => {
+    if (!enabled || !initialized) return;
+
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    listen('ambient-start-requested', () => {
+      onStartAmbientRef.current();
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [enabled, initialized]);
+}
@@ -0,0 +1,95 @@
+import { useCallback, useEffect, useRef, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { commitAppendBuffer, discardAppendBuffer, getAppendBuffer, type AppendBuffer } from '../dictation';
+import type { DoubleTapKey, ProfileKey, TransformKey } from '../settings';
+
+const EMPTY_BUFFER: AppendBuffer = { takes: 0, text: '' };
+
+interface UseAppendBufferProps {
+  enabled: boolean;
+  initialized: boolean;
+  accessibilityGranted: boolean | null;
+  commitKey: ProfileKey | null;
+  /** Keys owned by the dictation and transform hotkeys; a commit key on them stays unbound. */
+  dictationKey: DoubleTapKey;
+  transformKey: TransformKey | null;
+}
+
+/**
+ * Mirrors the backend append buffer and binds the optional commit key on the
+ * shared rdev listener, routing `append-commit-key` to `commit_append_buffer`.
+ */
+export function useAppendBuffer({ enabled, initialized, accessibilityGranted, commitKey, dictationKey, transformKey }: UseAppendBufferProps) {
+  const [buffer, setBuffer] = useState<AppendBuffer>(EMPTY_BUFFER);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+
+    listen<AppendBuffer>('append-buffer-changed', (event) => {
+      setBuffer(event.payload ?? EMPTY_BUFFER);
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    getAppendBuffer()
+      .then((current) => { if (!cancelled && current) setBuffer(current); })
+      .catch(() => {});
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const commit = useCallback(async () => {
+    try {
+      setError(null);
+      await commitAppendBuffer();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  }, []);
+
+  const discard = useCallback(async () => {
+    setError(null);
+    await discardAppendBuffer().catch((err) => console.error('Failed to discard append buffer:', err));
+  }, []);
+
+  const commitRef = useRef(commit);
+  useEffect(() => { commitRef.current = commit; }, [commit]);
+
+  const boundKey = commitKey !== null && commitKey !== dictationKey && commitKey !== transformKey ? commitKey : null;
+
+  useEffect(() => {
+    if (!enabled || !initialized || !accessibilityGranted || boundKey === null) return;
+
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+
+    const setup = async () => {
+      unlisten = await listen('append-commit-key', () => {
+        commitRef.current();
+      });
+      if (cancelled) { unlisten(); return; }
+
+      try {
+        await invoke('set_append_commit_key', { hotkey: boundKey });
+      } catch (err) {
+        console.error('Failed to bind append commit key:', err);
+      }
+    };
+
+    setup();
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      invoke('set_append_commit_key', { hotkey: null }).catch(() => {});
+    };
+  }, [enabled, initialized, accessibilityGranted, boundKey]);
+
+  return { buffer, error, commit, discard };
+}
@@ -1,7 +1,6 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
-import { check, type Update } from '@tauri-apps/plugin-updater';
-import { relaunch } from '@tauri-apps/plugin-process';
-import { getVersion } from '@tauri-apps/api/app';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import {
   isPermissionGranted,
   requestPermission,
@@ -9,16 +8,16 @@ import {
 } from '@tauri-apps/plugin-notification';
 import { flog } from '../log';
 import {
+  type UpdateInfo,
   type UpdateStatus,
-  isBelowMinVersion,
   getSkippedVersion,
   setSkippedVersion,
   clearSkippedVersion,
   isDueForCheck,
   setLastCheckTimestamp,
-  fetchMinVersion,
   CHECK_INTERVAL_MS,
 } from '../updater';
+import type { UpdateChannel } from '../settings';
 
 export interface UseAutoUpdaterReturn {
   updateStatus: UpdateStatus;
@@ -28,11 +27,19 @@ export interface UseAutoUpdaterReturn {
   dismissUpdate: () => void;
 }
 
-export function useAutoUpdater(): UseAutoUpdaterReturn {
+/**
+ * Drives the Rust updater commands: `check_for_update` on the chosen channel
+ * (the staged rollout and `min_version` are applied in Rust),
+ * `download_update`, then `install_update_on_quit` with a relaunch that Rust
+ * holds back until no dictation is in flight.
+ */
+export function useAutoUpdater(channel: UpdateChannel): UseAutoUpdaterReturn {
   const [updateStatus, setUpdateStatus] = useState<UpdateStatus>({ phase: 'idle' });
-  const updateRef = useRef<Update | null>(null);
+  const updateRef = useRef<UpdateInfo | null>(null);
   const isCheckingRef = useRef(false);
   const isForcedRef = useRef(false);
+  const channelRef = useRef(channel);
+  useEffect(() => { channelRef.current = channel; }, [channel]);
 
   const performCheck = useCallback(async (opts: { isBackground: boolean }) => {
     if (isCheckingRef.current) return;
@@ -44,11 +51,11 @@ export function useAutoUpdater(): UseAutoUpdaterReturn {
     }
 
     try {
-      const update = await check();
+      const update = await invoke<UpdateInfo | null>('check_for_update', { channel: channelRef.current });
       setLastCheckTimestamp(Date.now());
 
-      if (!update?.available || !update.version) {
-        flog.info('updater', 'no update available');
+      if (!update) {
+        flog.info('updater', 'no update available', { channel: channelRef.current });
         if (!opts.isBackground) {
           setUpdateStatus({ phase: 'up-to-date' });
           // Reset back to idle after a brief display
@@ -58,12 +65,8 @@ export function useAutoUpdater(): UseAutoUpdaterReturn {
         return;
       }
 
-      flog.info('updater', 'update available', { version: update.version });
-
-      // Check min_version (custom field not exposed by Tauri updater)
-      const currentVersion = await getVersion();
-      const minVersion = await fetchMinVersion();
-      const isForced = minVersion !== null && isBelowMinVersion(currentVersion, minVersion);
+      flog.info('updater', 'update available', { version: update.version, channel: update.channel });
+      const { isForced } = update;
 
       // If not forced and user previously skipped this version, suppress
       if (!isForced && getSkippedVersion() === update.version) {
@@ -80,7 +83,7 @@ export function useAutoUpdater(): UseAutoUpdaterReturn {
       setUpdateStatus({
         phase: 'available',
         version: update.version,
-        notes: update.body ?? '',
+        notes: update.notes,
         isForced,
       });
 
@@ -148,39 +151,36 @@ export function useAutoUpdater(): UseAutoUpdaterReturn {
     setUpdateStatus({ phase: 'downloading', version, progress: 0 });
     flog.info('updater', 'starting download', { version });
 
+    let unlistenProgress: (() => void) | null = null;
+    let unlistenDeferred: (() => void) | null = null;
     try {
-      let totalContentLength = 0;
-      let totalDownloaded = 0;
-
-      await update.downloadAndInstall((event) => {
-        switch (event.event) {
-          case 'Started':
-            totalContentLength = event.data.contentLength ?? 0;
-            flog.info('updater', 'download started', { contentLength: totalContentLength });
-            break;
-          case 'Progress':
-            totalDownloaded += event.data.chunkLength;
-            setUpdateStatus({
-              phase: 'downloading',
-              version,
-              progress: totalContentLength > 0
-                ? Math.round((totalDownloaded / totalContentLength) * 100)
-                : 0,
-            });
-            break;
-          case 'Finished':
-            flog.info('updater', 'download finished');
-            break;
-        }
+      unlistenProgress = await listen<{ downloaded: number; total: number | null }>('update-download-progress', (event) => {
+        const { downloaded, total } = event.payload;
+        setUpdateStatus({
+          phase: 'downloading',
+          version,
+          progress: total ? Math.min(100, Math.round((downloaded / total) * 100)) : 0,
+        });
       });
+      await invoke('download_update');
+      flog.info('updater', 'download finished');
 
-      setUpdateStatus({ phase: 'ready', version });
-      flog.info('updater', 'installed, relaunching');
+      setUpdateStatus({ phase: 'ready', version, deferred: false });
       clearSkippedVersion();
-      await relaunch();
+      unlistenDeferred = await listen('update-install-deferred', () => {
+        flog.info('updater', 'install deferred until dictation is idle');
+        setUpdateStatus({ phase: 'ready', version, deferred: true });
+      });
+      // Rust installs and relaunches once no recording, transcription, or
+      // transform is running.
+      await invoke('install_update_on_quit', { relaunch: true });
+      flog.info('updater', 'install scheduled, relaunching when idle');
     } catch (err) {
+      unlistenDeferred?.();
       flog.error('updater', 'download/install failed', { error: String(err) });
       setUpdateStatus({ phase: 'error', message: String(err), isForced: isForcedRef.current });
+    } finally {
+      unlistenProgress?.();
     }
   }, [updateStatus]);
 
@@ -0,0 +1,60 @@
+import { useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { transcribeClipboardAudio } from '../dictation';
+import type { DoubleTapKey, ProfileKey, TransformKey } from '../settings';
+
+interface UseClipboardAudioProps {
+  enabled: boolean;
+  initialized: boolean;
+  accessibilityGranted: boolean | null;
+  clipboardAudioKey: ProfileKey | null;
+  /** Keys owned by other hotkeys; a copied-audio key on one of them stays unbound. */
+  dictationKey: DoubleTapKey;
+  transformKey: TransformKey | null;
+  appendCommitKey: ProfileKey | null;
+  pasteLastKey: ProfileKey | null;
+  markerKey: ProfileKey | null;
+}
+
+/**
+ * Binds the optional copied-audio key on the shared rdev listener, routing
+ * `clipboard-audio-key` to `transcribe_clipboard_audio`. Failures arrive as
+ * `clipboard-audio-failed`, which `useRecordingState` shows.
+ */
+export function useClipboardAudio({
+  enabled, initialized, accessibilityGranted, clipboardAudioKey, dictationKey, transformKey, appendCommitKey, pasteLastKey, markerKey,
+}: UseClipboardAudioProps) {
+  const boundKey = clipboardAudioKey !== null
+    && ![dictationKey, transformKey, appendCommitKey, pasteLastKey, markerKey].includes(clipboardAudioKey)
+    ? clipboardAudioKey
+    : null;
+
+  useEffect(() => {
+    if (!enabled || !initialized || !accessibilityGranted || boundKey === null) return;
+
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+
+    const setup = async () => {
+      unlisten = await listen('clipboard-audio-key', () => {
+        transcribeClipboardAudio().catch((err) => console.error('Failed to transcribe copied audio:', err));
+      });
+      if (cancelled) { unlisten(); return; }
+
+      try {
+        await invoke('set_clipboard_audio_key', { hotkey: boundKey });
+      } catch (err) {
+        console.error('Failed to bind copied-audio key:', err);
+      }
+    };
+
+    setup();
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      invoke('set_clipboard_audio_key', { hotkey: null }).catch(() => {});
+    };
+  }, [enabled, initialized, accessibilityGranted, boundKey]);
+}
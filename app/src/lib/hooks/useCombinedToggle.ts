@@ -1,6 +1,7 @@
 import { useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
+import type { HotkeyTiming } from '../settings';
 import type { DictationStatus } from '../types';
 
 interface UseCombinedToggleProps {
@@ -8,13 +9,14 @@ interface UseCombinedToggleProps {
   initialized: boolean;
   accessibilityGranted: boolean | null;
   triggerKey: string;
+  timing: HotkeyTiming;
   status: DictationStatus;
   onStart: () => void;
   onStop: () => void;
   onToggle: () => void;
 }
 
-export function useCombinedToggle({ enabled, initialized, accessibilityGranted, triggerKey, status, onStart, onStop, onToggle }: UseCombinedToggleProps) {
+export function useCombinedToggle({ enabled, initialized, accessibilityGranted, triggerKey, timing, status, onStart, onStop, onToggle }: UseCombinedToggleProps) {
   const onStartRef = useRef(onStart);
   const onStopRef = useRef(onStop);
   const onToggleRef = useRef(onToggle);
@@ -79,7 +81,7 @@ export function useCombinedToggle({ enabled, initialized, accessibilityGranted,
         await new Promise<void>((r) => setTimeout(r, 2000));
         if (!cancelled) {
           try {
-            await invoke('start_keyboard_listener', { hotkey: triggerKey, mode: 'both' });
+            await invoke('start_keyboard_listener', { hotkey: triggerKey, mode: 'both', timing });
           } catch (err) {
             console.error('Failed to restart combined listener after error:', err);
           }
@@ -88,7 +90,7 @@ export function useCombinedToggle({ enabled, initialized, accessibilityGranted,
       if (cancelled) { unlistenStart(); unlistenStop(); unlistenCancel(); unlistenToggle(); unlistenError(); return; }
 
       try {
-        await invoke('start_keyboard_listener', { hotkey: triggerKey, mode: 'both' });
+        await invoke('start_keyboard_listener', { hotkey: triggerKey, mode: 'both', timing });
         if (cancelled) {
           invoke('stop_keyboard_listener').catch(() => {});
         }
@@ -109,5 +111,5 @@ export function useCombinedToggle({ enabled, initialized, accessibilityGranted,
       unlistenError?.();
       invoke('stop_keyboard_listener').catch(() => {});
     };
-  }, [enabled, initialized, accessibilityGranted, triggerKey]);
+  }, [enabled, initialized, accessibilityGranted, triggerKey, timing]);
 }
@@ -0,0 +1,56 @@
+import { useCallback, useEffect, useMemo, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { flog } from '../log';
+import { matchingPreset, presetUpdates, syncConfigPresets } from '../presets';
+import type { Settings } from '../settings';
+
+interface UseConfigPresetsProps {
+  initialized: boolean;
+  settings: Settings;
+  update: (updates: Partial<Settings>) => void;
+}
+
+/**
+ * Mirrors the configuration presets to Rust for the tray's Presets submenu,
+ * with the one the current settings match checked, and applies the presets
+ * the tray or `apply_config_preset` ask for (`config-preset-requested`).
+ * Returns `applyPreset` for dictation macros.
+ */
+export function useConfigPresets({ initialized, settings, update }: UseConfigPresetsProps) {
+  const latestRef = useRef({ settings, update });
+  latestRef.current = { settings, update };
+
+  const presets = settings.configPresets;
+  const activeId = useMemo(() => matchingPreset(settings, presets)?.id ?? null, [settings, presets]);
+
+  useEffect(() => {
+    if (!initialized) return;
+    syncConfigPresets(presets, activeId)
+      .catch((err) => console.error('Failed to sync config presets:', err));
+  }, [initialized, presets, activeId]);
+
+  const applyPreset = useCallback((id: string) => {
+    const { settings: current, update: apply } = latestRef.current;
+    const preset = current.configPresets.find((candidate) => candidate.id === id);
+    if (!preset) {
+      flog.warn('presets', 'requested preset no longer exists');
+      return;
+    }
+    flog.info('presets', 'applying preset', { keys: Object.keys(preset.settings).length });
+    apply(presetUpdates(current, preset));
+  }, []);
+
+  useEffect(() => {
+    if (!initialized) return;
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ id: string; name: string }>('config-preset-requested', (event) => {
+      if (!cancelled) applyPreset(event.payload.id);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, [initialized, applyPreset]);
+
+  return { applyPreset };
+}
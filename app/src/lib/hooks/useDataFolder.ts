@@ -0,0 +1,144 @@
+import { useEffect, useRef, useState } from 'react';
+import type { HistoryEntry } from '../history';
+import { flog } from '../log';
+import {
+  importedHistory,
+  importedSettings,
+  loadSyncState,
+  portableSettings,
+  readDataFolder,
+  saveSyncState,
+  writeDataFolder,
+} from '../portable';
+import type { Settings } from '../settings';
+
+/** Changes are written this long after the last one, so a slider drag is one write. */
+const WRITE_DELAY_MS = 2000;
+
+interface UseDataFolderProps {
+  initialized: boolean;
+  /** `settings.dataFolder`; empty turns syncing off. */
+  folder: string;
+  settings: Settings;
+  historyEntries: HistoryEntry[];
+  /** History is shared only while it is kept as plain text in localStorage. */
+  shareHistory: boolean;
+  onSettings: (updates: Partial<Settings>) => void;
+  /** Merges entries from another Mac into history. */
+  onHistory: (entries: HistoryEntry[]) => void;
+}
+
+/**
+ * Keeps settings and history in step with the sync folder. The folder is read
+ * when it is chosen, at startup, and whenever the window regains focus; a
+ * newer settings archive replaces this Mac's portable settings and newer
+ * history is merged by entry id. Local changes are written back a moment
+ * later, after checking the folder hasn't moved on in the meantime. Returns
+ * the last sync error, cleared by the next successful sync.
+ */
+export function useDataFolder({ initialized, folder, settings, historyEntries, shareHistory, onSettings, onHistory }: UseDataFolderProps) {
+  const [error, setError] = useState<string | null>(null);
+  // The folder whose first read has finished; nothing is written before it.
+  const [readFolder, setReadFolder] = useState<string | null>(null);
+  const onSettingsRef = useRef(onSettings);
+  const onHistoryRef = useRef(onHistory);
+  useEffect(() => { onSettingsRef.current = onSettings; }, [onSettings]);
+  useEffect(() => { onHistoryRef.current = onHistory; }, [onHistory]);
+  const latestRef = useRef({ settings, historyEntries, shareHistory });
+  latestRef.current = { settings, historyEntries, shareHistory };
+  // JSON of what the folder is known to hold, so pulled data isn't written straight back.
+  const knownRef = useRef<{ settings: string | null; history: string | null }>({ settings: null, history: null });
+  const queueRef = useRef<Promise<void>>(Promise.resolve());
+  const syncRef = useRef<(folder: string) => Promise<void>>(async () => {});
+
+  // Pull anything newer; when there was nothing, write what the folder lacks.
+  // Pulled changes are written back (e.g. merged history) on the next pass,
+  // once they have reached state.
+  syncRef.current = async (target: string) => {
+    const contents = await readDataFolder(target);
+    const state = loadSyncState(target);
+    const known = knownRef.current;
+    const { settings: current, historyEntries: entries, shareHistory: share } = latestRef.current;
+    let pulled = false;
+    if (contents.settings && contents.settings.exportedAtMs > state.settingsAt) {
+      const updates = importedSettings(contents.settings.data);
+      known.settings = JSON.stringify(updates);
+      state.settingsAt = contents.settings.exportedAtMs;
+      onSettingsRef.current(updates);
+      pulled = true;
+    }
+    if (share && contents.history && contents.history.exportedAtMs > state.historyAt) {
+      const imported = importedHistory(contents.history.data);
+      known.history = JSON.stringify(imported);
+      state.historyAt = contents.history.exportedAtMs;
+      onHistoryRef.current(imported);
+      pulled = true;
+    }
+    if (!pulled) {
+      const portable = portableSettings(current);
+      const settingsJson = JSON.stringify(portable);
+      const historyJson = share ? JSON.stringify(entries) : null;
+      const sendSettings = settingsJson !== known.settings;
+      const sendHistory = historyJson !== null && historyJson !== known.history;
+      if (sendSettings || sendHistory) {
+        const stamp = await writeDataFolder(target, sendSettings ? portable : null, sendHistory ? entries : null);
+        if (sendSettings) {
+          known.settings = settingsJson;
+          state.settingsAt = stamp;
+        }
+        if (sendHistory) {
+          known.history = historyJson;
+          state.historyAt = stamp;
+        }
+        flog.info('sync', 'wrote to sync folder', { settings: sendSettings, history: sendHistory });
+      }
+    }
+    saveSyncState(state);
+  };
+
+  useEffect(() => {
+    setReadFolder(null);
+    if (!initialized || !folder) {
+      setError(null);
+      return;
+    }
+    let cancelled = false;
+    knownRef.current = { settings: null, history: null };
+    const sync = () => {
+      queueRef.current = queueRef.current
+        .then(() => (cancelled ? undefined : syncRef.current(folder)))
+        .then(() => { if (!cancelled) { setError(null); setReadFolder(folder); } })
+        .catch((e: unknown) => {
+          if (cancelled) return;
+          setError(String(e));
+          flog.warn('sync', 'sync folder unavailable', { error: String(e) });
+        });
+    };
+    sync();
+    window.addEventListener('focus', sync);
+    return () => {
+      cancelled = true;
+      window.removeEventListener('focus', sync);
+    };
+  }, [initialized, folder]);
+
+  useEffect(() => {
+    if (!readFolder || readFolder !== folder) return;
+    const known = knownRef.current;
+    const settingsChanged = JSON.stringify(portableSettings(settings)) !== known.settings;
+    const historyChanged = shareHistory && JSON.stringify(historyEntries) !== known.history;
+    if (!settingsChanged && !historyChanged) return;
+    const timer = setTimeout(() => {
+      queueRef.current = queueRef.current
+        .then(() => syncRef.current(folder))
+        .then(() => setError(null))
+        .catch((e: unknown) => {
+          setError(String(e));
+          flog.warn('sync', 'sync folder write failed', { error: String(e) });
+        });
+    }, WRITE_DELAY_MS);
+    return () => clearTimeout(timer);
+  }, [readFolder, folder, settings, historyEntries, shareHistory]);
+
+  return { error };
+}
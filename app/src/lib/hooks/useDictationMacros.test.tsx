@@ -0,0 +1,89 @@
+import { act } from 'react';
+import { createRoot, type Root } from 'react-dom/client';
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+type MacroListener = (event: { payload: unknown }) => void;
+
+const mocks = vi.hoisted(() => ({
+  listener: null as MacroListener | null,
+  unlisten: vi.fn(),
+}));
+
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: vi.fn(async (_event: string, listener: MacroListener) => {
+    mocks.listener = listener;
+    return mocks.unlisten;
+  }),
+}));
+
+import { useDictationMacros } from './useDictationMacros';
+
+describe('useDictationMacros', () => {
+  let container: HTMLDivElement;
+  let root: Root;
+  const update = vi.fn();
+  const openSettings = vi.fn();
+  const openHistory = vi.fn();
+  const applyPreset = vi.fn();
+
+  function Harness() {
+    useDictationMacros({ update, openSettings, openHistory, applyPreset });
+    return null;
+  }
+
+  async function mount() {
+    await act(async () => {
+      root.render(<Harness />);
+      await Promise.resolve();
+    });
+  }
+
+  function fire(action: unknown) {
+    act(() => { mocks.listener?.({ payload: { recordingId: 1, id: 'macro', action } }); });
+  }
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    mocks.listener = null;
+    container = document.createElement('div');
+    document.body.appendChild(container);
+    root = createRoot(container);
+  });
+
+  afterEach(async () => {
+    await act(async () => root.unmount());
+    container.remove();
+  });
+
+  it('opens history or settings', async () => {
+    await mount();
+    fire({ type: 'open_history' });
+    expect(openHistory).toHaveBeenCalledTimes(1);
+    fire({ type: 'open_settings' });
+    expect(openSettings).toHaveBeenCalledTimes(1);
+    expect(update).not.toHaveBeenCalled();
+  });
+
+  it('changes auto-paste and the model through settings', async () => {
+    await mount();
+    fire({ type: 'set_auto_paste', enabled: false });
+    expect(update).toHaveBeenLastCalledWith({ autoPaste: false });
+    fire({ type: 'set_model', model: 'base.en' });
+    expect(update).toHaveBeenLastCalledWith({ model: 'base.en' });
+  });
+
+  it('hands presets to applyPreset', async () => {
+    await mount();
+    fire({ type: 'apply_preset', preset: 'coding' });
+    expect(applyPreset).toHaveBeenCalledWith('coding');
+    expect(update).not.toHaveBeenCalled();
+  });
+
+  it('ignores a model this platform does not offer', async () => {
+    await mount();
+    fire({ type: 'set_model', model: 'not-a-model' });
+    fire({ type: 'copy_last_transcription' });
+    expect(update).not.toHaveBeenCalled();
+    expect(openHistory).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,58 @@
+import { useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { AVAILABLE_MODEL_OPTIONS, isCloudModel, isCustomModel, isExternalModel, type Settings } from '../settings';
+import type { DictationMacroRan } from '../dictation';
+
+interface DictationMacroHandlers {
+  update: (updates: Partial<Settings>) => void;
+  openSettings: () => void;
+  openHistory: () => void;
+  applyPreset: (id: string) => void;
+}
+
+/**
+ * Carries out the frontend half of a dictation macro. The backend has already
+ * shown the main window (and copied the last transcript); settings changes go
+ * through `update` like the tray's quick toggles, so they persist and
+ * reconfigure the backend.
+ */
+export function useDictationMacros(handlers: DictationMacroHandlers) {
+  // The handlers are recreated every render; read them through a ref so the
+  // listener is registered once.
+  const handlersRef = useRef(handlers);
+  useEffect(() => { handlersRef.current = handlers; }, [handlers]);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<DictationMacroRan>('dictation-macro', (event) => {
+      if (cancelled) return;
+      const { action } = event.payload;
+      const { update, openSettings, openHistory, applyPreset } = handlersRef.current;
+      switch (action.type) {
+        case 'open_history':
+          openHistory();
+          break;
+        case 'open_settings':
+          openSettings();
+          break;
+        case 'set_auto_paste':
+          update({ autoPaste: action.enabled });
+          break;
+        case 'set_model':
+          if (isCustomModel(action.model) || isExternalModel(action.model) || isCloudModel(action.model) || AVAILABLE_MODEL_OPTIONS.some((option) => option.value === action.model)) {
+            update({ model: action.model });
+          }
+          break;
+        case 'apply_preset':
+          applyPreset(action.preset);
+          break;
+        case 'copy_last_transcription':
+          break;
+      }
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+}
@@ -0,0 +1,70 @@
+import { useEffect, useRef } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { DictationProfile, DoubleTapKey, TransformKey } from '../settings';
+
+interface UseDictationProfileHotkeysProps {
+  enabled: boolean;
+  initialized: boolean;
+  accessibilityGranted: boolean | null;
+  profiles: DictationProfile[];
+  /** Keys owned by the dictation and transform hotkeys; profiles on them stay unbound. */
+  dictationKey: DoubleTapKey;
+  transformKey: TransformKey | null;
+  onStart: (profileId: string) => void;
+  onStop: () => void;
+}
+
+/**
+ * Binds each dictation profile's hold-down key on the shared rdev listener and
+ * routes `profile-hold-start` / `profile-hold-stop` to the recorder. The main
+ * hotkey hook owns starting and stopping the listener itself.
+ */
+export function useDictationProfileHotkeys({ enabled, initialized, accessibilityGranted, profiles, dictationKey, transformKey, onStart, onStop }: UseDictationProfileHotkeysProps) {
+  const onStartRef = useRef(onStart);
+  const onStopRef = useRef(onStop);
+  useEffect(() => { onStartRef.current = onStart; }, [onStart]);
+  useEffect(() => { onStopRef.current = onStop; }, [onStop]);
+
+  const bindings = profiles
+    .filter((profile) => profile.hotkey !== dictationKey && profile.hotkey !== transformKey)
+    .map((profile) => ({ profileId: profile.id, hotkey: profile.hotkey }));
+  const bindingsKey = JSON.stringify(bindings);
+
+  useEffect(() => {
+    if (!enabled || !initialized || !accessibilityGranted || bindings.length === 0) return;
+
+    let unlistenStart: (() => void) | null = null;
+    let unlistenStop: (() => void) | null = null;
+    let cancelled = false;
+
+    const setup = async () => {
+      unlistenStart = await listen<{ profileId: string }>('profile-hold-start', (event) => {
+        if (typeof event.payload?.profileId !== 'string') return;
+        onStartRef.current(event.payload.profileId);
+      });
+      if (cancelled) { unlistenStart(); return; }
+
+      unlistenStop = await listen('profile-hold-stop', () => {
+        onStopRef.current();
+      });
+      if (cancelled) { unlistenStart(); unlistenStop(); return; }
+
+      try {
+        await invoke('set_dictation_profile_keys', { bindings });
+      } catch (err) {
+        console.error('Failed to bind dictation profile keys:', err);
+      }
+    };
+
+    setup();
+
+    return () => {
+      cancelled = true;
+      unlistenStart?.();
+      unlistenStop?.();
+      invoke('set_dictation_profile_keys', { bindings: [] }).catch(() => {});
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [enabled, initialized, accessibilityGranted, bindingsKey]);
+}
@@ -1,6 +1,7 @@
 import { useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
+import type { HotkeyTiming } from '../settings';
 import type { DictationStatus } from '../types';
 
 interface UseDoubleTapToggleProps {
@@ -8,11 +9,12 @@ interface UseDoubleTapToggleProps {
   initialized: boolean;
   accessibilityGranted: boolean | null;
   doubleTapKey: string;
+  timing: HotkeyTiming;
   status: DictationStatus;
   onToggle: () => void;
 }
 
-export function useDoubleTapToggle({ enabled, initialized, accessibilityGranted, doubleTapKey, status, onToggle }: UseDoubleTapToggleProps) {
+export function useDoubleTapToggle({ enabled, initialized, accessibilityGranted, doubleTapKey, timing, status, onToggle }: UseDoubleTapToggleProps) {
   const onToggleRef = useRef(onToggle);
   useEffect(() => { onToggleRef.current = onToggle; }, [onToggle]);
 
@@ -46,7 +48,7 @@ export function useDoubleTapToggle({ enabled, initialized, accessibilityGranted,
         await new Promise<void>((r) => setTimeout(r, 2000));
         if (!cancelled) {
           try {
-            await invoke('start_keyboard_listener', { hotkey: doubleTapKey, mode: 'double_tap' });
+            await invoke('start_keyboard_listener', { hotkey: doubleTapKey, mode: 'double_tap', timing });
           } catch (err) {
             console.error('Failed to restart double-tap listener after error:', err);
           }
@@ -61,7 +63,7 @@ export function useDoubleTapToggle({ enabled, initialized, accessibilityGranted,
 
       // Start the rdev listener
       try {
-        await invoke('start_keyboard_listener', { hotkey: doubleTapKey, mode: 'double_tap' });
+        await invoke('start_keyboard_listener', { hotkey: doubleTapKey, mode: 'double_tap', timing });
         if (cancelled) {
           invoke('stop_keyboard_listener').catch(() => {});
         }
@@ -80,5 +82,5 @@ export function useDoubleTapToggle({ enabled, initialized, accessibilityGranted,
         console.warn('Failed to stop double-tap listener on cleanup:', err);
       });
     };
-  }, [enabled, initialized, accessibilityGranted, doubleTapKey]);
+  }, [enabled, initialized, accessibilityGranted, doubleTapKey, timing]);
 }
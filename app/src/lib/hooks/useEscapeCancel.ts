@@ -2,7 +2,7 @@ import { useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { cancelRecording } from '../dictation';
-import type { DictationStatus } from '../types';
+import { isSettledStatus, type DictationStatus } from '../types';
 
 interface UseEscapeCancelProps {
   status: DictationStatus;
@@ -58,7 +58,7 @@ export function useEscapeCancel({ status, enabled }: UseEscapeCancelProps) {
         // across the brief transition-before-focus handoff; Applying has no
         // global Escape action. A null target can therefore only fall back to
         // dictation cancellation.
-        if (statusRef.current === 'idle') return;
+        if (isSettledStatus(statusRef.current)) return;
         await cancelRecording();
       } catch (err) {
         console.error('Escape cancellation failed:', err);
@@ -2,6 +2,8 @@ import { useState, useEffect, useCallback, useRef } from 'react';
 import { getCurrentWebview } from '@tauri-apps/api/webview';
 import { transcribeFile } from '../dictation';
 import { flog } from '../log';
+import type { TeachingContext } from '../correctAndTeach';
+import type { HistoryConfidence, HistoryDecode, HistoryProcessing } from '../history';
 import {
   QueueItem,
   UNSUPPORTED_MESSAGE,
@@ -16,7 +18,7 @@ export type { QueueItem, QueueItemStatus } from '../fileQueue';
 
 interface UseFileTranscriptionProps {
   /** Persist completed transcriptions to shared history (no WPM stats). */
-  addEntry: (text: string, duration: number, source?: 'recording' | 'file', sourceName?: string) => void;
+  addEntry: (text: string, duration: number, source?: 'recording' | 'file', sourceName?: string, teachingContext?: TeachingContext, confidence?: HistoryConfidence, decode?: HistoryDecode, processing?: HistoryProcessing) => void;
 }
 
 /**
@@ -74,7 +76,8 @@ export function useFileTranscription({ addEntry }: UseFileTranscriptionProps) {
         const text = res.text || '';
         setQueue((q) => updateItem(q, item.id, { status: 'done', text }));
         if (text.trim()) {
-          addEntryRef.current(text, res.duration ?? 0, 'file', item.name);
+          const processing = res.processedOn ? { on: res.processedOn, by: res.processedBy } : undefined;
+          addEntryRef.current(text, res.duration ?? 0, 'file', item.name, undefined, undefined, undefined, processing);
         }
         flog.info('file-transcribe', 'complete', { textLen: text.length });
       }
@@ -1,30 +1,157 @@
-import { useState, useCallback } from 'react';
+import { useState, useCallback, useEffect, useRef } from 'react';
 import type { TeachingContext } from '../correctAndTeach';
-import { HistoryEntry, HistorySource, loadHistory, saveHistory, addHistoryEntry, updateHistoryEntry, clearHistory as clearPersistedHistory } from '../history';
+import { correctTranscription, type AlternativeSpan } from '../dictation';
+import {
+  HistoryConfidence,
+  HistoryDecode,
+  HistoryEntry,
+  HistoryProcessing,
+  HistorySource,
+  HistoryStorage,
+  loadHistory,
+  saveHistory,
+  addHistoryEntry,
+  updateHistoryEntry,
+  refineHistoryEntry,
+  attachAlternatives,
+  chooseAlternative,
+  clearHistory as clearPersistedHistory,
+  loadEncryptedHistory,
+  saveEncryptedHistory,
+  deleteEncryptedHistory,
+  mergeHistory,
+} from '../history';
+import { flog } from '../log';
 
-export function useHistoryManagement() {
-  const [historyEntries, setHistoryEntries] = useState<HistoryEntry[]>(() => loadHistory());
+/** History state, kept per `storage`. Switching to 'encrypted' migrates any
+ *  plaintext entries into the encrypted store and removes them from
+ *  localStorage; switching away decrypts back (or drops them for 'memory').
+ *  While the encrypted history can't be read, new entries stay in memory only
+ *  and `error` says so; the encrypted file is never deleted unless it was
+ *  decrypted first, so a Keychain hiccup can't cost the user their history. */
+export function useHistoryManagement(storage: HistoryStorage = 'local') {
+  const [historyEntries, setHistoryEntries] = useState<HistoryEntry[]>(() => storage === 'local' ? loadHistory() : []);
+  const [error, setError] = useState<string | null>(null);
+  const entriesRef = useRef(historyEntries);
+  const storageRef = useRef(storage);
+  const previousStorageRef = useRef<HistoryStorage | null>(null);
+  // Encrypted saves wait for the first decrypt, so an early entry can't
+  // overwrite the stored history.
+  const encryptedReadyRef = useRef(false);
+  entriesRef.current = historyEntries;
+  storageRef.current = storage;
 
-  const addEntry = useCallback((text: string, duration: number, source: HistorySource = 'recording', sourceName?: string, teachingContext?: TeachingContext) => {
+  const persist = useCallback((entries: HistoryEntry[]) => {
+    if (storageRef.current === 'local') {
+      saveHistory(entries);
+    } else if (storageRef.current === 'encrypted' && encryptedReadyRef.current) {
+      saveEncryptedHistory(entries).catch((e: unknown) => {
+        flog.warn('history', 'encrypted history save failed', { error: String(e) });
+        setError(`Couldn't save encrypted history: ${String(e)}`);
+      });
+    } else if (storageRef.current === 'encrypted') {
+      flog.warn('history', 'encrypted history locked; entry kept in memory only');
+    }
+  }, []);
+
+  useEffect(() => {
+    const previous = previousStorageRef.current;
+    previousStorageRef.current = storage;
+    let cancelled = false;
+    const decrypted = encryptedReadyRef.current;
+    encryptedReadyRef.current = false;
+    setError(null);
+    if (storage === 'encrypted') {
+      loadEncryptedHistory()
+        .then(async (stored) => {
+          if (cancelled) return;
+          const merged = mergeHistory(stored, mergeHistory(loadHistory(), entriesRef.current));
+          setHistoryEntries((current) => mergeHistory(merged, current));
+          await saveEncryptedHistory(merged);
+          encryptedReadyRef.current = true;
+          // Only drop the plaintext copy once the encrypted one is written.
+          clearPersistedHistory();
+        })
+        .catch((e: unknown) => {
+          flog.warn('history', 'encrypted history unavailable', { error: String(e) });
+          if (!cancelled) setError(`Encrypted history couldn't be unlocked, so new dictations aren't being saved: ${String(e)}`);
+        });
+    } else {
+      if (storage === 'local') {
+        setHistoryEntries((current) => {
+          saveHistory(current);
+          return current;
+        });
+      } else {
+        clearPersistedHistory();
+      }
+      if (previous === 'encrypted' && decrypted) {
+        deleteEncryptedHistory().catch((e: unknown) => flog.warn('history', 'encrypted history delete failed', { error: String(e) }));
+      } else if (previous === 'encrypted') {
+        flog.warn('history', 'encrypted history kept: it was never decrypted');
+      }
+    }
+    return () => { cancelled = true; };
+  }, [storage]);
+
+  const addEntry = useCallback((text: string, duration: number, source: HistorySource = 'recording', sourceName?: string, teachingContext?: TeachingContext, confidence?: HistoryConfidence, decode?: HistoryDecode, processing?: HistoryProcessing) => {
     setHistoryEntries(prev => {
-      const newHistory = addHistoryEntry(prev, text, duration, source, sourceName, teachingContext);
-      saveHistory(newHistory);
+      const newHistory = addHistoryEntry(prev, text, duration, source, sourceName, teachingContext, confidence, decode, processing);
+      persist(newHistory);
       return newHistory;
     });
-  }, []);
+  }, [persist]);
 
   const updateEntry = useCallback((id: string, text: string) => {
+    const original = entriesRef.current.find((entry) => entry.id === id)?.text;
+    if (original !== undefined && original !== text) {
+      correctTranscription(id, original, text).catch((e: unknown) => flog.warn('history', 'correction not recorded', { error: String(e) }));
+    }
     setHistoryEntries(prev => {
       const newHistory = updateHistoryEntry(prev, id, text);
-      saveHistory(newHistory);
+      persist(newHistory);
       return newHistory;
     });
-  }, []);
+  }, [persist]);
+
+  const refineEntry = useCallback((draft: string, text: string) => {
+    setHistoryEntries(prev => {
+      const newHistory = refineHistoryEntry(prev, draft, text);
+      if (newHistory !== prev) persist(newHistory);
+      return newHistory;
+    });
+  }, [persist]);
+
+  const attachEntryAlternatives = useCallback((text: string, spans: AlternativeSpan[]) => {
+    setHistoryEntries(prev => {
+      const newHistory = attachAlternatives(prev, text, spans);
+      if (newHistory !== prev) persist(newHistory);
+      return newHistory;
+    });
+  }, [persist]);
+
+  const chooseEntryAlternative = useCallback((id: string, spanIndex: number, option: string) => {
+    setHistoryEntries(prev => {
+      const newHistory = chooseAlternative(prev, id, spanIndex, option);
+      persist(newHistory);
+      return newHistory;
+    });
+  }, [persist]);
+
+  /** Merge entries from an import or another Mac, by id. */
+  const importEntries = useCallback((entries: HistoryEntry[]) => {
+    setHistoryEntries(prev => {
+      const newHistory = mergeHistory(prev, entries);
+      persist(newHistory);
+      return newHistory;
+    });
+  }, [persist]);
 
   const clearHistory = useCallback(() => {
     setHistoryEntries([]);
     clearPersistedHistory();
+    deleteEncryptedHistory().catch((e: unknown) => flog.warn('history', 'encrypted history delete failed', { error: String(e) }));
   }, []);
 
-  return { historyEntries, addEntry, updateEntry, clearHistory };
+  return { historyEntries, error, addEntry, updateEntry, refineEntry, attachEntryAlternatives, chooseEntryAlternative, importEntries, clearHistory };
 }
@@ -1,17 +1,19 @@
 import { useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
+import type { HotkeyTiming } from '../settings';
 
 interface UseHoldDownToggleProps {
   enabled: boolean;
   initialized: boolean;
   accessibilityGranted: boolean | null;
   holdDownKey: string;
+  timing: HotkeyTiming;
   onStart: () => void;
   onStop: () => void;
 }
 
-export function useHoldDownToggle({ enabled, initialized, accessibilityGranted, holdDownKey, onStart, onStop }: UseHoldDownToggleProps) {
+export function useHoldDownToggle({ enabled, initialized, accessibilityGranted, holdDownKey, timing, onStart, onStop }: UseHoldDownToggleProps) {
   const onStartRef = useRef(onStart);
   const onStopRef = useRef(onStop);
   useEffect(() => { onStartRef.current = onStart; }, [onStart]);
@@ -42,7 +44,7 @@ export function useHoldDownToggle({ enabled, initialized, accessibilityGranted,
         await new Promise<void>((r) => setTimeout(r, 2000));
         if (!cancelled) {
           try {
-            await invoke('start_keyboard_listener', { hotkey: holdDownKey, mode: 'hold_down' });
+            await invoke('start_keyboard_listener', { hotkey: holdDownKey, mode: 'hold_down', timing });
           } catch (err) {
             console.error('Failed to restart hold-down listener after error:', err);
           }
@@ -52,7 +54,7 @@ export function useHoldDownToggle({ enabled, initialized, accessibilityGranted,
 
       // Start the rdev listener
       try {
-        await invoke('start_keyboard_listener', { hotkey: holdDownKey, mode: 'hold_down' });
+        await invoke('start_keyboard_listener', { hotkey: holdDownKey, mode: 'hold_down', timing });
         if (cancelled) {
           invoke('stop_keyboard_listener').catch(() => {});
         }
@@ -70,5 +72,5 @@ export function useHoldDownToggle({ enabled, initialized, accessibilityGranted,
       unlistenError?.();
       invoke('stop_keyboard_listener').catch(() => {});
     };
-  }, [enabled, initialized, accessibilityGranted, holdDownKey]);
+  }, [enabled, initialized, accessibilityGranted, holdDownKey, timing]);
 }
@@ -1,6 +1,7 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { initDictation, configure, buildConfigureOptions } from '../dictation';
+import { initDictation, configure, buildConfigureOptions, setDownloadNetwork, setCloudTranscription } from '../dictation';
+import { setStorageRetention } from '../storage';
 import { Settings } from '../settings';
 
 export function useInitialization(settings: Settings) {
@@ -18,6 +19,42 @@ export function useInitialization(settings: Settings) {
         if (cancelled) return;
         return invoke('set_app_disabled', { disabled: settings.disabled }).catch(() => {});
       })
+      .then(() => {
+        if (cancelled) return;
+        return invoke('set_quiet_hours', { ...settings.quietHours }).catch(() => {});
+      })
+      .then(() => {
+        if (cancelled) return;
+        return invoke('set_input_device_preferences', { devices: settings.microphonePreferences }).catch(() => {});
+      })
+      .then(() => {
+        if (cancelled) return;
+        return invoke('set_low_quality_input_action', { action: settings.lowQualityMicAction }).catch(() => {});
+      })
+      .then(() => {
+        if (cancelled) return;
+        return invoke('set_overlay_display', { mode: settings.overlayDisplay }).catch(() => {});
+      })
+      .then(() => {
+        if (cancelled) return;
+        return invoke('set_overlay_style', { style: settings.overlayStyle, pillPosition: settings.overlayPillPosition }).catch(() => {});
+      })
+      .then(() => {
+        if (cancelled) return;
+        return invoke('set_overlay_fullscreen', { mode: settings.overlayFullscreen }).catch(() => {});
+      })
+      .then(() => {
+        if (cancelled) return;
+        return setDownloadNetwork(settings.downloadNetwork).catch(() => {});
+      })
+      .then(() => {
+        if (cancelled) return;
+        return setCloudTranscription(settings.cloudTranscription).catch(() => {});
+      })
+      .then(() => {
+        if (cancelled) return;
+        return setStorageRetention(settings.storageRetention).catch(() => {});
+      })
       .then(() => { if (!cancelled) setInitialized(true); })
       .catch((err) => { if (!cancelled) setError(String(err)); });
     return () => { cancelled = true; };
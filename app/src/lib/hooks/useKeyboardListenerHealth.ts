@@ -0,0 +1,72 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { relaunch } from '@tauri-apps/plugin-process';
+import { flog } from '../log';
+
+/** Payload of `keyboard-listener-degraded`, from Rust's listener health monitor. */
+export interface KeyboardListenerDegraded {
+  reason: 'thread_exited' | 'permission_revoked' | 'tap_silent';
+  action: 'grant_accessibility' | 'retry_listener' | 'restart_app';
+  restartAttempts: number;
+}
+
+/**
+ * Tracks whether the global hotkey listener is degraded. Rust re-registers a
+ * dead event tap on its own and only reports what it could not fix; this hook
+ * also asks once on mount, since the event can fire before the window listens.
+ */
+export function useKeyboardListenerHealth() {
+  const [degraded, setDegraded] = useState<KeyboardListenerDegraded | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlistenDegraded: (() => void) | null = null;
+    let unlistenRecovered: (() => void) | null = null;
+    invoke<KeyboardListenerDegraded | null>('get_keyboard_listener_health')
+      .then((current) => { if (!cancelled && current) setDegraded(current); })
+      .catch((err) => flog.warn('keyboard', 'get_keyboard_listener_health failed', { error: String(err) }));
+    listen<KeyboardListenerDegraded>('keyboard-listener-degraded', (event) => {
+      setError(null);
+      setDegraded(event.payload);
+    }).then((fn) => { if (cancelled) { fn(); } else { unlistenDegraded = fn; } });
+    listen('keyboard-listener-recovered', () => {
+      setError(null);
+      setDegraded(null);
+    }).then((fn) => { if (cancelled) { fn(); } else { unlistenRecovered = fn; } });
+    return () => {
+      cancelled = true;
+      unlistenDegraded?.();
+      unlistenRecovered?.();
+    };
+  }, []);
+
+  const recover = useCallback(async () => {
+    if (!degraded) return;
+    setBusy(true);
+    setError(null);
+    flog.info('keyboard', 'listener recovery requested', { action: degraded.action });
+    try {
+      switch (degraded.action) {
+        case 'grant_accessibility':
+          await invoke('request_accessibility_permission');
+          break;
+        case 'retry_listener':
+          await invoke('retry_keyboard_listener');
+          break;
+        case 'restart_app':
+          await relaunch();
+          break;
+      }
+    } catch (err) {
+      setError(String(err));
+      flog.warn('keyboard', 'listener recovery failed', { action: degraded.action, error: String(err) });
+    } finally {
+      setBusy(false);
+    }
+  }, [degraded]);
+
+  return { degraded, busy, error, recover };
+}
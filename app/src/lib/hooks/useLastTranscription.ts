@@ -0,0 +1,66 @@
+import { useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { pasteLastTranscription, setLastTranscription } from '../dictation';
+import type { HistoryEntry } from '../history';
+import type { DoubleTapKey, ProfileKey, TransformKey } from '../settings';
+
+interface UseLastTranscriptionProps {
+  historyEntries: HistoryEntry[];
+  enabled: boolean;
+  initialized: boolean;
+  accessibilityGranted: boolean | null;
+  pasteKey: ProfileKey | null;
+  /** Keys owned by other hotkeys; a paste key on one of them stays unbound. */
+  dictationKey: DoubleTapKey;
+  transformKey: TransformKey | null;
+  appendCommitKey: ProfileKey | null;
+}
+
+/**
+ * Mirrors the newest history entry to Rust, which backs the tray's Copy Last
+ * and Paste Last items, and binds the optional paste-last key on the shared
+ * rdev listener, routing `paste-last-key` to `paste_last_transcription`.
+ */
+export function useLastTranscription({
+  historyEntries, enabled, initialized, accessibilityGranted, pasteKey, dictationKey, transformKey, appendCommitKey,
+}: UseLastTranscriptionProps) {
+  const lastText = historyEntries[historyEntries.length - 1]?.text ?? null;
+
+  useEffect(() => {
+    if (!initialized) return;
+    setLastTranscription(lastText).catch((err) => console.error('Failed to sync last transcription:', err));
+  }, [initialized, lastText]);
+
+  const boundKey = pasteKey !== null && pasteKey !== dictationKey && pasteKey !== transformKey && pasteKey !== appendCommitKey
+    ? pasteKey
+    : null;
+
+  useEffect(() => {
+    if (!enabled || !initialized || !accessibilityGranted || boundKey === null) return;
+
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+
+    const setup = async () => {
+      unlisten = await listen('paste-last-key', () => {
+        pasteLastTranscription().catch((err) => console.error('Failed to paste last transcription:', err));
+      });
+      if (cancelled) { unlisten(); return; }
+
+      try {
+        await invoke('set_paste_last_key', { hotkey: boundKey });
+      } catch (err) {
+        console.error('Failed to bind paste-last key:', err);
+      }
+    };
+
+    setup();
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      invoke('set_paste_last_key', { hotkey: null }).catch(() => {});
+    };
+  }, [enabled, initialized, accessibilityGranted, boundKey]);
+}
@@ -0,0 +1,73 @@
+import { act } from 'react';
+import { createRoot, type Root } from 'react-dom/client';
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+type BudgetListener = (event: { payload: unknown }) => void;
+
+const mocks = vi.hoisted(() => ({
+  listener: null as BudgetListener | null,
+  unlisten: vi.fn(),
+}));
+
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: vi.fn(async (_event: string, listener: BudgetListener) => {
+    mocks.listener = listener;
+    return mocks.unlisten;
+  }),
+}));
+
+import { useLatencyBudget } from './useLatencyBudget';
+
+describe('useLatencyBudget', () => {
+  let container: HTMLDivElement;
+  let root: Root;
+  const update = vi.fn();
+
+  function Harness() {
+    useLatencyBudget('large-v3-turbo', update);
+    return null;
+  }
+
+  async function mount() {
+    await act(async () => {
+      root.render(<Harness />);
+      await Promise.resolve();
+    });
+  }
+
+  function fire(payload: Record<string, unknown>) {
+    act(() => {
+      mocks.listener?.({
+        payload: { recordingId: 1, model: 'large-v3-turbo', realtimeFactor: 2, maxRealtimeFactor: 1.5, fasterModel: 'large-v3-turbo-q5_0', switch: true, guidance: '', ...payload },
+      });
+    });
+  }
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    mocks.listener = null;
+    container = document.createElement('div');
+    document.body.appendChild(container);
+    root = createRoot(container);
+  });
+
+  afterEach(async () => {
+    await act(async () => root.unmount());
+    container.remove();
+  });
+
+  it('switches the selected model to the faster one', async () => {
+    await mount();
+    fire({});
+    expect(update).toHaveBeenCalledWith({ model: 'large-v3-turbo-q5_0' });
+  });
+
+  it('only suggests when switching is off, stale, or has no known model', async () => {
+    await mount();
+    fire({ switch: false });
+    fire({ model: 'small.en' });
+    fire({ fasterModel: 'huge.en' });
+    fire({ fasterModel: null });
+    expect(update).not.toHaveBeenCalled();
+  });
+});
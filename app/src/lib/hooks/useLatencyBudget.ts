@@ -0,0 +1,34 @@
+import { useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { AVAILABLE_MODEL_OPTIONS, type ModelOption, type Settings } from '../settings';
+import type { LatencyBudgetExceeded } from '../dictation';
+
+/**
+ * Switches to the faster model the backend names when the selected model
+ * keeps missing `latencyBudget` and `autoSwitch` is on. The change goes
+ * through `update`, like a tray or macro model change, so it persists and
+ * reconfigures the backend. A notice that arrives after the user picked
+ * another model is ignored; `useRecordingState` shows the guidance either way.
+ */
+export function useLatencyBudget(model: ModelOption, update: (updates: Partial<Settings>) => void) {
+  // Read both through refs so the listener is registered once.
+  const modelRef = useRef(model);
+  const updateRef = useRef(update);
+  useEffect(() => { modelRef.current = model; }, [model]);
+  useEffect(() => { updateRef.current = update; }, [update]);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<LatencyBudgetExceeded>('latency-budget-exceeded', (event) => {
+      if (cancelled) return;
+      const { model: slowModel, fasterModel, switch: shouldSwitch } = event.payload;
+      if (!shouldSwitch || slowModel !== modelRef.current) return;
+      const option = AVAILABLE_MODEL_OPTIONS.find((candidate) => candidate.value === fasterModel);
+      if (option) updateRef.current({ model: option.value });
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+}
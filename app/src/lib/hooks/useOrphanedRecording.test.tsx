@@ -0,0 +1,97 @@
+import { act } from 'react';
+import { createRoot, type Root } from 'react-dom/client';
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+type OrphanListener = (event: { payload: unknown }) => void;
+
+const mocks = vi.hoisted(() => ({
+  invoke: vi.fn(),
+  listener: null as OrphanListener | null,
+  unlisten: vi.fn(),
+}));
+
+vi.mock('@tauri-apps/api/core', () => ({ invoke: mocks.invoke }));
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: vi.fn(async (_event: string, listener: OrphanListener) => {
+    mocks.listener = listener;
+    return mocks.unlisten;
+  }),
+}));
+vi.mock('../log', () => ({
+  flog: { info: vi.fn(), warn: vi.fn(), error: vi.fn() },
+}));
+
+import { useOrphanedRecording } from './useOrphanedRecording';
+
+const orphan = { durationSecs: 42.5, recordedAtMs: 1_700_000_000_000 };
+
+describe('useOrphanedRecording', () => {
+  let container: HTMLDivElement;
+  let root: Root;
+  let current: ReturnType<typeof useOrphanedRecording> | null = null;
+  const addEntry = vi.fn();
+
+  function Harness() {
+    current = useOrphanedRecording({ addEntry });
+    return null;
+  }
+
+  async function mount() {
+    await act(async () => {
+      root.render(<Harness />);
+      await Promise.resolve();
+      await Promise.resolve();
+    });
+  }
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    mocks.invoke.mockReset();
+    mocks.listener = null;
+    current = null;
+    container = document.createElement('div');
+    document.body.appendChild(container);
+    root = createRoot(container);
+  });
+
+  afterEach(async () => {
+    await act(async () => root.unmount());
+    container.remove();
+  });
+
+  it('picks up an orphan from the startup query or the event', async () => {
+    mocks.invoke.mockResolvedValueOnce(null);
+    await mount();
+    expect(mocks.invoke).toHaveBeenCalledWith('get_orphaned_recording');
+    expect(current!.orphan).toBeNull();
+
+    await act(async () => { mocks.listener?.({ payload: orphan }); });
+    expect(current!.orphan).toEqual(orphan);
+  });
+
+  it('adds the recovered text to history and clears the offer', async () => {
+    mocks.invoke
+      .mockResolvedValueOnce(orphan)
+      .mockResolvedValueOnce({ type: 'transcription', text: 'recovered words', duration: 42 });
+    await mount();
+    expect(current!.orphan).toEqual(orphan);
+
+    await act(async () => { await current!.transcribe(); });
+    expect(mocks.invoke).toHaveBeenLastCalledWith('transcribe_orphaned_recording');
+    expect(addEntry).toHaveBeenCalledWith('recovered words', 42, 'recording');
+    expect(current!.orphan).toBeNull();
+  });
+
+  it('keeps the offer with an error when transcription fails', async () => {
+    mocks.invoke
+      .mockResolvedValueOnce(orphan)
+      .mockRejectedValueOnce('Wait for the benchmark to finish before transcribing a file.');
+    await mount();
+
+    await act(async () => { await current!.transcribe(); });
+    expect(addEntry).not.toHaveBeenCalled();
+    expect(current!.orphan).toEqual(orphan);
+    expect(current!.error).toContain('benchmark');
+    expect(current!.busy).toBe(false);
+  });
+});
@@ -0,0 +1,70 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { TranscriptionResult } from '../bindings/TranscriptionResult';
+import { flog } from '../log';
+
+/** An unfinished recording left by a crash, from Rust's recording journal. */
+export interface OrphanedRecording {
+  durationSecs: number;
+  recordedAtMs: number;
+}
+
+interface UseOrphanedRecordingOptions {
+  /** Persist the recovered transcription to shared history. */
+  addEntry: (text: string, duration: number, source?: 'recording' | 'file', sourceName?: string) => void;
+}
+
+/**
+ * Offers recovery of a recording that was interrupted by a crash. Rust adopts
+ * the journal at startup and emits `orphaned-recording-found`; this hook also
+ * asks once on mount, since the event can fire before the window listens.
+ */
+export function useOrphanedRecording({ addEntry }: UseOrphanedRecordingOptions) {
+  const [orphan, setOrphan] = useState<OrphanedRecording | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    invoke<OrphanedRecording | null>('get_orphaned_recording')
+      .then((found) => { if (!cancelled && found) setOrphan(found); })
+      .catch((err) => flog.warn('recording', 'get_orphaned_recording failed', { error: String(err) }));
+    listen<OrphanedRecording>('orphaned-recording-found', (event) => setOrphan(event.payload))
+      .then((fn) => { if (cancelled) { fn(); } else { unlisten = fn; } });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const transcribe = useCallback(async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      const res = await invoke<TranscriptionResult>('transcribe_orphaned_recording');
+      const text = res.text || '';
+      if (text.trim()) addEntry(text, res.duration ?? 0, 'recording');
+      flog.info('recording', 'orphaned recording transcribed', { textLen: text.length });
+      setOrphan(null);
+    } catch (err) {
+      setError(String(err));
+      flog.warn('recording', 'transcribe_orphaned_recording failed', { error: String(err) });
+    } finally {
+      setBusy(false);
+    }
+  }, [addEntry]);
+
+  const discard = useCallback(async () => {
+    try {
+      await invoke('discard_orphaned_recording');
+    } catch (err) {
+      flog.warn('recording', 'discard_orphaned_recording failed', { error: String(err) });
+    }
+    setOrphan(null);
+    setError(null);
+  }, []);
+
+  return { orphan, busy, error, transcribe, discard };
+}
@@ -18,6 +18,9 @@ vi.mock('@tauri-apps/api/event', () => ({
     return mocks.unlisten;
   }),
 }));
+vi.mock('@tauri-apps/api/window', () => ({
+  getCurrentWindow: () => ({ label: 'overlay' }),
+}));
 vi.mock('../log', () => ({
   flog: { info: vi.fn(), warn: vi.fn(), error: vi.fn() },
 }));
@@ -34,6 +37,7 @@ const notched: OverlayGeometry = {
   pillMarginActive: 0,
   dropdownH: 44,
   wingW: 36,
+  floating: false,
 };
 
 const fallback: OverlayGeometry = {
@@ -46,6 +50,7 @@ const fallback: OverlayGeometry = {
   pillMarginActive: 0,
   dropdownH: 44,
   wingW: 36,
+  floating: false,
 };
 
 function deferred<T>() {
@@ -1,6 +1,7 @@
 import { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
+import { getCurrentWindow } from '@tauri-apps/api/window';
 import { flog } from '../log';
 import { isOverlayGeometry } from '../overlayGeometry';
 import type { OverlayGeometry } from '../overlayGeometry';
@@ -15,7 +16,8 @@ const FETCH_RETRY_DELAYS_MS = [250, 1000];
  * for every overlay dimension; the frontend only reads it. On mount we fetch
  * the current geometry (retrying on failure), and we subscribe to
  * `overlay-geometry-changed` so the island resizes when the display
- * configuration changes.
+ * configuration changes. With "all displays" each overlay window sits on a
+ * different screen, so Rust targets the event at this window's label.
  */
 export function useOverlayGeometry(): OverlayGeometry | null {
   const [geometry, setGeometry] = useState<OverlayGeometry | null>(null);
@@ -39,7 +41,7 @@ export function useOverlayGeometry(): OverlayGeometry | null {
         } else {
           flog.warn('overlay', 'overlay-geometry-changed had invalid payload');
         }
-      });
+      }, { target: getCurrentWindow().label });
 
       if (cancelled) {
         stopListening();
@@ -0,0 +1,63 @@
+import { useCallback, useEffect, useRef } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { emit } from '@tauri-apps/api/event';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import { flog } from '../log';
+import { loadSettings, saveSettings } from '../settings';
+import type { OverlayPillPosition } from '../settings';
+
+// Moves arrive as a stream while the window is dragged; commit once they settle.
+const SETTLE_MS = 300;
+// A drag that never produces a move (a plain click) disarms after this long, so
+// a later programmatic reposition is never mistaken for the user's drag.
+const ARM_TIMEOUT_MS = 2000;
+
+/**
+ * Persists where the user drags a floating pill. The top bar is a Tauri drag
+ * region, so the window moves natively; this hook only arms on mousedown,
+ * waits for the window's moves to settle, then asks Rust for the
+ * display-relative position (`commit_overlay_pill_position`) and saves it.
+ * Moves that were not preceded by a mousedown are Rust's own repositioning
+ * and are ignored. Notch islands never commit a position.
+ */
+export function useOverlayPillDrag(floating: boolean) {
+  const armedUntilRef = useRef(0);
+  const settleTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
+  const floatingRef = useRef(floating);
+
+  useEffect(() => { floatingRef.current = floating; }, [floating]);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    getCurrentWindow().onMoved(() => {
+      if (!floatingRef.current || Date.now() > armedUntilRef.current) return;
+      armedUntilRef.current = Date.now() + ARM_TIMEOUT_MS;
+      if (settleTimerRef.current) clearTimeout(settleTimerRef.current);
+      settleTimerRef.current = setTimeout(async () => {
+        settleTimerRef.current = null;
+        armedUntilRef.current = 0;
+        try {
+          const position = await invoke<OverlayPillPosition>('commit_overlay_pill_position');
+          saveSettings({ ...loadSettings(), overlayPillPosition: position });
+          emit('settings-changed').catch((err) => flog.warn('overlay', 'emit settings-changed failed', { error: String(err) }));
+        } catch (err) {
+          flog.warn('overlay', 'commit_overlay_pill_position failed', { error: String(err) });
+        }
+      }, SETTLE_MS);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      if (settleTimerRef.current) clearTimeout(settleTimerRef.current);
+    };
+  }, []);
+
+  const handleMouseDown = useCallback(() => {
+    if (floatingRef.current) armedUntilRef.current = Date.now() + ARM_TIMEOUT_MS;
+  }, []);
+
+  return { handleMouseDown };
+}
@@ -2,7 +2,7 @@ import { useEffect, useRef, useState } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { flog } from '../log';
 import { loadSettings } from '../settings';
-import type { DictationStatus } from '../types';
+import { isSettledStatus, type DictationStatus } from '../types';
 import {
   HOTKEY_MISS_FLASH_MS,
   isHotkeyTapRejectedPayload,
@@ -82,7 +82,7 @@ export function useOverlayRuntime({
   // branch — resetting locked mode — lives in useRecordingControls, the hook
   // that owns locked mode.)
   useEffect(() => {
-    if (status === 'idle') return;
+    if (isSettledStatus(status)) return;
     setShowHotkeyMiss(false);
     if (hotkeyMissTimerRef.current) {
       clearTimeout(hotkeyMissTimerRef.current);
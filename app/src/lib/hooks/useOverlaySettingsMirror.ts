@@ -4,7 +4,7 @@ import { emit, listen } from '@tauri-apps/api/event';
 import { flog } from '../log';
 import { loadSettings, saveSettings } from '../settings';
 import type { Settings } from '../settings';
-import { buildConfigureOptions } from '../dictation';
+import { buildConfigureOptions, overlayAction } from '../dictation';
 
 export interface UseOverlaySettingsMirrorArgs {
   setDisabled: (value: boolean) => void;
@@ -115,7 +115,7 @@ export function useOverlaySettingsMirror({
   const handleOpenSettings = useCallback(async (e: React.MouseEvent) => {
     e.stopPropagation();
     try {
-      await invoke('show_main_window');
+      await overlayAction('open_main_window');
       await emit('open-settings');
     } catch (err) {
       flog.error('overlay', 'open settings failed', { error: String(err) });
@@ -0,0 +1,35 @@
+import { useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+
+interface UseOverlayStartRequestsProps {
+  enabled: boolean;
+  initialized: boolean;
+  onStart: () => void;
+}
+
+/**
+ * Starts a recording when the overlay asks for one (`overlay_action` with
+ * `toggle_recording` while idle). Rust routes the start here so it uses the
+ * selected microphone and profile, like a hotkey press.
+ */
+export function useOverlayStartRequests({ enabled, initialized, onStart }: UseOverlayStartRequestsProps) {
+  const onStartRef = useRef(onStart);
+  useEffect(() => { onStartRef.current = onStart; }, [onStart]);
+
+  useEffect(() => {
+    if (!enabled || !initialized) return;
+
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    listen('overlay-start-requested', () => {
+      onStartRef.current();
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [enabled, initialized]);
+}
@@ -0,0 +1,81 @@
+import { act } from 'react';
+import { createRoot, type Root } from 'react-dom/client';
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+const mocks = vi.hoisted(() => {
+  const listeners = new Map<string, (event: { payload: unknown }) => void>();
+  return {
+    listeners,
+    listen: vi.fn(async (event: string, handler: (event: { payload: unknown }) => void) => {
+      listeners.set(event, handler);
+      return () => listeners.delete(event);
+    }),
+  };
+});
+
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: mocks.listen,
+}));
+
+import { OVERLAY_ERROR_MS, useOverlayState, type OverlayContent } from './useOverlayState';
+
+describe('useOverlayState', () => {
+  let container: HTMLDivElement;
+  let root: Root;
+  let current: OverlayContent;
+
+  const emit = (payload: unknown) => act(async () => {
+    mocks.listeners.get('overlay-state')?.({ payload });
+  });
+
+  beforeEach(async () => {
+    vi.useFakeTimers();
+    mocks.listeners.clear();
+    container = document.createElement('div');
+    document.body.appendChild(container);
+    root = createRoot(container);
+
+    function Harness() {
+      current = useOverlayState();
+      return null;
+    }
+
+    await act(async () => {
+      root.render(<Harness />);
+    });
+  });
+
+  afterEach(async () => {
+    await act(async () => root.unmount());
+    container.remove();
+    vi.useRealTimers();
+  });
+
+  it('tracks the recording start, then partial text, then idle', async () => {
+    await emit({ state: 'recording', payload: { startedAtMs: 1234 } });
+    expect(current).toEqual({ recordingStartedAt: 1234, processing: false, partial: null, error: null });
+
+    await emit({ state: 'level', payload: { rms: 0.2 } });
+    expect(current.recordingStartedAt).toBe(1234);
+
+    await emit({ state: 'processing', payload: { partial: 'hello wor' } });
+    expect(current).toEqual({ recordingStartedAt: null, processing: true, partial: 'hello wor', error: null });
+
+    await emit({ state: 'idle' });
+    expect(current).toEqual({ recordingStartedAt: null, processing: false, partial: null, error: null });
+  });
+
+  it('keeps an error through later states until it times out', async () => {
+    await emit({ state: 'error', payload: { message: 'Dictation failed' } });
+    await emit({ state: 'idle' });
+    expect(current.error).toBe('Dictation failed');
+
+    await act(async () => { vi.advanceTimersByTime(OVERLAY_ERROR_MS - 1); });
+    await emit({ state: 'error', payload: { message: 'No text field' } });
+    await act(async () => { vi.advanceTimersByTime(OVERLAY_ERROR_MS - 1); });
+    expect(current.error).toBe('No text field');
+
+    await act(async () => { vi.advanceTimersByTime(1); });
+    expect(current.error).toBeNull();
+  });
+});
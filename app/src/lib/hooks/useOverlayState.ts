@@ -0,0 +1,66 @@
+import { useEffect, useRef, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import type { OverlayState } from '../types';
+
+/** How long an `error` overlay state stays on screen. */
+export const OVERLAY_ERROR_MS = 4000;
+
+export interface OverlayContent {
+  /** Unix ms the current recording started, for the timer; `null` when not recording. */
+  recordingStartedAt: number | null;
+  /** Transcription (or a draft's refinement) is in flight. */
+  processing: boolean;
+  /** Text known before the final result, shown while processing. */
+  partial: string | null;
+  /** Short error message, cleared after `OVERLAY_ERROR_MS`. */
+  error: string | null;
+}
+
+const IDLE: Omit<OverlayContent, 'error'> = { recordingStartedAt: null, processing: false, partial: null };
+
+/**
+ * Mirrors Rust's `overlay-state` channel. Levels are left to useWaveform,
+ * which reads them without re-rendering. An error shows beside whatever
+ * state follows it until its timer runs out or another error replaces it.
+ */
+export function useOverlayState(): OverlayContent {
+  const [content, setContent] = useState(IDLE);
+  const [error, setError] = useState<string | null>(null);
+  const errorTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<OverlayState>('overlay-state', (event) => {
+      const next = event.payload;
+      switch (next?.state) {
+        case 'idle':
+          setContent(IDLE);
+          break;
+        case 'recording':
+          setContent({ ...IDLE, recordingStartedAt: next.payload.startedAtMs });
+          break;
+        case 'processing':
+          setContent({ ...IDLE, processing: true, partial: next.payload.partial });
+          break;
+        case 'error':
+          setError(next.payload.message);
+          if (errorTimerRef.current) clearTimeout(errorTimerRef.current);
+          errorTimerRef.current = setTimeout(() => {
+            errorTimerRef.current = null;
+            setError(null);
+          }, OVERLAY_ERROR_MS);
+          break;
+      }
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      if (errorTimerRef.current) clearTimeout(errorTimerRef.current);
+    };
+  }, []);
+
+  return { ...content, error };
+}
@@ -25,7 +25,7 @@ export interface PerformanceHealth {
 }
 
 function isDictationStatus(value: unknown): value is DictationStatus {
-  return value === 'idle' || value === 'recording' || value === 'processing';
+  return value === 'idle' || value === 'recording' || value === 'processing' || value === 'buffering';
 }
 
 function isTransformStatus(value: unknown): value is TransformPipelineStatus {
@@ -0,0 +1,42 @@
+import { useEffect, useRef, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { getPowerStatus, profileForSource, type PowerSource, type PowerStatus } from '../power';
+import type { PowerProfilesSettings, Settings } from '../settings';
+import { flog } from '../log';
+
+/**
+ * Applies the adapter or battery profile whenever the power source changes,
+ * when profiles are turned on, and when the active profile is edited. `update`
+ * saves and configures as if the change came from Settings, so a manual change
+ * sticks until the next switch.
+ */
+export function usePowerProfiles(profiles: PowerProfilesSettings, update: (updates: Partial<Settings>) => void) {
+  // updateSettings is recreated every render; read it through a ref so the
+  // listener is registered once.
+  const updateRef = useRef(update);
+  useEffect(() => { updateRef.current = update; }, [update]);
+  const [source, setSource] = useState<PowerSource | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    getPowerStatus()
+      .then((status) => { if (!cancelled) setSource(status.source); })
+      .catch((e: unknown) => flog.warn('power', 'power status unavailable', { error: String(e) }));
+    listen<PowerStatus>('power-status-changed', (event) => {
+      if (!cancelled) setSource(event.payload.source);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  const active = profiles.enabled && source ? profileForSource(profiles, source) : null;
+  const model = active?.model;
+  const vadSensitivity = active?.vadSensitivity;
+  const idleTimeoutMinutes = active?.idleTimeoutMinutes;
+  useEffect(() => {
+    if (model === undefined || vadSensitivity === undefined || idleTimeoutMinutes === undefined) return;
+    updateRef.current({ model, vadSensitivity, idleTimeoutMinutes });
+  }, [model, vadSensitivity, idleTimeoutMinutes]);
+}
@@ -0,0 +1,31 @@
+import { useEffect } from 'react';
+import { startPreRoll, stopPreRoll } from '../dictation';
+
+interface UsePreRollProps {
+  /** Seconds to keep; 0 = off. */
+  seconds: number;
+  initialized: boolean;
+  microphone: string;
+}
+
+/**
+ * Keeps the pre-roll buffer running while enabled, so each recording starts
+ * with the audio heard just before it. Nothing leaves memory otherwise.
+ */
+export function usePreRoll({ seconds, initialized, microphone }: UsePreRollProps) {
+  useEffect(() => {
+    if (seconds <= 0 || !initialized) return;
+
+    let cancelled = false;
+    startPreRoll(microphone, seconds)
+      .then(() => {
+        if (cancelled) stopPreRoll().catch(() => {});
+      })
+      .catch((err) => console.error('Failed to start pre-roll:', err));
+
+    return () => {
+      cancelled = true;
+      stopPreRoll().catch(() => {});
+    };
+  }, [seconds, initialized, microphone]);
+}
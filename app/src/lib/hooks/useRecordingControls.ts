@@ -2,8 +2,9 @@ import { useCallback, useEffect, useRef, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { flog } from '../log';
 import { DEFAULT_SETTINGS, loadSettings } from '../settings';
-import type { DictationStatus } from '../types';
-import type { DictationResponse } from '../dictation';
+import { isSettledStatus, type DictationStatus } from '../types';
+import { overlayAction } from '../dictation';
+import type { StartRecordingResult } from '../bindings/StartRecordingResult';
 
 export interface UseRecordingControlsArgs {
   /** Current dictation status (reactive value, drives the locked-mode reset). */
@@ -40,7 +41,7 @@ export function useRecordingControls({
   // When status returns to idle, locked mode is automatically reset — mirrors
   // the idle branch of the original combined "status changed" effect.
   useEffect(() => {
-    if (status === 'idle') setLockedMode(false);
+    if (isSettledStatus(status)) setLockedMode(false);
   }, [status]);
 
   // Clear the pending single-click debounce timer on unmount.
@@ -56,8 +57,7 @@ export function useRecordingControls({
       target: (e.target as HTMLElement).tagName,
     });
     const currentStatus = statusRef.current;
-    if (currentStatus === 'processing') return;
-    if (disabledRef.current && currentStatus === 'idle') return;
+    if (disabledRef.current && currentStatus !== 'recording') return;
     if (clickTimerRef.current) {
       clearTimeout(clickTimerRef.current);
       clickTimerRef.current = null;
@@ -83,7 +83,7 @@ export function useRecordingControls({
             }
           } catch { /* ignore parse errors */ }
           flog.info('overlay', 'invoking start_native_recording', { deviceName });
-          const res = await invoke<DictationResponse>('start_native_recording', { deviceName });
+          const res = await invoke<StartRecordingResult>('start_native_recording', { deviceName });
           flog.info('overlay', 'start_native_recording result', { type: res.type, state: res.state });
           if (res.type !== 'recording_started') {
             flog.warn('overlay', 'recording start declined', { type: res.type });
@@ -126,7 +126,7 @@ export function useRecordingControls({
       if (statusRef.current === 'recording') {
         setLockedMode(false);
         try {
-          await invoke('stop_native_recording');
+          await overlayAction('toggle_recording');
         } catch {
           // status will sync via event
         }
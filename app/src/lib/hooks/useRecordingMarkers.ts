@@ -0,0 +1,46 @@
+import { useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { DoubleTapKey, ProfileKey, TransformKey } from '../settings';
+
+interface UseRecordingMarkersProps {
+  enabled: boolean;
+  initialized: boolean;
+  accessibilityGranted: boolean | null;
+  markerKey: ProfileKey | null;
+  spokenMarkers: boolean;
+  /** Keys owned by other hotkeys; a marker key on one of them stays unbound. */
+  dictationKey: DoubleTapKey;
+  transformKey: TransformKey | null;
+  appendCommitKey: ProfileKey | null;
+  pasteLastKey: ProfileKey | null;
+}
+
+/**
+ * Binds the optional marker key on the shared rdev listener and sends the
+ * spoken-marker setting to Rust. Both take effect in Rust: a key press is
+ * timed where it happens and emits `recording-marker`, and the transcript
+ * arrives split at the markers.
+ */
+export function useRecordingMarkers({
+  enabled, initialized, accessibilityGranted, markerKey, spokenMarkers, dictationKey, transformKey, appendCommitKey, pasteLastKey,
+}: UseRecordingMarkersProps) {
+  useEffect(() => {
+    if (!initialized) return;
+    invoke('set_spoken_markers', { enabled: spokenMarkers }).catch((err) => console.error('Failed to apply spoken markers:', err));
+  }, [initialized, spokenMarkers]);
+
+  const boundKey = markerKey !== null
+    && markerKey !== dictationKey && markerKey !== transformKey && markerKey !== appendCommitKey && markerKey !== pasteLastKey
+    ? markerKey
+    : null;
+
+  useEffect(() => {
+    if (!enabled || !initialized || !accessibilityGranted || boundKey === null) return;
+
+    invoke('set_marker_key', { hotkey: boundKey }).catch((err) => console.error('Failed to bind marker key:', err));
+
+    return () => {
+      invoke('set_marker_key', { hotkey: null }).catch(() => {});
+    };
+  }, [enabled, initialized, accessibilityGranted, boundKey]);
+}
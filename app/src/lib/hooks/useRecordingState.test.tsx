@@ -13,6 +13,8 @@ const mocks = vi.hoisted(() => {
       return () => listeners.delete(event);
     }),
     addEntry: vi.fn(),
+    refineEntry: vi.fn(),
+    attachAlternatives: vi.fn(),
     updateStats: vi.fn(),
   };
 });
@@ -59,6 +61,8 @@ describe('useRecordingState transition ordering', () => {
     function Harness() {
       current = useRecordingState({
         addEntry: mocks.addEntry,
+        refineEntry: mocks.refineEntry,
+        attachAlternatives: mocks.attachAlternatives,
         microphone: 'system_default',
       });
       return null;
@@ -118,6 +122,9 @@ describe('useRecordingState transition ordering', () => {
           text: 'one final transcript',
           duration: 12,
           teachingContext: { appBundleId: 'com.example.Editor', appLabel: 'Editor' },
+          confidence: { score: 0.31, avgLogprob: -1.17, noSpeechProb: 0.02, low: true, segments: [] },
+          tokens: 5,
+          decodeMs: 250,
         },
       });
       return {
@@ -137,9 +144,117 @@ describe('useRecordingState transition ordering', () => {
       'recording',
       undefined,
       { appBundleId: 'com.example.Editor', appLabel: 'Editor' },
+      { score: 0.31, low: true },
+      { tokens: 5, decodeMs: 250 },
+      undefined,
     );
     expect(mocks.updateStats).toHaveBeenCalledTimes(1);
-    expect(mocks.updateStats).toHaveBeenCalledWith('one final transcript', 12);
+    expect(mocks.updateStats).toHaveBeenCalledWith('one final transcript', 12, 5);
     expect(current.transcription).toBe('one final transcript');
   });
+
+  it('swaps a refined dual-model transcript into history and the display', async () => {
+    await act(async () => {
+      mocks.listeners.get('transcription-complete')?.({ payload: { text: 'send it too Sam', duration: 2 } });
+    });
+    await act(async () => {
+      mocks.listeners.get('transcription-refined')?.({
+        payload: { recordingId: 1, draft: 'send it too Sam', text: 'send it to Sam', replaced: false },
+      });
+    });
+
+    expect(mocks.refineEntry).toHaveBeenCalledWith('send it too Sam', 'send it to Sam');
+    expect(current.transcription).toBe('send it to Sam');
+  });
+
+  it('hands alternative readings of a delivered transcript to history', async () => {
+    const spans = [{ start: 4, end: 5, options: ['quay'] }];
+    await act(async () => {
+      mocks.listeners.get('transcription-alternatives')?.({
+        payload: { recordingId: 1, text: 'meet me at the key', spans },
+      });
+    });
+
+    expect(mocks.attachAlternatives).toHaveBeenCalledWith('meet me at the key', spans);
+  });
+
+  it('stays processing after a queued stop until the worker settles the status', async () => {
+    mocks.startRecording.mockResolvedValueOnce({
+      type: 'recording_started',
+      state: 'recording',
+    });
+    mocks.stopRecording.mockResolvedValueOnce({
+      type: 'queued',
+      text: null,
+      state: 'processing',
+      fileRunId: null,
+      jobId: 3,
+      duration: null,
+    });
+
+    await act(async () => current.handleStart());
+    await act(async () => current.handleStop());
+    expect(current.status).toBe('processing');
+
+    await act(async () => {
+      mocks.listeners.get('transcription-failed')?.({ payload: { recordingId: 3, error: 'Model not found' } });
+      mocks.listeners.get('recording-status-changed')?.({ payload: 'idle' });
+    });
+
+    expect(current.status).toBe('idle');
+    expect(current.error).toBe('Model not found');
+    expect(mocks.addEntry).not.toHaveBeenCalled();
+  });
+
+  it('starts the next take from the hotkey while earlier takes transcribe', async () => {
+    const started = { type: 'recording_started', state: 'recording' };
+    mocks.startRecording.mockResolvedValueOnce(started).mockResolvedValueOnce(started);
+    mocks.stopRecording.mockResolvedValueOnce({
+      type: 'queued',
+      text: null,
+      state: 'processing',
+      fileRunId: null,
+      jobId: 1,
+      duration: null,
+    });
+
+    await act(async () => current.toggleRecording());
+    await act(async () => current.toggleRecording());
+    expect(current.status).toBe('processing');
+
+    await act(async () => current.toggleRecording());
+
+    expect(mocks.startRecording).toHaveBeenCalledTimes(2);
+    expect(current.status).toBe('recording');
+  });
+
+  it('shows the Automation hint when a paste is refused for lack of permission', async () => {
+    const hint = 'Allow it in System Settings > Privacy & Security > Automation.';
+
+    await act(async () => {
+      mocks.listeners.get('automation-permission-denied')?.({ payload: hint });
+    });
+
+    expect(current.error).toBe(hint);
+  });
+
+  it('shows the clipboard hint when no text field had focus', async () => {
+    const hint = "No text field is focused, so Murmur didn't paste. Text is in your clipboard -- press Cmd+V where you want it.";
+
+    await act(async () => {
+      mocks.listeners.get('no-text-target')?.({ payload: hint });
+    });
+
+    expect(current.error).toBe(hint);
+  });
+
+  it('shows why a dictation over a selection was not written', async () => {
+    const hint = 'The selection changed before Murmur could replace it. Text is in your clipboard.';
+
+    await act(async () => {
+      mocks.listeners.get('selection-replace-failed')?.({ payload: hint });
+    });
+
+    expect(current.error).toBe(hint);
+  });
 });
@@ -1,18 +1,24 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { startRecording, stopRecording } from '../dictation';
-import { isDictationStatus } from '../types';
-import type { DictationStatus } from '../types';
+import { isDictationStatus, isSettledStatus } from '../types';
+import type { DictationStatus, OverlayState } from '../types';
 import { updateStats } from '../stats';
 import { flog } from '../log';
 import type { TeachingContext } from '../correctAndTeach';
+import type { HistoryConfidence, HistoryDecode, HistoryProcessing } from '../history';
+import type { TranscriptConfidence, AlternativeSpan, TranscriptionAlternatives, LatencyBudgetExceeded } from '../dictation';
 
 interface UseRecordingStateProps {
-  addEntry: (text: string, duration: number, source?: 'recording' | 'file', sourceName?: string, teachingContext?: TeachingContext) => void;
+  addEntry: (text: string, duration: number, source?: 'recording' | 'file', sourceName?: string, teachingContext?: TeachingContext, confidence?: HistoryConfidence, decode?: HistoryDecode, processing?: HistoryProcessing) => void;
+  /** Replace a dual-model draft with its refined text in history. */
+  refineEntry: (draft: string, text: string) => void;
+  /** Offer other Whisper readings of a delivered transcript in history. */
+  attachAlternatives: (text: string, spans: AlternativeSpan[]) => void;
   microphone: string;
 }
 
-export function useRecordingState({ addEntry, microphone }: UseRecordingStateProps) {
+export function useRecordingState({ addEntry, refineEntry, attachAlternatives, microphone }: UseRecordingStateProps) {
   const [status, setStatus] = useState<DictationStatus>('idle');
   const [transcription, setTranscription] = useState('');
   const [error, setError] = useState('');
@@ -68,13 +74,13 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
           setRecordingStartTime(now);
         }
         // When recording stops, clear recordingStartTime.
-        if (event.payload === 'idle' || event.payload === 'processing') {
+        if (event.payload !== 'recording') {
           recordingStartTimeRef.current = null;
           setRecordingStartTime(null);
         }
         // If idle arrived externally (e.g. Escape cancel), unblock handleStop
         // so the next recording cycle can stop normally.
-        if (event.payload === 'idle') {
+        if (isSettledStatus(event.payload)) {
           isStoppingRef.current = false;
         }
       }
@@ -88,8 +94,8 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
   useEffect(() => {
     let cancelled = false;
     let unlisten: (() => void) | null = null;
-    listen<number>('audio-level', (event) => {
-      setAudioLevel(event.payload);
+    listen<OverlayState>('overlay-state', (event) => {
+      if (event.payload?.state === 'level') setAudioLevel(event.payload.payload.rms);
     }).then((fn) => {
       if (cancelled) { fn(); } else { unlisten = fn; }
     });
@@ -115,6 +121,63 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
     };
   }, []);
 
+  // Transcribing a copied audio file from the tray or its key failed.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<string>('clipboard-audio-failed', (event) => {
+      setError(event.payload);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // The paste guard skipped the paste because no text field had focus.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<string>('no-text-target', (event) => {
+      setError(event.payload);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // A dictation over a selection couldn't replace or rewrite it.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<string>('selection-replace-failed', (event) => {
+      setError(event.payload);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // Auto-paste failed because Automation of System Events is denied. The
+  // payload names the System Settings pane to fix it in, so keep it up longer.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<string>('automation-permission-denied', (event) => {
+      setError(event.payload);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 10000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
   // Listen for file-output (save transcript/audio) failures and surface a hint.
   // Reuses the same auto-clearing error banner as auto-paste failures.
   useEffect(() => {
@@ -130,24 +193,180 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
     return () => { cancelled = true; unlisten?.(); };
   }, []);
 
-  // Sync transcription results from Rust — picks up text when recording was
-  // initiated from the overlay (where handleStop doesn't run in this window).
-  // Skip if isStoppingRef is true — handleStop is active and will handle it.
+  // A rewrite template failed on the transform model; the original text was
+  // pasted instead. Same auto-clearing banner as auto-paste failures.
   useEffect(() => {
     let cancelled = false;
     let unlisten: (() => void) | null = null;
-    listen<{ text: string; duration: number; teachingContext?: TeachingContext }>('transcription-complete', (event) => {
+    listen<string>('rewrite-template-failed', (event) => {
+      setError(event.payload);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // Do-not-inject list hit: the text was copied but deliberately not pasted.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<string>('injection-blocked', (event) => {
+      setError(event.payload);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // The notes target couldn't write today's note; the text went to the
+  // clipboard instead.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<string>('note-save-failed', (event) => {
+      setError(event.payload);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // The adaptive policy swapped in the fallback model for this recording.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ recordingId: number; fromModel: string; toModel: string; reason: 'thermal' | 'lowPowerMode' }>('model-downgraded', (event) => {
+      const why = event.payload.reason === 'thermal' ? 'the Mac is running hot' : 'Low Power Mode is on';
+      setError(`Using ${event.payload.toModel} for this recording because ${why}.`);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // The GPU ran out of memory; say what the take fell back to.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ recordingId: number; model: string; fallback: 'cpu' | 'smallerModel' | 'failed'; fallbackModel: string | null; guidance: string }>('gpu-memory-pressure', (event) => {
+      setError(event.payload.guidance);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // A model keeps missing the latency budget; say which one would fit.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<LatencyBudgetExceeded>('latency-budget-exceeded', (event) => {
+      setError(event.payload.guidance);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // The recording is using a fallback microphone, or lost its device.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ deviceName: string | null; rank: number | null; failover: boolean }>('recording-device', (event) => {
+      const { deviceName, rank, failover } = event.payload;
+      if (!failover && rank === 0) return;
+      if (deviceName === null) {
+        setError('Microphone disconnected and no other input is available — stop to keep what was captured.');
+      } else if (failover) {
+        setError(`Microphone disconnected — switched to ${deviceName}.`);
+      } else if (rank !== null) {
+        setError(`Preferred microphone not connected — using ${deviceName}.`);
+      } else {
+        return;
+      }
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // The microphone is a Bluetooth headset on the narrowband hands-free profile.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ deviceName: string; sampleRate: number; transport: string; switchedTo: string | null }>('degraded-input-warning', (event) => {
+      const { deviceName, sampleRate, switchedTo } = event.payload;
+      setError(switchedTo
+        ? `${deviceName} is in low-quality call mode — recording from ${switchedTo} instead.`
+        : `${deviceName} is in low-quality call mode (${sampleRate / 1000} kHz); transcripts may suffer.`);
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // A recording that ended without usable speech (silence, or output dropped
+  // as a known hallucination) pastes nothing; say so rather than stay silent.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ recordingId: number; reason: 'vad' | 'silence' | 'hallucination' }>('no-speech-detected', () => {
+      setError('No speech detected — nothing was pasted.');
+      if (pasteErrorTimerRef.current) clearTimeout(pasteErrorTimerRef.current);
+      pasteErrorTimerRef.current = setTimeout(() => setError(''), 5000);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // A queued transcription failed after stop_native_recording returned.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ recordingId: number; error: string }>('transcription-failed', (event) => {
+      setError(event.payload.error || 'Transcription failed');
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+
+  // Every transcription result arrives here, whichever window stopped the
+  // recording: stop_native_recording only queues the job for the worker.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ text: string; duration: number; teachingContext?: TeachingContext; confidence?: TranscriptConfidence | null; tokens?: number | null; decodeMs?: number; processedOn?: HistoryProcessing['on']; processedBy?: string | null }>('transcription-complete', (event) => {
       flog.info('recording', 'transcription-complete event', {
         textLen: event.payload.text?.length, duration: event.payload.duration,
         isStopping: isStoppingRef.current,
       });
       // Single source of truth for history entries — always handle here,
       // never in handleStop, to avoid race-condition duplicates.
-      const { text, duration, teachingContext } = event.payload;
+      const { text, duration, teachingContext, confidence, tokens, decodeMs, processedOn, processedBy } = event.payload;
       if (text) {
         setTranscription(text);
-        addEntry(text, duration, 'recording', undefined, teachingContext);
-        updateStats(text, duration);
+        const decode = typeof tokens === 'number' ? { tokens, decodeMs: decodeMs ?? 0 } : undefined;
+        const processing = processedOn ? { on: processedOn, by: processedBy } : undefined;
+        addEntry(text, duration, 'recording', undefined, teachingContext, confidence ? { score: confidence.score, low: confidence.low } : undefined, decode, processing);
+        updateStats(text, duration, decode?.tokens);
         setStatsVersion(v => v + 1);
       }
     }).then((fn) => {
@@ -156,9 +375,52 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
     return () => { cancelled = true; unlisten?.(); };
   }, [addEntry]);
 
-  const handleStart = useCallback(async () => {
+  // Dual-model mode: the selected model re-transcribed a draft's audio and
+  // got different text. The backend may already have replaced the pasted draft.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ recordingId: number; draft: string; text: string; replaced: boolean }>('transcription-refined', (event) => {
+      const { draft, text } = event.payload;
+      refineEntry(draft, text);
+      setTranscription((current) => current === draft ? text : current);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, [refineEntry]);
+
+  // A rewrite template turned the transcript into the text that was pasted.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<{ recordingId: number; template: string; original: string; text: string }>('transcription-rewritten', (event) => {
+      const { original, text } = event.payload;
+      refineEntry(original, text);
+      setTranscription((current) => current === original ? text : current);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, [refineEntry]);
+
+  // Extra Whisper hypotheses read some words of a delivered transcript
+  // differently; history offers them as alternatives.
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<TranscriptionAlternatives>('transcription-alternatives', (event) => {
+      attachAlternatives(event.payload.text, event.payload.spans);
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, [attachAlternatives]);
+
+  // `profileId` is set when a dictation profile's hotkey started the recording.
+  const startWithProfile = useCallback(async (profileId?: string) => {
     flog.info('recording', 'handleStart called', {
-      isStarting: isStartingRef.current, status: statusRef.current,
+      isStarting: isStartingRef.current, status: statusRef.current, profile: profileId !== undefined,
     });
     if (startOperationRef.current) {
       await startOperationRef.current;
@@ -168,8 +430,8 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
     const operation = (async () => {
       try {
         setError('');
-        const res = await startRecording(microphoneRef.current);
-        if (isDictationStatus(res.state)) {
+        const res = await startRecording(microphoneRef.current, profileId);
+        if (res.type !== 'error' && isDictationStatus(res.state)) {
           statusRef.current = res.state;
           setStatus(res.state);
         }
@@ -205,6 +467,9 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
     }
   }, []);
 
+  const handleStart = useCallback(() => startWithProfile(), [startWithProfile]);
+  const handleProfileStart = useCallback((profileId: string) => startWithProfile(profileId), [startWithProfile]);
+
   const handleStop = useCallback(async () => {
     flog.info('recording', 'handleStop called', {
       isStopping: isStoppingRef.current, status: statusRef.current,
@@ -232,18 +497,21 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
       statusRef.current = 'processing';
       setStatus('processing');
       const res = await stopRecording();
-      if (res.text) {
+      if (res.type === 'error') {
+        setError(res.error || 'Unknown error');
+      } else if (res.text) {
         setTranscription(res.text);
         // addEntry/updateStats handled by transcription-complete event listener
         // to avoid race-condition duplicates.
       }
-      if (res.type === 'error') setError(res.error || 'Unknown error');
+      // A queued stop reports "processing"; the recording-status-changed
+      // event settles the status once the worker finishes.
       // Only update status from the return value if we're still in
       // processing. If cancel already set us to idle (or a new recording
       // started), don't clobber the current state with a stale result.
       // Event handlers update statusRef synchronously, so this check cannot
       // lag behind React rendering.
-      const newStatus = isDictationStatus(res.state) ? res.state : 'idle';
+      const newStatus = res.type !== 'error' && isDictationStatus(res.state) ? res.state : 'idle';
       if (statusRef.current === 'processing') {
         statusRef.current = newStatus;
         setStatus(newStatus);
@@ -260,7 +528,7 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
   // Stable toggle for hotkey use — reads status from ref
   const toggleRecording = useCallback(async () => {
     flog.info('recording', 'toggleRecording', { status: statusRef.current });
-    if (statusRef.current === 'processing') return;
+    // Processing takes sit on the backend queue, so a new take may start.
     if (statusRef.current === 'recording') {
       await handleStop();
     } else {
@@ -286,6 +554,7 @@ export function useRecordingState({ addEntry, microphone }: UseRecordingStatePro
     error,
     setError,
     handleStart,
+    handleProfileStart,
     handleStop,
     toggleRecording,
     audioLevel,
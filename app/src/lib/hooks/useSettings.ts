@@ -2,7 +2,8 @@ import { useState, useRef, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { emit, listen } from '@tauri-apps/api/event';
 import { Settings, loadSettings, saveSettings } from '../settings';
-import { configure, buildConfigureOptions } from '../dictation';
+import { configure, buildConfigureOptions, setDownloadNetwork, setCloudTranscription } from '../dictation';
+import { setStorageRetention } from '../storage';
 import { enable, disable, isEnabled } from '@tauri-apps/plugin-autostart';
 
 let lastAutostartOp: Promise<void> = Promise.resolve();
@@ -76,6 +77,60 @@ export function useSettings() {
       });
     }
 
+    if ('quietHours' in updates) {
+      invoke('set_quiet_hours', { ...newSettings.quietHours }).catch((err) => {
+        console.error('Failed to apply quiet hours:', err);
+      });
+    }
+
+    if ('microphonePreferences' in updates) {
+      invoke('set_input_device_preferences', { devices: newSettings.microphonePreferences }).catch((err) => {
+        console.error('Failed to apply microphone preferences:', err);
+      });
+    }
+
+    if ('lowQualityMicAction' in updates) {
+      invoke('set_low_quality_input_action', { action: newSettings.lowQualityMicAction }).catch((err) => {
+        console.error('Failed to apply low-quality microphone action:', err);
+      });
+    }
+
+    if ('overlayDisplay' in updates) {
+      invoke('set_overlay_display', { mode: newSettings.overlayDisplay }).catch((err) => {
+        console.error('Failed to apply overlay display:', err);
+      });
+    }
+
+    if ('overlayFullscreen' in updates) {
+      invoke('set_overlay_fullscreen', { mode: newSettings.overlayFullscreen }).catch((err) => {
+        console.error('Failed to apply overlay fullscreen mode:', err);
+      });
+    }
+
+    if ('overlayStyle' in updates || 'overlayPillPosition' in updates) {
+      invoke('set_overlay_style', { style: newSettings.overlayStyle, pillPosition: newSettings.overlayPillPosition }).catch((err) => {
+        console.error('Failed to apply overlay style:', err);
+      });
+    }
+
+    if ('storageRetention' in updates) {
+      setStorageRetention(newSettings.storageRetention).catch((err) => {
+        console.error('Failed to apply storage retention settings:', err);
+      });
+    }
+
+    if ('downloadNetwork' in updates) {
+      setDownloadNetwork(newSettings.downloadNetwork).catch((err) => {
+        console.error('Failed to apply download network settings:', err);
+      });
+    }
+
+    if ('cloudTranscription' in updates) {
+      setCloudTranscription(newSettings.cloudTranscription).catch((err) => {
+        console.error('Failed to apply cloud transcription settings:', err);
+      });
+    }
+
     if ('model' in updates || 'autoPaste' in updates || 'disabled' in updates || 'saveTranscript' in updates || 'saveAudio' in updates || 'hotkeyMissFeedback' in updates) {
       // Notify the overlay window (separate React context) so its quick-settings
       // controls reflect changes made here. The diff-guard in applyExternalSettings
@@ -83,7 +138,7 @@ export function useSettings() {
       emit('settings-changed').catch((err) => console.error('Failed to emit settings-changed:', err));
     }
 
-    if ('model' in updates || 'language' in updates || 'autoPaste' in updates || 'autoPasteDelayMs' in updates || 'vadSensitivity' in updates || 'idleTimeoutMinutes' in updates || 'customVocabulary' in updates || 'vocabularyEntries' in updates || 'smartPunctuation' in updates || 'saveTranscript' in updates || 'saveAudio' in updates || 'outputDir' in updates || 'appProfiles' in updates || 'voiceCommandsEnabled' in updates || 'voiceCommands' in updates || 'cleanupEnabled' in updates || 'smartFormattingEnabled' in updates || 'cleanupRemoveFiller' in updates || 'cleanupCapitalize' in updates || 'codeVocabEnabled' in updates || 'codeVocabFolder' in updates || 'correctionEnabled' in updates || 'correctionFuzzy' in updates) {
+    if ('model' in updates || 'language' in updates || 'autoPaste' in updates || 'autoPasteDelayMs' in updates || 'vadSensitivity' in updates || 'idleTimeoutMinutes' in updates || 'backendKeepAliveMb' in updates || 'customVocabulary' in updates || 'vocabularyEntries' in updates || 'smartPunctuation' in updates || 'saveTranscript' in updates || 'saveAudio' in updates || 'outputDir' in updates || 'appProfiles' in updates || 'dictationProfiles' in updates || 'rewriteTemplates' in updates || 'replacementRules' in updates || 'dictationMacros' in updates || 'voiceCommandsEnabled' in updates || 'voiceCommands' in updates || 'cleanupEnabled' in updates || 'smartFormattingEnabled' in updates || 'cleanupRemoveFiller' in updates || 'cleanupCapitalize' in updates || 'codeVocabEnabled' in updates || 'codeVocabFolder' in updates || 'correctionEnabled' in updates || 'correctionFuzzy' in updates || 'learnFromCorrections' in updates || 'contextBiasing' in updates || 'notificationPolicy' in updates || 'focusFilter' in updates || 'interruptedRecordingAction' in updates || 'whisperDecoding' in updates || 'hallucinationFilter' in updates || 'redaction' in updates || 'adaptiveModel' in updates || 'latencyBudget' in updates || 'dualModel' in updates || 'confidenceRetry' in updates || 'alternatives' in updates || 'privacyMode' in updates || 'keepAudioOnDevice' in updates || 'appendMode' in updates || 'legacyAutomation' in updates || 'previewBeforePaste' in updates || 'cursorContext' in updates || 'trailingBehavior' in updates || 'notesTarget' in updates || 'selectionDictation' in updates) {
       const version = ++configureVersionRef.current;
       configure(buildConfigureOptions(newSettings))
         .catch(() => {
@@ -97,6 +152,7 @@ export function useSettings() {
               autoPasteDelayMs: previousSettings.autoPasteDelayMs,
               vadSensitivity: previousSettings.vadSensitivity,
               idleTimeoutMinutes: previousSettings.idleTimeoutMinutes,
+              backendKeepAliveMb: previousSettings.backendKeepAliveMb,
               customVocabulary: previousSettings.customVocabulary,
               vocabularyEntries: previousSettings.vocabularyEntries,
               smartPunctuation: previousSettings.smartPunctuation,
@@ -104,6 +160,10 @@ export function useSettings() {
               saveAudio: previousSettings.saveAudio,
               outputDir: previousSettings.outputDir,
               appProfiles: previousSettings.appProfiles,
+              dictationProfiles: previousSettings.dictationProfiles,
+              rewriteTemplates: previousSettings.rewriteTemplates,
+              replacementRules: previousSettings.replacementRules,
+              dictationMacros: previousSettings.dictationMacros,
               voiceCommandsEnabled: previousSettings.voiceCommandsEnabled,
               voiceCommands: previousSettings.voiceCommands,
               cleanupEnabled: previousSettings.cleanupEnabled,
@@ -114,12 +174,34 @@ export function useSettings() {
               codeVocabFolder: previousSettings.codeVocabFolder,
               correctionEnabled: previousSettings.correctionEnabled,
               correctionFuzzy: previousSettings.correctionFuzzy,
+              learnFromCorrections: previousSettings.learnFromCorrections,
+              contextBiasing: previousSettings.contextBiasing,
+              notificationPolicy: previousSettings.notificationPolicy,
+              focusFilter: previousSettings.focusFilter,
+              interruptedRecordingAction: previousSettings.interruptedRecordingAction,
+              whisperDecoding: previousSettings.whisperDecoding,
+              hallucinationFilter: previousSettings.hallucinationFilter,
+              redaction: previousSettings.redaction,
+              adaptiveModel: previousSettings.adaptiveModel,
+              latencyBudget: previousSettings.latencyBudget,
+              dualModel: previousSettings.dualModel,
+              confidenceRetry: previousSettings.confidenceRetry,
+              alternatives: previousSettings.alternatives,
+              privacyMode: previousSettings.privacyMode,
+              keepAudioOnDevice: previousSettings.keepAudioOnDevice,
+              appendMode: previousSettings.appendMode,
+              legacyAutomation: previousSettings.legacyAutomation,
+              previewBeforePaste: previousSettings.previewBeforePaste,
+              cursorContext: previousSettings.cursorContext,
+              trailingBehavior: previousSettings.trailingBehavior,
+              notesTarget: previousSettings.notesTarget,
+              selectionDictation: previousSettings.selectionDictation,
             };
             settingsRef.current = reverted;
             setSettings(reverted);
             saveSettings(reverted);
             setConfigureError(
-              'Settings could not be saved. Previous settings were restored. Check vocabulary aliases and Voice Commands for conflicts, then try again.',
+              'Settings could not be saved. Previous settings were restored. Check vocabulary aliases and Voice Commands for conflicts, and redaction patterns for invalid expressions, then try again.',
             );
           }
         });
@@ -133,7 +215,9 @@ export function useSettings() {
     const prev = settingsRef.current;
     const disabledChanged = fresh.disabled !== prev.disabled;
     const autoPasteChanged = fresh.autoPaste !== prev.autoPaste;
-    if (!disabledChanged && !autoPasteChanged) return;
+    // A dragged pill: Rust already applied the position, so only persist it.
+    const pillMoved = JSON.stringify(fresh.overlayPillPosition) !== JSON.stringify(prev.overlayPillPosition);
+    if (!disabledChanged && !autoPasteChanged && !pillMoved) return;
 
     settingsRef.current = fresh;
     setSettings(fresh);
@@ -0,0 +1,42 @@
+import { useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { AVAILABLE_MODEL_OPTIONS, isCloudModel, isCustomModel, isExternalModel, type Settings } from '../settings';
+
+interface TraySettingsRequest {
+  autoPaste?: boolean;
+  model?: string;
+}
+
+/**
+ * Applies quick-toggle changes requested from the menu-bar tray. Settings are
+ * persisted here, not in Rust, so the tray only asks; `update` saves and
+ * configures as if the change came from Settings, and the tray's check marks
+ * follow once the backend accepts it.
+ */
+export function useTraySettingsRequests(update: (updates: Partial<Settings>) => void) {
+  // updateSettings is recreated every render; read it through a ref so the
+  // listener is registered once.
+  const updateRef = useRef(update);
+  useEffect(() => { updateRef.current = update; }, [update]);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    listen<TraySettingsRequest>('tray-settings-request', (event) => {
+      if (cancelled) return;
+      const { autoPaste, model } = event.payload;
+      if (typeof autoPaste === 'boolean') {
+        updateRef.current({ autoPaste });
+      }
+      const option = AVAILABLE_MODEL_OPTIONS.find((candidate) => candidate.value === model);
+      if (option) {
+        updateRef.current({ model: option.value });
+      } else if (isCustomModel(model) || isExternalModel(model) || isCloudModel(model)) {
+        updateRef.current({ model });
+      }
+    }).then((fn) => {
+      if (cancelled) { fn(); } else { unlisten = fn; }
+    });
+    return () => { cancelled = true; unlisten?.(); };
+  }, []);
+}
@@ -0,0 +1,61 @@
+import { useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { startWakeWord, stopWakeWord } from '../dictation';
+
+interface UseWakeWordProps {
+  enabled: boolean;
+  initialized: boolean;
+  microphone: string;
+  onStart: () => void;
+  onStop: () => void;
+}
+
+/**
+ * Runs the "Hey Murmur" detector while enabled. `wake-word-detected` starts a
+ * recording like a hotkey press; `wake-word-silence` stops a recording the
+ * wake word started once the speaker goes quiet.
+ */
+export function useWakeWord({ enabled, initialized, microphone, onStart, onStop }: UseWakeWordProps) {
+  const onStartRef = useRef(onStart);
+  const onStopRef = useRef(onStop);
+  useEffect(() => { onStartRef.current = onStart; }, [onStart]);
+  useEffect(() => { onStopRef.current = onStop; }, [onStop]);
+
+  useEffect(() => {
+    if (!enabled || !initialized) return;
+
+    let unlistenDetected: (() => void) | null = null;
+    let unlistenSilence: (() => void) | null = null;
+    let cancelled = false;
+
+    const setup = async () => {
+      unlistenDetected = await listen('wake-word-detected', () => {
+        onStartRef.current();
+      });
+      if (cancelled) { unlistenDetected(); return; }
+
+      unlistenSilence = await listen('wake-word-silence', () => {
+        onStopRef.current();
+      });
+      if (cancelled) { unlistenDetected(); unlistenSilence(); return; }
+
+      try {
+        await startWakeWord(microphone);
+        if (cancelled) {
+          stopWakeWord().catch(() => {});
+        }
+      } catch (err) {
+        console.error('Failed to start wake word detector:', err);
+      }
+    };
+
+    setup();
+
+    return () => {
+      cancelled = true;
+      unlistenDetected?.();
+      unlistenSilence?.();
+      stopWakeWord().catch(() => {});
+    };
+  }, [enabled, initialized, microphone]);
+}
@@ -1,6 +1,6 @@
 import { useEffect, useRef } from 'react';
 import { listen } from '@tauri-apps/api/event';
-import type { DictationStatus } from '../types';
+import type { DictationStatus, OverlayState } from '../types';
 
 /** Number of animated waveform bars in the top-bar right slot. */
 export const BAR_COUNT = 7;
@@ -11,7 +11,7 @@ export interface OverlayWaveform {
 }
 
 /**
- * Owns the audio-level listener and the rAF bar-height animation. Bars are
+ * Owns the overlay-state level listener and the rAF bar-height animation. Bars are
  * updated via direct DOM writes (no React state per frame) — unchanged from
  * the original inline implementation, just relocated.
  */
@@ -19,12 +19,12 @@ export function useWaveform(status: DictationStatus): OverlayWaveform {
   const audioLevelRef = useRef(0);
   const barRefs = useRef<(HTMLDivElement | null)[]>([]);
 
-  // Subscribe to audio level events from Rust (store in ref, no state update)
+  // Subscribe to overlay-state levels from Rust (store in ref, no state update)
   useEffect(() => {
     let cancelled = false;
     let unlisten: (() => void) | null = null;
-    listen<number>('audio-level', (event) => {
-      audioLevelRef.current = event.payload;
+    listen<OverlayState>('overlay-state', (event) => {
+      if (event.payload?.state === 'level') audioLevelRef.current = event.payload.payload.rms;
     }).then((fn) => {
       if (cancelled) { fn(); } else { unlisten = fn; }
     });
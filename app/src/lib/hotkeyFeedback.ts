@@ -1,4 +1,4 @@
-import type { DictationStatus } from './types';
+import { isSettledStatus, type DictationStatus } from './types';
 
 export const HOTKEY_MISS_FLASH_MS = 500;
 
@@ -19,7 +19,7 @@ export function shouldShowHotkeyMissFeedback(
   payload: HotkeyTapRejectedPayload,
 ): boolean {
   return enabled
-    && status === 'idle'
+    && isSettledStatus(status)
     && payload.reason === 'second_tap_expired'
     && (payload.mode === 'double_tap' || payload.mode === 'both');
 }
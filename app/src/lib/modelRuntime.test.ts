@@ -1,5 +1,5 @@
 import { describe, expect, it } from 'vitest';
-import { applyRuntimeUpdate, type ModelRuntimeSnapshot } from './modelRuntime';
+import { applyRuntimeUpdate, describeTradeoffs, type ModelRuntimeSnapshot } from './modelRuntime';
 
 function snapshot(generation: number, lifecycleState: ModelRuntimeSnapshot['lifecycleState']): ModelRuntimeSnapshot {
   return {
@@ -39,3 +39,23 @@ describe('model runtime updates', () => {
     expect(applyRuntimeUpdate([current], snapshot(3, 'loading'))).toEqual([current]);
   });
 });
+
+describe('model catalog trade-offs', () => {
+  it('summarizes ratings, download size, and quantization', () => {
+    const entry = {
+      modelName: 'base.en-q5_1',
+      label: 'Whisper Base (English, Q5)',
+      size: '~57 MB',
+      backend: 'whisper',
+      quantization: 'q5_1',
+      downloadMb: 57,
+      speed: 4,
+      accuracy: 2,
+      expectedRtf: null,
+      supported: true,
+      installed: false,
+    };
+    expect(describeTradeoffs(entry)).toBe('Speed 4/5 · Accuracy 2/5 · 57 MB download · Q5_1 quantized');
+    expect(describeTradeoffs({ ...entry, quantization: null, downloadMb: 3000, expectedRtf: 0.05 })).toBe('Speed 4/5 · Accuracy 2/5 · 3.0 GB download · RTF ~0.05');
+  });
+});
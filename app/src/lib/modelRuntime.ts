@@ -51,6 +51,56 @@ export function getModelRuntimeStatus(modelName: string): Promise<ModelRuntimeSn
   return invoke('get_model_runtime_status', { modelName });
 }
 
+/** A shipped model with its download trade-offs. `speed` and `accuracy` are
+ * coarse 1–5 ratings relative to the rest of the catalog. */
+export interface ModelCatalogEntry {
+  modelName: string;
+  label: string;
+  size: string;
+  backend: string;
+  quantization: string | null;
+  downloadMb: number;
+  speed: number;
+  accuracy: number;
+  /** Real-time factor on Apple Silicon; `null` when not measured or estimated. */
+  expectedRtf: number | null;
+  supported: boolean;
+  installed: boolean;
+}
+
+export function getModelCatalog(): Promise<ModelCatalogEntry[]> {
+  return invoke('get_model_catalog');
+}
+
+/** One-line trade-off summary for the model picker. */
+export function describeTradeoffs(entry: ModelCatalogEntry): string {
+  const download = entry.downloadMb >= 1000
+    ? `${(entry.downloadMb / 1000).toFixed(1)} GB`
+    : `${entry.downloadMb} MB`;
+  const parts = [
+    `Speed ${entry.speed}/5`,
+    `Accuracy ${entry.accuracy}/5`,
+    `${download} download`,
+  ];
+  if (entry.expectedRtf !== null) parts.push(`RTF ~${entry.expectedRtf}`);
+  if (entry.quantization) parts.push(`${entry.quantization.toUpperCase()} quantized`);
+  return parts.join(' · ');
+}
+
+/** A model held in memory: the active one, or an engine parked for a quick
+ * switch back. */
+export interface LoadedBackend {
+  backend: string;
+  modelName: string;
+  label: string;
+  residentMb: number;
+  active: boolean;
+}
+
+export function getLoadedBackends(): Promise<LoadedBackend[]> {
+  return invoke('get_loaded_backends');
+}
+
 export function applyRuntimeUpdate(
   catalog: ModelRuntimeSnapshot[],
   update: ModelRuntimeSnapshot,
@@ -106,3 +156,29 @@ export function useModelRuntimeCatalog(enabled = true) {
   );
   return { models, byName, error };
 }
+
+/** Resident models, refreshed on every runtime transition. */
+export function useLoadedBackends(enabled = true) {
+  const [loaded, setLoaded] = useState<LoadedBackend[]>([]);
+
+  useEffect(() => {
+    if (!enabled) return;
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+    const refresh = () => {
+      getLoadedBackends()
+        .then((backends) => { if (!cancelled) setLoaded(backends ?? []); })
+        .catch(() => {});
+    };
+    refresh();
+    listen('model-runtime-status-changed', refresh)
+      .then((fn) => { if (cancelled) { fn(); } else { unlisten = fn; } })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [enabled]);
+
+  return loaded;
+}
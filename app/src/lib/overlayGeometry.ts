@@ -3,14 +3,17 @@ export interface OverlayGeometry {
   pillIdleW: number; pillActiveW: number;
   pillMarginIdle: number; pillMarginActive: number;
   dropdownH: number; wingW: number;
+  /** Free-standing pill (displays without a notch) rather than a notch island. */
+  floating: boolean;
 }
 
-const KEYS = ['windowW', 'collapsedH', 'expandedH', 'pillIdleW', 'pillActiveW',
+const NUMBER_KEYS = ['windowW', 'collapsedH', 'expandedH', 'pillIdleW', 'pillActiveW',
   'pillMarginIdle', 'pillMarginActive', 'dropdownH', 'wingW'] as const;
 
 export function isOverlayGeometry(v: unknown): v is OverlayGeometry {
   if (typeof v !== 'object' || v === null) return false;
   const o = v as Record<string, unknown>;
-  return Object.keys(o).length === KEYS.length
-    && KEYS.every((k) => typeof o[k] === 'number' && Number.isFinite(o[k] as number));
+  return Object.keys(o).length === NUMBER_KEYS.length + 1
+    && NUMBER_KEYS.every((k) => typeof o[k] === 'number' && Number.isFinite(o[k] as number))
+    && typeof o.floating === 'boolean';
 }
@@ -41,6 +41,33 @@ describe('performance contracts', () => {
     })).toBe(false);
   });
 
+  it('accepts samples with and without a GPU measurement', () => {
+    const sample = {
+      schemaVersion: 1,
+      observedAtMs: 1,
+      host: { cpuPercent: { status: 'measured', value: 12 } },
+      mainProcess: {
+        cpuPercent: { status: 'measured', value: 3 },
+        rssBytes: { status: 'measured', value: 100 },
+        rustHeapBytes: { status: 'measured', value: 10 },
+        ffiNativeHeapBytes: { status: 'measured', value: 20 },
+      },
+      sidecarProcess: {
+        cpuPercent: { status: 'notApplicable' },
+        rssBytes: { status: 'notApplicable' },
+      },
+    };
+    expect(isResourceSampleV1(sample)).toBe(true);
+    expect(isResourceSampleV1({
+      ...sample,
+      host: { ...sample.host, gpuPercent: { status: 'measured', value: 40 } },
+    })).toBe(true);
+    expect(isResourceSampleV1({
+      ...sample,
+      host: { ...sample.host, gpuPercent: 40 },
+    })).toBe(false);
+  });
+
   it('requires correlation to match the run kind', () => {
     const unavailable = { status: 'unavailable', reason: 'noSamples' };
     const notApplicable = { status: 'notApplicable' };
@@ -60,7 +60,7 @@ export interface StageTimingV1 {
 export interface RuntimeIdentityV1 {
   role: 'transcription' | 'instructionAsr' | 'generation';
   modelId: string;
-  backend: 'whisper' | 'parakeet' | 'coreml' | 'llamaCpp';
+  backend: 'whisper' | 'parakeet' | 'coreml' | 'llamaCpp' | 'external' | 'cloud';
   accelerator: 'cpu' | 'metalGpu' | 'appleNeuralEngine' | 'platformFallback';
   warmState: 'warm' | 'coldLoaded' | 'unknown';
 }
@@ -92,6 +92,8 @@ export interface ResourceSampleV1 {
   observedAtMs: number;
   host: {
     cpuPercent: MeasurementV1<number>;
+    /** Absent from samples recorded before GPU sampling existed. */
+    gpuPercent?: MeasurementV1<number>;
   };
   mainProcess: {
     cpuPercent: MeasurementV1<number>;
@@ -107,7 +109,7 @@ export interface ResourceSampleV1 {
 
 export interface ResourceSummaryV1 {
   sampleCount: number;
-  host: { cpuPercent: ResourceRangeV1<number> };
+  host: { cpuPercent: ResourceRangeV1<number>; gpuPercent?: ResourceRangeV1<number> };
   mainProcess: {
     cpuPercent: ResourceRangeV1<number>;
     rssBytes: ResourceRangeV1<number>;
@@ -166,6 +168,23 @@ export interface PerformanceRunListV1 {
   runs: PerformanceRunV1[];
 }
 
+export type PipelinePhase =
+  | 'capture'
+  | 'resample'
+  | 'preprocess'
+  | 'inference'
+  | 'postprocess'
+  | 'inject';
+
+/** Phase spans of one dictation, keyed by its recording id. */
+export interface PipelineTrace {
+  pipelineId: number;
+  startedAtMs: number;
+  totalMs: number;
+  /** In start order; resample runs inside capture. */
+  phases: Array<{ phase: PipelinePhase; startMs: number; durationMs: number }>;
+}
+
 const unavailableReasons = new Set<UnavailableReasonV1>([
   'unsupportedPlatform',
   'sampleFailed',
@@ -229,6 +248,7 @@ export function isResourceSampleV1(value: unknown): value is ResourceSampleV1 {
   const numberMeasurement = (candidate: unknown): candidate is MeasurementV1<number> =>
     isMeasurementV1(candidate, isFiniteNumber);
   return numberMeasurement(value.host.cpuPercent)
+    && (value.host.gpuPercent === undefined || numberMeasurement(value.host.gpuPercent))
     && numberMeasurement(value.mainProcess.cpuPercent)
     && numberMeasurement(value.mainProcess.rssBytes)
     && numberMeasurement(value.mainProcess.rustHeapBytes)
@@ -254,7 +274,7 @@ function isRuntime(value: unknown): value is RuntimeIdentityV1 {
   return isRecord(value)
     && ['transcription', 'instructionAsr', 'generation'].includes(String(value.role))
     && typeof value.modelId === 'string'
-    && ['whisper', 'parakeet', 'coreml', 'llamaCpp'].includes(String(value.backend))
+    && ['whisper', 'parakeet', 'coreml', 'llamaCpp', 'external', 'cloud'].includes(String(value.backend))
     && ['cpu', 'metalGpu', 'appleNeuralEngine', 'platformFallback'].includes(String(value.accelerator))
     && ['warm', 'coldLoaded', 'unknown'].includes(String(value.warmState));
 }
@@ -288,6 +308,7 @@ function isResourceSummary(value: unknown): value is ResourceSummaryV1 {
     && isFiniteNumber(value.sampleCount)
     && isRecord(value.host)
     && isResourceRange(value.host.cpuPercent)
+    && (value.host.gpuPercent === undefined || isResourceRange(value.host.gpuPercent))
     && isRecord(value.mainProcess)
     && isResourceRange(value.mainProcess.cpuPercent)
     && isResourceRange(value.mainProcess.rssBytes)
@@ -386,6 +407,20 @@ export async function getPerformanceResourceWindow(): Promise<ResourceSampleV1[]
   return value;
 }
 
+/** Persisted samples between the two bounds (inclusive, either optional), oldest first. */
+export async function getResourceTimeline(sinceMs?: number, untilMs?: number): Promise<ResourceSampleV1[]> {
+  const value = await invoke<unknown>('get_resource_timeline', { sinceMs, untilMs });
+  if (!Array.isArray(value) || !value.every(isResourceSampleV1)) {
+    throw new Error('Murmur returned an unsupported resource-sample schema.');
+  }
+  return value;
+}
+
+/** The most recently finished dictation's phase timings, or null before the first. */
+export async function getLastPipelineTrace(): Promise<PipelineTrace | null> {
+  return invoke<PipelineTrace | null>('get_last_pipeline_trace');
+}
+
 export async function clearPerformanceDiagnostics(): Promise<void> {
   await invoke('clear_performance_diagnostics');
 }
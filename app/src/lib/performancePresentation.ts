@@ -300,9 +300,13 @@ export function peakResourceSummary(run: PerformanceRunV1): string {
   const mainRss = formatMeasurement(run.resources.mainProcess.rssBytes.peak, formatBytes);
   const hostCpu = formatMeasurement(run.resources.host.cpuPercent.peak, formatPercent);
   const sidecarRss = formatMeasurement(run.resources.sidecarProcess.rssBytes.peak, formatBytes);
+  const hostGpu = run.resources.host.gpuPercent
+    ? formatMeasurement(run.resources.host.gpuPercent.peak, formatPercent)
+    : null;
   if (mainRss.status === 'measured') parts.push(`Main ${mainRss.text}`);
   if (sidecarRss.status === 'measured') parts.push(`Sidecar ${sidecarRss.text}`);
   if (hostCpu.status === 'measured') parts.push(`Host ${hostCpu.text}`);
+  if (hostGpu?.status === 'measured') parts.push(`GPU ${hostGpu.text}`);
   return parts.join(' · ') || 'Unavailable';
 }
 
@@ -0,0 +1,44 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { DEFAULT_SETTINGS } from './settings';
+import { DEVICE_SETTINGS, importedHistory, importedSettings, loadSyncState, portableSettings, saveSyncState } from './portable';
+
+beforeEach(() => {
+  localStorage.clear();
+});
+
+describe('portableSettings', () => {
+  it('leaves out what belongs to this Mac, in a fixed order', () => {
+    const settings = { ...DEFAULT_SETTINGS, microphone: 'USB Mic', dataFolder: '/Users/me/Sync', language: 'de' };
+    const portable = portableSettings(settings);
+    for (const key of DEVICE_SETTINGS) expect(portable).not.toHaveProperty(key);
+    expect(portable.language).toBe('de');
+    const reordered = Object.fromEntries(Object.entries(settings).reverse()) as typeof settings;
+    expect(JSON.stringify(portableSettings(reordered))).toBe(JSON.stringify(portable));
+  });
+});
+
+describe('importedSettings', () => {
+  it('sanitizes the archive and never carries device fields', () => {
+    const imported = importedSettings({ ...DEFAULT_SETTINGS, language: 'fr', outputDir: '/Volumes/Other', quietHours: 'always' });
+    expect(imported.language).toBe('fr');
+    expect(imported.quietHours).toEqual(DEFAULT_SETTINGS.quietHours);
+    expect(imported).not.toHaveProperty('outputDir');
+  });
+});
+
+describe('importedHistory', () => {
+  it('keeps only well-formed entries', () => {
+    const entry = { id: '1', text: 'hello', timestamp: 5, duration: 1.2 };
+    expect(importedHistory([entry, { id: 2, text: 'x', timestamp: 1, duration: 1 }, null, 'text'])).toEqual([entry]);
+    expect(importedHistory({ entries: [entry] })).toEqual([]);
+  });
+});
+
+describe('sync state', () => {
+  it('starts from zero for a folder it has not synced', () => {
+    expect(loadSyncState('/a')).toEqual({ folder: '/a', settingsAt: 0, historyAt: 0 });
+    saveSyncState({ folder: '/a', settingsAt: 10, historyAt: 20 });
+    expect(loadSyncState('/a')).toEqual({ folder: '/a', settingsAt: 10, historyAt: 20 });
+    expect(loadSyncState('/b')).toEqual({ folder: '/b', settingsAt: 0, historyAt: 0 });
+  });
+});
@@ -0,0 +1,124 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { HistoryEntry } from './history';
+import { DEFAULT_SETTINGS, sanitizeSettings, type Settings } from './settings';
+
+/**
+ * Settings that describe this Mac rather than how the user dictates: its
+ * microphones, folders, login item, and overlay placement. They are left out
+ * of exports and the sync folder, so importing on another Mac never points it
+ * at a device or path it doesn't have.
+ */
+export const DEVICE_SETTINGS = [
+  'microphone',
+  'microphonePreferences',
+  'launchAtLogin',
+  'overlayPillPosition',
+  'outputDir',
+  'benchmarkOutputDir',
+  'codeVocabFolder',
+  'codeVocabLastScan',
+  'dataFolder',
+] as const satisfies ReadonlyArray<keyof Settings>;
+
+const DEVICE_KEYS = new Set<string>(DEVICE_SETTINGS);
+
+/** A settings or history archive as written by `export_settings`, `export_history`, or the sync folder. */
+export interface PortableArchive {
+  format: 'murmur-settings' | 'murmur-history';
+  version: number;
+  exportedAtMs: number;
+  appVersion: string;
+  data: unknown;
+}
+
+/** What `read_data_folder` found; a missing or half-synced file is null. */
+export interface DataFolderContents {
+  settings: PortableArchive | null;
+  history: PortableArchive | null;
+}
+
+/** The settings worth carrying to another Mac, in a fixed key order so two copies compare equal as JSON. */
+export function portableSettings(settings: Settings): Partial<Settings> {
+  return Object.fromEntries(
+    Object.keys(DEFAULT_SETTINGS)
+      .filter((key) => !DEVICE_KEYS.has(key))
+      .map((key) => [key, settings[key as keyof Settings]]),
+  ) as Partial<Settings>;
+}
+
+/** Imported settings, sanitized like stored ones, without this Mac's own fields. */
+export function importedSettings(raw: unknown): Partial<Settings> {
+  return portableSettings(sanitizeSettings(raw));
+}
+
+/** History entries from an archive, dropping anything that isn't one. */
+export function importedHistory(raw: unknown): HistoryEntry[] {
+  if (!Array.isArray(raw)) return [];
+  return raw.filter((entry): entry is HistoryEntry => (
+    !!entry && typeof entry === 'object'
+    && typeof entry.id === 'string'
+    && typeof entry.text === 'string'
+    && typeof entry.timestamp === 'number'
+    && typeof entry.duration === 'number'
+  ));
+}
+
+export async function exportSettings(path: string, settings: Settings): Promise<void> {
+  await invoke('export_settings', { path, settings: portableSettings(settings) });
+}
+
+export async function importSettings(path: string): Promise<Partial<Settings>> {
+  return importedSettings(await invoke<unknown>('import_settings', { path }));
+}
+
+/** Returns how many entries were written. */
+export async function exportHistory(path: string, entries: HistoryEntry[]): Promise<number> {
+  return await invoke('export_history', { path, entries });
+}
+
+export async function importHistory(path: string): Promise<HistoryEntry[]> {
+  return importedHistory(await invoke<unknown>('import_history', { path }));
+}
+
+export async function readDataFolder(folder: string): Promise<DataFolderContents> {
+  return await invoke('read_data_folder', { folder });
+}
+
+/** Write settings and/or history into the sync folder. Resolves to the `exportedAtMs` stamped on them. */
+export async function writeDataFolder(folder: string, settings: Partial<Settings> | null, history: HistoryEntry[] | null): Promise<number> {
+  return await invoke('write_data_folder', { folder, settings, history });
+}
+
+const SYNC_STATE_KEY = 'data-folder-sync';
+
+/** The `exportedAtMs` of the folder's archives this Mac last wrote or applied. */
+export interface DataFolderSyncState {
+  folder: string;
+  settingsAt: number;
+  historyAt: number;
+}
+
+/** Sync progress for `folder`; a different or newly chosen folder starts from zero, so its archives win. */
+export function loadSyncState(folder: string): DataFolderSyncState {
+  try {
+    const stored = JSON.parse(localStorage.getItem(SYNC_STATE_KEY) ?? 'null') as Partial<DataFolderSyncState> | null;
+    if (stored?.folder === folder) {
+      return {
+        folder,
+        settingsAt: typeof stored.settingsAt === 'number' ? stored.settingsAt : 0,
+        historyAt: typeof stored.historyAt === 'number' ? stored.historyAt : 0,
+      };
+    }
+  } catch {
+    // Treated as never synced.
+  }
+  return { folder, settingsAt: 0, historyAt: 0 };
+}
+
+export function saveSyncState(state: DataFolderSyncState): void {
+  try {
+    localStorage.setItem(SYNC_STATE_KEY, JSON.stringify(state));
+  } catch (e) {
+    console.error('Failed to save sync folder state:', e);
+  }
+}
@@ -0,0 +1,20 @@
+import { describe, expect, it } from 'vitest';
+import { profileForSource } from './power';
+import { DEFAULT_SETTINGS } from './settings';
+
+describe('profileForSource', () => {
+  const profiles = {
+    ...DEFAULT_SETTINGS.powerProfiles,
+    ac: { model: 'large-v3-turbo' as const, vadSensitivity: 70, idleTimeoutMinutes: 0 },
+    battery: { model: 'tiny.en' as const, vadSensitivity: 30, idleTimeoutMinutes: 5 },
+  };
+
+  it('uses the battery profile only on battery', () => {
+    expect(profileForSource(profiles, 'battery')).toBe(profiles.battery);
+    expect(profileForSource(profiles, 'ac')).toBe(profiles.ac);
+  });
+
+  it('treats an unknown source as the adapter', () => {
+    expect(profileForSource(profiles, 'unknown')).toBe(profiles.ac);
+  });
+});
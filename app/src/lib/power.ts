@@ -0,0 +1,21 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { PowerProfile, PowerProfilesSettings } from './settings';
+
+export type PowerSource = 'ac' | 'battery' | 'unknown';
+
+/** Payload of `get_power_status` and `power-status-changed`. */
+export interface PowerStatus {
+  source: PowerSource;
+  /** Internal battery charge, 0–100; null without a battery. */
+  batteryPercent: number | null;
+}
+
+export async function getPowerStatus(): Promise<PowerStatus> {
+  return await invoke<PowerStatus>('get_power_status');
+}
+
+/** The profile for `source`. An unknown source (desktops, unsupported
+ * platforms) counts as the adapter. */
+export function profileForSource(profiles: PowerProfilesSettings, source: PowerSource): PowerProfile {
+  return source === 'battery' ? profiles.battery : profiles.ac;
+}
@@ -0,0 +1,67 @@
+import { describe, expect, it } from 'vitest';
+import { CONFIG_PRESETS_MAX, DEFAULT_SETTINGS, PRESET_SETTINGS, type ConfigPreset } from './settings';
+import { capturePreset, matchingPreset, presetUpdates, removePreset, savePreset } from './presets';
+
+const coding: ConfigPreset = {
+  id: 'coding',
+  name: 'Coding',
+  settings: { language: 'en', autoPaste: true, trailingBehavior: 'newline' },
+  updatedAt: 10,
+};
+
+describe('capturePreset', () => {
+  it('takes only the preset settings', () => {
+    const captured = capturePreset({ ...DEFAULT_SETTINGS, language: 'es', microphone: 'USB Mic' });
+    expect(Object.keys(captured)).toEqual([...PRESET_SETTINGS]);
+    expect(captured.language).toBe('es');
+    expect(captured).not.toHaveProperty('microphone');
+  });
+});
+
+describe('presetUpdates', () => {
+  it('changes only what the preset holds, sanitized', () => {
+    const current = { ...DEFAULT_SETTINGS, language: 'de', microphone: 'USB Mic' };
+    expect(presetUpdates(current, coding)).toEqual({ language: 'en', autoPaste: true, trailingBehavior: 'newline' });
+    expect(presetUpdates(current, { ...coding, settings: { language: 'xx' } })).toEqual({ language: 'auto' });
+  });
+});
+
+describe('matchingPreset', () => {
+  it('finds the preset the settings match', () => {
+    const settings = { ...DEFAULT_SETTINGS, language: 'en', autoPaste: true, trailingBehavior: 'newline' as const };
+    expect(matchingPreset(settings, [coding])?.id).toBe('coding');
+    expect(matchingPreset({ ...settings, autoPaste: false }, [coding])).toBeNull();
+    expect(matchingPreset(settings, [{ ...coding, settings: {} }])).toBeNull();
+  });
+});
+
+describe('savePreset', () => {
+  it('adds a new preset and refuses a taken or blank name', () => {
+    const added = savePreset([coding], { id: 'meetings', name: ' Meetings ', settings: {}, baseUpdatedAt: null }, 50);
+    expect(added).toEqual({ ok: true, presets: [coding, { id: 'meetings', name: 'Meetings', settings: {}, updatedAt: 50 }] });
+    expect(savePreset([coding], { id: 'other', name: 'CODING', settings: {}, baseUpdatedAt: null }, 50).ok).toBe(false);
+    expect(savePreset([coding], { id: 'other', name: '  ', settings: {}, baseUpdatedAt: null }, 50).ok).toBe(false);
+  });
+
+  it('refuses a save based on a stale or deleted copy', () => {
+    const edit = { id: 'coding', name: 'Code', settings: {}, baseUpdatedAt: 10 };
+    const saved = savePreset([coding], edit, 5);
+    expect(saved).toEqual({ ok: true, presets: [{ id: 'coding', name: 'Code', settings: {}, updatedAt: 11 }] });
+    expect(savePreset([{ ...coding, updatedAt: 12 }], edit, 50)).toEqual({
+      ok: false,
+      error: '"Coding" was changed elsewhere. Review it and try again.',
+    });
+    expect(savePreset([], edit, 50).ok).toBe(false);
+  });
+
+  it('stops at the preset limit', () => {
+    const full = Array.from({ length: CONFIG_PRESETS_MAX }, (_, index) => ({ ...coding, id: `p${index}`, name: `Preset ${index}` }));
+    expect(savePreset(full, { id: 'new', name: 'New', settings: {}, baseUpdatedAt: null }, 50).ok).toBe(false);
+  });
+});
+
+describe('removePreset', () => {
+  it('drops the preset by id', () => {
+    expect(removePreset([coding], 'coding')).toEqual([]);
+  });
+});
@@ -0,0 +1,98 @@
+import { invoke } from '@tauri-apps/api/core';
+import {
+  CONFIG_PRESET_NAME_MAX,
+  CONFIG_PRESETS_MAX,
+  PRESET_SETTINGS,
+  sanitizeSettings,
+  type ConfigPreset,
+  type Settings,
+} from './settings';
+
+/** The preset-worthy part of `settings`, in `PRESET_SETTINGS` order. */
+export function capturePreset(settings: Settings): Partial<Settings> {
+  return Object.fromEntries(PRESET_SETTINGS.map((key) => [key, settings[key]])) as Partial<Settings>;
+}
+
+/** The updates that apply `preset` on top of `current`, sanitized like stored settings. */
+export function presetUpdates(current: Settings, preset: ConfigPreset): Partial<Settings> {
+  const next = sanitizeSettings({ ...current, ...preset.settings });
+  const keys = PRESET_SETTINGS.filter((key) => key in preset.settings);
+  const updates = Object.fromEntries(keys.map((key) => [key, next[key]])) as Partial<Settings>;
+  // The legacy vocabulary mirror is derived from the entries.
+  if ('vocabularyEntries' in updates) updates.customVocabulary = next.customVocabulary;
+  return updates;
+}
+
+/** The first preset whose values all match `settings`, if any. */
+export function matchingPreset(settings: Settings, presets: ConfigPreset[]): ConfigPreset | null {
+  return presets.find((preset) => {
+    const keys = PRESET_SETTINGS.filter((key) => key in preset.settings);
+    return keys.length > 0 && keys.every((key) => JSON.stringify(settings[key]) === JSON.stringify(preset.settings[key]));
+  }) ?? null;
+}
+
+/** A new or edited preset, with the `updatedAt` of the copy the edit started from (`null` when new). */
+export interface PresetDraft {
+  id: string;
+  name: string;
+  settings: Partial<Settings>;
+  baseUpdatedAt: number | null;
+}
+
+export type PresetSaveResult =
+  | { ok: true; presets: ConfigPreset[] }
+  | { ok: false; error: string };
+
+/**
+ * Add or replace a preset. Refused when the name is blank or taken, when the
+ * list is full, or when the stored preset moved on since the edit started
+ * (another window or a synced Mac saved it), so one save never silently
+ * overwrites another.
+ */
+export function savePreset(presets: ConfigPreset[], draft: PresetDraft, now: number): PresetSaveResult {
+  const name = draft.name.trim();
+  if (!name) return { ok: false, error: 'Give the preset a name.' };
+  if (name.length > CONFIG_PRESET_NAME_MAX) {
+    return { ok: false, error: `Preset names can be at most ${CONFIG_PRESET_NAME_MAX} characters.` };
+  }
+  if (presets.some((preset) => preset.id !== draft.id && preset.name.toLowerCase() === name.toLowerCase())) {
+    return { ok: false, error: `There is already a preset named "${name}".` };
+  }
+  const stored = presets.find((preset) => preset.id === draft.id);
+  if (draft.baseUpdatedAt !== null) {
+    if (!stored) return { ok: false, error: `"${name}" was deleted elsewhere.` };
+    if (stored.updatedAt !== draft.baseUpdatedAt) {
+      return { ok: false, error: `"${stored.name}" was changed elsewhere. Review it and try again.` };
+    }
+  } else if (stored) {
+    return { ok: false, error: 'That preset already exists.' };
+  }
+  if (!stored && presets.length >= CONFIG_PRESETS_MAX) {
+    return { ok: false, error: `You can keep up to ${CONFIG_PRESETS_MAX} presets.` };
+  }
+  // Strictly increasing, so a save within the same millisecond still counts as a change.
+  const saved: ConfigPreset = {
+    id: draft.id,
+    name,
+    settings: draft.settings,
+    updatedAt: Math.max(now, (stored?.updatedAt ?? 0) + 1),
+  };
+  return {
+    ok: true,
+    presets: stored
+      ? presets.map((preset) => (preset.id === draft.id ? saved : preset))
+      : [...presets, saved],
+  };
+}
+
+export function removePreset(presets: ConfigPreset[], id: string): ConfigPreset[] {
+  return presets.filter((preset) => preset.id !== id);
+}
+
+/** Mirror the preset names and the active one to Rust for the tray. */
+export async function syncConfigPresets(presets: ConfigPreset[], active: string | null): Promise<void> {
+  await invoke('set_config_presets', {
+    presets: presets.map(({ id, name }) => ({ id, name })),
+    active,
+  });
+}
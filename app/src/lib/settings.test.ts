@@ -2,7 +2,9 @@ import { describe, it, expect, beforeEach } from 'vitest';
 import {
   loadSettings,
   saveSettings,
+  sanitizeSettings,
   DEFAULT_SETTINGS,
+  macroPhraseKey,
   defaultModelForPlatform,
   modelOptionsForPlatform,
 } from './settings';
@@ -23,10 +25,17 @@ describe('loadSettings', () => {
       autoPasteDelayMs: 230,
       recordingMode: 'both' as const,
       hotkeyMissFeedback: true,
+      hotkeyTiming: { maxTapMs: 320, doubleTapWindowMs: 600, cooldownMs: 80, holdCooldownMs: 40, holdPromotionMs: 350 },
+      wakeWord: true,
+      ambientMode: true,
+      preRollSeconds: 2,
       microphone: 'Studio Mic',
+      microphonePreferences: ['AirPods Pro', 'MacBook Pro Microphone'],
+      lowQualityMicAction: 'use_built_in' as const,
       launchAtLogin: true,
       vadSensitivity: 75,
       idleTimeoutMinutes: 15,
+      backendKeepAliveMb: 4096,
       customVocabulary: 'Murmur',
       vocabularyEntries: [{ id: 'murmur', written: 'Murmur', aliases: ['murmur app'], enabled: true, scope: { kind: 'global' as const } }],
       disabled: true,
@@ -34,6 +43,13 @@ describe('loadSettings', () => {
       saveTranscript: true,
       saveAudio: true,
       outputDir: '/tmp/murmur-output',
+      dataFolder: '/Users/me/Library/Mobile Documents/com~apple~CloudDocs/Murmur',
+      configPresets: [{
+        id: 'coding',
+        name: 'Coding',
+        settings: { language: 'en', autoPaste: true, trailingBehavior: 'newline' as const },
+        updatedAt: 1700000000000,
+      }],
       appProfiles: [{
         bundleId: 'com.apple.Terminal',
         label: 'Terminal',
@@ -41,10 +57,29 @@ describe('loadSettings', () => {
         cleanupOverride: true,
         smartFormattingOverride: false,
         cliFormattingOverride: true,
+        trailingOverride: 'newline' as const,
         writingStyle: 'code_technical' as const,
         ideContextEnabled: true,
         ideProjectRoots: ['/tmp/project'],
       }],
+      dictationProfiles: [{
+        id: 'spanish',
+        name: 'Spanish',
+        hotkey: 'alt_r' as const,
+        modelOverride: 'large-v3-turbo' as const,
+        languageOverride: 'es',
+        autoPasteOverride: true,
+        cleanupOverride: null,
+        smartFormattingOverride: false,
+        rewriteTemplate: 'standup',
+      }],
+      rewriteTemplates: [{ id: 'standup', name: 'Standup', instruction: 'Rewrite as a standup update.' }],
+      replacementRules: [{ id: 'mac', find: 'macbook', replace: 'MacBook', regex: false, matchCase: false, enabled: true }],
+      dictationMacros: {
+        enabled: true,
+        confirm: false,
+        macros: [{ id: 'paste-off', phrase: 'stop pasting', action: { type: 'set_auto_paste' as const, enabled: false } }],
+      },
       voiceCommandsEnabled: true,
       voiceCommands: [{ phrase: 'standup', replacement: 'Yesterday:\nToday:' }],
       cleanupEnabled: true,
@@ -60,6 +95,85 @@ describe('loadSettings', () => {
       },
       correctionEnabled: false,
       correctionFuzzy: false,
+      learnFromCorrections: true,
+      notificationPolicy: 'errors_only' as const,
+      focusFilter: {
+        suppressNotifications: false,
+        disableHotkey: true,
+        modes: ['com.apple.focus.work'],
+      },
+      interruptedRecordingAction: 'transcribe' as const,
+      overlayDisplay: 'all' as const,
+      overlayStyle: 'pill' as const,
+      overlayPillPosition: { x: 0.25, y: 0.8 },
+      overlayFullscreen: 'pill' as const,
+      whisperDecoding: {
+        sampling: 'beam_search' as const,
+        beamSize: 3,
+        temperature: 0.2,
+        noSpeechThreshold: 0.45,
+        threads: 4,
+        singleSegment: 'never' as const,
+      },
+      hallucinationFilter: false,
+      flagLowConfidence: false,
+      redaction: {
+        enabled: true,
+        mode: 'remove' as const,
+        includeProfanity: false,
+        words: ['Project Falcon'],
+        patterns: ['\\d{4}'],
+      },
+      adaptiveModel: {
+        enabled: true,
+        fallbackModel: 'small.en' as const,
+      },
+      latencyBudget: { enabled: true, maxRtf: 1.5, autoSwitch: true },
+      powerProfiles: {
+        enabled: true,
+        ac: { model: 'large-v3-turbo' as const, vadSensitivity: 70, idleTimeoutMinutes: 0 },
+        battery: { model: 'tiny.en' as const, vadSensitivity: 30, idleTimeoutMinutes: 5 },
+      },
+      dualModel: {
+        enabled: true,
+        draftModel: 'base.en' as const,
+        replaceDraft: true,
+      },
+      confidenceRetry: {
+        enabled: true,
+        threshold: 0.3,
+        model: 'medium.en' as const,
+        budgetMs: 5000,
+      },
+      alternatives: {
+        enabled: true,
+        count: 2,
+      },
+      privacyMode: true,
+      keepAudioOnDevice: true,
+      cloudTranscription: { provider: 'deepgram' as const, endpoint: 'https://api.eu.deepgram.com/v1/listen', model: 'nova-3' },
+      persistHistory: false,
+      encryptHistory: true,
+      updateChannel: 'beta' as const,
+      downloadNetwork: { huggingFaceMirror: 'https://hf-mirror.com', githubMirror: '', proxy: 'socks5://127.0.0.1:1080', pauseOnMetered: true, pauseOnVpn: false },
+      storageRetention: { enabled: false, maxTotalMb: 1000, maxAgeDays: 0, includeRecordings: true },
+      typingWpm: 65,
+      appendMode: true,
+      appendCommitKey: 'ctrl_r' as const,
+      legacyAutomation: true,
+      previewBeforePaste: true,
+      cursorContext: true,
+      trailingBehavior: 'smart' as const,
+      notesTarget: 'no_text_field' as const,
+      selectionDictation: 'rewrite' as const,
+      contextBiasing: { enabled: true, windowTitle: false, selectedText: true, apps: [{ bundleId: 'com.apple.mail', name: 'Mail' }] },
+      pasteLastKey: 'alt_r' as const,
+      clipboardAudioKey: 'ctrl_l' as const,
+      markerKey: 'shift_r' as const,
+      spokenMarkers: true,
+      meetingChunkSeconds: 300,
+      quietHours: { enabled: true, start: '23:30', end: '06:45' },
+      appTriggers: [{ bundleId: 'md.obsidian', name: 'Obsidian', showOverlay: false, promptAmbient: true }],
     };
 
     saveSettings(stored);
@@ -278,6 +392,48 @@ describe('loadSettings', () => {
     expect(loadSettings().hotkeyMissFeedback).toBe(false);
   });
 
+  it('clamps hotkey timing into bounds and never promotes a tap to a hold', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      hotkeyTiming: { maxTapMs: 900, doubleTapWindowMs: 50, cooldownMs: 'slow', holdCooldownMs: 12.4, holdPromotionMs: 150 },
+    }));
+    expect(loadSettings().hotkeyTiming).toEqual({
+      maxTapMs: 600,
+      doubleTapWindowMs: 150,
+      cooldownMs: DEFAULT_SETTINGS.hotkeyTiming.cooldownMs,
+      holdCooldownMs: 12,
+      holdPromotionMs: 600,
+    });
+
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      hotkeyTiming: null,
+    }));
+    expect(loadSettings().hotkeyTiming).toEqual(DEFAULT_SETTINGS.hotkeyTiming);
+  });
+
+  it('keeps the wake word off unless it was explicitly enabled', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      wakeWord: true,
+    }));
+    expect(loadSettings().wakeWord).toBe(true);
+
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      wakeWord: 'on',
+    }));
+    expect(loadSettings().wakeWord).toBe(false);
+  });
+
+  it('keeps ambient mode off unless it was explicitly enabled', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      ambientMode: 1,
+    }));
+    expect(loadSettings().ambientMode).toBe(false);
+  });
+
   it('removes the retired live transcript preview setting', () => {
     localStorage.setItem('dictation-settings', JSON.stringify({
       ...DEFAULT_SETTINGS,
@@ -355,6 +511,50 @@ describe('loadSettings', () => {
     expect(loadSettings().model).toBe('parakeet-tdt-0.6b-v2-fp16');
   });
 
+  it('preserves a custom Whisper model and rejects path-like names', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      model: 'custom:ggml-distil-large-v3.bin',
+    }));
+    expect(loadSettings().model).toBe('custom:ggml-distil-large-v3.bin');
+
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      model: 'custom:../ggml-base.en.bin',
+    }));
+    expect(loadSettings().model).toBe(DEFAULT_SETTINGS.model);
+  });
+
+  it('preserves a transcription plugin and rejects malformed plugin ids', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      model: 'external:whisper-server',
+    }));
+    expect(loadSettings().model).toBe('external:whisper-server');
+
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      model: 'external:../whisper-server',
+    }));
+    expect(loadSettings().model).toBe(DEFAULT_SETTINGS.model);
+  });
+
+  it('preserves the cloud model and sanitizes the cloud service', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      model: 'cloud',
+      keepAudioOnDevice: 'yes',
+      cloudTranscription: { provider: 'whisper-api', endpoint: ' https://api.groq.com/openai/v1/audio/transcriptions ', model: 'x'.repeat(101) },
+    }));
+    const settings = loadSettings();
+    expect(settings.model).toBe('cloud');
+    expect(settings.keepAudioOnDevice).toBe(false);
+    expect(settings.cloudTranscription).toEqual({ provider: 'openai', endpoint: 'https://api.groq.com/openai/v1/audio/transcriptions', model: '' });
+
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, cloudTranscription: 'openai' }));
+    expect(loadSettings().cloudTranscription).toEqual(DEFAULT_SETTINGS.cloudTranscription);
+  });
+
   it('preserves valid recordingMode values', () => {
     for (const mode of ['hold_down', 'double_tap'] as const) {
       localStorage.setItem('dictation-settings', JSON.stringify({
@@ -492,10 +692,491 @@ describe('loadSettings', () => {
       ...DEFAULT_SETTINGS,
       correctionEnabled: 'yes',
       correctionFuzzy: 1,
+      learnFromCorrections: 'sometimes',
     }));
     const settings = loadSettings();
     expect(settings.correctionEnabled).toBe(DEFAULT_SETTINGS.correctionEnabled);
     expect(settings.correctionFuzzy).toBe(DEFAULT_SETTINGS.correctionFuzzy);
+    expect(settings.learnFromCorrections).toBe(DEFAULT_SETTINGS.learnFromCorrections);
+  });
+
+  it('keeps low-confidence flags on unless explicitly turned off', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      flagLowConfidence: 'no',
+    }));
+    expect(loadSettings().flagLowConfidence).toBe(true);
+  });
+
+  it('coerces non-boolean privacy toggles to defaults', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      privacyMode: 'on',
+      persistHistory: 0,
+      encryptHistory: null,
+    }));
+    const settings = loadSettings();
+    expect(settings.privacyMode).toBe(false);
+    expect(settings.persistHistory).toBe(true);
+    expect(settings.encryptHistory).toBe(false);
+  });
+
+  it('coerces unknown notification policies to the default', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      notificationPolicy: 'always',
+    }));
+    expect(loadSettings().notificationPolicy).toBe('off');
+  });
+
+  it('coerces unknown update channels to stable', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      updateChannel: 'nightly',
+    }));
+    expect(loadSettings().updateChannel).toBe('stable');
+  });
+
+  it('trims download mirrors and drops malformed fields', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      downloadNetwork: { huggingFaceMirror: ' https://hf-mirror.com ', githubMirror: 42, proxy: null },
+    }));
+    expect(loadSettings().downloadNetwork).toEqual({ huggingFaceMirror: 'https://hf-mirror.com', githubMirror: '', proxy: '', pauseOnMetered: false, pauseOnVpn: false });
+  });
+
+  it('snaps storage retention limits to the offered options', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      storageRetention: { enabled: 'yes', maxTotalMb: 123, maxAgeDays: 90 },
+    }));
+    expect(loadSettings().storageRetention).toEqual({ enabled: true, maxTotalMb: 500, maxAgeDays: 90, includeRecordings: false });
+  });
+
+  it('coerces unknown interrupted-recording actions to discard', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      interruptedRecordingAction: 'keep',
+    }));
+    expect(loadSettings().interruptedRecordingAction).toBe('discard');
+  });
+
+  it('coerces unknown overlay display choices to the built-in display', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      overlayDisplay: 'primary',
+    }));
+    expect(loadSettings().overlayDisplay).toBe('built-in');
+  });
+
+  it('keeps only distinct device names in the microphone preference list', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      microphonePreferences: ['AirPods Pro', '', 7, 'AirPods Pro', 'USB Mic'],
+    }));
+    expect(loadSettings().microphonePreferences).toEqual(['AirPods Pro', 'USB Mic']);
+
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      microphonePreferences: 'AirPods Pro',
+    }));
+    expect(loadSettings().microphonePreferences).toEqual([]);
+  });
+
+  it('turns pre-roll off for anything but a listed length', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      preRollSeconds: 5,
+    }));
+    expect(loadSettings().preRollSeconds).toBe(0);
+  });
+
+  it('coerces unknown low-quality microphone actions to a warning', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      lowQualityMicAction: 'ignore',
+    }));
+    expect(loadSettings().lowQualityMicAction).toBe('warn');
+  });
+
+  it('coerces unknown fullscreen overlay choices to hiding it', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      overlayFullscreen: 'minimize',
+    }));
+    expect(loadSettings().overlayFullscreen).toBe('hide');
+  });
+
+  it('coerces overlay style and clamps or drops malformed pill positions', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      overlayStyle: 'floating',
+      overlayPillPosition: { x: 1.4, y: -2 },
+    }));
+    let settings = loadSettings();
+    expect(settings.overlayStyle).toBe('auto');
+    expect(settings.overlayPillPosition).toEqual({ x: 1, y: 0 });
+
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      overlayPillPosition: { x: 'left', y: 0.5 },
+    }));
+    settings = loadSettings();
+    expect(settings.overlayPillPosition).toBeNull();
+  });
+
+  it('clamps persisted whisper decoding options field by field', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      whisperDecoding: { sampling: 'nucleus', beamSize: 40, temperature: -1, noSpeechThreshold: 'high', threads: 2.7, singleSegment: 'always' },
+    }));
+    expect(loadSettings().whisperDecoding).toEqual({
+      sampling: 'greedy',
+      beamSize: 8,
+      temperature: 0,
+      noSpeechThreshold: DEFAULT_SETTINGS.whisperDecoding.noSpeechThreshold,
+      threads: 2,
+      singleSegment: 'always',
+    });
+  });
+
+  it('drops malformed redaction entries and coerces unknown modes', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      redaction: { enabled: 'yes', mode: 'shred', includeProfanity: false, words: ['secret', '  ', 7], patterns: 'nope' },
+    }));
+    expect(loadSettings().redaction).toEqual({
+      enabled: false,
+      mode: 'mask',
+      includeProfanity: false,
+      words: ['secret'],
+      patterns: [],
+    });
+  });
+
+  it('coerces malformed focus filter fields and drops blank modes', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      focusFilter: { suppressNotifications: 'no', disableHotkey: true, modes: ['com.apple.focus.work', ' ', 3] },
+    }));
+    expect(loadSettings().focusFilter).toEqual({
+      suppressNotifications: true,
+      disableHotkey: true,
+      modes: ['com.apple.focus.work'],
+    });
+  });
+
+  it('falls back per field for malformed quiet hours', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      quietHours: { enabled: 'yes', start: '7:00', end: '21:15' },
+    }));
+    expect(loadSettings().quietHours).toEqual({ enabled: false, start: '22:00', end: '21:15' });
+  });
+
+  it('drops app triggers without a bundle id and repeats', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      appTriggers: [
+        { bundleId: ' us.zoom.xos ', name: '', showOverlay: true, promptAmbient: 'yes' },
+        { bundleId: 'US.ZOOM.XOS', name: 'Zoom', showOverlay: false, promptAmbient: true },
+        { bundleId: ' ', name: 'Blank' },
+        null,
+      ],
+    }));
+    expect(loadSettings().appTriggers).toEqual([
+      { bundleId: 'us.zoom.xos', name: 'us.zoom.xos', showOverlay: true, promptAmbient: false },
+    ]);
+  });
+
+  it('keeps context biasing off and drops allowlisted apps without a bundle id', () => {
+    expect(loadSettings().contextBiasing).toEqual({ enabled: false, windowTitle: true, selectedText: false, apps: [] });
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      contextBiasing: {
+        enabled: true,
+        windowTitle: 'yes',
+        apps: [
+          { bundleId: ' com.apple.mail ', name: 'Mail' },
+          { bundleId: 'COM.APPLE.MAIL', name: 'Mail again' },
+          { name: 'No id' },
+          'com.tinyspeck.slackmacgap',
+        ],
+      },
+    }));
+    expect(loadSettings().contextBiasing).toEqual({
+      enabled: true,
+      windowTitle: true,
+      selectedText: false,
+      apps: [{ bundleId: 'com.apple.mail', name: 'Mail' }],
+    });
+  });
+
+  it('sanitizes an imported settings object like stored settings', () => {
+    expect(sanitizeSettings(null)).toEqual(DEFAULT_SETTINGS);
+    expect(sanitizeSettings([1, 2])).toEqual(DEFAULT_SETTINGS);
+    const imported = sanitizeSettings({ language: 'fr', dataFolder: 7, quietHours: { enabled: true } });
+    expect(imported.language).toBe('fr');
+    expect(imported.dataFolder).toBe('');
+    expect(imported.quietHours).toEqual({ ...DEFAULT_SETTINGS.quietHours, enabled: true });
+  });
+
+  it('coerces malformed adaptive model settings', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      adaptiveModel: { enabled: 'yes', fallbackModel: 'large-v3-turbo' },
+    }));
+    expect(loadSettings().adaptiveModel).toEqual(DEFAULT_SETTINGS.adaptiveModel);
+  });
+
+  it('keeps only offered latency budgets', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      latencyBudget: { enabled: true, maxRtf: 0.7, autoSwitch: 'on' },
+    }));
+    expect(loadSettings().latencyBudget).toEqual({ enabled: true, maxRtf: 1, autoSwitch: false });
+  });
+
+  it('coerces malformed power profile fields per source', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      powerProfiles: {
+        enabled: true,
+        ac: { model: 'huge.en', vadSensitivity: 140, idleTimeoutMinutes: 7 },
+        battery: 'nope',
+      },
+    }));
+    expect(loadSettings().powerProfiles).toEqual({
+      enabled: true,
+      ac: { model: DEFAULT_SETTINGS.powerProfiles.ac.model, vadSensitivity: 100, idleTimeoutMinutes: DEFAULT_SETTINGS.powerProfiles.ac.idleTimeoutMinutes },
+      battery: DEFAULT_SETTINGS.powerProfiles.battery,
+    });
+  });
+
+  it('coerces malformed dual-model settings', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      dualModel: { enabled: true, draftModel: 'moonshine-tiny', replaceDraft: 'yes' },
+    }));
+    expect(loadSettings().dualModel).toEqual({ ...DEFAULT_SETTINGS.dualModel, enabled: true });
+  });
+
+  it('clamps confidence retry bounds and drops non-Whisper retry models', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      confidenceRetry: { enabled: true, threshold: 3, model: 'parakeet-tdt-0.6b-v2-fp16', budgetMs: 'soon' },
+    }));
+    expect(loadSettings().confidenceRetry).toEqual({
+      ...DEFAULT_SETTINGS.confidenceRetry,
+      enabled: true,
+      threshold: 0.95,
+    });
+  });
+
+  it('clamps the alternatives count', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      alternatives: { enabled: 'on', count: 9 },
+    }));
+    expect(loadSettings().alternatives).toEqual({ enabled: false, count: 4 });
+  });
+
+  it('drops dictation profiles without an id or a free key and nulls bad overrides', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      dictationProfiles: [
+        { id: 'english', name: 'English', hotkey: 'ctrl_r', modelOverride: 'moonshine-tiny', languageOverride: 'xx', autoPasteOverride: 'yes' },
+        { id: 'clash', name: 'Clash', hotkey: 'ctrl_r' },
+        { id: '', name: 'No id', hotkey: 'alt_l' },
+        { id: 'unknown-key', hotkey: 'fn' },
+        'not a profile',
+      ],
+    }));
+    expect(loadSettings().dictationProfiles).toEqual([{
+      id: 'english',
+      name: 'English',
+      hotkey: 'ctrl_r',
+      modelOverride: null,
+      languageOverride: null,
+      autoPasteOverride: null,
+      cleanupOverride: null,
+      smartFormattingOverride: null,
+      rewriteTemplate: null,
+    }]);
+  });
+
+  it('drops invalid rewrite templates and profile references to missing ones', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      rewriteTemplates: [
+        { id: 'email', name: ' Email ', instruction: 'Be polite.' },
+        { id: 'email', name: 'Other', instruction: 'Duplicate id.' },
+        { id: 'mail', name: 'EMAIL', instruction: 'Duplicate name.' },
+        { id: 'blank', name: 'Blank', instruction: '   ' },
+        { id: 'long', name: 'x'.repeat(61), instruction: 'Too long a name.' },
+        'not a template',
+      ],
+      dictationProfiles: [
+        { id: 'work', name: 'Work', hotkey: 'ctrl_r', rewriteTemplate: 'email' },
+        { id: 'notes', name: 'Notes', hotkey: 'alt_l', rewriteTemplate: 'blank' },
+      ],
+    }));
+    const settings = loadSettings();
+    expect(settings.rewriteTemplates).toEqual([{ id: 'email', name: 'Email', instruction: 'Be polite.' }]);
+    expect(settings.dictationProfiles.map((profile) => profile.rewriteTemplate)).toEqual(['email', null]);
+  });
+
+  it('drops invalid replacement rules and fills missing flags', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      replacementRules: [
+        { id: 'dash', find: ' slash dash ', replace: '--' },
+        { id: 'ticket', find: 'ticket (\\d+) ', replace: '#$1', regex: true, matchCase: 'yes', enabled: false },
+        { id: 'dash', find: 'duplicate id', replace: 'x' },
+        { id: 'blank', find: '   ', replace: 'x' },
+        { id: 'long', find: 'x'.repeat(201), replace: 'x' },
+        'not a rule',
+      ],
+    }));
+    expect(loadSettings().replacementRules).toEqual([
+      { id: 'dash', find: 'slash dash', replace: '--', regex: false, matchCase: false, enabled: true },
+      { id: 'ticket', find: 'ticket (\\d+) ', replace: '#$1', regex: true, matchCase: false, enabled: false },
+    ]);
+  });
+
+  it('drops invalid dictation macros and keeps the confirmation default', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      dictationMacros: {
+        enabled: true,
+        macros: [
+          { id: 'history', phrase: ' Open history ', action: { type: 'open_history' } },
+          { id: 'again', phrase: 'open, HISTORY', action: { type: 'open_settings' } },
+          { id: 'history', phrase: 'show history', action: { type: 'open_history' } },
+          { id: 'model', phrase: 'use a bad model', action: { type: 'set_model', model: 'not-a-model' } },
+          { id: 'reboot', phrase: 'reboot', action: { type: 'reboot' } },
+          { id: 'paste', phrase: 'auto-paste off', action: { type: 'set_auto_paste', enabled: false } },
+          { id: 'blank', phrase: ' ... ', action: { type: 'open_history' } },
+          { id: 'coding', phrase: 'switch to coding', action: { type: 'apply_preset', preset: ' coding ' } },
+          { id: 'no-preset', phrase: 'switch preset', action: { type: 'apply_preset', preset: '' } },
+        ],
+      },
+    }));
+    expect(loadSettings().dictationMacros).toEqual({
+      enabled: true,
+      confirm: true,
+      macros: [
+        { id: 'history', phrase: 'Open history', action: { type: 'open_history' } },
+        { id: 'paste', phrase: 'auto-paste off', action: { type: 'set_auto_paste', enabled: false } },
+        { id: 'coding', phrase: 'switch to coding', action: { type: 'apply_preset', preset: 'coding' } },
+      ],
+    });
+  });
+
+  it('drops malformed config presets and keeps only preset settings', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      configPresets: [
+        { id: 'meetings', name: ' Meetings ', settings: { language: 'xx', autoPaste: false, microphone: 'USB Mic' }, updatedAt: 5 },
+        { id: 'meetings', name: 'Meetings again', settings: {} },
+        { id: 'other', name: 'MEETINGS', settings: {} },
+        { id: '', name: 'No id', settings: {} },
+        { id: 'spanish', name: 'Spanish email', settings: 'es' },
+        null,
+      ],
+    }));
+    expect(loadSettings().configPresets).toEqual([
+      { id: 'meetings', name: 'Meetings', settings: { language: 'auto', autoPaste: false }, updatedAt: 5 },
+      { id: 'spanish', name: 'Spanish email', settings: {}, updatedAt: 0 },
+    ]);
+  });
+
+  it('keys macro phrases the way the backend matches them', () => {
+    expect(macroPhraseKey('  Disable auto-paste! ')).toBe('disable auto paste');
+    expect(macroPhraseKey('...')).toBe('');
+  });
+
+  it('coerces an unlisted engine keep-alive budget to the default', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, backendKeepAliveMb: 3000 }));
+    expect(loadSettings().backendKeepAliveMb).toBe(DEFAULT_SETTINGS.backendKeepAliveMb);
+  });
+
+  it('clamps the typing speed baseline and drops non-numbers', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, typingWpm: 512.4 }));
+    expect(loadSettings().typingWpm).toBe(200);
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, typingWpm: 'fast' }));
+    expect(loadSettings().typingWpm).toBe(DEFAULT_SETTINGS.typingWpm);
+  });
+
+  it('disarms an unrecognised append commit key', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, appendMode: 'yes', appendCommitKey: 'f13' }));
+    const settings = loadSettings();
+    expect(settings.appendMode).toBe(false);
+    expect(settings.appendCommitKey).toBeNull();
+  });
+
+  it('unbinds an unrecognised paste-last key', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, pasteLastKey: 'f13' }));
+    expect(loadSettings().pasteLastKey).toBeNull();
+  });
+
+  it('unbinds an unrecognised copied-audio key', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, clipboardAudioKey: 'f13' }));
+    expect(loadSettings().clipboardAudioKey).toBeNull();
+  });
+
+  it('unbinds an unrecognised marker key and keeps spoken markers off unless enabled', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, markerKey: 'f13', spokenMarkers: 'yes' }));
+    const settings = loadSettings();
+    expect(settings.markerKey).toBeNull();
+    expect(settings.spokenMarkers).toBe(false);
+  });
+
+  it('falls back to one-minute meeting chunks for an unknown length', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, meetingChunkSeconds: 45 }));
+    expect(loadSettings().meetingChunkSeconds).toBe(60);
+  });
+
+  it('turns legacy automation off unless it was explicitly enabled', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, legacyAutomation: 'on' }));
+    expect(loadSettings().legacyAutomation).toBe(false);
+  });
+
+  it('turns preview mode off unless it was explicitly enabled', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, previewBeforePaste: 1 }));
+    expect(loadSettings().previewBeforePaste).toBe(false);
+  });
+
+  it('turns cursor context off unless it was explicitly enabled', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, cursorContext: 'yes' }));
+    expect(loadSettings().cursorContext).toBe(false);
+  });
+
+  it('appends nothing for an unknown trailing behavior', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, trailingBehavior: 'tab' }));
+    expect(loadSettings().trailingBehavior).toBe('none');
+  });
+
+  it('keeps known per-app trailing overrides and drops the rest', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({
+      ...DEFAULT_SETTINGS,
+      appProfiles: [
+        { bundleId: 'com.apple.mail', label: 'Mail', trailingOverride: 'smart' },
+        { bundleId: 'com.apple.Terminal', label: 'Terminal', trailingOverride: 'tab' },
+        { bundleId: 'com.apple.Notes', label: 'Notes' },
+      ],
+    }));
+    expect(loadSettings().appProfiles.map((profile) => profile.trailingOverride)).toEqual(['smart', null, null]);
+  });
+
+  it('falls back to pasting for an unknown notes target', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, notesTarget: 'sometimes' }));
+    expect(loadSettings().notesTarget).toBe('off');
+  });
+
+  it('leaves selected text alone for an unknown selection dictation mode', () => {
+    localStorage.setItem('dictation-settings', JSON.stringify({ ...DEFAULT_SETTINGS, selectionDictation: 'append' }));
+    expect(loadSettings().selectionDictation).toBe('off');
   });
 
   it('preserves explicit correction settings', () => {
@@ -1,7 +1,200 @@
 export type RecordingMode = 'hold_down' | 'double_tap' | 'both';
 
+export type NotificationPolicy = 'off' | 'errors_only' | 'all';
+export type InterruptedRecordingAction = 'discard' | 'transcribe';
+export type NotesTarget = 'off' | 'always' | 'no_text_field';
+export type SelectionDictation = 'off' | 'replace' | 'rewrite';
+export type TrailingBehavior = 'none' | 'space' | 'newline' | 'smart';
+export type UpdateChannel = 'stable' | 'beta';
+
+export type OverlayDisplay = 'built-in' | 'active' | 'all';
+
+export type OverlayStyle = 'auto' | 'notch' | 'pill';
+
+export type OverlayFullscreen = 'show' | 'hide' | 'pill';
+
+/** What a recording does when its microphone is a Bluetooth headset on the low-quality hands-free profile. */
+export type LowQualityMicAction = 'warn' | 'use_built_in';
+
+/** A dragged floating pill's place, as 0–1 fractions of the room its display
+ * leaves around it. */
+export interface OverlayPillPosition {
+  x: number;
+  y: number;
+}
+
+/** Advanced Whisper decoding knobs. Ignored by the Parakeet backends. */
+export interface WhisperDecodingSettings {
+  sampling: 'greedy' | 'beam_search';
+  /** Beams for beam search (1–8). Unused by greedy decoding. */
+  beamSize: number;
+  /** Initial sampling temperature (0–1). 0 is deterministic. */
+  temperature: number;
+  /** Drop segments whose no-speech probability exceeds this (0–1). */
+  noSpeechThreshold: number;
+  /** Decode threads; 0 uses one per performance core. */
+  threads: number;
+  /** `auto` uses single-segment mode for clips up to 12 seconds. */
+  singleSegment: 'auto' | 'always' | 'never';
+}
+
+/** Words and patterns masked (or removed) before paste and history. */
+export interface RedactionSettings {
+  enabled: boolean;
+  mode: 'mask' | 'remove';
+  /** Include the built-in profanity list alongside `words`. */
+  includeProfanity: boolean;
+  /** Whole words or phrases, matched case-insensitively. */
+  words: string[];
+  /** Regular expressions (Rust `regex` syntax), e.g. card numbers. */
+  patterns: string[];
+}
+
+/** What changes while a macOS Focus mode (e.g. Do Not Disturb) is on. */
+export interface FocusFilterSettings {
+  /** Hold dictation notifications while a matching Focus is on. */
+  suppressNotifications: boolean;
+  /** Ignore the dictation hotkey while a matching Focus is on. */
+  disableHotkey: boolean;
+  /** Focus mode identifiers this applies to; empty means every Focus. */
+  modes: string[];
+}
+
+/** A daily window when the hotkeys and the wake word are ignored. */
+export interface QuietHoursSettings {
+  enabled: boolean;
+  /** Local time, `HH:MM`. A start after the end spans midnight. */
+  start: string;
+  end: string;
+}
+
+/** Mirrors and proxy for model downloads. Empty strings use the defaults. */
+export interface DownloadNetworkSettings {
+  /** Replaces `https://huggingface.co` (Whisper, VAD, and transform models). */
+  huggingFaceMirror: string;
+  /** Replaces `https://github.com` (Parakeet bundles). */
+  githubMirror: string;
+  /** Empty uses the system proxy, `none` connects directly, otherwise an
+   * `http`, `https`, `socks5`, or `socks5h` proxy URL. */
+  proxy: string;
+  /** Hold background downloads while the connection looks metered. */
+  pauseOnMetered: boolean;
+  /** Hold background downloads while a VPN carries the default route. */
+  pauseOnVpn: boolean;
+}
+
+export type CloudProvider = 'openai' | 'deepgram';
+
+export const CLOUD_PROVIDER_OPTIONS: { value: CloudProvider; label: string; endpoint: string; model: string }[] = [
+  { value: 'openai', label: 'OpenAI-compatible', endpoint: 'https://api.openai.com/v1/audio/transcriptions', model: 'whisper-1' },
+  { value: 'deepgram', label: 'Deepgram', endpoint: 'https://api.deepgram.com/v1/listen', model: 'nova-3' },
+];
+
+/** The service the `cloud` model sends recordings to. The API key is kept in
+ * the Keychain, not here. Empty strings use the provider's defaults. */
+export interface CloudTranscriptionSettings {
+  provider: CloudProvider;
+  /** Full request URL, e.g. another host's `/v1/audio/transcriptions`. */
+  endpoint: string;
+  model: string;
+}
+
+/** Mirrors `cloud::MODEL_MAX` in Rust. */
+export const CLOUD_MODEL_MAX = 100;
+
+/** Daily cleanup of rotated logs and, optionally, saved recordings. */
+export interface StorageRetentionSettings {
+  enabled: boolean;
+  /** Size budget for everything cleanup manages. */
+  maxTotalMb: number;
+  /** Files older than this are removed; 0 keeps any age. */
+  maxAgeDays: number;
+  /** Also remove old `murmur-*.wav` files from the output folder. */
+  includeRecordings: boolean;
+}
+
+/** Per-recording fallback from Whisper Large Turbo while the Mac is hot or in
+ * Low Power Mode. The selected model itself never changes. */
+export interface AdaptiveModelSettings {
+  enabled: boolean;
+  /** Used instead of Whisper Large Turbo under pressure, when installed. */
+  fallbackModel: ModelOption;
+}
+
+/** How long a live decode may take, as a multiple of the speech's length.
+ * A model that keeps missing it gets a faster model suggested, or switched
+ * in when `autoSwitch` is on. */
+export interface LatencyBudgetSettings {
+  enabled: boolean;
+  /** Realtime factor: 1.5 means results within 1.5× the recording's length. */
+  maxRtf: number;
+  /** Switch the selected model instead of only suggesting one. */
+  autoSwitch: boolean;
+}
+
+/** Defaults applied automatically on one power source. */
+export interface PowerProfile {
+  model: ModelOption;
+  vadSensitivity: number;
+  idleTimeoutMinutes: number;
+}
+
+/** Per-power-source defaults. When enabled, switching between the adapter and
+ * battery applies the matching profile through the normal settings path. */
+export interface PowerProfilesSettings {
+  enabled: boolean;
+  ac: PowerProfile;
+  battery: PowerProfile;
+}
+
+/** Paste a fast draft, then re-transcribe the same audio with the selected
+ * model in the background. */
+export interface DualModelSettings {
+  enabled: boolean;
+  /** Model for the immediate draft; skipped when not installed. */
+  draftModel: ModelOption;
+  /** Swap the pasted draft for the refined text when they differ. */
+  replaceDraft: boolean;
+}
+
+/** Decode an unsure Whisper transcript again before it is pasted, keeping
+ * whichever pass Whisper was surer of. Bounded by `CONFIDENCE_RETRY_BOUNDS`. */
+export interface ConfidenceRetrySettings {
+  enabled: boolean;
+  /** Retry transcripts whose confidence score is below this. */
+  threshold: number;
+  /** Whisper model for the retry; null, or a model that isn't installed,
+   * re-runs the same model with beam search. */
+  model: ModelOption | null;
+  /** Longest the retry may add to a dictation, model load included. */
+  budgetMs: number;
+}
+
+/** Extra Whisper hypotheses decoded after a transcript is delivered, offered
+ * in history as alternative readings of the words they differ on. */
+export interface AlternativesSettings {
+  enabled: boolean;
+  /** Hypotheses to decode, 1 to `ALTERNATIVES_MAX`. */
+  count: number;
+}
+
 export type DoubleTapKey = 'shift_l' | 'alt_l' | 'ctrl_r';
 
+/** Hotkey thresholds in milliseconds, validated by the Rust listener against
+ * `HOTKEY_TIMING_BOUNDS`. */
+export interface HotkeyTiming {
+  /** Longest press that still counts as a tap. */
+  maxTapMs: number;
+  /** Longest gap between the two taps of a double-tap. */
+  doubleTapWindowMs: number;
+  /** Quiet period after a double-tap fires. */
+  cooldownMs: number;
+  /** Quiet period after a hold-down recording stops. */
+  holdCooldownMs: number;
+  /** Both mode: how long a press lasts before it becomes a hold. Never shorter than `maxTapMs`. */
+  holdPromotionMs: number;
+}
+
 /**
  * Independent hotkey for the AX-selection transform shortcut (issue #312).
  * Deliberately a distinct id set from `DoubleTapKey` (same `<modifier>_<side>`
@@ -11,6 +204,178 @@ export type DoubleTapKey = 'shift_l' | 'alt_l' | 'ctrl_r';
  */
 export type TransformKey = 'alt_r' | 'ctrl_l' | 'shift_r';
 
+/** Any hotkey id the shared listener understands; dictation profiles may use
+ * whichever the dictation and transform hotkeys leave free. */
+export type ProfileKey = DoubleTapKey | TransformKey;
+
+/**
+ * A named dictation profile bound to its own hold-down key. Holding `hotkey`
+ * records with these overrides on top of the global settings and any matching
+ * app profile. `null` means "no override".
+ */
+export interface DictationProfile {
+  id: string;
+  name: string;
+  hotkey: ProfileKey;
+  modelOverride: ModelOption | null;
+  /** A `LANGUAGE_OPTIONS` value, including 'auto'. */
+  languageOverride: string | null;
+  autoPasteOverride: boolean | null;
+  cleanupOverride: boolean | null;
+  smartFormattingOverride: boolean | null;
+  /** `RewriteTemplate.id` applied to every take, or `null` for none. */
+  rewriteTemplate: string | null;
+}
+
+/**
+ * A named instruction for the local transform model. A take starting with
+ * "format as <name>" is rewritten with it, as is every take from a dictation
+ * profile that selects it.
+ */
+export interface RewriteTemplate {
+  id: string;
+  name: string;
+  instruction: string;
+}
+
+export const REWRITE_TEMPLATE_NAME_MAX = 60;
+export const REWRITE_TEMPLATE_INSTRUCTION_MAX = 2000;
+
+export const DEFAULT_REWRITE_TEMPLATES: RewriteTemplate[] = [
+  {
+    id: 'email',
+    name: 'Email',
+    instruction: 'Rewrite the text as a clear, polite email. Keep every fact and request. Return only the email body.',
+  },
+  {
+    id: 'bullet-summary',
+    name: 'Bullet summary',
+    instruction: 'Summarize the text as short bullet points, one idea per bullet. Return only the bullets.',
+  },
+  {
+    id: 'code-comment',
+    name: 'Code comment',
+    instruction: 'Rewrite the text as a concise code comment in plain technical language. Return only the comment text, without comment markers.',
+  },
+];
+
+/**
+ * A find/replace rule applied to every live transcript, in list order. A
+ * literal `find` matches whole words; a regex `find` may use `$1` groups in
+ * `replace`. Both ignore case unless `matchCase` is set.
+ */
+export interface ReplacementRule {
+  id: string;
+  find: string;
+  replace: string;
+  regex: boolean;
+  matchCase: boolean;
+  enabled: boolean;
+}
+
+export const REPLACEMENT_RULES_MAX = 100;
+export const REPLACEMENT_RULE_FIND_MAX = 200;
+export const REPLACEMENT_RULE_REPLACE_MAX = 1000;
+
+/** What a dictation macro does. Mirrors `dictation_macros::MacroAction`. */
+export type MacroAction =
+  | { type: 'open_history' }
+  | { type: 'open_settings' }
+  | { type: 'copy_last_transcription' }
+  | { type: 'set_auto_paste'; enabled: boolean }
+  | { type: 'set_model'; model: ModelOption }
+  | { type: 'apply_preset'; preset: string };
+
+/** A spoken phrase that runs `action` instead of being pasted. */
+export interface DictationMacro {
+  id: string;
+  phrase: string;
+  action: MacroAction;
+}
+
+export interface DictationMacrosSettings {
+  enabled: boolean;
+  /** Ask before running a matched macro. */
+  confirm: boolean;
+  macros: DictationMacro[];
+}
+
+/** Mirrors `dictation_macros::MAX_MACROS` in Rust. */
+export const DICTATION_MACROS_MAX = 50;
+export const DICTATION_MACRO_PHRASE_MAX = 60;
+
+export const DEFAULT_DICTATION_MACROS: DictationMacro[] = [
+  { id: 'open-history', phrase: 'open history', action: { type: 'open_history' } },
+  { id: 'open-settings', phrase: 'open settings', action: { type: 'open_settings' } },
+  { id: 'copy-last', phrase: 'copy last transcription', action: { type: 'copy_last_transcription' } },
+  { id: 'auto-paste-off', phrase: 'disable auto paste', action: { type: 'set_auto_paste', enabled: false } },
+  { id: 'auto-paste-on', phrase: 'enable auto paste', action: { type: 'set_auto_paste', enabled: true } },
+  { id: 'large-model', phrase: 'switch to large model', action: { type: 'set_model', model: 'large-v3-turbo' } },
+];
+
+/**
+ * A named snapshot of how dictation behaves ("Meetings", "Coding"), switched
+ * from Settings, the tray, `apply_config_preset`, or a dictation macro.
+ */
+export interface ConfigPreset {
+  id: string;
+  name: string;
+  /** Values for the keys in `PRESET_SETTINGS`; a key it lacks is left alone when applied. */
+  settings: Partial<Settings>;
+  /** Bumped on each save, so a save from a stale copy is refused. */
+  updatedAt: number;
+}
+
+/** Mirrors `config_presets::MAX_PRESETS` in Rust. */
+export const CONFIG_PRESETS_MAX = 20;
+export const CONFIG_PRESET_NAME_MAX = 40;
+
+/**
+ * What a preset captures: the model and language, post-processing, and how
+ * text is delivered. Hotkeys, devices, privacy, and storage are left as they are.
+ */
+export const PRESET_SETTINGS = [
+  'model',
+  'language',
+  'vadSensitivity',
+  'whisperDecoding',
+  'vocabularyEntries',
+  'smartPunctuation',
+  'cleanupEnabled',
+  'cleanupRemoveFiller',
+  'cleanupCapitalize',
+  'smartFormattingEnabled',
+  'correctionEnabled',
+  'correctionFuzzy',
+  'hallucinationFilter',
+  'replacementRules',
+  'voiceCommandsEnabled',
+  'voiceCommands',
+  'redaction',
+  'autoPaste',
+  'autoPasteDelayMs',
+  'previewBeforePaste',
+  'appendMode',
+  'cursorContext',
+  'trailingBehavior',
+  'notesTarget',
+  'selectionDictation',
+  'legacyAutomation',
+] as const satisfies ReadonlyArray<keyof Settings>;
+
+/**
+ * How the backend compares macro phrases: lowercase, punctuation around words
+ * dropped, and hyphens read as spaces.
+ */
+export function macroPhraseKey(phrase: string): string {
+  return phrase
+    .replace(/-/g, ' ')
+    .split(/\s+/)
+    .map((word) => word.replace(/^[^\p{L}\p{N}]+|[^\p{L}\p{N}]+$/gu, '').toLowerCase())
+    .filter(Boolean)
+    .join(' ');
+}
+
 export type WritingStyle =
   | 'conversational'
   | 'polished'
@@ -41,6 +406,8 @@ export interface AppProfile {
   cleanupOverride: boolean | null;
   smartFormattingOverride: boolean | null;
   cliFormattingOverride: boolean | null;
+  /** What follows each paste in this app. `null` uses `trailingBehavior`. */
+  trailingOverride: TrailingBehavior | null;
   /** Explicit deterministic writing policy. `null` preserves current behavior. */
   writingStyle: WritingStyle | null;
   /** Explicit opt-in to a memory-only local project index for this profile. */
@@ -51,6 +418,38 @@ export interface AppProfile {
 
 const MAX_IDE_PROJECT_ROOT_BYTES = 4096;
 
+/** What happens when an app comes to the front. Nothing records on its own. */
+export interface AppTrigger {
+  bundleId: string;
+  name: string;
+  /** Keep the overlay showing over this app, even fullscreen. */
+  showOverlay: boolean;
+  /** Offer ambient transcription from the overlay when the app comes forward. */
+  promptAmbient: boolean;
+}
+
+/** An app context biasing may read from. */
+export interface ContextBiasingApp {
+  bundleId: string;
+  name: string;
+}
+
+/**
+ * Names from the focused app fed into Whisper's prompt. Nothing is read
+ * unless it is on and the frontmost app is in `apps`.
+ */
+export interface ContextBiasingSettings {
+  enabled: boolean;
+  /** Read the focused window's title. */
+  windowTitle: boolean;
+  /** Read the selected text too. */
+  selectedText: boolean;
+  apps: ContextBiasingApp[];
+}
+
+/** Mirrors `context_biasing::MAX_APPS` in Rust. */
+export const CONTEXT_BIASING_APPS_MAX = 50;
+
 /**
  * A user-defined voice command. When `phrase` is spoken it is replaced by
  * `replacement` (case-insensitive, word-boundary). Applied after the built-in
@@ -192,6 +591,9 @@ const MAX_RANKED_TERMS = 500;
 /** Hard ceiling on the persisted sample-chip list (backend sends ~12). */
 const MAX_SAMPLE_TERMS = 50;
 
+/** Longest input device preference list kept. */
+const MAX_MICROPHONE_PREFERENCES = 8;
+
 export interface Settings {
   model: ModelOption;
   doubleTapKey: DoubleTapKey;
@@ -203,10 +605,30 @@ export interface Settings {
   autoPasteDelayMs: number;
   recordingMode: RecordingMode;
   hotkeyMissFeedback: boolean;
+  hotkeyTiming: HotkeyTiming;
+  /** Start a recording on "Hey Murmur", alongside the hotkeys. Needs the wake word model. */
+  wakeWord: boolean;
+  /** Transcribe everything the microphone hears into the ambient log. Never pasted. */
+  ambientMode: boolean;
+  /** Seconds of audio kept in memory and prepended to each recording; 0 = off. Keeps the microphone open. */
+  preRollSeconds: number;
   microphone: string;
+  /** Input devices to record from, in order, when the selected microphone is missing or disconnects. */
+  microphonePreferences: string[];
+  lowQualityMicAction: LowQualityMicAction;
   launchAtLogin: boolean;
+  /** Which displays show the notch overlay when more than one is attached. */
+  overlayDisplay: OverlayDisplay;
+  /** Notch island, floating pill, or the notch island only where a notch exists. */
+  overlayStyle: OverlayStyle;
+  /** Where the floating pill was dragged. `null` = centered below the menu bar. */
+  overlayPillPosition: OverlayPillPosition | null;
+  /** What the overlay does over a fullscreen app, where the menu bar is hidden. */
+  overlayFullscreen: OverlayFullscreen;
   vadSensitivity: number;
   idleTimeoutMinutes: number;
+  /** Memory budget (MB) for keeping a switched-away engine loaded. 0 = off. */
+  backendKeepAliveMb: number;
   /** @deprecated Migration-only mirror; structured entries are authoritative. */
   customVocabulary: string;
   vocabularyEntries: VocabularyEntry[];
@@ -222,7 +644,22 @@ export interface Settings {
   /** Write each benchmark report to `benchmarkOutputDir` automatically as it
    * completes, so reports survive the 10-slot localStorage cap. */
   benchmarkAutoSave: boolean;
+  /** Sync folder (iCloud Drive, Syncthing, Dropbox) that holds the copies of
+   * settings and history shared with other Macs. Empty = off. Belongs to this
+   * Mac, so it is never synced itself. */
+  dataFolder: string;
+  /** Named presets of the settings in `PRESET_SETTINGS`. */
+  configPresets: ConfigPreset[];
   appProfiles: AppProfile[];
+  appTriggers: AppTrigger[];
+  /** Hold-to-talk profiles, each on its own key (e.g. English and Spanish). */
+  dictationProfiles: DictationProfile[];
+  /** Rewrite templates for the local transform model. */
+  rewriteTemplates: RewriteTemplate[];
+  /** Ordered find/replace rules for live transcripts. */
+  replacementRules: ReplacementRule[];
+  /** Spoken phrases that run an app action instead of being pasted. */
+  dictationMacros: DictationMacrosSettings;
   voiceCommandsEnabled: boolean;
   /** User-defined voice commands applied after the built-in set. */
   voiceCommands: VoiceCommand[];
@@ -254,6 +691,87 @@ export interface Settings {
   correctionEnabled: boolean;
   /** Tier 2 phonetic "sounds-like" matching. Gated under correctionEnabled. */
   correctionFuzzy: boolean;
+  /** Bias Whisper toward phrases you keep correcting history entries to. */
+  learnFromCorrections: boolean;
+  /** Bias Whisper toward names in allowlisted apps' window titles and selections. */
+  contextBiasing: ContextBiasingSettings;
+  /** System notifications while the main window is hidden: none, failures
+   * only, or failures plus completed transcriptions. */
+  notificationPolicy: NotificationPolicy;
+  focusFilter: FocusFilterSettings;
+  quietHours: QuietHoursSettings;
+  /** What happens to a recording cut short by screen lock, sleep, or a user
+   * switch. */
+  interruptedRecordingAction: InterruptedRecordingAction;
+  whisperDecoding: WhisperDecodingSettings;
+  /** Skip near-silent recordings and drop output that is only a known
+   * hallucination ("Thank you for watching"). */
+  hallucinationFilter: boolean;
+  /** Mark history entries Whisper was unsure of, so they get a second look. */
+  flagLowConfidence: boolean;
+  redaction: RedactionSettings;
+  adaptiveModel: AdaptiveModelSettings;
+  latencyBudget: LatencyBudgetSettings;
+  powerProfiles: PowerProfilesSettings;
+  dualModel: DualModelSettings;
+  confidenceRetry: ConfidenceRetrySettings;
+  alternatives: AlternativesSettings;
+  /** Keep free-form pipeline strings out of the logs, even in dev builds. */
+  privacyMode: boolean;
+  /** Refuse cloud transcription and transcription plugins, so recordings
+   * never leave this Mac. */
+  keepAudioOnDevice: boolean;
+  cloudTranscription: CloudTranscriptionSettings;
+  /** Save dictation history across launches. When off, history lives only in
+   * memory for the current session. */
+  persistHistory: boolean;
+  /** Keep persisted history encrypted on disk with a Keychain key instead of
+   * plaintext in localStorage. */
+  encryptHistory: boolean;
+  /** Release feed the updater checks: stable releases or beta builds. */
+  updateChannel: UpdateChannel;
+  downloadNetwork: DownloadNetworkSettings;
+  storageRetention: StorageRetentionSettings;
+  /** Typing speed the usage dashboard measures "time saved" against. */
+  typingWpm: number;
+  /** Hold each finished take in a buffer instead of pasting it, until the
+   * buffer is committed. */
+  appendMode: boolean;
+  /** Key that commits the append buffer. `null` = commit from the overlay or
+   * main window only. */
+  appendCommitKey: ProfileKey | null;
+  /** Paste through System Events and open System Settings with `open`
+   * instead of the native macOS APIs, for setups where those misbehave. */
+  legacyAutomation: boolean;
+  /** Show each dictation in the overlay with Accept and Discard before it
+   * reaches the clipboard. Unanswered previews are discarded after 30s. */
+  previewBeforePaste: boolean;
+  /** Fit each paste to the text before the caret: add or drop the leading
+   * space and capitalize after a sentence end. macOS only. */
+  cursorContext: boolean;
+  /** What follows each paste: nothing, a space, a newline, or a space unless
+   * the text after the caret starts with whitespace or punctuation. */
+  trailingBehavior: TrailingBehavior;
+  /** Save dictations to today's Murmur note instead of pasting them: never,
+   * always, or only when no text field has focus. */
+  notesTarget: NotesTarget;
+  /** What a dictation does to text selected when it started: nothing, replace
+   * it, or rewrite it with the dictation as the instruction. macOS only. */
+  selectionDictation: SelectionDictation;
+  /** Key that pastes the newest history entry again. `null` = tray menu
+   * only. */
+  pasteLastKey: ProfileKey | null;
+  /** Key that transcribes the copied or Finder-selected audio file and pastes
+   * the text. `null` = tray menu only. */
+  clipboardAudioKey: ProfileKey | null;
+  /** Key that marks a section break in the current recording. `null` = no
+   * key. */
+  markerKey: ProfileKey | null;
+  /** Saying "marker" as a sentence of its own also marks a section break. */
+  spokenMarkers: boolean;
+  /** How often a meeting is sliced and transcribed into today's note, in
+   * seconds. Each slice ends at the next pause. */
+  meetingChunkSeconds: number;
 }
 
 export type ModelOption =
@@ -263,8 +781,46 @@ export type ModelOption =
   | 'small.en'
   | 'medium.en'
   | 'large-v3-turbo'
+  | 'distil-medium.en'
+  | 'distil-large-v3'
+  | 'tiny.en-q5_1'
+  | 'tiny.en-q8_0'
+  | 'base.en-q5_1'
+  | 'base.en-q8_0'
+  | 'small.en-q5_1'
+  | 'small.en-q8_0'
+  | 'medium.en-q5_0'
+  | 'medium.en-q8_0'
+  | 'large-v3-turbo-q5_0'
+  | 'large-v3-turbo-q8_0'
   // --- Parakeet backend (removable): delete this member to remove. ---
-  | 'parakeet-tdt-0.6b-v2-fp16';
+  | 'parakeet-tdt-0.6b-v2-fp16'
+  | CustomModelOption
+  | ExternalModelOption
+  | CloudModelOption;
+
+/** A downloaded GGML file outside the catalog, named `custom:<file>.bin`. */
+export type CustomModelOption = `custom:${string}`;
+
+/** Mirrors Rust's `custom_model_file`: a plain `.bin` file name, no paths. */
+export function isCustomModel(value: unknown): value is CustomModelOption {
+  return typeof value === 'string' && /^custom:(?!\.)[A-Za-z0-9._-]{1,124}\.bin$/.test(value);
+}
+
+/** A registered transcription plugin, named `external:<id>`. */
+export type ExternalModelOption = `external:${string}`;
+
+/** Mirrors Rust's `external_plugin_id`: lowercase letters, digits, and dashes. */
+export function isExternalModel(value: unknown): value is ExternalModelOption {
+  return typeof value === 'string' && /^external:[a-z0-9][a-z0-9-]{0,63}$/.test(value);
+}
+
+/** Transcription by the service in `cloudTranscription`. */
+export type CloudModelOption = 'cloud';
+
+export function isCloudModel(value: unknown): value is CloudModelOption {
+  return value === 'cloud';
+}
 
 export type TranscriptionBackend = 'whisper' | 'parakeet' | 'coreml';
 
@@ -275,6 +831,18 @@ export const MODEL_OPTIONS: { value: ModelOption; label: string; size: string; b
   { value: 'small.en', label: 'Whisper Small (English)', size: '~500 MB', backend: 'whisper' },
   { value: 'medium.en', label: 'Whisper Medium (English)', size: '~1.5 GB', backend: 'whisper' },
   { value: 'large-v3-turbo', label: 'Whisper Large Turbo', size: '~3 GB', backend: 'whisper' },
+  { value: 'distil-medium.en', label: 'Distil-Whisper Medium (English)', size: '~800 MB', backend: 'whisper' },
+  { value: 'distil-large-v3', label: 'Distil-Whisper Large v3 (English)', size: '~1.5 GB', backend: 'whisper' },
+  { value: 'tiny.en-q5_1', label: 'Whisper Tiny (English, Q5)', size: '~31 MB', backend: 'whisper' },
+  { value: 'tiny.en-q8_0', label: 'Whisper Tiny (English, Q8)', size: '~42 MB', backend: 'whisper' },
+  { value: 'base.en-q5_1', label: 'Whisper Base (English, Q5)', size: '~57 MB', backend: 'whisper' },
+  { value: 'base.en-q8_0', label: 'Whisper Base (English, Q8)', size: '~78 MB', backend: 'whisper' },
+  { value: 'small.en-q5_1', label: 'Whisper Small (English, Q5)', size: '~181 MB', backend: 'whisper' },
+  { value: 'small.en-q8_0', label: 'Whisper Small (English, Q8)', size: '~252 MB', backend: 'whisper' },
+  { value: 'medium.en-q5_0', label: 'Whisper Medium (English, Q5)', size: '~514 MB', backend: 'whisper' },
+  { value: 'medium.en-q8_0', label: 'Whisper Medium (English, Q8)', size: '~785 MB', backend: 'whisper' },
+  { value: 'large-v3-turbo-q5_0', label: 'Whisper Large Turbo (Q5)', size: '~547 MB', backend: 'whisper' },
+  { value: 'large-v3-turbo-q8_0', label: 'Whisper Large Turbo (Q8)', size: '~834 MB', backend: 'whisper' },
   // --- Parakeet backend (removable): delete this entry to remove. ---
   { value: 'parakeet-tdt-0.6b-v2-fp16', label: 'Parakeet TDT 0.6B (English, fast)', size: '~1.2 GB', backend: 'parakeet' },
 ];
@@ -313,18 +881,126 @@ export const TRANSFORM_KEY_OPTIONS: { value: TransformKey; label: string }[] = [
   { value: 'shift_r', label: 'Right Shift' },
 ];
 
+export const PROFILE_KEY_OPTIONS: { value: ProfileKey; label: string }[] = [
+  { value: 'shift_l', label: 'Left Shift' },
+  { value: 'alt_l', label: 'Left Option' },
+  { value: 'ctrl_r', label: 'Right Control' },
+  ...TRANSFORM_KEY_OPTIONS,
+];
+
 export const RECORDING_MODE_OPTIONS: { value: RecordingMode; label: string }[] = [
   { value: 'hold_down', label: 'Hold Down' },
   { value: 'double_tap', label: 'Double-Tap' },
   { value: 'both', label: 'Both' },
 ];
 
+export const NOTIFICATION_POLICY_OPTIONS: { value: NotificationPolicy; label: string }[] = [
+  { value: 'off', label: 'Off' },
+  { value: 'errors_only', label: 'Errors Only' },
+  { value: 'all', label: 'All' },
+];
+
+export const INTERRUPTED_RECORDING_OPTIONS: { value: InterruptedRecordingAction; label: string }[] = [
+  { value: 'discard', label: 'Discard' },
+  { value: 'transcribe', label: 'Transcribe' },
+];
+
+export const NOTES_TARGET_OPTIONS: { value: NotesTarget; label: string }[] = [
+  { value: 'off', label: 'Off' },
+  { value: 'no_text_field', label: 'No Text Field' },
+  { value: 'always', label: 'Always' },
+];
+
+export const TRAILING_BEHAVIOR_OPTIONS: { value: TrailingBehavior; label: string }[] = [
+  { value: 'none', label: 'Nothing' },
+  { value: 'space', label: 'Space' },
+  { value: 'newline', label: 'New line' },
+  { value: 'smart', label: 'Smart space' },
+];
+
+export const SELECTION_DICTATION_OPTIONS: { value: SelectionDictation; label: string }[] = [
+  { value: 'off', label: 'Off' },
+  { value: 'replace', label: 'Replace' },
+  { value: 'rewrite', label: 'Rewrite' },
+];
+
+export const UPDATE_CHANNEL_OPTIONS: { value: UpdateChannel; label: string }[] = [
+  { value: 'stable', label: 'Stable' },
+  { value: 'beta', label: 'Beta' },
+];
+
+export const OVERLAY_DISPLAY_OPTIONS: { value: OverlayDisplay; label: string }[] = [
+  { value: 'built-in', label: 'Built-in' },
+  { value: 'active', label: 'Active' },
+  { value: 'all', label: 'All Displays' },
+];
+
+export const OVERLAY_STYLE_OPTIONS: { value: OverlayStyle; label: string }[] = [
+  { value: 'auto', label: 'Automatic' },
+  { value: 'notch', label: 'Notch' },
+  { value: 'pill', label: 'Floating Pill' },
+];
+
+export const OVERLAY_FULLSCREEN_OPTIONS: { value: OverlayFullscreen; label: string }[] = [
+  { value: 'hide', label: 'Hide' },
+  { value: 'pill', label: 'Floating Pill' },
+  { value: 'show', label: 'Keep Showing' },
+];
+
+export const PRE_ROLL_OPTIONS: { value: number; label: string }[] = [
+  { value: 0, label: 'Off' },
+  { value: 1, label: '1 second' },
+  { value: 2, label: '2 seconds' },
+];
+
+export const MEETING_CHUNK_OPTIONS: { value: number; label: string }[] = [
+  { value: 30, label: 'Every 30 seconds' },
+  { value: 60, label: 'Every minute' },
+  { value: 120, label: 'Every 2 minutes' },
+  { value: 300, label: 'Every 5 minutes' },
+];
+
+export const LOW_QUALITY_MIC_OPTIONS: { value: LowQualityMicAction; label: string }[] = [
+  { value: 'warn', label: 'Warn Me' },
+  { value: 'use_built_in', label: 'Use Built-in Mic' },
+];
+
+export const RETENTION_SIZE_OPTIONS: { value: number; label: string }[] = [
+  { value: 100, label: '100 MB' },
+  { value: 250, label: '250 MB' },
+  { value: 500, label: '500 MB' },
+  { value: 1000, label: '1 GB' },
+  { value: 5000, label: '5 GB' },
+];
+
+export const RETENTION_AGE_OPTIONS: { value: number; label: string }[] = [
+  { value: 7, label: '1 week' },
+  { value: 30, label: '30 days' },
+  { value: 90, label: '90 days' },
+  { value: 0, label: 'Any age' },
+];
+
+export const LATENCY_BUDGET_OPTIONS: { value: number; label: string }[] = [
+  { value: 0.25, label: 'Within 0.25× the recording' },
+  { value: 0.5, label: 'Within 0.5× the recording' },
+  { value: 1, label: 'Within the recording’s length' },
+  { value: 1.5, label: 'Within 1.5× the recording' },
+  { value: 2, label: 'Within 2× the recording' },
+];
+
 export const IDLE_TIMEOUT_OPTIONS: { value: number; label: string }[] = [
   { value: 5, label: '5 minutes' },
   { value: 15, label: '15 minutes' },
   { value: 0, label: 'Never' },
 ];
 
+export const KEEP_ALIVE_OPTIONS: { value: number; label: string }[] = [
+  { value: 0, label: 'Off' },
+  { value: 1024, label: '1 GB' },
+  { value: 2048, label: '2 GB' },
+  { value: 4096, label: '4 GB' },
+];
+
 export const LANGUAGE_OPTIONS: { value: string; label: string }[] = [
   { value: 'auto', label: 'Auto Detect' },
   { value: 'en', label: 'English' },
@@ -358,10 +1034,27 @@ export const DEFAULT_SETTINGS: Settings = {
   autoPasteDelayMs: 50,
   recordingMode: 'hold_down',
   hotkeyMissFeedback: false,
+  hotkeyTiming: {
+    maxTapMs: 200,
+    doubleTapWindowMs: 400,
+    cooldownMs: 50,
+    holdCooldownMs: 50,
+    holdPromotionMs: 200,
+  },
+  wakeWord: false,
+  ambientMode: false,
+  preRollSeconds: 0,
   microphone: 'system_default',
+  microphonePreferences: [],
+  lowQualityMicAction: 'warn',
   launchAtLogin: false,
+  overlayDisplay: 'built-in',
+  overlayStyle: 'auto',
+  overlayPillPosition: null,
+  overlayFullscreen: 'hide',
   vadSensitivity: 50,
   idleTimeoutMinutes: 5,
+  backendKeepAliveMb: 2048,
   customVocabulary: '',
   vocabularyEntries: [],
   disabled: false,
@@ -371,7 +1064,14 @@ export const DEFAULT_SETTINGS: Settings = {
   outputDir: '',
   benchmarkOutputDir: '',
   benchmarkAutoSave: false,
+  dataFolder: '',
+  configPresets: [],
   appProfiles: [],
+  appTriggers: [],
+  dictationProfiles: [],
+  rewriteTemplates: DEFAULT_REWRITE_TEMPLATES,
+  replacementRules: [],
+  dictationMacros: { enabled: false, confirm: true, macros: DEFAULT_DICTATION_MACROS },
   voiceCommandsEnabled: false,
   voiceCommands: [],
   cleanupEnabled: false,
@@ -385,10 +1085,551 @@ export const DEFAULT_SETTINGS: Settings = {
   // non-Whisper engines. A no-op when there's no vocabulary configured.
   correctionEnabled: true,
   correctionFuzzy: true,
+  learnFromCorrections: false,
+  contextBiasing: { enabled: false, windowTitle: true, selectedText: false, apps: [] },
+  notificationPolicy: 'off',
+  focusFilter: {
+    suppressNotifications: true,
+    disableHotkey: false,
+    modes: [],
+  },
+  quietHours: {
+    enabled: false,
+    start: '22:00',
+    end: '08:00',
+  },
+  interruptedRecordingAction: 'discard',
+  whisperDecoding: {
+    sampling: 'greedy',
+    beamSize: 5,
+    temperature: 0,
+    noSpeechThreshold: 0.6,
+    threads: 0,
+    singleSegment: 'auto',
+  },
+  hallucinationFilter: true,
+  flagLowConfidence: true,
+  redaction: {
+    enabled: false,
+    mode: 'mask',
+    includeProfanity: true,
+    words: [],
+    patterns: [],
+  },
+  adaptiveModel: {
+    enabled: false,
+    fallbackModel: 'base.en',
+  },
+  latencyBudget: {
+    enabled: false,
+    maxRtf: 1,
+    autoSwitch: false,
+  },
+  powerProfiles: {
+    enabled: false,
+    ac: { model: defaultModelForPlatform(runtimePlatform), vadSensitivity: 50, idleTimeoutMinutes: 15 },
+    battery: { model: defaultModelForPlatform(runtimePlatform), vadSensitivity: 50, idleTimeoutMinutes: 5 },
+  },
+  dualModel: {
+    enabled: false,
+    draftModel: 'tiny.en',
+    replaceDraft: false,
+  },
+  confidenceRetry: {
+    enabled: false,
+    threshold: 0.4,
+    model: null,
+    budgetMs: 3000,
+  },
+  alternatives: {
+    enabled: false,
+    count: 3,
+  },
+  privacyMode: false,
+  keepAudioOnDevice: false,
+  cloudTranscription: { provider: 'openai', endpoint: '', model: '' },
+  persistHistory: true,
+  encryptHistory: false,
+  updateChannel: 'stable',
+  downloadNetwork: { huggingFaceMirror: '', githubMirror: '', proxy: '', pauseOnMetered: false, pauseOnVpn: false },
+  storageRetention: { enabled: true, maxTotalMb: 500, maxAgeDays: 30, includeRecordings: false },
+  typingWpm: 40,
+  appendMode: false,
+  appendCommitKey: null,
+  legacyAutomation: false,
+  previewBeforePaste: false,
+  cursorContext: false,
+  trailingBehavior: 'none',
+  notesTarget: 'off',
+  selectionDictation: 'off',
+  pasteLastKey: null,
+  clipboardAudioKey: null,
+  markerKey: null,
+  spokenMarkers: false,
+  meetingChunkSeconds: 60,
+};
+
+export const TYPING_WPM_MIN = 10;
+export const TYPING_WPM_MAX = 200;
+
+/** Mirrors the clamps in Rust's `parse_confidence_retry`. */
+export const CONFIDENCE_RETRY_BOUNDS = {
+  threshold: { min: 0.05, max: 0.95 },
+  budgetMs: { min: 500, max: 10000 },
+} as const;
+
+/** Mirrors `alternatives::MAX_ALTERNATIVES` in Rust. */
+export const ALTERNATIVES_MAX = 4;
+
+/** Inclusive bounds matching `HotkeyTiming::validate` in keyboard.rs. */
+
+export const HOTKEY_TIMING_BOUNDS: Record<keyof HotkeyTiming, { min: number; max: number }> = {
+  maxTapMs: { min: 100, max: 600 },
+  doubleTapWindowMs: { min: 150, max: 1000 },
+  cooldownMs: { min: 0, max: 500 },
+  holdCooldownMs: { min: 0, max: 500 },
+  holdPromotionMs: { min: 100, max: 1000 },
 };
 
 export const STORAGE_KEY = 'dictation-settings';
 
+/**
+ * Coerce persisted Whisper decoding options field by field, clamping numbers to
+ * the ranges the backend accepts. Unknown or missing values fall back to the
+ * shipped decode rather than discarding the whole object.
+ */
+function sanitizeWhisperDecoding(raw: unknown): WhisperDecodingSettings {
+  const defaults = DEFAULT_SETTINGS.whisperDecoding;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const inRange = (v: unknown, min: number, max: number, fallback: number) =>
+    typeof v === 'number' && Number.isFinite(v) ? Math.min(max, Math.max(min, v)) : fallback;
+  return {
+    sampling: r.sampling === 'beam_search' ? 'beam_search' : 'greedy',
+    beamSize: Math.trunc(inRange(r.beamSize, 1, 8, defaults.beamSize)),
+    temperature: inRange(r.temperature, 0, 1, defaults.temperature),
+    noSpeechThreshold: inRange(r.noSpeechThreshold, 0, 1, defaults.noSpeechThreshold),
+    threads: Math.trunc(inRange(r.threads, 0, 16, defaults.threads)),
+    singleSegment: r.singleSegment === 'always' || r.singleSegment === 'never' ? r.singleSegment : 'auto',
+  };
+}
+
+/**
+ * Coerce persisted redaction settings field by field. List entries that are not
+ * non-blank strings are dropped; patterns are validated by the backend.
+ */
+function sanitizeRedaction(raw: unknown): RedactionSettings {
+  const defaults = DEFAULT_SETTINGS.redaction;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const strings = (v: unknown) =>
+    Array.isArray(v) ? v.filter((item): item is string => typeof item === 'string' && item.trim() !== '') : [];
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    mode: r.mode === 'remove' ? 'remove' : 'mask',
+    includeProfanity: typeof r.includeProfanity === 'boolean' ? r.includeProfanity : defaults.includeProfanity,
+    words: strings(r.words),
+    patterns: strings(r.patterns),
+  };
+}
+
+function sanitizeFocusFilter(raw: unknown): FocusFilterSettings {
+  const defaults = DEFAULT_SETTINGS.focusFilter;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  return {
+    suppressNotifications: typeof r.suppressNotifications === 'boolean' ? r.suppressNotifications : defaults.suppressNotifications,
+    disableHotkey: typeof r.disableHotkey === 'boolean' ? r.disableHotkey : defaults.disableHotkey,
+    modes: Array.isArray(r.modes) ? r.modes.filter((mode): mode is string => typeof mode === 'string' && mode.trim() !== '') : [],
+  };
+}
+
+function sanitizeAppTriggers(raw: unknown): AppTrigger[] {
+  if (!Array.isArray(raw)) return DEFAULT_SETTINGS.appTriggers;
+  const seen = new Set<string>();
+  return raw.flatMap((item): AppTrigger[] => {
+    if (!item || typeof item !== 'object') return [];
+    const r = item as Record<string, unknown>;
+    const bundleId = typeof r.bundleId === 'string' ? r.bundleId.trim() : '';
+    if (!bundleId || seen.has(bundleId.toLowerCase())) return [];
+    seen.add(bundleId.toLowerCase());
+    return [{
+      bundleId,
+      name: typeof r.name === 'string' && r.name.trim() ? r.name.trim() : bundleId,
+      showOverlay: r.showOverlay === true,
+      promptAmbient: r.promptAmbient === true,
+    }];
+  });
+}
+
+/** Apps without a bundle id or already listed (ignoring case) are dropped. */
+function sanitizeContextBiasing(raw: unknown): ContextBiasingSettings {
+  const defaults = DEFAULT_SETTINGS.contextBiasing;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const seen = new Set<string>();
+  const apps = Array.isArray(r.apps)
+    ? r.apps.flatMap((item): ContextBiasingApp[] => {
+      if (!item || typeof item !== 'object') return [];
+      const a = item as Record<string, unknown>;
+      const bundleId = typeof a.bundleId === 'string' ? a.bundleId.trim() : '';
+      if (!bundleId || seen.has(bundleId.toLowerCase()) || seen.size === CONTEXT_BIASING_APPS_MAX) return [];
+      seen.add(bundleId.toLowerCase());
+      return [{ bundleId, name: typeof a.name === 'string' && a.name.trim() ? a.name.trim() : bundleId }];
+    })
+    : defaults.apps;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    windowTitle: typeof r.windowTitle === 'boolean' ? r.windowTitle : defaults.windowTitle,
+    selectedText: typeof r.selectedText === 'boolean' ? r.selectedText : defaults.selectedText,
+    apps,
+  };
+}
+
+/**
+ * Drop presets without an id or name, or whose id or name (ignoring case) an
+ * earlier preset already claimed. Each preset's values are sanitized like
+ * stored settings, keeping only the keys it has from `PRESET_SETTINGS`.
+ */
+function sanitizeConfigPresets(raw: unknown): ConfigPreset[] {
+  if (!Array.isArray(raw)) return DEFAULT_SETTINGS.configPresets;
+  const ids = new Set<string>();
+  const names = new Set<string>();
+  return raw.flatMap((item): ConfigPreset[] => {
+    if (!item || typeof item !== 'object') return [];
+    const r = item as Record<string, unknown>;
+    const id = typeof r.id === 'string' ? r.id.trim() : '';
+    const name = typeof r.name === 'string' ? r.name.trim().slice(0, CONFIG_PRESET_NAME_MAX) : '';
+    if (!id || !name || ids.has(id) || names.has(name.toLowerCase()) || ids.size === CONFIG_PRESETS_MAX) return [];
+    ids.add(id);
+    names.add(name.toLowerCase());
+    const stored = r.settings && typeof r.settings === 'object' ? r.settings as Record<string, unknown> : {};
+    const keys = PRESET_SETTINGS.filter((key) => key in stored);
+    const clean = sanitizeSettings({ ...DEFAULT_SETTINGS, ...Object.fromEntries(keys.map((key) => [key, stored[key]])) });
+    return [{
+      id,
+      name,
+      settings: Object.fromEntries(keys.map((key) => [key, clean[key]])) as Partial<Settings>,
+      updatedAt: typeof r.updatedAt === 'number' && Number.isFinite(r.updatedAt) ? r.updatedAt : 0,
+    }];
+  });
+}
+
+const CLOCK_TIME = /^([01]\d|2[0-3]):[0-5]\d$/;
+
+function sanitizeQuietHours(raw: unknown): QuietHoursSettings {
+  const defaults = DEFAULT_SETTINGS.quietHours;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const clock = (value: unknown, fallback: string) => typeof value === 'string' && CLOCK_TIME.test(value) ? value : fallback;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    start: clock(r.start, defaults.start),
+    end: clock(r.end, defaults.end),
+  };
+}
+
+function sanitizeDownloadNetwork(raw: unknown): DownloadNetworkSettings {
+  const defaults = DEFAULT_SETTINGS.downloadNetwork;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const text = (value: unknown, fallback: string) => typeof value === 'string' ? value.trim() : fallback;
+  return {
+    huggingFaceMirror: text(r.huggingFaceMirror, defaults.huggingFaceMirror),
+    githubMirror: text(r.githubMirror, defaults.githubMirror),
+    proxy: text(r.proxy, defaults.proxy),
+    pauseOnMetered: typeof r.pauseOnMetered === 'boolean' ? r.pauseOnMetered : defaults.pauseOnMetered,
+    pauseOnVpn: typeof r.pauseOnVpn === 'boolean' ? r.pauseOnVpn : defaults.pauseOnVpn,
+  };
+}
+
+/** Keeps an endpoint as typed; Rust rejects one that isn't a valid URL. */
+function sanitizeCloudTranscription(raw: unknown): CloudTranscriptionSettings {
+  const defaults = DEFAULT_SETTINGS.cloudTranscription;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  return {
+    provider: CLOUD_PROVIDER_OPTIONS.some((option) => option.value === r.provider) ? r.provider as CloudProvider : defaults.provider,
+    endpoint: typeof r.endpoint === 'string' ? r.endpoint.trim() : defaults.endpoint,
+    model: typeof r.model === 'string' && r.model.trim().length <= CLOUD_MODEL_MAX ? r.model.trim() : defaults.model,
+  };
+}
+
+function sanitizeStorageRetention(raw: unknown): StorageRetentionSettings {
+  const defaults = DEFAULT_SETTINGS.storageRetention;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const option = (options: { value: number }[], value: unknown, fallback: number) =>
+    options.some((o) => o.value === value) ? value as number : fallback;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    maxTotalMb: option(RETENTION_SIZE_OPTIONS, r.maxTotalMb, defaults.maxTotalMb),
+    maxAgeDays: option(RETENTION_AGE_OPTIONS, r.maxAgeDays, defaults.maxAgeDays),
+    includeRecordings: typeof r.includeRecordings === 'boolean' ? r.includeRecordings : defaults.includeRecordings,
+  };
+}
+
+function sanitizeAdaptiveModel(raw: unknown): AdaptiveModelSettings {
+  const defaults = DEFAULT_SETTINGS.adaptiveModel;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    fallbackModel: AVAILABLE_MODEL_OPTIONS.some((model) => model.value === r.fallbackModel && model.value !== 'large-v3-turbo')
+      ? r.fallbackModel as ModelOption
+      : defaults.fallbackModel,
+  };
+}
+
+function sanitizeLatencyBudget(raw: unknown): LatencyBudgetSettings {
+  const defaults = DEFAULT_SETTINGS.latencyBudget;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    maxRtf: LATENCY_BUDGET_OPTIONS.some((option) => option.value === r.maxRtf) ? r.maxRtf as number : defaults.maxRtf,
+    autoSwitch: typeof r.autoSwitch === 'boolean' ? r.autoSwitch : defaults.autoSwitch,
+  };
+}
+
+function sanitizeDualModel(raw: unknown): DualModelSettings {
+  const defaults = DEFAULT_SETTINGS.dualModel;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    draftModel: AVAILABLE_MODEL_OPTIONS.some((model) => model.value === r.draftModel)
+      ? r.draftModel as ModelOption
+      : defaults.draftModel,
+    replaceDraft: typeof r.replaceDraft === 'boolean' ? r.replaceDraft : defaults.replaceDraft,
+  };
+}
+
+function sanitizeConfidenceRetry(raw: unknown): ConfidenceRetrySettings {
+  const defaults = DEFAULT_SETTINGS.confidenceRetry;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const clamp = (key: keyof typeof CONFIDENCE_RETRY_BOUNDS) => {
+    const value = r[key];
+    if (typeof value !== 'number' || !Number.isFinite(value)) return defaults[key];
+    const { min, max } = CONFIDENCE_RETRY_BOUNDS[key];
+    return Math.min(max, Math.max(min, value));
+  };
+  // Only Whisper reports confidence, so only a Whisper retry can be compared.
+  const whisperModel = isCustomModel(r.model)
+    || AVAILABLE_MODEL_OPTIONS.some((model) => model.value === r.model && model.backend === 'whisper');
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    threshold: clamp('threshold'),
+    model: whisperModel ? r.model as ModelOption : null,
+    budgetMs: Math.round(clamp('budgetMs')),
+  };
+}
+
+function sanitizeAlternatives(raw: unknown): AlternativesSettings {
+  const defaults = DEFAULT_SETTINGS.alternatives;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    count: typeof r.count === 'number' && Number.isFinite(r.count)
+      ? Math.min(ALTERNATIVES_MAX, Math.max(1, Math.round(r.count)))
+      : defaults.count,
+  };
+}
+
+/**
+ * Drop profiles without an id or a known key, and any profile whose key an
+ * earlier profile already claimed. Overrides that aren't valid fall back to
+ * "no override".
+ */
+function sanitizeDictationProfiles(raw: unknown, templates: RewriteTemplate[]): DictationProfile[] {
+  if (!Array.isArray(raw)) return DEFAULT_SETTINGS.dictationProfiles;
+  const claimed = new Set<string>();
+  const nullableBoolean = (v: unknown) => typeof v === 'boolean' ? v : null;
+  return raw.flatMap((item): DictationProfile[] => {
+    if (!item || typeof item !== 'object') return [];
+    const p = item as Record<string, unknown>;
+    if (typeof p.id !== 'string' || p.id.trim() === '') return [];
+    const hotkey = PROFILE_KEY_OPTIONS.find((option) => option.value === p.hotkey)?.value;
+    if (!hotkey || claimed.has(hotkey)) return [];
+    claimed.add(hotkey);
+    return [{
+      id: p.id,
+      name: typeof p.name === 'string' ? p.name : '',
+      hotkey,
+      modelOverride: AVAILABLE_MODEL_OPTIONS.some((model) => model.value === p.modelOverride)
+        ? p.modelOverride as ModelOption
+        : null,
+      languageOverride: LANGUAGE_OPTIONS.some((option) => option.value === p.languageOverride)
+        ? p.languageOverride as string
+        : null,
+      autoPasteOverride: nullableBoolean(p.autoPasteOverride),
+      cleanupOverride: nullableBoolean(p.cleanupOverride),
+      smartFormattingOverride: nullableBoolean(p.smartFormattingOverride),
+      rewriteTemplate: templates.some((template) => template.id === p.rewriteTemplate)
+        ? p.rewriteTemplate as string
+        : null,
+    }];
+  });
+}
+
+/**
+ * Drop templates without an id, name, or instruction, or over a length limit,
+ * and any template whose id or name an earlier one already claimed.
+ */
+function sanitizeRewriteTemplates(raw: unknown): RewriteTemplate[] {
+  if (!Array.isArray(raw)) return DEFAULT_SETTINGS.rewriteTemplates;
+  const claimed = new Set<string>();
+  return raw.flatMap((item): RewriteTemplate[] => {
+    if (!item || typeof item !== 'object') return [];
+    const t = item as Record<string, unknown>;
+    if (typeof t.id !== 'string' || typeof t.name !== 'string' || typeof t.instruction !== 'string') return [];
+    const id = t.id.trim();
+    const name = t.name.trim();
+    const instruction = t.instruction.trim();
+    if (!id || !name || !instruction) return [];
+    if (name.length > REWRITE_TEMPLATE_NAME_MAX || instruction.length > REWRITE_TEMPLATE_INSTRUCTION_MAX) return [];
+    const nameKey = `name:${name.toLowerCase()}`;
+    if (claimed.has(`id:${id}`) || claimed.has(nameKey)) return [];
+    claimed.add(`id:${id}`);
+    claimed.add(nameKey);
+    return [{ id, name, instruction }];
+  });
+}
+
+/**
+ * Drop rules without an id or find text, or over a length limit, and any rule
+ * whose id an earlier one already claimed. Whether a regex compiles is checked
+ * by the backend, which skips one that doesn't.
+ */
+function sanitizeReplacementRules(raw: unknown): ReplacementRule[] {
+  if (!Array.isArray(raw)) return DEFAULT_SETTINGS.replacementRules;
+  const claimed = new Set<string>();
+  return raw.flatMap((item): ReplacementRule[] => {
+    if (!item || typeof item !== 'object') return [];
+    const r = item as Record<string, unknown>;
+    if (typeof r.id !== 'string' || typeof r.find !== 'string') return [];
+    const id = r.id.trim();
+    const regex = r.regex === true;
+    const find = regex ? r.find : r.find.trim();
+    const replace = typeof r.replace === 'string' ? r.replace : '';
+    if (!id || !find.trim() || claimed.has(id)) return [];
+    if (find.length > REPLACEMENT_RULE_FIND_MAX || replace.length > REPLACEMENT_RULE_REPLACE_MAX) return [];
+    if (claimed.size === REPLACEMENT_RULES_MAX) return [];
+    claimed.add(id);
+    return [{ id, find, replace, regex, matchCase: r.matchCase === true, enabled: r.enabled !== false }];
+  });
+}
+
+function sanitizeMacroAction(raw: unknown): MacroAction | null {
+  if (!raw || typeof raw !== 'object') return null;
+  const r = raw as Record<string, unknown>;
+  switch (r.type) {
+    case 'open_history':
+    case 'open_settings':
+    case 'copy_last_transcription':
+      return { type: r.type };
+    case 'set_auto_paste':
+      return typeof r.enabled === 'boolean' ? { type: 'set_auto_paste', enabled: r.enabled } : null;
+    case 'set_model':
+      return isCustomModel(r.model) || isExternalModel(r.model) || isCloudModel(r.model) || AVAILABLE_MODEL_OPTIONS.some((model) => model.value === r.model)
+        ? { type: 'set_model', model: r.model as ModelOption }
+        : null;
+    case 'apply_preset':
+      return typeof r.preset === 'string' && r.preset.trim() ? { type: 'apply_preset', preset: r.preset.trim() } : null;
+    default:
+      return null;
+  }
+}
+
+/**
+ * Drop macros without an id or phrase, with an unknown action or a model this
+ * platform lacks, or whose id or phrase an earlier macro already claimed. A
+ * missing list restores the defaults; an empty one stays empty.
+ */
+function sanitizeDictationMacros(raw: unknown): DictationMacrosSettings {
+  const defaults = DEFAULT_SETTINGS.dictationMacros;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const ids = new Set<string>();
+  const phrases = new Set<string>();
+  const macros = Array.isArray(r.macros)
+    ? r.macros.flatMap((item): DictationMacro[] => {
+      if (!item || typeof item !== 'object') return [];
+      const m = item as Record<string, unknown>;
+      if (typeof m.id !== 'string' || typeof m.phrase !== 'string') return [];
+      const id = m.id.trim();
+      const phrase = m.phrase.trim();
+      const key = macroPhraseKey(phrase);
+      const action = sanitizeMacroAction(m.action);
+      if (!id || !key || !action || ids.has(id) || phrases.has(key)) return [];
+      if (phrase.length > DICTATION_MACRO_PHRASE_MAX || ids.size === DICTATION_MACROS_MAX) return [];
+      ids.add(id);
+      phrases.add(key);
+      return [{ id, phrase, action }];
+    })
+    : defaults.macros;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    confirm: typeof r.confirm === 'boolean' ? r.confirm : defaults.confirm,
+    macros,
+  };
+}
+
+function sanitizePowerProfile(raw: unknown, defaults: PowerProfile): PowerProfile {
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  return {
+    model: AVAILABLE_MODEL_OPTIONS.some((model) => model.value === r.model) ? r.model as ModelOption : defaults.model,
+    vadSensitivity: typeof r.vadSensitivity === 'number' && Number.isFinite(r.vadSensitivity)
+      ? Math.min(100, Math.max(0, Math.round(r.vadSensitivity)))
+      : defaults.vadSensitivity,
+    idleTimeoutMinutes: IDLE_TIMEOUT_OPTIONS.some((option) => option.value === r.idleTimeoutMinutes)
+      ? r.idleTimeoutMinutes as number
+      : defaults.idleTimeoutMinutes,
+  };
+}
+
+function sanitizePowerProfiles(raw: unknown): PowerProfilesSettings {
+  const defaults = DEFAULT_SETTINGS.powerProfiles;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  return {
+    enabled: typeof r.enabled === 'boolean' ? r.enabled : defaults.enabled,
+    ac: sanitizePowerProfile(r.ac, defaults.ac),
+    battery: sanitizePowerProfile(r.battery, defaults.battery),
+  };
+}
+
+/** Clamp each threshold into bounds and keep the promotion delay at least one tap long. */
+export function sanitizeHotkeyTiming(raw: unknown): HotkeyTiming {
+  const defaults = DEFAULT_SETTINGS.hotkeyTiming;
+  if (!raw || typeof raw !== 'object') return defaults;
+  const r = raw as Record<string, unknown>;
+  const clamp = (key: keyof HotkeyTiming) => {
+    const value = r[key];
+    if (typeof value !== 'number' || !Number.isFinite(value)) return defaults[key];
+    const { min, max } = HOTKEY_TIMING_BOUNDS[key];
+    return Math.round(Math.min(max, Math.max(min, value)));
+  };
+  const maxTapMs = clamp('maxTapMs');
+  return {
+    maxTapMs,
+    doubleTapWindowMs: clamp('doubleTapWindowMs'),
+    cooldownMs: clamp('cooldownMs'),
+    holdCooldownMs: clamp('holdCooldownMs'),
+    holdPromotionMs: Math.max(maxTapMs, clamp('holdPromotionMs')),
+  };
+}
+
+function sanitizePillPosition(raw: unknown): OverlayPillPosition | null {
+  if (!raw || typeof raw !== 'object') return null;
+  const { x, y } = raw as Record<string, unknown>;
+  if (typeof x !== 'number' || typeof y !== 'number' || !Number.isFinite(x) || !Number.isFinite(y)) return null;
+  return { x: Math.min(1, Math.max(0, x)), y: Math.min(1, Math.max(0, y)) };
+}
+
 /**
  * Validate a persisted code-vocab scan summary. Returns a clean
  * `VocabScanSummary` only when every field has the expected type; otherwise
@@ -458,184 +1699,341 @@ export function loadSettings(): Settings {
   try {
     const stored = localStorage.getItem(STORAGE_KEY);
     if (stored) {
-      const parsed = JSON.parse(stored) as Partial<Settings> & {
-        hotkey?: string;
-        liveTranscriptPreview?: unknown;
-        recordingMode?: string;
-      };
+      return sanitizeSettings(JSON.parse(stored));
+    }
+  } catch (e) {
+    console.error('Failed to load settings:', e);
+  }
+  return DEFAULT_SETTINGS;
+}
 
-      // Migrate: 'hotkey' mode no longer exists → default to 'hold_down'
-      const validModes: RecordingMode[] = ['hold_down', 'double_tap', 'both'];
-      if (!parsed.recordingMode || !validModes.includes(parsed.recordingMode as RecordingMode)) {
-        parsed.recordingMode = DEFAULT_SETTINGS.recordingMode;
-      }
+/**
+ * Coerce a settings object read from storage or an imported archive field by
+ * field, migrating old shapes and falling back to defaults for anything
+ * malformed.
+ */
+export function sanitizeSettings(raw: unknown): Settings {
+  if (!raw || typeof raw !== 'object' || Array.isArray(raw)) return DEFAULT_SETTINGS;
+  const parsed = { ...raw } as Partial<Settings> & {
+    hotkey?: string;
+    liveTranscriptPreview?: unknown;
+    recordingMode?: string;
+  };
 
-      // Remove legacy hotkey field if present
-      delete parsed.hotkey;
-      // The removed live-preview feature must not remain in persisted settings.
-      delete parsed.liveTranscriptPreview;
+  // Migrate: 'hotkey' mode no longer exists → default to 'hold_down'
+  const validModes: RecordingMode[] = ['hold_down', 'double_tap', 'both'];
+  if (!parsed.recordingMode || !validModes.includes(parsed.recordingMode as RecordingMode)) {
+    parsed.recordingMode = DEFAULT_SETTINGS.recordingMode;
+  }
 
-      // Validate model against current allow-list (includes Moonshine migration)
-      const validModels = new Set<string>(AVAILABLE_MODEL_OPTIONS.map((m) => m.value));
-      if (typeof parsed.model !== 'string' || !validModels.has(parsed.model)) {
-        parsed.model = DEFAULT_SETTINGS.model;
-      }
+  // Remove legacy hotkey field if present
+  delete parsed.hotkey;
+  // The removed live-preview feature must not remain in persisted settings.
+  delete parsed.liveTranscriptPreview;
 
-      // Validate language against current allow-list
-      const validLanguages = new Set<string>(LANGUAGE_OPTIONS.map((o) => o.value));
-      if (typeof parsed.language !== 'string' || !validLanguages.has(parsed.language)) {
-        parsed.language = DEFAULT_SETTINGS.language;
-      }
+  // Validate model against current allow-list (includes Moonshine migration)
+  const validModels = new Set<string>(AVAILABLE_MODEL_OPTIONS.map((m) => m.value));
+  if (typeof parsed.model !== 'string' || (!validModels.has(parsed.model) && !isCustomModel(parsed.model) && !isExternalModel(parsed.model) && !isCloudModel(parsed.model))) {
+    parsed.model = DEFAULT_SETTINGS.model;
+  }
 
-      // transformHoldKey: `null` (disabled) or one of TRANSFORM_KEY_OPTIONS.
-      // Anything else — including an absent field on pre-feature blobs, or a
-      // tampered/unrecognised id — coerces back to disabled rather than
-      // silently arming an unexpected shortcut.
-      {
-        const validTransformKeys = new Set<string>(TRANSFORM_KEY_OPTIONS.map((o) => o.value));
-        if (
-          parsed.transformHoldKey !== null
-          && (typeof parsed.transformHoldKey !== 'string' || !validTransformKeys.has(parsed.transformHoldKey))
-        ) {
-          parsed.transformHoldKey = DEFAULT_SETTINGS.transformHoldKey;
-        }
-      }
+  // Validate language against current allow-list
+  const validLanguages = new Set<string>(LANGUAGE_OPTIONS.map((o) => o.value));
+  if (typeof parsed.language !== 'string' || !validLanguages.has(parsed.language)) {
+    parsed.language = DEFAULT_SETTINGS.language;
+  }
 
-      // outputDir feeds a filesystem path on the Rust side — coerce anything
-      // non-string back to the default (empty = app-chosen Documents/Murmur).
-      if (typeof parsed.outputDir !== 'string') {
-        parsed.outputDir = DEFAULT_SETTINGS.outputDir;
-      }
+  // transformHoldKey: `null` (disabled) or one of TRANSFORM_KEY_OPTIONS.
+  // Anything else — including an absent field on pre-feature blobs, or a
+  // tampered/unrecognised id — coerces back to disabled rather than
+  // silently arming an unexpected shortcut.
+  {
+    const validTransformKeys = new Set<string>(TRANSFORM_KEY_OPTIONS.map((o) => o.value));
+    if (
+      parsed.transformHoldKey !== null
+      && (typeof parsed.transformHoldKey !== 'string' || !validTransformKeys.has(parsed.transformHoldKey))
+    ) {
+      parsed.transformHoldKey = DEFAULT_SETTINGS.transformHoldKey;
+    }
+  }
 
-      // benchmarkOutputDir also feeds a filesystem path on the Rust side.
-      if (typeof parsed.benchmarkOutputDir !== 'string') {
-        parsed.benchmarkOutputDir = DEFAULT_SETTINGS.benchmarkOutputDir;
-      }
-      if (typeof parsed.benchmarkAutoSave !== 'boolean') {
-        parsed.benchmarkAutoSave = DEFAULT_SETTINGS.benchmarkAutoSave;
-      }
+  // outputDir feeds a filesystem path on the Rust side — coerce anything
+  // non-string back to the default (empty = app-chosen Documents/Murmur).
+  if (typeof parsed.outputDir !== 'string') {
+    parsed.outputDir = DEFAULT_SETTINGS.outputDir;
+  }
 
-      parsed.vocabularyEntries = sanitizeVocabularyEntries(
-        parsed.vocabularyEntries,
-        parsed.customVocabulary,
-      );
-      // Keep the legacy field as a derived compatibility mirror. It is never an
-      // independently editable source after migration.
-      parsed.customVocabulary = vocabularyPrompt(parsed.vocabularyEntries);
-
-      // appProfiles drives per-app delivery and transformation overrides. Drop
-      // malformed entries and coerce a non-array back to the empty default so
-      // the Rust side and UI never see a bad shape.
-      if (!Array.isArray(parsed.appProfiles)) {
-        parsed.appProfiles = DEFAULT_SETTINGS.appProfiles;
-      } else {
-        parsed.appProfiles = parsed.appProfiles
-          .filter((p): p is AppProfile =>
-            !!p && typeof (p as AppProfile).bundleId === 'string' && (p as AppProfile).bundleId.trim() !== '')
-          .map((p) => ({
-            bundleId: p.bundleId.trim(),
-            label: typeof p.label === 'string' ? p.label : '',
-            autoPasteOverride:
-              typeof p.autoPasteOverride === 'boolean' ? p.autoPasteOverride : null,
-            cleanupOverride:
-              typeof p.cleanupOverride === 'boolean' ? p.cleanupOverride : null,
-            smartFormattingOverride:
-              typeof p.smartFormattingOverride === 'boolean' ? p.smartFormattingOverride : null,
-            cliFormattingOverride:
-              typeof p.cliFormattingOverride === 'boolean' ? p.cliFormattingOverride : null,
-            writingStyle:
-              typeof p.writingStyle === 'string' &&
-              ['conversational', 'polished', 'code_technical', 'verbatim', 'notes'].includes(p.writingStyle)
-                ? p.writingStyle as WritingStyle
-                : null,
-            ideContextEnabled: typeof p.ideContextEnabled === 'boolean' ? p.ideContextEnabled : false,
-            ideProjectRoots: Array.isArray(p.ideProjectRoots)
-              ? p.ideProjectRoots
-                  .filter((root): root is string => typeof root === 'string' && root.trim().length > 0)
-                  .map((root) => root.trim())
-                  .filter((root) => root.length <= MAX_IDE_PROJECT_ROOT_BYTES)
-                  .filter((root, index, roots) => roots.indexOf(root) === index)
-                  .slice(0, 4)
-              : [],
-          }));
-      }
+  // benchmarkOutputDir also feeds a filesystem path on the Rust side.
+  if (typeof parsed.benchmarkOutputDir !== 'string') {
+    parsed.benchmarkOutputDir = DEFAULT_SETTINGS.benchmarkOutputDir;
+  }
 
-      // voiceCommands: array of { phrase, replacement }. Drop malformed entries
-      // and coerce a non-array (or absent on older blobs) back to the default.
-      if (!Array.isArray(parsed.voiceCommands)) {
-        parsed.voiceCommands = DEFAULT_SETTINGS.voiceCommands;
-      } else {
-        parsed.voiceCommands = parsed.voiceCommands
-          .filter((c): c is VoiceCommand =>
-            !!c && typeof (c as VoiceCommand).phrase === 'string' && (c as VoiceCommand).phrase.trim() !== '')
-          .map((c) => ({
-            phrase: c.phrase.trim(),
-            replacement: typeof c.replacement === 'string' ? c.replacement : '',
-          }));
-      }
+  // dataFolder is another filesystem path Rust writes to.
+  if (typeof parsed.dataFolder !== 'string') {
+    parsed.dataFolder = DEFAULT_SETTINGS.dataFolder;
+  }
+  if (typeof parsed.benchmarkAutoSave !== 'boolean') {
+    parsed.benchmarkAutoSave = DEFAULT_SETTINGS.benchmarkAutoSave;
+  }
 
-      // cleanup sub-toggles default to on; coerce non-booleans back to the default.
-      if (typeof parsed.cleanupRemoveFiller !== 'boolean') {
-        parsed.cleanupRemoveFiller = DEFAULT_SETTINGS.cleanupRemoveFiller;
-      }
-      if (typeof parsed.cleanupCapitalize !== 'boolean') {
-        parsed.cleanupCapitalize = DEFAULT_SETTINGS.cleanupCapitalize;
-      }
+  parsed.vocabularyEntries = sanitizeVocabularyEntries(
+    parsed.vocabularyEntries,
+    parsed.customVocabulary,
+  );
+  // Keep the legacy field as a derived compatibility mirror. It is never an
+  // independently editable source after migration.
+  parsed.customVocabulary = vocabularyPrompt(parsed.vocabularyEntries);
 
-      // Voice commands gate the Rust transform — coerce non-booleans (or a
-      // missing field on pre-feature stored settings) back to the default.
-      if (typeof parsed.voiceCommandsEnabled !== 'boolean') {
-        parsed.voiceCommandsEnabled = DEFAULT_SETTINGS.voiceCommandsEnabled;
-      }
+  // appProfiles drives per-app delivery and transformation overrides. Drop
+  // malformed entries and coerce a non-array back to the empty default so
+  // the Rust side and UI never see a bad shape.
+  if (!Array.isArray(parsed.appProfiles)) {
+    parsed.appProfiles = DEFAULT_SETTINGS.appProfiles;
+  } else {
+    parsed.appProfiles = parsed.appProfiles
+      .filter((p): p is AppProfile =>
+        !!p && typeof (p as AppProfile).bundleId === 'string' && (p as AppProfile).bundleId.trim() !== '')
+      .map((p) => ({
+        bundleId: p.bundleId.trim(),
+        label: typeof p.label === 'string' ? p.label : '',
+        autoPasteOverride:
+          typeof p.autoPasteOverride === 'boolean' ? p.autoPasteOverride : null,
+        cleanupOverride:
+          typeof p.cleanupOverride === 'boolean' ? p.cleanupOverride : null,
+        smartFormattingOverride:
+          typeof p.smartFormattingOverride === 'boolean' ? p.smartFormattingOverride : null,
+        cliFormattingOverride:
+          typeof p.cliFormattingOverride === 'boolean' ? p.cliFormattingOverride : null,
+        trailingOverride: TRAILING_BEHAVIOR_OPTIONS.some((option) => option.value === p.trailingOverride)
+          ? p.trailingOverride
+          : null,
+        writingStyle:
+          typeof p.writingStyle === 'string' &&
+          ['conversational', 'polished', 'code_technical', 'verbatim', 'notes'].includes(p.writingStyle)
+            ? p.writingStyle as WritingStyle
+            : null,
+        ideContextEnabled: typeof p.ideContextEnabled === 'boolean' ? p.ideContextEnabled : false,
+        ideProjectRoots: Array.isArray(p.ideProjectRoots)
+          ? p.ideProjectRoots
+              .filter((root): root is string => typeof root === 'string' && root.trim().length > 0)
+              .map((root) => root.trim())
+              .filter((root) => root.length <= MAX_IDE_PROJECT_ROOT_BYTES)
+              .filter((root, index, roots) => roots.indexOf(root) === index)
+              .slice(0, 4)
+          : [],
+      }));
+  }
 
-      // cleanupEnabled is a boolean toggle — coerce anything non-boolean
-      // (including absent on older settings blobs) back to the default.
-      if (typeof parsed.cleanupEnabled !== 'boolean') {
-        parsed.cleanupEnabled = DEFAULT_SETTINGS.cleanupEnabled;
-      }
+  // voiceCommands: array of { phrase, replacement }. Drop malformed entries
+  // and coerce a non-array (or absent on older blobs) back to the default.
+  if (!Array.isArray(parsed.voiceCommands)) {
+    parsed.voiceCommands = DEFAULT_SETTINGS.voiceCommands;
+  } else {
+    parsed.voiceCommands = parsed.voiceCommands
+      .filter((c): c is VoiceCommand =>
+        !!c && typeof (c as VoiceCommand).phrase === 'string' && (c as VoiceCommand).phrase.trim() !== '')
+      .map((c) => ({
+        phrase: c.phrase.trim(),
+        replacement: typeof c.replacement === 'string' ? c.replacement : '',
+      }));
+  }
 
-      // Smart formatting is an explicit opt-in. Older or malformed settings
-      // stay off rather than silently enabling broad prose transformations.
-      if (typeof parsed.smartFormattingEnabled !== 'boolean') {
-        parsed.smartFormattingEnabled = DEFAULT_SETTINGS.smartFormattingEnabled;
-      }
+  // cleanup sub-toggles default to on; coerce non-booleans back to the default.
+  if (typeof parsed.cleanupRemoveFiller !== 'boolean') {
+    parsed.cleanupRemoveFiller = DEFAULT_SETTINGS.cleanupRemoveFiller;
+  }
+  if (typeof parsed.cleanupCapitalize !== 'boolean') {
+    parsed.cleanupCapitalize = DEFAULT_SETTINGS.cleanupCapitalize;
+  }
 
-      if (typeof parsed.hotkeyMissFeedback !== 'boolean') {
-        parsed.hotkeyMissFeedback = DEFAULT_SETTINGS.hotkeyMissFeedback;
-      }
-      // codeVocabEnabled gates the Rust scan — coerce non-booleans (or a missing
-      // field on pre-feature stored settings) back to the default.
-      if (typeof parsed.codeVocabEnabled !== 'boolean') {
-        parsed.codeVocabEnabled = DEFAULT_SETTINGS.codeVocabEnabled;
-      }
+  // Voice commands gate the Rust transform — coerce non-booleans (or a
+  // missing field on pre-feature stored settings) back to the default.
+  if (typeof parsed.voiceCommandsEnabled !== 'boolean') {
+    parsed.voiceCommandsEnabled = DEFAULT_SETTINGS.voiceCommandsEnabled;
+  }
 
-      // codeVocabFolder feeds a filesystem path on the Rust side — coerce
-      // anything non-string back to the empty default.
-      if (typeof parsed.codeVocabFolder !== 'string') {
-        parsed.codeVocabFolder = DEFAULT_SETTINGS.codeVocabFolder;
-      }
+  // cleanupEnabled is a boolean toggle — coerce anything non-boolean
+  // (including absent on older settings blobs) back to the default.
+  if (typeof parsed.cleanupEnabled !== 'boolean') {
+    parsed.cleanupEnabled = DEFAULT_SETTINGS.cleanupEnabled;
+  }
 
-      // codeVocabLastScan is a persisted scan summary (or null). Validate the
-      // whole shape — a partial/malformed blob would render bad numbers in the
-      // done-state, so coerce anything that doesn't match back to null.
-      parsed.codeVocabLastScan = sanitizeVocabScan(parsed.codeVocabLastScan);
+  // Smart formatting is an explicit opt-in. Older or malformed settings
+  // stay off rather than silently enabling broad prose transformations.
+  if (typeof parsed.smartFormattingEnabled !== 'boolean') {
+    parsed.smartFormattingEnabled = DEFAULT_SETTINGS.smartFormattingEnabled;
+  }
 
-      // Correction toggles — coerce non-booleans (or absent on pre-feature blobs)
-      // back to defaults. correctionEnabled defaults ON, so an older settings blob
-      // that predates this field opts into correction (the intended migration).
-      if (typeof parsed.correctionEnabled !== 'boolean') {
-        parsed.correctionEnabled = DEFAULT_SETTINGS.correctionEnabled;
-      }
-      if (typeof parsed.correctionFuzzy !== 'boolean') {
-        parsed.correctionFuzzy = DEFAULT_SETTINGS.correctionFuzzy;
-      }
+  if (typeof parsed.hotkeyMissFeedback !== 'boolean') {
+    parsed.hotkeyMissFeedback = DEFAULT_SETTINGS.hotkeyMissFeedback;
+  }
+  parsed.hotkeyTiming = sanitizeHotkeyTiming(parsed.hotkeyTiming);
+  // The wake word keeps the microphone open, so anything but an explicit
+  // opt-in stays off.
+  if (typeof parsed.wakeWord !== 'boolean') {
+    parsed.wakeWord = DEFAULT_SETTINGS.wakeWord;
+  }
+  if (typeof parsed.ambientMode !== 'boolean') {
+    parsed.ambientMode = DEFAULT_SETTINGS.ambientMode;
+  }
+  // Pre-roll keeps the microphone open too: anything but a listed length is off.
+  if (!PRE_ROLL_OPTIONS.some((option) => option.value === parsed.preRollSeconds)) {
+    parsed.preRollSeconds = DEFAULT_SETTINGS.preRollSeconds;
+  }
+  parsed.microphonePreferences = Array.isArray(parsed.microphonePreferences)
+    ? parsed.microphonePreferences
+        .filter((name): name is string => typeof name === 'string' && name.trim() !== '')
+        .filter((name, index, names) => names.indexOf(name) === index)
+        .slice(0, MAX_MICROPHONE_PREFERENCES)
+    : DEFAULT_SETTINGS.microphonePreferences;
+  if (!LOW_QUALITY_MIC_OPTIONS.some((option) => option.value === parsed.lowQualityMicAction)) {
+    parsed.lowQualityMicAction = DEFAULT_SETTINGS.lowQualityMicAction;
+  }
+  // codeVocabEnabled gates the Rust scan — coerce non-booleans (or a missing
+  // field on pre-feature stored settings) back to the default.
+  if (typeof parsed.codeVocabEnabled !== 'boolean') {
+    parsed.codeVocabEnabled = DEFAULT_SETTINGS.codeVocabEnabled;
+  }
 
-      return { ...DEFAULT_SETTINGS, ...parsed } as Settings;
-    }
-  } catch (e) {
-    console.error('Failed to load settings:', e);
+  // codeVocabFolder feeds a filesystem path on the Rust side — coerce
+  // anything non-string back to the empty default.
+  if (typeof parsed.codeVocabFolder !== 'string') {
+    parsed.codeVocabFolder = DEFAULT_SETTINGS.codeVocabFolder;
   }
-  return DEFAULT_SETTINGS;
+
+  // codeVocabLastScan is a persisted scan summary (or null). Validate the
+  // whole shape — a partial/malformed blob would render bad numbers in the
+  // done-state, so coerce anything that doesn't match back to null.
+  parsed.codeVocabLastScan = sanitizeVocabScan(parsed.codeVocabLastScan);
+
+  // Correction toggles — coerce non-booleans (or absent on pre-feature blobs)
+  // back to defaults. correctionEnabled defaults ON, so an older settings blob
+  // that predates this field opts into correction (the intended migration).
+  if (typeof parsed.correctionEnabled !== 'boolean') {
+    parsed.correctionEnabled = DEFAULT_SETTINGS.correctionEnabled;
+  }
+  if (typeof parsed.correctionFuzzy !== 'boolean') {
+    parsed.correctionFuzzy = DEFAULT_SETTINGS.correctionFuzzy;
+  }
+  if (typeof parsed.learnFromCorrections !== 'boolean') {
+    parsed.learnFromCorrections = DEFAULT_SETTINGS.learnFromCorrections;
+  }
+  parsed.contextBiasing = sanitizeContextBiasing(parsed.contextBiasing);
+  if (!NOTIFICATION_POLICY_OPTIONS.some((option) => option.value === parsed.notificationPolicy)) {
+    parsed.notificationPolicy = DEFAULT_SETTINGS.notificationPolicy;
+  }
+  parsed.focusFilter = sanitizeFocusFilter(parsed.focusFilter);
+  parsed.quietHours = sanitizeQuietHours(parsed.quietHours);
+  parsed.appTriggers = sanitizeAppTriggers(parsed.appTriggers);
+  parsed.configPresets = sanitizeConfigPresets(parsed.configPresets);
+  if (!INTERRUPTED_RECORDING_OPTIONS.some((option) => option.value === parsed.interruptedRecordingAction)) {
+    parsed.interruptedRecordingAction = DEFAULT_SETTINGS.interruptedRecordingAction;
+  }
+  if (!OVERLAY_DISPLAY_OPTIONS.some((option) => option.value === parsed.overlayDisplay)) {
+    parsed.overlayDisplay = DEFAULT_SETTINGS.overlayDisplay;
+  }
+  if (!OVERLAY_STYLE_OPTIONS.some((option) => option.value === parsed.overlayStyle)) {
+    parsed.overlayStyle = DEFAULT_SETTINGS.overlayStyle;
+  }
+  parsed.overlayPillPosition = sanitizePillPosition(parsed.overlayPillPosition);
+  if (!OVERLAY_FULLSCREEN_OPTIONS.some((option) => option.value === parsed.overlayFullscreen)) {
+    parsed.overlayFullscreen = DEFAULT_SETTINGS.overlayFullscreen;
+  }
+  parsed.whisperDecoding = sanitizeWhisperDecoding(parsed.whisperDecoding);
+  if (typeof parsed.hallucinationFilter !== 'boolean') {
+    parsed.hallucinationFilter = DEFAULT_SETTINGS.hallucinationFilter;
+  }
+  if (typeof parsed.flagLowConfidence !== 'boolean') {
+    parsed.flagLowConfidence = DEFAULT_SETTINGS.flagLowConfidence;
+  }
+  parsed.redaction = sanitizeRedaction(parsed.redaction);
+  parsed.adaptiveModel = sanitizeAdaptiveModel(parsed.adaptiveModel);
+  parsed.latencyBudget = sanitizeLatencyBudget(parsed.latencyBudget);
+  parsed.powerProfiles = sanitizePowerProfiles(parsed.powerProfiles);
+  parsed.dualModel = sanitizeDualModel(parsed.dualModel);
+  parsed.confidenceRetry = sanitizeConfidenceRetry(parsed.confidenceRetry);
+  parsed.alternatives = sanitizeAlternatives(parsed.alternatives);
+  parsed.rewriteTemplates = sanitizeRewriteTemplates(parsed.rewriteTemplates);
+  parsed.replacementRules = sanitizeReplacementRules(parsed.replacementRules);
+  parsed.dictationMacros = sanitizeDictationMacros(parsed.dictationMacros);
+  parsed.dictationProfiles = sanitizeDictationProfiles(parsed.dictationProfiles, parsed.rewriteTemplates);
+  if (!KEEP_ALIVE_OPTIONS.some((option) => option.value === parsed.backendKeepAliveMb)) {
+    parsed.backendKeepAliveMb = DEFAULT_SETTINGS.backendKeepAliveMb;
+  }
+  if (typeof parsed.privacyMode !== 'boolean') {
+    parsed.privacyMode = DEFAULT_SETTINGS.privacyMode;
+  }
+  if (typeof parsed.keepAudioOnDevice !== 'boolean') {
+    parsed.keepAudioOnDevice = DEFAULT_SETTINGS.keepAudioOnDevice;
+  }
+  parsed.cloudTranscription = sanitizeCloudTranscription(parsed.cloudTranscription);
+  if (typeof parsed.persistHistory !== 'boolean') {
+    parsed.persistHistory = DEFAULT_SETTINGS.persistHistory;
+  }
+  if (typeof parsed.encryptHistory !== 'boolean') {
+    parsed.encryptHistory = DEFAULT_SETTINGS.encryptHistory;
+  }
+  if (!UPDATE_CHANNEL_OPTIONS.some((option) => option.value === parsed.updateChannel)) {
+    parsed.updateChannel = DEFAULT_SETTINGS.updateChannel;
+  }
+  parsed.downloadNetwork = sanitizeDownloadNetwork(parsed.downloadNetwork);
+  parsed.storageRetention = sanitizeStorageRetention(parsed.storageRetention);
+  parsed.typingWpm = typeof parsed.typingWpm === 'number' && Number.isFinite(parsed.typingWpm)
+    ? Math.round(Math.min(TYPING_WPM_MAX, Math.max(TYPING_WPM_MIN, parsed.typingWpm)))
+    : DEFAULT_SETTINGS.typingWpm;
+  if (typeof parsed.appendMode !== 'boolean') {
+    parsed.appendMode = DEFAULT_SETTINGS.appendMode;
+  }
+  if (
+    parsed.appendCommitKey !== null
+    && !PROFILE_KEY_OPTIONS.some((option) => option.value === parsed.appendCommitKey)
+  ) {
+    parsed.appendCommitKey = DEFAULT_SETTINGS.appendCommitKey;
+  }
+  if (typeof parsed.legacyAutomation !== 'boolean') {
+    parsed.legacyAutomation = DEFAULT_SETTINGS.legacyAutomation;
+  }
+  if (typeof parsed.previewBeforePaste !== 'boolean') {
+    parsed.previewBeforePaste = DEFAULT_SETTINGS.previewBeforePaste;
+  }
+  if (typeof parsed.cursorContext !== 'boolean') {
+    parsed.cursorContext = DEFAULT_SETTINGS.cursorContext;
+  }
+  if (!TRAILING_BEHAVIOR_OPTIONS.some((option) => option.value === parsed.trailingBehavior)) {
+    parsed.trailingBehavior = DEFAULT_SETTINGS.trailingBehavior;
+  }
+  if (!NOTES_TARGET_OPTIONS.some((option) => option.value === parsed.notesTarget)) {
+    parsed.notesTarget = DEFAULT_SETTINGS.notesTarget;
+  }
+  if (!SELECTION_DICTATION_OPTIONS.some((option) => option.value === parsed.selectionDictation)) {
+    parsed.selectionDictation = DEFAULT_SETTINGS.selectionDictation;
+  }
+  if (
+    parsed.pasteLastKey !== null
+    && !PROFILE_KEY_OPTIONS.some((option) => option.value === parsed.pasteLastKey)
+  ) {
+    parsed.pasteLastKey = DEFAULT_SETTINGS.pasteLastKey;
+  }
+  if (
+    parsed.clipboardAudioKey !== null
+    && !PROFILE_KEY_OPTIONS.some((option) => option.value === parsed.clipboardAudioKey)
+  ) {
+    parsed.clipboardAudioKey = DEFAULT_SETTINGS.clipboardAudioKey;
+  }
+  if (
+    parsed.markerKey !== null
+    && !PROFILE_KEY_OPTIONS.some((option) => option.value === parsed.markerKey)
+  ) {
+    parsed.markerKey = DEFAULT_SETTINGS.markerKey;
+  }
+  if (typeof parsed.spokenMarkers !== 'boolean') {
+    parsed.spokenMarkers = DEFAULT_SETTINGS.spokenMarkers;
+  }
+  if (!MEETING_CHUNK_OPTIONS.some((option) => option.value === parsed.meetingChunkSeconds)) {
+    parsed.meetingChunkSeconds = DEFAULT_SETTINGS.meetingChunkSeconds;
+  }
+
+  return { ...DEFAULT_SETTINGS, ...parsed } as Settings;
 }
 
 export function saveSettings(settings: Settings): void {
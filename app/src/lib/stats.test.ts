@@ -0,0 +1,17 @@
+import { describe, expect, it } from 'vitest';
+import { countWords } from './stats';
+
+describe('countWords', () => {
+  it('splits spaced scripts on whitespace', () => {
+    expect(countWords('Send it -- now, please')).toBe(5);
+    expect(countWords('مرحبا بالعالم')).toBe(2);
+    expect(countWords('안녕하세요 세계')).toBe(2);
+    expect(countWords('  ')).toBe(0);
+  });
+
+  it('counts Chinese and Japanese by character', () => {
+    expect(countWords('今日は晴れです。')).toBe(7);
+    expect(countWords('我用iPhone 15打字')).toBe(6);
+    expect(countWords('𠮷野家へ')).toBe(4);
+  });
+});
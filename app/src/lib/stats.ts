@@ -1,3 +1,5 @@
+import { invoke } from '@tauri-apps/api/core';
+
 // Per-day usage bucket, keyed by 'YYYY-MM-DD' (local time) in `dailyBuckets`.
 export interface DayBucket {
   words: number;
@@ -9,6 +11,9 @@ export type DailyBuckets = Record<string, DayBucket>;
 
 export interface DictationStats {
   totalWords: number;
+  // Text tokens Whisper decoded. Other backends don't report tokens, and stats
+  // saved before this field existed load it as 0.
+  totalTokens: number;
   totalRecordings: number;
   totalDurationSeconds: number;
   wpmSamples: number[];
@@ -19,6 +24,7 @@ export interface DictationStats {
 
 const DEFAULT_STATS: DictationStats = {
   totalWords: 0,
+  totalTokens: 0,
   totalRecordings: 0,
   totalDurationSeconds: 0,
   wpmSamples: [],
@@ -27,6 +33,34 @@ const DEFAULT_STATS: DictationStats = {
 
 const EMPTY_BUCKET: DayBucket = { words: 0, recordings: 0, recordingSeconds: 0 };
 
+// Chinese and Japanese characters and full-width punctuation; mirrors
+// `is_unspaced_char` in language_rules.rs.
+const UNSPACED_CHAR =
+  /[\u3000-\u303F\u3040-\u30FF\u3400-\u4DBF\u4E00-\u9FFF\uF900-\uFAFF\uFF00-\uFF65\u{20000}-\u{2FA1F}\u{30000}-\u{3134F}]/u;
+
+// Word count matching `text_metrics::word_count`: whitespace separates words,
+// except in Chinese and Japanese, where each character counts as one and a
+// Latin run beside them ("iPhone" in "我用iPhone") counts once.
+export function countWords(text: string): number {
+  let total = 0;
+  for (const token of text.split(/\s+/)) {
+    if (!token) continue;
+    let count = 0;
+    let inRun = false;
+    for (const char of token) {
+      if (UNSPACED_CHAR.test(char)) {
+        if (/\p{L}/u.test(char)) count += 1;
+        inRun = false;
+      } else if (!inRun) {
+        count += 1;
+        inRun = true;
+      }
+    }
+    total += Math.max(count, 1);
+  }
+  return total;
+}
+
 // Local-time 'YYYY-MM-DD' key for a date (defaults to now). Local — so a day's
 // bucket aligns with the user's calendar, not UTC.
 export function dayKey(date: Date = new Date()): string {
@@ -69,6 +103,9 @@ export function loadStats(): DictationStats {
       ) {
         parsed.wpmSamples = DEFAULT_STATS.wpmSamples;
       }
+      if (typeof parsed.totalTokens !== 'number' || !isFinite(parsed.totalTokens)) {
+        parsed.totalTokens = DEFAULT_STATS.totalTokens;
+      }
       // Back-compat: stats saved before dailyBuckets existed have no map; the
       // sanitizer turns `undefined` into {} so older installs migrate cleanly.
       parsed.dailyBuckets = sanitizeBuckets(parsed.dailyBuckets);
@@ -88,10 +125,11 @@ export function saveStats(stats: DictationStats): void {
   }
 }
 
-export function updateStats(text: string, durationSeconds: number): void {
+// `tokens` is the decoder's count from `transcription-complete`, when it has one.
+export function updateStats(text: string, durationSeconds: number, tokens?: number): void {
   try {
     const stats = loadStats();
-    const wordCount = text.trim() === '' ? 0 : text.trim().split(/\s+/).length;
+    const wordCount = countWords(text);
 
     const newSamples = [...stats.wpmSamples];
     if (durationSeconds > 0 && wordCount > 0) {
@@ -116,6 +154,7 @@ export function updateStats(text: string, durationSeconds: number): void {
 
     saveStats({
       totalWords: stats.totalWords + wordCount,
+      totalTokens: stats.totalTokens + (tokens ?? 0),
       totalRecordings: stats.totalRecordings + 1,
       totalDurationSeconds: stats.totalDurationSeconds + durationSeconds,
       wpmSamples: newSamples,
@@ -132,6 +171,7 @@ export function resetStats(): void {
   } catch (e) {
     console.error('Failed to reset stats:', e);
   }
+  invoke('clear_dictation_stats').catch((e) => console.error('Failed to clear dictation stats log:', e));
 }
 
 export function getWPM(stats: DictationStats): number {
@@ -140,10 +180,6 @@ export function getWPM(stats: DictationStats): number {
   return Math.round(sum / stats.wpmSamples.length);
 }
 
-export function getApproxTokens(stats: DictationStats): number {
-  return Math.round(stats.totalWords * 1.3);
-}
-
 // --- Daily-bucket derivations for the usage dashboard ---
 
 export interface DaySummary extends DayBucket {
@@ -223,3 +259,50 @@ export function getCurrentStreak(stats: DictationStats): number {
   }
   return streak;
 }
+
+// --- Range summaries computed by the backend's usage log ---
+
+export type StatsRange = 'day' | 'week';
+
+export interface ModelUsage {
+  model: string;
+  dictations: number;
+  words: number;
+  share: number; // 0–1 of the range's words
+  tokens: number; // decoded by Whisper; 0 for other backends
+  tokensPerSec: number | null; // decode throughput; null without token counts
+}
+
+export interface DictationStatsSummary {
+  range: StatsRange;
+  dictations: number;
+  words: number;
+  tokens: number;
+  audioSecs: number;
+  typingWpm: number;
+  timeSavedSecs: number;
+  days: { date: string; dictations: number; words: number }[];
+  hourlyWords: number[]; // index 0 = midnight, local time
+  busiestHours: number[];
+  models: ModelUsage[];
+}
+
+export function getDictationStats(range: StatsRange, typingWpm: number): Promise<DictationStatsSummary> {
+  return invoke<DictationStatsSummary>('get_dictation_stats', { range, typingWpm });
+}
+
+// '2h 5m' / '12m' / '40s' — compact enough for the dashboard's summary row.
+export function formatDuration(seconds: number): string {
+  const total = Math.max(0, Math.round(seconds));
+  const hours = Math.floor(total / 3600);
+  const minutes = Math.floor((total % 3600) / 60);
+  if (hours > 0) return `${hours}h ${minutes}m`;
+  if (minutes > 0) return `${minutes}m`;
+  return `${total}s`;
+}
+
+// 0 → '12am', 13 → '1pm'.
+export function formatHour(hour: number): string {
+  const h = hour % 12 === 0 ? 12 : hour % 12;
+  return `${h}${hour < 12 ? 'am' : 'pm'}`;
+}
@@ -0,0 +1,27 @@
+import { describe, expect, it } from 'vitest';
+import { describeCleanup, formatStorageSize } from './storage';
+
+describe('formatStorageSize', () => {
+  it('picks a unit a person would use', () => {
+    expect(formatStorageSize(0)).toBe('Empty');
+    expect(formatStorageSize(900)).toBe('900 bytes');
+    expect(formatStorageSize(12_800)).toBe('13 KB');
+    expect(formatStorageSize(470 * 1024 ** 2)).toBe('470 MB');
+    expect(formatStorageSize(1.5 * 1024 ** 3)).toBe('1.5 GB');
+  });
+});
+
+describe('describeCleanup', () => {
+  it('names what each category lost', () => {
+    expect(describeCleanup({ trigger: 'manual', removed: [], removedFiles: 0, freedBytes: 0 })).toBe('Nothing was over the limits.');
+    expect(describeCleanup({
+      trigger: 'daily',
+      removed: [
+        { category: 'logs', removedFiles: 3, freedBytes: 2 * 1024 ** 2 },
+        { category: 'recordings', removedFiles: 1, freedBytes: 10 * 1024 ** 2 },
+      ],
+      removedFiles: 4,
+      freedBytes: 12 * 1024 ** 2,
+    })).toBe('Removed 3 files from logs and 1 file from recordings, freeing 12 MB.');
+  });
+});
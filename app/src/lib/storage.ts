@@ -0,0 +1,82 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { StorageRetentionSettings } from './settings';
+
+export type StorageCategory = 'models' | 'logs' | 'recordings' | 'history';
+
+/** Each category with what purging it removes, shown next to its size. */
+export const STORAGE_CATEGORIES: ReadonlyArray<{ category: StorageCategory; label: string; description: string }> = [
+  { category: 'models', label: 'Models', description: 'Downloaded transcription, voice detection, and wake word models. You’ll need to download a model again to dictate.' },
+  { category: 'logs', label: 'Logs', description: 'App, event, and transcript logs.' },
+  { category: 'recordings', label: 'Recordings', description: 'Audio saved to your output folder, and any unfinished recording from a crash.' },
+  { category: 'history', label: 'History', description: 'Your saved dictation history.' },
+];
+
+export interface CategoryUsage {
+  category: StorageCategory;
+  bytes: number;
+  files: number;
+  path: string | null;
+}
+
+export interface DiskUsageReport {
+  categories: CategoryUsage[];
+  totalBytes: number;
+  /** Free space on the disk holding the models; null where it can't be read. */
+  availableBytes: number | null;
+}
+
+/** Payload of `disk-space-insufficient`, sent when a download or audio saving is refused. */
+export interface DiskSpaceShortfall {
+  /** The model name, or `recordings`. */
+  purpose: string;
+  requiredBytes: number;
+  availableBytes: number;
+}
+
+export interface PurgeResult {
+  category: StorageCategory;
+  removedFiles: number;
+  freedBytes: number;
+}
+
+export async function getDiskUsageReport(): Promise<DiskUsageReport> {
+  return await invoke('get_disk_usage_report');
+}
+
+/** Delete everything in one category. Models can't be purged while dictating or downloading. */
+export async function purgeStorage(category: StorageCategory): Promise<PurgeResult> {
+  return await invoke('purge_storage', { category });
+}
+
+export function formatStorageSize(bytes: number): string {
+  if (bytes >= 1024 ** 3) return `${(bytes / 1024 ** 3).toFixed(1)} GB`;
+  if (bytes >= 1024 ** 2) return `${Math.round(bytes / 1024 ** 2)} MB`;
+  if (bytes >= 1024) return `${Math.round(bytes / 1024)} KB`;
+  return bytes === 0 ? 'Empty' : `${bytes} bytes`;
+}
+
+/** Payload of `storage-cleaned`, sent when retention cleanup removed something. */
+export interface StorageCleaned {
+  trigger: 'startup' | 'daily' | 'manual';
+  removed: PurgeResult[];
+  removedFiles: number;
+  freedBytes: number;
+}
+
+export async function setStorageRetention(settings: StorageRetentionSettings): Promise<void> {
+  await invoke('set_storage_retention', { settings });
+}
+
+/** Apply the retention limits now instead of at the next daily run. */
+export async function runStorageCleanup(): Promise<StorageCleaned> {
+  return await invoke('run_storage_cleanup');
+}
+
+export function describeCleanup(cleaned: StorageCleaned): string {
+  if (cleaned.removedFiles === 0) return 'Nothing was over the limits.';
+  const parts = cleaned.removed.map(({ category, removedFiles }) => {
+    const label = STORAGE_CATEGORIES.find((entry) => entry.category === category)?.label.toLowerCase() ?? category;
+    return `${removedFiles} ${removedFiles === 1 ? 'file' : 'files'} from ${label}`;
+  });
+  return `Removed ${parts.join(' and ')}, freeing ${formatStorageSize(cleaned.freedBytes)}.`;
+}
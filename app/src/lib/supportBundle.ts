@@ -0,0 +1,43 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type BundleSection = 'system' | 'permissions' | 'models' | 'metrics' | 'logs' | 'keyboard';
+
+/** Each section with what it contains, shown before the user agrees to include it. */
+export const BUNDLE_SECTIONS: ReadonlyArray<{ section: BundleSection; label: string; description: string }> = [
+  { section: 'system', label: 'System', description: 'macOS version, chip, Mac model, and memory.' },
+  { section: 'permissions', label: 'Permissions', description: 'Whether Microphone, Accessibility, Input Monitoring, and Automation are granted.' },
+  { section: 'models', label: 'Models', description: 'Which models are installed, and the names and sizes of the files in the models folder.' },
+  { section: 'metrics', label: 'Performance', description: 'Timings of recent dictations and this week’s usage totals. No transcript text.' },
+  { section: 'logs', label: 'Recent logs', description: 'The last 500 log lines. They can name apps, devices, and folders; your home folder is shown as ~.' },
+  { section: 'keyboard', label: 'Keyboard', description: 'Hotkey listener health and your last keyboard diagnostics recording.' },
+];
+
+export interface SupportBundle {
+  path: string;
+  sections: BundleSection[];
+  bytes: number;
+}
+
+/** Write the chosen sections into one zip on the Desktop, for attaching to a GitHub issue. */
+export async function generateSupportBundle(sections: BundleSection[]): Promise<SupportBundle> {
+  return await invoke('generate_support_bundle', { sections });
+}
+
+export type IssueDestination = { kind: 'github' } | { kind: 'folder'; path: string };
+
+export interface IssueReport {
+  /** The new-issue page that was opened. */
+  url: string | null;
+  /** Files written into the chosen folder. */
+  saved: string[];
+  /** The anonymized facts added under the description. */
+  summary: Record<string, unknown>;
+}
+
+/**
+ * File a bug report: the description plus an anonymized summary (versions, permissions, model names, timings), as a
+ * pre-filled GitHub issue or written into a folder with a copy of `bundle`.
+ */
+export async function reportIssue(description: string, destination: IssueDestination, bundle?: string): Promise<IssueReport> {
+  return await invoke('report_issue', { description, bundle: bundle ?? null, destination });
+}
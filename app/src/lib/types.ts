@@ -1,6 +1,20 @@
-export type DictationStatus = 'idle' | 'recording' | 'processing';
+/** `buffering`: append mode is holding takes until they are committed. */
+export type DictationStatus = 'idle' | 'recording' | 'processing' | 'buffering';
 
-export const VALID_STATUSES = ['idle', 'recording', 'processing'] as const;
+export const VALID_STATUSES = ['idle', 'recording', 'processing', 'buffering'] as const;
 export function isDictationStatus(v: unknown): v is DictationStatus {
   return typeof v === 'string' && (VALID_STATUSES as readonly string[]).includes(v);
 }
+
+/** Nothing is recording or transcribing, so a new take may start. */
+export function isSettledStatus(status: DictationStatus): boolean {
+  return status === 'idle' || status === 'buffering';
+}
+
+/** Payload of `overlay-state`: what the overlay shows beyond its status icon. */
+export type OverlayState =
+  | { state: 'idle' }
+  | { state: 'recording'; payload: { startedAtMs: number } }
+  | { state: 'level'; payload: { rms: number } }
+  | { state: 'processing'; payload: { partial: string | null } }
+  | { state: 'error'; payload: { message: string } };
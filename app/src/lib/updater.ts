@@ -2,9 +2,6 @@ const SKIPPED_VERSION_KEY = 'skipped-update-version';
 const LAST_CHECK_KEY = 'updater-last-check';
 export const CHECK_INTERVAL_MS = 24 * 60 * 60 * 1000; // 24 hours
 
-const LATEST_JSON_URL =
-  'https://github.com/georgenijo/murmur-app/releases/latest/download/latest-v2.json';
-
 // --- Semver comparison ---
 
 export function parseSemver(version: string): [number, number, number] | null {
@@ -84,31 +81,23 @@ export function isDueForCheck(): boolean {
   return Date.now() - getLastCheckTimestamp() >= CHECK_INTERVAL_MS;
 }
 
-// --- min_version fetch ---
+// --- Update state types ---
 
-/**
- * Fetch the custom min_version field from the current update channel.
- * Returns null if absent, fetch fails, or JSON is invalid.
- */
-export async function fetchMinVersion(): Promise<string | null> {
-  try {
-    const response = await fetch(LATEST_JSON_URL, { cache: 'no-store' });
-    if (!response.ok) return null;
-    const data = await response.json();
-    if (typeof data.min_version === 'string') return data.min_version;
-    return null;
-  } catch {
-    return null;
-  }
+/** An update offered by Rust's `check_for_update`. */
+export interface UpdateInfo {
+  version: string;
+  notes: string;
+  channel: 'stable' | 'beta';
+  /** The running version is below the feed's `min_version`. */
+  isForced: boolean;
 }
 
-// --- Update state types ---
-
 export type UpdateStatus =
   | { phase: 'idle' }
   | { phase: 'checking' }
   | { phase: 'available'; version: string; notes: string; isForced: boolean }
   | { phase: 'downloading'; version: string; progress: number }
-  | { phase: 'ready'; version: string }
+  /** `deferred` while the relaunch waits for dictation to finish. */
+  | { phase: 'ready'; version: string; deferred: boolean }
   | { phase: 'error'; message: string; isForced: boolean }
   | { phase: 'up-to-date' };
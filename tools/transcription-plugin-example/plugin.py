@@ -0,0 +1,123 @@
+#!/usr/bin/env python3
+"""Example Murmur transcription plugin backed by an OpenAI-compatible server.
+
+Speaks protocol 1 on stdin/stdout (one JSON object per line) and forwards each
+recording to a ``/v1/audio/transcriptions`` endpoint, such as a local
+faster-whisper server. Uses only the standard library.
+"""
+
+import argparse
+import base64
+import json
+import sys
+import urllib.error
+import urllib.request
+import uuid
+from typing import Any, Callable, Optional, TextIO
+
+PROTOCOL_VERSION = 1
+NAME = "OpenAI-compatible server"
+
+Post = Callable[[bytes, dict[str, str]], str]
+
+
+def multipart_body(wav: bytes, fields: dict[str, str]) -> tuple[bytes, str]:
+    """Encode ``fields`` and the recording as multipart/form-data."""
+    boundary = uuid.uuid4().hex
+    parts = []
+    for key, value in fields.items():
+        parts.append(
+            f'--{boundary}\r\nContent-Disposition: form-data; name="{key}"\r\n\r\n{value}\r\n'.encode()
+        )
+    parts.append(
+        f'--{boundary}\r\nContent-Disposition: form-data; name="file"; filename="audio.wav"\r\n'
+        "Content-Type: audio/wav\r\n\r\n".encode()
+        + wav
+        + b"\r\n"
+    )
+    parts.append(f"--{boundary}--\r\n".encode())
+    return b"".join(parts), f"multipart/form-data; boundary={boundary}"
+
+
+def http_post(url: str, api_key: Optional[str], timeout: float) -> Post:
+    def post(wav: bytes, fields: dict[str, str]) -> str:
+        body, content_type = multipart_body(wav, fields)
+        request = urllib.request.Request(url, data=body, method="POST")
+        request.add_header("Content-Type", content_type)
+        if api_key:
+            request.add_header("Authorization", f"Bearer {api_key}")
+        try:
+            with urllib.request.urlopen(request, timeout=timeout) as response:
+                payload = json.load(response)
+        except urllib.error.HTTPError as error:
+            raise RuntimeError(f"Server answered {error.code}") from None
+        except (urllib.error.URLError, TimeoutError) as error:
+            raise RuntimeError(f"Server unreachable: {error}") from None
+        text = payload.get("text") if isinstance(payload, dict) else None
+        if not isinstance(text, str):
+            raise RuntimeError("Server response has no text")
+        return text
+
+    return post
+
+
+class Plugin:
+    def __init__(self, post: Post, model: str) -> None:
+        self.post = post
+        self.model = model
+
+    def handle(self, method: str, params: dict[str, Any]) -> dict[str, Any]:
+        if method == "initialize":
+            return {"protocol": PROTOCOL_VERSION, "name": NAME}
+        if method == "transcribe":
+            if params.get("format") != "wav":
+                raise RuntimeError("Only WAV audio is supported")
+            wav = base64.b64decode(params["audio"])
+            fields = {"model": self.model, "response_format": "json"}
+            language = params.get("language")
+            if isinstance(language, str) and language != "auto":
+                fields["language"] = language
+            prompt = params.get("initialPrompt")
+            if isinstance(prompt, str) and prompt:
+                fields["prompt"] = prompt
+            return {"text": self.post(wav, fields)}
+        raise RuntimeError(f"Unknown method {method}")
+
+
+def serve(plugin: Plugin, stdin: TextIO, stdout: TextIO) -> None:
+    """Answer requests until ``shutdown`` or end of input."""
+    for line in stdin:
+        try:
+            request = json.loads(line)
+        except json.JSONDecodeError:
+            continue
+        if not isinstance(request, dict):
+            continue
+        request_id = request.get("id")
+        method = request.get("method")
+        if method == "shutdown":
+            return
+        try:
+            response = {"id": request_id, "result": plugin.handle(method, request.get("params") or {})}
+        except Exception as error:  # noqa: BLE001 -- every failure is reported to Murmur
+            response = {"id": request_id, "error": {"message": str(error)}}
+        stdout.write(json.dumps(response) + "\n")
+        stdout.flush()
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser(description=__doc__)
+    parser.add_argument("--url", default="http://127.0.0.1:8000/v1/audio/transcriptions")
+    parser.add_argument("--model", default="Systran/faster-whisper-small.en")
+    parser.add_argument("--api-key-file", help="File holding a bearer token, if the server needs one")
+    parser.add_argument("--timeout", type=float, default=60.0)
+    args = parser.parse_args()
+    api_key = None
+    if args.api_key_file:
+        with open(args.api_key_file, encoding="utf-8") as key_file:
+            api_key = key_file.read().strip()
+    serve(Plugin(http_post(args.url, api_key, args.timeout), args.model), sys.stdin, sys.stdout)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,81 @@
+#!/usr/bin/env python3
+"""Tests for the example transcription plugin's protocol handling."""
+
+import base64
+import io
+import json
+import unittest
+
+import plugin
+
+
+class ProtocolTests(unittest.TestCase):
+    def setUp(self) -> None:
+        self.posts: list[tuple[bytes, dict[str, str]]] = []
+
+        def post(wav: bytes, fields: dict[str, str]) -> str:
+            self.posts.append((wav, fields))
+            if fields.get("language") == "xx":
+                raise RuntimeError("Unsupported language")
+            return "Hello world."
+
+        self.plugin = plugin.Plugin(post, "small.en")
+
+    def run_session(self, *requests: dict) -> list[dict]:
+        stdin = io.StringIO("".join(json.dumps(request) + "\n" for request in requests))
+        stdout = io.StringIO()
+        plugin.serve(self.plugin, stdin, stdout)
+        return [json.loads(line) for line in stdout.getvalue().splitlines()]
+
+    def test_answers_initialize_and_transcribe_by_id(self) -> None:
+        audio = base64.b64encode(b"RIFF").decode()
+        responses = self.run_session(
+            {"id": 1, "method": "initialize", "params": {"protocol": 1, "sampleRate": 16000}},
+            {
+                "id": 2,
+                "method": "transcribe",
+                "params": {"audio": audio, "format": "wav", "language": "en", "initialPrompt": "Murmur"},
+            },
+        )
+        self.assertEqual(responses[0], {"id": 1, "result": {"protocol": 1, "name": plugin.NAME}})
+        self.assertEqual(responses[1], {"id": 2, "result": {"text": "Hello world."}})
+        wav, fields = self.posts[0]
+        self.assertEqual(wav, b"RIFF")
+        self.assertEqual(fields["language"], "en")
+        self.assertEqual(fields["prompt"], "Murmur")
+        self.assertEqual(fields["model"], "small.en")
+
+    def test_auto_language_is_left_to_the_server(self) -> None:
+        self.run_session(
+            {"id": 1, "method": "transcribe", "params": {"audio": "", "format": "wav", "language": "auto"}},
+        )
+        self.assertNotIn("language", self.posts[0][1])
+
+    def test_failures_become_error_responses(self) -> None:
+        responses = self.run_session(
+            {"id": 1, "method": "transcribe", "params": {"audio": "", "format": "wav", "language": "xx"}},
+            {"id": 2, "method": "transcribe", "params": {"audio": "", "format": "flac"}},
+            {"id": 3, "method": "translate", "params": {}},
+        )
+        self.assertEqual(responses[0], {"id": 1, "error": {"message": "Unsupported language"}})
+        self.assertEqual(responses[1]["error"]["message"], "Only WAV audio is supported")
+        self.assertEqual(responses[2]["error"]["message"], "Unknown method translate")
+
+    def test_shutdown_ends_the_session_and_junk_is_skipped(self) -> None:
+        stdin = io.StringIO('not json\n{"id": 1, "method": "shutdown", "params": {}}\n{"id": 2, "method": "initialize"}\n')
+        stdout = io.StringIO()
+        plugin.serve(self.plugin, stdin, stdout)
+        self.assertEqual(stdout.getvalue(), "")
+
+    def test_multipart_body_carries_fields_and_audio(self) -> None:
+        body, content_type = plugin.multipart_body(b"WAVDATA", {"model": "small.en"})
+        boundary = content_type.split("boundary=")[1]
+        self.assertTrue(body.startswith(f"--{boundary}\r\n".encode()))
+        self.assertIn(b'name="model"\r\n\r\nsmall.en\r\n', body)
+        self.assertIn(b'filename="audio.wav"', body)
+        self.assertIn(b"WAVDATA", body)
+        self.assertTrue(body.endswith(f"--{boundary}--\r\n".encode()))
+
+
+if __name__ == "__main__":
+    unittest.main()